@@ -0,0 +1,240 @@
+//! Building and signing transfers
+//!
+//! [`TransactionBuilder`] collects a transfer's sender and outputs, takes
+//! the sender's next nonce from the node if it isn't set, and signs the
+//! result with any [`Signer`], so keys can stay in a remote signer. One
+//! output makes a [`Send`], several a [`MultiSend`].
+
+use kala_common::crypto::signer::{SignatureScheme, Signer};
+use kala_common::prelude::{KalaError, KalaResult};
+use kala_transaction::{
+    MultiSend, MultiSendOutput, Send, Transaction, MAX_MEMO_SIZE, MAX_MULTISEND_OUTPUTS,
+};
+
+use crate::KalaClient;
+
+/// Builder of a transfer from one account
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_client::{KalaClient, TransactionBuilder};
+/// use kala_common::crypto::signer::InMemorySigner;
+///
+/// # use kala_common::prelude::KalaResult;
+/// # async fn example(sender: [u8; 32], receiver: [u8; 32]) -> KalaResult<()> {
+/// let client = KalaClient::new("http://127.0.0.1:8545");
+/// let signer = InMemorySigner::from_seed(&[7u8; 32])?;
+/// let tx = TransactionBuilder::new("kala-testnet", sender)
+///     .with_output(receiver, 1_000)
+///     .with_tip(10)
+///     .fetch_nonce(&client)
+///     .await?
+///     .sign(&signer)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TransactionBuilder {
+    chain_id: String,
+    sender: [u8; 32],
+    denom: [u8; 32],
+    outputs: Vec<MultiSendOutput>,
+    nonce: Option<u64>,
+    tip: u64,
+    memo: Vec<u8>,
+}
+
+impl TransactionBuilder {
+    /// Start a transfer from `sender`, signed for `chain_id`
+    ///
+    /// # Parameters
+    /// * `chain_id` - Chain the transaction is signed for, as reported by
+    ///   `kala_getChainParams`
+    /// * `sender` - Address of the sending account, its Ed25519 public key
+    pub fn new(chain_id: impl Into<String>, sender: [u8; 32]) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            sender,
+            denom: [0u8; 32],
+            outputs: Vec::new(),
+            nonce: None,
+            tip: 0,
+            memo: Vec::new(),
+        }
+    }
+
+    /// Pay `amount` to `receiver`
+    pub fn with_output(mut self, receiver: [u8; 32], amount: u64) -> Self {
+        self.outputs.push(MultiSendOutput { receiver, amount });
+        self
+    }
+
+    /// Transfer `denom` instead of the native denomination
+    pub fn with_denom(mut self, denom: [u8; 32]) -> Self {
+        self.denom = denom;
+        self
+    }
+
+    /// Offer `tip` to the tick's producer
+    ///
+    /// A higher tip replaces a pending transaction with the same nonce.
+    pub fn with_tip(mut self, tip: u64) -> Self {
+        self.tip = tip;
+        self
+    }
+
+    /// Attach a memo, for transfers to a single receiver
+    pub fn with_memo(mut self, memo: impl Into<Vec<u8>>) -> Self {
+        self.memo = memo.into();
+        self
+    }
+
+    /// Use `nonce` instead of fetching it
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Use the sender's next nonce according to the node behind `client`
+    ///
+    /// Transactions still pending in the node's pool aren't counted, so
+    /// sending several in one tick means numbering them with
+    /// [`TransactionBuilder::with_nonce`].
+    pub async fn fetch_nonce(self, client: &KalaClient) -> KalaResult<Self> {
+        let nonce = client.next_nonce(&self.sender).await?;
+        Ok(self.with_nonce(nonce))
+    }
+
+    /// The unsigned transaction
+    ///
+    /// # Returns
+    /// A [`Send`] for one output or a [`MultiSend`] for several, or an
+    /// error if there are no outputs, the nonce is unset, or the memo
+    /// doesn't fit
+    pub fn build(&self) -> KalaResult<Transaction> {
+        let nonce = self
+            .nonce
+            .ok_or_else(|| KalaError::validation("Transaction nonce is not set"))?;
+        if self.memo.len() > MAX_MEMO_SIZE {
+            return Err(KalaError::validation(format!(
+                "Memo too large: {} bytes exceeds {}",
+                self.memo.len(),
+                MAX_MEMO_SIZE
+            )));
+        }
+        match &self.outputs[..] {
+            [] => Err(KalaError::validation("Transfer has no outputs")),
+            [output] => Ok(Transaction::Send(Send {
+                sender: self.sender,
+                receiver: output.receiver,
+                denom: self.denom,
+                amount: output.amount,
+                nonce,
+                signature: vec![0u8; 64],
+                gas_sponsorer: [0u8; 32],
+                memo: self.memo.clone(),
+                sponsor_signature: Vec::new(),
+                tip: self.tip,
+            })),
+            _ if !self.memo.is_empty() => {
+                Err(KalaError::validation("Only single-output transfers carry a memo"))
+            }
+            outputs if outputs.len() > MAX_MULTISEND_OUTPUTS => {
+                Err(KalaError::validation(format!(
+                    "Too many outputs: {} exceeds {}",
+                    outputs.len(),
+                    MAX_MULTISEND_OUTPUTS
+                )))
+            }
+            outputs => Ok(Transaction::MultiSend(MultiSend {
+                sender: self.sender,
+                denom: self.denom,
+                outputs: outputs.to_vec(),
+                nonce,
+                signature: vec![0u8; 64],
+                gas_sponsorer: [0u8; 32],
+                sponsor_signature: Vec::new(),
+                tip: self.tip,
+            })),
+        }
+    }
+
+    /// The transaction, signed by the sender
+    ///
+    /// # Parameters
+    /// * `signer` - Holds the sender's Ed25519 key
+    ///
+    /// # Returns
+    /// The signed transaction, or an error if the transaction can't be
+    /// built or `signer` doesn't hold the sender's key
+    pub async fn sign(&self, signer: &dyn Signer) -> KalaResult<Transaction> {
+        if signer.public_key(SignatureScheme::Ed25519) != self.sender {
+            return Err(KalaError::crypto("Signer does not hold the sender's key"));
+        }
+        let mut tx = self.build()?;
+        let message = tx.signing_message(&self.chain_id)?;
+        let signature = signer.sign(SignatureScheme::Ed25519, &message).await?;
+        match &mut tx {
+            Transaction::Send(send) => send.signature = signature,
+            Transaction::MultiSend(multi_send) => multi_send.signature = signature,
+            _ => unreachable!("Builder only makes transfers"),
+        }
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_common::crypto::signer::InMemorySigner;
+
+    fn sender() -> (InMemorySigner, [u8; 32]) {
+        let signer = InMemorySigner::from_seed(&[7u8; 32]).unwrap();
+        let address = signer.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+        (signer, address)
+    }
+
+    #[tokio::test]
+    async fn test_builds_signed_transfers() {
+        let (signer, address) = sender();
+        let builder = TransactionBuilder::new("kala-test", address).with_nonce(3);
+        assert!(builder.build().is_err());
+
+        let send = builder
+            .clone()
+            .with_output([1u8; 32], 500)
+            .with_memo(b"invoice 42".to_vec())
+            .sign(&signer)
+            .await
+            .unwrap();
+        let Transaction::Send(inner) = &send else {
+            panic!("One output makes a Send");
+        };
+        assert_eq!((inner.amount, inner.nonce), (500, 3));
+        let message = send.signing_message("kala-test").unwrap();
+        assert!(SignatureScheme::Ed25519.verify(&address, &message, &inner.signature));
+
+        let multi_send = builder
+            .clone()
+            .with_output([1u8; 32], 500)
+            .with_output([2u8; 32], 250)
+            .with_tip(5)
+            .sign(&signer)
+            .await
+            .unwrap();
+        let Transaction::MultiSend(inner) = &multi_send else {
+            panic!("Several outputs make a MultiSend");
+        };
+        assert_eq!((inner.outputs.len(), inner.tip), (2, 5));
+        let message = multi_send.signing_message("kala-test").unwrap();
+        assert!(SignatureScheme::Ed25519.verify(&address, &message, &inner.signature));
+
+        // Memos only fit single transfers, and only the sender can sign
+        let two_outputs = builder.with_output([1u8; 32], 1).with_output([2u8; 32], 1);
+        assert!(two_outputs.clone().with_memo(b"memo".to_vec()).build().is_err());
+        let other = InMemorySigner::from_seed(&[8u8; 32]).unwrap();
+        assert!(two_outputs.sign(&other).await.is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! # Kala Client
+//!
+//! Building, sealing and submitting transactions from Rust.
+//!
+//! Getting a transaction into a tick takes several steps that every
+//! integrator would otherwise reimplement: signing it over the chain's
+//! signing message, picking a target tick whose acceptance window is open,
+//! sizing an RSW timelock puzzle that the node accepts for that tick,
+//! sealing the transaction behind the puzzle, and submitting the envelope
+//! in its canonical wire format. This crate does each of them:
+//!
+//! - [`TransactionBuilder`] builds transfers, fetches the sender's nonce
+//!   and signs with any [`Signer`](kala_common::crypto::signer::Signer);
+//! - [`TimelockPlan`] works out the target tick, the puzzle hardness and
+//!   when the envelope must reach the node;
+//! - [`KalaClient`] talks to a node over JSON-RPC, and
+//!   [`KalaClient::send`] seals and submits a signed transaction.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use kala_client::{KalaClient, TransactionBuilder};
+//! use kala_common::crypto::signer::InMemorySigner;
+//!
+//! # use kala_common::prelude::KalaResult;
+//! # async fn example(sender: [u8; 32], receiver: [u8; 32]) -> KalaResult<()> {
+//! let client = KalaClient::new("http://127.0.0.1:8545");
+//! let params = client.chain_params().await?;
+//! let signer = InMemorySigner::from_seed(&[7u8; 32])?;
+//! let tx = TransactionBuilder::new(params.chain_id, sender)
+//!     .with_output(receiver, 1_000)
+//!     .fetch_nonce(&client)
+//!     .await?
+//!     .sign(&signer)
+//!     .await?;
+//! let response = client.send(&tx).await?;
+//! println!("{} targets tick {}", response.tx_hash, response.target_tick);
+//! # Ok(())
+//! # }
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod builder;
+pub mod timelock;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use kala_common::prelude::{KalaError, KalaResult};
+use kala_rpc::{
+    AccountInfo, ChainInfo, ChainParams, SubmitTransactionRequest, SubmitTransactionResponse,
+};
+use kala_transaction::{PuzzlePool, TimelockTransaction, Transaction};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+pub use builder::TransactionBuilder;
+pub use timelock::TimelockPlan;
+
+/// Attempts [`KalaClient::send`] makes to seal an envelope in time
+const SEAL_ATTEMPTS: usize = 3;
+
+/// Time between polls of the node's iteration
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A JSON-RPC client of one node
+pub struct KalaClient {
+    client: reqwest::Client,
+    url: String,
+    next_id: AtomicU64,
+    puzzle_pool: Option<Arc<PuzzlePool>>,
+}
+
+impl KalaClient {
+    /// Client of the node serving JSON-RPC at `url`
+    ///
+    /// # Parameters
+    /// * `url` - HTTP endpoint of the node, e.g. `http://127.0.0.1:8545`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+            next_id: AtomicU64::new(1),
+            puzzle_pool: None,
+        }
+    }
+
+    /// Seal envelopes on moduli pregenerated by `pool`
+    ///
+    /// Makes [`KalaClient::send`] much faster, so it can target sooner
+    /// ticks. Without a pool every envelope generates its own modulus.
+    pub fn with_puzzle_pool(mut self, pool: Arc<PuzzlePool>) -> Self {
+        self.puzzle_pool = Some(pool);
+        self
+    }
+
+    /// Call `method` on the node
+    ///
+    /// # Parameters
+    /// * `method` - Method name, e.g. `kala_chainInfo`
+    /// * `params` - Positional parameters, as a JSON array
+    ///
+    /// # Returns
+    /// The result, or an error carrying the JSON-RPC error object
+    pub async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> KalaResult<T> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+        });
+        let mut response: Value = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| KalaError::network(format!("{} failed: {}", method, e)))?
+            .json()
+            .await
+            .map_err(|e| KalaError::network(format!("{} failed: {}", method, e)))?;
+        if let Some(error) = response.get("error") {
+            return Err(KalaError::network(format!("{} failed: {}", method, error)));
+        }
+        Ok(serde_json::from_value(response["result"].take())?)
+    }
+
+    /// Protocol parameters of the node's chain
+    pub async fn chain_params(&self) -> KalaResult<ChainParams> {
+        self.call("kala_getChainParams", json!([])).await
+    }
+
+    /// Current tick and VDF iteration of the node
+    pub async fn chain_info(&self) -> KalaResult<ChainInfo> {
+        self.call("kala_chainInfo", json!([])).await
+    }
+
+    /// State of the account at `address`, if it has any
+    pub async fn account(&self, address: &[u8; 32]) -> KalaResult<Option<AccountInfo>> {
+        self.call("kala_getAccount", json!([{ "address": hex::encode(address) }]))
+            .await
+    }
+
+    /// Nonce the next transaction from `address` must carry
+    pub async fn next_nonce(&self, address: &[u8; 32]) -> KalaResult<u64> {
+        let info = self.account(address).await?;
+        Ok(info.map_or(0, |info| info.nonce) + 1)
+    }
+
+    /// Submit a sealed envelope through `kala_submitTransaction`
+    pub async fn submit(
+        &self,
+        envelope: &TimelockTransaction,
+    ) -> KalaResult<SubmitTransactionResponse> {
+        let request = json!([SubmitTransactionRequest::new(envelope)]);
+        self.call("kala_submitTransaction", request).await
+    }
+
+    /// Seal a signed transaction for the next tick that can take it, and submit it
+    ///
+    /// The envelope is planned for the node's current iteration plus the
+    /// time the last attempt took to seal, and submitted once its target
+    /// tick's acceptance window opens. Sealing generates an RSA modulus,
+    /// which can outlast a short tick, so a plan the node has moved past
+    /// by the time the envelope is ready is given up for a later one.
+    ///
+    /// # Parameters
+    /// * `tx` - The signed transaction
+    ///
+    /// # Returns
+    /// The node's response, with the tick the transaction targets
+    pub async fn send(&self, tx: &Transaction) -> KalaResult<SubmitTransactionResponse> {
+        let params = self.chain_params().await?;
+        let mut lead = 0;
+        for _ in 0..SEAL_ATTEMPTS {
+            let sealing_from = self.chain_info().await?.current_iteration;
+            let plan = TimelockPlan::new(&params, sealing_from + lead);
+            let envelope = tokio::task::spawn_blocking({
+                let (tx, pool) = (tx.clone(), self.puzzle_pool.clone());
+                move || match pool {
+                    Some(pool) => plan.seal_with(&pool, &tx),
+                    None => plan.seal(&tx),
+                }
+            })
+            .await
+            .map_err(|e| KalaError::internal(format!("Sealing task failed: {}", e)))??;
+
+            let iteration = self.wait_for_iteration(plan.submit_from).await?;
+            if iteration <= plan.submit_by {
+                return self.submit(&envelope).await;
+            }
+            lead = 2 * (iteration - sealing_from);
+        }
+        Err(KalaError::transaction(format!(
+            "Sealing never finished in time for the target tick in {} attempts",
+            SEAL_ATTEMPTS
+        )))
+    }
+
+    /// Wait until the node reaches `iteration`
+    ///
+    /// # Returns
+    /// The iteration the node had reached
+    async fn wait_for_iteration(&self, iteration: u64) -> KalaResult<u64> {
+        loop {
+            let current = self.chain_info().await?.current_iteration;
+            if current >= iteration {
+                return Ok(current);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Picking a target tick and puzzle hardness the node will accept
+//!
+//! A node takes an envelope for tick T from 90% of the way through tick
+//! T-1 until 30% of the way into T, and records the iteration it arrived
+//! at. Its puzzle must then become solvable after T's collection phase,
+//! when the order is fixed, and before T ends. [`TimelockPlan`] works
+//! those limits out from the node's [`ChainParams`], so clients don't
+//! have to mirror the node's arithmetic.
+
+use kala_common::prelude::KalaResult;
+use kala_rpc::ChainParams;
+use kala_transaction::{
+    seal_transaction, seal_transaction_fast, PuzzlePool, TimelockTransaction, Transaction,
+};
+
+/// When to submit an envelope and how hard to make its puzzle
+///
+/// The puzzle is sized to become solvable right as the target tick's
+/// order is fixed, if the envelope arrives at `submit_from`. An envelope
+/// arriving later decrypts correspondingly later, which the node accepts
+/// until `submit_by`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimelockPlan {
+    /// Tick the envelope targets
+    pub target_tick: u64,
+    /// Iterations the puzzle takes to solve
+    pub hardness: u32,
+    /// First iteration the envelope can be submitted at
+    pub submit_from: u64,
+    /// Last iteration the envelope can arrive at and still be accepted
+    pub submit_by: u64,
+}
+
+impl TimelockPlan {
+    /// Plan an envelope that reaches the node at `iteration` or later
+    ///
+    /// # Parameters
+    /// * `params` - Protocol parameters of the node's chain
+    /// * `iteration` - Earliest iteration the envelope can be submitted
+    ///   at, e.g. the current one plus the time it takes to seal it
+    ///
+    /// # Returns
+    /// A plan for the first tick still accepting envelopes at `iteration`
+    pub fn new(params: &ChainParams, iteration: u64) -> Self {
+        let k = params.iterations_per_tick;
+        let bounds = &params.hardness;
+        let target_tick = iteration.saturating_sub(bounds.acceptance_window_end).div_ceil(k);
+        let acceptance_start = match target_tick {
+            0 => 0,
+            tick => (tick - 1) * k + bounds.acceptance_window_start,
+        };
+        let submit_from = iteration.max(acceptance_start);
+
+        let tick_start = target_tick * k;
+        let decrypt_at = (tick_start + params.phases.collection_end)
+            .max(submit_from + bounds.min_hardness as u64);
+        let hardness = (decrypt_at - submit_from) as u32;
+
+        // Arriving later pushes decryption back, until it would miss the tick
+        let submit_by = (tick_start + bounds.acceptance_window_end)
+            .min(tick_start + k - 1 - hardness as u64);
+
+        Self {
+            target_tick,
+            hardness,
+            submit_from,
+            submit_by,
+        }
+    }
+
+    /// Earliest iteration the puzzle can be solved at, if submitted at `iteration`
+    pub fn decrypt_at(&self, iteration: u64) -> u64 {
+        iteration + self.hardness as u64
+    }
+
+    /// Seal `tx` in an envelope with a freshly generated RSW puzzle
+    ///
+    /// Generating the puzzle's modulus takes a while, so this should run
+    /// off the async runtime.
+    ///
+    /// # Parameters
+    /// * `tx` - The signed transaction
+    ///
+    /// # Returns
+    /// The envelope, ready to submit from `submit_from`
+    pub fn seal(&self, tx: &Transaction) -> KalaResult<TimelockTransaction> {
+        seal_transaction(tx, self.target_tick, self.submit_from, self.hardness)
+    }
+
+    /// Seal `tx` like [`TimelockPlan::seal`], on a modulus from `pool`
+    pub fn seal_with(
+        &self,
+        pool: &PuzzlePool,
+        tx: &Transaction,
+    ) -> KalaResult<TimelockTransaction> {
+        seal_transaction_fast(tx, pool, self.target_tick, self.submit_from, self.hardness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_rpc::{HardnessBounds, TickPhases};
+
+    const K: u64 = 1_000;
+
+    fn params() -> ChainParams {
+        ChainParams {
+            protocol_version: 1,
+            chain_id: "kala-test".to_string(),
+            iterations_per_tick: K,
+            epoch_length: 100,
+            phases: TickPhases {
+                collection_end: K / 3,
+                decryption_end: 2 * K / 3,
+                validation_end: K,
+            },
+            discriminant: String::new(),
+            hardness: HardnessBounds {
+                hardness_factor: 0.1,
+                min_hardness: 1,
+                max_hardness: 100,
+                acceptance_window_start: 900,
+                acceptance_window_end: 300,
+            },
+            features: Vec::new(),
+        }
+    }
+
+    /// Whether the node would accept the planned envelope arriving at `iteration`
+    fn accepted(plan: &TimelockPlan, iteration: u64) -> bool {
+        let tick_start = plan.target_tick * K;
+        let acceptance_start = match plan.target_tick {
+            0 => 0,
+            tick => (tick - 1) * K + 900,
+        };
+        let decrypt_at = plan.decrypt_at(iteration);
+        (acceptance_start..=tick_start + 300).contains(&iteration)
+            && decrypt_at >= tick_start + K / 3
+            && decrypt_at < tick_start + K
+    }
+
+    #[test]
+    fn test_plans_are_accepted() {
+        let params = params();
+        for iteration in (0..5 * K).step_by(7) {
+            let plan = TimelockPlan::new(&params, iteration);
+            assert!(plan.submit_from >= iteration);
+            assert!(plan.submit_from <= plan.submit_by);
+            assert!(accepted(&plan, plan.submit_from), "{:?}", plan);
+            assert!(accepted(&plan, plan.submit_by), "{:?}", plan);
+            assert!(!accepted(&plan, plan.submit_by + 1), "{:?}", plan);
+            assert_eq!(plan.decrypt_at(plan.submit_from), plan.target_tick * K + K / 3);
+        }
+
+        // Between windows the envelope waits for the next one to open
+        let plan = TimelockPlan::new(&params, 2 * K + 500);
+        assert_eq!((plan.target_tick, plan.submit_from), (3, 2 * K + 900));
+        assert_eq!(plan.hardness as u64, K / 3 + 100);
+        let plan = TimelockPlan::new(&params, 2 * K + 100);
+        assert_eq!((plan.target_tick, plan.submit_from), (2, 2 * K + 100));
+        assert_eq!(plan.submit_by, 2 * K + 300);
+    }
+}
@@ -0,0 +1,17 @@
+use std::path::Path;
+
+fn main() {
+    // Tell Cargo to rerun this build script if the schema file changes
+    println!("cargo:rerun-if-changed=schema/network.proto");
+
+    // The generated code is checked in, so protoc is only needed to change the schema
+    if std::env::var_os("PROTOC").is_none() && which::which("protoc").is_err() {
+        return;
+    }
+
+    // Generate prost Rust code for the network messages
+    prost_build::Config::new()
+        .out_dir(Path::new("src/proto"))
+        .compile_protos(&["schema/network.proto"], &["schema"])
+        .expect("Failed to generate protobuf code");
+}
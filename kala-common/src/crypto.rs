@@ -1,6 +1,14 @@
 //! Cryptographic utilities and hash operations
 
+pub mod bls;
+pub mod shamir;
+pub mod signer;
+
 use sha2::{Digest, Sha256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use ed25519_dalek::VerifyingKey;
 use crate::{
     types::{Hash, PublicKey, Signature, HashExt, PublicKeyExt, SignatureExt}, 
     error::KalaResult
@@ -85,6 +93,105 @@ impl CryptoUtils {
     }
 }
 
+/// Data carrying an Ed25519 signature over a chain-bound message
+pub trait SignedPayload {
+    /// Public key the signature must verify under
+    fn signer(&self) -> &PublicKey;
+
+    /// Raw signature bytes
+    fn signature_bytes(&self) -> &[u8];
+
+    /// Message the signer signed for `chain_id`
+    fn signing_message(&self, chain_id: &str) -> KalaResult<Vec<u8>>;
+}
+
+impl<T: SignedPayload + ?Sized> SignedPayload for &T {
+    fn signer(&self) -> &PublicKey {
+        (**self).signer()
+    }
+
+    fn signature_bytes(&self) -> &[u8] {
+        (**self).signature_bytes()
+    }
+
+    fn signing_message(&self, chain_id: &str) -> KalaResult<Vec<u8>> {
+        (**self).signing_message(chain_id)
+    }
+}
+
+/// Verify the signatures of `items` for `chain_id` in one batch
+///
+/// Batch verification costs a fraction of verifying each signature on its
+/// own, but only says whether all of them are valid. When the batch fails,
+/// the items are verified one by one to find the bad ones, so a single
+/// forged signature costs no more than sequential verification.
+///
+/// Every item gets the answer of [`VerifyingKey::verify_strict`], so all
+/// nodes agree on which signatures are valid. Only signatures the batch
+/// decides the same way are batched, see [`strictly_batchable`]; the rest
+/// are verified one by one.
+///
+/// # Returns
+///
+/// Whether each item's signature is valid, in the order of `items`
+pub fn verify_signatures_batch<T: SignedPayload>(items: &[T], chain_id: &str) -> Vec<bool> {
+    let parsed: Vec<Option<(VerifyingKey, ed25519_dalek::Signature, Vec<u8>)>> = items
+        .iter()
+        .map(|item| {
+            let signature: [u8; SIGNATURE_SIZE] = item.signature_bytes().try_into().ok()?;
+            let key = VerifyingKey::from_bytes(item.signer()).ok()?;
+            let message = item.signing_message(chain_id).ok()?;
+            Some((key, ed25519_dalek::Signature::from_bytes(&signature), message))
+        })
+        .collect();
+    let batched: Vec<bool> = parsed
+        .iter()
+        .map(|entry| {
+            entry
+                .as_ref()
+                .is_some_and(|(key, signature, _)| strictly_batchable(key, signature))
+        })
+        .collect();
+
+    let batch: Vec<_> = parsed
+        .iter()
+        .zip(&batched)
+        .filter_map(|(entry, &batched)| entry.as_ref().filter(|_| batched))
+        .collect();
+    let messages: Vec<&[u8]> = batch.iter().map(|(_, _, m)| m.as_slice()).collect();
+    let signatures: Vec<_> = batch.iter().map(|(_, s, _)| *s).collect();
+    let keys: Vec<_> = batch.iter().map(|(k, _, _)| *k).collect();
+    let batch_valid = ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok();
+
+    parsed
+        .iter()
+        .zip(batched)
+        .map(|(entry, batched)| {
+            entry.as_ref().is_some_and(|(key, signature, message)| {
+                (batched && batch_valid) || key.verify_strict(message, signature).is_ok()
+            })
+        })
+        .collect()
+}
+
+/// Whether batch verification decides `signature` by `key` the same way
+/// as [`VerifyingKey::verify_strict`]
+///
+/// The batch check weighs each signature by a random scalar and doesn't
+/// clear the cofactor, so a small-order component in `R` or in the key
+/// drops out for some weights and not others, and an `R` that isn't
+/// canonically encoded passes where the strict check fails. Signatures
+/// whose `R` and key are canonical points of the prime-order subgroup have
+/// neither problem.
+fn strictly_batchable(key: &VerifyingKey, signature: &ed25519_dalek::Signature) -> bool {
+    let prime_order = |point: EdwardsPoint| !point.is_small_order() && point.is_torsion_free();
+    let r = CompressedEdwardsY(*signature.r_bytes());
+    match r.decompress() {
+        Some(point) => point.compress() == r && prime_order(point) && prime_order(key.to_edwards()),
+        None => false,
+    }
+}
+
 /// Merkle tree implementation for batch verification
 pub struct MerkleTree {
     leaves: Vec<Hash>,
@@ -103,15 +210,16 @@ impl MerkleTree {
 
         let mut nodes = leaves.clone();
         let mut current_level = leaves.len();
+        let mut level_start = 0;
 
         // Build tree bottom-up
         while current_level > 1 {
             let mut next_level = Vec::new();
             
             for i in (0..current_level).step_by(2) {
-                let left = nodes[i];
+                let left = nodes[level_start + i];
                 let right = if i + 1 < current_level {
-                    nodes[i + 1]
+                    nodes[level_start + i + 1]
                 } else {
                     left // Duplicate if odd number
                 };
@@ -120,6 +228,7 @@ impl MerkleTree {
                 next_level.push(parent);
             }
             
+            level_start += current_level;
             nodes.extend_from_slice(&next_level);
             current_level = next_level.len();
         }
@@ -152,6 +261,9 @@ impl MerkleTree {
 
             if sibling_index < level_size {
                 proof.push(self.nodes[level_start + sibling_index]);
+            } else {
+                // Odd level: the node was paired with itself
+                proof.push(self.nodes[level_start + current_index]);
             }
 
             level_start += level_size;
@@ -178,6 +290,139 @@ impl MerkleTree {
 
         current_hash == *root
     }
+
+    /// Number of leaves in the tree
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Generate a single proof covering all leaves at `indices`
+    ///
+    /// Sibling hashes shared between the requested paths are included only
+    /// once, and nodes derivable from the requested leaves are omitted.
+    pub fn multiproof(&self, indices: &[usize]) -> Option<MerkleMultiProof> {
+        if indices.is_empty() || indices.iter().any(|&i| i >= self.leaves.len()) {
+            return None;
+        }
+
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut hashes = Vec::new();
+        let mut known: Vec<usize> = indices.clone();
+        let mut level_size = self.leaves.len();
+        let mut level_start = 0;
+
+        while level_size > 1 {
+            let mut parents = Vec::with_capacity(known.len());
+            let mut i = 0;
+
+            while i < known.len() {
+                let index = known[i];
+                let sibling_index = index ^ 1;
+
+                if sibling_index > index && i + 1 < known.len() && known[i + 1] == sibling_index {
+                    // Both children are known, nothing to add
+                    i += 1;
+                } else if sibling_index < level_size {
+                    hashes.push(self.nodes[level_start + sibling_index]);
+                }
+
+                parents.push(index / 2);
+                i += 1;
+            }
+
+            level_start += level_size;
+            level_size = level_size.div_ceil(2);
+            known = parents;
+        }
+
+        Some(MerkleMultiProof {
+            leaf_count: self.leaves.len(),
+            indices,
+            hashes,
+        })
+    }
+
+    /// Verify a multiproof for `leaves`, given in the order of `proof.indices`
+    pub fn verify_multiproof(leaves: &[Hash], proof: &MerkleMultiProof, root: &Hash) -> bool {
+        if leaves.is_empty()
+            || leaves.len() != proof.indices.len()
+            || proof.leaf_count == 0
+            || proof.indices.windows(2).any(|w| w[0] >= w[1])
+            || proof.indices.iter().any(|&i| i >= proof.leaf_count)
+        {
+            return false;
+        }
+
+        let mut known: BTreeMap<usize, Hash> = proof
+            .indices
+            .iter()
+            .copied()
+            .zip(leaves.iter().copied())
+            .collect();
+        let mut supplied = proof.hashes.iter();
+        let mut level_size = proof.leaf_count;
+
+        while level_size > 1 {
+            let mut parents = BTreeMap::new();
+            let mut nodes = known.into_iter().peekable();
+
+            while let Some((index, hash)) = nodes.next() {
+                let sibling_index = index ^ 1;
+
+                let is_left = sibling_index > index;
+
+                let sibling = if is_left && nodes.peek().map(|(i, _)| *i) == Some(sibling_index) {
+                    nodes.next().map(|(_, h)| h)
+                } else if sibling_index < level_size {
+                    supplied.next().copied()
+                } else {
+                    Some(hash)
+                };
+
+                let Some(sibling) = sibling else {
+                    return false;
+                };
+
+                let parent = if is_left {
+                    CryptoUtils::hash_multiple(&[&hash, &sibling])
+                } else {
+                    CryptoUtils::hash_multiple(&[&sibling, &hash])
+                };
+                parents.insert(index / 2, parent);
+            }
+
+            level_size = level_size.div_ceil(2);
+            known = parents;
+        }
+
+        supplied.next().is_none() && known.get(&0) == Some(root)
+    }
+}
+
+/// Compact inclusion proof for several leaves of the same merkle tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleMultiProof {
+    /// Total number of leaves in the tree
+    pub leaf_count: usize,
+    /// Sorted, deduplicated leaf indices covered by the proof
+    pub indices: Vec<usize>,
+    /// Sibling hashes, bottom-up and left-to-right within each level
+    pub hashes: Vec<Hash>,
+}
+
+impl MerkleMultiProof {
+    /// Number of leaves covered by the proof
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Whether the proof covers no leaves
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -224,6 +469,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_merkle_multiproof() {
+        for leaf_count in 1..=9usize {
+            let leaves: Vec<Hash> = (0..leaf_count)
+                .map(|i| CryptoUtils::hash(&i.to_le_bytes()))
+                .collect();
+            let tree = MerkleTree::new(leaves.clone());
+            let root = tree.root();
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(MerkleTree::verify_proof(leaf, &proof, &root, i));
+            }
+
+            // Every non-empty subset of leaves
+            for mask in 1u32..(1 << leaf_count) {
+                let indices: Vec<usize> =
+                    (0..leaf_count).filter(|i| mask & (1 << i) != 0).collect();
+                let proof = tree.multiproof(&indices).unwrap();
+                let covered: Vec<Hash> = proof.indices.iter().map(|&i| leaves[i]).collect();
+                assert!(MerkleTree::verify_multiproof(&covered, &proof, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_merkle_multiproof_is_compact() {
+        let leaves: Vec<Hash> = (0..8u8).map(|i| CryptoUtils::hash(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+
+        // Leaves 0..4 fill the left subtree, so only its sibling is needed
+        let proof = tree.multiproof(&[3, 1, 0, 2, 2]).unwrap();
+        assert_eq!(proof.indices, vec![0, 1, 2, 3]);
+        assert_eq!(proof.hashes.len(), 1);
+
+        let all = tree.multiproof(&(0..8).collect::<Vec<_>>()).unwrap();
+        assert!(all.hashes.is_empty());
+        assert!(MerkleTree::verify_multiproof(&leaves, &all, &tree.root()));
+    }
+
+    #[test]
+    fn test_merkle_multiproof_rejects_tampering() {
+        let leaves: Vec<Hash> = (0..6u8).map(|i| CryptoUtils::hash(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+        let root = tree.root();
+
+        assert!(tree.multiproof(&[]).is_none());
+        assert!(tree.multiproof(&[6]).is_none());
+
+        let proof = tree.multiproof(&[1, 4]).unwrap();
+        assert!(MerkleTree::verify_multiproof(&[leaves[1], leaves[4]], &proof, &root));
+
+        // Wrong leaf, swapped order, truncated and padded proofs all fail
+        assert!(!MerkleTree::verify_multiproof(&[leaves[2], leaves[4]], &proof, &root));
+        assert!(!MerkleTree::verify_multiproof(&[leaves[4], leaves[1]], &proof, &root));
+
+        let mut truncated = proof.clone();
+        truncated.hashes.pop();
+        assert!(!MerkleTree::verify_multiproof(&[leaves[1], leaves[4]], &truncated, &root));
+
+        let mut padded = proof.clone();
+        padded.hashes.push(root);
+        assert!(!MerkleTree::verify_multiproof(&[leaves[1], leaves[4]], &padded, &root));
+
+        let mut wrong_size = proof;
+        wrong_size.leaf_count = 8;
+        assert!(!MerkleTree::verify_multiproof(&[leaves[1], leaves[4]], &wrong_size, &root));
+    }
+
+    #[derive(Clone)]
+    struct Signed {
+        signer: PublicKey,
+        signature: Vec<u8>,
+        message: Vec<u8>,
+    }
+
+    impl SignedPayload for Signed {
+        fn signer(&self) -> &PublicKey {
+            &self.signer
+        }
+
+        fn signature_bytes(&self) -> &[u8] {
+            &self.signature
+        }
+
+        fn signing_message(&self, chain_id: &str) -> KalaResult<Vec<u8>> {
+            Ok([chain_id.as_bytes(), &self.message].concat())
+        }
+    }
+
+    fn signed(seed: u8, message: &[u8]) -> Signed {
+        use ed25519_dalek::{Signer, SigningKey};
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        Signed {
+            signer: key.verifying_key().to_bytes(),
+            signature: key.sign(&[b"test".as_slice(), message].concat()).to_bytes().to_vec(),
+            message: message.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_verify_signatures_batch() {
+        let mut items: Vec<Signed> = (1..=8u8).map(|i| signed(i, &[i; 40])).collect();
+        assert_eq!(verify_signatures_batch(&items, "test"), vec![true; 8]);
+        assert_eq!(verify_signatures_batch(&items, "other"), vec![false; 8]);
+        assert!(verify_signatures_batch::<Signed>(&[], "test").is_empty());
+
+        // Bad signatures are singled out without failing the rest
+        items[2].message[0] ^= 1;
+        items[5].signature.truncate(10);
+        let mut expected = vec![true; 8];
+        expected[2] = false;
+        expected[5] = false;
+        assert_eq!(verify_signatures_batch(&items, "test"), expected);
+
+        let refs: Vec<&Signed> = items.iter().collect();
+        assert_eq!(verify_signatures_batch(&refs, "test"), expected);
+    }
+
+    #[test]
+    fn test_verify_signatures_batch_torsion() {
+        use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+        use curve25519_dalek::Scalar;
+        use sha2::Sha512;
+
+        // A signature whose R carries an order-8 component passes the batch
+        // check for one in eight batch weights, but never the strict check
+        let secret = Scalar::from_bytes_mod_order([9; 32]);
+        let public = (ED25519_BASEPOINT_POINT * secret).compress();
+        let nonce = Scalar::from_bytes_mod_order([5; 32]);
+        let r = (ED25519_BASEPOINT_POINT * nonce + EIGHT_TORSION[1]).compress();
+        let message = b"torsion".to_vec();
+        let challenge = Scalar::from_bytes_mod_order_wide(
+            &Sha512::new()
+                .chain_update(r.as_bytes())
+                .chain_update(public.as_bytes())
+                .chain_update([b"test".as_slice(), &message].concat())
+                .finalize()
+                .into(),
+        );
+        let tweaked = Signed {
+            signer: public.to_bytes(),
+            signature: [*r.as_bytes(), (nonce + challenge * secret).to_bytes()].concat(),
+            message,
+        };
+
+        // The batch weights are drawn from the batch, so vary it
+        for size in 0..32u8 {
+            let mut items: Vec<Signed> = (1..=size).map(|i| signed(i, &[i; 8])).collect();
+            items.push(tweaked.clone());
+            let mut expected = vec![true; size as usize];
+            expected.push(false);
+            assert_eq!(verify_signatures_batch(&items, "test"), expected);
+        }
+    }
+
     #[test]
     fn test_hex_conversion() {
         let original_hash = CryptoUtils::hash(b"test");
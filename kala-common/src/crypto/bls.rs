@@ -0,0 +1,151 @@
+//! BLS12-381 witness signatures
+//!
+//! Keys are on G1 (48-byte compressed public keys) and signatures on G2
+//! (96-byte compressed signatures), so any number of signatures aggregate
+//! into one of constant size. Signing uses the message-augmentation scheme:
+//! every signer signs its own compressed public key followed by the
+//! message, which makes aggregates over a shared message safe against
+//! rogue-key attacks without proofs of possession.
+//!
+//! # Example
+//!
+//! ```
+//! use kala_common::crypto::bls;
+//!
+//! # fn example() -> kala_common::KalaResult<()> {
+//! let keys = [bls::keygen(&[1u8; 32])?, bls::keygen(&[2u8; 32])?];
+//! let public_keys: Vec<_> = keys.iter().map(bls::public_key).collect();
+//! let signatures: Vec<_> = keys.iter().map(|key| bls::sign(key, b"tick")).collect();
+//!
+//! let aggregate = bls::aggregate(&signatures)?;
+//! assert!(bls::aggregate_verify(&public_keys, b"tick", &aggregate));
+//! # Ok(())
+//! # }
+//! ```
+
+use blst::min_pk::{AggregateSignature, PublicKey, Signature};
+use blst::BLST_ERROR;
+
+use crate::error::{KalaError, KalaResult};
+
+/// BLS secret key of a witness
+pub use blst::min_pk::SecretKey;
+
+/// Domain separation tag of witness BLS signatures
+pub const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+
+/// Key derivation info for the BLS key of a witness seed
+pub const BLS_KEY_INFO: &[u8] = b"kala-witness";
+
+/// Size of a compressed BLS public key
+pub const BLS_PUBLIC_KEY_SIZE: usize = 48;
+
+/// Size of a compressed BLS signature
+pub const BLS_SIGNATURE_SIZE: usize = 96;
+
+/// Derive the BLS secret key of a 32-byte witness seed
+pub fn keygen(seed: &[u8; 32]) -> KalaResult<SecretKey> {
+    SecretKey::key_gen(seed, BLS_KEY_INFO)
+        .map_err(|e| KalaError::crypto(format!("BLS key generation failed: {:?}", e)))
+}
+
+/// Compressed public key of a secret key
+pub fn public_key(key: &SecretKey) -> Vec<u8> {
+    key.sk_to_pk().compress().to_vec()
+}
+
+/// Check that `public_key` is a valid key, returning its compressed form
+pub fn validate_public_key(public_key: &[u8]) -> KalaResult<Vec<u8>> {
+    PublicKey::key_validate(public_key)
+        .map(|key| key.compress().to_vec())
+        .map_err(|e| KalaError::crypto(format!("Invalid BLS key: {:?}", e)))
+}
+
+/// Sign `message` augmented with the signer's public key
+pub fn sign(key: &SecretKey, message: &[u8]) -> Vec<u8> {
+    key.sign(message, BLS_DST, &public_key(key)).compress().to_vec()
+}
+
+/// Check a signature made with [`sign`]
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let (Ok(key), Ok(signature)) = (
+        PublicKey::uncompress(public_key),
+        Signature::uncompress(signature),
+    ) else {
+        return false;
+    };
+    signature.verify(true, message, BLS_DST, public_key, &key, true) == BLST_ERROR::BLST_SUCCESS
+}
+
+/// Combine compressed signatures into one of the same size
+pub fn aggregate<S: AsRef<[u8]>>(signatures: &[S]) -> KalaResult<Vec<u8>> {
+    if signatures.is_empty() {
+        return Err(KalaError::validation("No signatures to aggregate"));
+    }
+    let signatures = signatures
+        .iter()
+        .map(|signature| Signature::uncompress(signature.as_ref()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| KalaError::crypto(format!("Invalid BLS signature: {:?}", e)))?;
+    let refs: Vec<&Signature> = signatures.iter().collect();
+    let aggregate = AggregateSignature::aggregate(&refs, true)
+        .map_err(|e| KalaError::crypto(format!("BLS aggregation failed: {:?}", e)))?;
+    Ok(aggregate.to_signature().compress().to_vec())
+}
+
+/// Check an aggregate of every holder of `public_keys` signing `message`
+pub fn aggregate_verify<K: AsRef<[u8]>>(public_keys: &[K], message: &[u8], signature: &[u8]) -> bool {
+    if public_keys.is_empty() {
+        return false;
+    }
+    let Ok(keys) = public_keys
+        .iter()
+        .map(|key| PublicKey::uncompress(key.as_ref()))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+    let Ok(signature) = Signature::uncompress(signature) else {
+        return false;
+    };
+
+    // Each signer signed its own public key followed by the message
+    let messages: Vec<Vec<u8>> = public_keys
+        .iter()
+        .map(|key| [key.as_ref(), message].concat())
+        .collect();
+    let message_refs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+    let key_refs: Vec<&PublicKey> = keys.iter().collect();
+
+    signature.aggregate_verify(true, &message_refs, BLS_DST, &key_refs, true)
+        == BLST_ERROR::BLST_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_aggregate() {
+        let keys: Vec<_> = (1..=3).map(|seed| keygen(&[seed; 32]).unwrap()).collect();
+        let public_keys: Vec<_> = keys.iter().map(public_key).collect();
+        let signatures: Vec<_> = keys.iter().map(|key| sign(key, b"tick")).collect();
+
+        assert_eq!(public_keys[0].len(), BLS_PUBLIC_KEY_SIZE);
+        assert_eq!(signatures[0].len(), BLS_SIGNATURE_SIZE);
+        assert!(verify(&public_keys[0], b"tick", &signatures[0]));
+        assert!(!verify(&public_keys[1], b"tick", &signatures[0]));
+        assert!(!verify(&public_keys[0], b"tock", &signatures[0]));
+
+        let aggregate = aggregate(&signatures).unwrap();
+        assert_eq!(aggregate.len(), BLS_SIGNATURE_SIZE);
+        assert!(aggregate_verify(&public_keys, b"tick", &aggregate));
+        assert!(!aggregate_verify(&public_keys[..2], b"tick", &aggregate));
+        assert!(!aggregate_verify(&public_keys, b"tock", &aggregate));
+        assert!(!aggregate_verify::<Vec<u8>>(&[], b"tick", &aggregate));
+
+        assert!(validate_public_key(&public_keys[0]).is_ok());
+        assert!(validate_public_key(&[1u8; 48]).is_err());
+        assert!(super::aggregate::<Vec<u8>>(&[]).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Shamir secret sharing of 32-byte keys
+//!
+//! A key is split into `count` shares such that any `threshold` of them
+//! recover it and fewer reveal nothing about it. Each byte of the key is
+//! the constant term of its own random polynomial of degree
+//! `threshold - 1` over GF(2^8), and share `i` holds every polynomial
+//! evaluated at `x = i`.
+//!
+//! # Example
+//!
+//! ```
+//! use kala_common::crypto::shamir;
+//!
+//! # fn example() -> kala_common::error::KalaResult<()> {
+//! let key = [7u8; 32];
+//! let shares = shamir::split(&key, 2, 3)?;
+//! assert_eq!(shamir::combine(&shares[1..], 2)?, key);
+//! # Ok(())
+//! # }
+//! ```
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{KalaError, KalaResult};
+
+/// Size of the secrets and of each share's value
+pub const SECRET_SIZE: usize = 32;
+
+/// One share of a secret
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct KeyShare {
+    /// Evaluation point, from 1
+    pub index: u8,
+    pub value: [u8; SECRET_SIZE],
+}
+
+impl KeyShare {
+    /// Hash binding the share, published so revealed shares can be checked
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-key-share");
+        hasher.update([self.index]);
+        hasher.update(self.value);
+        hasher.finalize().into()
+    }
+}
+
+/// Split `secret` into `count` shares, any `threshold` of which recover it
+pub fn split(secret: &[u8; SECRET_SIZE], threshold: u8, count: u8) -> KalaResult<Vec<KeyShare>> {
+    if threshold == 0 || threshold > count {
+        return Err(KalaError::validation(format!(
+            "Threshold {} must be between 1 and the share count {}",
+            threshold, count
+        )));
+    }
+
+    // coefficients[j][b] is the x^(j+1) coefficient of byte b's polynomial
+    let mut coefficients = vec![[0u8; SECRET_SIZE]; threshold as usize - 1];
+    for coefficient in &mut coefficients {
+        OsRng.fill_bytes(coefficient);
+    }
+
+    Ok((1..=count)
+        .map(|index| {
+            let mut value = [0u8; SECRET_SIZE];
+            for (b, byte) in value.iter_mut().enumerate() {
+                // Horner's rule, from the highest coefficient down
+                let mut y = 0u8;
+                for coefficient in coefficients.iter().rev() {
+                    y = gf_mul(y, index) ^ coefficient[b];
+                }
+                *byte = gf_mul(y, index) ^ secret[b];
+            }
+            KeyShare { index, value }
+        })
+        .collect())
+}
+
+/// Recover a secret from the first `threshold` of `shares`
+///
+/// Shares must have distinct, non-zero indices. Combining shares of
+/// different secrets, or fewer than the threshold the secret was split
+/// with, yields an unrelated value rather than an error.
+pub fn combine(shares: &[KeyShare], threshold: u8) -> KalaResult<[u8; SECRET_SIZE]> {
+    if threshold == 0 || shares.len() < threshold as usize {
+        return Err(KalaError::validation(format!(
+            "Need {} shares, got {}",
+            threshold,
+            shares.len()
+        )));
+    }
+    let shares = &shares[..threshold as usize];
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0 || shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(KalaError::validation(format!("Invalid share index {}", share.index)));
+        }
+    }
+
+    // Lagrange interpolation at x = 0; subtraction is XOR in GF(2^8)
+    let mut secret = [0u8; SECRET_SIZE];
+    for share in shares {
+        let mut numerator = 1u8;
+        let mut denominator = 1u8;
+        for other in shares.iter().filter(|other| other.index != share.index) {
+            numerator = gf_mul(numerator, other.index);
+            denominator = gf_mul(denominator, share.index ^ other.index);
+        }
+        let basis = gf_mul(numerator, gf_inverse(denominator));
+        for (byte, value) in secret.iter_mut().zip(share.value) {
+            *byte ^= gf_mul(value, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Multiplication in GF(2^8) modulo the AES polynomial
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8), as a^254
+fn gf_inverse(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut power = a;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            result = gf_mul(result, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_combine() {
+        let secret: [u8; 32] = std::array::from_fn(|i| i as u8 * 7);
+        let shares = split(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // Any three shares, in any order, recover the secret
+        assert_eq!(combine(&shares[..3], 3).unwrap(), secret);
+        let picked = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(combine(&picked, 3).unwrap(), secret);
+
+        // Two shares aren't enough
+        assert!(combine(&shares[..2], 3).is_err());
+        assert_ne!(combine(&shares[..2], 2).unwrap(), secret);
+
+        let duplicate = [shares[0].clone(), shares[0].clone(), shares[1].clone()];
+        assert!(combine(&duplicate, 3).is_err());
+        assert!(split(&secret, 0, 5).is_err());
+        assert!(split(&secret, 6, 5).is_err());
+
+        // A threshold of one hands the secret to every share holder
+        assert!(split(&secret, 1, 2).unwrap().iter().all(|share| share.value == secret));
+    }
+
+    #[test]
+    fn test_field_inverse() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inverse(a)), 1);
+        }
+    }
+}
@@ -0,0 +1,489 @@
+//! Witness key signers
+//!
+//! A witness signs with two keys derived from one 32-byte seed: an Ed25519
+//! key identifying it and signing epochs and transactions, and a BLS12-381
+//! key for tick votes. [`Signer`] hides where the seed lives, so the node
+//! never needs the keys itself:
+//!
+//! - [`InMemorySigner`] holds the derived keys in process memory
+//! - [`KeystoreSigner`] decrypts the seed from a password-protected
+//!   [`Keystore`] file at startup, so it is never stored unencrypted
+//! - [`RemoteSigner`] forwards each signing request over HTTP to a signer
+//!   service or hardware wallet bridge, so the seed never reaches the node
+//!
+//! BLS signatures are made and checked with the [`bls`](super::bls) module.
+//!
+//! The Ed25519 key also decrypts what is sent to the witness, such as its
+//! shares of threshold-locked envelopes, through [`Signer::key_agreement`].
+
+use std::path::Path;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+use bincode::{Decode, Encode};
+use ed25519_dalek::{Signer as _, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use super::bls;
+use crate::error::{KalaError, KalaResult};
+
+/// Current [`Keystore`] format version
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Default scrypt cost of new keystores, as log2 of N
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 15;
+
+/// Longest a remote signer may take to answer
+#[cfg(feature = "remote-signer")]
+pub const REMOTE_SIGNER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Signature schemes a witness signs with
+///
+/// Tick votes default to BLS12-381, whose signatures aggregate.
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    /// Ed25519, with 32-byte public keys and 64-byte signatures
+    Ed25519,
+    /// BLS12-381, with 48-byte public keys and 96-byte signatures
+    #[default]
+    Bls12381,
+}
+
+impl SignatureScheme {
+    /// Check `signature` over `message` under `public_key`
+    pub fn verify(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        match self {
+            SignatureScheme::Ed25519 => {
+                let (Ok(key), Ok(signature)) = (
+                    <[u8; 32]>::try_from(public_key),
+                    ed25519_dalek::Signature::from_slice(signature),
+                ) else {
+                    return false;
+                };
+                VerifyingKey::from_bytes(&key)
+                    .is_ok_and(|key| key.verify(message, &signature).is_ok())
+            }
+            SignatureScheme::Bls12381 => bls::verify(public_key, message, signature),
+        }
+    }
+}
+
+/// Signs with a witness's keys wherever they are kept
+///
+/// # Example
+///
+/// ```
+/// use kala_common::crypto::signer::{InMemorySigner, SignatureScheme, Signer};
+///
+/// # async fn example() -> kala_common::KalaResult<()> {
+/// let signer = InMemorySigner::from_seed(&[7u8; 32])?;
+/// let signature = signer.sign(SignatureScheme::Ed25519, b"message").await?;
+/// let public_key = signer.public_key(SignatureScheme::Ed25519);
+/// assert!(SignatureScheme::Ed25519.verify(&public_key, b"message", &signature));
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Public key of the witness for `scheme`, compressed for BLS
+    fn public_key(&self, scheme: SignatureScheme) -> Vec<u8>;
+
+    /// Sign `message` with the witness's key for `scheme`
+    async fn sign(&self, scheme: SignatureScheme, message: &[u8]) -> KalaResult<Vec<u8>>;
+
+    /// X25519 shared secret of the witness's Ed25519 key and `public_key`
+    ///
+    /// See [`key_agreement`]. Signers that can only sign don't support it.
+    async fn key_agreement(&self, public_key: &[u8; 32]) -> KalaResult<[u8; 32]> {
+        let _ = public_key;
+        Err(KalaError::crypto("Signer does not support key agreement"))
+    }
+}
+
+/// X25519 shared secret of an Ed25519 key and another Ed25519 public key
+///
+/// Both keys are mapped to their Montgomery form, so either side computes
+/// the same secret from its own key and the other's public key.
+pub fn key_agreement(key: &SigningKey, public_key: &[u8; 32]) -> KalaResult<[u8; 32]> {
+    let shared = VerifyingKey::from_bytes(public_key)
+        .map_err(|e| KalaError::crypto(format!("Invalid Ed25519 public key: {}", e)))?
+        .to_montgomery()
+        .mul_clamped(key.to_scalar_bytes())
+        .0;
+    // Low-order points give a secret anyone can compute
+    if shared == [0u8; 32] {
+        return Err(KalaError::crypto("Key agreement with a low-order point"));
+    }
+    Ok(shared)
+}
+
+/// Witness keys held in process memory
+pub struct InMemorySigner {
+    ed25519: SigningKey,
+    bls: bls::SecretKey,
+    bls_public: Vec<u8>,
+}
+
+impl InMemorySigner {
+    /// Derive both witness keys from a 32-byte seed
+    pub fn from_seed(seed: &[u8; 32]) -> KalaResult<Self> {
+        let bls = bls::keygen(seed)?;
+        Ok(Self {
+            ed25519: SigningKey::from_bytes(seed),
+            bls_public: bls::public_key(&bls),
+            bls,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for InMemorySigner {
+    fn public_key(&self, scheme: SignatureScheme) -> Vec<u8> {
+        match scheme {
+            SignatureScheme::Ed25519 => self.ed25519.verifying_key().to_bytes().to_vec(),
+            SignatureScheme::Bls12381 => self.bls_public.clone(),
+        }
+    }
+
+    async fn sign(&self, scheme: SignatureScheme, message: &[u8]) -> KalaResult<Vec<u8>> {
+        Ok(match scheme {
+            SignatureScheme::Ed25519 => self.ed25519.sign(message).to_bytes().to_vec(),
+            SignatureScheme::Bls12381 => bls::sign(&self.bls, message),
+        })
+    }
+
+    async fn key_agreement(&self, public_key: &[u8; 32]) -> KalaResult<[u8; 32]> {
+        key_agreement(&self.ed25519, public_key)
+    }
+}
+
+/// scrypt parameters of a [`Keystore`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// log2 of the CPU/memory cost N
+    pub log_n: u8,
+    /// Block size
+    pub r: u32,
+    /// Parallelism
+    pub p: u32,
+    /// Hex-encoded random salt
+    pub salt: String,
+}
+
+/// A witness seed encrypted under a password
+///
+/// The AES-256-GCM key is derived from the password with scrypt. The
+/// Ed25519 public key is authenticated along with the seed, so a keystore
+/// can't be passed off as another witness's.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Keystore {
+    /// Keystore format version
+    pub version: u32,
+    /// Hex-encoded Ed25519 public key of the witness
+    pub public_key: String,
+    /// Key derivation parameters
+    pub scrypt: ScryptParams,
+    /// Hex-encoded AES-GCM nonce
+    pub nonce: String,
+    /// Hex-encoded encrypted seed followed by the GCM tag
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt `seed` under `password` with scrypt cost 2^`log_n`
+    pub fn encrypt(seed: &[u8; 32], password: &str, log_n: u8) -> KalaResult<Self> {
+        let mut salt = [0u8; 32];
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let params = ScryptParams {
+            log_n,
+            r: 8,
+            p: 1,
+            salt: hex::encode(salt),
+        };
+        let public_key = SigningKey::from_bytes(seed).verifying_key().to_bytes();
+        let cipher = Self::cipher(password, &params)?;
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: seed,
+                    aad: &public_key,
+                },
+            )
+            .map_err(|_| KalaError::crypto("Keystore encryption failed"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            public_key: hex::encode(public_key),
+            scrypt: params,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Encrypt a freshly generated seed under `password`
+    pub fn generate(password: &str, log_n: u8) -> KalaResult<Self> {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        Self::encrypt(&seed, password, log_n)
+    }
+
+    /// Decrypt the seed with `password`
+    pub fn decrypt(&self, password: &str) -> KalaResult<[u8; 32]> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KalaError::validation(format!(
+                "Unsupported keystore version {}",
+                self.version
+            )));
+        }
+        let public_key = decode_hex(&self.public_key, "public key")?;
+        let nonce: [u8; 12] = decode_hex(&self.nonce, "nonce")?
+            .try_into()
+            .map_err(|_| KalaError::validation("Keystore nonce must be 12 bytes"))?;
+        let ciphertext = decode_hex(&self.ciphertext, "ciphertext")?;
+
+        let cipher = Self::cipher(password, &self.scrypt)?;
+        let seed: [u8; 32] = cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &public_key,
+                },
+            )
+            .map_err(|_| KalaError::crypto("Wrong keystore password or corrupt keystore"))?
+            .try_into()
+            .map_err(|_| KalaError::crypto("Keystore seed must be 32 bytes"))?;
+
+        if SigningKey::from_bytes(&seed).verifying_key().to_bytes()[..] != public_key[..] {
+            return Err(KalaError::crypto("Keystore seed does not match its public key"));
+        }
+        Ok(seed)
+    }
+
+    /// Read a keystore from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> KalaResult<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write the keystore to `path` as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> KalaResult<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn cipher(password: &str, params: &ScryptParams) -> KalaResult<Aes256Gcm> {
+        let salt = decode_hex(&params.salt, "salt")?;
+        let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p, 32)
+            .map_err(|e| KalaError::validation(format!("Invalid scrypt parameters: {}", e)))?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(password.as_bytes(), &salt, &scrypt_params, &mut key)
+            .map_err(|e| KalaError::crypto(format!("scrypt failed: {}", e)))?;
+        Aes256Gcm::new_from_slice(&key)
+            .map_err(|_| KalaError::crypto("Invalid keystore encryption key"))
+    }
+}
+
+fn decode_hex(value: &str, field: &str) -> KalaResult<Vec<u8>> {
+    hex::decode(value).map_err(|e| KalaError::validation(format!("Invalid keystore {} hex: {}", field, e)))
+}
+
+/// Witness keys decrypted from a [`Keystore`] file
+pub struct KeystoreSigner {
+    inner: InMemorySigner,
+}
+
+impl KeystoreSigner {
+    /// Decrypt the keystore at `path` with `password`
+    pub fn open(path: impl AsRef<Path>, password: &str) -> KalaResult<Self> {
+        let seed = Keystore::load(path)?.decrypt(password)?;
+        Ok(Self {
+            inner: InMemorySigner::from_seed(&seed)?,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for KeystoreSigner {
+    fn public_key(&self, scheme: SignatureScheme) -> Vec<u8> {
+        self.inner.public_key(scheme)
+    }
+
+    async fn sign(&self, scheme: SignatureScheme, message: &[u8]) -> KalaResult<Vec<u8>> {
+        self.inner.sign(scheme, message).await
+    }
+
+    async fn key_agreement(&self, public_key: &[u8; 32]) -> KalaResult<[u8; 32]> {
+        self.inner.key_agreement(public_key).await
+    }
+}
+
+/// Public keys served by a remote signer at `GET /public_keys`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemotePublicKeys {
+    /// Hex-encoded Ed25519 public key
+    pub ed25519: String,
+    /// Hex-encoded compressed BLS12-381 public key
+    pub bls12381: String,
+}
+
+/// Body of a `POST /sign` request to a remote signer
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemoteSignRequest {
+    /// Scheme to sign with
+    pub scheme: SignatureScheme,
+    /// Hex-encoded message
+    pub message: String,
+}
+
+/// Response of a remote signer to `POST /sign`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemoteSignResponse {
+    /// Hex-encoded signature
+    pub signature: String,
+}
+
+/// Witness keys held by a signer service reached over HTTP
+///
+/// The service answers `GET /public_keys` with [`RemotePublicKeys`] and
+/// `POST /sign` with a [`RemoteSignResponse`] for a [`RemoteSignRequest`].
+/// Every signature it returns is verified before use.
+#[cfg(feature = "remote-signer")]
+pub struct RemoteSigner {
+    url: String,
+    client: reqwest::Client,
+    ed25519_public: Vec<u8>,
+    bls_public: Vec<u8>,
+}
+
+#[cfg(feature = "remote-signer")]
+impl RemoteSigner {
+    /// Connect to the signer at `url` and fetch its public keys
+    pub async fn connect(url: impl Into<String>) -> KalaResult<Self> {
+        let url = url.into().trim_end_matches('/').to_string();
+        let client = reqwest::Client::builder()
+            .timeout(REMOTE_SIGNER_TIMEOUT)
+            .build()
+            .map_err(|e| KalaError::network(format!("Failed to build HTTP client: {}", e)))?;
+
+        let keys: RemotePublicKeys = client
+            .get(format!("{}/public_keys", url))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| KalaError::network(format!("Remote signer {} unreachable: {}", url, e)))?
+            .json()
+            .await
+            .map_err(|e| KalaError::network(format!("Invalid remote signer response: {}", e)))?;
+
+        Ok(Self {
+            ed25519_public: hex::decode(&keys.ed25519)
+                .map_err(|e| KalaError::validation(format!("Invalid Ed25519 key hex: {}", e)))?,
+            bls_public: hex::decode(&keys.bls12381)
+                .map_err(|e| KalaError::validation(format!("Invalid BLS key hex: {}", e)))?,
+            url,
+            client,
+        })
+    }
+}
+
+#[cfg(feature = "remote-signer")]
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn public_key(&self, scheme: SignatureScheme) -> Vec<u8> {
+        match scheme {
+            SignatureScheme::Ed25519 => self.ed25519_public.clone(),
+            SignatureScheme::Bls12381 => self.bls_public.clone(),
+        }
+    }
+
+    async fn sign(&self, scheme: SignatureScheme, message: &[u8]) -> KalaResult<Vec<u8>> {
+        let response: RemoteSignResponse = self
+            .client
+            .post(format!("{}/sign", self.url))
+            .json(&RemoteSignRequest {
+                scheme,
+                message: hex::encode(message),
+            })
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| KalaError::network(format!("Remote signing failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| KalaError::network(format!("Invalid remote signer response: {}", e)))?;
+
+        let signature = hex::decode(&response.signature)
+            .map_err(|e| KalaError::validation(format!("Invalid signature hex: {}", e)))?;
+        if !scheme.verify(&self.public_key(scheme), message, &signature) {
+            return Err(KalaError::crypto("Remote signer returned an invalid signature"));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_signer() {
+        let signer = InMemorySigner::from_seed(&[7u8; 32]).unwrap();
+
+        for scheme in [SignatureScheme::Ed25519, SignatureScheme::Bls12381] {
+            let public_key = signer.public_key(scheme);
+            let signature = signer.sign(scheme, b"tick").await.unwrap();
+            assert!(scheme.verify(&public_key, b"tick", &signature));
+            assert!(!scheme.verify(&public_key, b"tock", &signature));
+        }
+        assert_eq!(signer.public_key(SignatureScheme::Ed25519).len(), 32);
+        assert_eq!(signer.public_key(SignatureScheme::Bls12381).len(), 48);
+    }
+
+    #[tokio::test]
+    async fn test_key_agreement() {
+        let witness = InMemorySigner::from_seed(&[7u8; 32]).unwrap();
+        let witness_key: [u8; 32] = witness.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+        let ephemeral = SigningKey::from_bytes(&[9u8; 32]);
+
+        let sender_secret = key_agreement(&ephemeral, &witness_key).unwrap();
+        let witness_secret = witness
+            .key_agreement(&ephemeral.verifying_key().to_bytes())
+            .await
+            .unwrap();
+        assert_eq!(sender_secret, witness_secret);
+
+        let other = SigningKey::from_bytes(&[8u8; 32]);
+        assert_ne!(key_agreement(&other, &witness_key).unwrap(), sender_secret);
+    }
+
+    #[tokio::test]
+    async fn test_keystore_roundtrip() {
+        let seed = [9u8; 32];
+        let keystore = Keystore::encrypt(&seed, "correct horse", 4).unwrap();
+        assert_eq!(keystore.decrypt("correct horse").unwrap(), seed);
+        assert!(keystore.decrypt("battery staple").is_err());
+
+        // The public key is authenticated with the seed
+        let mut relabeled = keystore.clone();
+        relabeled.public_key = hex::encode([1u8; 32]);
+        assert!(relabeled.decrypt("correct horse").is_err());
+
+        let path = std::env::temp_dir().join(format!("kala_keystore_{}.json", std::process::id()));
+        keystore.save(&path).unwrap();
+        let signer = KeystoreSigner::open(&path, "correct horse").unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let expected = InMemorySigner::from_seed(&seed).unwrap();
+        for scheme in [SignatureScheme::Ed25519, SignatureScheme::Bls12381] {
+            assert_eq!(signer.public_key(scheme), expected.public_key(scheme));
+        }
+    }
+}
@@ -1,7 +1,43 @@
 //! Database operation patterns and utilities
+//!
+//! Records are kept in dedicated RocksDB column families, chosen by the
+//! prefix of their key (see [`Column::for_key`]), so callers keep a single
+//! logical keyspace. Writes that must land together, such as a tick and the
+//! chain state after it, go through a [`KalaBatch`] committed atomically.
+//!
+//! Reads go through an optional [`ReadCache`] sized by
+//! [`DatabaseConfig::read_cache_bytes`], which every write invalidates.
+//!
+//! Batches that end a unit of work, such as a tick, are committed with
+//! [`KalaDatabase::commit_batch`], which syncs them to disk as often as the
+//! [`FsyncPolicy`] asks.
+//!
+//! Large values of the columns enabled in [`DatabaseConfig::compression`]
+//! are stored zstd-compressed by a [`ValueCompressor`], against a
+//! dictionary trained on the column once it holds enough values. Reads
+//! decompress them before the read cache, so callers never see the
+//! compressed form.
+//!
+//! Kinds of records are read and written through [`TypedColumn`] handles,
+//! which fix their key prefix, key type and value type.
+//!
+//! Every operation is timed into [`DbMetrics`], and those slower than
+//! [`DatabaseConfig::slow_op_threshold_ms`] are logged.
 
+pub mod cache;
+pub mod compression;
+pub mod metrics;
+pub mod typed;
+
+pub use cache::{CacheStats, ReadCache};
+pub use compression::{CompressionConfig, CompressionStats, ValueCompressor};
+pub use metrics::{DbMetrics, DbOperation, LatencyHistogram, OperationStats};
+pub use typed::{ColumnKey, TypedColumn};
+
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use rocksdb::{DB, Options};
+use std::time::{Duration, Instant};
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, DB, Options, WriteBatch, WriteOptions};
 use crate::{
     error::{KalaResult, KalaError},
     serialization::KalaSerialize,
@@ -43,34 +79,235 @@ pub trait DatabaseOps {
     ) -> KalaResult<()>;
 }
 
+/// Column family a record is stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    /// Chain state and account records
+    Accounts,
+    /// Tick certificates, the tick accumulator and the tick index
+    Ticks,
+    /// Encrypted transaction envelopes
+    Envelopes,
+    /// VDF certificates and tick data, finality records, oracle values,
+    /// time anchors and epoch summaries
+    Proofs,
+    /// Everything else: node keys, peers, metrics
+    Metadata,
+}
+
+/// Key prefixes routed to a column other than [`Column::Metadata`]
+const COLUMN_PREFIXES: &[(&str, Column)] = &[
+    (":chain_state", Column::Accounts),
+    ("account:", Column::Accounts),
+    ("tick:", Column::Ticks),
+    ("tick_index", Column::Ticks),
+    ("mmr:", Column::Ticks),
+    ("mmr_leaf_count", Column::Ticks),
+    ("history_start", Column::Ticks),
+    ("envelope:", Column::Envelopes),
+    ("observation:", Column::Envelopes),
+    ("vdf_tick:", Column::Proofs),
+    ("vdf_data:", Column::Proofs),
+    ("finality:", Column::Proofs),
+    ("finality_index", Column::Proofs),
+    ("oracle:", Column::Proofs),
+    ("time_anchor:", Column::Proofs),
+    ("time_anchor_index", Column::Proofs),
+    ("epoch:", Column::Proofs),
+    ("epoch_index", Column::Proofs),
+    ("epoch_cert:", Column::Proofs),
+    ("epoch_cert_index", Column::Proofs),
+];
+
+/// Prefix of stored compression dictionaries, by id
+const DICTIONARY_PREFIX: &str = "zstd_dict:";
+
+/// Prefix of the id of the dictionary a column is compressed with
+const ACTIVE_DICTIONARY_PREFIX: &str = "zstd_dict_active:";
+
+impl Column {
+    /// All columns, in the order they are opened
+    pub const ALL: [Column; 5] = [
+        Column::Accounts,
+        Column::Ticks,
+        Column::Envelopes,
+        Column::Proofs,
+        Column::Metadata,
+    ];
+
+    /// Name of the RocksDB column family
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Accounts => "accounts",
+            Column::Ticks => "ticks",
+            Column::Envelopes => "envelopes",
+            Column::Proofs => "proofs",
+            Column::Metadata => "metadata",
+        }
+    }
+
+    /// Column a key belongs to, by its prefix
+    pub fn for_key(key: &[u8]) -> Column {
+        COLUMN_PREFIXES
+            .iter()
+            .find(|(prefix, _)| key.starts_with(prefix.as_bytes()))
+            .map(|(_, column)| *column)
+            .unwrap_or(Column::Metadata)
+    }
+}
+
+/// RocksDB compaction style used for every column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactionStyle {
+    /// Leveled compaction, lower space amplification
+    Level,
+    /// Universal compaction, lower write amplification
+    #[default]
+    Universal,
+}
+
+impl From<CompactionStyle> for rocksdb::DBCompactionStyle {
+    fn from(style: CompactionStyle) -> Self {
+        match style {
+            CompactionStyle::Level => rocksdb::DBCompactionStyle::Level,
+            CompactionStyle::Universal => rocksdb::DBCompactionStyle::Universal,
+        }
+    }
+}
+
+/// How often committed batches are forced to stable storage
+///
+/// A process crash never loses a committed batch, since RocksDB has handed
+/// it to the OS. A power failure or kernel crash loses whatever the OS has
+/// not flushed yet. Syncing closes that window, at the cost of waiting for
+/// the disk on every synced commit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FsyncPolicy {
+    /// Sync every commit; nothing acknowledged is lost, slowest on slow disks
+    EveryTick,
+    /// Sync every n-th commit, which also syncs the ones before it
+    ///
+    /// Up to n - 1 commits can be lost on power failure.
+    EveryNTicks(u64),
+    /// Leave flushing to the OS; fastest, but the last few seconds of
+    /// commits can be lost on power failure
+    #[default]
+    OsDefault,
+}
+
+/// Writes committed together by [`KalaDatabase::write_batch`]
+///
+/// Keys are routed to their column the same way as single writes.
+#[derive(Default)]
+pub struct KalaBatch {
+    operations: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+}
+
+impl KalaBatch {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a raw value
+    pub fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.operations.push((key.to_vec(), Some(value.to_vec())));
+    }
+
+    /// Queue a raw key deletion
+    pub fn delete(&mut self, key: &[u8]) {
+        self.operations.push((key.to_vec(), None));
+    }
+
+    /// Queue an encoded value under `prefix:key`
+    pub fn put_data<T: KalaSerialize>(&mut self, prefix: &str, key: &str, data: &T) -> KalaResult<()> {
+        let encoded = data.encode()
+            .map_err(|e| KalaError::serialization(format!("Failed to encode batch data: {}", e)))?;
+        self.put(KalaDatabase::format_key(prefix, key).as_bytes(), &encoded);
+        Ok(())
+    }
+
+    /// Queue the deletion of `prefix:key`
+    pub fn delete_data(&mut self, prefix: &str, key: &str) {
+        self.delete(KalaDatabase::format_key(prefix, key).as_bytes());
+    }
+
+    /// Number of queued operations
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Whether nothing is queued
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
 /// Kala database wrapper with standardized operations
+///
+/// Cloning is cheap and shares the underlying RocksDB instance.
+#[derive(Clone)]
 pub struct KalaDatabase {
     db: Arc<DB>,
+    cache: Option<Arc<ReadCache>>,
+    compressor: Arc<ValueCompressor>,
+    metrics: Arc<DbMetrics>,
+    fsync_policy: FsyncPolicy,
+    /// Commits since the last synced one
+    unsynced_commits: Arc<AtomicU64>,
 }
 
 impl KalaDatabase {
     /// Create new database instance
     pub fn new(path: &str) -> KalaResult<Self> {
-        let mut opts = Options::default();
-        opts.create_if_missing(true);
-        opts.set_max_open_files(1000);
-        opts.set_use_fsync(false);
-        opts.set_bytes_per_sync(8388608);
-        opts.set_table_cache_num_shard_bits(6);
-        opts.set_max_write_buffer_number(32);
-        opts.set_write_buffer_size(536870912);
-        opts.set_target_file_size_base(1073741824);
-        opts.set_min_write_buffer_number_to_merge(4);
-        opts.set_level_zero_stop_writes_trigger(2000);
-        opts.set_level_zero_slowdown_writes_trigger(0);
-        opts.set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+        Self::with_config(&DatabaseConfig {
+            path: path.to_string(),
+            ..DatabaseConfig::default()
+        })
+    }
 
-        let db = DB::open(&opts, path)
+    /// Open the database at `config.path` with its tuning options
+    ///
+    /// Missing column families are created, and records of databases
+    /// written before column families were introduced are moved out of the
+    /// default column. Compressed columns without a dictionary get one
+    /// trained if they hold enough values.
+    pub fn with_config(config: &DatabaseConfig) -> KalaResult<Self> {
+        let opts = DatabaseUtils::create_options(config);
+        let columns = Column::ALL
+            .iter()
+            .map(|column| ColumnFamilyDescriptor::new(column.name(), DatabaseUtils::create_options(config)));
+
+        let db = DB::open_cf_descriptors(&opts, &config.path, columns)
             .map_err(|e| KalaError::database(format!("Failed to open database: {}", e)))?;
 
-        Ok(Self {
+        let database = Self {
             db: Arc::new(db),
-        })
+            cache: (config.read_cache_bytes > 0)
+                .then(|| Arc::new(ReadCache::new(config.read_cache_bytes))),
+            compressor: Arc::new(ValueCompressor::new(config.compression.clone())),
+            metrics: Arc::new(DbMetrics::new(
+                (config.slow_op_threshold_ms > 0).then(|| Duration::from_millis(config.slow_op_threshold_ms)),
+            )),
+            fsync_policy: config.fsync_policy,
+            unsynced_commits: Arc::new(AtomicU64::new(0)),
+        };
+        database.migrate_default_column()?;
+        database.load_dictionaries()?;
+
+        for column in config.compression.columns.iter().copied() {
+            if database.compressor.active_dictionary(column).is_some() {
+                continue;
+            }
+            // Fresh databases have nothing to train on; the next start will
+            if let Err(e) = database.train_dictionary(column) {
+                tracing::warn!("Failed to train a dictionary for {}: {}", column.name(), e);
+            }
+        }
+        Ok(database)
     }
 
     /// Format key with prefix
@@ -78,25 +315,268 @@ impl KalaDatabase {
         format!("{}:{}", prefix, key)
     }
 
-    /// Get raw value from database
-    pub fn get_raw(&self, key: &[u8]) -> KalaResult<Option<Vec<u8>>> {
+    /// Handle of a column family, all of which are opened with the database
+    fn column(&self, column: Column) -> KalaResult<&ColumnFamily> {
+        self.db
+            .cf_handle(column.name())
+            .ok_or_else(|| KalaError::database(format!("Missing column family {}", column.name())))
+    }
+
+    /// Move records from the flat default keyspace into their columns
+    fn migrate_default_column(&self) -> KalaResult<()> {
+        let mut batch = WriteBatch::default();
+        for item in self.db.iterator(rocksdb::IteratorMode::Start) {
+            let (key, value) = item.map_err(KalaError::from)?;
+            batch.put_cf(self.column(Column::for_key(&key))?, &key, &value);
+            batch.delete(&key);
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        tracing::info!("Moving {} records into column families", batch.len() / 2);
         self.db
-            .get(key)
+            .write(batch)
             .map_err(KalaError::from)
     }
 
+    /// Get raw value from database, through the read cache
+    pub fn get_raw(&self, key: &[u8]) -> KalaResult<Option<Vec<u8>>> {
+        let Some(cache) = &self.cache else {
+            return self.get_uncached(key);
+        };
+        if let Some(value) = cache.get(key) {
+            return Ok(Some(value));
+        }
+
+        let generation = cache.generation();
+        let value = self.get_uncached(key)?;
+        if let Some(value) = &value {
+            cache.insert(key, value, generation);
+        }
+        Ok(value)
+    }
+
+    fn get_uncached(&self, key: &[u8]) -> KalaResult<Option<Vec<u8>>> {
+        let column = Column::for_key(key);
+        let started = Instant::now();
+        let stored = self
+            .db
+            .get_cf(self.column(column)?, key)
+            .map_err(KalaError::from)?;
+        let value = match stored {
+            Some(stored) => Some(self.compressor.decompress(&stored)?.unwrap_or(stored)),
+            None => None,
+        };
+        self.metrics.record(Some(column), DbOperation::Get, started.elapsed(), || {
+            String::from_utf8_lossy(key).into_owned()
+        });
+        Ok(value)
+    }
+
     /// Put raw value to database
     pub fn put_raw(&self, key: &[u8], value: &[u8]) -> KalaResult<()> {
-        self.db
-            .put(key, value)
-            .map_err(KalaError::from)
+        let column = Column::for_key(key);
+        let started = Instant::now();
+        let compressed = self.compressor.compress(column, value)?;
+        let result = self
+            .db
+            .put_cf(self.column(column)?, key, compressed.as_deref().unwrap_or(value))
+            .map_err(KalaError::from);
+        self.invalidate(key);
+        self.metrics.record(Some(column), DbOperation::Put, started.elapsed(), || {
+            format!("{} ({} bytes)", String::from_utf8_lossy(key), value.len())
+        });
+        result
     }
 
     /// Delete raw key from database
     pub fn delete_raw(&self, key: &[u8]) -> KalaResult<()> {
-        self.db
-            .delete(key)
-            .map_err(KalaError::from)
+        let column = Column::for_key(key);
+        let started = Instant::now();
+        let result = self
+            .db
+            .delete_cf(self.column(column)?, key)
+            .map_err(KalaError::from);
+        self.invalidate(key);
+        self.metrics.record(Some(column), DbOperation::Delete, started.elapsed(), || {
+            String::from_utf8_lossy(key).into_owned()
+        });
+        result
+    }
+
+    /// Drop a written key from the read cache
+    fn invalidate(&self, key: &[u8]) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(key);
+        }
+    }
+
+    /// Hit and size counters of the read cache, if it is enabled
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Compression and decompression counters since the database was opened
+    pub fn compression_stats(&self) -> CompressionStats {
+        self.compressor.stats()
+    }
+
+    /// Latency histograms of every operation since the database was opened
+    pub fn operation_stats(&self) -> Vec<OperationStats> {
+        self.metrics.snapshot()
+    }
+
+    /// Train a compression dictionary on values of `column` and use it for
+    /// new writes to the column
+    ///
+    /// Values already stored keep the dictionary they were written with,
+    /// so every dictionary is kept. Returns the new dictionary's id, or
+    /// `None` if the column holds too few values large enough to compress.
+    pub fn train_dictionary(&self, column: Column) -> KalaResult<Option<u32>> {
+        let min_value_bytes = self.compressor.config().min_value_bytes;
+        let mut samples = Vec::new();
+        for item in self.db.iterator_cf(self.column(column)?, rocksdb::IteratorMode::Start) {
+            let (_, stored) = item.map_err(KalaError::from)?;
+            let value = self.compressor.decompress(&stored)?.unwrap_or_else(|| stored.to_vec());
+            if value.len() >= min_value_bytes {
+                samples.push(value);
+            }
+            if samples.len() == compression::MAX_TRAINING_SAMPLES {
+                break;
+            }
+        }
+        let Some((id, dictionary)) = self.compressor.train(&samples)? else {
+            return Ok(None);
+        };
+
+        // Dictionaries are stored as is, whatever the metadata column's setting
+        let metadata = self.column(Column::Metadata)?;
+        let mut batch = WriteBatch::default();
+        batch.put_cf(metadata, format!("{}{}", DICTIONARY_PREFIX, id), &dictionary);
+        batch.put_cf(metadata, format!("{}{}", ACTIVE_DICTIONARY_PREFIX, column.name()), id.to_le_bytes());
+        self.db.write(batch).map_err(KalaError::from)?;
+
+        tracing::info!(
+            "Trained a {}-byte compression dictionary for {} on {} values",
+            dictionary.len(),
+            column.name(),
+            samples.len()
+        );
+        self.compressor.add_dictionary(id, dictionary, Some(column));
+        Ok(Some(id))
+    }
+
+    /// Hand every stored dictionary to the compressor
+    fn load_dictionaries(&self) -> KalaResult<()> {
+        let metadata = self.column(Column::Metadata)?;
+        let prefix = DICTIONARY_PREFIX.as_bytes();
+        let iter = self.db.iterator_cf(metadata, rocksdb::IteratorMode::From(prefix, rocksdb::Direction::Forward));
+        for item in iter {
+            let (key, dictionary) = item.map_err(KalaError::from)?;
+            let Some(id) = key.strip_prefix(prefix) else {
+                break;
+            };
+            let id = String::from_utf8_lossy(id)
+                .parse()
+                .map_err(|_| KalaError::database("Invalid compression dictionary key".to_string()))?;
+            self.compressor.add_dictionary(id, dictionary.to_vec(), None);
+        }
+
+        for column in Column::ALL {
+            let key = format!("{}{}", ACTIVE_DICTIONARY_PREFIX, column.name());
+            if let Some(id) = self.db.get_cf(metadata, key).map_err(KalaError::from)? {
+                let id = id
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| KalaError::database("Invalid active compression dictionary".to_string()))?;
+                self.compressor.activate_dictionary(column, u32::from_le_bytes(id))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Commit every operation of a batch atomically
+    pub fn write_batch(&self, batch: KalaBatch) -> KalaResult<()> {
+        self.write_batch_opt(batch, &WriteOptions::default())
+    }
+
+    /// Commit a batch that ends a unit of work, such as a tick
+    ///
+    /// The write is synced to disk when the [`FsyncPolicy`] calls for it.
+    /// A synced write also makes every earlier write durable, as they share
+    /// the write-ahead log.
+    pub fn commit_batch(&self, batch: KalaBatch) -> KalaResult<()> {
+        let sync = match self.fsync_policy {
+            FsyncPolicy::EveryTick => true,
+            FsyncPolicy::EveryNTicks(n) => {
+                let commits = self.unsynced_commits.fetch_add(1, Ordering::Relaxed) + 1;
+                commits >= n
+            }
+            FsyncPolicy::OsDefault => false,
+        };
+
+        let mut options = WriteOptions::default();
+        options.set_sync(sync);
+        self.write_batch_opt(batch, &options)?;
+        if sync {
+            self.unsynced_commits.store(0, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Policy [`commit_batch`](Self::commit_batch) syncs by
+    pub fn fsync_policy(&self) -> FsyncPolicy {
+        self.fsync_policy
+    }
+
+    /// Force every write so far to stable storage
+    pub fn sync_wal(&self) -> KalaResult<()> {
+        let started = Instant::now();
+        self.db.flush_wal(true).map_err(KalaError::from)?;
+        self.unsynced_commits.store(0, Ordering::Relaxed);
+        self.metrics.record(None, DbOperation::Sync, started.elapsed(), || "write-ahead log".to_string());
+        Ok(())
+    }
+
+    fn write_batch_opt(&self, batch: KalaBatch, options: &WriteOptions) -> KalaResult<()> {
+        let started = Instant::now();
+        let mut columns = Vec::new();
+        let mut bytes = 0;
+        let mut write = WriteBatch::default();
+        for (key, value) in &batch.operations {
+            let column = Column::for_key(key);
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+            bytes += key.len() + value.as_ref().map_or(0, Vec::len);
+            let handle = self.column(column)?;
+            match value {
+                Some(value) => match self.compressor.compress(column, value)? {
+                    Some(compressed) => write.put_cf(handle, key, compressed),
+                    None => write.put_cf(handle, key, value),
+                },
+                None => write.delete_cf(handle, key),
+            }
+        }
+
+        let result = self
+            .db
+            .write_opt(write, options)
+            .map_err(KalaError::from);
+        for (key, _) in &batch.operations {
+            self.invalidate(key);
+        }
+
+        let column = match columns.as_slice() {
+            [column] => Some(*column),
+            _ => None,
+        };
+        self.metrics.record(column, DbOperation::Batch, started.elapsed(), || {
+            let names: Vec<&str> = columns.iter().map(|column| column.name()).collect();
+            format!("{} operations, {} bytes, columns {}", batch.len(), bytes, names.join(", "))
+        });
+        result
     }
 
     /// Get database statistics
@@ -109,11 +589,26 @@ impl KalaDatabase {
 
     /// Compact database
     pub fn compact(&self) -> KalaResult<()> {
-        self.db
-            .compact_range::<&[u8], &[u8]>(None, None);
+        for column in Column::ALL {
+            let started = Instant::now();
+            self.db
+                .compact_range_cf::<&[u8], &[u8]>(self.column(column)?, None, None);
+            self.metrics.record(Some(column), DbOperation::Compact, started.elapsed(), || "full range".to_string());
+        }
         Ok(())
     }
 
+    /// Write a consistent copy of the database to `path` as a RocksDB checkpoint
+    ///
+    /// Files are hard-linked where possible, so this is cheap even for
+    /// large databases. `path` must not exist yet.
+    pub fn create_checkpoint(&self, path: &str) -> KalaResult<()> {
+        let started = Instant::now();
+        let result = DatabaseUtils::backup_database(&self.db, path);
+        self.metrics.record(None, DbOperation::Checkpoint, started.elapsed(), || path.to_string());
+        result
+    }
+
     /// Create snapshot
     pub fn snapshot(&self) -> rocksdb::Snapshot {
         self.db.snapshot()
@@ -167,7 +662,9 @@ impl DatabaseOps for KalaDatabase {
         let prefix_with_separator = format!("{}:", prefix);
         let prefix_bytes = prefix_with_separator.as_bytes();
 
-        let iter = self.db.iterator(rocksdb::IteratorMode::From(prefix_bytes, rocksdb::Direction::Forward));
+        let started = Instant::now();
+        let column = Column::for_key(prefix_bytes);
+        let iter = self.db.iterator_cf(self.column(column)?, rocksdb::IteratorMode::From(prefix_bytes, rocksdb::Direction::Forward));
         
         for item in iter {
             let (key, _) = item.map_err(KalaError::from)?;
@@ -183,6 +680,9 @@ impl DatabaseOps for KalaDatabase {
             }
         }
 
+        self.metrics.record(Some(column), DbOperation::Scan, started.elapsed(), || {
+            format!("{} ({} keys)", prefix_with_separator, keys.len())
+        });
         Ok(keys)
     }
 
@@ -190,18 +690,13 @@ impl DatabaseOps for KalaDatabase {
         &self,
         operations: Vec<(String, String, T)>,
     ) -> KalaResult<()> {
-        let mut batch = rocksdb::WriteBatch::default();
+        let mut batch = KalaBatch::new();
 
         for (prefix, key, data) in operations {
-            let formatted_key = Self::format_key(&prefix, &key);
-            let encoded = data.encode()
-                .map_err(|e| KalaError::serialization(format!("Failed to encode batch data: {}", e)))?;
-            batch.put(formatted_key.as_bytes(), &encoded);
+            batch.put_data(&prefix, &key, &data)?;
         }
 
-        self.db
-            .write(batch)
-            .map_err(KalaError::from)
+        self.write_batch(batch)
     }
 }
 
@@ -213,6 +708,31 @@ pub struct DatabaseConfig {
     pub max_open_files: i32,
     pub write_buffer_size: usize,
     pub block_cache_size_mb: usize,
+    /// Compaction style of every column family
+    #[serde(default)]
+    pub compaction_style: CompactionStyle,
+    /// Byte budget of the in-memory read cache, 0 to disable it
+    #[serde(default = "default_read_cache_bytes")]
+    pub read_cache_bytes: usize,
+    /// How often [`KalaDatabase::commit_batch`] syncs to disk
+    #[serde(default)]
+    pub fsync_policy: FsyncPolicy,
+    /// Columns whose large values are stored compressed
+    #[serde(default)]
+    pub compression: CompressionConfig,
+    /// Operations slower than this many milliseconds are logged, 0 to never log
+    #[serde(default = "default_slow_op_threshold_ms")]
+    pub slow_op_threshold_ms: u64,
+}
+
+/// Default for [`DatabaseConfig::read_cache_bytes`]
+pub fn default_read_cache_bytes() -> usize {
+    64 * 1024 * 1024
+}
+
+/// Default for [`DatabaseConfig::slow_op_threshold_ms`]
+pub fn default_slow_op_threshold_ms() -> u64 {
+    100
 }
 
 impl Default for DatabaseConfig {
@@ -223,6 +743,11 @@ impl Default for DatabaseConfig {
             max_open_files: 1000,
             write_buffer_size: 536870912, // 512MB
             block_cache_size_mb: 256,
+            compaction_style: CompactionStyle::default(),
+            read_cache_bytes: default_read_cache_bytes(),
+            fsync_policy: FsyncPolicy::default(),
+            compression: CompressionConfig::default(),
+            slow_op_threshold_ms: default_slow_op_threshold_ms(),
         }
     }
 }
@@ -235,6 +760,7 @@ impl DatabaseUtils {
     pub fn create_options(config: &DatabaseConfig) -> Options {
         let mut opts = Options::default();
         opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
         opts.set_max_open_files(config.max_open_files);
         opts.set_use_fsync(false);
         opts.set_bytes_per_sync(8388608);
@@ -245,23 +771,31 @@ impl DatabaseUtils {
         opts.set_min_write_buffer_number_to_merge(4);
         opts.set_level_zero_stop_writes_trigger(2000);
         opts.set_level_zero_slowdown_writes_trigger(0);
-        opts.set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+        opts.set_compaction_style(config.compaction_style.into());
         opts
     }
 
-    /// Backup database to specified path
-    pub fn backup_database(_db: &DB, backup_path: &str) -> KalaResult<()> {
-        // This would require additional rocksdb backup functionality
-        // For now, just return success
-        tracing::info!("Database backup requested to: {}", backup_path);
+    /// Backup database to specified path using a RocksDB checkpoint
+    pub fn backup_database(db: &DB, backup_path: &str) -> KalaResult<()> {
+        let checkpoint = rocksdb::checkpoint::Checkpoint::new(db)
+            .map_err(|e| KalaError::database(format!("Failed to start checkpoint: {}", e)))?;
+        checkpoint
+            .create_checkpoint(backup_path)
+            .map_err(|e| KalaError::database(format!("Failed to create checkpoint at {}: {}", backup_path, e)))?;
+
+        tracing::info!("Database backed up to: {}", backup_path);
         Ok(())
     }
 
     /// Restore database from backup
+    ///
+    /// The backup is checkpointed again into `restore_path`, which must not
+    /// exist yet, so an existing database is never overwritten.
     pub fn restore_database(backup_path: &str, restore_path: &str) -> KalaResult<()> {
-        // This would require additional rocksdb backup functionality
-        // For now, just return success
-        tracing::info!("Database restore requested from: {} to: {}", backup_path, restore_path);
+        let backup = KalaDatabase::new(backup_path)?;
+        backup.create_checkpoint(restore_path)?;
+
+        tracing::info!("Database restored from: {} to: {}", backup_path, restore_path);
         Ok(())
     }
 }
@@ -356,4 +890,230 @@ mod tests {
         assert_eq!(keys2.len(), 1);
         assert!(keys2.contains(&"key1".to_string()));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_checkpoint_and_restore() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("checkpoint_test_db");
+        let backup_path = temp_dir.path().join("checkpoint_backup");
+        let restore_path = temp_dir.path().join("checkpoint_restore");
+        let db = KalaDatabase::new(db_path.to_str().unwrap()).unwrap();
+
+        db.put_raw(b"tick:01", b"tick").unwrap();
+        db.create_checkpoint(backup_path.to_str().unwrap()).unwrap();
+        db.put_raw(b"tick:02", b"after backup").unwrap();
+        // Checkpoints never overwrite
+        assert!(db.create_checkpoint(backup_path.to_str().unwrap()).is_err());
+
+        DatabaseUtils::restore_database(backup_path.to_str().unwrap(), restore_path.to_str().unwrap()).unwrap();
+        let restored = KalaDatabase::new(restore_path.to_str().unwrap()).unwrap();
+        assert_eq!(restored.get_raw(b"tick:01").unwrap(), Some(b"tick".to_vec()));
+        assert_eq!(restored.get_raw(b"tick:02").unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_keys_routed_to_columns() {
+        assert_eq!(Column::for_key(b"tick:0000000000000001"), Column::Ticks);
+        assert_eq!(Column::for_key(b"mmr_leaf_count"), Column::Ticks);
+        assert_eq!(Column::for_key(b":chain_state"), Column::Accounts);
+        assert_eq!(Column::for_key(b"epoch_cert:0000000000000002"), Column::Proofs);
+        assert_eq!(Column::for_key(b"witness_key"), Column::Metadata);
+
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("column_test_db");
+        let db = KalaDatabase::new(db_path.to_str().unwrap()).unwrap();
+
+        db.put_raw(b"tick:01", b"tick").unwrap();
+        let ticks = db.column(Column::Ticks).unwrap();
+        assert_eq!(db.db.get_cf(ticks, b"tick:01").unwrap(), Some(b"tick".to_vec()));
+        assert_eq!(db.db.get(b"tick:01").unwrap(), None);
+        assert_eq!(db.get_raw(b"tick:01").unwrap(), Some(b"tick".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_write_batch() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("write_batch_test_db");
+        let db = KalaDatabase::new(db_path.to_str().unwrap()).unwrap();
+        db.put_raw(b"tick_index", &1u64.to_le_bytes()).unwrap();
+
+        let mut batch = KalaBatch::new();
+        batch.put(b"tick:02", b"tick");
+        batch.put_data("", "chain_state", &TestData { id: 2, name: "state".to_string() }).unwrap();
+        batch.delete(b"tick_index");
+        assert_eq!(batch.len(), 3);
+        db.write_batch(batch).unwrap();
+
+        assert_eq!(db.get_raw(b"tick:02").unwrap(), Some(b"tick".to_vec()));
+        let state: Option<TestData> = db.load_data("", "chain_state").await.unwrap();
+        assert_eq!(state.unwrap().id, 2);
+        assert_eq!(db.get_raw(b"tick_index").unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_keys_migrated() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("legacy_test_db");
+        {
+            let mut opts = Options::default();
+            opts.create_if_missing(true);
+            let legacy = DB::open(&opts, &db_path).unwrap();
+            legacy.put(b"tick:01", b"tick").unwrap();
+            legacy.put(b"peer:abc", b"peer").unwrap();
+        }
+
+        let db = KalaDatabase::new(db_path.to_str().unwrap()).unwrap();
+        assert_eq!(db.get_raw(b"tick:01").unwrap(), Some(b"tick".to_vec()));
+        assert_eq!(db.get_raw(b"peer:abc").unwrap(), Some(b"peer".to_vec()));
+        assert_eq!(db.db.iterator(rocksdb::IteratorMode::Start).count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_cache_invalidated_on_write() {
+        let temp_dir = tempdir().unwrap();
+        let db = KalaDatabase::with_config(&DatabaseConfig {
+            path: temp_dir.path().join("cache_test_db").to_str().unwrap().to_string(),
+            read_cache_bytes: 1024,
+            ..DatabaseConfig::default()
+        })
+        .unwrap();
+
+        db.put_raw(b"tick:01", b"first").unwrap();
+        assert_eq!(db.get_raw(b"tick:01").unwrap(), Some(b"first".to_vec()));
+        assert_eq!(db.get_raw(b"tick:01").unwrap(), Some(b"first".to_vec()));
+        let stats = db.cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+        db.put_raw(b"tick:01", b"second").unwrap();
+        assert_eq!(db.get_raw(b"tick:01").unwrap(), Some(b"second".to_vec()));
+
+        let mut batch = KalaBatch::new();
+        batch.delete(b"tick:01");
+        db.write_batch(batch).unwrap();
+        assert_eq!(db.get_raw(b"tick:01").unwrap(), None);
+
+        db.store_data("test", "key1", &TestData { id: 1, name: "one".to_string() }).await.unwrap();
+        let _: Option<TestData> = db.load_data("test", "key1").await.unwrap();
+        db.delete_data("test", "key1").await.unwrap();
+        let loaded: Option<TestData> = db.load_data("test", "key1").await.unwrap();
+        assert_eq!(loaded, None);
+
+        // A disabled cache reports nothing
+        let uncached = KalaDatabase::with_config(&DatabaseConfig {
+            path: temp_dir.path().join("uncached_test_db").to_str().unwrap().to_string(),
+            read_cache_bytes: 0,
+            ..DatabaseConfig::default()
+        })
+        .unwrap();
+        assert!(uncached.cache_stats().is_none());
+    }
+
+    #[test]
+    fn test_commit_batch_fsync_policy() {
+        let temp_dir = tempdir().unwrap();
+        let db = KalaDatabase::with_config(&DatabaseConfig {
+            path: temp_dir.path().join("fsync_test_db").to_str().unwrap().to_string(),
+            fsync_policy: FsyncPolicy::EveryNTicks(3),
+            ..DatabaseConfig::default()
+        })
+        .unwrap();
+
+        let commit = |tick: u8| {
+            let mut batch = KalaBatch::new();
+            batch.put(&[b't', tick], b"tick");
+            db.commit_batch(batch).unwrap();
+            db.unsynced_commits.load(Ordering::Relaxed)
+        };
+        assert_eq!(commit(1), 1);
+        assert_eq!(commit(2), 2);
+        // The third commit syncs itself and the two before it
+        assert_eq!(commit(3), 0);
+        assert_eq!(commit(4), 1);
+        db.sync_wal().unwrap();
+        assert_eq!(db.unsynced_commits.load(Ordering::Relaxed), 0);
+        assert_eq!(db.get_raw(&[b't', 4]).unwrap(), Some(b"tick".to_vec()));
+
+        // Policies are written as snake_case, with the interval inline
+        let policy: FsyncPolicy = serde_json::from_str(r#"{"every_n_ticks":10}"#).unwrap();
+        assert_eq!(policy, FsyncPolicy::EveryNTicks(10));
+        assert_eq!(serde_json::to_string(&FsyncPolicy::EveryTick).unwrap(), r#""every_tick""#);
+    }
+
+    #[test]
+    fn test_compressed_columns() {
+        let temp_dir = tempdir().unwrap();
+        let config = DatabaseConfig {
+            path: temp_dir.path().join("compression_test_db").to_str().unwrap().to_string(),
+            read_cache_bytes: 0,
+            ..DatabaseConfig::default()
+        };
+        let proof = |tick: usize| {
+            let form = (tick as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            format!(r#"{{"tick":{},"form":"{:016x}","proof":"{}"}}"#, tick, form, "0".repeat(400)).into_bytes()
+        };
+
+        let db = KalaDatabase::with_config(&config).unwrap();
+        db.put_raw(b"vdf_tick:1", &proof(1)).unwrap();
+        db.put_raw(b"account:1", &proof(1)).unwrap();
+        let stored = |key: &[u8]| db.db.get_cf(db.column(Column::for_key(key)).unwrap(), key).unwrap().unwrap();
+        assert!(stored(b"vdf_tick:1").starts_with(&compression::FRAME_MAGIC));
+        assert!(stored(b"vdf_tick:1").len() < proof(1).len());
+        assert_eq!(stored(b"account:1"), proof(1));
+        assert_eq!(db.get_raw(b"vdf_tick:1").unwrap(), Some(proof(1)));
+        assert_eq!(db.compression_stats().decompressed, 1);
+
+        // Too few proofs to train on yet
+        assert_eq!(db.train_dictionary(Column::Proofs).unwrap(), None);
+        let mut batch = KalaBatch::new();
+        for tick in 2..200 {
+            batch.put(format!("vdf_tick:{}", tick).as_bytes(), &proof(tick));
+        }
+        db.write_batch(batch).unwrap();
+        drop(db);
+
+        // The dictionary is trained when the database is reopened, and kept
+        let db = KalaDatabase::with_config(&config).unwrap();
+        let id = db.compressor.active_dictionary(Column::Proofs).unwrap();
+        db.put_raw(b"vdf_tick:200", &proof(200)).unwrap();
+        drop(db);
+
+        let db = KalaDatabase::with_config(&config).unwrap();
+        assert_eq!(db.compressor.active_dictionary(Column::Proofs), Some(id));
+        assert_eq!(db.get_raw(b"vdf_tick:200").unwrap(), Some(proof(200)));
+        assert_eq!(db.get_raw(b"vdf_tick:2").unwrap(), Some(proof(2)));
+
+        // Values stay readable once their column is no longer compressed
+        drop(db);
+        let db = KalaDatabase::with_config(&DatabaseConfig {
+            compression: CompressionConfig::disabled(),
+            ..config
+        })
+        .unwrap();
+        assert_eq!(db.get_raw(b"vdf_tick:200").unwrap(), Some(proof(200)));
+    }
+
+    #[test]
+    fn test_operations_timed() {
+        let temp_dir = tempdir().unwrap();
+        let db = KalaDatabase::new(temp_dir.path().join("metrics_test_db").to_str().unwrap()).unwrap();
+
+        db.put_raw(b"tick:1", b"one").unwrap();
+        db.get_raw(b"tick:1").unwrap();
+        let mut batch = KalaBatch::new();
+        batch.put(b"tick:2", b"two");
+        batch.put(b"account:a", b"a");
+        db.write_batch(batch).unwrap();
+
+        let stats = db.operation_stats();
+        let count = |column, operation| {
+            stats
+                .iter()
+                .find(|s| s.column == column && s.operation == operation)
+                .map_or(0, |s| s.latency.count)
+        };
+        assert_eq!(count(Some(Column::Ticks), DbOperation::Put), 1);
+        assert_eq!(count(Some(Column::Ticks), DbOperation::Get), 1);
+        // Batches spanning columns aren't attributed to either
+        assert_eq!(count(None, DbOperation::Batch), 1);
+    }
+}
@@ -0,0 +1,218 @@
+//! Read-through LRU cache in front of RocksDB
+//!
+//! Explorer-style workloads read the same few records over and over: the
+//! latest ticks, their finality records, the current epoch. [`ReadCache`]
+//! keeps recently read values in memory up to a byte budget, evicting the
+//! least recently used first. Every write invalidates the keys it touches,
+//! so the cache never serves a value the database no longer holds.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// Fraction of the budget a single value may take, as a divisor
+///
+/// Larger values, such as a chain state with many accounts, would evict
+/// everything else and are read from the database instead.
+const MAX_ENTRY_FRACTION: usize = 8;
+
+/// Counters of a [`ReadCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Reads served from memory
+    pub hits: u64,
+    /// Reads that went to the database
+    pub misses: u64,
+    /// Values currently cached
+    pub entries: usize,
+    /// Bytes of keys and values currently cached
+    pub bytes: usize,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<Vec<u8>, (Vec<u8>, u64)>,
+    /// Keys by the sequence number of their last use
+    recency: BTreeMap<u64, Vec<u8>>,
+    next_use: u64,
+    /// Bumped by every invalidation, so reads that raced a write are not cached
+    generation: u64,
+    stats: CacheStats,
+}
+
+/// Byte-bounded LRU cache of raw database values
+pub struct ReadCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl ReadCache {
+    /// Create a cache holding up to `capacity` bytes of keys and values
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Cached value of `key`, marking it as recently used
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let mut state = self.lock();
+        let use_number = state.next_use;
+        let cached = match state.entries.get_mut(key) {
+            Some((value, last_use)) => {
+                let previous = std::mem::replace(last_use, use_number);
+                Some((value.clone(), previous))
+            }
+            None => None,
+        };
+
+        match cached {
+            Some((value, previous)) => {
+                state.next_use += 1;
+                state.recency.remove(&previous);
+                state.recency.insert(use_number, key.to_vec());
+                state.stats.hits += 1;
+                Some(value)
+            }
+            None => {
+                state.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Current invalidation generation, to pass to [`insert`](Self::insert)
+    pub fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    /// Cache a value read from the database
+    ///
+    /// `generation` must be taken before the database read. The value is
+    /// dropped if any key was invalidated since, as it may be stale.
+    pub fn insert(&self, key: &[u8], value: &[u8], generation: u64) {
+        let size = key.len() + value.len();
+        if size > self.capacity / MAX_ENTRY_FRACTION {
+            return;
+        }
+
+        let mut state = self.lock();
+        if state.generation != generation {
+            return;
+        }
+        Self::remove(&mut state, key);
+
+        while state.stats.bytes + size > self.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            if let Some((value, _)) = state.entries.remove(&oldest) {
+                state.stats.bytes -= oldest.len() + value.len();
+            }
+        }
+
+        let use_number = state.next_use;
+        state.next_use += 1;
+        state.recency.insert(use_number, key.to_vec());
+        state.entries.insert(key.to_vec(), (value.to_vec(), use_number));
+        state.stats.bytes += size;
+        state.stats.entries = state.entries.len();
+    }
+
+    /// Drop `key` after a write to it
+    pub fn invalidate(&self, key: &[u8]) {
+        let mut state = self.lock();
+        state.generation += 1;
+        Self::remove(&mut state, key);
+    }
+
+    /// Drop every cached value
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.generation += 1;
+        state.entries.clear();
+        state.recency.clear();
+        state.stats.entries = 0;
+        state.stats.bytes = 0;
+    }
+
+    /// Hit and size counters
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// Byte budget of the cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn remove(state: &mut CacheState, key: &[u8]) {
+        if let Some((value, last_use)) = state.entries.remove(key) {
+            state.recency.remove(&last_use);
+            state.stats.bytes -= key.len() + value.len();
+            state.stats.entries = state.entries.len();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        // Room for eight 20-byte entries
+        let cache = ReadCache::new(160);
+        let generation = cache.generation();
+        for tick in 0..8u8 {
+            cache.insert(&[b't', tick], &[0u8; 18], generation);
+        }
+        assert_eq!(cache.stats().entries, 8);
+        assert_eq!(cache.stats().bytes, 160);
+
+        // Reading tick 0 makes tick 1 the oldest
+        assert!(cache.get(&[b't', 0]).is_some());
+        cache.insert(&[b't', 8], &[0u8; 18], generation);
+        assert!(cache.get(&[b't', 1]).is_none());
+        assert!(cache.get(&[b't', 0]).is_some());
+        assert!(cache.get(&[b't', 8]).is_some());
+        assert_eq!(cache.stats().bytes, 160);
+
+        // Values over an eighth of the budget are never cached
+        cache.insert(b"big", &[0u8; 40], generation);
+        assert!(cache.get(b"big").is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 3);
+        assert_eq!(stats.misses, 2);
+    }
+
+    #[test]
+    fn test_invalidation_rejects_stale_reads() {
+        let cache = ReadCache::new(1024);
+        let before_write = cache.generation();
+        cache.insert(b"tick:1", b"old", before_write);
+
+        cache.invalidate(b"tick:1");
+        assert!(cache.get(b"tick:1").is_none());
+        assert_eq!(cache.stats().bytes, 0);
+
+        // A read that started before the write must not repopulate the cache
+        cache.insert(b"tick:1", b"old", before_write);
+        assert!(cache.get(b"tick:1").is_none());
+
+        cache.insert(b"tick:1", b"new", cache.generation());
+        assert_eq!(cache.get(b"tick:1"), Some(b"new".to_vec()));
+
+        cache.clear();
+        assert!(cache.get(b"tick:1").is_none());
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -0,0 +1,355 @@
+//! Transparent zstd compression of large stored values
+//!
+//! Tick bodies and proofs make up most of an archive node's disk usage, and
+//! consecutive ones share most of their structure. [`ValueCompressor`]
+//! compresses values written to the columns enabled in
+//! [`CompressionConfig`], once they reach a minimum size, optionally
+//! against a dictionary trained on earlier values of the same column.
+//!
+//! Compressed values are framed as [`FRAME_MAGIC`], the dictionary id (0
+//! for none) and the uncompressed length, followed by the zstd frame.
+//! Records are JSON, which never contains a 0xFF byte, so values without
+//! the magic are read as is. Columns can therefore be switched on and off
+//! at any time: values keep the form they were written in.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::Column;
+use crate::error::{KalaError, KalaResult};
+
+/// Leading bytes of a compressed value
+pub const FRAME_MAGIC: [u8; 8] = [0xff, b'k', b'a', b'l', b'a', b'z', b's', 1];
+
+/// Magic, dictionary id and uncompressed length
+const HEADER_SIZE: usize = FRAME_MAGIC.len() + 4 + 4;
+
+/// Fewest values a dictionary is trained on
+pub const MIN_TRAINING_SAMPLES: usize = 64;
+
+/// Most values a dictionary is trained on
+pub const MAX_TRAINING_SAMPLES: usize = 2048;
+
+/// Which values are compressed, and how
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// Columns whose values are compressed on write
+    #[serde(default = "default_compressed_columns")]
+    pub columns: Vec<Column>,
+    /// Values smaller than this are stored as is
+    #[serde(default = "default_min_value_bytes")]
+    pub min_value_bytes: usize,
+    /// zstd compression level
+    #[serde(default = "default_compression_level")]
+    pub level: i32,
+    /// Size of the dictionaries trained per column, 0 to never train one
+    #[serde(default = "default_dictionary_bytes")]
+    pub dictionary_bytes: usize,
+}
+
+/// Default for [`CompressionConfig::columns`]: tick bodies and proofs
+pub fn default_compressed_columns() -> Vec<Column> {
+    vec![Column::Ticks, Column::Proofs]
+}
+
+/// Default for [`CompressionConfig::min_value_bytes`]
+pub fn default_min_value_bytes() -> usize {
+    256
+}
+
+/// Default for [`CompressionConfig::level`]
+pub fn default_compression_level() -> i32 {
+    3
+}
+
+/// Default for [`CompressionConfig::dictionary_bytes`]
+pub fn default_dictionary_bytes() -> usize {
+    64 * 1024
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            columns: default_compressed_columns(),
+            min_value_bytes: default_min_value_bytes(),
+            level: default_compression_level(),
+            dictionary_bytes: default_dictionary_bytes(),
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// No column compressed
+    pub fn disabled() -> Self {
+        Self {
+            columns: Vec::new(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Counters of a [`ValueCompressor`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressionStats {
+    /// Values written compressed
+    pub compressed: u64,
+    /// Bytes of those values before compression
+    pub compressed_input_bytes: u64,
+    /// Bytes of those values as stored
+    pub compressed_output_bytes: u64,
+    /// Compressed values read back
+    pub decompressed: u64,
+    /// Bytes those values expanded to
+    pub decompressed_bytes: u64,
+    /// Time spent decompressing, in microseconds
+    pub decompression_micros: u64,
+}
+
+#[derive(Default)]
+struct Dictionaries {
+    by_id: HashMap<u32, Vec<u8>>,
+    /// Dictionary new values of a column are compressed with
+    active: HashMap<Column, u32>,
+}
+
+/// Compresses and decompresses stored values
+pub struct ValueCompressor {
+    config: CompressionConfig,
+    dictionaries: RwLock<Dictionaries>,
+    stats: Mutex<CompressionStats>,
+}
+
+impl ValueCompressor {
+    /// Create a compressor without dictionaries
+    pub fn new(config: CompressionConfig) -> Self {
+        Self {
+            config,
+            dictionaries: RwLock::new(Dictionaries::default()),
+            stats: Mutex::new(CompressionStats::default()),
+        }
+    }
+
+    /// Settings the compressor was created with
+    pub fn config(&self) -> &CompressionConfig {
+        &self.config
+    }
+
+    /// Whether values written to `column` are compressed
+    pub fn is_enabled(&self, column: Column) -> bool {
+        self.config.columns.contains(&column)
+    }
+
+    /// Make a dictionary available for reads, and for writes to `active_for`
+    pub fn add_dictionary(&self, id: u32, dictionary: Vec<u8>, active_for: Option<Column>) {
+        let mut dictionaries = self.dictionaries.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        dictionaries.by_id.insert(id, dictionary);
+        if let Some(column) = active_for {
+            dictionaries.active.insert(column, id);
+        }
+    }
+
+    /// Compress new values of `column` with a dictionary already added
+    pub fn activate_dictionary(&self, column: Column, id: u32) -> KalaResult<()> {
+        let mut dictionaries = self.dictionaries.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !dictionaries.by_id.contains_key(&id) {
+            return Err(KalaError::database(format!("Missing compression dictionary {}", id)));
+        }
+        dictionaries.active.insert(column, id);
+        Ok(())
+    }
+
+    /// Id of the dictionary new values of `column` are compressed with
+    pub fn active_dictionary(&self, column: Column) -> Option<u32> {
+        self.read_dictionaries().active.get(&column).copied()
+    }
+
+    /// Framed, compressed form of a value written to `column`
+    ///
+    /// Returns `None` when the value is to be stored as is: its column is
+    /// not enabled or it is too small to be worth it. Values that happen to
+    /// start with [`FRAME_MAGIC`] are always framed, so they read back
+    /// unchanged.
+    pub fn compress(&self, column: Column, value: &[u8]) -> KalaResult<Option<Vec<u8>>> {
+        let framed_anyway = value.starts_with(&FRAME_MAGIC);
+        if !framed_anyway && (!self.is_enabled(column) || value.len() < self.config.min_value_bytes) {
+            return Ok(None);
+        }
+        let length = u32::try_from(value.len())
+            .map_err(|_| KalaError::database(format!("Value of {} bytes is too large to compress", value.len())))?;
+
+        let dictionaries = self.read_dictionaries();
+        let dictionary_id = dictionaries.active.get(&column).copied().unwrap_or(0);
+        let mut compressor = match dictionaries.by_id.get(&dictionary_id) {
+            Some(dictionary) => zstd::bulk::Compressor::with_dictionary(self.config.level, dictionary),
+            None => zstd::bulk::Compressor::new(self.config.level),
+        }
+        .map_err(|e| KalaError::database(format!("Failed to create compressor: {}", e)))?;
+        let compressed = compressor
+            .compress(value)
+            .map_err(|e| KalaError::database(format!("Compression failed: {}", e)))?;
+        drop(dictionaries);
+
+        // Incompressible values stay as they are unless they must be framed
+        if !framed_anyway && HEADER_SIZE + compressed.len() >= value.len() {
+            return Ok(None);
+        }
+
+        let mut framed = Vec::with_capacity(HEADER_SIZE + compressed.len());
+        framed.extend_from_slice(&FRAME_MAGIC);
+        framed.extend_from_slice(&dictionary_id.to_le_bytes());
+        framed.extend_from_slice(&length.to_le_bytes());
+        framed.extend_from_slice(&compressed);
+
+        let mut stats = self.lock_stats();
+        stats.compressed += 1;
+        stats.compressed_input_bytes += value.len() as u64;
+        stats.compressed_output_bytes += framed.len() as u64;
+        Ok(Some(framed))
+    }
+
+    /// Original form of a stored value, or `None` if it was stored as is
+    pub fn decompress(&self, stored: &[u8]) -> KalaResult<Option<Vec<u8>>> {
+        if !stored.starts_with(&FRAME_MAGIC) {
+            return Ok(None);
+        }
+        if stored.len() < HEADER_SIZE {
+            return Err(KalaError::database("Truncated compressed value".to_string()));
+        }
+        let started = Instant::now();
+        let dictionary_id = u32::from_le_bytes(stored[8..12].try_into().expect("4-byte slice"));
+        let length = u32::from_le_bytes(stored[12..16].try_into().expect("4-byte slice")) as usize;
+
+        let dictionaries = self.read_dictionaries();
+        let mut decompressor = if dictionary_id == 0 {
+            zstd::bulk::Decompressor::new()
+        } else {
+            let dictionary = dictionaries
+                .by_id
+                .get(&dictionary_id)
+                .ok_or_else(|| KalaError::database(format!("Missing compression dictionary {}", dictionary_id)))?;
+            zstd::bulk::Decompressor::with_dictionary(dictionary)
+        }
+        .map_err(|e| KalaError::database(format!("Failed to create decompressor: {}", e)))?;
+        let value = decompressor
+            .decompress(&stored[HEADER_SIZE..], length)
+            .map_err(|e| KalaError::database(format!("Decompression failed: {}", e)))?;
+        drop(dictionaries);
+        if value.len() != length {
+            return Err(KalaError::database(format!(
+                "Decompressed {} bytes, expected {}",
+                value.len(),
+                length
+            )));
+        }
+
+        let mut stats = self.lock_stats();
+        stats.decompressed += 1;
+        stats.decompressed_bytes += length as u64;
+        stats.decompression_micros += started.elapsed().as_micros() as u64;
+        Ok(Some(value))
+    }
+
+    /// Train a dictionary on sample values of one column
+    ///
+    /// Returns `None` if there are fewer than [`MIN_TRAINING_SAMPLES`]
+    /// samples, too little data for a dictionary to help.
+    pub fn train(&self, samples: &[Vec<u8>]) -> KalaResult<Option<(u32, Vec<u8>)>> {
+        if self.config.dictionary_bytes == 0 || samples.len() < MIN_TRAINING_SAMPLES {
+            return Ok(None);
+        }
+        // zstd needs several times the dictionary size in samples
+        let total: usize = samples.iter().map(Vec::len).sum();
+        let size = self.config.dictionary_bytes.min(total / 10);
+        if size < 1024 {
+            return Ok(None);
+        }
+
+        let dictionary = zstd::dict::from_samples(samples, size)
+            .map_err(|e| KalaError::database(format!("Dictionary training failed: {}", e)))?;
+        Ok(Some((dictionary_id(&dictionary), dictionary)))
+    }
+
+    /// Compression and decompression counters
+    pub fn stats(&self) -> CompressionStats {
+        *self.lock_stats()
+    }
+
+    fn read_dictionaries(&self) -> std::sync::RwLockReadGuard<'_, Dictionaries> {
+        self.dictionaries.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_stats(&self) -> std::sync::MutexGuard<'_, CompressionStats> {
+        self.stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Id a dictionary is stored and referenced under, never 0
+pub fn dictionary_id(dictionary: &[u8]) -> u32 {
+    let digest = Sha256::digest(dictionary);
+    u32::from_le_bytes(digest[..4].try_into().expect("4-byte slice")).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(i: u32) -> Vec<u8> {
+        format!(
+            r#"{{"tick_number":{},"iterations":65536,"form":"{}","proof":"{}"}}"#,
+            i,
+            "0".repeat(200),
+            "f".repeat(200)
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let compressor = ValueCompressor::new(CompressionConfig::default());
+        let value = proof(1);
+
+        let stored = compressor.compress(Column::Proofs, &value).unwrap().unwrap();
+        assert!(stored.len() < value.len());
+        assert_eq!(compressor.decompress(&stored).unwrap(), Some(value.clone()));
+
+        // Disabled columns, small values and plain records are left alone
+        assert!(compressor.compress(Column::Accounts, &value).unwrap().is_none());
+        assert!(compressor.compress(Column::Proofs, b"{}").unwrap().is_none());
+        assert!(compressor.decompress(&value).unwrap().is_none());
+
+        // A value that looks framed is framed again, even in a disabled column
+        let lookalike = [&FRAME_MAGIC[..], b"raw"].concat();
+        let stored_lookalike = compressor.compress(Column::Accounts, &lookalike).unwrap().unwrap();
+        assert_eq!(compressor.decompress(&stored_lookalike).unwrap(), Some(lookalike));
+
+        let stats = compressor.stats();
+        assert_eq!(stats.compressed, 2);
+        assert_eq!(stats.decompressed, 2);
+        assert_eq!(stats.decompressed_bytes, value.len() as u64 + 11);
+    }
+
+    #[test]
+    fn test_dictionary_training() {
+        let compressor = ValueCompressor::new(CompressionConfig::default());
+        let samples: Vec<Vec<u8>> = (0..MIN_TRAINING_SAMPLES as u32 * 2).map(proof).collect();
+        assert!(compressor.train(&samples[..10]).unwrap().is_none());
+
+        let (id, dictionary) = compressor.train(&samples).unwrap().unwrap();
+        assert_eq!(id, dictionary_id(&dictionary));
+        compressor.add_dictionary(id, dictionary, Some(Column::Proofs));
+        assert_eq!(compressor.active_dictionary(Column::Proofs), Some(id));
+
+        let value = proof(1000);
+        let stored = compressor.compress(Column::Proofs, &value).unwrap().unwrap();
+        assert_eq!(stored[8..12], id.to_le_bytes());
+        assert_eq!(compressor.decompress(&stored).unwrap(), Some(value));
+
+        // Values compressed with a dictionary the reader lacks don't decode
+        let reader = ValueCompressor::new(CompressionConfig::default());
+        assert!(reader.decompress(&stored).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Timing of database operations
+//!
+//! Every read, write, batch and maintenance call of a
+//! [`KalaDatabase`](super::KalaDatabase) is timed into a
+//! [`LatencyHistogram`] per column and operation. Operations slower than
+//! the configured threshold are also logged as they happen, tagged the same
+//! way, so a tick that stalls on storage shows which records it waited on.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::Column;
+
+/// Upper bounds of the histogram buckets, in microseconds
+///
+/// Durations above the last bound fall in one more, unbounded bucket.
+pub const LATENCY_BUCKETS_MICROS: [u64; 12] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 100_000, 250_000, 1_000_000,
+];
+
+/// Kind of database call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DbOperation {
+    /// Read of one key, decompression included
+    Get,
+    /// Write of one key
+    Put,
+    /// Deletion of one key
+    Delete,
+    /// Atomic batch of writes
+    Batch,
+    /// Iteration over a key prefix
+    Scan,
+    /// Forced sync of the write-ahead log
+    Sync,
+    /// Manual compaction
+    Compact,
+    /// Checkpoint of the whole database
+    Checkpoint,
+}
+
+impl DbOperation {
+    /// Name used in logs
+    pub fn name(self) -> &'static str {
+        match self {
+            DbOperation::Get => "get",
+            DbOperation::Put => "put",
+            DbOperation::Delete => "delete",
+            DbOperation::Batch => "batch",
+            DbOperation::Scan => "scan",
+            DbOperation::Sync => "sync",
+            DbOperation::Compact => "compact",
+            DbOperation::Checkpoint => "checkpoint",
+        }
+    }
+}
+
+/// Distribution of operation durations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    /// Operations per bucket of [`LATENCY_BUCKETS_MICROS`], then the overflow bucket
+    pub buckets: Vec<u64>,
+    pub count: u64,
+    pub total_micros: u64,
+    pub max_micros: u64,
+}
+
+impl LatencyHistogram {
+    /// Add one operation
+    pub fn record(&mut self, duration: Duration) {
+        let micros = duration.as_micros().min(u64::MAX as u128) as u64;
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LATENCY_BUCKETS_MICROS.len() + 1];
+        }
+        let bucket = LATENCY_BUCKETS_MICROS
+            .iter()
+            .position(|bound| micros <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MICROS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total_micros = self.total_micros.saturating_add(micros);
+        self.max_micros = self.max_micros.max(micros);
+    }
+
+    /// Mean duration in microseconds, 0 if nothing was recorded
+    pub fn mean_micros(&self) -> u64 {
+        self.total_micros.checked_div(self.count).unwrap_or(0)
+    }
+
+    /// Upper bound of the bucket holding the `quantile` (0.0 to 1.0)
+    ///
+    /// Returns `None` if nothing was recorded, or the quantile falls in
+    /// the unbounded bucket.
+    pub fn quantile_micros(&self, quantile: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((self.count as f64 * quantile).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return LATENCY_BUCKETS_MICROS.get(bucket).copied();
+            }
+        }
+        None
+    }
+}
+
+/// Timings of one operation on one column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationStats {
+    /// Column operated on; `None` for batches spanning several and for
+    /// whole-database operations
+    pub column: Option<Column>,
+    pub operation: DbOperation,
+    pub latency: LatencyHistogram,
+    /// Operations over the slow threshold
+    pub slow: u64,
+}
+
+/// Histogram and slow count per column and operation
+type Timings = BTreeMap<(Option<Column>, DbOperation), (LatencyHistogram, u64)>;
+
+/// Histograms of every operation, and the slow-operation log
+pub struct DbMetrics {
+    slow_threshold: Option<Duration>,
+    operations: Mutex<Timings>,
+}
+
+impl DbMetrics {
+    /// Log operations taking longer than `slow_threshold`, if set
+    pub fn new(slow_threshold: Option<Duration>) -> Self {
+        Self {
+            slow_threshold,
+            operations: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Record a finished operation
+    ///
+    /// `detail` describes what it touched, such as the key or the size of
+    /// a batch, and only appears in the slow-operation log.
+    pub fn record(&self, column: Option<Column>, operation: DbOperation, duration: Duration, detail: impl FnOnce() -> String) {
+        let slow = self.slow_threshold.is_some_and(|threshold| duration > threshold);
+        {
+            let mut operations = self.lock();
+            let (latency, slow_count) = operations.entry((column, operation)).or_default();
+            latency.record(duration);
+            if slow {
+                *slow_count += 1;
+            }
+        }
+
+        if slow {
+            tracing::warn!(
+                column = column.map_or("multiple", Column::name),
+                operation = operation.name(),
+                "Slow database {} took {:?}: {}",
+                operation.name(),
+                duration,
+                detail()
+            );
+        }
+    }
+
+    /// Timings so far, by column and operation
+    pub fn snapshot(&self) -> Vec<OperationStats> {
+        self.lock()
+            .iter()
+            .map(|((column, operation), (latency, slow))| OperationStats {
+                column: *column,
+                operation: *operation,
+                latency: latency.clone(),
+                slow: *slow,
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Timings> {
+        self.operations.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.quantile_micros(0.5), None);
+
+        for micros in [10, 80, 80, 900, 3_000_000] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.buckets[0], 1);
+        assert_eq!(histogram.buckets[1], 2);
+        assert_eq!(histogram.buckets[4], 1);
+        assert_eq!(histogram.buckets[LATENCY_BUCKETS_MICROS.len()], 1);
+        assert_eq!(histogram.count, 5);
+        assert_eq!(histogram.max_micros, 3_000_000);
+        assert_eq!(histogram.mean_micros(), 3_001_070 / 5);
+        assert_eq!(histogram.quantile_micros(0.5), Some(100));
+        assert_eq!(histogram.quantile_micros(0.8), Some(1_000));
+        assert_eq!(histogram.quantile_micros(1.0), None);
+    }
+
+    #[test]
+    fn test_slow_operations_counted() {
+        let metrics = DbMetrics::new(Some(Duration::from_millis(10)));
+        metrics.record(Some(Column::Ticks), DbOperation::Get, Duration::from_millis(1), || "tick:1".to_string());
+        metrics.record(Some(Column::Ticks), DbOperation::Get, Duration::from_millis(20), || "tick:2".to_string());
+        metrics.record(None, DbOperation::Batch, Duration::from_millis(20), || "3 writes".to_string());
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.len(), 2);
+        let gets = stats.iter().find(|s| s.operation == DbOperation::Get).unwrap();
+        assert_eq!((gets.column, gets.latency.count, gets.slow), (Some(Column::Ticks), 2, 1));
+
+        // Without a threshold nothing is slow
+        let metrics = DbMetrics::new(None);
+        metrics.record(None, DbOperation::Sync, Duration::from_secs(5), String::new);
+        assert_eq!(metrics.snapshot()[0].slow, 0);
+    }
+}
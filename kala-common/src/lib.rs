@@ -6,12 +6,16 @@
 //!
 //! ## Modules
 //!
-//! - **serialization**: Standardized data encoding/decoding patterns
+//! - **serialization**: Standardized data encoding/decoding patterns and the canonical encoding behind consensus hashes
 //! - **network**: Network layer abstractions and messaging
-//! - **crypto**: Cryptographic utilities and hash operations  
+//! - **proto**: Protobuf wire format of the messages nodes exchange
+//! - **crypto**: Cryptographic utilities, hash operations, batch signature verification, BLS aggregate signatures, Shamir secret sharing, and witness signers
 //! - **database**: Database operation patterns
 //! - **validation**: Input validation utilities
+//! - **quorum**: Byzantine fault thresholds and quorum sizes
 //! - **types**: Common type definitions and constants
+//! - **vdf**: The [`vdf::VdfEngine`] trait consensus drives VDF implementations through
+//! - **timeline**: Conversion between VDF iterations and wall-clock time
 //!
 //! ## Example Usage
 //!
@@ -41,18 +45,24 @@
 
 pub mod serialization;
 pub mod network;
+pub mod proto;
 pub mod crypto;
+pub mod mmr;
 pub mod database;
 pub mod validation;
+pub mod quorum;
 pub mod types;
 pub mod error;
+pub mod vdf;
+pub mod timeline;
 
 /// Re-export commonly used types and traits
 pub mod prelude {
-    pub use crate::serialization::{KalaSerialize, EncodingType, HashCompute, NetworkMessage};
+    pub use crate::serialization::{KalaSerialize, EncodingType, HashCompute, NetworkMessage, CanonicalEncode, CanonicalEncoder};
     pub use crate::network::{NetworkLayer, MessageHandler, MessageType, NetworkConfig};
-    pub use crate::crypto::{CryptoUtils, HASH_SIZE, PUBKEY_SIZE, SIGNATURE_SIZE};
-    pub use crate::database::{DatabaseOps, KalaDatabase};
+    pub use crate::crypto::{CryptoUtils, MerkleTree, MerkleMultiProof, SignedPayload, verify_signatures_batch, HASH_SIZE, PUBKEY_SIZE, SIGNATURE_SIZE};
+    pub use crate::database::{DatabaseOps, KalaBatch, KalaDatabase};
+    pub use crate::mmr::{MerkleMountainRange, MmrProof, MmrStore};
     pub use crate::validation::ValidationUtils;
     pub use crate::types::{NodeId, Timestamp, BlockHeight, IterationNumber, HashExt, PublicKeyExt, SignatureExt};
     pub use crate::error::{KalaError, KalaResult};
@@ -0,0 +1,395 @@
+//! Merkle Mountain Range accumulator
+//!
+//! An append-only accumulator over a growing list of hashes. Each append
+//! touches only O(log n) nodes, and an inclusion proof for any leaf is
+//! O(log n) hashes against the current root, which makes it suitable for
+//! committing to the entire tick history as it grows.
+//!
+//! Nodes are addressed by their 0-based position in post-order. Storage is
+//! abstracted behind [`MmrStore`] so the same logic works over an in-memory
+//! vector or a database.
+
+use serde::{Deserialize, Serialize};
+use crate::{
+    crypto::CryptoUtils,
+    error::{KalaError, KalaResult},
+    types::{Hash, HashExt},
+};
+
+/// Node storage for a Merkle Mountain Range
+pub trait MmrStore {
+    /// Load the node at `pos`
+    fn get_node(&self, pos: u64) -> KalaResult<Option<Hash>>;
+
+    /// Store the node at `pos`
+    fn put_node(&mut self, pos: u64, hash: Hash) -> KalaResult<()>;
+}
+
+impl MmrStore for Vec<Hash> {
+    fn get_node(&self, pos: u64) -> KalaResult<Option<Hash>> {
+        Ok(self.get(pos as usize).copied())
+    }
+
+    fn put_node(&mut self, pos: u64, hash: Hash) -> KalaResult<()> {
+        let pos = pos as usize;
+        if pos < self.len() {
+            self[pos] = hash;
+        } else if pos == self.len() {
+            self.push(hash);
+        } else {
+            return Err(KalaError::internal(format!(
+                "MMR node {} written out of order (size {})",
+                pos,
+                self.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Merkle Mountain Range over a node store
+pub struct MerkleMountainRange<S: MmrStore = Vec<Hash>> {
+    store: S,
+    leaf_count: u64,
+}
+
+impl MerkleMountainRange<Vec<Hash>> {
+    /// Create an empty in-memory accumulator
+    pub fn new() -> Self {
+        Self::with_store(Vec::new(), 0)
+    }
+}
+
+impl Default for MerkleMountainRange<Vec<Hash>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: MmrStore> MerkleMountainRange<S> {
+    /// Open an accumulator over `store` that already holds `leaf_count` leaves
+    pub fn with_store(store: S, leaf_count: u64) -> Self {
+        Self { store, leaf_count }
+    }
+
+    /// Number of leaves appended so far
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Total number of nodes, leaves included
+    pub fn size(&self) -> u64 {
+        mmr_size(self.leaf_count)
+    }
+
+    /// Access the underlying store
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Consume the accumulator, returning the store
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// Append a leaf, returning its leaf index
+    pub fn append(&mut self, leaf: Hash) -> KalaResult<u64> {
+        let mut pos = self.size();
+        let mut height = 0;
+        let mut current = leaf;
+        self.store.put_node(pos, current)?;
+
+        // Merge with the left sibling while the next position is a parent
+        while pos_height(pos + 1) > height {
+            let left = self.node(pos + 1 - (2 << height))?;
+            current = CryptoUtils::hash_multiple(&[&left, &current]);
+            pos += 1;
+            height += 1;
+            self.store.put_node(pos, current)?;
+        }
+
+        let index = self.leaf_count;
+        self.leaf_count += 1;
+        Ok(index)
+    }
+
+    /// Hashes of the mountain peaks, left to right
+    pub fn peaks(&self) -> KalaResult<Vec<Hash>> {
+        peak_positions(self.leaf_count)
+            .into_iter()
+            .map(|pos| self.node(pos))
+            .collect()
+    }
+
+    /// Root committing to every leaf and the leaf count
+    pub fn root(&self) -> KalaResult<Hash> {
+        Ok(bag_peaks(self.leaf_count, &self.peaks()?))
+    }
+
+    /// Generate an inclusion proof for the leaf at `leaf_index`
+    pub fn proof(&self, leaf_index: u64) -> KalaResult<MmrProof> {
+        if leaf_index >= self.leaf_count {
+            return Err(KalaError::validation(format!(
+                "Leaf {} out of range (leaf count {})",
+                leaf_index, self.leaf_count
+            )));
+        }
+
+        let peaks = peak_positions(self.leaf_count);
+        let mut pos = leaf_position(leaf_index);
+        let mut height = 0;
+        let mut siblings = Vec::new();
+
+        while !peaks.contains(&pos) {
+            let (sibling, parent) = sibling_and_parent(pos, height);
+            siblings.push(self.node(sibling)?);
+            pos = parent;
+            height += 1;
+        }
+
+        let other_peaks = peaks
+            .into_iter()
+            .filter(|&peak| peak != pos)
+            .map(|peak| self.node(peak))
+            .collect::<KalaResult<Vec<_>>>()?;
+
+        Ok(MmrProof {
+            leaf_index,
+            leaf_count: self.leaf_count,
+            siblings,
+            peaks: other_peaks,
+        })
+    }
+
+    fn node(&self, pos: u64) -> KalaResult<Hash> {
+        self.store
+            .get_node(pos)?
+            .ok_or_else(|| KalaError::internal(format!("Missing MMR node {}", pos)))
+    }
+}
+
+/// Inclusion proof for a single leaf of a Merkle Mountain Range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MmrProof {
+    /// Index of the proven leaf
+    pub leaf_index: u64,
+    /// Leaf count of the accumulator the proof was generated against
+    pub leaf_count: u64,
+    /// Sibling hashes from the leaf up to its mountain peak
+    pub siblings: Vec<Hash>,
+    /// Hashes of the other peaks, left to right
+    pub peaks: Vec<Hash>,
+}
+
+impl MmrProof {
+    /// Verify that `leaf` is included under `root`
+    pub fn verify(&self, leaf: &Hash, root: &Hash) -> bool {
+        if self.leaf_index >= self.leaf_count {
+            return false;
+        }
+
+        let peak_positions = peak_positions(self.leaf_count);
+        if self.peaks.len() + 1 != peak_positions.len() {
+            return false;
+        }
+
+        let mut pos = leaf_position(self.leaf_index);
+        let mut height = 0;
+        let mut current = *leaf;
+        let mut siblings = self.siblings.iter();
+
+        while !peak_positions.contains(&pos) {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            let (sibling_pos, parent) = sibling_and_parent(pos, height);
+            current = if sibling_pos < pos {
+                CryptoUtils::hash_multiple(&[sibling, &current])
+            } else {
+                CryptoUtils::hash_multiple(&[&current, sibling])
+            };
+            pos = parent;
+            height += 1;
+        }
+
+        if siblings.next().is_some() {
+            return false;
+        }
+
+        let Some(peak_index) = peak_positions.iter().position(|&peak| peak == pos) else {
+            return false;
+        };
+        let mut peaks = self.peaks.clone();
+        peaks.insert(peak_index, current);
+
+        bag_peaks(self.leaf_count, &peaks) == *root
+    }
+}
+
+/// Number of nodes in an MMR holding `leaf_count` leaves
+pub fn mmr_size(leaf_count: u64) -> u64 {
+    2 * leaf_count - leaf_count.count_ones() as u64
+}
+
+/// Node position of the leaf at `leaf_index`
+pub fn leaf_position(leaf_index: u64) -> u64 {
+    mmr_size(leaf_index)
+}
+
+/// Positions of the mountain peaks, left to right
+///
+/// Each set bit of the leaf count corresponds to one perfect mountain.
+pub fn peak_positions(leaf_count: u64) -> Vec<u64> {
+    let mut peaks = Vec::new();
+    let mut offset = 0;
+
+    for height in (0..64).rev() {
+        if leaf_count & (1 << height) != 0 {
+            let mountain_size = (2u64 << height) - 1;
+            peaks.push(offset + mountain_size - 1);
+            offset += mountain_size;
+        }
+    }
+
+    peaks
+}
+
+/// Combine peaks right to left, binding the leaf count into the root
+pub fn bag_peaks(leaf_count: u64, peaks: &[Hash]) -> Hash {
+    let Some((last, rest)) = peaks.split_last() else {
+        return HashExt::zero();
+    };
+
+    let bagged = rest
+        .iter()
+        .rev()
+        .fold(*last, |acc, peak| CryptoUtils::hash_multiple(&[peak, &acc]));
+
+    CryptoUtils::hash_multiple(&[&leaf_count.to_le_bytes(), &bagged])
+}
+
+/// Height of the node at `pos` (leaves have height 0)
+fn pos_height(pos: u64) -> u32 {
+    let mut pos = pos + 1;
+    // Jump left across whole mountains until pos is a run of 1 bits
+    while pos.count_ones() != 64 - pos.leading_zeros() {
+        let msb = 1u64 << (63 - pos.leading_zeros());
+        pos -= msb - 1;
+    }
+    63 - pos.leading_zeros()
+}
+
+/// Sibling and parent positions of a non-peak node at `pos`
+fn sibling_and_parent(pos: u64, height: u32) -> (u64, u64) {
+    let offset = (2u64 << height) - 1;
+    if pos_height(pos + 1) > height {
+        // Right child: the parent follows immediately
+        (pos - offset, pos + 1)
+    } else {
+        (pos + offset, pos + offset + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(i: u64) -> Hash {
+        CryptoUtils::hash(&i.to_le_bytes())
+    }
+
+    #[test]
+    fn test_positions() {
+        let heights: Vec<u32> = (0..11).map(pos_height).collect();
+        assert_eq!(heights, vec![0, 0, 1, 0, 0, 1, 2, 0, 0, 1, 0]);
+
+        assert_eq!(leaf_position(0), 0);
+        assert_eq!(leaf_position(2), 3);
+        assert_eq!(leaf_position(4), 7);
+        assert_eq!(mmr_size(7), 11);
+        assert_eq!(peak_positions(7), vec![6, 9, 10]);
+        assert!(peak_positions(0).is_empty());
+    }
+
+    #[test]
+    fn test_append_matches_manual_tree() {
+        let mut mmr = MerkleMountainRange::new();
+        for i in 0..3 {
+            mmr.append(leaf(i)).unwrap();
+        }
+
+        let left = CryptoUtils::hash_multiple(&[&leaf(0), &leaf(1)]);
+        assert_eq!(mmr.peaks().unwrap(), vec![left, leaf(2)]);
+        assert_eq!(mmr.size(), 4);
+
+        let expected = bag_peaks(3, &[left, leaf(2)]);
+        assert_eq!(mmr.root().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_proofs_for_every_leaf() {
+        let mut mmr = MerkleMountainRange::new();
+        for n in 0..40u64 {
+            mmr.append(leaf(n)).unwrap();
+            let root = mmr.root().unwrap();
+
+            for i in 0..=n {
+                let proof = mmr.proof(i).unwrap();
+                assert!(proof.verify(&leaf(i), &root), "leaf {} of {}", i, n + 1);
+                assert!(!proof.verify(&leaf(i + 1000), &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_root_and_tampering() {
+        let mut mmr = MerkleMountainRange::new();
+        for i in 0..13 {
+            mmr.append(leaf(i)).unwrap();
+        }
+        let root = mmr.root().unwrap();
+        let proof = mmr.proof(5).unwrap();
+        assert!(proof.verify(&leaf(5), &root));
+
+        // Older roots don't verify against proofs for the current size
+        let mut old = MerkleMountainRange::new();
+        for i in 0..12 {
+            old.append(leaf(i)).unwrap();
+        }
+        assert!(!proof.verify(&leaf(5), &old.root().unwrap()));
+
+        let mut wrong_index = proof.clone();
+        wrong_index.leaf_index = 4;
+        assert!(!wrong_index.verify(&leaf(5), &root));
+
+        let mut extra_sibling = proof.clone();
+        extra_sibling.siblings.push(root);
+        assert!(!extra_sibling.verify(&leaf(5), &root));
+
+        let mut missing_peak = proof;
+        missing_peak.peaks.pop();
+        assert!(!missing_peak.verify(&leaf(5), &root));
+
+        assert!(mmr.proof(13).is_err());
+    }
+
+    #[test]
+    fn test_reopen_from_store() {
+        let mut mmr = MerkleMountainRange::new();
+        for i in 0..9 {
+            mmr.append(leaf(i)).unwrap();
+        }
+        let nodes = mmr.into_store();
+
+        let mut reopened = MerkleMountainRange::with_store(nodes, 9);
+        reopened.append(leaf(9)).unwrap();
+
+        let mut fresh = MerkleMountainRange::new();
+        for i in 0..10 {
+            fresh.append(leaf(i)).unwrap();
+        }
+        assert_eq!(reopened.root().unwrap(), fresh.root().unwrap());
+        assert_eq!(MerkleMountainRange::new().root().unwrap(), Hash::default());
+    }
+}
@@ -0,0 +1,115 @@
+//! Protobuf wire format of network messages
+//!
+//! The messages nodes gossip and exchange while syncing are defined in
+//! `schema/network.proto`, so witnesses written in other languages can
+//! generate their own codecs from the same schema. The Rust codecs in this
+//! module are generated from it with prost.
+//!
+//! Each crate converts its own types to and from the generated messages by
+//! implementing [`ProtoCodec`]. Decoding checks what protobuf can't express,
+//! such as the length of hashes and keys and the presence of required
+//! submessages.
+
+use prost::Message;
+
+use crate::crypto::shamir::KeyShare as SecretShare;
+use crate::error::{KalaError, KalaResult};
+
+#[allow(missing_docs, clippy::all)]
+#[path = "proto/kala.network.rs"]
+mod generated;
+
+pub use generated::*;
+
+/// A type with a protobuf wire format
+pub trait ProtoCodec: Sized {
+    /// Generated message the type is sent as
+    type Proto: Message + Default;
+
+    /// Convert to the wire message
+    fn to_proto(&self) -> Self::Proto;
+
+    /// Convert from a wire message, checking the fields protobuf doesn't
+    fn from_proto(proto: Self::Proto) -> KalaResult<Self>;
+
+    /// Encode as protobuf bytes
+    fn encode_proto(&self) -> Vec<u8> {
+        self.to_proto().encode_to_vec()
+    }
+
+    /// Decode from protobuf bytes
+    fn decode_proto(bytes: &[u8]) -> KalaResult<Self> {
+        let proto = Self::Proto::decode(bytes)
+            .map_err(|e| KalaError::serialization(format!("Invalid protobuf message: {}", e)))?;
+        Self::from_proto(proto)
+    }
+}
+
+/// Bytes of a fixed-size field, such as a hash or key
+pub fn fixed<const N: usize>(bytes: Vec<u8>, field: &str) -> KalaResult<[u8; N]> {
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| KalaError::serialization(format!("{} must be {} bytes, got {}", field, N, len)))
+}
+
+/// Bytes of a repeated fixed-size field
+pub fn fixed_list<const N: usize>(list: Vec<Vec<u8>>, field: &str) -> KalaResult<Vec<[u8; N]>> {
+    list.into_iter().map(|bytes| fixed(bytes, field)).collect()
+}
+
+/// Value of a submessage the sender must set
+pub fn required<T>(value: Option<T>, field: &str) -> KalaResult<T> {
+    value.ok_or_else(|| KalaError::serialization(format!("Missing {}", field)))
+}
+
+/// Value of a `uint32` field holding a narrower integer
+pub fn narrow<T: TryFrom<u32>>(value: u32, field: &str) -> KalaResult<T> {
+    T::try_from(value).map_err(|_| KalaError::serialization(format!("{} out of range: {}", field, value)))
+}
+
+impl ProtoCodec for SecretShare {
+    type Proto = KeyShare;
+
+    fn to_proto(&self) -> KeyShare {
+        KeyShare {
+            index: self.index.into(),
+            value: self.value.to_vec(),
+        }
+    }
+
+    fn from_proto(proto: KeyShare) -> KalaResult<Self> {
+        Ok(Self {
+            index: narrow(proto.index, "key share index")?,
+            value: fixed(proto.value, "key share value")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_share_round_trip() {
+        let share = SecretShare {
+            index: 3,
+            value: [7u8; 32],
+        };
+        let encoded = share.encode_proto();
+        // Field 1 varint 3, then field 2 length-delimited 32 bytes
+        assert_eq!(&encoded[..4], &[0x08, 3, 0x12, 32]);
+        assert_eq!(SecretShare::decode_proto(&encoded).unwrap(), share);
+
+        let short = KeyShare {
+            index: 3,
+            value: vec![7u8; 31],
+        };
+        assert!(SecretShare::decode_proto(&short.encode_to_vec()).is_err());
+        let wide = KeyShare {
+            index: 256,
+            value: vec![7u8; 32],
+        };
+        assert!(SecretShare::decode_proto(&wide.encode_to_vec()).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+// This file is @generated by prost-build.
+/// AES-256-GCM ciphertext of a transaction
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SealedTransaction {
+    /// 12-byte nonce
+    #[prost(bytes = "vec", tag = "1")]
+    pub nonce: ::prost::alloc::vec::Vec<u8>,
+    /// 16-byte authentication tag
+    #[prost(bytes = "vec", tag = "2")]
+    pub tag: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub ciphertext: ::prost::alloc::vec::Vec<u8>,
+}
+/// RSW puzzle anyone can solve with enough sequential work
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PuzzleLock {
+    #[prost(bytes = "vec", tag = "1")]
+    pub puzzle_value: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub a: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub n: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "4")]
+    pub hardness: u32,
+}
+/// One witness's share of an envelope key, encrypted to that witness
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EncryptedShare {
+    #[prost(bytes = "vec", tag = "1")]
+    pub witness: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub index: u32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub commitment: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub ciphertext: ::prost::alloc::vec::Vec<u8>,
+}
+/// Envelope key shared among the witnesses
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ThresholdLock {
+    #[prost(uint32, tag = "1")]
+    pub threshold: u32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub ephemeral_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag = "3")]
+    pub shares: ::prost::alloc::vec::Vec<EncryptedShare>,
+}
+/// A timelock-encrypted transaction
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Envelope {
+    #[prost(message, optional, tag = "1")]
+    pub sealed: ::core::option::Option<SealedTransaction>,
+    #[prost(uint64, tag = "4")]
+    pub submission_iteration: u64,
+    #[prost(uint64, tag = "5")]
+    pub target_tick: u64,
+    /// How the AES key is withheld until the ordering is committed
+    #[prost(oneof = "envelope::Lock", tags = "2, 3")]
+    pub lock: ::core::option::Option<envelope::Lock>,
+}
+/// Nested message and enum types in `Envelope`.
+pub mod envelope {
+    /// How the AES key is withheld until the ordering is committed
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Lock {
+        #[prost(message, tag = "2")]
+        Puzzle(super::PuzzleLock),
+        #[prost(message, tag = "3")]
+        Threshold(super::ThresholdLock),
+    }
+}
+/// When a witness first saw an envelope
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WitnessObservation {
+    #[prost(bytes = "vec", tag = "1")]
+    pub witness: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub tick_number: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub envelope_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "4")]
+    pub observed_iteration: u64,
+    /// Hash of the witness's VDF form a tick before the observation
+    #[prost(bytes = "vec", tag = "5")]
+    pub vdf_proof: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Binary quadratic form of the VDF, coefficients as integer strings, or
+/// packed into `encoded` in the compact byte encoding of kala-vdf's form_bytes
+/// with the strings left empty
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VdfForm {
+    #[prost(string, tag = "1")]
+    pub a: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub b: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub c: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub encoded: ::prost::alloc::vec::Vec<u8>,
+}
+/// A finalized tick
+///
+/// Compact certificates leave the form's c empty, for the receiver to
+/// derive from a, b and the discriminant.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TickCertificate {
+    #[prost(uint64, tag = "1")]
+    pub tick_number: u64,
+    #[prost(enumeration = "TickType", tag = "2")]
+    pub tick_type: i32,
+    #[prost(uint64, tag = "3")]
+    pub vdf_iteration: u64,
+    #[prost(message, optional, tag = "4")]
+    pub vdf_form: ::core::option::Option<VdfForm>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub hash_chain_value: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub tick_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "7")]
+    pub transaction_count: u32,
+    #[prost(bytes = "vec", tag = "8")]
+    pub transaction_merkle_root: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "9")]
+    pub timestamp: u64,
+    #[prost(bytes = "vec", tag = "10")]
+    pub previous_tick_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "11")]
+    pub usage: ::core::option::Option<ModuleUsage>,
+}
+/// Resources used, by module
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ModuleUsage {
+    #[prost(map = "string, uint64", tag = "1")]
+    pub native_transactions: ::std::collections::HashMap<::prost::alloc::string::String, u64>,
+    #[prost(uint64, tag = "2")]
+    pub contract_gas: u64,
+}
+/// The fields identifying a tick and linking it to the one before it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TickHeader {
+    #[prost(uint64, tag = "1")]
+    pub tick_number: u64,
+    #[prost(uint64, tag = "2")]
+    pub vdf_iteration: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub tick_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub previous_tick_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "5")]
+    pub transaction_count: u32,
+}
+/// A witness signature on a tick hash
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TickVote {
+    #[prost(uint64, tag = "1")]
+    pub tick_number: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub tick_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub witness: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub metadata: ::core::option::Option<VoteMetadata>,
+    #[prost(message, optional, tag = "6")]
+    pub time: ::core::option::Option<VoteTime>,
+}
+/// A signed blob a witness attaches to its tick vote
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VoteMetadata {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A signed clock reading a witness attaches to its tick vote
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VoteTime {
+    #[prost(uint64, tag = "1")]
+    pub time_ms: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A witness's Ed25519 signature
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WitnessSignature {
+    #[prost(bytes = "vec", tag = "1")]
+    pub witness: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A witness signature on an epoch summary
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochSignature {
+    #[prost(uint64, tag = "1")]
+    pub epoch_number: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub summary_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub signature: ::core::option::Option<WitnessSignature>,
+}
+/// One share of a secret
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeyShare {
+    #[prost(uint32, tag = "1")]
+    pub index: u32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: ::prost::alloc::vec::Vec<u8>,
+}
+/// A witness's key share of a threshold-locked envelope
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareReveal {
+    #[prost(bytes = "vec", tag = "1")]
+    pub witness: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub tick_number: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub envelope_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub share: ::core::option::Option<KeyShare>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A message published over gossip
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GossipMessage {
+    #[prost(oneof = "gossip_message::Payload", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    pub payload: ::core::option::Option<gossip_message::Payload>,
+}
+/// Nested message and enum types in `GossipMessage`.
+pub mod gossip_message {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag = "1")]
+        Envelope(super::Envelope),
+        #[prost(message, tag = "2")]
+        Observation(super::WitnessObservation),
+        #[prost(message, tag = "3")]
+        TickCertificate(super::TickCertificate),
+        #[prost(message, tag = "4")]
+        TickVote(super::TickVote),
+        #[prost(message, tag = "5")]
+        EpochSignature(super::EpochSignature),
+        #[prost(message, tag = "6")]
+        ShareReveal(super::ShareReveal),
+        #[prost(message, tag = "7")]
+        CompactTickCertificate(super::TickCertificate),
+        #[prost(message, tag = "8")]
+        TickHeader(super::TickHeader),
+    }
+}
+/// Aggregate statistics over the ticks of an epoch
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochStats {
+    #[prost(uint64, tag = "1")]
+    pub tick_count: u64,
+    #[prost(uint64, tag = "2")]
+    pub full_ticks: u64,
+    #[prost(uint64, tag = "3")]
+    pub empty_ticks: u64,
+    #[prost(uint64, tag = "4")]
+    pub checkpoint_ticks: u64,
+    #[prost(uint64, tag = "5")]
+    pub transaction_count: u64,
+    #[prost(uint64, tag = "6")]
+    pub start_iteration: u64,
+    #[prost(uint64, tag = "7")]
+    pub end_iteration: u64,
+    #[prost(uint64, tag = "8")]
+    pub start_timestamp: u64,
+    #[prost(uint64, tag = "9")]
+    pub end_timestamp: u64,
+    #[prost(message, optional, tag = "10")]
+    pub usage: ::core::option::Option<ModuleUsage>,
+    #[prost(uint64, tag = "11")]
+    pub oracle_updates: u64,
+}
+/// Summary a sealed epoch commits to
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochSummary {
+    #[prost(uint64, tag = "1")]
+    pub epoch_number: u64,
+    #[prost(uint64, tag = "2")]
+    pub start_tick: u64,
+    #[prost(uint64, tag = "3")]
+    pub end_tick: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub end_tick_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub mmr_root: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub state_root: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "7")]
+    pub witnesses: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag = "8")]
+    pub stats: ::core::option::Option<EpochStats>,
+    #[prost(bytes = "vec", tag = "9")]
+    pub previous_epoch_hash: ::prost::alloc::vec::Vec<u8>,
+}
+/// An epoch summary with the witness signatures collected for it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedEpochSummary {
+    #[prost(message, optional, tag = "1")]
+    pub summary: ::core::option::Option<EpochSummary>,
+    #[prost(message, repeated, tag = "2")]
+    pub signatures: ::prost::alloc::vec::Vec<WitnessSignature>,
+}
+/// The peer's current head
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StatusRequest {}
+/// Consecutive tick certificates
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TicksRequest {
+    #[prost(uint64, tag = "1")]
+    pub start: u64,
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+    /// Whether to send compact certificates
+    #[prost(bool, tag = "3")]
+    pub compact: bool,
+}
+/// The peer's chain state at its latest sealed epoch
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SnapshotRequest {}
+/// The peer's chain state at its latest tick
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StateRequest {}
+/// Consecutive signed epoch summaries
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct EpochsRequest {
+    #[prost(uint64, tag = "1")]
+    pub start: u64,
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+/// Tick accumulator peaks at a given size
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct MmrPeaksRequest {
+    #[prost(uint64, tag = "1")]
+    pub leaf_count: u64,
+}
+/// A request over the sync protocol
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SyncRequest {
+    #[prost(oneof = "sync_request::Request", tags = "1, 2, 3, 4, 5, 6")]
+    pub request: ::core::option::Option<sync_request::Request>,
+}
+/// Nested message and enum types in `SyncRequest`.
+pub mod sync_request {
+    #[derive(Clone, Copy, PartialEq, ::prost::Oneof)]
+    pub enum Request {
+        #[prost(message, tag = "1")]
+        Status(super::StatusRequest),
+        #[prost(message, tag = "2")]
+        Ticks(super::TicksRequest),
+        #[prost(message, tag = "3")]
+        Snapshot(super::SnapshotRequest),
+        #[prost(message, tag = "4")]
+        State(super::StateRequest),
+        #[prost(message, tag = "5")]
+        Epochs(super::EpochsRequest),
+        #[prost(message, tag = "6")]
+        MmrPeaks(super::MmrPeaksRequest),
+    }
+}
+/// The peer's head
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StatusResponse {
+    #[prost(uint64, tag = "1")]
+    pub current_tick: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub last_tick_hash: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TickCertificates {
+    #[prost(message, repeated, tag = "1")]
+    pub ticks: ::prost::alloc::vec::Vec<TickCertificate>,
+}
+/// Chain state, bincode-encoded
+///
+/// Accounts, puzzles and the VDF checkpoint have no schema of their own
+/// yet, so the state is carried in bincode's standard configuration.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainState {
+    #[prost(bytes = "vec", tag = "1")]
+    pub encoded: ::prost::alloc::vec::Vec<u8>,
+}
+/// A chain state with the signed summary of the epoch it was sealed with
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProvenSnapshot {
+    #[prost(message, optional, tag = "1")]
+    pub state: ::core::option::Option<ChainState>,
+    #[prost(message, optional, tag = "2")]
+    pub epoch: ::core::option::Option<SignedEpochSummary>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochSummaries {
+    #[prost(message, repeated, tag = "1")]
+    pub epochs: ::prost::alloc::vec::Vec<SignedEpochSummary>,
+}
+/// Tick accumulator peaks, left to right
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MmrPeaks {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub peaks: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+/// The answer to a SyncRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncResponse {
+    #[prost(oneof = "sync_response::Response", tags = "1, 2, 3, 4, 5, 6, 7")]
+    pub response: ::core::option::Option<sync_response::Response>,
+}
+/// Nested message and enum types in `SyncResponse`.
+pub mod sync_response {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Response {
+        #[prost(message, tag = "1")]
+        Status(super::StatusResponse),
+        #[prost(message, tag = "2")]
+        Ticks(super::TickCertificates),
+        #[prost(message, tag = "3")]
+        Snapshot(super::ProvenSnapshot),
+        #[prost(message, tag = "4")]
+        State(super::ChainState),
+        #[prost(message, tag = "5")]
+        Epochs(super::EpochSummaries),
+        #[prost(message, tag = "6")]
+        MmrPeaks(super::MmrPeaks),
+        /// The request could not be served
+        #[prost(string, tag = "7")]
+        Error(::prost::alloc::string::String),
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TickType {
+    Full = 0,
+    Empty = 1,
+    Checkpoint = 2,
+}
+impl TickType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Full => "TICK_TYPE_FULL",
+            Self::Empty => "TICK_TYPE_EMPTY",
+            Self::Checkpoint => "TICK_TYPE_CHECKPOINT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TICK_TYPE_FULL" => Some(Self::Full),
+            "TICK_TYPE_EMPTY" => Some(Self::Empty),
+            "TICK_TYPE_CHECKPOINT" => Some(Self::Checkpoint),
+            _ => None,
+        }
+    }
+}
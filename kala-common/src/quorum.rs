@@ -0,0 +1,84 @@
+//! Byzantine fault thresholds
+//!
+//! With a total voting weight of n, consensus tolerates f = ⌊(n - 1) / 3⌋
+//! faulty weight, the largest f with 3f < n. A quorum is any weight of at
+//! least n - f: 2f + 1 when n = 3f + 1, and in general the smallest weight
+//! above 2/3 of n. Any two quorums then overlap in more than f, so they
+//! share an honest member, and the honest weight alone still makes a quorum.
+//!
+//! Truncating n / 3 instead overstates f whenever 3 divides n: three
+//! witnesses would tolerate one fault, leaving two quorums of two that only
+//! overlap in the faulty one.
+
+use serde::{Deserialize, Serialize};
+
+/// Fault threshold and quorum size for a total voting weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumCalculator {
+    total: u64,
+}
+
+impl QuorumCalculator {
+    /// Thresholds for votes out of `total` weight
+    pub fn new(total: u64) -> Self {
+        Self { total }
+    }
+
+    /// Total voting weight
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Largest faulty weight tolerated, ⌊(n - 1) / 3⌋
+    pub fn max_faults(&self) -> u64 {
+        self.total.saturating_sub(1) / 3
+    }
+
+    /// Smallest weight that makes a quorum, n - f
+    ///
+    /// At least 1, so an empty set never reaches quorum.
+    pub fn quorum(&self) -> u64 {
+        (self.total - self.max_faults()).max(1)
+    }
+
+    /// Whether `weight` makes a quorum
+    pub fn is_quorum(&self, weight: u64) -> bool {
+        weight >= self.quorum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_sets() {
+        let expected = [(1, 0, 1), (2, 0, 2), (3, 0, 3), (4, 1, 3), (5, 1, 4), (6, 1, 5), (7, 2, 5), (10, 3, 7)];
+        for (total, faults, quorum) in expected {
+            let calculator = QuorumCalculator::new(total);
+            assert_eq!((calculator.max_faults(), calculator.quorum()), (faults, quorum), "n = {}", total);
+        }
+        assert!(!QuorumCalculator::new(0).is_quorum(0));
+    }
+
+    #[test]
+    fn test_thresholds_exhaustive() {
+        for total in 1..=300u64 {
+            let calculator = QuorumCalculator::new(total);
+            let (f, q) = (calculator.max_faults(), calculator.quorum());
+
+            // f is the largest weight strictly below a third
+            assert!(3 * f < total && 3 * (f + 1) >= total, "n = {}", total);
+            // Two quorums overlap in more than f, and honest weight alone is a quorum
+            assert!(2 * q > total + f, "n = {}", total);
+            assert!(total - f >= q, "n = {}", total);
+            for weight in 0..=total {
+                assert_eq!(calculator.is_quorum(weight), 3 * weight > 2 * total, "n = {}, w = {}", total, weight);
+            }
+        }
+
+        let calculator = QuorumCalculator::new(u64::MAX);
+        assert!(calculator.is_quorum(u64::MAX));
+        assert!(!calculator.is_quorum(calculator.quorum() - 1));
+    }
+}
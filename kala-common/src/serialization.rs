@@ -96,6 +96,61 @@ pub trait KalaSerialize: Serialize + DeserializeOwned {
     }
 }
 
+/// Encode a record with bincode itself rather than the JSON fallback
+///
+/// For records JSON can't encode, such as maps keyed by account hash, or
+/// would bloat, such as VDF forms written out as hex.
+pub fn encode_bincode<T: bincode::Encode>(value: &T) -> Result<Vec<u8>, SerializationError> {
+    bincode::encode_to_vec(value, bincode::config::standard())
+        .map_err(|e| SerializationError::Bincode(e.to_string()))
+}
+
+/// Decode a record written by [`encode_bincode`]
+pub fn decode_bincode<T: bincode::Decode<()>>(bytes: &[u8]) -> Result<T, SerializationError> {
+    bincode::decode_from_slice(bytes, bincode::config::standard())
+        .map(|(value, _)| value)
+        .map_err(|e| SerializationError::Bincode(e.to_string()))
+}
+
+/// First byte of a versioned record, which neither JSON nor a bincode
+/// varint starts with
+pub const VERSIONED_MARKER: u8 = 0xff;
+
+/// Storage format of versioned records, after [`VERSIONED_MARKER`]
+pub const STORAGE_FORMAT_VERSION: u8 = 1;
+
+/// Encode a record as [`VERSIONED_MARKER`], [`STORAGE_FORMAT_VERSION`] and
+/// its bincode encoding
+pub fn encode_versioned<T: bincode::Encode>(value: &T) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = vec![VERSIONED_MARKER, STORAGE_FORMAT_VERSION];
+    bytes.extend(encode_bincode(value)?);
+    Ok(bytes)
+}
+
+/// Whether `bytes` is a record written by [`encode_versioned`]
+pub fn is_versioned(bytes: &[u8]) -> bool {
+    bytes.first() == Some(&VERSIONED_MARKER)
+}
+
+/// Decode a record written by [`encode_versioned`], or by a node that
+/// stored it unversioned
+///
+/// Unversioned records were written as JSON, and later as bare bincode.
+/// They are rewritten in the current format the next time they're saved,
+/// or by the state database's migration when the node starts.
+pub fn decode_versioned<T: bincode::Decode<()> + DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<T, SerializationError> {
+    match bytes {
+        [VERSIONED_MARKER, STORAGE_FORMAT_VERSION, encoded @ ..] => decode_bincode(encoded),
+        [VERSIONED_MARKER, version, ..] => Err(SerializationError::UnsupportedEncoding(format!(
+            "Storage format version {} is newer than {}",
+            version, STORAGE_FORMAT_VERSION
+        ))),
+        _ => serde_json::from_slice(bytes).or_else(|_| decode_bincode(bytes)),
+    }
+}
+
 /// Hash computation utilities with standardized patterns
 pub struct HashCompute;
 
@@ -152,6 +207,104 @@ impl HashCompute {
     }
 }
 
+/// Deterministic binary encoding for hashing and signing
+///
+/// Serde and bincode layouts can change between versions and have no
+/// specification outside Rust, so anything hashed into consensus is
+/// encoded with [`CanonicalEncoder`] instead. The rules are simple enough
+/// to reimplement in any language:
+///
+/// - Integers are fixed-width little-endian
+/// - Fixed-size byte arrays are written as is
+/// - Variable-length bytes and UTF-8 strings are prefixed with their
+///   length as a u32
+/// - Enums are a u8 discriminant assigned by the type, never by
+///   declaration order
+/// - Fields follow in the order the type documents
+///
+/// Every encoding starts with a length-prefixed domain tag naming the type
+/// and its version, so encodings of different types never collide.
+pub trait CanonicalEncode {
+    /// Domain tag the encoding starts with, such as `"kala-tick-v1"`
+    const DOMAIN: &'static str;
+
+    /// Append the fields in canonical order
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder);
+
+    /// Canonical encoding, domain tag included
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = CanonicalEncoder::new(Self::DOMAIN);
+        self.encode_fields(&mut encoder);
+        encoder.finish()
+    }
+
+    /// SHA-256 of the canonical encoding
+    fn canonical_hash(&self) -> [u8; 32] {
+        HashCompute::hash_bytes(&self.canonical_bytes())
+    }
+}
+
+/// Writer for the [`CanonicalEncode`] format
+#[derive(Debug, Clone)]
+pub struct CanonicalEncoder {
+    buffer: Vec<u8>,
+}
+
+impl CanonicalEncoder {
+    /// Start an encoding with its domain tag
+    pub fn new(domain: &str) -> Self {
+        let mut encoder = Self { buffer: Vec::new() };
+        encoder.str(domain);
+        encoder
+    }
+
+    /// A single byte
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.buffer.push(value);
+        self
+    }
+
+    /// A little-endian u32
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// A little-endian u64
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// A fixed-size array, such as a hash or key, without a length
+    pub fn fixed<const N: usize>(&mut self, value: &[u8; N]) -> &mut Self {
+        self.buffer.extend_from_slice(value);
+        self
+    }
+
+    /// Length-prefixed bytes
+    ///
+    /// # Panics
+    ///
+    /// If `value` is 4 GiB or longer, which no consensus field can be.
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        let length = u32::try_from(value.len()).expect("canonical fields are under 4 GiB");
+        self.u32(length);
+        self.buffer.extend_from_slice(value);
+        self
+    }
+
+    /// Length-prefixed UTF-8
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    /// The encoding so far
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
 /// Network message wrapper with standardized headers
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct NetworkMessage {
@@ -369,6 +522,50 @@ mod tests {
         assert_eq!(hash1, hash2); // Same data should produce same hash
     }
     
+    struct Golden {
+        number: u64,
+        kind: u8,
+        hash: [u8; 4],
+        name: String,
+    }
+
+    impl CanonicalEncode for Golden {
+        const DOMAIN: &'static str = "kala-golden-v1";
+
+        fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+            encoder
+                .u64(self.number)
+                .u8(self.kind)
+                .fixed(&self.hash)
+                .str(&self.name)
+                .u32(7);
+        }
+    }
+
+    #[test]
+    fn test_canonical_encoding_golden_vector() {
+        let golden = Golden {
+            number: 0x0102030405060708,
+            kind: 2,
+            hash: [0xaa, 0xbb, 0xcc, 0xdd],
+            name: "tick".to_string(),
+        };
+
+        // Other implementations must produce exactly these bytes
+        assert_eq!(
+            hex::encode(golden.canonical_bytes()),
+            concat!(
+                "0e000000", "6b616c612d676f6c64656e2d7631", // domain "kala-golden-v1"
+                "0807060504030201",                         // number
+                "02",                                       // kind
+                "aabbccdd",                                 // hash
+                "04000000", "7469636b",                     // name "tick"
+                "07000000",                                 // u32 7
+            )
+        );
+        assert_eq!(golden.canonical_hash(), HashCompute::hash_bytes(&golden.canonical_bytes()));
+    }
+
     #[test]
     fn test_merkle_root() {
         let items = vec![
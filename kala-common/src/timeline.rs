@@ -0,0 +1,168 @@
+//! Mapping VDF iterations to wall-clock time
+//!
+//! The VDF is the chain's clock, but it counts iterations, not seconds.
+//! A [`TimelineClock`] converts between the two from an anchor, an
+//! iteration known to have run at a given UTC time such as genesis, and
+//! the rate iterations are computed at. The conversion is a pure function
+//! of those three values, so clocks built from the same anchor and rate
+//! agree exactly; how close the estimate is to real time depends on how
+//! well the rate matches the VDF's actual pace.
+//!
+//! Witnesses periodically sign anchors of their own, and each one corrects
+//! the clock with [`TimelineClock::correct`]: the clock moves to the newer
+//! anchor and takes the pace the chain kept between the two as its rate, so
+//! estimates stop drifting with the hardware the rate was measured on.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::consensus::{DEFAULT_ITERATIONS_PER_TICK, DEFAULT_TICK_DURATION_MS};
+use crate::types::{IterationNumber, Timestamp};
+
+/// Converts between iterations and Unix times in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimelineClock {
+    /// Iteration of the anchor
+    pub anchor_iteration: IterationNumber,
+    /// Unix time of the anchor, in milliseconds
+    pub anchor_time_ms: Timestamp,
+    /// Iterations computed per second
+    pub iterations_per_second: f64,
+}
+
+impl TimelineClock {
+    /// Clock anchored at `anchor_iteration` running at `iterations_per_second`
+    ///
+    /// Rates that aren't positive and finite fall back to the
+    /// [target rate](Self::target_rate).
+    pub fn new(
+        anchor_iteration: IterationNumber,
+        anchor_time_ms: Timestamp,
+        iterations_per_second: f64,
+    ) -> Self {
+        let mut clock = Self {
+            anchor_iteration,
+            anchor_time_ms,
+            iterations_per_second: Self::target_rate(),
+        };
+        clock.set_rate(iterations_per_second);
+        clock
+    }
+
+    /// Rate of the reference hardware, [`DEFAULT_ITERATIONS_PER_TICK`] per
+    /// [`DEFAULT_TICK_DURATION_MS`]
+    pub fn target_rate() -> f64 {
+        DEFAULT_ITERATIONS_PER_TICK as f64 * 1000.0 / DEFAULT_TICK_DURATION_MS as f64
+    }
+
+    /// Replace the rate with a newer measurement, keeping the anchor
+    ///
+    /// Rates that aren't positive and finite are ignored.
+    pub fn set_rate(&mut self, iterations_per_second: f64) {
+        if iterations_per_second.is_finite() && iterations_per_second > 0.0 {
+            self.iterations_per_second = iterations_per_second;
+        }
+    }
+
+    /// Move the anchor to a later one, correcting drift
+    ///
+    /// The rate becomes the pace the chain kept from the current anchor to
+    /// the new one. Anchors at or before the current iteration are ignored,
+    /// and the rate is kept if no time passed between the two.
+    pub fn correct(&mut self, anchor_iteration: IterationNumber, anchor_time_ms: Timestamp) {
+        if anchor_iteration <= self.anchor_iteration {
+            return;
+        }
+        if anchor_time_ms > self.anchor_time_ms {
+            let iterations = (anchor_iteration - self.anchor_iteration) as f64;
+            self.set_rate(iterations * 1000.0 / (anchor_time_ms - self.anchor_time_ms) as f64);
+        }
+        self.anchor_iteration = anchor_iteration;
+        self.anchor_time_ms = anchor_time_ms;
+    }
+
+    /// Estimated Unix time in milliseconds at which `iteration` runs
+    ///
+    /// Iterations before the anchor map to earlier times, saturating at the
+    /// Unix epoch.
+    pub fn iteration_to_time(&self, iteration: IterationNumber) -> Timestamp {
+        let offset_ms = |iterations: u64| {
+            (iterations as f64 * 1000.0 / self.iterations_per_second).round() as u64
+        };
+        if iteration >= self.anchor_iteration {
+            self.anchor_time_ms.saturating_add(offset_ms(iteration - self.anchor_iteration))
+        } else {
+            self.anchor_time_ms.saturating_sub(offset_ms(self.anchor_iteration - iteration))
+        }
+    }
+
+    /// Estimated iteration running at Unix time `time_ms`
+    ///
+    /// The last iteration started by then, saturating at iteration 0 for
+    /// times before the timeline began.
+    pub fn time_to_iteration(&self, time_ms: Timestamp) -> IterationNumber {
+        let iterations = |elapsed_ms: u64| elapsed_ms as f64 * self.iterations_per_second / 1000.0;
+        if time_ms >= self.anchor_time_ms {
+            let elapsed = iterations(time_ms - self.anchor_time_ms).floor() as u64;
+            self.anchor_iteration.saturating_add(elapsed)
+        } else {
+            let remaining = iterations(self.anchor_time_ms - time_ms).ceil() as u64;
+            self.anchor_iteration.saturating_sub(remaining)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        // 100,000 iterations per second from iteration 1,000 at t = 10s
+        let clock = TimelineClock::new(1_000, 10_000, 100_000.0);
+        assert_eq!(clock.iteration_to_time(1_000), 10_000);
+        assert_eq!(clock.iteration_to_time(101_000), 11_000);
+        assert_eq!(clock.iteration_to_time(0), 9_990);
+        assert_eq!(clock.time_to_iteration(11_000), 101_000);
+        assert_eq!(clock.time_to_iteration(10_000), 1_000);
+        assert_eq!(clock.time_to_iteration(9_995), 500);
+        assert_eq!(clock.time_to_iteration(9_994), 400);
+        assert_eq!(clock.time_to_iteration(0), 0);
+        assert_eq!(TimelineClock::new(10, 5, 1.0).iteration_to_time(0), 0);
+
+        for iteration in [0, 1_000, 12_345, 1 << 40] {
+            let time = clock.iteration_to_time(iteration);
+            assert!(clock.iteration_to_time(clock.time_to_iteration(time)) <= time);
+        }
+    }
+
+    #[test]
+    fn test_rate_fallback() {
+        let mut clock = TimelineClock::new(0, 0, 0.0);
+        assert_eq!(clock.iterations_per_second, TimelineClock::target_rate());
+        // One reference tick takes the reference tick duration
+        assert_eq!(clock.iteration_to_time(DEFAULT_ITERATIONS_PER_TICK), DEFAULT_TICK_DURATION_MS);
+
+        clock.set_rate(f64::NAN);
+        assert_eq!(clock.iterations_per_second, TimelineClock::target_rate());
+        clock.set_rate(1_000.0);
+        assert_eq!(clock.iteration_to_time(1_000), 1_000);
+    }
+
+    #[test]
+    fn test_correct() {
+        // The clock assumes 1,000 iterations per second, the chain keeps 800
+        let mut clock = TimelineClock::new(0, 0, 1_000.0);
+        assert_eq!(clock.iteration_to_time(8_000), 8_000);
+        clock.correct(8_000, 10_000);
+        assert_eq!(clock.iterations_per_second, 800.0);
+        assert_eq!(clock.iteration_to_time(16_000), 20_000);
+        assert_eq!(clock.time_to_iteration(20_000), 16_000);
+
+        // Stale anchors are ignored, and one at the same time keeps the rate
+        clock.correct(4_000, 1);
+        assert_eq!((clock.anchor_iteration, clock.anchor_time_ms), (8_000, 10_000));
+        clock.correct(9_000, 10_000);
+        assert_eq!(clock.iterations_per_second, 800.0);
+        assert_eq!(clock.iteration_to_time(9_000), 10_000);
+    }
+}
@@ -69,13 +69,45 @@ pub mod consensus {
     /// Default tick duration in milliseconds (~497.7ms as measured)
     pub const DEFAULT_TICK_DURATION_MS: u64 = 497;
     
+    /// Chain ID of the development network
+    ///
+    /// Transaction signatures commit to the chain ID, so they can't be
+    /// replayed on another network.
+    pub const DEFAULT_CHAIN_ID: &str = "kala-devnet";
+
     /// VDF discriminant from the paper
     pub const DEFAULT_DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
     
+    /// Discriminants of known chains, by chain ID
+    ///
+    /// A node configured for a registered chain must run its discriminant,
+    /// so nodes of one network can't end up on different class groups.
+    pub const DISCRIMINANT_REGISTRY: &[(&str, &str)] = &[(DEFAULT_CHAIN_ID, DEFAULT_DISCRIMINANT)];
+
+    /// The discriminant registered for `chain_id`
+    pub fn registered_discriminant(chain_id: &str) -> Option<&'static str> {
+        DISCRIMINANT_REGISTRY
+            .iter()
+            .find(|(registered, _)| *registered == chain_id)
+            .map(|(_, discriminant)| *discriminant)
+    }
+
     /// Tick phases
     pub const COLLECTION_PHASE_RATIO: f64 = 1.0 / 3.0;  // k/3
     pub const CONSENSUS_PHASE_RATIO: f64 = 2.0 / 3.0;   // 2k/3
     pub const FINALIZATION_PHASE_RATIO: f64 = 1.0;      // k
+
+    /// Protocol features and the tick each activates at
+    ///
+    /// Every feature of the current protocol version is active from
+    /// genesis; upgrades add entries with a future activation tick.
+    pub const PROTOCOL_FEATURES: &[(&str, u64)] = &[
+        ("timelock_ordering", 0),
+        ("witness_finality", 0),
+        ("epoch_summaries", 0),
+        ("witness_rotation", 0),
+        ("fork_choice", 0),
+    ];
 }
 
 /// Database constants
@@ -84,6 +116,7 @@ pub mod database {
     pub const CHAIN_STATE_KEY: &[u8] = b"chain_state";
     pub const TICK_PREFIX: &str = "tick";
     pub const VDF_TICK_PREFIX: &str = "vdf_tick";
+    pub const VDF_TICK_DATA_PREFIX: &str = "vdf_data";
     pub const ACCOUNT_PREFIX: &str = "account";
     pub const TRANSACTION_PREFIX: &str = "tx";
     pub const BLOCK_INDEX_KEY: &[u8] = b"tick_index";
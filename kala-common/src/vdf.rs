@@ -0,0 +1,36 @@
+//! The interface consensus drives a VDF through
+//!
+//! VDF implementations differ in their form types and in what they keep:
+//! the eternal VDF hashes timestamped data into a chain and certifies each
+//! tick, while a bare squaring chain only tracks forms. Consensus steps an
+//! engine, reads its iteration, and takes its checkpoints and proofs, so
+//! anything implementing [`VdfEngine`] can be swapped in without touching
+//! the consensus code.
+
+/// A sequential VDF computation
+///
+/// Iterations are taken one step at a time so data can be timestamped at
+/// any of them. Engines are moved between threads by the node, so must be
+/// `Send + Sync`.
+pub trait VdfEngine: Send + Sync {
+    /// Snapshot the computation can be resumed from
+    type Checkpoint;
+
+    /// Proof that a tick's iterations were computed
+    type Proof;
+
+    /// Compute the next iteration, timestamping `data` in it if given
+    fn step(&mut self, data: Option<Vec<u8>>);
+
+    /// Iterations computed so far
+    fn iteration(&self) -> u64;
+
+    /// Proof of tick `tick_number`, if the engine has finished it
+    fn prove(&self, tick_number: u64) -> Option<Self::Proof>;
+
+    /// Check a proof made by an engine over the same discriminant
+    fn verify(&self, proof: &Self::Proof) -> bool;
+
+    /// Snapshot of the computation as it stands
+    fn checkpoint(&self) -> Self::Checkpoint;
+}
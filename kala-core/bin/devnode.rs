@@ -1,9 +1,20 @@
 // bin/devnode.rs - Kala development node
 use anyhow::Result;
-use clap::Parser;
-use kala_core::{KalaNode, NodeConfig};
+use clap::{Parser, Subcommand};
+use kala_core::genesis::Genesis;
+use kala_common::crypto::signer::{
+    Keystore, KeystoreSigner, SignatureScheme, Signer, DEFAULT_SCRYPT_LOG_N,
+};
+use kala_core::{KalaNode, NodeConfig, SignerConfig};
+use kala_state::StateDB;
 use std::sync::Arc;
 
+/// Environment variable holding the keystore password
+const KEYSTORE_PASSWORD_ENV: &str = "KALA_KEYSTORE_PASSWORD";
+
+/// Environment variable holding the admin RPC token
+const ADMIN_TOKEN_ENV: &str = "KALA_ADMIN_TOKEN";
+
 #[derive(Parser, Debug)]
 #[command(name = "kala-devnode")]
 #[command(about = "Kala development node - the eternal timeline", long_about = None)]
@@ -16,6 +27,14 @@ struct Args {
     #[arg(short, long, default_value = "8545")]
     rpc_port: u16,
 
+    /// GraphQL port (serves /graphql when set)
+    #[arg(long)]
+    graphql_port: Option<u16>,
+
+    /// REST gateway port (serves /ticks, /accounts and /transactions when set)
+    #[arg(long)]
+    rest_port: Option<u16>,
+
     /// Iterations per tick (also determines tick duration)
     #[arg(short, long, default_value = "65536")]
     iterations_per_tick: u64,
@@ -24,9 +43,136 @@ struct Args {
     #[arg(short, long)]
     fast: bool,
 
+    /// Simulate the VDF so ticks complete instantly (NOT FOR PRODUCTION)
+    #[arg(long, conflicts_with = "p2p_port")]
+    instant: bool,
+
+    /// Let every account mint the native denomination (NOT FOR PRODUCTION)
+    #[arg(long, conflicts_with = "genesis")]
+    open_mint: bool,
+
     /// Log level
     #[arg(short, long, default_value = "info")]
     log_level: String,
+
+    /// P2P listen port (enables gossip with other nodes)
+    #[arg(long)]
+    p2p_port: Option<u16>,
+
+    /// Peer multiaddr to dial, may be repeated
+    #[arg(long = "peer")]
+    peers: Vec<String>,
+
+    /// Bootstrap node multiaddr for peer discovery, may be repeated
+    #[arg(long = "bootstrap")]
+    bootstrap_nodes: Vec<String>,
+
+    /// Maximum number of connected peers
+    #[arg(long, default_value = "50")]
+    max_peers: usize,
+
+    /// Sealed genesis file to start the chain from
+    #[arg(long)]
+    genesis: Option<String>,
+
+    /// Keystore file holding the witness keys, unlocked with $KALA_KEYSTORE_PASSWORD
+    #[arg(long, conflicts_with = "remote_signer")]
+    keystore: Option<String>,
+
+    /// URL of a remote signer holding the witness keys
+    #[arg(long)]
+    remote_signer: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Back up the database to a new directory and exit
+    Backup {
+        /// Directory to write the backup to; must not exist
+        path: String,
+    },
+    /// Restore the database from a backup, verify it, and exit
+    ///
+    /// The database path must not exist yet.
+    Restore {
+        /// Directory written by `backup`
+        path: String,
+    },
+    /// Generate witness keys into a new keystore file and exit
+    ///
+    /// The keystore is encrypted with $KALA_KEYSTORE_PASSWORD.
+    NewKeystore {
+        /// Keystore file to create; must not exist
+        path: String,
+    },
+    /// Embed the canonical hash in a genesis file and exit
+    ///
+    /// Operators who seal the same genesis contents get the same hash.
+    SealGenesis {
+        /// Genesis file to seal in place
+        path: String,
+    },
+    /// Operate a running node over its admin RPC namespace and exit
+    ///
+    /// Authenticates with $KALA_ADMIN_TOKEN, which the node must have been
+    /// started with.
+    Admin {
+        /// URL of the node's JSON-RPC server
+        #[arg(long, default_value = "http://127.0.0.1:8545")]
+        url: String,
+
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AdminCommand {
+    /// List the peers the node knows
+    Peers,
+    /// Ban a peer, disconnecting it and never redialing it
+    BanPeer {
+        /// Base58 libp2p peer ID
+        peer_id: String,
+    },
+    /// List the transactions waiting in the mempool
+    Mempool,
+    /// Drop every transaction waiting in the mempool
+    FlushMempool,
+    /// Write a verified snapshot of the database on the node's machine
+    ExportSnapshot {
+        /// Directory to write the snapshot to; must not exist
+        path: String,
+    },
+    /// Move settled ticks past the retention window to the archive now
+    Prune,
+    /// Change the node's log filter, such as `debug` or `info,kala_core=trace`
+    SetLogLevel {
+        /// Level or filter directives
+        filter: String,
+    },
+}
+
+impl AdminCommand {
+    /// Admin RPC method and parameters of the command
+    fn call(&self) -> (&'static str, serde_json::Value) {
+        match self {
+            AdminCommand::Peers => ("admin_peers", serde_json::json!([])),
+            AdminCommand::BanPeer { peer_id } => ("admin_banPeer", serde_json::json!([peer_id])),
+            AdminCommand::Mempool => ("admin_mempool", serde_json::json!([])),
+            AdminCommand::FlushMempool => ("admin_flushMempool", serde_json::json!([])),
+            AdminCommand::ExportSnapshot { path } => {
+                ("admin_exportSnapshot", serde_json::json!([path]))
+            }
+            AdminCommand::Prune => ("admin_pruneNow", serde_json::json!([])),
+            AdminCommand::SetLogLevel { filter } => {
+                ("admin_setLogLevel", serde_json::json!([filter]))
+            }
+        }
+    }
 }
 
 #[tokio::main]
@@ -38,12 +184,25 @@ async fn main() -> Result<()> {
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(&args.log_level));
 
-    tracing_subscriber::fmt()
+    let subscriber = tracing_subscriber::fmt()
         .with_env_filter(filter)
         .with_target(false)
         .with_thread_ids(true)
         .with_level(true)
-        .init();
+        .with_filter_reloading();
+    // Lets operators change the filter through admin_setLogLevel
+    let log_filter = subscriber.reload_handle();
+    subscriber.init();
+
+    // Maintenance commands run against a stopped node's database
+    match &args.command {
+        Some(Command::Backup { path }) => return backup(&args.db_path, path).await,
+        Some(Command::Restore { path }) => return restore(path, &args.db_path).await,
+        Some(Command::NewKeystore { path }) => return new_keystore(path),
+        Some(Command::SealGenesis { path }) => return seal_genesis(path),
+        Some(Command::Admin { url, command }) => return admin(url, command).await,
+        None => {}
+    }
 
     // Print banner
     println!(
@@ -61,8 +220,21 @@ async fn main() -> Result<()> {
     "#
     );
 
+    let witness_signer = match (args.keystore, args.remote_signer) {
+        (Some(path), _) => Some(SignerConfig::Keystore {
+            path,
+            password_env: KEYSTORE_PASSWORD_ENV.to_string(),
+        }),
+        (None, Some(url)) => Some(SignerConfig::Remote { url }),
+        (None, None) => None,
+    };
+
+    let p2p_listen_addr = args
+        .p2p_port
+        .map(|port| format!("/ip4/0.0.0.0/tcp/{}", port));
+
     // Create config
-    let config = if args.fast {
+    let mut config = if args.fast {
         tracing::info!("Running in FAST mode - 1 second ticks");
         NodeConfig {
             db_path: args.db_path,
@@ -72,6 +244,12 @@ async fn main() -> Result<()> {
             enable_gpu: false,
             max_transactions_per_tick: 100,
             log_level: args.log_level,
+            p2p_listen_addr,
+            p2p_peers: args.peers,
+            p2p_bootstrap_nodes: args.bootstrap_nodes,
+            p2p_max_peers: args.max_peers,
+            genesis_file: args.genesis,
+            witness_signer,
             ..Default::default()
         }
     } else {
@@ -82,9 +260,23 @@ async fn main() -> Result<()> {
             timelock_hardness_factor: 0.1,
             enable_gpu: false,
             log_level: args.log_level,
+            p2p_listen_addr,
+            p2p_peers: args.peers,
+            p2p_bootstrap_nodes: args.bootstrap_nodes,
+            p2p_max_peers: args.max_peers,
+            genesis_file: args.genesis,
+            witness_signer,
             ..Default::default()
         }
     };
+    if args.instant {
+        tracing::warn!("Running in INSTANT mode - simulated VDF, NOT FOR PRODUCTION");
+        config.dev_instant = true;
+    }
+    config.dev_open_mint = args.open_mint;
+    config.rpc_admin_token = std::env::var(ADMIN_TOKEN_ENV).ok();
+    config.graphql_port = args.graphql_port;
+    config.rest_port = args.rest_port;
 
     // Validate config
     config
@@ -96,15 +288,33 @@ async fn main() -> Result<()> {
     tracing::info!("Configuration:");
     tracing::info!("  Database: {}", config.db_path);
     tracing::info!("  RPC port: {}", config.rpc_port);
+    if let Some(port) = config.graphql_port {
+        tracing::info!("  GraphQL port: {}", port);
+    }
+    if let Some(port) = config.rest_port {
+        tracing::info!("  REST port: {}", port);
+    }
     tracing::info!("  Iterations per tick: {}", config.iterations_per_tick);
+    if let Some(genesis) = &config.genesis_file {
+        tracing::info!("  Genesis: {}", genesis);
+    }
+    if let Some(addr) = &config.p2p_listen_addr {
+        tracing::info!(
+            "  P2P listen: {} ({} peers, {} bootstrap nodes, max {} connected)",
+            addr,
+            config.p2p_peers.len(),
+            config.p2p_bootstrap_nodes.len(),
+            config.p2p_max_peers
+        );
+    }
     tracing::info!(
         "  Timelock hardness: {}%",
         config.timelock_hardness_factor * 100.0
     );
     tracing::info!("");
 
-    // Create and run node
-    let node = Arc::new(KalaNode::new(config).await?);
+    // Create and run node, continuing the VDF from where it stopped
+    let node = Arc::new(KalaNode::resume(config).await?.with_log_filter(Arc::new(log_filter)));
 
     // Set up shutdown handler
     let shutdown_node = node.clone();
@@ -129,3 +339,96 @@ async fn main() -> Result<()> {
 
     node.run().await
 }
+
+/// Check the state database and write a checkpoint of it to `backup_path`
+async fn backup(db_path: &str, backup_path: &str) -> Result<()> {
+    let state_db = StateDB::open(db_path)?;
+    let state_root = state_db.verify_state().await?;
+    state_db.create_backup(backup_path).await?;
+
+    tracing::info!(
+        "Backed up {} to {} (state root {})",
+        db_path,
+        backup_path,
+        hex::encode(state_root)
+    );
+    Ok(())
+}
+
+/// Restore `backup_path` into `db_path` and verify the restored state
+async fn restore(backup_path: &str, db_path: &str) -> Result<()> {
+    let state_db = StateDB::restore_backup(backup_path, db_path).await?;
+    let state_root = state_db.verify_state().await?;
+
+    tracing::info!(
+        "Restored {} from {} (state root {})",
+        db_path,
+        backup_path,
+        hex::encode(state_root)
+    );
+    Ok(())
+}
+
+/// Call the admin RPC method of `command` on the node at `url` and print the result
+async fn admin(url: &str, command: &AdminCommand) -> Result<()> {
+    let token = std::env::var(ADMIN_TOKEN_ENV)
+        .map_err(|_| anyhow::anyhow!("Set {} to the node's admin token", ADMIN_TOKEN_ENV))?;
+    let (method, params) = command.call();
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1,
+    });
+
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(url)
+        .header("authorization", format!("Bearer {}", token))
+        .json(&request)
+        .send()
+        .await?
+        .json()
+        .await?;
+    if let Some(error) = response.get("error") {
+        let message = error["message"].as_str().unwrap_or("unknown error");
+        anyhow::bail!("{} failed: {}", method, message);
+    }
+
+    println!("{}", serde_json::to_string_pretty(&response["result"])?);
+    Ok(())
+}
+
+/// Embed the canonical hash of the genesis file at `path`
+fn seal_genesis(path: &str) -> Result<()> {
+    let contents = std::fs::read_to_string(path)?;
+    let genesis: Genesis = serde_json::from_str(&contents)?;
+    let genesis = genesis.seal()?;
+    genesis.save(path)?;
+
+    tracing::info!(
+        "Sealed {} with genesis hash {}",
+        path,
+        genesis.genesis_hash.unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// Generate witness keys and write them to a new keystore at `path`
+fn new_keystore(path: &str) -> Result<()> {
+    if std::path::Path::new(path).exists() {
+        anyhow::bail!("{} already exists", path);
+    }
+    let password = std::env::var(KEYSTORE_PASSWORD_ENV)
+        .map_err(|_| anyhow::anyhow!("Set {} to the keystore password", KEYSTORE_PASSWORD_ENV))?;
+    let keystore = Keystore::generate(&password, DEFAULT_SCRYPT_LOG_N)?;
+    keystore.save(path)?;
+
+    let signer = KeystoreSigner::open(path, &password)?;
+    tracing::info!(
+        "Wrote keystore {} (witness key {}, BLS key {})",
+        path,
+        keystore.public_key,
+        hex::encode(signer.public_key(SignatureScheme::Bls12381))
+    );
+    Ok(())
+}
@@ -0,0 +1,557 @@
+// bin/soak.rs - Kala soak test harness
+//
+// Runs the eternal timeline for days under synthetic transaction load,
+// killing the node at random points and restarting it from its database.
+// Between runs the harness opens the database and checks that the timeline
+// carried on where it stopped: every tick links to the one before it,
+// nothing committed was lost or rewritten, the VDF never ran backwards, and
+// the chain state matches the latest tick and the latest sealed epoch. Every
+// few restarts the database is also checkpointed and the checkpoint restored.
+//
+// Usage: cargo run --release -p kala-core --features soak --bin soak -- --help
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use clap::{Parser, Subcommand};
+use kala_common::crypto::signer::{InMemorySigner, SignatureScheme, Signer};
+use kala_core::{KalaNode, NodeConfig};
+use kala_rpc::{
+    AccountInfo, ChainInfo, ChainParams, SubmitTransactionRequest, SubmitTransactionResponse,
+};
+use kala_state::{EpochSummary, StateDB};
+use kala_transaction::{seal_transaction, Mint, Send, Transaction};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::process::{Child, Command as Process};
+
+/// Amount each load account mints when it runs low
+const LOAD_MINT: u64 = 1_000_000;
+
+/// Amount each load transfer moves to the next account
+const LOAD_TRANSFER: u64 = 1;
+
+/// Time between load rounds
+const LOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Parser, Debug)]
+#[command(name = "kala-soak")]
+#[command(about = "Kala soak test - kills and restarts the eternal timeline", long_about = None)]
+struct Args {
+    /// Directory holding the node database and checkpoints
+    #[arg(short, long, default_value = "./kala_soak")]
+    work_dir: String,
+
+    /// How long to run for, in hours
+    #[arg(long, default_value = "72")]
+    duration_hours: f64,
+
+    /// Average time the node runs before it is killed, in seconds
+    ///
+    /// Each run lasts between half and one and a half times this.
+    #[arg(long, default_value = "600")]
+    restart_interval_secs: u64,
+
+    /// Checkpoint the database and restore the checkpoint every this many restarts, 0 for never
+    #[arg(long, default_value = "6")]
+    checkpoint_every: u64,
+
+    /// Number of accounts sending synthetic transactions
+    #[arg(long, default_value = "8")]
+    load_accounts: u8,
+
+    /// RPC port of the node under test
+    #[arg(short, long, default_value = "18545")]
+    rpc_port: u16,
+
+    /// Iterations per tick
+    #[arg(short, long, default_value = "1024")]
+    iterations_per_tick: u64,
+
+    /// Simulate the VDF so ticks complete instantly
+    #[arg(long)]
+    instant: bool,
+
+    /// Log level
+    #[arg(short, long, default_value = "info")]
+    log_level: String,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the node under test until it is killed
+    #[command(hide = true)]
+    Node {
+        /// Database path
+        db_path: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(&args.log_level));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_level(true)
+        .init();
+
+    match &args.command {
+        Some(Command::Node { db_path }) => run_node(&args, db_path).await,
+        None => supervise(&args).await,
+    }
+}
+
+/// Run the node under test, continuing the VDF from where it stopped
+async fn run_node(args: &Args, db_path: &str) -> Result<()> {
+    let config = NodeConfig {
+        db_path: db_path.to_string(),
+        rpc_port: args.rpc_port,
+        iterations_per_tick: args.iterations_per_tick,
+        timelock_hardness_factor: 0.05,
+        enable_gpu: false,
+        log_level: args.log_level.clone(),
+        dev_instant: args.instant,
+        // Accounts running low top themselves up by minting
+        dev_open_mint: true,
+        ..Default::default()
+    };
+    config
+        .validate()
+        .map_err(|e| anyhow!("Config validation failed: {}", e))?;
+
+    let node = Arc::new(KalaNode::resume(config).await?);
+    node.run().await
+}
+
+/// Run, kill and check the node until the duration is up
+async fn supervise(args: &Args) -> Result<()> {
+    let work_dir = PathBuf::from(&args.work_dir);
+    let db_path = work_dir.join("db");
+    let checkpoint_dir = work_dir.join("checkpoints");
+    std::fs::create_dir_all(&checkpoint_dir)?;
+
+    let deadline = Instant::now() + Duration::from_secs_f64(args.duration_hours * 3600.0);
+    let mut continuity = Continuity::default();
+    let mut load = Load::new(args.rpc_port, args.load_accounts)?;
+
+    tracing::info!(
+        "Soaking {} for {} hours, restarting about every {} seconds",
+        db_path.display(),
+        args.duration_hours,
+        args.restart_interval_secs
+    );
+
+    let mut restarts = 0u64;
+    while Instant::now() < deadline {
+        let run_for = jittered(Duration::from_secs(args.restart_interval_secs))
+            .min(deadline.saturating_duration_since(Instant::now()));
+        let child = spawn_node(args, &db_path)?;
+        drive(child, run_for, &mut load).await?;
+        restarts += 1;
+
+        let state_root = continuity
+            .check(&db_path)
+            .await
+            .with_context(|| format!("Continuity broken after restart {}", restarts))?;
+        tracing::info!(
+            "Restart {}: timeline at tick {}, {} transactions applied, state root {}",
+            restarts,
+            continuity.current_tick,
+            continuity.total_transactions,
+            hex::encode(state_root)
+        );
+        tracing::info!(
+            "Load so far: {} transactions submitted, {} rejected",
+            load.submitted,
+            load.rejected
+        );
+
+        if restarts.is_multiple_of(args.checkpoint_every) {
+            checkpoint(&db_path, &checkpoint_dir, restarts, state_root).await?;
+        }
+    }
+
+    tracing::info!(
+        "Soak passed: {} restarts, {} ticks, {} transactions applied",
+        restarts,
+        continuity.current_tick,
+        continuity.total_transactions
+    );
+    Ok(())
+}
+
+/// Start the node under test as a child process
+fn spawn_node(args: &Args, db_path: &Path) -> Result<Child> {
+    let mut command = Process::new(std::env::current_exe()?);
+    command
+        .arg("--rpc-port")
+        .arg(args.rpc_port.to_string())
+        .arg("--iterations-per-tick")
+        .arg(args.iterations_per_tick.to_string())
+        .arg("--log-level")
+        .arg(&args.log_level);
+    if args.instant {
+        command.arg("--instant");
+    }
+    command.arg("node").arg(db_path).kill_on_drop(true);
+    Ok(command.spawn()?)
+}
+
+/// Put load on the node for `run_for`, then kill it
+///
+/// The node exiting on its own fails the soak.
+async fn drive(mut child: Child, run_for: Duration, load: &mut Load) -> Result<()> {
+    let kill_at = tokio::time::Instant::now() + run_for;
+    loop {
+        tokio::select! {
+            status = child.wait() => bail!("Node exited before it was killed: {}", status?),
+            _ = tokio::time::sleep_until(kill_at) => break,
+            _ = tokio::time::sleep(LOAD_INTERVAL) => {
+                // The node refuses connections while it starts up
+                if let Err(e) = load.round().await {
+                    tracing::debug!("Load round failed: {:#}", e);
+                }
+            }
+        }
+    }
+    child.kill().await?;
+    Ok(())
+}
+
+/// Check a checkpoint of the database restores to the same state
+async fn checkpoint(
+    db_path: &Path,
+    checkpoint_dir: &Path,
+    restarts: u64,
+    state_root: [u8; 32],
+) -> Result<()> {
+    let backup_path = checkpoint_dir.join(format!("restart-{}", restarts));
+    let restore_path = checkpoint_dir.join("restore-check");
+    if restore_path.exists() {
+        std::fs::remove_dir_all(&restore_path)?;
+    }
+
+    StateDB::open(&path_str(db_path)?)?
+        .create_backup(&path_str(&backup_path)?)
+        .await?;
+    let restored = StateDB::restore_backup(&path_str(&backup_path)?, &path_str(&restore_path)?)
+        .await
+        .context("Checkpoint doesn't restore")?;
+    let restored_root = restored.verify_state().await?;
+    drop(restored);
+    std::fs::remove_dir_all(&restore_path)?;
+    if restored_root != state_root {
+        bail!(
+            "Checkpoint restored to state root {}, expected {}",
+            hex::encode(restored_root),
+            hex::encode(state_root)
+        );
+    }
+
+    // Keep only the latest checkpoint
+    for entry in std::fs::read_dir(checkpoint_dir)? {
+        let path = entry?.path();
+        if path != backup_path {
+            std::fs::remove_dir_all(&path)?;
+        }
+    }
+    tracing::info!("Checkpointed to {}", backup_path.display());
+    Ok(())
+}
+
+fn path_str(path: &Path) -> Result<String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("{} is not UTF-8", path.display()))
+}
+
+/// `interval` scaled by a random factor between one half and one and a half
+fn jittered(interval: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    interval.mul_f64(0.5 + nanos as f64 / 1e9)
+}
+
+/// What the timeline looked like at the last check
+#[derive(Default)]
+struct Continuity {
+    current_tick: u64,
+    current_iteration: u64,
+    total_transactions: u64,
+    /// Latest tick checked, with its hash and VDF iteration
+    tip: Option<(u64, [u8; 32], u64)>,
+    /// Latest sealed epoch checked
+    epoch: Option<EpochSummary>,
+}
+
+impl Continuity {
+    /// Check the database continues the timeline seen at the last check
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: Database of the stopped node
+    ///
+    /// # Returns
+    ///
+    /// The state root of the chain state
+    async fn check(&mut self, db_path: &Path) -> Result<[u8; 32]> {
+        let state_db = StateDB::open(&path_str(db_path)?)?;
+        let state_root = state_db
+            .verify_state()
+            .await
+            .context("Chain state doesn't follow the latest tick")?;
+        let state = state_db.load_chain_state().await?;
+
+        if state.current_tick < self.current_tick
+            || state.current_iteration < self.current_iteration
+            || state.total_transactions < self.total_transactions
+        {
+            bail!(
+                "Timeline went back from tick {} (iteration {}, {} transactions) \
+                 to tick {} (iteration {}, {} transactions)",
+                self.current_tick,
+                self.current_iteration,
+                self.total_transactions,
+                state.current_tick,
+                state.current_iteration,
+                state.total_transactions
+            );
+        }
+
+        // The previous tip is checked again, so a rewrite of it shows up
+        let start = self
+            .tip
+            .map_or(0, |(tick_number, _, _)| tick_number)
+            .max(state_db.history_start().await?);
+        for tick_number in start..state.current_tick {
+            let tick = state_db
+                .get_tick(tick_number)
+                .await?
+                .ok_or_else(|| anyhow!("Tick {} is missing", tick_number))?;
+            if tick.tick_number != tick_number || tick.compute_hash() != tick.tick_hash {
+                bail!("Tick {} doesn't match its hash", tick_number);
+            }
+            match self.tip {
+                Some((tip, hash, _)) if tip == tick_number => {
+                    ensure!(tick.tick_hash == hash, "Tick {} was rewritten", tick_number);
+                }
+                Some((tip, hash, iteration)) => {
+                    ensure!(
+                        tip + 1 == tick_number && tick.previous_tick_hash == hash,
+                        "Tick {} doesn't link to tick {}",
+                        tick_number,
+                        tip
+                    );
+                    ensure!(
+                        tick.vdf_iteration > iteration,
+                        "VDF went back from iteration {} to {} at tick {}",
+                        iteration,
+                        tick.vdf_iteration,
+                        tick_number
+                    );
+                }
+                None => {}
+            }
+            self.tip = Some((tick_number, tick.tick_hash, tick.vdf_iteration));
+        }
+
+        // Epochs sealed since the last check extend the epoch chain
+        if let Some(latest) = state_db.latest_epoch_summary().await? {
+            let first = self
+                .epoch
+                .as_ref()
+                .map_or(latest.summary.epoch_number, |epoch| epoch.epoch_number + 1);
+            for epoch_number in first..=latest.summary.epoch_number {
+                let epoch = state_db
+                    .get_epoch_summary(epoch_number)
+                    .await?
+                    .ok_or_else(|| anyhow!("Epoch {} is missing", epoch_number))?
+                    .summary;
+                if let Some(previous) = &self.epoch {
+                    previous.check_successor(&epoch)?;
+                }
+                let end_tick = state_db.get_tick(epoch.end_tick).await?;
+                if end_tick.map(|tick| tick.tick_hash) != Some(epoch.end_tick_hash) {
+                    bail!("Epoch {} doesn't end at its stored tick", epoch_number);
+                }
+                self.epoch = Some(epoch);
+            }
+        }
+
+        self.current_tick = state.current_tick;
+        self.current_iteration = state.current_iteration;
+        self.total_transactions = state.total_transactions;
+        Ok(state_root)
+    }
+}
+
+/// Mint for an account running low, a transfer to `receiver` otherwise
+fn load_transaction(
+    sender: [u8; 32],
+    receiver: [u8; 32],
+    balance: u64,
+    nonce: u64,
+    signature: Vec<u8>,
+) -> Transaction {
+    if balance < LOAD_MINT / 2 {
+        Transaction::Mint(Mint {
+            sender,
+            amount: LOAD_MINT,
+            denom: [0; 32],
+            nonce,
+            signature,
+            gas_sponsorer: sender,
+            sponsor_signature: Vec::new(),
+            tip: 0,
+        })
+    } else {
+        Transaction::Send(Send {
+            sender,
+            receiver,
+            denom: [0; 32],
+            amount: LOAD_TRANSFER,
+            nonce,
+            signature,
+            gas_sponsorer: sender,
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: 0,
+        })
+    }
+}
+
+/// Synthetic transactions from a ring of accounts, each paying the next
+struct Load {
+    client: reqwest::Client,
+    url: String,
+    accounts: Vec<LoadAccount>,
+    next_id: u64,
+    submitted: u64,
+    rejected: u64,
+}
+
+struct LoadAccount {
+    signer: InMemorySigner,
+    address: [u8; 32],
+    /// Nonce and target tick of the latest transaction submitted
+    in_flight: Option<(u64, u64)>,
+}
+
+impl Load {
+    fn new(rpc_port: u16, accounts: u8) -> Result<Self> {
+        let accounts = (1..=accounts)
+            .map(|seed| {
+                let signer = InMemorySigner::from_seed(&[seed; 32])?;
+                let address = signer
+                    .public_key(SignatureScheme::Ed25519)
+                    .try_into()
+                    .map_err(|_| anyhow!("Account key must be 32 bytes"))?;
+                Ok(LoadAccount {
+                    signer,
+                    address,
+                    in_flight: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url: format!("http://127.0.0.1:{}", rpc_port),
+            accounts,
+            next_id: 0,
+            submitted: 0,
+            rejected: 0,
+        })
+    }
+
+    /// Submit a transaction for the current tick from every account whose
+    /// previous one has had its chance to apply
+    async fn round(&mut self) -> Result<()> {
+        let params: ChainParams = self.call("kala_getChainParams", json!([])).await?;
+        let info: ChainInfo = self.call("kala_chainInfo", json!([])).await?;
+        let k = params.iterations_per_tick;
+
+        for index in 0..self.accounts.len() {
+            let address = self.accounts[index].address;
+            let receiver = self.accounts[(index + 1) % self.accounts.len()].address;
+            let account: Option<AccountInfo> = self
+                .call("kala_getAccount", json!([{ "address": hex::encode(address) }]))
+                .await?;
+            let (balance, nonce) =
+                account.map_or((0, 0), |account| (account.balance, account.nonce));
+            if let Some((in_flight, target_tick)) = self.accounts[index].in_flight {
+                if in_flight > nonce && info.current_tick <= target_tick + 1 {
+                    continue;
+                }
+            }
+
+            let nonce = nonce + 1;
+            let message = load_transaction(address, receiver, balance, nonce, Vec::new())
+                .signing_message(&params.chain_id)?;
+            let signature = self.accounts[index]
+                .signer
+                .sign(SignatureScheme::Ed25519, &message)
+                .await?;
+            let tx = load_transaction(address, receiver, balance, nonce, signature);
+
+            // Decrypt halfway through the target tick, like the node's own transactions
+            let target_tick = info.current_tick;
+            let submission_iteration = info.current_iteration;
+            let hardness = (target_tick * k + k / 2)
+                .saturating_sub(submission_iteration)
+                .clamp(params.hardness.min_hardness.max(1) as u64, u32::MAX as u64)
+                as u32;
+            let envelope = tokio::task::spawn_blocking(move || {
+                seal_transaction(&tx, target_tick, submission_iteration, hardness)
+            })
+            .await??;
+
+            let request = json!([SubmitTransactionRequest::new(&envelope)]);
+            match self
+                .call::<SubmitTransactionResponse>("kala_submitTransaction", request)
+                .await
+            {
+                Ok(_) => {
+                    self.submitted += 1;
+                    self.accounts[index].in_flight = Some((nonce, target_tick));
+                }
+                Err(e) => {
+                    self.rejected += 1;
+                    tracing::debug!("Load transaction rejected: {:#}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Call `method` on the node's JSON-RPC server
+    async fn call<T: DeserializeOwned>(&mut self, method: &str, params: Value) -> Result<T> {
+        self.next_id += 1;
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": self.next_id,
+        });
+        let mut response: Value = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            bail!("{} failed: {}", method, error);
+        }
+        Ok(serde_json::from_value(response["result"].take())?)
+    }
+}
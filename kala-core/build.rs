@@ -1,10 +1,40 @@
 use std::env;
 use std::path::PathBuf;
+use std::process::Command;
 fn main() {
     // Get the directory where build.rs is located
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     println!("cargo:warning=CARGO_MANIFEST_DIR: {}", manifest_dir);
 
+    // Identify the build for kala_getVersion. Nothing time-dependent is
+    // recorded, so release builds stay reproducible. Builds from a source
+    // archive set KALA_GIT_COMMIT themselves.
+    let git_commit = env::var("KALA_GIT_COMMIT")
+        .ok()
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "HEAD"])
+                .current_dir(&manifest_dir)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=KALA_GIT_COMMIT={}", git_commit);
+    println!("cargo:rerun-if-env-changed=KALA_GIT_COMMIT");
+    println!("cargo:rerun-if-changed={}/../.git/HEAD", manifest_dir);
+    println!("cargo:rerun-if-changed={}/../.git/refs/heads", manifest_dir);
+    for (name, variable) in [
+        ("KALA_BUILD_PROFILE", "PROFILE"),
+        ("KALA_BUILD_TARGET", "TARGET"),
+        ("KALA_BUILD_OPT_LEVEL", "OPT_LEVEL"),
+    ] {
+        println!("cargo:rustc-env={}={}", name, env::var(variable).unwrap_or_default());
+    }
+    let rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+    println!("cargo:rustc-env=KALA_BUILD_RUSTFLAGS={}", rustflags.replace('\x1f', " "));
+
     // Navigate to the tick/src directory from kala-vdf
     let src_dir = PathBuf::from(&manifest_dir)
         .parent()
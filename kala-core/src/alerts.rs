@@ -0,0 +1,391 @@
+//! Webhook alerts for consensus anomalies
+//!
+//! [`AlertSink`] is a [`NodeObserver`] that turns worrying node events
+//! into [`Alert`]s and posts them to a generic JSON webhook, a Slack
+//! incoming webhook, or both:
+//!
+//! - the watchdog saw no new tick for `watchdog_timeout_secs`
+//! - producing a tick failed
+//! - re-sealing an epoch gave a different state root than the stored summary
+//! - the witness weight signing finalized ticks dropped sharply, or ticks
+//!   stopped being finalized altogether
+//! - a witness signed two hashes for one tick
+//!
+//! Alerts of the same kind are rate-limited by `cooldown_secs`. Delivery
+//! runs in a background task so the tick loop never waits on a webhook,
+//! except for failed ticks, which are delivered before the node stops.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use tokio::sync::{mpsc, Mutex};
+use tracing::{debug, warn};
+
+use crate::config::AlertConfig;
+use crate::observer::NodeObserver;
+use kala_state::{DoubleSignEvidence, TickCertificate};
+
+/// Alerts waiting for delivery before new ones are dropped
+const ALERT_QUEUE_SIZE: usize = 64;
+
+/// Time allowed for one webhook request
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What an alert is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// No tick was produced within the watchdog timeout
+    Stall,
+    /// Producing a tick failed and the node stopped
+    TickFailed,
+    /// A re-sealed epoch disagrees with its stored summary
+    StateRootMismatch,
+    /// Fewer witnesses are signing ticks
+    SignatureDrop,
+    /// A witness voted for two hashes at one tick
+    DoubleSign,
+}
+
+impl fmt::Display for AlertKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Stall => "watchdog stall",
+            Self::TickFailed => "tick failed",
+            Self::StateRootMismatch => "state root mismatch",
+            Self::SignatureDrop => "witness signature drop",
+            Self::DoubleSign => "witness double-sign",
+        })
+    }
+}
+
+/// An alert as posted to the generic webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    /// What the alert is about
+    pub kind: AlertKind,
+    /// Tick the alert refers to
+    pub tick_number: u64,
+    /// Human-readable description
+    pub message: String,
+    /// Unix time the alert was raised, in seconds
+    pub timestamp: u64,
+}
+
+impl Alert {
+    fn new(kind: AlertKind, tick_number: u64, message: String) -> Self {
+        Self {
+            kind,
+            tick_number,
+            message,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Detection state carried between events
+#[derive(Default)]
+struct AlertState {
+    /// When each kind of alert was last raised
+    last_raised: HashMap<AlertKind, Instant>,
+    /// Latest finalized tick with its signed and total weight
+    last_finalized: Option<(u64, u64, u64)>,
+    /// Whether the finality lag alert has fired since the last finalized tick
+    lagging: bool,
+}
+
+/// Node observer posting alerts to the configured webhooks
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_core::alerts::AlertSink;
+/// use kala_core::config::AlertConfig;
+/// use kala_core::{KalaNode, NodeConfig};
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let sink = AlertSink::spawn(AlertConfig {
+///     slack_webhook_url: Some("https://hooks.slack.com/services/...".to_string()),
+///     ..Default::default()
+/// })?;
+/// let node = KalaNode::new(NodeConfig::default()).await?.with_observer(sink);
+/// # Ok(())
+/// # }
+/// ```
+pub struct AlertSink {
+    config: AlertConfig,
+    client: reqwest::Client,
+    outbox: mpsc::Sender<Alert>,
+    state: Mutex<AlertState>,
+}
+
+impl AlertSink {
+    /// Create a sink and start its delivery task
+    ///
+    /// # Errors
+    ///
+    /// Fails if the HTTP client cannot be created.
+    pub fn spawn(config: AlertConfig) -> Result<Arc<Self>> {
+        let client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .map_err(|e| anyhow!("Failed to create alert client: {}", e))?;
+        let (sink, mut inbox) = Self::new(config.clone(), client.clone());
+
+        tokio::spawn(async move {
+            while let Some(alert) = inbox.recv().await {
+                deliver(&client, &config, &alert).await;
+            }
+        });
+        Ok(Arc::new(sink))
+    }
+
+    fn new(config: AlertConfig, client: reqwest::Client) -> (Self, mpsc::Receiver<Alert>) {
+        let (outbox, inbox) = mpsc::channel(ALERT_QUEUE_SIZE);
+        let sink = Self {
+            config,
+            client,
+            outbox,
+            state: Mutex::new(AlertState::default()),
+        };
+        (sink, inbox)
+    }
+
+    /// Rate-limit an alert, returning it if it should be sent
+    fn admit(&self, state: &mut AlertState, alert: Alert) -> Option<Alert> {
+        warn!("Alert ({}): {}", alert.kind, alert.message);
+
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        if let Some(last) = state.last_raised.get(&alert.kind) {
+            if last.elapsed() < cooldown {
+                debug!("Suppressing {} alert during cooldown", alert.kind);
+                return None;
+            }
+        }
+        state.last_raised.insert(alert.kind, Instant::now());
+        Some(alert)
+    }
+
+    /// Queue an alert for the delivery task
+    async fn raise(&self, alert: Alert) {
+        let admitted = self.admit(&mut *self.state.lock().await, alert);
+        if let Some(alert) = admitted {
+            if self.outbox.try_send(alert).is_err() {
+                warn!("Alert queue full, dropping alert");
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeObserver for AlertSink {
+    async fn on_tick(&self, certificate: &TickCertificate, _duration: Duration) {
+        let alert = {
+            let mut state = self.state.lock().await;
+            match state.last_finalized {
+                Some((finalized, _, _))
+                    if !state.lagging
+                        && certificate.tick_number >= finalized + self.config.finality_lag_ticks =>
+                {
+                    state.lagging = true;
+                    Some(Alert::new(
+                        AlertKind::SignatureDrop,
+                        certificate.tick_number,
+                        format!(
+                            "No tick finalized for {} ticks since tick {}",
+                            certificate.tick_number - finalized,
+                            finalized
+                        ),
+                    ))
+                }
+                _ => None,
+            }
+        };
+        if let Some(alert) = alert {
+            self.raise(alert).await;
+        }
+    }
+
+    async fn on_tick_failed(&self, tick_number: u64, error: &str) {
+        let alert = Alert::new(
+            AlertKind::TickFailed,
+            tick_number,
+            format!("Tick {} failed, node stopping: {}", tick_number, error),
+        );
+        // The node exits next, so deliver now rather than through the queue
+        let admitted = self.admit(&mut *self.state.lock().await, alert);
+        if let Some(alert) = admitted {
+            deliver(&self.client, &self.config, &alert).await;
+        }
+    }
+
+    async fn on_stall(&self, next_tick: u64, idle: Duration) {
+        self.raise(Alert::new(
+            AlertKind::Stall,
+            next_tick,
+            format!("No tick produced for {}s, waiting on tick {}", idle.as_secs(), next_tick),
+        ))
+        .await;
+    }
+
+    async fn on_state_root_mismatch(&self, epoch_number: u64, stored: [u8; 32], computed: [u8; 32]) {
+        self.raise(Alert::new(
+            AlertKind::StateRootMismatch,
+            epoch_number,
+            format!(
+                "Epoch {} state root {} does not match stored summary {}",
+                epoch_number,
+                hex::encode(computed),
+                hex::encode(stored)
+            ),
+        ))
+        .await;
+    }
+
+    async fn on_double_sign(&self, evidence: &DoubleSignEvidence) {
+        self.raise(Alert::new(
+            AlertKind::DoubleSign,
+            evidence.tick_number(),
+            format!(
+                "Witness {} voted for {} and {} at tick {}",
+                hex::encode(evidence.witness()),
+                hex::encode(evidence.first.tick_hash),
+                hex::encode(evidence.second.tick_hash),
+                evidence.tick_number()
+            ),
+        ))
+        .await;
+    }
+
+    async fn on_tick_finalized(&self, tick_number: u64, signed_weight: u64, total_weight: u64) {
+        let alert = {
+            let mut state = self.state.lock().await;
+            let previous = state.last_finalized.replace((tick_number, signed_weight, total_weight));
+            state.lagging = false;
+
+            // Compare signed fractions, scaled by each other's total weight
+            previous.and_then(|(_, previous_signed, previous_total)| {
+                let before = previous_signed as u128 * total_weight as u128;
+                let now = signed_weight as u128 * previous_total as u128;
+                let kept = 100 - self.config.signature_drop_percent.min(100) as u128;
+                (now * 100 < before * kept).then(|| {
+                    Alert::new(
+                        AlertKind::SignatureDrop,
+                        tick_number,
+                        format!(
+                            "Tick {} signed by weight {} of {}, down from {} of {}",
+                            tick_number, signed_weight, total_weight, previous_signed, previous_total
+                        ),
+                    )
+                })
+            })
+        };
+        if let Some(alert) = alert {
+            self.raise(alert).await;
+        }
+    }
+}
+
+/// Post an alert to every configured webhook
+async fn deliver(client: &reqwest::Client, config: &AlertConfig, alert: &Alert) {
+    if let Some(url) = &config.webhook_url {
+        post(client, url, alert).await;
+    }
+    if let Some(url) = &config.slack_webhook_url {
+        let message = serde_json::json!({
+            "text": format!("[kala] {} at tick {}: {}", alert.kind, alert.tick_number, alert.message),
+        });
+        post(client, url, &message).await;
+    }
+}
+
+async fn post<T: Serialize + ?Sized>(client: &reqwest::Client, url: &str, body: &T) {
+    let result = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    if let Err(e) = result {
+        warn!("Failed to deliver alert to {}: {}", url, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::TickType;
+
+    fn sink(config: AlertConfig) -> (AlertSink, mpsc::Receiver<Alert>) {
+        AlertSink::new(config, reqwest::Client::new())
+    }
+
+    fn certificate(tick_number: u64) -> TickCertificate {
+        TickCertificate {
+            tick_number,
+            tick_type: TickType::Empty,
+            vdf_iteration: 0,
+            vdf_form: ("1".into(), "0".into(), "1".into()),
+            hash_chain_value: [0u8; 32],
+            tick_hash: [0u8; 32],
+            transaction_count: 0,
+            transaction_merkle_root: [0u8; 32],
+            timestamp: 0,
+            previous_tick_hash: [0u8; 32],
+            usage: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signature_drop_and_finality_lag() {
+        let (sink, mut alerts) = sink(AlertConfig {
+            signature_drop_percent: 25,
+            finality_lag_ticks: 10,
+            cooldown_secs: 0,
+            ..Default::default()
+        });
+
+        sink.on_tick_finalized(1, 9, 10).await;
+        // A 20% drop is within tolerance
+        sink.on_tick_finalized(2, 8, 10).await;
+        assert!(alerts.try_recv().is_err());
+
+        // Half the weight stopped signing; the set grew, so compare fractions
+        sink.on_tick_finalized(3, 8, 20).await;
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.kind, AlertKind::SignatureDrop);
+        assert_eq!(alert.tick_number, 3);
+
+        sink.on_tick(&certificate(12), Duration::ZERO).await;
+        assert!(alerts.try_recv().is_err());
+        sink.on_tick(&certificate(13), Duration::ZERO).await;
+        assert_eq!(alerts.try_recv().unwrap().kind, AlertKind::SignatureDrop);
+
+        // The lag alert fires once until finality resumes
+        sink.on_tick(&certificate(14), Duration::ZERO).await;
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_per_kind() {
+        let (sink, mut alerts) = sink(AlertConfig {
+            cooldown_secs: 3600,
+            ..Default::default()
+        });
+
+        sink.on_stall(5, Duration::from_secs(90)).await;
+        sink.on_stall(6, Duration::from_secs(90)).await;
+        sink.on_state_root_mismatch(2, [1u8; 32], [2u8; 32]).await;
+
+        let kinds: Vec<_> = std::iter::from_fn(|| alerts.try_recv().ok())
+            .map(|alert| alert.kind)
+            .collect();
+        assert_eq!(kinds, vec![AlertKind::Stall, AlertKind::StateRootMismatch]);
+    }
+}
@@ -0,0 +1,129 @@
+//! Wall-clock calibration of the VDF
+//!
+//! The protocol fixes the iterations per tick, not the tick duration: a
+//! tick lasts as long as the node takes to square k times, about 497ms for
+//! k = 65536 on reference hardware. The node benchmarks its squaring rate
+//! at startup and every `vdf_calibration_interval_secs`, derives the tick
+//! duration it can expect from the rate, and warns when that falls behind
+//! the target. The latest [`VdfCalibration`] is reported by
+//! `kala_chainInfo`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use kala_common::types::consensus::{DEFAULT_ITERATIONS_PER_TICK, DEFAULT_TICK_DURATION_MS};
+use kala_rpc::VdfCalibration;
+use tracing::{info, warn};
+
+/// Iterations each benchmark runs, a few tens of milliseconds of squaring
+pub const CALIBRATION_ITERATIONS: u64 = 4096;
+
+/// Percent by which the expected tick duration may exceed the target
+/// before the node counts as too slow, to absorb benchmark noise
+pub const PACE_TOLERANCE_PERCENT: u64 = 10;
+
+/// Benchmark the VDF and compare the rate against the tick target
+///
+/// Blocks for the duration of the benchmark, so run it off the async
+/// runtime. Logs a warning when the node can't keep pace.
+///
+/// # Parameters
+/// * `discriminant` - Class group discriminant the node's VDF runs over
+/// * `iterations_per_tick` - Iterations per tick (k)
+///
+/// # Returns
+/// The measured rate and the tick duration expected from it
+pub fn calibrate(discriminant: &str, iterations_per_tick: u64) -> VdfCalibration {
+    let iterations_per_second = kala_vdf::benchmark(discriminant, CALIBRATION_ITERATIONS);
+    let measured_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let calibration = evaluate(iterations_per_second, iterations_per_tick, measured_at);
+
+    if calibration.keeps_pace {
+        info!(
+            "VDF calibrated at {:.0} iterations/sec: {}ms per tick, target {}ms",
+            calibration.iterations_per_second,
+            calibration.tick_duration_ms,
+            calibration.target_tick_duration_ms
+        );
+    } else {
+        warn!(
+            "VDF runs at {:.0} iterations/sec, too slow to keep pace: {}ms per tick, target {}ms",
+            calibration.iterations_per_second,
+            calibration.tick_duration_ms,
+            calibration.target_tick_duration_ms
+        );
+    }
+    calibration
+}
+
+/// Compare a measured rate against the tick target
+///
+/// The target scales with `iterations_per_tick` from the reference of
+/// [`DEFAULT_TICK_DURATION_MS`] at [`DEFAULT_ITERATIONS_PER_TICK`].
+///
+/// # Parameters
+/// * `iterations_per_second` - Measured VDF rate
+/// * `iterations_per_tick` - Iterations per tick (k)
+/// * `measured_at` - Unix time of the measurement, in seconds
+///
+/// # Returns
+/// The calibration for the rate
+///
+/// # Example
+/// ```
+/// use kala_core::calibration::evaluate;
+///
+/// let calibration = evaluate(131_072.0, 65536, 0);
+/// assert_eq!(calibration.tick_duration_ms, 500);
+/// assert!(calibration.keeps_pace);
+/// ```
+pub fn evaluate(
+    iterations_per_second: f64,
+    iterations_per_tick: u64,
+    measured_at: u64,
+) -> VdfCalibration {
+    let tick_duration_ms =
+        (iterations_per_tick as f64 * 1000.0 / iterations_per_second.max(f64::MIN_POSITIVE)).ceil();
+    let target_tick_duration_ms = iterations_per_tick.saturating_mul(DEFAULT_TICK_DURATION_MS)
+        / DEFAULT_ITERATIONS_PER_TICK;
+    let tolerated_ms = target_tick_duration_ms.saturating_mul(100 + PACE_TOLERANCE_PERCENT) / 100;
+
+    VdfCalibration {
+        iterations_per_second,
+        // Saturates for rates too slow to represent
+        tick_duration_ms: tick_duration_ms as u64,
+        target_tick_duration_ms,
+        keeps_pace: tick_duration_ms as u64 <= tolerated_ms,
+        measured_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_pace() {
+        // The reference rate of 7.6μs per iteration
+        let reference = evaluate(1_000_000.0 / 7.6, 65536, 42);
+        assert_eq!(reference.tick_duration_ms, 499);
+        assert_eq!(reference.target_tick_duration_ms, 497);
+        assert!(reference.keeps_pace);
+        assert_eq!(reference.measured_at, 42);
+
+        // Within the tolerance, then past it
+        assert!(evaluate(125_000.0, 65536, 0).keeps_pace);
+        let slow = evaluate(100_000.0, 65536, 0);
+        assert_eq!(slow.tick_duration_ms, 656);
+        assert!(!slow.keeps_pace);
+
+        // The target scales with the tick size
+        let small = evaluate(100_000.0, 1024, 0);
+        assert_eq!(small.target_tick_duration_ms, 7);
+        assert_eq!(small.tick_duration_ms, 11);
+        assert!(!small.keeps_pace);
+
+        assert!(!evaluate(0.0, 65536, 0).keeps_pace);
+    }
+}
@@ -10,7 +10,21 @@
 //! - Timelock puzzle settings
 //! - Performance and debugging options
 
+use crate::denom::DenomRegistry;
+use crate::network::CertificateDetail;
+use crate::sync::TrustedCheckpoint;
+use kala_common::crypto::signer::SignatureScheme;
+use kala_common::database::{
+    default_read_cache_bytes, default_slow_op_threshold_ms, CompactionStyle, CompressionConfig, FsyncPolicy,
+};
+use kala_common::types::consensus::{
+    registered_discriminant, DEFAULT_CHAIN_ID, DEFAULT_DISCRIMINANT,
+};
+use kala_rpc::DenomMetadata;
+use kala_state::Witness;
+use kala_vdf::{VdfBackend, VdfConfig};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Complete configuration for a Kala blockchain node
@@ -35,6 +49,65 @@ pub struct NodeConfig {
     /// and VDF checkpoints. Created automatically if it doesn't exist.
     pub db_path: String,
 
+    /// RocksDB compaction style of the state database
+    /// 
+    /// "universal" trades space for fewer rewrites of the append-heavy
+    /// tick history, "level" keeps the database smaller at the cost of
+    /// more write amplification.
+    /// Default: "universal"
+    #[serde(default)]
+    pub db_compaction: CompactionStyle,
+
+    /// Bytes of recently read records kept in memory
+    /// 
+    /// Repeated reads of hot records, such as the latest ticks and their
+    /// finality, are served without touching RocksDB. Writes invalidate
+    /// the records they change. Set to 0 to disable the cache.
+    /// Default: 67108864 (64 MiB)
+    #[serde(default = "default_read_cache_bytes")]
+    pub db_read_cache_bytes: usize,
+
+    /// How often tick commits are forced to disk
+    /// 
+    /// Each tick and the chain state after it are written as one batch.
+    /// A process crash never loses a committed tick; this only matters on
+    /// power failure or a kernel crash:
+    /// - "every_tick": no committed tick is lost, but every tick waits for
+    ///   the disk, which dominates tick time on slow disks
+    /// - { "every_n_ticks": N }: up to N - 1 ticks can be lost, the sync
+    ///   cost is paid once per N ticks
+    /// - "os_default": the OS flushes in the background; the fastest, but
+    ///   the last few seconds of ticks can be lost
+    /// 
+    /// Lost ticks are re-fetched from peers on restart.
+    /// Default: "os_default"
+    #[serde(default)]
+    pub db_fsync_policy: FsyncPolicy,
+
+    /// zstd compression of large stored values
+    /// 
+    /// Values of the listed columns ("accounts", "ticks", "envelopes",
+    /// "proofs", "metadata") of at least `min_value_bytes` are stored
+    /// compressed at `level`. Once a column holds enough values, a
+    /// dictionary of up to `dictionary_bytes` is trained on them at startup
+    /// and used for later writes, which shrinks proofs the most. Values
+    /// keep the form they were written in, so columns can be switched on
+    /// and off at any time.
+    /// Default: { "columns": ["ticks", "proofs"], "min_value_bytes": 256,
+    /// "level": 3, "dictionary_bytes": 65536 }
+    #[serde(default)]
+    pub db_compression: CompressionConfig,
+
+    /// Milliseconds after which a database operation is logged as slow
+    /// 
+    /// Every operation is timed per column and operation type; those
+    /// taking longer are logged with the column, the operation and what it
+    /// touched, which shows where a tick waits on storage. Set to 0 to
+    /// disable the log; timings are still collected.
+    /// Default: 100
+    #[serde(default = "default_slow_op_threshold_ms")]
+    pub db_slow_op_threshold_ms: u64,
+
     /// Port for the JSON-RPC API server
     /// 
     /// The RPC server provides external access to the blockchain,
@@ -42,6 +115,107 @@ pub struct NodeConfig {
     /// Default: 8545 (Ethereum-compatible)
     pub rpc_port: u16,
 
+    /// IP address the JSON-RPC server binds to
+    /// 
+    /// The loopback address keeps the API local to the machine. Bind to
+    /// "0.0.0.0" to let browsers and dApps elsewhere call the node,
+    /// ideally with rpc_tls_cert_path and rpc_tls_key_path set.
+    /// Default: "127.0.0.1"
+    #[serde(default = "default_rpc_bind_addr")]
+    pub rpc_bind_addr: String,
+
+    /// PEM certificate chain to serve the JSON-RPC API over HTTPS with
+    /// 
+    /// Leaf certificate first. Must be set together with
+    /// rpc_tls_key_path; leave both unset for plain HTTP.
+    /// Default: None
+    #[serde(default)]
+    pub rpc_tls_cert_path: Option<String>,
+
+    /// PEM private key of the HTTPS certificate
+    /// 
+    /// Default: None
+    #[serde(default)]
+    pub rpc_tls_key_path: Option<String>,
+
+    /// Origins browsers may call the JSON-RPC API from
+    /// 
+    /// Exact origins such as "https://app.example.com", or "*" for any.
+    /// Leave empty to only allow pages served from the node itself.
+    /// Default: empty
+    #[serde(default)]
+    pub rpc_cors_allowed_origins: Vec<String>,
+
+    /// Bearer token operators call the admin_* JSON-RPC methods with
+    /// 
+    /// The admin namespace lists and bans peers, inspects and flushes the
+    /// mempool, exports snapshots, prunes and changes the log level. It is
+    /// only served when a token is set, which must be at least
+    /// MIN_ADMIN_TOKEN_LEN characters. Serve it over HTTPS or loopback only.
+    /// Default: None
+    #[serde(default)]
+    pub rpc_admin_token: Option<String>,
+
+    /// Largest JSON-RPC request body accepted, in bytes
+    /// 
+    /// Larger requests are rejected with HTTP 413 before being parsed.
+    /// Default: 10485760 (10 MiB)
+    #[serde(default = "default_rpc_max_body_size")]
+    pub rpc_max_request_body_size: u32,
+
+    /// Largest JSON-RPC response body sent, in bytes
+    /// 
+    /// Calls whose response would be larger, such as long tick ranges,
+    /// get an error instead.
+    /// Default: 10485760 (10 MiB)
+    #[serde(default = "default_rpc_max_body_size")]
+    pub rpc_max_response_body_size: u32,
+
+    /// JSON-RPC calls each client IP may make per second
+    /// 
+    /// Every call in a batch counts. Clients over the limit get HTTP 429
+    /// until their budget refills. Set to 0 for no limit.
+    /// Default: 0
+    #[serde(default)]
+    pub rpc_requests_per_second: u32,
+
+    /// JSON-RPC calls a client IP may make at once after being idle
+    /// 
+    /// Raised to rpc_requests_per_second if lower.
+    /// Default: 0
+    #[serde(default)]
+    pub rpc_burst: u32,
+
+    /// Calls per second each client IP may make to particular methods
+    /// 
+    /// Applies on top of rpc_requests_per_second, for methods expensive
+    /// to serve. Example: { "kala_getTickRangeProof" = 2 }
+    /// Default: empty
+    #[serde(default)]
+    pub rpc_method_rate_limits: HashMap<String, u32>,
+
+    /// Port for the GraphQL endpoint
+    /// 
+    /// When set, ticks, accounts and transactions can be queried with
+    /// their relations, filters, and pagination by POSTing GraphQL to
+    /// `/graphql` on this port, bound to rpc_bind_addr. The RPC limits,
+    /// TLS and CORS settings apply, and queries are bounded in depth and
+    /// complexity.
+    /// Default: None
+    #[serde(default)]
+    pub graphql_port: Option<u16>,
+
+    /// Port for the REST gateway
+    /// 
+    /// When set, `GET /ticks/{n}`, `GET /accounts/{addr}` and
+    /// `POST /transactions` are served on this port, bound to
+    /// rpc_bind_addr, for clients that can't speak JSON-RPC. The RPC
+    /// limits, TLS and CORS settings apply, each request counting as a
+    /// call to the JSON-RPC method it stands for.
+    /// Default: None
+    #[serde(default)]
+    pub rest_port: Option<u16>,
+
     /// Number of VDF iterations per tick (k parameter from the paper)
     /// 
     /// This is the fundamental timing parameter that determines:
@@ -69,6 +243,16 @@ pub struct NodeConfig {
     /// Falls back to CPU if GPU acceleration fails.
     pub enable_gpu: bool,
 
+    /// Where the VDF's squarings run
+    /// 
+    /// "gpu" dispatches them to the CUDA library `libtick_cuda.so`, or the
+    /// one named by `TICK_CUDA_LIBRARY`, when it loads and finds a device.
+    /// Otherwise, or if the device fails, the node downgrades to "cpu"
+    /// with a warning. Both produce identical ticks.
+    /// Default: "cpu"
+    #[serde(default)]
+    pub vdf_backend: VdfBackend,
+
     /// Maximum number of transactions processed per tick
     /// 
     /// Limits the transaction throughput to prevent tick overruns.
@@ -80,9 +264,34 @@ pub struct NodeConfig {
     /// This large negative integer parameter must be identical across
     /// all nodes in the network. It determines the VDF class group
     /// and ensures network consensus. Changing this creates a new
-    /// incompatible network.
+    /// incompatible network. Chains in the
+    /// [registry](kala_common::types::consensus::DISCRIMINANT_REGISTRY)
+    /// must use their registered discriminant, and a database holding
+    /// state over another discriminant is refused.
+    /// 
+    /// Default: the discriminant from the paper, registered for `kala-devnet`
+    #[serde(default = "default_discriminant")]
     pub discriminant: String,
 
+    /// Identifier of the network
+    /// 
+    /// Transactions are signed over the chain ID, so a transaction signed
+    /// for one network is rejected by every other. Must be identical
+    /// across all nodes in the network.
+    #[serde(default = "default_chain_id")]
+    pub chain_id: String,
+
+    /// Path to a sealed genesis file
+    /// 
+    /// The file's embedded hash is checked against its contents on every
+    /// startup, and against the hash recorded when the database was
+    /// created, so nodes can't silently start from different genesis
+    /// states. Its chain ID, tick size, and discriminant must match this
+    /// config, and its witnesses replace `witnesses` when it lists any.
+    /// Leave unset to start from an empty state.
+    #[serde(default)]
+    pub genesis_file: Option<String>,
+
     /// Logging verbosity level
     /// 
     /// Controls the amount of logging output. Levels:
@@ -93,17 +302,548 @@ pub struct NodeConfig {
     /// - "trace": Maximum verbosity
     pub log_level: String,
 
-    /// Enable Prometheus metrics collection
+    /// Enable the metrics endpoint
     /// 
-    /// When enabled, exposes performance metrics on the metrics port
-    /// for monitoring and observability. Useful for production deployments.
+    /// When enabled, serves a built-in HTML status page with the chain
+    /// head, sync progress, and the last hour of persisted performance
+    /// samples on the metrics port. Samples are recorded regardless and
+    /// are also available through `kala_getMetricsHistory`.
     pub enable_metrics: bool,
 
-    /// Port for Prometheus metrics endpoint
+    /// Port for the metrics endpoint
     /// 
-    /// HTTP endpoint serving metrics in Prometheus format.
+    /// HTTP endpoint serving the status page at `/`.
     /// Only active when enable_metrics is true.
     pub metrics_port: u16,
+
+    /// Serve debugging RPC methods
+    /// 
+    /// Enables `kala_debugFfiMemory`, which reports allocations across the
+    /// native VDF and timelock libraries. Leave disabled on public nodes.
+    /// Default: false
+    #[serde(default)]
+    pub enable_debug_rpc: bool,
+
+    /// Multiaddr for the peer-to-peer gossip listener
+    /// 
+    /// When set, the node joins the libp2p gossip network and propagates
+    /// encrypted envelopes, witness observations, and tick certificates
+    /// to its peers. Leave unset to run as an isolated single node.
+    /// Example: "/ip4/0.0.0.0/tcp/30333"
+    #[serde(default)]
+    pub p2p_listen_addr: Option<String>,
+
+    /// Peers to dial on startup
+    /// 
+    /// Full multiaddrs including the peer ID, for example
+    /// "/ip4/10.0.0.2/tcp/30333/p2p/12D3KooW...". Only used when
+    /// p2p_listen_addr is set.
+    #[serde(default)]
+    pub p2p_peers: Vec<String>,
+
+    /// Bootstrap nodes for Kademlia peer discovery
+    /// 
+    /// Full multiaddrs including the peer ID. These are added to the DHT
+    /// routing table on startup and used to find the rest of the network.
+    /// Peers on the local network are also discovered through mDNS.
+    #[serde(default)]
+    pub p2p_bootstrap_nodes: Vec<String>,
+
+    /// Maximum number of connected peers
+    /// 
+    /// Connections beyond this limit are closed as soon as they are
+    /// established, and discovered peers are not dialed while the node
+    /// is at capacity.
+    #[serde(default = "default_max_peers")]
+    pub p2p_max_peers: usize,
+
+    /// Detail of the tick certificates received over gossip
+    /// 
+    /// "full" receives whole certificates. "compact" leaves out a third of
+    /// each certificate's form, which the node derives itself. "header"
+    /// receives only tick headers and fetches a certificate from peers
+    /// when it needs to check the tick, for witnesses on slow links.
+    /// Sync downloads compact certificates unless this is "full".
+    /// Default: "full"
+    #[serde(default)]
+    pub p2p_certificate_detail: CertificateDetail,
+
+    /// Number of ticks per epoch
+    /// 
+    /// After the last tick of each epoch the node seals an epoch summary
+    /// committing to the tick accumulator, state root, and witness set,
+    /// which light clients use to follow the timeline epoch-to-epoch.
+    /// All nodes in the network must use the same value.
+    /// Default: 1024 (about 8.5 minutes at k=65536)
+    #[serde(default = "default_epoch_length")]
+    pub epoch_length: u64,
+
+    /// Witnesses signing ticks and epoch summaries
+    /// 
+    /// A tick is final once witnesses holding more than 2/3 of the total
+    /// weight have signed it. The node signs with its own witness keys,
+    /// from `witness_signer`, when it is in this set. Leave empty
+    /// to run with the node as the only witness.
+    /// 
+    /// This is the genesis set and the pool of candidates: at every epoch
+    /// boundary the set is recomputed from the stake delegated to each
+    /// candidate's key, which then replaces the configured weight.
+    #[serde(default)]
+    pub witnesses: Vec<WitnessConfig>,
+
+    /// Signature scheme witnesses vote on ticks with
+    /// 
+    /// `bls12381` aggregates the votes finalizing a tick into a single
+    /// 96-byte signature however large the witness set grows. `ed25519`
+    /// keeps one 64-byte signature per signer, which is cheaper to verify
+    /// for small sets. All nodes in the network must use the same value.
+    /// Default: bls12381
+    #[serde(default)]
+    pub witness_vote_scheme: SignatureScheme,
+
+    /// Where the node's witness keys are kept
+    /// 
+    /// A password-protected keystore file or a remote signer keeps the
+    /// keys off disk in plain form. Leave unset to generate a seed and
+    /// store it unencrypted in the state database, which is only suitable
+    /// for development.
+    #[serde(default)]
+    pub witness_signer: Option<SignerConfig>,
+
+    /// Stake a candidate needs delegated to stay in the witness set
+    /// 
+    /// While no candidate reaches it, the current witness set is kept.
+    /// All nodes in the network must use the same value.
+    /// Default: 1
+    #[serde(default = "default_min_witness_stake")]
+    pub min_witness_stake: u64,
+
+    /// Ticks between witness-signed time anchors
+    /// 
+    /// Witnesses sign their clock reading with their votes on every tick
+    /// that is a multiple of this. Once such a tick is final, the median
+    /// of the readings anchors its last iteration to UTC time, correcting
+    /// the drift of the node's timeline clock and bounding the time of the
+    /// ticks around it for light clients. Set to 0 to disable anchors.
+    /// Default: 120 (about a minute at the ~497ms target)
+    #[serde(default = "default_time_anchor_interval")]
+    pub time_anchor_interval: u64,
+
+    /// Trusted checkpoint a fresh node syncs from instead of genesis
+    /// 
+    /// Must name the last tick of an epoch, as published in that epoch's
+    /// summary. A node with an empty database checks the checkpoint tick,
+    /// epoch summary, and tick accumulator against these values and then
+    /// syncs forward from there, skipping the history before it.
+    /// Requires p2p_listen_addr.
+    #[serde(default)]
+    pub trusted_checkpoint: Option<CheckpointConfig>,
+
+    /// Threads recomputing VDF segments when verifying synced ticks
+    /// 
+    /// Sync and fork choice verify downloaded tick certificates on a
+    /// dedicated pool of this many threads. Set to 0 to use the thread
+    /// count of `vdf_tuning`.
+    /// Default: 0
+    #[serde(default)]
+    pub sync_verify_threads: usize,
+
+    /// How the VDF library uses this machine
+    /// 
+    /// Verification threads, and whether the fast squaring path may use
+    /// AVX-512 IFMA and its assembly. Leave unset to auto-tune at startup
+    /// from the detected CPU features and a short benchmark; the choice
+    /// is logged and reported by `kala_chainInfo`.
+    /// Example: { "num_threads": 7, "avx512": false, "fast_mode": true }
+    #[serde(default)]
+    pub vdf_tuning: Option<VdfConfig>,
+
+    /// Ticks between VDF checkpoints written in the background
+    /// 
+    /// Each VDF tick certificate is written to the state database as its
+    /// tick finalizes, off the thread computing the VDF. Every this many
+    /// ticks the VDF state is written too. Set to 0 to only write
+    /// certificates.
+    /// Default: 64
+    #[serde(default = "default_vdf_checkpoint_interval")]
+    pub vdf_checkpoint_interval: u64,
+
+    /// Seconds without a new tick before the watchdog reports a stall
+    /// 
+    /// Stalls are passed to the node's observers, including the alert
+    /// sink. Set to 0 to disable the watchdog.
+    /// Default: 60
+    #[serde(default = "default_watchdog_timeout")]
+    pub watchdog_timeout_secs: u64,
+
+    /// Run with a simulated VDF clock, for development only
+    /// 
+    /// NOT FOR PRODUCTION. Iterations advance without squaring, so ticks
+    /// complete as fast as their transactions are processed, and their
+    /// certificates prove nothing. Meant for iterating on transactions and
+    /// RPC clients locally. A database first opened in this mode only
+    /// opens in it afterwards. Requires running without peers.
+    /// Default: false
+    #[serde(default)]
+    pub dev_instant: bool,
+
+    /// Let every account mint the native denomination, for development only
+    /// 
+    /// NOT FOR PRODUCTION. Applies to chains started without a genesis
+    /// file, and to genesis files written from this configuration; a
+    /// genesis file's own `open_mint` decides otherwise. Meant for funding
+    /// test accounts locally.
+    /// Default: false
+    #[serde(default)]
+    pub dev_open_mint: bool,
+
+    /// Seconds between benchmarks of the node's VDF speed
+    /// 
+    /// The node measures its squaring rate at startup and then every
+    /// this many seconds, reports it through `kala_chainInfo`, and warns
+    /// when the tick duration it implies falls behind the ~497ms target.
+    /// Set to 0 to disable calibration.
+    /// Default: 3600
+    #[serde(default = "default_vdf_calibration_interval")]
+    pub vdf_calibration_interval_secs: u64,
+
+    /// Webhook alerts for consensus anomalies
+    /// 
+    /// When set, watchdog stalls, failed ticks, state root mismatches,
+    /// and sudden drops in witness signatures are posted to the
+    /// configured webhooks. Leave unset to only log them.
+    #[serde(default)]
+    pub alerts: Option<AlertConfig>,
+
+    /// Message queues node events are published to
+    /// 
+    /// Tick certificates, finality, failures, stalls, double-signs, and
+    /// account changes are published as JSON to every configured Kafka
+    /// topic, NATS subject, or Redis stream. Delivery is at most once.
+    #[serde(default)]
+    pub event_sinks: Vec<EventSinkConfig>,
+
+    /// File finalized ticks are fed to indexers through
+    /// 
+    /// When set, every finalized tick, with the transactions it applied,
+    /// is appended to this file as a line of JSON, in order and exactly
+    /// once, even across restarts and reorgs. Transactions of unfinalized
+    /// ticks wait in `<path>.spool`. Leave unset to not write the feed.
+    #[serde(default)]
+    pub indexer_path: Option<String>,
+
+    /// Remote archive that old ticks are moved to
+    /// 
+    /// When set, tick and VDF certificates more than `retain_ticks`
+    /// behind the latest sealed epoch are uploaded to an S3-compatible
+    /// bucket and removed from the local database. Queries for them are
+    /// served from the bucket and verified against the tick accumulator.
+    /// Leave unset to keep every tick locally.
+    #[serde(default)]
+    pub archive: Option<ArchiveConfig>,
+
+    /// How timestamped payloads are kept once their tick finalizes
+    /// 
+    /// Each payload takes the first tier it fits in by size: kept in full,
+    /// reduced to its hash, or uploaded to the `archive` bucket and kept
+    /// as its hash and the object's URI. Payloads larger than every tier
+    /// are kept in full. Inclusion proofs commit to the hash, so they hold
+    /// in every case. Leave empty to keep every payload.
+    #[serde(default)]
+    pub payload_retention: Vec<PayloadRetentionTier>,
+
+    /// Denomination registry for RPC responses
+    /// 
+    /// Symbols and decimals returned with account balances and the
+    /// token supply. The first entry is the native denomination that
+    /// balances are held in. Leave empty for KALA with 9 decimals.
+    #[serde(default)]
+    pub denoms: Vec<DenomConfig>,
+}
+
+/// A witness entry in [`NodeConfig::witnesses`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WitnessConfig {
+    /// Hex-encoded Ed25519 public key identifying the witness
+    pub key: String,
+    /// Hex-encoded compressed BLS12-381 public key for tick votes
+    pub bls_key: String,
+    /// Voting weight of the witness
+    #[serde(default = "default_witness_weight")]
+    pub weight: u64,
+}
+
+impl WitnessConfig {
+    /// Parses the hex-encoded keys into a [`Witness`]
+    pub fn parse(&self) -> Result<Witness, Box<dyn std::error::Error>> {
+        let key = hex::decode(&self.key)
+            .map_err(|e| format!("invalid witness key {}: {}", self.key, e))?;
+        let key = <[u8; 32]>::try_from(key.as_slice())
+            .map_err(|_| format!("witness key {} must be 32 bytes", self.key))?;
+        let bls_key = hex::decode(&self.bls_key)
+            .map_err(|e| format!("invalid BLS key {}: {}", self.bls_key, e))?;
+        if bls_key.len() != kala_state::witness::BLS_PUBLIC_KEY_SIZE {
+            return Err(format!("BLS key {} must be 48 bytes", self.bls_key).into());
+        }
+        Ok(Witness {
+            key,
+            bls_key,
+            weight: self.weight,
+        })
+    }
+}
+
+/// The [`NodeConfig::witness_signer`] entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SignerConfig {
+    /// Keys decrypted from a keystore file at startup
+    Keystore {
+        /// Path to the keystore file
+        path: String,
+        /// Environment variable holding the keystore password
+        #[serde(default = "default_keystore_password_env")]
+        password_env: String,
+    },
+    /// Keys held by a signer service, asked for every signature
+    Remote {
+        /// Base http(s) URL of the signer service
+        url: String,
+    },
+}
+
+/// The [`NodeConfig::trusted_checkpoint`] entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    /// Number of the checkpoint tick
+    pub tick: u64,
+    /// Hex-encoded hash of the checkpoint tick certificate
+    pub tick_hash: String,
+    /// Hex-encoded state root in the summary of the epoch ending at the tick
+    pub state_root: String,
+    /// Download the ticks before the checkpoint in the background
+    #[serde(default)]
+    pub backfill: bool,
+}
+
+/// A denomination entry in [`NodeConfig::denoms`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DenomConfig {
+    /// Hex-encoded 32-byte denomination ID
+    pub denom: String,
+    /// Ticker symbol of the display unit
+    pub symbol: String,
+    /// Human-readable name, the symbol if unset
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Number of decimal places in the display unit
+    pub decimals: u8,
+}
+
+/// The [`NodeConfig::alerts`] entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertConfig {
+    /// URL receiving each alert as a JSON POST
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Slack incoming webhook URL receiving each alert as a message
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+    /// Alert when the weight signing a finalized tick falls by this many
+    /// percent from the previous finalized tick
+    #[serde(default = "default_signature_drop_percent")]
+    pub signature_drop_percent: u8,
+    /// Alert when this many ticks pass without one being finalized
+    #[serde(default = "default_finality_lag_ticks")]
+    pub finality_lag_ticks: u64,
+    /// Minimum seconds between two alerts of the same kind
+    #[serde(default = "default_alert_cooldown")]
+    pub cooldown_secs: u64,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            slack_webhook_url: None,
+            signature_drop_percent: default_signature_drop_percent(),
+            finality_lag_ticks: default_finality_lag_ticks(),
+            cooldown_secs: default_alert_cooldown(),
+        }
+    }
+}
+
+/// A [`NodeConfig::event_sinks`] entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum EventSinkConfig {
+    /// A Kafka topic, written through a Confluent-compatible REST proxy
+    Kafka {
+        /// Base http(s) URL of the REST proxy
+        rest_proxy_url: String,
+        /// Topic events are produced to
+        topic: String,
+    },
+    /// A NATS subject
+    Nats {
+        /// Server address as host:port
+        address: String,
+        /// Subject events are published on
+        subject: String,
+    },
+    /// A Redis stream, appended to with XADD
+    Redis {
+        /// Server address as host:port
+        address: String,
+        /// Key of the stream
+        stream: String,
+        /// Approximate number of entries the stream is trimmed to
+        #[serde(default)]
+        max_len: Option<u64>,
+    },
+}
+
+/// The [`NodeConfig::archive`] entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    /// Base http(s) URL of the object store, such as
+    /// `https://s3.us-east-1.amazonaws.com` or `https://storage.googleapis.com`
+    pub endpoint: String,
+    /// Bucket the ticks are stored in
+    pub bucket: String,
+    /// Region requests are signed for; `auto` for Google Cloud Storage
+    #[serde(default = "default_archive_region")]
+    pub region: String,
+    /// Prefix of every object key, such as `mainnet/`
+    #[serde(default)]
+    pub prefix: String,
+    /// Environment variable holding the access key ID
+    pub access_key_env: String,
+    /// Environment variable holding the secret access key
+    pub secret_key_env: String,
+    /// Ticks before the latest sealed epoch's end that stay local
+    #[serde(default = "default_archive_retain_ticks")]
+    pub retain_ticks: u64,
+}
+
+/// A [`NodeConfig::payload_retention`] tier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadRetentionTier {
+    /// Largest payload the tier applies to, in bytes; unset for any size
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+    /// What is kept of the tier's payloads
+    pub keep: PayloadRetention,
+}
+
+/// What a [`PayloadRetentionTier`] keeps of a payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadRetention {
+    /// The payload itself
+    Full,
+    /// Only the payload's hash
+    Hash,
+    /// The hash and the URI of a copy uploaded to the archive bucket
+    External,
+}
+
+/// Most decimals a [`DenomConfig`] may have; more would put every
+/// representable amount below one display unit
+pub const MAX_DENOM_DECIMALS: u8 = 19;
+
+/// Fewest characters a [`NodeConfig::rpc_admin_token`] may have
+pub const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+/// Default for [`NodeConfig::chain_id`]
+fn default_chain_id() -> String {
+    DEFAULT_CHAIN_ID.to_string()
+}
+
+/// Default for [`NodeConfig::discriminant`]
+fn default_discriminant() -> String {
+    DEFAULT_DISCRIMINANT.to_string()
+}
+
+/// Default for [`NodeConfig::rpc_bind_addr`]
+fn default_rpc_bind_addr() -> String {
+    "127.0.0.1".to_string()
+}
+
+/// Default for [`NodeConfig::rpc_max_request_body_size`] and
+/// [`NodeConfig::rpc_max_response_body_size`]
+fn default_rpc_max_body_size() -> u32 {
+    kala_rpc::limits::DEFAULT_MAX_REQUEST_BODY_SIZE
+}
+
+/// Default for [`NodeConfig::p2p_max_peers`]
+fn default_max_peers() -> usize {
+    50
+}
+
+/// Default for [`NodeConfig::epoch_length`]
+fn default_epoch_length() -> u64 {
+    1024
+}
+
+/// Default for [`NodeConfig::min_witness_stake`]
+fn default_min_witness_stake() -> u64 {
+    1
+}
+
+/// Default for [`NodeConfig::time_anchor_interval`]
+fn default_time_anchor_interval() -> u64 {
+    120
+}
+
+/// Default for [`NodeConfig::watchdog_timeout_secs`]
+fn default_watchdog_timeout() -> u64 {
+    60
+}
+
+/// Default for [`NodeConfig::vdf_calibration_interval_secs`]
+fn default_vdf_calibration_interval() -> u64 {
+    3600
+}
+
+/// Default for [`NodeConfig::vdf_checkpoint_interval`]
+fn default_vdf_checkpoint_interval() -> u64 {
+    64
+}
+
+/// Default for [`AlertConfig::signature_drop_percent`]
+fn default_signature_drop_percent() -> u8 {
+    25
+}
+
+/// Default for [`AlertConfig::finality_lag_ticks`]
+fn default_finality_lag_ticks() -> u64 {
+    128
+}
+
+/// Default for [`AlertConfig::cooldown_secs`]
+fn default_alert_cooldown() -> u64 {
+    300
+}
+
+/// Default for [`ArchiveConfig::region`]
+fn default_archive_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// Default for [`ArchiveConfig::retain_ticks`]
+fn default_archive_retain_ticks() -> u64 {
+    65536
+}
+
+/// Default for the keystore `password_env` of [`SignerConfig`]
+fn default_keystore_password_env() -> String {
+    "KALA_KEYSTORE_PASSWORD".to_string()
+}
+
+/// Default for [`WitnessConfig::weight`]
+fn default_witness_weight() -> u64 {
+    1
 }
 
 impl Default for NodeConfig {
@@ -115,6 +855,7 @@ impl Default for NodeConfig {
     /// - Full security parameters (65536 iterations per tick)
     /// - Conservative timelock settings (10% hardness factor)
     /// - Network discriminant from the research paper
+    /// - Development network chain ID (`kala-devnet`)
     /// 
     /// # Example
     /// ```
@@ -126,7 +867,24 @@ impl Default for NodeConfig {
     fn default() -> Self {
         Self {
             db_path: "./kala_db".to_string(),
+            db_compaction: CompactionStyle::default(),
+            db_read_cache_bytes: default_read_cache_bytes(),
+            db_fsync_policy: FsyncPolicy::default(),
+            db_compression: CompressionConfig::default(),
+            db_slow_op_threshold_ms: default_slow_op_threshold_ms(),
             rpc_port: 8545,
+            rpc_bind_addr: default_rpc_bind_addr(),
+            rpc_tls_cert_path: None,
+            rpc_tls_key_path: None,
+            rpc_cors_allowed_origins: Vec::new(),
+            rpc_admin_token: None,
+            rpc_max_request_body_size: default_rpc_max_body_size(),
+            rpc_max_response_body_size: default_rpc_max_body_size(),
+            rpc_requests_per_second: 0,
+            rpc_burst: 0,
+            rpc_method_rate_limits: HashMap::new(),
+            graphql_port: None,
+            rest_port: None,
             // 2^16 iterations as specified in the paper
             // Provides ~497ms tick duration at 7.6μs per iteration
             iterations_per_tick: 65536,
@@ -134,14 +892,43 @@ impl Default for NodeConfig {
             // while leaving sufficient time for decryption and validation
             timelock_hardness_factor: 0.1,
             enable_gpu: true,
+            vdf_backend: VdfBackend::default(),
             max_transactions_per_tick: 10000,
             // Default discriminant from the research paper
             // This specific value ensures compatibility with the reference implementation
             // WARNING: All nodes in the network must use identical discriminant
-            discriminant: "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679".to_string(),
+            discriminant: default_discriminant(),
+            chain_id: default_chain_id(),
+            genesis_file: None,
             log_level: "info".to_string(),
             enable_metrics: false,
             metrics_port: 9090,
+            enable_debug_rpc: false,
+            p2p_listen_addr: None,
+            p2p_peers: Vec::new(),
+            p2p_bootstrap_nodes: Vec::new(),
+            p2p_max_peers: default_max_peers(),
+            p2p_certificate_detail: CertificateDetail::Full,
+            epoch_length: default_epoch_length(),
+            witnesses: Vec::new(),
+            witness_vote_scheme: SignatureScheme::default(),
+            witness_signer: None,
+            min_witness_stake: default_min_witness_stake(),
+            time_anchor_interval: default_time_anchor_interval(),
+            trusted_checkpoint: None,
+            sync_verify_threads: 0,
+            vdf_tuning: None,
+            vdf_checkpoint_interval: default_vdf_checkpoint_interval(),
+            watchdog_timeout_secs: default_watchdog_timeout(),
+            dev_instant: false,
+            dev_open_mint: false,
+            vdf_calibration_interval_secs: default_vdf_calibration_interval(),
+            alerts: None,
+            event_sinks: Vec::new(),
+            indexer_path: None,
+            archive: None,
+            payload_retention: Vec::new(),
+            denoms: Vec::new(),
         }
     }
 }
@@ -157,7 +944,30 @@ impl NodeConfig {
     /// 
     /// - `iterations_per_tick` must be greater than 0
     /// - `timelock_hardness_factor` must be between 0.0 and 1.0
-    /// - `discriminant` must not be empty
+    /// - `discriminant` and `chain_id` must not be empty
+    /// - `discriminant` must be negative and 1 mod 4, and the registered
+    ///   one if `chain_id` is in the registry
+    /// - `p2p_listen_addr`, `p2p_peers`, and `p2p_bootstrap_nodes` must be valid multiaddrs
+    /// - `p2p_bootstrap_nodes` must include a `/p2p/` peer ID
+    /// - `p2p_max_peers` must be greater than 0
+    /// - `epoch_length` must be greater than 0
+    /// - `witnesses` must have valid hex keys and non-zero weights
+    /// - a remote `witness_signer` must have an http(s) URL
+    /// - `trusted_checkpoint` must end an epoch and have 32-byte hex hashes
+    /// - `dev_instant` can't be combined with `p2p_listen_addr`
+    /// - `dev_open_mint` can't be combined with `genesis_file`
+    /// - `alerts` must name at least one http(s) webhook and a drop of at
+    ///   most 100 percent
+    /// - `graphql_port` must differ from `rpc_port` and, with metrics
+    ///   enabled, `metrics_port`
+    /// - `rest_port` must differ from `rpc_port`, `graphql_port` and, with
+    ///   metrics enabled, `metrics_port`
+    /// - `indexer_path` must not be empty
+    /// - `archive` must have an http(s) endpoint and a bucket
+    /// - `payload_retention` tiers must grow in size, only the last may be
+    ///   unbounded, and `external` ones need an `archive`
+    /// - `denoms` must have unique 32-byte hex IDs, symbols, and at most
+    ///   [`MAX_DENOM_DECIMALS`] decimals
     /// 
     /// # Returns
     /// 
@@ -189,9 +999,321 @@ impl NodeConfig {
             return Err("discriminant cannot be empty".into());
         }
 
+        if self.chain_id.is_empty() {
+            return Err("chain_id cannot be empty".into());
+        }
+
+        kala_vdf::validate_discriminant(&self.discriminant)
+            .map_err(|e| format!("invalid discriminant: {}", e))?;
+        if let Some(registered) = registered_discriminant(&self.chain_id) {
+            if self.discriminant != registered {
+                return Err(format!(
+                    "chain {} runs its registered discriminant, not the configured one",
+                    self.chain_id
+                )
+                .into());
+            }
+        }
+
+        self.rpc_bind_addr
+            .parse::<std::net::IpAddr>()
+            .map_err(|e| format!("invalid rpc_bind_addr {}: {}", self.rpc_bind_addr, e))?;
+
+        if self.rpc_tls_cert_path.is_some() != self.rpc_tls_key_path.is_some() {
+            return Err("rpc_tls_cert_path and rpc_tls_key_path must be set together".into());
+        }
+
+        if let Some(token) = &self.rpc_admin_token {
+            if token.len() < MIN_ADMIN_TOKEN_LEN {
+                return Err(format!(
+                    "rpc_admin_token must be at least {} characters",
+                    MIN_ADMIN_TOKEN_LEN
+                )
+                .into());
+            }
+        }
+
+        if let Some(port) = self.graphql_port {
+            if port == self.rpc_port || (self.enable_metrics && port == self.metrics_port) {
+                return Err("graphql_port must differ from rpc_port and metrics_port".into());
+            }
+        }
+
+        if let Some(port) = self.rest_port {
+            if port == self.rpc_port
+                || self.graphql_port == Some(port)
+                || (self.enable_metrics && port == self.metrics_port)
+            {
+                return Err("rest_port must differ from rpc_port, graphql_port and metrics_port".into());
+            }
+        }
+
+        if let Some(addr) = &self.p2p_listen_addr {
+            addr.parse::<libp2p::Multiaddr>()
+                .map_err(|e| format!("invalid p2p_listen_addr {}: {}", addr, e))?;
+        }
+
+        for peer in &self.p2p_peers {
+            peer.parse::<libp2p::Multiaddr>()
+                .map_err(|e| format!("invalid p2p peer {}: {}", peer, e))?;
+        }
+
+        for node in &self.p2p_bootstrap_nodes {
+            let addr = node.parse::<libp2p::Multiaddr>()
+                .map_err(|e| format!("invalid bootstrap node {}: {}", node, e))?;
+            if !addr.iter().any(|p| matches!(p, libp2p::multiaddr::Protocol::P2p(_))) {
+                return Err(format!("bootstrap node {} is missing a /p2p/ peer ID", node).into());
+            }
+        }
+
+        if self.p2p_max_peers == 0 {
+            return Err("p2p_max_peers must be greater than 0".into());
+        }
+
+        if self.db_fsync_policy == FsyncPolicy::EveryNTicks(0) {
+            return Err("db_fsync_policy every_n_ticks must be greater than 0".into());
+        }
+
+        if !(1..=22).contains(&self.db_compression.level) {
+            return Err("db_compression level must be between 1 and 22".into());
+        }
+
+        if self.epoch_length == 0 {
+            return Err("epoch_length must be greater than 0".into());
+        }
+
+        let witnesses = self.witness_set()?;
+        if witnesses.iter().any(|witness| witness.weight == 0) {
+            return Err("witness weight must be greater than 0".into());
+        }
+
+        if let Some(checkpoint) = self.checkpoint()? {
+            if (checkpoint.tick_number + 1) % self.epoch_length != 0 {
+                return Err(format!(
+                    "trusted checkpoint tick {} is not the last tick of an epoch",
+                    checkpoint.tick_number
+                ).into());
+            }
+            if self.p2p_listen_addr.is_none() {
+                return Err("trusted_checkpoint requires p2p_listen_addr".into());
+            }
+        }
+
+        if self.dev_instant && self.p2p_listen_addr.is_some() {
+            return Err("dev_instant can't be used with p2p_listen_addr".into());
+        }
+
+        if self.dev_open_mint && self.genesis_file.is_some() {
+            return Err(
+                "dev_open_mint can't be used with genesis_file; set open_mint in the genesis".into(),
+            );
+        }
+
+        if let Some(SignerConfig::Remote { url }) = &self.witness_signer {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("remote signer {} must be an http(s) URL", url).into());
+            }
+        }
+
+        if let Some(alerts) = &self.alerts {
+            let urls = [&alerts.webhook_url, &alerts.slack_webhook_url];
+            if urls.iter().all(|url| url.is_none()) {
+                return Err("alerts requires webhook_url or slack_webhook_url".into());
+            }
+            for url in urls.into_iter().flatten() {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(format!("alert webhook {} must be an http(s) URL", url).into());
+                }
+            }
+            if alerts.signature_drop_percent > 100 {
+                return Err("signature_drop_percent must be at most 100".into());
+            }
+        }
+
+        for sink in &self.event_sinks {
+            let (address, name) = match sink {
+                EventSinkConfig::Kafka { rest_proxy_url: url, topic } => {
+                    if !url.starts_with("http://") && !url.starts_with("https://") {
+                        return Err(
+                            format!("Kafka REST proxy {} must be an http(s) URL", url).into()
+                        );
+                    }
+                    (url, topic)
+                }
+                EventSinkConfig::Nats { address, subject } => (address, subject),
+                EventSinkConfig::Redis { address, stream, .. } => (address, stream),
+            };
+            if address.is_empty() || name.is_empty() {
+                return Err("event sinks need an address and a topic, subject, or stream".into());
+            }
+        }
+
+        if self.indexer_path.as_ref().is_some_and(|path| path.is_empty()) {
+            return Err("indexer_path cannot be empty".into());
+        }
+
+        if let Some(archive) = &self.archive {
+            let endpoint = &archive.endpoint;
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                return Err(format!("archive endpoint {} must be an http(s) URL", endpoint).into());
+            }
+            if archive.bucket.is_empty() {
+                return Err("archive bucket must not be empty".into());
+            }
+        }
+
+        let mut smallest = 0;
+        for (i, tier) in self.payload_retention.iter().enumerate() {
+            match tier.max_bytes {
+                Some(max_bytes) if max_bytes < smallest => {
+                    return Err("payload retention tiers must be in increasing size".into());
+                }
+                Some(max_bytes) => smallest = max_bytes + 1,
+                None if i + 1 < self.payload_retention.len() => {
+                    return Err("only the last payload retention tier may be unbounded".into());
+                }
+                None => {}
+            }
+            if tier.keep == PayloadRetention::External && self.archive.is_none() {
+                return Err("external payload retention needs an archive".into());
+            }
+        }
+
+        self.denom_registry()?;
+
         Ok(())
     }
 
+    /// Parses the configured witnesses
+    /// 
+    /// # Returns
+    /// 
+    /// The witnesses in configuration order, or an error naming the first
+    /// entry whose keys are not valid hex of the right length. BLS keys
+    /// are checked for validity when the witness set is built.
+    /// 
+    /// # Example
+    /// ```
+    /// use kala_core::{NodeConfig, WitnessConfig};
+    /// 
+    /// let config = NodeConfig {
+    ///     witnesses: vec![WitnessConfig {
+    ///         key: "11".repeat(32),
+    ///         bls_key: "22".repeat(48),
+    ///         weight: 3,
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    /// let witnesses = config.witness_set().unwrap();
+    /// assert_eq!(witnesses[0].key, [0x11u8; 32]);
+    /// assert_eq!(witnesses[0].weight, 3);
+    /// ```
+    pub fn witness_set(&self) -> Result<Vec<Witness>, Box<dyn std::error::Error>> {
+        self.witnesses.iter().map(WitnessConfig::parse).collect()
+    }
+
+    /// Parses the configured trusted checkpoint
+    /// 
+    /// # Returns
+    /// 
+    /// The checkpoint, `None` when none is configured, or an error if a
+    /// hash is not 32 bytes of hex.
+    /// 
+    /// # Example
+    /// ```
+    /// use kala_core::{CheckpointConfig, NodeConfig};
+    /// 
+    /// let config = NodeConfig {
+    ///     trusted_checkpoint: Some(CheckpointConfig {
+    ///         tick: 1023,
+    ///         tick_hash: "11".repeat(32),
+    ///         state_root: "22".repeat(32),
+    ///         backfill: false,
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    /// let checkpoint = config.checkpoint().unwrap().unwrap();
+    /// assert_eq!(checkpoint.tick_number, 1023);
+    /// assert_eq!(checkpoint.state_root, [0x22u8; 32]);
+    /// ```
+    pub fn checkpoint(&self) -> Result<Option<TrustedCheckpoint>, Box<dyn std::error::Error>> {
+        let Some(checkpoint) = &self.trusted_checkpoint else {
+            return Ok(None);
+        };
+
+        let parse = |name: &str, value: &str| -> Result<[u8; 32], Box<dyn std::error::Error>> {
+            let bytes = hex::decode(value)
+                .map_err(|e| format!("invalid checkpoint {} {}: {}", name, value, e))?;
+            <[u8; 32]>::try_from(bytes.as_slice())
+                .map_err(|_| format!("checkpoint {} {} must be 32 bytes", name, value).into())
+        };
+
+        Ok(Some(TrustedCheckpoint {
+            tick_number: checkpoint.tick,
+            tick_hash: parse("tick hash", &checkpoint.tick_hash)?,
+            state_root: parse("state root", &checkpoint.state_root)?,
+        }))
+    }
+
+    /// Builds the denomination registry from [`NodeConfig::denoms`]
+    /// 
+    /// # Returns
+    /// 
+    /// The registry, or an error if a denomination ID is not 32 bytes of
+    /// hex or repeated, a symbol is empty, or an entry has more than
+    /// [`MAX_DENOM_DECIMALS`] decimals.
+    /// 
+    /// # Example
+    /// ```
+    /// use kala_core::config::DenomConfig;
+    /// use kala_core::NodeConfig;
+    /// 
+    /// let config = NodeConfig {
+    ///     denoms: vec![DenomConfig {
+    ///         denom: "00".repeat(32),
+    ///         symbol: "TIME".to_string(),
+    ///         name: None,
+    ///         decimals: 6,
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    /// let registry = config.denom_registry().unwrap();
+    /// assert_eq!(registry.native().symbol, "TIME");
+    /// assert_eq!(registry.native().format_amount(1_500_000), "1.5");
+    /// ```
+    pub fn denom_registry(&self) -> Result<DenomRegistry, Box<dyn std::error::Error>> {
+        let mut denoms: Vec<([u8; 32], DenomMetadata)> = Vec::with_capacity(self.denoms.len());
+        for denom in &self.denoms {
+            let id = hex::decode(&denom.denom)
+                .map_err(|e| format!("invalid denom {}: {}", denom.denom, e))?;
+            let id = <[u8; 32]>::try_from(id.as_slice())
+                .map_err(|_| format!("denom {} must be 32 bytes", denom.denom))?;
+            if denoms.iter().any(|(known, _)| *known == id) {
+                return Err(format!("denom {} is listed twice", denom.denom).into());
+            }
+            if denom.symbol.is_empty() {
+                return Err(format!("denom {} needs a symbol", denom.denom).into());
+            }
+            if denom.decimals > MAX_DENOM_DECIMALS {
+                return Err(format!(
+                    "denom {} has {} decimals, at most {} are supported",
+                    denom.symbol, denom.decimals, MAX_DENOM_DECIMALS
+                ).into());
+            }
+
+            denoms.push((
+                id,
+                DenomMetadata {
+                    denom: hex::encode(id),
+                    symbol: denom.symbol.clone(),
+                    name: denom.name.clone().unwrap_or_else(|| denom.symbol.clone()),
+                    decimals: denom.decimals,
+                },
+            ));
+        }
+        Ok(DenomRegistry::new(denoms))
+    }
+
     /// Returns the database path as a [`PathBuf`]
     /// 
     /// Convenience method for working with filesystem operations.
@@ -299,6 +1421,434 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_discriminant_registry() {
+        let mut config = NodeConfig::default();
+        config.discriminant = "-5".to_string();
+        assert!(config.validate().is_err());
+
+        // Any valid discriminant on an unregistered chain, only the
+        // registered one on a registered chain
+        config.discriminant = "-7".to_string();
+        assert!(config.validate().is_err());
+        config.chain_id = "kala-localnet".to_string();
+        assert!(config.validate().is_ok());
+
+        // Omitted, the paper's discriminant is used
+        let parsed: NodeConfig = serde_json::from_value({
+            let mut value = serde_json::to_value(NodeConfig::default()).unwrap();
+            value.as_object_mut().unwrap().remove("discriminant");
+            value
+        })
+        .unwrap();
+        assert_eq!(parsed.discriminant, DEFAULT_DISCRIMINANT);
+    }
+
+    #[test]
+    fn test_validation_empty_chain_id() {
+        let mut config = NodeConfig::default();
+        config.chain_id = String::new();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_rpc_server() {
+        let mut config = NodeConfig {
+            rpc_bind_addr: "0.0.0.0".to_string(),
+            rpc_tls_cert_path: Some("rpc.crt".to_string()),
+            rpc_tls_key_path: Some("rpc.key".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.rpc_tls_key_path = None;
+        assert!(config.validate().is_err());
+
+        config.rpc_tls_cert_path = None;
+        config.rpc_bind_addr = "localhost".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_rpc_admin_token() {
+        let mut config = NodeConfig {
+            rpc_admin_token: Some("0123456789abcdef".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.rpc_admin_token = Some("hunter2".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_graphql_port() {
+        let mut config = NodeConfig {
+            graphql_port: Some(8547),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.graphql_port = Some(config.rpc_port);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_rest_port() {
+        let mut config = NodeConfig {
+            rest_port: Some(8548),
+            graphql_port: Some(8547),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.rest_port = config.graphql_port;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_witness_signer() {
+        let mut config = NodeConfig {
+            witness_signer: Some(SignerConfig::Remote {
+                url: "https://signer.local:9000".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.witness_signer = Some(SignerConfig::Remote {
+            url: "signer.local:9000".to_string(),
+        });
+        assert!(config.validate().is_err());
+
+        let keystore: SignerConfig =
+            serde_json::from_str(r#"{"type": "keystore", "path": "witness.json"}"#).unwrap();
+        assert!(matches!(
+            keystore,
+            SignerConfig::Keystore { password_env, .. } if password_env == "KALA_KEYSTORE_PASSWORD"
+        ));
+    }
+
+    #[test]
+    fn test_validation_p2p_addresses() {
+        let mut config = NodeConfig {
+            p2p_listen_addr: Some("/ip4/0.0.0.0/tcp/30333".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.p2p_peers.push("not a multiaddr".to_string());
+        assert!(config.validate().is_err());
+
+        config.p2p_peers.clear();
+        config.p2p_listen_addr = Some("0.0.0.0:30333".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_peer_discovery() {
+        let mut config = NodeConfig {
+            p2p_bootstrap_nodes: vec![format!(
+                "/ip4/10.0.0.2/tcp/30333/p2p/{}",
+                libp2p::PeerId::random()
+            )],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        // Bootstrap nodes need a peer ID to seed the routing table
+        config.p2p_bootstrap_nodes = vec!["/ip4/10.0.0.2/tcp/30333".to_string()];
+        assert!(config.validate().is_err());
+
+        config.p2p_bootstrap_nodes.clear();
+        config.p2p_max_peers = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_epochs() {
+        let witness = WitnessConfig {
+            key: "ab".repeat(32),
+            bls_key: "cd".repeat(48),
+            weight: 1,
+        };
+        let mut config = NodeConfig {
+            witnesses: vec![witness.clone()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.witnesses = vec![WitnessConfig {
+            key: "abcd".to_string(),
+            ..witness.clone()
+        }];
+        assert!(config.validate().is_err());
+
+        config.witnesses = vec![WitnessConfig {
+            bls_key: "cd".repeat(32),
+            ..witness.clone()
+        }];
+        assert!(config.validate().is_err());
+
+        config.witnesses = vec![WitnessConfig {
+            weight: 0,
+            ..witness
+        }];
+        assert!(config.validate().is_err());
+
+        config.witnesses.clear();
+        config.epoch_length = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_fsync_policy() {
+        let mut config = NodeConfig::default();
+        config.db_fsync_policy = FsyncPolicy::EveryNTicks(10);
+        assert!(config.validate().is_ok());
+
+        config.db_fsync_policy = FsyncPolicy::EveryNTicks(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_compression() {
+        let mut config = NodeConfig::default();
+        config.db_compression.level = 19;
+        assert!(config.validate().is_ok());
+
+        config.db_compression.level = 0;
+        assert!(config.validate().is_err());
+
+        // Unset fields keep their defaults
+        let compression: CompressionConfig = serde_json::from_str(r#"{"columns":["proofs"]}"#).unwrap();
+        assert_eq!(compression.min_value_bytes, CompressionConfig::default().min_value_bytes);
+    }
+
+    #[test]
+    fn test_validation_trusted_checkpoint() {
+        let checkpoint = CheckpointConfig {
+            tick: 2047,
+            tick_hash: "ab".repeat(32),
+            state_root: "cd".repeat(32),
+            backfill: true,
+        };
+        let mut config = NodeConfig {
+            p2p_listen_addr: Some("/ip4/0.0.0.0/tcp/30333".to_string()),
+            trusted_checkpoint: Some(checkpoint.clone()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        // Checkpoints must end an epoch
+        config.trusted_checkpoint = Some(CheckpointConfig {
+            tick: 2048,
+            ..checkpoint.clone()
+        });
+        assert!(config.validate().is_err());
+
+        config.trusted_checkpoint = Some(CheckpointConfig {
+            state_root: "cd".repeat(16),
+            ..checkpoint.clone()
+        });
+        assert!(config.validate().is_err());
+
+        config.trusted_checkpoint = Some(checkpoint);
+        config.p2p_listen_addr = None;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_dev_instant() {
+        let mut config = NodeConfig {
+            dev_instant: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        // Simulated ticks must never reach peers
+        config.p2p_listen_addr = Some("/ip4/0.0.0.0/tcp/30333".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_dev_open_mint() {
+        let mut config = NodeConfig {
+            dev_open_mint: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        // A genesis file decides for itself
+        config.genesis_file = Some("genesis.json".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_alerts() {
+        let alerts = AlertConfig {
+            slack_webhook_url: Some("https://hooks.slack.com/services/T0/B0/x".to_string()),
+            ..Default::default()
+        };
+        let mut config = NodeConfig {
+            alerts: Some(alerts.clone()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.alerts = Some(AlertConfig::default());
+        assert!(config.validate().is_err());
+
+        config.alerts = Some(AlertConfig {
+            webhook_url: Some("ftp://alerts.example".to_string()),
+            ..alerts.clone()
+        });
+        assert!(config.validate().is_err());
+
+        config.alerts = Some(AlertConfig {
+            signature_drop_percent: 101,
+            ..alerts
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_event_sinks() {
+        let mut config = NodeConfig {
+            event_sinks: vec![
+                EventSinkConfig::Kafka {
+                    rest_proxy_url: "http://kafka-rest:8082".to_string(),
+                    topic: "kala.ticks".to_string(),
+                },
+                EventSinkConfig::Redis {
+                    address: "127.0.0.1:6379".to_string(),
+                    stream: "kala:events".to_string(),
+                    max_len: Some(10_000),
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.event_sinks.push(EventSinkConfig::Kafka {
+            rest_proxy_url: "kafka:9092".to_string(),
+            topic: "kala.ticks".to_string(),
+        });
+        assert!(config.validate().is_err());
+
+        config.event_sinks.pop();
+        config.event_sinks.push(EventSinkConfig::Nats {
+            address: "127.0.0.1:4222".to_string(),
+            subject: String::new(),
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_indexer_path() {
+        let mut config = NodeConfig::default();
+        config.indexer_path = Some("./indexer.jsonl".to_string());
+        assert!(config.validate().is_ok());
+
+        config.indexer_path = Some(String::new());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_archive() {
+        let archive = ArchiveConfig {
+            endpoint: "https://storage.googleapis.com".to_string(),
+            bucket: "kala-archive".to_string(),
+            region: "auto".to_string(),
+            prefix: String::new(),
+            access_key_env: "KALA_ARCHIVE_ACCESS_KEY".to_string(),
+            secret_key_env: "KALA_ARCHIVE_SECRET_KEY".to_string(),
+            retain_ticks: default_archive_retain_ticks(),
+        };
+        let mut config = NodeConfig {
+            archive: Some(archive.clone()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.archive = Some(ArchiveConfig {
+            endpoint: "s3://kala-archive".to_string(),
+            ..archive.clone()
+        });
+        assert!(config.validate().is_err());
+
+        config.archive = Some(ArchiveConfig {
+            bucket: String::new(),
+            ..archive
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_payload_retention() {
+        let tier = |max_bytes: Option<usize>, keep| PayloadRetentionTier { max_bytes, keep };
+        let mut config = NodeConfig {
+            payload_retention: vec![
+                tier(Some(256), PayloadRetention::Full),
+                tier(None, PayloadRetention::Hash),
+            ],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.payload_retention.reverse();
+        assert!(config.validate().is_err());
+
+        config.payload_retention = vec![
+            tier(Some(256), PayloadRetention::Full),
+            tier(Some(128), PayloadRetention::Hash),
+        ];
+        assert!(config.validate().is_err());
+
+        config.payload_retention = vec![tier(None, PayloadRetention::External)];
+        assert!(config.validate().is_err());
+        config.archive = Some(ArchiveConfig {
+            endpoint: "https://storage.googleapis.com".to_string(),
+            bucket: "kala-archive".to_string(),
+            region: "auto".to_string(),
+            prefix: String::new(),
+            access_key_env: "KALA_ARCHIVE_ACCESS_KEY".to_string(),
+            secret_key_env: "KALA_ARCHIVE_SECRET_KEY".to_string(),
+            retain_ticks: default_archive_retain_ticks(),
+        });
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validation_denoms() {
+        let denom = |id: &str, symbol: &str, decimals: u8| DenomConfig {
+            denom: id.to_string(),
+            symbol: symbol.to_string(),
+            name: None,
+            decimals,
+        };
+        let mut config = NodeConfig {
+            denoms: vec![denom(&"01".repeat(32), "TIME", 6), denom(&"02".repeat(32), "USDK", 2)],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let registry = config.denom_registry().unwrap();
+        assert_eq!(registry.native().name, "TIME");
+        assert_eq!(registry.get(&[2u8; 32]).unwrap().symbol, "USDK");
+
+        config.denoms.push(denom(&"01".repeat(32), "DUP", 0));
+        assert!(config.validate().is_err());
+
+        config.denoms = vec![denom("0102", "SHORT", 0)];
+        assert!(config.validate().is_err());
+
+        config.denoms = vec![denom(&"01".repeat(32), "", 0)];
+        assert!(config.validate().is_err());
+
+        config.denoms = vec![denom(&"01".repeat(32), "WIDE", 20)];
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_db_path_conversion() {
         let config = NodeConfig {
@@ -5,8 +5,9 @@
 //!
 //! 1. **Collection Phase** (0 to k/3): Timestamp transactions as they arrive
 //! 2. **Ordering Phase** (at k/3): Determine canonical transaction ordering  
-//! 3. **Decryption Phase** (k/3 to 2k/3): Decrypt timelock puzzles in parallel
-//! 4. **Validation Phase** (2k/3 to k): Validate and apply transactions
+//! 3. **Decryption Phase** (k/3 to 2k/3): Decrypt timelock puzzles in parallel,
+//!    while witnesses reveal the key shares of threshold-locked envelopes
+//! 4. **Validation Phase** (2k/3 to k): Batch-verify signatures, validate and apply transactions
 //!
 //! # VDF Integration
 //!
@@ -35,17 +36,76 @@
 
 use anyhow::Result;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
-use kala_state::{ChainState, TickCertificate, TickType};
+use crate::evidence::double_sign_evidence;
+use crate::nonce_queue::replace_by_fee;
+use crate::trace::TransactionTracer;
+use kala_rpc::TraceStage;
+use kala_common::crypto::shamir::KeyShare;
+use kala_common::crypto::verify_signatures_batch;
+use kala_common::types::consensus::DEFAULT_CHAIN_ID;
+use kala_common::vdf::VdfEngine;
+use kala_state::{
+    decode_foreign_epoch, CanonicalTimestamp, ChainState, GovernanceParameter, HeldTransaction,
+    ModuleUsage, Recoveries, TickCertificate, TickType, WitnessSet,
+};
 use kala_transaction::{
-    decrypt_timelock_batch, decrypt_timelock_transaction, EncryptionContext, TimelockTransaction,
-    Transaction,
+    decrypt_timelock_batch, decrypt_timelock_transaction, flatbuffer_to_transaction,
+    open_threshold_transaction, transaction_to_flatbuffer, EncryptionContext, KeyLock,
+    TimelockTransaction, Transaction,
 };
-use kala_vdf::EternalVDF;
+use kala_vdf::{TickCertificate as VdfTickCertificate, VDFCheckpoint};
+
+/// A transaction applied to the chain state during a tick
+#[derive(Debug, Clone)]
+pub struct AppliedTransaction {
+    /// Hash of the envelope the transaction arrived in
+    pub envelope_hash: [u8; 32],
+    /// Accounts the transaction modified
+    pub accounts: Vec<[u8; 32]>,
+    /// The decrypted transaction
+    pub transaction: Transaction,
+}
+
+/// A [`VdfEngine`] with the eternal VDF's checkpoints and tick certificates
+///
+/// The tick processor drives any such engine, such as
+/// [`EternalVDF`](kala_vdf::EternalVDF), or [`MockVdf`](kala_vdf::MockVdf)
+/// to simulate ticks without squaring.
+pub trait TickEngine: VdfEngine<Checkpoint = VDFCheckpoint, Proof = VdfTickCertificate> {}
+
+impl<E> TickEngine for E where
+    E: VdfEngine<Checkpoint = VDFCheckpoint, Proof = VdfTickCertificate>
+{
+}
+
+/// Result of processing a tick
+#[derive(Clone)]
+pub struct TickOutcome {
+    /// Certificate for the tick
+    pub certificate: TickCertificate,
+    /// Transactions applied in the tick, in execution order
+    pub applied: Vec<AppliedTransaction>,
+}
+
+/// Reveals and collects the key shares of threshold-locked envelopes
+///
+/// The [`TickProcessor`] calls [`reveal`](Self::reveal) once a tick's
+/// ordering is committed and asks for the revealed shares at the end of the
+/// decryption phase, which leaves witnesses the phase to exchange them.
+#[async_trait::async_trait]
+pub trait KeyShareExchange: Send + Sync {
+    /// Reveal this node's shares of the tick's threshold-locked `envelopes`
+    async fn reveal(&self, tick_num: u64, envelopes: &[TimelockTransaction]);
+
+    /// Shares of an envelope revealed so far
+    async fn shares(&self, tick_num: u64, envelope_hash: &[u8; 32]) -> Vec<KeyShare>;
+}
 
 /// Core consensus processor implementing Kala's tick-based architecture
 ///
@@ -69,6 +129,12 @@ pub struct TickProcessor {
     iterations_per_tick: u64,
     /// Shared encryption context for timelock operations
     encryption_ctx: Arc<EncryptionContext>,
+    /// Records each transaction's progress through the tick, if set
+    tracer: Option<Arc<TransactionTracer>>,
+    /// Chain ID transaction signatures must commit to
+    chain_id: String,
+    /// Exchanges key shares of threshold-locked envelopes, if set
+    key_shares: Option<Arc<dyn KeyShareExchange>>,
 }
 
 impl TickProcessor {
@@ -98,9 +164,63 @@ impl TickProcessor {
         Self {
             iterations_per_tick,
             encryption_ctx,
+            tracer: None,
+            chain_id: DEFAULT_CHAIN_ID.to_string(),
+            key_shares: None,
         }
     }
 
+    /// Record transaction lifecycle events in `tracer`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kala_core::consensus::TickProcessor;
+    /// use kala_core::trace::TransactionTracer;
+    /// use std::sync::Arc;
+    ///
+    /// let tracer = Arc::new(TransactionTracer::default());
+    /// let processor = TickProcessor::new(65536).with_tracer(tracer.clone());
+    /// ```
+    pub fn with_tracer(mut self, tracer: Arc<TransactionTracer>) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// Only accept transactions signed for `chain_id`
+    ///
+    /// Defaults to [`DEFAULT_CHAIN_ID`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kala_core::consensus::TickProcessor;
+    ///
+    /// let processor = TickProcessor::new(65536).with_chain_id("kala-testnet");
+    /// ```
+    pub fn with_chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+
+    /// Open threshold-locked envelopes with shares exchanged through `exchange`
+    ///
+    /// Without an exchange such envelopes can't be decrypted and are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kala_core::consensus::{KeyShareExchange, TickProcessor};
+    /// use std::sync::Arc;
+    ///
+    /// # let exchange: Arc<dyn KeyShareExchange> = todo!();
+    /// let processor = TickProcessor::new(65536).with_key_shares(exchange);
+    /// ```
+    pub fn with_key_shares(mut self, exchange: Arc<dyn KeyShareExchange>) -> Self {
+        self.key_shares = Some(exchange);
+        self
+    }
+
     /// Returns a shared reference to the encryption context
     ///
     /// The encryption context is used by clients to create timelock
@@ -130,13 +250,15 @@ impl TickProcessor {
     ///
     /// 2. **Ordering Phase (at k/3)**:
     ///    - Commits to final transaction ordering
-    ///    - Based on VDF timestamped arrival order
+    ///    - Based on the witnesses' canonical arrival iterations
     ///    - Prevents MEV through pre-commitment
     ///
     /// 3. **Decryption Phase (k/3 to 2k/3)**:
     ///    - Decrypts timelock puzzles in parallel
     ///    - Uses GPU acceleration when available
     ///    - Falls back to CPU if GPU fails
+    ///    - Reveals key shares of threshold-locked envelopes and opens
+    ///      them with the shares revealed by the end of the phase
     ///    - Continues VDF computation during decryption
     ///
     /// 4. **Validation Phase (2k/3 to k)**:
@@ -148,13 +270,35 @@ impl TickProcessor {
     /// # Parameters
     ///
     /// - `tick_num`: The tick number being processed
-    /// - `vdf`: Shared reference to the eternal VDF computation
+    /// - `vdf`: Shared reference to the VDF engine, usually the eternal VDF
     /// - `state`: Shared reference to the blockchain state
     /// - `encrypted_txs`: List of timelock-encrypted transactions for this tick
+    /// - `canonical`: Agreed arrival iterations for the envelopes witnesses
+    ///   have observed; see [`TickProcessor::order_transactions`] and
+    ///   [`TickProcessor::tick_type`]
+    ///
+    /// # Tick Types
+    ///
+    /// - **Full**: A quorum agreed on some envelopes and some applied. The
+    ///   certificate commits to the applied transactions and the state root
+    ///   moves with them.
+    /// - **Empty** (witness-only): There were no envelopes, or none applied.
+    ///   The certificate attests the VDF proof with the root of no
+    ///   transactions.
+    /// - **Checkpoint** (partial): Witnesses holding less than 2/3 of the
+    ///   weight observed the envelopes, so their order can't be agreed and
+    ///   none are opened. They are dropped, the empty order is committed in
+    ///   place of theirs, and the certificate carries only the VDF proof.
+    ///
+    /// Every type runs the full k iterations with the same input schedule,
+    /// and applies the state changes scheduled for the tick, such as vesting
+    /// releases. Those are the only changes to the state root in an empty or
+    /// partial tick.
     ///
     /// # Returns
     ///
-    /// A [`TickCertificate`] containing:
+    /// A [`TickOutcome`] holding the transactions that were applied and a
+    /// [`TickCertificate`] containing:
     /// - VDF proof and state at tick completion
     /// - Transaction processing results
     /// - Cryptographic commitments to tick contents
@@ -178,30 +322,35 @@ impl TickProcessor {
     /// # async fn example() -> anyhow::Result<()> {
     /// let processor = TickProcessor::new(65536);
     /// // Assuming vdf, state, and transactions are initialized...
-    /// # let vdf = Arc::new(RwLock::new(todo!()));
+    /// # let vdf: Arc<RwLock<kala_vdf::EternalVDF>> = Arc::new(RwLock::new(todo!()));
     /// # let state = Arc::new(RwLock::new(todo!()));
     /// # let encrypted_txs = vec![];
+    /// # let witnesses: kala_state::WitnessSet = todo!();
     ///
-    /// let certificate = processor.process_tick(
+    /// let outcome = processor.process_tick(
     ///     42,  // tick number
     ///     vdf,
     ///     state,
-    ///     encrypted_txs
+    ///     encrypted_txs,
+    ///     &[],  // canonical timestamps
+    ///     &witnesses
     /// ).await?;
     ///
     /// println!(\"Processed tick {} with {} transactions\", 
-    ///          certificate.tick_number, 
-    ///          certificate.transaction_count);
+    ///          outcome.certificate.tick_number, 
+    ///          outcome.certificate.transaction_count);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn process_tick(
+    pub async fn process_tick<V: TickEngine>(
         &self,
         tick_num: u64,
-        vdf: Arc<RwLock<EternalVDF>>,
+        vdf: Arc<RwLock<V>>,
         state: Arc<RwLock<ChainState>>,
         encrypted_txs: Vec<TimelockTransaction>,
-    ) -> Result<TickCertificate> {
+        canonical: &[CanonicalTimestamp],
+        witnesses: &WitnessSet,
+    ) -> Result<TickOutcome> {
         let k = self.iterations_per_tick;
         let tick_start_iter = tick_num * k;
 
@@ -222,44 +371,76 @@ impl TickProcessor {
         // Leader timestamps transactions as they arrive (from the paper)
         info!("Tick {}: Phase 1 - Collection phase", tick_num);
 
-        // Track which transactions we've timestamped
-        let mut timestamped_indices = Vec::new();
-
-        for i in 0..collection_phase_end {
+        for _ in 0..collection_phase_end {
             let mut vdf_write = vdf.write().await;
 
-            // Check if we should timestamp a transaction at this iteration
-            // Transactions are timestamped at their submission_iteration
-            for (idx, tx) in encrypted_txs.iter().enumerate() {
-                let current_iter = vdf_write.get_iteration() + 1; // Next iteration
-                if tx.submission_iteration == current_iter && !timestamped_indices.contains(&idx) {
-                    // Timestamp the encrypted transaction data
+            // Transactions are timestamped at their submission_iteration, in
+            // that iteration's step; at most one per iteration
+            let current_iter = vdf_write.iteration() + 1;
+            let arrival = encrypted_txs
+                .iter()
+                .enumerate()
+                .find(|(_, tx)| tx.submission_iteration == current_iter);
+            match arrival {
+                Some((idx, tx)) => {
                     let tx_data = Self::serialize_timelock_tx(tx);
                     debug!(
                         "Timestamping transaction {} at iteration {}",
                         idx, current_iter
                     );
                     vdf_write.step(Some(tx_data));
-                    timestamped_indices.push(idx);
-                    break; // Only one transaction per iteration
                 }
-            }
-
-            // If no transaction to timestamp, just advance VDF
-            if !timestamped_indices.iter().any(|&idx| {
-                encrypted_txs[idx].submission_iteration == vdf_write.get_iteration() + 1
-            }) {
-                vdf_write.step(None);
+                // If no transaction to timestamp, just advance VDF
+                None => vdf_write.step(None),
             }
 
             drop(vdf_write);
         }
 
         // Phase 2: Ordering (at k/3)
-        // For single node, order by submission iteration (already timestamped in VDF)
+        // Order by the witnesses' agreed arrival iterations, before anything is decrypted
         info!("Tick {}: Phase 2 - Ordering transactions", tick_num);
-        let mut ordered_txs = encrypted_txs;
-        ordered_txs.sort_by_key(|tx| tx.submission_iteration);
+        let tick_type = Self::tick_type(&encrypted_txs, canonical, witnesses);
+        let ordered_txs = match tick_type {
+            TickType::Checkpoint => {
+                warn!(
+                    "Tick {}: No witness quorum agreed on the arrival of any of its {} \
+                     envelopes, dropping them as a partial tick",
+                    tick_num,
+                    encrypted_txs.len()
+                );
+                for tx in &encrypted_txs {
+                    self.trace(
+                        &tx.envelope_hash(),
+                        TraceStage::Dropped,
+                        tick_num,
+                        None,
+                        Some("no witness quorum agreed on the tick".to_string()),
+                    );
+                }
+                Vec::new()
+            }
+            _ => Self::order_transactions(encrypted_txs, canonical),
+        };
+        if self.tracer.is_some() {
+            for (position, tx) in ordered_txs.iter().enumerate() {
+                let envelope_hash = tx.envelope_hash();
+                let agreed = canonical
+                    .iter()
+                    .find(|timestamp| timestamp.envelope_hash == envelope_hash);
+                let (iteration, source) = match agreed {
+                    Some(timestamp) => (timestamp.canonical_iteration, "canonical timestamp"),
+                    None => (tx.submission_iteration, "local arrival"),
+                };
+                self.trace(
+                    &envelope_hash,
+                    TraceStage::Ordered,
+                    tick_num,
+                    Some(iteration),
+                    Some(format!("position {} by {}", position, source)),
+                );
+            }
+        }
 
         // Timestamp the ordering decision
         let ordering_data = Self::create_ordering_commitment(&ordered_txs);
@@ -270,20 +451,56 @@ impl TickProcessor {
         // Phase 3: Parallel Decryption (k/3 to 2k/3)
         info!("Tick {}: Phase 3 - Parallel decryption phase", tick_num);
 
+        // Envelopes keep their position in the committed order
+        let (puzzle_txs, threshold_txs): (Vec<_>, Vec<_>) = ordered_txs
+            .iter()
+            .cloned()
+            .enumerate()
+            .partition(|(_, tx)| matches!(tx.lock, KeyLock::Puzzle(_)));
+
+        // Witnesses reveal their key shares only now that the order is fixed
+        if let Some(exchange) = &self.key_shares {
+            if !threshold_txs.is_empty() {
+                let envelopes: Vec<TimelockTransaction> =
+                    threshold_txs.iter().map(|(_, tx)| tx.clone()).collect();
+                exchange.reveal(tick_num, &envelopes).await;
+            }
+        }
+
         // Start parallel decryption using GPU batch processing
+        // Decrypted transactions stay paired with their envelope hash for tracing
         let decrypt_handle = tokio::spawn({
-            let txs = ordered_txs.clone();
+            let tracer = self.tracer.clone();
             async move {
+                let (positions, txs): (Vec<usize>, Vec<TimelockTransaction>) =
+                    puzzle_txs.into_iter().unzip();
+                let envelope_hashes: Vec<[u8; 32]> = txs.iter().map(|tx| tx.envelope_hash()).collect();
                 match decrypt_timelock_batch(&txs) {
-                    Ok(decrypted) => decrypted,
+                    Ok(decrypted) => positions
+                        .into_iter()
+                        .zip(envelope_hashes)
+                        .zip(decrypted)
+                        .map(|((position, envelope_hash), tx)| (position, envelope_hash, tx))
+                        .collect(),
                     Err(e) => {
                         warn!("Batch decryption failed: {}, falling back to sequential", e);
                         // Fallback to sequential decryption
                         let mut results = Vec::new();
-                        for tx in txs {
+                        for ((position, envelope_hash), tx) in positions.into_iter().zip(envelope_hashes).zip(txs) {
                             match decrypt_timelock_transaction(&tx) {
-                                Ok(decrypted) => results.push(decrypted),
-                                Err(e) => warn!("Failed to decrypt transaction: {}", e),
+                                Ok(decrypted) => results.push((position, envelope_hash, decrypted)),
+                                Err(e) => {
+                                    warn!("Failed to decrypt transaction: {}", e);
+                                    if let Some(tracer) = &tracer {
+                                        tracer.record(
+                                            &envelope_hash,
+                                            TraceStage::DecryptionFailed,
+                                            tick_num,
+                                            None,
+                                            Some(e.to_string()),
+                                        );
+                                    }
+                                }
                             }
                         }
                         results
@@ -299,8 +516,17 @@ impl TickProcessor {
             drop(vdf_write);
         }
 
-        // Wait for decryption to complete
-        let decrypted_txs = decrypt_handle.await?;
+        // Wait for decryption to complete, then restore the committed order
+        let mut decrypted: Vec<(usize, [u8; 32], Transaction)> = decrypt_handle.await?;
+        decrypted.extend(self.open_threshold_envelopes(tick_num, threshold_txs).await);
+        decrypted.sort_by_key(|(position, _, _)| *position);
+        let decrypted_txs: Vec<([u8; 32], Transaction)> = decrypted
+            .into_iter()
+            .map(|(_, envelope_hash, tx)| (envelope_hash, tx))
+            .collect();
+        for (envelope_hash, _) in &decrypted_txs {
+            self.trace(envelope_hash, TraceStage::Decrypted, tick_num, None, None);
+        }
         info!(
             "Tick {}: Decrypted {} transactions",
             tick_num,
@@ -310,15 +536,131 @@ impl TickProcessor {
         // Phase 4: Validation and State Updates (2k/3 to k)
         info!("Tick {}: Phase 4 - Validation and finalization", tick_num);
 
+        // Only the sender's key may spend from its account
+        let signatures_valid = {
+            let txs: Vec<&Transaction> = decrypted_txs.iter().map(|(_, tx)| tx).collect();
+            verify_signatures_batch(&txs, &self.chain_id)
+        };
+
+        let mut checked_txs = Vec::new();
+        for ((envelope_hash, tx), signature_valid) in decrypted_txs.into_iter().zip(signatures_valid) {
+            if !signature_valid {
+                warn!("Rejecting transaction from {}: invalid signature", hex::encode(tx.sender()));
+                self.trace(
+                    &envelope_hash,
+                    TraceStage::Rejected,
+                    tick_num,
+                    None,
+                    Some("invalid signature".to_string()),
+                );
+                continue;
+            }
+            if let Err(e) = tx.verify_sponsor_signature(&self.chain_id) {
+                warn!("Rejecting transaction from {}: {}", hex::encode(tx.sender()), e);
+                self.trace(
+                    &envelope_hash,
+                    TraceStage::Rejected,
+                    tick_num,
+                    None,
+                    Some("invalid sponsor signature".to_string()),
+                );
+                continue;
+            }
+            checked_txs.push((envelope_hash, tx));
+        }
+
+        // Of resubmissions of a nonce, only the highest tip is processed
+        let (checked_txs, replaced) = replace_by_fee(checked_txs);
+        for envelope_hash in replaced {
+            self.trace(
+                &envelope_hash,
+                TraceStage::Rejected,
+                tick_num,
+                None,
+                Some("replaced by a higher tip".to_string()),
+            );
+        }
+
         let mut valid_txs = Vec::new();
+        let mut applied = Vec::new();
         let mut state_write = state.write().await;
 
-        for tx in decrypted_txs {
-            if Self::validate_transaction(&tx, &state_write) {
+        for envelope_hash in state_write.prune_held_transactions(tick_num) {
+            self.trace(
+                &envelope_hash,
+                TraceStage::Dropped,
+                tick_num,
+                None,
+                Some("held too long for earlier nonces".to_string()),
+            );
+        }
+
+        for (envelope_hash, tx) in checked_txs {
+            // Transactions ahead of the sender's next nonce wait for the gap to fill
+            let account_nonce = state_write.get_account_nonce(tx.sender()).unwrap_or(0);
+            if tx.nonce() > account_nonce.saturating_add(1) {
+                let nonce = tx.nonce();
+                let held = transaction_to_flatbuffer(&tx).map(|transaction| HeldTransaction {
+                    envelope_hash,
+                    transaction,
+                    tip: tx.tip(),
+                    held_at: tick_num,
+                });
+                match held.and_then(|held| state_write.hold_transaction(tx.sender(), nonce, held)) {
+                    Ok(replaced) => {
+                        self.trace(
+                            &envelope_hash,
+                            TraceStage::Held,
+                            tick_num,
+                            None,
+                            Some(format!("account nonce {}", account_nonce)),
+                        );
+                        if let Some(replaced) = replaced {
+                            self.trace(
+                                &replaced,
+                                TraceStage::Rejected,
+                                tick_num,
+                                None,
+                                Some("replaced by a higher tip".to_string()),
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Not holding transaction with nonce {}: {}", nonce, e);
+                        let detail = Some(e.to_string());
+                        self.trace(&envelope_hash, TraceStage::Rejected, tick_num, None, detail);
+                    }
+                }
+                continue;
+            }
+
+            // Applying a transaction may release the sender's held ones
+            let mut next = Some((envelope_hash, tx));
+            while let Some((envelope_hash, tx)) = next.take() {
+                if !self.validate_transaction(&tx, &state_write, witnesses) {
+                    self.trace(
+                        &envelope_hash,
+                        TraceStage::Rejected,
+                        tick_num,
+                        None,
+                        Some("failed validation".to_string()),
+                    );
+                    continue;
+                }
+                // Slashing changes the delegators, so collect them before applying
+                let accounts = Self::modified_accounts(&tx, &state_write);
                 if let Err(e) = Self::apply_transaction(&tx, &mut state_write) {
                     warn!("Failed to apply transaction: {}", e);
+                    self.trace(&envelope_hash, TraceStage::Rejected, tick_num, None, Some(e.to_string()));
                     continue;
                 }
+                self.trace(&envelope_hash, TraceStage::Applied, tick_num, None, None);
+                applied.push(AppliedTransaction {
+                    envelope_hash,
+                    accounts,
+                    transaction: tx.clone(),
+                });
+                next = Self::release_held(&tx, &mut state_write);
                 valid_txs.push(tx);
             }
         }
@@ -342,7 +684,7 @@ impl TickProcessor {
 
         // Complete remaining VDF iterations
         let vdf_read = vdf.read().await;
-        let current = vdf_read.get_iteration();
+        let current = vdf_read.iteration();
         drop(vdf_read);
 
         let target = (tick_num + 1) * k; // where we must end
@@ -358,6 +700,7 @@ impl TickProcessor {
         let certificate = self
             .create_unified_certificate(
                 tick_num,
+                tick_type,
                 valid_txs,
                 tx_merkle_root,
                 vdf.clone(),
@@ -367,6 +710,7 @@ impl TickProcessor {
 
         // Update chain state
         let mut state_write = state.write().await;
+        Self::finalize_state(tick_num, &mut state_write);
         state_write.current_tick = tick_num + 1;
         state_write.last_tick_hash = certificate.tick_hash;
 
@@ -377,16 +721,134 @@ impl TickProcessor {
         drop(vdf_read);
         drop(state_write);
 
+        for (index, applied_tx) in applied.iter().enumerate() {
+            self.trace(
+                &applied_tx.envelope_hash,
+                TraceStage::Included,
+                tick_num,
+                Some(certificate.vdf_iteration),
+                Some(format!(
+                    "transaction {} of certificate {}",
+                    index,
+                    hex::encode(certificate.tick_hash)
+                )),
+            );
+        }
+
         info!(
             "Tick {}: Completed with certificate hash {:?}",
             tick_num,
             hex::encode(&certificate.tick_hash)
         );
 
-        Ok(certificate)
+        Ok(TickOutcome {
+            certificate,
+            applied,
+        })
+    }
+
+    /// Record a lifecycle event if tracing is enabled
+    fn trace(
+        &self,
+        envelope_hash: &[u8; 32],
+        stage: TraceStage,
+        tick_num: u64,
+        iteration: Option<u64>,
+        detail: Option<String>,
+    ) {
+        if let Some(tracer) = &self.tracer {
+            tracer.record(envelope_hash, stage, tick_num, iteration, detail);
+        }
+    }
+
+    /// Open threshold-locked envelopes with the key shares revealed so far
+    ///
+    /// Envelopes without enough valid shares are dropped.
+    async fn open_threshold_envelopes(
+        &self,
+        tick_num: u64,
+        envelopes: Vec<(usize, TimelockTransaction)>,
+    ) -> Vec<(usize, [u8; 32], Transaction)> {
+        let mut opened = Vec::new();
+        for (position, tx) in envelopes {
+            let envelope_hash = tx.envelope_hash();
+            let shares = match &self.key_shares {
+                Some(exchange) => exchange.shares(tick_num, &envelope_hash).await,
+                None => Vec::new(),
+            };
+            match open_threshold_transaction(&tx, &shares) {
+                Ok(decrypted) => opened.push((position, envelope_hash, decrypted)),
+                Err(e) => {
+                    warn!("Failed to open threshold-locked transaction: {}", e);
+                    self.trace(
+                        &envelope_hash,
+                        TraceStage::DecryptionFailed,
+                        tick_num,
+                        None,
+                        Some(format!("{} ({} shares revealed)", e, shares.len())),
+                    );
+                }
+            }
+        }
+        opened
+    }
+
+    /// Order a tick's envelopes by their canonical timestamps
+    ///
+    /// Each envelope is placed at the weighted median of the iterations at
+    /// which the witnesses observed it, with ties broken by envelope hash so
+    /// every node arrives at the same order. Envelopes that no quorum of
+    /// witnesses has observed yet fall back to the local submission
+    /// iteration.
+    ///
+    /// # Parameters
+    ///
+    /// - `txs`: The tick's encrypted transactions
+    /// - `canonical`: Agreed timestamps, matched to envelopes by hash
+    ///
+    /// # Returns
+    ///
+    /// The transactions in execution order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kala_core::consensus::TickProcessor;
+    ///
+    /// let ordered = TickProcessor::order_transactions(vec![], &[]);
+    /// assert!(ordered.is_empty());
+    /// ```
+    pub fn order_transactions(
+        txs: Vec<TimelockTransaction>,
+        canonical: &[CanonicalTimestamp],
+    ) -> Vec<TimelockTransaction> {
+        let agreed: HashMap<[u8; 32], u64> = canonical
+            .iter()
+            .map(|timestamp| (timestamp.envelope_hash, timestamp.canonical_iteration))
+            .collect();
+
+        let mut keyed: Vec<(u64, [u8; 32], TimelockTransaction)> = txs
+            .into_iter()
+            .map(|tx| {
+                let envelope_hash = tx.envelope_hash();
+                let iteration = match agreed.get(&envelope_hash) {
+                    Some(&iteration) => iteration,
+                    None => {
+                        debug!(
+                            "No canonical timestamp for envelope {}, using local arrival",
+                            hex::encode(&envelope_hash[..8])
+                        );
+                        tx.submission_iteration
+                    }
+                };
+                (iteration, envelope_hash, tx)
+            })
+            .collect();
+        keyed.sort_by_key(|(iteration, envelope_hash, _)| (*iteration, *envelope_hash));
+        keyed.into_iter().map(|(_, _, tx)| tx).collect()
     }
 
-    fn serialize_timelock_tx(tx: &TimelockTransaction) -> Vec<u8> {
+    pub(crate) fn serialize_timelock_tx(tx: &TimelockTransaction) -> Vec<u8> {
         // Serialize the encrypted transaction for timestamping
         let mut data = Vec::new();
         data.extend_from_slice(&tx.submission_iteration.to_le_bytes());
@@ -397,13 +859,16 @@ impl TickProcessor {
         data.extend_from_slice(&tx.encrypted_data.tag);
         data.extend_from_slice(&tx.encrypted_data.ciphertext);
 
-        // Include puzzle hardness for ordering
-        data.extend_from_slice(&tx.puzzle.hardness.to_le_bytes());
+        // Include puzzle hardness, or the share threshold, for ordering
+        match &tx.lock {
+            KeyLock::Puzzle(puzzle) => data.extend_from_slice(&puzzle.hardness.to_le_bytes()),
+            KeyLock::Threshold(lock) => data.push(lock.threshold),
+        }
 
         data
     }
 
-    fn create_ordering_commitment(txs: &[TimelockTransaction]) -> Vec<u8> {
+    pub(crate) fn create_ordering_commitment(txs: &[TimelockTransaction]) -> Vec<u8> {
         let mut hasher = Sha256::new();
         hasher.update(b"ordering");
         for tx in txs {
@@ -418,27 +883,15 @@ impl TickProcessor {
         hasher.finalize().to_vec()
     }
 
-    fn compute_transaction_merkle_root(txs: &[Transaction]) -> [u8; 32] {
+    pub(crate) fn compute_transaction_merkle_root(txs: &[Transaction]) -> [u8; 32] {
         let hashes: Vec<[u8; 32]> = txs.iter().map(|tx| Self::hash_transaction(tx)).collect();
         compute_merkle_root(&hashes)
     }
 
-    fn validate_transaction(tx: &Transaction, state: &ChainState) -> bool {
-        // Get sender address from transaction
-        let sender = match tx {
-            Transaction::Send(s) => &s.sender,
-            Transaction::Mint(m) => &m.sender,
-            Transaction::Stake(s) => &s.sender,
-            Transaction::Solve(s) => &s.sender,
-        };
-
-        // Get nonce from transaction
-        let tx_nonce = match tx {
-            Transaction::Send(s) => s.nonce,
-            Transaction::Mint(m) => m.nonce,
-            Transaction::Stake(s) => s.nonce,
-            Transaction::Solve(s) => s.nonce,
-        };
+    /// Check a transaction whose signature has been verified against `state`
+    fn validate_transaction(&self, tx: &Transaction, state: &ChainState, witnesses: &WitnessSet) -> bool {
+        let sender = tx.sender();
+        let tx_nonce = tx.nonce();
 
         // Check nonce
         if let Some(account_nonce) = state.get_account_nonce(sender) {
@@ -451,20 +904,193 @@ impl TickProcessor {
             }
         }
 
+        // The fee payer must afford the fee on top of anything it spends
+        let fee = state.transaction_fee().saturating_add(tx.tip());
+        let sender_fee = if tx.is_sponsored() { 0 } else { fee };
+        if state.get_balance(tx.fee_payer()) < fee {
+            warn!("Insufficient balance for fee of {}", fee);
+            return false;
+        }
+
         // Check balance for transfers
         match tx {
             Transaction::Send(send) => {
-                if state.get_balance(&send.sender) < send.amount {
+                if let Err(e) = send.validate() {
+                    warn!("Invalid send: {}", e);
+                    return false;
+                }
+                if state.get_balance(&send.sender) < send.amount.saturating_add(sender_fee) {
                     warn!("Insufficient balance for send");
                     return false;
                 }
             }
+            Transaction::MultiSend(multi_send) => {
+                let total = match multi_send.validate().and_then(|_| multi_send.total_amount()) {
+                    Ok(total) => total,
+                    Err(e) => {
+                        warn!("Invalid multisend: {}", e);
+                        return false;
+                    }
+                };
+                if state.get_balance(&multi_send.sender) < total.saturating_add(sender_fee) {
+                    warn!("Insufficient balance for multisend");
+                    return false;
+                }
+            }
+            Transaction::VestingTransfer(vesting) => {
+                if let Err(e) = vesting.validate() {
+                    warn!("Invalid vesting transfer: {}", e);
+                    return false;
+                }
+                if vesting.unlock_tick <= state.current_tick {
+                    warn!("Vesting transfer unlocks at past tick {}", vesting.unlock_tick);
+                    return false;
+                }
+                if state.get_balance(&vesting.sender) < vesting.amount.saturating_add(sender_fee) {
+                    warn!("Insufficient balance for vesting transfer");
+                    return false;
+                }
+            }
+            Transaction::Propose(propose) => {
+                let parameter = match GovernanceParameter::from_id(propose.parameter)
+                    .and_then(|parameter| parameter.validate(propose.value).map(|_| parameter))
+                {
+                    Ok(parameter) => parameter,
+                    Err(e) => {
+                        warn!("Invalid proposal: {}", e);
+                        return false;
+                    }
+                };
+                // Only minter approvals name a denomination and minter
+                if (parameter == GovernanceParameter::MinterApproval) != propose.minter.is_some() {
+                    warn!("Invalid proposal: minter approvals need a denom and minter");
+                    return false;
+                }
+            }
+            Transaction::Vote(vote) => {
+                if state.governance().proposal(vote.proposal_id).is_none() {
+                    warn!("Vote on unknown proposal {}", vote.proposal_id);
+                    return false;
+                }
+                if state.get_account(&vote.sender).map_or(0, |a| a.staked_amount) == 0 {
+                    warn!("Vote from an account without stake");
+                    return false;
+                }
+            }
+            Transaction::Mint(mint) => {
+                if let Err(e) = state.mint_authorities().check_mint(&mint.sender, &mint.denom) {
+                    warn!("Unauthorized mint: {}", e);
+                    return false;
+                }
+            }
             Transaction::Stake(stake) => {
-                if state.get_balance(&stake.sender) < stake.amount {
+                if state.get_balance(&stake.sender) < stake.amount.saturating_add(sender_fee) {
                     warn!("Insufficient balance for stake");
                     return false;
                 }
             }
+            Transaction::Unstake(unstake) => {
+                let staked = state
+                    .get_account(&unstake.sender)
+                    .map_or(0, |account| account.staked_amount);
+                if staked < unstake.amount {
+                    warn!("Insufficient stake for unstake");
+                    return false;
+                }
+            }
+            Transaction::Evidence(evidence) => {
+                // Votes are checked against the current witness set
+                if let Err(e) = double_sign_evidence(evidence).verify(witnesses) {
+                    warn!("Invalid double-sign evidence: {}", e);
+                    return false;
+                }
+                if state
+                    .last_slashed(&evidence.witness)
+                    .is_some_and(|tick| evidence.tick_number <= tick)
+                {
+                    warn!("Witness already slashed for tick {}", evidence.tick_number);
+                    return false;
+                }
+            }
+            Transaction::SubmitForeignEpoch(submit) => {
+                if let Err(e) = submit
+                    .validate()
+                    .and_then(|_| decode_foreign_epoch(&submit.epoch))
+                    .and_then(|epoch| state.foreign_clients().check(&submit.client_id, &epoch))
+                {
+                    warn!("Invalid foreign epoch: {}", e);
+                    return false;
+                }
+            }
+            Transaction::CreateAuction(create) => {
+                if let Err(e) = create.validate() {
+                    warn!("Invalid auction: {}", e);
+                    return false;
+                }
+                if create.close_tick <= state.current_tick {
+                    warn!("Auction closes at past tick {}", create.close_tick);
+                    return false;
+                }
+            }
+            Transaction::Bid(bid) => {
+                // Bids sealed for any tick but the closing one are rejected here
+                let check = state.auctions().check_bid(
+                    &bid.sender,
+                    bid.auction_id,
+                    bid.amount,
+                    state.current_tick,
+                );
+                if let Err(e) = check {
+                    warn!("Invalid bid: {}", e);
+                    return false;
+                }
+                if state.get_balance(&bid.sender) < bid.amount.saturating_add(sender_fee) {
+                    warn!("Insufficient balance for bid");
+                    return false;
+                }
+            }
+            Transaction::SetGuardians(set) => {
+                let check = set.validate().and_then(|_| {
+                    Recoveries::check_guardians(
+                        &set.sender,
+                        &set.guardians,
+                        set.threshold,
+                        set.delay_ticks,
+                    )
+                });
+                if let Err(e) = check {
+                    warn!("Invalid guardians: {}", e);
+                    return false;
+                }
+            }
+            Transaction::RequestRecovery(request) => {
+                let check = request.validate().and_then(|_| {
+                    state
+                        .recoveries()
+                        .check_request(&request.sender, &request.account, &request.new_owner)
+                });
+                if let Err(e) = check {
+                    warn!("Invalid recovery request: {}", e);
+                    return false;
+                }
+            }
+            Transaction::TransferMintAuthority(transfer) => {
+                let check = transfer.validate().and_then(|_| {
+                    state
+                        .mint_authorities()
+                        .check_transfer(&transfer.sender, &transfer.denom)
+                });
+                if let Err(e) = check {
+                    warn!("Invalid mint authority transfer: {}", e);
+                    return false;
+                }
+            }
+            Transaction::VetoRecovery(veto)
+                if state.recoveries().pending(&veto.sender).is_none() =>
+            {
+                warn!("No recovery pending to veto");
+                return false;
+            }
             _ => {}
         }
 
@@ -472,13 +1098,75 @@ impl TickProcessor {
     }
 
     fn apply_transaction(tx: &Transaction, state: &mut ChainState) -> Result<()> {
+        // The fee is taken first so a payer who can't cover it leaves the
+        // state untouched, and handed back if the body then fails
+        let fee = state.transaction_fee().saturating_add(tx.tip());
+        state.charge_fee(tx.fee_payer(), fee)?;
+        if let Err(e) = Self::apply_body(tx, state) {
+            state.refund_fee(tx.fee_payer(), fee);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Apply the body of `tx`, its fee already charged
+    fn apply_body(tx: &Transaction, state: &mut ChainState) -> Result<()> {
         match tx {
             Transaction::Send(send) => {
                 state.transfer(&send.sender, &send.receiver, send.amount)?;
                 state.update_nonce(&send.sender, send.nonce);
             }
+            Transaction::MultiSend(multi_send) => {
+                let outputs: Vec<([u8; 32], u64)> = multi_send
+                    .outputs
+                    .iter()
+                    .map(|output| (output.receiver, output.amount))
+                    .collect();
+                state.multi_transfer(&multi_send.sender, &outputs)?;
+                state.update_nonce(&multi_send.sender, multi_send.nonce);
+            }
+            Transaction::VestingTransfer(vesting) => {
+                state.lock_vesting(
+                    &vesting.sender,
+                    &vesting.receiver,
+                    vesting.amount,
+                    vesting.unlock_tick,
+                )?;
+                state.update_nonce(&vesting.sender, vesting.nonce);
+            }
+            Transaction::Propose(propose) => {
+                let parameter = GovernanceParameter::from_id(propose.parameter)?;
+                let id = match &propose.minter {
+                    Some(grant) => state.propose_minter(
+                        &propose.sender,
+                        &grant.denom,
+                        &grant.minter,
+                        propose.value == 1,
+                        propose.activation_tick,
+                    )?,
+                    None => state.propose(
+                        &propose.sender,
+                        parameter,
+                        propose.value,
+                        propose.activation_tick,
+                    )?,
+                };
+                state.update_nonce(&propose.sender, propose.nonce);
+                info!(
+                    "Proposal {} by {}: {:?} = {} from tick {}",
+                    id,
+                    hex::encode(&propose.sender[..8]),
+                    parameter,
+                    propose.value,
+                    propose.activation_tick
+                );
+            }
+            Transaction::Vote(vote) => {
+                state.vote(&vote.sender, vote.proposal_id, vote.approve)?;
+                state.update_nonce(&vote.sender, vote.nonce);
+            }
             Transaction::Mint(mint) => {
-                state.mint(&mint.sender, mint.amount)?;
+                state.apply_mint(&mint.sender, &mint.denom, mint.amount)?;
                 state.update_nonce(&mint.sender, mint.nonce);
             }
             Transaction::Stake(stake) => {
@@ -489,35 +1177,263 @@ impl TickProcessor {
                 state.record_puzzle_solution(&solve.sender, &solve.puzzle_id, &solve.proof)?;
                 state.update_nonce(&solve.sender, solve.nonce);
             }
+            Transaction::Unstake(unstake) => {
+                state.unstake(&unstake.sender, unstake.amount)?;
+                state.update_nonce(&unstake.sender, unstake.nonce);
+            }
+            Transaction::Evidence(evidence) => {
+                let burned = state.slash(&evidence.witness, evidence.tick_number)?;
+                state.update_nonce(&evidence.sender, evidence.nonce);
+                warn!(
+                    "Slashed witness {} for double-signing tick {}: burned {}",
+                    hex::encode(evidence.witness),
+                    evidence.tick_number,
+                    burned
+                );
+            }
+            Transaction::SubmitForeignEpoch(submit) => {
+                let epoch = decode_foreign_epoch(&submit.epoch)?;
+                let id = state.submit_foreign_epoch(&submit.client_id, &epoch)?;
+                state.update_nonce(&submit.sender, submit.nonce);
+                info!(
+                    "Foreign client {} verified epoch {}",
+                    hex::encode(&id[..8]),
+                    epoch.summary.epoch_number
+                );
+            }
+            Transaction::CreateAuction(create) => {
+                let id = state.create_auction(
+                    &create.sender,
+                    create.item.clone(),
+                    create.reserve_price,
+                    create.close_tick,
+                )?;
+                state.update_nonce(&create.sender, create.nonce);
+                info!(
+                    "Auction {} by {} closes at tick {}",
+                    id,
+                    hex::encode(&create.sender[..8]),
+                    create.close_tick
+                );
+            }
+            Transaction::Bid(bid) => {
+                state.place_bid(&bid.sender, bid.auction_id, bid.amount)?;
+                state.update_nonce(&bid.sender, bid.nonce);
+            }
+            Transaction::SetGuardians(set) => {
+                state.set_guardians(
+                    &set.sender,
+                    set.guardians.clone(),
+                    set.threshold,
+                    set.delay_ticks,
+                )?;
+                state.update_nonce(&set.sender, set.nonce);
+            }
+            Transaction::RequestRecovery(request) => {
+                let activation =
+                    state.request_recovery(&request.sender, &request.account, &request.new_owner)?;
+                state.update_nonce(&request.sender, request.nonce);
+                if let Some(tick) = activation {
+                    info!(
+                        "Recovery of {} to {} activates at tick {}",
+                        hex::encode(&request.account[..8]),
+                        hex::encode(&request.new_owner[..8]),
+                        tick
+                    );
+                }
+            }
+            Transaction::VetoRecovery(veto) => {
+                state.veto_recovery(&veto.sender)?;
+                state.update_nonce(&veto.sender, veto.nonce);
+            }
+            Transaction::TransferMintAuthority(transfer) => {
+                state.transfer_mint_authority(
+                    &transfer.sender,
+                    &transfer.denom,
+                    &transfer.new_authority,
+                )?;
+                state.update_nonce(&transfer.sender, transfer.nonce);
+                info!(
+                    "Issuance of denom {} moved to {}",
+                    hex::encode(&transfer.denom[..8]),
+                    hex::encode(&transfer.new_authority[..8])
+                );
+            }
         }
-
         Ok(())
     }
 
-    async fn create_unified_certificate(
+    /// Take the transaction `tx`'s sender holds for the nonce after it
+    ///
+    /// Held transactions were canonically encoded when held, so one that no
+    /// longer decodes is dropped rather than released.
+    fn release_held(tx: &Transaction, state: &mut ChainState) -> Option<([u8; 32], Transaction)> {
+        let held = state.release_held_transaction(tx.sender())?;
+        match flatbuffer_to_transaction(&held.transaction) {
+            Ok(next) => Some((held.envelope_hash, next)),
+            Err(e) => {
+                warn!("Dropping undecodable held transaction: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Accounts whose state `apply_transaction` changes for `tx`
+    fn modified_accounts(tx: &Transaction, state: &ChainState) -> Vec<[u8; 32]> {
+        let mut accounts = Self::body_accounts(tx, state);
+        if tx.is_sponsored() && !accounts.contains(tx.gas_sponsorer()) {
+            accounts.push(*tx.gas_sponsorer());
+        }
+        accounts
+    }
+
+    /// Accounts the body of `tx` changes, leaving out a sponsor paying its fee
+    fn body_accounts(tx: &Transaction, state: &ChainState) -> Vec<[u8; 32]> {
+        match tx {
+            Transaction::Send(send) if send.receiver != send.sender => vec![send.sender, send.receiver],
+            Transaction::Send(send) => vec![send.sender],
+            Transaction::MultiSend(multi_send) => {
+                let mut accounts = vec![multi_send.sender];
+                for output in &multi_send.outputs {
+                    if !accounts.contains(&output.receiver) {
+                        accounts.push(output.receiver);
+                    }
+                }
+                accounts
+            }
+            // The receiver is only credited once the transfer unlocks
+            Transaction::VestingTransfer(vesting) => vec![vesting.sender],
+            Transaction::Propose(propose) => vec![propose.sender],
+            Transaction::Vote(vote) => vec![vote.sender],
+            Transaction::Mint(mint) => vec![mint.sender],
+            Transaction::Stake(stake) => vec![stake.sender],
+            Transaction::Solve(solve) => vec![solve.sender],
+            Transaction::Unstake(unstake) => vec![unstake.sender],
+            Transaction::Evidence(evidence) => {
+                let mut accounts = state.delegators(&evidence.witness);
+                if !accounts.contains(&evidence.sender) {
+                    accounts.push(evidence.sender);
+                }
+                accounts
+            }
+            Transaction::SubmitForeignEpoch(submit) => vec![submit.sender],
+            // The seller is only paid once the auction settles
+            Transaction::CreateAuction(create) => vec![create.sender],
+            Transaction::Bid(bid) => vec![bid.sender],
+            Transaction::SetGuardians(set) => vec![set.sender],
+            // The account only moves once the recovery activates
+            Transaction::RequestRecovery(request) => vec![request.sender],
+            Transaction::VetoRecovery(veto) => vec![veto.sender],
+            Transaction::TransferMintAuthority(transfer) => vec![transfer.sender],
+        }
+    }
+
+    /// Apply the state changes due when `tick_num` finalizes
+    ///
+    /// Credits the vesting transfers unlocking at the tick, settles the
+    /// auctions closing at it, carries out the account recoveries
+    /// activating at it, then tallies and executes governance proposals.
+    fn finalize_state(tick_num: u64, state: &mut ChainState) {
+        for schedule in state.release_vesting(tick_num) {
+            info!(
+                "Tick {}: Released {} vested from {} to {}",
+                tick_num,
+                schedule.amount,
+                hex::encode(&schedule.sender[..8]),
+                hex::encode(&schedule.receiver[..8])
+            );
+        }
+        for settlement in state.settle_auctions(tick_num) {
+            info!(
+                "Tick {}: Auction {} closed as {:?}, {} bids refunded",
+                tick_num,
+                settlement.auction_id,
+                settlement.status,
+                settlement.refunds.len()
+            );
+        }
+        for recovery in state.execute_recoveries(tick_num) {
+            info!(
+                "Tick {}: Recovered {} to {}, moving {}",
+                tick_num,
+                hex::encode(&recovery.account[..8]),
+                hex::encode(&recovery.new_owner[..8]),
+                recovery.amount
+            );
+        }
+        for (id, status) in state.finalize_governance(tick_num) {
+            info!("Tick {}: Proposal {} is now {:?}", tick_num, id, status);
+        }
+    }
+
+    /// Classify a tick before its envelopes are ordered
+    ///
+    /// A tick with envelopes none of which has a canonical timestamp backed
+    /// by more than 2/3 of the witness weight is a [`TickType::Checkpoint`],
+    /// and applies none of them. Canonical timestamps rest on signed
+    /// observations from a quorum, so the classification follows what the
+    /// witnesses agreed on rather than what reached this node. Otherwise the
+    /// tick is provisionally [`TickType::Full`], and becomes
+    /// [`TickType::Empty`] if no transaction applies.
+    ///
+    /// # Parameters
+    ///
+    /// - `envelopes`: Envelopes targeting the tick
+    /// - `canonical`: Agreed arrival iterations of envelopes
+    /// - `witnesses`: The witness set of the tick
+    ///
+    /// # Returns
+    ///
+    /// [`TickType::Checkpoint`] for a partial tick, [`TickType::Full`]
+    /// otherwise
+    pub fn tick_type(
+        envelopes: &[TimelockTransaction],
+        canonical: &[CanonicalTimestamp],
+        witnesses: &WitnessSet,
+    ) -> TickType {
+        let agreed = |tx: &TimelockTransaction| {
+            let envelope_hash = tx.envelope_hash();
+            canonical.iter().any(|timestamp| {
+                timestamp.envelope_hash == envelope_hash
+                    && witnesses.is_quorum(timestamp.observed_weight)
+            })
+        };
+        if !envelopes.is_empty() && !envelopes.iter().any(agreed) {
+            TickType::Checkpoint
+        } else {
+            TickType::Full
+        }
+    }
+
+    async fn create_unified_certificate<V: TickEngine>(
         &self,
         tick_num: u64,
+        tick_type: TickType,
         transactions: Vec<Transaction>,
         tx_merkle_root: [u8; 32],
-        vdf: Arc<RwLock<EternalVDF>>,
+        vdf: Arc<RwLock<V>>,
         state: Arc<RwLock<ChainState>>,
     ) -> Result<TickCertificate> {
         let vdf_read = vdf.read().await;
         let state_read = state.read().await;
 
         // Get VDF tick certificate if available
-        let vdf_tick_cert = vdf_read.get_tick_certificate(tick_num);
+        let vdf_tick_cert = vdf_read.prove(tick_num);
 
         // Determine tick type based on paper's classification
-        let tick_type = if transactions.is_empty() {
-            TickType::Empty
-        } else {
-            TickType::Full
+        let tick_type = match tick_type {
+            TickType::Full if transactions.is_empty() => TickType::Empty,
+            tick_type => tick_type,
         };
 
         // Get VDF state from checkpoint
         let vdf_checkpoint = vdf_read.checkpoint();
 
+        let mut usage = ModuleUsage::default();
+        for tx in &transactions {
+            usage.record_native(tx.module());
+        }
+
         // Create unified certificate that combines VDF and consensus data
         let certificate = TickCertificate {
             tick_number: tick_num,
@@ -534,6 +1450,7 @@ impl TickProcessor {
             transaction_merkle_root: tx_merkle_root,
             timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
             previous_tick_hash: state_read.last_tick_hash,
+            usage,
         };
 
         // Compute tick hash
@@ -555,6 +1472,53 @@ impl TickProcessor {
                 hasher.update(&send.amount.to_le_bytes());
                 hasher.update(&send.nonce.to_le_bytes());
                 hasher.update(&send.signature);
+                // Memos are committed to the tick's transaction root
+                if !send.memo.is_empty() {
+                    hasher.update((send.memo.len() as u32).to_le_bytes());
+                    hasher.update(&send.memo);
+                }
+            }
+            Transaction::MultiSend(multi_send) => {
+                hasher.update(b"multisend");
+                hasher.update(multi_send.sender);
+                hasher.update(multi_send.denom);
+                for output in &multi_send.outputs {
+                    hasher.update(output.receiver);
+                    hasher.update(output.amount.to_le_bytes());
+                }
+                hasher.update(multi_send.nonce.to_le_bytes());
+                hasher.update(&multi_send.signature);
+            }
+            Transaction::VestingTransfer(vesting) => {
+                hasher.update(b"vesting");
+                hasher.update(vesting.sender);
+                hasher.update(vesting.receiver);
+                hasher.update(vesting.denom);
+                hasher.update(vesting.amount.to_le_bytes());
+                hasher.update(vesting.unlock_tick.to_le_bytes());
+                hasher.update(vesting.nonce.to_le_bytes());
+                hasher.update(&vesting.signature);
+            }
+            Transaction::Propose(propose) => {
+                hasher.update(b"propose");
+                hasher.update(propose.sender);
+                hasher.update(propose.parameter.to_le_bytes());
+                hasher.update(propose.value.to_le_bytes());
+                hasher.update(propose.activation_tick.to_le_bytes());
+                if let Some(grant) = &propose.minter {
+                    hasher.update(grant.denom);
+                    hasher.update(grant.minter);
+                }
+                hasher.update(propose.nonce.to_le_bytes());
+                hasher.update(&propose.signature);
+            }
+            Transaction::Vote(vote) => {
+                hasher.update(b"vote");
+                hasher.update(vote.sender);
+                hasher.update(vote.proposal_id.to_le_bytes());
+                hasher.update([vote.approve as u8]);
+                hasher.update(vote.nonce.to_le_bytes());
+                hasher.update(&vote.signature);
             }
             Transaction::Mint(mint) => {
                 hasher.update(b"mint");
@@ -580,6 +1544,93 @@ impl TickProcessor {
                 hasher.update(&solve.nonce.to_le_bytes());
                 hasher.update(&solve.signature);
             }
+            Transaction::Unstake(unstake) => {
+                hasher.update(b"unstake");
+                hasher.update(unstake.sender);
+                hasher.update(unstake.amount.to_le_bytes());
+                hasher.update(unstake.nonce.to_le_bytes());
+                hasher.update(&unstake.signature);
+            }
+            Transaction::Evidence(evidence) => {
+                hasher.update(b"evidence");
+                hasher.update(evidence.sender);
+                hasher.update(evidence.witness);
+                hasher.update(evidence.tick_number.to_le_bytes());
+                hasher.update(evidence.first_hash);
+                hasher.update(evidence.second_hash);
+                hasher.update(evidence.nonce.to_le_bytes());
+                hasher.update(&evidence.signature);
+            }
+            Transaction::SubmitForeignEpoch(submit) => {
+                hasher.update(b"foreign_epoch");
+                hasher.update(submit.sender);
+                hasher.update(submit.client_id);
+                hasher.update((submit.epoch.len() as u64).to_le_bytes());
+                hasher.update(&submit.epoch);
+                hasher.update(submit.nonce.to_le_bytes());
+                hasher.update(&submit.signature);
+            }
+            Transaction::CreateAuction(create) => {
+                hasher.update(b"create_auction");
+                hasher.update(create.sender);
+                hasher.update((create.item.len() as u64).to_le_bytes());
+                hasher.update(&create.item);
+                hasher.update(create.reserve_price.to_le_bytes());
+                hasher.update(create.close_tick.to_le_bytes());
+                hasher.update(create.nonce.to_le_bytes());
+                hasher.update(&create.signature);
+            }
+            Transaction::Bid(bid) => {
+                hasher.update(b"bid");
+                hasher.update(bid.sender);
+                hasher.update(bid.auction_id.to_le_bytes());
+                hasher.update(bid.amount.to_le_bytes());
+                hasher.update(bid.nonce.to_le_bytes());
+                hasher.update(&bid.signature);
+            }
+            Transaction::SetGuardians(set) => {
+                hasher.update(b"set_guardians");
+                hasher.update(set.sender);
+                hasher.update((set.guardians.len() as u64).to_le_bytes());
+                for guardian in &set.guardians {
+                    hasher.update(guardian);
+                }
+                hasher.update(set.threshold.to_le_bytes());
+                hasher.update(set.delay_ticks.to_le_bytes());
+                hasher.update(set.nonce.to_le_bytes());
+                hasher.update(&set.signature);
+            }
+            Transaction::RequestRecovery(request) => {
+                hasher.update(b"request_recovery");
+                hasher.update(request.sender);
+                hasher.update(request.account);
+                hasher.update(request.new_owner);
+                hasher.update(request.nonce.to_le_bytes());
+                hasher.update(&request.signature);
+            }
+            Transaction::VetoRecovery(veto) => {
+                hasher.update(b"veto_recovery");
+                hasher.update(veto.sender);
+                hasher.update(veto.nonce.to_le_bytes());
+                hasher.update(&veto.signature);
+            }
+            Transaction::TransferMintAuthority(transfer) => {
+                hasher.update(b"transfer_mint_authority");
+                hasher.update(transfer.sender);
+                hasher.update(transfer.denom);
+                hasher.update(transfer.new_authority);
+                hasher.update(transfer.nonce.to_le_bytes());
+                hasher.update(&transfer.signature);
+            }
+        }
+        // A sponsor is committed along with its agreement to pay
+        if tx.is_sponsored() {
+            hasher.update(tx.gas_sponsorer());
+            hasher.update(tx.sponsor_signature());
+        }
+        if tx.tip() > 0 {
+            hasher.update(b"tip");
+            hasher.update(tx.tip().to_le_bytes());
         }
 
         hasher.finalize().into()
@@ -610,7 +1661,7 @@ impl TickProcessor {
     /// # Parameters
     ///
     /// - `tick_num`: The tick number that failed to process normally
-    /// - `vdf`: Shared reference to the eternal VDF computation
+    /// - `vdf`: Shared reference to the VDF engine
     /// - `state`: Shared reference to the blockchain state
     /// - `current_iteration`: Current VDF iteration when failure occurred
     ///
@@ -620,10 +1671,10 @@ impl TickProcessor {
     /// - Contains no transactions but maintains VDF proof
     /// - Preserves chain continuity and timing
     /// - Allows the network to continue operation
-    pub async fn handle_tick_failure(
+    pub async fn handle_tick_failure<V: TickEngine>(
         &self,
         tick_num: u64,
-        vdf: Arc<RwLock<EternalVDF>>,
+        vdf: Arc<RwLock<V>>,
         state: Arc<RwLock<ChainState>>,
         current_iteration: u64,
     ) -> Result<TickCertificate> {
@@ -665,6 +1716,7 @@ impl TickProcessor {
             transaction_merkle_root: [0; 32],
             timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
             previous_tick_hash: state_read.last_tick_hash,
+            usage: ModuleUsage::default(),
         };
 
         let mut cert_with_hash = certificate;
@@ -673,6 +1725,7 @@ impl TickProcessor {
         // Update state even for checkpoint
         drop(state_read);
         let mut state_write = state.write().await;
+        Self::finalize_state(tick_num, &mut state_write);
         state_write.current_tick = tick_num + 1;
         state_write.last_tick_hash = cert_with_hash.tick_hash;
 
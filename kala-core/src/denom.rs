@@ -0,0 +1,110 @@
+//! Denomination registry
+//!
+//! Display metadata of the denominations a node knows, configured through
+//! [`NodeConfig::denoms`](crate::config::NodeConfig::denoms). RPC responses
+//! carry the metadata next to raw amounts so front-ends don't hardcode
+//! token parameters. Account balances are held in the native denomination,
+//! the first registry entry, which defaults to [`NATIVE_SYMBOL`] with
+//! [`NATIVE_DECIMALS`] decimals.
+
+use kala_rpc::DenomMetadata;
+
+/// Symbol of the native denomination when none is configured
+pub const NATIVE_SYMBOL: &str = "KALA";
+
+/// Decimals of the native denomination when none is configured
+pub const NATIVE_DECIMALS: u8 = 9;
+
+/// Known denominations, keyed by their 32-byte ID
+///
+/// # Example
+///
+/// ```
+/// use kala_core::denom::{DenomRegistry, NATIVE_SYMBOL};
+///
+/// let registry = DenomRegistry::default();
+/// assert_eq!(registry.native().symbol, NATIVE_SYMBOL);
+/// assert!(registry.get(&[0u8; 32]).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DenomRegistry {
+    /// Native denomination first
+    denoms: Vec<([u8; 32], DenomMetadata)>,
+}
+
+impl DenomRegistry {
+    /// Create a registry from denominations, the native one first
+    ///
+    /// Without denominations the registry holds the default native one.
+    pub fn new(denoms: Vec<([u8; 32], DenomMetadata)>) -> Self {
+        if denoms.is_empty() {
+            return Self::default();
+        }
+        Self { denoms }
+    }
+
+    /// Metadata of the denomination account balances are held in
+    pub fn native(&self) -> &DenomMetadata {
+        &self.denoms[0].1
+    }
+
+    /// Metadata of a denomination by ID
+    pub fn get(&self, denom: &[u8; 32]) -> Option<&DenomMetadata> {
+        self.denoms
+            .iter()
+            .find(|(id, _)| id == denom)
+            .map(|(_, metadata)| metadata)
+    }
+
+    /// All denominations, the native one first
+    pub fn denoms(&self) -> impl Iterator<Item = &DenomMetadata> {
+        self.denoms.iter().map(|(_, metadata)| metadata)
+    }
+}
+
+impl Default for DenomRegistry {
+    fn default() -> Self {
+        let id = [0u8; 32];
+        Self {
+            denoms: vec![(
+                id,
+                DenomMetadata {
+                    denom: hex::encode(id),
+                    symbol: NATIVE_SYMBOL.to_string(),
+                    name: "Kala".to_string(),
+                    decimals: NATIVE_DECIMALS,
+                },
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(id: [u8; 32], symbol: &str, decimals: u8) -> ([u8; 32], DenomMetadata) {
+        (
+            id,
+            DenomMetadata {
+                denom: hex::encode(id),
+                symbol: symbol.to_string(),
+                name: symbol.to_string(),
+                decimals,
+            },
+        )
+    }
+
+    #[test]
+    fn test_first_denom_is_native() {
+        let registry = DenomRegistry::new(vec![metadata([1u8; 32], "TIME", 6), metadata([2u8; 32], "USDK", 2)]);
+        assert_eq!(registry.native().symbol, "TIME");
+        assert_eq!(registry.get(&[2u8; 32]).unwrap().decimals, 2);
+        assert!(registry.get(&[0u8; 32]).is_none());
+        assert_eq!(registry.denoms().count(), 2);
+
+        let registry = DenomRegistry::new(Vec::new());
+        assert_eq!(registry.native().decimals, NATIVE_DECIMALS);
+        assert_eq!(registry.native().format_amount(2_250_000_000), "2.25");
+    }
+}
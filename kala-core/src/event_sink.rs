@@ -0,0 +1,535 @@
+//! Node events published to message queues
+//!
+//! [`EventSink`] is a [`NodeObserver`] that publishes node events as JSON
+//! to the queues configured in
+//! [`NodeConfig::event_sinks`](crate::NodeConfig::event_sinks), for systems
+//! consuming the timeline through an existing event pipeline:
+//!
+//! - Kafka topics, through a Confluent-compatible REST proxy
+//! - NATS subjects, over the NATS client protocol
+//! - Redis streams, appended to with `XADD`
+//!
+//! Each event is a [`NodeEvent`] tagged with its kind, such as
+//! `{"event": "tick", ...}`. Publishing runs in a background task so the
+//! tick loop never waits on a broker. Delivery is at most once: events are
+//! dropped while the queue is full or a broker is unreachable, and broker
+//! connections are reopened for the next event.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::config::EventSinkConfig;
+use crate::observer::{AccountChange, NodeObserver};
+use kala_state::{Account, DoubleSignEvidence, TickCertificate};
+
+/// Events waiting for publication before new ones are dropped
+const EVENT_QUEUE_SIZE: usize = 1024;
+
+/// Time allowed to publish one event to one broker
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A node event as published
+#[derive(Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NodeEvent {
+    /// A tick was produced and stored
+    Tick {
+        /// The stored certificate
+        certificate: Box<TickCertificate>,
+        /// Wall-clock time the tick took, in milliseconds
+        duration_ms: u64,
+    },
+    /// A tick reached finality
+    TickFinalized {
+        /// The finalized tick
+        tick_number: u64,
+        /// Witness weight that signed it
+        signed_weight: u64,
+        /// Total weight of the witness set
+        total_weight: u64,
+    },
+    /// Producing a tick failed and the node stopped
+    TickFailed {
+        /// The failed tick
+        tick_number: u64,
+        /// Why it failed
+        error: String,
+    },
+    /// No tick was produced for `idle_secs`
+    Stall {
+        /// The overdue tick
+        next_tick: u64,
+        /// Seconds since the last tick
+        idle_secs: u64,
+    },
+    /// A witness voted for two hashes at one tick
+    DoubleSign {
+        /// Tick of the conflicting votes
+        tick_number: u64,
+        /// Hex-encoded key of the witness
+        witness: String,
+    },
+    /// Transactions in a tick modified accounts
+    AccountsChanged {
+        /// The tick that applied the transactions
+        tick_number: u64,
+        /// One entry per modified account
+        accounts: Vec<ChangedAccount>,
+    },
+}
+
+impl NodeEvent {
+    /// Kind of the event, as in its `event` tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Tick { .. } => "tick",
+            Self::TickFinalized { .. } => "tick_finalized",
+            Self::TickFailed { .. } => "tick_failed",
+            Self::Stall { .. } => "stall",
+            Self::DoubleSign { .. } => "double_sign",
+            Self::AccountsChanged { .. } => "accounts_changed",
+        }
+    }
+}
+
+/// An account in a [`NodeEvent::AccountsChanged`] event
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedAccount {
+    /// Hex-encoded address
+    pub address: String,
+    /// The account after the tick
+    pub account: Account,
+    /// Hex-encoded envelope hashes of the transactions that modified it
+    pub tx_hashes: Vec<String>,
+}
+
+impl From<&AccountChange> for ChangedAccount {
+    fn from(change: &AccountChange) -> Self {
+        Self {
+            address: hex::encode(change.address),
+            account: change.account.clone(),
+            tx_hashes: change.tx_hashes.iter().map(hex::encode).collect(),
+        }
+    }
+}
+
+/// Node observer publishing events to the configured message queues
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_core::config::EventSinkConfig;
+/// use kala_core::event_sink::EventSink;
+/// use kala_core::{KalaNode, NodeConfig};
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let sink = EventSink::spawn(&[EventSinkConfig::Nats {
+///     address: "127.0.0.1:4222".to_string(),
+///     subject: "kala.events".to_string(),
+/// }])?;
+/// let node = KalaNode::new(NodeConfig::default()).await?.with_observer(sink);
+/// # Ok(())
+/// # }
+/// ```
+pub struct EventSink {
+    outbox: mpsc::Sender<NodeEvent>,
+}
+
+impl EventSink {
+    /// Create a sink and start its publishing task
+    ///
+    /// # Errors
+    ///
+    /// Fails if the HTTP client for Kafka cannot be created.
+    pub fn spawn(configs: &[EventSinkConfig]) -> Result<Arc<Self>> {
+        let client = reqwest::Client::builder()
+            .timeout(PUBLISH_TIMEOUT)
+            .build()
+            .map_err(|e| anyhow!("Failed to create event sink client: {}", e))?;
+        let mut publishers: Vec<Publisher> = configs
+            .iter()
+            .map(|config| Publisher::new(config, &client))
+            .collect();
+        let (sink, mut inbox) = Self::new();
+
+        tokio::spawn(async move {
+            while let Some(event) = inbox.recv().await {
+                let value = match serde_json::to_value(&event) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        warn!("Failed to encode {} event: {}", event.kind(), e);
+                        continue;
+                    }
+                };
+                for publisher in &mut publishers {
+                    let published = tokio::time::timeout(
+                        PUBLISH_TIMEOUT,
+                        publisher.publish(event.kind(), &value),
+                    )
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow!("timed out")));
+                    if let Err(e) = published {
+                        publisher.disconnect();
+                        warn!("Failed to publish {} event to {}: {}", event.kind(), publisher, e);
+                    }
+                }
+            }
+        });
+        Ok(Arc::new(sink))
+    }
+
+    fn new() -> (Self, mpsc::Receiver<NodeEvent>) {
+        let (outbox, inbox) = mpsc::channel(EVENT_QUEUE_SIZE);
+        (Self { outbox }, inbox)
+    }
+
+    /// Queue an event for the publishing task
+    fn publish(&self, event: NodeEvent) {
+        let kind = event.kind();
+        if self.outbox.try_send(event).is_err() {
+            warn!("Event queue full, dropping {} event", kind);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeObserver for EventSink {
+    async fn on_tick(&self, certificate: &TickCertificate, duration: Duration) {
+        self.publish(NodeEvent::Tick {
+            certificate: Box::new(certificate.clone()),
+            duration_ms: duration.as_millis() as u64,
+        });
+    }
+
+    async fn on_tick_failed(&self, tick_number: u64, error: &str) {
+        self.publish(NodeEvent::TickFailed {
+            tick_number,
+            error: error.to_string(),
+        });
+    }
+
+    async fn on_stall(&self, next_tick: u64, idle: Duration) {
+        self.publish(NodeEvent::Stall {
+            next_tick,
+            idle_secs: idle.as_secs(),
+        });
+    }
+
+    async fn on_tick_finalized(&self, tick_number: u64, signed_weight: u64, total_weight: u64) {
+        self.publish(NodeEvent::TickFinalized {
+            tick_number,
+            signed_weight,
+            total_weight,
+        });
+    }
+
+    async fn on_double_sign(&self, evidence: &DoubleSignEvidence) {
+        self.publish(NodeEvent::DoubleSign {
+            tick_number: evidence.tick_number(),
+            witness: hex::encode(evidence.witness()),
+        });
+    }
+
+    async fn on_accounts_changed(&self, tick_number: u64, changes: &[AccountChange]) {
+        self.publish(NodeEvent::AccountsChanged {
+            tick_number,
+            accounts: changes.iter().map(ChangedAccount::from).collect(),
+        });
+    }
+}
+
+/// One configured message queue
+enum Publisher {
+    Kafka {
+        client: reqwest::Client,
+        /// REST proxy endpoint of the topic
+        url: String,
+    },
+    Nats {
+        address: String,
+        subject: String,
+        connection: Option<NatsConnection>,
+    },
+    Redis {
+        address: String,
+        stream: String,
+        max_len: Option<u64>,
+        connection: Option<BufReader<TcpStream>>,
+    },
+}
+
+impl fmt::Display for Publisher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kafka { url, .. } => write!(f, "Kafka {}", url),
+            Self::Nats { address, subject, .. } => write!(f, "NATS {} {}", address, subject),
+            Self::Redis { address, stream, .. } => write!(f, "Redis {} {}", address, stream),
+        }
+    }
+}
+
+impl Publisher {
+    fn new(config: &EventSinkConfig, client: &reqwest::Client) -> Self {
+        match config {
+            EventSinkConfig::Kafka { rest_proxy_url, topic } => Self::Kafka {
+                client: client.clone(),
+                url: format!("{}/topics/{}", rest_proxy_url.trim_end_matches('/'), topic),
+            },
+            EventSinkConfig::Nats { address, subject } => Self::Nats {
+                address: address.clone(),
+                subject: subject.clone(),
+                connection: None,
+            },
+            EventSinkConfig::Redis { address, stream, max_len } => Self::Redis {
+                address: address.clone(),
+                stream: stream.clone(),
+                max_len: *max_len,
+                connection: None,
+            },
+        }
+    }
+
+    async fn publish(&mut self, kind: &str, event: &Value) -> Result<()> {
+        match self {
+            Self::Kafka { client, url } => {
+                let records = serde_json::json!({ "records": [{ "key": kind, "value": event }] });
+                client
+                    .post(url.as_str())
+                    .header(reqwest::header::CONTENT_TYPE, "application/vnd.kafka.json.v2+json")
+                    .body(records.to_string())
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                Ok(())
+            }
+            Self::Nats { address, subject, connection } => {
+                let open = match connection.take() {
+                    Some(open) => open,
+                    None => NatsConnection::open(address).await?,
+                };
+                connection.insert(open).publish(subject, event.to_string().as_bytes()).await
+            }
+            Self::Redis { address, stream, max_len, connection } => {
+                let open = match connection.take() {
+                    Some(open) => open,
+                    None => BufReader::new(TcpStream::connect(address.as_str()).await?),
+                };
+                let connection = connection.insert(open);
+                let payload = event.to_string();
+                let max_len = max_len.map(|max_len| max_len.to_string());
+                let mut args = vec!["XADD", stream.as_str()];
+                if let Some(max_len) = &max_len {
+                    args.extend(["MAXLEN", "~", max_len.as_str()]);
+                }
+                args.extend(["*", "event", kind, "data", payload.as_str()]);
+                redis_command(connection, &args).await
+            }
+        }
+    }
+
+    /// Drop the broker connection, to be reopened for the next event
+    fn disconnect(&mut self) {
+        match self {
+            Self::Kafka { .. } => {}
+            Self::Nats { connection, .. } => *connection = None,
+            Self::Redis { connection, .. } => *connection = None,
+        }
+    }
+}
+
+/// A connection to a NATS server
+struct NatsConnection {
+    stream: TcpStream,
+    /// Server lines received but not yet complete
+    received: Vec<u8>,
+}
+
+impl NatsConnection {
+    async fn open(address: &str) -> Result<Self> {
+        let mut stream = BufReader::new(TcpStream::connect(address).await?);
+        let mut info = String::new();
+        stream.read_line(&mut info).await?;
+        if !info.starts_with("INFO") {
+            bail!("unexpected greeting {:?}", info.trim_end());
+        }
+        // Lines sent along with the greeting, such as a first ping
+        let received = stream.buffer().to_vec();
+        let mut stream = stream.into_inner();
+        stream
+            .write_all(b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"kala\"}\r\n")
+            .await?;
+        Ok(Self { stream, received })
+    }
+
+    async fn publish(&mut self, subject: &str, payload: &[u8]) -> Result<()> {
+        self.answer_pings().await?;
+        let mut frame = format!("PUB {} {}\r\n", subject, payload.len()).into_bytes();
+        frame.extend_from_slice(payload);
+        frame.extend_from_slice(b"\r\n");
+        self.stream.write_all(&frame).await?;
+        Ok(())
+    }
+
+    /// Answer the keep-alive pings the server sent since the last event
+    ///
+    /// The server closes connections that leave its pings unanswered, and
+    /// the sink only talks to it when there is an event to publish.
+    async fn answer_pings(&mut self) -> Result<()> {
+        let mut buffer = [0u8; 1024];
+        loop {
+            match self.stream.try_read(&mut buffer) {
+                Ok(0) => bail!("connection closed"),
+                Ok(read) => self.received.extend_from_slice(&buffer[..read]),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        while let Some(end) = self.received.windows(2).position(|window| window == b"\r\n") {
+            let line: Vec<u8> = self.received.drain(..end + 2).collect();
+            if line.starts_with(b"PING") {
+                self.stream.write_all(b"PONG\r\n").await?;
+            } else if line.starts_with(b"-ERR") {
+                bail!("{}", String::from_utf8_lossy(&line).trim_end());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Send a Redis command and check its reply isn't an error
+async fn redis_command(connection: &mut BufReader<TcpStream>, args: &[&str]) -> Result<()> {
+    let mut command = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        command.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        command.extend_from_slice(arg.as_bytes());
+        command.extend_from_slice(b"\r\n");
+    }
+    connection.get_mut().write_all(&command).await?;
+
+    let mut reply = String::new();
+    if connection.read_line(&mut reply).await? == 0 {
+        bail!("connection closed");
+    }
+    match reply.as_bytes().first() {
+        // XADD answers with the entry ID as a bulk string
+        Some(b'$') => {
+            let len: i64 = reply[1..].trim_end().parse()?;
+            if len >= 0 {
+                let mut id = vec![0u8; len as usize + 2];
+                connection.read_exact(&mut id).await?;
+            }
+            Ok(())
+        }
+        Some(b'-') => bail!("{}", reply[1..].trim_end()),
+        _ => bail!("unexpected reply {:?}", reply.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::TickType;
+    use tokio::net::TcpListener;
+
+    fn certificate(tick_number: u64) -> TickCertificate {
+        TickCertificate {
+            tick_number,
+            tick_type: TickType::Empty,
+            vdf_iteration: 0,
+            vdf_form: ("1".into(), "0".into(), "1".into()),
+            hash_chain_value: [0u8; 32],
+            tick_hash: [0u8; 32],
+            transaction_count: 0,
+            transaction_merkle_root: [0u8; 32],
+            timestamp: 0,
+            previous_tick_hash: [0u8; 32],
+            usage: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_queued_with_their_kind() {
+        let (sink, mut events) = EventSink::new();
+        sink.on_tick(&certificate(7), Duration::from_millis(1500)).await;
+        sink.on_tick_finalized(7, 2, 3).await;
+
+        let event = serde_json::to_value(events.try_recv().unwrap()).unwrap();
+        assert_eq!(event["event"], "tick");
+        assert_eq!(event["certificate"]["tick_number"], 7);
+        assert_eq!(event["duration_ms"], 1500);
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.kind(), "tick_finalized");
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_publish_to_nats_and_redis() {
+        let event = serde_json::json!({ "event": "stall", "next_tick": 3, "idle_secs": 90 });
+
+        // A NATS server greets, pings, and reads the published frame
+        let nats = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = nats.local_addr().unwrap().to_string();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = nats.accept().await.unwrap();
+            socket.write_all(b"INFO {}\r\nPING\r\n").await.unwrap();
+            let mut received = Vec::new();
+            while !String::from_utf8_lossy(&received).contains("idle_secs") {
+                let mut buffer = [0u8; 256];
+                let read = socket.read(&mut buffer).await.unwrap();
+                received.extend_from_slice(&buffer[..read]);
+            }
+            String::from_utf8(received).unwrap()
+        });
+        let mut publisher = Publisher::new(
+            &EventSinkConfig::Nats {
+                address,
+                subject: "kala.events".to_string(),
+            },
+            &reqwest::Client::new(),
+        );
+        // Let the ping arrive before publishing
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        publisher.publish("stall", &event).await.unwrap();
+        let received = server.await.unwrap();
+        assert!(received.starts_with("CONNECT "));
+        assert!(received.contains("PONG\r\n"));
+        assert!(received.contains(&format!("PUB kala.events {}\r\n", event.to_string().len())));
+
+        // A Redis server answers XADD with the entry ID
+        let redis = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = redis.local_addr().unwrap().to_string();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = redis.accept().await.unwrap();
+            let mut buffer = [0u8; 512];
+            let read = socket.read(&mut buffer).await.unwrap();
+            socket.write_all(b"$15\r\n1700000000000-0\r\n").await.unwrap();
+            let command = String::from_utf8_lossy(&buffer[..read]).to_string();
+            let _ = socket.read(&mut buffer).await.unwrap();
+            socket.write_all(b"-ERR wrong type\r\n").await.unwrap();
+            command
+        });
+        let mut publisher = Publisher::new(
+            &EventSinkConfig::Redis {
+                address,
+                stream: "kala:events".to_string(),
+                max_len: Some(1000),
+            },
+            &reqwest::Client::new(),
+        );
+        publisher.publish("stall", &event).await.unwrap();
+        assert!(publisher.publish("stall", &event).await.is_err());
+        let command = server.await.unwrap();
+        assert!(command.starts_with("*10\r\n$4\r\nXADD\r\n$11\r\nkala:events\r\n$6\r\nMAXLEN\r\n"));
+        assert!(command.contains("$5\r\nevent\r\n$5\r\nstall\r\n$4\r\ndata\r\n"));
+    }
+}
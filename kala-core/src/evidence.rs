@@ -0,0 +1,220 @@
+//! Witness double-sign detection
+//!
+//! Every verified tick vote the node sees, gossiped or its own, passes
+//! through the [`DoubleSignDetector`], which remembers the first hash each
+//! witness signed at each recent tick. A vote for a second hash is
+//! equivocation: the detector pairs the two votes into
+//! [`DoubleSignEvidence`] and queues it. The node wraps queued evidence in
+//! an [`Evidence`] transaction signed by its witness signer and gossips the
+//! envelope like any other. Applying the transaction burns the stake
+//! delegated to the witness, so detection leads to punishment without
+//! anyone having to act on it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::{anyhow, Result};
+use kala_common::crypto::signer::{SignatureScheme, Signer};
+use kala_state::{DoubleSignEvidence, TickVote};
+use kala_transaction::{Evidence, Transaction, EMPTY64BYTES};
+
+use crate::finality::MAX_VOTE_LAG;
+
+/// Most evidence kept waiting for a tick that accepts its envelope
+pub const MAX_PENDING_EVIDENCE: usize = 64;
+
+/// Compares the votes of each witness per tick and collects evidence
+///
+/// Only votes whose signatures have been verified may be recorded, or a
+/// forged vote could hide a witness's real one.
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_core::evidence::DoubleSignDetector;
+/// # fn example(vote: kala_state::TickVote, conflicting: kala_state::TickVote) {
+/// let mut detector = DoubleSignDetector::new();
+/// assert!(detector.record(&vote).is_none());
+/// if let Some(evidence) = detector.record(&conflicting) {
+///     println!("witness double-signed tick {}", evidence.tick_number());
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct DoubleSignDetector {
+    /// First vote of each witness per tick
+    votes: BTreeMap<u64, HashMap<[u8; 32], TickVote>>,
+    /// Witnesses already caught at each tick
+    reported: BTreeSet<(u64, [u8; 32])>,
+    /// Evidence not yet submitted
+    pending: Vec<DoubleSignEvidence>,
+}
+
+impl DoubleSignDetector {
+    /// Create a detector with no recorded votes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a verified vote
+    ///
+    /// # Returns
+    ///
+    /// The evidence the first time the vote's witness is seen signing a
+    /// second hash for its tick. The evidence is also queued for
+    /// [`take_pending`](Self::take_pending).
+    pub fn record(&mut self, vote: &TickVote) -> Option<DoubleSignEvidence> {
+        let votes = self.votes.entry(vote.tick_number).or_default();
+        let first = match votes.get(&vote.witness) {
+            Some(first) => first.clone(),
+            None => {
+                votes.insert(vote.witness, vote.clone());
+                return None;
+            }
+        };
+
+        let evidence = DoubleSignEvidence::new(first, vote.clone())?;
+        if !self.reported.insert((vote.tick_number, vote.witness)) {
+            return None;
+        }
+        self.requeue(evidence.clone());
+        Some(evidence)
+    }
+
+    /// Take the evidence waiting to be submitted
+    pub fn take_pending(&mut self) -> Vec<DoubleSignEvidence> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Queue evidence again after a failed submission
+    ///
+    /// The queue is bounded; the oldest evidence is dropped when it's full.
+    pub fn requeue(&mut self, evidence: DoubleSignEvidence) {
+        if self.pending.len() >= MAX_PENDING_EVIDENCE {
+            self.pending.remove(0);
+        }
+        self.pending.push(evidence);
+    }
+
+    /// Forget votes for ticks too far behind `current_tick` to be voted on
+    ///
+    /// Queued evidence is kept, since it can be applied at any later tick.
+    pub fn prune(&mut self, current_tick: u64) {
+        let cutoff = current_tick.saturating_sub(MAX_VOTE_LAG);
+        self.votes = self.votes.split_off(&cutoff);
+        self.reported = self.reported.split_off(&(cutoff, [0u8; 32]));
+    }
+}
+
+/// Build the transaction submitting `evidence`, signed by `signer` for `chain_id`
+pub async fn evidence_transaction(
+    evidence: &DoubleSignEvidence,
+    signer: &dyn Signer,
+    nonce: u64,
+    chain_id: &str,
+) -> Result<Transaction> {
+    let sender: [u8; 32] = signer
+        .public_key(SignatureScheme::Ed25519)
+        .try_into()
+        .map_err(|_| anyhow!("Witness key must be 32 bytes"))?;
+    let mut tx = Evidence {
+        sender,
+        witness: evidence.witness(),
+        tick_number: evidence.tick_number(),
+        first_hash: evidence.first.tick_hash,
+        first_signature: evidence.first.signature.clone(),
+        second_hash: evidence.second.tick_hash,
+        second_signature: evidence.second.signature.clone(),
+        nonce,
+        signature: EMPTY64BYTES.to_vec(),
+        gas_sponsorer: sender,
+        sponsor_signature: Vec::new(),
+        tip: 0,
+    };
+    let message = Transaction::Evidence(tx.clone())
+        .signing_message(chain_id)
+        .map_err(|e| anyhow!("Failed to encode evidence: {}", e))?;
+    tx.signature = signer
+        .sign(SignatureScheme::Ed25519, &message)
+        .await
+        .map_err(|e| anyhow!("Failed to sign evidence: {}", e))?;
+    Ok(Transaction::Evidence(tx))
+}
+
+/// The conflicting votes an evidence transaction carries
+pub fn double_sign_evidence(tx: &Evidence) -> DoubleSignEvidence {
+    let vote = |tick_hash: [u8; 32], signature: &[u8]| TickVote {
+        tick_number: tx.tick_number,
+        tick_hash,
+        witness: tx.witness,
+        signature: signature.to_vec(),
+        metadata: None,
+        time: None,
+    };
+    DoubleSignEvidence {
+        first: vote(tx.first_hash, &tx.first_signature),
+        second: vote(tx.second_hash, &tx.second_signature),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_common::crypto::signer::InMemorySigner;
+    use kala_state::witness::{bls_public_key, derive_bls_key};
+    use kala_state::{Witness, WitnessSet};
+
+    fn vote(tick: u64, hash: [u8; 32]) -> TickVote {
+        TickVote::sign(tick, hash, [1u8; 32], &derive_bls_key(&[1u8; 32]).unwrap())
+    }
+
+    #[test]
+    fn test_detects_conflicting_votes_once() {
+        let mut detector = DoubleSignDetector::new();
+
+        assert!(detector.record(&vote(5, [1u8; 32])).is_none());
+        assert!(detector.record(&vote(5, [1u8; 32])).is_none());
+        assert!(detector.record(&vote(6, [2u8; 32])).is_none());
+
+        let evidence = detector.record(&vote(5, [2u8; 32])).unwrap();
+        assert_eq!(evidence.tick_number(), 5);
+        assert_eq!(evidence.witness(), [1u8; 32]);
+        // Further conflicting votes for the tick aren't reported again
+        assert!(detector.record(&vote(5, [3u8; 32])).is_none());
+
+        assert_eq!(detector.take_pending(), vec![evidence]);
+        assert!(detector.take_pending().is_empty());
+
+        // Pruned ticks start over, but queued evidence survives
+        detector.record(&vote(6, [3u8; 32])).unwrap();
+        detector.prune(6 + MAX_VOTE_LAG + 1);
+        assert!(detector.record(&vote(6, [2u8; 32])).is_none());
+        assert_eq!(detector.take_pending().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_evidence_transaction_roundtrip() {
+        let bls_key = derive_bls_key(&[1u8; 32]).unwrap();
+        let witnesses = WitnessSet::new(vec![Witness {
+            key: [1u8; 32],
+            bls_key: bls_public_key(&bls_key),
+            weight: 1,
+        }])
+        .unwrap();
+        let evidence = DoubleSignEvidence::new(vote(5, [1u8; 32]), vote(5, [2u8; 32])).unwrap();
+        let reporter = InMemorySigner::from_seed(&[9u8; 32]).unwrap();
+
+        let tx = evidence_transaction(&evidence, &reporter, 3, "kala-devnet").await.unwrap();
+        assert!(tx.verify_signature("kala-devnet").is_ok());
+        assert!(tx.verify_signature("kala-mainnet").is_err());
+        let tx = match tx {
+            Transaction::Evidence(tx) => tx,
+            _ => panic!("Expected an evidence transaction"),
+        };
+        assert_eq!(tx.sender[..], reporter.public_key(SignatureScheme::Ed25519)[..]);
+        assert_eq!(tx.nonce, 3);
+
+        let carried = double_sign_evidence(&tx);
+        assert_eq!(carried, evidence);
+        assert!(carried.verify(&witnesses).is_ok());
+    }
+}
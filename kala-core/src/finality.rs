@@ -0,0 +1,262 @@
+//! Witness signing round for tick finality
+//!
+//! Every witness signs each tick it produces with its BLS key and gossips
+//! the [`TickVote`]. The [`FinalityTracker`] collects verified votes from
+//! all witnesses, and once the witnesses agreeing with the local tick hash
+//! hold more than 2/3 of the total weight, aggregates their votes into a
+//! compact [`TickFinality`] record.
+//!
+//! Votes can arrive before the local node has produced the tick, so they
+//! are buffered per tick until the tick is known or falls too far behind.
+//! When the witness set rotates at an epoch boundary, buffered votes are
+//! dropped, since they were checked against the outgoing set.
+//!
+//! A witness node given a [`VoteMetadataSource`] attaches its reading to
+//! each vote, and the metadata of the agreeing votes ends up in the
+//! finality record.
+
+use std::collections::{BTreeMap, HashMap};
+
+use kala_common::error::{KalaError, KalaResult};
+use kala_state::{TickFinality, TickVote, WitnessSet};
+use tracing::debug;
+
+/// Supplies the metadata a witness attaches to its tick votes
+///
+/// A price feed or a light client of another chain, for instance. Blobs
+/// over [`kala_state::MAX_VOTE_METADATA_SIZE`] are not attached.
+#[async_trait::async_trait]
+pub trait VoteMetadataSource: Send + Sync {
+    /// Metadata for the vote on `tick_hash` at `tick_number`, if any
+    async fn metadata(&self, tick_number: u64, tick_hash: &[u8; 32]) -> Option<Vec<u8>>;
+}
+
+/// Votes are accepted for ticks at most this far ahead of the local head
+pub const MAX_VOTE_LEAD: u64 = 64;
+
+/// Unfinalized ticks further than this behind the local head are dropped
+pub const MAX_VOTE_LAG: u64 = 256;
+
+/// Collects witness votes and detects when ticks reach finality
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_core::finality::FinalityTracker;
+/// # fn example(witnesses: kala_state::WitnessSet, vote: kala_state::TickVote) {
+/// let mut tracker = FinalityTracker::new(witnesses);
+/// tracker.add_vote(vote, 10).unwrap();
+/// if let Some(finality) = tracker.try_finalize(10, [0u8; 32]) {
+///     println!("tick {} is final", finality.tick_number);
+/// }
+/// # }
+/// ```
+pub struct FinalityTracker {
+    witnesses: WitnessSet,
+    /// Votes per tick, keyed by witness so each witness counts once
+    pending: BTreeMap<u64, HashMap<[u8; 32], TickVote>>,
+    finalized: Option<u64>,
+}
+
+impl FinalityTracker {
+    /// Create a tracker for the given witness set
+    pub fn new(witnesses: WitnessSet) -> Self {
+        Self {
+            witnesses,
+            pending: BTreeMap::new(),
+            finalized: None,
+        }
+    }
+
+    /// The witness set votes are checked against
+    pub fn witnesses(&self) -> &WitnessSet {
+        &self.witnesses
+    }
+
+    /// Switch to the witness set of a new epoch
+    ///
+    /// Votes still awaiting quorum were verified against the outgoing set
+    /// and are discarded.
+    pub fn rotate(&mut self, witnesses: WitnessSet) {
+        if witnesses != self.witnesses {
+            self.witnesses = witnesses;
+            self.pending.clear();
+        }
+    }
+
+    /// Highest tick finalized so far
+    pub fn finalized_tick(&self) -> Option<u64> {
+        self.finalized
+    }
+
+    /// Verify and record a vote
+    ///
+    /// # Parameters
+    ///
+    /// - `vote`: The vote to record
+    /// - `current_tick`: The next tick the local node will produce
+    ///
+    /// # Returns
+    ///
+    /// `true` if the vote was new, `false` if it was a duplicate or for a
+    /// tick outside the accepted window. Invalid votes are an error.
+    pub fn add_vote(&mut self, vote: TickVote, current_tick: u64) -> KalaResult<bool> {
+        if vote.tick_number > current_tick + MAX_VOTE_LEAD
+            || vote.tick_number + MAX_VOTE_LAG < current_tick
+        {
+            return Ok(false);
+        }
+
+        let votes = self.pending.entry(vote.tick_number).or_default();
+        if let Some(existing) = votes.get(&vote.witness) {
+            if existing.tick_hash != vote.tick_hash {
+                // Signing two hashes for one tick is equivocation
+                return Err(KalaError::validation(format!(
+                    "Witness {} voted for two hashes at tick {}",
+                    hex::encode(vote.witness),
+                    vote.tick_number
+                )));
+            }
+            return Ok(false);
+        }
+
+        vote.verify(&self.witnesses)?;
+        votes.insert(vote.witness, vote);
+        Ok(true)
+    }
+
+    /// Aggregate votes for the local tick hash if they reach quorum
+    ///
+    /// Votes for other hashes at the same tick are ignored. Returns the
+    /// finality record the first time the tick reaches quorum.
+    pub fn try_finalize(&mut self, tick_number: u64, tick_hash: [u8; 32]) -> Option<TickFinality> {
+        let votes = self.pending.get(&tick_number)?;
+        let agreeing: Vec<TickVote> = votes
+            .values()
+            .filter(|vote| vote.tick_hash == tick_hash)
+            .cloned()
+            .collect();
+
+        let weight: u64 = agreeing
+            .iter()
+            .filter_map(|vote| self.witnesses.get(&vote.witness))
+            .map(|witness| witness.weight)
+            .sum();
+        if !self.witnesses.is_quorum(weight) {
+            return None;
+        }
+
+        match TickFinality::aggregate(&self.witnesses, &agreeing) {
+            Ok(finality) => {
+                self.pending.remove(&tick_number);
+                self.finalized = self.finalized.max(Some(tick_number));
+                Some(finality)
+            }
+            Err(e) => {
+                debug!("Failed to aggregate votes for tick {}: {}", tick_number, e);
+                None
+            }
+        }
+    }
+
+    /// Drop votes for ticks that fell too far behind `current_tick`
+    pub fn prune(&mut self, current_tick: u64) {
+        let cutoff = current_tick.saturating_sub(MAX_VOTE_LAG);
+        self.pending = self.pending.split_off(&cutoff);
+    }
+
+    /// Number of ticks with votes awaiting quorum
+    pub fn pending_ticks(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::witness::{bls_public_key, derive_bls_key, BlsSecretKey};
+    use kala_state::Witness;
+
+    fn setup(count: u8) -> (FinalityTracker, Vec<BlsSecretKey>) {
+        let keys: Vec<_> = (1..=count)
+            .map(|i| derive_bls_key(&[i; 32]).unwrap())
+            .collect();
+        let witnesses = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| Witness {
+                key: [i as u8 + 1; 32],
+                bls_key: bls_public_key(key),
+                weight: 1,
+            })
+            .collect();
+        (
+            FinalityTracker::new(WitnessSet::new(witnesses).unwrap()),
+            keys,
+        )
+    }
+
+    fn vote(keys: &[BlsSecretKey], index: usize, tick: u64, hash: [u8; 32]) -> TickVote {
+        TickVote::sign(tick, hash, [index as u8 + 1; 32], &keys[index])
+    }
+
+    #[test]
+    fn test_finality_after_two_thirds() {
+        let (mut tracker, keys) = setup(4);
+        let hash = [7u8; 32];
+
+        assert!(tracker.add_vote(vote(&keys, 0, 5, hash), 5).unwrap());
+        assert!(!tracker.add_vote(vote(&keys, 0, 5, hash), 5).unwrap());
+        assert!(tracker.add_vote(vote(&keys, 1, 5, hash), 5).unwrap());
+        // A dissenting vote doesn't count towards the local hash
+        assert!(tracker.add_vote(vote(&keys, 2, 5, [8u8; 32]), 5).unwrap());
+        assert!(tracker.try_finalize(5, hash).is_none());
+
+        assert!(tracker.add_vote(vote(&keys, 3, 5, hash), 5).unwrap());
+        let finality = tracker.try_finalize(5, hash).unwrap();
+        assert_eq!(finality.verify(tracker.witnesses()).unwrap(), 3);
+        assert_eq!(tracker.finalized_tick(), Some(5));
+        assert_eq!(tracker.pending_ticks(), 0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_and_equivocating_votes() {
+        let (mut tracker, keys) = setup(3);
+
+        let forged = TickVote {
+            witness: [2u8; 32],
+            ..vote(&keys, 0, 1, [1u8; 32])
+        };
+        assert!(tracker.add_vote(forged, 1).is_err());
+
+        tracker.add_vote(vote(&keys, 0, 1, [1u8; 32]), 1).unwrap();
+        assert!(tracker.add_vote(vote(&keys, 0, 1, [2u8; 32]), 1).is_err());
+
+        // Votes far outside the window are ignored
+        assert!(!tracker
+            .add_vote(vote(&keys, 1, 1 + MAX_VOTE_LEAD + 1, [1u8; 32]), 1)
+            .unwrap());
+        tracker.prune(1 + MAX_VOTE_LAG + 1);
+        assert_eq!(tracker.pending_ticks(), 0);
+    }
+
+    #[test]
+    fn test_rotation_switches_witness_set() {
+        let (mut tracker, keys) = setup(3);
+        let hash = [3u8; 32];
+        tracker.add_vote(vote(&keys, 0, 8, hash), 8).unwrap();
+
+        // Keeping the same set keeps the buffered votes
+        tracker.rotate(tracker.witnesses().clone());
+        assert_eq!(tracker.pending_ticks(), 1);
+
+        // Only the first witness remains after the rotation
+        let next = WitnessSet::new(vec![tracker.witnesses().witnesses()[0].clone()]).unwrap();
+        tracker.rotate(next);
+        assert_eq!(tracker.pending_ticks(), 0);
+        assert!(tracker.add_vote(vote(&keys, 1, 8, hash), 8).is_err());
+
+        tracker.add_vote(vote(&keys, 0, 8, hash), 8).unwrap();
+        assert!(tracker.try_finalize(8, hash).is_some());
+    }
+}
@@ -0,0 +1,527 @@
+//! Fork choice across competing tick chains
+//!
+//! After a network partition each side keeps extending its own tick chain
+//! from the last tick they shared. Once the partition heals, a node starts
+//! receiving certificates that do not link to its head. [`ForkChoice`]
+//! keeps the tree of recent ticks, rooted up to [`REORG_WINDOW`] ticks
+//! below the head, and weighs every branch by a [`ChainWeight`]: the VDF
+//! iterations behind its tip, then the witness weight voting for its ticks.
+//!
+//! When another branch becomes heavier than the head, [`ForkChoice::plan_reorg`]
+//! lists the ticks to roll back and apply, along with the chain state to
+//! resume from. Reorgs never cross a finalized tick.
+//!
+//! Certificates are only linked and hash-checked here. Their VDF outputs
+//! must be verified beforehand, for example with
+//! [`verify_certificates`](crate::sync::verify_certificates) from the
+//! parent's [`ForkChoice::anchor`].
+
+use anyhow::{anyhow, bail, Result};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::sync::SyncAnchor;
+use kala_state::{ChainState, TickCertificate};
+
+/// Number of ticks below the head that competing branches are kept for
+pub const REORG_WINDOW: u64 = 32;
+
+/// How much work backs a branch, compared field by field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChainWeight {
+    /// VDF iterations from genesis to the branch tip
+    pub vdf_work: u64,
+    /// Witness weight voting for the branch's ticks inside the window
+    pub witness_weight: u64,
+}
+
+/// The ticks to undo and redo to switch to another branch
+#[derive(Clone)]
+pub struct Reorg {
+    /// First tick that differs between the two branches
+    pub fork_tick: u64,
+    /// Ticks of the current branch to remove, newest first
+    pub rolled_back: Vec<TickCertificate>,
+    /// Ticks of the new branch to store, oldest first
+    pub applied: Vec<TickCertificate>,
+    /// Chain state after the new branch's tip
+    pub state: ChainState,
+}
+
+impl Reorg {
+    /// Hash of the tick the node ends up on
+    pub fn new_head(&self) -> [u8; 32] {
+        self.state.last_tick_hash
+    }
+}
+
+/// A tick in the fork tree
+struct ForkTick {
+    certificate: TickCertificate,
+    /// Weight of each witness that voted for the tick
+    voters: HashMap<[u8; 32], u64>,
+    /// Chain state after the tick; only kept for branch tips
+    state: Option<ChainState>,
+}
+
+/// Tree of recent tick chains with the node's current head
+///
+/// # Example
+///
+/// ```
+/// use kala_core::fork_choice::{ForkChoice, REORG_WINDOW};
+/// use kala_state::ChainState;
+///
+/// let state = ChainState::new();
+/// let fork_choice = ForkChoice::new(&state, 65536, REORG_WINDOW);
+/// assert_eq!(fork_choice.head(), state.last_tick_hash);
+/// assert_eq!(fork_choice.best_tip(), fork_choice.head());
+/// ```
+pub struct ForkChoice {
+    iterations_per_tick: u64,
+    window: u64,
+    /// Tick below which no branch is kept
+    root: SyncAnchor,
+    ticks: HashMap<[u8; 32], ForkTick>,
+    head: [u8; 32],
+    finalized: Option<u64>,
+}
+
+impl ForkChoice {
+    /// Start a fork tree at the head of `state`
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: Chain state the node runs from
+    /// - `iterations_per_tick`: VDF iterations in every tick
+    /// - `window`: Ticks below the head that competing branches are kept for
+    pub fn new(state: &ChainState, iterations_per_tick: u64, window: u64) -> Self {
+        let root = SyncAnchor::from_state(state);
+        Self {
+            iterations_per_tick,
+            window,
+            head: root.tick_hash,
+            root,
+            ticks: HashMap::new(),
+            finalized: None,
+        }
+    }
+
+    /// Hash of the tick the node is on
+    pub fn head(&self) -> [u8; 32] {
+        self.head
+    }
+
+    /// Whether the tick is the root of the tree or in it
+    pub fn contains(&self, tick_hash: &[u8; 32]) -> bool {
+        *tick_hash == self.root.tick_hash || self.ticks.contains_key(tick_hash)
+    }
+
+    /// Anchor to verify a child of the tick from
+    pub fn anchor(&self, tick_hash: &[u8; 32]) -> Option<SyncAnchor> {
+        if *tick_hash == self.root.tick_hash {
+            return Some(self.root.clone());
+        }
+        self.ticks
+            .get(tick_hash)
+            .map(|tick| SyncAnchor::from_certificate(&tick.certificate))
+    }
+
+    /// Add a tick with a verified VDF output to the tree
+    ///
+    /// # Parameters
+    ///
+    /// - `certificate`: Tick extending a tick in the tree
+    /// - `state`: Chain state after the tick, if known
+    ///
+    /// # Returns
+    ///
+    /// `true` if the tick is new. Ticks that do not link to the tree, or
+    /// that compete with a finalized tick, are an error.
+    pub fn insert(&mut self, certificate: TickCertificate, state: Option<ChainState>) -> Result<bool> {
+        if self.ticks.contains_key(&certificate.tick_hash) {
+            if let Some(state) = state {
+                self.attach_state(&certificate.tick_hash, state)?;
+            }
+            return Ok(false);
+        }
+
+        let parent = self.anchor(&certificate.previous_tick_hash).ok_or_else(|| {
+            anyhow!("Tick {} does not link to a known tick", certificate.tick_number)
+        })?;
+        if certificate.tick_number != parent.next_tick {
+            bail!(
+                "Tick {} follows tick {} in the tree",
+                certificate.tick_number,
+                parent.next_tick
+            );
+        }
+        if certificate.compute_hash() != certificate.tick_hash {
+            bail!("Tick {} has an invalid hash", certificate.tick_number);
+        }
+        if certificate.vdf_iteration != parent.vdf_iteration + self.iterations_per_tick {
+            bail!(
+                "Tick {} ends at iteration {}, expected {}",
+                certificate.tick_number,
+                certificate.vdf_iteration,
+                parent.vdf_iteration + self.iterations_per_tick
+            );
+        }
+        if let Some(finalized) = self.finalized.filter(|&f| certificate.tick_number <= f) {
+            bail!(
+                "Tick {} competes with finalized tick {}",
+                certificate.tick_number,
+                finalized
+            );
+        }
+        if let Some(state) = &state {
+            SyncAnchor::from_certificate(&certificate).check_snapshot(state)?;
+        }
+
+        let tick_hash = certificate.tick_hash;
+        let previous = certificate.previous_tick_hash;
+        let has_state = state.is_some();
+        self.ticks.insert(
+            tick_hash,
+            ForkTick {
+                certificate,
+                voters: HashMap::new(),
+                state,
+            },
+        );
+        if has_state {
+            // Only branch tips are reorg targets
+            if let Some(parent) = self.ticks.get_mut(&previous) {
+                parent.state = None;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Add a tick the node produced on top of its head and move the head to it
+    pub fn extend(&mut self, certificate: TickCertificate, state: ChainState) -> Result<()> {
+        if certificate.previous_tick_hash != self.head {
+            bail!("Tick {} does not extend the head", certificate.tick_number);
+        }
+        let tick_hash = certificate.tick_hash;
+        self.insert(certificate, Some(state))?;
+        self.head = tick_hash;
+        self.prune();
+        Ok(())
+    }
+
+    /// Record the chain state after a tick, fetched from a peer
+    pub fn attach_state(&mut self, tick_hash: &[u8; 32], state: ChainState) -> Result<()> {
+        let tick = self
+            .ticks
+            .get_mut(tick_hash)
+            .ok_or_else(|| anyhow!("Unknown tick {}", hex::encode(tick_hash)))?;
+        SyncAnchor::from_certificate(&tick.certificate).check_snapshot(&state)?;
+        tick.state = Some(state);
+        Ok(())
+    }
+
+    /// Whether the chain state after a tick is known
+    pub fn has_state(&self, tick_hash: &[u8; 32]) -> bool {
+        self.ticks.get(tick_hash).is_some_and(|tick| tick.state.is_some())
+    }
+
+    /// Count a witness vote towards a tick
+    ///
+    /// Returns `false` if the tick is not in the tree or the witness
+    /// already voted for it.
+    pub fn add_vote(&mut self, tick_hash: &[u8; 32], witness: [u8; 32], weight: u64) -> bool {
+        match self.ticks.get_mut(tick_hash) {
+            Some(tick) => tick.voters.insert(witness, weight).is_none(),
+            None => false,
+        }
+    }
+
+    /// Stop reorgs from removing ticks up to `tick_number`
+    pub fn finalize(&mut self, tick_number: u64) {
+        self.finalized = self.finalized.max(Some(tick_number));
+    }
+
+    /// Weight of the branch ending at a tick
+    pub fn weight(&self, tick_hash: &[u8; 32]) -> Option<ChainWeight> {
+        if *tick_hash == self.root.tick_hash {
+            return Some(ChainWeight {
+                vdf_work: self.root.vdf_iteration,
+                witness_weight: 0,
+            });
+        }
+
+        let tip = self.ticks.get(tick_hash)?;
+        let mut witness_weight = 0u64;
+        let mut current = Some(tip);
+        while let Some(tick) = current {
+            witness_weight = witness_weight.saturating_add(tick.voters.values().sum());
+            current = self.ticks.get(&tick.certificate.previous_tick_hash);
+        }
+        Some(ChainWeight {
+            vdf_work: tip.certificate.vdf_iteration,
+            witness_weight,
+        })
+    }
+
+    /// Tip of the heaviest branch
+    ///
+    /// The head is kept unless another branch is strictly heavier; ties
+    /// between other branches go to the smaller tick hash.
+    pub fn best_tip(&self) -> [u8; 32] {
+        let Some(head_weight) = self.weight(&self.head) else {
+            return self.head;
+        };
+        self.ticks
+            .keys()
+            .filter_map(|tick_hash| Some((self.weight(tick_hash)?, Reverse(*tick_hash))))
+            .filter(|(weight, _)| *weight > head_weight)
+            .max()
+            .map_or(self.head, |(_, Reverse(tick_hash))| tick_hash)
+    }
+
+    /// Plan the switch from the head to the branch ending at `target`
+    ///
+    /// # Errors
+    ///
+    /// Fails if `target` is not in the tree, its chain state is unknown,
+    /// or the switch would remove a finalized tick.
+    pub fn plan_reorg(&self, target: &[u8; 32]) -> Result<Reorg> {
+        let tip = self
+            .ticks
+            .get(target)
+            .ok_or_else(|| anyhow!("Unknown tick {}", hex::encode(target)))?;
+        let state = tip.state.clone().ok_or_else(|| {
+            anyhow!("No chain state for tick {}", tip.certificate.tick_number)
+        })?;
+
+        let head_branch = self.branch(&self.head);
+        let mut applied = Vec::new();
+        let mut fork_point = self.root.tick_hash;
+        for certificate in self.branch(target) {
+            if head_branch.iter().any(|tick| tick.tick_hash == certificate.tick_hash) {
+                fork_point = certificate.tick_hash;
+                break;
+            }
+            applied.push(certificate.clone());
+        }
+        applied.reverse();
+
+        let rolled_back: Vec<TickCertificate> = head_branch
+            .into_iter()
+            .take_while(|tick| tick.tick_hash != fork_point)
+            .cloned()
+            .collect();
+        let fork_tick = self
+            .anchor(&fork_point)
+            .map_or(self.root.next_tick, |anchor| anchor.next_tick);
+
+        if let Some(finalized) = self.finalized {
+            if !rolled_back.is_empty() && finalized >= fork_tick {
+                bail!(
+                    "Switching to tick {} would remove finalized tick {}",
+                    tip.certificate.tick_number,
+                    finalized
+                );
+            }
+        }
+
+        Ok(Reorg {
+            fork_tick,
+            rolled_back,
+            applied,
+            state,
+        })
+    }
+
+    /// Move the head to the new branch after a reorg was applied
+    pub fn adopt(&mut self, reorg: &Reorg) {
+        self.head = reorg.new_head();
+        self.prune();
+    }
+
+    /// Ticks from `tick_hash` down to the root, exclusive, newest first
+    fn branch(&self, tick_hash: &[u8; 32]) -> Vec<&TickCertificate> {
+        let mut branch = Vec::new();
+        let mut current = self.ticks.get(tick_hash);
+        while let Some(tick) = current {
+            branch.push(&tick.certificate);
+            current = self.ticks.get(&tick.certificate.previous_tick_hash);
+        }
+        branch
+    }
+
+    /// Move the root up to `window` ticks below the head, dropping
+    /// branches that fork off below it
+    fn prune(&mut self) {
+        let head_branch = self.branch(&self.head);
+        let Some(new_root) = head_branch.get(self.window as usize) else {
+            return;
+        };
+        let new_root = SyncAnchor::from_certificate(new_root);
+
+        let keep: Vec<[u8; 32]> = self
+            .ticks
+            .keys()
+            .filter(|tick_hash| {
+                self.branch(tick_hash)
+                    .iter()
+                    .any(|tick| tick.previous_tick_hash == new_root.tick_hash)
+            })
+            .copied()
+            .collect();
+        self.ticks.retain(|tick_hash, _| keep.contains(tick_hash));
+        self.root = new_root;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::TickType;
+
+    const K: u64 = 8;
+
+    fn genesis() -> ChainState {
+        let mut state = ChainState::new();
+        state.last_tick_hash = [0u8; 32];
+        state.vdf_checkpoint.iteration = 0;
+        state
+    }
+
+    /// A tick after `parent`, distinguished by `salt`, with the state after it
+    fn child(parent: &SyncAnchor, salt: u64) -> (TickCertificate, ChainState) {
+        let mut certificate = TickCertificate {
+            tick_number: parent.next_tick,
+            tick_type: TickType::Empty,
+            vdf_iteration: parent.vdf_iteration + K,
+            vdf_form: (salt.to_string().into(), "0".into(), "1".into()),
+            hash_chain_value: [0u8; 32],
+            tick_hash: [0u8; 32],
+            transaction_count: 0,
+            transaction_merkle_root: [0u8; 32],
+            timestamp: salt,
+            previous_tick_hash: parent.tick_hash,
+            usage: Default::default(),
+        };
+        certificate.tick_hash = certificate.compute_hash();
+
+        let mut state = genesis();
+        state.current_tick = certificate.tick_number + 1;
+        state.last_tick_hash = certificate.tick_hash;
+        state.vdf_checkpoint.iteration = certificate.vdf_iteration;
+        state.vdf_checkpoint.form_a = certificate.vdf_form.0.clone();
+        state.vdf_checkpoint.form_b = certificate.vdf_form.1.clone();
+        state.vdf_checkpoint.form_c = certificate.vdf_form.2.clone();
+        (certificate, state)
+    }
+
+    /// Extend the head with `count` local ticks
+    fn extend(fork_choice: &mut ForkChoice, count: u64) -> Vec<TickCertificate> {
+        let mut ticks = Vec::new();
+        for _ in 0..count {
+            let anchor = fork_choice.anchor(&fork_choice.head()).unwrap();
+            let (certificate, state) = child(&anchor, 1);
+            fork_choice.extend(certificate.clone(), state).unwrap();
+            ticks.push(certificate);
+        }
+        ticks
+    }
+
+    #[test]
+    fn test_longer_branch_wins_and_reorg_plan() {
+        let mut fork_choice = ForkChoice::new(&genesis(), K, REORG_WINDOW);
+        let local = extend(&mut fork_choice, 3);
+
+        // A competing branch forking after tick 0
+        let mut parent = fork_choice.anchor(&local[0].tick_hash).unwrap();
+        let mut remote = Vec::new();
+        for _ in 0..3 {
+            let (certificate, state) = child(&parent, 2);
+            assert!(fork_choice.insert(certificate.clone(), None).unwrap());
+            parent = SyncAnchor::from_certificate(&certificate);
+            remote.push((certificate, state));
+        }
+        let (remote_tip, remote_state) = remote.last().cloned().unwrap();
+
+        // One tick longer than the local branch
+        assert_eq!(fork_choice.best_tip(), remote_tip.tick_hash);
+        assert!(fork_choice.plan_reorg(&remote_tip.tick_hash).is_err());
+        fork_choice.attach_state(&remote_tip.tick_hash, remote_state).unwrap();
+
+        let reorg = fork_choice.plan_reorg(&remote_tip.tick_hash).unwrap();
+        assert_eq!(reorg.fork_tick, 1);
+        let rolled_back: Vec<_> = reorg.rolled_back.iter().map(|tick| tick.tick_hash).collect();
+        assert_eq!(rolled_back, vec![local[2].tick_hash, local[1].tick_hash]);
+        let applied: Vec<_> = reorg.applied.iter().map(|tick| tick.tick_hash).collect();
+        let expected: Vec<_> = remote.iter().map(|(tick, _)| tick.tick_hash).collect();
+        assert_eq!(applied, expected);
+
+        fork_choice.adopt(&reorg);
+        assert_eq!(fork_choice.head(), remote_tip.tick_hash);
+        assert_eq!(fork_choice.best_tip(), remote_tip.tick_hash);
+    }
+
+    #[test]
+    fn test_witness_weight_breaks_ties() {
+        let mut fork_choice = ForkChoice::new(&genesis(), K, REORG_WINDOW);
+        let local = extend(&mut fork_choice, 2);
+
+        let parent = fork_choice.anchor(&local[0].tick_hash).unwrap();
+        let (remote, _) = child(&parent, 2);
+        fork_choice.insert(remote.clone(), None).unwrap();
+
+        // Equal VDF work keeps the head
+        assert_eq!(fork_choice.best_tip(), local[1].tick_hash);
+
+        assert!(fork_choice.add_vote(&remote.tick_hash, [1u8; 32], 3));
+        assert!(!fork_choice.add_vote(&remote.tick_hash, [1u8; 32], 3));
+        assert!(fork_choice.add_vote(&local[1].tick_hash, [2u8; 32], 1));
+        assert_eq!(fork_choice.best_tip(), remote.tick_hash);
+
+        // Votes on a shared ancestor count for both branches
+        fork_choice.add_vote(&local[0].tick_hash, [3u8; 32], 5);
+        assert_eq!(fork_choice.weight(&remote.tick_hash).unwrap().witness_weight, 8);
+        assert_eq!(fork_choice.best_tip(), remote.tick_hash);
+    }
+
+    #[test]
+    fn test_finalized_ticks_are_not_reorged() {
+        let mut fork_choice = ForkChoice::new(&genesis(), K, REORG_WINDOW);
+        let local = extend(&mut fork_choice, 3);
+
+        let parent = fork_choice.anchor(&local[0].tick_hash).unwrap();
+        let (fork, _) = child(&parent, 2);
+        let (tip, tip_state) = child(&SyncAnchor::from_certificate(&fork), 2);
+        let (longer, longer_state) = child(&SyncAnchor::from_certificate(&tip), 2);
+        fork_choice.insert(fork, None).unwrap();
+        fork_choice.insert(tip, Some(tip_state)).unwrap();
+
+        fork_choice.finalize(1);
+        fork_choice.insert(longer.clone(), Some(longer_state)).unwrap();
+        assert!(fork_choice.plan_reorg(&longer.tick_hash).is_err());
+
+        // Ticks competing with finalized ones are refused
+        let (late, _) = child(&fork_choice.anchor(&local[0].tick_hash).unwrap(), 3);
+        assert!(fork_choice.insert(late, None).is_err());
+    }
+
+    #[test]
+    fn test_window_prunes_old_branches() {
+        let mut fork_choice = ForkChoice::new(&genesis(), K, 4);
+        let local = extend(&mut fork_choice, 2);
+
+        let (stale, _) = child(&fork_choice.anchor(&local[0].tick_hash).unwrap(), 2);
+        fork_choice.insert(stale.clone(), None).unwrap();
+        assert!(fork_choice.contains(&stale.tick_hash));
+
+        extend(&mut fork_choice, 4);
+        assert!(!fork_choice.contains(&stale.tick_hash));
+        assert!(!fork_choice.contains(&local[0].tick_hash));
+        assert!(fork_choice.contains(&local[1].tick_hash));
+
+        // Unlinked ticks are refused
+        let (orphan, _) = child(&SyncAnchor::from_certificate(&stale), 2);
+        assert!(fork_choice.insert(orphan, None).is_err());
+    }
+}
@@ -0,0 +1,449 @@
+//! Genesis files
+//!
+//! A [`Genesis`] fixes what a chain starts from: its chain ID, tick size,
+//! VDF discriminant, initial witnesses, account balances, and the issuers
+//! of its denominations. Operators
+//! exchange it as a JSON file, which leaves room for drift: two files with
+//! the same balances but different key order or hex case are equivalent,
+//! while a single changed digit is not, and neither is obvious by eye.
+//!
+//! The [canonical hash](Genesis::canonical_hash) settles this. It covers a
+//! fixed binary encoding of the parsed contents, with witnesses and
+//! accounts sorted by key, so equivalent files hash the same however they
+//! were written. [`Genesis::seal`] embeds the hash in the file. Every node
+//! recomputes it on startup and compares it with the embedded hash and
+//! with the one recorded when its database was created, refusing to start
+//! on a mismatch.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use kala_state::{ChainState, Witness};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{NodeConfig, WitnessConfig};
+
+/// Domain separating genesis hashes from other hashes
+pub const GENESIS_DOMAIN: &[u8] = b"kala-genesis-v1";
+
+/// Initial state of a chain, as exchanged between operators
+///
+/// # Example
+///
+/// ```
+/// use kala_core::genesis::{Genesis, GenesisAccount};
+///
+/// let genesis = Genesis {
+///     accounts: vec![GenesisAccount {
+///         address: "11".repeat(32),
+///         balance: 1_000_000,
+///     }],
+///     ..Genesis::from_config(&kala_core::NodeConfig::default())
+/// }
+/// .seal()
+/// .unwrap();
+///
+/// assert!(genesis.verify().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Genesis {
+    /// Chain ID transactions are signed for
+    pub chain_id: String,
+    /// VDF iterations per tick (k)
+    pub iterations_per_tick: u64,
+    /// Class group discriminant of the VDF (decimal)
+    pub discriminant: String,
+    /// Initial witness set, the configured one if empty
+    #[serde(default)]
+    pub witnesses: Vec<WitnessConfig>,
+    /// Initial account balances
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+    /// Unix time in milliseconds the chain starts at, anchoring its timeline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_time: Option<u64>,
+    /// Accounts allowed to mint each denomination and move its issuance
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issuers: Vec<GenesisIssuer>,
+    /// Whether every account may mint the native denomination, for
+    /// development chains only
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub open_mint: bool,
+    /// Hex-encoded canonical hash of the fields above
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_hash: Option<String>,
+}
+
+/// An account funded at genesis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenesisAccount {
+    /// Hex-encoded 32-byte account address
+    pub address: String,
+    /// Initial balance in the native denomination
+    pub balance: u64,
+}
+
+/// The issuer of a denomination at genesis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenesisIssuer {
+    /// Hex-encoded 32-byte denomination ID
+    pub denom: String,
+    /// Hex-encoded 32-byte address of the issuer
+    pub issuer: String,
+}
+
+impl Genesis {
+    /// Unsealed genesis with the chain parameters of `config` and no accounts
+    pub fn from_config(config: &NodeConfig) -> Self {
+        Self {
+            chain_id: config.chain_id.clone(),
+            iterations_per_tick: config.iterations_per_tick,
+            discriminant: config.discriminant.clone(),
+            witnesses: config.witnesses.clone(),
+            accounts: Vec::new(),
+            genesis_time: None,
+            issuers: Vec::new(),
+            open_mint: config.dev_open_mint,
+            genesis_hash: None,
+        }
+    }
+
+    /// Read a genesis file and [verify](Self::verify) its embedded hash
+    ///
+    /// # Returns
+    ///
+    /// The genesis and its canonical hash
+    pub fn load(path: impl AsRef<Path>) -> Result<(Self, [u8; 32])> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read genesis file {}: {}", path.display(), e))?;
+        let genesis: Self = serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("Invalid genesis file {}: {}", path.display(), e))?;
+        let hash = genesis.verify()?;
+        Ok((genesis, hash))
+    }
+
+    /// Write the genesis to `path` as pretty-printed JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .map_err(|e| anyhow!("Failed to write genesis file {}: {}", path.display(), e))
+    }
+
+    /// Hash of the genesis contents, independent of how the file is written
+    ///
+    /// The hash covers [`GENESIS_DOMAIN`], then the length-prefixed chain
+    /// ID, the tick size, the length-prefixed discriminant, the witnesses
+    /// sorted by key, the accounts sorted by address, the genesis time if
+    /// set, the issuers sorted by denomination after an `issuers` tag if
+    /// there are any, and an `open_mint` tag if minting is open, so files
+    /// without them keep their hash. Integers are
+    /// little-endian, and keys, addresses and denominations are hashed as
+    /// decoded bytes.
+    /// The embedded `genesis_hash` is not covered.
+    ///
+    /// # Errors
+    ///
+    /// If a key, address or denomination is malformed, a witness, account
+    /// or denomination appears twice, or the discriminant isn't a negative decimal integer.
+    pub fn canonical_hash(&self) -> Result<[u8; 32]> {
+        let digits = self.discriminant.strip_prefix('-').unwrap_or_default();
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            bail!("Genesis discriminant must be a negative decimal integer");
+        }
+
+        let witnesses: BTreeMap<[u8; 32], Witness> = self
+            .witness_set()?
+            .into_iter()
+            .map(|witness| (witness.key, witness))
+            .collect();
+        if witnesses.len() != self.witnesses.len() {
+            bail!("Genesis lists a witness twice");
+        }
+        let accounts = self.balances()?;
+        let issuers = self.issuer_map()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(GENESIS_DOMAIN);
+        hasher.update((self.chain_id.len() as u32).to_le_bytes());
+        hasher.update(self.chain_id.as_bytes());
+        hasher.update(self.iterations_per_tick.to_le_bytes());
+        hasher.update((self.discriminant.len() as u32).to_le_bytes());
+        hasher.update(self.discriminant.as_bytes());
+
+        hasher.update((witnesses.len() as u32).to_le_bytes());
+        for witness in witnesses.values() {
+            hasher.update(witness.key);
+            hasher.update((witness.bls_key.len() as u32).to_le_bytes());
+            hasher.update(&witness.bls_key);
+            hasher.update(witness.weight.to_le_bytes());
+        }
+
+        hasher.update((accounts.len() as u32).to_le_bytes());
+        for (address, balance) in &accounts {
+            hasher.update(address);
+            hasher.update(balance.to_le_bytes());
+        }
+        if let Some(genesis_time) = self.genesis_time {
+            hasher.update(genesis_time.to_le_bytes());
+        }
+        if !issuers.is_empty() {
+            hasher.update(b"issuers");
+            hasher.update((issuers.len() as u32).to_le_bytes());
+            for (denom, issuer) in &issuers {
+                hasher.update(denom);
+                hasher.update(issuer);
+            }
+        }
+        if self.open_mint {
+            hasher.update(b"open_mint");
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// Embed the canonical hash
+    pub fn seal(mut self) -> Result<Self> {
+        self.genesis_hash = Some(hex::encode(self.canonical_hash()?));
+        Ok(self)
+    }
+
+    /// Check the embedded hash against the contents
+    ///
+    /// # Returns
+    ///
+    /// The canonical hash, if the genesis is sealed and unchanged since
+    pub fn verify(&self) -> Result<[u8; 32]> {
+        let embedded = self
+            .genesis_hash
+            .as_ref()
+            .ok_or_else(|| anyhow!("Genesis file is not sealed with a genesis_hash"))?;
+        let hash = self.canonical_hash()?;
+        if !embedded.eq_ignore_ascii_case(&hex::encode(hash)) {
+            bail!(
+                "Genesis contents hash to {}, not the embedded {}",
+                hex::encode(hash),
+                embedded
+            );
+        }
+        Ok(hash)
+    }
+
+    /// Check that `config` runs the chain this genesis describes
+    pub fn check_config(&self, config: &NodeConfig) -> Result<()> {
+        if self.chain_id != config.chain_id {
+            bail!(
+                "Genesis is for chain {}, but the node is configured for {}",
+                self.chain_id,
+                config.chain_id
+            );
+        }
+        if self.iterations_per_tick != config.iterations_per_tick {
+            bail!(
+                "Genesis has {} iterations per tick, but the node is configured with {}",
+                self.iterations_per_tick,
+                config.iterations_per_tick
+            );
+        }
+        if self.discriminant != config.discriminant {
+            bail!("Genesis discriminant differs from the configured one");
+        }
+        Ok(())
+    }
+
+    /// Parsed initial witnesses
+    pub fn witness_set(&self) -> Result<Vec<Witness>> {
+        self.witnesses
+            .iter()
+            .map(|witness| witness.parse().map_err(|e| anyhow!("Invalid genesis witness: {}", e)))
+            .collect()
+    }
+
+    /// Chain state before the first tick
+    pub fn chain_state(&self) -> Result<ChainState> {
+        let mut state = ChainState::for_chain(&self.discriminant, self.iterations_per_tick);
+        for (address, balance) in self.balances()? {
+            state
+                .mint(&address, balance)
+                .map_err(|e| anyhow!("Failed to fund genesis account: {}", e))?;
+        }
+        for (denom, issuer) in self.issuer_map()? {
+            state.set_issuer(&denom, &issuer);
+        }
+        state.set_open_mint(self.open_mint);
+        Ok(state)
+    }
+
+    /// Initial balances by decoded address
+    fn balances(&self) -> Result<BTreeMap<[u8; 32], u64>> {
+        let mut balances = BTreeMap::new();
+        for account in &self.accounts {
+            let address = decode_key("address", &account.address)?;
+            if balances.insert(address, account.balance).is_some() {
+                bail!("Genesis funds account {} twice", account.address);
+            }
+        }
+        Ok(balances)
+    }
+
+    /// Issuers by decoded denomination
+    fn issuer_map(&self) -> Result<BTreeMap<[u8; 32], [u8; 32]>> {
+        let mut issuers = BTreeMap::new();
+        for entry in &self.issuers {
+            let denom = decode_key("denomination", &entry.denom)?;
+            let issuer = decode_key("address", &entry.issuer)?;
+            if issuers.insert(denom, issuer).is_some() {
+                bail!("Genesis names two issuers of denomination {}", entry.denom);
+            }
+        }
+        Ok(issuers)
+    }
+}
+
+/// Decode a hex-encoded 32-byte genesis `kind`, such as an address
+fn decode_key(kind: &str, encoded: &str) -> Result<[u8; 32]> {
+    hex::decode(encoded)
+        .map_err(|e| anyhow!("Invalid genesis {} {}: {}", kind, encoded, e))?
+        .try_into()
+        .map_err(|_| anyhow!("Genesis {} {} must be 32 bytes", kind, encoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::NATIVE_DENOM;
+    use kala_state::witness::{bls_public_key, derive_bls_key};
+
+    fn witness(seed: u8) -> WitnessConfig {
+        WitnessConfig {
+            key: hex::encode([seed; 32]),
+            bls_key: hex::encode(bls_public_key(&derive_bls_key(&[seed; 32]).unwrap())),
+            weight: seed as u64,
+        }
+    }
+
+    fn account(seed: u8, balance: u64) -> GenesisAccount {
+        GenesisAccount {
+            address: hex::encode([seed; 32]),
+            balance,
+        }
+    }
+
+    fn genesis() -> Genesis {
+        Genesis {
+            witnesses: vec![witness(1), witness(2)],
+            accounts: vec![account(3, 100), account(4, 200)],
+            ..Genesis::from_config(&NodeConfig::default())
+        }
+    }
+
+    #[test]
+    fn test_canonical_hash_ignores_formatting() {
+        let hash = genesis().canonical_hash().unwrap();
+
+        let mut reordered = genesis();
+        reordered.witnesses.reverse();
+        reordered.accounts.reverse();
+        reordered.accounts[0].address = reordered.accounts[0].address.to_uppercase();
+        assert_eq!(reordered.canonical_hash().unwrap(), hash);
+
+        // The JSON key order doesn't matter either
+        let json = serde_json::to_value(genesis()).unwrap();
+        let mut fields: Vec<_> = json.as_object().unwrap().iter().collect();
+        fields.reverse();
+        let rewritten = format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|(key, value)| format!("{:?}:{}", key, value))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let parsed: Genesis = serde_json::from_str(&rewritten).unwrap();
+        assert_eq!(parsed.canonical_hash().unwrap(), hash);
+
+        let mut changed = genesis();
+        changed.accounts[1].balance += 1;
+        assert_ne!(changed.canonical_hash().unwrap(), hash);
+
+        let mut timed = genesis();
+        timed.genesis_time = Some(1_760_000_000_000);
+        assert_ne!(timed.canonical_hash().unwrap(), hash);
+
+        let mut duplicated = genesis();
+        duplicated.accounts.push(account(3, 1));
+        assert!(duplicated.canonical_hash().is_err());
+
+        let mut issued = genesis();
+        issued.issuers.push(GenesisIssuer {
+            denom: hex::encode([7u8; 32]),
+            issuer: hex::encode([3u8; 32]),
+        });
+        assert_ne!(issued.canonical_hash().unwrap(), hash);
+        issued.issuers.push(issued.issuers[0].clone());
+        assert!(issued.canonical_hash().is_err());
+
+        let mut open = genesis();
+        open.open_mint = true;
+        assert_ne!(open.canonical_hash().unwrap(), hash);
+    }
+
+    #[test]
+    fn test_seal_and_verify() {
+        assert!(genesis().verify().is_err());
+
+        let sealed = genesis().seal().unwrap();
+        assert_eq!(sealed.verify().unwrap(), genesis().canonical_hash().unwrap());
+
+        let mut drifted = sealed.clone();
+        drifted.accounts[0].balance = 101;
+        assert!(drifted.verify().is_err());
+
+        let dir = std::env::temp_dir().join(format!("kala_genesis_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("genesis.json");
+        sealed.save(&path).unwrap();
+        let (loaded, hash) = Genesis::load(&path).unwrap();
+        assert_eq!(hash, sealed.verify().unwrap());
+        assert_eq!(loaded.genesis_hash, sealed.genesis_hash);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_chain_state_and_config() {
+        let genesis = genesis();
+        let state = genesis.chain_state().unwrap();
+        assert_eq!(state.get_balance(&[3u8; 32]), 100);
+        assert_eq!(state.get_balance(&[4u8; 32]), 200);
+        assert_eq!(state.tick_size, genesis.iterations_per_tick);
+        assert!(state.mint_authorities().issuer(&[7u8; 32]).is_none());
+
+        let mut issued = genesis.clone();
+        issued.issuers.push(GenesisIssuer {
+            denom: hex::encode([7u8; 32]),
+            issuer: hex::encode([3u8; 32]),
+        });
+        let state = issued.chain_state().unwrap();
+        assert_eq!(state.mint_authorities().issuer(&[7u8; 32]), Some(&[3u8; 32]));
+
+        let mut config = NodeConfig::default();
+        assert!(genesis.check_config(&config).is_ok());
+        config.chain_id = "kala-testnet".to_string();
+        assert!(genesis.check_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_minting_closed_by_default() {
+        let mut state = Genesis::from_config(&NodeConfig::default()).chain_state().unwrap();
+        assert!(state.apply_mint(&[3u8; 32], &NATIVE_DENOM, 10).is_err());
+
+        let config = NodeConfig {
+            dev_open_mint: true,
+            ..Default::default()
+        };
+        let mut state = Genesis::from_config(&config).chain_state().unwrap();
+        state.apply_mint(&[3u8; 32], &NATIVE_DENOM, 10).unwrap();
+        assert_eq!(state.get_balance(&[3u8; 32]), 10);
+    }
+}
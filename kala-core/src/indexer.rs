@@ -0,0 +1,565 @@
+//! Exactly-once feed of finalized ticks for indexers
+//!
+//! An [`Indexer`] writes every finalized tick, with the transactions it
+//! applied decoded, to an [`IndexerSink`] in tick order, so downstream
+//! databases can mirror the chain without scraping RPC. Unlike the
+//! [`EventSink`](crate::event_sink::EventSink), which publishes what the
+//! node is doing as it happens, the feed only carries ticks that can no
+//! longer be reorged, and carries each exactly once:
+//!
+//! - A tick is only written once it is finalized, and only if it links to
+//!   the tick written before it.
+//! - The sink records its own position. After a restart, or a failed
+//!   write, the indexer resumes from the tick after the last one the sink
+//!   holds, so nothing is skipped or written twice.
+//! - Transactions are decoded when the node produces a tick, and spooled
+//!   to disk under the tick's hash until it is finalized. Transactions of
+//!   a tick that was reorged away are never written; a finalized tick the
+//!   node didn't produce itself, such as one synced from peers, is written
+//!   without them.
+//!
+//! [`FileSink`] appends the feed to a JSON lines file. Message queues fit
+//! behind the trait too, for example a Kafka topic whose last record gives
+//! the position.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use kala_state::{StateDB, TickCertificate, TickFinality};
+use kala_transaction::Transaction;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::consensus::AppliedTransaction;
+
+/// Bytes read at a time when looking for a feed file's last line
+const TAIL_CHUNK: u64 = 64 * 1024;
+
+/// A transaction applied by an indexed tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedTransaction {
+    /// Hex-encoded hash of the envelope it arrived in
+    pub envelope_hash: String,
+    /// Hex-encoded addresses of the accounts it modified
+    pub accounts: Vec<String>,
+    /// The decrypted transaction
+    pub transaction: Transaction,
+}
+
+impl From<&AppliedTransaction> for IndexedTransaction {
+    fn from(applied: &AppliedTransaction) -> Self {
+        Self {
+            envelope_hash: hex::encode(applied.envelope_hash),
+            accounts: applied.accounts.iter().map(hex::encode).collect(),
+            transaction: applied.transaction.clone(),
+        }
+    }
+}
+
+/// A finalized tick as written to the feed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IndexedTick {
+    /// The finalized tick
+    pub tick_number: u64,
+    /// Hex-encoded hash of its certificate
+    pub tick_hash: String,
+    /// The tick's certificate
+    pub certificate: TickCertificate,
+    /// Witness signatures finalizing the tick; `None` if it was finalized
+    /// by a later tick's
+    pub finality: Option<TickFinality>,
+    /// Transactions applied by the tick, in execution order; `None` if the
+    /// node didn't produce the tick itself
+    pub transactions: Option<Vec<IndexedTransaction>>,
+}
+
+/// The last tick a sink holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedPosition {
+    /// Number of the tick
+    pub tick_number: u64,
+    /// Hash of its certificate
+    pub tick_hash: [u8; 32],
+}
+
+/// Durable, ordered destination of the feed
+///
+/// # Example
+///
+/// ```
+/// use kala_core::indexer::{FeedPosition, IndexedTick, IndexerSink};
+/// use tokio::sync::Mutex;
+///
+/// /// Keeps the feed in memory
+/// struct MemorySink(Mutex<Vec<IndexedTick>>);
+///
+/// #[async_trait::async_trait]
+/// impl IndexerSink for MemorySink {
+///     async fn position(&self) -> anyhow::Result<Option<FeedPosition>> {
+///         let ticks = self.0.lock().await;
+///         Ok(ticks.last().map(|tick| FeedPosition {
+///             tick_number: tick.tick_number,
+///             tick_hash: tick.certificate.tick_hash,
+///         }))
+///     }
+///
+///     async fn append(&self, tick: &IndexedTick) -> anyhow::Result<()> {
+///         self.0.lock().await.push(tick.clone());
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait IndexerSink: Send + Sync {
+    /// The last tick the sink durably holds, or `None` if it is empty
+    async fn position(&self) -> Result<Option<FeedPosition>>;
+
+    /// Durably append the next tick
+    ///
+    /// Must either hold the tick afterwards or fail without holding any of
+    /// it, since the indexer resumes from [`position`](Self::position).
+    async fn append(&self, tick: &IndexedTick) -> Result<()>;
+}
+
+/// Sink appending the feed to a file, one JSON [`IndexedTick`] per line
+///
+/// Each line is synced to disk before the next is written. A line cut
+/// short by a crash is truncated when the file is reopened.
+pub struct FileSink {
+    path: PathBuf,
+    file: Mutex<SinkFile>,
+}
+
+struct SinkFile {
+    file: tokio::fs::File,
+    /// Bytes of complete lines
+    len: u64,
+    position: Option<FeedPosition>,
+}
+
+impl FileSink {
+    /// Open or create the feed file at `path`
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path of the feed file
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be opened, or its last complete line isn't
+    /// an [`IndexedTick`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open indexer feed {}", path.display()))?;
+        let (len, last_line) = read_tail(&mut file)?;
+        if len < file.metadata()?.len() {
+            warn!("Truncating a partial line at the end of {}", path.display());
+            file.set_len(len)?;
+        }
+        let position = match last_line {
+            Some(line) => {
+                let tick: IndexedTick = serde_json::from_slice(&line).with_context(|| {
+                    format!("Last line of {} is not an indexed tick", path.display())
+                })?;
+                Some(FeedPosition {
+                    tick_number: tick.tick_number,
+                    tick_hash: tick.certificate.tick_hash,
+                })
+            }
+            None => None,
+        };
+        Ok(Self {
+            path,
+            file: Mutex::new(SinkFile {
+                file: tokio::fs::File::from_std(file),
+                len,
+                position,
+            }),
+        })
+    }
+
+    /// Path of the feed file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl IndexerSink for FileSink {
+    async fn position(&self) -> Result<Option<FeedPosition>> {
+        Ok(self.file.lock().await.position)
+    }
+
+    async fn append(&self, tick: &IndexedTick) -> Result<()> {
+        let mut line = serde_json::to_vec(tick)?;
+        line.push(b'\n');
+        let mut sink = self.file.lock().await;
+        let written = async {
+            sink.file.write_all(&line).await?;
+            sink.file.sync_data().await
+        }
+        .await;
+        if let Err(e) = written {
+            // Cut off whatever part of the line made it to the file
+            sink.file.set_len(sink.len).await?;
+            return Err(e.into());
+        }
+        sink.len += line.len() as u64;
+        sink.position = Some(FeedPosition {
+            tick_number: tick.tick_number,
+            tick_hash: tick.certificate.tick_hash,
+        });
+        Ok(())
+    }
+}
+
+/// Length of a file's complete lines, and the last of them
+fn read_tail(file: &mut std::fs::File) -> Result<(u64, Option<Vec<u8>>)> {
+    let mut start = file.metadata()?.len();
+    let mut tail: Vec<u8> = Vec::new();
+    loop {
+        let newlines: Vec<usize> = tail
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte == b'\n')
+            .map(|(offset, _)| offset)
+            .collect();
+        match newlines[..] {
+            [.., before, last] => {
+                let line = tail[before + 1..last].to_vec();
+                return Ok((start + last as u64 + 1, Some(line)));
+            }
+            [last] if start == 0 => return Ok((last as u64 + 1, Some(tail[..last].to_vec()))),
+            [] if start == 0 => return Ok((0, None)),
+            _ => {}
+        }
+        let read = TAIL_CHUNK.min(start);
+        start -= read;
+        let mut chunk = vec![0u8; read as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+}
+
+/// Writes finalized ticks to a sink, in order and exactly once
+pub struct Indexer {
+    sink: Arc<dyn IndexerSink>,
+    /// Directory holding produced ticks' transactions until they finalize
+    spool: PathBuf,
+    /// Held while ticks are written, so they go out in order
+    writing: Mutex<()>,
+}
+
+impl Indexer {
+    /// Create an indexer writing to `sink`
+    ///
+    /// # Parameters
+    ///
+    /// - `sink`: Destination of the feed
+    /// - `spool`: Directory for the transactions of unfinalized ticks,
+    ///   created if missing
+    ///
+    /// # Errors
+    ///
+    /// Fails if the spool directory can't be created.
+    pub fn new(sink: Arc<dyn IndexerSink>, spool: impl Into<PathBuf>) -> Result<Self> {
+        let spool = spool.into();
+        std::fs::create_dir_all(&spool)
+            .with_context(|| format!("Failed to create indexer spool {}", spool.display()))?;
+        Ok(Self {
+            sink,
+            spool,
+            writing: Mutex::new(()),
+        })
+    }
+
+    /// Spool the transactions a tick applied until it is finalized
+    ///
+    /// Called as the node produces the tick, before it is committed.
+    ///
+    /// # Parameters
+    ///
+    /// - `certificate`: The tick's certificate
+    /// - `applied`: Transactions the tick applied, in execution order
+    pub async fn stage(
+        &self,
+        certificate: &TickCertificate,
+        applied: &[AppliedTransaction],
+    ) -> Result<()> {
+        let transactions: Vec<IndexedTransaction> =
+            applied.iter().map(IndexedTransaction::from).collect();
+        let path = self.spool_path(certificate.tick_number, &certificate.tick_hash);
+        let staging = path.with_extension("tmp");
+        tokio::fs::write(&staging, serde_json::to_vec(&transactions)?).await?;
+        tokio::fs::rename(&staging, &path).await?;
+        Ok(())
+    }
+
+    /// Write every finalized tick the sink doesn't hold yet
+    ///
+    /// Resumes after the sink's position and stops at the first tick that
+    /// can't be written, to be retried on the next call.
+    ///
+    /// # Parameters
+    ///
+    /// - `state_db`: Database the ticks and their finality are read from
+    /// - `finalized`: The latest finalized tick
+    ///
+    /// # Returns
+    ///
+    /// The number of ticks written
+    pub async fn catch_up(&self, state_db: &StateDB, finalized: u64) -> Result<u64> {
+        let _writing = self.writing.lock().await;
+        let mut position = self.sink.position().await?;
+        let mut written = 0;
+        let next = position.map_or(0, |position| position.tick_number + 1);
+        for tick_number in next..=finalized {
+            let certificate = state_db
+                .get_tick(tick_number)
+                .await?
+                .ok_or_else(|| anyhow!("Tick {} is not stored", tick_number))?;
+            let finality = state_db.get_tick_finality(tick_number).await?;
+            if let Some(finality) = &finality {
+                if finality.tick_hash != certificate.tick_hash {
+                    bail!("Stored tick {} is not the one finalized", tick_number);
+                }
+            }
+            if let Some(previous) = position {
+                if certificate.previous_tick_hash != previous.tick_hash {
+                    bail!("Tick {} does not link to the last indexed tick", tick_number);
+                }
+            }
+
+            let tick = IndexedTick {
+                tick_number,
+                tick_hash: hex::encode(certificate.tick_hash),
+                finality,
+                transactions: self.spooled(tick_number, &certificate.tick_hash).await?,
+                certificate,
+            };
+            self.sink.append(&tick).await?;
+            position = Some(FeedPosition {
+                tick_number,
+                tick_hash: tick.certificate.tick_hash,
+            });
+            written += 1;
+            self.prune_spool(tick_number).await;
+        }
+        if written > 0 {
+            debug!("Indexed {} finalized ticks up to {}", written, finalized);
+        }
+        Ok(written)
+    }
+
+    fn spool_path(&self, tick_number: u64, tick_hash: &[u8; 32]) -> PathBuf {
+        self.spool.join(format!("{:016x}-{}.json", tick_number, hex::encode(tick_hash)))
+    }
+
+    /// Transactions spooled for the tick with `tick_hash`
+    async fn spooled(
+        &self,
+        tick_number: u64,
+        tick_hash: &[u8; 32],
+    ) -> Result<Option<Vec<IndexedTransaction>>> {
+        match tokio::fs::read(self.spool_path(tick_number, tick_hash)).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Remove spooled ticks up to `tick_number`, including reorged ones
+    async fn prune_spool(&self, tick_number: u64) {
+        let mut entries = match tokio::fs::read_dir(&self.spool).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read indexer spool: {}", e);
+                return;
+            }
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name();
+            let spooled = name
+                .to_str()
+                .and_then(|name| name.get(..16))
+                .and_then(|tick| u64::from_str_radix(tick, 16).ok());
+            if spooled.is_some_and(|spooled| spooled <= tick_number) {
+                if let Err(e) = tokio::fs::remove_file(entry.path()).await {
+                    warn!("Failed to remove {}: {}", entry.path().display(), e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::witness::{self, TickVote, Witness, WitnessSet};
+    use kala_state::TickType;
+    use kala_transaction::Send;
+
+    fn certificate(tick_number: u64, fork: u8) -> TickCertificate {
+        let hash = |tick: u64| {
+            let mut hash = [fork; 32];
+            hash[..8].copy_from_slice(&tick.to_le_bytes());
+            hash
+        };
+        TickCertificate {
+            tick_number,
+            tick_type: TickType::Empty,
+            vdf_iteration: tick_number * 1024,
+            vdf_form: ("1".into(), "0".into(), "1".into()),
+            hash_chain_value: [0u8; 32],
+            tick_hash: hash(tick_number),
+            transaction_count: 0,
+            transaction_merkle_root: [0u8; 32],
+            timestamp: 0,
+            previous_tick_hash: if tick_number == 0 { [0u8; 32] } else { hash(tick_number - 1) },
+            usage: Default::default(),
+        }
+    }
+
+    fn indexed(tick_number: u64) -> IndexedTick {
+        let certificate = certificate(tick_number, 0);
+        IndexedTick {
+            tick_number,
+            tick_hash: hex::encode(certificate.tick_hash),
+            certificate,
+            finality: None,
+            transactions: None,
+        }
+    }
+
+    fn applied(amount: u64) -> AppliedTransaction {
+        AppliedTransaction {
+            envelope_hash: [amount as u8; 32],
+            accounts: vec![[1u8; 32], [2u8; 32]],
+            transaction: Transaction::Send(Send {
+                sender: [1u8; 32],
+                receiver: [2u8; 32],
+                denom: [0u8; 32],
+                amount,
+                nonce: 0,
+                signature: vec![0u8; 64],
+                gas_sponsorer: [0u8; 32],
+                memo: Vec::new(),
+                sponsor_signature: Vec::new(),
+                tip: 0,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_truncates_torn_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("feed.jsonl");
+
+        let sink = FileSink::open(&path).unwrap();
+        assert_eq!(sink.position().await.unwrap(), None);
+        sink.append(&indexed(0)).await.unwrap();
+        sink.append(&indexed(1)).await.unwrap();
+        drop(sink);
+        let complete = std::fs::metadata(&path).unwrap().len();
+
+        // A crash in the middle of writing tick 2
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"{\"tick_number\":2,").unwrap();
+        drop(file);
+
+        let sink = FileSink::open(&path).unwrap();
+        let position = sink.position().await.unwrap().unwrap();
+        assert_eq!(position.tick_number, 1);
+        assert_eq!(position.tick_hash, certificate(1, 0).tick_hash);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), complete);
+
+        sink.append(&indexed(2)).await.unwrap();
+        let feed = std::fs::read_to_string(&path).unwrap();
+        let ticks: Vec<u64> = feed
+            .lines()
+            .map(|line| serde_json::from_str::<IndexedTick>(line).unwrap().tick_number)
+            .collect();
+        assert_eq!(ticks, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_indexer_writes_finalized_ticks_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::open(dir.path().join("db").to_str().unwrap()).unwrap();
+        let feed = dir.path().join("feed.jsonl");
+        let spool = dir.path().join("feed.jsonl.spool");
+
+        let indexer = Indexer::new(Arc::new(FileSink::open(&feed).unwrap()), &spool).unwrap();
+        for tick in 0..4 {
+            let certificate = certificate(tick, 0);
+            state_db.store_tick(&certificate).await.unwrap();
+            indexer.stage(&certificate, &[applied(tick + 1)]).await.unwrap();
+        }
+        // A tick 1 on a fork that lost
+        indexer.stage(&certificate(1, 7), &[applied(100)]).await.unwrap();
+        // Tick 0 arrived through sync
+        std::fs::remove_file(indexer.spool_path(0, &certificate(0, 0).tick_hash)).unwrap();
+
+        let key = witness::derive_bls_key(&[5u8; 32]).unwrap();
+        let witnesses = WitnessSet::new(vec![Witness {
+            key: [5u8; 32],
+            bls_key: witness::bls_public_key(&key),
+            weight: 1,
+        }])
+        .unwrap();
+        let vote = TickVote::sign(2, certificate(2, 0).tick_hash, [5u8; 32], &key);
+        let finality = TickFinality::aggregate(&witnesses, &[vote]).unwrap();
+        state_db.store_tick_finality(&finality).await.unwrap();
+
+        assert_eq!(indexer.catch_up(&state_db, 2).await.unwrap(), 3);
+        assert_eq!(indexer.catch_up(&state_db, 2).await.unwrap(), 0);
+
+        // Resumes from the feed after a restart, without repeating ticks
+        drop(indexer);
+        let indexer = Indexer::new(Arc::new(FileSink::open(&feed).unwrap()), &spool).unwrap();
+        assert_eq!(indexer.catch_up(&state_db, 3).await.unwrap(), 1);
+
+        let ticks: Vec<IndexedTick> = std::fs::read_to_string(&feed)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(ticks.iter().map(|tick| tick.tick_number).collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert!(ticks[0].transactions.is_none());
+        let transactions = ticks[1].transactions.as_ref().unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].envelope_hash, hex::encode([2u8; 32]));
+        assert!(matches!(transactions[0].transaction, Transaction::Send(Send { amount: 2, .. })));
+        assert!(ticks[1].finality.is_none());
+        assert_eq!(ticks[2].finality.as_ref().unwrap().verify(&witnesses).unwrap(), 1);
+
+        // The losing fork's transactions went with the rest of the spool
+        assert_eq!(std::fs::read_dir(&spool).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_indexer_stops_at_unlinked_tick() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::open(dir.path().join("db").to_str().unwrap()).unwrap();
+        let feed = dir.path().join("feed.jsonl");
+        let sink = Arc::new(FileSink::open(&feed).unwrap());
+        let indexer = Indexer::new(sink.clone(), dir.path().join("spool")).unwrap();
+
+        state_db.store_tick(&certificate(0, 0)).await.unwrap();
+        state_db.store_tick(&certificate(1, 7)).await.unwrap();
+        assert!(indexer.catch_up(&state_db, 1).await.is_err());
+        assert_eq!(sink.position().await.unwrap().unwrap().tick_number, 0);
+    }
+}
@@ -0,0 +1,205 @@
+//! Key share exchange for threshold-locked envelopes
+//!
+//! [`WitnessKeyShares`] is the node's [`KeyShareExchange`]. When the tick
+//! processor has committed a tick's ordering, it decrypts this witness's
+//! share of every threshold-locked envelope in the tick with the witness
+//! key behind the [`Signer`], records it, and gossips it as a signed
+//! [`ShareReveal`]. Reveals gossiped by other witnesses are verified
+//! against the current witness set and collected alongside, until the
+//! processor asks for them at the end of the decryption phase.
+
+use std::sync::Arc;
+
+use kala_common::crypto::shamir::KeyShare;
+use kala_common::crypto::signer::Signer;
+use kala_common::error::KalaResult;
+use kala_state::{ShareReveal, ShareRevealPool, WitnessSet};
+use kala_transaction::{KeyLock, TimelockTransaction};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, warn};
+
+use crate::consensus::KeyShareExchange;
+use crate::network::{GossipMessage, NetworkHandle};
+
+/// Reveals this node's key shares and collects those of other witnesses
+pub struct WitnessKeyShares {
+    /// Decrypts and signs this witness's shares
+    signer: Arc<dyn Signer>,
+    /// Ed25519 key shares are encrypted to
+    witness_key: [u8; 32],
+    /// Verified reveals, ours included
+    reveals: Mutex<ShareRevealPool>,
+    /// Where reveals are gossiped, once the node has joined the network
+    network: RwLock<Option<NetworkHandle>>,
+}
+
+impl WitnessKeyShares {
+    /// Exchange shares for the witness key behind `signer`
+    ///
+    /// # Parameters
+    ///
+    /// - `signer`: Signer holding the witness keys
+    /// - `witness_key`: The signer's Ed25519 public key
+    pub fn new(signer: Arc<dyn Signer>, witness_key: [u8; 32]) -> Self {
+        Self {
+            signer,
+            witness_key,
+            reveals: Mutex::new(ShareRevealPool::new()),
+            network: RwLock::new(None),
+        }
+    }
+
+    /// Gossip this node's reveals over `network` from now on
+    pub async fn attach(&self, network: NetworkHandle) {
+        *self.network.write().await = Some(network);
+    }
+
+    /// Record a reveal received from a peer
+    ///
+    /// # Returns
+    ///
+    /// Whether the reveal was new; an error if it isn't from a member of
+    /// `witnesses` or its signature doesn't verify
+    pub async fn insert(&self, reveal: ShareReveal, witnesses: &WitnessSet) -> KalaResult<bool> {
+        self.reveals.lock().await.insert(reveal, witnesses)
+    }
+
+    /// Drop reveals for ticks before `tick_num`
+    pub async fn prune(&self, tick_num: u64) {
+        self.reveals.lock().await.prune(tick_num);
+    }
+
+    /// Decrypt and sign this witness's share of `envelope`, if it holds one
+    async fn open_share(&self, tick_num: u64, envelope: &TimelockTransaction) -> KalaResult<Option<ShareReveal>> {
+        let KeyLock::Threshold(lock) = &envelope.lock else {
+            return Ok(None);
+        };
+        let Some(encrypted) = lock.share_of(&self.witness_key) else {
+            return Ok(None);
+        };
+
+        let shared_secret = self.signer.key_agreement(&lock.ephemeral_key).await?;
+        let share = encrypted.open(&lock.ephemeral_key, &shared_secret)?;
+        ShareReveal::sign(tick_num, envelope.envelope_hash(), share, self.signer.as_ref())
+            .await
+            .map(Some)
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyShareExchange for WitnessKeyShares {
+    async fn reveal(&self, tick_num: u64, envelopes: &[TimelockTransaction]) {
+        for envelope in envelopes {
+            let reveal = match self.open_share(tick_num, envelope).await {
+                Ok(Some(reveal)) => reveal,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(
+                        "Failed to reveal key share of {}: {}",
+                        hex::encode(&envelope.envelope_hash()[..8]),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if !self.reveals.lock().await.insert_verified(reveal.clone()) {
+                continue;
+            }
+            debug!(
+                "Revealing key share {} of {} for tick {}",
+                reveal.share.index,
+                hex::encode(&reveal.envelope_hash[..8]),
+                tick_num
+            );
+            if let Some(network) = self.network.read().await.as_ref() {
+                if let Err(e) = network.publish(GossipMessage::ShareReveal(reveal)).await {
+                    warn!("Failed to gossip key share: {}", e);
+                }
+            }
+        }
+    }
+
+    async fn shares(&self, tick_num: u64, envelope_hash: &[u8; 32]) -> Vec<KeyShare> {
+        self.reveals.lock().await.shares(tick_num, envelope_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_common::crypto::signer::{InMemorySigner, SignatureScheme};
+    use kala_state::Witness;
+    use kala_transaction::{open_threshold_transaction, seal_threshold_transaction, Send, Transaction};
+
+    #[tokio::test]
+    async fn test_witnesses_reveal_and_collect_shares() {
+        let signers: Vec<Arc<dyn Signer>> = (1..=3u8)
+            .map(|seed| Arc::new(InMemorySigner::from_seed(&[seed; 32]).unwrap()) as Arc<dyn Signer>)
+            .collect();
+        let keys: Vec<[u8; 32]> = signers
+            .iter()
+            .map(|signer| signer.public_key(SignatureScheme::Ed25519).try_into().unwrap())
+            .collect();
+        let witnesses = WitnessSet::new(
+            signers
+                .iter()
+                .zip(&keys)
+                .map(|(signer, key)| Witness {
+                    key: *key,
+                    bls_key: signer.public_key(SignatureScheme::Bls12381),
+                    weight: 1,
+                })
+                .collect(),
+        )
+        .unwrap();
+
+        let tx = Transaction::Send(Send {
+            sender: [1u8; 32],
+            receiver: [2u8; 32],
+            denom: [0u8; 32],
+            amount: 50,
+            nonce: 1,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: 0,
+        });
+        let envelope = seal_threshold_transaction(&tx, 4, 0, &keys, 2).unwrap();
+        let envelope_hash = envelope.envelope_hash();
+
+        // Every witness reveals its own share
+        let exchanges: Vec<WitnessKeyShares> = signers
+            .iter()
+            .zip(&keys)
+            .map(|(signer, key)| WitnessKeyShares::new(signer.clone(), *key))
+            .collect();
+        for exchange in &exchanges {
+            exchange.reveal(4, std::slice::from_ref(&envelope)).await;
+        }
+        let own = exchanges[0].shares(4, &envelope_hash).await;
+        assert_eq!(own.len(), 1);
+        assert!(open_threshold_transaction(&envelope, &own).is_err());
+
+        // A second witness's reveal, received over gossip, completes the key
+        let gossiped = exchanges[1].open_share(4, &envelope).await.unwrap().unwrap();
+        assert!(exchanges[0].insert(gossiped.clone(), &witnesses).await.unwrap());
+        assert!(!exchanges[0].insert(gossiped, &witnesses).await.unwrap());
+        let shares = exchanges[0].shares(4, &envelope_hash).await;
+        match open_threshold_transaction(&envelope, &shares).unwrap() {
+            Transaction::Send(send) => assert_eq!(send.amount, 50),
+            _ => panic!("Transaction type mismatch"),
+        }
+
+        // Witnesses without a share reveal nothing
+        let outsider = Arc::new(InMemorySigner::from_seed(&[9u8; 32]).unwrap());
+        let outsider_key = outsider.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+        let exchange = WitnessKeyShares::new(outsider, outsider_key);
+        exchange.reveal(4, &[envelope]).await;
+        assert!(exchange.shares(4, &envelope_hash).await.is_empty());
+
+        exchanges[0].prune(5).await;
+        assert!(exchanges[0].shares(4, &envelope_hash).await.is_empty());
+    }
+}
@@ -12,7 +12,18 @@
 //! - **Eternal VDF**: Continuous computation creating an unstoppable timeline
 //! - **Tick-based Consensus**: Fixed epochs of 65,536 iterations
 //! - **RSW Timelock**: MEV protection through temporal encryption
+//! - **Threshold Keys**: Envelope keys shared among witnesses as an alternative to RSW
 //! - **Single Node**: Simplified implementation for demonstration
+//! - **Gossip Network**: Optional libp2p gossipsub for multi-node operation
+//! - **Peer Discovery**: Kademlia and mDNS with a persistent peer store
+//! - **Tick Sync**: Catching up from peers with parallel VDF verification
+//! - **Fork Choice**: Reorgs onto the heaviest tick chain after partitions
+//! - **Genesis**: Sealed genesis files verified by every node on startup
+//! - **Slashing**: Evidence of witnesses double-signing ticks, gossiped automatically
+//! - **Alerting**: Webhook alerts for stalls and consensus anomalies
+//! - **Indexing**: An exactly-once feed of finalized ticks and their transactions
+//! - **Tracing**: Per-transaction lifecycle timelines for debugging
+//! - **Specification**: The paper's tick rules, checked against the processor
 //!
 //! ## Example
 //!
@@ -47,19 +58,108 @@ pub mod consensus;
 /// Node implementation
 pub mod node;
 
+/// Peer-to-peer gossip network
+pub mod network;
+
+/// Witness signing round for tick finality
+pub mod finality;
+
+/// Fork choice across competing tick chains
+pub mod fork_choice;
+
+/// Witness double-sign detection
+pub mod evidence;
+
+/// Genesis files with canonical hashes
+pub mod genesis;
+
+/// Performance sampling for the metrics history
+pub mod metrics;
+
+/// Wall-clock calibration of the VDF
+pub mod calibration;
+
+/// Hooks into node events
+pub mod observer;
+
+/// Webhook alerts for consensus anomalies
+pub mod alerts;
+
+/// S3-compatible remote archive for old ticks
+pub mod archive;
+
+/// Retention of timestamped payloads
+pub mod retention;
+
+/// Node events published to message queues
+pub mod event_sink;
+
+/// Exactly-once feed of finalized ticks for indexers
+pub mod indexer;
+
+/// Transaction lifecycle tracing
+pub mod trace;
+
+/// Account watch subscriptions
+pub mod watch;
+
+/// Denomination metadata for RPC responses
+pub mod denom;
+
+/// Nonce reservations for parallel transaction construction
+pub mod nonces;
+
+/// Replace-by-fee among resubmissions of a nonce
+pub mod nonce_queue;
+
+/// Persistent store of known peers
+pub mod peer_store;
+
+/// Tick sync for nodes catching up with the network
+pub mod sync;
+
+/// Key share exchange for threshold-locked envelopes
+pub mod key_shares;
+
+/// Executable specification of the paper's tick rules
+pub mod spec;
+
 // Serialization and networking now provided by kala-common
 
 /// Prelude with commonly used types
 pub mod prelude {
-    pub use crate::config::NodeConfig;
-    pub use crate::consensus::TickProcessor;
-    pub use crate::node::KalaNode;
+    pub use crate::alerts::{Alert, AlertKind, AlertSink};
+    pub use crate::archive::S3Archive;
+    pub use crate::config::{
+        AlertConfig, ArchiveConfig, CheckpointConfig, DenomConfig, EventSinkConfig, NodeConfig,
+        SignerConfig, WitnessConfig,
+    };
+    pub use crate::consensus::{AppliedTransaction, KeyShareExchange, TickOutcome, TickProcessor};
+    pub use crate::denom::DenomRegistry;
+    pub use crate::event_sink::{ChangedAccount, EventSink, NodeEvent};
+    pub use crate::evidence::DoubleSignDetector;
+    pub use crate::finality::{FinalityTracker, VoteMetadataSource};
+    pub use crate::fork_choice::{ChainWeight, ForkChoice, Reorg};
+    pub use crate::genesis::{Genesis, GenesisAccount};
+    pub use crate::indexer::{FileSink, IndexedTick, IndexedTransaction, Indexer, IndexerSink};
+    pub use crate::key_shares::WitnessKeyShares;
+    pub use crate::node::{KalaNode, LogFilterControl};
+    pub use crate::nonces::NonceReservations;
+    pub use crate::network::{GossipMessage, GossipTopic, NetworkConfig, NetworkHandle, NetworkService};
+    pub use crate::observer::{AccountChange, NodeObserver};
+    pub use crate::peer_store::{PeerRecord, PeerStore};
+    pub use crate::sync::{SyncRequest, SyncResponse, TickSync, TrustedCheckpoint};
+    pub use crate::trace::TransactionTracer;
+    pub use crate::watch::AccountWatcher;
     // Re-export kala-common prelude
     pub use kala_common::prelude::*;
 }
 
 // Re-export main types at crate root
-pub use config::NodeConfig;
+pub use config::{
+    AlertConfig, ArchiveConfig, CheckpointConfig, DenomConfig, EventSinkConfig, NodeConfig,
+    PayloadRetention, PayloadRetentionTier, SignerConfig, WitnessConfig,
+};
 pub use consensus::TickProcessor;
 pub use node::KalaNode;
 
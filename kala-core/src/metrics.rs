@@ -0,0 +1,125 @@
+//! Sampling of node performance metrics
+//!
+//! The node reports every tick it produces to a [`MetricsRecorder`], which
+//! folds them into one [`MetricsSample`] per [`METRICS_SAMPLE_INTERVAL`].
+//! Samples are persisted in the state database's metrics ring and served
+//! through `kala_getMetricsHistory` and the built-in status page.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use kala_state::MetricsSample;
+
+/// How often the recorder emits a sample
+pub const METRICS_SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Accumulates tick timings between samples
+///
+/// # Example
+///
+/// ```
+/// use kala_core::metrics::MetricsRecorder;
+/// use std::time::Duration;
+///
+/// let mut recorder = MetricsRecorder::new(Duration::ZERO);
+/// let sample = recorder
+///     .record_tick(7, 65536, Duration::from_millis(500), 3)
+///     .unwrap();
+/// assert_eq!(sample.tick_number, 7);
+/// assert_eq!(sample.iterations_per_second, 131072.0);
+/// ```
+pub struct MetricsRecorder {
+    interval: Duration,
+    window_start: Instant,
+    ticks: u64,
+    iterations: u64,
+    busy: Duration,
+    max_tick: Duration,
+}
+
+impl MetricsRecorder {
+    /// Create a recorder emitting a sample every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            window_start: Instant::now(),
+            ticks: 0,
+            iterations: 0,
+            busy: Duration::ZERO,
+            max_tick: Duration::ZERO,
+        }
+    }
+
+    /// Record a completed tick
+    ///
+    /// # Parameters
+    ///
+    /// - `tick_number`: The tick that was produced
+    /// - `iterations`: VDF iterations computed for the tick
+    /// - `duration`: Wall-clock time the tick took
+    /// - `mempool_depth`: Transactions left in the pool after the tick
+    ///
+    /// # Returns
+    ///
+    /// A sample covering every tick since the previous one, once the
+    /// sample interval has elapsed
+    pub fn record_tick(
+        &mut self,
+        tick_number: u64,
+        iterations: u64,
+        duration: Duration,
+        mempool_depth: usize,
+    ) -> Option<MetricsSample> {
+        self.ticks += 1;
+        self.iterations += iterations;
+        self.busy += duration;
+        self.max_tick = self.max_tick.max(duration);
+
+        if self.window_start.elapsed() < self.interval {
+            return None;
+        }
+
+        let busy_secs = self.busy.as_secs_f64();
+        let sample = MetricsSample {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            tick_number,
+            ticks: self.ticks,
+            iterations_per_second: if busy_secs > 0.0 {
+                self.iterations as f64 / busy_secs
+            } else {
+                0.0
+            },
+            mean_tick_ms: (self.busy / self.ticks as u32).as_millis() as u64,
+            max_tick_ms: self.max_tick.as_millis() as u64,
+            mempool_depth: mempool_depth as u64,
+        };
+
+        *self = Self::new(self.interval);
+        Some(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_cover_interval() {
+        let mut recorder = MetricsRecorder::new(Duration::from_millis(50));
+        assert!(recorder.record_tick(0, 1000, Duration::from_millis(100), 0).is_none());
+        assert!(recorder.record_tick(1, 1000, Duration::from_millis(300), 0).is_none());
+
+        std::thread::sleep(Duration::from_millis(60));
+        let sample = recorder.record_tick(2, 1000, Duration::from_millis(200), 4).unwrap();
+        assert_eq!(sample.tick_number, 2);
+        assert_eq!(sample.ticks, 3);
+        assert_eq!(sample.iterations_per_second, 5000.0);
+        assert_eq!(sample.mean_tick_ms, 200);
+        assert_eq!(sample.max_tick_ms, 300);
+        assert_eq!(sample.mempool_depth, 4);
+
+        // The next sample starts a fresh window
+        assert!(recorder.record_tick(3, 1000, Duration::from_millis(100), 0).is_none());
+    }
+}
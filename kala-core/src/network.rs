@@ -0,0 +1,1284 @@
+//! Peer-to-peer gossip layer for Kala nodes
+//!
+//! This module connects Kala nodes over libp2p and propagates the data a
+//! multi-node timeline depends on using gossipsub:
+//!
+//! - **Encrypted envelopes**: timelock transactions accepted by any node
+//! - **Witness observations**: when a witness first saw each envelope
+//! - **Tick certificates**: finalized ticks for cross-node comparison, in
+//!   full, compact, or as headers only, see [`CertificateDetail`]
+//! - **Tick votes**: witness signatures finalizing each tick
+//! - **Epoch signatures**: witness signatures on sealed epoch summaries
+//!
+//! Messages travel in the protobuf wire format of
+//! [`kala_common::proto`], so witnesses written in other languages can take
+//! part. They are identified by the hash of their encoding, so the same payload
+//! arriving from several peers is delivered to the node only once. Peers that
+//! forward undecodable messages are penalised through gossipsub peer scoring
+//! and eventually graylisted.
+//!
+//! Peers are found through configured bootstrap nodes, a Kademlia DHT, and
+//! mDNS on the local network. Every peer seen is recorded in a
+//! [`PeerStore`] along with a long-lived reputation, so a restarted node
+//! redials the peers it knows and keeps refusing the ones it has banned.
+//!
+//! Each certificate is published in every [`CertificateDetail`], each on its
+//! own topic, and a node subscribes to the one its link can carry. Peers
+//! therefore negotiate the detail they receive through their subscriptions,
+//! and gossipsub only forwards each variant to the peers that asked for it.
+//!
+//! Nodes also answer [`SyncRequest`]s from peers that are catching up, over
+//! a request/response protocol alongside gossip, encoded the same way by
+//! [`SyncCodec`].
+
+use anyhow::{anyhow, Result};
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, StreamExt};
+use libp2p::{
+    core::ConnectedPoint,
+    gossipsub::{self, IdentTopic, MessageAcceptance, MessageAuthenticity, MessageId, TopicHash},
+    identify,
+    identity::Keypair,
+    kad, mdns,
+    multiaddr::Protocol,
+    noise,
+    request_response::{self, OutboundRequestId, ProtocolSupport},
+    swarm::{behaviour::toggle::Toggle, dial_opts::DialOpts, NetworkBehaviour, SwarmEvent},
+    tcp, yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info, warn};
+
+use crate::peer_store::{PeerRecord, PeerStore};
+use crate::sync::{serve_request, SyncRequest, SyncResponse};
+use kala_common::error::KalaResult;
+use kala_common::proto::{self, gossip_message, required, ProtoCodec};
+use kala_state::{
+    EpochSignature, ShareReveal, StateDB, TickCertificate, TickHeader, TickVote, WitnessObservation,
+};
+use kala_transaction::TimelockTransaction;
+
+/// Maximum size of a single gossip message (1MB)
+pub const MAX_GOSSIP_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// Number of recently seen message hashes kept for deduplication
+pub const SEEN_CACHE_SIZE: usize = 16 * 1024;
+
+/// Gossipsub heartbeat interval
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Capacity of the command and inbound message channels
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Kademlia protocol name, keeping the Kala DHT separate from other networks
+const KAD_PROTOCOL: StreamProtocol = StreamProtocol::new("/kala/kad/1.0.0");
+
+/// Request/response protocol for tick sync
+const SYNC_PROTOCOL: StreamProtocol = StreamProtocol::new("/kala/sync/2");
+
+/// Largest sync request read from a peer
+const MAX_SYNC_REQUEST_SIZE: u64 = 1024 * 1024;
+
+/// Largest sync response read from a peer, which may carry a full state snapshot
+const MAX_SYNC_RESPONSE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Time allowed for a sync response, which may carry a full state snapshot
+const SYNC_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Protocol version advertised through identify
+const IDENTIFY_PROTOCOL: &str = "/kala/id/1.0.0";
+
+/// How often peer store changes are written to the database
+const PEER_STORE_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Reputation gained for each valid gossip message
+const VALID_MESSAGE_REPUTATION: i32 = 1;
+
+/// Reputation lost for each invalid gossip message
+const INVALID_MESSAGE_REPUTATION: i32 = -50;
+
+/// Settings for the network service
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    /// Multiaddr to listen on, e.g. `/ip4/0.0.0.0/tcp/30333`
+    pub listen_addr: String,
+    /// Multiaddrs of peers to dial directly on startup
+    pub peers: Vec<String>,
+    /// Multiaddrs with peer IDs used to seed the Kademlia routing table
+    pub bootstrap_nodes: Vec<String>,
+    /// Maximum number of connected peers
+    pub max_peers: usize,
+    /// Whether to discover peers on the local network through mDNS
+    pub enable_mdns: bool,
+    /// Detail of the tick certificates to receive over gossip
+    pub certificate_detail: CertificateDetail,
+}
+
+impl NetworkConfig {
+    /// Configuration listening on `listen_addr` with no known peers
+    pub fn new(listen_addr: impl Into<String>) -> Self {
+        Self {
+            listen_addr: listen_addr.into(),
+            peers: Vec::new(),
+            bootstrap_nodes: Vec::new(),
+            max_peers: 50,
+            enable_mdns: true,
+            certificate_detail: CertificateDetail::Full,
+        }
+    }
+}
+
+/// How much of each tick certificate a node receives over gossip
+///
+/// Certificates carry no succinct proof of their VDF segment yet, only its
+/// output form, which a receiver checks by recomputing the segment. The
+/// form is most of a certificate, so nodes on slow links can take less of
+/// it and fetch the rest over sync when they need to check a tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CertificateDetail {
+    /// Whole certificates
+    #[default]
+    Full,
+    /// Certificates without the form's `c`, which the receiver derives,
+    /// see [`TickCertificate::compact`]
+    Compact,
+    /// Only [`TickHeader`]s; certificates that aren't already known are
+    /// fetched from peers when needed
+    Header,
+}
+
+impl CertificateDetail {
+    /// Topic certificates in this detail are published on
+    pub fn topic(&self) -> GossipTopic {
+        match self {
+            Self::Full => GossipTopic::TickCertificates,
+            Self::Compact => GossipTopic::CompactTickCertificates,
+            Self::Header => GossipTopic::TickHeaders,
+        }
+    }
+}
+
+/// Gossip topics used by Kala nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GossipTopic {
+    /// Timelock-encrypted transactions awaiting inclusion
+    EncryptedEnvelopes,
+    /// Witness observations of envelope arrival iterations
+    WitnessObservations,
+    /// Finalized tick certificates
+    TickCertificates,
+    /// Finalized tick certificates without the form's `c`
+    CompactTickCertificates,
+    /// Headers of finalized ticks
+    TickHeaders,
+    /// Witness votes on tick hashes
+    TickVotes,
+    /// Witness signatures on epoch summaries
+    EpochSignatures,
+    /// Witness key shares of threshold-locked envelopes
+    KeyShares,
+}
+
+impl GossipTopic {
+    /// All topics
+    pub const ALL: [GossipTopic; 8] = [
+        GossipTopic::EncryptedEnvelopes,
+        GossipTopic::WitnessObservations,
+        GossipTopic::TickCertificates,
+        GossipTopic::CompactTickCertificates,
+        GossipTopic::TickHeaders,
+        GossipTopic::TickVotes,
+        GossipTopic::EpochSignatures,
+        GossipTopic::KeyShares,
+    ];
+
+    /// Topic name on the wire
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::EncryptedEnvelopes => "/kala/envelopes/2",
+            Self::WitnessObservations => "/kala/observations/2",
+            Self::TickCertificates => "/kala/ticks/2",
+            Self::CompactTickCertificates => "/kala/ticks/compact/2",
+            Self::TickHeaders => "/kala/ticks/headers/2",
+            Self::TickVotes => "/kala/votes/2",
+            Self::EpochSignatures => "/kala/epochs/2",
+            Self::KeyShares => "/kala/shares/2",
+        }
+    }
+
+    /// Topics a node receiving certificates in `detail` subscribes to
+    ///
+    /// Every topic but those of the other certificate details.
+    pub fn subscribed(detail: CertificateDetail) -> Vec<GossipTopic> {
+        let certificates = [
+            GossipTopic::TickCertificates,
+            GossipTopic::CompactTickCertificates,
+            GossipTopic::TickHeaders,
+        ];
+        Self::ALL
+            .into_iter()
+            .filter(|topic| *topic == detail.topic() || !certificates.contains(topic))
+            .collect()
+    }
+
+    /// The gossipsub topic for this Kala topic
+    pub fn ident_topic(&self) -> IdentTopic {
+        IdentTopic::new(self.as_str())
+    }
+
+    /// Look up the Kala topic for a gossipsub topic hash
+    pub fn from_hash(hash: &TopicHash) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|topic| topic.ident_topic().hash() == *hash)
+    }
+}
+
+/// Messages exchanged over the gossip network
+#[derive(Clone, Serialize, Deserialize)]
+pub enum GossipMessage {
+    /// A timelock-encrypted transaction
+    Envelope(TimelockTransaction),
+    /// A witness observation of an envelope
+    Observation(WitnessObservation),
+    /// A finalized tick certificate
+    TickCertificate(TickCertificate),
+    /// A finalized tick certificate made [`compact`](TickCertificate::compact)
+    CompactTickCertificate(TickCertificate),
+    /// The header of a finalized tick
+    TickHeader(TickHeader),
+    /// A witness vote on a tick hash
+    TickVote(TickVote),
+    /// A witness signature on an epoch summary
+    EpochSignature(EpochSignature),
+    /// A witness's key share of a threshold-locked envelope
+    ShareReveal(ShareReveal),
+}
+
+impl GossipMessage {
+    /// The topic this message is published on
+    pub fn topic(&self) -> GossipTopic {
+        match self {
+            Self::Envelope(_) => GossipTopic::EncryptedEnvelopes,
+            Self::Observation(_) => GossipTopic::WitnessObservations,
+            Self::TickCertificate(_) => GossipTopic::TickCertificates,
+            Self::CompactTickCertificate(_) => GossipTopic::CompactTickCertificates,
+            Self::TickHeader(_) => GossipTopic::TickHeaders,
+            Self::TickVote(_) => GossipTopic::TickVotes,
+            Self::EpochSignature(_) => GossipTopic::EpochSignatures,
+            Self::ShareReveal(_) => GossipTopic::KeyShares,
+        }
+    }
+
+    /// Encode the message for the wire
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        Ok(self.encode_proto())
+    }
+
+    /// Decode a message received from the wire
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        Self::decode_proto(data).map_err(|e| anyhow!("Failed to decode gossip message: {}", e))
+    }
+}
+
+impl ProtoCodec for GossipMessage {
+    type Proto = proto::GossipMessage;
+
+    fn to_proto(&self) -> proto::GossipMessage {
+        use gossip_message::Payload;
+
+        let payload = match self {
+            Self::Envelope(envelope) => Payload::Envelope(envelope.to_proto()),
+            Self::Observation(observation) => Payload::Observation(observation.to_proto()),
+            Self::TickCertificate(certificate) => Payload::TickCertificate(certificate.to_proto()),
+            Self::CompactTickCertificate(certificate) => {
+                Payload::CompactTickCertificate(certificate.to_proto())
+            }
+            Self::TickHeader(header) => Payload::TickHeader(header.to_proto()),
+            Self::TickVote(vote) => Payload::TickVote(vote.to_proto()),
+            Self::EpochSignature(signature) => Payload::EpochSignature(signature.to_proto()),
+            Self::ShareReveal(reveal) => Payload::ShareReveal(reveal.to_proto()),
+        };
+        proto::GossipMessage { payload: Some(payload) }
+    }
+
+    fn from_proto(proto: proto::GossipMessage) -> KalaResult<Self> {
+        use gossip_message::Payload;
+
+        Ok(match required(proto.payload, "gossip payload")? {
+            Payload::Envelope(envelope) => Self::Envelope(TimelockTransaction::from_proto(envelope)?),
+            Payload::Observation(observation) => Self::Observation(WitnessObservation::from_proto(observation)?),
+            Payload::TickCertificate(certificate) => Self::TickCertificate(TickCertificate::from_proto(certificate)?),
+            Payload::CompactTickCertificate(certificate) => {
+                Self::CompactTickCertificate(TickCertificate::from_proto(certificate)?)
+            }
+            Payload::TickHeader(header) => Self::TickHeader(TickHeader::from_proto(header)?),
+            Payload::TickVote(vote) => Self::TickVote(TickVote::from_proto(vote)?),
+            Payload::EpochSignature(signature) => Self::EpochSignature(EpochSignature::from_proto(signature)?),
+            Payload::ShareReveal(reveal) => Self::ShareReveal(ShareReveal::from_proto(reveal)?),
+        })
+    }
+}
+
+/// Content hash used as the gossipsub message ID and for deduplication
+pub fn message_hash(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// Bounded set of recently seen message hashes
+///
+/// Gossipsub only remembers message IDs for a limited time. This cache
+/// keeps the most recent [`SEEN_CACHE_SIZE`] hashes so that a message
+/// replayed later, or one we published ourselves, is not handed to the
+/// node a second time.
+pub struct SeenCache {
+    capacity: usize,
+    order: VecDeque<[u8; 32]>,
+    seen: HashSet<[u8; 32]>,
+}
+
+impl SeenCache {
+    /// Create a cache holding at most `capacity` hashes
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            order: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Record a hash, returning `false` if it was already present
+    pub fn insert(&mut self, hash: [u8; 32]) -> bool {
+        if !self.seen.insert(hash) {
+            return false;
+        }
+
+        self.order.push_back(hash);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    /// Whether the hash has been seen recently
+    pub fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.seen.contains(hash)
+    }
+
+    /// Number of hashes currently held
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+/// libp2p behaviour for a Kala node
+#[derive(NetworkBehaviour)]
+pub struct KalaBehaviour {
+    /// Gossipsub pub/sub for envelopes, observations, and certificates
+    pub gossipsub: gossipsub::Behaviour,
+    /// Kademlia DHT for discovering peers beyond the bootstrap nodes
+    pub kademlia: kad::Behaviour<kad::store::MemoryStore>,
+    /// mDNS discovery of peers on the local network
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
+    /// Exchange of listen addresses with connected peers
+    pub identify: identify::Behaviour,
+    /// Tick sync requests between nodes
+    pub sync: request_response::Behaviour<SyncCodec>,
+}
+
+impl KalaBehaviour {
+    /// Build the behaviour with content-addressed message IDs and peer scoring
+    ///
+    /// Messages are validated by the node before being forwarded, and peers
+    /// that send invalid messages lose score on the offending topic.
+    /// If mDNS is requested but cannot be started, for example because
+    /// multicast is unavailable, the node continues without it.
+    pub fn new(keypair: &Keypair, enable_mdns: bool) -> Result<Self> {
+        let local_peer_id = keypair.public().to_peer_id();
+
+        let config = gossipsub::ConfigBuilder::default()
+            .heartbeat_interval(HEARTBEAT_INTERVAL)
+            .validation_mode(gossipsub::ValidationMode::Strict)
+            .validate_messages()
+            .max_transmit_size(MAX_GOSSIP_MESSAGE_SIZE)
+            .duplicate_cache_time(Duration::from_secs(120))
+            .message_id_fn(|message: &gossipsub::Message| {
+                MessageId::from(hex::encode(message_hash(&message.data)))
+            })
+            .build()
+            .map_err(|e| anyhow!("Invalid gossipsub config: {}", e))?;
+
+        let mut gossipsub =
+            gossipsub::Behaviour::new(MessageAuthenticity::Signed(keypair.clone()), config)
+                .map_err(|e| anyhow!("Failed to create gossipsub: {}", e))?;
+
+        let (params, thresholds) = peer_score_config();
+        gossipsub
+            .with_peer_score(params, thresholds)
+            .map_err(|e| anyhow!("Invalid peer score parameters: {}", e))?;
+
+        let mut kademlia = kad::Behaviour::with_config(
+            local_peer_id,
+            kad::store::MemoryStore::new(local_peer_id),
+            kad::Config::new(KAD_PROTOCOL),
+        );
+        // Nodes are expected to be reachable, so answer DHT queries from the start
+        kademlia.set_mode(Some(kad::Mode::Server));
+
+        let mdns = if enable_mdns {
+            match mdns::tokio::Behaviour::new(mdns::Config::default(), local_peer_id) {
+                Ok(mdns) => Some(mdns),
+                Err(e) => {
+                    warn!("mDNS discovery unavailable: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let identify = identify::Behaviour::new(
+            identify::Config::new(IDENTIFY_PROTOCOL.to_string(), keypair.public())
+                .with_agent_version(format!("kala/{}", crate::VERSION)),
+        );
+
+        let sync = request_response::Behaviour::new(
+            [(SYNC_PROTOCOL, ProtocolSupport::Full)],
+            request_response::Config::default().with_request_timeout(SYNC_REQUEST_TIMEOUT),
+        );
+
+        Ok(Self {
+            gossipsub,
+            kademlia,
+            mdns: mdns.into(),
+            identify,
+            sync,
+        })
+    }
+}
+
+/// Protobuf codec for the sync protocol
+///
+/// Each stream carries one request and one response, so messages are not
+/// length-prefixed: the reader takes everything up to the end of the
+/// stream, within [`MAX_SYNC_REQUEST_SIZE`] or [`MAX_SYNC_RESPONSE_SIZE`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncCodec;
+
+/// Read a whole message of at most `limit` bytes from `io`
+async fn read_message<T, M>(io: &mut T, limit: u64) -> io::Result<M>
+where
+    T: AsyncRead + Unpin + Send,
+    M: ProtoCodec,
+{
+    let mut data = Vec::new();
+    io.take(limit).read_to_end(&mut data).await?;
+    M::decode_proto(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+#[async_trait::async_trait]
+impl request_response::Codec for SyncCodec {
+    type Protocol = StreamProtocol;
+    type Request = SyncRequest;
+    type Response = SyncResponse;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<SyncRequest>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_message(io, MAX_SYNC_REQUEST_SIZE).await
+    }
+
+    async fn read_response<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<SyncResponse>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_message(io, MAX_SYNC_RESPONSE_SIZE).await
+    }
+
+    async fn write_request<T>(&mut self, _: &StreamProtocol, io: &mut T, request: SyncRequest) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(&request.encode_proto()).await
+    }
+
+    async fn write_response<T>(&mut self, _: &StreamProtocol, io: &mut T, response: SyncResponse) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(&response.encode_proto()).await
+    }
+}
+
+/// Peer scoring parameters for the Kala topics
+///
+/// Invalid messages carry a heavy penalty so a peer forwarding garbage is
+/// graylisted after a handful of offences, while first deliveries of valid
+/// messages earn a small bonus.
+pub fn peer_score_config() -> (gossipsub::PeerScoreParams, gossipsub::PeerScoreThresholds) {
+    let mut params = gossipsub::PeerScoreParams::default();
+
+    for topic in GossipTopic::ALL {
+        let topic_params = gossipsub::TopicScoreParams {
+            topic_weight: match topic {
+                GossipTopic::TickCertificates => 1.0,
+                GossipTopic::CompactTickCertificates => 1.0,
+                GossipTopic::TickHeaders => 1.0,
+                GossipTopic::EncryptedEnvelopes => 0.5,
+                GossipTopic::WitnessObservations => 0.5,
+                GossipTopic::TickVotes => 1.0,
+                GossipTopic::EpochSignatures => 0.5,
+                GossipTopic::KeyShares => 0.5,
+            },
+            first_message_deliveries_weight: 1.0,
+            first_message_deliveries_decay: 0.9,
+            first_message_deliveries_cap: 100.0,
+            invalid_message_deliveries_weight: -100.0,
+            invalid_message_deliveries_decay: 0.5,
+            ..Default::default()
+        };
+        params
+            .topics
+            .insert(topic.ident_topic().hash(), topic_params);
+    }
+
+    let thresholds = gossipsub::PeerScoreThresholds {
+        gossip_threshold: -10.0,
+        publish_threshold: -50.0,
+        graylist_threshold: -80.0,
+        accept_px_threshold: 10.0,
+        opportunistic_graft_threshold: 5.0,
+    };
+
+    (params, thresholds)
+}
+
+/// Commands sent from the node to the network service
+enum NetworkCommand {
+    Publish(GossipMessage),
+    ConnectedPeers(oneshot::Sender<Vec<PeerId>>),
+    KnownPeers(oneshot::Sender<Vec<(PeerRecord, bool)>>),
+    BanPeer(PeerId, oneshot::Sender<PeerRecord>),
+    SyncRequest {
+        peer: PeerId,
+        request: SyncRequest,
+        reply: oneshot::Sender<Result<SyncResponse>>,
+    },
+}
+
+/// Cloneable handle for publishing to the gossip network
+#[derive(Clone)]
+pub struct NetworkHandle {
+    commands: mpsc::Sender<NetworkCommand>,
+    local_peer_id: PeerId,
+}
+
+impl NetworkHandle {
+    /// Publish a message to all peers subscribed to its topic
+    pub async fn publish(&self, message: GossipMessage) -> Result<()> {
+        self.commands
+            .send(NetworkCommand::Publish(message))
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))
+    }
+
+    /// This node's peer ID
+    pub fn local_peer_id(&self) -> PeerId {
+        self.local_peer_id
+    }
+
+    /// Peers currently connected to this node
+    pub async fn connected_peers(&self) -> Result<Vec<PeerId>> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(NetworkCommand::ConnectedPeers(reply))
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))?;
+        response
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))
+    }
+
+    /// Records of all known peers, each with whether it's connected now
+    pub async fn known_peers(&self) -> Result<Vec<(PeerRecord, bool)>> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(NetworkCommand::KnownPeers(reply))
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))?;
+        response
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))
+    }
+
+    /// Ban a peer, disconnecting it if connected, and return its record
+    pub async fn ban_peer(&self, peer: PeerId) -> Result<PeerRecord> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(NetworkCommand::BanPeer(peer, reply))
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))?;
+        response
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))
+    }
+
+    /// Send a sync request to a connected peer and wait for its response
+    pub async fn sync_request(&self, peer: PeerId, request: SyncRequest) -> Result<SyncResponse> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(NetworkCommand::SyncRequest {
+                peer,
+                request,
+                reply,
+            })
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))?;
+        response
+            .await
+            .map_err(|_| anyhow!("Network service has stopped"))?
+    }
+}
+
+/// Network service driving the libp2p swarm
+///
+/// Created with [`NetworkService::new`], which also returns a
+/// [`NetworkHandle`] for publishing and a receiver of validated inbound
+/// messages. Call [`NetworkService::run`] on a separate task.
+pub struct NetworkService {
+    swarm: Swarm<KalaBehaviour>,
+    commands: mpsc::Receiver<NetworkCommand>,
+    inbound: mpsc::Sender<GossipMessage>,
+    seen: SeenCache,
+    peer_store: PeerStore,
+    max_peers: usize,
+    state_db: Option<Arc<StateDB>>,
+    pending_sync: HashMap<OutboundRequestId, oneshot::Sender<Result<SyncResponse>>>,
+}
+
+impl NetworkService {
+    /// Create the network service and start listening
+    ///
+    /// The node identity is taken from the peer store, and the best known
+    /// peers from previous runs are dialed along with the configured ones.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: Listen address, peers, bootstrap nodes, and limits
+    /// - `peer_store`: Known peers and the node identity
+    ///
+    /// # Returns
+    ///
+    /// The service, a handle for publishing, and a receiver of inbound messages
+    pub fn new(
+        config: NetworkConfig,
+        peer_store: PeerStore,
+    ) -> Result<(Self, NetworkHandle, mpsc::Receiver<GossipMessage>)> {
+        let keypair = peer_store.load_or_create_identity()?;
+        let local_peer_id = keypair.public().to_peer_id();
+        let enable_mdns = config.enable_mdns;
+        let certificate_detail = config.certificate_detail;
+
+        let mut swarm = SwarmBuilder::with_existing_identity(keypair)
+            .with_tokio()
+            .with_tcp(
+                tcp::Config::default(),
+                noise::Config::new,
+                yamux::Config::default,
+            )
+            .map_err(|e| anyhow!("Failed to configure transport: {}", e))?
+            .with_behaviour(|key| KalaBehaviour::new(key, enable_mdns).map_err(|e| e.into()))
+            .map_err(|e| anyhow!("Failed to create network behaviour: {}", e))?
+            .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(60)))
+            .build();
+
+        for topic in GossipTopic::subscribed(certificate_detail) {
+            swarm
+                .behaviour_mut()
+                .gossipsub
+                .subscribe(&topic.ident_topic())
+                .map_err(|e| anyhow!("Failed to subscribe to {}: {}", topic.as_str(), e))?;
+        }
+
+        let listen_addr: Multiaddr = config
+            .listen_addr
+            .parse()
+            .map_err(|e| anyhow!("Invalid listen address {}: {}", config.listen_addr, e))?;
+        swarm.listen_on(listen_addr)?;
+
+        for peer in &config.peers {
+            let addr: Multiaddr = peer
+                .parse()
+                .map_err(|e| anyhow!("Invalid peer address {}: {}", peer, e))?;
+            if let Err(e) = swarm.dial(addr) {
+                warn!("Failed to dial peer {}: {}", peer, e);
+            }
+        }
+
+        for node in &config.bootstrap_nodes {
+            let addr: Multiaddr = node
+                .parse()
+                .map_err(|e| anyhow!("Invalid bootstrap node {}: {}", node, e))?;
+            let peer_id = peer_id_of(&addr)
+                .ok_or_else(|| anyhow!("Bootstrap node {} is missing a /p2p/ peer ID", node))?;
+            swarm.behaviour_mut().kademlia.add_address(&peer_id, addr.clone());
+            if let Err(e) = swarm.dial(addr) {
+                warn!("Failed to dial bootstrap node {}: {}", node, e);
+            }
+        }
+
+        let known = peer_store.dial_candidates(config.max_peers);
+        for (peer_id, addrs) in &known {
+            for addr in addrs {
+                swarm.behaviour_mut().kademlia.add_address(peer_id, addr.clone());
+            }
+            let opts = DialOpts::peer_id(*peer_id)
+                .addresses(addrs.clone())
+                .build();
+            if let Err(e) = swarm.dial(opts) {
+                debug!("Failed to dial known peer {}: {}", peer_id, e);
+            }
+        }
+
+        if let Err(e) = swarm.behaviour_mut().kademlia.bootstrap() {
+            debug!("Skipping initial DHT bootstrap: {}", e);
+        }
+
+        let (command_tx, command_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let (inbound_tx, inbound_rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        info!(
+            "P2P network started with peer ID {} ({} known peers, {} bootstrap nodes)",
+            local_peer_id,
+            known.len(),
+            config.bootstrap_nodes.len()
+        );
+
+        let service = Self {
+            swarm,
+            commands: command_rx,
+            inbound: inbound_tx,
+            seen: SeenCache::new(SEEN_CACHE_SIZE),
+            peer_store,
+            max_peers: config.max_peers,
+            state_db: None,
+            pending_sync: HashMap::new(),
+        };
+        let handle = NetworkHandle {
+            commands: command_tx,
+            local_peer_id,
+        };
+
+        Ok((service, handle, inbound_rx))
+    }
+
+    /// Serve sync requests from peers out of `state_db`
+    ///
+    /// Without a database, sync requests are answered with an error.
+    pub fn with_state_db(mut self, state_db: Arc<StateDB>) -> Self {
+        self.state_db = Some(state_db);
+        self
+    }
+
+    /// Addresses the swarm is currently listening on
+    pub fn listeners(&self) -> Vec<Multiaddr> {
+        self.swarm.listeners().cloned().collect()
+    }
+
+    /// Drive the swarm until all handles are dropped
+    pub async fn run(mut self) {
+        let mut flush_interval = tokio::time::interval(PEER_STORE_FLUSH_INTERVAL);
+
+        loop {
+            tokio::select! {
+                command = self.commands.recv() => match command {
+                    Some(NetworkCommand::Publish(message)) => self.publish(message),
+                    Some(NetworkCommand::ConnectedPeers(reply)) => {
+                        let _ = reply.send(self.swarm.connected_peers().copied().collect());
+                    }
+                    Some(NetworkCommand::KnownPeers(reply)) => {
+                        let peers = self
+                            .peer_store
+                            .records()
+                            .map(|(peer, record)| (record.clone(), self.swarm.is_connected(peer)))
+                            .collect();
+                        let _ = reply.send(peers);
+                    }
+                    Some(NetworkCommand::BanPeer(peer_id, reply)) => {
+                        warn!("Banning peer {} on operator request", peer_id);
+                        let record = self.peer_store.ban(peer_id);
+                        let _ = self.swarm.disconnect_peer_id(peer_id);
+                        let _ = reply.send(record);
+                    }
+                    Some(NetworkCommand::SyncRequest { peer, request, reply }) => {
+                        let id = self.swarm.behaviour_mut().sync.send_request(&peer, request);
+                        self.pending_sync.insert(id, reply);
+                    }
+                    None => {
+                        info!("All network handles dropped, stopping P2P network");
+                        self.flush_peer_store().await;
+                        return;
+                    }
+                },
+                event = self.swarm.select_next_some() => self.handle_swarm_event(event).await,
+                _ = flush_interval.tick() => self.flush_peer_store().await,
+            }
+        }
+    }
+
+    async fn flush_peer_store(&mut self) {
+        match self.peer_store.flush().await {
+            Ok(0) => {}
+            Ok(count) => debug!("Persisted {} peer records", count),
+            Err(e) => warn!("Failed to persist peer store: {}", e),
+        }
+    }
+
+    /// Dial a discovered peer unless it is banned, connected, or we are full
+    fn dial_discovered(&mut self, peer_id: PeerId, addrs: Vec<Multiaddr>) {
+        if peer_id == *self.swarm.local_peer_id()
+            || self.swarm.is_connected(&peer_id)
+            || self.peer_store.is_banned(&peer_id)
+            || self.swarm.connected_peers().count() >= self.max_peers
+        {
+            return;
+        }
+
+        let opts = DialOpts::peer_id(peer_id)
+            .addresses(addrs)
+            .build();
+        if let Err(e) = self.swarm.dial(opts) {
+            debug!("Failed to dial discovered peer {}: {}", peer_id, e);
+        }
+    }
+
+    fn publish(&mut self, message: GossipMessage) {
+        let data = match message.encode() {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("{}", e);
+                return;
+            }
+        };
+
+        // Don't republish something a peer already sent us
+        let hash = message_hash(&data);
+        if self.seen.contains(&hash) {
+            debug!("Skipping publish of already seen {} message", message.topic().as_str());
+            return;
+        }
+
+        let topic = message.topic();
+        match self
+            .swarm
+            .behaviour_mut()
+            .gossipsub
+            .publish(topic.ident_topic(), data)
+        {
+            Ok(id) => {
+                self.seen.insert(hash);
+                debug!("Published {} message {}", topic.as_str(), id)
+            }
+            // Expected while the node is still alone on the network
+            Err(gossipsub::PublishError::InsufficientPeers) => {
+                debug!("No peers to publish {} message to", topic.as_str())
+            }
+            Err(e) => warn!("Failed to publish {} message: {}", topic.as_str(), e),
+        }
+    }
+
+    async fn handle_swarm_event(&mut self, event: SwarmEvent<KalaBehaviourEvent>) {
+        match event {
+            SwarmEvent::Behaviour(KalaBehaviourEvent::Gossipsub(gossipsub::Event::Message {
+                propagation_source,
+                message_id,
+                message,
+            })) => {
+                let acceptance = self.validate_message(&message).await;
+                self.update_reputation(propagation_source, &acceptance);
+                if let Err(e) = self
+                    .swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(&message_id, &propagation_source, acceptance)
+                {
+                    debug!("Failed to report validation result for {}: {}", message_id, e);
+                }
+            }
+            SwarmEvent::Behaviour(KalaBehaviourEvent::Sync(event)) => {
+                self.handle_sync_event(event).await;
+            }
+            SwarmEvent::NewListenAddr { address, .. } => {
+                info!("P2P listening on {}", address);
+            }
+            SwarmEvent::Behaviour(KalaBehaviourEvent::Kademlia(kad::Event::RoutingUpdated {
+                peer,
+                addresses,
+                ..
+            })) => {
+                let addrs: Vec<Multiaddr> = addresses.iter().cloned().collect();
+                self.peer_store.add_addresses(peer, &addrs);
+                self.dial_discovered(peer, addrs);
+            }
+            SwarmEvent::Behaviour(KalaBehaviourEvent::Kademlia(
+                kad::Event::OutboundQueryProgressed {
+                    result: kad::QueryResult::Bootstrap(result),
+                    ..
+                },
+            )) => match result {
+                Ok(ok) => debug!("DHT bootstrap step complete, {} peers remaining", ok.num_remaining),
+                Err(e) => debug!("DHT bootstrap failed: {:?}", e),
+            },
+            SwarmEvent::Behaviour(KalaBehaviourEvent::Mdns(mdns::Event::Discovered(found))) => {
+                for (peer_id, addr) in found {
+                    debug!("Discovered peer {} on local network at {}", peer_id, addr);
+                    self.swarm.behaviour_mut().kademlia.add_address(&peer_id, addr.clone());
+                    self.peer_store.add_addresses(peer_id, [&addr]);
+                    self.dial_discovered(peer_id, vec![addr]);
+                }
+            }
+            SwarmEvent::Behaviour(KalaBehaviourEvent::Identify(identify::Event::Received {
+                peer_id,
+                info,
+                ..
+            })) => {
+                // Only peers speaking the Kala DHT protocol go in the routing table
+                if info.protocols.contains(&KAD_PROTOCOL) {
+                    for addr in &info.listen_addrs {
+                        self.swarm.behaviour_mut().kademlia.add_address(&peer_id, addr.clone());
+                    }
+                }
+                self.peer_store.add_addresses(peer_id, &info.listen_addrs);
+            }
+            SwarmEvent::ConnectionEstablished {
+                peer_id,
+                endpoint,
+                num_established,
+                ..
+            } => {
+                if self.peer_store.is_banned(&peer_id) {
+                    debug!("Disconnecting banned peer {}", peer_id);
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return;
+                }
+
+                if num_established.get() == 1
+                    && self.swarm.connected_peers().count() > self.max_peers
+                {
+                    debug!("Peer limit {} reached, disconnecting {}", self.max_peers, peer_id);
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return;
+                }
+
+                // Only dialed addresses are known to be reachable; listeners
+                // advertise theirs through identify
+                let addr = match &endpoint {
+                    ConnectedPoint::Dialer { address, .. } => Some(address),
+                    ConnectedPoint::Listener { .. } => None,
+                };
+                self.peer_store.record_seen(peer_id, addr);
+                debug!("Connected to peer {}", peer_id);
+            }
+            SwarmEvent::ConnectionClosed { peer_id, .. } => {
+                debug!("Disconnected from peer {}", peer_id);
+            }
+            SwarmEvent::OutgoingConnectionError { peer_id, error, .. } => {
+                warn!("Failed to connect to {:?}: {}", peer_id, error);
+            }
+            _ => {}
+        }
+    }
+
+    async fn handle_sync_event(
+        &mut self,
+        event: request_response::Event<SyncRequest, SyncResponse>,
+    ) {
+        match event {
+            request_response::Event::Message {
+                peer,
+                message: request_response::Message::Request { request, channel, .. },
+            } => {
+                debug!("Sync request {:?} from {}", request, peer);
+                let response = match &self.state_db {
+                    Some(state_db) => serve_request(state_db, request).await,
+                    None => SyncResponse::Error("Sync is not served by this node".to_string()),
+                };
+                if self
+                    .swarm
+                    .behaviour_mut()
+                    .sync
+                    .send_response(channel, response)
+                    .is_err()
+                {
+                    debug!("Peer {} went away before its sync response was sent", peer);
+                }
+            }
+            request_response::Event::Message {
+                message: request_response::Message::Response { request_id, response },
+                ..
+            } => {
+                if let Some(reply) = self.pending_sync.remove(&request_id) {
+                    let _ = reply.send(Ok(response));
+                }
+            }
+            request_response::Event::OutboundFailure { peer, request_id, error } => {
+                if let Some(reply) = self.pending_sync.remove(&request_id) {
+                    let _ = reply.send(Err(anyhow!("Sync request to {} failed: {}", peer, error)));
+                }
+            }
+            request_response::Event::InboundFailure { peer, error, .. } => {
+                debug!("Failed to answer sync request from {}: {}", peer, error);
+            }
+            request_response::Event::ResponseSent { .. } => {}
+        }
+    }
+
+    /// Credit or penalise the peer that forwarded a message
+    fn update_reputation(&mut self, peer_id: PeerId, acceptance: &MessageAcceptance) {
+        let delta = match acceptance {
+            MessageAcceptance::Accept => VALID_MESSAGE_REPUTATION,
+            MessageAcceptance::Reject => INVALID_MESSAGE_REPUTATION,
+            MessageAcceptance::Ignore => return,
+        };
+
+        self.peer_store.adjust_reputation(peer_id, delta);
+        if self.peer_store.is_banned(&peer_id) {
+            warn!("Banning peer {} for repeated invalid messages", peer_id);
+            let _ = self.swarm.disconnect_peer_id(peer_id);
+        }
+    }
+
+    /// Decode an inbound message and hand it to the node if it is new
+    async fn validate_message(&mut self, message: &gossipsub::Message) -> MessageAcceptance {
+        let decoded = match GossipMessage::decode(&message.data) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                warn!("Rejecting message from {:?}: {}", message.source, e);
+                return MessageAcceptance::Reject;
+            }
+        };
+
+        // Messages must arrive on the topic matching their type
+        if GossipTopic::from_hash(&message.topic) != Some(decoded.topic()) {
+            warn!("Rejecting message on mismatched topic {}", message.topic);
+            return MessageAcceptance::Reject;
+        }
+
+        if !self.seen.insert(message_hash(&message.data)) {
+            return MessageAcceptance::Ignore;
+        }
+
+        if self.inbound.send(decoded).await.is_err() {
+            return MessageAcceptance::Ignore;
+        }
+
+        MessageAcceptance::Accept
+    }
+}
+
+/// The peer ID at the end of a `/p2p/` multiaddr, if present
+fn peer_id_of(addr: &Multiaddr) -> Option<PeerId> {
+    addr.iter().find_map(|protocol| match protocol {
+        Protocol::P2p(peer_id) => Some(peer_id),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::TickType;
+
+    fn test_certificate(tick_number: u64) -> TickCertificate {
+        TickCertificate {
+            tick_number,
+            tick_type: TickType::Empty,
+            vdf_iteration: tick_number * 1024,
+            vdf_form: ("1".into(), "0".into(), "1".into()),
+            hash_chain_value: [1u8; 32],
+            tick_hash: [2u8; 32],
+            transaction_count: 0,
+            transaction_merkle_root: [0u8; 32],
+            timestamp: 0,
+            previous_tick_hash: [0u8; 32],
+            usage: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_topic_lookup() {
+        for topic in GossipTopic::ALL {
+            assert_eq!(GossipTopic::from_hash(&topic.ident_topic().hash()), Some(topic));
+        }
+        assert_eq!(GossipTopic::from_hash(&IdentTopic::new("/other/1").hash()), None);
+    }
+
+    #[test]
+    fn test_subscribed_topics() {
+        let header = GossipTopic::subscribed(CertificateDetail::Header);
+        assert_eq!(header.len(), GossipTopic::ALL.len() - 2);
+        assert!(header.contains(&GossipTopic::TickHeaders));
+        assert!(header.contains(&GossipTopic::TickVotes));
+        assert!(!header.contains(&GossipTopic::TickCertificates));
+        assert!(!header.contains(&GossipTopic::CompactTickCertificates));
+
+        let full = GossipTopic::subscribed(CertificateDetail::Full);
+        assert!(full.contains(&GossipTopic::TickCertificates));
+        assert!(!full.contains(&GossipTopic::TickHeaders));
+
+        let header = GossipMessage::TickHeader(test_certificate(3).header());
+        let decoded = GossipMessage::decode(&header.encode().unwrap()).unwrap();
+        assert_eq!(decoded.topic(), CertificateDetail::Header.topic());
+    }
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = GossipMessage::Observation(WitnessObservation {
+            witness: [7u8; 32],
+            tick_number: 3,
+            envelope_hash: [9u8; 32],
+            observed_iteration: 3100,
+            vdf_proof: [5u8; 32],
+            signature: vec![1u8; 64],
+        });
+
+        let encoded = message.encode().unwrap();
+        let decoded = GossipMessage::decode(&encoded).unwrap();
+        assert_eq!(decoded.topic(), GossipTopic::WitnessObservations);
+        assert_eq!(decoded.encode().unwrap(), encoded);
+
+        assert!(GossipMessage::decode(b"not a message").is_err());
+    }
+
+    #[test]
+    fn test_seen_cache_deduplicates_and_evicts() {
+        let mut cache = SeenCache::new(2);
+        assert!(cache.insert([1u8; 32]));
+        assert!(!cache.insert([1u8; 32]));
+        assert!(cache.insert([2u8; 32]));
+        assert!(cache.insert([3u8; 32]));
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&[1u8; 32]));
+        assert!(cache.contains(&[3u8; 32]));
+    }
+
+    #[test]
+    fn test_behaviour_accepts_score_params() {
+        let keypair = Keypair::generate_ed25519();
+        assert!(KalaBehaviour::new(&keypair, false).is_ok());
+    }
+
+    fn test_config() -> NetworkConfig {
+        NetworkConfig {
+            enable_mdns: false,
+            ..NetworkConfig::new("/ip4/127.0.0.1/tcp/0")
+        }
+    }
+
+    /// Start a node and wait for it to report its full listen address
+    async fn start_listening_node() -> (NetworkService, NetworkHandle, String) {
+        let (mut service, handle, _inbound) =
+            NetworkService::new(test_config(), PeerStore::in_memory()).unwrap();
+
+        let listen_addr = loop {
+            if let SwarmEvent::NewListenAddr { address, .. } =
+                service.swarm.select_next_some().await
+            {
+                break address;
+            }
+        };
+        let peer_addr = format!("{}/p2p/{}", listen_addr, handle.local_peer_id());
+
+        (service, handle, peer_addr)
+    }
+
+    /// Publish from `handle` until `inbound` receives a message
+    async fn expect_gossip(
+        handle: &NetworkHandle,
+        inbound: &mut mpsc::Receiver<GossipMessage>,
+    ) -> GossipMessage {
+        // Retry until the mesh has formed and the certificate arrives
+        tokio::time::timeout(Duration::from_secs(20), async {
+            loop {
+                handle
+                    .publish(GossipMessage::TickCertificate(test_certificate(5)))
+                    .await
+                    .unwrap();
+                if let Ok(Some(message)) =
+                    tokio::time::timeout(Duration::from_millis(500), inbound.recv()).await
+                {
+                    break message;
+                }
+            }
+        })
+        .await
+        .expect("certificate was not gossiped")
+    }
+
+    #[test]
+    fn test_peer_id_of() {
+        let peer_id = PeerId::random();
+        let addr: Multiaddr = format!("/ip4/127.0.0.1/tcp/1/p2p/{}", peer_id).parse().unwrap();
+        assert_eq!(peer_id_of(&addr), Some(peer_id));
+        assert_eq!(peer_id_of(&"/ip4/127.0.0.1/tcp/1".parse().unwrap()), None);
+    }
+
+    #[tokio::test]
+    async fn test_gossip_between_two_nodes() {
+        let (service_a, handle_a, peer_addr) = start_listening_node().await;
+
+        let config = NetworkConfig {
+            peers: vec![peer_addr],
+            ..test_config()
+        };
+        let (service_b, _handle_b, mut inbound_b) =
+            NetworkService::new(config, PeerStore::in_memory()).unwrap();
+        tokio::spawn(service_a.run());
+        tokio::spawn(service_b.run());
+
+        let received = expect_gossip(&handle_a, &mut inbound_b).await;
+        assert_eq!(received.topic(), GossipTopic::TickCertificates);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_node_discovery() {
+        let (service_a, handle_a, peer_addr) = start_listening_node().await;
+
+        let config = NetworkConfig {
+            bootstrap_nodes: vec![peer_addr],
+            ..test_config()
+        };
+        let (service_b, _handle_b, mut inbound_b) =
+            NetworkService::new(config, PeerStore::in_memory()).unwrap();
+        tokio::spawn(service_a.run());
+        tokio::spawn(service_b.run());
+
+        let received = expect_gossip(&handle_a, &mut inbound_b).await;
+        assert_eq!(received.topic(), GossipTopic::TickCertificates);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_node_requires_peer_id() {
+        let config = NetworkConfig {
+            bootstrap_nodes: vec!["/ip4/127.0.0.1/tcp/30333".to_string()],
+            ..test_config()
+        };
+        assert!(NetworkService::new(config, PeerStore::in_memory()).is_err());
+    }
+}
@@ -1,21 +1,70 @@
-use anyhow::Result;
-use sha2::{Digest, Sha256};
+use anyhow::{anyhow, Result};
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
-use crate::config::NodeConfig;
-use crate::consensus::TickProcessor;
+use crate::alerts::AlertSink;
+use crate::archive::S3Archive;
+use crate::calibration;
+use crate::config::{NodeConfig, SignerConfig};
+use crate::consensus::{AppliedTransaction, TickOutcome, TickProcessor};
+use crate::denom::DenomRegistry;
+use crate::event_sink::EventSink;
+use crate::indexer::{FileSink, Indexer};
+use crate::evidence::{evidence_transaction, DoubleSignDetector};
+use crate::finality::{FinalityTracker, VoteMetadataSource};
+use crate::fork_choice::{ForkChoice, Reorg, REORG_WINDOW};
+use crate::genesis::Genesis;
+use crate::key_shares::WitnessKeyShares;
+use crate::metrics::{MetricsRecorder, METRICS_SAMPLE_INTERVAL};
+use crate::network::{
+    CertificateDetail, GossipMessage, NetworkConfig, NetworkHandle, NetworkService,
+};
+use crate::nonces::{authenticate_reservation, NonceReservations};
+use crate::observer::{AccountChange, NodeObserver};
+use crate::peer_store::PeerStore;
+use crate::retention::PayloadRetainer;
+use crate::sync::{
+    verify_certificates, SyncAnchor, SyncRequest, SyncResponse, TickSync, VerifierPool,
+};
+use crate::trace::TransactionTracer;
+use crate::watch::AccountWatcher;
+use jsonrpsee::core::SubscriptionResult;
+use jsonrpsee::PendingSubscriptionSink;
+use kala_common::crypto::signer::{InMemorySigner, KeystoreSigner, RemoteSigner, SignatureScheme, Signer};
+use kala_common::database::{DatabaseConfig, FsyncPolicy, KalaDatabase};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::timeline::TimelineClock;
+use kala_common::types::consensus::PROTOCOL_FEATURES;
+use kala_common::PROTOCOL_VERSION;
 use kala_rpc::{
-    AccountInfo, ChainInfo, GetAccountRequest, GetTickRequest, KalaApiServer,
-    SubmitTransactionRequest, SubmitTransactionResponse,
+    AccountInfo, ChainInfo, ChainParams, FeatureStatus, FfiAllocations, FfiMemoryReport,
+    GetAccountRequest, GetEpochRequest, GetMetricsHistoryRequest, GetOracleValueRequest,
+    GetTickDataRequest, GetTickRangeProofRequest, GetTickRequest, HardnessBounds, KalaApiServer,
+    NodeVersion, NonceReservation, ProveTickDataRequest, ReserveNoncesRequest,
+    SubmitTransactionRequest, SubmitTransactionResponse, SupplyInfo, SyncMode, SyncStatus,
+    TickDataEntry, TickPhases,
+    TickProofVerification, TimelinePoint, TimestampDataRequest, TimestampVerification, TraceStage,
+    TransactionTrace, VdfCalibration, VdfTuning, VerifyTickProofRequest, MAX_TIMESTAMP_DATA_BYTES,
+};
+use kala_rpc::admin::{authorize, KalaAdminApiServer, MempoolEntry, PeerInfo, SnapshotExport};
+use kala_state::{
+    verify_tick_certificate, ArchiveBackend, ChainState, DoubleSignEvidence, EpochCertificate,
+    EpochSignature, GovernanceParameter, MetricsSample, ObservationPool, OracleProof,
+    SignedEpochSummary, StateDB, TickCertificate, TickFinality, TickHeader, TickRangeProof,
+    TickVote, TimeAnchor, TimeBounds, Witness, WitnessObservation, WitnessSet,
+};
+use kala_transaction::{seal_transaction, EncryptionContext, KeyLock, TimelockTransaction};
+use kala_vdf::{
+    DatabasePersistence, EternalVDF, PackedForm, PayloadLocation, VDFCheckpoint, VdfConfig,
+    VdfPersistence,
 };
-use kala_state::{ChainState, StateDB, TickCertificate};
-use kala_transaction::{EncryptionContext, TimelockTransaction};
-use kala_vdf::EternalVDF;
+use libp2p::PeerId;
 use serde_json;
 
 // RPC handler that communicates with the node via channels
+#[derive(Clone)]
 pub struct KalaRpcHandler {
     chain_info_tx: mpsc::Sender<mpsc::Sender<ChainInfo>>,
     submit_tx: mpsc::Sender<(
@@ -23,12 +72,74 @@ pub struct KalaRpcHandler {
         mpsc::Sender<Result<SubmitTransactionResponse, String>>,
     )>,
     state_db: Arc<StateDB>,
+    sync_status: Arc<RwLock<SyncStatus>>,
+    tracer: Arc<TransactionTracer>,
+    account_watcher: Arc<AccountWatcher>,
+    denoms: Arc<DenomRegistry>,
+    nonce_reservations: Arc<NonceReservations>,
+    timeline: Arc<RwLock<TimelineClock>>,
+    vdf: Arc<RwLock<EternalVDF>>,
+    config: Arc<NodeConfig>,
+}
+
+/// Admin RPC handler, served only when the node has an admin token
+#[derive(Clone)]
+pub struct KalaAdminHandler {
+    tx_pool: Arc<Mutex<Vec<TimelockTransaction>>>,
+    tracer: Arc<TransactionTracer>,
+    state: Arc<RwLock<ChainState>>,
+    state_db: Arc<StateDB>,
+    // Set once the node joins the gossip network
+    network: Arc<RwLock<Option<NetworkHandle>>>,
+    archiving: Arc<Mutex<()>>,
+    log_filter: Option<Arc<dyn LogFilterControl>>,
+    config: Arc<NodeConfig>,
+}
+
+/// Changes which log lines the node writes while it runs
+///
+/// Served by `admin_setLogLevel`. Implemented for the reload handle of a
+/// `tracing_subscriber` [`EnvFilter`](tracing_subscriber::EnvFilter).
+pub trait LogFilterControl: Send + Sync {
+    /// Replace the filter with `directives`, such as `debug` or
+    /// `info,kala_core::network=trace`
+    fn set_filter(&self, directives: &str) -> Result<()>;
+}
+
+impl<S: 'static> LogFilterControl
+    for tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, S>
+{
+    fn set_filter(&self, directives: &str) -> Result<()> {
+        let filter = tracing_subscriber::EnvFilter::try_new(directives)
+            .map_err(|e| anyhow!("Invalid log filter {}: {}", directives, e))?;
+        self.reload(filter)?;
+        Ok(())
+    }
 }
 
 // Transaction acceptance window constants
 const TX_ACCEPTANCE_WINDOW_START: f64 = 0.9; // Accept txs starting at 90% of previous tick
 const TX_ACCEPTANCE_WINDOW_END: f64 = 0.3; // Accept txs until 30% of target tick
 
+// Database key for the seed of the node's witness keys
+const WITNESS_KEY: &[u8] = b"witness_key";
+// Database key for the hash of the genesis the database was created from
+const GENESIS_HASH_KEY: &[u8] = b"genesis_hash";
+// Database key marking a database written with a simulated VDF clock
+const DEV_INSTANT_KEY: &[u8] = b"dev_instant";
+// Database key for the iteration and Unix time in milliseconds the timeline is anchored at
+const TIMELINE_ANCHOR_KEY: &[u8] = b"timeline_anchor";
+// Anchor intervals searched on either side of a tick for the anchors bounding its time
+const TIME_ANCHOR_SEARCH_INTERVALS: u64 = 16;
+// Signatures kept for epochs peers sealed before us
+const MAX_PENDING_EPOCH_SIGNATURES: usize = 1024;
+// Attempts at committing a finalized tick before the node stops
+const TICK_COMMIT_ATTEMPTS: u32 = 5;
+// Wait before the first commit retry, doubled after each failure
+const TICK_COMMIT_BACKOFF: Duration = Duration::from_millis(100);
+// Longest kala_timestampData waits for the tick holding its data to finalize
+const TIMESTAMP_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub struct KalaNode {
     config: NodeConfig,
     vdf: Arc<RwLock<EternalVDF>>,
@@ -37,24 +148,268 @@ pub struct KalaNode {
     tick_processor: Arc<TickProcessor>,
     // Transaction pool for encrypted transactions
     tx_pool: Arc<Mutex<Vec<TimelockTransaction>>>,
+    // Lifecycle timelines of recent transactions
+    tracer: Arc<TransactionTracer>,
+    // Progress of catching up with peers
+    sync_status: Arc<RwLock<SyncStatus>>,
+    // Signs epoch summaries, tick votes, and evidence with the witness keys
+    signer: Arc<dyn Signer>,
+    // Ed25519 public key identifying this node as a witness
+    witness_key: [u8; 32],
+    // Configured witnesses, re-weighted by stake at every epoch boundary
+    witness_candidates: Vec<Witness>,
+    // Tick votes awaiting quorum, checked against the current witness set
+    finality: Mutex<FinalityTracker>,
+    // Verified votes compared per tick and witness; taken after finality
+    double_signs: Mutex<DoubleSignDetector>,
+    // Recent competing tick chains, for switching to the heaviest one
+    fork_choice: Mutex<ForkChoice>,
+    // Witness observations of pending envelopes, agreed into canonical timestamps
+    observations: Mutex<ObservationPool>,
+    // Key shares of threshold-locked envelopes, ours and other witnesses'
+    key_shares: Arc<WitnessKeyShares>,
+    // Signatures for epochs not yet sealed locally; also serialises epoch updates
+    pending_epoch_signatures: Mutex<Vec<EpochSignature>>,
+    // Notified of ticks, failures, stalls, finality, and account changes
+    observers: Vec<Arc<dyn NodeObserver>>,
+    // Serves kala_watchAccount; also registered as an observer
+    account_watcher: Arc<AccountWatcher>,
+    // When the last tick was produced, for the watchdog
+    last_tick_at: Mutex<Instant>,
+    // Denomination metadata returned with balances
+    denoms: Arc<DenomRegistry>,
+    // Serves kala_reserveNonces; also registered as an observer
+    nonce_reservations: Arc<NonceReservations>,
+    // Threads synced and branch tick certificates are verified on
+    verifier: VerifierPool,
+    // Writes VDF certificates and checkpoints; reattached whenever the VDF is replaced
+    vdf_persistence: Arc<dyn VdfPersistence>,
+    // Metadata attached to this witness's tick votes
+    vote_metadata: Option<Arc<dyn VoteMetadataSource>>,
+    // Held while old ticks are uploaded to the archive
+    archiving: Arc<Mutex<()>>,
+    // Latest benchmark of the VDF speed, reported by kala_chainInfo
+    calibration: RwLock<Option<VdfCalibration>>,
+    // Maps iterations to wall-clock time at the calibrated speed
+    timeline: Arc<RwLock<TimelineClock>>,
+    // How the VDF library uses this machine
+    vdf_tuning: VdfConfig,
+    // Changed by admin_setLogLevel
+    log_filter: Option<Arc<dyn LogFilterControl>>,
+    // Feeds finalized ticks and their transactions to indexers
+    indexer: Option<Arc<Indexer>>,
 }
 
 impl KalaNode {
     pub async fn new(config: NodeConfig) -> Result<Self> {
+        Self::open(config, false).await
+    }
+
+    /// Open the node, continuing the VDF from its own on-disk output
+    ///
+    /// Like [`KalaNode::new`], but after a crash the VDF is restored from
+    /// its latest checkpoint and the tick certificates streamed after it,
+    /// instead of only the checkpoint in the chain state. The certificates
+    /// must continue the checkpoint's hash chain and reach the chain
+    /// state's current tick; otherwise the chain state's checkpoint is used.
+    ///
+    /// # Parameters
+    /// * `config` - Node configuration, with the database to resume from
+    ///
+    /// # Returns
+    /// The node, ready to run from the resumed iteration
+    pub async fn resume(config: NodeConfig) -> Result<Self> {
+        Self::open(config, true).await
+    }
+
+    async fn open(config: NodeConfig, resume: bool) -> Result<Self> {
         // Open state database
-        let state_db = Arc::new(StateDB::open(&config.db_path)?);
+        let mut state_db = StateDB::open_with_config(&DatabaseConfig {
+            path: config.db_path.clone(),
+            compaction_style: config.db_compaction,
+            read_cache_bytes: config.db_read_cache_bytes,
+            fsync_policy: config.db_fsync_policy,
+            compression: config.db_compression.clone(),
+            slow_op_threshold_ms: config.db_slow_op_threshold_ms,
+            ..DatabaseConfig::default()
+        })?;
+        let migrated = state_db.migrate_storage().await?;
+        if migrated > 0 {
+            info!("Rewrote {} records stored by an older node with packed forms", migrated);
+        }
+        let archive: Option<Arc<dyn ArchiveBackend>> = match &config.archive {
+            Some(archive) => Some(Arc::new(S3Archive::new(archive)?)),
+            None => None,
+        };
+        if let Some(archive) = &archive {
+            state_db = state_db.with_archive(archive.clone());
+        }
+        let state_db = Arc::new(state_db);
 
         // Load chain state
-        let chain_state = state_db.load_chain_state().await?;
+        let mut chain_state = state_db.load_chain_state().await?;
+
+        // A genesis file sets the initial state and must never change after
+        let genesis = match &config.genesis_file {
+            Some(path) => {
+                let (genesis, hash) = Genesis::load(path)?;
+                genesis.check_config(&config)?;
+                if check_genesis_hash(state_db.database(), &hash)? && chain_state.current_iteration == 0 {
+                    chain_state = genesis.chain_state()?;
+                    state_db.save_chain_state(&chain_state).await?;
+                }
+                info!("Genesis {} from {}", hex::encode(hash), path);
+                Some(genesis)
+            }
+            None => None,
+        };
+
+        // Without a genesis file a fresh chain starts from the configured
+        // parameters, and state over any other discriminant belongs to
+        // another chain
+        if genesis.is_none() && chain_state.current_iteration == 0 {
+            chain_state = Genesis::from_config(&config).chain_state()?;
+        }
+        if chain_state.vdf_checkpoint.discriminant != config.discriminant {
+            return Err(anyhow!(
+                "Database holds a chain over a different VDF discriminant than configured"
+            ));
+        }
+
+        // A tick finalized before its commit failed is committed now
+        replay_pending_tick(&state_db, &mut chain_state).await?;
+
+        // Until calibrated, the timeline runs at the reference hardware's rate
+        let (anchor_iteration, anchor_time_ms) = timeline_anchor(
+            state_db.database(),
+            genesis.as_ref().and_then(|genesis| genesis.genesis_time),
+            chain_state.current_iteration,
+        )?;
+        let mut timeline =
+            TimelineClock::new(anchor_iteration, anchor_time_ms, TimelineClock::target_rate());
+        if let Some(anchor) = state_db.latest_time_anchor().await? {
+            timeline.correct(anchor.iteration, anchor.time_ms);
+        }
 
-        // Initialize or restore VDF from checkpoint
-        let vdf = match EternalVDF::from_checkpoint(&chain_state.vdf_checkpoint) {
-            Ok(vdf) => Arc::new(RwLock::new(vdf)),
-            Err(e) => return Err(anyhow::anyhow!("Failed to initialize VDF: {}", e)),
+        // Tune the VDF library to this machine unless configured
+        let vdf_tuning = match config.vdf_tuning {
+            Some(tuning) => {
+                tuning.apply();
+                tuning
+            }
+            None => VdfConfig::auto_tune(&config.discriminant),
+        };
+        info!(
+            "VDF tuning: {} verification threads, AVX-512 {}, fast mode {}",
+            vdf_tuning.num_threads, vdf_tuning.avx512, vdf_tuning.fast_mode
+        );
+
+        // Simulated VDF output must never mix with real output
+        check_dev_instant(
+            state_db.database(),
+            config.dev_instant,
+            chain_state.current_iteration == 0,
+        )?;
+
+        // Initialize or restore VDF from checkpoint. A corrupt checkpoint
+        // can't be resumed, so a node with peers starts over from genesis
+        // and restores the chain state from them.
+        let resumed = if resume {
+            resume_vdf(state_db.database(), &chain_state)?
+        } else {
+            None
+        };
+        let mut vdf = match resumed {
+            Some(vdf) => vdf,
+            None => match EternalVDF::from_checkpoint(&chain_state.vdf_checkpoint) {
+                Ok(vdf) => vdf,
+                Err(e) if config.p2p_listen_addr.is_some() => {
+                    error!("Stored VDF checkpoint is invalid, resyncing from peers: {}", e);
+                    chain_state = match &genesis {
+                        Some(genesis) => genesis.chain_state()?,
+                        None => Genesis::from_config(&config).chain_state()?,
+                    };
+                    EternalVDF::from_checkpoint(&chain_state.vdf_checkpoint)
+                        .map_err(|e| anyhow!("Failed to initialize VDF: {}", e))?
+                }
+                Err(e) => return Err(anyhow::anyhow!("Failed to initialize VDF: {}", e)),
+            },
+        };
+        let vdf_persistence: Arc<dyn VdfPersistence> = Arc::new(PayloadRetainer::new(
+            Arc::new(DatabasePersistence::new(state_db.database())?),
+            config.payload_retention.clone(),
+            archive,
+        ));
+        vdf.set_persistence(vdf_persistence.clone(), config.vdf_checkpoint_interval);
+        let backend = vdf.set_backend(config.vdf_backend);
+        if backend != config.vdf_backend {
+            warn!("No CUDA device for the VDF, squaring on the CPU");
+        }
+        info!("VDF squarings run on the {:?} backend", backend);
+        if config.dev_open_mint {
+            warn!("dev_open_mint: every account may mint the native denomination; NOT FOR PRODUCTION");
+        }
+        if config.dev_instant {
+            warn!("dev_instant: the VDF is simulated and ticks prove nothing; NOT FOR PRODUCTION");
+            vdf.set_simulated(true);
+        }
+        let vdf = Arc::new(RwLock::new(vdf));
+
+        // Witness keys come from the configured signer, or a seed in the database
+        let signer: Arc<dyn Signer> = match &config.witness_signer {
+            Some(SignerConfig::Keystore { path, password_env }) => {
+                let password = std::env::var(password_env)
+                    .map_err(|_| anyhow!("Keystore password variable {} is not set", password_env))?;
+                Arc::new(KeystoreSigner::open(path, &password)?)
+            }
+            Some(SignerConfig::Remote { url }) => Arc::new(RemoteSigner::connect(url.clone()).await?),
+            None => Arc::new(InMemorySigner::from_seed(&load_or_create_witness_seed(
+                state_db.database(),
+            )?)?),
         };
+        let witness_key: [u8; 32] = signer
+            .public_key(SignatureScheme::Ed25519)
+            .try_into()
+            .map_err(|_| anyhow!("Signer has no 32-byte Ed25519 witness key"))?;
 
         // Create tick processor with proper parameters
-        let tick_processor = Arc::new(TickProcessor::new(config.iterations_per_tick));
+        let tracer = Arc::new(TransactionTracer::default());
+        let key_shares = Arc::new(WitnessKeyShares::new(signer.clone(), witness_key));
+        let verifier = VerifierPool::new(match config.sync_verify_threads {
+            0 => vdf_tuning.num_threads,
+            threads => threads,
+        })?;
+        let tick_processor = Arc::new(
+            TickProcessor::new(config.iterations_per_tick)
+                .with_tracer(tracer.clone())
+                .with_chain_id(config.chain_id.clone())
+                .with_key_shares(key_shares.clone()),
+        );
+
+        // Without a configured witness set the node witnesses its own ticks
+        let mut witnesses = match genesis.filter(|genesis| !genesis.witnesses.is_empty()) {
+            Some(genesis) => genesis.witness_set()?,
+            None => config
+                .witness_set()
+                .map_err(|e| anyhow!("Invalid witness set: {}", e))?,
+        };
+        if witnesses.is_empty() {
+            witnesses.push(Witness {
+                key: witness_key,
+                bls_key: signer.public_key(SignatureScheme::Bls12381),
+                weight: 1,
+            });
+        }
+        let witness_candidates = witnesses.clone();
+        let denoms = config
+            .denom_registry()
+            .map_err(|e| anyhow!("Invalid denomination registry: {}", e))?;
+
+        // The last epoch handover decides the witnesses after a restart
+        let witness_set = match state_db.latest_epoch_certificate().await? {
+            Some(certificate) => certificate.witnesses,
+            None => WitnessSet::new(witnesses)?.with_scheme(config.witness_vote_scheme),
+        };
 
         info!("Initialized Kala node - The Eternal Timeline");
         info!(
@@ -70,6 +425,52 @@ impl KalaNode {
             "  - Last tick hash: {}",
             hex::encode(chain_state.last_tick_hash)
         );
+        info!(
+            "  - Witness key: {} ({} witnesses, {} ticks per epoch)",
+            hex::encode(witness_key),
+            witness_set.witnesses().len(),
+            config.epoch_length
+        );
+
+        // Observations of ticks not yet processed survive a restart
+        let mut observations = ObservationPool::new();
+        for tick_number in chain_state.current_tick..=chain_state.current_tick + 1 {
+            for observation in state_db.get_observations(tick_number).await? {
+                observations.insert(observation, &witness_set).ok();
+            }
+        }
+
+        let mut fork_choice =
+            ForkChoice::new(&chain_state, config.iterations_per_tick, REORG_WINDOW);
+        if let Some(finalized) = state_db.finalized_tick().await? {
+            fork_choice.finalize(finalized);
+        }
+
+        let account_watcher = Arc::new(AccountWatcher::new());
+        let nonce_reservations = Arc::new(NonceReservations::new());
+        let mut observers: Vec<Arc<dyn NodeObserver>> =
+            vec![account_watcher.clone(), nonce_reservations.clone()];
+        if let Some(alerts) = &config.alerts {
+            observers.push(AlertSink::spawn(alerts.clone())?);
+        }
+        if !config.event_sinks.is_empty() {
+            observers.push(EventSink::spawn(&config.event_sinks)?);
+        }
+        let indexer = match &config.indexer_path {
+            Some(path) => {
+                let sink = Arc::new(FileSink::open(path)?);
+                Some(Arc::new(Indexer::new(sink, format!("{}.spool", path))?))
+            }
+            None => None,
+        };
+
+        let sync_status = SyncStatus {
+            mode: SyncMode::Following,
+            starting_tick: chain_state.current_tick,
+            current_tick: chain_state.current_tick,
+            highest_tick: chain_state.current_tick,
+            peer_count: 0,
+        };
 
         Ok(Self {
             config,
@@ -78,9 +479,109 @@ impl KalaNode {
             state_db,
             tick_processor,
             tx_pool: Arc::new(Mutex::new(Vec::new())),
+            tracer,
+            sync_status: Arc::new(RwLock::new(sync_status)),
+            signer,
+            witness_key,
+            witness_candidates,
+            finality: Mutex::new(FinalityTracker::new(witness_set)),
+            double_signs: Mutex::new(DoubleSignDetector::new()),
+            fork_choice: Mutex::new(fork_choice),
+            observations: Mutex::new(observations),
+            key_shares,
+            pending_epoch_signatures: Mutex::new(Vec::new()),
+            observers,
+            account_watcher,
+            last_tick_at: Mutex::new(Instant::now()),
+            denoms: Arc::new(denoms),
+            nonce_reservations,
+            verifier,
+            vdf_persistence,
+            vote_metadata: None,
+            archiving: Arc::new(Mutex::new(())),
+            calibration: RwLock::new(None),
+            timeline: Arc::new(RwLock::new(timeline)),
+            vdf_tuning,
+            log_filter: None,
+            indexer,
         })
     }
 
+    /// Register an observer for node events
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kala_core::observer::NodeObserver;
+    /// use kala_core::{KalaNode, NodeConfig};
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example(observer: Arc<dyn NodeObserver>) -> anyhow::Result<()> {
+    /// let node = KalaNode::new(NodeConfig::default()).await?.with_observer(observer);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_observer(mut self, observer: Arc<dyn NodeObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Attach metadata from `source` to this node's tick votes
+    ///
+    /// Only has an effect while the node is a witness. The metadata is
+    /// aggregated into the tick's finality record, served by
+    /// `kala_getTickFinality`. Metadata encoded as an
+    /// [`OracleReport`](kala_state::OracleReport) is also medianized into
+    /// the tick's oracle values, served by `kala_getOracleValue`.
+    pub fn with_vote_metadata(mut self, source: Arc<dyn VoteMetadataSource>) -> Self {
+        self.vote_metadata = Some(source);
+        self
+    }
+
+    /// Let operators change the log filter over the admin RPC namespace
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kala_core::{KalaNode, NodeConfig};
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let builder = tracing_subscriber::fmt().with_env_filter("info").with_filter_reloading();
+    /// let log_filter = builder.reload_handle();
+    /// builder.init();
+    ///
+    /// let node = KalaNode::new(NodeConfig::default())
+    ///     .await?
+    ///     .with_log_filter(Arc::new(log_filter));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_log_filter(mut self, log_filter: Arc<dyn LogFilterControl>) -> Self {
+        self.log_filter = Some(log_filter);
+        self
+    }
+
+    /// Feed finalized ticks to `indexer`, in place of `indexer_path`'s
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kala_core::indexer::{Indexer, IndexerSink};
+    /// use kala_core::{KalaNode, NodeConfig};
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example(sink: Arc<dyn IndexerSink>) -> anyhow::Result<()> {
+    /// let indexer = Indexer::new(sink, "./indexer.spool")?;
+    /// let node = KalaNode::new(NodeConfig::default()).await?.with_indexer(indexer);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_indexer(mut self, indexer: Indexer) -> Self {
+        self.indexer = Some(Arc::new(indexer));
+        self
+    }
+
     /// Get the encryption context for creating timelock transactions
     pub fn encryption_context(&self) -> Arc<EncryptionContext> {
         self.tick_processor.encryption_context()
@@ -91,6 +592,9 @@ impl KalaNode {
         info!("Starting Kala node - the eternal timeline begins...");
         info!("\"kalo'smi loka-kshaya-krit pravriddho\" - I am Time, the destroyer of worlds");
 
+        // Ticks finalized while the node was down
+        self.index_finalized().await;
+
         // Create channels for RPC communication
         let (chain_info_tx, mut chain_info_rx) = mpsc::channel::<mpsc::Sender<ChainInfo>>(100);
         let (submit_tx, mut submit_rx) = mpsc::channel::<(
@@ -103,23 +607,123 @@ impl KalaNode {
             chain_info_tx,
             submit_tx,
             state_db: self.state_db.clone(),
+            sync_status: self.sync_status.clone(),
+            tracer: self.tracer.clone(),
+            account_watcher: self.account_watcher.clone(),
+            denoms: self.denoms.clone(),
+            nonce_reservations: self.nonce_reservations.clone(),
+            timeline: self.timeline.clone(),
+            vdf: self.vdf.clone(),
+            config: Arc::new(self.config.clone()),
+        };
+        let admin_network = Arc::new(RwLock::new(None));
+        let admin_handler = KalaAdminHandler {
+            tx_pool: self.tx_pool.clone(),
+            tracer: self.tracer.clone(),
+            state: self.state.clone(),
+            state_db: self.state_db.clone(),
+            network: admin_network.clone(),
+            archiving: self.archiving.clone(),
+            log_filter: self.log_filter.clone(),
+            config: rpc_handler.config.clone(),
         };
 
-        // Start RPC server in separate task
+        // Serve the status page alongside the RPC API
+        if self.config.enable_metrics {
+            let status_handler = rpc_handler.clone();
+            let metrics_port = self.config.metrics_port;
+            tokio::spawn(async move {
+                let listen_addr = ([127, 0, 0, 1], metrics_port).into();
+                if let Err(e) = kala_rpc::start_status_server(listen_addr, status_handler).await {
+                    error!("Status page error: {}", e);
+                }
+            });
+        }
+
+        // The JSON-RPC server and the endpoints beside it share limits and TLS
         let rpc_port = self.config.rpc_port;
-        tokio::spawn(async move {
+        let rpc_ip: std::net::IpAddr = self.config.rpc_bind_addr.parse()?;
+        let rpc_tls = self
+            .config
+            .rpc_tls_cert_path
+            .clone()
+            .zip(self.config.rpc_tls_key_path.clone())
+            .map(|(cert_path, key_path)| kala_rpc::TlsConfig {
+                cert_path: cert_path.into(),
+                key_path: key_path.into(),
+            });
+        let rpc_config = kala_rpc::RpcConfig {
+            listen_addr: (rpc_ip, rpc_port).into(),
+            limits: kala_rpc::RpcLimits {
+                max_request_body_size: self.config.rpc_max_request_body_size,
+                max_response_body_size: self.config.rpc_max_response_body_size,
+                requests_per_second: self.config.rpc_requests_per_second,
+                burst: self.config.rpc_burst,
+                method_requests_per_second: self.config.rpc_method_rate_limits.clone(),
+            },
+            tls: rpc_tls,
+            cors_allowed_origins: self.config.rpc_cors_allowed_origins.clone(),
+            admin_token: self.config.rpc_admin_token.clone(),
+        };
+
+        // Serve the GraphQL endpoint over the same handler, limits and TLS
+        if let Some(graphql_port) = self.config.graphql_port {
+            let graphql_handler = rpc_handler.clone();
+            let config = kala_rpc::RpcConfig {
+                listen_addr: (rpc_ip, graphql_port).into(),
+                admin_token: None,
+                ..rpc_config.clone()
+            };
+            tokio::spawn(async move {
+                if let Err(e) = kala_rpc::start_graphql_server(config, graphql_handler).await {
+                    error!("GraphQL endpoint error: {}", e);
+                }
+            });
+        }
+
+        // Serve the REST gateway over the same handler, limits and TLS
+        if let Some(rest_port) = self.config.rest_port {
+            let rest_handler = rpc_handler.clone();
             let config = kala_rpc::RpcConfig {
-                listen_addr: ([127, 0, 0, 1], rpc_port).into(),
+                listen_addr: (rpc_ip, rest_port).into(),
+                admin_token: None,
+                ..rpc_config.clone()
             };
+            tokio::spawn(async move {
+                if let Err(e) = kala_rpc::start_rest_server(config, rest_handler).await {
+                    error!("REST gateway error: {}", e);
+                }
+            });
+        }
 
+        // Start RPC server in separate task
+        tokio::spawn(async move {
             info!("Starting RPC server on port {}", rpc_port);
-            if let Err(e) = kala_rpc::start_server(config, rpc_handler).await {
+            let server = kala_rpc::start_server_with_admin(rpc_config, rpc_handler, admin_handler);
+            if let Err(e) = server.await {
                 error!("RPC server error: {}", e);
             }
         });
 
+        // Measure the VDF speed now and then periodically
+        if self.config.vdf_calibration_interval_secs > 0 && !self.config.dev_instant {
+            let calibration_node = self.clone();
+            let period = Duration::from_secs(self.config.vdf_calibration_interval_secs);
+            tokio::spawn(async move { calibration_node.calibrate_vdf(period).await });
+        }
+
+        // Join the gossip network if configured
+        let network = self.start_network().await?;
+        *admin_network.write().await = network.clone();
+
+        // Catch up with peers before producing ticks
+        if let Some(network) = &network {
+            self.sync_with_peers(network).await?;
+        }
+
         // Handle RPC requests in separate task
         let rpc_node = self.clone();
+        let rpc_network = network.clone();
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -138,91 +742,66 @@ impl KalaNode {
                             hash_chain: hex::encode(vdf.get_hash_chain()),
                             total_transactions: state.total_transactions,
                             accounts: state.get_account_count(),
+                            vdf_calibration: rpc_node.calibration.read().await.clone(),
+                            vdf_tuning: Some(VdfTuning {
+                                num_threads: rpc_node.vdf_tuning.num_threads,
+                                avx512: rpc_node.vdf_tuning.avx512,
+                                fast_mode: rpc_node.vdf_tuning.fast_mode,
+                            }),
                         };
 
                         let _ = reply_tx.send(info).await;
                     }
 
                     // Handle transaction submissions
-                    Some((mut tx, reply_tx)) = submit_rx.recv() => {
-                        let current_tick = rpc_node.state.read().await.current_tick;
-                        let current_iter = rpc_node.vdf.read().await.get_iteration();
-                        let k = rpc_node.config.iterations_per_tick;
-
-                        // Validate transaction target tick
-                        if tx.target_tick < current_tick {
-                            let _ = reply_tx.send(Err(format!(
-                                "Transaction target tick {} is in the past (current: {})",
-                                tx.target_tick, current_tick
-                            ))).await;
-                            continue;
+                    Some((tx, reply_tx)) = submit_rx.recv() => {
+                        let result = rpc_node
+                            .accept_transaction(tx.clone(), TraceStage::Submitted)
+                            .await;
+
+                        // Share accepted envelopes with the rest of the network
+                        if let Ok(response) = &result {
+                            let observation = rpc_node
+                                .observe_envelope(&tx, response.submission_iteration)
+                                .await;
+                            if let Some(network) = &rpc_network {
+                                if let Err(e) = network.publish(GossipMessage::Envelope(tx)).await {
+                                    warn!("Failed to gossip transaction: {}", e);
+                                }
+                                if let Some(observation) = observation {
+                                    if let Err(e) = network
+                                        .publish(GossipMessage::Observation(observation))
+                                        .await
+                                    {
+                                        warn!("Failed to gossip observation: {}", e);
+                                    }
+                                }
+                            }
                         }
 
-                        // Check if we're in the acceptance window for this tick
-                        let target_tick_start = tx.target_tick * k;
-                        let target_tick_end = (tx.target_tick + 1) * k;
-                        let acceptance_start = if tx.target_tick == 0 {
-                            0
-                        } else {
-                            ((tx.target_tick - 1) * k) + ((k as f64 * TX_ACCEPTANCE_WINDOW_START) as u64)
-                        };
-                        let acceptance_end = target_tick_start + ((k as f64 * TX_ACCEPTANCE_WINDOW_END) as u64);
-
-                        if current_iter < acceptance_start || current_iter > acceptance_end {
-                            let _ = reply_tx.send(Err(format!(
-                                "Outside acceptance window for tick {} (current iter: {}, window: {}-{})",
-                                tx.target_tick, current_iter, acceptance_start, acceptance_end
-                            ))).await;
-                            continue;
-                        }
-
-                        // Set submission iteration to current VDF iteration
-                        tx.submission_iteration = current_iter;
-
-                        // Validate timelock parameters
-                        let decrypt_iter = tx.submission_iteration + tx.puzzle.hardness as u64;
-                        if decrypt_iter >= target_tick_end {
-                            let _ = reply_tx.send(Err(format!(
-                                "Transaction would not decrypt in time (decrypt at {} > tick end {})",
-                                decrypt_iter, target_tick_end
-                            ))).await;
-                            continue;
-                        }
-
-                        // Ensure decryption happens after consensus phase (k/3)
-                        let consensus_end = target_tick_start + k / 3;
-                        if decrypt_iter < consensus_end {
-                            let _ = reply_tx.send(Err(format!(
-                                "Transaction would decrypt too early (decrypt at {} < consensus end {})",
-                                decrypt_iter, consensus_end
-                            ))).await;
-                            continue;
-                        }
-
-                        // Compute transaction hash using serde_json for now
-                        let tx_json = serde_json::to_string(&tx).unwrap();
-                        let mut hasher = Sha256::new();
-                        hasher.update(tx_json.as_bytes());
-                        let tx_hash = hex::encode(hasher.finalize());
-
-                        // Add to pool
-                        rpc_node.tx_pool.lock().await.push(tx.clone());
-
-                        info!("Accepted transaction {} for tick {} (submission: {}, decrypt: {})",
-                              tx_hash, tx.target_tick, tx.submission_iteration, decrypt_iter);
-
-                        let _ = reply_tx.send(Ok(SubmitTransactionResponse {
-                            tx_hash,
-                            submission_iteration: tx.submission_iteration,
-                            target_tick: tx.target_tick,
-                        })).await;
+                        let _ = reply_tx.send(result).await;
                     }
                 }
             }
         });
 
+        let mut metrics = MetricsRecorder::new(METRICS_SAMPLE_INTERVAL);
+
+        // Report stalls once the node starts producing ticks
+        *self.last_tick_at.lock().await = Instant::now();
+        if self.config.watchdog_timeout_secs > 0 {
+            let watchdog_node = self.clone();
+            let timeout = Duration::from_secs(self.config.watchdog_timeout_secs);
+            tokio::spawn(async move { watchdog_node.watchdog(timeout).await });
+        }
+
         // Main eternal loop
         loop {
+            // Between ticks, move to a heavier branch learned from peers
+            if let Err(e) = self.switch_to_best_branch().await {
+                warn!("Failed to switch to the heaviest branch: {}", e);
+            }
+
             let current_tick = self.state.read().await.current_tick;
 
             info!("┌─────────────────────────────────────────┐");
@@ -248,10 +827,10 @@ impl KalaNode {
             }
 
             // Process the eternal tick
+            let tick_started = Instant::now();
             match self.process_eternal_tick(current_tick).await {
-                Ok(certificate) => {
-                    // Store certificate in database
-                    self.state_db.store_tick(&certificate).await?;
+                Ok(outcome) => {
+                    let certificate = outcome.certificate;
 
                     // Update chain state
                     let mut state = self.state.write().await;
@@ -265,10 +844,108 @@ impl KalaNode {
                     let vdf_end = vdf.get_iteration();
                     drop(vdf);
 
-                    // Persist state to database
-                    self.state_db.save_chain_state(&state).await?;
+                    // Persist the tick and the state after it in one write. The
+                    // tick is staged first so a failed write costs no VDF work.
+                    if let Err(e) = self.state_db.stage_tick(&certificate, &state).await {
+                        error!("Failed to stage tick {}: {}", certificate.tick_number, e);
+                    }
+                    if let Some(indexer) = &self.indexer {
+                        if let Err(e) = indexer.stage(&certificate, &outcome.applied).await {
+                            let tick_number = certificate.tick_number;
+                            warn!("Failed to spool tick {} for indexing: {}", tick_number, e);
+                        }
+                    }
+                    self.commit_tick_with_retry(&certificate, &state).await?;
+                    let account_changes = collect_account_changes(&state, &outcome.applied);
+                    if let Err(e) = self
+                        .fork_choice
+                        .lock()
+                        .await
+                        .extend(certificate.clone(), state.clone())
+                    {
+                        warn!("Fork choice rejected tick {}: {}", certificate.tick_number, e);
+                    }
                     drop(state);
 
+                    {
+                        let mut sync_status = self.sync_status.write().await;
+                        sync_status.current_tick = certificate.tick_number + 1;
+                        sync_status.highest_tick =
+                            sync_status.highest_tick.max(sync_status.current_tick);
+                        if let Some(network) = &network {
+                            if let Ok(peers) = network.connected_peers().await {
+                                sync_status.peer_count = peers.len();
+                            }
+                        }
+                    }
+
+                    if let Some(network) = &network {
+                        if let Err(e) = self.publish_certificate(network, &certificate).await {
+                            warn!("Failed to gossip tick certificate: {}", e);
+                        }
+                    }
+
+                    if let Some(vote) = self.vote_on_tick(&certificate).await {
+                        if let Some(network) = &network {
+                            if let Err(e) = network.publish(GossipMessage::TickVote(vote)).await {
+                                warn!("Failed to gossip tick vote: {}", e);
+                            }
+                        }
+                    }
+
+                    // The next tick's acceptance window opens as this one ends
+                    self.submit_evidence(network.as_ref()).await;
+
+                    // Seal the epoch after its last tick
+                    if (certificate.tick_number + 1) % self.config.epoch_length == 0 {
+                        let sealed = self.seal_epoch(certificate.tick_number).await;
+                        if sealed.is_ok() {
+                            // Sealed epochs are never reorged
+                            self.fork_choice.lock().await.finalize(certificate.tick_number);
+                            self.archive_old_ticks(certificate.tick_number);
+                        }
+                        match sealed {
+                            Ok(Some(signature)) => {
+                                if let Some(network) = &network {
+                                    if let Err(e) = network
+                                        .publish(GossipMessage::EpochSignature(signature))
+                                        .await
+                                    {
+                                        warn!("Failed to gossip epoch signature: {}", e);
+                                    }
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => warn!(
+                                "Failed to seal epoch ending at tick {}: {}",
+                                certificate.tick_number, e
+                            ),
+                        }
+                    }
+
+                    let tick_duration = tick_started.elapsed();
+                    *self.last_tick_at.lock().await = Instant::now();
+                    for observer in &self.observers {
+                        observer.on_tick(&certificate, tick_duration).await;
+                        if !account_changes.is_empty() {
+                            observer
+                                .on_accounts_changed(certificate.tick_number, &account_changes)
+                                .await;
+                        }
+                    }
+
+                    let mempool_depth = self.tx_pool.lock().await.len();
+                    if let Some(sample) = metrics.record_tick(
+                        certificate.tick_number,
+                        self.config.iterations_per_tick,
+                        tick_duration,
+                        mempool_depth,
+                    ) {
+                        if let Err(e) = self.state_db.record_metrics_sample(&sample).await {
+                            warn!("Failed to record metrics sample: {}", e);
+                        }
+                    }
+
                     // Format all values first to get consistent widths
                     let tick_str = format!("Tick {} Complete!", current_tick);
                     let type_str = format!("Type: {:?}", certificate.tick_type);
@@ -312,6 +989,9 @@ impl KalaNode {
                 Err(e) => {
                     error!("FATAL: Tick {} failed: {}", current_tick, e);
                     error!("The eternal timeline has been disrupted!");
+                    for observer in &self.observers {
+                        observer.on_tick_failed(current_tick, &e.to_string()).await;
+                    }
                     return Err(e);
                 }
             }
@@ -320,112 +1000,1345 @@ impl KalaNode {
         }
     }
 
-    /// Process a single eternal tick
-    async fn process_eternal_tick(&self, tick_num: u64) -> Result<TickCertificate> {
-        let k = self.config.iterations_per_tick;
-        let _tick_start_iter = tick_num * k;
-
-        // Get transactions for this tick from the pool
-        let encrypted_txs = self.extract_tick_transactions(tick_num).await;
+    /// Report a stall to the observers when no tick arrives within `timeout`
+    ///
+    /// Each stalled tick is reported once.
+    async fn watchdog(&self, timeout: Duration) {
+        let mut interval = tokio::time::interval((timeout / 4).max(Duration::from_secs(1)));
+        let mut reported = None;
 
-        info!(
-            "Processing tick {} with {} encrypted transactions",
-            tick_num,
-            encrypted_txs.len()
-        );
+        loop {
+            interval.tick().await;
+            let idle = self.last_tick_at.lock().await.elapsed();
+            let next_tick = self.state.read().await.current_tick;
+            if idle < timeout || reported == Some(next_tick) {
+                continue;
+            }
 
-        // For single node, we follow the paper but skip Byzantine consensus
-        // The tick processor handles all the phases correctly
-        let certificate = self
-            .tick_processor
-            .process_tick(
-                tick_num,
-                self.vdf.clone(),
-                self.state.clone(),
-                encrypted_txs,
-            )
-            .await?;
+            warn!("Watchdog: no tick produced for {}s", idle.as_secs());
+            for observer in &self.observers {
+                observer.on_stall(next_tick, idle).await;
+            }
+            reported = Some(next_tick);
+        }
+    }
 
-        Ok(certificate)
+    /// Commit a finalized tick, retrying storage failures with backoff
+    ///
+    /// A tick the state database rejects is not retried. If every attempt
+    /// fails the tick stays staged, and is committed when the node reopens.
+    ///
+    /// # Parameters
+    /// * `certificate` - Certificate of the finalized tick
+    /// * `state` - Chain state after the tick
+    ///
+    /// # Returns
+    /// The last error once the attempts run out
+    async fn commit_tick_with_retry(
+        &self,
+        certificate: &TickCertificate,
+        state: &ChainState,
+    ) -> Result<()> {
+        let mut backoff = TICK_COMMIT_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match self.state_db.commit_tick(certificate, state).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < TICK_COMMIT_ATTEMPTS && is_storage_failure(&e) => {
+                    warn!(
+                        "Failed to commit tick {} (attempt {} of {}), retrying: {}",
+                        certificate.tick_number, attempt, TICK_COMMIT_ATTEMPTS, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
     }
 
-    /// Extract transactions for the current tick from the pool
-    async fn extract_tick_transactions(&self, tick_num: u64) -> Vec<TimelockTransaction> {
-        let mut pool = self.tx_pool.lock().await;
-        let mut tick_txs = Vec::new();
-        let mut remaining_txs = Vec::new();
+    /// Benchmark the VDF every `period`, starting immediately
+    ///
+    /// Each benchmark runs on a blocking thread, beside the VDF.
+    async fn calibrate_vdf(&self, period: Duration) {
+        let mut interval = tokio::time::interval(period);
 
-        // Separate transactions for this tick vs future ticks
-        for tx in pool.drain(..) {
-            if tx.target_tick == tick_num {
-                tick_txs.push(tx);
-            } else if tx.target_tick > tick_num {
-                remaining_txs.push(tx);
+        loop {
+            interval.tick().await;
+            let discriminant = self.config.discriminant.clone();
+            let iterations_per_tick = self.config.iterations_per_tick;
+            let measured = tokio::task::spawn_blocking(move || {
+                calibration::calibrate(&discriminant, iterations_per_tick)
+            })
+            .await;
+            match measured {
+                Ok(measured) => {
+                    // Once witnesses anchor the timeline, it runs at the pace the chain kept
+                    if matches!(self.state_db.latest_time_anchor().await, Ok(None)) {
+                        self.timeline.write().await.set_rate(measured.iterations_per_second);
+                    }
+                    *self.calibration.write().await = Some(measured);
+                }
+                Err(e) => warn!("VDF calibration failed: {}", e),
             }
-            // Drop any transactions for past ticks
         }
+    }
 
-        // Sort transactions by submission iteration (as per paper's ordering)
-        tick_txs.sort_by_key(|tx| tx.submission_iteration);
+    /// Start the gossip network when `p2p_listen_addr` is configured
+    ///
+    /// Spawns the network service and a task handling inbound gossip.
+    /// Known peers are loaded from the state database. Returns `None`
+    /// when the node runs without peers.
+    async fn start_network(self: &Arc<Self>) -> Result<Option<NetworkHandle>> {
+        let Some(listen_addr) = &self.config.p2p_listen_addr else {
+            return Ok(None);
+        };
 
-        // Put back future transactions
-        *pool = remaining_txs;
+        let network_config = NetworkConfig {
+            peers: self.config.p2p_peers.clone(),
+            bootstrap_nodes: self.config.p2p_bootstrap_nodes.clone(),
+            max_peers: self.config.p2p_max_peers,
+            certificate_detail: self.config.p2p_certificate_detail,
+            ..NetworkConfig::new(listen_addr.clone())
+        };
+        let peer_store = PeerStore::open(self.state_db.database().clone()).await?;
 
-        debug!(
-            "Extracted {} transactions for tick {}, {} remaining in pool",
-            tick_txs.len(),
-            tick_num,
-            pool.len()
-        );
+        let (service, handle, mut inbound) = NetworkService::new(network_config, peer_store)?;
+        tokio::spawn(service.with_state_db(self.state_db.clone()).run());
+        self.key_shares.attach(handle.clone()).await;
 
-        tick_txs
+        let node = self.clone();
+        let gossip_network = handle.clone();
+        tokio::spawn(async move {
+            while let Some(message) = inbound.recv().await {
+                node.handle_gossip(message, &gossip_network).await;
+            }
+        });
+
+        Ok(Some(handle))
     }
 
-    /// Log VDF checkpoint information
-    async fn log_vdf_checkpoint(&self) {
-        let vdf = self.vdf.read().await;
-        let checkpoint = vdf.checkpoint();
+    /// Sync ticks from peers and resume from the snapshot they provide
+    ///
+    /// Sync failures are not fatal: the node keeps its local state and
+    /// follows the network from there. The exception is a fresh node
+    /// configured with a trusted checkpoint, which must not start its own
+    /// timeline from genesis.
+    async fn sync_with_peers(&self, network: &NetworkHandle) -> Result<()> {
+        let mut sync = TickSync::new(
+            network.clone(),
+            self.state_db.clone(),
+            self.sync_status.clone(),
+            self.config.iterations_per_tick,
+            self.verifier.clone(),
+        )
+        .with_compact_ticks(self.config.p2p_certificate_detail != CertificateDetail::Full);
+        let checkpoint = self
+            .config
+            .checkpoint()
+            .map_err(|e| anyhow!("Invalid trusted checkpoint: {}", e))?;
+        if let Some(checkpoint) = checkpoint {
+            sync = sync.with_checkpoint(checkpoint, self.config.epoch_length);
+        }
+        let local = self.state.read().await.clone();
+        let cold_start = local.current_tick == 0;
+
+        let result = sync.run(local).await;
+        let backfill = self
+            .config
+            .trusted_checkpoint
+            .as_ref()
+            .is_some_and(|checkpoint| checkpoint.backfill);
+        if backfill && result.is_ok() {
+            // History before the checkpoint is optional, so fetch it in the background
+            tokio::spawn(async move {
+                if let Err(e) = sync.backfill().await {
+                    warn!("Backfill stopped: {}", e);
+                }
+            });
+        }
 
-        // Format all values first
-        let title = "VDF Checkpoint";
-        let iteration_str = format!("Iteration: {}", checkpoint.iteration);
-        let forms_str = format!(
-            "Forms: ({}, {}, {})",
-            preview(&checkpoint.form_a, 8),
-            preview(&checkpoint.form_b, 8),
-            preview(&checkpoint.form_c, 8)
+        let snapshot = match result {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => return Ok(()),
+            Err(e) if checkpoint.is_some() && cold_start => {
+                return Err(e.context("Failed to sync from the trusted checkpoint"));
+            }
+            Err(e) => {
+                warn!("Tick sync failed, continuing from local state: {}", e);
+                return Ok(());
+            }
+        };
+
+        let mut vdf = match EternalVDF::from_checkpoint(&snapshot.vdf_checkpoint) {
+            Ok(vdf) => vdf,
+            Err(e) if checkpoint.is_some() && cold_start => {
+                return Err(anyhow!("Failed to restore VDF from the trusted checkpoint: {}", e));
+            }
+            Err(e) => {
+                warn!("Synced VDF state is invalid, continuing from local state: {}", e);
+                return Ok(());
+            }
+        };
+        self.state_db.save_chain_state(&snapshot).await?;
+
+        info!(
+            "Resuming from synced tick {} at VDF iteration {}",
+            snapshot.current_tick, snapshot.vdf_checkpoint.iteration
         );
-        let hash_str = format!("Hash: {}...", hex::encode(&checkpoint.hash_chain[..8]));
-        let certs_str = format!("Tick Certs: {}", checkpoint.tick_certificates.len());
+        let mut fork_choice =
+            ForkChoice::new(&snapshot, self.config.iterations_per_tick, REORG_WINDOW);
+        if let Some(finalized) = self.state_db.finalized_tick().await? {
+            fork_choice.finalize(finalized);
+        }
+        *self.fork_choice.lock().await = fork_choice;
+        vdf.set_persistence(self.vdf_persistence.clone(), self.config.vdf_checkpoint_interval);
+        vdf.set_backend(self.config.vdf_backend);
+        *self.vdf.write().await = vdf;
+        *self.state.write().await = snapshot;
 
-        // Calculate the box width based on the longest line
-        let box_width = [
-            title.len(),
-            iteration_str.len(),
-            forms_str.len(),
-            hash_str.len(),
-            certs_str.len(),
-        ]
-        .iter()
-        .max()
-        .unwrap()
-        .max(&40)
-            + 4; // Add padding and ensure minimum width
+        Ok(())
+    }
 
-        // Helper function to pad strings (left-aligned)
-        let pad = |s: &str| format!("│  {:<width$}  │", s, width = box_width - 6);
+    /// Gossip a certificate in every [`CertificateDetail`]
+    ///
+    /// Each detail goes out on its own topic and only reaches the peers
+    /// subscribed to it.
+    async fn publish_certificate(
+        &self,
+        network: &NetworkHandle,
+        certificate: &TickCertificate,
+    ) -> Result<()> {
+        let discriminant = self.state.read().await.vdf_checkpoint.discriminant.clone();
+        network
+            .publish(GossipMessage::TickCertificate(certificate.clone()))
+            .await?;
+        network
+            .publish(GossipMessage::CompactTickCertificate(certificate.compact(&discriminant)))
+            .await?;
+        network
+            .publish(GossipMessage::TickHeader(certificate.header()))
+            .await
+    }
 
-        // Helper function to center text
-        let center = |s: &str| {
-            let padding = (box_width - 4 - s.len()) / 2;
-            let left_pad = " ".repeat(padding);
-            let right_pad = " ".repeat(box_width - 4 - padding - s.len());
-            format!("│  {}{}{}  │", left_pad, s, right_pad)
-        };
+    /// Compare a peer's certificate with the local tick and track its branch
+    async fn receive_certificate(&self, remote: TickCertificate, network: &NetworkHandle) {
+        {
+            let mut sync_status = self.sync_status.write().await;
+            sync_status.highest_tick = sync_status.highest_tick.max(remote.tick_number + 1);
+        }
 
-        // Create the box
-        let top_line = format!("┌{}┐", "─".repeat(box_width - 2));
-        let bottom_line = format!("└{}┘", "─".repeat(box_width - 2));
+        match self.state_db.get_tick(remote.tick_number).await {
+            Ok(Some(local)) if local.tick_hash != remote.tick_hash => {
+                warn!(
+                    "Peer certificate for tick {} diverges: local {}..., remote {}...",
+                    remote.tick_number,
+                    hex::encode(&local.tick_hash[..8]),
+                    hex::encode(&remote.tick_hash[..8])
+                );
+            }
+            Ok(_) => debug!("Received certificate for tick {}", remote.tick_number),
+            Err(e) => warn!("Failed to load tick {}: {}", remote.tick_number, e),
+        }
+
+        let tick_number = remote.tick_number;
+        if let Err(e) = self.track_branch(remote, network).await {
+            debug!("Not tracking peer tick {}: {}", tick_number, e);
+        }
+    }
+
+    /// Handle a peer's tick header, fetching the certificate if the tick is
+    /// new to this node
+    ///
+    /// Headers of ticks the node already holds, locally or in the fork
+    /// tree, cost nothing more. Any other tick's certificate is fetched
+    /// from the first peer that serves it, in the detail the node receives
+    /// over gossip, and handled like a gossiped certificate.
+    async fn receive_header(&self, header: TickHeader, network: &NetworkHandle) {
+        {
+            let mut sync_status = self.sync_status.write().await;
+            sync_status.highest_tick = sync_status.highest_tick.max(header.tick_number + 1);
+        }
+
+        if self.fork_choice.lock().await.contains(&header.tick_hash) {
+            return;
+        }
+        match self.state_db.get_tick(header.tick_number).await {
+            Ok(Some(local)) if local.tick_hash == header.tick_hash => return,
+            Ok(_) => {}
+            Err(e) => {
+                warn!("Failed to load tick {}: {}", header.tick_number, e);
+                return;
+            }
+        }
+
+        match self.fetch_certificate(network, &header).await {
+            Ok(certificate) => self.receive_certificate(certificate, network).await,
+            Err(e) => debug!("Cannot fetch certificate of tick {}: {}", header.tick_number, e),
+        }
+    }
+
+    /// Fetch the certificate `header` belongs to from a connected peer
+    async fn fetch_certificate(
+        &self,
+        network: &NetworkHandle,
+        header: &TickHeader,
+    ) -> Result<TickCertificate> {
+        let discriminant = self.state.read().await.vdf_checkpoint.discriminant.clone();
+        let compact = self.config.p2p_certificate_detail != CertificateDetail::Full;
+        for peer in network.connected_peers().await? {
+            let request = SyncRequest::Ticks {
+                start: header.tick_number,
+                count: 1,
+                compact,
+            };
+            let certificate = match network.sync_request(peer, request).await {
+                Ok(SyncResponse::Ticks(ticks)) => ticks.into_iter().next(),
+                Ok(other) => {
+                    debug!("Peer {} answered with {:?}", peer, other);
+                    None
+                }
+                Err(e) => {
+                    debug!("Peer {} cannot serve tick {}: {}", peer, header.tick_number, e);
+                    None
+                }
+            };
+            // A peer on another branch serves its own certificate for the tick
+            if let Some(mut certificate) = certificate {
+                if certificate.expand(&discriminant).is_ok()
+                    && certificate.tick_hash == header.tick_hash
+                    && certificate.compute_hash() == header.tick_hash
+                {
+                    return Ok(certificate);
+                }
+            }
+        }
+        Err(anyhow!("no peer serves it"))
+    }
+
+    /// Add a peer's tick to the fork tree, preparing a reorg if it wins
+    ///
+    /// The tick's VDF segment is verified from its parent in the tree. If
+    /// the tick makes its branch the heaviest, the chain state at the
+    /// branch tip is fetched from peers so the main loop can switch to it.
+    async fn track_branch(&self, remote: TickCertificate, network: &NetworkHandle) -> Result<()> {
+        let anchor = {
+            let fork_choice = self.fork_choice.lock().await;
+            if fork_choice.contains(&remote.tick_hash) {
+                return Ok(());
+            }
+            fork_choice
+                .anchor(&remote.previous_tick_hash)
+                .ok_or_else(|| anyhow!("parent is not a recent tick"))?
+        };
+
+        let discriminant = self.state.read().await.vdf_checkpoint.discriminant.clone();
+        let tip = verify_certificates(
+            &anchor,
+            std::slice::from_ref(&remote),
+            self.config.iterations_per_tick,
+            &discriminant,
+            &self.verifier,
+        )
+        .await?;
+
+        {
+            let mut fork_choice = self.fork_choice.lock().await;
+            fork_choice.insert(remote, None)?;
+            if fork_choice.best_tip() != tip.tick_hash {
+                return Ok(());
+            }
+        }
+
+        info!(
+            "Branch ending at peer tick {} ({}...) outweighs the local chain",
+            tip.next_tick - 1,
+            hex::encode(&tip.tick_hash[..8])
+        );
+        for peer in network.connected_peers().await? {
+            match self.fetch_branch_state(network, peer, &tip, &discriminant).await {
+                Ok(()) => return Ok(()),
+                Err(e) => debug!("Peer {} cannot serve the branch state: {}", peer, e),
+            }
+        }
+        Err(anyhow!("no peer served the state of the heavier branch"))
+    }
+
+    /// Fetch a peer's chain state on the branch through `tip`
+    ///
+    /// The peer may have moved past `tip`; the ticks in between are
+    /// verified and added to the fork tree along with the state.
+    async fn fetch_branch_state(
+        &self,
+        network: &NetworkHandle,
+        peer: PeerId,
+        tip: &SyncAnchor,
+        discriminant: &str,
+    ) -> Result<()> {
+        let snapshot = match network.sync_request(peer, SyncRequest::State).await? {
+            SyncResponse::State(state) => *state,
+            other => return Err(anyhow!("expected a chain state, got {:?}", other)),
+        };
+        if snapshot.vdf_checkpoint.discriminant != discriminant {
+            return Err(anyhow!("peer uses a different VDF discriminant"));
+        }
+        if snapshot.current_tick < tip.next_tick
+            || snapshot.current_tick - tip.next_tick > REORG_WINDOW
+        {
+            return Err(anyhow!(
+                "peer head {} is not within reach of tick {}",
+                snapshot.current_tick,
+                tip.next_tick
+            ));
+        }
+
+        let mut certificates = Vec::new();
+        if snapshot.current_tick > tip.next_tick {
+            let request = SyncRequest::Ticks {
+                start: tip.next_tick,
+                count: snapshot.current_tick - tip.next_tick,
+                compact: self.config.p2p_certificate_detail != CertificateDetail::Full,
+            };
+            certificates = match network.sync_request(peer, request).await? {
+                SyncResponse::Ticks(ticks) => ticks,
+                other => return Err(anyhow!("expected ticks, got {:?}", other)),
+            };
+            for certificate in &mut certificates {
+                certificate.expand(discriminant)?;
+            }
+        }
+        let head = verify_certificates(
+            tip,
+            &certificates,
+            self.config.iterations_per_tick,
+            discriminant,
+            &self.verifier,
+        )
+        .await?;
+        head.check_snapshot(&snapshot)?;
+
+        let mut fork_choice = self.fork_choice.lock().await;
+        for certificate in certificates {
+            fork_choice.insert(certificate, None)?;
+        }
+        fork_choice.attach_state(&head.tick_hash, snapshot)
+    }
+
+    /// Switch to the heaviest branch if it is not the current one
+    ///
+    /// Only called between ticks, while nothing else changes the state.
+    async fn switch_to_best_branch(&self) -> Result<()> {
+        let reorg = {
+            let fork_choice = self.fork_choice.lock().await;
+            let best = fork_choice.best_tip();
+            if best == fork_choice.head() || !fork_choice.has_state(&best) {
+                return Ok(());
+            }
+            fork_choice.plan_reorg(&best)?
+        };
+        self.apply_reorg(&reorg).await
+    }
+
+    /// Replace the ticks after the fork point with the new branch
+    ///
+    /// The VDF and chain state resume from the new branch's tip.
+    /// Transactions included in the rolled back ticks are not re-queued.
+    async fn apply_reorg(&self, reorg: &Reorg) -> Result<()> {
+        let mut vdf = EternalVDF::from_checkpoint(&reorg.state.vdf_checkpoint)
+            .map_err(|e| anyhow!("Failed to restore VDF for reorg: {}", e))?;
+        vdf.set_persistence(self.vdf_persistence.clone(), self.config.vdf_checkpoint_interval);
+        vdf.set_backend(self.config.vdf_backend);
+
+        self.state_db.truncate_ticks(reorg.fork_tick).await?;
+        for certificate in &reorg.applied {
+            self.state_db.store_tick(certificate).await?;
+        }
+        self.state_db.save_chain_state(&reorg.state).await?;
+        // The rewritten ticks aren't commits, so sync them unless left to the OS
+        if self.config.db_fsync_policy != FsyncPolicy::OsDefault {
+            self.state_db.sync().await?;
+        }
+
+        warn!(
+            "Reorg from tick {}: replaced {} local ticks with {} peer ticks, now at tick {}",
+            reorg.fork_tick,
+            reorg.rolled_back.len(),
+            reorg.applied.len(),
+            reorg.state.current_tick
+        );
+        *self.vdf.write().await = vdf;
+        *self.state.write().await = reorg.state.clone();
+        self.fork_choice.lock().await.adopt(reorg);
+        self.sync_status.write().await.current_tick = reorg.state.current_tick;
+
+        Ok(())
+    }
+
+    /// Move ticks more than `retain_ticks` behind `sealed_tick` to the archive
+    ///
+    /// Uploads run in the background. While one is still running, the
+    /// ticks of the new epoch are left for the next one.
+    fn archive_old_ticks(&self, sealed_tick: u64) {
+        let Some(archive) = &self.config.archive else {
+            return;
+        };
+        let Ok(guard) = self.archiving.clone().try_lock_owned() else {
+            return;
+        };
+        let before = (sealed_tick + 1).saturating_sub(archive.retain_ticks);
+        let state_db = self.state_db.clone();
+        tokio::spawn(async move {
+            let _guard = guard;
+            match state_db.archive_ticks(before).await {
+                Ok(next) => debug!("Ticks before {} are archived", next),
+                Err(e) => warn!("Failed to archive ticks before {}: {}", before, e),
+            }
+        });
+    }
+
+    /// Seal the epoch ending at `end_tick`, signing it if this node is a witness
+    ///
+    /// Signatures peers gossiped before the epoch was sealed locally are
+    /// applied here, and the witness set for the next epoch is recomputed
+    /// from stake. Returns this node's signature to gossip.
+    async fn seal_epoch(&self, end_tick: u64) -> Result<Option<EpochSignature>> {
+        let epoch_number = end_tick / self.config.epoch_length;
+        let mut pending = self.pending_epoch_signatures.lock().await;
+        let witness_set = self.finality.lock().await.witnesses().clone();
+
+        let (summary, stake) = {
+            let state = self.state.read().await;
+            let summary = self
+                .state_db
+                .build_epoch_summary(
+                    epoch_number,
+                    self.config.epoch_length,
+                    &state,
+                    witness_set.keys(),
+                )
+                .await?;
+            // Served to syncing peers as a snapshot proven by the summary
+            self.state_db.save_epoch_state(&state).await?;
+            (summary, state.delegated_stake())
+        };
+        let summary_hash = summary.compute_hash();
+
+        // Without enough stake on any candidate the current set carries over
+        let next_set = WitnessSet::from_stake(
+            &self.witness_candidates,
+            &stake,
+            self.config.min_witness_stake,
+        )?
+        .map(|set| set.with_scheme(witness_set.scheme()))
+        .unwrap_or_else(|| witness_set.clone());
+        let handover = EpochCertificate::new(&summary, witness_set.clone(), next_set);
+        let mut epoch = SignedEpochSummary::new(summary);
+
+        let signature = if witness_set.get(&self.witness_key).is_some() {
+            Some(epoch.sign_with(self.signer.as_ref()).await?)
+        } else {
+            None
+        };
+
+        // Apply early signatures for this epoch and drop those for older ones
+        for early in std::mem::take(&mut *pending) {
+            if early.epoch_number > epoch_number {
+                pending.push(early);
+            } else if early.epoch_number == epoch_number && early.summary_hash == summary_hash {
+                if let Err(e) = epoch.add_signature(early.signature) {
+                    debug!("Dropping early signature for epoch {}: {}", epoch_number, e);
+                }
+            }
+        }
+
+        // A summary may already be stored from a peer or an earlier run
+        if let Some(stored) = self.state_db.get_epoch_summary(epoch_number).await? {
+            if stored.summary.state_root != epoch.summary.state_root {
+                error!(
+                    "Epoch {} state root {} differs from stored summary {}",
+                    epoch_number,
+                    hex::encode(epoch.summary.state_root),
+                    hex::encode(stored.summary.state_root)
+                );
+                for observer in &self.observers {
+                    observer
+                        .on_state_root_mismatch(
+                            epoch_number,
+                            stored.summary.state_root,
+                            epoch.summary.state_root,
+                        )
+                        .await;
+                }
+            }
+        }
+
+        self.state_db.store_epoch_summary(&epoch).await?;
+        info!(
+            "Sealed epoch {} (ticks {}..={}) with {} of {} witness signatures",
+            epoch_number,
+            epoch.summary.start_tick,
+            epoch.summary.end_tick,
+            epoch.signatures.len(),
+            epoch.summary.witnesses.len()
+        );
+
+        self.state_db.store_epoch_certificate(&handover).await?;
+        if handover.is_rotation() {
+            info!(
+                "Witness set rotated from tick {}: {} witnesses, total weight {}, byzantine threshold {}",
+                handover.effective_tick,
+                handover.witnesses.witnesses().len(),
+                handover.witnesses.total_weight(),
+                handover.byzantine_threshold
+            );
+        }
+        self.finality.lock().await.rotate(handover.witnesses);
+
+        Ok(signature)
+    }
+
+    /// Vote on a tick this node produced and check it for finality
+    ///
+    /// Returns this node's vote to gossip if it is a witness.
+    async fn vote_on_tick(&self, certificate: &TickCertificate) -> Option<TickVote> {
+        // A remote signer may be slow, so sign without holding the tracker
+        let (is_witness, scheme) = {
+            let finality = self.finality.lock().await;
+            let witnesses = finality.witnesses();
+            (witnesses.get(&self.witness_key).is_some(), witnesses.scheme())
+        };
+        let mut vote = if is_witness {
+            match TickVote::sign_with(
+                certificate.tick_number,
+                certificate.tick_hash,
+                scheme,
+                self.signer.as_ref(),
+            )
+            .await
+            {
+                Ok(vote) => Some(vote),
+                Err(e) => {
+                    warn!("Failed to sign vote for tick {}: {}", certificate.tick_number, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let interval = self.config.time_anchor_interval;
+        if let Some(vote) = vote.as_mut().filter(|_| interval > 0) {
+            if certificate.tick_number.is_multiple_of(interval) {
+                let time_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_millis() as u64);
+                if let Err(e) = vote.attach_time(time_ms, self.signer.as_ref()).await {
+                    warn!(
+                        "Failed to attach time to vote for tick {}: {}",
+                        certificate.tick_number, e
+                    );
+                }
+            }
+        }
+        if let (Some(vote), Some(source)) = (vote.as_mut(), &self.vote_metadata) {
+            let tick_number = certificate.tick_number;
+            if let Some(data) = source.metadata(tick_number, &certificate.tick_hash).await {
+                if let Err(e) = vote.attach_metadata(data, self.signer.as_ref()).await {
+                    warn!("Failed to attach metadata to vote for tick {}: {}", tick_number, e);
+                }
+            }
+        }
+
+        let mut finality = self.finality.lock().await;
+        if let Some(vote) = &vote {
+            match finality.add_vote(vote.clone(), certificate.tick_number) {
+                Ok(_) => {
+                    self.count_vote(finality.witnesses(), vote).await;
+                    self.detect_double_sign(vote).await;
+                }
+                Err(e) => warn!(
+                    "Failed to record own vote for tick {}: {}",
+                    certificate.tick_number, e
+                ),
+            }
+        }
+
+        // Votes from faster peers may already have reached quorum
+        if let Some(record) = finality.try_finalize(certificate.tick_number, certificate.tick_hash)
+        {
+            self.store_finality(record, finality.witnesses()).await;
+        }
+        finality.prune(certificate.tick_number + 1);
+        self.double_signs.lock().await.prune(certificate.tick_number + 1);
+
+        vote
+    }
+
+    /// Record a tick vote gossiped by a witness
+    async fn add_tick_vote(&self, vote: TickVote) {
+        let tick_number = vote.tick_number;
+        let current_tick = self.state.read().await.current_tick;
+        let mut finality = self.finality.lock().await;
+
+        match finality.add_vote(vote.clone(), current_tick) {
+            Ok(true) => {
+                self.count_vote(finality.witnesses(), &vote).await;
+                self.detect_double_sign(&vote).await;
+            }
+            Ok(false) => return,
+            Err(e) => {
+                // A validly signed vote the tracker refuses conflicts with an earlier one
+                if vote.verify(finality.witnesses()).is_ok() {
+                    self.detect_double_sign(&vote).await;
+                }
+                debug!("Ignoring vote for tick {}: {}", tick_number, e);
+                return;
+            }
+        }
+
+        // Finality is only reached for ticks we have produced ourselves
+        match self.state_db.get_tick(tick_number).await {
+            Ok(Some(local)) => {
+                if let Some(record) = finality.try_finalize(tick_number, local.tick_hash) {
+                    self.store_finality(record, finality.witnesses()).await;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load tick {}: {}", tick_number, e),
+        }
+    }
+
+    /// Compare a verified vote with the witness's earlier votes for the tick
+    ///
+    /// Evidence of a double-sign is queued for [`submit_evidence`](Self::submit_evidence).
+    async fn detect_double_sign(&self, vote: &TickVote) {
+        let evidence = self.double_signs.lock().await.record(vote);
+        if let Some(evidence) = evidence {
+            warn!(
+                "Witness {} voted for {}... and {}... at tick {}",
+                hex::encode(evidence.witness()),
+                hex::encode(&evidence.first.tick_hash[..8]),
+                hex::encode(&evidence.second.tick_hash[..8]),
+                evidence.tick_number()
+            );
+            for observer in &self.observers {
+                observer.on_double_sign(&evidence).await;
+            }
+        }
+    }
+
+    /// Submit queued double-sign evidence for the next tick and gossip it
+    ///
+    /// Called between ticks, while the VDF is paused at the start of the
+    /// next tick's acceptance window. Evidence that fails to submit is
+    /// queued again for the tick after.
+    async fn submit_evidence(&self, network: Option<&NetworkHandle>) {
+        let pending = self.double_signs.lock().await.take_pending();
+        for evidence in pending {
+            if let Err(e) = self.submit_double_sign(&evidence, network).await {
+                warn!(
+                    "Failed to submit evidence against witness {} for tick {}: {}",
+                    hex::encode(evidence.witness()),
+                    evidence.tick_number(),
+                    e
+                );
+                self.double_signs.lock().await.requeue(evidence);
+            }
+        }
+    }
+
+    /// Seal an evidence transaction signed by this node's witness key
+    async fn submit_double_sign(
+        &self,
+        evidence: &DoubleSignEvidence,
+        network: Option<&NetworkHandle>,
+    ) -> Result<()> {
+        let k = self.config.iterations_per_tick;
+        let sender = self.witness_key;
+        let (target_tick, account_nonce) = {
+            let state = self.state.read().await;
+            (state.current_tick, state.get_account_nonce(&sender).unwrap_or(0))
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let nonce = *self
+            .nonce_reservations
+            .reserve(sender, account_nonce, 1, now)?
+            .start();
+        let tx = evidence_transaction(evidence, self.signer.as_ref(), nonce, &self.config.chain_id).await?;
+
+        // Decrypt halfway through the target tick, after ordering is decided
+        let submission_iteration = self.vdf.read().await.get_iteration();
+        let hardness = (target_tick * k + k / 2)
+            .saturating_sub(submission_iteration)
+            .clamp(1, u32::MAX as u64) as u32;
+        let envelope = tokio::task::spawn_blocking(move || {
+            seal_transaction(&tx, target_tick, submission_iteration, hardness)
+        })
+        .await?
+        .map_err(|e| anyhow!("Failed to seal evidence: {}", e))?;
+
+        let response = self
+            .accept_transaction(envelope.clone(), TraceStage::Submitted)
+            .await
+            .map_err(|e| anyhow!(e))?;
+        info!(
+            "Submitted evidence against witness {} for tick {} in {}",
+            hex::encode(evidence.witness()),
+            evidence.tick_number(),
+            response.tx_hash
+        );
+
+        let observation = self
+            .observe_envelope(&envelope, response.submission_iteration)
+            .await;
+        if let Some(network) = network {
+            network.publish(GossipMessage::Envelope(envelope)).await?;
+            if let Some(observation) = observation {
+                network.publish(GossipMessage::Observation(observation)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Count a verified vote towards the weight of the tick's branch
+    async fn count_vote(&self, witnesses: &WitnessSet, vote: &TickVote) {
+        if let Some(witness) = witnesses.get(&vote.witness) {
+            self.fork_choice
+                .lock()
+                .await
+                .add_vote(&vote.tick_hash, vote.witness, witness.weight);
+        }
+    }
+
+    /// Persist the aggregated votes finalizing a tick
+    async fn store_finality(&self, record: TickFinality, witnesses: &WitnessSet) {
+        let tick_number = record.tick_number;
+        self.fork_choice.lock().await.finalize(tick_number);
+        let signers = record.signers(witnesses);
+        let signed_weight = signers.iter().map(|witness| witness.weight).sum();
+        match self.state_db.store_tick_finality(&record).await {
+            Ok(()) => info!(
+                "Tick {} finalized by {} of {} witnesses",
+                tick_number,
+                signers.len(),
+                witnesses.witnesses().len()
+            ),
+            Err(e) => warn!("Failed to store finality for tick {}: {}", tick_number, e),
+        }
+
+        // The tick's order is final, so its observations are no longer needed
+        self.observations.lock().await.prune(tick_number + 1);
+        if let Err(e) = self.state_db.prune_observations(tick_number + 1).await {
+            warn!("Failed to prune observations before tick {}: {}", tick_number + 1, e);
+        }
+
+        let iteration = (tick_number + 1) * self.config.iterations_per_tick;
+        if let Some(anchor) = TimeAnchor::from_finality(&record, witnesses, iteration) {
+            match self.state_db.store_time_anchor(&anchor).await {
+                Ok(()) => self.timeline.write().await.correct(anchor.iteration, anchor.time_ms),
+                Err(e) => warn!("Failed to store time anchor for tick {}: {}", tick_number, e),
+            }
+        }
+
+        for observer in &self.observers {
+            observer
+                .on_tick_finalized(tick_number, signed_weight, witnesses.total_weight())
+                .await;
+        }
+        self.index_finalized().await;
+    }
+
+    /// Feed the indexer every finalized tick it hasn't been given yet
+    async fn index_finalized(&self) {
+        let Some(indexer) = &self.indexer else {
+            return;
+        };
+        let finalized = match self.state_db.finalized_tick().await {
+            Ok(Some(finalized)) => finalized,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to read the finalized tick for indexing: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = indexer.catch_up(&self.state_db, finalized).await {
+            warn!("Failed to index finalized ticks: {}", e);
+        }
+    }
+
+    /// Record a witness signature gossiped for an epoch
+    async fn add_epoch_signature(&self, signature: EpochSignature) {
+        let mut pending = self.pending_epoch_signatures.lock().await;
+        let epoch_number = signature.epoch_number;
+
+        let mut epoch = match self.state_db.get_epoch_summary(epoch_number).await {
+            Ok(Some(epoch)) => epoch,
+            Ok(None) => {
+                // Peers may seal an epoch before we do
+                if pending.len() < MAX_PENDING_EPOCH_SIGNATURES {
+                    pending.push(signature);
+                }
+                return;
+            }
+            Err(e) => {
+                warn!("Failed to load epoch {}: {}", epoch_number, e);
+                return;
+            }
+        };
+
+        if epoch.summary.compute_hash() != signature.summary_hash {
+            warn!(
+                "Witness {} signed a different summary for epoch {}",
+                hex::encode(signature.signature.witness),
+                epoch_number
+            );
+            return;
+        }
+
+        match epoch.add_signature(signature.signature) {
+            Ok(true) => {
+                if let Err(e) = self.state_db.store_epoch_summary(&epoch).await {
+                    warn!("Failed to store epoch {}: {}", epoch_number, e);
+                    return;
+                }
+                debug!(
+                    "Epoch {} now has {} of {} witness signatures",
+                    epoch_number,
+                    epoch.signatures.len(),
+                    epoch.summary.witnesses.len()
+                );
+            }
+            Ok(false) => {}
+            Err(e) => debug!("Ignoring signature for epoch {}: {}", epoch_number, e),
+        }
+    }
+
+    /// Handle a validated message received from a peer
+    async fn handle_gossip(&self, message: GossipMessage, network: &NetworkHandle) {
+        match message {
+            GossipMessage::Envelope(tx) => {
+                let target_tick = tx.target_tick;
+                match self.accept_transaction(tx.clone(), TraceStage::Received).await {
+                    Ok(response) => {
+                        // Witnesses report when they first saw the envelope
+                        if let Some(observation) =
+                            self.observe_envelope(&tx, response.submission_iteration).await
+                        {
+                            if let Err(e) = network.publish(GossipMessage::Observation(observation)).await {
+                                warn!("Failed to gossip observation: {}", e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Ignoring gossiped transaction for tick {}: {}", target_tick, e)
+                    }
+                }
+            }
+            GossipMessage::Observation(observation) => {
+                debug!(
+                    "Received observation of {} at iteration {} for tick {}",
+                    hex::encode(&observation.envelope_hash[..8]),
+                    observation.observed_iteration,
+                    observation.tick_number
+                );
+                let Some(vdf_form) = self.observation_form(observation.observed_iteration).await
+                else {
+                    debug!(
+                        "Ignoring observation at iteration {}, its VDF proof is ahead of this node",
+                        observation.observed_iteration
+                    );
+                    return;
+                };
+                let finality = self.finality.lock().await;
+                let witnesses = finality.witnesses();
+                let recorded = match observation.verify(witnesses, &vdf_form) {
+                    Ok(()) => self.record_observation(observation, witnesses).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = recorded {
+                    debug!("Ignoring observation: {}", e);
+                }
+            }
+            GossipMessage::TickCertificate(remote) => {
+                self.receive_certificate(remote, network).await;
+            }
+            GossipMessage::CompactTickCertificate(mut remote) => {
+                let discriminant = self.state.read().await.vdf_checkpoint.discriminant.clone();
+                match remote.expand(&discriminant) {
+                    Ok(()) => self.receive_certificate(remote, network).await,
+                    Err(e) => debug!("Ignoring compact certificate: {}", e),
+                }
+            }
+            GossipMessage::TickHeader(header) => {
+                self.receive_header(header, network).await;
+            }
+            GossipMessage::TickVote(vote) => {
+                self.add_tick_vote(vote).await;
+            }
+            GossipMessage::EpochSignature(signature) => {
+                self.add_epoch_signature(signature).await;
+            }
+            GossipMessage::ShareReveal(reveal) => {
+                debug!(
+                    "Received key share {} of {} for tick {}",
+                    reveal.share.index,
+                    hex::encode(&reveal.envelope_hash[..8]),
+                    reveal.tick_number
+                );
+                let finality = self.finality.lock().await;
+                if let Err(e) = self.key_shares.insert(reveal, finality.witnesses()).await {
+                    debug!("Ignoring key share: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Record this node's observation of an accepted envelope
+    ///
+    /// Only members of the current witness set observe envelopes.
+    ///
+    /// # Returns
+    ///
+    /// The signed observation to gossip, if this node is a witness
+    async fn observe_envelope(
+        &self,
+        tx: &TimelockTransaction,
+        observed_iteration: u64,
+    ) -> Option<WitnessObservation> {
+        self.finality.lock().await.witnesses().get(&self.witness_key)?;
+        let vdf_form = self.observation_form(observed_iteration).await?;
+
+        // A remote signer may be slow, so sign without holding the tracker
+        let observation = match WitnessObservation::sign(
+            tx.target_tick,
+            tx.envelope_hash(),
+            observed_iteration,
+            &vdf_form,
+            self.signer.as_ref(),
+        )
+        .await
+        {
+            Ok(observation) => observation,
+            Err(e) => {
+                warn!("Failed to sign observation: {}", e);
+                return None;
+            }
+        };
+
+        let finality = self.finality.lock().await;
+        match self
+            .record_observation(observation.clone(), finality.witnesses())
+            .await
+        {
+            Ok(true) => {
+                self.tracer.record(
+                    &observation.envelope_hash,
+                    TraceStage::Observed,
+                    observation.tick_number,
+                    Some(observed_iteration),
+                    None,
+                );
+                Some(observation)
+            }
+            Ok(false) => None,
+            Err(e) => {
+                debug!("Not observing envelope: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Validate a timelock transaction and add it to the pool
+    ///
+    /// Checks that the target tick is in the acceptance window and that the
+    /// puzzle is well-formed and decrypts after the consensus phase but
+    /// before the tick ends, or, for threshold locks, that every key share goes to a witness.
+    /// Used for both RPC submissions and envelopes received from peers;
+    /// `stage` records which one it was in the transaction's trace.
+    async fn accept_transaction(
+        &self,
+        mut tx: TimelockTransaction,
+        stage: TraceStage,
+    ) -> Result<SubmitTransactionResponse, String> {
+        let current_tick = self.state.read().await.current_tick;
+        let current_iter = self.vdf.read().await.get_iteration();
+        let k = self.config.iterations_per_tick;
+
+        // Validate transaction target tick
+        if tx.target_tick < current_tick {
+            return Err(format!(
+                "Transaction target tick {} is in the past (current: {})",
+                tx.target_tick, current_tick
+            ));
+        }
+
+        // Check if we're in the acceptance window for this tick
+        let target_tick_start = tx.target_tick * k;
+        let target_tick_end = (tx.target_tick + 1) * k;
+        let acceptance_start = if tx.target_tick == 0 {
+            0
+        } else {
+            ((tx.target_tick - 1) * k) + ((k as f64 * TX_ACCEPTANCE_WINDOW_START) as u64)
+        };
+        let acceptance_end = target_tick_start + ((k as f64 * TX_ACCEPTANCE_WINDOW_END) as u64);
+
+        if current_iter < acceptance_start || current_iter > acceptance_end {
+            return Err(format!(
+                "Outside acceptance window for tick {} (current iter: {}, window: {}-{})",
+                tx.target_tick, current_iter, acceptance_start, acceptance_end
+            ));
+        }
+
+        // Set submission iteration to current VDF iteration
+        tx.submission_iteration = current_iter;
+
+        // Validate timelock parameters
+        let decrypt_at = match &tx.lock {
+            KeyLock::Puzzle(puzzle) => {
+                // Caught here rather than by the solver in the middle of the tick
+                puzzle.validate(k).map_err(|e| e.to_string())?;
+                let decrypt_iter = tx.submission_iteration + puzzle.hardness as u64;
+                if decrypt_iter >= target_tick_end {
+                    return Err(format!(
+                        "Transaction would not decrypt in time (decrypt at {} > tick end {})",
+                        decrypt_iter, target_tick_end
+                    ));
+                }
+
+                // Ensure decryption happens after consensus phase (k/3)
+                let consensus_end = target_tick_start + k / 3;
+                if decrypt_iter < consensus_end {
+                    return Err(format!(
+                        "Transaction would decrypt too early (decrypt at {} < consensus end {})",
+                        decrypt_iter, consensus_end
+                    ));
+                }
+                decrypt_iter.to_string()
+            }
+            // Shares are revealed after the ordering commitment, so only the holders matter
+            KeyLock::Threshold(lock) => {
+                lock.validate().map_err(|e| e.to_string())?;
+                let finality = self.finality.lock().await;
+                if let Some(share) = lock
+                    .shares
+                    .iter()
+                    .find(|share| finality.witnesses().get(&share.witness).is_none())
+                {
+                    return Err(format!(
+                        "Key share holder {} is not a witness",
+                        hex::encode(share.witness)
+                    ));
+                }
+                format!("{} of {} key shares", lock.threshold, lock.shares.len())
+            }
+        };
+
+        // Identify the transaction by its envelope, which is the same on every node
+        let envelope_hash = tx.envelope_hash();
+        let tx_hash = hex::encode(envelope_hash);
+
+        // Add to pool
+        self.tx_pool.lock().await.push(tx.clone());
+        let trace_id = self
+            .tracer
+            .start(envelope_hash, tx.target_tick, tx.submission_iteration, stage);
+
+        info!(
+            trace_id = %trace_id,
+            "Accepted transaction {} for tick {} (submission: {}, decrypt: {})",
+            tx_hash, tx.target_tick, tx.submission_iteration, decrypt_at
+        );
+
+        Ok(SubmitTransactionResponse {
+            tx_hash,
+            submission_iteration: tx.submission_iteration,
+            target_tick: tx.target_tick,
+            trace_id,
+        })
+    }
+
+    /// Add a verified observation to the pool, and store it until finality
+    ///
+    /// # Returns
+    ///
+    /// Whether the observation is new
+    async fn record_observation(
+        &self,
+        observation: WitnessObservation,
+        witnesses: &WitnessSet,
+    ) -> KalaResult<bool> {
+        // Stored under the pool's lock, so writes for a tick don't race
+        let mut observations = self.observations.lock().await;
+        if !observations.insert(observation.clone(), witnesses)? {
+            return Ok(false);
+        }
+        if let Err(e) = self.state_db.add_observation(&observation).await {
+            warn!("Failed to store observation: {}", e);
+        }
+        Ok(true)
+    }
+
+    /// This node's VDF form at the iteration an observation commits to
+    ///
+    /// # Parameters
+    ///
+    /// * `observed_iteration` - Iteration the envelope was observed at
+    ///
+    /// # Returns
+    ///
+    /// The form at [`WitnessObservation::proof_iteration`], or `None` if
+    /// this node hasn't certified it
+    async fn observation_form(&self, observed_iteration: u64) -> Option<PackedForm> {
+        let k = self.config.iterations_per_tick;
+        match WitnessObservation::proof_iteration(observed_iteration, k) / k {
+            0 => {
+                let genesis = VDFCheckpoint::genesis(&self.config.discriminant, k);
+                Some((genesis.form_a, genesis.form_b, genesis.form_c))
+            }
+            tick => self
+                .vdf
+                .read()
+                .await
+                .get_tick_certificate(tick - 1)
+                .map(|certificate| (certificate.form_a, certificate.form_b, certificate.form_c)),
+        }
+    }
+
+    /// Process a single eternal tick
+    async fn process_eternal_tick(&self, tick_num: u64) -> Result<TickOutcome> {
+        let k = self.config.iterations_per_tick;
+        let _tick_start_iter = tick_num * k;
+
+        // Get transactions for this tick from the pool
+        let encrypted_txs = self.extract_tick_transactions(tick_num).await;
+
+        // Agree on when each envelope arrived; observations are kept until finality
+        let (canonical, witnesses): (Vec<_>, _) = {
+            let finality = self.finality.lock().await;
+            let observations = self.observations.lock().await;
+            let canonical = encrypted_txs
+                .iter()
+                .filter_map(|tx| {
+                    observations.canonical_timestamp(
+                        tick_num,
+                        &tx.envelope_hash(),
+                        finality.witnesses(),
+                    )
+                })
+                .collect();
+            (canonical, finality.witnesses().clone())
+        };
+
+        info!(
+            "Processing tick {} with {} encrypted transactions",
+            tick_num,
+            encrypted_txs.len()
+        );
+
+        // For single node, we follow the paper but skip Byzantine consensus
+        // The tick processor handles all the phases correctly
+        let outcome = self
+            .tick_processor
+            .process_tick(
+                tick_num,
+                self.vdf.clone(),
+                self.state.clone(),
+                encrypted_txs,
+                &canonical,
+                &witnesses,
+            )
+            .await?;
+        // Shares revealed for this tick were used up with it
+        self.key_shares.prune(tick_num + 1).await;
+
+        Ok(outcome)
+    }
+
+    /// Extract transactions for the current tick from the pool
+    async fn extract_tick_transactions(&self, tick_num: u64) -> Vec<TimelockTransaction> {
+        let mut pool = self.tx_pool.lock().await;
+        let mut tick_txs = Vec::new();
+        let mut remaining_txs = Vec::new();
+
+        // Separate transactions for this tick vs future ticks
+        for tx in pool.drain(..) {
+            if tx.target_tick == tick_num {
+                tick_txs.push(tx);
+            } else if tx.target_tick > tick_num {
+                remaining_txs.push(tx);
+            } else {
+                // Drop any transactions for past ticks
+                self.tracer.record(
+                    &tx.envelope_hash(),
+                    TraceStage::Dropped,
+                    tick_num,
+                    None,
+                    Some("target tick already passed".to_string()),
+                );
+            }
+        }
+
+        // Put back future transactions
+        *pool = remaining_txs;
+
+        debug!(
+            "Extracted {} transactions for tick {}, {} remaining in pool",
+            tick_txs.len(),
+            tick_num,
+            pool.len()
+        );
+
+        tick_txs
+    }
+
+    /// Log VDF checkpoint information
+    async fn log_vdf_checkpoint(&self) {
+        let vdf = self.vdf.read().await;
+        let checkpoint = vdf.checkpoint();
+
+        // Format all values first
+        let title = "VDF Checkpoint";
+        let iteration_str = format!("Iteration: {}", checkpoint.iteration);
+        let forms_str = format!(
+            "Forms: ({}, {}, {})",
+            preview(&checkpoint.form_a.to_hex(), 8),
+            preview(&checkpoint.form_b.to_hex(), 8),
+            preview(&checkpoint.form_c.to_hex(), 8)
+        );
+        let hash_str = format!("Hash: {}...", hex::encode(&checkpoint.hash_chain[..8]));
+        let certs_str = format!("Tick Certs: {}", checkpoint.tick_certificates.len());
+
+        // Calculate the box width based on the longest line
+        let box_width = [
+            title.len(),
+            iteration_str.len(),
+            forms_str.len(),
+            hash_str.len(),
+            certs_str.len(),
+        ]
+        .iter()
+        .max()
+        .unwrap()
+        .max(&40)
+            + 4; // Add padding and ensure minimum width
+
+        // Helper function to pad strings (left-aligned)
+        let pad = |s: &str| format!("│  {:<width$}  │", s, width = box_width - 6);
+
+        // Helper function to center text
+        let center = |s: &str| {
+            let padding = (box_width - 4 - s.len()) / 2;
+            let left_pad = " ".repeat(padding);
+            let right_pad = " ".repeat(box_width - 4 - padding - s.len());
+            format!("│  {}{}{}  │", left_pad, s, right_pad)
+        };
+
+        // Create the box
+        let top_line = format!("┌{}┐", "─".repeat(box_width - 2));
+        let bottom_line = format!("└{}┘", "─".repeat(box_width - 2));
 
         info!("{}", top_line);
         info!("{}", center(&title));
@@ -436,6 +2349,283 @@ impl KalaNode {
         info!("{}", bottom_line);
     }
 }
+/// Load the seed of the node's witness keys, generating and storing one if needed
+///
+/// Both the Ed25519 epoch signing key and the BLS tick voting key are
+/// derived from this seed.
+fn load_or_create_witness_seed(db: &KalaDatabase) -> Result<[u8; 32]> {
+    if let Some(bytes) = db.get_raw(WITNESS_KEY)? {
+        return bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("Stored witness key is corrupt"));
+    }
+
+    let secret = libp2p::identity::ed25519::SecretKey::generate();
+    db.put_raw(WITNESS_KEY, secret.as_ref())?;
+    secret
+        .as_ref()
+        .try_into()
+        .map_err(|_| anyhow!("Generated witness key has the wrong length"))
+}
+
+/// Check `hash` against the genesis hash recorded in `db`, recording it if there is none
+///
+/// Returns whether the hash was newly recorded.
+fn check_genesis_hash(db: &KalaDatabase, hash: &[u8; 32]) -> Result<bool> {
+    match db.get_raw(GENESIS_HASH_KEY)? {
+        Some(recorded) if recorded == hash => Ok(false),
+        Some(recorded) => Err(anyhow!(
+            "Database was created from genesis {}, not {}",
+            hex::encode(recorded),
+            hex::encode(hash)
+        )),
+        None => {
+            db.put_raw(GENESIS_HASH_KEY, hash)?;
+            Ok(true)
+        }
+    }
+}
+
+/// The iteration and Unix time in milliseconds the timeline is anchored at
+///
+/// A genesis time anchors iteration 0. Otherwise the anchor is recorded in
+/// `db` the first time the node opens it, at `iteration` and the current time.
+fn timeline_anchor(
+    db: &KalaDatabase,
+    genesis_time: Option<u64>,
+    iteration: u64,
+) -> Result<(u64, u64)> {
+    if let Some(genesis_time) = genesis_time {
+        return Ok((0, genesis_time));
+    }
+    if let Some(recorded) = db.get_raw(TIMELINE_ANCHOR_KEY)? {
+        let recorded: [u8; 16] = recorded
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("Stored timeline anchor is corrupt"))?;
+        let (iteration, time_ms) = recorded.split_at(8);
+        return Ok((
+            u64::from_le_bytes(iteration.try_into()?),
+            u64::from_le_bytes(time_ms.try_into()?),
+        ));
+    }
+
+    let time_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    let mut recorded = iteration.to_le_bytes().to_vec();
+    recorded.extend_from_slice(&time_ms.to_le_bytes());
+    db.put_raw(TIMELINE_ANCHOR_KEY, &recorded)?;
+    Ok((iteration, time_ms))
+}
+
+/// The first of `ticks` a time anchor is stored for
+async fn nearest_time_anchor(state_db: &StateDB, ticks: Vec<u64>) -> Result<Option<TimeAnchor>> {
+    for tick_number in ticks {
+        if let Some(anchor) = state_db.get_time_anchor(tick_number).await? {
+            return Ok(Some(anchor));
+        }
+    }
+    Ok(None)
+}
+
+/// Check that `db` was written in the VDF mode `dev_instant` selects
+///
+/// A database is marked when it is first opened with a simulated clock,
+/// which only a `fresh` one may be, and can't be opened without it after.
+fn check_dev_instant(db: &KalaDatabase, dev_instant: bool, fresh: bool) -> Result<()> {
+    let marked = db.get_raw(DEV_INSTANT_KEY)?.is_some();
+    if marked && !dev_instant {
+        return Err(anyhow!(
+            "Database was written with a simulated VDF clock and only opens with dev_instant"
+        ));
+    }
+    if dev_instant && !marked {
+        if !fresh {
+            return Err(anyhow!("dev_instant can't run on a database with real VDF output"));
+        }
+        db.put_raw(DEV_INSTANT_KEY, &[1])?;
+    }
+    Ok(())
+}
+
+/// Protocol parameters of the chain `config` describes, as of `state`
+///
+/// Values changed by governance override the configured ones.
+fn chain_params(config: &NodeConfig, state: &ChainState) -> ChainParams {
+    let k = config.iterations_per_tick;
+    let current_tick = state.current_tick;
+    let hardness_factor = state
+        .governance()
+        .parameter(GovernanceParameter::TimelockHardness)
+        .map_or(config.timelock_hardness_factor, |bps| bps as f64 / 10_000.0);
+    ChainParams {
+        protocol_version: PROTOCOL_VERSION,
+        chain_id: config.chain_id.clone(),
+        iterations_per_tick: k,
+        epoch_length: config.epoch_length,
+        phases: TickPhases {
+            collection_end: k / 3,
+            decryption_end: 2 * k / 3,
+            validation_end: k,
+        },
+        discriminant: config.discriminant.clone(),
+        hardness: HardnessBounds {
+            hardness_factor,
+            min_hardness: 1,
+            max_hardness: ((k as f64 * hardness_factor) as u32).max(1),
+            acceptance_window_start: (k as f64 * TX_ACCEPTANCE_WINDOW_START) as u64,
+            acceptance_window_end: (k as f64 * TX_ACCEPTANCE_WINDOW_END) as u64,
+        },
+        features: PROTOCOL_FEATURES
+            .iter()
+            .map(|(name, activation_tick)| FeatureStatus {
+                name: name.to_string(),
+                activation_tick: *activation_tick,
+                active: current_tick >= *activation_tick,
+            })
+            .collect(),
+    }
+}
+
+/// Commit the tick staged after `state`, if a failure left one behind
+///
+/// The staged tick carries its certificate and the state after it, so
+/// neither the VDF nor decryption runs again. A staged tick that doesn't
+/// follow `state` is stale and dropped.
+async fn replay_pending_tick(state_db: &StateDB, state: &mut ChainState) -> Result<()> {
+    let tick_number = state.current_tick;
+    let pending = match state_db.pending_tick(tick_number).await? {
+        Some(pending) => pending,
+        None => return Ok(()),
+    };
+    let previous = pending.certificate.previous_tick_hash;
+    let follows = tick_number == 0 || previous == state.last_tick_hash;
+    if !follows || pending.state.current_tick != tick_number + 1 {
+        warn!("Dropping staged tick {}, it doesn't follow the chain state", tick_number);
+        state_db.discard_pending_tick(tick_number).await?;
+        return Ok(());
+    }
+
+    state_db.commit_tick(&pending.certificate, &pending.state).await?;
+    info!("Committed tick {} staged before a failed write", tick_number);
+    *state = pending.state;
+    Ok(())
+}
+
+/// Whether a failed write may succeed if retried
+///
+/// Storage faults can be transient; a tick the state rejects stays rejected.
+fn is_storage_failure(error: &KalaError) -> bool {
+    matches!(error, KalaError::Database(_) | KalaError::IO(_))
+}
+
+/// The VDF as last streamed to `db`, if it reaches `state`'s current tick
+///
+/// The chain state is committed after each tick's VDF output, so output
+/// past its tick belongs to a tick that was never committed.
+fn resume_vdf(db: &KalaDatabase, state: &ChainState) -> Result<Option<EternalVDF>> {
+    let checkpoint = match DatabasePersistence::resume(db, state.current_tick)? {
+        Some(checkpoint) => checkpoint,
+        None => return Ok(None),
+    };
+    let tick = checkpoint.iteration / checkpoint.tick_size;
+    if tick != state.current_tick {
+        warn!(
+            "VDF output is stored up to tick {} of {}, resuming from the chain state",
+            tick, state.current_tick
+        );
+        return Ok(None);
+    }
+    let stored = &state.vdf_checkpoint;
+    if stored.iteration == checkpoint.iteration && stored.hash_chain != checkpoint.hash_chain {
+        return Err(anyhow!(
+            "Stored VDF output diverges from the chain state at iteration {}",
+            checkpoint.iteration
+        ));
+    }
+
+    info!("Resuming the VDF at iteration {}", checkpoint.iteration);
+    let vdf = EternalVDF::from_checkpoint(&checkpoint)
+        .map_err(|e| anyhow!("Failed to resume VDF: {}", e))?;
+    Ok(Some(vdf))
+}
+
+/// The build of this node, as compiled in by the build script
+fn node_version() -> NodeVersion {
+    NodeVersion {
+        version: crate::VERSION.to_string(),
+        git_commit: option_env!("KALA_GIT_COMMIT").unwrap_or("unknown").to_string(),
+        build_profile: option_env!("KALA_BUILD_PROFILE").unwrap_or_default().to_string(),
+        target: option_env!("KALA_BUILD_TARGET").unwrap_or_default().to_string(),
+        opt_level: option_env!("KALA_BUILD_OPT_LEVEL").unwrap_or_default().to_string(),
+        rustflags: option_env!("KALA_BUILD_RUSTFLAGS").unwrap_or_default().to_string(),
+        features: kala_state::FEATURES.iter().map(|feature| feature.to_string()).collect(),
+        protocol_version: PROTOCOL_VERSION,
+        vdf_library: kala_vdf::library_version(),
+        solver_library: format!("timelocks {}", kala_transaction::SOLVER_VERSION),
+    }
+}
+
+/// A timestamp proof as served over RPC, with its bytes hex-encoded
+fn timestamp_proof_to_rpc(proof: &kala_vdf::TimestampProof) -> kala_rpc::TimestampProof {
+    kala_rpc::TimestampProof {
+        iteration: proof.iteration,
+        tick_number: proof.tick_number,
+        data: hex::encode(&proof.data),
+        data_hash: hex::encode(proof.data_hash),
+        hash_at_timestamp: hex::encode(proof.hash_at_timestamp),
+        leaf_index: proof.leaf_index,
+        merkle_path: proof.merkle_path.iter().map(hex::encode).collect(),
+    }
+}
+
+/// Read back a timestamp proof served over RPC
+fn timestamp_proof_from_rpc(proof: &kala_rpc::TimestampProof) -> Result<kala_vdf::TimestampProof> {
+    let hash = |name: &str, value: &str| -> Result<[u8; 32]> {
+        hex::decode(value)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow!("{} must be a hex-encoded 32-byte hash", name))
+    };
+    Ok(kala_vdf::TimestampProof {
+        iteration: proof.iteration,
+        tick_number: proof.tick_number,
+        data: hex::decode(&proof.data).map_err(|e| anyhow!("Invalid data hex: {}", e))?,
+        data_hash: hash("Data hash", &proof.data_hash)?,
+        hash_at_timestamp: hash("Hash at timestamp", &proof.hash_at_timestamp)?,
+        leaf_index: proof.leaf_index,
+        merkle_path: proof
+            .merkle_path
+            .iter()
+            .map(|node| hash("Merkle path node", node))
+            .collect::<Result<_>>()?,
+    })
+}
+
+/// Group a tick's applied transactions by the accounts they modified
+fn collect_account_changes(state: &ChainState, applied: &[AppliedTransaction]) -> Vec<AccountChange> {
+    let mut changes: Vec<AccountChange> = Vec::new();
+    for tx in applied {
+        for address in &tx.accounts {
+            match changes.iter_mut().find(|change| change.address == *address) {
+                Some(change) => change.tx_hashes.push(tx.envelope_hash),
+                None => {
+                    if let Some(account) = state.get_account(address) {
+                        changes.push(AccountChange {
+                            address: *address,
+                            account: account.clone(),
+                            tx_hashes: vec![tx.envelope_hash],
+                        });
+                    }
+                }
+            }
+        }
+    }
+    changes
+}
+
 fn preview(s: &str, n: usize) -> &str {
     // str::get() returns Option<&str>; unwrap_or just gives back s
     s.get(..n).unwrap_or(s)
@@ -465,20 +2655,24 @@ impl KalaApiServer for KalaRpcHandler {
         })
     }
 
-    async fn submit_transaction(
-        &self,
-        req: SubmitTransactionRequest,
-    ) -> jsonrpsee::core::RpcResult<SubmitTransactionResponse> {
-        // Decode timelock transaction
-        let tx_bytes = hex::decode(&req.encrypted_tx).map_err(|e| {
+    async fn get_chain_params(&self) -> jsonrpsee::core::RpcResult<ChainParams> {
+        let state = self.state_db.load_chain_state().await.map_err(|e| {
             jsonrpsee::types::error::ErrorObject::owned(
-                jsonrpsee::types::error::INVALID_PARAMS_CODE,
-                format!("Invalid hex: {}", e),
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
                 None::<()>,
             )
         })?;
 
-        let tx: TimelockTransaction = serde_json::from_slice(&tx_bytes).map_err(|e| {
+        Ok(chain_params(&self.config, &state))
+    }
+
+    async fn submit_transaction(
+        &self,
+        req: SubmitTransactionRequest,
+    ) -> jsonrpsee::core::RpcResult<SubmitTransactionResponse> {
+        // Decode timelock transaction
+        let tx = req.envelope().map_err(|e| {
             jsonrpsee::types::error::ErrorObject::owned(
                 jsonrpsee::types::error::INVALID_PARAMS_CODE,
                 format!("Invalid transaction format: {}", e),
@@ -513,6 +2707,56 @@ impl KalaApiServer for KalaRpcHandler {
         }
     }
 
+    async fn reserve_nonces(
+        &self,
+        req: ReserveNoncesRequest,
+    ) -> jsonrpsee::core::RpcResult<NonceReservation> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let address = authenticate_reservation(&req, &self.config.chain_id, now)
+            .and_then(|address| {
+                self.nonce_reservations
+                    .claim_request(address, req.request_id, req.expires_at, now)
+                    .map(|()| address)
+            })
+            .map_err(|e| {
+                jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    e.to_string(),
+                    None::<()>,
+                )
+            })?;
+
+        let state = self.state_db.load_chain_state().await.map_err(|e| {
+            jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            )
+        })?;
+        let account_nonce = state.get_account_nonce(&address).unwrap_or(0);
+
+        let nonces = self
+            .nonce_reservations
+            .reserve(address, account_nonce, req.count, now)
+            .map_err(|e| {
+                jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    e.to_string(),
+                    None::<()>,
+                )
+            })?;
+
+        Ok(NonceReservation {
+            address: req.address,
+            first_nonce: *nonces.start(),
+            last_nonce: *nonces.end(),
+            account_nonce,
+        })
+    }
+
     async fn get_tick(
         &self,
         req: GetTickRequest,
@@ -527,6 +2771,223 @@ impl KalaApiServer for KalaRpcHandler {
         }
     }
 
+    async fn verify_tick_proof(
+        &self,
+        req: VerifyTickProofRequest,
+    ) -> jsonrpsee::core::RpcResult<TickProofVerification> {
+        let certificate = req.certificate;
+        let tick_number = certificate.tick_number;
+        let previous = match (req.previous, tick_number) {
+            (Some(previous), _) => Some(previous),
+            (None, 0) => None,
+            (None, _) => match self.state_db.get_tick(tick_number - 1).await {
+                Ok(Some(previous)) => Some(previous),
+                Ok(None) => {
+                    return Err(jsonrpsee::types::error::ErrorObject::owned(
+                        jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                        format!(
+                            "Tick {} is unknown; pass its certificate as previous",
+                            tick_number - 1
+                        ),
+                        None::<()>,
+                    ));
+                }
+                Err(e) => {
+                    return Err(jsonrpsee::types::error::ErrorObject::owned(
+                        jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                        e.to_string(),
+                        None::<()>,
+                    ));
+                }
+            },
+        };
+
+        // Recomputing the VDF segment takes as long as the tick did
+        let iterations_per_tick = self.config.iterations_per_tick;
+        let discriminant = self.config.discriminant.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let previous = previous.as_ref();
+            verify_tick_certificate(&certificate, previous, iterations_per_tick, &discriminant)
+        })
+        .await;
+        match result {
+            Ok(result) => Ok(TickProofVerification {
+                tick_number,
+                valid: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            }),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ).into())
+        }
+    }
+
+    async fn get_tick_range_proof(
+        &self,
+        req: GetTickRangeProofRequest,
+    ) -> jsonrpsee::core::RpcResult<TickRangeProof> {
+        match self.state_db.prove_tick_range(req.start_tick, req.end_tick).await {
+            Ok(result) => Ok(result),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ).into())
+        }
+    }
+
+    async fn timestamp_data(
+        &self,
+        req: TimestampDataRequest,
+    ) -> jsonrpsee::core::RpcResult<kala_rpc::TimestampProof> {
+        let data = hex::decode(&req.data).map_err(|e| {
+            jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                format!("Invalid hex: {}", e),
+                None::<()>,
+            )
+        })?;
+        if data.len() > MAX_TIMESTAMP_DATA_BYTES {
+            return Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                format!(
+                    "Data of {} bytes exceeds {} bytes; timestamp its hash instead",
+                    data.len(),
+                    MAX_TIMESTAMP_DATA_BYTES
+                ),
+                None::<()>,
+            ));
+        }
+
+        // The tick loop hashes the data into its next iteration without data,
+        // and completes the proof when the tick finalizes
+        let proof_rx = self.vdf.read().await.schedule_important_data(data);
+        let result =
+            tokio::task::spawn_blocking(move || proof_rx.recv_timeout(TIMESTAMP_TIMEOUT)).await;
+        match result {
+            Ok(Ok(proof)) => Ok(timestamp_proof_to_rpc(&proof)),
+            Ok(Err(_)) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                "The tick holding the data did not finalize in time; is the node producing ticks?",
+                None::<()>,
+            )),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ).into())
+        }
+    }
+
+    async fn verify_timestamp(
+        &self,
+        proof: kala_rpc::TimestampProof,
+    ) -> jsonrpsee::core::RpcResult<TimestampVerification> {
+        let proof = match timestamp_proof_from_rpc(&proof) {
+            Ok(proof) => proof,
+            Err(e) => {
+                return Ok(TimestampVerification {
+                    valid: false,
+                    error: Some(e.to_string()),
+                });
+            }
+        };
+
+        // Certificates reach the database in the background, so a tick
+        // that just finalized may only be known to the VDF
+        let certificate = match self.state_db.get_vdf_tick_certificate(proof.tick_number).await {
+            Ok(certificate) => certificate,
+            Err(e) => {
+                return Err(jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                    e.to_string(),
+                    None::<()>,
+                ));
+            }
+        };
+        let certificate = match certificate {
+            Some(certificate) => Some(certificate),
+            None => self.vdf.read().await.get_tick_certificate(proof.tick_number),
+        };
+
+        let error = match certificate {
+            None => Some(format!("Tick {} is not finalized", proof.tick_number)),
+            Some(certificate) if !proof.verify(&certificate) => Some(format!(
+                "Data is not committed to tick {} at iteration {}",
+                proof.tick_number, proof.iteration
+            )),
+            Some(_) => None,
+        };
+        Ok(TimestampVerification {
+            valid: error.is_none(),
+            error,
+        })
+    }
+
+    async fn get_tick_data(
+        &self,
+        req: GetTickDataRequest,
+    ) -> jsonrpsee::core::RpcResult<Option<TickDataEntry>> {
+        let data = match self.state_db.get_vdf_tick_data(req.tick_number).await {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                    e.to_string(),
+                    None::<()>,
+                ));
+            }
+        };
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        let leaf_count = data.entries.len() as u64;
+        let entry = usize::try_from(req.leaf_index).ok().and_then(|index| data.entries.get(index));
+        Ok(entry.map(|entry| TickDataEntry {
+            tick_number: data.tick_number,
+            leaf_index: req.leaf_index,
+            leaf_count,
+            iteration: entry.iteration,
+            data: (entry.location == PayloadLocation::Inline).then(|| hex::encode(&entry.data)),
+            data_hash: hex::encode(entry.data_hash),
+            hash_at_timestamp: hex::encode(entry.hash_at_timestamp),
+            data_uri: match &entry.location {
+                PayloadLocation::External(uri) => Some(uri.clone()),
+                _ => None,
+            },
+        }))
+    }
+
+    async fn prove_tick_data(
+        &self,
+        req: ProveTickDataRequest,
+    ) -> jsonrpsee::core::RpcResult<Option<kala_rpc::TimestampProof>> {
+        let data_hash: [u8; 32] = hex::decode(&req.data_hash)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    "Data hash must be 32 hex-encoded bytes",
+                    None::<()>,
+                )
+            })?;
+
+        match self.state_db.get_vdf_tick_data(req.tick_number).await {
+            Ok(data) => Ok(data.and_then(|data| {
+                let proof = data.prove(data.position(&data_hash)?)?;
+                Some(timestamp_proof_to_rpc(&proof))
+            })),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            )),
+        }
+    }
+
     async fn get_recent_ticks(
         &self,
         count: usize,
@@ -541,6 +3002,266 @@ impl KalaApiServer for KalaRpcHandler {
         }
     }
 
+    async fn get_epoch_summary(
+        &self,
+        req: GetEpochRequest,
+    ) -> jsonrpsee::core::RpcResult<Option<SignedEpochSummary>> {
+        let result = match req.epoch_number {
+            Some(epoch_number) => self.state_db.get_epoch_summary(epoch_number).await,
+            None => self.state_db.latest_epoch_summary().await,
+        };
+        match result {
+            Ok(result) => Ok(result),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ).into())
+        }
+    }
+
+    async fn get_tick_finality(
+        &self,
+        req: GetTickRequest,
+    ) -> jsonrpsee::core::RpcResult<Option<TickFinality>> {
+        match self.state_db.get_tick_finality(req.tick_number).await {
+            Ok(result) => Ok(result),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ).into())
+        }
+    }
+
+    async fn get_oracle_value(
+        &self,
+        req: GetOracleValueRequest,
+    ) -> jsonrpsee::core::RpcResult<Option<OracleProof>> {
+        let feed: [u8; 32] = hex::decode(&req.feed)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    "Feed must be 32 hex-encoded bytes",
+                    None::<()>,
+                )
+            })?;
+
+        match self.state_db.get_tick_finality(req.tick_number).await {
+            Ok(finality) => Ok(finality
+                .and_then(|finality| OracleProof::from_finality(&finality, &feed))),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ).into())
+        }
+    }
+
+    async fn get_metrics_history(
+        &self,
+        req: GetMetricsHistoryRequest,
+    ) -> jsonrpsee::core::RpcResult<Vec<MetricsSample>> {
+        let from = req.from.unwrap_or(0);
+        let to = req.to.unwrap_or(u64::MAX);
+        match self.state_db.metrics_history(from, to).await {
+            Ok(samples) => Ok(samples),
+            Err(e) => Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn watch_account(
+        &self,
+        pending: PendingSubscriptionSink,
+        address: String,
+    ) -> SubscriptionResult {
+        let address = match (GetAccountRequest { address }).validate() {
+            Ok(address) => address,
+            Err(e) => {
+                pending
+                    .reject(jsonrpsee::types::error::ErrorObject::owned(
+                        jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                        e.to_string(),
+                        None::<()>,
+                    ))
+                    .await;
+                return Ok(());
+            }
+        };
+
+        // Subscribe before accepting so no tick is missed in between
+        let mut updates = self.account_watcher.subscribe(address);
+        let sink = pending.accept().await?;
+        loop {
+            tokio::select! {
+                _ = sink.closed() => break,
+                update = updates.recv() => match update {
+                    Ok(update) => {
+                        let message = serde_json::value::to_raw_value(&update)?;
+                        if sink.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "Account watcher for {} lagged, skipped {} updates",
+                            hex::encode(address),
+                            skipped
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn trace_transaction(
+        &self,
+        tx_hash: String,
+    ) -> jsonrpsee::core::RpcResult<Option<TransactionTrace>> {
+        let envelope_hash: [u8; 32] = hex::decode(&tx_hash)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    "Transaction hash must be 32 hex-encoded bytes",
+                    None::<()>,
+                )
+            })?;
+        Ok(self.tracer.get(&envelope_hash))
+    }
+
+    async fn get_version(&self) -> jsonrpsee::core::RpcResult<NodeVersion> {
+        Ok(node_version())
+    }
+
+    async fn iteration_to_time(
+        &self,
+        iteration: u64,
+    ) -> jsonrpsee::core::RpcResult<TimelinePoint> {
+        let timeline = self.timeline.read().await;
+        Ok(TimelinePoint {
+            iteration,
+            time_ms: timeline.iteration_to_time(iteration),
+            iterations_per_second: timeline.iterations_per_second,
+        })
+    }
+
+    async fn time_to_iteration(
+        &self,
+        time_ms: u64,
+    ) -> jsonrpsee::core::RpcResult<TimelinePoint> {
+        let timeline = self.timeline.read().await;
+        Ok(TimelinePoint {
+            iteration: timeline.time_to_iteration(time_ms),
+            time_ms,
+            iterations_per_second: timeline.iterations_per_second,
+        })
+    }
+
+    async fn get_time_bounds(
+        &self,
+        req: GetTickRequest,
+    ) -> jsonrpsee::core::RpcResult<TimeBounds> {
+        let tick_number = req.tick_number;
+        let iterations_per_tick = self.config.iterations_per_tick;
+        let iteration = tick_number.saturating_add(1).saturating_mul(iterations_per_tick);
+        let interval = self.config.time_anchor_interval;
+        if interval == 0 {
+            return Ok(TimeBounds::new(iteration, &[]));
+        }
+
+        let below = tick_number / interval * interval;
+        let above = below.saturating_add(if below == tick_number { 0 } else { interval });
+        let steps = 0..TIME_ANCHOR_SEARCH_INTERVALS;
+        let offset = |step: u64| step.checked_mul(interval);
+        let earlier = steps.clone().map_while(|step| below.checked_sub(offset(step)?));
+        let later = steps.map_while(|step| above.checked_add(offset(step)?));
+        let internal = |e: String| {
+            jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e,
+                None::<()>,
+            )
+        };
+        let mut anchors = Vec::new();
+        for ticks in [earlier.collect::<Vec<_>>(), later.collect()] {
+            let anchor = nearest_time_anchor(&self.state_db, ticks)
+                .await
+                .map_err(|e| internal(e.to_string()))?;
+            anchors.extend(anchor);
+        }
+
+        // The signed readings let the client check the anchors itself
+        let mut records = Vec::new();
+        for anchor in &anchors {
+            let record = self
+                .state_db
+                .get_tick_finality(anchor.tick_number)
+                .await
+                .map_err(|e| internal(e.to_string()))?;
+            records.extend(record);
+        }
+        Ok(TimeBounds::new(iteration, &anchors).with_finality(records))
+    }
+
+    async fn debug_ffi_memory(&self) -> jsonrpsee::core::RpcResult<FfiMemoryReport> {
+        if !self.config.enable_debug_rpc {
+            return Err(jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::METHOD_NOT_FOUND_CODE,
+                "Debug RPC is disabled",
+                None::<()>,
+            ));
+        }
+
+        let tick = kala_vdf::ffi_allocations().into_iter().map(|usage| ("tick", usage));
+        // The two bindings count alike but each defines its own stats type
+        let timelocks = kala_transaction::solver_allocations().into_iter().map(|(name, stats)| {
+            let stats = kala_vdf::AllocationStats {
+                allocations: stats.allocations,
+                frees: stats.frees,
+                bytes_allocated: stats.bytes_allocated,
+                bytes_freed: stats.bytes_freed,
+            };
+            ("timelocks", (name, stats))
+        });
+        let mut allocations = Vec::new();
+        let mut over_freed = Vec::new();
+        for (library, (resource, stats)) in tick.chain(timelocks) {
+            if stats.over_freed() {
+                over_freed.push(format!("{}/{}", library, resource));
+            }
+            allocations.push(FfiAllocations {
+                library: library.to_string(),
+                resource: resource.to_string(),
+                allocations: stats.allocations,
+                frees: stats.frees,
+                live: stats.live(),
+                live_bytes: stats.live_bytes(),
+            });
+        }
+
+        let vdf = self.vdf.read().await.memory_usage();
+        Ok(FfiMemoryReport {
+            allocations,
+            over_freed,
+            vdf_certificates: vdf.certificates,
+            vdf_certificate_bytes: vdf.certificate_bytes,
+        })
+    }
+
+    async fn sync_status(&self) -> jsonrpsee::core::RpcResult<SyncStatus> {
+        Ok(self.sync_status.read().await.clone())
+    }
+
     async fn get_account(
         &self,
         req: GetAccountRequest,
@@ -575,11 +3296,194 @@ impl KalaApiServer for KalaRpcHandler {
             ).into())
         };
 
+        let denom = self.denoms.native();
         Ok(state.get_account(&address).map(|account| AccountInfo {
             balance: account.balance,
             nonce: account.nonce,
             staked_amount: account.staked_amount,
             delegation: account.delegation.map(hex::encode),
+            denom: denom.clone(),
+            balance_display: denom.format_amount(account.balance),
+            staked_display: denom.format_amount(account.staked_amount),
+            pending_nonces: state.held_transactions().pending_nonces(&address),
         }))
     }
+
+    async fn get_supply(&self) -> jsonrpsee::core::RpcResult<SupplyInfo> {
+        let state = self.state_db.load_chain_state().await.map_err(|e| {
+            jsonrpsee::types::error::ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let denom = self.denoms.native();
+        let total_supply = state.total_supply();
+        let staked_supply = state.total_staked();
+        Ok(SupplyInfo {
+            tick_number: state.current_tick,
+            denom: denom.clone(),
+            total_supply,
+            staked_supply,
+            total_supply_display: denom.format_amount(total_supply),
+            staked_supply_display: denom.format_amount(staked_supply),
+        })
+    }
+}
+
+/// Admin RPC error carrying `message`
+fn admin_error(message: impl ToString) -> jsonrpsee::types::ErrorObjectOwned {
+    jsonrpsee::types::error::ErrorObject::owned(
+        jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+        message.to_string(),
+        None::<()>,
+    )
+}
+
+fn peer_info(record: crate::peer_store::PeerRecord, connected: bool) -> PeerInfo {
+    PeerInfo {
+        banned: record.is_banned(),
+        peer_id: record.peer_id,
+        addresses: record.addresses,
+        last_seen: record.last_seen,
+        reputation: record.reputation,
+        connected,
+    }
+}
+
+impl KalaAdminHandler {
+    async fn network(&self) -> jsonrpsee::core::RpcResult<NetworkHandle> {
+        self.network
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| admin_error("Node is not on the gossip network"))
+    }
+}
+
+#[async_trait::async_trait]
+impl KalaAdminApiServer for KalaAdminHandler {
+    async fn peers(
+        &self,
+        ext: &jsonrpsee::Extensions,
+    ) -> jsonrpsee::core::RpcResult<Vec<PeerInfo>> {
+        authorize(ext)?;
+        let Some(network) = self.network.read().await.clone() else {
+            return Ok(Vec::new());
+        };
+        let mut peers: Vec<_> = network
+            .known_peers()
+            .await
+            .map_err(admin_error)?
+            .into_iter()
+            .map(|(record, connected)| peer_info(record, connected))
+            .collect();
+        peers.sort_by(|a, b| b.connected.cmp(&a.connected).then(b.last_seen.cmp(&a.last_seen)));
+        Ok(peers)
+    }
+
+    async fn ban_peer(
+        &self,
+        ext: &jsonrpsee::Extensions,
+        peer_id: String,
+    ) -> jsonrpsee::core::RpcResult<PeerInfo> {
+        authorize(ext)?;
+        let peer: PeerId = peer_id
+            .parse()
+            .map_err(|e| admin_error(format!("Invalid peer ID {}: {}", peer_id, e)))?;
+        let record = self.network().await?.ban_peer(peer).await.map_err(admin_error)?;
+        Ok(peer_info(record, false))
+    }
+
+    async fn mempool(
+        &self,
+        ext: &jsonrpsee::Extensions,
+    ) -> jsonrpsee::core::RpcResult<Vec<MempoolEntry>> {
+        authorize(ext)?;
+        let pool = self.tx_pool.lock().await;
+        Ok(pool
+            .iter()
+            .map(|tx| MempoolEntry {
+                tx_hash: hex::encode(tx.envelope_hash()),
+                submission_iteration: tx.submission_iteration,
+                target_tick: tx.target_tick,
+                lock: match tx.lock {
+                    KeyLock::Puzzle(_) => "puzzle",
+                    KeyLock::Threshold(_) => "threshold",
+                }
+                .to_string(),
+                ciphertext_bytes: tx.encrypted_data.ciphertext.len(),
+            })
+            .collect())
+    }
+
+    async fn flush_mempool(
+        &self,
+        ext: &jsonrpsee::Extensions,
+    ) -> jsonrpsee::core::RpcResult<usize> {
+        authorize(ext)?;
+        let dropped: Vec<_> = self.tx_pool.lock().await.drain(..).collect();
+        let current_tick = self.state.read().await.current_tick;
+        for tx in &dropped {
+            self.tracer.record(
+                &tx.envelope_hash(),
+                TraceStage::Dropped,
+                current_tick,
+                None,
+                Some("mempool flushed by operator".to_string()),
+            );
+        }
+        warn!("Flushed {} transactions from the mempool on operator request", dropped.len());
+        Ok(dropped.len())
+    }
+
+    async fn export_snapshot(
+        &self,
+        ext: &jsonrpsee::Extensions,
+        path: String,
+    ) -> jsonrpsee::core::RpcResult<SnapshotExport> {
+        authorize(ext)?;
+        if std::path::Path::new(&path).exists() {
+            return Err(admin_error(format!("{} already exists", path)));
+        }
+        let state_root = self.state_db.verify_state().await.map_err(admin_error)?;
+        self.state_db.create_backup(&path).await.map_err(admin_error)?;
+
+        info!("Exported snapshot to {} (state root {})", path, hex::encode(state_root));
+        Ok(SnapshotExport {
+            path,
+            state_root: hex::encode(state_root),
+        })
+    }
+
+    async fn prune_now(&self, ext: &jsonrpsee::Extensions) -> jsonrpsee::core::RpcResult<u64> {
+        authorize(ext)?;
+        let Some(archive) = &self.config.archive else {
+            return Err(admin_error("No archive is configured to prune to"));
+        };
+        // Wait for an epoch's pruning in progress rather than racing it
+        let _guard = self.archiving.lock().await;
+        let current_tick = self.state.read().await.current_tick;
+        let before = (current_tick + 1).saturating_sub(archive.retain_ticks);
+        let next = self.state_db.archive_ticks(before).await.map_err(admin_error)?;
+
+        info!("Ticks before {} are archived on operator request", next);
+        Ok(next)
+    }
+
+    async fn set_log_level(
+        &self,
+        ext: &jsonrpsee::Extensions,
+        filter: String,
+    ) -> jsonrpsee::core::RpcResult<()> {
+        authorize(ext)?;
+        let Some(log_filter) = &self.log_filter else {
+            return Err(admin_error("The log filter can't be changed on this node"));
+        };
+        log_filter.set_filter(&filter).map_err(admin_error)?;
+
+        info!("Log filter set to {} on operator request", filter);
+        Ok(())
+    }
 }
@@ -0,0 +1,83 @@
+//! Replace-by-fee among resubmissions of a nonce
+//!
+//! Envelopes stay encrypted until their tick, so the mempool can't tell two
+//! submissions of the same sender and nonce apart. Once a tick's envelopes
+//! are decrypted, only the highest-tipped of them is processed. Those ahead
+//! of the account's next nonce are then held in the chain state, see
+//! [`kala_state::HeldTransactions`].
+
+use std::collections::HashMap;
+
+use kala_transaction::Transaction;
+
+/// A decrypted transaction with the hash of the envelope it arrived in
+pub type EnvelopedTransaction = ([u8; 32], Transaction);
+
+/// Keep only the highest-tipped of transactions sharing a sender and nonce
+///
+/// The first submission wins a tie. Survivors keep their order.
+///
+/// # Returns
+///
+/// The surviving transactions, and the envelope hashes of the replaced ones
+pub fn replace_by_fee(
+    txs: Vec<EnvelopedTransaction>,
+) -> (Vec<EnvelopedTransaction>, Vec<[u8; 32]>) {
+    let mut best: HashMap<([u8; 32], u64), usize> = HashMap::new();
+    for (index, (_, tx)) in txs.iter().enumerate() {
+        best.entry((*tx.sender(), tx.nonce()))
+            .and_modify(|best| {
+                if tx.tip() > txs[*best].1.tip() {
+                    *best = index;
+                }
+            })
+            .or_insert(index);
+    }
+
+    let mut kept = Vec::new();
+    let mut replaced = Vec::new();
+    for (index, (envelope_hash, tx)) in txs.into_iter().enumerate() {
+        if best[&(*tx.sender(), tx.nonce())] == index {
+            kept.push((envelope_hash, tx));
+        } else {
+            replaced.push(envelope_hash);
+        }
+    }
+    (kept, replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_transaction::Send;
+
+    fn send(sender: u8, nonce: u64, tip: u64) -> Transaction {
+        Transaction::Send(Send {
+            sender: [sender; 32],
+            receiver: [9u8; 32],
+            denom: [0u8; 32],
+            amount: 1,
+            nonce,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip,
+        })
+    }
+
+    #[test]
+    fn test_replace_by_fee() {
+        let txs = vec![
+            ([1u8; 32], send(1, 1, 5)),
+            ([2u8; 32], send(2, 1, 0)),
+            ([3u8; 32], send(1, 1, 7)),
+            ([4u8; 32], send(1, 1, 7)),
+            ([5u8; 32], send(1, 2, 0)),
+        ];
+        let (kept, replaced) = replace_by_fee(txs);
+        let kept: Vec<_> = kept.iter().map(|(hash, _)| hash[0]).collect();
+        assert_eq!(kept, vec![2, 3, 5]);
+        assert_eq!(replaced, vec![[1u8; 32], [4u8; 32]]);
+    }
+}
@@ -0,0 +1,304 @@
+//! Nonce reservations for high-frequency submitters
+//!
+//! Transactions reach the mempool encrypted, so the node can't hand out
+//! nonces by looking at pending transactions. Instead a client holding an
+//! account key reserves a contiguous range through `kala_reserveNonces` and
+//! builds transactions with those nonces in parallel. [`NonceReservations`]
+//! remembers the highest nonce handed out per account so concurrent
+//! reservations never overlap, and forgets it once the account's applied
+//! nonce has caught up or [`RESERVATION_LIFETIME`] passes without another
+//! reservation. It also remembers the identifiers of unexpired requests, so
+//! each signed request reserves nonces only once.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use kala_rpc::ReserveNoncesRequest;
+
+use crate::observer::{AccountChange, NodeObserver};
+
+/// Most nonces a single request can reserve
+pub const MAX_NONCE_RESERVATION: u64 = 10_000;
+
+/// Longest a signed reservation request stays valid, in seconds
+pub const MAX_RESERVATION_SIGNATURE_LIFETIME: u64 = 300;
+
+/// Seconds a reservation is held after the account's latest one, unless
+/// the account's applied nonce catches up first
+pub const RESERVATION_LIFETIME: u64 = 600;
+
+/// Highest reserved nonce of each account with an outstanding reservation
+///
+/// # Example
+///
+/// ```
+/// use kala_core::nonces::NonceReservations;
+///
+/// let reservations = NonceReservations::new();
+/// assert_eq!(reservations.reserve([1u8; 32], 4, 10, 0).unwrap(), 5..=14);
+/// assert_eq!(reservations.reserve([1u8; 32], 4, 2, 0).unwrap(), 15..=16);
+/// ```
+#[derive(Default)]
+pub struct NonceReservations {
+    table: Mutex<ReservationTable>,
+}
+
+#[derive(Default)]
+struct ReservationTable {
+    /// Highest reserved nonce and expiry time of each reservation
+    reserved: HashMap<[u8; 32], (u64, u64)>,
+    /// Expiry time of each request accepted, by account and request id
+    requests: HashMap<([u8; 32], u64), u64>,
+}
+
+impl NonceReservations {
+    /// Create an empty reservation table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve `count` nonces for `address` at Unix time `now`
+    ///
+    /// The range starts after both the account's applied nonce and any
+    /// unexpired earlier reservation, and renews the account's reservation
+    /// for [`RESERVATION_LIFETIME`].
+    pub fn reserve(
+        &self,
+        address: [u8; 32],
+        account_nonce: u64,
+        count: u64,
+        now: u64,
+    ) -> Result<RangeInclusive<u64>> {
+        if count == 0 || count > MAX_NONCE_RESERVATION {
+            bail!(
+                "Can reserve between 1 and {} nonces, not {}",
+                MAX_NONCE_RESERVATION,
+                count
+            );
+        }
+
+        let mut table = self.table.lock().unwrap();
+        table.reserved.retain(|_, (_, expires_at)| *expires_at > now);
+        let last = table.reserved.get(&address).map_or(0, |(end, _)| *end).max(account_nonce);
+        let end = last
+            .checked_add(count)
+            .ok_or_else(|| anyhow!("Nonce space of the account is exhausted"))?;
+        table.reserved.insert(address, (end, now.saturating_add(RESERVATION_LIFETIME)));
+        Ok(last + 1..=end)
+    }
+
+    /// Accept request `request_id` of `address`, valid until `expires_at`
+    ///
+    /// Fails if the account already used the identifier in a request that
+    /// hasn't expired at Unix time `now`. Expired ones are forgotten, as
+    /// their signatures are no longer accepted anyway.
+    pub fn claim_request(
+        &self,
+        address: [u8; 32],
+        request_id: u64,
+        expires_at: u64,
+        now: u64,
+    ) -> Result<()> {
+        let mut table = self.table.lock().unwrap();
+        table.requests.retain(|_, expiry| *expiry >= now);
+        if table.requests.contains_key(&(address, request_id)) {
+            bail!("Reservation request {} was already used", request_id);
+        }
+        table.requests.insert((address, request_id), expires_at);
+        Ok(())
+    }
+
+    /// Highest nonce reserved for `address`, if a reservation is held
+    pub fn reserved_up_to(&self, address: &[u8; 32]) -> Option<u64> {
+        self.table.lock().unwrap().reserved.get(address).map(|(end, _)| *end)
+    }
+
+    /// Drop reservations the account's applied nonce has caught up with
+    pub fn release(&self, changes: &[AccountChange]) {
+        let mut table = self.table.lock().unwrap();
+        for change in changes {
+            let caught_up = |(end, _): &(u64, u64)| *end <= change.account.nonce;
+            if table.reserved.get(&change.address).is_some_and(caught_up) {
+                table.reserved.remove(&change.address);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeObserver for NonceReservations {
+    async fn on_accounts_changed(&self, _tick_number: u64, changes: &[AccountChange]) {
+        self.release(changes);
+    }
+}
+
+/// Check that a reservation request is signed by the account key for
+/// `chain_id` and unexpired
+///
+/// Returns the account address. `now` is the current Unix time in seconds.
+/// Whether the request was used before is left to
+/// [`NonceReservations::claim_request`].
+pub fn authenticate_reservation(
+    req: &ReserveNoncesRequest,
+    chain_id: &str,
+    now: u64,
+) -> Result<[u8; 32]> {
+    let address: [u8; 32] = hex::decode(&req.address)
+        .map_err(|e| anyhow!("Invalid address hex: {}", e))?
+        .try_into()
+        .map_err(|_| anyhow!("Address must be 32 bytes"))?;
+    let signature: [u8; 64] = hex::decode(&req.signature)
+        .map_err(|e| anyhow!("Invalid signature hex: {}", e))?
+        .try_into()
+        .map_err(|_| anyhow!("Signature must be 64 bytes"))?;
+
+    if req.expires_at < now {
+        bail!("Reservation request expired at {}", req.expires_at);
+    }
+    if req.expires_at > now + MAX_RESERVATION_SIGNATURE_LIFETIME {
+        bail!(
+            "Reservation requests may be valid for at most {} seconds",
+            MAX_RESERVATION_SIGNATURE_LIFETIME
+        );
+    }
+
+    let key = VerifyingKey::from_bytes(&address)
+        .map_err(|_| anyhow!("Address is not an Ed25519 public key"))?;
+    let message = ReserveNoncesRequest::signing_message(
+        chain_id,
+        &address,
+        req.count,
+        req.request_id,
+        req.expires_at,
+    );
+    key.verify(&message, &Signature::from_bytes(&signature))
+        .map_err(|_| anyhow!("Invalid signature for {}", req.address))?;
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use kala_state::Account;
+
+    const CHAIN_ID: &str = "kala-test";
+
+    fn signed_request(key: &SigningKey, count: u64, expires_at: u64) -> ReserveNoncesRequest {
+        let address = key.verifying_key().to_bytes();
+        let message =
+            ReserveNoncesRequest::signing_message(CHAIN_ID, &address, count, 1, expires_at);
+        ReserveNoncesRequest {
+            address: hex::encode(address),
+            count,
+            request_id: 1,
+            expires_at,
+            signature: hex::encode(key.sign(&message).to_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_reservations_do_not_overlap() {
+        let reservations = NonceReservations::new();
+        let alice = [1u8; 32];
+
+        assert_eq!(reservations.reserve(alice, 0, 3, 0).unwrap(), 1..=3);
+        assert_eq!(reservations.reserve(alice, 0, 2, 0).unwrap(), 4..=5);
+        // A nonce applied past the reservations moves the next range on
+        assert_eq!(reservations.reserve(alice, 9, 1, 0).unwrap(), 10..=10);
+        assert_eq!(reservations.reserve([2u8; 32], 0, 1, 0).unwrap(), 1..=1);
+
+        assert!(reservations.reserve(alice, 0, 0, 0).is_err());
+        assert!(reservations.reserve(alice, 0, MAX_NONCE_RESERVATION + 1, 0).is_err());
+        assert!(reservations.reserve(alice, u64::MAX - 1, 2, 0).is_err());
+    }
+
+    #[test]
+    fn test_reservations_expire() {
+        let reservations = NonceReservations::new();
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        reservations.reserve(alice, 0, 5, 0).unwrap();
+        reservations.reserve(bob, 0, 5, 0).unwrap();
+
+        // Reserving again renews the account's reservation
+        let renewed = RESERVATION_LIFETIME - 1;
+        assert_eq!(reservations.reserve(alice, 0, 1, renewed).unwrap(), 6..=6);
+
+        // Left unrenewed, a reservation is forgotten and its nonces handed out again
+        let later = RESERVATION_LIFETIME;
+        assert_eq!(reservations.reserve(bob, 0, 1, later).unwrap(), 1..=1);
+        assert_eq!(reservations.reserve(alice, 0, 1, later).unwrap(), 7..=7);
+    }
+
+    #[test]
+    fn test_released_once_nonce_catches_up() {
+        let reservations = NonceReservations::new();
+        let alice = [1u8; 32];
+        reservations.reserve(alice, 0, 5, 0).unwrap();
+
+        let mut account = Account::new();
+        account.nonce = 4;
+        let change = AccountChange {
+            address: alice,
+            account,
+            tx_hashes: Vec::new(),
+        };
+        reservations.release(std::slice::from_ref(&change));
+        assert_eq!(reservations.reserved_up_to(&alice), Some(5));
+
+        let mut change = change;
+        change.account.nonce = 5;
+        reservations.release(&[change]);
+        assert_eq!(reservations.reserved_up_to(&alice), None);
+    }
+
+    #[test]
+    fn test_authenticate_reservation() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let now = 1_000;
+
+        let request = signed_request(&key, 10, now + 60);
+        assert_eq!(
+            authenticate_reservation(&request, CHAIN_ID, now).unwrap(),
+            key.verifying_key().to_bytes()
+        );
+        assert!(authenticate_reservation(&request, CHAIN_ID, now + 61).is_err());
+
+        // The signature covers the count, the request id and the chain
+        let mut inflated = request.clone();
+        inflated.count = 11;
+        assert!(authenticate_reservation(&inflated, CHAIN_ID, now).is_err());
+        let mut renumbered = request.clone();
+        renumbered.request_id = 2;
+        assert!(authenticate_reservation(&renumbered, CHAIN_ID, now).is_err());
+        assert!(authenticate_reservation(&request, "kala-other", now).is_err());
+
+        // Someone else's key can't reserve the account's nonces
+        let mut stolen = signed_request(&SigningKey::from_bytes(&[8u8; 32]), 10, now + 60);
+        stolen.address = request.address.clone();
+        assert!(authenticate_reservation(&stolen, CHAIN_ID, now).is_err());
+
+        let long_lived = signed_request(&key, 10, now + MAX_RESERVATION_SIGNATURE_LIFETIME + 1);
+        assert!(authenticate_reservation(&long_lived, CHAIN_ID, now).is_err());
+    }
+
+    #[test]
+    fn test_requests_claimed_once() {
+        let reservations = NonceReservations::new();
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+
+        reservations.claim_request(alice, 1, 60, 0).unwrap();
+        // A replay is refused while the request is valid
+        assert!(reservations.claim_request(alice, 1, 60, 30).is_err());
+        // Ids are per account
+        reservations.claim_request(alice, 2, 60, 30).unwrap();
+        reservations.claim_request(bob, 1, 60, 30).unwrap();
+
+        // Once expired the signature is refused anyway, so the id is forgotten
+        reservations.claim_request(alice, 1, 120, 61).unwrap();
+        assert_eq!(reservations.table.lock().unwrap().requests.len(), 1);
+    }
+}
@@ -0,0 +1,100 @@
+//! Hooks into node events
+//!
+//! Components that react to what the node is doing, such as the alert
+//! sink, implement [`NodeObserver`] and are registered with
+//! [`KalaNode::with_observer`](crate::KalaNode::with_observer). Every
+//! method has an empty default, so observers only implement the events
+//! they care about.
+//!
+//! Observers are called inline from the tick loop and must return
+//! quickly; slow work such as network delivery belongs in a background
+//! task.
+
+use std::time::Duration;
+
+use kala_state::{Account, DoubleSignEvidence, TickCertificate};
+
+/// An account modified by a tick
+#[derive(Debug, Clone)]
+pub struct AccountChange {
+    /// Address of the account
+    pub address: [u8; 32],
+    /// The account after the tick
+    pub account: Account,
+    /// Envelope hashes of the tick's transactions that modified it
+    pub tx_hashes: Vec<[u8; 32]>,
+}
+
+/// Receives notifications about node events
+///
+/// # Example
+///
+/// ```
+/// use kala_core::observer::NodeObserver;
+/// use std::time::Duration;
+///
+/// struct StallLogger;
+///
+/// #[async_trait::async_trait]
+/// impl NodeObserver for StallLogger {
+///     async fn on_stall(&self, next_tick: u64, idle: Duration) {
+///         eprintln!("tick {} overdue by {:?}", next_tick, idle);
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait NodeObserver: Send + Sync {
+    /// A tick was produced and stored
+    ///
+    /// # Parameters
+    ///
+    /// - `certificate`: The stored tick certificate
+    /// - `duration`: Wall-clock time the tick took
+    async fn on_tick(&self, _certificate: &TickCertificate, _duration: Duration) {}
+
+    /// Producing a tick failed
+    ///
+    /// The node stops after notifying its observers, so this is the last
+    /// event they receive.
+    async fn on_tick_failed(&self, _tick_number: u64, _error: &str) {}
+
+    /// The watchdog saw no new tick for `idle`
+    ///
+    /// Reported once per stalled tick.
+    async fn on_stall(&self, _next_tick: u64, _idle: Duration) {}
+
+    /// Re-sealing an epoch produced a different state root than the summary
+    /// already stored for it
+    ///
+    /// # Parameters
+    ///
+    /// - `epoch_number`: The epoch being sealed
+    /// - `stored`: State root in the stored summary
+    /// - `computed`: State root of the local chain state
+    async fn on_state_root_mismatch(&self, _epoch_number: u64, _stored: [u8; 32], _computed: [u8; 32]) {}
+
+    /// A tick reached finality
+    ///
+    /// # Parameters
+    ///
+    /// - `tick_number`: The finalized tick
+    /// - `signed_weight`: Witness weight that signed it
+    /// - `total_weight`: Total weight of the witness set
+    async fn on_tick_finalized(&self, _tick_number: u64, _signed_weight: u64, _total_weight: u64) {}
+
+    /// A witness was caught voting for two hashes at one tick
+    ///
+    /// Called once per witness and tick, before the evidence is submitted.
+    async fn on_double_sign(&self, _evidence: &DoubleSignEvidence) {}
+
+    /// Transactions in a tick modified accounts
+    ///
+    /// Called after the tick's state is persisted, once per tick that
+    /// applied at least one transaction.
+    ///
+    /// # Parameters
+    ///
+    /// - `tick_number`: The tick that applied the transactions
+    /// - `changes`: One entry per modified account
+    async fn on_accounts_changed(&self, _tick_number: u64, _changes: &[AccountChange]) {}
+}
@@ -0,0 +1,357 @@
+//! Persistent store of known peers
+//!
+//! The network service records every peer it connects to or discovers,
+//! together with the addresses it can be reached on, when it was last seen,
+//! and a reputation score that tracks how well it has behaved. Records are
+//! kept in [`KalaDatabase`] so a restarted node can reconnect to the peers it
+//! already knows instead of relying on bootstrap nodes alone.
+//!
+//! The node's own libp2p identity is stored alongside the records, keeping
+//! its peer ID stable across restarts.
+
+use kala_common::database::{DatabaseOps, KalaDatabase};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{EncodingType, KalaSerialize};
+use libp2p::{identity::Keypair, Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Database prefix for peer records
+pub const PEER_PREFIX: &str = "peer";
+
+/// Database key for the node's libp2p identity
+const IDENTITY_KEY: &[u8] = b"p2p_identity";
+
+/// Maximum number of addresses remembered per peer
+pub const MAX_ADDRESSES_PER_PEER: usize = 8;
+
+/// Lowest reputation a peer can reach
+pub const MIN_REPUTATION: i32 = -1000;
+
+/// Highest reputation a peer can reach
+pub const MAX_REPUTATION: i32 = 1000;
+
+/// Peers at or below this reputation are disconnected and never redialed
+pub const BAN_THRESHOLD: i32 = -500;
+
+/// A peer known to this node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// Base58 libp2p peer ID
+    pub peer_id: String,
+    /// Multiaddrs the peer was reached on or advertised, most recent last
+    pub addresses: Vec<String>,
+    /// Unix timestamp of the last connection to the peer
+    pub last_seen: u64,
+    /// Accumulated reputation, between [`MIN_REPUTATION`] and [`MAX_REPUTATION`]
+    pub reputation: i32,
+}
+
+impl PeerRecord {
+    fn new(peer_id: PeerId) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            addresses: Vec::new(),
+            last_seen: 0,
+            reputation: 0,
+        }
+    }
+
+    /// Whether the peer has misbehaved badly enough to be refused
+    pub fn is_banned(&self) -> bool {
+        self.reputation <= BAN_THRESHOLD
+    }
+
+    /// Parsed addresses, skipping any that are no longer valid
+    pub fn multiaddrs(&self) -> Vec<Multiaddr> {
+        self.addresses
+            .iter()
+            .filter_map(|addr| addr.parse().ok())
+            .collect()
+    }
+
+    fn add_address(&mut self, addr: &Multiaddr) {
+        let addr = addr.to_string();
+        self.addresses.retain(|existing| *existing != addr);
+        self.addresses.push(addr);
+        if self.addresses.len() > MAX_ADDRESSES_PER_PEER {
+            self.addresses.remove(0);
+        }
+    }
+}
+
+impl KalaSerialize for PeerRecord {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json // Small records, readable when inspecting the DB
+    }
+}
+
+/// In-memory view of known peers, persisted to the database on [`flush`](PeerStore::flush)
+///
+/// Updates happen on every connection and gossip message, so they are
+/// batched in memory and written out periodically by the network service.
+pub struct PeerStore {
+    db: Option<KalaDatabase>,
+    peers: HashMap<PeerId, PeerRecord>,
+    dirty: HashSet<PeerId>,
+}
+
+impl PeerStore {
+    /// Open the peer store, loading all previously recorded peers
+    pub async fn open(db: KalaDatabase) -> KalaResult<Self> {
+        let mut peers = HashMap::new();
+
+        for key in db.get_keys_with_prefix(PEER_PREFIX).await? {
+            let Ok(peer_id) = key.parse::<PeerId>() else {
+                tracing::warn!("Skipping peer record with invalid ID {}", key);
+                continue;
+            };
+            if let Some(record) = db.load_data::<PeerRecord>(PEER_PREFIX, &key).await? {
+                peers.insert(peer_id, record);
+            }
+        }
+
+        Ok(Self {
+            db: Some(db),
+            peers,
+            dirty: HashSet::new(),
+        })
+    }
+
+    /// A peer store that is never persisted, for tests and ephemeral nodes
+    pub fn in_memory() -> Self {
+        Self {
+            db: None,
+            peers: HashMap::new(),
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load the node's identity, generating and storing a new one if needed
+    ///
+    /// In-memory stores always return a fresh identity.
+    pub fn load_or_create_identity(&self) -> KalaResult<Keypair> {
+        let Some(db) = &self.db else {
+            return Ok(Keypair::generate_ed25519());
+        };
+
+        if let Some(bytes) = db.get_raw(IDENTITY_KEY)? {
+            return Keypair::from_protobuf_encoding(&bytes)
+                .map_err(|e| KalaError::network(format!("Invalid stored identity: {}", e)));
+        }
+
+        let keypair = Keypair::generate_ed25519();
+        let bytes = keypair
+            .to_protobuf_encoding()
+            .map_err(|e| KalaError::network(format!("Failed to encode identity: {}", e)))?;
+        db.put_raw(IDENTITY_KEY, &bytes)?;
+
+        Ok(keypair)
+    }
+
+    /// Look up a peer's record
+    pub fn get(&self, peer: &PeerId) -> Option<&PeerRecord> {
+        self.peers.get(peer)
+    }
+
+    /// Number of known peers
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peers are known
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Record a connection to a peer, optionally on a dialable address
+    pub fn record_seen(&mut self, peer: PeerId, addr: Option<&Multiaddr>) {
+        let record = self.entry(peer);
+        record.last_seen = now();
+        if let Some(addr) = addr {
+            record.add_address(addr);
+        }
+    }
+
+    /// Remember addresses a peer was discovered on or advertised
+    pub fn add_addresses<'a>(&mut self, peer: PeerId, addrs: impl IntoIterator<Item = &'a Multiaddr>) {
+        let record = self.entry(peer);
+        for addr in addrs {
+            record.add_address(addr);
+        }
+    }
+
+    /// Adjust a peer's reputation, returning the new value
+    pub fn adjust_reputation(&mut self, peer: PeerId, delta: i32) -> i32 {
+        let record = self.entry(peer);
+        record.reputation = record
+            .reputation
+            .saturating_add(delta)
+            .clamp(MIN_REPUTATION, MAX_REPUTATION);
+        record.reputation
+    }
+
+    /// Ban a peer by dropping its reputation to the minimum
+    ///
+    /// Persisted like any other reputation change, so the ban survives
+    /// restarts. Returns the peer's updated record.
+    pub fn ban(&mut self, peer: PeerId) -> PeerRecord {
+        let record = self.entry(peer);
+        record.reputation = MIN_REPUTATION;
+        record.clone()
+    }
+
+    /// All known peers
+    pub fn records(&self) -> impl Iterator<Item = (&PeerId, &PeerRecord)> {
+        self.peers.iter()
+    }
+
+    /// Whether a peer is banned
+    pub fn is_banned(&self, peer: &PeerId) -> bool {
+        self.peers.get(peer).is_some_and(PeerRecord::is_banned)
+    }
+
+    /// Peers worth dialing on startup
+    ///
+    /// Returns up to `limit` non-banned peers with at least one address,
+    /// best reputation first and most recently seen among equals.
+    pub fn dial_candidates(&self, limit: usize) -> Vec<(PeerId, Vec<Multiaddr>)> {
+        let mut candidates: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(_, record)| !record.is_banned())
+            .map(|(peer, record)| (*peer, record, record.multiaddrs()))
+            .filter(|(_, _, addrs)| !addrs.is_empty())
+            .collect();
+
+        candidates.sort_by(|(_, a, _), (_, b, _)| {
+            b.reputation
+                .cmp(&a.reputation)
+                .then(b.last_seen.cmp(&a.last_seen))
+        });
+
+        candidates
+            .into_iter()
+            .take(limit)
+            .map(|(peer, _, addrs)| (peer, addrs))
+            .collect()
+    }
+
+    /// Write changed records to the database, returning how many were written
+    pub async fn flush(&mut self) -> KalaResult<usize> {
+        let Some(db) = &self.db else {
+            self.dirty.clear();
+            return Ok(0);
+        };
+
+        let operations: Vec<_> = self
+            .dirty
+            .iter()
+            .filter_map(|peer| self.peers.get(peer))
+            .map(|record| (PEER_PREFIX.to_string(), record.peer_id.clone(), record.clone()))
+            .collect();
+        let count = operations.len();
+
+        if count > 0 {
+            db.batch_store(operations).await?;
+        }
+        self.dirty.clear();
+
+        Ok(count)
+    }
+
+    fn entry(&mut self, peer: PeerId) -> &mut PeerRecord {
+        self.dirty.insert(peer);
+        self.peers.entry(peer).or_insert_with(|| PeerRecord::new(peer))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(port: u16) -> Multiaddr {
+        format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_peer_store_persists_records_and_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = KalaDatabase::new(dir.path().to_str().unwrap()).unwrap();
+        let peer = PeerId::random();
+
+        let identity = {
+            let mut store = PeerStore::open(db.clone()).await.unwrap();
+            store.record_seen(peer, Some(&addr(30333)));
+            store.adjust_reputation(peer, 7);
+            assert_eq!(store.flush().await.unwrap(), 1);
+            assert_eq!(store.flush().await.unwrap(), 0);
+            store.load_or_create_identity().unwrap()
+        };
+
+        let store = PeerStore::open(db).await.unwrap();
+        let record = store.get(&peer).unwrap();
+        assert_eq!(record.addresses, vec![addr(30333).to_string()]);
+        assert_eq!(record.reputation, 7);
+        assert!(record.last_seen > 0);
+
+        let reloaded = store.load_or_create_identity().unwrap();
+        assert_eq!(reloaded.public().to_peer_id(), identity.public().to_peer_id());
+    }
+
+    #[test]
+    fn test_addresses_are_bounded_and_deduplicated() {
+        let mut store = PeerStore::in_memory();
+        let peer = PeerId::random();
+
+        let addrs: Vec<_> = (0..MAX_ADDRESSES_PER_PEER as u16 + 2).map(addr).collect();
+        store.add_addresses(peer, &addrs);
+        store.add_addresses(peer, [&addrs[3]]);
+
+        let record = store.get(&peer).unwrap();
+        assert_eq!(record.addresses.len(), MAX_ADDRESSES_PER_PEER);
+        assert_eq!(record.addresses.last(), Some(&addrs[3].to_string()));
+        assert!(!record.addresses.contains(&addrs[0].to_string()));
+    }
+
+    #[test]
+    fn test_dial_candidates_skip_banned_peers() {
+        let mut store = PeerStore::in_memory();
+        let (good, neutral, banned, unreachable) =
+            (PeerId::random(), PeerId::random(), PeerId::random(), PeerId::random());
+
+        store.record_seen(good, Some(&addr(1)));
+        store.record_seen(neutral, Some(&addr(2)));
+        store.record_seen(banned, Some(&addr(3)));
+        store.record_seen(unreachable, None);
+        store.adjust_reputation(good, 10);
+        assert_eq!(store.adjust_reputation(banned, 2 * MIN_REPUTATION), MIN_REPUTATION);
+
+        assert!(store.is_banned(&banned));
+        let candidates: Vec<_> = store.dial_candidates(10).into_iter().map(|(p, _)| p).collect();
+        assert_eq!(candidates, vec![good, neutral]);
+        assert_eq!(store.dial_candidates(1).len(), 1);
+    }
+
+    #[test]
+    fn test_ban_overrides_good_reputation() {
+        let mut store = PeerStore::in_memory();
+        let peer = PeerId::random();
+        store.record_seen(peer, Some(&addr(1)));
+        store.adjust_reputation(peer, MAX_REPUTATION);
+
+        let record = store.ban(peer);
+        assert_eq!(record.reputation, MIN_REPUTATION);
+        assert!(store.is_banned(&peer));
+        assert!(store.dial_candidates(10).is_empty());
+        assert_eq!(store.records().count(), 1);
+    }
+}
@@ -0,0 +1,198 @@
+//! Retention of timestamped payloads
+//!
+//! Nodes store the data each tick timestamped, so `kala_getTickData` and
+//! `kala_proveTickData` can serve it with its inclusion proof. Keeping
+//! every payload forever isn't always worth it: [`PayloadRetainer`] sits in
+//! front of the VDF's persistence and applies the
+//! [`NodeConfig::payload_retention`] tiers to each tick's data before it is
+//! stored. A payload is kept in full, dropped with only its hash kept, or
+//! uploaded to the archive bucket and replaced by the object's URI.
+//!
+//! [`NodeConfig::payload_retention`]: crate::NodeConfig::payload_retention
+
+use std::sync::Arc;
+
+use kala_state::ArchiveBackend;
+use kala_vdf::{PayloadLocation, TickCertificate, TickData, VDFCheckpoint, VdfPersistence};
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use crate::config::{PayloadRetention, PayloadRetentionTier};
+
+/// Key of an uploaded payload, by its hash
+fn payload_key(data_hash: &[u8; 32]) -> String {
+    format!("payloads/{}", hex::encode(data_hash))
+}
+
+/// What `tiers` keep of a payload of `size` bytes
+///
+/// The first tier the payload fits in applies. Payloads larger than every
+/// tier are kept in full.
+pub fn retention_for(tiers: &[PayloadRetentionTier], size: usize) -> PayloadRetention {
+    tiers
+        .iter()
+        .find(|tier| tier.max_bytes.is_none_or(|max_bytes| size <= max_bytes))
+        .map_or(PayloadRetention::Full, |tier| tier.keep)
+}
+
+/// VDF persistence that applies payload retention to each tick's data
+///
+/// Certificates and checkpoints pass through unchanged. Uploads run in the
+/// background; a failed upload is logged and leaves only the hash.
+pub struct PayloadRetainer {
+    inner: Arc<dyn VdfPersistence>,
+    tiers: Vec<PayloadRetentionTier>,
+    archive: Option<Arc<dyn ArchiveBackend>>,
+    runtime: Handle,
+}
+
+impl PayloadRetainer {
+    /// Wrap `inner` with the retention `tiers`
+    ///
+    /// Must be called within a Tokio runtime, which runs the uploads.
+    ///
+    /// # Parameters
+    /// * `inner` - Persistence the retained data is handed to
+    /// * `tiers` - The configured [`PayloadRetentionTier`]s
+    /// * `archive` - Bucket `external` payloads are uploaded to; without
+    ///   one they keep only their hash
+    pub fn new(
+        inner: Arc<dyn VdfPersistence>,
+        tiers: Vec<PayloadRetentionTier>,
+        archive: Option<Arc<dyn ArchiveBackend>>,
+    ) -> Self {
+        Self {
+            inner,
+            tiers,
+            archive,
+            runtime: Handle::current(),
+        }
+    }
+}
+
+impl VdfPersistence for PayloadRetainer {
+    fn tick_finalized(&self, certificate: &TickCertificate) {
+        self.inner.tick_finalized(certificate);
+    }
+
+    fn tick_data(&self, data: &TickData) {
+        if self.tiers.is_empty() {
+            self.inner.tick_data(data);
+            return;
+        }
+
+        let mut data = data.clone();
+        for entry in &mut data.entries {
+            let location = match retention_for(&self.tiers, entry.data.len()) {
+                PayloadRetention::Full => continue,
+                PayloadRetention::Hash => PayloadLocation::Dropped,
+                PayloadRetention::External => match &self.archive {
+                    Some(archive) => {
+                        let key = payload_key(&entry.data_hash);
+                        let location = PayloadLocation::External(archive.uri(&key));
+                        let payload = std::mem::take(&mut entry.data);
+                        let archive = archive.clone();
+                        self.runtime.spawn(async move {
+                            if let Err(e) = archive.put(&key, payload).await {
+                                warn!("Failed to upload timestamped payload {}: {}", key, e);
+                            }
+                        });
+                        location
+                    }
+                    None => PayloadLocation::Dropped,
+                },
+            };
+            entry.data = Vec::new();
+            entry.location = location;
+        }
+        self.inner.tick_data(&data);
+    }
+
+    fn checkpoint(&self, checkpoint: &VDFCheckpoint) {
+        self.inner.checkpoint(checkpoint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use kala_common::error::KalaResult;
+    use kala_vdf::TimestampedData;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<TickData>>);
+
+    impl VdfPersistence for Recorder {
+        fn tick_finalized(&self, _certificate: &TickCertificate) {}
+
+        fn tick_data(&self, data: &TickData) {
+            self.0.lock().unwrap().push(data.clone());
+        }
+
+        fn checkpoint(&self, _checkpoint: &VDFCheckpoint) {}
+    }
+
+    #[derive(Default)]
+    struct MemoryArchive(Mutex<HashMap<String, Vec<u8>>>);
+
+    #[async_trait]
+    impl ArchiveBackend for MemoryArchive {
+        async fn put(&self, key: &str, data: Vec<u8>) -> KalaResult<()> {
+            self.0.lock().unwrap().insert(key.to_string(), data);
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> KalaResult<Option<Vec<u8>>> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        fn uri(&self, key: &str) -> String {
+            format!("memory://{}", key)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_payloads_retained_by_size() {
+        let tier = |max_bytes: Option<usize>, keep| PayloadRetentionTier { max_bytes, keep };
+        let tiers = vec![
+            tier(Some(4), PayloadRetention::Full),
+            tier(Some(8), PayloadRetention::Hash),
+            tier(Some(16), PayloadRetention::External),
+        ];
+        assert_eq!(retention_for(&tiers, 4), PayloadRetention::Full);
+        assert_eq!(retention_for(&tiers, 5), PayloadRetention::Hash);
+        assert_eq!(retention_for(&tiers, 17), PayloadRetention::Full);
+        assert_eq!(retention_for(&[], 1 << 20), PayloadRetention::Full);
+
+        let recorder = Arc::new(Recorder::default());
+        let archive = Arc::new(MemoryArchive::default());
+        let retainer = PayloadRetainer::new(recorder.clone(), tiers, Some(archive.clone()));
+        let entry = |size: usize| TimestampedData {
+            iteration: size as u64,
+            data: vec![7; size],
+            data_hash: [size as u8; 32],
+            hash_at_timestamp: [0; 32],
+            location: PayloadLocation::Inline,
+        };
+        retainer.tick_data(&TickData {
+            tick_number: 3,
+            entries: vec![entry(2), entry(6), entry(12)],
+        });
+
+        let stored = recorder.0.lock().unwrap().pop().unwrap();
+        assert_eq!(stored.entries[0].data, vec![7; 2]);
+        assert_eq!(stored.entries[0].location, PayloadLocation::Inline);
+        assert!(stored.entries[1].data.is_empty());
+        assert_eq!(stored.entries[1].location, PayloadLocation::Dropped);
+        // Hashes stay, so the tick's Merkle leaves are unchanged
+        assert_eq!(stored.entries[2].data_hash, [12; 32]);
+        let key = payload_key(&[12; 32]);
+        assert_eq!(stored.entries[2].location, PayloadLocation::External(archive.uri(&key)));
+
+        tokio::task::yield_now().await;
+        assert_eq!(archive.get(&key).await.unwrap(), Some(vec![7; 12]));
+    }
+}
@@ -0,0 +1,930 @@
+//! Executable specification of the tick rules in the Kala paper
+//!
+//! The paper's rules for a tick are written out here as tables and direct
+//! transcriptions, independently of how [`TickProcessor`] implements them.
+//! The tests run a library of canonical scenarios through the processor and
+//! the chain state and check the outcome against what the rules predict, so
+//! a divergence between the code and the paper fails CI. Property tests
+//! do the same for generated transaction sequences, and check that supply
+//! and nonces evolve soundly over several ticks.
+//!
+//! 1. **Phases** ([`PHASES`]): collection spans the first third of the
+//!    tick, ordering is fixed as it ends, decryption spans the second third
+//!    and validation the last.
+//! 2. **Timestamping** ([`input_schedule`]): an envelope arriving during
+//!    collection is hashed into the VDF at its arrival iteration, at most one
+//!    per iteration. The ordering commitment is taken in the first step after
+//!    collection, the root of the applied transactions in the first step
+//!    after decryption.
+//! 3. **Ordering** ([`canonical_order`]): envelopes execute in order of
+//!    their agreed arrival, or their local arrival without an agreement, with
+//!    ties broken by envelope hash. The order is fixed before decryption.
+//! 4. **Decryption**: envelopes that can't be opened are dropped, and the
+//!    rest keep their position.
+//! 5. **Validation** ([`SpecLedger::validate`]): transactions the sender
+//!    didn't sign are rejected, and of those sharing a sender and nonce only
+//!    the highest tip is considered. In order, a transaction whose nonce is
+//!    ahead of its sender's waits for the gap to fill; otherwise it applies if
+//!    its nonce is the next one and the sender covers the amount, fee and tip.
+//! 6. **Tick types** ([`tick_type`]): a tick with envelopes none of which
+//!    has an agreed arrival is partial. None of its envelopes are opened,
+//!    and the empty order is committed in place of theirs. A tick that
+//!    applies no transactions is empty. Either kind still runs its k
+//!    iterations on the same schedule.
+//!
+//! [`TickProcessor`]: crate::consensus::TickProcessor
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+use kala_state::{CanonicalTimestamp, TickType};
+
+/// A phase of a tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Envelopes are timestamped as they arrive
+    Collection,
+    /// The execution order is committed
+    Ordering,
+    /// Envelopes are opened
+    Decryption,
+    /// Transactions are validated and applied
+    Validation,
+}
+
+/// Each phase with the thirds of the tick it starts and ends at
+///
+/// Ordering takes no time of its own.
+pub const PHASES: [(Phase, u64, u64); 4] = [
+    (Phase::Collection, 0, 1),
+    (Phase::Ordering, 1, 1),
+    (Phase::Decryption, 1, 2),
+    (Phase::Validation, 2, 3),
+];
+
+/// Iterations into a tick of `k` iterations that `phase` spans
+pub fn phase_span(phase: Phase, k: u64) -> Range<u64> {
+    let (_, start, end) = PHASES
+        .iter()
+        .find(|(entry, _, _)| *entry == phase)
+        .copied()
+        .expect("every phase is in the table");
+    start * k / 3..end * k / 3
+}
+
+/// Data the VDF hash chain takes in during a tick
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VdfInput {
+    /// An envelope, by hash, timestamped on arrival
+    Envelope([u8; 32]),
+    /// The commitment to the execution order
+    Ordering,
+    /// The Merkle root of the applied transactions
+    TransactionRoot,
+}
+
+/// The iterations at which a tick's VDF takes in data
+///
+/// Iteration `i` is the step from `i - 1` to `i`; all other steps take in
+/// nothing.
+///
+/// # Parameters
+/// * `tick_num` - The tick
+/// * `k` - Iterations per tick
+/// * `arrivals` - The tick's envelopes with their local arrival iterations,
+///   in the order they were received
+///
+/// # Returns
+/// The inputs by iteration, in iteration order
+pub fn input_schedule(tick_num: u64, k: u64, arrivals: &[([u8; 32], u64)]) -> Vec<(u64, VdfInput)> {
+    let start = tick_num * k;
+    let collection = phase_span(Phase::Collection, k);
+    let decryption = phase_span(Phase::Decryption, k);
+
+    let mut schedule = Vec::new();
+    for iteration in start + collection.start + 1..=start + collection.end {
+        let arrival = arrivals.iter().find(|(_, arrival)| *arrival == iteration);
+        if let Some((envelope_hash, _)) = arrival {
+            schedule.push((iteration, VdfInput::Envelope(*envelope_hash)));
+        }
+    }
+    schedule.push((start + collection.end + 1, VdfInput::Ordering));
+    schedule.push((start + decryption.end + 1, VdfInput::TransactionRoot));
+    schedule
+}
+
+/// The execution order of a tick's envelopes
+///
+/// # Parameters
+/// * `arrivals` - Envelope hashes with their local arrival iterations
+/// * `canonical` - Arrival iterations the witnesses agreed on
+///
+/// # Returns
+/// The envelope hashes in execution order
+pub fn canonical_order(
+    arrivals: &[([u8; 32], u64)],
+    canonical: &[CanonicalTimestamp],
+) -> Vec<[u8; 32]> {
+    let mut keyed: Vec<(u64, [u8; 32])> = arrivals
+        .iter()
+        .map(|(envelope_hash, arrival)| {
+            let agreed = canonical
+                .iter()
+                .find(|timestamp| timestamp.envelope_hash == *envelope_hash)
+                .map(|timestamp| timestamp.canonical_iteration);
+            (agreed.unwrap_or(*arrival), *envelope_hash)
+        })
+        .collect();
+    keyed.sort();
+    keyed.into_iter().map(|(_, envelope_hash)| envelope_hash).collect()
+}
+
+/// The type of a tick's certificate
+///
+/// # Parameters
+/// * `envelopes` - Number of envelopes targeting the tick
+/// * `applied` - Number of transactions the tick applied
+/// * `agreed` - Number of the envelopes with an agreed arrival
+///
+/// # Returns
+/// [`TickType::Checkpoint`] for a partial tick, [`TickType::Empty`] for
+/// one that applied nothing, and [`TickType::Full`] otherwise
+pub fn tick_type(envelopes: usize, applied: usize, agreed: usize) -> TickType {
+    if envelopes > 0 && agreed == 0 {
+        TickType::Checkpoint
+    } else if applied == 0 {
+        TickType::Empty
+    } else {
+        TickType::Full
+    }
+}
+
+/// What a tick does with an envelope
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Applied to the chain state
+    Applied,
+    /// Dropped unopened by a partial tick
+    Dropped,
+    /// Waiting for the sender's earlier nonces
+    Held,
+    /// Failed validation
+    Rejected,
+    /// Could not be opened
+    Undecryptable,
+}
+
+/// A decrypted transfer, as the validation rules see it
+#[derive(Clone, Debug)]
+pub struct SpecTransfer {
+    /// Hash of the envelope it arrived in
+    pub envelope_hash: [u8; 32],
+    /// Sending account
+    pub sender: [u8; 32],
+    /// Receiving account
+    pub receiver: [u8; 32],
+    /// Amount transferred
+    pub amount: u64,
+    /// Sender's nonce
+    pub nonce: u64,
+    /// Tip burned on top of the fee
+    pub tip: u64,
+    /// Whether the sender's signature is valid
+    pub signed: bool,
+}
+
+/// Balances and nonces, as the validation rules see them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpecLedger {
+    /// Balance and nonce of each account
+    pub accounts: BTreeMap<[u8; 32], (u64, u64)>,
+    /// Fee burned by every transaction
+    pub fee: u64,
+}
+
+impl SpecLedger {
+    /// Balance of an account
+    pub fn balance(&self, address: &[u8; 32]) -> u64 {
+        self.accounts.get(address).map_or(0, |(balance, _)| *balance)
+    }
+
+    /// Nonce of an account's latest transaction
+    pub fn nonce(&self, address: &[u8; 32]) -> u64 {
+        self.accounts.get(address).map_or(0, |(_, nonce)| *nonce)
+    }
+
+    /// Run the validation phase over decrypted transfers
+    ///
+    /// # Parameters
+    /// * `transfers` - The tick's decrypted transfers, in execution order
+    ///
+    /// # Returns
+    /// The verdict on each transfer by envelope hash, and the applied
+    /// transfers' envelope hashes in the order they were applied
+    pub fn validate(
+        &mut self,
+        transfers: &[SpecTransfer],
+    ) -> (HashMap<[u8; 32], Verdict>, Vec<[u8; 32]>) {
+        let mut verdicts = HashMap::new();
+        let mut applied = Vec::new();
+
+        // Of signed transfers sharing a sender and nonce, the first with the highest tip
+        let signed: Vec<&SpecTransfer> =
+            transfers.iter().filter(|transfer| transfer.signed).collect();
+        let mut best: HashMap<([u8; 32], u64), &SpecTransfer> = HashMap::new();
+        for transfer in &signed {
+            let entry = best.entry((transfer.sender, transfer.nonce)).or_insert(transfer);
+            if transfer.tip > entry.tip {
+                *entry = transfer;
+            }
+        }
+        for transfer in transfers {
+            verdicts.insert(transfer.envelope_hash, Verdict::Rejected);
+        }
+
+        let mut held: Vec<&SpecTransfer> = Vec::new();
+        for transfer in signed {
+            if best[&(transfer.sender, transfer.nonce)].envelope_hash != transfer.envelope_hash {
+                continue;
+            }
+            if transfer.nonce > self.nonce(&transfer.sender) + 1 {
+                verdicts.insert(transfer.envelope_hash, Verdict::Held);
+                held.push(transfer);
+                continue;
+            }
+
+            // Applying a transfer releases the sender's next held one
+            let mut next = Some(transfer);
+            while let Some(transfer) = next.take() {
+                if !self.apply(transfer) {
+                    verdicts.insert(transfer.envelope_hash, Verdict::Rejected);
+                    continue;
+                }
+                verdicts.insert(transfer.envelope_hash, Verdict::Applied);
+                applied.push(transfer.envelope_hash);
+                if let Some(index) = held.iter().position(|waiting| {
+                    waiting.sender == transfer.sender && waiting.nonce == transfer.nonce + 1
+                }) {
+                    next = Some(held.remove(index));
+                }
+            }
+        }
+        (verdicts, applied)
+    }
+
+    /// Apply a transfer if its nonce is next and the sender can pay for it
+    fn apply(&mut self, transfer: &SpecTransfer) -> bool {
+        let cost = transfer.amount + self.fee + transfer.tip;
+        if transfer.nonce <= self.nonce(&transfer.sender) || self.balance(&transfer.sender) < cost {
+            return false;
+        }
+        let sender = self.accounts.entry(transfer.sender).or_default();
+        sender.0 -= cost;
+        sender.1 = transfer.nonce;
+        self.accounts.entry(transfer.receiver).or_default().0 += transfer.amount;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::TickProcessor;
+    use crate::key_shares::WitnessKeyShares;
+    use crate::trace::TransactionTracer;
+    use ed25519_dalek::SigningKey;
+    use kala_common::crypto::signer::{InMemorySigner, SignatureScheme, Signer};
+    use kala_common::types::consensus::DEFAULT_CHAIN_ID;
+    use kala_common::vdf::VdfEngine;
+    use kala_rpc::TraceStage;
+    use kala_state::{ChainState, Witness, WitnessSet};
+    use kala_transaction::{seal_threshold_transaction, Send, TimelockTransaction, Transaction};
+    use kala_transaction::strategy;
+    use kala_vdf::MockVdf;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    /// Seed of the mock VDF the scenarios run on
+    const SEED: u64 = 1;
+    /// Iterations per tick, so collection ends at 10 and decryption at 20
+    const K: u64 = 30;
+
+    /// A transfer sealed into an envelope, and what the paper says becomes of it
+    #[derive(Clone, Copy)]
+    struct Envelope {
+        sender: u8,
+        receiver: u8,
+        amount: u64,
+        nonce: u64,
+        tip: u64,
+        arrival: u64,
+        agreed: Option<u64>,
+        signed: bool,
+        openable: bool,
+        expect: Verdict,
+    }
+
+    fn send(
+        sender: u8,
+        receiver: u8,
+        amount: u64,
+        nonce: u64,
+        arrival: u64,
+        expect: Verdict,
+    ) -> Envelope {
+        Envelope {
+            sender,
+            receiver,
+            amount,
+            nonce,
+            tip: 0,
+            arrival,
+            agreed: Some(arrival),
+            signed: true,
+            openable: true,
+            expect,
+        }
+    }
+
+    impl Envelope {
+        fn tip(self, tip: u64) -> Self {
+            Self { tip, ..self }
+        }
+
+        fn agreed(self, iteration: u64) -> Self {
+            Self {
+                agreed: Some(iteration),
+                ..self
+            }
+        }
+
+        fn unagreed(self) -> Self {
+            Self { agreed: None, ..self }
+        }
+
+        fn unsigned(self) -> Self {
+            Self { signed: false, ..self }
+        }
+
+        fn unopenable(self) -> Self {
+            Self {
+                openable: false,
+                ..self
+            }
+        }
+    }
+
+    struct Scenario {
+        name: &'static str,
+        /// Opening balance and nonce of each account
+        accounts: Vec<(u8, u64, u64)>,
+        /// Envelopes in the order they were received
+        envelopes: Vec<Envelope>,
+    }
+
+    fn scenarios() -> Vec<Scenario> {
+        use Verdict::*;
+        vec![
+            Scenario {
+                name: "empty tick",
+                accounts: vec![],
+                envelopes: vec![],
+            },
+            Scenario {
+                name: "envelopes without an agreed arrival make a partial tick",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![
+                    send(1, 2, 40, 1, 3, Dropped).unagreed(),
+                    send(1, 2, 10, 2, 5, Dropped).unagreed(),
+                ],
+            },
+            Scenario {
+                name: "nothing to agree on is not partial",
+                accounts: vec![],
+                envelopes: vec![],
+            },
+            Scenario {
+                name: "single transfer",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![send(1, 2, 40, 1, 3, Applied)],
+            },
+            Scenario {
+                name: "later nonce arriving first waits for the earlier one",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![send(1, 2, 10, 1, 5, Applied), send(1, 2, 10, 2, 2, Applied)],
+            },
+            Scenario {
+                name: "agreed arrival overrides local arrival",
+                accounts: vec![(1, 100, 0), (2, 0, 0)],
+                envelopes: vec![
+                    send(2, 3, 30, 1, 3, Applied).agreed(9),
+                    send(1, 2, 40, 1, 8, Applied),
+                ],
+            },
+            Scenario {
+                name: "local arrival orders without an agreement",
+                accounts: vec![(1, 100, 0), (2, 0, 0)],
+                envelopes: vec![
+                    send(2, 3, 30, 1, 3, Rejected).unagreed(),
+                    send(1, 2, 40, 1, 8, Applied),
+                ],
+            },
+            Scenario {
+                name: "same arrival iteration",
+                accounts: vec![(1, 100, 0), (2, 100, 0)],
+                envelopes: vec![send(1, 3, 10, 1, 4, Applied), send(2, 3, 10, 1, 4, Applied)],
+            },
+            Scenario {
+                name: "arrival after collection",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![send(1, 2, 10, 1, 15, Applied)],
+            },
+            Scenario {
+                name: "stale nonce",
+                accounts: vec![(1, 100, 3)],
+                envelopes: vec![send(1, 2, 10, 3, 2, Rejected), send(1, 2, 10, 4, 6, Applied)],
+            },
+            Scenario {
+                name: "insufficient balance",
+                accounts: vec![(1, 10, 0)],
+                envelopes: vec![send(1, 2, 40, 1, 2, Rejected)],
+            },
+            Scenario {
+                name: "tip counts against the balance",
+                accounts: vec![(1, 50, 0)],
+                envelopes: vec![send(1, 2, 45, 1, 2, Rejected).tip(10)],
+            },
+            Scenario {
+                name: "invalid signature",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![send(1, 2, 10, 1, 2, Rejected).unsigned()],
+            },
+            Scenario {
+                name: "higher tip replaces a nonce",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![
+                    send(1, 2, 10, 1, 2, Rejected),
+                    send(1, 3, 10, 1, 4, Applied).tip(5),
+                ],
+            },
+            Scenario {
+                name: "nonce gap held",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![send(1, 2, 10, 2, 2, Held)],
+            },
+            Scenario {
+                name: "undecryptable envelope leaves a gap",
+                accounts: vec![(1, 100, 0)],
+                envelopes: vec![
+                    send(1, 2, 10, 1, 2, Undecryptable).unopenable(),
+                    send(1, 2, 10, 2, 5, Held),
+                ],
+            },
+        ]
+    }
+
+    fn key(index: u8) -> SigningKey {
+        SigningKey::from_bytes(&[index; 32])
+    }
+
+    fn address(index: u8) -> [u8; 32] {
+        key(index).verifying_key().to_bytes()
+    }
+
+    fn transaction(envelope: &Envelope) -> Transaction {
+        let mut tx = Transaction::Send(Send {
+            sender: address(envelope.sender),
+            receiver: address(envelope.receiver),
+            denom: [0u8; 32],
+            amount: envelope.amount,
+            nonce: envelope.nonce,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: envelope.tip,
+        });
+        if envelope.signed {
+            tx.sign(&key(envelope.sender), DEFAULT_CHAIN_ID).unwrap();
+        }
+        tx
+    }
+
+    /// What the processor did with an envelope, from its trace
+    fn traced_verdict(tracer: &TransactionTracer, envelope_hash: &[u8; 32]) -> Option<Verdict> {
+        let trace = tracer.get(envelope_hash)?;
+        trace.events.iter().rev().find_map(|event| match event.stage {
+            TraceStage::Applied | TraceStage::Included => Some(Verdict::Applied),
+            TraceStage::Dropped => Some(Verdict::Dropped),
+            TraceStage::Held => Some(Verdict::Held),
+            TraceStage::Rejected => Some(Verdict::Rejected),
+            TraceStage::DecryptionFailed => Some(Verdict::Undecryptable),
+            _ => None,
+        })
+    }
+
+    async fn check(scenario: &Scenario) {
+        let name = scenario.name;
+        let signer: Arc<dyn Signer> = Arc::new(InMemorySigner::from_seed(&[100u8; 32]).unwrap());
+        let witness_key: [u8; 32] =
+            signer.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+        // A witness that never reveals, holding the second share of unopenable envelopes
+        let absent = InMemorySigner::from_seed(&[101u8; 32]).unwrap();
+        let absent_key: [u8; 32] = absent.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+        let witnesses = WitnessSet::new(vec![Witness {
+            key: witness_key,
+            bls_key: signer.public_key(SignatureScheme::Bls12381),
+            weight: 1,
+        }])
+        .unwrap();
+
+        let mut state = ChainState::new();
+        let mut ledger = SpecLedger {
+            fee: state.transaction_fee(),
+            ..SpecLedger::default()
+        };
+        for &(index, balance, nonce) in &scenario.accounts {
+            state.mint(&address(index), balance).unwrap();
+            state.update_nonce(&address(index), nonce);
+            ledger.accounts.insert(address(index), (balance, nonce));
+        }
+
+        let tracer = Arc::new(TransactionTracer::default());
+        let mut sealed: Vec<TimelockTransaction> = Vec::new();
+        let mut transactions = HashMap::new();
+        let mut canonical = Vec::new();
+        for envelope in &scenario.envelopes {
+            let tx = transaction(envelope);
+            let (holders, threshold) = match envelope.openable {
+                true => (vec![witness_key], 1),
+                false => (vec![witness_key, absent_key], 2),
+            };
+            let timelock = seal_threshold_transaction(&tx, 0, envelope.arrival, &holders, threshold)
+                .unwrap();
+            let envelope_hash = timelock.envelope_hash();
+            tracer.start(envelope_hash, 0, envelope.arrival, TraceStage::Submitted);
+            if let Some(iteration) = envelope.agreed {
+                canonical.push(CanonicalTimestamp {
+                    envelope_hash,
+                    tick_number: 0,
+                    canonical_iteration: iteration,
+                    observed_weight: 1,
+                    observations: 1,
+                });
+            }
+            transactions.insert(envelope_hash, tx);
+            sealed.push(timelock);
+        }
+        let arrivals: Vec<([u8; 32], u64)> = sealed
+            .iter()
+            .map(|timelock| (timelock.envelope_hash(), timelock.submission_iteration))
+            .collect();
+
+        // Ordering
+        let order = canonical_order(&arrivals, &canonical);
+        let ordered = TickProcessor::order_transactions(sealed.clone(), &canonical);
+        let processor_order: Vec<[u8; 32]> = ordered.iter().map(|tx| tx.envelope_hash()).collect();
+        assert_eq!(processor_order, order, "{}: order", name);
+
+        // A partial tick commits to no order, and opens nothing
+        let partial = matches!(tick_type(arrivals.len(), 0, canonical.len()), TickType::Checkpoint);
+        let committed = match partial {
+            true => Vec::new(),
+            false => ordered.clone(),
+        };
+
+        // Decryption and validation, by the rules
+        let transfers: Vec<SpecTransfer> = order
+            .iter()
+            .filter(|_| !partial)
+            .filter_map(|envelope_hash| {
+                let index = arrivals.iter().position(|(hash, _)| hash == envelope_hash)?;
+                let envelope = &scenario.envelopes[index];
+                envelope.openable.then(|| SpecTransfer {
+                    envelope_hash: *envelope_hash,
+                    sender: address(envelope.sender),
+                    receiver: address(envelope.receiver),
+                    amount: envelope.amount,
+                    nonce: envelope.nonce,
+                    tip: envelope.tip,
+                    signed: envelope.signed,
+                })
+            })
+            .collect();
+        let (mut verdicts, applied) = ledger.validate(&transfers);
+        for (envelope_hash, _) in &arrivals {
+            let unopened = if partial { Verdict::Dropped } else { Verdict::Undecryptable };
+            verdicts.entry(*envelope_hash).or_insert(unopened);
+        }
+
+        // The scenario's expectations hold by the rules
+        for (envelope, (envelope_hash, _)) in scenario.envelopes.iter().zip(&arrivals) {
+            assert_eq!(verdicts[envelope_hash], envelope.expect, "{}: rules", name);
+        }
+
+        // The processor and chain state conform
+        let vdf = Arc::new(RwLock::new(MockVdf::new(SEED, K)));
+        let shared_state = Arc::new(RwLock::new(state));
+        let processor = TickProcessor::new(K)
+            .with_tracer(tracer.clone())
+            .with_key_shares(Arc::new(WitnessKeyShares::new(signer, witness_key)));
+        let outcome = processor
+            .process_tick(
+                0,
+                vdf.clone(),
+                shared_state.clone(),
+                sealed.clone(),
+                &canonical,
+                &witnesses,
+            )
+            .await
+            .unwrap();
+
+        for (envelope, (envelope_hash, _)) in scenario.envelopes.iter().zip(&arrivals) {
+            let traced = traced_verdict(&tracer, envelope_hash);
+            assert_eq!(traced, Some(envelope.expect), "{}: processor", name);
+        }
+        let processor_applied: Vec<[u8; 32]> =
+            outcome.applied.iter().map(|tx| tx.envelope_hash).collect();
+        assert_eq!(processor_applied, applied, "{}: applied", name);
+
+        let state = shared_state.read().await;
+        for (address, (balance, nonce)) in &ledger.accounts {
+            assert_eq!(state.get_balance(address), *balance, "{}: balance", name);
+            assert_eq!(state.get_account_nonce(address).unwrap_or(0), *nonce, "{}: nonce", name);
+        }
+        assert_eq!(state.current_tick, 1, "{}: tick", name);
+
+        // The VDF took in the scheduled data at the scheduled iterations
+        let applied_txs: Vec<Transaction> =
+            applied.iter().map(|envelope_hash| transactions[envelope_hash].clone()).collect();
+        let schedule = input_schedule(0, K, &arrivals);
+        let mut reference = MockVdf::new(SEED, K);
+        for iteration in 1..=K {
+            let input = schedule
+                .iter()
+                .find(|(scheduled, _)| *scheduled == iteration)
+                .map(|(_, input)| match input {
+                    VdfInput::Envelope(envelope_hash) => {
+                        let index = arrivals.iter().position(|(hash, _)| hash == envelope_hash);
+                        TickProcessor::serialize_timelock_tx(&sealed[index.unwrap()])
+                    }
+                    VdfInput::Ordering => TickProcessor::create_ordering_commitment(&committed),
+                    VdfInput::TransactionRoot => {
+                        TickProcessor::compute_transaction_merkle_root(&applied_txs).to_vec()
+                    }
+                });
+            reference.step(input);
+        }
+        let vdf = vdf.read().await;
+        assert_eq!(vdf.iteration(), K, "{}: iterations", name);
+        assert_eq!(vdf.hash_chain(), reference.hash_chain(), "{}: hash chain", name);
+        assert_eq!(outcome.certificate.vdf_iteration, K, "{}: certificate", name);
+        assert_eq!(
+            outcome.certificate.transaction_merkle_root,
+            TickProcessor::compute_transaction_merkle_root(&applied_txs),
+            "{}: transaction root",
+            name
+        );
+        let expected = tick_type(arrivals.len(), applied.len(), canonical.len());
+        assert_eq!(
+            outcome.certificate.tick_type.canonical_id(),
+            expected.canonical_id(),
+            "{}: tick type",
+            name
+        );
+    }
+
+    /// A witness processing ticks over a mock VDF, for the property tests
+    struct Simulation {
+        processor: TickProcessor,
+        vdf: Arc<RwLock<MockVdf>>,
+        state: Arc<RwLock<ChainState>>,
+        witness_key: [u8; 32],
+        witnesses: WitnessSet,
+        tick: u64,
+    }
+
+    impl Simulation {
+        /// Start from opening balances of the strategy's accounts
+        fn new(balances: &[u64]) -> Self {
+            let signer: Arc<dyn Signer> =
+                Arc::new(InMemorySigner::from_seed(&[100u8; 32]).unwrap());
+            let witness_key: [u8; 32] =
+                signer.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+            let witnesses = WitnessSet::new(vec![Witness {
+                key: witness_key,
+                bls_key: signer.public_key(SignatureScheme::Bls12381),
+                weight: 1,
+            }])
+            .unwrap();
+            let mut state = ChainState::new();
+            // The strategy mints from any of its accounts
+            state.set_open_mint(true);
+            for (index, balance) in balances.iter().enumerate() {
+                state.mint(&strategy::account(index as u8), *balance).unwrap();
+            }
+            Self {
+                processor: TickProcessor::new(K)
+                    .with_key_shares(Arc::new(WitnessKeyShares::new(signer, witness_key))),
+                vdf: Arc::new(RwLock::new(MockVdf::new(SEED, K))),
+                state: Arc::new(RwLock::new(state)),
+                witness_key,
+                witnesses,
+                tick: 0,
+            }
+        }
+
+        /// Seal `txs` for the next tick, arriving at their offsets into it
+        fn seal(&self, txs: &[(Transaction, u64)]) -> Vec<TimelockTransaction> {
+            txs.iter()
+                .map(|(tx, offset)| {
+                    let arrival = self.tick * K + offset;
+                    seal_threshold_transaction(tx, self.tick, arrival, &[self.witness_key], 1)
+                        .unwrap()
+                })
+                .collect()
+        }
+
+        /// Process the next tick, returning the envelope hashes applied in order
+        async fn run_tick(&mut self, sealed: Vec<TimelockTransaction>) -> Vec<[u8; 32]> {
+            // The witness agrees every envelope arrived when it did
+            let canonical: Vec<CanonicalTimestamp> = sealed
+                .iter()
+                .map(|timelock| CanonicalTimestamp {
+                    envelope_hash: timelock.envelope_hash(),
+                    tick_number: self.tick,
+                    canonical_iteration: timelock.submission_iteration,
+                    observed_weight: 1,
+                    observations: 1,
+                })
+                .collect();
+            let outcome = self
+                .processor
+                .process_tick(
+                    self.tick,
+                    self.vdf.clone(),
+                    self.state.clone(),
+                    sealed,
+                    &canonical,
+                    &self.witnesses,
+                )
+                .await
+                .unwrap();
+            self.tick += 1;
+            outcome.applied.iter().map(|tx| tx.envelope_hash).collect()
+        }
+    }
+
+    /// Transactions spread over up to three ticks, with their arrival offsets
+    fn simulated_ticks() -> impl Strategy<Value = Vec<Vec<(Transaction, u64)>>> {
+        let txs = strategy::sequence(strategy::transaction(), 0..24, DEFAULT_CHAIN_ID);
+        (txs, vec(1..=10u64, 24), 1..4usize).prop_map(|(txs, offsets, ticks)| {
+            let per_tick = txs.len().div_ceil(ticks).max(1);
+            let arriving: Vec<(Transaction, u64)> = txs.into_iter().zip(offsets).collect();
+            let mut ticks: Vec<Vec<(Transaction, u64)>> =
+                arriving.chunks(per_tick).map(<[_]>::to_vec).collect();
+            ticks.resize(ticks.len().max(1), Vec::new());
+            ticks
+        })
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap()
+    }
+
+    #[test]
+    fn test_phase_table() {
+        assert_eq!(phase_span(Phase::Collection, 65536), 0..21845);
+        assert_eq!(phase_span(Phase::Ordering, 65536), 21845..21845);
+        assert_eq!(phase_span(Phase::Decryption, 65536), 21845..43690);
+        assert_eq!(phase_span(Phase::Validation, 65536), 43690..65536);
+
+        // The phases tile the tick in order
+        let mut end = 0;
+        for (phase, _, _) in PHASES {
+            let span = phase_span(phase, K);
+            assert_eq!(span.start, end);
+            end = span.end;
+        }
+        assert_eq!(end, K);
+    }
+
+    #[tokio::test]
+    async fn test_processor_conforms_to_spec() {
+        for scenario in scenarios() {
+            check(&scenario).await;
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        /// Transfers of a tick apply as the rules predict
+        #[test]
+        fn test_transfers_match_rules(
+            balances in vec(0..2_000u64, strategy::ACCOUNTS as usize),
+            txs in strategy::sequence(strategy::send(), 0..16, DEFAULT_CHAIN_ID),
+            offsets in vec(1..=10u64, 16),
+        ) {
+            let arriving: Vec<(Transaction, u64)> = txs.into_iter().zip(offsets).collect();
+            let mut simulation = Simulation::new(&balances);
+            let sealed = simulation.seal(&arriving);
+            let hashes: Vec<[u8; 32]> = sealed.iter().map(|tx| tx.envelope_hash()).collect();
+            let applied = runtime().block_on(simulation.run_tick(sealed));
+
+            let mut ledger = SpecLedger {
+                fee: ChainState::new().transaction_fee(),
+                ..SpecLedger::default()
+            };
+            for (index, balance) in balances.iter().enumerate() {
+                ledger.accounts.insert(strategy::account(index as u8), (*balance, 0));
+            }
+            let arrivals: Vec<([u8; 32], u64)> = hashes
+                .iter()
+                .zip(&arriving)
+                .map(|(envelope_hash, (_, offset))| (*envelope_hash, *offset))
+                .collect();
+            let transfers: Vec<SpecTransfer> = canonical_order(&arrivals, &[])
+                .into_iter()
+                .map(|envelope_hash| {
+                    let index = hashes.iter().position(|hash| *hash == envelope_hash).unwrap();
+                    let Transaction::Send(send) = &arriving[index].0 else {
+                        unreachable!()
+                    };
+                    SpecTransfer {
+                        envelope_hash,
+                        sender: send.sender,
+                        receiver: send.receiver,
+                        amount: send.amount,
+                        nonce: send.nonce,
+                        tip: send.tip,
+                        signed: true,
+                    }
+                })
+                .collect();
+            let (_, expected) = ledger.validate(&transfers);
+            prop_assert_eq!(applied, expected);
+
+            let state = runtime().block_on(simulation.state.read()).clone();
+            for (address, (balance, nonce)) in &ledger.accounts {
+                prop_assert_eq!(state.get_balance(address), *balance);
+                prop_assert_eq!(state.get_account_nonce(address).unwrap_or(0), *nonce);
+            }
+        }
+
+        /// Supply changes only by what is minted and burned in fees, nonces
+        /// only grow, and replaying the ticks reaches the same state
+        #[test]
+        fn test_state_transitions_conserve_supply(
+            balances in vec(0..2_000u64, strategy::ACCOUNTS as usize),
+            ticks in simulated_ticks(),
+        ) {
+            let runtime = runtime();
+            let mut simulation = Simulation::new(&balances);
+            let mut replay = Simulation::new(&balances);
+            let mut sent = HashMap::new();
+            for txs in &ticks {
+                let sealed = simulation.seal(txs);
+                for (timelock, (tx, _)) in sealed.iter().zip(txs) {
+                    sent.insert(timelock.envelope_hash(), tx.clone());
+                }
+                let before = runtime.block_on(simulation.state.read()).clone();
+                let applied = runtime.block_on(simulation.run_tick(sealed.clone()));
+                let after = runtime.block_on(simulation.state.read()).clone();
+
+                let mut minted = 0u64;
+                let mut burned = 0u64;
+                let mut nonces: HashMap<[u8; 32], u64> = HashMap::new();
+                for envelope_hash in &applied {
+                    let tx = &sent[envelope_hash];
+                    if let Transaction::Mint(mint) = tx {
+                        minted += mint.amount;
+                    }
+                    burned += before.transaction_fee() + tx.tip();
+                    let last = nonces
+                        .get(tx.sender())
+                        .copied()
+                        .unwrap_or_else(|| before.get_account_nonce(tx.sender()).unwrap_or(0));
+                    prop_assert!(tx.nonce() > last, "nonce {} applied after {}", tx.nonce(), last);
+                    nonces.insert(*tx.sender(), tx.nonce());
+                }
+                prop_assert_eq!(after.total_supply(), before.total_supply() + minted - burned);
+                for index in 0..strategy::ACCOUNTS {
+                    let address = strategy::account(index);
+                    let nonce = after.get_account_nonce(&address).unwrap_or(0);
+                    prop_assert!(nonce >= before.get_account_nonce(&address).unwrap_or(0));
+                    prop_assert_eq!(nonce, nonces.get(&address).copied().unwrap_or(nonce));
+                }
+
+                prop_assert_eq!(runtime.block_on(replay.run_tick(sealed)), applied);
+                let root = runtime.block_on(replay.state.read()).state_root();
+                prop_assert_eq!(root, after.state_root());
+            }
+            let chain = runtime.block_on(simulation.vdf.read()).hash_chain();
+            prop_assert_eq!(runtime.block_on(replay.vdf.read()).hash_chain(), chain);
+        }
+    }
+}
@@ -0,0 +1,1394 @@
+//! Tick sync for nodes catching up with the network
+//!
+//! A node that starts behind its peers cannot recompute their timeline: the
+//! tick contents depend on transactions it never saw. Instead it asks a peer
+//! for a state snapshot and the tick certificates leading up to it, checks
+//! that the certificates form an unbroken chain from its own head, and
+//! re-runs each tick's VDF segment to confirm the outputs. Segments are
+//! independent given their start form, so they are verified in parallel on
+//! a [`VerifierPool`] and the node catches up faster than the timeline
+//! advances.
+//!
+//! Snapshots are [`ProvenSnapshot`]s: the state a peer sealed its latest
+//! epoch with, carrying the signed epoch summary. The summary's tick
+//! accumulator root must match the ticks the node verified itself, and its
+//! state root must match the snapshot, so a peer cannot hand over accounts
+//! that were never committed to the timeline.
+//!
+//! Epoch summaries covering the downloaded ticks are fetched afterwards,
+//! so the node can keep sealing epochs where its peers left off.
+//!
+//! A node with an empty database can instead start from a
+//! [`TrustedCheckpoint`] configured by its operator. The checkpoint tick,
+//! the summary of the epoch it ends, and the tick accumulator peaks at
+//! that point are checked against the checkpoint, and sync continues from
+//! there. The ticks before the checkpoint can be backfilled later with
+//! [`TickSync::backfill`].
+//!
+//! Once the node is within [`FOLLOW_DISTANCE`] ticks of its best peer it
+//! switches from [`SyncMode::Syncing`] to [`SyncMode::Following`] and
+//! produces ticks itself. Progress is reported through `kala_syncStatus`.
+
+use anyhow::{anyhow, bail, Result};
+use libp2p::PeerId;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::network::NetworkHandle;
+use kala_rpc::{SyncMode, SyncStatus};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::mmr::bag_peaks;
+use kala_common::proto::{self, fixed, fixed_list, required, sync_request, sync_response, ProtoCodec};
+use kala_state::{ChainState, EpochSummary, SignedEpochSummary, StateDB, TickCertificate};
+use kala_vdf::{verify_form_transition, PackedForm};
+
+/// Maximum number of tick certificates returned for one request
+pub const MAX_TICKS_PER_REQUEST: u64 = 64;
+
+/// Maximum number of epoch summaries returned for one request
+pub const MAX_EPOCHS_PER_REQUEST: u64 = 64;
+
+/// A node this close to its best peer's head is considered caught up
+pub const FOLLOW_DISTANCE: u64 = 1;
+
+/// How long to wait for the first peer before giving up on syncing
+const PEER_WAIT: Duration = Duration::from_secs(10);
+
+/// Requests served over the sync protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SyncRequest {
+    /// The peer's current head
+    Status,
+    /// Up to `count` consecutive certificates starting at tick `start`
+    Ticks {
+        /// First tick to return
+        start: u64,
+        /// Number of ticks requested, capped at [`MAX_TICKS_PER_REQUEST`]
+        count: u64,
+        /// Whether to send them [`compact`](TickCertificate::compact)
+        compact: bool,
+    },
+    /// The peer's chain state at its latest sealed epoch, with its summary
+    Snapshot,
+    /// The peer's chain state at its latest tick, without a proof
+    ///
+    /// Only for branches whose ticks were verified from a local tip.
+    State,
+    /// Up to `count` consecutive signed epoch summaries starting at epoch `start`
+    Epochs {
+        /// First epoch to return
+        start: u64,
+        /// Number of epochs requested, capped at [`MAX_EPOCHS_PER_REQUEST`]
+        count: u64,
+    },
+    /// Peaks of the tick accumulator when it held `leaf_count` ticks
+    MmrPeaks {
+        /// Accumulator size the peaks are requested for
+        leaf_count: u64,
+    },
+}
+
+/// Responses to [`SyncRequest`]s
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SyncResponse {
+    /// The peer's head
+    Status {
+        /// Next tick the peer will produce
+        current_tick: u64,
+        /// Hash of the peer's latest tick
+        last_tick_hash: [u8; 32],
+    },
+    /// Consecutive certificates, possibly fewer than requested
+    Ticks(Vec<TickCertificate>),
+    /// The peer's chain state at its latest sealed epoch
+    Snapshot(Box<ProvenSnapshot>),
+    /// The peer's chain state at its latest tick
+    State(Box<ChainState>),
+    /// Consecutive epoch summaries, possibly fewer than requested
+    Epochs(Vec<SignedEpochSummary>),
+    /// Tick accumulator peaks, left to right
+    MmrPeaks(Vec<[u8; 32]>),
+    /// The request could not be served
+    Error(String),
+}
+
+impl fmt::Debug for SyncResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status { current_tick, .. } => write!(f, "Status(current_tick={})", current_tick),
+            Self::Ticks(ticks) => write!(f, "Ticks({} certificates)", ticks.len()),
+            Self::Snapshot(snapshot) => write!(
+                f,
+                "Snapshot(current_tick={}, epoch={})",
+                snapshot.state.current_tick, snapshot.epoch.summary.epoch_number
+            ),
+            Self::State(state) => write!(f, "State(current_tick={})", state.current_tick),
+            Self::Epochs(epochs) => write!(f, "Epochs({} summaries)", epochs.len()),
+            Self::MmrPeaks(peaks) => write!(f, "MmrPeaks({} peaks)", peaks.len()),
+            Self::Error(e) => write!(f, "Error({})", e),
+        }
+    }
+}
+
+impl ProtoCodec for SyncRequest {
+    type Proto = proto::SyncRequest;
+
+    fn to_proto(&self) -> proto::SyncRequest {
+        use sync_request::Request;
+
+        let request = match *self {
+            Self::Status => Request::Status(proto::StatusRequest {}),
+            Self::Ticks { start, count, compact } => Request::Ticks(proto::TicksRequest {
+                start,
+                count,
+                compact,
+            }),
+            Self::Snapshot => Request::Snapshot(proto::SnapshotRequest {}),
+            Self::State => Request::State(proto::StateRequest {}),
+            Self::Epochs { start, count } => Request::Epochs(proto::EpochsRequest { start, count }),
+            Self::MmrPeaks { leaf_count } => Request::MmrPeaks(proto::MmrPeaksRequest { leaf_count }),
+        };
+        proto::SyncRequest { request: Some(request) }
+    }
+
+    fn from_proto(proto: proto::SyncRequest) -> KalaResult<Self> {
+        use sync_request::Request;
+
+        Ok(match required(proto.request, "sync request")? {
+            Request::Status(_) => Self::Status,
+            Request::Ticks(ticks) => Self::Ticks {
+                start: ticks.start,
+                count: ticks.count,
+                compact: ticks.compact,
+            },
+            Request::Snapshot(_) => Self::Snapshot,
+            Request::State(_) => Self::State,
+            Request::Epochs(epochs) => Self::Epochs {
+                start: epochs.start,
+                count: epochs.count,
+            },
+            Request::MmrPeaks(peaks) => Self::MmrPeaks {
+                leaf_count: peaks.leaf_count,
+            },
+        })
+    }
+}
+
+impl ProtoCodec for SyncResponse {
+    type Proto = proto::SyncResponse;
+
+    fn to_proto(&self) -> proto::SyncResponse {
+        use sync_response::Response;
+
+        let response = match self {
+            Self::Status { current_tick, last_tick_hash } => Response::Status(proto::StatusResponse {
+                current_tick: *current_tick,
+                last_tick_hash: last_tick_hash.to_vec(),
+            }),
+            Self::Ticks(ticks) => Response::Ticks(proto::TickCertificates {
+                ticks: ticks.iter().map(ProtoCodec::to_proto).collect(),
+            }),
+            Self::Snapshot(snapshot) => Response::Snapshot(snapshot.to_proto()),
+            Self::State(state) => Response::State(state.to_proto()),
+            Self::Epochs(epochs) => Response::Epochs(proto::EpochSummaries {
+                epochs: epochs.iter().map(ProtoCodec::to_proto).collect(),
+            }),
+            Self::MmrPeaks(peaks) => Response::MmrPeaks(proto::MmrPeaks {
+                peaks: peaks.iter().map(|peak| peak.to_vec()).collect(),
+            }),
+            Self::Error(e) => Response::Error(e.clone()),
+        };
+        proto::SyncResponse { response: Some(response) }
+    }
+
+    fn from_proto(proto: proto::SyncResponse) -> KalaResult<Self> {
+        use sync_response::Response;
+
+        Ok(match required(proto.response, "sync response")? {
+            Response::Status(status) => Self::Status {
+                current_tick: status.current_tick,
+                last_tick_hash: fixed(status.last_tick_hash, "last tick hash")?,
+            },
+            Response::Ticks(ticks) => Self::Ticks(
+                ticks
+                    .ticks
+                    .into_iter()
+                    .map(TickCertificate::from_proto)
+                    .collect::<KalaResult<_>>()?,
+            ),
+            Response::Snapshot(snapshot) => Self::Snapshot(Box::new(ProvenSnapshot::from_proto(snapshot)?)),
+            Response::State(state) => Self::State(Box::new(ChainState::from_proto(state)?)),
+            Response::Epochs(epochs) => Self::Epochs(
+                epochs
+                    .epochs
+                    .into_iter()
+                    .map(SignedEpochSummary::from_proto)
+                    .collect::<KalaResult<_>>()?,
+            ),
+            Response::MmrPeaks(peaks) => Self::MmrPeaks(fixed_list(peaks.peaks, "MMR peak")?),
+            Response::Error(e) => Self::Error(e),
+        })
+    }
+}
+
+/// Answer a sync request from the local database
+pub async fn serve_request(state_db: &StateDB, request: SyncRequest) -> SyncResponse {
+    let result = match request {
+        SyncRequest::Status => state_db.load_chain_state().await.map(|state| SyncResponse::Status {
+            current_tick: state.current_tick,
+            last_tick_hash: state.last_tick_hash,
+        }),
+        SyncRequest::Ticks { start, count, compact } => {
+            let mut ticks = Vec::new();
+            let end = start.saturating_add(count.min(MAX_TICKS_PER_REQUEST));
+            let mut result = Ok(());
+            for tick_number in start..end {
+                match state_db.get_tick(tick_number).await {
+                    Ok(Some(tick)) => ticks.push(tick),
+                    Ok(None) => break,
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+            if compact && result.is_ok() && !ticks.is_empty() {
+                result = state_db.load_chain_state().await.map(|state| {
+                    let discriminant = &state.vdf_checkpoint.discriminant;
+                    for tick in &mut ticks {
+                        *tick = tick.compact(discriminant);
+                    }
+                });
+            }
+            result.map(|_| SyncResponse::Ticks(ticks))
+        }
+        SyncRequest::Snapshot => ProvenSnapshot::load(state_db)
+            .await
+            .map(|snapshot| SyncResponse::Snapshot(Box::new(snapshot))),
+        SyncRequest::State => state_db
+            .load_chain_state()
+            .await
+            .map(|state| SyncResponse::State(Box::new(state))),
+        SyncRequest::Epochs { start, count } => {
+            let mut epochs = Vec::new();
+            let end = start.saturating_add(count.min(MAX_EPOCHS_PER_REQUEST));
+            let mut result = Ok(());
+            for epoch_number in start..end {
+                match state_db.get_epoch_summary(epoch_number).await {
+                    Ok(Some(epoch)) => epochs.push(epoch),
+                    Ok(None) => break,
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+            result.map(|_| SyncResponse::Epochs(epochs))
+        }
+        SyncRequest::MmrPeaks { leaf_count } => state_db
+            .tick_mmr_peaks(leaf_count)
+            .await
+            .map(SyncResponse::MmrPeaks),
+    };
+
+    result.unwrap_or_else(|e| SyncResponse::Error(e.to_string()))
+}
+
+/// A chain state with the signed summary of the epoch it was sealed with
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProvenSnapshot {
+    /// Chain state right after the epoch's last tick
+    pub state: ChainState,
+    /// Summary committing to the state root and the tick accumulator
+    pub epoch: SignedEpochSummary,
+}
+
+impl ProtoCodec for ProvenSnapshot {
+    type Proto = proto::ProvenSnapshot;
+
+    fn to_proto(&self) -> proto::ProvenSnapshot {
+        proto::ProvenSnapshot {
+            state: Some(self.state.to_proto()),
+            epoch: Some(self.epoch.to_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::ProvenSnapshot) -> KalaResult<Self> {
+        Ok(Self {
+            state: ChainState::from_proto(required(proto.state, "snapshot state")?)?,
+            epoch: SignedEpochSummary::from_proto(required(proto.epoch, "snapshot epoch")?)?,
+        })
+    }
+}
+
+impl ProvenSnapshot {
+    /// The latest sealed epoch state and its summary from the local database
+    pub async fn load(state_db: &StateDB) -> KalaResult<Self> {
+        let state = state_db
+            .load_epoch_state()
+            .await?
+            .ok_or_else(|| KalaError::state("No epoch has been sealed yet"))?;
+        let epoch = state_db
+            .latest_epoch_summary()
+            .await?
+            .filter(|epoch| epoch.summary.end_tick + 1 == state.current_tick)
+            .ok_or_else(|| {
+                KalaError::state(format!(
+                    "Missing summary of the epoch ending at tick {}",
+                    state.current_tick
+                ))
+            })?;
+        Ok(Self { state, epoch })
+    }
+
+    /// Check that the snapshot is anchored in a verified timeline
+    ///
+    /// `mmr_root` is the root of the local tick accumulator after the
+    /// snapshot's last tick was verified and stored.
+    pub fn check_anchor(&self, mmr_root: &[u8; 32]) -> Result<()> {
+        let summary = &self.epoch.summary;
+        if summary.end_tick + 1 != self.state.current_tick
+            || summary.end_tick_hash != self.state.last_tick_hash
+        {
+            bail!(
+                "Summary of epoch {} does not end at snapshot tick {}",
+                summary.epoch_number,
+                self.state.current_tick
+            );
+        }
+        if summary.mmr_root != *mmr_root {
+            bail!("Summary of epoch {} commits to a different timeline", summary.epoch_number);
+        }
+        if summary.state_root != self.state.state_root() {
+            bail!("Snapshot state root does not match epoch {}", summary.epoch_number);
+        }
+        Ok(())
+    }
+}
+
+/// An operator-supplied point in the timeline a fresh node syncs from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedCheckpoint {
+    /// Last tick of the checkpointed epoch
+    pub tick_number: u64,
+    /// Hash of the checkpoint tick certificate
+    pub tick_hash: [u8; 32],
+    /// State root in the summary of the epoch ending at the checkpoint
+    pub state_root: [u8; 32],
+}
+
+/// The verified tip a sync continues from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncAnchor {
+    /// Next tick expected after the anchor
+    pub next_tick: u64,
+    /// Hash of the anchor tick, zero before genesis
+    pub tick_hash: [u8; 32],
+    /// VDF form at the end of the anchor tick
+    pub vdf_form: PackedForm,
+    /// VDF iteration at the end of the anchor tick
+    pub vdf_iteration: u64,
+}
+
+impl SyncAnchor {
+    /// The head of a chain state
+    pub fn from_state(state: &ChainState) -> Self {
+        let checkpoint = &state.vdf_checkpoint;
+        Self {
+            next_tick: state.current_tick,
+            tick_hash: state.last_tick_hash,
+            vdf_form: (
+                checkpoint.form_a.clone(),
+                checkpoint.form_b.clone(),
+                checkpoint.form_c.clone(),
+            ),
+            vdf_iteration: checkpoint.iteration,
+        }
+    }
+
+    /// The head after a certificate
+    pub fn from_certificate(certificate: &TickCertificate) -> Self {
+        Self {
+            next_tick: certificate.tick_number + 1,
+            tick_hash: certificate.tick_hash,
+            vdf_form: certificate.vdf_form.clone(),
+            vdf_iteration: certificate.vdf_iteration,
+        }
+    }
+
+    /// Check that a snapshot resumes exactly at this anchor
+    pub fn check_snapshot(&self, snapshot: &ChainState) -> Result<()> {
+        let snapshot_anchor = Self::from_state(snapshot);
+        if snapshot_anchor != *self {
+            bail!(
+                "Snapshot at tick {} does not match verified tick {}",
+                snapshot.current_tick,
+                self.next_tick
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Threads VDF segments are recomputed on when verifying certificates
+///
+/// Verifying a segment takes as long as producing it did, so a node
+/// syncing a long history keeps every thread of the pool busy. Sizing the
+/// pool below the core count leaves room for the rest of the node.
+#[derive(Clone)]
+pub struct VerifierPool {
+    pool: Arc<rayon::ThreadPool>,
+}
+
+impl VerifierPool {
+    /// Start a pool of `threads` threads, or one per CPU core if 0
+    pub fn new(threads: usize) -> Result<Self> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("kala-verify-{}", index))
+            .build()
+            .map_err(|e| anyhow!("Failed to start verification threads: {}", e))?;
+        Ok(Self { pool: Arc::new(pool) })
+    }
+
+    /// Number of threads in the pool
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+}
+
+/// Verify that certificates extend `anchor`, returning the new anchor
+///
+/// Linkage, hashes, and iteration counts are checked in order. The VDF
+/// segment of every certificate is then recomputed on `verifier`.
+pub async fn verify_certificates(
+    anchor: &SyncAnchor,
+    certificates: &[TickCertificate],
+    iterations_per_tick: u64,
+    discriminant: &str,
+    verifier: &VerifierPool,
+) -> Result<SyncAnchor> {
+    let mut current = anchor.clone();
+    let mut segments = Vec::with_capacity(certificates.len());
+
+    for certificate in certificates {
+        if certificate.tick_number != current.next_tick {
+            bail!(
+                "Expected tick {}, got tick {}",
+                current.next_tick,
+                certificate.tick_number
+            );
+        }
+        if certificate.previous_tick_hash != current.tick_hash {
+            bail!("Tick {} does not link to the previous tick", certificate.tick_number);
+        }
+        if certificate.compute_hash() != certificate.tick_hash {
+            bail!("Tick {} has an invalid hash", certificate.tick_number);
+        }
+        if certificate.vdf_iteration != current.vdf_iteration + iterations_per_tick {
+            bail!(
+                "Tick {} ends at iteration {}, expected {}",
+                certificate.tick_number,
+                certificate.vdf_iteration,
+                current.vdf_iteration + iterations_per_tick
+            );
+        }
+
+        segments.push((
+            certificate.tick_number,
+            current.vdf_form.clone(),
+            certificate.vdf_form.clone(),
+        ));
+        current = SyncAnchor::from_certificate(certificate);
+    }
+
+    let pool = verifier.pool.clone();
+    let discriminant = discriminant.to_string();
+    let invalid = tokio::task::spawn_blocking(move || {
+        pool.install(|| {
+            segments.par_iter().find_map_first(|(tick_number, start, end)| {
+                let valid = verify_form_transition(&discriminant, start, iterations_per_tick, end);
+                (!valid).then_some(*tick_number)
+            })
+        })
+    })
+    .await?;
+    if let Some(tick_number) = invalid {
+        bail!("VDF output of tick {} does not verify", tick_number);
+    }
+
+    Ok(current)
+}
+
+/// Drives a node from its local head to the head of its peers
+pub struct TickSync {
+    network: NetworkHandle,
+    state_db: Arc<StateDB>,
+    status: Arc<RwLock<SyncStatus>>,
+    iterations_per_tick: u64,
+    verifier: VerifierPool,
+    checkpoint: Option<TrustedCheckpoint>,
+    epoch_length: u64,
+    compact_ticks: bool,
+}
+
+impl TickSync {
+    /// Create a sync driver reporting progress into `status`
+    ///
+    /// Downloaded certificates are verified on `verifier`.
+    pub fn new(
+        network: NetworkHandle,
+        state_db: Arc<StateDB>,
+        status: Arc<RwLock<SyncStatus>>,
+        iterations_per_tick: u64,
+        verifier: VerifierPool,
+    ) -> Self {
+        Self {
+            network,
+            state_db,
+            status,
+            iterations_per_tick,
+            verifier,
+            checkpoint: None,
+            epoch_length: 1,
+            compact_ticks: false,
+        }
+    }
+
+    /// Download certificates [`compact`](TickCertificate::compact), for
+    /// nodes on slow links
+    pub fn with_compact_ticks(mut self, compact_ticks: bool) -> Self {
+        self.compact_ticks = compact_ticks;
+        self
+    }
+
+    /// Start a node with an empty database from a trusted checkpoint
+    ///
+    /// `epoch_length` locates the summary of the epoch the checkpoint ends.
+    pub fn with_checkpoint(mut self, checkpoint: TrustedCheckpoint, epoch_length: u64) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self.epoch_length = epoch_length;
+        self
+    }
+
+    /// The checkpoint to start from, if the node has no ticks yet
+    fn pending_checkpoint(&self, local: &ChainState) -> Option<TrustedCheckpoint> {
+        self.checkpoint.filter(|_| local.current_tick == 0)
+    }
+
+    /// Catch up with the network starting from `local`
+    ///
+    /// Verified certificates are stored as they arrive. Peers that serve
+    /// invalid data are skipped for the rest of the sync. Returns the
+    /// sealed epoch state to resume from, or `None` if the node was already
+    /// at the head or no peer could help. A node waiting on a trusted
+    /// checkpoint cannot follow its own timeline, so failing to reach one
+    /// is an error.
+    pub async fn run(&self, local: ChainState) -> Result<Option<ChainState>> {
+        let discriminant = local.vdf_checkpoint.discriminant.clone();
+        let mut current = local;
+        let mut adopted = None;
+        let mut excluded = HashSet::new();
+        let deadline = tokio::time::Instant::now() + PEER_WAIT;
+
+        {
+            let mut status = self.status.write().await;
+            status.mode = SyncMode::Syncing;
+            status.starting_tick = current.current_tick;
+            status.current_tick = current.current_tick;
+        }
+
+        loop {
+            let peers: Vec<PeerId> = self
+                .network
+                .connected_peers()
+                .await?
+                .into_iter()
+                .filter(|peer| !excluded.contains(peer))
+                .collect();
+            self.status.write().await.peer_count = peers.len();
+
+            if peers.is_empty() {
+                if tokio::time::Instant::now() < deadline {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                    continue;
+                }
+                info!("No peers to sync from, following local timeline");
+                break;
+            }
+
+            let Some((peer, head)) = self.best_peer(&peers).await else {
+                break;
+            };
+            {
+                let mut status = self.status.write().await;
+                status.highest_tick = status.highest_tick.max(head);
+            }
+            if head <= current.current_tick + FOLLOW_DISTANCE {
+                break;
+            }
+
+            info!(
+                "Syncing from peer {}: local tick {}, peer tick {}",
+                peer, current.current_tick, head
+            );
+            let result = match self.pending_checkpoint(&current) {
+                Some(checkpoint) => match self.sync_checkpoint(peer, &checkpoint).await {
+                    Ok(anchor) => self.sync_from(peer, anchor, &discriminant).await,
+                    Err(e) => Err(e),
+                },
+                None => {
+                    let anchor = SyncAnchor::from_state(&current);
+                    self.sync_from(peer, anchor, &discriminant).await
+                }
+            };
+            match result {
+                Ok(Some(snapshot)) => {
+                    current = snapshot.clone();
+                    adopted = Some(snapshot);
+                }
+                Ok(None) => {
+                    info!("Best peer has no newer sealed epoch, following local timeline");
+                    break;
+                }
+                Err(e) => {
+                    warn!("Sync from peer {} failed: {}", peer, e);
+                    excluded.insert(peer);
+                }
+            }
+        }
+
+        if let Some(checkpoint) = self.pending_checkpoint(&current) {
+            bail!(
+                "No peer could serve the trusted checkpoint at tick {}",
+                checkpoint.tick_number
+            );
+        }
+
+        let mut status = self.status.write().await;
+        status.mode = SyncMode::Following;
+        status.current_tick = current.current_tick;
+        status.highest_tick = status.highest_tick.max(current.current_tick);
+        info!("Sync complete at tick {}, following the network", current.current_tick);
+
+        Ok(adopted)
+    }
+
+    /// Ask every peer for its head and pick the highest
+    async fn best_peer(&self, peers: &[PeerId]) -> Option<(PeerId, u64)> {
+        let mut best = None;
+        for peer in peers {
+            match self.network.sync_request(*peer, SyncRequest::Status).await {
+                Ok(SyncResponse::Status { current_tick, .. }) => {
+                    if best.is_none_or(|(_, head)| current_tick > head) {
+                        best = Some((*peer, current_tick));
+                    }
+                }
+                Ok(other) => debug!("Unexpected status response from {}: {:?}", peer, other),
+                Err(e) => debug!("Status request to {} failed: {}", peer, e),
+            }
+        }
+        best
+    }
+
+    /// Check a peer's view of the trusted checkpoint and start from it
+    ///
+    /// The checkpoint tick, its epoch summary, and the accumulator peaks
+    /// the summary commits to are stored, so ticks after the checkpoint
+    /// extend the local accumulator as if the node had the full history.
+    async fn sync_checkpoint(&self, peer: PeerId, checkpoint: &TrustedCheckpoint) -> Result<SyncAnchor> {
+        let tick_number = checkpoint.tick_number;
+        let request = SyncRequest::Ticks {
+            start: tick_number,
+            count: 1,
+            compact: false,
+        };
+        let certificate = match self.network.sync_request(peer, request).await? {
+            SyncResponse::Ticks(ticks) => ticks
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("Peer has no checkpoint tick {}", tick_number))?,
+            other => bail!("Expected ticks, got {:?}", other),
+        };
+        if certificate.tick_number != tick_number
+            || certificate.compute_hash() != certificate.tick_hash
+            || certificate.tick_hash != checkpoint.tick_hash
+        {
+            bail!("Peer's tick {} does not match the trusted checkpoint", tick_number);
+        }
+
+        let epoch_number = tick_number / self.epoch_length;
+        let request = SyncRequest::Epochs {
+            start: epoch_number,
+            count: 1,
+        };
+        let epoch = match self.network.sync_request(peer, request).await? {
+            SyncResponse::Epochs(epochs) => epochs
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("Peer has no summary of epoch {}", epoch_number))?,
+            other => bail!("Expected epochs, got {:?}", other),
+        };
+        let summary = &epoch.summary;
+        if summary.epoch_number != epoch_number
+            || summary.end_tick != tick_number
+            || summary.end_tick_hash != checkpoint.tick_hash
+            || summary.state_root != checkpoint.state_root
+        {
+            bail!("Peer's summary of epoch {} does not match the trusted checkpoint", epoch_number);
+        }
+
+        let leaf_count = tick_number + 1;
+        let peaks = match self.network.sync_request(peer, SyncRequest::MmrPeaks { leaf_count }).await? {
+            SyncResponse::MmrPeaks(peaks) => peaks,
+            other => bail!("Expected accumulator peaks, got {:?}", other),
+        };
+        if bag_peaks(leaf_count, &peaks) != summary.mmr_root {
+            bail!("Tick accumulator peaks do not match epoch {}", epoch_number);
+        }
+
+        self.state_db
+            .import_tick_mmr_peaks(leaf_count, &peaks)
+            .await
+            .map_err(|e| anyhow!("Failed to import tick accumulator: {}", e))?;
+        self.state_db
+            .store_tick(&certificate)
+            .await
+            .map_err(|e| anyhow!("Failed to store tick {}: {}", tick_number, e))?;
+        self.state_db
+            .store_epoch_summary(&verified_epoch(epoch, peer))
+            .await
+            .map_err(|e| anyhow!("Failed to store epoch {}: {}", epoch_number, e))?;
+        self.state_db
+            .set_history_start(tick_number)
+            .await
+            .map_err(|e| anyhow!("Failed to record history start: {}", e))?;
+
+        info!("Starting from trusted checkpoint at tick {}", tick_number);
+        Ok(SyncAnchor::from_certificate(&certificate))
+    }
+
+    /// Download, verify, and store the ticks from `anchor` to a peer's snapshot
+    ///
+    /// Returns `None` if the peer's latest sealed epoch is not ahead of
+    /// `anchor`. The snapshot is only returned once its epoch summary is
+    /// anchored in the verified ticks.
+    async fn sync_from(
+        &self,
+        peer: PeerId,
+        mut anchor: SyncAnchor,
+        discriminant: &str,
+    ) -> Result<Option<ChainState>> {
+        let snapshot = match self.network.sync_request(peer, SyncRequest::Snapshot).await? {
+            SyncResponse::Snapshot(snapshot) => *snapshot,
+            other => bail!("Expected a snapshot, got {:?}", other),
+        };
+        if snapshot.state.vdf_checkpoint.discriminant != discriminant {
+            bail!("Peer uses a different VDF discriminant");
+        }
+
+        let target = snapshot.state.current_tick;
+        if target <= anchor.next_tick {
+            debug!("Peer {} has no sealed epoch past tick {}", peer, anchor.next_tick);
+            return Ok(None);
+        }
+
+        while anchor.next_tick < target {
+            let count = (target - anchor.next_tick).min(MAX_TICKS_PER_REQUEST);
+            let request = SyncRequest::Ticks {
+                start: anchor.next_tick,
+                count,
+                compact: self.compact_ticks,
+            };
+            let mut certificates = match self.network.sync_request(peer, request).await? {
+                SyncResponse::Ticks(ticks) if !ticks.is_empty() => ticks,
+                SyncResponse::Ticks(_) => bail!("Peer has no tick {}", anchor.next_tick),
+                other => bail!("Expected ticks, got {:?}", other),
+            };
+            for certificate in &mut certificates {
+                certificate.expand(discriminant)?;
+            }
+
+            anchor = verify_certificates(
+                &anchor,
+                &certificates,
+                self.iterations_per_tick,
+                discriminant,
+                &self.verifier,
+            )
+            .await?;
+
+            for certificate in &certificates {
+                self.state_db
+                    .store_tick(certificate)
+                    .await
+                    .map_err(|e| anyhow!("Failed to store tick {}: {}", certificate.tick_number, e))?;
+            }
+
+            self.status.write().await.current_tick = anchor.next_tick;
+            debug!("Verified ticks up to {}", anchor.next_tick - 1);
+        }
+
+        anchor.check_snapshot(&snapshot.state)?;
+        let mmr_root = self
+            .state_db
+            .tick_mmr_root()
+            .await
+            .map_err(|e| anyhow!("Failed to load tick accumulator: {}", e))?;
+        snapshot.check_anchor(&mmr_root)?;
+
+        if let Err(e) = self.sync_epochs(peer).await {
+            warn!("Failed to sync epoch summaries from {}: {}", peer, e);
+        }
+
+        Ok(Some(snapshot.state))
+    }
+
+    /// Download the peer's epoch summaries for ticks we have verified
+    ///
+    /// Each summary must end on a locally stored tick and link to the
+    /// summary before it. Only signatures that verify are kept.
+    async fn sync_epochs(&self, peer: PeerId) -> Result<()> {
+        let mut previous = self
+            .state_db
+            .latest_epoch_summary()
+            .await
+            .map_err(|e| anyhow!("Failed to load latest epoch: {}", e))?
+            .map(|epoch| epoch.summary);
+
+        loop {
+            let start = previous.as_ref().map_or(0, |epoch| epoch.epoch_number + 1);
+            let request = SyncRequest::Epochs {
+                start,
+                count: MAX_EPOCHS_PER_REQUEST,
+            };
+            let epochs = match self.network.sync_request(peer, request).await? {
+                SyncResponse::Epochs(epochs) => epochs,
+                other => bail!("Expected epochs, got {:?}", other),
+            };
+            if epochs.is_empty() {
+                return Ok(());
+            }
+
+            for epoch in epochs {
+                if !self.check_epoch(previous.as_ref(), &epoch.summary).await? {
+                    return Ok(());
+                }
+
+                let epoch_number = epoch.summary.epoch_number;
+                let verified = verified_epoch(epoch, peer);
+                self.state_db
+                    .store_epoch_summary(&verified)
+                    .await
+                    .map_err(|e| anyhow!("Failed to store epoch {}: {}", epoch_number, e))?;
+
+                debug!("Synced summary of epoch {}", epoch_number);
+                previous = Some(verified.summary);
+            }
+        }
+    }
+
+    /// Check an epoch against its predecessor and the local ticks
+    ///
+    /// Returns `false` if the epoch ends beyond the ticks we hold.
+    async fn check_epoch(&self, previous: Option<&EpochSummary>, epoch: &EpochSummary) -> Result<bool> {
+        match previous {
+            Some(previous) => previous.check_successor(epoch)?,
+            None if epoch.epoch_number != 0 || epoch.start_tick != 0 => {
+                bail!("First epoch must start at tick 0, got epoch {}", epoch.epoch_number)
+            }
+            None => {}
+        }
+
+        let tick = self
+            .state_db
+            .get_tick(epoch.end_tick)
+            .await
+            .map_err(|e| anyhow!("Failed to load tick {}: {}", epoch.end_tick, e))?;
+        match tick {
+            Some(tick) if tick.tick_hash == epoch.end_tick_hash => Ok(true),
+            Some(_) => bail!("Epoch {} ends on a different tick {}", epoch.epoch_number, epoch.end_tick),
+            None => Ok(false),
+        }
+    }
+
+    /// Download the ticks before a trusted checkpoint
+    ///
+    /// Walks backwards from the oldest stored tick, checking that every
+    /// batch hash-links to the tick after it. Tick hashes commit to the VDF
+    /// outputs, so the VDF is not recomputed. Progress is persisted, and an
+    /// interrupted backfill resumes where it stopped.
+    pub async fn backfill(&self) -> Result<()> {
+        let mut start = self
+            .state_db
+            .history_start()
+            .await
+            .map_err(|e| anyhow!("Failed to load history start: {}", e))?;
+        if start == 0 {
+            return Ok(());
+        }
+
+        let oldest = self
+            .state_db
+            .get_tick(start)
+            .await
+            .map_err(|e| anyhow!("Failed to load tick {}: {}", start, e))?
+            .ok_or_else(|| anyhow!("Oldest tick {} is missing", start))?;
+        let mut expected_hash = oldest.previous_tick_hash;
+        let mut excluded = HashSet::new();
+        info!("Backfilling {} ticks before the trusted checkpoint", start);
+
+        while start > 0 {
+            let Some(peer) = self
+                .network
+                .connected_peers()
+                .await?
+                .into_iter()
+                .find(|peer| !excluded.contains(peer))
+            else {
+                bail!("No peers to backfill ticks before {} from", start);
+            };
+
+            let batch_start = start.saturating_sub(MAX_TICKS_PER_REQUEST);
+            match self.backfill_batch(peer, batch_start, start, expected_hash).await {
+                Ok(hash) => {
+                    expected_hash = hash;
+                    start = batch_start;
+                    self.state_db
+                        .set_history_start(start)
+                        .await
+                        .map_err(|e| anyhow!("Failed to record history start: {}", e))?;
+                    debug!("Backfilled ticks down to {}", start);
+                }
+                Err(e) => {
+                    warn!("Backfill from peer {} failed: {}", peer, e);
+                    excluded.insert(peer);
+                }
+            }
+        }
+
+        info!("Backfill complete, full history is available");
+        Ok(())
+    }
+
+    /// Fetch and store ticks `start..end`, the last linking to `expected_hash`
+    ///
+    /// Returns the hash the tick before `start` must have.
+    async fn backfill_batch(
+        &self,
+        peer: PeerId,
+        start: u64,
+        end: u64,
+        expected_hash: [u8; 32],
+    ) -> Result<[u8; 32]> {
+        let request = SyncRequest::Ticks {
+            start,
+            count: end - start,
+            compact: false,
+        };
+        let certificates = match self.network.sync_request(peer, request).await? {
+            SyncResponse::Ticks(ticks) => ticks,
+            other => bail!("Expected ticks, got {:?}", other),
+        };
+        if certificates.len() as u64 != end - start {
+            bail!("Peer returned {} of {} ticks", certificates.len(), end - start);
+        }
+
+        let mut expected = expected_hash;
+        for (tick_number, certificate) in (start..end).rev().zip(certificates.iter().rev()) {
+            if certificate.tick_number != tick_number
+                || certificate.tick_hash != expected
+                || certificate.compute_hash() != certificate.tick_hash
+            {
+                bail!("Tick {} does not link to tick {}", tick_number, tick_number + 1);
+            }
+            expected = certificate.previous_tick_hash;
+        }
+
+        for certificate in &certificates {
+            self.state_db
+                .store_tick(certificate)
+                .await
+                .map_err(|e| anyhow!("Failed to store tick {}: {}", certificate.tick_number, e))?;
+        }
+        Ok(expected)
+    }
+}
+
+/// Keep only the signatures on a peer's epoch summary that verify
+fn verified_epoch(epoch: SignedEpochSummary, peer: PeerId) -> SignedEpochSummary {
+    let mut verified = SignedEpochSummary::new(epoch.summary);
+    for signature in epoch.signatures {
+        if let Err(e) = verified.add_signature(signature) {
+            debug!("Dropping epoch signature from {}: {}", peer, e);
+        }
+    }
+    verified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{NetworkConfig, NetworkService};
+    use crate::peer_store::PeerStore;
+    use ed25519_dalek::SigningKey;
+    use kala_state::{verify_epoch_chain, TickType};
+    use kala_vdf::EternalVDF;
+
+    const DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+    const K: u64 = 8;
+
+    fn verifier() -> VerifierPool {
+        VerifierPool::new(2).unwrap()
+    }
+
+    /// A chain state at genesis and one after `ticks` ticks, with their certificates
+    fn build_chain(ticks: u64) -> (ChainState, ChainState, Vec<TickCertificate>) {
+        let mut vdf = EternalVDF::with_tick_size(DISCRIMINANT, K);
+        let mut genesis = ChainState::from_vdf_checkpoint(vdf.checkpoint());
+        genesis.last_tick_hash = [0u8; 32];
+
+        let mut certificates = Vec::new();
+        let mut previous = [0u8; 32];
+        for tick_number in 0..ticks {
+            vdf.timestamp_data(tick_number.to_le_bytes().to_vec());
+            vdf.advance(K - 1);
+            let mut certificate = TickCertificate {
+                tick_number,
+                tick_type: TickType::Empty,
+                vdf_iteration: vdf.get_iteration(),
+                vdf_form: vdf.get_form_values(),
+                hash_chain_value: vdf.get_hash_chain(),
+                tick_hash: [0u8; 32],
+                transaction_count: 0,
+                transaction_merkle_root: [0u8; 32],
+                timestamp: 0,
+                previous_tick_hash: previous,
+                usage: Default::default(),
+            };
+            certificate.tick_hash = certificate.compute_hash();
+            previous = certificate.tick_hash;
+            certificates.push(certificate);
+        }
+
+        let mut head = ChainState::from_vdf_checkpoint(vdf.checkpoint());
+        head.last_tick_hash = previous;
+        (genesis, head, certificates)
+    }
+
+    /// The chain state right after `certificate`, with the accounts of `base`
+    fn state_after(base: &ChainState, certificate: &TickCertificate) -> ChainState {
+        let mut state = base.clone();
+        let (form_a, form_b, form_c) = certificate.vdf_form.clone();
+        state.current_tick = certificate.tick_number + 1;
+        state.current_iteration = certificate.vdf_iteration;
+        state.last_tick_hash = certificate.tick_hash;
+        state.vdf_checkpoint.form_a = form_a;
+        state.vdf_checkpoint.form_b = form_b;
+        state.vdf_checkpoint.form_c = form_c;
+        state.vdf_checkpoint.iteration = certificate.vdf_iteration;
+        state
+    }
+
+    #[tokio::test]
+    async fn test_verify_certificates() {
+        let (genesis, head, certificates) = build_chain(4);
+        let anchor = SyncAnchor::from_state(&genesis);
+
+        let verifier = verifier();
+        assert_eq!(verifier.threads(), 2);
+
+        let tip = verify_certificates(&anchor, &certificates, K, DISCRIMINANT, &verifier)
+            .await
+            .unwrap();
+        assert_eq!(tip.next_tick, 4);
+        assert!(tip.check_snapshot(&head).is_ok());
+        assert!(tip.check_snapshot(&genesis).is_err());
+
+        // Broken linkage
+        let mut skipped = certificates.clone();
+        skipped.remove(1);
+        let result = verify_certificates(&anchor, &skipped, K, DISCRIMINANT, &verifier).await;
+        assert!(result.is_err());
+
+        // A forged VDF output with a consistent tick hash
+        let mut forged = certificates.clone();
+        forged[2].vdf_form = forged[1].vdf_form.clone();
+        forged[2].tick_hash = forged[2].compute_hash();
+        forged[3].previous_tick_hash = forged[2].tick_hash;
+        forged[3].tick_hash = forged[3].compute_hash();
+        let err = verify_certificates(&anchor, &forged, K, DISCRIMINANT, &verifier)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("VDF output of tick 2"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        let (_, head, certificates) = build_chain(3);
+        for certificate in &certificates {
+            state_db.store_tick(certificate).await.unwrap();
+        }
+        state_db.save_chain_state(&head).await.unwrap();
+
+        match serve_request(&state_db, SyncRequest::Status).await {
+            SyncResponse::Status { current_tick, last_tick_hash } => {
+                assert_eq!(current_tick, 3);
+                assert_eq!(last_tick_hash, certificates[2].tick_hash);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        let request = SyncRequest::Ticks { start: 1, count: 10, compact: false };
+        match serve_request(&state_db, request).await {
+            SyncResponse::Ticks(ticks) => {
+                let numbers: Vec<_> = ticks.iter().map(|t| t.tick_number).collect();
+                assert_eq!(numbers, vec![1, 2]);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        let request = SyncRequest::Ticks { start: 1, count: 1, compact: true };
+        match serve_request(&state_db, request).await {
+            SyncResponse::Ticks(mut ticks) => {
+                assert!(ticks[0].is_compact());
+                ticks[0].expand(&head.vdf_checkpoint.discriminant).unwrap();
+                assert_eq!(ticks[0].vdf_form, certificates[1].vdf_form);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        let summary = state_db.build_epoch_summary(0, 3, &head, Vec::new()).await.unwrap();
+        state_db.store_epoch_summary(&SignedEpochSummary::new(summary)).await.unwrap();
+        match serve_request(&state_db, SyncRequest::Epochs { start: 0, count: 10 }).await {
+            SyncResponse::Epochs(epochs) => {
+                assert_eq!(epochs.len(), 1);
+                assert_eq!(epochs[0].summary.end_tick, 2);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sync_messages_round_trip() {
+        let request = SyncRequest::Ticks { start: 5, count: 2, compact: true };
+        let request = SyncRequest::decode_proto(&request.encode_proto()).unwrap();
+        assert!(matches!(request, SyncRequest::Ticks { start: 5, count: 2, compact: true }));
+        assert!(matches!(
+            SyncRequest::decode_proto(&SyncRequest::Snapshot.encode_proto()).unwrap(),
+            SyncRequest::Snapshot
+        ));
+
+        let (_, head, certificates) = build_chain(2);
+        let response = SyncResponse::Ticks(certificates.clone()).encode_proto();
+        match SyncResponse::decode_proto(&response).unwrap() {
+            SyncResponse::Ticks(ticks) => {
+                let hashes: Vec<_> = ticks.iter().map(|t| t.compute_hash()).collect();
+                assert_eq!(hashes, vec![certificates[0].tick_hash, certificates[1].tick_hash]);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        let response = SyncResponse::State(Box::new(head.clone())).encode_proto();
+        match SyncResponse::decode_proto(&response).unwrap() {
+            SyncResponse::State(state) => assert_eq!(state.state_root(), head.state_root()),
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        // A request with no variant set is rejected rather than read as Status
+        assert!(SyncRequest::decode_proto(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proven_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        let (_, head, certificates) = build_chain(2);
+
+        // Nothing to serve before the first epoch is sealed
+        assert!(ProvenSnapshot::load(&state_db).await.is_err());
+
+        for certificate in &certificates {
+            state_db.store_tick(certificate).await.unwrap();
+        }
+        let sealed = state_after(&head, &certificates[1]);
+        let summary = state_db.build_epoch_summary(0, 2, &sealed, Vec::new()).await.unwrap();
+        state_db.store_epoch_summary(&SignedEpochSummary::new(summary)).await.unwrap();
+        state_db.save_epoch_state(&sealed).await.unwrap();
+
+        let snapshot = match serve_request(&state_db, SyncRequest::Snapshot).await {
+            SyncResponse::Snapshot(snapshot) => *snapshot,
+            other => panic!("unexpected response {:?}", other),
+        };
+        let mmr_root = mmr_root(&certificates);
+        assert!(snapshot.check_anchor(&mmr_root).is_ok());
+        assert!(snapshot.check_anchor(&[0u8; 32]).is_err());
+
+        // Accounts the summary never committed to are rejected
+        let mut forged = snapshot.clone();
+        forged.state.mint(&[1u8; 32], 1000).unwrap();
+        let err = forged.check_anchor(&mmr_root).unwrap_err();
+        assert!(err.to_string().contains("state root"));
+
+        // So is a state from a different tick
+        let mut early = snapshot;
+        early.state = state_after(&head, &certificates[0]);
+        assert!(early.check_anchor(&mmr_root).is_err());
+    }
+
+    /// Start a peer serving the chain, with epochs of two ticks
+    async fn serve_chain(
+        head: &ChainState,
+        certificates: &[TickCertificate],
+    ) -> (tempfile::TempDir, Arc<StateDB>, NetworkHandle, String) {
+        let dir_a = tempfile::tempdir().unwrap();
+        let db_a = Arc::new(StateDB::open(dir_a.path().to_str().unwrap()).unwrap());
+        let witness = SigningKey::from_bytes(&[3u8; 32]);
+        for certificate in certificates {
+            db_a.store_tick(certificate).await.unwrap();
+            if certificate.tick_number % 2 == 1 {
+                let witnesses = vec![witness.verifying_key().to_bytes()];
+                let summary = db_a
+                    .build_epoch_summary(certificate.tick_number / 2, 2, head, witnesses)
+                    .await
+                    .unwrap();
+                let mut epoch = SignedEpochSummary::new(summary);
+                epoch.sign(&witness).unwrap();
+                db_a.store_epoch_summary(&epoch).await.unwrap();
+                db_a.save_epoch_state(&state_after(head, certificate)).await.unwrap();
+            }
+        }
+        db_a.save_chain_state(head).await.unwrap();
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = NetworkConfig {
+            enable_mdns: false,
+            ..NetworkConfig::new(format!("/ip4/127.0.0.1/tcp/{}", port))
+        };
+        let (service_a, handle_a, _inbound_a) =
+            NetworkService::new(config, PeerStore::in_memory()).unwrap();
+        let peer_addr = format!("/ip4/127.0.0.1/tcp/{}/p2p/{}", port, handle_a.local_peer_id());
+        tokio::spawn(service_a.with_state_db(db_a.clone()).run());
+        (dir_a, db_a, handle_a, peer_addr)
+    }
+
+    /// Start a node with an empty database connected to `peer_addr`
+    fn empty_node(
+        peer_addr: String,
+    ) -> (tempfile::TempDir, Arc<StateDB>, NetworkHandle, Arc<RwLock<SyncStatus>>) {
+        let dir_b = tempfile::tempdir().unwrap();
+        let db_b = Arc::new(StateDB::open(dir_b.path().to_str().unwrap()).unwrap());
+        let config = NetworkConfig {
+            peers: vec![peer_addr],
+            enable_mdns: false,
+            ..NetworkConfig::new("/ip4/127.0.0.1/tcp/0")
+        };
+        let (service_b, handle_b, _inbound_b) =
+            NetworkService::new(config, PeerStore::in_memory()).unwrap();
+        tokio::spawn(service_b.run());
+
+        let status = Arc::new(RwLock::new(SyncStatus {
+            mode: SyncMode::Following,
+            starting_tick: 0,
+            current_tick: 0,
+            highest_tick: 0,
+            peer_count: 0,
+        }));
+        (dir_b, db_b, handle_b, status)
+    }
+
+    #[tokio::test]
+    async fn test_sync_from_peer() {
+        let (genesis, head, certificates) = build_chain(5);
+        let (_dir_a, _db_a, _handle_a, peer_addr) = serve_chain(&head, &certificates).await;
+
+        // Node B starts from genesis
+        let (_dir_b, db_b, handle_b, status) = empty_node(peer_addr);
+        let sync = TickSync::new(handle_b, db_b.clone(), status.clone(), K, verifier())
+            .with_compact_ticks(true);
+
+        let snapshot = tokio::time::timeout(Duration::from_secs(20), sync.run(genesis))
+            .await
+            .expect("sync timed out")
+            .unwrap()
+            .expect("no snapshot adopted");
+
+        // The snapshot is the state sealed with epoch 1, after tick 3
+        assert_eq!(snapshot.current_tick, 4);
+        assert_eq!(db_b.get_tick(3).await.unwrap().unwrap().tick_hash, certificates[3].tick_hash);
+        assert!(db_b.get_tick(4).await.unwrap().is_none());
+        assert_eq!(db_b.tick_mmr_root().await.unwrap(), mmr_root(&certificates[..4]));
+
+        let first = db_b.get_epoch_summary(0).await.unwrap().unwrap();
+        let second = db_b.latest_epoch_summary().await.unwrap().unwrap();
+        assert_eq!(second.summary.end_tick, 3);
+        assert!(verify_epoch_chain(&first.summary, &[second]).is_ok());
+
+        let status = status.read().await;
+        assert_eq!(status.mode, SyncMode::Following);
+        assert_eq!(status.current_tick, 4);
+        assert_eq!(status.highest_tick, 5);
+    }
+
+    #[tokio::test]
+    async fn test_sync_from_checkpoint() {
+        let (genesis, head, certificates) = build_chain(5);
+        let (_dir_a, db_a, _handle_a, peer_addr) = serve_chain(&head, &certificates).await;
+        let epoch = db_a.get_epoch_summary(0).await.unwrap().unwrap();
+
+        let (_dir_b, db_b, handle_b, status) = empty_node(peer_addr);
+        let checkpoint = TrustedCheckpoint {
+            tick_number: 1,
+            tick_hash: certificates[1].tick_hash,
+            state_root: epoch.summary.state_root,
+        };
+
+        // A checkpoint the peer disagrees with is never adopted
+        let wrong = TrustedCheckpoint {
+            state_root: [9u8; 32],
+            ..checkpoint
+        };
+        let sync = TickSync::new(handle_b.clone(), db_b.clone(), status.clone(), K, verifier())
+            .with_checkpoint(wrong, 2);
+        assert!(sync.run(genesis.clone()).await.is_err());
+        assert_eq!(db_b.tick_mmr_leaf_count().await.unwrap(), 0);
+
+        let sync = TickSync::new(handle_b, db_b.clone(), status, K, verifier())
+            .with_checkpoint(checkpoint, 2);
+        let snapshot = tokio::time::timeout(Duration::from_secs(20), sync.run(genesis))
+            .await
+            .expect("sync timed out")
+            .unwrap()
+            .expect("no snapshot adopted");
+
+        assert_eq!(snapshot.current_tick, 4);
+        assert!(db_b.get_tick(0).await.unwrap().is_none());
+        assert_eq!(db_b.history_start().await.unwrap(), 1);
+        assert_eq!(db_b.tick_mmr_root().await.unwrap(), mmr_root(&certificates[..4]));
+        assert_eq!(db_b.latest_epoch_summary().await.unwrap().unwrap().summary.end_tick, 3);
+
+        sync.backfill().await.unwrap();
+        assert_eq!(db_b.get_tick(0).await.unwrap().unwrap().tick_hash, certificates[0].tick_hash);
+        assert_eq!(db_b.history_start().await.unwrap(), 0);
+    }
+
+    fn mmr_root(certificates: &[TickCertificate]) -> [u8; 32] {
+        let mut mmr = kala_common::mmr::MerkleMountainRange::new();
+        for certificate in certificates {
+            mmr.append(certificate.tick_hash).unwrap();
+        }
+        mmr.root().unwrap()
+    }
+}
@@ -0,0 +1,209 @@
+//! Transaction lifecycle tracing
+//!
+//! Every transaction the node accepts is given a trace ID. The
+//! [`TransactionTracer`] records a [`TraceEvent`] as the transaction moves
+//! through the mempool, witness observation, ordering, decryption, and
+//! application, and logs each event tagged with the trace ID so one
+//! transaction can be followed through the node's logs. The assembled
+//! timeline is served by `kala_traceTransaction`.
+//!
+//! Traces are kept in memory for the most recent [`TRACE_CAPACITY`]
+//! transactions; older ones are evicted.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use kala_rpc::{TraceEvent, TraceStage, TransactionTrace};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+/// Number of transactions traced before the oldest traces are evicted
+pub const TRACE_CAPACITY: usize = 10_000;
+
+#[derive(Default)]
+struct TraceStore {
+    traces: HashMap<[u8; 32], TransactionTrace>,
+    /// Traced envelopes, oldest first
+    order: VecDeque<[u8; 32]>,
+}
+
+/// Bounded store of transaction traces, keyed by envelope hash
+///
+/// # Example
+///
+/// ```
+/// use kala_core::trace::TransactionTracer;
+/// use kala_rpc::TraceStage;
+///
+/// let tracer = TransactionTracer::new(16);
+/// let envelope_hash = [7u8; 32];
+/// let trace_id = tracer.start(envelope_hash, 3, 1000, TraceStage::Submitted);
+/// tracer.record(&envelope_hash, TraceStage::Ordered, 3, None, Some("position 0".to_string()));
+///
+/// let trace = tracer.get(&envelope_hash).unwrap();
+/// assert_eq!(trace.trace_id, trace_id);
+/// assert_eq!(trace.events.len(), 2);
+/// ```
+pub struct TransactionTracer {
+    capacity: usize,
+    store: Mutex<TraceStore>,
+}
+
+impl TransactionTracer {
+    /// Create a tracer keeping at most `capacity` traces
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            store: Mutex::new(TraceStore::default()),
+        }
+    }
+
+    /// Start tracing a transaction entering the mempool
+    ///
+    /// A transaction seen again, for example gossiped back by a peer,
+    /// keeps its trace ID and gains another event.
+    ///
+    /// # Parameters
+    ///
+    /// - `envelope_hash`: Hash identifying the transaction
+    /// - `target_tick`: Tick the transaction targets
+    /// - `iteration`: VDF iteration the node assigned on arrival
+    /// - `stage`: [`TraceStage::Submitted`] or [`TraceStage::Received`]
+    ///
+    /// # Returns
+    ///
+    /// The transaction's trace ID
+    pub fn start(&self, envelope_hash: [u8; 32], target_tick: u64, iteration: u64, stage: TraceStage) -> String {
+        let mut store = self.store.lock().unwrap();
+        if !store.traces.contains_key(&envelope_hash) {
+            while store.order.len() >= self.capacity.max(1) {
+                if let Some(evicted) = store.order.pop_front() {
+                    store.traces.remove(&evicted);
+                }
+            }
+            store.order.push_back(envelope_hash);
+            store.traces.insert(
+                envelope_hash,
+                TransactionTrace {
+                    trace_id: new_trace_id(&envelope_hash),
+                    tx_hash: hex::encode(envelope_hash),
+                    target_tick,
+                    events: Vec::new(),
+                },
+            );
+        }
+
+        let trace = store.traces.get_mut(&envelope_hash).expect("trace was just inserted");
+        push_event(trace, stage, target_tick, Some(iteration), None);
+        trace.trace_id.clone()
+    }
+
+    /// Record an event for a traced transaction
+    ///
+    /// Transactions without a trace, such as evicted ones, are ignored.
+    pub fn record(
+        &self,
+        envelope_hash: &[u8; 32],
+        stage: TraceStage,
+        tick_number: u64,
+        iteration: Option<u64>,
+        detail: Option<String>,
+    ) {
+        let mut store = self.store.lock().unwrap();
+        if let Some(trace) = store.traces.get_mut(envelope_hash) {
+            push_event(trace, stage, tick_number, iteration, detail);
+        }
+    }
+
+    /// Trace ID of a transaction, if it is traced
+    pub fn trace_id(&self, envelope_hash: &[u8; 32]) -> Option<String> {
+        let store = self.store.lock().unwrap();
+        store.traces.get(envelope_hash).map(|trace| trace.trace_id.clone())
+    }
+
+    /// The recorded timeline of a transaction
+    pub fn get(&self, envelope_hash: &[u8; 32]) -> Option<TransactionTrace> {
+        self.store.lock().unwrap().traces.get(envelope_hash).cloned()
+    }
+}
+
+impl Default for TransactionTracer {
+    fn default() -> Self {
+        Self::new(TRACE_CAPACITY)
+    }
+}
+
+fn push_event(
+    trace: &mut TransactionTrace,
+    stage: TraceStage,
+    tick_number: u64,
+    iteration: Option<u64>,
+    detail: Option<String>,
+) {
+    debug!(
+        trace_id = %trace.trace_id,
+        "Transaction {}... {:?} at tick {}{}",
+        &trace.tx_hash[..16],
+        stage,
+        tick_number,
+        detail.as_deref().map(|detail| format!(": {}", detail)).unwrap_or_default()
+    );
+    trace.events.push(TraceEvent {
+        stage,
+        timestamp_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64),
+        tick_number,
+        iteration,
+        detail,
+    });
+}
+
+/// A trace ID unique to this node's sighting of the envelope
+fn new_trace_id(envelope_hash: &[u8; 32]) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let mut hasher = Sha256::new();
+    hasher.update(envelope_hash);
+    hasher.update(nanos.to_le_bytes());
+    hex::encode(&hasher.finalize()[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_lifecycle() {
+        let tracer = TransactionTracer::new(16);
+        let envelope_hash = [1u8; 32];
+
+        let trace_id = tracer.start(envelope_hash, 5, 300, TraceStage::Submitted);
+        // Seeing the envelope again keeps the trace
+        assert_eq!(tracer.start(envelope_hash, 5, 310, TraceStage::Received), trace_id);
+        tracer.record(&envelope_hash, TraceStage::Applied, 5, None, None);
+        tracer.record(&[2u8; 32], TraceStage::Applied, 5, None, None);
+
+        let trace = tracer.get(&envelope_hash).unwrap();
+        assert_eq!(trace.tx_hash, hex::encode(envelope_hash));
+        assert_eq!(trace.target_tick, 5);
+        let stages: Vec<_> = trace.events.iter().map(|event| event.stage).collect();
+        assert_eq!(stages, vec![TraceStage::Submitted, TraceStage::Received, TraceStage::Applied]);
+        assert_eq!(trace.events[1].iteration, Some(310));
+        assert!(tracer.get(&[2u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_oldest_traces_evicted() {
+        let tracer = TransactionTracer::new(2);
+        for i in 0..3u8 {
+            tracer.start([i; 32], 1, i as u64, TraceStage::Received);
+        }
+
+        assert!(tracer.trace_id(&[0u8; 32]).is_none());
+        assert!(tracer.trace_id(&[1u8; 32]).is_some());
+        assert!(tracer.trace_id(&[2u8; 32]).is_some());
+    }
+}
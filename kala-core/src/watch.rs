@@ -0,0 +1,136 @@
+//! Account watch service
+//!
+//! [`AccountWatcher`] is a [`NodeObserver`] that turns the account changes
+//! of every tick into [`AccountUpdate`]s for the addresses someone is
+//! watching. It backs the `kala_watchAccount` subscription: each watched
+//! address gets a broadcast channel, created on the first subscription and
+//! dropped once its last subscriber is gone.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use kala_rpc::AccountUpdate;
+use tokio::sync::broadcast;
+
+use crate::observer::{AccountChange, NodeObserver};
+
+/// Updates buffered per watched address before slow subscribers lag
+const WATCH_CHANNEL_SIZE: usize = 64;
+
+/// Publishes account changes to per-address subscribers
+///
+/// # Example
+///
+/// ```
+/// use kala_core::watch::AccountWatcher;
+///
+/// let watcher = AccountWatcher::new();
+/// let mut updates = watcher.subscribe([7u8; 32]);
+/// assert!(updates.try_recv().is_err());
+/// assert_eq!(watcher.watched_accounts(), 1);
+/// ```
+#[derive(Default)]
+pub struct AccountWatcher {
+    channels: Mutex<HashMap<[u8; 32], broadcast::Sender<AccountUpdate>>>,
+}
+
+impl AccountWatcher {
+    /// Create a watcher with no subscribers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive updates for `address` from the next tick on
+    pub fn subscribe(&self, address: [u8; 32]) -> broadcast::Receiver<AccountUpdate> {
+        self.channels
+            .lock()
+            .unwrap()
+            .entry(address)
+            .or_insert_with(|| broadcast::channel(WATCH_CHANNEL_SIZE).0)
+            .subscribe()
+    }
+
+    /// Number of addresses with at least one subscriber
+    pub fn watched_accounts(&self) -> usize {
+        let mut channels = self.channels.lock().unwrap();
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+        channels.len()
+    }
+
+    /// Send each change to the subscribers of its account
+    pub fn publish(&self, tick_number: u64, changes: &[AccountChange]) {
+        let mut channels = self.channels.lock().unwrap();
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+
+        for change in changes {
+            if let Some(sender) = channels.get(&change.address) {
+                let _ = sender.send(AccountUpdate {
+                    address: hex::encode(change.address),
+                    tick_number,
+                    balance: change.account.balance,
+                    nonce: change.account.nonce,
+                    staked_amount: change.account.staked_amount,
+                    delegation: change.account.delegation.map(hex::encode),
+                    tx_hashes: change.tx_hashes.iter().map(hex::encode).collect(),
+                });
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeObserver for AccountWatcher {
+    async fn on_accounts_changed(&self, tick_number: u64, changes: &[AccountChange]) {
+        self.publish(tick_number, changes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_state::Account;
+
+    fn change(address: u8, balance: u64, tx_hashes: Vec<[u8; 32]>) -> AccountChange {
+        let mut account = Account::new();
+        account.balance = balance;
+        AccountChange {
+            address: [address; 32],
+            account,
+            tx_hashes,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_updates_reach_watchers_of_the_account() {
+        let watcher = AccountWatcher::new();
+        let mut alice = watcher.subscribe([1u8; 32]);
+        let mut bob = watcher.subscribe([2u8; 32]);
+
+        watcher
+            .on_accounts_changed(9, &[change(1, 40, vec![[5u8; 32], [6u8; 32]])])
+            .await;
+
+        let update = alice.try_recv().unwrap();
+        assert_eq!(update.tick_number, 9);
+        assert_eq!(update.balance, 40);
+        assert_eq!(update.address, hex::encode([1u8; 32]));
+        assert_eq!(update.tx_hashes, vec![hex::encode([5u8; 32]), hex::encode([6u8; 32])]);
+        assert!(bob.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_channels_dropped_without_subscribers() {
+        let watcher = AccountWatcher::new();
+        let first = watcher.subscribe([1u8; 32]);
+        let second = watcher.subscribe([1u8; 32]);
+        assert_eq!(watcher.watched_accounts(), 1);
+
+        drop(first);
+        assert_eq!(watcher.watched_accounts(), 1);
+        drop(second);
+        assert_eq!(watcher.watched_accounts(), 0);
+
+        // Publishing to an unwatched account is a no-op
+        watcher.publish(1, &[change(1, 10, vec![])]);
+    }
+}
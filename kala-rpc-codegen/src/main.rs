@@ -0,0 +1,66 @@
+// kala-rpc-codegen/src/main.rs - Client bindings of the Kala JSON-RPC API
+//
+// Reads the OpenRPC document kala-rpc builds from its request and response
+// types and writes typed clients for TypeScript and Python, so SDKs follow
+// the Rust definitions instead of being kept in sync by hand. The bindings
+// are committed under `sdk/`; a test fails while they're stale.
+//
+// Usage:
+//   cargo run -p kala-rpc-codegen              Regenerate the bindings in sdk/
+//   cargo run -p kala-rpc-codegen -- <dir>     Write them under <dir> instead
+mod model;
+mod python;
+mod typescript;
+
+use anyhow::{Context, Result};
+use kala_rpc::openrpc;
+use model::Api;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() -> Result<()> {
+    let out_dir = env::args().nth(1).map(PathBuf::from).unwrap_or_else(sdk_dir);
+    for (path, contents) in bindings()? {
+        let path = out_dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Directory the bindings are committed in
+fn sdk_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("sdk")
+}
+
+/// Each binding's path under the SDK directory, with its contents
+fn bindings() -> Result<Vec<(&'static str, String)>> {
+    let api = Api::from_openrpc(&openrpc::full_document())
+        .context("OpenRPC document can't be expressed in the bindings")?;
+    Ok(vec![
+        ("typescript/kala.ts", typescript::render(&api)),
+        ("python/kala_client.py", python::render(&api)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_committed_bindings_are_current() {
+        for (path, contents) in bindings().unwrap() {
+            let committed = fs::read_to_string(sdk_dir().join(path)).unwrap_or_default();
+            assert!(
+                committed == contents,
+                "sdk/{} is stale, run `cargo run -p kala-rpc-codegen`",
+                path
+            );
+        }
+    }
+}
@@ -0,0 +1,593 @@
+//! API model read from the OpenRPC document
+//!
+//! The JSON schemas `schemars` derives from the RPC types are reduced to the
+//! few shapes both target languages can express: primitives, arrays,
+//! tuples, maps, unions and named objects. Objects nested inside another
+//! type, such as the variants of an externally tagged enum, are hoisted into
+//! named types of their own, so every object ends up with a name.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Prefix of references to the document's shared schemas
+const SCHEMAS_PREFIX: &str = "#/components/schemas/";
+
+/// Namespace of the public methods, left out of client method names
+const PUBLIC_NAMESPACE: &str = "kala";
+
+/// Type of a value on the wire
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    /// Anything the schema doesn't constrain
+    Any,
+    Null,
+    Bool,
+    Integer,
+    Number,
+    String,
+    /// A single constant value
+    Literal(Value),
+    Array(Box<Type>),
+    /// Fixed-length array with a type per position
+    Tuple(Vec<Type>),
+    /// Object with arbitrary keys
+    Map(Box<Type>),
+    /// Type defined in [`Api::types`]
+    Named(String),
+    /// The type or `null`
+    Nullable(Box<Type>),
+    /// One of several types, none of them `null`
+    Union(Vec<Type>),
+}
+
+/// Property of an object
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+    /// Whether the property is always present
+    pub required: bool,
+    pub doc: Option<String>,
+}
+
+/// What a named type stands for
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Object(Vec<Field>),
+    Alias(Type),
+}
+
+/// Named type of the API
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDef {
+    pub name: String,
+    pub doc: Option<String>,
+    pub shape: Shape,
+}
+
+/// Positional parameter of a method
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub ty: Type,
+}
+
+/// Method clients can call
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    /// Name on the wire, such as `kala_getTick`
+    pub name: String,
+    pub summary: Option<String>,
+    pub params: Vec<Param>,
+    pub result: Type,
+}
+
+impl Method {
+    /// Lowercase words of the client method name
+    ///
+    /// `kala_getTick` gives `get`, `tick`; other namespaces are kept, so
+    /// `admin_banPeer` gives `admin`, `ban`, `peer`.
+    pub fn words(&self) -> Vec<String> {
+        let mut parts: Vec<&str> = self.name.split(['_', '.']).collect();
+        if parts.len() > 1 && parts[0] == PUBLIC_NAMESPACE {
+            parts.remove(0);
+        }
+        parts.into_iter().flat_map(split_camel).collect()
+    }
+}
+
+/// Types and methods of the API
+#[derive(Debug, Clone, PartialEq)]
+pub struct Api {
+    /// Version of the crate the document was generated from
+    pub version: String,
+    /// Named types, each after the types it refers to
+    pub types: Vec<TypeDef>,
+    pub methods: Vec<Method>,
+}
+
+impl Api {
+    /// Read the API from an OpenRPC document
+    ///
+    /// Subscriptions, marked with `x-notification`, are left out as they
+    /// need a WebSocket connection.
+    ///
+    /// # Errors
+    ///
+    /// If the document is malformed or uses a schema construct the
+    /// bindings can't express
+    pub fn from_openrpc(document: &Value) -> Result<Self> {
+        let mut converter = Converter::default();
+
+        let schemas = document
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        for (name, schema) in &schemas {
+            converter.define(name, schema).with_context(|| format!("schema {}", name))?;
+        }
+
+        let mut methods = Vec::new();
+        let listed = document["methods"]
+            .as_array()
+            .ok_or_else(|| anyhow!("document has no methods"))?;
+        for method in listed {
+            if method.get("x-notification").is_some() {
+                continue;
+            }
+            let method = converter.method(method)?;
+            methods.push(method);
+        }
+
+        let version = document
+            .pointer("/info/version")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        Ok(Self {
+            version,
+            types: dependency_order(converter.types)?,
+            methods,
+        })
+    }
+}
+
+/// Builds named types from schemas
+#[derive(Default)]
+struct Converter {
+    types: Vec<TypeDef>,
+}
+
+impl Converter {
+    /// Add the shared schema `name`
+    fn define(&mut self, name: &str, schema: &Value) -> Result<()> {
+        let ty = self.convert(schema, name)?;
+        // Objects are hoisted under the name already
+        if ty != Type::Named(name.to_string()) {
+            self.types.push(TypeDef {
+                name: name.to_string(),
+                doc: description(schema),
+                shape: Shape::Alias(ty),
+            });
+        }
+        Ok(())
+    }
+
+    fn method(&mut self, method: &Value) -> Result<Method> {
+        let name = method["name"]
+            .as_str()
+            .ok_or_else(|| anyhow!("method without a name"))?
+            .to_string();
+        let hint = pascal_case(&name);
+
+        let mut params = Vec::new();
+        for param in method["params"].as_array().into_iter().flatten() {
+            let param_name = param["name"]
+                .as_str()
+                .ok_or_else(|| anyhow!("parameter of {} without a name", name))?;
+            let ty = self
+                .convert(&param["schema"], &format!("{}{}", hint, pascal_case(param_name)))
+                .with_context(|| format!("parameter {} of {}", param_name, name))?;
+            params.push(Param {
+                name: param_name.to_string(),
+                ty,
+            });
+        }
+        let result = self
+            .convert(&method["result"]["schema"], &format!("{}Result", hint))
+            .with_context(|| format!("result of {}", name))?;
+
+        Ok(Method {
+            summary: method["summary"].as_str().map(str::to_string),
+            name,
+            params,
+            result,
+        })
+    }
+
+    /// Type of `schema`, hoisting any object into a type named `hint`
+    fn convert(&mut self, schema: &Value, hint: &str) -> Result<Type> {
+        let schema = match schema {
+            Value::Bool(_) => return Ok(Type::Any),
+            Value::Object(schema) => schema,
+            other => bail!("schema is neither an object nor a boolean: {}", other),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference
+                .strip_prefix(SCHEMAS_PREFIX)
+                .ok_or_else(|| anyhow!("reference outside the shared schemas: {}", reference))?;
+            return Ok(Type::Named(name.to_string()));
+        }
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            match all_of.as_slice() {
+                [only] => return self.convert(only, hint),
+                _ => bail!("allOf of several schemas"),
+            }
+        }
+        if let Some(variants) = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array)
+        {
+            let mut types = Vec::new();
+            for (index, variant) in variants.iter().enumerate() {
+                let variant_hint = format!("{}{}", hint, variant_name(variant, index));
+                types.push(self.convert(variant, &variant_hint)?);
+            }
+            return Ok(union(types));
+        }
+        if let Some(value) = schema.get("const") {
+            return Ok(literal(value));
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return Ok(union(values.iter().map(literal).collect()));
+        }
+
+        match schema.get("type") {
+            None => Ok(Type::Any),
+            Some(Value::Array(types)) => {
+                let mut converted = Vec::new();
+                for ty in types {
+                    let mut single = schema.clone();
+                    single.insert("type".to_string(), ty.clone());
+                    converted.push(self.convert(&Value::Object(single), hint)?);
+                }
+                Ok(union(converted))
+            }
+            Some(Value::String(ty)) => match ty.as_str() {
+                "null" => Ok(Type::Null),
+                "boolean" => Ok(Type::Bool),
+                "integer" => Ok(Type::Integer),
+                "number" => Ok(Type::Number),
+                "string" => Ok(Type::String),
+                "array" => self.array(schema, hint),
+                "object" => self.object(schema, hint),
+                other => bail!("unknown type {}", other),
+            },
+            Some(other) => bail!("malformed type {}", other),
+        }
+    }
+
+    fn array(&mut self, schema: &Map<String, Value>, hint: &str) -> Result<Type> {
+        let positional = schema.get("prefixItems").or_else(|| schema.get("items"));
+        match positional {
+            Some(Value::Array(items)) => {
+                let mut types = Vec::new();
+                for (index, item) in items.iter().enumerate() {
+                    types.push(self.convert(item, &format!("{}{}", hint, index))?);
+                }
+                Ok(Type::Tuple(types))
+            }
+            Some(item) => Ok(Type::Array(Box::new(self.convert(item, &format!("{}Item", hint))?))),
+            None => Ok(Type::Array(Box::new(Type::Any))),
+        }
+    }
+
+    fn object(&mut self, schema: &Map<String, Value>, hint: &str) -> Result<Type> {
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            let value = match schema.get("additionalProperties") {
+                Some(Value::Object(_)) => {
+                    self.convert(&schema["additionalProperties"], &format!("{}Value", hint))?
+                }
+                _ => Type::Any,
+            };
+            return Ok(Type::Map(Box::new(value)));
+        };
+
+        let required: HashSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let mut fields = Vec::new();
+        for (name, property) in properties {
+            let ty = self
+                .convert(property, &format!("{}{}", hint, pascal_case(name)))
+                .with_context(|| format!("property {}", name))?;
+            fields.push(Field {
+                name: name.clone(),
+                ty,
+                required: required.contains(name.as_str()),
+                doc: description(property),
+            });
+        }
+
+        self.types.push(TypeDef {
+            name: hint.to_string(),
+            doc: schema.get("description").and_then(Value::as_str).map(str::to_string),
+            shape: Shape::Object(fields),
+        });
+        Ok(Type::Named(hint.to_string()))
+    }
+}
+
+/// Name suffix of a hoisted union variant
+///
+/// Externally tagged variants are objects with a single property, named
+/// after the variant.
+fn variant_name(variant: &Value, index: usize) -> String {
+    let properties = variant.get("properties").and_then(Value::as_object);
+    match properties {
+        Some(properties) if properties.len() == 1 => {
+            properties.keys().map(|key| pascal_case(key)).collect()
+        }
+        _ => format!("Variant{}", index),
+    }
+}
+
+fn literal(value: &Value) -> Type {
+    match value {
+        Value::Null => Type::Null,
+        other => Type::Literal(other.clone()),
+    }
+}
+
+/// Union of `types`, flattened, with `null` pulled out into [`Type::Nullable`]
+fn union(types: Vec<Type>) -> Type {
+    let mut nullable = false;
+    let mut members: Vec<Type> = Vec::new();
+    for ty in types {
+        let flattened = match ty {
+            Type::Null => {
+                nullable = true;
+                vec![]
+            }
+            Type::Nullable(inner) => {
+                nullable = true;
+                vec![*inner]
+            }
+            Type::Union(inner) => inner,
+            other => vec![other],
+        };
+        for ty in flattened {
+            if !members.contains(&ty) {
+                members.push(ty);
+            }
+        }
+    }
+
+    let ty = match members.len() {
+        0 => return Type::Null,
+        1 => members.remove(0),
+        _ => Type::Union(members),
+    };
+    if nullable {
+        Type::Nullable(Box::new(ty))
+    } else {
+        ty
+    }
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema.get("description").and_then(Value::as_str).map(str::to_string)
+}
+
+/// Order `types` so each comes after the types it refers to
+///
+/// Ties keep the order by name, so the output is stable.
+fn dependency_order(mut types: Vec<TypeDef>) -> Result<Vec<TypeDef>> {
+    types.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut names = HashSet::new();
+    if let Some(duplicate) = types.iter().find(|def| !names.insert(def.name.clone())) {
+        bail!("type {} is defined twice", duplicate.name);
+    }
+
+    fn visit(index: usize, types: &[TypeDef], done: &mut Vec<bool>, order: &mut Vec<usize>) {
+        if done[index] {
+            return;
+        }
+        done[index] = true;
+        let mut references = Vec::new();
+        match &types[index].shape {
+            Shape::Object(fields) => fields.iter().for_each(|f| collect(&f.ty, &mut references)),
+            Shape::Alias(ty) => collect(ty, &mut references),
+        }
+        for name in references {
+            if let Some(position) = types.iter().position(|def| def.name == name) {
+                visit(position, types, done, order);
+            }
+        }
+        order.push(index);
+    }
+
+    let mut done = vec![false; types.len()];
+    let mut order = Vec::new();
+    for index in 0..types.len() {
+        visit(index, &types, &mut done, &mut order);
+    }
+    let mut slots: Vec<Option<TypeDef>> = types.into_iter().map(Some).collect();
+    Ok(order.into_iter().filter_map(|index| slots[index].take()).collect())
+}
+
+/// Names of the types `ty` refers to
+fn collect(ty: &Type, names: &mut Vec<String>) {
+    match ty {
+        Type::Named(name) => names.push(name.clone()),
+        Type::Array(inner) | Type::Map(inner) | Type::Nullable(inner) => collect(inner, names),
+        Type::Tuple(types) | Type::Union(types) => types.iter().for_each(|t| collect(t, names)),
+        _ => {}
+    }
+}
+
+/// Lowercase words of a camelCase identifier
+fn split_camel(identifier: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut previous_lower = false;
+    for c in identifier.chars() {
+        if (c.is_uppercase() && previous_lower) || words.is_empty() {
+            words.push(String::new());
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        if let Some(word) = words.last_mut() {
+            word.extend(c.to_lowercase());
+        }
+    }
+    words
+}
+
+/// PascalCase of a snake_case or camelCase name
+pub fn pascal_case(name: &str) -> String {
+    name.split(['_', '.'])
+        .flat_map(split_camel)
+        .map(|word| capitalize(&word))
+        .collect()
+}
+
+/// `word` with its first letter uppercase
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn api(schemas: Value, methods: Value) -> Api {
+        let document = json!({
+            "info": { "version": "0.0.2" },
+            "methods": methods,
+            "components": { "schemas": schemas },
+        });
+        Api::from_openrpc(&document).unwrap()
+    }
+
+    #[test]
+    fn test_option_becomes_nullable() {
+        let api = api(
+            json!({
+                "Account": {
+                    "type": "object",
+                    "properties": {
+                        "delegation": { "type": ["string", "null"] },
+                        "nonce": { "type": "integer", "format": "uint64" },
+                    },
+                    "required": ["nonce"],
+                },
+            }),
+            json!([{
+                "name": "kala_getAccount",
+                "params": [],
+                "result": {
+                    "name": "result",
+                    "schema": {
+                        "anyOf": [
+                            { "$ref": "#/components/schemas/Account" },
+                            { "type": "null" },
+                        ],
+                    },
+                },
+            }]),
+        );
+
+        let Shape::Object(fields) = &api.types[0].shape else {
+            panic!("Account should be an object");
+        };
+        assert_eq!(fields[0].ty, Type::Nullable(Box::new(Type::String)));
+        assert!(!fields[0].required);
+        assert!(fields[1].required);
+        assert_eq!(
+            api.methods[0].result,
+            Type::Nullable(Box::new(Type::Named("Account".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_tagged_variants_are_hoisted() {
+        let api = api(
+            json!({
+                "Signatures": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "properties": { "ed25519": { "type": "array" } },
+                            "required": ["ed25519"],
+                        },
+                        { "const": "none" },
+                    ],
+                },
+            }),
+            json!([]),
+        );
+
+        let names: Vec<&str> = api.types.iter().map(|def| def.name.as_str()).collect();
+        // The variant is defined before the union referring to it
+        assert_eq!(names, ["SignaturesEd25519", "Signatures"]);
+        assert_eq!(
+            api.types[1].shape,
+            Shape::Alias(Type::Union(vec![
+                Type::Named("SignaturesEd25519".to_string()),
+                Type::Literal(json!("none")),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_subscriptions_are_left_out() {
+        let api = api(
+            json!({}),
+            json!([
+                {
+                    "name": "kala_watchAccount",
+                    "params": [{ "name": "address", "schema": { "type": "string" } }],
+                    "result": { "name": "result", "schema": { "type": "integer" } },
+                    "x-notification": { "name": "kala_accountUpdate", "schema": {} },
+                },
+                {
+                    "name": "admin_setLogLevel",
+                    "params": [{ "name": "filter", "schema": { "type": "string" } }],
+                    "result": { "name": "result", "schema": { "type": "null" } },
+                },
+            ]),
+        );
+
+        assert_eq!(api.methods.len(), 1);
+        assert_eq!(api.methods[0].params[0].ty, Type::String);
+        assert_eq!(api.methods[0].result, Type::Null);
+    }
+
+    #[test]
+    fn test_method_words() {
+        let method = |name: &str| Method {
+            name: name.to_string(),
+            summary: None,
+            params: vec![],
+            result: Type::Any,
+        };
+
+        assert_eq!(method("kala_getTickRangeProof").words(), ["get", "tick", "range", "proof"]);
+        assert_eq!(method("kala_debugFfiMemory").words(), ["debug", "ffi", "memory"]);
+        assert_eq!(method("admin_banPeer").words(), ["admin", "ban", "peer"]);
+        assert_eq!(pascal_case("kala_getTick"), "KalaGetTick");
+        assert_eq!(pascal_case("bls12381"), "Bls12381");
+    }
+}
@@ -0,0 +1,311 @@
+//! Python bindings
+//!
+//! Objects become `TypedDict`s and everything else a type alias, so
+//! results stay the plain dictionaries `json` decodes while type checkers
+//! know their keys. The client only uses the standard library and needs
+//! Python 3.11+ for `NotRequired`.
+
+use crate::model::{Api, Field, Method, Shape, Type, TypeDef};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Imports of the module
+const IMPORTS: &str = r#"from __future__ import annotations
+
+import json
+import urllib.error
+import urllib.request
+from typing import (
+    Any,
+    Dict,
+    List,
+    Literal,
+    NotRequired,
+    Optional,
+    Tuple,
+    TypedDict,
+    Union,
+)
+"#;
+
+/// Error class and the transport of the client
+const CLIENT_PRELUDE: &str = r#"class KalaRpcError(Exception):
+    """Error a node answered a JSON-RPC call with"""
+
+    def __init__(self, code: int, message: str, data: Any = None) -> None:
+        super().__init__(message)
+        self.code = code
+        self.message = message
+        self.data = data
+
+
+class KalaClient:
+    """Client of a Kala node's JSON-RPC API over HTTP
+
+    ``headers`` are sent with every call, such as an ``Authorization``
+    bearer token for the ``admin_*`` methods.
+    """
+
+    def __init__(
+        self,
+        url: str,
+        headers: Optional[Dict[str, str]] = None,
+        timeout: float = 30.0,
+    ) -> None:
+        self.url = url
+        self.headers = dict(headers or {})
+        self.timeout = timeout
+        self._next_id = 1
+
+    def _call(self, method: str, params: List[Any]) -> Any:
+        body = {"jsonrpc": "2.0", "id": self._next_id, "method": method, "params": params}
+        self._next_id += 1
+        request = urllib.request.Request(
+            self.url,
+            data=json.dumps(body).encode(),
+            headers={"Content-Type": "application/json", **self.headers},
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=self.timeout) as response:
+                reply = json.load(response)
+        except urllib.error.HTTPError as error:
+            # Rate limits and oversized requests are still answered in JSON-RPC
+            try:
+                reply = json.load(error)
+            except ValueError:
+                raise error from None
+        if "error" in reply:
+            error = reply["error"]
+            raise KalaRpcError(error["code"], error["message"], error.get("data"))
+        return reply.get("result")
+"#;
+
+/// Python keywords, which can't name a `TypedDict` field in class syntax
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+    "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+    "try", "while", "with", "yield",
+];
+
+/// Render the bindings as a Python module
+pub fn render(api: &Api) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Kala JSON-RPC client, generated from the OpenRPC document of kala-rpc {}.",
+        api.version
+    );
+    out.push_str("# Do not edit: run `cargo run -p kala-rpc-codegen` to regenerate.\n");
+    out.push_str("\"\"\"Typed client of the Kala JSON-RPC API\"\"\"\n\n");
+    out.push_str(IMPORTS);
+
+    for def in &api.types {
+        out.push_str("\n\n");
+        render_type(&mut out, def);
+    }
+
+    out.push_str("\n\n");
+    out.push_str(CLIENT_PRELUDE);
+    for method in &api.methods {
+        out.push('\n');
+        render_method(&mut out, method);
+    }
+    out
+}
+
+fn render_type(out: &mut String, def: &TypeDef) {
+    match &def.shape {
+        Shape::Object(fields) if fields.iter().all(|field| is_identifier(&field.name)) => {
+            let _ = writeln!(out, "class {}(TypedDict):", def.name);
+            match &def.doc {
+                Some(doc) => docstring(out, "    ", doc),
+                None if fields.is_empty() => out.push_str("    pass\n"),
+                None => {}
+            }
+            for field in fields {
+                out.push('\n');
+                comment(out, "    ", field.doc.as_deref());
+                let _ = writeln!(out, "    {}: {}", field.name, field_type(field));
+            }
+        }
+        Shape::Object(fields) => {
+            // Functional syntax, for fields named like keywords
+            comment(out, "", def.doc.as_deref());
+            let _ = writeln!(out, "{} = TypedDict(", def.name);
+            let _ = writeln!(out, "    \"{}\",", def.name);
+            out.push_str("    {\n");
+            for field in fields {
+                comment(out, "        ", field.doc.as_deref());
+                let _ = writeln!(out, "        \"{}\": {},", field.name, field_type(field));
+            }
+            out.push_str("    },\n");
+            out.push_str(")\n");
+        }
+        Shape::Alias(ty) => {
+            comment(out, "", def.doc.as_deref());
+            let _ = writeln!(out, "{} = {}", def.name, type_name(ty));
+        }
+    }
+}
+
+fn render_method(out: &mut String, method: &Method) {
+    let mut params = vec!["self".to_string()];
+    params.extend(
+        method.params.iter().map(|param| format!("{}: {}", param.name, type_name(&param.ty))),
+    );
+    let args: Vec<&str> = method.params.iter().map(|param| param.name.as_str()).collect();
+
+    let _ = writeln!(
+        out,
+        "    def {}({}) -> {}:",
+        method.words().join("_"),
+        params.join(", "),
+        type_name(&method.result)
+    );
+    if let Some(summary) = &method.summary {
+        docstring(out, "        ", summary);
+    }
+    let _ = writeln!(out, "        return self._call(\"{}\", [{}])", method.name, args.join(", "));
+}
+
+fn field_type(field: &Field) -> String {
+    if field.required {
+        type_name(&field.ty)
+    } else {
+        format!("NotRequired[{}]", type_name(&field.ty))
+    }
+}
+
+/// Python annotation of `ty`
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Any => "Any".to_string(),
+        Type::Null => "None".to_string(),
+        Type::Bool => "bool".to_string(),
+        Type::Integer => "int".to_string(),
+        Type::Number => "float".to_string(),
+        Type::String => "str".to_string(),
+        Type::Literal(value) => format!("Literal[{}]", literal(value)),
+        Type::Array(item) => format!("List[{}]", type_name(item)),
+        Type::Tuple(types) => {
+            let types: Vec<String> = types.iter().map(type_name).collect();
+            format!("Tuple[{}]", types.join(", "))
+        }
+        Type::Map(value) => format!("Dict[str, {}]", type_name(value)),
+        Type::Named(name) => name.clone(),
+        Type::Nullable(inner) => format!("Optional[{}]", type_name(inner)),
+        Type::Union(types) => {
+            // A union of constants reads better as one Literal
+            let literals: Option<Vec<String>> = types
+                .iter()
+                .map(|ty| match ty {
+                    Type::Literal(value) => Some(literal(value)),
+                    _ => None,
+                })
+                .collect();
+            match literals {
+                Some(literals) => format!("Literal[{}]", literals.join(", ")),
+                None => {
+                    let types: Vec<String> = types.iter().map(type_name).collect();
+                    format!("Union[{}]", types.join(", "))
+                }
+            }
+        }
+    }
+}
+
+/// Python literal of a JSON constant
+fn literal(value: &Value) -> String {
+    match value {
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Null => "None".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_well = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    starts_well
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name)
+}
+
+/// Write `doc` as `#` comments indented by `indent`
+fn comment(out: &mut String, indent: &str, doc: Option<&str>) {
+    for line in doc.into_iter().flat_map(str::lines) {
+        let _ = writeln!(out, "{}#{}{}", indent, if line.is_empty() { "" } else { " " }, line);
+    }
+}
+
+/// Write `doc` as a docstring indented by `indent`
+fn docstring(out: &mut String, indent: &str, doc: &str) {
+    let doc = doc.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    let mut lines = doc.lines();
+    let first = lines.next().unwrap_or_default();
+    let rest: Vec<&str> = lines.collect();
+    if rest.is_empty() {
+        let _ = writeln!(out, "{}\"\"\"{}\"\"\"", indent, first);
+        return;
+    }
+    let _ = writeln!(out, "{}\"\"\"{}", indent, first);
+    for line in rest {
+        if line.is_empty() {
+            out.push('\n');
+        } else {
+            let _ = writeln!(out, "{}{}", indent, line);
+        }
+    }
+    let _ = writeln!(out, "{}\"\"\"", indent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_type_names() {
+        let named = |name: &str| Box::new(Type::Named(name.to_string()));
+
+        assert_eq!(
+            type_name(&Type::Nullable(named("TickCertificate"))),
+            "Optional[TickCertificate]"
+        );
+        assert_eq!(type_name(&Type::Array(Box::new(Type::Integer))), "List[int]");
+        assert_eq!(
+            type_name(&Type::Union(vec![
+                Type::Literal(json!("syncing")),
+                Type::Literal(json!("following")),
+            ])),
+            "Literal[\"syncing\", \"following\"]"
+        );
+        assert_eq!(
+            type_name(&Type::Union(vec![Type::Named("A".to_string()), Type::String])),
+            "Union[A, str]"
+        );
+    }
+
+    #[test]
+    fn test_keyword_fields_use_functional_syntax() {
+        let def = TypeDef {
+            name: "Range".to_string(),
+            doc: None,
+            shape: Shape::Object(vec![Field {
+                name: "from".to_string(),
+                ty: Type::Integer,
+                required: true,
+                doc: None,
+            }]),
+        };
+        let mut out = String::new();
+        render_type(&mut out, &def);
+
+        assert_eq!(
+            out,
+            "Range = TypedDict(\n    \"Range\",\n    {\n        \"from\": int,\n    },\n)\n"
+        );
+    }
+}
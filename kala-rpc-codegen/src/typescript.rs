@@ -0,0 +1,197 @@
+//! TypeScript bindings
+//!
+//! Objects become interfaces and everything else a type alias. The client
+//! uses the global `fetch`, so it runs in browsers, Node.js 18+, Deno and
+//! Bun without dependencies.
+
+use crate::model::{capitalize, Api, Method, Shape, Type, TypeDef};
+use std::fmt::Write;
+
+/// Error class and the transport of the client
+const CLIENT_PRELUDE: &str = r#"/** Error a node answered a JSON-RPC call with */
+export class KalaRpcError extends Error {
+  constructor(
+    readonly code: number,
+    message: string,
+    readonly data?: unknown,
+  ) {
+    super(message);
+    this.name = "KalaRpcError";
+  }
+}
+
+/**
+ * Client of a Kala node's JSON-RPC API over HTTP
+ *
+ * `headers` are sent with every call, such as an `Authorization` bearer
+ * token for the `admin_*` methods.
+ */
+export class KalaClient {
+  private nextId = 1;
+
+  constructor(
+    readonly url: string,
+    readonly headers: Record<string, string> = {},
+  ) {}
+
+  private async call<T>(method: string, params: unknown[]): Promise<T> {
+    const response = await fetch(this.url, {
+      method: "POST",
+      headers: { "Content-Type": "application/json", ...this.headers },
+      body: JSON.stringify({ jsonrpc: "2.0", id: this.nextId++, method, params }),
+    });
+    const reply = await response.json();
+    if (reply.error) {
+      throw new KalaRpcError(reply.error.code, reply.error.message, reply.error.data);
+    }
+    return reply.result as T;
+  }
+"#;
+
+/// Render the bindings as a TypeScript module
+pub fn render(api: &Api) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Kala JSON-RPC client, generated from the OpenRPC document of kala-rpc {}.",
+        api.version
+    );
+    out.push_str("// Do not edit: run `cargo run -p kala-rpc-codegen` to regenerate.\n");
+    out.push_str("//\n");
+    out.push_str("// Integers are plain numbers, exact up to 2^53.\n");
+
+    for def in &api.types {
+        out.push('\n');
+        render_type(&mut out, def);
+    }
+
+    out.push('\n');
+    out.push_str(CLIENT_PRELUDE);
+    for method in &api.methods {
+        out.push('\n');
+        render_method(&mut out, method);
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_type(out: &mut String, def: &TypeDef) {
+    doc_comment(out, "", def.doc.as_deref());
+    match &def.shape {
+        Shape::Object(fields) => {
+            let _ = writeln!(out, "export interface {} {{", def.name);
+            for field in fields {
+                doc_comment(out, "  ", field.doc.as_deref());
+                let optional = if field.required { "" } else { "?" };
+                let _ = writeln!(out, "  {}{}: {};", field.name, optional, type_name(&field.ty));
+            }
+            out.push_str("}\n");
+        }
+        Shape::Alias(ty) => {
+            let _ = writeln!(out, "export type {} = {};", def.name, type_name(ty));
+        }
+    }
+}
+
+fn render_method(out: &mut String, method: &Method) {
+    doc_comment(out, "  ", method.summary.as_deref());
+    let params: Vec<String> = method
+        .params
+        .iter()
+        .map(|param| format!("{}: {}", camel_case(&param.name), type_name(&param.ty)))
+        .collect();
+    let args: Vec<String> = method.params.iter().map(|param| camel_case(&param.name)).collect();
+    let _ = writeln!(
+        out,
+        "  {}({}): Promise<{}> {{",
+        method_name(method),
+        params.join(", "),
+        type_name(&method.result)
+    );
+    let _ = writeln!(out, "    return this.call(\"{}\", [{}]);", method.name, args.join(", "));
+    out.push_str("  }\n");
+}
+
+/// TypeScript type of `ty`
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Any => "unknown".to_string(),
+        Type::Null => "null".to_string(),
+        Type::Bool => "boolean".to_string(),
+        Type::Integer | Type::Number => "number".to_string(),
+        Type::String => "string".to_string(),
+        Type::Literal(value) => value.to_string(),
+        Type::Array(item) => match **item {
+            Type::Nullable(_) | Type::Union(_) => format!("Array<{}>", type_name(item)),
+            _ => format!("{}[]", type_name(item)),
+        },
+        Type::Tuple(types) => {
+            let types: Vec<String> = types.iter().map(type_name).collect();
+            format!("[{}]", types.join(", "))
+        }
+        Type::Map(value) => format!("Record<string, {}>", type_name(value)),
+        Type::Named(name) => name.clone(),
+        Type::Nullable(inner) => format!("{} | null", type_name(inner)),
+        Type::Union(types) => {
+            let types: Vec<String> = types.iter().map(type_name).collect();
+            types.join(" | ")
+        }
+    }
+}
+
+fn method_name(method: &Method) -> String {
+    let words = method.words();
+    let mut name = words.first().cloned().unwrap_or_default();
+    name.extend(words.iter().skip(1).map(|word| capitalize(word)));
+    name
+}
+
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut camel = words.next().unwrap_or_default().to_string();
+    camel.extend(words.map(capitalize));
+    camel
+}
+
+/// Write `doc` as a JSDoc comment indented by `indent`
+fn doc_comment(out: &mut String, indent: &str, doc: Option<&str>) {
+    let Some(doc) = doc else {
+        return;
+    };
+    let lines: Vec<&str> = doc.lines().collect();
+    if let [line] = lines.as_slice() {
+        let _ = writeln!(out, "{}/** {} */", indent, line);
+        return;
+    }
+    let _ = writeln!(out, "{}/**", indent);
+    for line in lines {
+        let _ = writeln!(out, "{} *{}{}", indent, if line.is_empty() { "" } else { " " }, line);
+    }
+    let _ = writeln!(out, "{} */", indent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_type_names() {
+        let named = |name: &str| Box::new(Type::Named(name.to_string()));
+
+        assert_eq!(type_name(&Type::Nullable(named("TickCertificate"))), "TickCertificate | null");
+        assert_eq!(type_name(&Type::Array(Box::new(Type::Integer))), "number[]");
+        assert_eq!(
+            type_name(&Type::Array(Box::new(Type::Nullable(named("Tick"))))),
+            "Array<Tick | null>"
+        );
+        assert_eq!(
+            type_name(&Type::Union(vec![
+                Type::Literal(json!("syncing")),
+                Type::Literal(json!("following")),
+            ])),
+            "\"syncing\" | \"following\""
+        );
+        assert_eq!(type_name(&Type::Map(Box::new(Type::Integer))), "Record<string, number>");
+    }
+}
@@ -0,0 +1,425 @@
+//! Admin JSON-RPC namespace for node operators
+//!
+//! Routine operations, such as banning a misbehaving peer, dropping a
+//! clogged mempool, exporting a snapshot, pruning old ticks or turning up
+//! logging, are served as `admin_*` methods so they need neither a restart
+//! nor direct access to the database.
+//!
+//! The namespace is only served when the node is configured with an admin
+//! token. [`AdminAuthLayer`] checks the `Authorization: Bearer <token>`
+//! header of each HTTP request and marks the requests that carry the token
+//! with [`AdminAuthorized`]; every admin method calls [`authorize`] first,
+//! so calls without it fail with [`UNAUTHORIZED_CODE`]. Public `kala_*`
+//! methods are served with or without the token.
+
+use jsonrpsee::core::{BoxError, RpcResult};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::server::{HttpRequest, HttpResponse};
+use jsonrpsee::types::error::ErrorObject;
+use jsonrpsee::Extensions;
+use kala_common::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Error code of admin calls made without the admin token
+pub const UNAUTHORIZED_CODE: i32 = -32001;
+
+/// Marks a request that carried the admin token
+///
+/// Inserted into the request's extensions by [`AdminAuthLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdminAuthorized;
+
+/// Fail unless the call came with the admin token
+///
+/// # Errors
+///
+/// [`UNAUTHORIZED_CODE`] if `ext` lacks [`AdminAuthorized`]
+pub fn authorize(ext: &Extensions) -> RpcResult<()> {
+    if ext.get::<AdminAuthorized>().is_some() {
+        return Ok(());
+    }
+    Err(ErrorObject::owned(
+        UNAUTHORIZED_CODE,
+        "Admin token missing or invalid",
+        None::<()>,
+    ))
+}
+
+/// Tower layer marking requests that carry the admin token
+///
+/// Without a token no request is marked, so every admin call is refused.
+#[derive(Debug, Clone)]
+pub struct AdminAuthLayer {
+    token: Option<Arc<str>>,
+}
+
+impl AdminAuthLayer {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.map(Arc::from),
+        }
+    }
+}
+
+impl<S> Layer<S> for AdminAuthLayer {
+    type Service = AdminAuth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AdminAuth {
+            inner,
+            token: self.token.clone(),
+        }
+    }
+}
+
+/// Service marking the requests of an [`AdminAuthLayer`]
+#[derive(Debug, Clone)]
+pub struct AdminAuth<S> {
+    inner: S,
+    token: Option<Arc<str>>,
+}
+
+impl<S> Service<HttpRequest> for AdminAuth<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<HttpResponse, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: HttpRequest) -> Self::Future {
+        // Never trust a marker the request arrived with
+        request.extensions_mut().remove::<AdminAuthorized>();
+        let presented = request
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if let (Some(token), Some(presented)) = (&self.token, presented) {
+            if constant_time_eq(token.as_bytes(), presented.trim().as_bytes()) {
+                request.extensions_mut().insert(AdminAuthorized);
+            }
+        }
+
+        // Take the service that was polled ready, leaving a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move { inner.call(request).await })
+    }
+}
+
+/// Compare without returning early, so timing doesn't reveal the token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// A peer known to the node
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct PeerInfo {
+    /// Base58 libp2p peer ID
+    pub peer_id: String,
+    /// Multiaddrs the peer was reached on or advertised
+    pub addresses: Vec<String>,
+    /// Unix timestamp of the last connection to the peer
+    pub last_seen: u64,
+    /// Accumulated reputation score
+    pub reputation: i32,
+    /// Whether the peer is refused and never redialed
+    pub banned: bool,
+    /// Whether the peer is connected now
+    pub connected: bool,
+}
+
+/// A transaction waiting in the node's mempool
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct MempoolEntry {
+    /// Hex-encoded envelope hash
+    pub tx_hash: String,
+    /// VDF iteration the node timestamped the transaction at
+    pub submission_iteration: IterationNumber,
+    /// Tick the transaction is ordered into
+    pub target_tick: BlockHeight,
+    /// How the key is locked, `puzzle` or `threshold`
+    pub lock: String,
+    /// Size of the sealed payload, in bytes
+    pub ciphertext_bytes: usize,
+}
+
+/// Snapshot written by `admin_exportSnapshot`
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct SnapshotExport {
+    /// Directory the snapshot was written to, on the node's machine
+    pub path: String,
+    /// Hex-encoded state root of the exported state
+    pub state_root: String,
+}
+
+/// Admin JSON-RPC API for node operators
+///
+/// Implementations must call [`authorize`] with the call's extensions
+/// before doing anything else.
+#[rpc(server)]
+pub trait KalaAdminApi {
+    /// List the peers the node knows, connected or not
+    ///
+    /// # Returns
+    ///
+    /// `Vec<PeerInfo>` - Known peers; empty if the node isn't on the
+    /// gossip network
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_peers",
+    ///   "id": 22
+    /// }
+    /// ```
+    #[method(name = "admin_peers", with_extensions)]
+    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+
+    /// Ban a peer, disconnecting it and never redialing it
+    ///
+    /// The ban is recorded as the lowest reputation, so it persists across
+    /// restarts.
+    ///
+    /// # Parameters
+    ///
+    /// - `peer_id`: Base58 libp2p peer ID
+    ///
+    /// # Returns
+    ///
+    /// [`PeerInfo`] of the banned peer
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_banPeer",
+    ///   "params": ["12D3KooW..."],
+    ///   "id": 23
+    /// }
+    /// ```
+    #[method(name = "admin_banPeer", with_extensions)]
+    async fn ban_peer(&self, peer_id: String) -> RpcResult<PeerInfo>;
+
+    /// List the transactions waiting in the mempool
+    ///
+    /// # Returns
+    ///
+    /// `Vec<MempoolEntry>` - Pending transactions, in arrival order
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_mempool",
+    ///   "id": 24
+    /// }
+    /// ```
+    #[method(name = "admin_mempool", with_extensions)]
+    async fn mempool(&self) -> RpcResult<Vec<MempoolEntry>>;
+
+    /// Drop every transaction waiting in the mempool
+    ///
+    /// Dropped transactions are traced as such, so their submitters can
+    /// see why they never landed.
+    ///
+    /// # Returns
+    ///
+    /// `usize` - Number of transactions dropped
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_flushMempool",
+    ///   "id": 25
+    /// }
+    /// ```
+    #[method(name = "admin_flushMempool", with_extensions)]
+    async fn flush_mempool(&self) -> RpcResult<usize>;
+
+    /// Write a verified snapshot of the state database
+    ///
+    /// The snapshot is a checkpoint of the live database, taken without
+    /// stopping the node, which `devnode restore` can start a node from.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Directory to write to on the node's machine; must not exist
+    ///
+    /// # Returns
+    ///
+    /// [`SnapshotExport`] with the state root of the snapshot
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_exportSnapshot",
+    ///   "params": ["/var/backups/kala-2026-10-16"],
+    ///   "id": 26
+    /// }
+    /// ```
+    #[method(name = "admin_exportSnapshot", with_extensions)]
+    async fn export_snapshot(&self, path: String) -> RpcResult<SnapshotExport>;
+
+    /// Move settled ticks past the retention window to the archive now
+    ///
+    /// Runs the pruning the node otherwise does at each epoch boundary,
+    /// waiting for one already in progress to finish first.
+    ///
+    /// # Returns
+    ///
+    /// `u64` - First tick still held locally
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_pruneNow",
+    ///   "id": 27
+    /// }
+    /// ```
+    #[method(name = "admin_pruneNow", with_extensions)]
+    async fn prune_now(&self) -> RpcResult<u64>;
+
+    /// Change which log lines the node writes
+    ///
+    /// # Parameters
+    ///
+    /// - `filter`: Level or directives, such as `debug` or
+    ///   `info,kala_core::network=trace`
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "admin_setLogLevel",
+    ///   "params": ["debug"],
+    ///   "id": 28
+    /// }
+    /// ```
+    #[method(name = "admin_setLogLevel", with_extensions)]
+    async fn set_log_level(&self, filter: String) -> RpcResult<()>;
+}
+
+impl KalaSerialize for PeerInfo {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for MempoolEntry {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for SnapshotExport {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::server::HttpBody;
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "s3cret-admin-token";
+
+    /// Send a request through [`AdminAuth`], returning whether it was marked
+    async fn authorized(token: Option<&str>, header: Option<&str>, forged: bool) -> bool {
+        let inner = tower::service_fn(|request: HttpRequest| async move {
+            let mut response = HttpResponse::new(HttpBody::empty());
+            if request.extensions().get::<AdminAuthorized>().is_none() {
+                *response.status_mut() = axum::http::StatusCode::UNAUTHORIZED;
+            }
+            Ok::<_, BoxError>(response)
+        });
+        let service = AdminAuthLayer::new(token.map(str::to_string)).layer(inner);
+
+        let mut request = HttpRequest::new(HttpBody::empty());
+        if let Some(header) = header {
+            request.headers_mut().insert("authorization", header.parse().unwrap());
+        }
+        if forged {
+            request.extensions_mut().insert(AdminAuthorized);
+        }
+        service.oneshot(request).await.unwrap().status().is_success()
+    }
+
+    #[tokio::test]
+    async fn test_correct_token() {
+        assert!(authorized(Some(TOKEN), Some(&format!("Bearer {}", TOKEN)), false).await);
+        // Surrounding whitespace is ignored
+        assert!(authorized(Some(TOKEN), Some(&format!("Bearer {} ", TOKEN)), false).await);
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_wrong_token() {
+        assert!(!authorized(Some(TOKEN), None, false).await);
+        assert!(!authorized(Some(TOKEN), Some(TOKEN), false).await);
+        assert!(!authorized(Some(TOKEN), Some("Bearer s3cret-admin-tokeN"), false).await);
+        // Shorter and longer tokens, including prefixes of the real one
+        assert!(!authorized(Some(TOKEN), Some("Bearer s3cret"), false).await);
+        assert!(!authorized(Some(TOKEN), Some("Bearer s3cret-admin-token-2"), false).await);
+        assert!(!authorized(Some(TOKEN), Some("Bearer "), false).await);
+        // Without a configured token nothing is authorized
+        assert!(!authorized(None, Some(&format!("Bearer {}", TOKEN)), false).await);
+    }
+
+    #[tokio::test]
+    async fn test_forged_marker_stripped() {
+        assert!(!authorized(Some(TOKEN), None, true).await);
+        assert!(!authorized(Some(TOKEN), Some("Bearer wrong"), true).await);
+        assert!(!authorized(None, None, true).await);
+        assert!(authorized(Some(TOKEN), Some(&format!("Bearer {}", TOKEN)), true).await);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"token", b"tokeN"));
+        assert!(!constant_time_eq(b"token", b"toke"));
+        assert!(!constant_time_eq(b"toke", b"token"));
+    }
+
+    #[test]
+    fn test_authorize() {
+        let mut extensions = Extensions::new();
+        let error = authorize(&extensions).unwrap_err();
+        assert_eq!(error.code(), UNAUTHORIZED_CODE);
+
+        extensions.insert(AdminAuthorized);
+        assert!(authorize(&extensions).is_ok());
+    }
+}
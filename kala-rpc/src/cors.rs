@@ -0,0 +1,123 @@
+//! Cross-origin access to the JSON-RPC server
+//!
+//! Browsers only let a page read responses from another origin when the
+//! server allows that origin. [`CorsLayer`] answers preflight `OPTIONS`
+//! requests and adds `Access-Control-Allow-Origin` to responses for the
+//! origins in its allow-list, so dApps served elsewhere can call the node
+//! directly. Requests from other origins are still served, without the
+//! headers, as CORS only restricts what browsers expose to pages.
+
+use jsonrpsee::core::BoxError;
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// How long browsers may cache a preflight answer, in seconds
+const PREFLIGHT_MAX_AGE_SECS: &str = "86400";
+
+/// Tower layer adding CORS headers for an allow-list of origins
+///
+/// An origin is matched exactly, such as `https://app.example.com`; `*`
+/// allows every origin. An empty list allows none, leaving the server
+/// same-origin only.
+#[derive(Debug, Clone)]
+pub struct CorsLayer {
+    allowed_origins: Arc<Vec<String>>,
+}
+
+impl CorsLayer {
+    pub fn new(allowed_origins: Vec<String>) -> Self {
+        Self {
+            allowed_origins: Arc::new(allowed_origins),
+        }
+    }
+}
+
+impl<S> Layer<S> for CorsLayer {
+    type Service = Cors<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Cors {
+            inner,
+            allowed_origins: self.allowed_origins.clone(),
+        }
+    }
+}
+
+/// Service adding the CORS headers of a [`CorsLayer`]
+#[derive(Debug, Clone)]
+pub struct Cors<S> {
+    inner: S,
+    allowed_origins: Arc<Vec<String>>,
+}
+
+impl<S> Cors<S> {
+    /// Value of `Access-Control-Allow-Origin` for `origin`, if allowed
+    fn allow_origin(&self, origin: &str) -> Option<String> {
+        self.allowed_origins
+            .iter()
+            .find(|allowed| *allowed == "*" || *allowed == origin)
+            .cloned()
+    }
+}
+
+impl<S> Service<HttpRequest> for Cors<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<HttpResponse, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest) -> Self::Future {
+        let allow_origin = request
+            .headers()
+            .get("origin")
+            .and_then(|origin| origin.to_str().ok())
+            .and_then(|origin| self.allow_origin(origin));
+
+        if request.method() == "OPTIONS" {
+            let requested_headers =
+                request.headers().get("access-control-request-headers").cloned();
+            let response = match allow_origin {
+                Some(origin) => {
+                    let mut response = HttpResponse::builder()
+                        .status(204)
+                        .header("access-control-allow-origin", origin)
+                        .header("access-control-allow-methods", "POST, GET, OPTIONS")
+                        .header("access-control-max-age", PREFLIGHT_MAX_AGE_SECS)
+                        .header("vary", "Origin");
+                    if let Some(headers) = requested_headers {
+                        response = response.header("access-control-allow-headers", headers);
+                    }
+                    response.body(HttpBody::empty())
+                }
+                None => HttpResponse::builder().status(403).body(HttpBody::empty()),
+            };
+            return Box::pin(async move { response.map_err(BoxError::from) });
+        }
+
+        // Take the service that was polled ready, leaving a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let mut response = inner.call(request).await?;
+            if let Some(origin) = allow_origin {
+                let headers = response.headers_mut();
+                if let Ok(origin) = origin.parse() {
+                    headers.insert("access-control-allow-origin", origin);
+                }
+                headers.append("vary", "Origin".parse().expect("static header value"));
+            }
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,753 @@
+//! GraphQL query layer
+//!
+//! Serves the node state behind the JSON-RPC API as one GraphQL schema, so
+//! explorers can fetch a tick with its finality and predecessor, or an
+//! account with its delegate, in a single request. Like the
+//! [`status`](crate::status) page, every field is resolved through the
+//! same [`KalaApiServer`] implementation that serves JSON-RPC, so the two
+//! APIs never disagree.
+//!
+//! The schema is read-only and has three roots besides `chain`:
+//!
+//! - `tick(number)` and `ticks(filter, first, after, last, before)`, a
+//!   cursor-paginated connection over stored ticks, filtered by range,
+//!   tick type, or transaction count
+//! - `account(address)`, with `delegate` resolving to the delegation
+//!   target's account
+//! - `transaction(hash)`, with `tick` resolving to the tick that included
+//!   it; like `kala_traceTransaction`, only recent transactions are known
+//!
+//! Requests are POSTed as JSON to `/graphql`. Available with the `graphql`
+//! feature.
+//!
+//! Queries are bounded in depth and complexity, a page of ticks counting
+//! as many times its selection as it may hold. The endpoint is served like
+//! the JSON-RPC server, over TLS when configured and behind the same CORS
+//! and [`limits`](crate::limits), each query counting as a call.
+
+use std::sync::Arc;
+
+use async_graphql::connection::{self, Connection, Edge};
+use async_graphql::{
+    EmptyMutation, EmptySubscription, Enum, InputObject, Object, Schema, SimpleObject,
+};
+use axum::extract::{DefaultBodyLimit, State};
+use axum::{routing::post, Json, Router};
+use jsonrpsee::core::RpcResult;
+use kala_common::prelude::*;
+use kala_state::{TickCertificate, TickType};
+
+use crate::limits::RateLimitLayer;
+use crate::{
+    accept_connections, router_service, AccountInfo, CorsLayer, DenomMetadata, GetAccountRequest,
+    GetTickRequest, KalaApiServer, RpcConfig, TlsConfig, TransactionTrace, TraceStage,
+};
+
+/// Ticks in a page when neither `first` nor `last` is given
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// Most ticks a page may hold
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Most ticks read for one page, bounding filters that match rarely
+const MAX_SCANNED_TICKS: u64 = 1000;
+
+/// Deepest selection a query may nest
+const MAX_DEPTH: usize = 16;
+
+/// Most fields a query may resolve, counting each page of ticks as full
+const MAX_COMPLEXITY: usize = 2500;
+
+/// The schema served at `/graphql`
+pub type KalaSchema<T> = Schema<QueryRoot<T>, EmptyMutation, EmptySubscription>;
+
+/// Build the GraphQL schema over `api_impl`
+///
+/// # Parameters
+///
+/// - `api_impl`: The node's API implementation, queried by every resolver
+///
+/// # Returns
+///
+/// A schema that executes queries against `api_impl`
+pub fn graphql_schema<T: KalaApiServer>(api_impl: T) -> KalaSchema<T> {
+    Schema::build(
+        QueryRoot { api: Arc::new(api_impl) },
+        EmptyMutation,
+        EmptySubscription,
+    )
+    .limit_depth(MAX_DEPTH)
+    .limit_complexity(MAX_COMPLEXITY)
+    .finish()
+}
+
+/// Serve the GraphQL endpoint until the server fails
+///
+/// # Parameters
+///
+/// - `config`: Listen address, limits, TLS and CORS of the endpoint; the
+///   admin token is unused
+/// - `api_impl`: The node's API implementation, queried on every request
+///
+/// # Errors
+///
+/// - [`KalaError::Config`] if the TLS certificate or key can't be loaded
+/// - [`KalaError::Network`] if binding fails
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_rpc::{start_graphql_server, RpcConfig};
+///
+/// # async fn example<T: kala_rpc::KalaApiServer>(api_impl: T) -> kala_common::KalaResult<()> {
+/// let config = RpcConfig {
+///     listen_addr: "127.0.0.1:8547".parse().unwrap(),
+///     limits: Default::default(),
+///     tls: None,
+///     cors_allowed_origins: Vec::new(),
+///     admin_token: None,
+/// };
+/// start_graphql_server(config, api_impl).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn start_graphql_server<T: KalaApiServer>(
+    config: RpcConfig,
+    api_impl: T,
+) -> KalaResult<()> {
+    let max_body_size = config.limits.max_request_body_size as usize;
+    let router = Router::new()
+        .route("/graphql", post(execute::<T>))
+        .with_state(graphql_schema(api_impl))
+        .layer(DefaultBodyLimit::max(max_body_size));
+    let service = tower::ServiceBuilder::new()
+        .layer(CorsLayer::new(config.cors_allowed_origins))
+        .layer(RateLimitLayer::new(&config.limits))
+        .service(router_service(router));
+
+    let acceptor = config.tls.as_ref().map(TlsConfig::acceptor).transpose()?;
+    let listener = tokio::net::TcpListener::bind(config.listen_addr)
+        .await
+        .map_err(|e| KalaError::network(format!("Failed to bind GraphQL endpoint: {}", e)))?;
+    let scheme = if acceptor.is_some() { "https" } else { "http" };
+    tracing::info!("GraphQL endpoint listening on {}://{}/graphql", scheme, config.listen_addr);
+
+    accept_connections(listener, acceptor, "GraphQL", || service.clone()).await
+}
+
+async fn execute<T: KalaApiServer>(
+    State(schema): State<KalaSchema<T>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+/// Surface a JSON-RPC error as a GraphQL one
+fn rpc<R>(result: RpcResult<R>) -> async_graphql::Result<R> {
+    result.map_err(|e| async_graphql::Error::new(e.message()))
+}
+
+/// Root of every query
+pub struct QueryRoot<T> {
+    api: Arc<T>,
+}
+
+#[Object]
+impl<T: KalaApiServer> QueryRoot<T> {
+    /// Head of the chain
+    async fn chain(&self) -> async_graphql::Result<Chain<T>> {
+        let info = rpc(self.api.chain_info().await)?;
+        Ok(Chain {
+            current_tick: info.current_tick,
+            current_iteration: info.current_iteration,
+            hash_chain: info.hash_chain,
+            total_transactions: info.total_transactions,
+            accounts: info.accounts as u64,
+            api: self.api.clone(),
+        })
+    }
+
+    /// A tick by number
+    async fn tick(&self, number: u64) -> async_graphql::Result<Option<Tick<T>>> {
+        load_tick(&self.api, number).await
+    }
+
+    /// Stored ticks in ascending order, paginated by tick number
+    ///
+    /// Pages hold 20 ticks unless `first` or `last` asks for up to 100.
+    /// A page reads at most 1000 ticks, so a selective filter may return
+    /// a short page with `hasNextPage` set.
+    #[graphql(complexity = "page_size(first, last) * child_complexity")]
+    async fn ticks(
+        &self,
+        filter: Option<TickFilter>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> async_graphql::Result<Connection<u64, Tick<T>>> {
+        let filter = filter.unwrap_or_default();
+        let head = rpc(self.api.chain_info().await)?.current_tick;
+        let lowest = filter.from.unwrap_or(0);
+        let highest = filter.to.map_or(head, |to| to.saturating_add(1).min(head));
+
+        connection::query(after, before, first, last, |after, before, first, last| async move {
+            let start = after.map_or(lowest, |after: u64| after.saturating_add(1).max(lowest));
+            let end = before.map_or(highest, |before: u64| before.min(highest));
+            let backwards = last.is_some() && first.is_none();
+            let size = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+            let span = end.saturating_sub(start);
+            let scan = span.min(MAX_SCANNED_TICKS);
+            let numbers: Vec<u64> = match backwards {
+                true => (end - scan..end).rev().collect(),
+                false => (start..start + scan).collect(),
+            };
+
+            let mut ticks = Vec::new();
+            let mut exhausted = scan == span;
+            for number in numbers {
+                if ticks.len() == size {
+                    exhausted = false;
+                    break;
+                }
+                let Some(tick) = load_tick(&self.api, number).await? else {
+                    continue;
+                };
+                if filter.matches(&tick.certificate) {
+                    ticks.push(tick);
+                }
+            }
+            if backwards {
+                ticks.reverse();
+            }
+
+            let (has_previous, has_next) = match backwards {
+                true => (!exhausted, end < highest),
+                false => (start > lowest, !exhausted),
+            };
+            let mut connection = Connection::new(has_previous, has_next);
+            connection.edges.extend(
+                ticks.into_iter().map(|tick| Edge::new(tick.certificate.tick_number, tick)),
+            );
+            Ok::<_, async_graphql::Error>(connection)
+        })
+        .await
+    }
+
+    /// An account by hex-encoded address
+    async fn account(&self, address: String) -> async_graphql::Result<Option<Account<T>>> {
+        load_account(&self.api, address).await
+    }
+
+    /// A recent transaction by hex-encoded envelope hash
+    async fn transaction(&self, hash: String) -> async_graphql::Result<Option<Transaction<T>>> {
+        let trace = rpc(self.api.trace_transaction(hash).await)?;
+        Ok(trace.map(|trace| Transaction {
+            trace,
+            api: self.api.clone(),
+        }))
+    }
+}
+
+/// Ticks a page asked for with `first` or `last` may hold
+fn page_size(first: Option<i32>, last: Option<i32>) -> usize {
+    first.or(last).map_or(DEFAULT_PAGE_SIZE, |size| size.max(0) as usize).min(MAX_PAGE_SIZE)
+}
+
+async fn load_tick<T: KalaApiServer>(
+    api: &Arc<T>,
+    number: u64,
+) -> async_graphql::Result<Option<Tick<T>>> {
+    let request = GetTickRequest { tick_number: number };
+    let certificate = rpc(api.get_tick(request).await)?;
+    Ok(certificate.map(|certificate| Tick {
+        certificate,
+        api: api.clone(),
+    }))
+}
+
+async fn load_account<T: KalaApiServer>(
+    api: &Arc<T>,
+    address: String,
+) -> async_graphql::Result<Option<Account<T>>> {
+    let request = GetAccountRequest { address: address.clone() };
+    let info = rpc(api.get_account(request).await)?;
+    Ok(info.map(|info| Account {
+        address,
+        info,
+        api: api.clone(),
+    }))
+}
+
+/// Head of the chain, from `kala_chainInfo`
+pub struct Chain<T> {
+    current_tick: u64,
+    current_iteration: u64,
+    hash_chain: String,
+    total_transactions: u64,
+    accounts: u64,
+    api: Arc<T>,
+}
+
+#[Object]
+impl<T: KalaApiServer> Chain<T> {
+    /// Next tick to be produced
+    async fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    /// Current VDF iteration
+    async fn current_iteration(&self) -> u64 {
+        self.current_iteration
+    }
+
+    /// Current hash chain value (hex-encoded)
+    async fn hash_chain(&self) -> &str {
+        &self.hash_chain
+    }
+
+    /// Transactions processed across all ticks
+    async fn total_transactions(&self) -> u64 {
+        self.total_transactions
+    }
+
+    /// Accounts with non-zero state
+    async fn accounts(&self) -> u64 {
+        self.accounts
+    }
+
+    /// The latest tick
+    async fn head(&self) -> async_graphql::Result<Option<Tick<T>>> {
+        match self.current_tick.checked_sub(1) {
+            Some(number) => load_tick(&self.api, number).await,
+            None => Ok(None),
+        }
+    }
+}
+
+/// Kind of tick, by what its certificate commits to
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+pub enum TickKind {
+    /// Transactions were ordered and applied
+    Full,
+    /// Consensus was reached without transactions
+    Empty,
+    /// Too few witnesses observed the envelopes; only the VDF proof is kept
+    Checkpoint,
+}
+
+impl From<&TickType> for TickKind {
+    fn from(tick_type: &TickType) -> Self {
+        match tick_type {
+            TickType::Full => TickKind::Full,
+            TickType::Empty => TickKind::Empty,
+            TickType::Checkpoint => TickKind::Checkpoint,
+        }
+    }
+}
+
+/// Which ticks `ticks` returns
+#[derive(InputObject, Default)]
+pub struct TickFilter {
+    /// Lowest tick number
+    from: Option<u64>,
+    /// Highest tick number
+    to: Option<u64>,
+    /// Only ticks of this kind
+    tick_type: Option<TickKind>,
+    /// Only ticks with at least this many transactions
+    min_transactions: Option<u32>,
+}
+
+impl TickFilter {
+    fn matches(&self, certificate: &TickCertificate) -> bool {
+        self.tick_type.is_none_or(|kind| kind == TickKind::from(&certificate.tick_type))
+            && self.min_transactions.is_none_or(|min| certificate.transaction_count >= min)
+    }
+}
+
+/// A tick certificate
+pub struct Tick<T> {
+    certificate: TickCertificate,
+    api: Arc<T>,
+}
+
+#[Object]
+impl<T: KalaApiServer> Tick<T> {
+    /// Tick number
+    async fn number(&self) -> u64 {
+        self.certificate.tick_number
+    }
+
+    /// What the tick commits to
+    async fn tick_type(&self) -> TickKind {
+        TickKind::from(&self.certificate.tick_type)
+    }
+
+    /// Hash of the certificate (hex-encoded)
+    async fn hash(&self) -> String {
+        hex::encode(self.certificate.tick_hash)
+    }
+
+    /// Hash of the previous tick's certificate (hex-encoded)
+    async fn previous_hash(&self) -> String {
+        hex::encode(self.certificate.previous_tick_hash)
+    }
+
+    /// VDF iteration the tick ends at
+    async fn vdf_iteration(&self) -> u64 {
+        self.certificate.vdf_iteration
+    }
+
+    /// Hash chain value at the end of the tick (hex-encoded)
+    async fn hash_chain_value(&self) -> String {
+        hex::encode(self.certificate.hash_chain_value)
+    }
+
+    /// Transactions applied by the tick
+    async fn transaction_count(&self) -> u32 {
+        self.certificate.transaction_count
+    }
+
+    /// Merkle root of the tick's transactions (hex-encoded)
+    async fn transaction_merkle_root(&self) -> String {
+        hex::encode(self.certificate.transaction_merkle_root)
+    }
+
+    /// Unix time the certificate was created, in milliseconds
+    async fn timestamp(&self) -> u64 {
+        self.certificate.timestamp
+    }
+
+    /// The tick before this one
+    async fn previous(&self) -> async_graphql::Result<Option<Tick<T>>> {
+        match self.certificate.tick_number.checked_sub(1) {
+            Some(number) => load_tick(&self.api, number).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Witness signatures finalizing the tick, if it has its own
+    async fn finality(&self) -> async_graphql::Result<Option<Finality>> {
+        let request = GetTickRequest { tick_number: self.certificate.tick_number };
+        let finality = rpc(self.api.get_tick_finality(request).await)?;
+        Ok(finality.map(|finality| Finality {
+            tick_hash: hex::encode(finality.tick_hash),
+            signers: (0..finality.signers.len() * 8)
+                .filter(|index| finality.signers[index / 8] & (1 << (index % 8)) != 0)
+                .map(|index| index as u32)
+                .collect(),
+        }))
+    }
+}
+
+/// Witness signatures finalizing a tick, from `kala_getTickFinality`
+#[derive(SimpleObject)]
+pub struct Finality {
+    /// Hash of the finalized certificate (hex-encoded)
+    tick_hash: String,
+    /// Indices of the signers in the witness set
+    signers: Vec<u32>,
+}
+
+/// An account's state at the head of the chain
+pub struct Account<T> {
+    address: String,
+    info: AccountInfo,
+    api: Arc<T>,
+}
+
+#[Object]
+impl<T: KalaApiServer> Account<T> {
+    /// Account address (hex-encoded)
+    async fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Balance in base units
+    async fn balance(&self) -> u64 {
+        self.info.balance
+    }
+
+    /// Balance in display units, e.g. "12.5"
+    async fn balance_display(&self) -> &str {
+        &self.info.balance_display
+    }
+
+    /// Next transaction nonce
+    async fn nonce(&self) -> u64 {
+        self.info.nonce
+    }
+
+    /// Amount staked by the account, in base units
+    async fn staked_amount(&self) -> u64 {
+        self.info.staked_amount
+    }
+
+    /// Staked amount in display units
+    async fn staked_display(&self) -> &str {
+        &self.info.staked_display
+    }
+
+    /// Denomination the balances are held in
+    async fn denom(&self) -> Denom {
+        Denom::from(&self.info.denom)
+    }
+
+    /// Nonces of decrypted transactions held for earlier ones
+    async fn pending_nonces(&self) -> &[u64] {
+        &self.info.pending_nonces
+    }
+
+    /// Account the stake is delegated to
+    async fn delegate(&self) -> async_graphql::Result<Option<Account<T>>> {
+        match &self.info.delegation {
+            Some(address) => load_account(&self.api, address.clone()).await,
+            None => Ok(None),
+        }
+    }
+}
+
+/// Display metadata of a denomination
+#[derive(SimpleObject)]
+pub struct Denom {
+    /// Denomination ID (hex-encoded)
+    id: String,
+    /// Ticker symbol of the display unit
+    symbol: String,
+    /// Human-readable name
+    name: String,
+    /// Decimal places in the display unit
+    decimals: u8,
+}
+
+impl From<&DenomMetadata> for Denom {
+    fn from(denom: &DenomMetadata) -> Self {
+        Self {
+            id: denom.denom.clone(),
+            symbol: denom.symbol.clone(),
+            name: denom.name.clone(),
+            decimals: denom.decimals,
+        }
+    }
+}
+
+/// A transaction's lifecycle on this node, from `kala_traceTransaction`
+pub struct Transaction<T> {
+    trace: TransactionTrace,
+    api: Arc<T>,
+}
+
+#[Object]
+impl<T: KalaApiServer> Transaction<T> {
+    /// Envelope hash (hex-encoded)
+    async fn hash(&self) -> &str {
+        &self.trace.tx_hash
+    }
+
+    /// Tick the transaction targets
+    async fn target_tick(&self) -> u64 {
+        self.trace.target_tick
+    }
+
+    /// Latest lifecycle stage, such as `applied` or `included`
+    async fn status(&self) -> Option<String> {
+        self.trace.events.last().map(|event| stage_name(event.stage))
+    }
+
+    /// Lifecycle events in the order they happened
+    async fn events(&self) -> Vec<TransactionEvent> {
+        self.trace
+            .events
+            .iter()
+            .map(|event| TransactionEvent {
+                stage: stage_name(event.stage),
+                timestamp_ms: event.timestamp_ms,
+                tick_number: event.tick_number,
+                iteration: event.iteration,
+                detail: event.detail.clone(),
+            })
+            .collect()
+    }
+
+    /// Tick that committed to the transaction
+    async fn tick(&self) -> async_graphql::Result<Option<Tick<T>>> {
+        let included = self.trace.events.iter().find(|event| event.stage == TraceStage::Included);
+        match included {
+            Some(event) => load_tick(&self.api, event.tick_number).await,
+            None => Ok(None),
+        }
+    }
+}
+
+/// An event in a transaction's lifecycle
+#[derive(SimpleObject)]
+pub struct TransactionEvent {
+    /// What happened, such as `decrypted`
+    stage: String,
+    /// Unix time of the event, in milliseconds
+    timestamp_ms: u64,
+    /// Tick the event happened in
+    tick_number: u64,
+    /// VDF iteration the event refers to
+    iteration: Option<u64>,
+    /// Stage-specific details
+    detail: Option<String>,
+}
+
+/// A stage's name as JSON-RPC serializes it
+fn stage_name(stage: TraceStage) -> String {
+    serde_json::to_value(stage)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{account, certificate, StubApi};
+    use crate::TraceEvent;
+    use serde_json::json;
+
+    const ALICE: &str = "aa";
+    const BOB: &str = "bb";
+
+    fn schema() -> KalaSchema<StubApi> {
+        let mut api = StubApi::default();
+        for number in 0..10 {
+            let (tick_type, transactions) = match number % 3 {
+                0 => (TickType::Full, number as u32),
+                1 => (TickType::Empty, 0),
+                _ => (TickType::Checkpoint, 0),
+            };
+            api.ticks.insert(number, certificate(number, tick_type, transactions));
+        }
+        api.accounts.insert(ALICE.to_string(), account(100, Some(BOB)));
+        api.accounts.insert(BOB.to_string(), account(7, None));
+        api.traces.insert(
+            "c0ffee".to_string(),
+            TransactionTrace {
+                trace_id: "trace".to_string(),
+                tx_hash: "c0ffee".to_string(),
+                target_tick: 6,
+                events: [(TraceStage::Submitted, 5), (TraceStage::Included, 6)]
+                    .into_iter()
+                    .map(|(stage, tick_number)| TraceEvent {
+                        stage,
+                        timestamp_ms: tick_number * 65,
+                        tick_number,
+                        iteration: None,
+                        detail: None,
+                    })
+                    .collect(),
+            },
+        );
+        graphql_schema(api)
+    }
+
+    async fn query(query: &str) -> serde_json::Value {
+        let response = schema().execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chain_and_ticks() {
+        let data = query("{ chain { currentTick head { number previous { number } } } }").await;
+        let head = json!({ "number": 9, "previous": { "number": 8 } });
+        assert_eq!(data, json!({ "chain": { "currentTick": 10, "head": head } }));
+
+        let tick = "{ tick(number: 3) { tickType transactionCount finality { signers } } }";
+        let data = query(tick).await;
+        assert_eq!(
+            data,
+            json!({ "tick": { "tickType": "FULL", "transactionCount": 3, "finality": null } })
+        );
+        assert_eq!(query("{ tick(number: 10) { number } }").await, json!({ "tick": null }));
+    }
+
+    #[tokio::test]
+    async fn test_ticks_pagination() {
+        let page = "pageInfo { hasPreviousPage hasNextPage } edges { cursor node { number } }";
+        let numbers = |data: &serde_json::Value| -> Vec<u64> {
+            data["ticks"]["edges"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|edge| edge["node"]["number"].as_u64().unwrap())
+                .collect()
+        };
+
+        let data = query(&format!("{{ ticks(first: 4) {{ {} }} }}", page)).await;
+        assert_eq!(numbers(&data), [0, 1, 2, 3]);
+        assert_eq!(data["ticks"]["pageInfo"]["hasNextPage"], true);
+
+        let cursor = data["ticks"]["edges"][3]["cursor"].as_str().unwrap().to_string();
+        let next = format!("{{ ticks(first: 4, after: \"{}\") {{ {} }} }}", cursor, page);
+        assert_eq!(numbers(&query(&next).await), [4, 5, 6, 7]);
+
+        let data = query(&format!("{{ ticks(last: 2) {{ {} }} }}", page)).await;
+        assert_eq!(numbers(&data), [8, 9]);
+        assert_eq!(data["ticks"]["pageInfo"]["hasPreviousPage"], true);
+
+        let filtered = |filter: &str| {
+            format!("{{ ticks(filter: {{ {} }}) {{ edges {{ node {{ number }} }} }} }}", filter)
+        };
+        let full = filtered("tickType: FULL, minTransactions: 1");
+        assert_eq!(numbers(&query(&full).await), [3, 6, 9]);
+        assert_eq!(numbers(&query(&filtered("from: 2, to: 4")).await), [2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_account_and_transaction() {
+        let account =
+            r#"account(address: "aa") { balance denom { symbol } delegate { address balance } }"#;
+        let data = query(&format!("{{ {} }}", account)).await;
+        assert_eq!(
+            data,
+            json!({ "account": {
+                "balance": 100,
+                "denom": { "symbol": "KALA" },
+                "delegate": { "address": "bb", "balance": 7 },
+            } })
+        );
+        let missing = query(r#"{ account(address: "cc") { balance } }"#).await;
+        assert_eq!(missing, json!({ "account": null }));
+
+        let transaction =
+            r#"transaction(hash: "c0ffee") { status events { stage } tick { number } }"#;
+        let data = query(&format!("{{ {} }}", transaction)).await;
+        assert_eq!(
+            data,
+            json!({ "transaction": {
+                "status": "included",
+                "events": [{ "stage": "submitted" }, { "stage": "included" }],
+                "tick": { "number": 6 },
+            } })
+        );
+
+        // JSON-RPC errors surface as GraphQL ones
+        let response = schema().execute(r#"{ account(address: "zz") { balance } }"#).await;
+        assert_eq!(response.errors[0].message, "Invalid address");
+    }
+
+    #[tokio::test]
+    async fn test_query_limits() {
+        // A full page of ticks, each with a few fields, is within bounds
+        let page =
+            "ticks(first: 100) { edges { node { number hash timestamp previous { number } } } }";
+        assert!(schema().execute(format!("{{ {} }}", page)).await.errors.is_empty());
+
+        // Several such pages add up past the limit
+        let pages: Vec<String> = (0..4).map(|i| format!("p{}: {}", i, page)).collect();
+        let response = schema().execute(format!("{{ {} }}", pages.join(" "))).await;
+        assert!(response.errors[0].message.contains("complex"), "{:?}", response.errors);
+
+        let previous = ("previous { ".repeat(MAX_DEPTH), " }".repeat(MAX_DEPTH));
+        let deep = format!("{{ tick(number: 9) {{ {}number{} }} }}", previous.0, previous.1);
+        let response = schema().execute(deep).await;
+        assert!(response.errors[0].message.contains("nested too deep"), "{:?}", response.errors);
+    }
+}
@@ -10,23 +10,68 @@
 //!
 //! ### Chain Information
 //! - **`kala_chainInfo`**: Get current blockchain state and VDF progress
+//! - **`kala_getChainParams`**: Get the protocol parameters SDKs configure from
 //! - **`kala_getTick`**: Retrieve specific tick certificates
+//! - **`kala_verifyTickProof`**: Verify a tick certificate's hash, linkage, and VDF output
+//! - **`kala_getTickRangeProof`**: Prove a range of ticks with sampled VDF segments
+//! - **`kala_timestampData`**: Timestamp arbitrary data in the VDF hash chain
+//! - **`kala_verifyTimestamp`**: Verify a timestamp proof issued by the node
+//! - **`kala_getTickData`**: Retrieve data timestamped in a tick
+//! - **`kala_proveTickData`**: Prove data is committed to its tick's data root
 //! - **`kala_getRecentTicks`**: Get recent tick history
+//! - **`kala_syncStatus`**: Check whether the node is catching up with its peers
+//! - **`kala_getEpochSummary`**: Get a signed epoch summary for light clients
+//! - **`kala_getTickFinality`**: Get the witness signatures and metadata finalizing a tick
+//! - **`kala_getOracleValue`**: Get a feed's medianized value at a tick, with its proof
+//! - **`kala_iterationToTime`**: Estimate the UTC time an iteration runs at
+//! - **`kala_timeToIteration`**: Estimate the iteration running at a UTC time
+//! - **`kala_getTimeBounds`**: Bound a tick's UTC time by witness-signed anchors
+//!
+//! ### API Discovery
+//! - **`rpc.discover`**: Get an OpenRPC document of the methods served, see
+//!   [`openrpc`]
+//!
+//! ### Node Monitoring
+//! - **`kala_getMetricsHistory`**: Get persisted performance samples
+//! - **`kala_traceTransaction`**: Get the lifecycle timeline of a transaction
+//! - **`kala_getVersion`**: Identify the exact build of the node software
+//! - **`kala_debugFfiMemory`**: Report memory held across native libraries, if enabled
+//! - A built-in HTML status page, see [`status`]
+//!
+//! ### GraphQL
+//! - Ticks, accounts and transactions with their relations, filtering and
+//!   pagination, served by the `graphql` feature, see `graphql`
+//!
+//! ### REST
+//! - `GET /ticks/{n}`, `GET /accounts/{addr}` and `POST /transactions` for
+//!   clients that can't speak JSON-RPC, see [`rest`]
 //!
 //! ### Transaction Operations  
 //! - **`kala_submitTransaction`**: Submit timelock-encrypted transactions
+//! - **`kala_reserveNonces`**: Reserve a nonce range for parallel submission
 //!
 //! ### Account Queries
 //! - **`kala_getAccount`**: Query account balances and state
+//! - **`kala_getSupply`**: Get the token supply
+//!
+//! Amounts are returned in base units together with the denomination's
+//! symbol and decimals and a formatted display value.
+//! - **`kala_watchAccount`**: Subscribe to account changes over WebSocket
+//!
+//! ### Node Administration
+//! - **`admin_*`**: Peer, mempool, snapshot, pruning and logging operations,
+//!   served only with an admin token, see [`admin`]
 //!
 //! ## Timelock Transaction Flow
 //!
 //! 1. **Client creates transaction**: Standard blockchain transaction
-//! 2. **Client encrypts with timelock**: Uses RSW puzzle for MEV protection
+//! 2. **Client encrypts with timelock**: Locks the key behind an RSW puzzle,
+//!    or shares it among the witnesses, for MEV protection
 //! 3. **Client submits via RPC**: Transaction enters the mempool
 //! 4. **Node timestamps arrival**: VDF timestamps transaction submission
 //! 5. **Node orders pre-decryption**: Canonical ordering prevents MEV
-//! 6. **Node decrypts in parallel**: GPU acceleration for puzzle solving
+//! 6. **Node decrypts in parallel**: GPU acceleration for puzzle solving, or
+//!    witnesses reveal their key shares after the ordering commitment
 //! 7. **Node validates and applies**: Standard blockchain state transition
 //!
 //! ## Example Usage
@@ -54,21 +99,66 @@
 //!
 //! - All transaction data is hex-encoded for safety
 //! - Address validation prevents malformed requests  
-//! - Rate limiting should be implemented at the HTTP layer
-//! - HTTPS is recommended for production deployments
+//! - Request bodies, responses and call rates are limited per client
+//!   address, see [`limits`]
+//! - HTTPS is served directly when a certificate is configured, see [`tls`]
+//! - Browsers may call the server from the origins in its CORS allow-list,
+//!   see [`cors`]
 
 use kala_common::prelude::*;
 use kala_common::types::PublicKey;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc, server::ServerBuilder};
-use kala_state::TickCertificate;
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use jsonrpsee::{
+    core::{BoxError, RpcResult, SubscriptionResult},
+    proc_macros::rpc,
+    server::{
+        middleware::rpc::RpcServiceBuilder, stop_channel, HttpBody, HttpRequest, HttpResponse,
+        ServerBuilder, ServerConfig,
+    },
+    Methods, RpcModule,
+};
+use futures_rustls::TlsAcceptor;
+use kala_transaction::{decode_envelope, encode_envelope, TimelockTransaction};
+use kala_state::{
+    MetricsSample, OracleProof, SignedEpochSummary, TickCertificate, TickFinality, TickRangeProof,
+    TimeBounds,
+};
+use schemars::JsonSchema;
 use std::net::SocketAddr;
+use std::time::Duration;
+use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
+
+pub mod admin;
+pub mod cors;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod limits;
+pub mod openrpc;
+pub mod rest;
+pub mod status;
+#[cfg(test)]
+mod testing;
+pub mod tls;
+
+pub use admin::{
+    AdminAuthLayer, AdminAuthorized, KalaAdminApiServer, MempoolEntry, PeerInfo, SnapshotExport,
+};
+pub use cors::CorsLayer;
+#[cfg(feature = "graphql")]
+pub use graphql::{graphql_schema, start_graphql_server, KalaSchema};
+pub use limits::{RateLimitLayer, RemoteAddr, RpcLimits, RpcRateLimitLayer};
+pub use rest::start_rest_server;
+pub use status::start_status_server;
+pub use tls::TlsConfig;
 
 /// Current blockchain and VDF state information
 ///
 /// This structure contains a comprehensive snapshot of the current
 /// blockchain state, including VDF progress, transaction statistics,
 /// and network health indicators.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct ChainInfo {
     /// Current tick number (block height equivalent)
     pub current_tick: BlockHeight,
@@ -82,19 +172,189 @@ pub struct ChainInfo {
     pub total_transactions: u64,
     /// Number of accounts with non-zero state
     pub accounts: usize,
+    /// Latest measurement of the node's VDF speed, if calibration is enabled
+    #[serde(default)]
+    pub vdf_calibration: Option<VdfCalibration>,
+    /// How the node's VDF library uses its machine
+    #[serde(default)]
+    pub vdf_tuning: Option<VdfTuning>,
+}
+
+/// Configuration a node's VDF library runs with
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct VdfTuning {
+    /// Threads recomputing VDF segments during verification
+    pub num_threads: usize,
+    /// Whether the fast squaring path may use AVX-512 IFMA
+    pub avx512: bool,
+    /// Whether the fast squaring path may use its assembly
+    pub fast_mode: bool,
+}
+
+/// Measured VDF speed of a node against the tick duration target
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct VdfCalibration {
+    /// VDF iterations per second measured by the benchmark
+    pub iterations_per_second: f64,
+    /// Tick duration expected at the measured rate, in milliseconds
+    pub tick_duration_ms: u64,
+    /// Tick duration the network targets, in milliseconds
+    pub target_tick_duration_ms: u64,
+    /// Whether the node computes ticks at least as fast as the target
+    pub keeps_pace: bool,
+    /// Unix time of the measurement, in seconds
+    pub measured_at: u64,
+}
+
+/// A point on the timeline, estimated by the node's timeline clock
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct TimelinePoint {
+    /// VDF iteration
+    pub iteration: u64,
+    /// Unix time in milliseconds at which the iteration runs
+    pub time_ms: u64,
+    /// Iterations per second the estimate assumes
+    pub iterations_per_second: f64,
+}
+
+/// Protocol parameters of the chain a node runs
+///
+/// SDKs configure themselves from these values instead of compile-time
+/// constants, so they keep working across parameter changes and upgrades.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct ChainParams {
+    /// Network protocol version
+    pub protocol_version: u32,
+    /// Chain ID transactions are signed for
+    pub chain_id: String,
+    /// VDF iterations per tick (k)
+    pub iterations_per_tick: u64,
+    /// Ticks per epoch
+    pub epoch_length: u64,
+    /// Iteration offsets of the phases within a tick
+    pub phases: TickPhases,
+    /// Class group discriminant of the VDF (decimal)
+    pub discriminant: String,
+    /// Limits on timelock puzzles of submitted transactions
+    pub hardness: HardnessBounds,
+    /// Protocol features and whether they are active at the head
+    pub features: Vec<FeatureStatus>,
+}
+
+/// The build of a node's software
+///
+/// Builds are reproducible: the same commit, profile, target, flags, and
+/// features give the same binary, so operators and auditors can confirm
+/// exactly what code produced a tick.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct NodeVersion {
+    /// Version of the node crate
+    pub version: String,
+    /// Git commit the node was built from, `unknown` outside a checkout
+    pub git_commit: String,
+    /// Cargo profile, e.g. `release`
+    pub build_profile: String,
+    /// Target triple the binary was built for
+    pub target: String,
+    /// Optimization level
+    pub opt_level: String,
+    /// Extra flags passed to rustc, space-separated
+    pub rustflags: String,
+    /// Cargo features compiled in
+    pub features: Vec<String>,
+    /// Network protocol version
+    pub protocol_version: u32,
+    /// VDF library and the GMP version it links
+    pub vdf_library: String,
+    /// Timelock puzzle solver library
+    pub solver_library: String,
+}
+
+/// Allocations of one kind of object crossing into a native library
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct FfiAllocations {
+    /// Library the objects cross into, `tick` or `timelocks`
+    pub library: String,
+    /// Kind of object, e.g. `forms` or `c_strings`
+    pub resource: String,
+    pub allocations: u64,
+    pub frees: u64,
+    /// Objects allocated and not yet freed
+    pub live: u64,
+    /// Bytes allocated and not yet freed, where the size is known
+    pub live_bytes: u64,
+}
+
+/// Memory a node holds across its native library boundaries
+///
+/// Allocation counts are kept since the node started. A live count that
+/// keeps growing while the node runs points at a leak.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct FfiMemoryReport {
+    pub allocations: Vec<FfiAllocations>,
+    /// Kinds of object freed more often than allocated, `library/resource`
+    pub over_freed: Vec<String>,
+    /// Tick certificates the VDF keeps, all of which a full checkpoint copies
+    pub vdf_certificates: u64,
+    /// Bytes of the certificates' forms, hashes and proofs
+    pub vdf_certificate_bytes: u64,
+}
+
+/// Iteration offsets, from the start of a tick, at which its phases end
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TickPhases {
+    /// End of collection; the transaction order is fixed here (k/3)
+    pub collection_end: u64,
+    /// End of puzzle decryption (2k/3)
+    pub decryption_end: u64,
+    /// End of validation and of the tick (k)
+    pub validation_end: u64,
+}
+
+/// Timelock puzzle limits for transactions targeting a tick
+///
+/// A puzzle must decrypt after the target tick's collection phase and
+/// before the tick ends.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct HardnessBounds {
+    /// Largest fraction of a tick a puzzle may take to solve
+    pub hardness_factor: f64,
+    /// Smallest accepted hardness in iterations
+    pub min_hardness: u32,
+    /// Largest recommended hardness in iterations
+    pub max_hardness: u32,
+    /// Offset into the tick before the target from which transactions are accepted
+    pub acceptance_window_start: u64,
+    /// Offset into the target tick until which transactions are accepted
+    pub acceptance_window_end: u64,
+}
+
+/// Activation status of a protocol feature
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct FeatureStatus {
+    /// Feature name, e.g. "fork_choice"
+    pub name: String,
+    /// Tick the feature activates at
+    pub activation_tick: BlockHeight,
+    /// Whether the feature is active at the head of the chain
+    pub active: bool,
 }
 
 /// Request to submit a timelock-encrypted transaction
 ///
 /// Contains the complete timelock transaction data in hex-encoded format.
-/// The transaction must be properly encrypted with an RSW timelock puzzle
-/// and targeted for a future tick.
-#[derive(Serialize, Deserialize, Clone)]
+/// The transaction must be properly encrypted, with its key locked behind
+/// an RSW timelock puzzle or shared among the witnesses, and targeted for a
+/// future tick.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct SubmitTransactionRequest {
     /// Hex-encoded timelock-encrypted transaction data
     /// 
-    /// This contains the complete [`TimelockTransaction`] structure
-    /// serialized and encoded as a hex string for safe transport.
+    /// The [`TimelockTransaction`] in its canonical wire format: the
+    /// protobuf encoding of `kala.network.Envelope` from
+    /// kala-common/schema/network.proto, as made by
+    /// [`SubmitTransactionRequest::new`]. Hex of the envelope's JSON
+    /// serialization is still accepted from older clients.
     pub encrypted_tx: String,
 }
 
@@ -103,7 +363,7 @@ pub struct SubmitTransactionRequest {
 /// Contains confirmation details and timing information for the
 /// submitted transaction, allowing clients to track its progress
 /// through the MEV-resistant processing pipeline.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct SubmitTransactionResponse {
     /// Unique transaction hash for tracking and identification
     pub tx_hash: String,
@@ -111,33 +371,363 @@ pub struct SubmitTransactionResponse {
     pub submission_iteration: IterationNumber,
     /// Target tick number when the transaction will be processed
     pub target_tick: BlockHeight,
+    /// Identifier tagging the transaction in this node's logs and trace
+    pub trace_id: String,
 }
 
 /// Request to retrieve a specific tick certificate
 ///
 /// Used to query historical tick information including transaction
 /// processing results and VDF state at tick completion.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct GetTickRequest {
     /// The tick number to retrieve
     pub tick_number: BlockHeight,
 }
 
+/// Request to verify a tick certificate
+///
+/// The certificate is checked against the one of the tick before it, taken
+/// from the request if given and otherwise from the node's database. The
+/// first tick needs no predecessor.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct VerifyTickProofRequest {
+    /// Certificate to verify
+    pub certificate: TickCertificate,
+    /// Certificate of the tick before it, if the node shouldn't look it up
+    #[serde(default)]
+    pub previous: Option<TickCertificate>,
+}
+
+/// Outcome of verifying a tick certificate
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TickProofVerification {
+    /// Tick the certificate is for
+    pub tick_number: BlockHeight,
+    /// Whether the hash, linkage, and VDF output all check out
+    pub valid: bool,
+    /// Why the certificate failed, if it did
+    pub error: Option<String>,
+}
+
+/// Request to prove a range of ticks
+///
+/// Proofs are against the tick accumulator root after `end_tick`, so
+/// light clients usually end the range at an epoch summary's `end_tick`.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GetTickRangeProofRequest {
+    /// First tick of the range
+    pub start_tick: BlockHeight,
+    /// Last tick of the range, inclusive
+    pub end_tick: BlockHeight,
+}
+
+/// Largest data `kala_timestampData` accepts, in bytes
+///
+/// Callers timestamping documents should timestamp their hash.
+pub const MAX_TIMESTAMP_DATA_BYTES: usize = 1024;
+
+/// Request to timestamp data in the VDF
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TimestampDataRequest {
+    /// Hex-encoded data, at most [`MAX_TIMESTAMP_DATA_BYTES`] bytes
+    pub data: String,
+}
+
+/// Proof that data was hashed into the VDF at an iteration
+///
+/// The data can't have existed later than the iteration, since every
+/// later hash chain value depends on it. The Merkle path commits it to the
+/// data root of the tick's VDF certificate. Byte fields are hex-encoded.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TimestampProof {
+    /// VDF iteration the data was hashed into
+    pub iteration: IterationNumber,
+    /// Tick the iteration belongs to
+    pub tick_number: BlockHeight,
+    /// The timestamped data
+    pub data: String,
+    /// SHA-256 hash of the data
+    pub data_hash: String,
+    /// Hash chain value after the iteration
+    pub hash_at_timestamp: String,
+    /// Position of the data among the data timestamped in the tick
+    pub leaf_index: u64,
+    /// Sibling hashes from the leaf `H(iteration || data_hash)` up to the
+    /// tick's data root, where `iteration` is 8 bytes little-endian
+    pub merkle_path: Vec<String>,
+}
+
+/// Outcome of verifying a timestamp proof
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TimestampVerification {
+    /// Whether the data was timestamped at the proof's iteration
+    pub valid: bool,
+    /// Why the proof failed, if it did
+    pub error: Option<String>,
+}
+
+/// Request to retrieve data timestamped in a tick
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GetTickDataRequest {
+    /// The finalized tick the data was timestamped in
+    pub tick_number: BlockHeight,
+    /// Position of the data among the data timestamped in the tick
+    pub leaf_index: u64,
+}
+
+/// Data timestamped in a tick, with its place in the tick's data tree
+///
+/// Byte fields are hex-encoded.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TickDataEntry {
+    /// Tick the data was timestamped in
+    pub tick_number: BlockHeight,
+    /// Position of the data among the data timestamped in the tick
+    pub leaf_index: u64,
+    /// Number of data timestamped in the tick
+    pub leaf_count: u64,
+    /// VDF iteration the data was hashed into
+    pub iteration: IterationNumber,
+    /// The timestamped data, or `None` if the node doesn't keep it
+    pub data: Option<String>,
+    /// SHA-256 hash of the data
+    pub data_hash: String,
+    /// Hash chain value after the iteration
+    pub hash_at_timestamp: String,
+    /// Blob store URI the data was moved to, such as `s3://bucket/key`
+    pub data_uri: Option<String>,
+}
+
+/// Request to prove data was timestamped in a tick
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ProveTickDataRequest {
+    /// The finalized tick the data was timestamped in
+    pub tick_number: BlockHeight,
+    /// SHA-256 hash of the data as a hex-encoded 32-byte hash (64 characters)
+    pub data_hash: String,
+}
+
+/// Request to retrieve an oracle feed's value at a tick
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GetOracleValueRequest {
+    /// Feed ID as a hex-encoded 32-byte hash (64 characters)
+    pub feed: String,
+    /// The finalized tick to read the value at
+    pub tick_number: BlockHeight,
+}
+
+/// Request to retrieve an epoch summary
+///
+/// Epoch summaries commit to every tick in the epoch and are signed by
+/// the witnesses, letting light clients follow the timeline
+/// epoch-to-epoch instead of tick-by-tick.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GetEpochRequest {
+    /// The epoch number to retrieve, or `None` for the latest sealed epoch
+    #[serde(default)]
+    pub epoch_number: Option<u64>,
+}
+
+/// Request to retrieve persisted metrics samples
+///
+/// Both bounds are Unix timestamps in seconds and inclusive. The node
+/// keeps roughly a day of samples.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct GetMetricsHistoryRequest {
+    /// Earliest sample time, or `None` for the oldest sample kept
+    #[serde(default)]
+    pub from: Option<u64>,
+    /// Latest sample time, or `None` for the newest sample
+    #[serde(default)]
+    pub to: Option<u64>,
+}
+
+/// Stage of a transaction's lifecycle on a node
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStage {
+    /// Accepted into the mempool through `kala_submitTransaction`
+    Submitted,
+    /// Accepted into the mempool from a peer
+    Received,
+    /// This node, as a witness, recorded when it saw the envelope
+    Observed,
+    /// Placed in the tick's canonical order
+    Ordered,
+    /// Timelock puzzle solved and payload decrypted
+    Decrypted,
+    /// The payload could not be decrypted
+    DecryptionFailed,
+    /// Waiting for the sender's earlier nonces to be applied
+    Held,
+    /// Validated and applied to the chain state
+    Applied,
+    /// Failed validation or could not be applied
+    Rejected,
+    /// Committed to by a tick certificate
+    Included,
+    /// Removed from the mempool without being processed
+    Dropped,
+}
+
+/// One step of a transaction's lifecycle
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TraceEvent {
+    /// What happened
+    pub stage: TraceStage,
+    /// Unix time of the event, in milliseconds
+    pub timestamp_ms: u64,
+    /// Tick the event happened in
+    pub tick_number: BlockHeight,
+    /// VDF iteration the event refers to, if any
+    pub iteration: Option<IterationNumber>,
+    /// Stage-specific details, such as an error or the certificate hash
+    pub detail: Option<String>,
+}
+
+/// Lifecycle of a transaction as seen by one node
+///
+/// Traces are kept in memory for the most recent transactions only and do
+/// not survive a restart.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct TransactionTrace {
+    /// Identifier the node tagged the transaction's log lines with
+    pub trace_id: String,
+    /// Hex-encoded envelope hash, as returned on submission
+    pub tx_hash: String,
+    /// Tick the transaction targets
+    pub target_tick: BlockHeight,
+    /// Events in the order they happened
+    pub events: Vec<TraceEvent>,
+}
+
+/// Request to reserve a contiguous range of nonces for an account
+///
+/// Must be signed with the account's Ed25519 key over
+/// [`ReserveNoncesRequest::signing_message`], so only the key holder can
+/// reserve the account's nonces. The signature binds the chain and a
+/// request identifier the node accepts once, so a captured request can't
+/// be replayed, here or on another network.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ReserveNoncesRequest {
+    /// Account address as a hex-encoded public key (64 characters)
+    pub address: String,
+    /// Number of nonces to reserve
+    pub count: u64,
+    /// Client-chosen identifier, accepted once per account while the
+    /// request is unexpired
+    pub request_id: u64,
+    /// Unix time in seconds after which the request is rejected
+    pub expires_at: u64,
+    /// Hex-encoded Ed25519 signature by the account key
+    pub signature: String,
+}
+
+impl ReserveNoncesRequest {
+    /// Message the account key signs to reserve `count` nonces on `chain_id`
+    pub fn signing_message(
+        chain_id: &str,
+        address: &[u8; 32],
+        count: u64,
+        request_id: u64,
+        expires_at: u64,
+    ) -> [u8; 32] {
+        CryptoUtils::hash_multiple(&[
+            b"kala_reserveNonces",
+            &(chain_id.len() as u64).to_le_bytes(),
+            chain_id.as_bytes(),
+            address,
+            &count.to_le_bytes(),
+            &request_id.to_le_bytes(),
+            &expires_at.to_le_bytes(),
+        ])
+    }
+}
+
+/// Nonces reserved for an account
+///
+/// No other reservation will hand out nonces in `first_nonce..=last_nonce`.
+/// Reserved nonces may be used in any order within a tick's ordering and
+/// unused ones are simply skipped, since a transaction only needs a nonce
+/// above the account's last applied one.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct NonceReservation {
+    /// Account address (hex-encoded)
+    pub address: String,
+    /// First reserved nonce
+    pub first_nonce: u64,
+    /// Last reserved nonce, inclusive
+    pub last_nonce: u64,
+    /// Nonce of the account's last applied transaction
+    pub account_nonce: u64,
+}
+
 /// Request to retrieve account information
 ///
 /// Queries the current state of a specific account, including
 /// balance, nonce, and staking information.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct GetAccountRequest {
     /// Account address as a hex-encoded public key (64 characters)
     pub address: String,
 }
 
+/// Display metadata of a denomination from the node's registry
+///
+/// Amounts on chain are integers of base units; `decimals` says how many
+/// of their digits are fractional in the display unit named by `symbol`.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct DenomMetadata {
+    /// Denomination ID (hex-encoded)
+    pub denom: String,
+    /// Ticker symbol of the display unit, e.g. "KALA"
+    pub symbol: String,
+    /// Human-readable name
+    pub name: String,
+    /// Number of decimal places in the display unit
+    pub decimals: u8,
+}
+
+impl DenomMetadata {
+    /// Format an amount of base units in the display unit
+    ///
+    /// Trailing fractional zeros are dropped and no symbol is added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kala_rpc::DenomMetadata;
+    ///
+    /// let kala = DenomMetadata {
+    ///     denom: "00".repeat(32),
+    ///     symbol: "KALA".to_string(),
+    ///     name: "Kala".to_string(),
+    ///     decimals: 9,
+    /// };
+    /// assert_eq!(kala.format_amount(1_500_000_000), "1.5");
+    /// assert_eq!(kala.format_amount(42), "0.000000042");
+    /// assert_eq!(kala.format_amount(3_000_000_000), "3");
+    /// ```
+    pub fn format_amount(&self, amount: u64) -> String {
+        let scale = 10u128.pow(self.decimals as u32);
+        let whole = amount as u128 / scale;
+        let fraction = amount as u128 % scale;
+        if fraction == 0 {
+            return whole.to_string();
+        }
+
+        let fraction = format!("{:0width$}", fraction, width = self.decimals as usize);
+        format!("{}.{}", whole, fraction.trim_end_matches('0'))
+    }
+}
+
 /// Account state information
 ///
 /// Contains the complete state of an account including balances,
-/// transaction history, and staking status.
-#[derive(Serialize, Deserialize, Clone)]
+/// transaction history, and staking status. Amounts are given in base
+/// units and, for display, formatted with the denomination's decimals.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct AccountInfo {
     /// Account balance in base units
     pub balance: u64,
@@ -147,6 +737,86 @@ pub struct AccountInfo {
     pub staked_amount: u64,
     /// Optional delegation target (hex-encoded address)
     pub delegation: Option<String>,
+    /// Denomination the balances are held in
+    pub denom: DenomMetadata,
+    /// Balance in display units, e.g. "12.5"
+    pub balance_display: String,
+    /// Staked amount in display units
+    pub staked_display: String,
+    /// Nonces of decrypted transactions held until the nonces before them
+    /// are applied
+    pub pending_nonces: Vec<u64>,
+}
+
+/// Token supply at the head of the chain
+///
+/// Amounts are given in base units and, for display, formatted with the
+/// denomination's decimals.
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SupplyInfo {
+    /// Next tick to be produced; the supply includes all ticks before it
+    pub tick_number: BlockHeight,
+    /// Denomination the supply is counted in
+    pub denom: DenomMetadata,
+    /// Base units held by all accounts, staked amounts included
+    pub total_supply: u64,
+    /// Base units currently staked
+    pub staked_supply: u64,
+    /// Total supply in display units
+    pub total_supply_display: String,
+    /// Staked supply in display units
+    pub staked_supply_display: String,
+}
+
+/// Account state pushed to `kala_watchAccount` subscribers
+///
+/// Sent after every tick that changed the account, with the hashes of the
+/// transactions that changed it.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct AccountUpdate {
+    /// Account address (hex-encoded)
+    pub address: String,
+    /// Tick whose transactions changed the account
+    pub tick_number: BlockHeight,
+    /// Account balance in base units after the tick
+    pub balance: u64,
+    /// Transaction nonce after the tick
+    pub nonce: u64,
+    /// Amount staked after the tick
+    pub staked_amount: u64,
+    /// Optional delegation target (hex-encoded address)
+    pub delegation: Option<String>,
+    /// Hex-encoded hashes of the transactions that changed the account
+    pub tx_hashes: Vec<String>,
+}
+
+/// Whether a node is catching up with the network or at its head
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncMode {
+    /// Downloading and verifying tick certificates from peers
+    Syncing,
+    /// Caught up and producing ticks alongside the network
+    Following,
+}
+
+/// Progress of the tick sync protocol
+///
+/// A node joining an existing network starts in [`SyncMode::Syncing`],
+/// downloads tick certificates and a state snapshot from its peers, and
+/// switches to [`SyncMode::Following`] once it has reached their head.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct SyncStatus {
+    /// Current sync mode
+    pub mode: SyncMode,
+    /// Local tick when syncing started
+    pub starting_tick: BlockHeight,
+    /// Next tick the node will process or verify
+    pub current_tick: BlockHeight,
+    /// Highest next tick reported by any peer
+    pub highest_tick: BlockHeight,
+    /// Number of connected peers
+    pub peer_count: usize,
 }
 
 /// Main Kala blockchain JSON-RPC API trait
@@ -171,6 +841,8 @@ pub trait KalaApi {
     /// - Current tick number and VDF iteration
     /// - VDF output values and hash chain state  
     /// - Transaction and account statistics
+    /// - Measured VDF speed, when calibration is enabled
+    /// - The VDF library's tuning
     ///
     /// # Example
     ///
@@ -184,11 +856,34 @@ pub trait KalaApi {
     #[method(name = "kala_chainInfo")]
     async fn chain_info(&self) -> RpcResult<ChainInfo>;
 
+    /// Get the chain's protocol parameters
+    ///
+    /// Returns the tick size, phase boundaries, VDF discriminant, timelock
+    /// hardness bounds, protocol version, and which protocol features are
+    /// active, so SDKs can configure themselves from the node.
+    ///
+    /// # Returns
+    ///
+    /// [`ChainParams`] of the chain at its head
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getChainParams",
+    ///   "id": 12
+    /// }
+    /// ```
+    #[method(name = "kala_getChainParams")]
+    async fn get_chain_params(&self) -> RpcResult<ChainParams>;
+
     /// Submit a timelock-encrypted transaction for processing
     ///
     /// Accepts a timelock-encrypted transaction and adds it to the mempool
     /// for processing in a future tick. The transaction must be properly
-    /// encrypted with an RSW timelock puzzle and targeted appropriately.
+    /// encrypted, with an RSW timelock puzzle or a threshold key lock over
+    /// the current witnesses, and targeted appropriately.
     ///
     /// # Parameters
     ///
@@ -203,6 +898,8 @@ pub trait KalaApi {
     /// Returns error if:
     /// - Transaction is malformed or invalid
     /// - Target tick is in the past or too far in the future
+    /// - Timelock puzzle is malformed: its modulus is outside 1536 to 2048
+    ///   bits, its base is trivial, or it takes more squarings than a tick
     /// - Transaction would not decrypt in time for processing
     /// - Node is not accepting transactions for the target tick
     ///
@@ -224,6 +921,41 @@ pub trait KalaApi {
         req: SubmitTransactionRequest,
     ) -> RpcResult<SubmitTransactionResponse>;
 
+    /// Reserve a contiguous range of nonces for an account
+    ///
+    /// Lets high-frequency submitters build many transactions in parallel
+    /// without nonce collisions. The range starts after the account's last
+    /// applied nonce and any range reserved before, and the reservation is
+    /// released once transactions have used it up or it goes unrenewed for
+    /// the node's reservation lifetime.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`ReserveNoncesRequest`] signed by the account key
+    ///
+    /// # Returns
+    ///
+    /// [`NonceReservation`] with the reserved range
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_reserveNonces",
+    ///   "params": {
+    ///     "address": "a1b2c3d4e5f6789012345678901234567890abcdef1234567890abcdef123456",
+    ///     "count": 100,
+    ///     "request_id": 7,
+    ///     "expires_at": 1767225600,
+    ///     "signature": "5f1e...c08a"
+    ///   },
+    ///   "id": 11
+    /// }
+    /// ```
+    #[method(name = "kala_reserveNonces")]
+    async fn reserve_nonces(&self, req: ReserveNoncesRequest) -> RpcResult<NonceReservation>;
+
     /// Retrieve a specific tick certificate by tick number
     ///
     /// Returns the complete tick certificate for the specified tick,
@@ -254,129 +986,911 @@ pub trait KalaApi {
     #[method(name = "kala_getTick")]
     async fn get_tick(&self, req: GetTickRequest) -> RpcResult<Option<TickCertificate>>;
 
-    /// Get recent tick certificates for blockchain exploration
+    /// Verify a tick certificate without running a full node
     ///
-    /// Returns the most recent tick certificates, useful for blockchain
-    /// explorers and monitoring tools to display recent activity.
+    /// Checks the certificate's hash, that it links to the previous tick,
+    /// and that its VDF form follows from the previous tick's form after
+    /// one tick of squarings. Certificates don't carry a succinct proof
+    /// yet, so the squarings are recomputed, taking about as long as the
+    /// tick did. Clients can run the same check themselves with
+    /// `kala_state::verify_tick_certificate`.
     ///
     /// # Parameters
     ///
-    /// - `count`: Number of recent ticks to retrieve (maximum recommended: 100)
+    /// - `req`: [`VerifyTickProofRequest`] with the certificate and,
+    ///   optionally, the certificate of the tick before it
     ///
     /// # Returns
     ///
-    /// Vector of [`TickCertificate`] ordered from most recent to oldest.
+    /// [`TickProofVerification`] saying whether the certificate is valid,
+    /// or an error if the previous certificate is needed and unknown
     ///
     /// # Example
     ///
     /// ```json
     /// {
     ///   "jsonrpc": "2.0",
-    ///   "method": "kala_getRecentTicks",
-    ///   "params": 10,
-    ///   "id": 4
+    ///   "method": "kala_verifyTickProof",
+    ///   "params": {
+    ///     "certificate": {
+    ///       "tick_number": 12345,
+    ///       "tick_type": "Full",
+    ///       "vdf_iteration": 809041920,
+    ///       "vdf_form": ["0x1f...", "0x3b...", "0x2a..."],
+    ///       "hash_chain_value": [0, 0, 0],
+    ///       "tick_hash": [0, 0, 0],
+    ///       "transaction_count": 3,
+    ///       "transaction_merkle_root": [0, 0, 0],
+    ///       "timestamp": 1760000000000,
+    ///       "previous_tick_hash": [0, 0, 0]
+    ///     }
+    ///   },
+    ///   "id": 15
     /// }
     /// ```
-    #[method(name = "kala_getRecentTicks")]
-    async fn get_recent_ticks(&self, count: usize) -> RpcResult<Vec<TickCertificate>>;
+    #[method(name = "kala_verifyTickProof")]
+    async fn verify_tick_proof(
+        &self,
+        req: VerifyTickProofRequest,
+    ) -> RpcResult<TickProofVerification>;
 
-    /// Query account information by address
+    /// Prove a range of ticks without downloading all of them
     ///
-    /// Retrieves the current state of an account including balance,
-    /// nonce, staking information, and delegation status.
+    /// The proof carries the range's first and last certificates and a
+    /// number of sampled VDF segments logarithmic in its length, each with
+    /// an MMR inclusion proof. The samples are chosen by the accumulator
+    /// root after the last tick, so clients check the proof against the
+    /// `mmr_root` of a signed epoch summary with
+    /// `TickRangeProof::verify`. Segments that aren't sampled aren't
+    /// checked, so a forged range passes only with a probability that
+    /// shrinks with the share of forged segments.
     ///
     /// # Parameters
     ///
-    /// - `req`: [`GetAccountRequest`] with hex-encoded account address
+    /// - `req`: [`GetTickRangeProofRequest`] with the range's first and last tick
     ///
     /// # Returns
     ///
-    /// `Option<AccountInfo>` - `None` if account doesn't exist or has
-    /// never been used, otherwise complete account state information.
+    /// [`TickRangeProof`] of the range, or an error if a tick in it isn't
+    /// stored or committed yet
     ///
     /// # Example
     ///
     /// ```json
     /// {
     ///   "jsonrpc": "2.0",
-    ///   "method": "kala_getAccount", 
+    ///   "method": "kala_getTickRangeProof",
     ///   "params": {
-    ///     "address": "0x1234567890abcdef1234567890abcdef12345678"
+    ///     "start_tick": 0,
+    ///     "end_tick": 99999
     ///   },
-    ///   "id": 5
+    ///   "id": 16
     /// }
     /// ```
-    #[method(name = "kala_getAccount")]
-    async fn get_account(&self, req: GetAccountRequest) -> RpcResult<Option<AccountInfo>>;
-}
-
-/// Configuration for the JSON-RPC server
-///
-/// Contains network and binding configuration for the HTTP server
-/// that hosts the JSON-RPC endpoints.
-pub struct RpcConfig {
-    /// Socket address to bind the server to (IP:port)
-    pub listen_addr: SocketAddr,
-}
-
-/// Start the JSON-RPC server with the provided API implementation
-///
-/// Creates and starts an HTTP server that hosts the JSON-RPC endpoints.
-/// This function will run indefinitely, serving requests until the server
-/// is explicitly stopped or encounters a fatal error.
-///
-/// # Parameters
-///
-/// - `config`: [`RpcConfig`] specifying server binding configuration
-/// - `api_impl`: Implementation of the [`KalaApiServer`] trait
-///
-/// # Returns
-///
-/// Returns `Ok(())` when the server shuts down gracefully, or an error
-/// if the server fails to start or encounters a fatal error.
-///
-/// # Errors
-///
-/// - [`KalaError::Network`] if server binding fails
-/// - [`KalaError::Network`] if server startup fails
-///
-/// # Example
-///
-/// ```no_run
-/// use kala_rpc::{RpcConfig, start_server};
-/// use std::net::SocketAddr;
-///
-/// # async fn example() -> kala_common::KalaResult<()> {
-/// let config = RpcConfig {
-///     listen_addr: "127.0.0.1:8545".parse::<SocketAddr>().unwrap(),
-/// };
-///
-/// // api_impl would be your KalaApiServer implementation
-/// # let api_impl = todo!();
-/// start_server(config, api_impl).await?;
-/// # Ok(())
-/// # }
-/// ```
-pub async fn start_server<T: KalaApiServer>(config: RpcConfig, api_impl: T) -> KalaResult<()> {
-    let server = ServerBuilder::default().build(config.listen_addr).await
-        .map_err(|e| KalaError::network(format!("Failed to build server: {}", e)))?;
-
-    let addr = server.local_addr()
-        .map_err(|e| KalaError::network(format!("Failed to get local address: {}", e)))?;
-    let handle = server.start(api_impl.into_rpc());
-
-    tracing::info!("RPC server listening on {}", addr);
-
-    handle.stopped().await;
-    Ok(())
-}
+    #[method(name = "kala_getTickRangeProof")]
+    async fn get_tick_range_proof(
+        &self,
+        req: GetTickRangeProofRequest,
+    ) -> RpcResult<TickRangeProof>;
 
-// Implement KalaSerialize for RPC types
-// All RPC types use JSON encoding for human readability and HTTP compatibility
+    /// Timestamp data in the VDF hash chain
+    ///
+    /// The data is hashed into the next iteration that timestamps nothing
+    /// else, without adding an iteration, and the call returns once the
+    /// tick holding it finalizes, with the data's Merkle path to the tick's
+    /// data root. The proof shows the data existed by that iteration, which
+    /// `kala_iterationToTime` converts to a UTC time, turning the node into
+    /// a verifiable timestamping authority.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`TimestampDataRequest`] with the hex-encoded data
+    ///
+    /// # Returns
+    ///
+    /// [`TimestampProof`] of the iteration the data was hashed into, or an
+    /// error if the data is invalid or its tick doesn't finalize in time
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_timestampData",
+    ///   "params": {
+    ///     "data": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+    ///   },
+    ///   "id": 29
+    /// }
+    /// ```
+    #[method(name = "kala_timestampData")]
+    async fn timestamp_data(&self, req: TimestampDataRequest) -> RpcResult<TimestampProof>;
 
-impl KalaSerialize for ChainInfo {
-    /// RPC types use JSON for human readability over HTTP
-    fn preferred_encoding() -> EncodingType {
+    /// Verify a timestamp proof issued by `kala_timestampData`
+    ///
+    /// Checks that the data matches its hash, that the iteration falls in
+    /// the proof's tick, and that the Merkle path leads from the data to the
+    /// data root of the tick's VDF certificate, as stored by this node.
+    ///
+    /// # Parameters
+    ///
+    /// - `proof`: [`TimestampProof`] as returned by `kala_timestampData`
+    ///
+    /// # Returns
+    ///
+    /// [`TimestampVerification`] saying whether the proof is valid
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_verifyTimestamp",
+    ///   "params": {
+    ///     "proof": {
+    ///       "iteration": 809041921,
+    ///       "tick_number": 12345,
+    ///       "data": "9f86d081...",
+    ///       "data_hash": "1f2b...",
+    ///       "hash_at_timestamp": "7c4e...",
+    ///       "leaf_index": 2,
+    ///       "merkle_path": ["a3f1...", "08c2..."]
+    ///     }
+    ///   },
+    ///   "id": 30
+    /// }
+    /// ```
+    #[method(name = "kala_verifyTimestamp")]
+    async fn verify_timestamp(&self, proof: TimestampProof) -> RpcResult<TimestampVerification>;
+
+    /// Retrieve data timestamped in a tick
+    ///
+    /// Nodes keep the data hashed into the VDF during each tick, so anyone
+    /// holding a tick's certificate can fetch the payloads committed to its
+    /// data root. Leaves are in timestamp order; `leaf_count` lets callers
+    /// page through all of them. Depending on its retention policy, the
+    /// node may keep only the hash of large data, or the URI of the blob
+    /// store it moved the data to.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetTickDataRequest`] with the tick and leaf index
+    ///
+    /// # Returns
+    ///
+    /// `Option<TickDataEntry>` - `None` if the tick isn't finalized or has
+    /// no data at the leaf index
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getTickData",
+    ///   "params": {
+    ///     "tick_number": 12345,
+    ///     "leaf_index": 0
+    ///   },
+    ///   "id": 31
+    /// }
+    /// ```
+    #[method(name = "kala_getTickData")]
+    async fn get_tick_data(&self, req: GetTickDataRequest) -> RpcResult<Option<TickDataEntry>>;
+
+    /// Prove data was timestamped in a tick, by the data's hash
+    ///
+    /// Builds the Merkle path from the data's leaf to the data root of the
+    /// tick's VDF certificate, for data timestamped by anyone, not only
+    /// through `kala_timestampData`. Data timestamped more than once in the
+    /// tick is proven at its first iteration. If the node doesn't keep the
+    /// data, the proof's `data` is empty and must be filled in before the
+    /// proof verifies.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`ProveTickDataRequest`] with the tick and the data's hash
+    ///
+    /// # Returns
+    ///
+    /// `Option<TimestampProof>` - `None` if the tick isn't finalized or
+    /// didn't timestamp the data, otherwise a proof `kala_verifyTimestamp`
+    /// accepts
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_proveTickData",
+    ///   "params": {
+    ///     "tick_number": 12345,
+    ///     "data_hash": "1f2b8c0d4e5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c"
+    ///   },
+    ///   "id": 32
+    /// }
+    /// ```
+    #[method(name = "kala_proveTickData")]
+    async fn prove_tick_data(&self, req: ProveTickDataRequest) -> RpcResult<Option<TimestampProof>>;
+
+    /// Get recent tick certificates for blockchain exploration
+    ///
+    /// Returns the most recent tick certificates, useful for blockchain
+    /// explorers and monitoring tools to display recent activity.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: Number of recent ticks to retrieve (maximum recommended: 100)
+    ///
+    /// # Returns
+    ///
+    /// Vector of [`TickCertificate`] ordered from most recent to oldest.
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getRecentTicks",
+    ///   "params": 10,
+    ///   "id": 4
+    /// }
+    /// ```
+    #[method(name = "kala_getRecentTicks")]
+    async fn get_recent_ticks(&self, count: usize) -> RpcResult<Vec<TickCertificate>>;
+
+    /// Query account information by address
+    ///
+    /// Retrieves the current state of an account including balance,
+    /// nonce, staking information, and delegation status. Transactions
+    /// decrypted ahead of the account's next nonce are held until the gap
+    /// fills; their nonces are listed in `pending_nonces`.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetAccountRequest`] with hex-encoded account address
+    ///
+    /// # Returns
+    ///
+    /// `Option<AccountInfo>` - `None` if account doesn't exist or has
+    /// never been used, otherwise complete account state information.
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getAccount", 
+    ///   "params": {
+    ///     "address": "0x1234567890abcdef1234567890abcdef12345678"
+    ///   },
+    ///   "id": 5
+    /// }
+    /// ```
+    #[method(name = "kala_getAccount")]
+    async fn get_account(&self, req: GetAccountRequest) -> RpcResult<Option<AccountInfo>>;
+
+    /// Get the token supply with its denomination metadata
+    ///
+    /// Sums the balances and stakes of all accounts at the head of the
+    /// chain. The symbol and decimals come from the node's denomination
+    /// registry, so front-ends can format amounts without hardcoding them.
+    ///
+    /// # Returns
+    ///
+    /// [`SupplyInfo`] with raw and display amounts.
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getSupply",
+    ///   "id": 10
+    /// }
+    /// ```
+    #[method(name = "kala_getSupply")]
+    async fn get_supply(&self) -> RpcResult<SupplyInfo>;
+
+    /// Subscribe to changes of an account
+    ///
+    /// Pushes an [`AccountUpdate`] as a `kala_accountUpdate` notification
+    /// after every tick whose transactions changed the account's balance,
+    /// nonce, or stake, so wallets don't need to poll `kala_getAccount`.
+    /// Requires a WebSocket connection; cancel with `kala_unwatchAccount`.
+    ///
+    /// # Parameters
+    ///
+    /// - `address`: Account address as a hex-encoded public key (64 characters)
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_watchAccount",
+    ///   "params": "a1b2c3d4e5f6789012345678901234567890abcdef1234567890abcdef123456",
+    ///   "id": 10
+    /// }
+    /// ```
+    #[subscription(name = "kala_watchAccount" => "kala_accountUpdate", unsubscribe = "kala_unwatchAccount", item = AccountUpdate)]
+    async fn watch_account(&self, address: String) -> SubscriptionResult;
+
+    /// Get the progress of syncing from peers
+    ///
+    /// Reports whether the node is still catching up with the network and
+    /// how far it has got. Nodes running without peers are always
+    /// following.
+    ///
+    /// # Returns
+    ///
+    /// [`SyncStatus`] with the sync mode and the local and peer tick heights
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_syncStatus",
+    ///   "id": 6
+    /// }
+    /// ```
+    #[method(name = "kala_syncStatus")]
+    async fn sync_status(&self) -> RpcResult<SyncStatus>;
+
+    /// Retrieve a signed epoch summary
+    ///
+    /// Returns the summary of an epoch together with the witness
+    /// signatures collected for it. A light client holding a trusted
+    /// summary verifies the next one against the trusted witness set, and
+    /// checks individual ticks with MMR inclusion proofs against the
+    /// summary's `mmr_root`.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetEpochRequest`] specifying the epoch, or none for the latest
+    ///
+    /// # Returns
+    ///
+    /// `Option<SignedEpochSummary>` - `None` if the epoch hasn't been sealed yet
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getEpochSummary",
+    ///   "params": {
+    ///     "epoch_number": 12
+    ///   },
+    ///   "id": 7
+    /// }
+    /// ```
+    #[method(name = "kala_getEpochSummary")]
+    async fn get_epoch_summary(&self, req: GetEpochRequest) -> RpcResult<Option<SignedEpochSummary>>;
+
+    /// Retrieve the witness signatures finalizing a tick
+    ///
+    /// Besides the aggregated signatures, the record carries the metadata
+    /// witnesses attached to their votes, such as price feeds or the heads
+    /// of other chains. Each blob is signed by the witness that reported
+    /// it, so consumers can check who attested to what at which tick.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetTickRequest`] with the tick number
+    ///
+    /// # Returns
+    ///
+    /// `Option<TickFinality>` - `None` if the tick hasn't been finalized
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getTickFinality",
+    ///   "params": {
+    ///     "tick_number": 12345
+    ///   },
+    ///   "id": 13
+    /// }
+    /// ```
+    #[method(name = "kala_getTickFinality")]
+    async fn get_tick_finality(&self, req: GetTickRequest) -> RpcResult<Option<TickFinality>>;
+
+    /// Retrieve an oracle feed's value at a finalized tick
+    ///
+    /// Witnesses report feed values in their vote metadata; the value is
+    /// the median of the reports carried in the tick's finality record.
+    /// It comes with a Merkle path to the root over all of the tick's feed
+    /// values, and with the finality record itself, so
+    /// [`OracleProof::verify`] checks the root against the witness
+    /// signatures on the reports and the tick.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetOracleValueRequest`] with the feed ID and tick number
+    ///
+    /// # Returns
+    ///
+    /// `Option<OracleProof>` - `None` if the tick isn't finalized or no
+    /// witness reported the feed
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getOracleValue",
+    ///   "params": {
+    ///     "feed": "7b4c9651c426361ed0e6bd9a9b3e70d71ec9507686a12b899c50c1faba8db94d",
+    ///     "tick_number": 12345
+    ///   },
+    ///   "id": 14
+    /// }
+    /// ```
+    #[method(name = "kala_getOracleValue")]
+    async fn get_oracle_value(&self, req: GetOracleValueRequest) -> RpcResult<Option<OracleProof>>;
+
+    /// Retrieve persisted performance samples
+    ///
+    /// The node stores a sample of its VDF speed, tick durations, and
+    /// mempool depth every few seconds in a fixed-size ring, so recent
+    /// performance can be inspected without a Prometheus deployment.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetMetricsHistoryRequest`] with an optional time range
+    ///
+    /// # Returns
+    ///
+    /// Vector of [`MetricsSample`] in the range, oldest first
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getMetricsHistory",
+    ///   "params": {
+    ///     "from": 1760000000,
+    ///     "to": 1760003600
+    ///   },
+    ///   "id": 8
+    /// }
+    /// ```
+    #[method(name = "kala_getMetricsHistory")]
+    async fn get_metrics_history(&self, req: GetMetricsHistoryRequest) -> RpcResult<Vec<MetricsSample>>;
+
+    /// Trace a transaction through the node
+    ///
+    /// Assembles the timeline of a recently submitted or received
+    /// transaction: mempool admission, the node's witness observation,
+    /// its position in the canonical order, decryption, application, and
+    /// the certificate that included it. Each event carries the
+    /// transaction's trace ID, which also tags the node's log lines.
+    ///
+    /// # Parameters
+    ///
+    /// - `tx_hash`: Hex-encoded transaction hash from [`SubmitTransactionResponse`]
+    ///
+    /// # Returns
+    ///
+    /// `Option<TransactionTrace>` - `None` if the node has no trace for the
+    /// transaction, for example because it was evicted or the node restarted
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_traceTransaction",
+    ///   "params": "9f2c...e41a",
+    ///   "id": 9
+    /// }
+    /// ```
+    #[method(name = "kala_traceTransaction")]
+    async fn trace_transaction(&self, tx_hash: String) -> RpcResult<Option<TransactionTrace>>;
+
+    /// Identify the exact build of the node software
+    ///
+    /// Returns the crate version, the git commit and build settings the
+    /// binary was built from, its enabled features, and the versions of
+    /// the native VDF and timelock solver libraries it links.
+    ///
+    /// # Returns
+    ///
+    /// [`NodeVersion`] of the running binary
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getVersion",
+    ///   "id": 17
+    /// }
+    /// ```
+    #[method(name = "kala_getVersion")]
+    async fn get_version(&self) -> RpcResult<NodeVersion>;
+
+    /// Estimate the UTC time at which an iteration runs
+    ///
+    /// The node's timeline clock extrapolates from the chain's anchor, the
+    /// genesis time or the iteration the node first started at, using the
+    /// latest calibrated VDF rate. Estimates for iterations far from the
+    /// anchor drift with the difference between that rate and the pace the
+    /// chain actually kept.
+    ///
+    /// # Parameters
+    ///
+    /// - `iteration`: VDF iteration, past or future
+    ///
+    /// # Returns
+    ///
+    /// [`TimelinePoint`] with the estimated Unix time in milliseconds
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_iterationToTime",
+    ///   "params": 131072000,
+    ///   "id": 18
+    /// }
+    /// ```
+    #[method(name = "kala_iterationToTime")]
+    async fn iteration_to_time(&self, iteration: u64) -> RpcResult<TimelinePoint>;
+
+    /// Estimate the iteration running at a UTC time
+    ///
+    /// The inverse of `kala_iterationToTime`, for scheduling against the
+    /// timeline: a transaction meant to unlock at a given time targets the
+    /// tick containing the returned iteration.
+    ///
+    /// # Parameters
+    ///
+    /// - `time_ms`: Unix time in milliseconds, past or future
+    ///
+    /// # Returns
+    ///
+    /// [`TimelinePoint`] with the last iteration started by `time_ms`
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_timeToIteration",
+    ///   "params": 1760000000000,
+    ///   "id": 19
+    /// }
+    /// ```
+    #[method(name = "kala_timeToIteration")]
+    async fn time_to_iteration(&self, time_ms: u64) -> RpcResult<TimelinePoint>;
+
+    /// Bound the UTC time a tick ended at by the time anchors around it
+    ///
+    /// Witnesses sign their clock readings every `time_anchor_interval`
+    /// ticks, and the median reading anchors the end of that tick. Unlike
+    /// `kala_iterationToTime`, the bounds don't depend on this node's
+    /// clock: each anchor comes with the finality record of its tick, and
+    /// [`TimeBounds::verify`] combines it again from the signed readings.
+    /// The tick's position between them is checked against VDF proofs.
+    ///
+    /// # Parameters
+    ///
+    /// - `req`: [`GetTickRequest`] with the tick number
+    ///
+    /// # Returns
+    ///
+    /// [`TimeBounds`] with the nearest anchors at or before and at or after
+    /// the tick's last iteration and their finality records, each `None`
+    /// if no anchor was found
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_getTimeBounds",
+    ///   "params": {
+    ///     "tick_number": 12345
+    ///   },
+    ///   "id": 20
+    /// }
+    /// ```
+    #[method(name = "kala_getTimeBounds")]
+    async fn get_time_bounds(&self, req: GetTickRequest) -> RpcResult<TimeBounds>;
+
+    /// Report memory held across the native library boundaries
+    ///
+    /// Counts the forms, reducers and strings exchanged with the VDF
+    /// library, the solvers and results of the timelock solver library,
+    /// and the certificates the VDF keeps for proofs. Only served when
+    /// the node enables debug RPC.
+    ///
+    /// # Returns
+    ///
+    /// [`FfiMemoryReport`] with the counts since the node started
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {
+    ///   "jsonrpc": "2.0",
+    ///   "method": "kala_debugFfiMemory",
+    ///   "id": 21
+    /// }
+    /// ```
+    #[method(name = "kala_debugFfiMemory")]
+    async fn debug_ffi_memory(&self) -> RpcResult<FfiMemoryReport>;
+}
+
+/// Longest a client may take to complete the TLS handshake
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Configuration for the JSON-RPC server
+///
+/// Contains network and binding configuration for the HTTP server
+/// that hosts the JSON-RPC endpoints. The REST gateway and GraphQL
+/// endpoint are configured the same way, without admin methods.
+#[derive(Clone)]
+pub struct RpcConfig {
+    /// Socket address to bind the server to (IP:port)
+    pub listen_addr: SocketAddr,
+    /// Body size and per-client rate limits
+    pub limits: RpcLimits,
+    /// Certificate and key to serve HTTPS with, plain HTTP if unset
+    pub tls: Option<TlsConfig>,
+    /// Origins browsers may call the server from, `*` for any
+    pub cors_allowed_origins: Vec<String>,
+    /// Bearer token admin calls must carry, refusing them all if unset
+    pub admin_token: Option<String>,
+}
+
+/// Start the JSON-RPC server with the provided API implementation
+///
+/// Creates and starts an HTTP server that hosts the JSON-RPC endpoints.
+/// This function will run indefinitely, serving requests until the server
+/// is explicitly stopped or encounters a fatal error.
+///
+/// Connections are accepted here rather than by jsonrpsee so TLS can be
+/// terminated first and each request can carry its client's
+/// [`RemoteAddr`], which the [`RateLimitLayer`] in front of the endpoints
+/// keys its limits on. The [`CorsLayer`] sits in front of both, and the
+/// [`AdminAuthLayer`] behind them.
+///
+/// # Parameters
+///
+/// - `config`: [`RpcConfig`] specifying server binding configuration
+/// - `api_impl`: Implementation of the [`KalaApiServer`] trait
+///
+/// # Returns
+///
+/// Serves until the task running it is dropped. Returns an error if the
+/// server fails to start.
+///
+/// # Errors
+///
+/// - [`KalaError::Config`] if the TLS certificate or key can't be loaded
+/// - [`KalaError::Network`] if server binding fails
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_rpc::{RpcConfig, RpcLimits, start_server};
+/// use std::net::SocketAddr;
+///
+/// # async fn example() -> kala_common::KalaResult<()> {
+/// let config = RpcConfig {
+///     listen_addr: "127.0.0.1:8545".parse::<SocketAddr>().unwrap(),
+///     limits: RpcLimits {
+///         requests_per_second: 50,
+///         ..Default::default()
+///     },
+///     tls: None,
+///     cors_allowed_origins: vec!["https://app.example.com".to_string()],
+///     admin_token: None,
+/// };
+///
+/// // api_impl would be your KalaApiServer implementation
+/// # let api_impl = todo!();
+/// start_server(config, api_impl).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn start_server<T: KalaApiServer>(config: RpcConfig, api_impl: T) -> KalaResult<()> {
+    serve(config, api_impl.into_rpc().into()).await
+}
+
+/// Start the JSON-RPC server with the admin namespace alongside the API
+///
+/// Serves the `admin_*` methods of `admin_impl` next to those of
+/// `api_impl`, as [`start_server`] does otherwise. The admin methods are
+/// only registered when [`RpcConfig::admin_token`] is set, and each call
+/// must carry that token, see [`admin`].
+///
+/// # Parameters
+///
+/// - `config`: [`RpcConfig`] specifying server binding configuration
+/// - `api_impl`: Implementation of the [`KalaApiServer`] trait
+/// - `admin_impl`: Implementation of the [`KalaAdminApiServer`] trait
+///
+/// # Errors
+///
+/// - [`KalaError::Config`] if the TLS certificate or key can't be loaded
+/// - [`KalaError::Network`] if server binding fails
+pub async fn start_server_with_admin<T: KalaApiServer, A: KalaAdminApiServer>(
+    config: RpcConfig,
+    api_impl: T,
+    admin_impl: A,
+) -> KalaResult<()> {
+    let mut methods = api_impl.into_rpc();
+    if config.admin_token.is_some() {
+        methods.merge(admin_impl.into_rpc()).map_err(|e| {
+            KalaError::config(format!("Admin methods clash with the API: {}", e))
+        })?;
+    }
+    serve(config, methods.into()).await
+}
+
+/// Accept connections and serve `methods` on them until dropped
+///
+/// `rpc.discover` is added, answering with the OpenRPC document of
+/// `methods`.
+async fn serve(config: RpcConfig, mut methods: Methods) -> KalaResult<()> {
+    let document = openrpc::document(methods.method_names());
+    let mut discover = RpcModule::new(());
+    discover
+        .register_method(openrpc::DISCOVER_METHOD, move |_, _, _| document.clone())
+        .map_err(|e| KalaError::config(format!("Failed to register rpc.discover: {}", e)))?;
+    methods
+        .merge(discover)
+        .map_err(|e| KalaError::config(format!("Failed to register rpc.discover: {}", e)))?;
+
+    let acceptor = config.tls.as_ref().map(TlsConfig::acceptor).transpose()?;
+    let listener = tokio::net::TcpListener::bind(config.listen_addr).await
+        .map_err(|e| KalaError::network(format!("Failed to build server: {}", e)))?;
+    let addr = listener.local_addr()
+        .map_err(|e| KalaError::network(format!("Failed to get local address: {}", e)))?;
+
+    let limits = config.limits;
+    let rate_limit = RateLimitLayer::new(&limits);
+    let server_config = ServerConfig::builder()
+        .max_request_body_size(limits.max_request_body_size)
+        .max_response_body_size(limits.max_response_body_size)
+        .build();
+    let service_builder = ServerBuilder::default()
+        .set_config(server_config)
+        .set_rpc_middleware(RpcServiceBuilder::new().layer(rate_limit.rpc_layer()))
+        .set_http_middleware(
+            tower::ServiceBuilder::new()
+                .layer(CorsLayer::new(config.cors_allowed_origins))
+                .layer(rate_limit)
+                .layer(AdminAuthLayer::new(config.admin_token)),
+        )
+        .to_service_builder();
+    // Dropping the server handle would stop the services, so it lives as
+    // long as the server does
+    let (stop_handle, _server_handle) = stop_channel();
+
+    let scheme = if acceptor.is_some() { "https" } else { "http" };
+    tracing::info!("RPC server listening on {}://{}", scheme, addr);
+
+    accept_connections(listener, acceptor, "RPC", || {
+        service_builder.clone().build(methods.clone(), stop_handle.clone())
+    })
+    .await
+}
+
+/// Accept connections on `listener` until dropped, serving each with the
+/// service `make_service` builds for it
+///
+/// Shared by every HTTP endpoint of the node, so each terminates TLS with
+/// `acceptor` when one is configured and hands its layers requests that
+/// carry the client's [`RemoteAddr`]. `name` labels the endpoint in logs.
+pub(crate) async fn accept_connections<S>(
+    listener: tokio::net::TcpListener,
+    acceptor: Option<TlsAcceptor>,
+    name: &'static str,
+    make_service: impl Fn() -> S,
+) -> KalaResult<()>
+where
+    S: tower::Service<HttpRequest, Response = HttpResponse, Error = BoxError>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    loop {
+        let (socket, remote_addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::warn!("Failed to accept {} connection: {}", name, e);
+                continue;
+            }
+        };
+
+        let service = make_service();
+        let service = hyper::service::service_fn(move |mut request: HttpRequest<Incoming>| {
+            request.extensions_mut().insert(RemoteAddr(remote_addr));
+            let service = service.clone();
+            async move {
+                tower::ServiceExt::oneshot(service, request.map(HttpBody::new))
+                    .await
+                    // A concrete error keeps the connection future provably Send
+                    .map_err(std::io::Error::other)
+            }
+        });
+
+        let acceptor = acceptor.clone();
+        tokio::spawn(async move {
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let connection = match acceptor {
+                Some(acceptor) => {
+                    let handshake = acceptor.accept(socket.compat());
+                    let handshake = tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, handshake);
+                    let stream = match handshake.await {
+                        Ok(Ok(stream)) => stream,
+                        Ok(Err(e)) => {
+                            tracing::debug!("TLS handshake with {} failed: {}", remote_addr, e);
+                            return;
+                        }
+                        Err(_) => {
+                            tracing::debug!("TLS handshake with {} timed out", remote_addr);
+                            return;
+                        }
+                    };
+                    builder
+                        .serve_connection_with_upgrades(TokioIo::new(stream.compat()), service)
+                        .await
+                }
+                None => builder.serve_connection_with_upgrades(TokioIo::new(socket), service).await,
+            };
+            if let Err(e) = connection {
+                tracing::debug!("{} connection from {} failed: {}", name, remote_addr, e);
+            }
+        });
+    }
+}
+
+/// An axum router as a service [`accept_connections`] and the shared
+/// layers can serve
+pub(crate) type RouterService = tower::util::MapErr<
+    tower::util::MapResponse<axum::Router, fn(axum::response::Response) -> HttpResponse>,
+    fn(std::convert::Infallible) -> BoxError,
+>;
+
+/// Wrap `router` so it answers with the same body type as jsonrpsee
+pub(crate) fn router_service(router: axum::Router) -> RouterService {
+    let map_response: fn(axum::response::Response) -> HttpResponse =
+        |response| response.map(HttpBody::new);
+    let map_err: fn(std::convert::Infallible) -> BoxError = BoxError::from;
+    tower::util::MapErr::new(tower::util::MapResponse::new(router, map_response), map_err)
+}
+
+// Implement KalaSerialize for RPC types
+// All RPC types use JSON encoding for human readability and HTTP compatibility
+
+impl KalaSerialize for ChainInfo {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for ChainParams {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for SyncStatus {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
         EncodingType::Json
     }
 }
@@ -402,6 +1916,41 @@ impl KalaSerialize for GetTickRequest {
     }
 }
 
+impl KalaSerialize for GetEpochRequest {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for GetMetricsHistoryRequest {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for TransactionTrace {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for ReserveNoncesRequest {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for NonceReservation {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
 impl KalaSerialize for GetAccountRequest {
     /// RPC types use JSON for human readability over HTTP
     fn preferred_encoding() -> EncodingType {
@@ -409,6 +1958,13 @@ impl KalaSerialize for GetAccountRequest {
     }
 }
 
+impl KalaSerialize for AccountUpdate {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
 impl KalaSerialize for AccountInfo {
     /// RPC types use JSON for human readability over HTTP
     fn preferred_encoding() -> EncodingType {
@@ -416,10 +1972,69 @@ impl KalaSerialize for AccountInfo {
     }
 }
 
+impl KalaSerialize for DenomMetadata {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for SupplyInfo {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for FfiMemoryReport {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
+impl KalaSerialize for TimelinePoint {
+    /// RPC types use JSON for human readability over HTTP
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json
+    }
+}
+
 // Validation helpers for RPC request types
 // These use kala-common validation utilities for consistency
 
 impl SubmitTransactionRequest {
+    /// Request submitting `envelope` in its canonical wire format
+    ///
+    /// # Parameters
+    ///
+    /// - `envelope`: The sealed transaction
+    ///
+    /// # Returns
+    ///
+    /// A request carrying the hex of [`encode_envelope`]
+    pub fn new(envelope: &TimelockTransaction) -> Self {
+        Self {
+            encrypted_tx: hex::encode(encode_envelope(envelope)),
+        }
+    }
+
+    /// Decodes the envelope the request carries
+    ///
+    /// Accepts a `0x` prefix on the hex.
+    ///
+    /// # Returns
+    ///
+    /// - The envelope, see [`decode_envelope`]
+    /// - [`KalaError::Validation`] if the hex is invalid
+    /// - An error if the bytes are no envelope
+    pub fn envelope(&self) -> KalaResult<TimelockTransaction> {
+        let encoded = self.encrypted_tx.strip_prefix("0x").unwrap_or(&self.encrypted_tx);
+        let bytes = hex::decode(encoded)
+            .map_err(|_| KalaError::validation("Invalid hex encoding in encrypted_tx"))?;
+        decode_envelope(&bytes)
+    }
+
     /// Validates the hex encoding of the encrypted transaction data
     ///
     /// Ensures the transaction data is properly formatted and can be
@@ -0,0 +1,647 @@
+//! Request limits for the JSON-RPC server
+//!
+//! The server enforces its own limits rather than relying on a proxy in
+//! front of it:
+//!
+//! - **Body size**: requests and responses over the configured sizes are
+//!   rejected before they are parsed or sent
+//! - **Per-IP rate**: every client address gets a token bucket refilled at
+//!   a steady rate, each call in a request taking one token
+//! - **Per-method rate**: methods expensive to serve can get a tighter
+//!   budget of their own, again per client address
+//!
+//! Limits are applied by [`RateLimitLayer`], a tower middleware in front
+//! of the JSON-RPC service. It reads the method names from the request
+//! body, so a batch counts as many calls as it holds. WebSocket
+//! connections count as one call when they're opened, and each call over
+//! them is limited by the [`RpcRateLimitLayer`] sharing its buckets. A
+//! batch holding more calls than a bucket's burst could never go through,
+//! so it's rejected outright rather than reported as rate limited.
+//!
+//! The REST and GraphQL endpoints sit behind the same layer. GraphQL
+//! bodies are JSON like JSON-RPC ones, each query counting as a call; REST
+//! requests count as a call to the JSON-RPC method their route stands for,
+//! so per-method limits hold there too.
+
+use http_body_util::LengthLimitError;
+use jsonrpsee::core::http_helpers::{read_body, HttpError};
+use jsonrpsee::core::BoxError;
+use jsonrpsee::server::middleware::rpc::{
+    Batch, BatchEntry, BatchEntryErr, MethodResponse, Notification, RpcServiceT,
+};
+use jsonrpsee::server::{Extensions, HttpBody, HttpRequest, HttpResponse};
+use jsonrpsee::types::error::{
+    OVERSIZED_REQUEST_CODE, PARSE_ERROR_CODE, SERVER_IS_BUSY_CODE, TOO_BIG_BATCH_REQUEST_CODE,
+};
+use jsonrpsee::types::{ErrorObject, Request};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+/// Default largest request body, in bytes
+pub const DEFAULT_MAX_REQUEST_BODY_SIZE: u32 = 10 * 1024 * 1024;
+
+/// Default largest response body, in bytes
+pub const DEFAULT_MAX_RESPONSE_BODY_SIZE: u32 = 10 * 1024 * 1024;
+
+/// Buckets tracked before idle ones are dropped
+const MAX_TRACKED_BUCKETS: usize = 65_536;
+
+/// Error message for batches holding more calls than a bucket's burst
+const BATCH_OVER_BURST: &str = "Batch holds more calls than the rate limit's burst allows";
+
+/// Size and rate limits of the JSON-RPC server
+#[derive(Debug, Clone)]
+pub struct RpcLimits {
+    /// Largest request body accepted, in bytes
+    pub max_request_body_size: u32,
+    /// Largest response body sent, in bytes
+    pub max_response_body_size: u32,
+    /// Calls each client address may make per second, 0 for no limit
+    pub requests_per_second: u32,
+    /// Calls a client address may make at once after being idle
+    ///
+    /// Raised to `requests_per_second` if lower.
+    pub burst: u32,
+    /// Calls per second each client address may make to particular
+    /// methods, on top of the overall limit
+    pub method_requests_per_second: HashMap<String, u32>,
+}
+
+impl Default for RpcLimits {
+    fn default() -> Self {
+        Self {
+            max_request_body_size: DEFAULT_MAX_REQUEST_BODY_SIZE,
+            max_response_body_size: DEFAULT_MAX_RESPONSE_BODY_SIZE,
+            requests_per_second: 0,
+            burst: 0,
+            method_requests_per_second: HashMap::new(),
+        }
+    }
+}
+
+/// Address of the client a request came from
+///
+/// Inserted into each request's extensions by the server. Requests without
+/// one are only limited per method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteAddr(pub SocketAddr);
+
+/// Outcome of [`RateLimiter::try_acquire`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acquire {
+    /// Tokens were taken for every call
+    Allowed,
+    /// A bucket ran short; the calls may go through once it refills
+    Limited,
+    /// More calls than a bucket ever holds; they never go through
+    OverBurst,
+}
+
+/// Marks a request whose calls the [`RateLimitLayer`] already counted
+#[derive(Debug, Clone, Copy)]
+struct Counted;
+
+/// Tokens refilled at a steady rate up to a capacity
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, rate: u32, capacity: u32, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate as f64).min(capacity as f64);
+        self.updated = now;
+    }
+}
+
+/// Token buckets per client address and method
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: u32,
+    burst: u32,
+    method_requests_per_second: HashMap<String, u32>,
+    buckets: Mutex<HashMap<(IpAddr, Option<String>), Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: &RpcLimits) -> Self {
+        Self {
+            requests_per_second: limits.requests_per_second,
+            burst: limits.burst.max(limits.requests_per_second),
+            method_requests_per_second: limits.method_requests_per_second.clone(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether any limit applies
+    pub fn is_enabled(&self) -> bool {
+        self.requests_per_second > 0 || self.method_requests_per_second.values().any(|&r| r > 0)
+    }
+
+    /// Take tokens for `calls` from `ip`, all or none
+    ///
+    /// `calls` holds the method name of each call, `None` where it
+    /// couldn't be read. Nothing is taken unless every bucket has enough.
+    pub fn try_acquire(&self, ip: IpAddr, calls: &[Option<String>]) -> Acquire {
+        self.try_acquire_at(ip, calls, Instant::now())
+    }
+
+    fn try_acquire_at(&self, ip: IpAddr, calls: &[Option<String>], now: Instant) -> Acquire {
+        // Tokens needed per bucket, with its rate and capacity
+        let mut needed: HashMap<Option<String>, (u32, u32, u32)> = HashMap::new();
+        if self.requests_per_second > 0 {
+            needed.insert(None, (calls.len() as u32, self.requests_per_second, self.burst));
+        }
+        for method in calls.iter().flatten() {
+            if let Some(&rate) = self.method_requests_per_second.get(method) {
+                if rate > 0 {
+                    needed.entry(Some(method.clone())).or_insert((0, rate, rate)).0 += 1;
+                }
+            }
+        }
+        if needed.is_empty() {
+            return Acquire::Allowed;
+        }
+        if needed.values().any(|&(count, _, capacity)| count > capacity) {
+            return Acquire::OverBurst;
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_BUCKETS {
+            // A bucket idle for a minute has refilled under any useful rate
+            buckets.retain(|_, bucket| {
+                now.saturating_duration_since(bucket.updated) < Duration::from_secs(60)
+            });
+        }
+
+        let mut allowed = true;
+        for (method, &(count, rate, capacity)) in &needed {
+            let bucket = buckets.entry((ip, method.clone())).or_insert(Bucket {
+                tokens: capacity as f64,
+                updated: now,
+            });
+            bucket.refill(rate, capacity, now);
+            allowed &= bucket.tokens >= count as f64;
+        }
+        if allowed {
+            for (method, &(count, _, _)) in &needed {
+                if let Some(bucket) = buckets.get_mut(&(ip, method.clone())) {
+                    bucket.tokens -= count as f64;
+                }
+            }
+            Acquire::Allowed
+        } else {
+            Acquire::Limited
+        }
+    }
+}
+
+/// JSON-RPC method a request stands for, by HTTP method and path
+pub type RouteMethod = fn(&str, &str) -> Option<&'static str>;
+
+/// Tower layer applying [`RpcLimits`] to JSON-RPC requests
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+    max_request_body_size: u32,
+    route_method: Option<RouteMethod>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limits: &RpcLimits) -> Self {
+        Self {
+            limiter: Arc::new(RateLimiter::new(limits)),
+            max_request_body_size: limits.max_request_body_size,
+            route_method: None,
+        }
+    }
+
+    /// Layer for an HTTP API whose requests aren't JSON-RPC calls
+    ///
+    /// Each request counts as one call to the method `route_method` gives
+    /// for its HTTP method and path.
+    pub fn for_routes(limits: &RpcLimits, route_method: RouteMethod) -> Self {
+        Self {
+            route_method: Some(route_method),
+            ..Self::new(limits)
+        }
+    }
+
+    /// RPC middleware limiting each call over WebSocket, drawing on the
+    /// same buckets as this layer
+    pub fn rpc_layer(&self) -> RpcRateLimitLayer {
+        RpcRateLimitLayer { limiter: self.limiter.clone() }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+            max_request_body_size: self.max_request_body_size,
+            route_method: self.route_method,
+        }
+    }
+}
+
+/// Service rejecting requests over the limits of a [`RateLimitLayer`]
+#[derive(Debug, Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+    max_request_body_size: u32,
+    route_method: Option<RouteMethod>,
+}
+
+impl<S> Service<HttpRequest> for RateLimit<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<HttpResponse, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest) -> Self::Future {
+        // Take the service that was polled ready, leaving a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+        let max_request_body_size = self.max_request_body_size;
+        let route_method = self.route_method.map(|route_method| {
+            route_method(request.method().as_str(), request.uri().path()).map(str::to_string)
+        });
+
+        Box::pin(async move {
+            if !limiter.is_enabled() {
+                return inner.call(request).await;
+            }
+            let ip = request.extensions().get::<RemoteAddr>().map(|addr| addr.0.ip());
+            if request.method() != "POST" {
+                // WebSocket upgrades and the like, without calls to read
+                let calls = [route_method.flatten()];
+                if ip.is_some_and(|ip| limiter.try_acquire(ip, &calls) != Acquire::Allowed) {
+                    return Ok(rate_limited());
+                }
+                return inner.call(request).await;
+            }
+
+            let (parts, body) = request.into_parts();
+            let (bytes, single) = match read_body(&parts.headers, body, max_request_body_size).await
+            {
+                Ok(body) => body,
+                Err(HttpError::TooLarge) => {
+                    return Ok(error_response(413, OVERSIZED_REQUEST_CODE, "Request is too big"));
+                }
+                // A body without a length is cut off once it runs over
+                Err(HttpError::Stream(e)) if e.is::<LengthLimitError>() => {
+                    return Ok(error_response(413, OVERSIZED_REQUEST_CODE, "Request is too big"));
+                }
+                Err(_) => return Ok(error_response(400, PARSE_ERROR_CODE, "Parse error")),
+            };
+
+            let calls = match route_method {
+                Some(method) => vec![method],
+                None => method_names(&bytes, single),
+            };
+            let ip = ip.unwrap_or(IpAddr::from([0, 0, 0, 0]));
+            match limiter.try_acquire(ip, &calls) {
+                Acquire::Allowed => {}
+                Acquire::Limited => return Ok(rate_limited()),
+                Acquire::OverBurst => {
+                    return Ok(error_response(400, TOO_BIG_BATCH_REQUEST_CODE, BATCH_OVER_BURST));
+                }
+            }
+            let mut request = HttpRequest::from_parts(parts, HttpBody::from(bytes));
+            request.extensions_mut().insert(Counted);
+            inner.call(request).await
+        })
+    }
+}
+
+/// Tower layer applying [`RpcLimits`] to each JSON-RPC call
+///
+/// Calls the [`RateLimitLayer`] already counted from an HTTP body pass
+/// through, so only those over WebSocket take tokens here. Made by
+/// [`RateLimitLayer::rpc_layer`].
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimit<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RpcRateLimit { service, limiter: self.limiter.clone() }
+    }
+}
+
+/// RPC service answering calls over the limits of an [`RpcRateLimitLayer`]
+/// with an error
+#[derive(Debug, Clone)]
+pub struct RpcRateLimit<S> {
+    service: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> RpcRateLimit<S> {
+    fn acquire(&self, extensions: &Extensions, calls: &[Option<String>]) -> Acquire {
+        if extensions.get::<Counted>().is_some() || !self.limiter.is_enabled() {
+            return Acquire::Allowed;
+        }
+        let ip = extensions.get::<RemoteAddr>().map_or(IpAddr::from([0, 0, 0, 0]), |a| a.0.ip());
+        self.limiter.try_acquire(ip, calls)
+    }
+}
+
+impl<S> RpcServiceT for RpcRateLimit<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = MethodResponse;
+    type NotificationResponse = MethodResponse;
+    type BatchResponse = MethodResponse;
+
+    fn call<'a>(&self, request: Request<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+        let method = Some(request.method_name().to_string());
+        let acquired = self.acquire(request.extensions(), &[method]);
+        let service = self.service.clone();
+        async move {
+            match refusal(acquired) {
+                None => service.call(request).await,
+                Some(error) => MethodResponse::error(request.id, error),
+            }
+        }
+    }
+
+    fn batch<'a>(&self, mut batch: Batch<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+        let calls: Vec<_> = batch
+            .iter()
+            .flatten()
+            .map(|entry| Some(entry.method_name().to_string()))
+            .collect();
+        let acquired = self.acquire(batch.extensions(), &calls);
+        let service = self.service.clone();
+        async move {
+            let Some(error) = refusal(acquired) else {
+                return service.batch(batch).await;
+            };
+            // Every call is answered with the error; notifications get no answer
+            let entries = batch
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    Ok(BatchEntry::Call(request)) => {
+                        Some(Err(BatchEntryErr::new(request.id, error.clone())))
+                    }
+                    Ok(BatchEntry::Notification(_)) => None,
+                    Err(e) => Some(Err(e)),
+                })
+                .collect();
+            service.batch(Batch::from(entries)).await
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = MethodResponse> + Send + 'a {
+        let acquired = self.acquire(n.extensions(), &[Some(n.method_name().to_string())]);
+        let service = self.service.clone();
+        async move {
+            match acquired {
+                Acquire::Allowed => service.notification(n).await,
+                _ => MethodResponse::notification(),
+            }
+        }
+    }
+}
+
+/// Error answering calls that weren't allowed, `None` if they were
+fn refusal(acquired: Acquire) -> Option<ErrorObject<'static>> {
+    match acquired {
+        Acquire::Allowed => None,
+        Acquire::Limited => {
+            Some(ErrorObject::owned(SERVER_IS_BUSY_CODE, "Rate limit exceeded", None::<()>))
+        }
+        Acquire::OverBurst => {
+            Some(ErrorObject::owned(TOO_BIG_BATCH_REQUEST_CODE, BATCH_OVER_BURST, None::<()>))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Call {
+    #[serde(default)]
+    method: Option<String>,
+}
+
+/// Method name of each call in a request body, `None` where unreadable
+fn method_names(body: &[u8], single: bool) -> Vec<Option<String>> {
+    if single {
+        vec![serde_json::from_slice::<Call>(body).ok().and_then(|call| call.method)]
+    } else {
+        match serde_json::from_slice::<Vec<serde_json::Value>>(body) {
+            Ok(calls) => calls
+                .into_iter()
+                .map(|call| serde_json::from_value::<Call>(call).ok().and_then(|c| c.method))
+                .collect(),
+            Err(_) => vec![None],
+        }
+    }
+}
+
+fn rate_limited() -> HttpResponse {
+    let mut response = error_response(429, SERVER_IS_BUSY_CODE, "Rate limit exceeded");
+    response.headers_mut().insert("retry-after", 1u32.into());
+    response
+}
+
+/// JSON-RPC error response without an id, as the request wasn't read
+fn error_response(status: u16, code: i32, message: &str) -> HttpResponse {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": null,
+    });
+    HttpResponse::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(HttpBody::from(body.to_string()))
+        .expect("static status and headers are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::server::ResponsePayload;
+    use jsonrpsee::types::Id;
+
+    const IP: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+
+    fn limiter(requests_per_second: u32, burst: u32, methods: &[(&str, u32)]) -> RateLimiter {
+        RateLimiter::new(&RpcLimits {
+            requests_per_second,
+            burst,
+            method_requests_per_second: methods
+                .iter()
+                .map(|&(method, rate)| (method.to_string(), rate))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Calls whose method names couldn't be read
+    fn unnamed(count: usize) -> Vec<Option<String>> {
+        vec![None; count]
+    }
+
+    fn calls(methods: &[&str]) -> Vec<Option<String>> {
+        methods.iter().map(|method| Some(method.to_string())).collect()
+    }
+
+    #[test]
+    fn test_burst_then_refill() {
+        let limiter = limiter(2, 4, &[]);
+        let start = Instant::now();
+
+        // A fresh bucket holds the whole burst
+        for _ in 0..4 {
+            assert_eq!(limiter.try_acquire_at(IP, &[None], start), Acquire::Allowed);
+        }
+        assert_eq!(limiter.try_acquire_at(IP, &[None], start), Acquire::Limited);
+        // Other addresses have buckets of their own
+        let other = IpAddr::from([10, 0, 0, 1]);
+        assert_eq!(limiter.try_acquire_at(other, &[None], start), Acquire::Allowed);
+
+        // Two tokens a second come back, never past the burst
+        let later = start + Duration::from_millis(500);
+        assert_eq!(limiter.try_acquire_at(IP, &[None], later), Acquire::Allowed);
+        assert_eq!(limiter.try_acquire_at(IP, &[None], later), Acquire::Limited);
+        let idle = later + Duration::from_secs(60);
+        assert_eq!(limiter.try_acquire_at(IP, &unnamed(4), idle), Acquire::Allowed);
+        assert_eq!(limiter.try_acquire_at(IP, &[None], idle), Acquire::Limited);
+    }
+
+    #[test]
+    fn test_batches_all_or_none() {
+        let limiter = limiter(10, 0, &[("kala_submitTransaction", 2)]);
+        let start = Instant::now();
+
+        // The burst is raised to the rate
+        assert_eq!(limiter.try_acquire_at(IP, &unnamed(10), start), Acquire::Allowed);
+        assert_eq!(limiter.try_acquire_at(IP, &[None], start), Acquire::Limited);
+
+        let later = start + Duration::from_secs(1);
+        let batch = calls(&["kala_submitTransaction", "kala_getTick", "kala_submitTransaction"]);
+        // Three submissions could never fit the method's bucket
+        assert_eq!(
+            limiter.try_acquire_at(IP, &calls(&["kala_submitTransaction"; 3]), later),
+            Acquire::OverBurst
+        );
+        assert_eq!(limiter.try_acquire_at(IP, &batch, later), Acquire::Allowed);
+        assert_eq!(limiter.try_acquire_at(IP, &batch[..1], later), Acquire::Limited);
+        // The failed call took no overall token either
+        assert_eq!(limiter.try_acquire_at(IP, &unnamed(7), later), Acquire::Allowed);
+    }
+
+    #[test]
+    fn test_over_burst_never_allowed() {
+        let limiter = limiter(1, 3, &[]);
+        let start = Instant::now();
+
+        assert_eq!(limiter.try_acquire_at(IP, &unnamed(4), start), Acquire::OverBurst);
+        let idle = start + Duration::from_secs(3600);
+        assert_eq!(limiter.try_acquire_at(IP, &unnamed(4), idle), Acquire::OverBurst);
+        // Nothing was taken
+        assert_eq!(limiter.try_acquire_at(IP, &unnamed(3), idle), Acquire::Allowed);
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let limiter = limiter(0, 0, &[("kala_getTick", 0)]);
+        assert!(!limiter.is_enabled());
+        assert_eq!(limiter.try_acquire(IP, &unnamed(1000)), Acquire::Allowed);
+    }
+
+    /// RPC service answering every call with a success
+    #[derive(Clone)]
+    struct Echo;
+
+    impl RpcServiceT for Echo {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(
+            &self,
+            request: Request<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            let response = ResponsePayload::success(true);
+            std::future::ready(MethodResponse::response(request.id, response, usize::MAX))
+        }
+
+        fn batch<'a>(&self, _: Batch<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            std::future::ready(MethodResponse::notification())
+        }
+
+        fn notification<'a>(
+            &self,
+            _: Notification<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            std::future::ready(MethodResponse::notification())
+        }
+    }
+
+    fn request(counted: bool) -> Request<'static> {
+        let mut request = Request::owned("kala_getTick".to_string(), None, Id::Number(1));
+        request.extensions_mut().insert(RemoteAddr(SocketAddr::new(IP, 4000)));
+        if counted {
+            request.extensions_mut().insert(Counted);
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_websocket_calls_limited() {
+        let layer = RateLimitLayer::new(&RpcLimits {
+            requests_per_second: 1,
+            burst: 2,
+            ..Default::default()
+        });
+        let service = layer.rpc_layer().layer(Echo);
+
+        // Calls over WebSocket each take a token
+        assert!(service.call(request(false)).await.is_success());
+        assert!(service.call(request(false)).await.is_success());
+        let limited = service.call(request(false)).await;
+        assert!(limited.is_error());
+        assert_eq!(limited.as_error_code(), Some(SERVER_IS_BUSY_CODE));
+
+        // Calls the HTTP layer counted aren't taken from again
+        assert!(service.call(request(true)).await.is_success());
+    }
+}
@@ -0,0 +1,325 @@
+//! OpenRPC document describing the JSON-RPC API
+//!
+//! The server answers `rpc.discover` with an [OpenRPC](https://open-rpc.org)
+//! document listing every method it serves, with the names and JSON schemas
+//! of their parameters and results, so client SDK generators and explorers
+//! can pick up the API surface without reading this crate.
+//!
+//! Method names come from the methods actually registered, so the document
+//! never lists a method the server doesn't serve, such as the admin
+//! namespace without a token. Schemas are derived from the Rust types with
+//! `schemars`, following their serde attributes, and shared under
+//! `components/schemas`.
+
+use crate::admin::{MempoolEntry, PeerInfo, SnapshotExport};
+use crate::{
+    AccountInfo, AccountUpdate, ChainInfo, ChainParams, FfiMemoryReport, GetAccountRequest,
+    GetEpochRequest, GetMetricsHistoryRequest, GetOracleValueRequest, GetTickDataRequest,
+    GetTickRangeProofRequest, GetTickRequest, NodeVersion, NonceReservation, ProveTickDataRequest,
+    ReserveNoncesRequest, SubmitTransactionRequest, SubmitTransactionResponse, SupplyInfo,
+    SyncStatus, TickDataEntry, TickProofVerification, TimelinePoint, TimestampDataRequest,
+    TimestampProof, TimestampVerification, TransactionTrace, VerifyTickProofRequest,
+};
+use kala_state::{
+    MetricsSample, OracleProof, SignedEpochSummary, TickCertificate, TickFinality, TickRangeProof,
+    TimeBounds,
+};
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::Schema;
+use serde_json::{json, Value};
+
+/// Method answering with the OpenRPC document
+pub const DISCOVER_METHOD: &str = "rpc.discover";
+
+/// Version of the OpenRPC specification the document follows
+pub const OPENRPC_VERSION: &str = "1.3.2";
+
+/// Schema of a parameter or result
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// Description of a served method
+struct MethodSpec {
+    name: &'static str,
+    summary: &'static str,
+    params: Vec<(&'static str, SchemaFn)>,
+    result: SchemaFn,
+    /// Notification method and item schema of a subscription
+    notification: Option<(&'static str, SchemaFn)>,
+}
+
+/// Describe a method as `name(param: Type, ...) -> Result`
+macro_rules! method {
+    ($name:literal, $summary:literal, ($($param:ident: $ty:ty),*) -> $result:ty) => {
+        MethodSpec {
+            name: $name,
+            summary: $summary,
+            params: vec![$(
+                (stringify!($param), |g: &mut SchemaGenerator| g.subschema_for::<$ty>())
+            ),*],
+            result: |g| g.subschema_for::<$result>(),
+            notification: None,
+        }
+    };
+}
+
+/// Every method the server may serve, in the order they're documented
+fn methods() -> Vec<MethodSpec> {
+    vec![
+        method!("kala_chainInfo", "Get current blockchain state and VDF progress", () -> ChainInfo),
+        method!(
+            "kala_getChainParams",
+            "Get the protocol parameters SDKs configure from",
+            () -> ChainParams
+        ),
+        method!(
+            "kala_submitTransaction",
+            "Submit a timelock-encrypted transaction",
+            (req: SubmitTransactionRequest) -> SubmitTransactionResponse
+        ),
+        method!(
+            "kala_reserveNonces",
+            "Reserve a nonce range for parallel submission",
+            (req: ReserveNoncesRequest) -> NonceReservation
+        ),
+        method!(
+            "kala_getTick",
+            "Retrieve a tick certificate",
+            (req: GetTickRequest) -> Option<TickCertificate>
+        ),
+        method!(
+            "kala_verifyTickProof",
+            "Verify a tick certificate's hash, linkage, and VDF output",
+            (req: VerifyTickProofRequest) -> TickProofVerification
+        ),
+        method!(
+            "kala_getTickRangeProof",
+            "Prove a range of ticks with sampled VDF segments",
+            (req: GetTickRangeProofRequest) -> TickRangeProof
+        ),
+        method!(
+            "kala_timestampData",
+            "Timestamp arbitrary data in the VDF hash chain",
+            (req: TimestampDataRequest) -> TimestampProof
+        ),
+        method!(
+            "kala_verifyTimestamp",
+            "Verify a timestamp proof issued by the node",
+            (proof: TimestampProof) -> TimestampVerification
+        ),
+        method!(
+            "kala_getTickData",
+            "Retrieve data timestamped in a tick",
+            (req: GetTickDataRequest) -> Option<TickDataEntry>
+        ),
+        method!(
+            "kala_proveTickData",
+            "Prove data is committed to its tick's data root",
+            (req: ProveTickDataRequest) -> Option<TimestampProof>
+        ),
+        method!(
+            "kala_getRecentTicks",
+            "Get recent tick history",
+            (count: usize) -> Vec<TickCertificate>
+        ),
+        method!(
+            "kala_getAccount",
+            "Query account balances and state",
+            (req: GetAccountRequest) -> Option<AccountInfo>
+        ),
+        method!("kala_getSupply", "Get the token supply", () -> SupplyInfo),
+        MethodSpec {
+            notification: Some(("kala_accountUpdate", |g| g.subschema_for::<AccountUpdate>())),
+            ..method!(
+                "kala_watchAccount",
+                "Subscribe to account changes over WebSocket",
+                (address: String) -> u64
+            )
+        },
+        method!(
+            "kala_unwatchAccount",
+            "Cancel an account subscription",
+            (subscription: u64) -> bool
+        ),
+        method!(
+            "kala_syncStatus",
+            "Check whether the node is catching up with its peers",
+            () -> SyncStatus
+        ),
+        method!(
+            "kala_getEpochSummary",
+            "Get a signed epoch summary for light clients",
+            (req: GetEpochRequest) -> Option<SignedEpochSummary>
+        ),
+        method!(
+            "kala_getTickFinality",
+            "Get the witness signatures and metadata finalizing a tick",
+            (req: GetTickRequest) -> Option<TickFinality>
+        ),
+        method!(
+            "kala_getOracleValue",
+            "Get a feed's medianized value at a tick, with its proof",
+            (req: GetOracleValueRequest) -> Option<OracleProof>
+        ),
+        method!(
+            "kala_getMetricsHistory",
+            "Get persisted performance samples",
+            (req: GetMetricsHistoryRequest) -> Vec<MetricsSample>
+        ),
+        method!(
+            "kala_traceTransaction",
+            "Get the lifecycle timeline of a transaction",
+            (tx_hash: String) -> Option<TransactionTrace>
+        ),
+        method!(
+            "kala_getVersion",
+            "Identify the exact build of the node software",
+            () -> NodeVersion
+        ),
+        method!(
+            "kala_iterationToTime",
+            "Estimate the UTC time an iteration runs at",
+            (iteration: u64) -> TimelinePoint
+        ),
+        method!(
+            "kala_timeToIteration",
+            "Estimate the iteration running at a UTC time",
+            (time_ms: u64) -> TimelinePoint
+        ),
+        method!(
+            "kala_getTimeBounds",
+            "Bound a tick's UTC time by witness-signed anchors",
+            (req: GetTickRequest) -> TimeBounds
+        ),
+        method!(
+            "kala_debugFfiMemory",
+            "Report memory held across native libraries, if enabled",
+            () -> FfiMemoryReport
+        ),
+        method!("admin_peers", "List the peers the node knows", () -> Vec<PeerInfo>),
+        method!(
+            "admin_banPeer",
+            "Ban a peer, disconnecting it and never redialing it",
+            (peer_id: String) -> PeerInfo
+        ),
+        method!(
+            "admin_mempool",
+            "List the transactions waiting in the mempool",
+            () -> Vec<MempoolEntry>
+        ),
+        method!(
+            "admin_flushMempool",
+            "Drop every transaction waiting in the mempool",
+            () -> usize
+        ),
+        method!(
+            "admin_exportSnapshot",
+            "Write a verified snapshot of the state database",
+            (path: String) -> SnapshotExport
+        ),
+        method!(
+            "admin_pruneNow",
+            "Move settled ticks past the retention window to the archive now",
+            () -> u64
+        ),
+        method!(
+            "admin_setLogLevel",
+            "Change which log lines the node writes",
+            (filter: String) -> ()
+        ),
+    ]
+}
+
+/// Build the OpenRPC document for the methods in `served`
+///
+/// Methods without a description here are still listed, with untyped
+/// parameters and result, so the document always matches the server.
+///
+/// # Parameters
+///
+/// - `served`: Names of the registered methods
+///
+/// # Returns
+///
+/// The document as JSON, ready to serve from [`DISCOVER_METHOD`]
+pub fn document<'a>(served: impl IntoIterator<Item = &'a str>) -> Value {
+    let mut generator = SchemaSettings::draft07()
+        .with(|settings| settings.definitions_path = "/components/schemas".into())
+        .into_generator();
+
+    let mut served: Vec<&str> = served.into_iter().collect();
+    let specs = methods();
+    // Documented methods first, in their order, then any others by name
+    served.sort_by_key(|name| {
+        let position = specs.iter().position(|spec| spec.name == *name);
+        (position.unwrap_or(usize::MAX), *name)
+    });
+
+    let methods: Vec<Value> = served
+        .into_iter()
+        .map(|name| match specs.iter().find(|spec| spec.name == name) {
+            Some(spec) => describe(spec, &mut generator),
+            None => json!({
+                "name": name,
+                "params": [],
+                "result": { "name": "result", "schema": {} },
+            }),
+        })
+        .collect();
+
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "Kala JSON-RPC API",
+            "description": "Timestamping, timelock transactions and accounts of a Kala node",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods,
+        "components": {
+            "schemas": generator.take_definitions(true),
+        },
+    })
+}
+
+/// Build the OpenRPC document of every method this crate describes
+///
+/// Unlike [`document`], this covers the admin namespace whether or not a
+/// server serves it, as client bindings are generated from it.
+///
+/// # Returns
+///
+/// The document as JSON
+pub fn full_document() -> Value {
+    document(methods().into_iter().map(|spec| spec.name))
+}
+
+/// OpenRPC method object of `spec`
+fn describe(spec: &MethodSpec, generator: &mut SchemaGenerator) -> Value {
+    let params: Vec<Value> = spec
+        .params
+        .iter()
+        .map(|(name, schema)| {
+            json!({
+                "name": name,
+                "required": true,
+                "schema": schema(generator),
+            })
+        })
+        .collect();
+
+    let mut method = json!({
+        "name": spec.name,
+        "summary": spec.summary,
+        "params": params,
+        "result": {
+            "name": "result",
+            "schema": (spec.result)(generator),
+        },
+    });
+    if let Some((notification, item)) = &spec.notification {
+        method["x-notification"] = json!({
+            "name": notification,
+            "schema": item(generator),
+        });
+    }
+    method
+}
@@ -0,0 +1,252 @@
+//! REST gateway
+//!
+//! A small HTTP facade for integrators that can't speak JSON-RPC. Like
+//! the [`status`](crate::status) page, every route is answered by the same
+//! [`KalaApiServer`] implementation that serves JSON-RPC, so requests and
+//! responses carry the JSON-RPC types unchanged:
+//!
+//! - `GET /ticks/{number}`: the tick certificate, as `kala_getTick`
+//! - `GET /accounts/{address}`: the account's state, as `kala_getAccount`
+//! - `POST /transactions`: submit a [`SubmitTransactionRequest`] body, as
+//!   `kala_submitTransaction`
+//!
+//! Missing ticks and accounts answer `404 Not Found`. Failed calls answer
+//! with the JSON-RPC error object, under `400 Bad Request` for invalid
+//! parameters and `500 Internal Server Error` otherwise.
+//!
+//! The gateway is served like the JSON-RPC server, over TLS when
+//! configured and behind the same CORS and [`limits`](crate::limits):
+//! each request counts as a call to the JSON-RPC method it stands for.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{DefaultBodyLimit, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::error::{ErrorObjectOwned, INVALID_PARAMS_CODE};
+use kala_common::prelude::*;
+
+use crate::limits::RateLimitLayer;
+use crate::{
+    accept_connections, router_service, CorsLayer, GetAccountRequest, GetTickRequest,
+    KalaApiServer, RpcConfig, SubmitTransactionRequest, TlsConfig,
+};
+
+/// Serve the REST gateway until the server fails
+///
+/// # Parameters
+///
+/// - `config`: Listen address, limits, TLS and CORS of the gateway; the
+///   admin token is unused
+/// - `api_impl`: The node's API implementation, queried on every request
+///
+/// # Errors
+///
+/// - [`KalaError::Config`] if the TLS certificate or key can't be loaded
+/// - [`KalaError::Network`] if binding fails
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_rpc::{start_rest_server, RpcConfig};
+///
+/// # async fn example<T: kala_rpc::KalaApiServer>(api_impl: T) -> kala_common::KalaResult<()> {
+/// let config = RpcConfig {
+///     listen_addr: "127.0.0.1:8548".parse().unwrap(),
+///     limits: Default::default(),
+///     tls: None,
+///     cors_allowed_origins: Vec::new(),
+///     admin_token: None,
+/// };
+/// start_rest_server(config, api_impl).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn start_rest_server<T: KalaApiServer>(config: RpcConfig, api_impl: T) -> KalaResult<()> {
+    let max_body_size = config.limits.max_request_body_size as usize;
+    let router = router(api_impl).layer(DefaultBodyLimit::max(max_body_size));
+    let service = tower::ServiceBuilder::new()
+        .layer(CorsLayer::new(config.cors_allowed_origins))
+        .layer(RateLimitLayer::for_routes(&config.limits, route_method))
+        .service(router_service(router));
+
+    let acceptor = config.tls.as_ref().map(TlsConfig::acceptor).transpose()?;
+    let listener = tokio::net::TcpListener::bind(config.listen_addr)
+        .await
+        .map_err(|e| KalaError::network(format!("Failed to bind REST gateway: {}", e)))?;
+    let scheme = if acceptor.is_some() { "https" } else { "http" };
+    tracing::info!("REST gateway listening on {}://{}", scheme, config.listen_addr);
+
+    accept_connections(listener, acceptor, "REST", || service.clone()).await
+}
+
+/// Routes of the gateway over `api_impl`
+fn router<T: KalaApiServer>(api_impl: T) -> Router {
+    Router::new()
+        .route("/ticks/{number}", get(get_tick::<T>))
+        .route("/accounts/{address}", get(get_account::<T>))
+        .route("/transactions", post(submit_transaction::<T>))
+        .with_state(Arc::new(api_impl))
+}
+
+/// JSON-RPC method a gateway request stands for, for rate limits
+fn route_method(method: &str, path: &str) -> Option<&'static str> {
+    match (method, path.split('/').nth(1)) {
+        ("GET", Some("ticks")) => Some("kala_getTick"),
+        ("GET", Some("accounts")) => Some("kala_getAccount"),
+        ("POST", Some("transactions")) => Some("kala_submitTransaction"),
+        _ => None,
+    }
+}
+
+async fn get_tick<T: KalaApiServer>(State(api): State<Arc<T>>, Path(number): Path<u64>) -> Response {
+    let request = GetTickRequest { tick_number: number };
+    found(api.get_tick(request).await)
+}
+
+async fn get_account<T: KalaApiServer>(State(api): State<Arc<T>>, Path(address): Path<String>) -> Response {
+    let request = GetAccountRequest { address };
+    found(api.get_account(request).await)
+}
+
+async fn submit_transaction<T: KalaApiServer>(
+    State(api): State<Arc<T>>,
+    Json(request): Json<SubmitTransactionRequest>,
+) -> Response {
+    match api.submit_transaction(request).await {
+        Ok(response) => (StatusCode::ACCEPTED, Json(response)).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Answer with the value, or `404 Not Found` if there is none
+fn found<R: Serialize>(result: RpcResult<Option<R>>) -> Response {
+    match result {
+        Ok(Some(value)) => Json(value).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Answer with a JSON-RPC error object under the matching HTTP status
+fn error_response(error: ErrorObjectOwned) -> Response {
+    let status = match error.code() {
+        INVALID_PARAMS_CODE => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(error)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{account, certificate, StubApi};
+    use crate::RpcLimits;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use kala_state::TickType;
+    use tower::ServiceExt;
+
+    fn api() -> StubApi {
+        let mut api = StubApi::default();
+        api.ticks.insert(3, certificate(3, TickType::Full, 2));
+        api.accounts.insert("ab".repeat(32), account(500, None));
+        api
+    }
+
+    async fn send(request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = router(api()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn submit(encrypted_tx: &str) -> Request<Body> {
+        Request::post("/transactions")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({ "encrypted_tx": encrypted_tx }).to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_tick() {
+        let (status, body) = send(get("/ticks/3")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tick_number"], 3);
+        assert_eq!(body["transaction_count"], 2);
+
+        let (status, _) = send(get("/ticks/4")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = send(get("/ticks/latest")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_account() {
+        let (status, body) = send(get(&format!("/accounts/{}", "ab".repeat(32)))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["balance"], 500);
+
+        let (status, _) = send(get(&format!("/accounts/{}", "cd".repeat(32)))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Invalid parameters keep the JSON-RPC error object
+        let (status, body) = send(get("/accounts/not-hex")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], INVALID_PARAMS_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_submit_transaction() {
+        let (status, body) = send(submit("c0ffee")).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(body["tx_hash"], "c0ffee");
+
+        let (status, body) = send(submit("not hex")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], INVALID_PARAMS_CODE);
+    }
+
+    #[test]
+    fn test_route_methods() {
+        assert_eq!(route_method("GET", "/ticks/3"), Some("kala_getTick"));
+        assert_eq!(route_method("GET", "/accounts/ab"), Some("kala_getAccount"));
+        assert_eq!(route_method("POST", "/transactions"), Some("kala_submitTransaction"));
+        assert_eq!(route_method("OPTIONS", "/transactions"), None);
+        assert_eq!(route_method("GET", "/"), None);
+    }
+
+    #[tokio::test]
+    async fn test_served_behind_limits() {
+        let limits = RpcLimits {
+            max_request_body_size: 64,
+            method_requests_per_second: [("kala_submitTransaction".to_string(), 1)].into(),
+            ..Default::default()
+        };
+        let service = tower::ServiceBuilder::new()
+            .layer(RateLimitLayer::for_routes(&limits, route_method))
+            .service(router_service(router(api())));
+        let request = |request: Request<Body>| request.map(jsonrpsee::server::HttpBody::new);
+
+        let response = service.clone().oneshot(request(submit("c0ffee"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        // The submission budget is spent, while reads go on
+        let response = service.clone().oneshot(request(submit("c0ffee"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let response = service.clone().oneshot(request(get("/ticks/3"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let oversized = submit(&"00".repeat(64));
+        let response = service.oneshot(request(oversized)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -0,0 +1,215 @@
+//! Built-in HTML status page
+//!
+//! A single self-refreshing page showing the chain head, sync progress,
+//! and the last hour of persisted metrics samples, for deployments without
+//! Prometheus or Grafana. The page is rendered on the server from the same
+//! [`KalaApiServer`] implementation that serves JSON-RPC, so it needs no
+//! JavaScript and no extra state.
+
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{extract::State, response::Html, routing::get, Router};
+use kala_common::prelude::*;
+use kala_state::MetricsSample;
+
+use crate::{ChainInfo, GetMetricsHistoryRequest, KalaApiServer, SyncStatus};
+
+/// Seconds of history shown on the status page
+const STATUS_HISTORY_SECS: u64 = 3600;
+
+/// Number of most recent samples listed in the table
+const STATUS_TABLE_ROWS: usize = 20;
+
+/// Serve the status page on `listen_addr` until the server fails
+///
+/// # Parameters
+///
+/// - `listen_addr`: Socket address for the HTTP listener
+/// - `api_impl`: The node's API implementation, queried on every request
+///
+/// # Errors
+///
+/// - [`KalaError::Network`] if binding or serving fails
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_rpc::start_status_server;
+///
+/// # async fn example<T: kala_rpc::KalaApiServer>(api_impl: T) -> kala_common::KalaResult<()> {
+/// start_status_server("127.0.0.1:9090".parse().unwrap(), api_impl).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn start_status_server<T: KalaApiServer>(listen_addr: SocketAddr, api_impl: T) -> KalaResult<()> {
+    let router = Router::new()
+        .route("/", get(status_page::<T>))
+        .with_state(Arc::new(api_impl));
+
+    let listener = tokio::net::TcpListener::bind(listen_addr)
+        .await
+        .map_err(|e| KalaError::network(format!("Failed to bind status page: {}", e)))?;
+    tracing::info!("Status page listening on http://{}", listen_addr);
+
+    axum::serve(listener, router)
+        .await
+        .map_err(|e| KalaError::network(format!("Status page server failed: {}", e)))
+}
+
+async fn status_page<T: KalaApiServer>(State(api): State<Arc<T>>) -> Html<String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let request = GetMetricsHistoryRequest {
+        from: Some(now.saturating_sub(STATUS_HISTORY_SECS)),
+        to: None,
+    };
+
+    let info = api.chain_info().await.ok();
+    let sync = api.sync_status().await.ok();
+    let samples = api.get_metrics_history(request).await.unwrap_or_default();
+    Html(render_status_page(info.as_ref(), sync.as_ref(), &samples))
+}
+
+/// Render the status page
+///
+/// Sections whose data could not be loaded are shown as unavailable.
+///
+/// # Example
+///
+/// ```
+/// use kala_rpc::status::render_status_page;
+///
+/// let html = render_status_page(None, None, &[]);
+/// assert!(html.contains("No samples recorded yet"));
+/// ```
+pub fn render_status_page(
+    info: Option<&ChainInfo>,
+    sync: Option<&SyncStatus>,
+    samples: &[MetricsSample],
+) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+         <meta http-equiv=\"refresh\" content=\"10\"><title>Kala node status</title>\
+         <style>body{font-family:monospace;margin:2em}table{border-collapse:collapse}\
+         td,th{padding:2px 12px;text-align:right}svg{border:1px solid #ccc}</style>\
+         </head><body><h1>Kala node status</h1>\n",
+    );
+
+    html.push_str("<h2>Chain</h2>\n");
+    match info {
+        Some(info) => {
+            let _ = writeln!(
+                html,
+                "<table><tr><th>Tick</th><td>{}</td></tr><tr><th>VDF iteration</th><td>{}</td></tr>\
+                 <tr><th>Transactions</th><td>{}</td></tr><tr><th>Accounts</th><td>{}</td></tr></table>",
+                info.current_tick, info.current_iteration, info.total_transactions, info.accounts
+            );
+            if let Some(tuning) = &info.vdf_tuning {
+                let _ = writeln!(
+                    html,
+                    "<p>VDF tuning: {} verification threads, AVX-512 {}, fast mode {}</p>",
+                    tuning.num_threads,
+                    on_off(tuning.avx512),
+                    on_off(tuning.fast_mode)
+                );
+            }
+            if let Some(calibration) = &info.vdf_calibration {
+                let _ = writeln!(
+                    html,
+                    "<p>Calibrated at {:.0} iterations/sec: {} ms per tick, target {} ms</p>",
+                    calibration.iterations_per_second,
+                    calibration.tick_duration_ms,
+                    calibration.target_tick_duration_ms
+                );
+            }
+        }
+        None => html.push_str("<p>Unavailable</p>\n"),
+    }
+
+    html.push_str("<h2>Sync</h2>\n");
+    match sync {
+        Some(sync) => {
+            let _ = writeln!(
+                html,
+                "<table><tr><th>Mode</th><td>{:?}</td></tr><tr><th>Highest peer tick</th><td>{}</td></tr>\
+                 <tr><th>Peers</th><td>{}</td></tr></table>",
+                sync.mode, sync.highest_tick, sync.peer_count
+            );
+        }
+        None => html.push_str("<p>Unavailable</p>\n"),
+    }
+
+    html.push_str("<h2>Last hour</h2>\n");
+    if samples.is_empty() {
+        html.push_str("<p>No samples recorded yet</p>\n");
+    } else {
+        let speeds: Vec<f64> = samples.iter().map(|s| s.iterations_per_second).collect();
+        let durations: Vec<f64> = samples.iter().map(|s| s.mean_tick_ms as f64).collect();
+        let depths: Vec<f64> = samples.iter().map(|s| s.mempool_depth as f64).collect();
+        let _ = writeln!(
+            html,
+            "<p>Iterations/sec<br>{}</p><p>Mean tick (ms)<br>{}</p><p>Mempool depth<br>{}</p>",
+            sparkline(&speeds),
+            sparkline(&durations),
+            sparkline(&depths)
+        );
+
+        html.push_str(
+            "<table><tr><th>Time</th><th>Tick</th><th>Iter/s</th><th>Mean ms</th>\
+             <th>Max ms</th><th>Mempool</th></tr>\n",
+        );
+        for sample in samples.iter().rev().take(STATUS_TABLE_ROWS) {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{:.0}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                sample.timestamp,
+                sample.tick_number,
+                sample.iterations_per_second,
+                sample.mean_tick_ms,
+                sample.max_tick_ms,
+                sample.mempool_depth
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body></html>\n");
+    html
+}
+
+/// Inline SVG line chart of `values`, scaled to fit
+fn sparkline(values: &[f64]) -> String {
+    const WIDTH: f64 = 360.0;
+    const HEIGHT: f64 = 60.0;
+
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    let step = if values.len() > 1 { WIDTH / (values.len() - 1) as f64 } else { 0.0 };
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let y = if max > 0.0 { HEIGHT - value / max * HEIGHT } else { HEIGHT };
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{}\" height=\"{}\"><polyline fill=\"none\" stroke=\"#36c\" points=\"{}\"/></svg> max {:.0}",
+        WIDTH,
+        HEIGHT,
+        points.join(" "),
+        max
+    )
+}
+
+/// `on` or `off`
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
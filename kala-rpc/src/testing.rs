@@ -0,0 +1,216 @@
+//! Stub API the endpoint tests query
+//!
+//! [`StubApi`] answers the calls the REST and GraphQL endpoints make from
+//! maps filled in by each test; the rest aren't served.
+
+use std::collections::{BTreeMap, HashMap};
+
+use jsonrpsee::core::{async_trait, RpcResult, SubscriptionResult};
+use jsonrpsee::types::error::{ErrorObject, INVALID_PARAMS_CODE};
+use jsonrpsee::PendingSubscriptionSink;
+use kala_state::{
+    MetricsSample, OracleProof, SignedEpochSummary, TickCertificate, TickFinality, TickRangeProof,
+    TickType, TimeBounds,
+};
+
+use crate::*;
+
+/// Node state the stub serves
+#[derive(Default)]
+pub(crate) struct StubApi {
+    pub ticks: BTreeMap<u64, TickCertificate>,
+    pub accounts: HashMap<String, AccountInfo>,
+    pub traces: HashMap<String, TransactionTrace>,
+}
+
+/// Certificate of tick `tick_number`, its hashes derived from the number
+pub(crate) fn certificate(
+    tick_number: u64,
+    tick_type: TickType,
+    transactions: u32,
+) -> TickCertificate {
+    TickCertificate {
+        tick_number,
+        tick_type,
+        vdf_iteration: tick_number * 1000,
+        vdf_form: ("1".into(), "1".into(), "1".into()),
+        hash_chain_value: [0u8; 32],
+        tick_hash: [tick_number as u8; 32],
+        transaction_count: transactions,
+        transaction_merkle_root: [0u8; 32],
+        timestamp: tick_number * 65,
+        previous_tick_hash: [tick_number.wrapping_sub(1) as u8; 32],
+        usage: Default::default(),
+    }
+}
+
+/// Account holding `balance`, delegating to `delegation` if set
+pub(crate) fn account(balance: u64, delegation: Option<&str>) -> AccountInfo {
+    AccountInfo {
+        balance,
+        nonce: 1,
+        staked_amount: 0,
+        delegation: delegation.map(str::to_string),
+        denom: DenomMetadata {
+            denom: "00".repeat(32),
+            symbol: "KALA".to_string(),
+            name: "Kala".to_string(),
+            decimals: 6,
+        },
+        balance_display: balance.to_string(),
+        staked_display: "0".to_string(),
+        pending_nonces: Vec::new(),
+    }
+}
+
+fn invalid_params(message: &str) -> ErrorObject<'static> {
+    ErrorObject::owned(INVALID_PARAMS_CODE, message.to_string(), None::<()>)
+}
+
+#[async_trait]
+impl KalaApiServer for StubApi {
+    async fn chain_info(&self) -> RpcResult<ChainInfo> {
+        Ok(ChainInfo {
+            current_tick: self.ticks.keys().next_back().map_or(0, |&number| number + 1),
+            current_iteration: 0,
+            vdf_output: String::new(),
+            hash_chain: "00".repeat(32),
+            total_transactions: self.ticks.values().map(|t| t.transaction_count as u64).sum(),
+            accounts: self.accounts.len(),
+            vdf_calibration: None,
+            vdf_tuning: None,
+        })
+    }
+
+    async fn get_chain_params(&self) -> RpcResult<ChainParams> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn submit_transaction(
+        &self,
+        req: SubmitTransactionRequest,
+    ) -> RpcResult<SubmitTransactionResponse> {
+        hex::decode(&req.encrypted_tx).map_err(|_| invalid_params("Invalid hex encoding"))?;
+        Ok(SubmitTransactionResponse {
+            tx_hash: req.encrypted_tx,
+            submission_iteration: 0,
+            target_tick: 1,
+            trace_id: "trace".to_string(),
+        })
+    }
+
+    async fn reserve_nonces(&self, _req: ReserveNoncesRequest) -> RpcResult<NonceReservation> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_tick(&self, req: GetTickRequest) -> RpcResult<Option<TickCertificate>> {
+        Ok(self.ticks.get(&req.tick_number).cloned())
+    }
+
+    async fn verify_tick_proof(
+        &self,
+        _req: VerifyTickProofRequest,
+    ) -> RpcResult<TickProofVerification> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_tick_range_proof(
+        &self,
+        _req: GetTickRangeProofRequest,
+    ) -> RpcResult<TickRangeProof> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn timestamp_data(&self, _req: TimestampDataRequest) -> RpcResult<TimestampProof> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn verify_timestamp(&self, _proof: TimestampProof) -> RpcResult<TimestampVerification> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_tick_data(&self, _req: GetTickDataRequest) -> RpcResult<Option<TickDataEntry>> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn prove_tick_data(
+        &self,
+        _req: ProveTickDataRequest,
+    ) -> RpcResult<Option<TimestampProof>> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_recent_ticks(&self, count: usize) -> RpcResult<Vec<TickCertificate>> {
+        Ok(self.ticks.values().rev().take(count).cloned().collect())
+    }
+
+    async fn get_account(&self, req: GetAccountRequest) -> RpcResult<Option<AccountInfo>> {
+        hex::decode(&req.address).map_err(|_| invalid_params("Invalid address"))?;
+        Ok(self.accounts.get(&req.address).cloned())
+    }
+
+    async fn get_supply(&self) -> RpcResult<SupplyInfo> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn watch_account(
+        &self,
+        _pending: PendingSubscriptionSink,
+        _address: String,
+    ) -> SubscriptionResult {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn sync_status(&self) -> RpcResult<SyncStatus> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_epoch_summary(
+        &self,
+        _req: GetEpochRequest,
+    ) -> RpcResult<Option<SignedEpochSummary>> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_tick_finality(&self, _req: GetTickRequest) -> RpcResult<Option<TickFinality>> {
+        Ok(None)
+    }
+
+    async fn get_oracle_value(
+        &self,
+        _req: GetOracleValueRequest,
+    ) -> RpcResult<Option<OracleProof>> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_metrics_history(
+        &self,
+        _req: GetMetricsHistoryRequest,
+    ) -> RpcResult<Vec<MetricsSample>> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn trace_transaction(&self, tx_hash: String) -> RpcResult<Option<TransactionTrace>> {
+        Ok(self.traces.get(&tx_hash).cloned())
+    }
+
+    async fn get_version(&self) -> RpcResult<NodeVersion> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn iteration_to_time(&self, _iteration: u64) -> RpcResult<TimelinePoint> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn time_to_iteration(&self, _time_ms: u64) -> RpcResult<TimelinePoint> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn get_time_bounds(&self, _req: GetTickRequest) -> RpcResult<TimeBounds> {
+        unimplemented!("not served by the stub")
+    }
+
+    async fn debug_ffi_memory(&self) -> RpcResult<FfiMemoryReport> {
+        unimplemented!("not served by the stub")
+    }
+}
@@ -0,0 +1,65 @@
+//! HTTPS for the JSON-RPC server
+//!
+//! With a certificate and key configured, the server terminates TLS
+//! itself, so browsers and dApps can reach it over HTTPS without a reverse
+//! proxy. Both files are PEM; the certificate file holds the chain, leaf
+//! first. HTTP/2 and HTTP/1.1 are offered through ALPN.
+//!
+//! TLS runs on rustls through `futures-rustls`, which the node already
+//! builds for libp2p, with the streams adapted to tokio.
+
+use futures_rustls::pki_types::pem::PemObject;
+use futures_rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use futures_rustls::rustls::crypto::ring;
+use futures_rustls::rustls::ServerConfig;
+use futures_rustls::TlsAcceptor;
+use kala_common::prelude::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Certificate and key the server presents
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// PEM file with the certificate chain, leaf first
+    pub cert_path: PathBuf,
+    /// PEM file with the private key
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Load the certificate and key into an acceptor for incoming connections
+    ///
+    /// # Errors
+    ///
+    /// - [`KalaError::Config`] if either file can't be read or parsed, or
+    ///   the key doesn't suit the certificate
+    pub fn acceptor(&self) -> KalaResult<TlsAcceptor> {
+        let certs = CertificateDer::pem_file_iter(&self.cert_path)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                KalaError::config(format!(
+                    "Failed to read TLS certificate {}: {}",
+                    self.cert_path.display(),
+                    e
+                ))
+            })?;
+        if certs.is_empty() {
+            return Err(KalaError::config(format!(
+                "No certificate in {}",
+                self.cert_path.display()
+            )));
+        }
+        let key = PrivateKeyDer::from_pem_file(&self.key_path).map_err(|e| {
+            KalaError::config(format!("Failed to read TLS key {}: {}", self.key_path.display(), e))
+        })?;
+
+        let mut config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| KalaError::config(format!("Unsupported TLS versions: {}", e)))?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| KalaError::config(format!("Invalid TLS certificate or key: {}", e)))?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
@@ -0,0 +1,321 @@
+//! Witness-signed anchors between iterations and UTC time
+//!
+//! Every few ticks, witnesses sign the time on their clocks along with
+//! their tick vote. Once the tick is finalized, the readings carried in its
+//! [`TickFinality`] are combined into a [`TimeAnchor`]: the weighted median
+//! of the times, pinned to the iteration the tick ended at. As with
+//! [`CanonicalTimestamp`](crate::CanonicalTimestamp), the reporting
+//! witnesses must hold more than 2/3 of the weight, so faulty witnesses
+//! can't pull the median outside the range honest clocks reported.
+//!
+//! Anchors correct the drift of a
+//! [`TimelineClock`](kala_common::timeline::TimelineClock). They also let a
+//! light client bound when any historical tick ran: with the finality
+//! records of the anchors around it verified, and VDF proofs placing the
+//! tick between them, the tick ran no earlier than the anchor before it and
+//! no later than the anchor after it, see [`TimeBounds`].
+//!
+//! A node combines anchors from the finality records it aggregated, so on
+//! their own they're only its word. Bounds served to a light client carry
+//! those records, and [`TimeBounds::verify`] combines the anchors again from
+//! the signed readings.
+
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{EncodingType, KalaSerialize};
+use kala_common::types::{BlockHeight, IterationNumber, Timestamp};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::witness::{TickFinality, WitnessSet};
+
+/// Time the witnesses agree an iteration ran at
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimeAnchor {
+    /// Tick whose finality record carries the readings
+    pub tick_number: BlockHeight,
+    /// Iteration the tick ended at
+    pub iteration: IterationNumber,
+    /// Weighted median of the readings, Unix time in milliseconds
+    pub time_ms: Timestamp,
+    /// Witness weight behind the readings
+    pub reported_weight: u64,
+    pub reports: u32,
+}
+
+impl TimeAnchor {
+    /// Combine the clock readings in a finality record
+    ///
+    /// The record is assumed to be verified against `witnesses`. Returns
+    /// `None` unless the witnesses that reported a time hold a quorum of
+    /// the weight.
+    pub fn from_finality(
+        finality: &TickFinality,
+        witnesses: &WitnessSet,
+        iteration: IterationNumber,
+    ) -> Option<Self> {
+        let mut by_witness = BTreeMap::new();
+        for entry in &finality.times {
+            if let Some(witness) = witnesses.get(&entry.witness) {
+                by_witness.entry(entry.witness).or_insert((entry.time.time_ms, witness.weight));
+            }
+        }
+
+        let reported_weight: u64 = by_witness.values().map(|(_, weight)| weight).sum();
+        if !witnesses.is_quorum(reported_weight) {
+            return None;
+        }
+
+        // Lower weighted median: the first time covering half the weight
+        let mut times: Vec<(u64, u64)> = by_witness.into_values().collect();
+        times.sort_unstable();
+        let mut cumulative = 0u128;
+        let time_ms = times.iter().find_map(|&(time_ms, weight)| {
+            cumulative += weight as u128;
+            (cumulative * 2 >= reported_weight as u128).then_some(time_ms)
+        })?;
+
+        Some(Self {
+            tick_number: finality.tick_number,
+            iteration,
+            time_ms,
+            reported_weight,
+            reports: times.len() as u32,
+        })
+    }
+}
+
+impl KalaSerialize for TimeAnchor {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Compact, one is written every anchor interval
+    }
+}
+
+/// Range of UTC times an iteration ran within, from the anchors around it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimeBounds {
+    pub iteration: IterationNumber,
+    /// Latest anchor at or before the iteration
+    pub earliest: Option<TimeAnchor>,
+    /// Earliest anchor at or after the iteration
+    pub latest: Option<TimeAnchor>,
+    /// Finality record carrying the readings of `earliest`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub earliest_finality: Option<TickFinality>,
+    /// Finality record carrying the readings of `latest`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_finality: Option<TickFinality>,
+}
+
+impl TimeBounds {
+    /// Bound `iteration` by the closest of `anchors` on either side
+    pub fn new<'a>(
+        iteration: IterationNumber,
+        anchors: impl IntoIterator<Item = &'a TimeAnchor>,
+    ) -> Self {
+        let mut earliest: Option<&TimeAnchor> = None;
+        let mut latest: Option<&TimeAnchor> = None;
+        for anchor in anchors {
+            if anchor.iteration <= iteration
+                && earliest.is_none_or(|earliest| anchor.iteration > earliest.iteration)
+            {
+                earliest = Some(anchor);
+            }
+            if anchor.iteration >= iteration
+                && latest.is_none_or(|latest| anchor.iteration < latest.iteration)
+            {
+                latest = Some(anchor);
+            }
+        }
+        Self {
+            iteration,
+            earliest: earliest.cloned(),
+            latest: latest.cloned(),
+            earliest_finality: None,
+            latest_finality: None,
+        }
+    }
+
+    /// Attach the finality records the anchors were combined from
+    ///
+    /// Records of ticks neither anchor is for are ignored.
+    pub fn with_finality(mut self, records: impl IntoIterator<Item = TickFinality>) -> Self {
+        let tick = |anchor: &Option<TimeAnchor>| anchor.as_ref().map(|anchor| anchor.tick_number);
+        for record in records {
+            if tick(&self.earliest) == Some(record.tick_number) {
+                self.earliest_finality = Some(record.clone());
+            }
+            if tick(&self.latest) == Some(record.tick_number) {
+                self.latest_finality = Some(record);
+            }
+        }
+        self
+    }
+
+    /// Earliest Unix time in milliseconds the iteration can have run at
+    pub fn earliest_ms(&self) -> Option<Timestamp> {
+        self.earliest.as_ref().map(|anchor| anchor.time_ms)
+    }
+
+    /// Latest Unix time in milliseconds the iteration can have run at
+    pub fn latest_ms(&self) -> Option<Timestamp> {
+        self.latest.as_ref().map(|anchor| anchor.time_ms)
+    }
+
+    /// Whether the anchors agree, the earlier iteration not having the
+    /// later time
+    pub fn is_consistent(&self) -> bool {
+        match (self.earliest_ms(), self.latest_ms()) {
+            (Some(earliest), Some(latest)) => earliest <= latest,
+            _ => true,
+        }
+    }
+
+    /// Check the anchors against `witnesses`, for a chain with ticks of
+    /// `iterations_per_tick`
+    ///
+    /// Each anchor must come with its finality record, signed by a quorum of
+    /// the witnesses, and be the one combined from the readings in it. The
+    /// anchors must also lie on either side of the iteration and agree.
+    pub fn verify(&self, witnesses: &WitnessSet, iterations_per_tick: u64) -> KalaResult<()> {
+        let sides = [
+            ("earliest", &self.earliest, &self.earliest_finality),
+            ("latest", &self.latest, &self.latest_finality),
+        ];
+        for (side, anchor, finality) in sides {
+            let Some(anchor) = anchor else {
+                continue;
+            };
+            let finality = finality.as_ref().ok_or_else(|| {
+                KalaError::validation(format!("No finality record for the {} anchor", side))
+            })?;
+            finality.verify(witnesses)?;
+            let tick_end = finality.tick_number.saturating_add(1);
+            let iteration = tick_end.saturating_mul(iterations_per_tick);
+            if TimeAnchor::from_finality(finality, witnesses, iteration).as_ref() != Some(anchor) {
+                return Err(KalaError::validation(format!(
+                    "The {} anchor is not combined from the readings of tick {}",
+                    side, finality.tick_number
+                )));
+            }
+        }
+
+        if self.earliest.as_ref().is_some_and(|anchor| anchor.iteration > self.iteration)
+            || self.latest.as_ref().is_some_and(|anchor| anchor.iteration < self.iteration)
+        {
+            return Err(KalaError::validation(format!(
+                "Anchors do not bound iteration {}",
+                self.iteration
+            )));
+        }
+        if !self.is_consistent() {
+            return Err(KalaError::validation("The earlier anchor has the later time"));
+        }
+        Ok(())
+    }
+}
+
+impl KalaSerialize for TimeBounds {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json // Served to light clients
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::{TickVote, Witness};
+    use kala_common::crypto::signer::{InMemorySigner, SignatureScheme, Signer};
+
+    #[tokio::test]
+    async fn test_anchor_from_finality() {
+        let signers: Vec<_> = (1..=4u8)
+            .map(|seed| InMemorySigner::from_seed(&[seed; 32]).unwrap())
+            .collect();
+        let set = WitnessSet::new(
+            signers
+                .iter()
+                .map(|signer| Witness {
+                    key: signer.public_key(SignatureScheme::Ed25519).try_into().unwrap(),
+                    bls_key: signer.public_key(SignatureScheme::Bls12381),
+                    weight: 1,
+                })
+                .collect(),
+        )
+        .unwrap();
+
+        // One clock runs far ahead, and can't move the median past the others
+        let mut votes = Vec::new();
+        for (signer, time_ms) in signers.iter().zip([1_000, 1_010, 1_020, 9_000_000]) {
+            let mut vote = TickVote::sign_with(5, [9; 32], SignatureScheme::Bls12381, signer)
+                .await
+                .unwrap();
+            vote.attach_time(time_ms, signer).await.unwrap();
+            assert!(vote.verify(&set).is_ok());
+            votes.push(vote);
+        }
+        let finality = TickFinality::aggregate(&set, &votes).unwrap();
+        assert_eq!(finality.verify(&set).unwrap(), 4);
+        let anchor = TimeAnchor::from_finality(&finality, &set, 600).unwrap();
+        assert!((1_000..=1_020).contains(&anchor.time_ms));
+        assert_eq!((anchor.tick_number, anchor.iteration, anchor.reports), (5, 600, 4));
+
+        // A tampered reading fails the record
+        let mut tampered = finality.clone();
+        tampered.times[0].time.time_ms += 1;
+        assert!(tampered.verify(&set).is_err());
+
+        // Readings from less than a quorum make no anchor
+        let mut partial = finality.clone();
+        partial.times.truncate(2);
+        assert!(partial.verify(&set).is_ok());
+        assert!(TimeAnchor::from_finality(&partial, &set, 600).is_none());
+
+        // Light clients combine the anchor again from the signed readings
+        let bounds = TimeBounds::new(550, [&anchor]);
+        assert!(bounds.verify(&set, 100).is_err());
+        let bounds = bounds.with_finality([finality.clone()]);
+        assert_eq!(bounds.latest_finality.as_ref(), Some(&finality));
+        assert!(bounds.earliest_finality.is_none());
+        bounds.verify(&set, 100).unwrap();
+        assert!(bounds.verify(&set, 99).is_err());
+
+        let mut moved = bounds.clone();
+        moved.latest.as_mut().unwrap().time_ms += 1;
+        assert!(moved.verify(&set, 100).is_err());
+        let mut unsigned = bounds.clone();
+        unsigned.latest_finality = Some(tampered);
+        assert!(unsigned.verify(&set, 100).is_err());
+        let mut misplaced = bounds.clone();
+        misplaced.iteration = 700;
+        assert!(misplaced.verify(&set, 100).is_err());
+    }
+
+    #[test]
+    fn test_time_bounds() {
+        let anchor = |iteration: u64, time_ms: u64| TimeAnchor {
+            tick_number: iteration / 100,
+            iteration,
+            time_ms,
+            reported_weight: 3,
+            reports: 3,
+        };
+        let anchors = [anchor(1_000, 10_000), anchor(3_000, 30_000), anchor(2_000, 20_000)];
+
+        let bounds = TimeBounds::new(2_500, &anchors);
+        assert_eq!((bounds.earliest_ms(), bounds.latest_ms()), (Some(20_000), Some(30_000)));
+        assert!(bounds.is_consistent());
+
+        let bounds = TimeBounds::new(2_000, &anchors);
+        assert_eq!((bounds.earliest_ms(), bounds.latest_ms()), (Some(20_000), Some(20_000)));
+        let bounds = TimeBounds::new(500, &anchors);
+        assert_eq!((bounds.earliest_ms(), bounds.latest_ms()), (None, Some(10_000)));
+        let bounds = TimeBounds::new(4_000, &anchors);
+        assert_eq!((bounds.earliest_ms(), bounds.latest_ms()), (Some(30_000), None));
+
+        let bounds = TimeBounds::new(1_500, &[anchor(1_000, 20_000), anchor(2_000, 10_000)]);
+        assert!(!bounds.is_consistent());
+    }
+}
@@ -0,0 +1,37 @@
+//! Remote archive of old ticks
+//!
+//! Tick certificates and their VDF certificates make up most of a long
+//! running node's disk usage, but are rarely read once their epoch is
+//! sealed. [`StateDB::archive_ticks`] moves them to an [`ArchiveBackend`],
+//! such as an object store, and removes them locally. Reading an archived
+//! tick fetches it back and checks it against the tick accumulator, which
+//! stays local, so the backend doesn't need to be trusted.
+//!
+//! [`StateDB::archive_ticks`]: crate::StateDB::archive_ticks
+
+use async_trait::async_trait;
+use kala_common::error::KalaResult;
+
+/// Object storage that archived ticks are moved to
+#[async_trait]
+pub trait ArchiveBackend: Send + Sync {
+    /// Store `data` under `key`, replacing any earlier object
+    async fn put(&self, key: &str, data: Vec<u8>) -> KalaResult<()>;
+
+    /// The object stored under `key`, if any
+    async fn get(&self, key: &str) -> KalaResult<Option<Vec<u8>>>;
+
+    /// URI the object stored under `key` can be fetched from outside the
+    /// node, such as `s3://bucket/key`
+    fn uri(&self, key: &str) -> String;
+}
+
+/// Key of an archived tick certificate
+pub(crate) fn tick_key(tick_number: u64) -> String {
+    format!("ticks/{:016x}", tick_number)
+}
+
+/// Key of an archived VDF tick certificate
+pub(crate) fn vdf_tick_key(tick_number: u64) -> String {
+    format!("vdf_ticks/{:016x}", tick_number)
+}
@@ -0,0 +1,471 @@
+//! Timestamp attestations for notarization
+//!
+//! An [`Attestation`] is a self-contained document showing that some data
+//! existed by the time a tick ran: the data's path to the tick's
+//! transaction root, the tick certificate, its inclusion in the tick
+//! accumulator an epoch summary signs, and the time the chain's
+//! [`TimelineClock`] gives its iteration, bounded by the witnesses' time
+//! anchors when available. The issuer signs the whole document, so it can
+//! be filed and checked later without a node.
+//!
+//! [`Attestation::verify`] checks that the pieces fit together and that the
+//! issuer signed them. Whether the epoch summary is trusted is up to the
+//! relying party, who counts its witness signatures with
+//! [`SignedEpochSummary::valid_signatures`] against a witness set it knows,
+//! or follows the epochs to it with [`verify_epoch_chain`](crate::verify_epoch_chain).
+
+use crate::anchor::TimeBounds;
+use crate::epoch::SignedEpochSummary;
+use crate::range_proof::ProvenTick;
+use kala_common::crypto::signer::{SignatureScheme, Signer};
+use kala_common::crypto::{CryptoUtils, MerkleTree};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{CanonicalEncode, CanonicalEncoder, EncodingType, KalaSerialize};
+use kala_common::timeline::TimelineClock;
+use kala_common::types::{Hash, Timestamp};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Current [`Attestation`] format version
+pub const ATTESTATION_VERSION: u32 = 1;
+
+/// Path from a transaction hash to a tick's transaction Merkle root
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionInclusion {
+    pub transaction_hash: Hash,
+    /// Position of the transaction in the tick
+    pub index: u64,
+    /// Sibling hashes from the leaf up, as [`MerkleTree::proof`] returns
+    pub path: Vec<Hash>,
+}
+
+impl TransactionInclusion {
+    pub fn verify(&self, root: &Hash) -> bool {
+        usize::try_from(self.index).is_ok_and(|index| {
+            MerkleTree::verify_proof(&self.transaction_hash, &self.path, root, index)
+        })
+    }
+}
+
+/// When the attested tick ran
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AttestedTime {
+    /// Iteration the tick ended at
+    pub iteration: u64,
+    /// The clock's estimate for the iteration, Unix time in milliseconds
+    pub time_ms: Timestamp,
+    pub clock: TimelineClock,
+    /// Witness time anchors around the iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<TimeBounds>,
+}
+
+/// A signed document attesting when a tick, and optionally a transaction
+/// in it, was recorded
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Attestation {
+    pub version: u32,
+    /// Transaction attested, if not the tick itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<TransactionInclusion>,
+    /// The tick certificate, proven against the epoch's `mmr_root`
+    pub tick: ProvenTick,
+    /// Epoch summary whose accumulator root covers the tick
+    pub epoch: SignedEpochSummary,
+    pub time: AttestedTime,
+    /// When the attestation was issued, Unix time in milliseconds
+    pub issued_at_ms: Timestamp,
+    /// Ed25519 public key of the issuer
+    pub issuer: [u8; 32],
+    /// Issuer's signature over [`Attestation::signing_hash`]
+    pub signature: Vec<u8>,
+}
+
+impl Attestation {
+    /// Unsigned attestation of `tick`, timed by `clock`
+    ///
+    /// Fails if the pieces don't fit together, see [`Self::verify`].
+    pub fn new(
+        tick: ProvenTick,
+        epoch: SignedEpochSummary,
+        clock: TimelineClock,
+        bounds: Option<TimeBounds>,
+        transaction: Option<TransactionInclusion>,
+        issued_at_ms: Timestamp,
+    ) -> KalaResult<Self> {
+        let iteration = tick.certificate.vdf_iteration;
+        let attestation = Self {
+            version: ATTESTATION_VERSION,
+            transaction,
+            tick,
+            epoch,
+            time: AttestedTime {
+                iteration,
+                time_ms: clock.iteration_to_time(iteration),
+                clock,
+                bounds,
+            },
+            issued_at_ms,
+            issuer: [0u8; 32],
+            signature: Vec::new(),
+        };
+        attestation.verify_contents()?;
+        Ok(attestation)
+    }
+
+    /// Hash the issuer signs, the canonical hash of everything but the
+    /// signature
+    pub fn signing_hash(&self) -> Hash {
+        self.canonical_hash()
+    }
+
+    /// Sign as the Ed25519 key behind `signer`
+    pub async fn sign(&mut self, signer: &dyn Signer) -> KalaResult<()> {
+        self.issuer = signer
+            .public_key(SignatureScheme::Ed25519)
+            .try_into()
+            .map_err(|_| KalaError::crypto("Issuer key must be 32 bytes"))?;
+        self.signature = signer.sign(SignatureScheme::Ed25519, &self.signing_hash()).await?;
+        Ok(())
+    }
+
+    /// Check the proofs, the time and the issuer's signature
+    ///
+    /// Doesn't recompute the tick's VDF segment or check the epoch's
+    /// witness signatures, see the [module docs](self).
+    pub fn verify(&self) -> KalaResult<()> {
+        self.verify_contents()?;
+        if !SignatureScheme::Ed25519.verify(&self.issuer, &self.signing_hash(), &self.signature) {
+            return Err(KalaError::crypto("Invalid attestation signature"));
+        }
+        Ok(())
+    }
+
+    fn verify_contents(&self) -> KalaResult<()> {
+        if self.version != ATTESTATION_VERSION {
+            return Err(KalaError::validation(format!(
+                "Unsupported attestation version {}",
+                self.version
+            )));
+        }
+
+        let certificate = &self.tick.certificate;
+        let tick = certificate.tick_number;
+        if certificate.compute_hash() != certificate.tick_hash {
+            return Err(KalaError::validation(format!("Tick {} has an invalid hash", tick)));
+        }
+        if self.tick.proof.leaf_index != tick
+            || !self.epoch.summary.verify_tick(&certificate.tick_hash, &self.tick.proof)
+        {
+            return Err(KalaError::validation(format!(
+                "Tick {} is not committed to epoch {}",
+                tick, self.epoch.summary.epoch_number
+            )));
+        }
+        if let Some(transaction) = &self.transaction {
+            if !transaction.verify(&certificate.transaction_merkle_root) {
+                return Err(KalaError::validation(format!(
+                    "Transaction {} is not in tick {}",
+                    CryptoUtils::hash_to_hex(&transaction.transaction_hash),
+                    tick
+                )));
+            }
+        }
+
+        let time = &self.time;
+        if time.iteration != certificate.vdf_iteration
+            || time.time_ms != time.clock.iteration_to_time(time.iteration)
+        {
+            return Err(KalaError::validation(format!(
+                "Attested time does not match tick {}",
+                tick
+            )));
+        }
+        if time.bounds.as_ref().is_some_and(|bounds| bounds.iteration != time.iteration) {
+            return Err(KalaError::validation(format!(
+                "Time bounds are not for tick {}",
+                tick
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> KalaResult<String> {
+        serde_json::to_string_pretty(self).map_err(KalaError::from)
+    }
+
+    pub fn from_json(json: &str) -> KalaResult<Self> {
+        serde_json::from_str(json).map_err(KalaError::from)
+    }
+
+    /// Plain-text rendering for printing or archiving alongside the JSON
+    ///
+    /// Only the JSON can be verified; the rendering is for people.
+    pub fn render(&self) -> String {
+        let certificate = &self.tick.certificate;
+        let summary = &self.epoch.summary;
+        let mut text = String::new();
+        let _ = writeln!(text, "KALA TIMESTAMP ATTESTATION (version {})", self.version);
+        let _ = writeln!(text);
+        match &self.transaction {
+            Some(transaction) => {
+                let _ = writeln!(
+                    text,
+                    "Transaction:      {}",
+                    CryptoUtils::hash_to_hex(&transaction.transaction_hash)
+                );
+                let _ = writeln!(text, "Position in tick: {}", transaction.index);
+            }
+            None => {
+                let _ = writeln!(text, "Subject:          tick {}", certificate.tick_number);
+            }
+        }
+        let _ = writeln!(text, "Tick:             {}", certificate.tick_number);
+        let tick_hash = CryptoUtils::hash_to_hex(&certificate.tick_hash);
+        let _ = writeln!(text, "Tick hash:        {}", tick_hash);
+        let _ = writeln!(text, "VDF iteration:    {}", self.time.iteration);
+        let _ = writeln!(text, "Time (estimated): {}", format_utc(self.time.time_ms));
+        if let Some(bounds) = &self.time.bounds {
+            if let Some(earliest) = bounds.earliest_ms() {
+                let _ = writeln!(text, "Not before:       {}", format_utc(earliest));
+            }
+            if let Some(latest) = bounds.latest_ms() {
+                let _ = writeln!(text, "Not after:        {}", format_utc(latest));
+            }
+        }
+        let _ = writeln!(
+            text,
+            "Epoch:            {} (ticks {}-{})",
+            summary.epoch_number, summary.start_tick, summary.end_tick
+        );
+        let _ = writeln!(text, "Accumulator root: {}", CryptoUtils::hash_to_hex(&summary.mmr_root));
+        let _ = writeln!(text, "Witness signatures: {}", self.epoch.signatures.len());
+        let _ = writeln!(text);
+        let _ = writeln!(text, "Issued:           {}", format_utc(self.issued_at_ms));
+        let _ = writeln!(text, "Issuer:           {}", CryptoUtils::hash_to_hex(&self.issuer));
+        let document_hash = CryptoUtils::hash_to_hex(&self.signing_hash());
+        let _ = writeln!(text, "Document hash:    {}", document_hash);
+        text
+    }
+}
+
+/// The version, the transaction if any, the tick certificate's hash and
+/// accumulator proof, the epoch summary's hash, the iteration, estimate,
+/// clock and anchors, the issue time and the issuer
+///
+/// The clock's rate is encoded by its IEEE 754 bits. Absent options are a
+/// zero byte, present ones a one byte followed by their fields.
+impl CanonicalEncode for Attestation {
+    const DOMAIN: &'static str = "kala-attestation-v1";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder.u32(self.version);
+        match &self.transaction {
+            Some(transaction) => {
+                encoder.u8(1).fixed(&transaction.transaction_hash).u64(transaction.index);
+                encode_hashes(encoder, &transaction.path);
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+
+        let proof = &self.tick.proof;
+        encoder
+            .fixed(&self.tick.certificate.tick_hash)
+            .u64(proof.leaf_index)
+            .u64(proof.leaf_count);
+        encode_hashes(encoder, &proof.siblings);
+        encode_hashes(encoder, &proof.peaks);
+        encoder.fixed(&self.epoch.summary.compute_hash());
+
+        let time = &self.time;
+        encoder
+            .u64(time.iteration)
+            .u64(time.time_ms)
+            .u64(time.clock.anchor_iteration)
+            .u64(time.clock.anchor_time_ms)
+            .u64(time.clock.iterations_per_second.to_bits());
+        match &time.bounds {
+            Some(bounds) => {
+                encoder.u8(1).u64(bounds.iteration);
+                for anchor in [&bounds.earliest, &bounds.latest] {
+                    match anchor {
+                        Some(anchor) => {
+                            encoder
+                                .u8(1)
+                                .u64(anchor.tick_number)
+                                .u64(anchor.iteration)
+                                .u64(anchor.time_ms)
+                                .u64(anchor.reported_weight)
+                                .u32(anchor.reports);
+                        }
+                        None => {
+                            encoder.u8(0);
+                        }
+                    }
+                }
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+
+        encoder.u64(self.issued_at_ms).fixed(&self.issuer);
+    }
+}
+
+impl KalaSerialize for Attestation {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json // Handed to end users and archived as documents
+    }
+}
+
+fn encode_hashes(encoder: &mut CanonicalEncoder, hashes: &[Hash]) {
+    encoder.u32(hashes.len() as u32);
+    for hash in hashes {
+        encoder.fixed(hash);
+    }
+}
+
+/// `time_ms` as `YYYY-MM-DD HH:MM:SS.mmm UTC`
+fn format_utc(time_ms: Timestamp) -> String {
+    // Days to a civil date, from Howard Hinnant's date algorithms
+    let unix_secs = time_ms / 1_000;
+    let days = unix_secs / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    let seconds = unix_secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03} UTC",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+        time_ms % 1_000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epoch::{EpochStats, EpochSummary};
+    use crate::tick::{TickCertificate, TickType};
+    use kala_common::crypto::signer::InMemorySigner;
+    use kala_common::mmr::MerkleMountainRange;
+
+    fn attestation() -> Attestation {
+        let transactions: Vec<Hash> = (0u8..5).map(|i| CryptoUtils::hash(&[i])).collect();
+        let tree = MerkleTree::new(transactions.clone());
+
+        let mut mmr = MerkleMountainRange::new();
+        let mut previous_tick_hash = [0u8; 32];
+        let mut certificates = Vec::new();
+        for tick_number in 0..4u64 {
+            let mut certificate = TickCertificate {
+                tick_number,
+                tick_type: TickType::Full,
+                vdf_iteration: (tick_number + 1) * 1000,
+                vdf_form: ("1".into(), "1".into(), "1".into()),
+                hash_chain_value: [0u8; 32],
+                tick_hash: [0u8; 32],
+                transaction_count: transactions.len() as u32,
+                transaction_merkle_root: tree.root(),
+                timestamp: 0,
+                previous_tick_hash,
+                usage: Default::default(),
+            };
+            certificate.tick_hash = certificate.compute_hash();
+            previous_tick_hash = certificate.tick_hash;
+            mmr.append(certificate.tick_hash).unwrap();
+            certificates.push(certificate);
+        }
+
+        let epoch = SignedEpochSummary::new(EpochSummary {
+            epoch_number: 0,
+            start_tick: 0,
+            end_tick: 3,
+            end_tick_hash: previous_tick_hash,
+            mmr_root: mmr.root().unwrap(),
+            state_root: [0u8; 32],
+            witnesses: Vec::new(),
+            stats: EpochStats::default(),
+            previous_epoch_hash: [0u8; 32],
+        });
+        let tick = ProvenTick {
+            certificate: certificates[2].clone(),
+            proof: mmr.proof(2).unwrap(),
+        };
+        let transaction = TransactionInclusion {
+            transaction_hash: transactions[4],
+            index: 4,
+            path: tree.proof(4).unwrap(),
+        };
+        let clock = TimelineClock::new(0, 1_700_000_000_000, 1000.0);
+        Attestation::new(tick, epoch, clock, None, Some(transaction), 1_700_000_100_000).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify() {
+        let mut attestation = attestation();
+        assert_eq!(attestation.time.time_ms, 1_700_000_003_000);
+        assert!(attestation.verify().is_err(), "unsigned");
+
+        let signer = InMemorySigner::from_seed(&[3u8; 32]).unwrap();
+        attestation.sign(&signer).await.unwrap();
+        attestation.verify().unwrap();
+
+        let decoded = Attestation::from_json(&attestation.to_json().unwrap()).unwrap();
+        decoded.verify().unwrap();
+        assert_eq!(decoded.signing_hash(), attestation.signing_hash());
+
+        let mut forged = decoded.clone();
+        forged.issued_at_ms += 1;
+        assert!(forged.verify().is_err());
+
+        let rendered = attestation.render();
+        assert!(rendered.contains("Time (estimated): 2023-11-14 22:13:23.000 UTC"));
+        assert!(rendered.contains(&CryptoUtils::hash_to_hex(&attestation.issuer)));
+    }
+
+    #[test]
+    fn test_rejects_mismatched_pieces() {
+        let attestation = attestation();
+        let rebuild = |attestation: Attestation| {
+            Attestation::new(
+                attestation.tick,
+                attestation.epoch,
+                attestation.time.clock,
+                attestation.time.bounds,
+                attestation.transaction,
+                attestation.issued_at_ms,
+            )
+        };
+
+        let mut wrong_transaction = attestation.clone();
+        wrong_transaction.transaction.as_mut().unwrap().index = 3;
+        assert!(rebuild(wrong_transaction).is_err());
+
+        let mut wrong_tick = attestation.clone();
+        wrong_tick.tick.certificate.transaction_count += 1;
+        assert!(rebuild(wrong_tick).is_err());
+
+        let mut wrong_epoch = attestation.clone();
+        wrong_epoch.epoch.summary.mmr_root = [9u8; 32];
+        assert!(rebuild(wrong_epoch).is_err());
+
+        let mut wrong_time = attestation;
+        wrong_time.time.time_ms += 1;
+        assert!(wrong_time.verify_contents().is_err());
+    }
+}
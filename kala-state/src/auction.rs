@@ -0,0 +1,263 @@
+//! Sealed-bid auctions
+//!
+//! A seller opens an auction with a reserve price and a closing tick. Bids
+//! only apply in the closing tick, so every bid has to be sealed in an
+//! envelope for that tick and none is readable, by witnesses or other
+//! bidders, until all of them are decrypted together. Each bid's amount is
+//! escrowed when it applies.
+//!
+//! When the closing tick finalizes, the highest bid at or above the
+//! reserve wins and pays its bid to the seller. A tie goes to the bid
+//! applied first in the tick's canonical order. Every other bid is
+//! refunded, all of them if no bid meets the reserve.
+
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::types::{BlockHeight, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A bid escrowed on an auction
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionBid {
+    pub bidder: Hash,
+    pub amount: u64,
+}
+
+/// Outcome of an auction
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionStatus {
+    /// Waiting for its closing tick
+    Open,
+    /// Won by `winner`, who paid `price`
+    Sold { winner: Hash, price: u64 },
+    /// No bid met the reserve
+    Unsold,
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct Auction {
+    pub id: u64,
+    pub seller: Hash,
+    /// Description of what is sold, opaque to the chain
+    pub item: Vec<u8>,
+    pub reserve_price: u64,
+    /// The only tick bids apply in
+    pub close_tick: BlockHeight,
+    pub status: AuctionStatus,
+    /// Bids in the order they applied; once sold, only the winning one
+    pub bids: Vec<AuctionBid>,
+}
+
+/// Transfers settling a closed auction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuctionSettlement {
+    pub auction_id: u64,
+    pub seller: Hash,
+    pub status: AuctionStatus,
+    /// Escrowed bids returned to their bidders
+    pub refunds: Vec<AuctionBid>,
+}
+
+/// Auctions by ID
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Auctions {
+    auctions: BTreeMap<u64, Auction>,
+    next_id: u64,
+}
+
+impl Auctions {
+    /// Open an auction at `current_tick`, closing at the later `close_tick`
+    ///
+    /// Returns the auction's ID.
+    pub fn create(
+        &mut self,
+        seller: &Hash,
+        item: Vec<u8>,
+        reserve_price: u64,
+        close_tick: BlockHeight,
+        current_tick: BlockHeight,
+    ) -> KalaResult<u64> {
+        if close_tick <= current_tick {
+            return Err(KalaError::validation(format!(
+                "Closing tick {} has already passed",
+                close_tick
+            )));
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.auctions.insert(
+            id,
+            Auction {
+                id,
+                seller: *seller,
+                item,
+                reserve_price,
+                close_tick,
+                status: AuctionStatus::Open,
+                bids: Vec::new(),
+            },
+        );
+        Ok(id)
+    }
+
+    /// Check `bidder` may bid `amount` on auction `auction_id` at `current_tick`
+    pub fn check_bid(
+        &self,
+        bidder: &Hash,
+        auction_id: u64,
+        amount: u64,
+        current_tick: BlockHeight,
+    ) -> KalaResult<()> {
+        let auction = self
+            .auctions
+            .get(&auction_id)
+            .ok_or_else(|| KalaError::state(format!("Unknown auction {}", auction_id)))?;
+        if auction.status != AuctionStatus::Open || current_tick != auction.close_tick {
+            return Err(KalaError::validation(format!(
+                "Auction {} only takes bids in tick {}",
+                auction_id, auction.close_tick
+            )));
+        }
+        if auction.seller == *bidder {
+            return Err(KalaError::validation("Sellers can't bid on their own auction"));
+        }
+        if amount < auction.reserve_price {
+            return Err(KalaError::validation(format!(
+                "Bid {} is below the reserve price {}",
+                amount, auction.reserve_price
+            )));
+        }
+        if auction.bids.iter().any(|bid| bid.bidder == *bidder) {
+            return Err(KalaError::validation(format!(
+                "Bidder already bid on auction {}",
+                auction_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Record a bid whose amount the caller has escrowed
+    pub fn bid(
+        &mut self,
+        bidder: &Hash,
+        auction_id: u64,
+        amount: u64,
+        current_tick: BlockHeight,
+    ) -> KalaResult<()> {
+        self.check_bid(bidder, auction_id, amount, current_tick)?;
+        if let Some(auction) = self.auctions.get_mut(&auction_id) {
+            auction.bids.push(AuctionBid {
+                bidder: *bidder,
+                amount,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn auction(&self, id: u64) -> Option<&Auction> {
+        self.auctions.get(&id)
+    }
+
+    pub fn auctions(&self) -> impl Iterator<Item = &Auction> {
+        self.auctions.values()
+    }
+
+    /// Base units escrowed by the bids of open auctions
+    pub fn escrowed(&self) -> u64 {
+        self.auctions
+            .values()
+            .filter(|auction| auction.status == AuctionStatus::Open)
+            .flat_map(|auction| &auction.bids)
+            .fold(0u64, |total, bid| total.saturating_add(bid.amount))
+    }
+
+    /// Close the auctions whose closing tick is at or before `tick`
+    ///
+    /// Returns the transfers settling each, for the caller to apply.
+    pub fn settle(&mut self, tick: BlockHeight) -> Vec<AuctionSettlement> {
+        let mut settled = Vec::new();
+        for auction in self.auctions.values_mut() {
+            if auction.status != AuctionStatus::Open || auction.close_tick > tick {
+                continue;
+            }
+
+            let mut refunds = std::mem::take(&mut auction.bids);
+            // The first of the highest bids, so ties go to the earlier one
+            let winner = refunds
+                .iter()
+                .enumerate()
+                .filter(|(_, bid)| bid.amount >= auction.reserve_price)
+                .max_by(|(a_index, a), (b_index, b)| {
+                    a.amount.cmp(&b.amount).then(b_index.cmp(a_index))
+                })
+                .map(|(index, _)| index);
+            auction.status = match winner {
+                Some(index) => {
+                    let bid = refunds.remove(index);
+                    auction.bids.push(bid.clone());
+                    AuctionStatus::Sold {
+                        winner: bid.bidder,
+                        price: bid.amount,
+                    }
+                }
+                None => AuctionStatus::Unsold,
+            };
+
+            settled.push(AuctionSettlement {
+                auction_id: auction.id,
+                seller: auction.seller,
+                status: auction.status,
+                refunds,
+            });
+        }
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest_sealed_bid_wins() {
+        let mut auctions = Auctions::default();
+        let (seller, alice, bob, carol) = ([1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]);
+
+        assert!(auctions.create(&seller, b"lot".to_vec(), 100, 10, 10).is_err());
+        let id = auctions.create(&seller, b"lot".to_vec(), 100, 20, 10).unwrap();
+
+        // Bids only apply in the closing tick
+        assert!(auctions.bid(&alice, id, 150, 19).is_err());
+        assert!(auctions.bid(&alice, id, 99, 20).is_err());
+        assert!(auctions.bid(&seller, id, 500, 20).is_err());
+        assert!(auctions.bid(&alice, 7, 150, 20).is_err());
+        auctions.bid(&alice, id, 150, 20).unwrap();
+        auctions.bid(&bob, id, 300, 20).unwrap();
+        auctions.bid(&carol, id, 300, 20).unwrap();
+        assert!(auctions.bid(&alice, id, 400, 20).is_err());
+        assert_eq!(auctions.escrowed(), 750);
+
+        assert!(auctions.settle(19).is_empty());
+        let settled = auctions.settle(20);
+        assert_eq!(settled.len(), 1);
+        assert_eq!(
+            settled[0].status,
+            AuctionStatus::Sold {
+                winner: bob,
+                price: 300
+            }
+        );
+        let refunded: Vec<_> = settled[0].refunds.iter().map(|bid| bid.bidder).collect();
+        assert_eq!(refunded, vec![alice, carol]);
+        assert_eq!(auctions.escrowed(), 0);
+        assert!(auctions.settle(21).is_empty());
+
+        // Without bids an auction closes unsold
+        let empty = auctions.create(&seller, Vec::new(), 0, 30, 21).unwrap();
+        let settled = auctions.settle(30);
+        assert_eq!(settled[0].auction_id, empty);
+        assert_eq!(settled[0].status, AuctionStatus::Unsold);
+    }
+}
@@ -0,0 +1,456 @@
+//! Epoch summaries (super-ticks)
+//!
+//! Every `epoch_length` ticks the node seals an [`EpochSummary`] that
+//! commits to the tick accumulator, the account state, the witness set,
+//! and aggregate statistics over the epoch's ticks. Witnesses sign the
+//! summary hash with their Ed25519 keys.
+//!
+//! A light client that trusts one summary can follow the timeline
+//! epoch-to-epoch with [`verify_epoch_chain`]: each successor must link to
+//! its predecessor and carry signatures from a quorum of the predecessor's
+//! witnesses. Individual ticks are then checked with an MMR inclusion proof
+//! against the epoch's `mmr_root`.
+//!
+//! When an epoch is sealed the witness set for the next epoch is
+//! recomputed from stake, and the handover is recorded in an
+//! [`EpochCertificate`].
+
+use crate::tick::{TickCertificate, TickType};
+use crate::usage::ModuleUsage;
+use crate::witness::WitnessSet;
+use bincode::{Decode, Encode};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use kala_common::crypto::signer::{SignatureScheme, Signer as WitnessSigner};
+use kala_common::crypto::CryptoUtils;
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::mmr::MmrProof;
+use kala_common::quorum::QuorumCalculator;
+use kala_common::serialization::{CanonicalEncode, EncodingType, KalaSerialize};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// Aggregate statistics over the ticks of an epoch
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EpochStats {
+    pub tick_count: u64,
+    pub full_ticks: u64,
+    pub empty_ticks: u64,
+    pub checkpoint_ticks: u64,
+    pub transaction_count: u64,
+    pub start_iteration: u64,
+    pub end_iteration: u64,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    /// Usage of the epoch's ticks, added up
+    #[serde(default)]
+    pub usage: ModuleUsage,
+    /// Oracle feed values finalized over the epoch
+    ///
+    /// Not covered by the summary hash: each node medianizes its own
+    /// finality records, which may hold different votes, so nodes can
+    /// count differently.
+    #[serde(default)]
+    pub oracle_updates: u64,
+}
+
+impl EpochStats {
+    /// Fold a tick into the statistics; ticks must be recorded in order
+    pub fn record(&mut self, tick: &TickCertificate) {
+        if self.tick_count == 0 {
+            self.start_iteration = tick.vdf_iteration;
+            self.start_timestamp = tick.timestamp;
+        }
+        self.tick_count += 1;
+        match tick.tick_type {
+            TickType::Full => self.full_ticks += 1,
+            TickType::Empty => self.empty_ticks += 1,
+            TickType::Checkpoint => self.checkpoint_ticks += 1,
+        }
+        self.transaction_count += tick.transaction_count as u64;
+        self.usage.add(&tick.usage);
+        self.end_iteration = tick.vdf_iteration;
+        self.end_timestamp = tick.timestamp;
+    }
+}
+
+/// Commitment to a run of `epoch_length` ticks
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EpochSummary {
+    pub epoch_number: u64,
+    pub start_tick: u64,
+    /// Last tick of the epoch, inclusive
+    pub end_tick: u64,
+    pub end_tick_hash: [u8; 32],
+    /// Root of the tick MMR after `end_tick` was appended
+    pub mmr_root: [u8; 32],
+    pub state_root: [u8; 32],
+    /// Ed25519 public keys of the witnesses, sorted
+    pub witnesses: Vec<[u8; 32]>,
+    pub stats: EpochStats,
+    /// Hash of the previous epoch's summary, zero for epoch 0
+    pub previous_epoch_hash: [u8; 32],
+}
+
+impl EpochSummary {
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-epoch");
+        hasher.update(self.epoch_number.to_le_bytes());
+        hasher.update(self.start_tick.to_le_bytes());
+        hasher.update(self.end_tick.to_le_bytes());
+        hasher.update(self.end_tick_hash);
+        hasher.update(self.mmr_root);
+        hasher.update(self.state_root);
+        hasher.update((self.witnesses.len() as u64).to_le_bytes());
+        for witness in &self.witnesses {
+            hasher.update(witness);
+        }
+        hasher.update(self.stats.tick_count.to_le_bytes());
+        hasher.update(self.stats.full_ticks.to_le_bytes());
+        hasher.update(self.stats.empty_ticks.to_le_bytes());
+        hasher.update(self.stats.checkpoint_ticks.to_le_bytes());
+        hasher.update(self.stats.transaction_count.to_le_bytes());
+        hasher.update(self.stats.start_iteration.to_le_bytes());
+        hasher.update(self.stats.end_iteration.to_le_bytes());
+        hasher.update(self.stats.start_timestamp.to_le_bytes());
+        hasher.update(self.stats.end_timestamp.to_le_bytes());
+        // Left out when nothing was used, so older summaries hash as before
+        if !self.stats.usage.is_empty() {
+            hasher.update(self.stats.usage.canonical_hash());
+        }
+        hasher.update(self.previous_epoch_hash);
+        hasher.finalize().into()
+    }
+
+    /// Number of witness signatures needed to accept the next epoch (> 2/3)
+    pub fn quorum(&self) -> usize {
+        QuorumCalculator::new(self.witnesses.len() as u64).quorum() as usize
+    }
+
+    /// Whether `tick_hash` is committed at `proof.leaf_index` in this epoch's timeline
+    pub fn verify_tick(&self, tick_hash: &[u8; 32], proof: &MmrProof) -> bool {
+        proof.leaf_count == self.end_tick + 1 && proof.verify(tick_hash, &self.mmr_root)
+    }
+
+    /// Check that `next` directly follows this epoch
+    pub fn check_successor(&self, next: &EpochSummary) -> KalaResult<()> {
+        if next.epoch_number != self.epoch_number + 1 {
+            return Err(KalaError::validation(format!(
+                "Expected epoch {}, got {}",
+                self.epoch_number + 1,
+                next.epoch_number
+            )));
+        }
+        if next.previous_epoch_hash != self.compute_hash() {
+            return Err(KalaError::validation(format!(
+                "Epoch {} does not link to epoch {}",
+                next.epoch_number, self.epoch_number
+            )));
+        }
+        if next.start_tick != self.end_tick + 1 || next.end_tick < next.start_tick {
+            return Err(KalaError::validation(format!(
+                "Epoch {} covers ticks {}..={} after epoch ending at tick {}",
+                next.epoch_number, next.start_tick, next.end_tick, self.end_tick
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A witness's Ed25519 signature over an epoch summary hash
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WitnessSignature {
+    pub witness: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl WitnessSignature {
+    pub fn sign(key: &SigningKey, message: &[u8; 32]) -> Self {
+        Self {
+            witness: key.verifying_key().to_bytes(),
+            signature: key.sign(message).to_bytes().to_vec(),
+        }
+    }
+
+    pub fn verify(&self, message: &[u8; 32]) -> bool {
+        let Ok(key) = VerifyingKey::from_bytes(&self.witness) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&self.signature) else {
+            return false;
+        };
+        key.verify(message, &signature).is_ok()
+    }
+}
+
+/// A witness signature on an epoch, as gossiped between nodes
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct EpochSignature {
+    pub epoch_number: u64,
+    pub summary_hash: [u8; 32],
+    pub signature: WitnessSignature,
+}
+
+/// An epoch summary together with the witness signatures collected for it
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SignedEpochSummary {
+    pub summary: EpochSummary,
+    pub signatures: Vec<WitnessSignature>,
+}
+
+impl SignedEpochSummary {
+    pub fn new(summary: EpochSummary) -> Self {
+        Self {
+            summary,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Sign the summary with a witness key from its own witness set
+    pub fn sign(&mut self, key: &SigningKey) -> KalaResult<EpochSignature> {
+        let hash = self.summary.compute_hash();
+        let signature = WitnessSignature::sign(key, &hash);
+        self.add_signature(signature.clone())?;
+        Ok(EpochSignature {
+            epoch_number: self.summary.epoch_number,
+            summary_hash: hash,
+            signature,
+        })
+    }
+
+    /// Sign the summary with the witness keys behind `signer`
+    pub async fn sign_with(&mut self, signer: &dyn WitnessSigner) -> KalaResult<EpochSignature> {
+        let hash = self.summary.compute_hash();
+        let signature = WitnessSignature {
+            witness: signer
+                .public_key(SignatureScheme::Ed25519)
+                .try_into()
+                .map_err(|_| KalaError::crypto("Witness key must be 32 bytes"))?,
+            signature: signer.sign(SignatureScheme::Ed25519, &hash).await?,
+        };
+        self.add_signature(signature.clone())?;
+        Ok(EpochSignature {
+            epoch_number: self.summary.epoch_number,
+            summary_hash: hash,
+            signature,
+        })
+    }
+
+    /// Add a signature from a member of the summary's witness set
+    ///
+    /// Returns `false` if the witness had already signed.
+    pub fn add_signature(&mut self, signature: WitnessSignature) -> KalaResult<bool> {
+        if !self.summary.witnesses.contains(&signature.witness) {
+            return Err(KalaError::validation(format!(
+                "{} is not a witness for epoch {}",
+                CryptoUtils::hash_to_hex(&signature.witness),
+                self.summary.epoch_number
+            )));
+        }
+        if !signature.verify(&self.summary.compute_hash()) {
+            return Err(KalaError::crypto(format!(
+                "Invalid signature from witness {} on epoch {}",
+                CryptoUtils::hash_to_hex(&signature.witness),
+                self.summary.epoch_number
+            )));
+        }
+        if self.signatures.iter().any(|s| s.witness == signature.witness) {
+            return Ok(false);
+        }
+        self.signatures.push(signature);
+        Ok(true)
+    }
+
+    /// Count distinct members of `witnesses` with a valid signature
+    pub fn valid_signatures(&self, witnesses: &[[u8; 32]]) -> usize {
+        let hash = self.summary.compute_hash();
+        self.signatures
+            .iter()
+            .filter(|s| witnesses.contains(&s.witness) && s.verify(&hash))
+            .map(|s| s.witness)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Verify this summary follows a trusted one and is signed by its witness quorum
+    pub fn verify_successor(&self, trusted: &EpochSummary) -> KalaResult<()> {
+        trusted.check_successor(&self.summary)?;
+
+        let signed = self.valid_signatures(&trusted.witnesses);
+        if signed < trusted.quorum() {
+            return Err(KalaError::validation(format!(
+                "Epoch {} has {} of {} required witness signatures",
+                self.summary.epoch_number,
+                signed,
+                trusted.quorum()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl KalaSerialize for SignedEpochSummary {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json // Served to light clients over RPC
+    }
+}
+
+/// Witness set handover at the end of an epoch
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct EpochCertificate {
+    /// Epoch whose end triggered the handover
+    pub epoch_number: u64,
+    /// Hash of that epoch's summary
+    pub summary_hash: [u8; 32],
+    /// First tick governed by `witnesses`
+    pub effective_tick: u64,
+    /// Witnesses that governed the epoch
+    pub previous_witnesses: WitnessSet,
+    /// Witnesses for the following epoch, weighted by stake
+    pub witnesses: WitnessSet,
+    /// Largest faulty weight `witnesses` tolerates
+    pub byzantine_threshold: u64,
+}
+
+impl EpochCertificate {
+    pub fn new(summary: &EpochSummary, previous_witnesses: WitnessSet, witnesses: WitnessSet) -> Self {
+        Self {
+            epoch_number: summary.epoch_number,
+            summary_hash: summary.compute_hash(),
+            effective_tick: summary.end_tick + 1,
+            previous_witnesses,
+            byzantine_threshold: witnesses.byzantine_threshold(),
+            witnesses,
+        }
+    }
+
+    /// Whether membership or weights changed at this handover
+    pub fn is_rotation(&self) -> bool {
+        self.previous_witnesses != self.witnesses
+    }
+}
+
+impl KalaSerialize for EpochCertificate {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json // Served to light clients over RPC
+    }
+}
+
+/// Follow a chain of epochs from a trusted summary, returning the newest verified one
+pub fn verify_epoch_chain(
+    trusted: &EpochSummary,
+    epochs: &[SignedEpochSummary],
+) -> KalaResult<EpochSummary> {
+    let mut current = trusted.clone();
+    for epoch in epochs {
+        epoch.verify_successor(&current)?;
+        current = epoch.summary.clone();
+    }
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn summary(epoch_number: u64, previous: Option<&EpochSummary>, keys: &[SigningKey]) -> EpochSummary {
+        let mut witnesses: Vec<_> = keys.iter().map(|k| k.verifying_key().to_bytes()).collect();
+        witnesses.sort();
+        EpochSummary {
+            epoch_number,
+            start_tick: epoch_number * 10,
+            end_tick: epoch_number * 10 + 9,
+            end_tick_hash: [epoch_number as u8; 32],
+            mmr_root: [1; 32],
+            state_root: [2; 32],
+            witnesses,
+            stats: EpochStats::default(),
+            previous_epoch_hash: previous.map(EpochSummary::compute_hash).unwrap_or([0; 32]),
+        }
+    }
+
+    fn signed(summary: EpochSummary, keys: &[SigningKey]) -> SignedEpochSummary {
+        let mut signed = SignedEpochSummary::new(summary);
+        for key in keys {
+            signed.sign(key).unwrap();
+        }
+        signed
+    }
+
+    #[test]
+    fn test_signatures_require_witness_membership() {
+        let keys = [key(1), key(2)];
+        let mut epoch = SignedEpochSummary::new(summary(0, None, &keys));
+
+        let signature = epoch.sign(&keys[0]).unwrap();
+        assert_eq!(signature.summary_hash, epoch.summary.compute_hash());
+        assert!(!epoch.add_signature(signature.signature).unwrap());
+        assert!(epoch.sign(&key(3)).is_err());
+
+        // A signature over a different summary is rejected
+        let other = signed(summary(1, None, &keys), &keys[1..]);
+        assert!(epoch.add_signature(other.signatures[0].clone()).is_err());
+        assert_eq!(epoch.valid_signatures(&epoch.summary.witnesses), 1);
+    }
+
+    #[test]
+    fn test_epoch_chain_needs_quorum_of_previous_witnesses() {
+        let keys = [key(1), key(2), key(3), key(4)];
+        let genesis = summary(0, None, &keys);
+        assert_eq!(genesis.quorum(), 3);
+
+        let first = signed(summary(1, Some(&genesis), &keys), &keys[..3]);
+        let second = signed(summary(2, Some(&first.summary), &keys), &keys[1..]);
+        let head = verify_epoch_chain(&genesis, &[first.clone(), second.clone()]).unwrap();
+        assert_eq!(head, second.summary);
+
+        // Two of four witnesses is not a quorum
+        let weak = signed(summary(1, Some(&genesis), &keys), &keys[..2]);
+        assert!(verify_epoch_chain(&genesis, &[weak]).is_err());
+
+        // Epochs can't be skipped or reordered
+        assert!(verify_epoch_chain(&genesis, std::slice::from_ref(&second)).is_err());
+        assert!(verify_epoch_chain(&genesis, &[second, first]).is_err());
+    }
+
+    #[test]
+    fn test_stats_add_up_usage() {
+        let mut tick = crate::tick::TickCertificate {
+            tick_number: 0,
+            tick_type: TickType::Full,
+            vdf_iteration: 10,
+            vdf_form: Default::default(),
+            hash_chain_value: [0; 32],
+            tick_hash: [0; 32],
+            transaction_count: 2,
+            transaction_merkle_root: [0; 32],
+            timestamp: 0,
+            previous_tick_hash: [0; 32],
+            usage: ModuleUsage::default(),
+        };
+        tick.usage.record_native("bank");
+        tick.usage.record_native("staking");
+
+        let mut epoch = summary(0, None, &[key(1)]);
+        let unused = epoch.compute_hash();
+        epoch.stats.record(&tick);
+        epoch.stats.record(&tick);
+        assert_eq!(epoch.stats.usage.native_transactions["bank"], 2);
+        assert_eq!(epoch.stats.usage.native_total(), epoch.stats.transaction_count);
+        let used = epoch.compute_hash();
+        assert_ne!(used, unused);
+
+        // Oracle updates are this node's own count, left out of the hash
+        epoch.stats.oracle_updates = 5;
+        assert_eq!(epoch.compute_hash(), used);
+    }
+}
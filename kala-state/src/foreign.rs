@@ -0,0 +1,186 @@
+//! Light clients of other Kala timelines
+//!
+//! A [`ForeignClient`] follows another Kala chain epoch-to-epoch, the way
+//! an external light client follows this one: it starts from an anchor
+//! summary and accepts each successor signed by a quorum of the previous
+//! epoch's witnesses (see [`SignedEpochSummary::verify_successor`]).
+//!
+//! Anyone may anchor a client. The anchor isn't checked, so a client is
+//! identified by the anchor's hash, and whoever relies on it decides
+//! whether that anchor really starts the timeline they mean. Once anchored,
+//! only properly signed successors move a client forward.
+//!
+//! Each verified epoch's tick accumulator root is kept, so a tick of the
+//! other timeline can be referenced here with an MMR inclusion proof.
+
+use crate::epoch::{EpochSummary, SignedEpochSummary};
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::mmr::MmrProof;
+use kala_common::serialization::KalaSerialize;
+use kala_common::types::Hash;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Parse the signed summary carried by a foreign epoch submission
+pub fn decode_foreign_epoch(data: &[u8]) -> KalaResult<SignedEpochSummary> {
+    <SignedEpochSummary as KalaSerialize>::decode(data)
+        .map_err(|e| KalaError::serialization(format!("Invalid foreign epoch: {}", e)))
+}
+
+/// Commitments of a verified foreign epoch
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct ForeignEpoch {
+    pub end_tick: u64,
+    pub end_tick_hash: Hash,
+    pub mmr_root: Hash,
+    pub state_root: Hash,
+    /// Unix time of the epoch's last tick, as reported by the foreign chain
+    pub end_timestamp: u64,
+}
+
+impl ForeignEpoch {
+    fn from_summary(summary: &EpochSummary) -> Self {
+        Self {
+            end_tick: summary.end_tick,
+            end_tick_hash: summary.end_tick_hash,
+            mmr_root: summary.mmr_root,
+            state_root: summary.state_root,
+            end_timestamp: summary.stats.end_timestamp,
+        }
+    }
+}
+
+/// Verified epochs of one foreign timeline
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct ForeignClient {
+    /// Latest verified summary, whose witnesses sign the next one
+    pub latest: EpochSummary,
+    /// Commitments by epoch number, the anchor's included
+    pub epochs: BTreeMap<u64, ForeignEpoch>,
+}
+
+impl ForeignClient {
+    /// Whether `tick_hash` is a tick of the foreign timeline, committed in
+    /// epoch `epoch_number` at `proof.leaf_index`
+    pub fn verify_tick(&self, epoch_number: u64, tick_hash: &Hash, proof: &MmrProof) -> bool {
+        self.epochs.get(&epoch_number).is_some_and(|epoch| {
+            proof.leaf_count == epoch.end_tick + 1 && proof.verify(tick_hash, &epoch.mmr_root)
+        })
+    }
+}
+
+/// Light clients by ID
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ForeignClients {
+    clients: BTreeMap<Hash, ForeignClient>,
+}
+
+impl ForeignClients {
+    pub fn client(&self, id: &Hash) -> Option<&ForeignClient> {
+        self.clients.get(id)
+    }
+
+    /// Check `epoch` could be submitted to client `id`; a zero ID anchors a new one
+    ///
+    /// Returns the ID of the client the epoch would be recorded in.
+    pub fn check(&self, id: &Hash, epoch: &SignedEpochSummary) -> KalaResult<Hash> {
+        if *id == [0u8; 32] {
+            let anchor = epoch.summary.compute_hash();
+            if self.clients.contains_key(&anchor) {
+                return Err(KalaError::validation("Foreign client already anchored"));
+            }
+            return Ok(anchor);
+        }
+
+        let client = self
+            .clients
+            .get(id)
+            .ok_or_else(|| KalaError::state("Unknown foreign client"))?;
+        epoch.verify_successor(&client.latest)?;
+        Ok(*id)
+    }
+
+    /// Anchor a client at `epoch`, or advance client `id` to it
+    ///
+    /// Returns the client's ID.
+    pub fn submit(&mut self, id: &Hash, epoch: &SignedEpochSummary) -> KalaResult<Hash> {
+        let id = self.check(id, epoch)?;
+        let summary = &epoch.summary;
+        let client = self.clients.entry(id).or_insert_with(|| ForeignClient {
+            latest: summary.clone(),
+            epochs: BTreeMap::new(),
+        });
+        client.latest = summary.clone();
+        client
+            .epochs
+            .insert(summary.epoch_number, ForeignEpoch::from_summary(summary));
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epoch::EpochStats;
+    use ed25519_dalek::SigningKey;
+    use kala_common::mmr::MerkleMountainRange;
+
+    fn summary(
+        epoch_number: u64,
+        previous: Option<&EpochSummary>,
+        keys: &[SigningKey],
+    ) -> EpochSummary {
+        let mut witnesses: Vec<_> = keys.iter().map(|k| k.verifying_key().to_bytes()).collect();
+        witnesses.sort();
+        EpochSummary {
+            epoch_number,
+            start_tick: epoch_number * 4,
+            end_tick: epoch_number * 4 + 3,
+            end_tick_hash: [epoch_number as u8; 32],
+            mmr_root: [1; 32],
+            state_root: [2; 32],
+            witnesses,
+            stats: EpochStats::default(),
+            previous_epoch_hash: previous.map(EpochSummary::compute_hash).unwrap_or([0; 32]),
+        }
+    }
+
+    #[test]
+    fn test_foreign_client_follows_signed_epochs() {
+        let keys: Vec<_> = (1..=4u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let mut clients = ForeignClients::default();
+
+        let anchor = SignedEpochSummary::new(summary(0, None, &keys));
+        let id = clients.submit(&[0u8; 32], &anchor).unwrap();
+        assert_eq!(id, anchor.summary.compute_hash());
+        assert!(clients.submit(&[0u8; 32], &anchor).is_err());
+
+        // Ticks of epoch 1, committed to its accumulator
+        let mut mmr = MerkleMountainRange::new();
+        let ticks: Vec<Hash> = (0..8u8).map(|tick| [tick + 10; 32]).collect();
+        for tick in &ticks {
+            mmr.append(*tick).unwrap();
+        }
+        let mut next = summary(1, Some(&anchor.summary), &keys);
+        next.mmr_root = mmr.root().unwrap();
+        let mut signed = SignedEpochSummary::new(next);
+
+        // A quorum of the anchor's witnesses must sign
+        for key in &keys[..2] {
+            signed.sign(key).unwrap();
+        }
+        assert!(clients.submit(&id, &signed).is_err());
+        signed.sign(&keys[2]).unwrap();
+        assert!(clients.submit(&[9u8; 32], &signed).is_err());
+        assert_eq!(clients.submit(&id, &signed).unwrap(), id);
+        assert!(clients.check(&id, &signed).is_err());
+
+        let client = clients.client(&id).unwrap();
+        assert_eq!(client.latest.epoch_number, 1);
+        let proof = mmr.proof(5).unwrap();
+        assert!(client.verify_tick(1, &ticks[5], &proof));
+        assert!(!client.verify_tick(1, &ticks[4], &proof));
+        assert!(!client.verify_tick(0, &ticks[5], &proof));
+    }
+}
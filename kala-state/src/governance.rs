@@ -0,0 +1,340 @@
+//! On-chain parameter governance
+//!
+//! Any account with stake may propose a new value for a
+//! [`GovernanceParameter`]. Stakers vote on it for [`VOTING_PERIOD_TICKS`]
+//! ticks. When the window closes, each vote is weighted by the voter's
+//! stake at that tick, so stake moved to another account after voting
+//! can't be counted twice. A proposal passes if the approving stake is a
+//! quorum of the stake that voted and more than a third of all stake took
+//! part. A passed value takes effect when its activation tick is finalized.
+//!
+//! A [`GovernanceParameter::MinterApproval`] proposal instead names a
+//! denomination and an account, which its value allows to mint the
+//! denomination with 1 or stops from minting it with 0; see
+//! [`crate::mint`].
+
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::quorum::QuorumCalculator;
+use kala_common::types::{BlockHeight, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Ticks a proposal stays open for votes, starting with the tick it's applied in
+pub const VOTING_PERIOD_TICKS: u64 = 1_000;
+
+/// Chain parameters governance can change
+#[derive(
+    Serialize, Deserialize, Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum GovernanceParameter {
+    /// Most iterations a timelock puzzle may take, in basis points of a tick
+    TimelockHardness,
+    /// Base units charged to the fee payer of each transaction
+    TransactionFee,
+    /// Whether an account may mint a denomination, 1 to approve or 0 to revoke
+    MinterApproval,
+}
+
+impl GovernanceParameter {
+    /// Identifier used in proposal transactions
+    pub fn id(self) -> u32 {
+        match self {
+            GovernanceParameter::TimelockHardness => 0,
+            GovernanceParameter::TransactionFee => 1,
+            GovernanceParameter::MinterApproval => 2,
+        }
+    }
+
+    pub fn from_id(id: u32) -> KalaResult<Self> {
+        match id {
+            0 => Ok(GovernanceParameter::TimelockHardness),
+            1 => Ok(GovernanceParameter::TransactionFee),
+            2 => Ok(GovernanceParameter::MinterApproval),
+            _ => Err(KalaError::validation(format!("Unknown governance parameter {}", id))),
+        }
+    }
+
+    /// Check `value` is in range for the parameter
+    pub fn validate(self, value: u64) -> KalaResult<()> {
+        match self {
+            GovernanceParameter::TimelockHardness if !(1..=10_000).contains(&value) => {
+                Err(KalaError::validation(format!(
+                    "Timelock hardness must be 1 to 10000 basis points, got {}",
+                    value
+                )))
+            }
+            GovernanceParameter::MinterApproval if value > 1 => Err(KalaError::validation(format!(
+                "Minter approval must be 0 or 1, got {}",
+                value
+            ))),
+            GovernanceParameter::TimelockHardness
+            | GovernanceParameter::TransactionFee
+            | GovernanceParameter::MinterApproval => Ok(()),
+        }
+    }
+}
+
+/// Stage of a proposal
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Voting,
+    /// Waiting for its activation tick
+    Passed,
+    Rejected,
+    /// The new value is in effect
+    Executed,
+}
+
+/// A proposed parameter change and the votes cast on it
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Hash,
+    pub parameter: GovernanceParameter,
+    pub value: u64,
+    /// Last tick votes are accepted in
+    pub voting_end: BlockHeight,
+    pub activation_tick: BlockHeight,
+    pub status: ProposalStatus,
+    /// Whether each voter approves
+    pub votes: BTreeMap<Hash, bool>,
+    /// Approving and rejecting stake, once the votes are tallied
+    pub tally: Option<(u64, u64)>,
+    /// Denomination and account of a minter approval
+    #[serde(default)]
+    pub minter: Option<(Hash, Hash)>,
+}
+
+/// Proposals and the parameter values they set
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Governance {
+    proposals: BTreeMap<u64, Proposal>,
+    next_id: u64,
+    /// Values set by executed proposals
+    parameters: BTreeMap<GovernanceParameter, u64>,
+}
+
+impl Governance {
+    /// Open a proposal at `current_tick`
+    ///
+    /// Returns the proposal's ID. The activation tick must come after the
+    /// voting window.
+    pub fn propose(
+        &mut self,
+        proposer: &Hash,
+        parameter: GovernanceParameter,
+        value: u64,
+        activation_tick: BlockHeight,
+        current_tick: BlockHeight,
+    ) -> KalaResult<u64> {
+        if parameter == GovernanceParameter::MinterApproval {
+            return Err(KalaError::validation("Minter approvals must name a denom and minter"));
+        }
+        self.open(proposer, parameter, value, activation_tick, current_tick, None)
+    }
+
+    /// Open a proposal at `current_tick` to approve or revoke `minter` of
+    /// `denom`
+    ///
+    /// Returns the proposal's ID, like [`Self::propose`].
+    pub fn propose_minter(
+        &mut self,
+        proposer: &Hash,
+        denom: &Hash,
+        minter: &Hash,
+        approve: bool,
+        activation_tick: BlockHeight,
+        current_tick: BlockHeight,
+    ) -> KalaResult<u64> {
+        self.open(
+            proposer,
+            GovernanceParameter::MinterApproval,
+            approve as u64,
+            activation_tick,
+            current_tick,
+            Some((*denom, *minter)),
+        )
+    }
+
+    fn open(
+        &mut self,
+        proposer: &Hash,
+        parameter: GovernanceParameter,
+        value: u64,
+        activation_tick: BlockHeight,
+        current_tick: BlockHeight,
+        minter: Option<(Hash, Hash)>,
+    ) -> KalaResult<u64> {
+        parameter.validate(value)?;
+        let voting_end = current_tick.saturating_add(VOTING_PERIOD_TICKS - 1);
+        if activation_tick <= voting_end {
+            return Err(KalaError::validation(format!(
+                "Activation tick {} is within the voting window ending at {}",
+                activation_tick, voting_end
+            )));
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.proposals.insert(
+            id,
+            Proposal {
+                id,
+                proposer: *proposer,
+                parameter,
+                value,
+                voting_end,
+                activation_tick,
+                status: ProposalStatus::Voting,
+                votes: BTreeMap::new(),
+                tally: None,
+                minter,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Record `voter`'s vote on an open proposal, replacing any earlier one
+    pub fn vote(
+        &mut self,
+        voter: &Hash,
+        proposal_id: u64,
+        approve: bool,
+        current_tick: BlockHeight,
+    ) -> KalaResult<()> {
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .ok_or_else(|| KalaError::state(format!("Unknown proposal {}", proposal_id)))?;
+        if proposal.status != ProposalStatus::Voting || current_tick > proposal.voting_end {
+            return Err(KalaError::validation(format!("Voting on proposal {} has closed", proposal_id)));
+        }
+        proposal.votes.insert(*voter, approve);
+        Ok(())
+    }
+
+    pub fn proposal(&self, id: u64) -> Option<&Proposal> {
+        self.proposals.get(&id)
+    }
+
+    pub fn proposals(&self) -> impl Iterator<Item = &Proposal> {
+        self.proposals.values()
+    }
+
+    /// Value set for `parameter` by governance, if any
+    pub fn parameter(&self, parameter: GovernanceParameter) -> Option<u64> {
+        self.parameters.get(&parameter).copied()
+    }
+
+    /// Tally proposals whose voting closes at or before `tick`, and execute
+    /// passed ones due to activate
+    ///
+    /// Executing a minter approval is left to the caller, which finds the
+    /// denomination and account on the proposal.
+    ///
+    /// `stake_of` gives each voter's stake out of `total_stake`. Returns the
+    /// proposals whose status changed, with their new status.
+    pub fn finalize_tick(
+        &mut self,
+        tick: BlockHeight,
+        stake_of: impl Fn(&Hash) -> u64,
+        total_stake: u64,
+    ) -> Vec<(u64, ProposalStatus)> {
+        let mut changed = Vec::new();
+        for proposal in self.proposals.values_mut() {
+            if proposal.status == ProposalStatus::Voting && proposal.voting_end <= tick {
+                let (mut approving, mut rejecting) = (0u64, 0u64);
+                for (voter, approve) in &proposal.votes {
+                    let weight = stake_of(voter);
+                    if *approve {
+                        approving = approving.saturating_add(weight);
+                    } else {
+                        rejecting = rejecting.saturating_add(weight);
+                    }
+                }
+                let turnout = approving.saturating_add(rejecting);
+                let passed = turnout as u128 * 3 > total_stake as u128
+                    && QuorumCalculator::new(turnout).is_quorum(approving);
+
+                proposal.tally = Some((approving, rejecting));
+                proposal.status = if passed {
+                    ProposalStatus::Passed
+                } else {
+                    ProposalStatus::Rejected
+                };
+                changed.push((proposal.id, proposal.status));
+            }
+
+            if proposal.status == ProposalStatus::Passed && proposal.activation_tick <= tick {
+                if proposal.minter.is_none() {
+                    self.parameters.insert(proposal.parameter, proposal.value);
+                }
+                proposal.status = ProposalStatus::Executed;
+                changed.push((proposal.id, proposal.status));
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proposal_lifecycle() {
+        let mut governance = Governance::default();
+        let parameter = GovernanceParameter::TimelockHardness;
+        let (alice, bob, carol) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        let stake = |voter: &Hash| match voter[0] {
+            1 => 50,
+            2 => 30,
+            3 => 20,
+            _ => 0,
+        };
+
+        assert!(governance.propose(&alice, parameter, 0, 2_000, 10).is_err());
+        assert!(governance.propose(&alice, parameter, 2_500, 1_009, 10).is_err());
+        let id = governance.propose(&alice, parameter, 2_500, 1_010, 10).unwrap();
+        assert_eq!(governance.proposal(id).unwrap().voting_end, 1_009);
+
+        governance.vote(&alice, id, true, 10).unwrap();
+        governance.vote(&bob, id, false, 500).unwrap();
+        governance.vote(&bob, id, true, 501).unwrap();
+        governance.vote(&carol, id, false, 1_009).unwrap();
+        assert!(governance.vote(&carol, id, true, 1_010).is_err());
+        assert!(governance.vote(&carol, 9, true, 10).is_err());
+
+        // 80 of 100 voting stake approves
+        assert!(governance.finalize_tick(1_008, stake, 100).is_empty());
+        assert_eq!(governance.finalize_tick(1_009, stake, 100), vec![(id, ProposalStatus::Passed)]);
+        assert_eq!(governance.proposal(id).unwrap().tally, Some((80, 20)));
+        assert_eq!(governance.parameter(parameter), None);
+
+        assert_eq!(governance.finalize_tick(1_010, stake, 100), vec![(id, ProposalStatus::Executed)]);
+        assert_eq!(governance.parameter(parameter), Some(2_500));
+        assert!(governance.finalize_tick(1_011, stake, 100).is_empty());
+    }
+
+    #[test]
+    fn test_proposal_rejected() {
+        let mut governance = Governance::default();
+        let parameter = GovernanceParameter::TimelockHardness;
+        let stake = |voter: &Hash| voter[0] as u64 * 10;
+
+        // Two thirds of the voting stake is not a supermajority
+        let split = governance.propose(&[1u8; 32], parameter, 100, 2_000, 0).unwrap();
+        governance.vote(&[2u8; 32], split, true, 0).unwrap();
+        governance.vote(&[1u8; 32], split, false, 0).unwrap();
+
+        // A unanimous vote by a third of all stake is too few voters
+        let quiet = governance.propose(&[1u8; 32], parameter, 200, 2_000, 0).unwrap();
+        governance.vote(&[3u8; 32], quiet, true, 0).unwrap();
+
+        let changed = governance.finalize_tick(VOTING_PERIOD_TICKS, stake, 90);
+        assert_eq!(changed, vec![(split, ProposalStatus::Rejected), (quiet, ProposalStatus::Rejected)]);
+        assert!(governance.finalize_tick(2_000, stake, 90).is_empty());
+        assert_eq!(governance.parameter(parameter), None);
+    }
+}
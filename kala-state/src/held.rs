@@ -0,0 +1,218 @@
+//! Transactions held until the nonces before them are applied
+//!
+//! Envelopes stay encrypted until their tick, so the mempool can't order an
+//! account's transactions by nonce. Once decrypted, a transaction whose
+//! nonce is ahead of the account's next one is held instead of being
+//! rejected, and released as soon as the transactions filling the gap are
+//! applied, possibly ticks later.
+//!
+//! Held transactions are part of the chain state and its root. Whether one
+//! is held, released or dropped depends only on that state and the tick
+//! being processed, so a node that restarted or synced from a snapshot
+//! releases the same transactions as one that watched them arrive.
+//!
+//! A resubmission with the same sender and nonce replaces the earlier
+//! transaction if it offers a higher tip. How many transactions an account
+//! may hold, how far ahead of its nonce, and for how many ticks is bounded,
+//! so holding costs the chain little while the sender has paid nothing.
+
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::types::{BlockHeight, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Most transactions held for one account
+pub const MAX_HELD_PER_ACCOUNT: usize = 64;
+
+/// How far a held transaction's nonce may be ahead of the account's
+pub const MAX_NONCE_GAP: u64 = 1_024;
+
+/// Ticks a transaction is held before it's dropped
+pub const HOLD_TICKS: u64 = 100;
+
+/// A decrypted transaction waiting for its sender's earlier nonces
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct HeldTransaction {
+    /// Hash of the envelope the transaction arrived in
+    pub envelope_hash: Hash,
+    /// Canonical encoding of the decrypted transaction
+    pub transaction: Vec<u8>,
+    pub tip: u64,
+    pub held_at: BlockHeight,
+}
+
+/// Future-nonce transactions by sender and nonce
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeldTransactions {
+    held: BTreeMap<Hash, BTreeMap<u64, HeldTransaction>>,
+}
+
+impl HeldTransactions {
+    /// Hold `transaction` from `sender` until the nonces between
+    /// `account_nonce` and `nonce` are applied
+    ///
+    /// # Returns
+    ///
+    /// The envelope hash of a held transaction it replaced; an error if the
+    /// transaction can't be held
+    pub fn hold(
+        &mut self,
+        sender: &Hash,
+        nonce: u64,
+        account_nonce: u64,
+        transaction: HeldTransaction,
+    ) -> KalaResult<Option<Hash>> {
+        if nonce <= account_nonce.saturating_add(1) {
+            return Err(KalaError::validation(format!(
+                "Nonce {} is not ahead of the next nonce",
+                nonce
+            )));
+        }
+        if nonce - account_nonce > MAX_NONCE_GAP {
+            return Err(KalaError::validation(format!(
+                "Nonce {} is more than {} ahead of account nonce {}",
+                nonce, MAX_NONCE_GAP, account_nonce
+            )));
+        }
+
+        let account = self.held.entry(*sender).or_default();
+        let replaced = match account.get(&nonce) {
+            Some(existing) if transaction.tip <= existing.tip => {
+                return Err(KalaError::validation(format!(
+                    "Replacement of nonce {} must offer a tip above {}",
+                    nonce, existing.tip
+                )));
+            }
+            Some(existing) => Some(existing.envelope_hash),
+            None if account.len() >= MAX_HELD_PER_ACCOUNT => {
+                return Err(KalaError::validation(format!(
+                    "Account already has {} held transactions",
+                    MAX_HELD_PER_ACCOUNT
+                )));
+            }
+            None => None,
+        };
+        account.insert(nonce, transaction);
+        Ok(replaced)
+    }
+
+    /// Take the held transaction following `account_nonce`, if there is one
+    ///
+    /// Held transactions at or below `account_nonce` can no longer apply
+    /// and are dropped.
+    pub fn take_next(&mut self, address: &Hash, account_nonce: u64) -> Option<HeldTransaction> {
+        let account = self.held.get_mut(address)?;
+        let next = account_nonce.saturating_add(1);
+        *account = account.split_off(&next);
+        let taken = account.remove(&next);
+        if account.is_empty() {
+            self.held.remove(address);
+        }
+        taken
+    }
+
+    /// Drop transactions held for [`HOLD_TICKS`] as of `tick`
+    ///
+    /// # Returns
+    ///
+    /// Envelope hashes of the dropped transactions
+    pub fn prune(&mut self, tick: BlockHeight) -> Vec<Hash> {
+        let mut dropped = Vec::new();
+        self.held.retain(|_, account| {
+            account.retain(|_, held| {
+                let keep = held.held_at.saturating_add(HOLD_TICKS) > tick;
+                if !keep {
+                    dropped.push(held.envelope_hash);
+                }
+                keep
+            });
+            !account.is_empty()
+        });
+        dropped
+    }
+
+    /// Nonces held for `address`, in order
+    pub fn pending_nonces(&self, address: &Hash) -> Vec<u64> {
+        self.held
+            .get(address)
+            .map(|account| account.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of transactions held across all accounts
+    pub fn len(&self) -> usize {
+        self.held.values().map(BTreeMap::len).sum()
+    }
+
+    /// Whether no transactions are held
+    pub fn is_empty(&self) -> bool {
+        self.held.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn held(envelope: u8, tip: u64, held_at: BlockHeight) -> HeldTransaction {
+        HeldTransaction {
+            envelope_hash: [envelope; 32],
+            transaction: vec![envelope],
+            tip,
+            held_at,
+        }
+    }
+
+    #[test]
+    fn test_released_in_nonce_order() {
+        let mut queue = HeldTransactions::default();
+        let alice = [1u8; 32];
+
+        assert!(queue.hold(&alice, 3, 0, held(3, 0, 10)).unwrap().is_none());
+        assert!(queue.hold(&alice, 4, 0, held(4, 0, 10)).unwrap().is_none());
+        assert!(queue.hold(&alice, 6, 0, held(6, 0, 10)).unwrap().is_none());
+        assert_eq!(queue.pending_nonces(&alice), vec![3, 4, 6]);
+
+        // Nothing follows until nonce 2 is applied
+        assert!(queue.take_next(&alice, 0).is_none());
+        assert_eq!(queue.take_next(&alice, 2).unwrap().envelope_hash, [3u8; 32]);
+        assert_eq!(queue.take_next(&alice, 3).unwrap().envelope_hash, [4u8; 32]);
+        assert!(queue.take_next(&alice, 4).is_none());
+        assert_eq!(queue.len(), 1);
+
+        // A nonce applied past the gap leaves nothing to release
+        assert!(queue.take_next(&alice, 7).is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_hold_limits_and_replacement() {
+        let mut queue = HeldTransactions::default();
+        let alice = [1u8; 32];
+
+        assert!(queue.hold(&alice, 1, 0, held(1, 0, 0)).is_err());
+        assert!(queue
+            .hold(&alice, MAX_NONCE_GAP + 1, 0, held(1, 0, 0))
+            .is_err());
+
+        queue.hold(&alice, 5, 0, held(1, 10, 0)).unwrap();
+        assert!(queue.hold(&alice, 5, 0, held(2, 10, 0)).is_err());
+        assert_eq!(
+            queue.hold(&alice, 5, 0, held(3, 11, 0)).unwrap(),
+            Some([1u8; 32])
+        );
+        assert_eq!(queue.len(), 1);
+
+        for nonce in 6..5 + MAX_HELD_PER_ACCOUNT as u64 {
+            queue.hold(&alice, nonce, 0, held(0, 0, 0)).unwrap();
+        }
+        assert!(queue.hold(&alice, 100, 0, held(0, 0, 0)).is_err());
+        assert!(queue.hold(&[2u8; 32], 100, 0, held(0, 0, 0)).is_ok());
+
+        // Held transactions expire
+        assert!(queue.prune(HOLD_TICKS - 1).is_empty());
+        assert_eq!(queue.prune(HOLD_TICKS).len(), MAX_HELD_PER_ACCOUNT + 1);
+        assert!(queue.is_empty());
+    }
+}
@@ -14,7 +14,8 @@
 //! - Efficient in-memory operations for consensus processing
 //!
 //! ### [`StateDB`] - Persistent Storage  
-//! - RocksDB-backed persistent storage
+//! - RocksDB-backed persistent storage, one column family per record kind
+//! - Ticks committed atomically with the chain state after them
 //! - Tick certificate archival and retrieval
 //! - State checkpoint persistence
 //! - Optimized for high-throughput tick processing
@@ -25,18 +26,62 @@
 //! - Balance tracking with overflow protection
 //! - Nonce-based replay attack prevention
 //! - Staking and delegation support
+//! - Slashing of stake delegated to double-signing witnesses
 //! - Efficient account lookup and updates
 //!
+//! ### Governance
+//! - Stake-weighted votes on chain parameter changes
+//! - Passed changes applied at their activation tick
+//! - Per-transaction fee, burned from the sender or a co-signing sponsor
+//!
+//! ### Mint Authority
+//! - The native denomination minted only by its issuer or governance-approved
+//!   minters, unless a development chain opens minting to every account
+//! - Issuance moved to another account only by the current issuer
+//!
+//! ### Auctions
+//! - Sealed-bid auctions whose bids are all decrypted in the closing tick
+//! - Bids escrowed, the highest paid to the seller and the rest refunded
+//!
+//! ### Account Recovery
+//! - Guardians able to move an account to a new owner once enough agree
+//! - A delay of ticks before it takes effect, during which the owner can veto
+//!
+//! ### Foreign Timelines
+//! - Light clients following other Kala chains' signed epoch summaries
+//! - Ticks of those chains referenced with MMR inclusion proofs
+//! - Experimental packet transfers proven against their state roots
+//!   (`ibc` feature)
+//!
+//! ### Time Anchors
+//! - Clock readings witnesses sign every few ticks, medianized into anchors
+//! - Bounds on the UTC time of any tick from the anchors around it
+//! - Signed attestations of when a tick or transaction was recorded, for
+//!   notarization
+//!
+//! ### Oracle
+//! - Feed values witnesses report in their tick votes, medianized per tick
+//! - Values served with Merkle proofs of inclusion, checked against the
+//!   signed finality record they were medianized from
+//!
 //! ### Tick Certificate Storage
 //! - Persistent storage of all processed ticks
+//! - Per-module resource usage, added up per epoch for governance
 //! - Fast retrieval by tick number
 //! - Recent tick queries for blockchain explorers
 //! - VDF certificate integration
+//! - Standalone verification of a certificate against its predecessor
+//! - Chains of finalized certificates verified with their witness
+//!   signatures, reporting the first tick where they diverge
+//! - Ranges of ticks proven by a logarithmic sample of their segments
+//! - Old ticks moved to a remote archive, fetched back and verified on read
 //!
 //! ### Timelock Integration
 //! - Puzzle solution tracking and verification
 //! - Integration with VDF timing for puzzle hardness
 //! - MEV-resistant transaction ordering support
+//! - Witness key share reveals for threshold-locked envelopes
+//! - Signed witness observations of envelope arrivals, kept until finality
 //!
 //! ## Example Usage
 //!
@@ -64,16 +109,89 @@
 
 use kala_common::prelude::*;
 use kala_common::types::Hash;
-use serde_json;
-use kala_vdf::{TickCertificate as VDFTickCertificate, VDFCheckpoint};
-use std::collections::HashMap;
+use kala_common::types::consensus::{DEFAULT_DISCRIMINANT, DEFAULT_ITERATIONS_PER_TICK};
+use kala_common::types::database::{TICK_PREFIX, VDF_TICK_DATA_PREFIX, VDF_TICK_PREFIX};
+use kala_common::crypto::MerkleTree;
+use kala_common::database::{ColumnKey, DatabaseConfig, DatabaseUtils, TypedColumn};
+use kala_common::serialization::{
+    decode_versioned, encode_bincode, encode_versioned, is_versioned, SerializationError,
+    STORAGE_FORMAT_VERSION,
+};
+use kala_common::mmr::{peak_positions, MerkleMountainRange, MmrProof, MmrStore};
+use kala_vdf::persistence::{DatabasePersistence, LATEST_CHECKPOINT};
+use kala_vdf::{
+    PackedInteger, TickCertificate as VDFTickCertificate, TickData as VDFTickData, VDFCheckpoint,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use bincode::{Decode, Encode};
 
 pub mod account;
+pub mod anchor;
+pub mod archive;
+pub mod attestation;
+pub mod auction;
+pub mod epoch;
+pub mod foreign;
+pub mod governance;
+pub mod held;
+pub mod metrics;
+pub mod mint;
+pub mod observation;
+pub mod oracle;
+#[cfg(feature = "ibc")]
+pub mod packet;
+pub mod proto;
+pub mod range_proof;
+pub mod recovery;
+pub mod reveal;
 pub mod tick;
+pub mod usage;
+pub mod witness;
 
 pub use account::{Account, AccountState};
-pub use tick::{TickCertificate, TickType};
+pub use anchor::{TimeAnchor, TimeBounds};
+pub use archive::ArchiveBackend;
+pub use attestation::{Attestation, AttestedTime, TransactionInclusion};
+pub use auction::{Auction, AuctionBid, AuctionSettlement, AuctionStatus, Auctions};
+pub use epoch::{
+    verify_epoch_chain, EpochCertificate, EpochSignature, EpochStats, EpochSummary,
+    SignedEpochSummary, WitnessSignature,
+};
+pub use foreign::{decode_foreign_epoch, ForeignClient, ForeignClients, ForeignEpoch};
+pub use governance::{
+    Governance, GovernanceParameter, Proposal, ProposalStatus, VOTING_PERIOD_TICKS,
+};
+pub use held::{
+    HeldTransaction, HeldTransactions, HOLD_TICKS, MAX_HELD_PER_ACCOUNT, MAX_NONCE_GAP,
+};
+pub use metrics::{MetricsSample, METRICS_HISTORY_CAPACITY};
+pub use mint::{MintAuthorities, NATIVE_DENOM};
+pub use observation::{CanonicalTimestamp, ObservationPool, TickObservations, WitnessObservation};
+pub use oracle::{FeedId, FeedValue, OracleProof, OracleReport, OracleTick};
+#[cfg(feature = "ibc")]
+pub use packet::{Packet, PacketProof, PacketStore};
+pub use range_proof::{sample_ticks, ProvenTick, SampledSegment, TickRangeProof};
+pub use recovery::{
+    CompletedRecovery, GuardianSet, PendingRecovery, Recoveries, ScheduledRecovery,
+    MIN_RECOVERY_DELAY_TICKS,
+};
+pub use reveal::{ShareReveal, ShareRevealPool};
+pub use tick::{
+    verify_certificate_chain, verify_tick_certificate, ChainDivergence, ChainSummary,
+    TickCertificate, TickHeader, TickType,
+};
+pub use usage::ModuleUsage;
+pub use witness::{
+    DoubleSignEvidence, TickFinality, TickVote, VoteMetadata, VoteTime, Witness, WitnessMetadata,
+    WitnessSet, WitnessSignatures, WitnessTime, MAX_VOTE_METADATA_SIZE,
+};
+
+/// Cargo features this crate was built with
+pub const FEATURES: &[&str] = &[
+    #[cfg(feature = "ibc")]
+    "ibc",
+];
 
 /// Global chain state using kala-common types
 #[derive(Serialize, Deserialize, Encode, Decode, Clone)]
@@ -86,6 +204,40 @@ pub struct ChainState {
     pub tick_size: u64, // k = 65536 by default
     accounts: HashMap<Hash, Account>,
     puzzles: HashMap<Hash, PuzzleState>,
+    /// Latest double-signed tick each slashed witness was punished for
+    #[serde(default)]
+    slashed: BTreeMap<Hash, BlockHeight>,
+    /// Locked transfers by the tick they unlock at
+    #[serde(default)]
+    vesting: BTreeMap<BlockHeight, Vec<VestingSchedule>>,
+    #[serde(default)]
+    governance: Governance,
+    /// Issuers and approved minters of each denomination
+    #[serde(default)]
+    mint_authorities: MintAuthorities,
+    /// Light clients of other Kala timelines
+    #[serde(default)]
+    foreign: ForeignClients,
+    #[serde(default)]
+    auctions: Auctions,
+    /// Account guardians and the recoveries they requested
+    #[serde(default)]
+    recoveries: Recoveries,
+    /// Decrypted transactions waiting for their sender's earlier nonces
+    #[serde(default)]
+    held: HeldTransactions,
+    /// Packets sent to and received from other timelines
+    #[cfg(feature = "ibc")]
+    #[serde(default)]
+    packets: PacketStore,
+}
+
+/// Funds locked by a vesting transfer until its unlock tick
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub sender: Hash,
+    pub receiver: Hash,
+    pub amount: u64,
 }
 
 #[derive(Serialize, Deserialize, Encode, Decode, Clone)]
@@ -96,266 +248,2620 @@ pub struct PuzzleState {
     pub solved_at_iteration: IterationNumber,
 }
 
+/// Name of the chain state at the head
+const HEAD_STATE: &str = "chain_state";
+
+/// Name of the chain state the latest epoch was sealed with
+const EPOCH_STATE: &str = "epoch_state";
+
+/// Key of the storage format [`StateDB::migrate_storage`] last converted
+/// the records to
+const STORAGE_FORMAT_KEY: &[u8] = b"storage_format";
+
+/// Records rewritten per write while migrating
+const MIGRATION_BATCH_SIZE: usize = 1024;
+
+/// A tick finalized but not yet committed, with the chain state after it
+///
+/// Staged before the commit so that a failed write can be retried, on the
+/// spot or after a restart, without recomputing the tick. Stored with
+/// bincode, as the state's maps are keyed by hash.
+#[derive(Serialize, Deserialize, Encode, Decode, Clone)]
+pub struct PendingTick {
+    pub certificate: TickCertificate,
+    pub state: ChainState,
+}
+
 /// State database wrapper using kala-common database operations
 pub struct StateDB {
     db: KalaDatabase,
+    /// Chain states by name, [`HEAD_STATE`] or [`EPOCH_STATE`]
+    states: TypedColumn<&'static str, ChainState>,
+    ticks: TypedColumn<u64, TickCertificate>,
+    vdf_ticks: TypedColumn<u64, VDFTickCertificate>,
+    /// Data timestamped in each VDF tick, for ticks with any
+    vdf_tick_data: TypedColumn<u64, VDFTickData>,
+    epochs: TypedColumn<u64, SignedEpochSummary>,
+    epoch_certificates: TypedColumn<u64, EpochCertificate>,
+    finality: TypedColumn<u64, TickFinality>,
+    /// Staged ticks by tick number, cleared as they are committed
+    pending: TypedColumn<u64, PendingTick>,
+    time_anchors: TypedColumn<u64, TimeAnchor>,
+    /// Witness observations by tick, from [`Self::observations_from`] until finalized
+    observations: TypedColumn<u64, TickObservations>,
+    /// Metrics samples by slot in the ring
+    metrics: TypedColumn<u32, MetricsSample>,
+    /// Where ticks before [`Self::archived_before`] were moved
+    archive: Option<Arc<dyn ArchiveBackend>>,
 }
 
 impl StateDB {
     pub fn open(path: &str) -> KalaResult<Self> {
         let db = KalaDatabase::new(path)?;
-        Ok(Self { db })
+        Ok(Self::with_database(db))
     }
 
-    pub async fn load_chain_state(&self) -> KalaResult<ChainState> {
-        match self.db.load_data("", "chain_state").await? {
-            Some(state) => Ok(state),
-            None => Ok(ChainState::new()),
-        }
+    /// Open the database with non-default tuning, such as its compaction style
+    pub fn open_with_config(config: &DatabaseConfig) -> KalaResult<Self> {
+        let db = KalaDatabase::with_config(config)?;
+        Ok(Self::with_database(db))
     }
 
-    pub async fn save_chain_state(&self, state: &ChainState) -> KalaResult<()> {
-        self.db.store_data("", "chain_state", state).await
+    fn with_database(db: KalaDatabase) -> Self {
+        Self {
+            states: TypedColumn::new(&db, ""),
+            ticks: TypedColumn::new(&db, TICK_PREFIX),
+            vdf_ticks: TypedColumn::new(&db, VDF_TICK_PREFIX),
+            vdf_tick_data: TypedColumn::new(&db, VDF_TICK_DATA_PREFIX),
+            epochs: TypedColumn::new(&db, "epoch"),
+            epoch_certificates: TypedColumn::new(&db, "epoch_cert"),
+            finality: TypedColumn::new(&db, "finality"),
+            pending: TypedColumn::new(&db, "pending_tick"),
+            time_anchors: TypedColumn::new(&db, "time_anchor"),
+            observations: TypedColumn::new(&db, "observation"),
+            metrics: TypedColumn::new(&db, "metrics"),
+            archive: None,
+            db,
+        }
     }
 
-    pub async fn store_tick(&self, certificate: &TickCertificate) -> KalaResult<()> {
-        let key = format!("{:016x}", certificate.tick_number);
-        // Use JSON serialization for external types
-        let json_data = serde_json::to_vec(certificate)
-            .map_err(|e| KalaError::serialization(format!("Failed to serialize tick certificate: {}", e)))?;
-        self.db.put_raw(format!("tick:{}", key).as_bytes(), &json_data)?;
-
-        // Update index
-        self.update_tick_index(certificate.tick_number).await?;
+    /// Move old ticks to `archive` with [`Self::archive_ticks`], and read
+    /// them back from it
+    pub fn with_archive(mut self, archive: Arc<dyn ArchiveBackend>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
 
-        Ok(())
+    /// Write a consistent copy of the state database to `path`
+    ///
+    /// Uses a RocksDB checkpoint, so the node can keep running. `path`
+    /// must not exist yet.
+    pub async fn create_backup(&self, path: &str) -> KalaResult<()> {
+        self.db.create_checkpoint(path)
     }
 
-    pub async fn store_vdf_tick_certificate(&self, cert: &VDFTickCertificate) -> KalaResult<()> {
-        let key = format!("{:016x}", cert.tick_number);
-        // Use JSON serialization for external types
-        let json_data = serde_json::to_vec(cert)
-            .map_err(|e| KalaError::serialization(format!("Failed to serialize VDF certificate: {}", e)))?;
-        self.db.put_raw(format!("vdf_tick:{}", key).as_bytes(), &json_data)
+    /// Restore a backup into `db_path` and open it
+    ///
+    /// `db_path` must not exist yet. The restored state is checked with
+    /// [`Self::verify_state`] before it is returned.
+    pub async fn restore_backup(backup_path: &str, db_path: &str) -> KalaResult<Self> {
+        DatabaseUtils::restore_database(backup_path, db_path)?;
+        let db = Self::open(db_path)?;
+        db.verify_state().await?;
+        Ok(db)
     }
 
-    pub async fn get_vdf_tick_certificate(&self, tick_number: u64) -> KalaResult<Option<VDFTickCertificate>> {
-        let key = format!("{:016x}", tick_number);
-        // Use JSON deserialization for external types
-        match self.db.get_raw(format!("vdf_tick:{}", key).as_bytes())? {
-            Some(data) => {
-                let cert = serde_json::from_slice(&data)
-                    .map_err(|e| KalaError::serialization(format!("Failed to deserialize VDF certificate: {}", e)))?;
-                Ok(Some(cert))
+    /// Check the chain state against the latest tick certificate
+    ///
+    /// The state must continue from the latest stored tick, which must be
+    /// committed to the tick accumulator. If that tick ends a sealed epoch,
+    /// the state root must match the epoch summary. Returns the state root.
+    pub async fn verify_state(&self) -> KalaResult<Hash> {
+        let state = self.load_chain_state().await?;
+        let state_root = state.state_root();
+
+        let leaf_count = self.get_mmr_leaf_count()?;
+        if leaf_count == 0 {
+            if state.current_tick != 0 {
+                return Err(KalaError::state(format!(
+                    "State is at tick {} but no ticks are stored",
+                    state.current_tick
+                )));
             }
-            None => Ok(None),
+            return Ok(state_root);
+        }
+
+        let tick_number = self.get_tick_index().await?;
+        let tick = self
+            .get_tick(tick_number)
+            .await?
+            .ok_or_else(|| KalaError::state(format!("Missing latest tick {}", tick_number)))?;
+        if tick_number + 1 != leaf_count {
+            return Err(KalaError::state(format!(
+                "Latest tick {} but {} ticks are committed",
+                tick_number, leaf_count
+            )));
         }
+        if state.current_tick != tick_number + 1 || state.last_tick_hash != tick.tick_hash {
+            return Err(KalaError::state(format!(
+                "State at tick {} doesn't follow the latest tick {}",
+                state.current_tick, tick_number
+            )));
+        }
+
+        if let Some(epoch) = self.latest_epoch_summary().await? {
+            if epoch.summary.end_tick == tick_number && epoch.summary.state_root != state_root {
+                return Err(KalaError::state(format!(
+                    "State root {} doesn't match epoch {} ({})",
+                    CryptoUtils::hash_to_hex(&state_root),
+                    epoch.summary.epoch_number,
+                    CryptoUtils::hash_to_hex(&epoch.summary.state_root)
+                )));
+            }
+        }
+
+        Ok(state_root)
     }
 
-    pub async fn get_tick(&self, tick_number: u64) -> KalaResult<Option<TickCertificate>> {
-        let key = format!("{:016x}", tick_number);
-        // Use JSON deserialization for external types
-        match self.db.get_raw(format!("tick:{}", key).as_bytes())? {
-            Some(data) => {
-                let cert = serde_json::from_slice(&data)
-                    .map_err(|e| KalaError::serialization(format!("Failed to deserialize tick certificate: {}", e)))?;
-                Ok(Some(cert))
+    /// Rewrite the records older nodes stored in the current storage format
+    ///
+    /// Older nodes stored tick certificates, VDF certificates and
+    /// checkpoints as JSON, with their forms as hex strings, and chain
+    /// states unversioned. Each such record is decoded, re-encoded with its
+    /// forms packed, and decoded again, and must come back with the same
+    /// state root or tick hash, or the same forms and hash chain, before it
+    /// is written. Records already converted are skipped, so a migration
+    /// cut short picks up where it stopped; once it completes it is
+    /// recorded under [`STORAGE_FORMAT_KEY`] and not run again.
+    ///
+    /// # Returns
+    ///
+    /// The number of records rewritten
+    pub async fn migrate_storage(&self) -> KalaResult<usize> {
+        if let Some(version) = self.db.get_raw(STORAGE_FORMAT_KEY)? {
+            if version.first().is_some_and(|&version| version >= STORAGE_FORMAT_VERSION) {
+                return Ok(0);
             }
-            None => Ok(None),
         }
+
+        let mut batch = KalaBatch::new();
+        let mut migrated = 0;
+        for name in [HEAD_STATE, EPOCH_STATE] {
+            migrated +=
+                self.migrate_record(&self.states, &name, &mut batch, ChainState::state_root)?;
+        }
+        for tick_number in self.stored_tick_numbers(self.ticks.prefix()).await? {
+            migrated += self.migrate_record(
+                &self.ticks,
+                &tick_number,
+                &mut batch,
+                TickCertificate::compute_hash,
+            )?;
+            self.flush_migration(&mut batch)?;
+        }
+        for tick_number in self.stored_tick_numbers(self.pending.prefix()).await? {
+            migrated += self.migrate_record(&self.pending, &tick_number, &mut batch, |pending| {
+                (pending.certificate.compute_hash(), pending.state.state_root())
+            })?;
+            self.flush_migration(&mut batch)?;
+        }
+        for tick_number in self.stored_tick_numbers(self.vdf_ticks.prefix()).await? {
+            migrated += self.migrate_record(
+                &self.vdf_ticks,
+                &tick_number,
+                &mut batch,
+                |certificate| {
+                    let form = [&certificate.form_a, &certificate.form_b, &certificate.form_c];
+                    (form.map(PackedInteger::to_hex), certificate.hash_chain)
+                },
+            )?;
+            self.flush_migration(&mut batch)?;
+        }
+        migrated += self.migrate_record(
+            &DatabasePersistence::checkpoints(&self.db),
+            &LATEST_CHECKPOINT,
+            &mut batch,
+            |checkpoint| {
+                let form = [&checkpoint.form_a, &checkpoint.form_b, &checkpoint.form_c];
+                (form.map(PackedInteger::to_hex), checkpoint.hash_chain)
+            },
+        )?;
+
+        batch.put(STORAGE_FORMAT_KEY, &[STORAGE_FORMAT_VERSION]);
+        self.db.write_batch(batch)?;
+        Ok(migrated)
     }
 
-    pub async fn get_recent_ticks(&self, count: usize) -> KalaResult<Vec<TickCertificate>> {
-        let index = self.get_tick_index().await?;
-        let start = index.saturating_sub(count as u64);
+    /// Queue `key` re-encoded in `batch` if it was stored unversioned
+    ///
+    /// `fingerprint` must give the same value for the record as stored and
+    /// as decoded from its new encoding. Returns the number of records
+    /// queued, one or none.
+    fn migrate_record<K: ColumnKey, V: KalaSerialize, F: PartialEq>(
+        &self,
+        column: &TypedColumn<K, V>,
+        key: &K,
+        batch: &mut KalaBatch,
+        fingerprint: impl Fn(&V) -> F,
+    ) -> KalaResult<usize> {
+        let db_key = column.key(key);
+        let stored = match self.db.get_raw(&db_key)? {
+            Some(stored) if !is_versioned(&stored) => stored,
+            _ => return Ok(0),
+        };
+        let failed = |action: &str, e: SerializationError| {
+            KalaError::serialization(format!(
+                "Failed to {} {} for migration: {}",
+                action,
+                String::from_utf8_lossy(&db_key),
+                e
+            ))
+        };
+        let record = V::decode(&stored).map_err(|e| failed("decode", e))?;
+        let encoded = record.encode().map_err(|e| failed("encode", e))?;
+        let migrated = V::decode(&encoded).map_err(|e| failed("decode converted", e))?;
+        if fingerprint(&migrated) != fingerprint(&record) {
+            return Err(KalaError::state(format!(
+                "{} changed in migration",
+                String::from_utf8_lossy(&db_key)
+            )));
+        }
+        batch.put(&db_key, &encoded);
+        Ok(1)
+    }
 
-        let mut ticks = Vec::new();
-        for i in start..=index {
-            if let Some(tick) = self.get_tick(i).await? {
-                ticks.push(tick);
-            }
+    /// Numbers of the records stored under `prefix`, keyed by tick
+    async fn stored_tick_numbers(&self, prefix: &str) -> KalaResult<Vec<u64>> {
+        self.db
+            .get_keys_with_prefix(prefix)
+            .await?
+            .iter()
+            .map(|key| {
+                u64::from_str_radix(key, 16).map_err(|_| {
+                    KalaError::state(format!("Malformed {} key {}", prefix, key))
+                })
+            })
+            .collect()
+    }
+
+    /// Write the migrated records once `batch` holds enough of them
+    fn flush_migration(&self, batch: &mut KalaBatch) -> KalaResult<()> {
+        if batch.len() >= MIGRATION_BATCH_SIZE {
+            self.db.write_batch(std::mem::take(batch))?;
         }
+        Ok(())
+    }
 
-        Ok(ticks)
+    /// The underlying database, for components that keep their own records
+    pub fn database(&self) -> &KalaDatabase {
+        &self.db
     }
 
-    async fn update_tick_index(&self, tick_number: u64) -> KalaResult<()> {
-        // Use raw bytes for simple u64 storage
-        self.db.put_raw(b"tick_index", &tick_number.to_le_bytes())
+    pub async fn load_chain_state(&self) -> KalaResult<ChainState> {
+        Ok(self.states.get(&HEAD_STATE)?.unwrap_or_else(ChainState::new))
     }
 
-    async fn get_tick_index(&self) -> KalaResult<u64> {
-        // Use raw bytes for simple u64 retrieval
-        match self.db.get_raw(b"tick_index")? {
-            Some(bytes) => {
-                if bytes.len() == 8 {
-                    let mut array = [0u8; 8];
-                    array.copy_from_slice(&bytes);
-                    Ok(u64::from_le_bytes(array))
-                } else {
-                    Ok(0)
-                }
-            }
-            None => Ok(0),
+    pub async fn save_chain_state(&self, state: &ChainState) -> KalaResult<()> {
+        self.states.put(&HEAD_STATE, state)
+    }
+
+    /// Keep the chain state the latest epoch was sealed with
+    ///
+    /// Served to syncing peers along with the epoch summary, which commits
+    /// to its state root. Only the latest epoch's state is kept.
+    pub async fn save_epoch_state(&self, state: &ChainState) -> KalaResult<()> {
+        self.states.put(&EPOCH_STATE, state)
+    }
+
+    pub async fn load_epoch_state(&self) -> KalaResult<Option<ChainState>> {
+        self.states.get(&EPOCH_STATE)
+    }
+
+    pub async fn store_tick(&self, certificate: &TickCertificate) -> KalaResult<()> {
+        let mut batch = KalaBatch::new();
+        self.batch_tick(&mut batch, certificate).await?;
+        self.db.write_batch(batch)
+    }
+
+    /// Store a tick and the chain state after it in one atomic write
+    ///
+    /// A crash can't leave the persisted state out of step with the ticks.
+    /// The write is synced to disk as the database's fsync policy asks, and
+    /// clears the tick if it was staged. Committing the same tick again is
+    /// harmless, so a failed commit can be retried.
+    pub async fn commit_tick(&self, certificate: &TickCertificate, state: &ChainState) -> KalaResult<()> {
+        let mut batch = KalaBatch::new();
+        self.batch_tick(&mut batch, certificate).await?;
+        self.states.put_in(&mut batch, &HEAD_STATE, state)?;
+        self.pending.delete_in(&mut batch, &certificate.tick_number);
+        self.db.commit_batch(batch)
+    }
+
+    /// Keep a finalized tick until [`Self::commit_tick`] succeeds
+    pub async fn stage_tick(&self, certificate: &TickCertificate, state: &ChainState) -> KalaResult<()> {
+        let pending = PendingTick {
+            certificate: certificate.clone(),
+            state: state.clone(),
+        };
+        self.pending.put(&certificate.tick_number, &pending)
+    }
+
+    /// The staged tick `tick_number`, if its commit never went through
+    pub async fn pending_tick(&self, tick_number: u64) -> KalaResult<Option<PendingTick>> {
+        self.pending.get(&tick_number)
+    }
+
+    /// Drop a staged tick that can no longer be committed
+    pub async fn discard_pending_tick(&self, tick_number: u64) -> KalaResult<()> {
+        self.pending.delete(&tick_number)
+    }
+
+    /// Force every write so far to stable storage
+    pub async fn sync(&self) -> KalaResult<()> {
+        self.db.sync_wal()
+    }
+
+    async fn batch_tick(&self, batch: &mut KalaBatch, certificate: &TickCertificate) -> KalaResult<()> {
+        // Commit the tick hash to the timeline accumulator
+        self.append_tick_to_mmr(batch, certificate)?;
+
+        self.ticks.put_in(batch, &certificate.tick_number, certificate)?;
+
+        // Update index, unless this is history backfilled below the head
+        if certificate.tick_number >= self.get_tick_index().await? {
+            batch.put(b"tick_index", &certificate.tick_number.to_le_bytes());
         }
+
+        Ok(())
     }
-}
 
-impl ChainState {
-    pub fn new() -> Self {
-        let discriminant = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+    /// Remove the ticks from `tick_number` on, for a reorg
+    ///
+    /// The certificates and their leaves in the tick accumulator are
+    /// dropped, so the head moves back to the tick before. Finalized ticks,
+    /// ticks in a sealed epoch, and the tick a checkpointed node started
+    /// from cannot be removed. Returns the removed certificates, newest first.
+    pub async fn truncate_ticks(&self, tick_number: u64) -> KalaResult<Vec<TickCertificate>> {
+        if let Some(finalized) = self.finalized_tick().await? {
+            if finalized >= tick_number {
+                return Err(KalaError::state(format!(
+                    "Cannot remove tick {}, ticks up to {} are finalized",
+                    tick_number, finalized
+                )));
+            }
+        }
+        if let Some(epoch) = self.latest_epoch_summary().await? {
+            if epoch.summary.end_tick >= tick_number {
+                return Err(KalaError::state(format!(
+                    "Cannot remove tick {}, epoch {} is sealed",
+                    tick_number, epoch.summary.epoch_number
+                )));
+            }
+        }
+        let history_start = self.history_start().await?;
+        if history_start > 0 && tick_number <= history_start {
+            return Err(KalaError::state(format!(
+                "Cannot remove tick {}, history starts at checkpoint tick {}",
+                tick_number, history_start
+            )));
+        }
 
-        Self {
-            current_tick: 0,
-            current_iteration: 0,
-            last_tick_hash: [0; 32],
-            total_transactions: 0,
-            tick_size: 65536, // k from the paper
-            vdf_checkpoint: VDFCheckpoint {
-                iteration: 0,
-                form_a: "1".to_string(),
-                form_b: "0".to_string(),
-                form_c: "1".to_string(),
-                hash_chain: {
-                    use sha2::{Digest, Sha256};
-                    let mut hasher = Sha256::new();
-                    hasher.update(b"genesis");
-                    hasher.finalize().into()
-                },
-                discriminant: discriminant.to_string(),
-                tick_size: 65536,
-                tick_certificates: Vec::new(),
-            },
-            accounts: HashMap::new(),
-            puzzles: HashMap::new(),
+        let leaf_count = self.get_mmr_leaf_count()?;
+        let mut batch = KalaBatch::new();
+        let mut removed = Vec::new();
+        for number in (tick_number..leaf_count).rev() {
+            if let Some(tick) = self.get_tick(number).await? {
+                removed.push(tick);
+            }
+            self.ticks.delete_in(&mut batch, &number);
+        }
+
+        // Accumulator nodes are addressed by position, so the dropped
+        // leaves are overwritten by the next appends
+        if tick_number < leaf_count {
+            batch.put(b"mmr_leaf_count", &tick_number.to_le_bytes());
         }
+        match tick_number.checked_sub(1) {
+            Some(head) => batch.put(b"tick_index", &head.to_le_bytes()),
+            None => batch.delete(b"tick_index"),
+        }
+        self.db.write_batch(batch)?;
+
+        Ok(removed)
     }
 
-    pub fn from_vdf_checkpoint(checkpoint: VDFCheckpoint) -> Self {
-        let current_tick = checkpoint.iteration / checkpoint.tick_size;
-        let current_iteration = checkpoint.iteration;
+    /// Root of the Merkle Mountain Range over all stored tick hashes
+    pub async fn tick_mmr_root(&self) -> KalaResult<Hash> {
+        self.tick_mmr()?.root()
+    }
 
-        Self {
-            current_tick,
-            current_iteration,
-            last_tick_hash: checkpoint.hash_chain,
-            total_transactions: 0,
-            tick_size: checkpoint.tick_size,
-            vdf_checkpoint: checkpoint,
-            accounts: HashMap::new(),
-            puzzles: HashMap::new(),
+    /// Number of ticks committed to the tick accumulator
+    pub async fn tick_mmr_leaf_count(&self) -> KalaResult<u64> {
+        self.get_mmr_leaf_count()
+    }
+
+    /// Prove that a tick is part of the timeline at the current head
+    ///
+    /// The proof verifies the tick's `tick_hash` against [`Self::tick_mmr_root`].
+    pub async fn prove_tick_inclusion(&self, tick_number: u64) -> KalaResult<MmrProof> {
+        self.tick_mmr()?.proof(tick_number)
+    }
+
+    /// Peaks of the tick accumulator when it held `leaf_count` ticks
+    ///
+    /// Accumulator nodes never change once written, so the peaks of any
+    /// earlier size can be served.
+    pub async fn tick_mmr_peaks(&self, leaf_count: u64) -> KalaResult<Vec<Hash>> {
+        let committed = self.get_mmr_leaf_count()?;
+        if leaf_count > committed {
+            return Err(KalaError::state(format!(
+                "Only {} ticks are committed, not {}",
+                committed, leaf_count
+            )));
         }
+
+        let store = TickMmrStore { db: &self.db, batch: None };
+        peak_positions(leaf_count)
+            .into_iter()
+            .map(|pos| {
+                store.get_node(pos)?.ok_or_else(|| {
+                    KalaError::state(format!("Missing MMR node {} (history not backfilled)", pos))
+                })
+            })
+            .collect()
     }
 
-    pub fn update_from_vdf_checkpoint(&mut self, checkpoint: VDFCheckpoint) {
-        self.current_iteration = checkpoint.iteration;
-        self.current_tick = checkpoint.iteration / checkpoint.tick_size;
-        self.last_tick_hash = checkpoint.hash_chain;
-        self.vdf_checkpoint = checkpoint;
+    /// Prove ticks `start..=end` with sampled VDF segments
+    ///
+    /// Proofs are against the accumulator root after `end`, which an epoch
+    /// summary ending at `end` signs as its `mmr_root`.
+    pub async fn prove_tick_range(&self, start: u64, end: u64) -> KalaResult<TickRangeProof> {
+        let committed = self.get_mmr_leaf_count()?;
+        if end >= committed {
+            return Err(KalaError::state(format!(
+                "Only {} ticks are committed, not tick {}",
+                committed, end
+            )));
+        }
+
+        let store = TickMmrStore { db: &self.db, batch: None };
+        let mmr = MerkleMountainRange::with_store(store, end + 1);
+        TickRangeProof::build(&mmr.root()?, start, end, |tick| {
+            let certificate = self
+                .ticks
+                .get(&tick)?
+                .ok_or_else(|| KalaError::state(format!("Tick {} is not stored", tick)))?;
+            Ok(ProvenTick {
+                certificate,
+                proof: mmr.proof(tick)?,
+            })
+        })
     }
 
-    pub fn get_account(&self, address: &Hash) -> Option<&Account> {
-        self.accounts.get(address)
+    /// Start the tick accumulator from the peaks of a trusted checkpoint
+    ///
+    /// Appends after the checkpoint only touch the peaks, so the node can
+    /// extend the accumulator without the ticks before it. Inclusion
+    /// proofs for those ticks are unavailable. Does nothing if the
+    /// accumulator already covers `leaf_count` ticks.
+    pub async fn import_tick_mmr_peaks(&self, leaf_count: u64, peaks: &[Hash]) -> KalaResult<()> {
+        let committed = self.get_mmr_leaf_count()?;
+        if committed >= leaf_count {
+            return Ok(());
+        }
+        if committed > 0 {
+            return Err(KalaError::state(format!(
+                "Tick accumulator already holds {} ticks",
+                committed
+            )));
+        }
+
+        let positions = peak_positions(leaf_count);
+        if positions.len() != peaks.len() {
+            return Err(KalaError::validation(format!(
+                "Expected {} peaks for {} ticks, got {}",
+                positions.len(),
+                leaf_count,
+                peaks.len()
+            )));
+        }
+
+        let mut store = TickMmrStore { db: &self.db, batch: None };
+        for (pos, peak) in positions.into_iter().zip(peaks) {
+            store.put_node(pos, *peak)?;
+        }
+        self.db.put_raw(b"mmr_leaf_count", &leaf_count.to_le_bytes())
     }
 
-    pub fn get_account_mut(&mut self, address: &Hash) -> &mut Account {
-        self.accounts.entry(*address).or_insert(Account::new())
+    /// First tick of the contiguous history held locally
+    ///
+    /// Zero unless the node started from a trusted checkpoint and has not
+    /// backfilled the ticks before it.
+    pub async fn history_start(&self) -> KalaResult<u64> {
+        match self.db.get_raw(b"history_start")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(u64::from_le_bytes(array))
+            }
+            _ => Ok(0),
+        }
     }
 
-    pub fn get_balance(&self, address: &Hash) -> u64 {
-        self.accounts.get(address).map(|a| a.balance).unwrap_or(0)
+    pub async fn set_history_start(&self, tick_number: u64) -> KalaResult<()> {
+        self.db.put_raw(b"history_start", &tick_number.to_le_bytes())
     }
 
-    pub fn get_account_nonce(&self, address: &Hash) -> Option<u64> {
-        self.accounts.get(address).map(|a| a.nonce)
+    /// First tick not moved to the archive
+    pub async fn archived_before(&self) -> KalaResult<u64> {
+        match self.db.get_raw(b"archived_before")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(u64::from_le_bytes(array))
+            }
+            _ => Ok(0),
+        }
     }
 
-    pub fn update_nonce(&mut self, address: &Hash, nonce: u64) {
-        self.get_account_mut(address).nonce = nonce;
+    /// Move the ticks before `before` to the archive backend
+    ///
+    /// Only ticks that can no longer be reorged, finalized or in a sealed
+    /// epoch, are moved. Each tick certificate and VDF certificate is
+    /// uploaded before it is removed locally, and the tick accumulator
+    /// keeps committing to them. Returns the first tick still held locally.
+    pub async fn archive_ticks(&self, before: u64) -> KalaResult<u64> {
+        let archive = self
+            .archive
+            .as_ref()
+            .ok_or_else(|| KalaError::config("No archive backend is configured"))?;
+        let mut settled = self.finalized_tick().await?.map_or(0, |tick| tick + 1);
+        if let Some(epoch) = self.latest_epoch_summary().await? {
+            settled = settled.max(epoch.summary.end_tick + 1);
+        }
+
+        let before = before.min(settled);
+        let mut next = self.archived_before().await?;
+        while next < before {
+            let mut batch = KalaBatch::new();
+            if let Some(tick) = self.ticks.get(&next)? {
+                archive.put(&archive::tick_key(next), KalaSerialize::encode(&tick)?).await?;
+                self.ticks.delete_in(&mut batch, &next);
+            }
+            if let Some(vdf_tick) = self.vdf_ticks.get(&next)? {
+                let bytes = KalaSerialize::encode(&vdf_tick)?;
+                archive.put(&archive::vdf_tick_key(next), bytes).await?;
+                self.vdf_ticks.delete_in(&mut batch, &next);
+            }
+            next += 1;
+            batch.put(b"archived_before", &next.to_le_bytes());
+            self.db.write_batch(batch)?;
+        }
+        Ok(next)
     }
 
-    pub fn transfer(&mut self, from: &Hash, to: &Hash, amount: u64) -> KalaResult<()> {
-        // Deduct from sender
-        let sender = self.get_account_mut(from);
-        if sender.balance < amount {
-            return Err(KalaError::state("Insufficient balance"));
+    /// An archived tick, checked against the tick accumulator
+    async fn fetch_archived_tick(
+        &self,
+        archive: &dyn ArchiveBackend,
+        tick_number: u64,
+    ) -> KalaResult<TickCertificate> {
+        let bytes = archive
+            .get(&archive::tick_key(tick_number))
+            .await?
+            .ok_or_else(|| {
+                KalaError::state(format!("Tick {} is missing from the archive", tick_number))
+            })?;
+        let tick = <TickCertificate as KalaSerialize>::decode(&bytes)?;
+        let proof = self.prove_tick_inclusion(tick_number).await?;
+        if tick.tick_number != tick_number
+            || tick.compute_hash() != tick.tick_hash
+            || !proof.verify(&tick.tick_hash, &self.tick_mmr_root().await?)
+        {
+            return Err(KalaError::validation(format!(
+                "Archived tick {} doesn't match the tick accumulator",
+                tick_number
+            )));
         }
-        sender.balance -= amount;
+        Ok(tick)
+    }
 
-        // Add to receiver
-        let receiver = self.get_account_mut(to);
-        receiver.balance += amount;
+    fn tick_mmr(&self) -> KalaResult<MerkleMountainRange<TickMmrStore<'_>>> {
+        let leaf_count = self.get_mmr_leaf_count()?;
+        Ok(MerkleMountainRange::with_store(TickMmrStore { db: &self.db, batch: None }, leaf_count))
+    }
+
+    fn append_tick_to_mmr(&self, batch: &mut KalaBatch, certificate: &TickCertificate) -> KalaResult<()> {
+        let leaf_count = self.get_mmr_leaf_count()?;
 
+        // Leaf index == tick number, so re-stored ticks are already committed
+        if certificate.tick_number < leaf_count {
+            return Ok(());
+        }
+        if certificate.tick_number > leaf_count {
+            return Err(KalaError::state(format!(
+                "Tick {} skips ahead of the tick accumulator ({} ticks committed)",
+                certificate.tick_number, leaf_count
+            )));
+        }
+
+        // Appends only read older nodes, so queued writes need not be visible
+        let store = TickMmrStore { db: &self.db, batch: Some(&mut *batch) };
+        MerkleMountainRange::with_store(store, leaf_count).append(certificate.tick_hash)?;
+        batch.put(b"mmr_leaf_count", &(leaf_count + 1).to_le_bytes());
         Ok(())
     }
 
-    pub fn mint(&mut self, address: &Hash, amount: u64) -> KalaResult<()> {
-        let account = self.get_account_mut(address);
-        account.balance = account.balance.saturating_add(amount);
-        Ok(())
+    fn get_mmr_leaf_count(&self) -> KalaResult<u64> {
+        match self.db.get_raw(b"mmr_leaf_count")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(u64::from_le_bytes(array))
+            }
+            _ => Ok(0),
+        }
     }
 
-    pub fn stake(&mut self, staker: &Hash, validator: &Hash, amount: u64) -> KalaResult<()> {
-        let account = self.get_account_mut(staker);
-        if account.balance < amount {
-            return Err(KalaError::state("Insufficient balance"));
+    /// Summarise the epoch ending at the most recently stored tick
+    ///
+    /// Must be called right after the epoch's last tick is stored, so the
+    /// tick accumulator root matches the end of the epoch.
+    pub async fn build_epoch_summary(
+        &self,
+        epoch_number: u64,
+        epoch_length: u64,
+        state: &ChainState,
+        mut witnesses: Vec<[u8; 32]>,
+    ) -> KalaResult<EpochSummary> {
+        let start_tick = epoch_number * epoch_length;
+        let end_tick = start_tick + epoch_length - 1;
+
+        let mmr = self.tick_mmr()?;
+        if mmr.leaf_count() != end_tick + 1 {
+            return Err(KalaError::state(format!(
+                "Epoch {} ends at tick {} but {} ticks are committed",
+                epoch_number,
+                end_tick,
+                mmr.leaf_count()
+            )));
         }
-        account.balance -= amount;
-        account.staked_amount += amount;
-        account.delegation = Some(*validator);
-        Ok(())
+
+        let mut stats = EpochStats::default();
+        let mut end_tick_hash = [0u8; 32];
+        for tick_number in start_tick..=end_tick {
+            let tick = self.get_tick(tick_number).await?.ok_or_else(|| {
+                KalaError::state(format!("Missing tick {} for epoch {}", tick_number, epoch_number))
+            })?;
+            stats.record(&tick);
+            end_tick_hash = tick.tick_hash;
+            if let Some(oracle) = self.get_oracle_tick(tick_number).await? {
+                stats.oracle_updates += oracle.values.len() as u64;
+            }
+        }
+
+        let previous_epoch_hash = match epoch_number.checked_sub(1) {
+            Some(previous) => self
+                .get_epoch_summary(previous)
+                .await?
+                .map(|epoch| epoch.summary.compute_hash())
+                .ok_or_else(|| KalaError::state(format!("Missing summary for epoch {}", previous)))?,
+            None => [0u8; 32],
+        };
+
+        witnesses.sort();
+        witnesses.dedup();
+
+        Ok(EpochSummary {
+            epoch_number,
+            start_tick,
+            end_tick,
+            end_tick_hash,
+            mmr_root: mmr.root()?,
+            state_root: state.state_root(),
+            witnesses,
+            stats,
+            previous_epoch_hash,
+        })
     }
 
-    pub fn record_puzzle_solution(
-        &mut self,
-        solver: &Hash,
-        puzzle_id: &Hash,
-        proof: &[u8],
-    ) -> KalaResult<()> {
-        self.puzzles.insert(
-            *puzzle_id,
-            PuzzleState {
-                solver: *solver,
-                solution_proof: proof.to_vec(),
-                solved_at_tick: self.current_tick,
-                solved_at_iteration: self.current_iteration,
-            },
-        );
+    pub async fn store_epoch_summary(&self, epoch: &SignedEpochSummary) -> KalaResult<()> {
+        self.epochs.put(&epoch.summary.epoch_number, epoch)?;
+
+        if self.latest_epoch_number()? < Some(epoch.summary.epoch_number) {
+            self.db.put_raw(b"epoch_index", &epoch.summary.epoch_number.to_le_bytes())?;
+        }
         Ok(())
     }
 
-    pub fn get_account_count(&self) -> usize {
-        self.accounts.len()
+    pub async fn get_epoch_summary(&self, epoch_number: u64) -> KalaResult<Option<SignedEpochSummary>> {
+        self.epochs.get(&epoch_number)
     }
 
-    /// Get the tick number for a given iteration
-    pub fn iteration_to_tick(&self, iteration: u64) -> u64 {
-        iteration / self.tick_size
+    pub async fn latest_epoch_summary(&self) -> KalaResult<Option<SignedEpochSummary>> {
+        match self.latest_epoch_number()? {
+            Some(epoch_number) => self.get_epoch_summary(epoch_number).await,
+            None => Ok(None),
+        }
     }
 
-    /// Get the starting iteration for a given tick
-    pub fn tick_to_iteration(&self, tick: u64) -> u64 {
-        tick * self.tick_size
+    fn latest_epoch_number(&self) -> KalaResult<Option<u64>> {
+        match self.db.get_raw(b"epoch_index")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(Some(u64::from_le_bytes(array)))
+            }
+            _ => Ok(None),
+        }
     }
 
-    /// Check if we're at a tick boundary
-    pub fn is_tick_boundary(&self, iteration: u64) -> bool {
-        iteration % self.tick_size == 0 && iteration > 0
+    /// Record the witness set handover at the end of an epoch
+    pub async fn store_epoch_certificate(&self, certificate: &EpochCertificate) -> KalaResult<()> {
+        self.epoch_certificates.put(&certificate.epoch_number, certificate)?;
+
+        if self.latest_epoch_certificate_number()? < Some(certificate.epoch_number) {
+            self.db.put_raw(b"epoch_cert_index", &certificate.epoch_number.to_le_bytes())?;
+        }
+        Ok(())
     }
-}
 
-// Implement KalaSerialize for state types
-impl KalaSerialize for ChainState {
-    fn preferred_encoding() -> EncodingType {
-        EncodingType::Bincode // Compact for frequent state persistence
+    pub async fn get_epoch_certificate(&self, epoch_number: u64) -> KalaResult<Option<EpochCertificate>> {
+        self.epoch_certificates.get(&epoch_number)
     }
-}
 
-impl KalaSerialize for PuzzleState {
-    fn preferred_encoding() -> EncodingType {
-        EncodingType::Bincode // Compact storage for puzzles
+    /// The most recent handover, whose `witnesses` govern the current epoch
+    pub async fn latest_epoch_certificate(&self) -> KalaResult<Option<EpochCertificate>> {
+        match self.latest_epoch_certificate_number()? {
+            Some(epoch_number) => self.get_epoch_certificate(epoch_number).await,
+            None => Ok(None),
+        }
     }
-}
 
-// Since we can't implement KalaSerialize for external types due to orphan rules,
-// we'll use direct serialization for these types in the database operations
+    fn latest_epoch_certificate_number(&self) -> KalaResult<Option<u64>> {
+        match self.db.get_raw(b"epoch_cert_index")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(Some(u64::from_le_bytes(array)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Record that a tick was finalized by the witnesses
+    pub async fn store_tick_finality(&self, finality: &TickFinality) -> KalaResult<()> {
+        self.finality.put(&finality.tick_number, finality)?;
+
+        if self.finalized_tick().await? < Some(finality.tick_number) {
+            self.db.put_raw(b"finality_index", &finality.tick_number.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_tick_finality(&self, tick_number: u64) -> KalaResult<Option<TickFinality>> {
+        self.finality.get(&tick_number)
+    }
+
+    /// Feed values medianized from a tick's finality record
+    pub async fn get_oracle_tick(&self, tick_number: u64) -> KalaResult<Option<OracleTick>> {
+        let finality = self.get_tick_finality(tick_number).await?;
+        Ok(finality.map(|finality| OracleTick::from_finality(&finality)))
+    }
+
+    /// Store the anchor combined from a tick's clock readings
+    pub async fn store_time_anchor(&self, anchor: &TimeAnchor) -> KalaResult<()> {
+        self.time_anchors.put(&anchor.tick_number, anchor)?;
+
+        if self.latest_time_anchor().await?.map(|latest| latest.tick_number)
+            < Some(anchor.tick_number)
+        {
+            self.db.put_raw(b"time_anchor_index", &anchor.tick_number.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_time_anchor(&self, tick_number: u64) -> KalaResult<Option<TimeAnchor>> {
+        self.time_anchors.get(&tick_number)
+    }
+
+    /// Anchor of the highest tick one was stored for
+    pub async fn latest_time_anchor(&self) -> KalaResult<Option<TimeAnchor>> {
+        match self.db.get_raw(b"time_anchor_index")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                self.time_anchors.get(&u64::from_le_bytes(array))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Store a witness observation until its tick is finalized
+    ///
+    /// Returns `false` if the observation was already stored. The
+    /// observations of a tick are rewritten together, so writes for the
+    /// same tick must not race.
+    pub async fn add_observation(&self, observation: &WitnessObservation) -> KalaResult<bool> {
+        let tick_number = observation.tick_number;
+        let mut stored = self.observations.get(&tick_number)?.unwrap_or_default();
+        if stored.observations.contains(observation) {
+            return Ok(false);
+        }
+        stored.observations.push(observation.clone());
+
+        let mut batch = KalaBatch::new();
+        self.observations.put_in(&mut batch, &tick_number, &stored)?;
+        match self.observations_from()? {
+            Some(from) if from <= tick_number => {}
+            _ => batch.put(b"observations_from", &tick_number.to_le_bytes()),
+        }
+        self.db.write_batch(batch)?;
+        Ok(true)
+    }
+
+    /// Observations stored for a tick
+    pub async fn get_observations(&self, tick_number: u64) -> KalaResult<Vec<WitnessObservation>> {
+        Ok(self.observations.get(&tick_number)?.unwrap_or_default().observations)
+    }
+
+    /// Delete the observations of ticks before `tick_number`
+    ///
+    /// Called as ticks are finalized, when their order can no longer change.
+    pub async fn prune_observations(&self, tick_number: u64) -> KalaResult<()> {
+        let from = match self.observations_from()? {
+            Some(from) if from < tick_number => from,
+            _ => return Ok(()),
+        };
+        let mut batch = KalaBatch::new();
+        for tick in from..tick_number {
+            self.observations.delete_in(&mut batch, &tick);
+        }
+        batch.put(b"observations_from", &tick_number.to_le_bytes());
+        self.db.write_batch(batch)
+    }
+
+    /// Lowest tick observations may be stored for
+    fn observations_from(&self) -> KalaResult<Option<u64>> {
+        match self.db.get_raw(b"observations_from")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(Some(u64::from_le_bytes(array)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Highest tick finalized by the witnesses
+    pub async fn finalized_tick(&self) -> KalaResult<Option<u64>> {
+        match self.db.get_raw(b"finality_index")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(Some(u64::from_le_bytes(array)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Append a sample to the metrics ring, overwriting the oldest when full
+    pub async fn record_metrics_sample(&self, sample: &MetricsSample) -> KalaResult<()> {
+        let count = self.metrics_sample_count()?;
+        self.metrics.put(&((count % METRICS_HISTORY_CAPACITY) as u32), sample)?;
+        self.db.put_raw(b"metrics_count", &(count + 1).to_le_bytes())
+    }
+
+    /// Samples taken between `from` and `to` (Unix seconds, inclusive), oldest first
+    pub async fn metrics_history(&self, from: u64, to: u64) -> KalaResult<Vec<MetricsSample>> {
+        let count = self.metrics_sample_count()?;
+        let oldest = count.saturating_sub(METRICS_HISTORY_CAPACITY);
+
+        let mut samples = Vec::new();
+        for index in oldest..count {
+            let sample = self.metrics.get(&((index % METRICS_HISTORY_CAPACITY) as u32))?;
+            if let Some(sample) = sample.filter(|s| s.timestamp >= from && s.timestamp <= to) {
+                samples.push(sample);
+            }
+        }
+        Ok(samples)
+    }
+
+    fn metrics_sample_count(&self) -> KalaResult<u64> {
+        match self.db.get_raw(b"metrics_count")? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut array = [0u8; 8];
+                array.copy_from_slice(&bytes);
+                Ok(u64::from_le_bytes(array))
+            }
+            _ => Ok(0),
+        }
+    }
+
+    pub async fn store_vdf_tick_certificate(&self, cert: &VDFTickCertificate) -> KalaResult<()> {
+        self.vdf_ticks.put(&cert.tick_number, cert)
+    }
+
+    /// Data timestamped in a VDF tick, in the order of its Merkle leaves
+    ///
+    /// `None` if the tick timestamped no data or isn't finalized.
+    pub async fn get_vdf_tick_data(&self, tick_number: u64) -> KalaResult<Option<VDFTickData>> {
+        self.vdf_tick_data.get(&tick_number)
+    }
+
+    /// A VDF tick certificate, fetched from the archive if it was moved
+    ///
+    /// An archived certificate must end at the VDF state of its tick.
+    pub async fn get_vdf_tick_certificate(&self, tick_number: u64) -> KalaResult<Option<VDFTickCertificate>> {
+        if let Some(certificate) = self.vdf_ticks.get(&tick_number)? {
+            return Ok(Some(certificate));
+        }
+        let archive = match &self.archive {
+            Some(archive) if tick_number < self.archived_before().await? => archive,
+            _ => return Ok(None),
+        };
+        let Some(bytes) = archive.get(&archive::vdf_tick_key(tick_number)).await? else {
+            return Ok(None);
+        };
+        let certificate = <VDFTickCertificate as KalaSerialize>::decode(&bytes)?;
+        let tick = self
+            .get_tick(tick_number)
+            .await?
+            .ok_or_else(|| KalaError::state(format!("Missing tick {}", tick_number)))?;
+        if certificate.tick_number != tick_number
+            || certificate.end_iteration != tick.vdf_iteration
+            || certificate.hash_chain != tick.hash_chain_value
+        {
+            return Err(KalaError::validation(format!(
+                "Archived VDF certificate {} doesn't end at its tick",
+                tick_number
+            )));
+        }
+        Ok(Some(certificate))
+    }
+
+    /// A tick certificate, fetched from the archive if it was moved
+    pub async fn get_tick(&self, tick_number: u64) -> KalaResult<Option<TickCertificate>> {
+        if let Some(tick) = self.ticks.get(&tick_number)? {
+            return Ok(Some(tick));
+        }
+        match &self.archive {
+            Some(archive) if tick_number < self.archived_before().await? => {
+                Ok(Some(self.fetch_archived_tick(archive.as_ref(), tick_number).await?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn get_recent_ticks(&self, count: usize) -> KalaResult<Vec<TickCertificate>> {
+        let index = self.get_tick_index().await?;
+        let start = index.saturating_sub(count as u64);
+
+        let mut ticks = Vec::new();
+        for i in start..=index {
+            if let Some(tick) = self.get_tick(i).await? {
+                ticks.push(tick);
+            }
+        }
+
+        Ok(ticks)
+    }
+
+    async fn get_tick_index(&self) -> KalaResult<u64> {
+        // Use raw bytes for simple u64 retrieval
+        match self.db.get_raw(b"tick_index")? {
+            Some(bytes) => {
+                if bytes.len() == 8 {
+                    let mut array = [0u8; 8];
+                    array.copy_from_slice(&bytes);
+                    Ok(u64::from_le_bytes(array))
+                } else {
+                    Ok(0)
+                }
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+/// Tick accumulator nodes stored under `mmr:{position}`
+///
+/// With a batch, new nodes are queued in it instead of written directly.
+struct TickMmrStore<'a> {
+    db: &'a KalaDatabase,
+    batch: Option<&'a mut KalaBatch>,
+}
+
+impl MmrStore for TickMmrStore<'_> {
+    fn get_node(&self, pos: u64) -> KalaResult<Option<Hash>> {
+        match self.db.get_raw(format!("mmr:{:016x}", pos).as_bytes())? {
+            Some(bytes) if bytes.len() == 32 => {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(&bytes);
+                Ok(Some(hash))
+            }
+            Some(_) => Err(KalaError::state(format!("Corrupt MMR node {}", pos))),
+            None => Ok(None),
+        }
+    }
+
+    fn put_node(&mut self, pos: u64, hash: Hash) -> KalaResult<()> {
+        let key = format!("mmr:{:016x}", pos);
+        match self.batch.as_deref_mut() {
+            Some(batch) => {
+                batch.put(key.as_bytes(), &hash);
+                Ok(())
+            }
+            None => self.db.put_raw(key.as_bytes(), &hash),
+        }
+    }
+}
+
+impl ChainState {
+    /// Empty state of the default chain, with the paper's discriminant and k
+    pub fn new() -> Self {
+        Self::for_chain(DEFAULT_DISCRIMINANT, DEFAULT_ITERATIONS_PER_TICK)
+    }
+
+    /// Empty state of a chain over `discriminant` with ticks of `tick_size`
+    pub fn for_chain(discriminant: &str, tick_size: u64) -> Self {
+        Self {
+            current_tick: 0,
+            current_iteration: 0,
+            last_tick_hash: [0; 32],
+            total_transactions: 0,
+            tick_size,
+            vdf_checkpoint: VDFCheckpoint::genesis(discriminant, tick_size),
+            accounts: HashMap::new(),
+            puzzles: HashMap::new(),
+            slashed: BTreeMap::new(),
+            vesting: BTreeMap::new(),
+            governance: Governance::default(),
+            mint_authorities: MintAuthorities::default(),
+            foreign: ForeignClients::default(),
+            auctions: Auctions::default(),
+            recoveries: Recoveries::default(),
+            held: HeldTransactions::default(),
+            #[cfg(feature = "ibc")]
+            packets: PacketStore::default(),
+        }
+    }
+
+    pub fn from_vdf_checkpoint(checkpoint: VDFCheckpoint) -> Self {
+        let current_tick = checkpoint.iteration / checkpoint.tick_size;
+        let current_iteration = checkpoint.iteration;
+
+        Self {
+            current_tick,
+            current_iteration,
+            last_tick_hash: checkpoint.hash_chain,
+            total_transactions: 0,
+            tick_size: checkpoint.tick_size,
+            vdf_checkpoint: checkpoint,
+            accounts: HashMap::new(),
+            puzzles: HashMap::new(),
+            slashed: BTreeMap::new(),
+            vesting: BTreeMap::new(),
+            governance: Governance::default(),
+            mint_authorities: MintAuthorities::default(),
+            foreign: ForeignClients::default(),
+            auctions: Auctions::default(),
+            recoveries: Recoveries::default(),
+            held: HeldTransactions::default(),
+            #[cfg(feature = "ibc")]
+            packets: PacketStore::default(),
+        }
+    }
+
+    pub fn update_from_vdf_checkpoint(&mut self, checkpoint: VDFCheckpoint) {
+        self.current_iteration = checkpoint.iteration;
+        self.current_tick = checkpoint.iteration / checkpoint.tick_size;
+        self.last_tick_hash = checkpoint.hash_chain;
+        self.vdf_checkpoint = checkpoint;
+    }
+
+    pub fn get_account(&self, address: &Hash) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn get_account_mut(&mut self, address: &Hash) -> &mut Account {
+        self.accounts.entry(*address).or_insert(Account::new())
+    }
+
+    pub fn get_balance(&self, address: &Hash) -> u64 {
+        self.accounts.get(address).map(|a| a.balance).unwrap_or(0)
+    }
+
+    pub fn get_account_nonce(&self, address: &Hash) -> Option<u64> {
+        self.accounts.get(address).map(|a| a.nonce)
+    }
+
+    pub fn update_nonce(&mut self, address: &Hash, nonce: u64) {
+        self.get_account_mut(address).nonce = nonce;
+    }
+
+    pub fn transfer(&mut self, from: &Hash, to: &Hash, amount: u64) -> KalaResult<()> {
+        // Deduct from sender
+        let sender = self.get_account_mut(from);
+        if sender.balance < amount {
+            return Err(KalaError::state("Insufficient balance"));
+        }
+        sender.balance -= amount;
+
+        // Add to receiver
+        let receiver = self.get_account_mut(to);
+        receiver.balance += amount;
+
+        Ok(())
+    }
+
+    /// Debit the sum of `outputs` from `from` and credit each receiver
+    ///
+    /// Fails without changing any balance if `from` can't cover the sum.
+    pub fn multi_transfer(&mut self, from: &Hash, outputs: &[(Hash, u64)]) -> KalaResult<()> {
+        let total = outputs
+            .iter()
+            .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or_else(|| KalaError::state("Transfer total overflows"))?;
+        let sender = self.get_account_mut(from);
+        if sender.balance < total {
+            return Err(KalaError::state("Insufficient balance"));
+        }
+        sender.balance -= total;
+
+        for (to, amount) in outputs {
+            let receiver = self.get_account_mut(to);
+            receiver.balance += amount;
+        }
+        Ok(())
+    }
+
+    /// Debit `amount` from `from` and hold it for `to` until `unlock_tick`
+    ///
+    /// The unlock tick must still be ahead of the current tick.
+    pub fn lock_vesting(
+        &mut self,
+        from: &Hash,
+        to: &Hash,
+        amount: u64,
+        unlock_tick: BlockHeight,
+    ) -> KalaResult<()> {
+        if unlock_tick <= self.current_tick {
+            return Err(KalaError::state("Unlock tick has already passed"));
+        }
+        let sender = self.get_account_mut(from);
+        if sender.balance < amount {
+            return Err(KalaError::state("Insufficient balance"));
+        }
+        sender.balance -= amount;
+
+        self.vesting.entry(unlock_tick).or_default().push(VestingSchedule {
+            sender: *from,
+            receiver: *to,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Locked transfers to `receiver`, with the tick each unlocks at
+    pub fn vesting_of(&self, receiver: &Hash) -> Vec<(BlockHeight, &VestingSchedule)> {
+        self.vesting
+            .iter()
+            .flat_map(|(tick, schedules)| schedules.iter().map(move |schedule| (*tick, schedule)))
+            .filter(|(_, schedule)| schedule.receiver == *receiver)
+            .collect()
+    }
+
+    /// Credit every transfer unlocking at or before `tick` to its receiver
+    ///
+    /// Returns the released transfers, in unlock order.
+    pub fn release_vesting(&mut self, tick: BlockHeight) -> Vec<VestingSchedule> {
+        let locked = match tick.checked_add(1) {
+            Some(next) => self.vesting.split_off(&next),
+            None => BTreeMap::new(),
+        };
+        let released: Vec<_> = std::mem::replace(&mut self.vesting, locked)
+            .into_values()
+            .flatten()
+            .collect();
+
+        for schedule in &released {
+            let receiver = self.get_account_mut(&schedule.receiver);
+            receiver.balance = receiver.balance.saturating_add(schedule.amount);
+        }
+        released
+    }
+
+    pub fn mint(&mut self, address: &Hash, amount: u64) -> KalaResult<()> {
+        let account = self.get_account_mut(address);
+        account.balance = account.balance.saturating_add(amount);
+        Ok(())
+    }
+
+    /// Issuers and approved minters of each denomination
+    pub fn mint_authorities(&self) -> &MintAuthorities {
+        &self.mint_authorities
+    }
+
+    /// Make `issuer` the issuer of `denom`, as at genesis
+    pub fn set_issuer(&mut self, denom: &Hash, issuer: &Hash) {
+        self.mint_authorities.set_issuer(denom, issuer);
+    }
+
+    /// Let every account mint the native denomination, as on development
+    /// chains, or stop them
+    pub fn set_open_mint(&mut self, open: bool) {
+        self.mint_authorities.set_open(open);
+    }
+
+    /// Mint `amount` of `denom` to `minter`, if it holds authority over
+    /// the denomination
+    ///
+    /// Only the native denomination can be minted, as it's the only one
+    /// balances are kept in.
+    pub fn apply_mint(&mut self, minter: &Hash, denom: &Hash, amount: u64) -> KalaResult<()> {
+        self.mint_authorities.check_mint(minter, denom)?;
+        self.mint(minter, amount)
+    }
+
+    /// Hand the issuance of `denom` from `sender` to `new_authority`
+    pub fn transfer_mint_authority(
+        &mut self,
+        sender: &Hash,
+        denom: &Hash,
+        new_authority: &Hash,
+    ) -> KalaResult<()> {
+        self.mint_authorities.transfer(sender, denom, new_authority)
+    }
+
+    pub fn stake(&mut self, staker: &Hash, validator: &Hash, amount: u64) -> KalaResult<()> {
+        let account = self.get_account_mut(staker);
+        if account.balance < amount {
+            return Err(KalaError::state("Insufficient balance"));
+        }
+        // Stake counts towards a single validator, so moving it needs an unstake first
+        if account.staked_amount > 0 && account.delegation != Some(*validator) {
+            return Err(KalaError::state("Stake is delegated to another validator"));
+        }
+        account.balance -= amount;
+        account.staked_amount += amount;
+        account.delegation = Some(*validator);
+        Ok(())
+    }
+
+    pub fn unstake(&mut self, staker: &Hash, amount: u64) -> KalaResult<()> {
+        let account = self.get_account_mut(staker);
+        if account.staked_amount < amount {
+            return Err(KalaError::state("Insufficient stake"));
+        }
+        account.staked_amount -= amount;
+        account.balance += amount;
+        if account.staked_amount == 0 {
+            account.delegation = None;
+        }
+        Ok(())
+    }
+
+    /// Total stake delegated to each validator
+    pub fn delegated_stake(&self) -> BTreeMap<Hash, u64> {
+        let mut stake = BTreeMap::new();
+        for account in self.accounts.values() {
+            if let Some(validator) = account.delegation {
+                let total: &mut u64 = stake.entry(validator).or_default();
+                *total = total.saturating_add(account.staked_amount);
+            }
+        }
+        stake
+    }
+
+    /// Accounts delegating stake to `validator`
+    pub fn delegators(&self, validator: &Hash) -> Vec<Hash> {
+        let mut delegators: Vec<Hash> = self
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.delegation == Some(*validator))
+            .map(|(address, _)| *address)
+            .collect();
+        delegators.sort();
+        delegators
+    }
+
+    /// Latest double-signed tick `validator` was slashed for
+    pub fn last_slashed(&self, validator: &Hash) -> Option<BlockHeight> {
+        self.slashed.get(validator).copied()
+    }
+
+    /// Burn all stake delegated to a witness that double-signed at `tick_number`
+    ///
+    /// Returns the amount burned. Offences at or before the last tick the
+    /// witness was slashed for were already punished and are rejected.
+    pub fn slash(&mut self, validator: &Hash, tick_number: BlockHeight) -> KalaResult<u64> {
+        if self.last_slashed(validator).is_some_and(|last| tick_number <= last) {
+            return Err(KalaError::state("Witness already slashed for this tick"));
+        }
+        let mut burned = 0u64;
+        for account in self.accounts.values_mut() {
+            if account.delegation == Some(*validator) {
+                burned = burned.saturating_add(account.staked_amount);
+                account.staked_amount = 0;
+                account.delegation = None;
+            }
+        }
+        self.slashed.insert(*validator, tick_number);
+        Ok(burned)
+    }
+
+    /// Proposals and the parameter values they set
+    pub fn governance(&self) -> &Governance {
+        &self.governance
+    }
+
+    /// Open a proposal from a staked account
+    ///
+    /// Returns the proposal's ID.
+    pub fn propose(
+        &mut self,
+        proposer: &Hash,
+        parameter: GovernanceParameter,
+        value: u64,
+        activation_tick: BlockHeight,
+    ) -> KalaResult<u64> {
+        if self.get_account(proposer).map_or(0, |a| a.staked_amount) == 0 {
+            return Err(KalaError::state("Only staked accounts may propose"));
+        }
+        self.governance
+            .propose(proposer, parameter, value, activation_tick, self.current_tick)
+    }
+
+    /// Open a proposal from a staked account to approve or revoke
+    /// `minter` of `denom`
+    ///
+    /// Returns the proposal's ID.
+    pub fn propose_minter(
+        &mut self,
+        proposer: &Hash,
+        denom: &Hash,
+        minter: &Hash,
+        approve: bool,
+        activation_tick: BlockHeight,
+    ) -> KalaResult<u64> {
+        if self.get_account(proposer).map_or(0, |a| a.staked_amount) == 0 {
+            return Err(KalaError::state("Only staked accounts may propose"));
+        }
+        self.governance
+            .propose_minter(proposer, denom, minter, approve, activation_tick, self.current_tick)
+    }
+
+    /// Vote on an open proposal from a staked account
+    pub fn vote(&mut self, voter: &Hash, proposal_id: u64, approve: bool) -> KalaResult<()> {
+        if self.get_account(voter).map_or(0, |a| a.staked_amount) == 0 {
+            return Err(KalaError::state("Only staked accounts may vote"));
+        }
+        self.governance.vote(voter, proposal_id, approve, self.current_tick)
+    }
+
+    /// Tally the proposals closing at `tick` by current stake, and execute
+    /// those activating
+    ///
+    /// Returns the proposals whose status changed.
+    pub fn finalize_governance(&mut self, tick: BlockHeight) -> Vec<(u64, ProposalStatus)> {
+        let total_staked = self.total_staked();
+        let accounts = &self.accounts;
+        let changed = self.governance.finalize_tick(
+            tick,
+            |voter| accounts.get(voter).map_or(0, |a| a.staked_amount),
+            total_staked,
+        );
+
+        for (id, status) in &changed {
+            if *status != ProposalStatus::Executed {
+                continue;
+            }
+            if let Some(proposal) = self.governance.proposal(*id) {
+                if let Some((denom, minter)) = proposal.minter {
+                    self.mint_authorities
+                        .set_approved(&denom, &minter, proposal.value == 1);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Light clients of other Kala timelines
+    pub fn foreign_clients(&self) -> &ForeignClients {
+        &self.foreign
+    }
+
+    /// Anchor a foreign light client at `epoch`, or advance client `id` to it
+    ///
+    /// A zero `id` anchors a new client. Returns the client's ID.
+    pub fn submit_foreign_epoch(
+        &mut self,
+        id: &Hash,
+        epoch: &SignedEpochSummary,
+    ) -> KalaResult<Hash> {
+        self.foreign.submit(id, epoch)
+    }
+
+    /// Escrow `amount` from `sender` and commit a packet crediting
+    /// `receiver` on timeline `destination`
+    #[cfg(feature = "ibc")]
+    pub fn send_packet(
+        &mut self,
+        sender: &Hash,
+        receiver: &Hash,
+        amount: u64,
+        destination: &Hash,
+    ) -> KalaResult<Packet> {
+        let account = self.get_account_mut(sender);
+        if account.balance < amount {
+            return Err(KalaError::state("Insufficient balance"));
+        }
+        account.balance -= amount;
+        Ok(self.packets.send(sender, receiver, amount, destination))
+    }
+
+    /// Merkle path of sent packet `sequence` in the current state root
+    ///
+    /// The path is only valid against the root it was taken from, so a
+    /// relayer takes it at the end of epoch `epoch_number`.
+    #[cfg(feature = "ibc")]
+    pub fn packet_proof(&self, sequence: u64, epoch_number: u64) -> Option<PacketProof> {
+        let position = self.packets.commitments().position(|(sent, _)| sent == sequence)?;
+        let index = self.accounts.len() + position;
+        Some(PacketProof {
+            epoch_number,
+            index: index as u64,
+            siblings: MerkleTree::new(self.state_leaves()).proof(index)?,
+        })
+    }
+
+    /// Receive a packet sent to `chain_id` by the timeline foreign client
+    /// `client_id` follows, minting its amount in vouchers to the receiver
+    #[cfg(feature = "ibc")]
+    pub fn receive_packet(
+        &mut self,
+        client_id: &Hash,
+        packet: &Packet,
+        proof: &PacketProof,
+        chain_id: &Hash,
+    ) -> KalaResult<()> {
+        let client = self
+            .foreign
+            .client(client_id)
+            .ok_or_else(|| KalaError::state("Unknown foreign client"))?;
+        self.packets.receive(client_id, client, packet, proof, chain_id)
+    }
+
+    /// Packets sent to and received from other timelines
+    #[cfg(feature = "ibc")]
+    pub fn packets(&self) -> &PacketStore {
+        &self.packets
+    }
+
+    pub fn auctions(&self) -> &Auctions {
+        &self.auctions
+    }
+
+    /// Open a sealed-bid auction closing at `close_tick`
+    ///
+    /// Returns the auction's ID.
+    pub fn create_auction(
+        &mut self,
+        seller: &Hash,
+        item: Vec<u8>,
+        reserve_price: u64,
+        close_tick: BlockHeight,
+    ) -> KalaResult<u64> {
+        self.auctions
+            .create(seller, item, reserve_price, close_tick, self.current_tick)
+    }
+
+    /// Escrow `amount` from `bidder` as a bid on auction `auction_id`
+    pub fn place_bid(&mut self, bidder: &Hash, auction_id: u64, amount: u64) -> KalaResult<()> {
+        self.auctions
+            .check_bid(bidder, auction_id, amount, self.current_tick)?;
+        let account = self.get_account_mut(bidder);
+        if account.balance < amount {
+            return Err(KalaError::state("Insufficient balance"));
+        }
+        account.balance -= amount;
+        self.auctions.bid(bidder, auction_id, amount, self.current_tick)
+    }
+
+    /// Close the auctions ending at or before `tick`, paying each winning
+    /// bid to its seller and refunding the rest
+    pub fn settle_auctions(&mut self, tick: BlockHeight) -> Vec<AuctionSettlement> {
+        let settled = self.auctions.settle(tick);
+        for settlement in &settled {
+            if let AuctionStatus::Sold { price, .. } = settlement.status {
+                let seller = self.get_account_mut(&settlement.seller);
+                seller.balance = seller.balance.saturating_add(price);
+            }
+            for refund in &settlement.refunds {
+                let bidder = self.get_account_mut(&refund.bidder);
+                bidder.balance = bidder.balance.saturating_add(refund.amount);
+            }
+        }
+        settled
+    }
+
+    pub fn recoveries(&self) -> &Recoveries {
+        &self.recoveries
+    }
+
+    /// Name the guardians able to recover `account`, removing them if
+    /// `guardians` is empty
+    pub fn set_guardians(
+        &mut self,
+        account: &Hash,
+        guardians: Vec<Hash>,
+        threshold: u32,
+        delay_ticks: u64,
+    ) -> KalaResult<()> {
+        self.recoveries
+            .set_guardians(account, guardians, threshold, delay_ticks)
+    }
+
+    /// Record `guardian`'s request to move `account` to `new_owner`
+    ///
+    /// Returns the tick the recovery activates at once enough guardians
+    /// agree.
+    pub fn request_recovery(
+        &mut self,
+        guardian: &Hash,
+        account: &Hash,
+        new_owner: &Hash,
+    ) -> KalaResult<Option<BlockHeight>> {
+        self.recoveries
+            .request(guardian, account, new_owner, self.current_tick)
+    }
+
+    /// Cancel the pending recovery of `account`
+    pub fn veto_recovery(&mut self, account: &Hash) -> KalaResult<()> {
+        self.recoveries.veto(account)
+    }
+
+    /// Carry out the recoveries activating at or before `tick`
+    ///
+    /// Each account's balance and stake are credited to its new owner as
+    /// balance, and transfers still vesting to it are redirected.
+    pub fn execute_recoveries(&mut self, tick: BlockHeight) -> Vec<CompletedRecovery> {
+        let mut completed = Vec::new();
+        for (account, new_owner) in self.recoveries.take_due(tick) {
+            let recovered = self.get_account_mut(&account);
+            let amount = recovered.balance.saturating_add(recovered.staked_amount);
+            recovered.balance = 0;
+            recovered.staked_amount = 0;
+            recovered.delegation = None;
+            let owner = self.get_account_mut(&new_owner);
+            owner.balance = owner.balance.saturating_add(amount);
+
+            for schedule in self.vesting.values_mut().flatten() {
+                if schedule.receiver == account {
+                    schedule.receiver = new_owner;
+                }
+            }
+            completed.push(CompletedRecovery {
+                account,
+                new_owner,
+                amount,
+            });
+        }
+        completed
+    }
+
+    pub fn held_transactions(&self) -> &HeldTransactions {
+        &self.held
+    }
+
+    /// Hold a transaction from `sender` whose `nonce` is ahead of the
+    /// account's next one
+    ///
+    /// Returns the envelope hash of a held transaction it replaced.
+    pub fn hold_transaction(
+        &mut self,
+        sender: &Hash,
+        nonce: u64,
+        transaction: HeldTransaction,
+    ) -> KalaResult<Option<Hash>> {
+        let account_nonce = self.get_account_nonce(sender).unwrap_or(0);
+        self.held.hold(sender, nonce, account_nonce, transaction)
+    }
+
+    /// Take the held transaction from `sender` that follows its account's
+    /// nonce, if there is one
+    pub fn release_held_transaction(&mut self, sender: &Hash) -> Option<HeldTransaction> {
+        let account_nonce = self.get_account_nonce(sender).unwrap_or(0);
+        self.held.take_next(sender, account_nonce)
+    }
+
+    /// Drop transactions held for [`HOLD_TICKS`] as of `tick`
+    ///
+    /// Returns the envelope hashes of the dropped transactions.
+    pub fn prune_held_transactions(&mut self, tick: BlockHeight) -> Vec<Hash> {
+        self.held.prune(tick)
+    }
+
+    /// Fee charged per transaction, 0 until governance sets one
+    pub fn transaction_fee(&self) -> u64 {
+        self.governance
+            .parameter(GovernanceParameter::TransactionFee)
+            .unwrap_or(0)
+    }
+
+    /// Burn `fee` from `payer`'s balance
+    ///
+    /// Fails without changing any state if `payer` can't cover it.
+    pub fn charge_fee(&mut self, payer: &Hash, fee: u64) -> KalaResult<()> {
+        if fee == 0 {
+            return Ok(());
+        }
+        if self.get_balance(payer) < fee {
+            return Err(KalaError::state("Insufficient balance for fee"));
+        }
+        self.get_account_mut(payer).balance -= fee;
+        Ok(())
+    }
+
+    /// Return a `fee` taken by [`charge_fee`](Self::charge_fee) to `payer`
+    pub fn refund_fee(&mut self, payer: &Hash, fee: u64) {
+        if fee > 0 {
+            self.get_account_mut(payer).balance += fee;
+        }
+    }
+
+    /// Base units held by all accounts, staked, vesting, and escrowed
+    /// amounts included
+    pub fn total_supply(&self) -> u64 {
+        let held = self.accounts.values().fold(0u64, |total, account| {
+            total
+                .saturating_add(account.balance)
+                .saturating_add(account.staked_amount)
+        });
+        self.vesting
+            .values()
+            .flatten()
+            .fold(held, |total, schedule| total.saturating_add(schedule.amount))
+            .saturating_add(self.auctions.escrowed())
+    }
+
+    /// Base units staked across all accounts
+    pub fn total_staked(&self) -> u64 {
+        self.accounts
+            .values()
+            .fold(0u64, |total, account| total.saturating_add(account.staked_amount))
+    }
+
+    pub fn record_puzzle_solution(
+        &mut self,
+        solver: &Hash,
+        puzzle_id: &Hash,
+        proof: &[u8],
+    ) -> KalaResult<()> {
+        self.puzzles.insert(
+            *puzzle_id,
+            PuzzleState {
+                solver: *solver,
+                solution_proof: proof.to_vec(),
+                solved_at_tick: self.current_tick,
+                solved_at_iteration: self.current_iteration,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_account_count(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Merkle root over all accounts, ordered by address, and the rest of
+    /// the state the chain agrees on
+    ///
+    /// With the `ibc` feature, commitments of sent packets follow the
+    /// accounts in sequence order. Each other component, such as solved
+    /// puzzles and slashing records, then adds one leaf hashing its name
+    /// and bincode encoding, so a snapshot can't alter any of them and
+    /// keep its root.
+    pub fn state_root(&self) -> Hash {
+        MerkleTree::new(self.state_leaves()).root()
+    }
+
+    fn state_leaves(&self) -> Vec<Hash> {
+        let mut addresses: Vec<_> = self.accounts.keys().collect();
+        addresses.sort();
+
+        let leaves = addresses
+            .into_iter()
+            .map(|address| {
+                let account = &self.accounts[address];
+                CryptoUtils::hash_multiple(&[
+                    address,
+                    &account.balance.to_le_bytes(),
+                    &account.nonce.to_le_bytes(),
+                    &account.staked_amount.to_le_bytes(),
+                    account.delegation.as_ref().map_or(&[][..], |d| &d[..]),
+                ])
+            });
+        #[cfg(feature = "ibc")]
+        let leaves = leaves.chain(self.packets.commitments().map(|(_, commitment)| *commitment));
+        let mut leaves: Vec<Hash> = leaves.collect();
+
+        let puzzles: BTreeMap<_, _> = self.puzzles.iter().collect();
+        leaves.push(component_leaf(b"puzzles", &puzzles));
+        leaves.push(component_leaf(b"slashed", &self.slashed));
+        leaves.push(component_leaf(b"vesting", &self.vesting));
+        leaves.push(component_leaf(b"governance", &self.governance));
+        leaves.push(component_leaf(b"foreign", &self.foreign));
+        leaves.push(component_leaf(b"auctions", &self.auctions));
+        leaves.push(component_leaf(b"recoveries", &self.recoveries));
+        leaves.push(component_leaf(b"held", &self.held));
+        leaves.push(component_leaf(b"mint_authorities", &self.mint_authorities));
+        leaves
+    }
+
+    /// Get the tick number for a given iteration
+    pub fn iteration_to_tick(&self, iteration: u64) -> u64 {
+        iteration / self.tick_size
+    }
+
+    /// Get the starting iteration for a given tick
+    pub fn tick_to_iteration(&self, tick: u64) -> u64 {
+        tick * self.tick_size
+    }
+
+    /// Check if we're at a tick boundary
+    pub fn is_tick_boundary(&self, iteration: u64) -> bool {
+        iteration % self.tick_size == 0 && iteration > 0
+    }
+}
+
+/// State root leaf committing to the component `name` of the chain state
+fn component_leaf<T: Encode>(name: &[u8], component: &T) -> Hash {
+    let encoded = encode_bincode(component).expect("Chain state components encode with bincode");
+    CryptoUtils::hash_multiple(&[name, &encoded])
+}
+
+// Implement KalaSerialize for state types
+impl KalaSerialize for ChainState {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Compact for frequent state persistence
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, SerializationError> {
+        encode_versioned(self)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, SerializationError> {
+        decode_versioned(bytes)
+    }
+}
+
+impl KalaSerialize for PuzzleState {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Compact storage for puzzles
+    }
+}
+
+impl KalaSerialize for PendingTick {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Written once per tick
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, SerializationError> {
+        encode_versioned(self)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, SerializationError> {
+        decode_versioned(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_common::serialization::{STORAGE_FORMAT_VERSION, VERSIONED_MARKER};
+    use tempfile::tempdir;
+
+    fn certificate(tick_number: u64) -> TickCertificate {
+        TickCertificate {
+            tick_number,
+            tick_type: TickType::Empty,
+            vdf_iteration: tick_number * 1024,
+            vdf_form: ("1".into(), "0".into(), "1".into()),
+            hash_chain_value: [0u8; 32],
+            tick_hash: CryptoUtils::hash(&tick_number.to_le_bytes()),
+            transaction_count: 0,
+            transaction_merkle_root: [0u8; 32],
+            timestamp: 0,
+            previous_tick_hash: [0u8; 32],
+            usage: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tick_inclusion_proofs() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+
+        for tick in 0..10 {
+            db.store_tick(&certificate(tick)).await.unwrap();
+        }
+        // Re-storing a tick doesn't append it twice
+        db.store_tick(&certificate(9)).await.unwrap();
+        assert_eq!(db.tick_mmr_leaf_count().await.unwrap(), 10);
+
+        let root = db.tick_mmr_root().await.unwrap();
+        for tick in 0..10 {
+            let proof = db.prove_tick_inclusion(tick).await.unwrap();
+            assert!(proof.verify(&certificate(tick).tick_hash, &root));
+        }
+
+        assert!(db.prove_tick_inclusion(10).await.is_err());
+        assert!(db.store_tick(&certificate(12)).await.is_err());
+
+        // Range proofs are against the accumulator after their last tick
+        let range = db.prove_tick_range(2, 6).await.unwrap();
+        assert_eq!((range.start(), range.end()), (2, 6));
+        assert_eq!(range.segments.len(), 4);
+        assert!(range.segments.iter().all(|segment| segment.tick.proof.leaf_count == 7));
+        assert!(db.prove_tick_range(2, 10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_commit_tick_with_state() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+
+        // Accounts are keyed by hash, so the state must not be stored as JSON
+        let mut state = ChainState::new();
+        state.get_account_mut(&[7; 32]).balance = 5;
+        for tick in 0..3 {
+            state.current_tick = tick + 1;
+            state.last_tick_hash = certificate(tick).tick_hash;
+            db.commit_tick(&certificate(tick), &state).await.unwrap();
+        }
+
+        let loaded = db.load_chain_state().await.unwrap();
+        assert_eq!(loaded.current_tick, 3);
+        assert_eq!(loaded.last_tick_hash, certificate(2).tick_hash);
+        assert_eq!(loaded.get_account(&[7; 32]).map(|account| account.balance), Some(5));
+        assert_eq!(db.get_recent_ticks(0).await.unwrap()[0].tick_number, 2);
+        assert_eq!(db.tick_mmr_leaf_count().await.unwrap(), 3);
+
+        // A tick the accumulator rejects leaves the state untouched
+        state.current_tick = 6;
+        assert!(db.commit_tick(&certificate(5), &state).await.is_err());
+        assert_eq!(db.load_chain_state().await.unwrap().current_tick, 3);
+    }
+
+    #[tokio::test]
+    async fn test_staged_tick_retry() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+
+        // A staged state holding accounts must round-trip, which JSON can't
+        let mut state = ChainState::new();
+        state.current_tick = 1;
+        state.last_tick_hash = certificate(0).tick_hash;
+        state.mint(&[7; 32], 5).unwrap();
+        state.update_nonce(&[7; 32], 2);
+        db.stage_tick(&certificate(0), &state).await.unwrap();
+        let pending = db.pending_tick(0).await.unwrap().unwrap();
+        assert_eq!(pending.certificate.tick_hash, certificate(0).tick_hash);
+        assert_eq!(pending.state.current_tick, 1);
+        assert_eq!(pending.state.get_balance(&[7; 32]), 5);
+        assert_eq!(pending.state.state_root(), state.state_root());
+
+        // Committing clears the staged tick, and committing again changes nothing
+        db.commit_tick(&pending.certificate, &pending.state).await.unwrap();
+        assert!(db.pending_tick(0).await.unwrap().is_none());
+        db.commit_tick(&pending.certificate, &pending.state).await.unwrap();
+        assert_eq!(db.tick_mmr_leaf_count().await.unwrap(), 1);
+        assert_eq!(db.verify_state().await.unwrap(), state.state_root());
+
+        db.stage_tick(&certificate(1), &state).await.unwrap();
+        db.discard_pending_tick(1).await.unwrap();
+        assert!(db.pending_tick(1).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_state_storage_formats() {
+        let mut state = ChainState::new();
+        state.mint(&[7; 32], 5).unwrap();
+        let decode = <ChainState as KalaSerialize>::decode;
+        let encoded = KalaSerialize::encode(&state).unwrap();
+        assert_eq!(&encoded[..2], &[VERSIONED_MARKER, STORAGE_FORMAT_VERSION]);
+        assert_eq!(decode(&encoded).unwrap().state_root(), state.state_root());
+
+        // Unversioned records of older nodes still load
+        let mut legacy = ChainState::new();
+        legacy.current_tick = 123;
+        let json = serde_json::to_vec(&legacy).unwrap();
+        assert_eq!(decode(&json).unwrap().current_tick, 123);
+        let bare = encode_bincode(&legacy).unwrap();
+        assert_eq!(decode(&bare).unwrap().current_tick, 123);
+
+        let mut newer = encoded;
+        newer[1] = STORAGE_FORMAT_VERSION + 1;
+        assert!(decode(&newer).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_storage_migration() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        let raw = |key: Vec<u8>| db.database().get_raw(&key).unwrap().unwrap();
+
+        // Records as an older node stored them, forms as hex strings in JSON
+        let mut tick = certificate(0);
+        tick.vdf_form = ("0x1f".into(), "-0xabc".into(), "0x2d".into());
+        let mut state = ChainState::new();
+        state.current_tick = 1;
+        state.last_tick_hash = tick.tick_hash;
+        let vdf_tick = VDFTickCertificate {
+            tick_number: 0,
+            start_iteration: 0,
+            end_iteration: 1024,
+            form_a: "0x1f".into(),
+            form_b: "-0xabc".into(),
+            form_c: "0x2d".into(),
+            hash_chain: [3u8; 32],
+            data_merkle_root: [0u8; 32],
+            wesolowski_proof: None,
+        };
+        let checkpoint = VDFCheckpoint::genesis(DEFAULT_DISCRIMINANT, 1024);
+        let checkpoints = DatabasePersistence::checkpoints(db.database());
+        let legacy = [
+            (db.ticks.key(&0), serde_json::to_vec(&tick).unwrap()),
+            (db.states.key(&HEAD_STATE), serde_json::to_vec(&state).unwrap()),
+            (db.vdf_ticks.key(&0), serde_json::to_vec(&vdf_tick).unwrap()),
+            (checkpoints.key(&LATEST_CHECKPOINT), serde_json::to_vec(&checkpoint).unwrap()),
+        ];
+        for (key, value) in &legacy {
+            db.database().put_raw(key, value).unwrap();
+        }
+
+        assert_eq!(db.migrate_storage().await.unwrap(), legacy.len());
+        for (key, value) in &legacy {
+            let migrated = raw(key.clone());
+            assert!(is_versioned(&migrated));
+            assert!(migrated.len() < value.len());
+        }
+        assert_eq!(db.get_tick(0).await.unwrap().unwrap().compute_hash(), tick.compute_hash());
+        assert_eq!(db.load_chain_state().await.unwrap().state_root(), state.state_root());
+        let migrated = db.get_vdf_tick_certificate(0).await.unwrap().unwrap();
+        assert_eq!(migrated.form_b.to_hex(), "-0xabc");
+        let migrated = DatabasePersistence::load_checkpoint(db.database()).unwrap().unwrap();
+        assert_eq!(migrated.form_a, checkpoint.form_a);
+
+        // Runs once
+        db.database().put_raw(&db.ticks.key(&1), &serde_json::to_vec(&certificate(1)).unwrap()).unwrap();
+        assert_eq!(db.migrate_storage().await.unwrap(), 0);
+        assert!(!is_versioned(&raw(db.ticks.key(&1))));
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_restore() {
+        let dir = tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+        let db = StateDB::open(&path("state")).unwrap();
+
+        let mut state = ChainState::new();
+        state.total_transactions = 5;
+        for tick in 0..3 {
+            state.current_tick = tick + 1;
+            state.last_tick_hash = certificate(tick).tick_hash;
+            db.commit_tick(&certificate(tick), &state).await.unwrap();
+        }
+        db.create_backup(&path("backup")).await.unwrap();
+
+        let restored = StateDB::restore_backup(&path("backup"), &path("restored")).await.unwrap();
+        assert_eq!(restored.verify_state().await.unwrap(), state.state_root());
+        assert_eq!(restored.load_chain_state().await.unwrap().total_transactions, 5);
+        assert!(StateDB::restore_backup(&path("backup"), &path("restored")).await.is_err());
+
+        // State that doesn't follow the latest tick is rejected
+        state.last_tick_hash = [7u8; 32];
+        restored.save_chain_state(&state).await.unwrap();
+        assert!(restored.verify_state().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_truncate_ticks() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        for tick in 0..6 {
+            db.store_tick(&certificate(tick)).await.unwrap();
+        }
+
+        let removed = db.truncate_ticks(4).await.unwrap();
+        let removed: Vec<u64> = removed.iter().map(|tick| tick.tick_number).collect();
+        assert_eq!(removed, vec![5, 4]);
+        assert!(db.get_tick(4).await.unwrap().is_none());
+        assert_eq!(db.get_recent_ticks(0).await.unwrap()[0].tick_number, 3);
+        assert_eq!(db.tick_mmr_leaf_count().await.unwrap(), 4);
+
+        // A competing tick 4 takes the place of the removed one
+        let mut competing = certificate(4);
+        competing.tick_hash = [9u8; 32];
+        db.store_tick(&competing).await.unwrap();
+
+        let reference_dir = tempdir().unwrap();
+        let reference = StateDB::open(reference_dir.path().to_str().unwrap()).unwrap();
+        for tick in 0..4 {
+            reference.store_tick(&certificate(tick)).await.unwrap();
+        }
+        reference.store_tick(&competing).await.unwrap();
+        assert_eq!(db.tick_mmr_root().await.unwrap(), reference.tick_mmr_root().await.unwrap());
+
+        // History before a checkpoint cannot be rewritten
+        db.set_history_start(3).await.unwrap();
+        assert!(db.truncate_ticks(3).await.is_err());
+        assert!(db.truncate_ticks(4).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_mmr_peaks() {
+        let full_dir = tempdir().unwrap();
+        let full = StateDB::open(full_dir.path().to_str().unwrap()).unwrap();
+        for tick in 0..12 {
+            full.store_tick(&certificate(tick)).await.unwrap();
+        }
+        let peaks = full.tick_mmr_peaks(7).await.unwrap();
+        assert!(full.tick_mmr_peaks(13).await.is_err());
+
+        // A node starting at tick 6 extends the accumulator without history
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        db.import_tick_mmr_peaks(7, &peaks).await.unwrap();
+        db.set_history_start(6).await.unwrap();
+        for tick in 6..12 {
+            db.store_tick(&certificate(tick)).await.unwrap();
+        }
+
+        let root = db.tick_mmr_root().await.unwrap();
+        assert_eq!(root, full.tick_mmr_root().await.unwrap());
+        let proof = db.prove_tick_inclusion(10).await.unwrap();
+        assert!(proof.verify(&certificate(10).tick_hash, &root));
+        assert!(db.tick_mmr_peaks(3).await.is_err());
+
+        // Backfilled ticks don't move the head or the accumulator
+        db.store_tick(&certificate(2)).await.unwrap();
+        assert_eq!(db.get_recent_ticks(0).await.unwrap()[0].tick_number, 11);
+        assert_eq!(db.tick_mmr_leaf_count().await.unwrap(), 12);
+        assert_eq!(db.history_start().await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_history_ring() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        let sample = |timestamp: u64| MetricsSample {
+            timestamp,
+            tick_number: timestamp,
+            ticks: 1,
+            iterations_per_second: 1000.0,
+            mean_tick_ms: 500,
+            max_tick_ms: 600,
+            mempool_depth: 0,
+        };
+
+        for timestamp in 0..METRICS_HISTORY_CAPACITY + 5 {
+            db.record_metrics_sample(&sample(timestamp)).await.unwrap();
+        }
+
+        // The first five samples were overwritten
+        let all = db.metrics_history(0, u64::MAX).await.unwrap();
+        assert_eq!(all.len() as u64, METRICS_HISTORY_CAPACITY);
+        assert_eq!(all[0].timestamp, 5);
+        assert_eq!(all.last().unwrap().timestamp, METRICS_HISTORY_CAPACITY + 4);
+
+        let range = db.metrics_history(100, 102).await.unwrap();
+        let timestamps: Vec<_> = range.iter().map(|s| s.timestamp).collect();
+        assert_eq!(timestamps, vec![100, 101, 102]);
+    }
+
+    #[tokio::test]
+    async fn test_tick_finality_storage() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(db.finalized_tick().await.unwrap(), None);
+
+        let key = witness::derive_bls_key(&[5u8; 32]).unwrap();
+        let witnesses = WitnessSet::new(vec![Witness {
+            key: [5u8; 32],
+            bls_key: witness::bls_public_key(&key),
+            weight: 1,
+        }])
+        .unwrap();
+
+        for tick in [3, 1] {
+            let vote = TickVote::sign(tick, certificate(tick).tick_hash, [5u8; 32], &key);
+            let finality = TickFinality::aggregate(&witnesses, &[vote]).unwrap();
+            db.store_tick_finality(&finality).await.unwrap();
+        }
+
+        // Finalizing an older tick late doesn't move the index back
+        assert_eq!(db.finalized_tick().await.unwrap(), Some(3));
+        let stored = db.get_tick_finality(1).await.unwrap().unwrap();
+        assert_eq!(stored.verify(&witnesses).unwrap(), 1);
+        assert!(db.get_tick_finality(2).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_observations_kept_until_pruned() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        let observation = |tick_number: u64, witness: u8| WitnessObservation {
+            witness: [witness; 32],
+            tick_number,
+            envelope_hash: [7u8; 32],
+            observed_iteration: tick_number * 1024,
+            vdf_proof: [0u8; 32],
+            signature: vec![witness; 64],
+        };
+
+        for tick in [5, 4, 6] {
+            assert!(db.add_observation(&observation(tick, 1)).await.unwrap());
+        }
+        assert!(!db.add_observation(&observation(5, 1)).await.unwrap());
+        db.add_observation(&observation(5, 2)).await.unwrap();
+        assert_eq!(db.get_observations(5).await.unwrap().len(), 2);
+
+        db.prune_observations(6).await.unwrap();
+        assert!(db.get_observations(4).await.unwrap().is_empty());
+        assert!(db.get_observations(5).await.unwrap().is_empty());
+        assert_eq!(db.get_observations(6).await.unwrap(), vec![observation(6, 1)]);
+    }
+
+    #[derive(Default)]
+    struct MemoryArchive(std::sync::Mutex<HashMap<String, Vec<u8>>>);
+
+    #[async_trait::async_trait]
+    impl ArchiveBackend for MemoryArchive {
+        async fn put(&self, key: &str, data: Vec<u8>) -> KalaResult<()> {
+            self.0.lock().unwrap().insert(key.to_string(), data);
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> KalaResult<Option<Vec<u8>>> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        fn uri(&self, key: &str) -> String {
+            format!("memory://{}", key)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_ticks() {
+        let dir = tempdir().unwrap();
+        let archive = Arc::new(MemoryArchive::default());
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        assert!(db.archive_ticks(5).await.is_err());
+        let db = db.with_archive(archive.clone());
+
+        let ticks: Vec<_> = (0..10)
+            .map(|tick| {
+                let mut certificate = certificate(tick);
+                certificate.tick_hash = certificate.compute_hash();
+                certificate
+            })
+            .collect();
+        for tick in &ticks {
+            db.store_tick(tick).await.unwrap();
+        }
+        let vdf_tick = VDFTickCertificate {
+            tick_number: 2,
+            start_iteration: 1024,
+            end_iteration: 2048,
+            form_a: "1".into(),
+            form_b: "0".into(),
+            form_c: "1".into(),
+            hash_chain: [0u8; 32],
+            data_merkle_root: [0u8; 32],
+            wesolowski_proof: None,
+        };
+        db.store_vdf_tick_certificate(&vdf_tick).await.unwrap();
+
+        // Nothing is moved before it is finalized
+        assert_eq!(db.archive_ticks(8).await.unwrap(), 0);
+        let key = witness::derive_bls_key(&[5u8; 32]).unwrap();
+        let witnesses = WitnessSet::new(vec![Witness {
+            key: [5u8; 32],
+            bls_key: witness::bls_public_key(&key),
+            weight: 1,
+        }])
+        .unwrap();
+        let vote = TickVote::sign(5, ticks[5].tick_hash, [5u8; 32], &key);
+        let finality = TickFinality::aggregate(&witnesses, &[vote]).unwrap();
+        db.store_tick_finality(&finality).await.unwrap();
+
+        assert_eq!(db.archive_ticks(8).await.unwrap(), 6);
+        assert_eq!(db.archived_before().await.unwrap(), 6);
+        assert!(db.ticks.get(&3).unwrap().is_none());
+        assert_eq!(db.get_tick(3).await.unwrap().unwrap().tick_hash, ticks[3].tick_hash);
+        assert_eq!(db.get_tick(7).await.unwrap().unwrap().tick_hash, ticks[7].tick_hash);
+        let fetched = db.get_vdf_tick_certificate(2).await.unwrap().unwrap();
+        assert_eq!(fetched.end_iteration, 2048);
+        assert!(db.get_vdf_tick_certificate(3).await.unwrap().is_none());
+
+        // A tick altered in the archive no longer matches the accumulator
+        let mut forged = ticks[4].clone();
+        forged.transaction_count = 7;
+        forged.tick_hash = forged.compute_hash();
+        let bytes = KalaSerialize::encode(&forged).unwrap();
+        archive.put(&archive::tick_key(4), bytes).await.unwrap();
+        assert!(db.get_tick(4).await.is_err());
+    }
+
+    #[test]
+    fn test_stake_and_unstake() {
+        let mut state = ChainState::new();
+        let (alice, bob, validator) = ([1u8; 32], [2u8; 32], [9u8; 32]);
+        state.mint(&alice, 100).unwrap();
+        state.mint(&bob, 100).unwrap();
+
+        state.stake(&alice, &validator, 60).unwrap();
+        state.stake(&bob, &validator, 30).unwrap();
+        assert!(state.stake(&alice, &[8u8; 32], 10).is_err());
+        assert_eq!(state.delegated_stake().get(&validator), Some(&90));
+
+        assert!(state.unstake(&alice, 61).is_err());
+        state.unstake(&alice, 60).unwrap();
+        assert_eq!(state.get_balance(&alice), 100);
+        assert!(state.get_account(&alice).unwrap().delegation.is_none());
+        assert_eq!(state.delegated_stake().get(&validator), Some(&30));
+
+        // Fully unstaked accounts may delegate elsewhere
+        state.stake(&alice, &[8u8; 32], 10).unwrap();
+        assert_eq!(state.delegated_stake().len(), 2);
+    }
+
+    #[test]
+    fn test_multi_transfer() {
+        let mut state = ChainState::new();
+        let (alice, bob, carol) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        state.mint(&alice, 100).unwrap();
+
+        // Nothing moves unless the sender covers every output
+        assert!(state.multi_transfer(&alice, &[(bob, 60), (carol, 50)]).is_err());
+        assert!(state.multi_transfer(&alice, &[(bob, u64::MAX), (carol, 1)]).is_err());
+        assert_eq!(state.get_balance(&alice), 100);
+        assert_eq!(state.get_balance(&bob), 0);
+
+        state.multi_transfer(&alice, &[(bob, 60), (carol, 30), (bob, 5)]).unwrap();
+        assert_eq!(state.get_balance(&alice), 5);
+        assert_eq!(state.get_balance(&bob), 65);
+        assert_eq!(state.get_balance(&carol), 30);
+    }
+
+    #[test]
+    fn test_vesting_released_at_unlock_tick() {
+        let mut state = ChainState::new();
+        let (alice, bob, carol) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        state.mint(&alice, 100).unwrap();
+        state.current_tick = 5;
+
+        assert!(state.lock_vesting(&alice, &bob, 10, 5).is_err());
+        assert!(state.lock_vesting(&alice, &bob, 101, 8).is_err());
+        state.lock_vesting(&alice, &bob, 40, 8).unwrap();
+        state.lock_vesting(&alice, &carol, 20, 10).unwrap();
+        state.lock_vesting(&alice, &bob, 15, 10).unwrap();
+        assert_eq!(state.get_balance(&alice), 25);
+        assert_eq!(state.total_supply(), 100);
+        assert_eq!(state.vesting_of(&bob).len(), 2);
+
+        assert!(state.release_vesting(7).is_empty());
+        let released = state.release_vesting(8);
+        assert_eq!(released, vec![VestingSchedule { sender: alice, receiver: bob, amount: 40 }]);
+        assert_eq!(state.get_balance(&bob), 40);
+
+        // Ticks skipped over release everything due by then
+        assert_eq!(state.release_vesting(12).len(), 2);
+        assert_eq!((state.get_balance(&bob), state.get_balance(&carol)), (55, 20));
+        assert!(state.vesting_of(&bob).is_empty());
+        assert_eq!(state.total_supply(), 100);
+    }
+
+    #[test]
+    fn test_governance_votes_weighted_by_stake() {
+        let mut state = ChainState::new();
+        let (alice, bob, carol) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        let parameter = GovernanceParameter::TimelockHardness;
+        for (account, stake) in [(alice, 70), (bob, 30)] {
+            state.mint(&account, 100).unwrap();
+            state.stake(&account, &[9u8; 32], stake).unwrap();
+        }
+
+        assert!(state.propose(&carol, parameter, 2_500, 2_000).is_err());
+        let id = state.propose(&alice, parameter, 2_500, 2_000).unwrap();
+        state.vote(&alice, id, true).unwrap();
+        state.vote(&bob, id, false).unwrap();
+        assert!(state.vote(&carol, id, true).is_err());
+
+        // Stake is counted when voting closes, not when the vote was cast
+        state.unstake(&alice, 5).unwrap();
+        let changed = state.finalize_governance(VOTING_PERIOD_TICKS - 1);
+        assert_eq!(changed, vec![(id, ProposalStatus::Passed)]);
+        assert_eq!(state.governance().proposal(id).unwrap().tally, Some((65, 30)));
+
+        state.finalize_governance(2_000);
+        assert_eq!(state.governance().parameter(parameter), Some(2_500));
+    }
+
+    #[test]
+    fn test_minter_approved_by_governance() {
+        let mut state = ChainState::new();
+        let (issuer, alice, bob) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        let denom = NATIVE_DENOM;
+        assert!(state.apply_mint(&issuer, &denom, 10).is_err());
+        state.set_issuer(&denom, &issuer);
+        state.mint(&alice, 100).unwrap();
+        state.stake(&alice, &[9u8; 32], 100).unwrap();
+
+        assert!(state.apply_mint(&bob, &denom, 10).is_err());
+        state.apply_mint(&issuer, &denom, 10).unwrap();
+
+        let id = state.propose_minter(&alice, &denom, &bob, true, 2_000).unwrap();
+        state.vote(&alice, id, true).unwrap();
+        state.finalize_governance(VOTING_PERIOD_TICKS - 1);
+        assert!(state.apply_mint(&bob, &denom, 10).is_err());
+        state.finalize_governance(2_000);
+        state.apply_mint(&bob, &denom, 10).unwrap();
+        assert_eq!(state.get_balance(&bob), 10);
+
+        // Approved minters can't move issuance; the issuer can
+        assert!(state.transfer_mint_authority(&bob, &denom, &bob).is_err());
+        state.transfer_mint_authority(&issuer, &denom, &alice).unwrap();
+        assert!(state.apply_mint(&issuer, &denom, 10).is_err());
+    }
+
+    #[test]
+    fn test_auction_escrows_and_settles_bids() {
+        let mut state = ChainState::new();
+        let (seller, alice, bob) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        state.mint(&alice, 100).unwrap();
+        state.mint(&bob, 100).unwrap();
+        let id = state.create_auction(&seller, b"lot".to_vec(), 10, 5).unwrap();
+
+        state.current_tick = 5;
+        assert!(state.place_bid(&alice, id, 150).is_err());
+        state.place_bid(&alice, id, 40).unwrap();
+        state.place_bid(&bob, id, 60).unwrap();
+        assert_eq!(state.get_balance(&alice), 60);
+        assert_eq!(state.total_supply(), 200);
+
+        let settled = state.settle_auctions(5);
+        assert_eq!(settled[0].status, AuctionStatus::Sold { winner: bob, price: 60 });
+        assert_eq!(state.get_balance(&seller), 60);
+        assert_eq!((state.get_balance(&alice), state.get_balance(&bob)), (100, 40));
+        assert_eq!(state.total_supply(), 200);
+    }
+
+    #[test]
+    fn test_recovery_moves_account() {
+        let mut state = ChainState::new();
+        let (owner, guardian, new_owner) = ([1u8; 32], [2u8; 32], [9u8; 32]);
+        state.mint(&owner, 100).unwrap();
+        state.stake(&owner, &[7u8; 32], 40).unwrap();
+        state.mint(&[3u8; 32], 10).unwrap();
+        state.lock_vesting(&[3u8; 32], &owner, 10, 5_000).unwrap();
+        state.set_guardians(&owner, vec![guardian], 1, MIN_RECOVERY_DELAY_TICKS).unwrap();
+
+        state.current_tick = 10;
+        let activation = state.request_recovery(&guardian, &owner, &new_owner).unwrap();
+        assert_eq!(activation, Some(10 + MIN_RECOVERY_DELAY_TICKS));
+        assert!(state.execute_recoveries(10 + MIN_RECOVERY_DELAY_TICKS - 1).is_empty());
+
+        let completed = state.execute_recoveries(10 + MIN_RECOVERY_DELAY_TICKS);
+        assert_eq!(completed[0].amount, 100);
+        assert_eq!(state.get_balance(&new_owner), 100);
+        assert_eq!(state.get_balance(&owner), 0);
+        assert!(state.delegated_stake().is_empty());
+        assert_eq!(state.vesting_of(&new_owner).len(), 1);
+        assert_eq!(state.total_supply(), 110);
+    }
+
+    #[test]
+    fn test_transaction_fee_charged_to_payer() {
+        let mut state = ChainState::new();
+        let (alice, sponsor) = ([1u8; 32], [2u8; 32]);
+        state.mint(&alice, 100).unwrap();
+        state.mint(&sponsor, 10).unwrap();
+        state.stake(&alice, &[9u8; 32], 50).unwrap();
+
+        // Free until governance sets a fee
+        assert_eq!(state.transaction_fee(), 0);
+        state.charge_fee(&[3u8; 32], state.transaction_fee()).unwrap();
+
+        let id = state
+            .propose(&alice, GovernanceParameter::TransactionFee, 4, 2_000)
+            .unwrap();
+        state.vote(&alice, id, true).unwrap();
+        state.finalize_governance(2_000);
+        assert_eq!(state.transaction_fee(), 4);
+
+        // Fees are burned
+        state.charge_fee(&sponsor, 4).unwrap();
+        state.charge_fee(&sponsor, 4).unwrap();
+        assert!(state.charge_fee(&sponsor, 4).is_err());
+        assert_eq!(state.get_balance(&sponsor), 2);
+        assert_eq!(state.total_supply(), 102);
+
+        // A refund restores the burned fee
+        state.refund_fee(&sponsor, 4);
+        assert_eq!(state.get_balance(&sponsor), 6);
+        assert_eq!(state.total_supply(), 106);
+
+        // A failed charge doesn't open an account for the payer
+        assert!(state.charge_fee(&[4u8; 32], 4).is_err());
+        assert!(state.get_account(&[4u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_slash_burns_delegated_stake() {
+        let mut state = ChainState::new();
+        let (alice, bob, validator) = ([1u8; 32], [2u8; 32], [9u8; 32]);
+        state.mint(&alice, 100).unwrap();
+        state.mint(&bob, 100).unwrap();
+        state.stake(&alice, &validator, 60).unwrap();
+        state.stake(&bob, &[8u8; 32], 30).unwrap();
+        assert_eq!(state.delegators(&validator), vec![alice]);
+
+        assert_eq!(state.slash(&validator, 5).unwrap(), 60);
+        assert_eq!(state.get_balance(&alice), 40);
+        assert!(state.get_account(&alice).unwrap().delegation.is_none());
+        assert_eq!(state.total_staked(), 30);
+        assert_eq!(state.last_slashed(&validator), Some(5));
+
+        // The same offence, or an older one, is only punished once
+        assert!(state.slash(&validator, 5).is_err());
+        assert!(state.slash(&validator, 4).is_err());
+        assert_eq!(state.slash(&validator, 6).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_state_root_commits_every_component() {
+        let mut state = ChainState::new();
+        state.mint(&[1u8; 32], 100).unwrap();
+        state.stake(&[1u8; 32], &[8u8; 32], 50).unwrap();
+        let mut roots = vec![state.state_root()];
+        let mut changed = |state: &ChainState| {
+            let root = state.state_root();
+            assert!(!roots.contains(&root), "state change left the root unchanged");
+            roots.push(root);
+        };
+
+        // Changes that leave every account as it was
+        state.slash(&[9u8; 32], 5).unwrap();
+        changed(&state);
+        state.record_puzzle_solution(&[1u8; 32], &[4u8; 32], b"proof").unwrap();
+        changed(&state);
+        // Refunded, so only the schedule differs
+        state.lock_vesting(&[1u8; 32], &[2u8; 32], 10, 5_000).unwrap();
+        state.mint(&[1u8; 32], 10).unwrap();
+        changed(&state);
+        let parameter = GovernanceParameter::TimelockHardness;
+        let id = state.propose(&[1u8; 32], parameter, 2_500, 2_000).unwrap();
+        changed(&state);
+        state.vote(&[1u8; 32], id, true).unwrap();
+        changed(&state);
+        let anchor = SignedEpochSummary::new(EpochSummary {
+            epoch_number: 0,
+            start_tick: 0,
+            end_tick: 3,
+            end_tick_hash: [3u8; 32],
+            mmr_root: [1u8; 32],
+            state_root: [2u8; 32],
+            witnesses: vec![[5u8; 32]],
+            stats: EpochStats::default(),
+            previous_epoch_hash: [0u8; 32],
+        });
+        state.submit_foreign_epoch(&[0u8; 32], &anchor).unwrap();
+        changed(&state);
+        state.create_auction(&[1u8; 32], b"lot".to_vec(), 10, 5).unwrap();
+        changed(&state);
+        state.set_guardians(&[1u8; 32], vec![[2u8; 32]], 1, MIN_RECOVERY_DELAY_TICKS).unwrap();
+        changed(&state);
+        state.set_issuer(&NATIVE_DENOM, &[1u8; 32]);
+        changed(&state);
+        state.set_open_mint(true);
+        changed(&state);
+        let held = HeldTransaction {
+            envelope_hash: [6u8; 32],
+            transaction: b"tx".to_vec(),
+            tip: 0,
+            held_at: 0,
+        };
+        state.hold_transaction(&[1u8; 32], 3, held).unwrap();
+        changed(&state);
+        state.prune_held_transactions(HOLD_TICKS);
+        assert_eq!(state.state_root(), roots[roots.len() - 2]);
+    }
+
+    #[tokio::test]
+    async fn test_epoch_certificates() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        assert!(db.latest_epoch_certificate().await.unwrap().is_none());
+
+        let candidates: Vec<_> = (1..=2u8)
+            .map(|seed| Witness {
+                key: [seed; 32],
+                bls_key: witness::bls_public_key(&witness::derive_bls_key(&[seed; 32]).unwrap()),
+                weight: 1,
+            })
+            .collect();
+        let genesis = WitnessSet::new(candidates.clone()).unwrap();
+
+        let mut state = ChainState::new();
+        state.mint(&[7u8; 32], 100).unwrap();
+        state.stake(&[7u8; 32], &[2u8; 32], 40).unwrap();
+        let next = WitnessSet::from_stake(&candidates, &state.delegated_stake(), 1)
+            .unwrap()
+            .unwrap();
+
+        for tick in 0..4 {
+            db.store_tick(&certificate(tick)).await.unwrap();
+        }
+        let summary = db.build_epoch_summary(0, 4, &state, genesis.keys()).await.unwrap();
+        let handover = EpochCertificate::new(&summary, genesis, next);
+        assert!(handover.is_rotation());
+        assert_eq!(handover.effective_tick, 4);
+        assert_eq!(handover.witnesses.keys(), vec![[2u8; 32]]);
+        assert_eq!(handover.byzantine_threshold, 13);
+
+        db.store_epoch_certificate(&handover).await.unwrap();
+        assert_eq!(db.latest_epoch_certificate().await.unwrap(), Some(handover.clone()));
+        assert_eq!(db.get_epoch_certificate(0).await.unwrap(), Some(handover));
+        assert!(db.get_epoch_certificate(1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_epoch_summaries() {
+        let dir = tempdir().unwrap();
+        let db = StateDB::open(dir.path().to_str().unwrap()).unwrap();
+        let mut state = ChainState::new();
+        state.mint(&[1u8; 32], 100).unwrap();
+        let witness = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let witnesses = vec![witness.verifying_key().to_bytes()];
+
+        for tick in 0..3 {
+            db.store_tick(&certificate(tick)).await.unwrap();
+        }
+        // Epoch 0 can only be sealed once its last tick is committed
+        assert!(db.build_epoch_summary(0, 4, &state, witnesses.clone()).await.is_err());
+        db.store_tick(&certificate(3)).await.unwrap();
+
+        let summary = db.build_epoch_summary(0, 4, &state, witnesses.clone()).await.unwrap();
+        assert_eq!(summary.stats.tick_count, 4);
+        assert_eq!(summary.stats.empty_ticks, 4);
+        assert_eq!(summary.end_tick_hash, certificate(3).tick_hash);
+        assert_eq!(summary.state_root, state.state_root());
+        assert_ne!(summary.state_root, ChainState::new().state_root());
+
+        let proof = db.prove_tick_inclusion(2).await.unwrap();
+        assert!(summary.verify_tick(&certificate(2).tick_hash, &proof));
+
+        let mut genesis = SignedEpochSummary::new(summary);
+        genesis.sign(&witness).unwrap();
+        db.store_epoch_summary(&genesis).await.unwrap();
+        assert!(db.get_epoch_summary(1).await.unwrap().is_none());
+
+        for tick in 4..8 {
+            db.store_tick(&certificate(tick)).await.unwrap();
+        }
+        let mut next = SignedEpochSummary::new(
+            db.build_epoch_summary(1, 4, &state, witnesses).await.unwrap(),
+        );
+        next.sign(&witness).unwrap();
+        db.store_epoch_summary(&next).await.unwrap();
+
+        let latest = db.latest_epoch_summary().await.unwrap().unwrap();
+        assert_eq!(latest, next);
+        let head = verify_epoch_chain(&genesis.summary, &[latest]).unwrap();
+        assert_eq!(head.start_tick, 4);
+    }
+}
@@ -0,0 +1,39 @@
+//! Persisted node metrics history
+//!
+//! The node periodically folds its recent ticks into a [`MetricsSample`]
+//! and stores it in a fixed-size ring in the state database, so a short
+//! performance history survives restarts without an external metrics
+//! stack. The oldest sample is overwritten once the ring is full.
+
+use bincode::{Decode, Encode};
+use kala_common::serialization::{EncodingType, KalaSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Number of samples kept; a day of history at one sample every 10 seconds
+pub const METRICS_HISTORY_CAPACITY: u64 = 8640;
+
+/// Node performance over the ticks since the previous sample
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MetricsSample {
+    /// Unix time the sample was taken, in seconds
+    pub timestamp: u64,
+    /// Latest tick produced when the sample was taken
+    pub tick_number: u64,
+    /// Number of ticks the sample covers
+    pub ticks: u64,
+    /// VDF iterations per second over those ticks
+    pub iterations_per_second: f64,
+    /// Mean tick duration in milliseconds
+    pub mean_tick_ms: u64,
+    /// Longest tick duration in milliseconds
+    pub max_tick_ms: u64,
+    /// Transactions waiting in the pool
+    pub mempool_depth: u64,
+}
+
+impl KalaSerialize for MetricsSample {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Compact, one is written every few seconds
+    }
+}
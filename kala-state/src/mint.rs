@@ -0,0 +1,169 @@
+//! Mint authority
+//!
+//! Every denomination may have an issuer, the account allowed to mint it
+//! and to hand that right to another account. Issuers are named at
+//! genesis; after that, only the issuer can move issuance, with a
+//! transfer transaction. Governance can also approve further minters of a
+//! denomination, or revoke them, with a
+//! [`MinterApproval`](crate::GovernanceParameter::MinterApproval)
+//! proposal. Approved minters can mint but not transfer issuance.
+//!
+//! Accounts only hold balances of the native denomination, so it is the
+//! only one that can be minted for now; authority over other
+//! denominations is recorded but mints nothing. Without an issuer or
+//! approved minter the native denomination can't be minted at all, unless
+//! the chain was started with open minting, which lets every account mint
+//! it on development chains.
+
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::types::Hash;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// ID of the native denomination, which fees and stake are paid in
+pub const NATIVE_DENOM: Hash = [0u8; 32];
+
+/// Issuers and approved minters by denomination
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MintAuthorities {
+    issuers: BTreeMap<Hash, Hash>,
+    /// Minters approved by governance
+    minters: BTreeMap<Hash, BTreeSet<Hash>>,
+    /// Whether every account may mint the native denomination
+    #[serde(default)]
+    open: bool,
+}
+
+impl MintAuthorities {
+    pub fn issuer(&self, denom: &Hash) -> Option<&Hash> {
+        self.issuers.get(denom)
+    }
+
+    /// Whether governance approved `account` to mint `denom`
+    pub fn is_approved(&self, denom: &Hash, account: &Hash) -> bool {
+        self.minters
+            .get(denom)
+            .is_some_and(|minters| minters.contains(account))
+    }
+
+    /// Make `issuer` the issuer of `denom`, as at genesis
+    pub fn set_issuer(&mut self, denom: &Hash, issuer: &Hash) {
+        self.issuers.insert(*denom, *issuer);
+    }
+
+    /// Whether every account may mint the native denomination
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Let every account mint the native denomination, or stop them
+    ///
+    /// NOT FOR PRODUCTION; set at genesis of development chains.
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    /// Approve `account` to mint `denom`, or revoke its approval
+    pub fn set_approved(&mut self, denom: &Hash, account: &Hash, approved: bool) {
+        if approved {
+            self.minters.entry(*denom).or_default().insert(*account);
+        } else if let Some(minters) = self.minters.get_mut(denom) {
+            minters.remove(account);
+            if minters.is_empty() {
+                self.minters.remove(denom);
+            }
+        }
+    }
+
+    /// Check `minter` may mint `denom`
+    pub fn check_mint(&self, minter: &Hash, denom: &Hash) -> KalaResult<()> {
+        // Balances are only kept in the native denomination
+        if *denom != NATIVE_DENOM {
+            return Err(KalaError::state("Only the native denom can be minted"));
+        }
+        if self.open || self.issuers.get(denom) == Some(minter) || self.is_approved(denom, minter) {
+            return Ok(());
+        }
+        Err(KalaError::state("Sender may not mint this denom"))
+    }
+
+    /// Check `sender` may hand the issuance of `denom` to another account
+    pub fn check_transfer(&self, sender: &Hash, denom: &Hash) -> KalaResult<()> {
+        match self.issuers.get(denom) {
+            Some(issuer) if issuer == sender => Ok(()),
+            Some(_) => Err(KalaError::state(
+                "Only the issuer may transfer mint authority",
+            )),
+            None => Err(KalaError::state("Denom has no issuer")),
+        }
+    }
+
+    /// Hand the issuance of `denom` from `sender` to `new_authority`
+    pub fn transfer(
+        &mut self,
+        sender: &Hash,
+        denom: &Hash,
+        new_authority: &Hash,
+    ) -> KalaResult<()> {
+        self.check_transfer(sender, denom)?;
+        self.issuers.insert(*denom, *new_authority);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_denom_closed_without_authority() {
+        let mut authorities = MintAuthorities::default();
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        assert!(authorities.check_mint(&alice, &NATIVE_DENOM).is_err());
+
+        authorities.set_issuer(&NATIVE_DENOM, &alice);
+        assert!(authorities.check_mint(&alice, &NATIVE_DENOM).is_ok());
+        assert!(authorities.check_mint(&bob, &NATIVE_DENOM).is_err());
+
+        // Open minting lets anyone mint the native denomination, and only it
+        authorities.set_open(true);
+        assert!(authorities.check_mint(&bob, &NATIVE_DENOM).is_ok());
+        assert!(authorities.check_mint(&bob, &[7u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_issuance_transfer() {
+        let mut authorities = MintAuthorities::default();
+        let (alice, bob, denom) = ([1u8; 32], [2u8; 32], [7u8; 32]);
+        assert!(authorities.transfer(&alice, &denom, &bob).is_err());
+
+        authorities.set_issuer(&denom, &alice);
+        assert!(authorities.transfer(&bob, &denom, &bob).is_err());
+        authorities.transfer(&alice, &denom, &bob).unwrap();
+        assert_eq!(authorities.issuer(&denom), Some(&bob));
+        // Issuers of other denominations can't mint into native balances
+        assert!(authorities.check_mint(&bob, &denom).is_err());
+
+        authorities.set_issuer(&NATIVE_DENOM, &alice);
+        authorities.transfer(&alice, &NATIVE_DENOM, &bob).unwrap();
+        assert!(authorities.check_mint(&alice, &NATIVE_DENOM).is_err());
+        assert!(authorities.check_mint(&bob, &NATIVE_DENOM).is_ok());
+    }
+
+    #[test]
+    fn test_approved_minters() {
+        let mut authorities = MintAuthorities::default();
+        let (alice, bob, carol) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        authorities.set_issuer(&NATIVE_DENOM, &alice);
+
+        authorities.set_approved(&NATIVE_DENOM, &bob, true);
+        assert!(authorities.check_mint(&bob, &NATIVE_DENOM).is_ok());
+        assert!(authorities.check_mint(&carol, &NATIVE_DENOM).is_err());
+        // Approval allows minting, not moving issuance
+        assert!(authorities.check_transfer(&bob, &NATIVE_DENOM).is_err());
+
+        authorities.set_approved(&NATIVE_DENOM, &bob, false);
+        assert!(authorities.check_mint(&bob, &NATIVE_DENOM).is_err());
+    }
+}
@@ -0,0 +1,419 @@
+//! Witness observations and canonical envelope timestamps
+//!
+//! Every witness records the VDF iteration at which it first saw each
+//! encrypted envelope. Those observations are combined into a
+//! [`CanonicalTimestamp`]: the weighted median of the iterations reported
+//! by distinct witnesses, once they hold more than 2/3 of the witness
+//! weight. Faulty witnesses hold less than 1/3 of the weight, so they are a
+//! minority of any such quorum and cannot pull the median outside the range
+//! reported by honest witnesses.
+//!
+//! Transactions are ordered by their canonical iteration before they are
+//! decrypted, so no single node decides the order.
+//!
+//! Observations are signed by their witness and commit to the VDF form the
+//! witness had computed a tick before, so a witness can't report iterations
+//! on a timeline it isn't following. Nodes keep them in the state database
+//! until their tick is finalized.
+
+use bincode::{Decode, Encode};
+use kala_common::crypto::signer::{SignatureScheme, Signer};
+use kala_common::crypto::CryptoUtils;
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{EncodingType, KalaSerialize};
+use kala_vdf::{form_bytes, PackedForm};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::witness::WitnessSet;
+
+/// A witness's record of the VDF iteration at which it first saw an envelope
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct WitnessObservation {
+    pub witness: [u8; 32],
+    pub tick_number: u64,
+    pub envelope_hash: [u8; 32],
+    pub observed_iteration: u64,
+    /// Hash of the witness's VDF form at [`WitnessObservation::proof_iteration`]
+    pub vdf_proof: [u8; 32],
+    /// Ed25519 signature over [`WitnessObservation::signing_message`]
+    pub signature: Vec<u8>,
+}
+
+impl WitnessObservation {
+    /// Observe an envelope with the witness key behind `signer`
+    ///
+    /// `vdf_form` is the form at [`WitnessObservation::proof_iteration`]
+    /// of `observed_iteration`.
+    pub async fn sign(
+        tick_number: u64,
+        envelope_hash: [u8; 32],
+        observed_iteration: u64,
+        vdf_form: &PackedForm,
+        signer: &dyn Signer,
+    ) -> KalaResult<Self> {
+        let witness = signer
+            .public_key(SignatureScheme::Ed25519)
+            .try_into()
+            .map_err(|_| KalaError::crypto("Witness key must be 32 bytes"))?;
+        let mut observation = Self {
+            witness,
+            tick_number,
+            envelope_hash,
+            observed_iteration,
+            vdf_proof: Self::form_hash(vdf_form),
+            signature: Vec::new(),
+        };
+        observation.signature = signer
+            .sign(SignatureScheme::Ed25519, &observation.signing_message())
+            .await?;
+        Ok(observation)
+    }
+
+    /// Iteration whose VDF form an observation at `observed_iteration` commits to
+    ///
+    /// The start of the tick before the one in progress, so nodes up to a
+    /// tick behind the witness can already check it.
+    pub fn proof_iteration(observed_iteration: u64, iterations_per_tick: u64) -> u64 {
+        (observed_iteration / iterations_per_tick).saturating_sub(1) * iterations_per_tick
+    }
+
+    /// Commitment to a VDF form, in its compact encoding
+    pub fn form_hash(form: &PackedForm) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-observation-form");
+        hasher.update(form_bytes::encode_form(&form.0, &form.1, &form.2));
+        hasher.finalize().into()
+    }
+
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.witness);
+        hasher.update(self.tick_number.to_le_bytes());
+        hasher.update(self.envelope_hash);
+        hasher.update(self.observed_iteration.to_le_bytes());
+        hasher.finalize().into()
+    }
+
+    pub fn signing_message(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-observation");
+        hasher.update(self.compute_hash());
+        hasher.update(self.vdf_proof);
+        hasher.finalize().into()
+    }
+
+    /// Check the observation is from a member of `witnesses`, its signature
+    /// is valid, and it commits to `vdf_form`
+    ///
+    /// `vdf_form` is this node's form at [`WitnessObservation::proof_iteration`].
+    pub fn verify(
+        &self,
+        witnesses: &WitnessSet,
+        vdf_form: &PackedForm,
+    ) -> KalaResult<()> {
+        if witnesses.get(&self.witness).is_none() {
+            return Err(KalaError::validation(format!(
+                "{} is not a witness",
+                CryptoUtils::hash_to_hex(&self.witness)
+            )));
+        }
+        let message = self.signing_message();
+        if !SignatureScheme::Ed25519.verify(&self.witness, &message, &self.signature) {
+            return Err(KalaError::crypto(format!(
+                "Observation from {} does not verify",
+                CryptoUtils::hash_to_hex(&self.witness)
+            )));
+        }
+        if self.vdf_proof != Self::form_hash(vdf_form) {
+            return Err(KalaError::validation(format!(
+                "Observation from {} commits to another VDF timeline",
+                CryptoUtils::hash_to_hex(&self.witness)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The observations stored for a tick, until it is finalized
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TickObservations {
+    pub observations: Vec<WitnessObservation>,
+}
+
+impl KalaSerialize for TickObservations {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Rewritten with every observation
+    }
+}
+
+/// The iteration witnesses agree an envelope arrived at
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct CanonicalTimestamp {
+    pub envelope_hash: [u8; 32],
+    pub tick_number: u64,
+    /// Weighted median of the observed iterations
+    pub canonical_iteration: u64,
+    /// Witness weight behind the observations
+    pub observed_weight: u64,
+    pub observations: usize,
+}
+
+impl CanonicalTimestamp {
+    /// Agree on the arrival iteration of an envelope
+    ///
+    /// Only observations of `envelope_hash` for `tick_number` by members of
+    /// `witnesses` count, and only the first one from each witness. Returns
+    /// `None` until the observing witnesses hold a quorum of the weight.
+    pub fn agree(
+        tick_number: u64,
+        envelope_hash: [u8; 32],
+        observations: &[WitnessObservation],
+        witnesses: &WitnessSet,
+    ) -> Option<Self> {
+        let mut by_witness: BTreeMap<[u8; 32], (u64, u64)> = BTreeMap::new();
+        for observation in observations {
+            if observation.tick_number != tick_number || observation.envelope_hash != envelope_hash {
+                continue;
+            }
+            if let Some(witness) = witnesses.get(&observation.witness) {
+                by_witness
+                    .entry(observation.witness)
+                    .or_insert((observation.observed_iteration, witness.weight));
+            }
+        }
+
+        let observed_weight: u64 = by_witness.values().map(|(_, weight)| weight).sum();
+        if !witnesses.is_quorum(observed_weight) {
+            return None;
+        }
+
+        // Lower weighted median: the first iteration covering half the weight
+        let mut iterations: Vec<(u64, u64)> = by_witness.into_values().collect();
+        iterations.sort_unstable();
+        let mut cumulative = 0u128;
+        let canonical_iteration = iterations.iter().find_map(|&(iteration, weight)| {
+            cumulative += weight as u128;
+            (cumulative * 2 >= observed_weight as u128).then_some(iteration)
+        })?;
+
+        Some(Self {
+            envelope_hash,
+            tick_number,
+            canonical_iteration,
+            observed_weight,
+            observations: iterations.len(),
+        })
+    }
+}
+
+/// Observations collected per envelope until their tick is processed
+#[derive(Default, Debug)]
+pub struct ObservationPool {
+    envelopes: BTreeMap<(u64, [u8; 32]), Vec<WitnessObservation>>,
+}
+
+impl ObservationPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an observation from a member of `witnesses`
+    ///
+    /// Returns `Ok(false)` if the same observation was already recorded and
+    /// an error if the witness is unknown or reported a different iteration
+    /// for the envelope before.
+    pub fn insert(&mut self, observation: WitnessObservation, witnesses: &WitnessSet) -> KalaResult<bool> {
+        if witnesses.get(&observation.witness).is_none() {
+            return Err(KalaError::validation(format!(
+                "Observation from unknown witness {}",
+                CryptoUtils::hash_to_hex(&observation.witness)
+            )));
+        }
+
+        let observations = self
+            .envelopes
+            .entry((observation.tick_number, observation.envelope_hash))
+            .or_default();
+        match observations.iter().find(|seen| seen.witness == observation.witness) {
+            Some(seen) if seen.observed_iteration == observation.observed_iteration => Ok(false),
+            Some(seen) => Err(KalaError::validation(format!(
+                "Witness {} observed envelope {} at iteration {} and {}",
+                CryptoUtils::hash_to_hex(&observation.witness),
+                CryptoUtils::hash_to_hex(&observation.envelope_hash),
+                seen.observed_iteration,
+                observation.observed_iteration
+            ))),
+            None => {
+                observations.push(observation);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Canonical timestamp of an envelope, once a quorum has observed it
+    pub fn canonical_timestamp(
+        &self,
+        tick_number: u64,
+        envelope_hash: &[u8; 32],
+        witnesses: &WitnessSet,
+    ) -> Option<CanonicalTimestamp> {
+        let observations = self.envelopes.get(&(tick_number, *envelope_hash))?;
+        CanonicalTimestamp::agree(tick_number, *envelope_hash, observations, witnesses)
+    }
+
+    /// Drop observations for ticks before `tick_number`
+    pub fn prune(&mut self, tick_number: u64) {
+        self.envelopes = self.envelopes.split_off(&(tick_number, [0u8; 32]));
+    }
+
+    /// Number of envelopes with observations
+    pub fn len(&self) -> usize {
+        self.envelopes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.envelopes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::{bls_public_key, derive_bls_key, Witness};
+    use kala_common::crypto::signer::InMemorySigner;
+
+    const ENVELOPE: [u8; 32] = [7u8; 32];
+
+    fn witness_set(weights: &[u64]) -> WitnessSet {
+        WitnessSet::new(
+            weights
+                .iter()
+                .enumerate()
+                .map(|(i, &weight)| Witness {
+                    key: [i as u8 + 1; 32],
+                    bls_key: bls_public_key(&derive_bls_key(&[i as u8 + 1; 32]).unwrap()),
+                    weight,
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn observation(witness: u8, observed_iteration: u64) -> WitnessObservation {
+        WitnessObservation {
+            witness: [witness; 32],
+            tick_number: 3,
+            envelope_hash: ENVELOPE,
+            observed_iteration,
+            vdf_proof: [0u8; 32],
+            signature: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_median_resists_outliers() {
+        let witnesses = witness_set(&[1, 1, 1, 1]);
+        // One faulty witness claims a much earlier arrival
+        let observations = vec![
+            observation(1, 0),
+            observation(2, 100),
+            observation(3, 102),
+            observation(4, 105),
+        ];
+
+        let canonical = CanonicalTimestamp::agree(3, ENVELOPE, &observations, &witnesses).unwrap();
+        assert_eq!(canonical.canonical_iteration, 100);
+        assert_eq!(canonical.observed_weight, 4);
+        assert_eq!(canonical.observations, 4);
+
+        // Moving the outlier to the other extreme keeps it inside the honest range
+        let observations = vec![
+            observation(1, u64::MAX),
+            observation(2, 100),
+            observation(3, 102),
+            observation(4, 105),
+        ];
+        let canonical = CanonicalTimestamp::agree(3, ENVELOPE, &observations, &witnesses).unwrap();
+        assert_eq!(canonical.canonical_iteration, 102);
+    }
+
+    #[test]
+    fn test_agreement_requires_quorum() {
+        let witnesses = witness_set(&[1, 1, 1]);
+        let mut observations = vec![observation(1, 10), observation(2, 12)];
+        // Duplicates and outsiders add no weight
+        observations.push(observation(2, 50));
+        observations.push(observation(9, 11));
+        assert!(CanonicalTimestamp::agree(3, ENVELOPE, &observations, &witnesses).is_none());
+
+        observations.push(observation(3, 11));
+        let canonical = CanonicalTimestamp::agree(3, ENVELOPE, &observations, &witnesses).unwrap();
+        assert_eq!(canonical.canonical_iteration, 11);
+        assert_eq!(canonical.observations, 3);
+
+        // Observations for another tick do not count
+        assert!(CanonicalTimestamp::agree(4, ENVELOPE, &observations, &witnesses).is_none());
+    }
+
+    #[test]
+    fn test_median_is_weighted() {
+        let witnesses = witness_set(&[5, 1, 1]);
+        let observations = vec![observation(1, 30), observation(2, 10), observation(3, 20)];
+        let canonical = CanonicalTimestamp::agree(3, ENVELOPE, &observations, &witnesses).unwrap();
+        assert_eq!(canonical.canonical_iteration, 30);
+    }
+
+    #[test]
+    fn test_observation_pool() {
+        let witnesses = witness_set(&[1, 1, 1]);
+        let mut pool = ObservationPool::new();
+
+        assert!(pool.insert(observation(1, 10), &witnesses).unwrap());
+        assert!(!pool.insert(observation(1, 10), &witnesses).unwrap());
+        assert!(pool.insert(observation(1, 11), &witnesses).is_err());
+        assert!(pool.insert(observation(9, 10), &witnesses).is_err());
+        assert!(pool.canonical_timestamp(3, &ENVELOPE, &witnesses).is_none());
+
+        pool.insert(observation(2, 14), &witnesses).unwrap();
+        pool.insert(observation(3, 12), &witnesses).unwrap();
+        let canonical = pool.canonical_timestamp(3, &ENVELOPE, &witnesses).unwrap();
+        assert_eq!(canonical.canonical_iteration, 12);
+
+        pool.prune(3);
+        assert_eq!(pool.len(), 1);
+        pool.prune(4);
+        assert!(pool.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_signed_observation() {
+        let signer = InMemorySigner::from_seed(&[1u8; 32]).unwrap();
+        let witnesses = WitnessSet::new(vec![Witness {
+            key: signer.public_key(SignatureScheme::Ed25519).try_into().unwrap(),
+            bls_key: bls_public_key(&derive_bls_key(&[1u8; 32]).unwrap()),
+            weight: 1,
+        }])
+        .unwrap();
+        let form: PackedForm = ("2".into(), "1".into(), "-3".into());
+        assert_eq!(WitnessObservation::proof_iteration(3100, 1000), 2000);
+        assert_eq!(WitnessObservation::proof_iteration(900, 1000), 0);
+
+        let observation =
+            WitnessObservation::sign(3, ENVELOPE, 3100, &form, &signer).await.unwrap();
+        observation.verify(&witnesses, &form).unwrap();
+
+        // Another timeline's form, or a moved iteration, is caught
+        let other: PackedForm = ("2".into(), "1".into(), "-7".into());
+        assert!(observation.verify(&witnesses, &other).is_err());
+        let mut moved = observation.clone();
+        moved.observed_iteration = 3000;
+        assert!(moved.verify(&witnesses, &form).is_err());
+
+        let outsider = InMemorySigner::from_seed(&[9u8; 32]).unwrap();
+        let observation =
+            WitnessObservation::sign(3, ENVELOPE, 3100, &form, &outsider).await.unwrap();
+        assert!(observation.verify(&witnesses, &form).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Price and data feeds reported by the witnesses
+//!
+//! Witnesses report feed values in the metadata attached to their tick
+//! votes, encoded as an [`OracleReport`]. Once a tick is finalized, the
+//! reports carried in its [`TickFinality`] are medianized per feed into an
+//! [`OracleTick`], so a minority of faulty witnesses can't move a value
+//! outside the range reported by honest ones. The tick's values are
+//! committed to a Merkle root, and each value is served with an
+//! [`OracleProof`] of its inclusion.
+//!
+//! The values are never stored on their own. They are medianized again from
+//! the finality record whenever they're needed, and a proof carries that
+//! record: its reports are each signed by their witness and the tick by a
+//! quorum, so the root can be recomputed and checked against nothing but the
+//! witness set.
+//!
+//! Blobs that don't decode as a report, such as metadata attached for
+//! another purpose, are left out.
+
+use crate::witness::{TickFinality, WitnessSet};
+use bincode::{Decode, Encode};
+use kala_common::crypto::{CryptoUtils, MerkleTree};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::types::{BlockHeight, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Identifier of a feed, such as the hash of its name
+pub type FeedId = Hash;
+
+/// Encoded size of one report entry: the feed ID, then the value
+pub const ORACLE_ENTRY_SIZE: usize = 40;
+
+/// Feed values one witness reports for a tick
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OracleReport {
+    /// Values by feed
+    pub values: BTreeMap<FeedId, u64>,
+}
+
+impl OracleReport {
+    /// Vote metadata carrying the report
+    ///
+    /// Entries of [`ORACLE_ENTRY_SIZE`] bytes in feed order, each value
+    /// little-endian.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.values.len() * ORACLE_ENTRY_SIZE);
+        for (feed, value) in &self.values {
+            data.extend_from_slice(feed);
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+
+    /// Parse a report, rejecting unordered or repeated feeds
+    pub fn decode(data: &[u8]) -> KalaResult<Self> {
+        let entries = data.chunks_exact(ORACLE_ENTRY_SIZE);
+        if data.is_empty() || !entries.remainder().is_empty() {
+            return Err(KalaError::validation(format!(
+                "Oracle report of {} bytes is not a whole number of entries",
+                data.len()
+            )));
+        }
+        let mut values = BTreeMap::new();
+        let mut previous: Option<FeedId> = None;
+        for entry in entries {
+            let feed: FeedId = entry[..32].try_into().expect("entry holds a feed ID");
+            if previous.is_some_and(|previous| feed <= previous) {
+                return Err(KalaError::validation("Oracle report feeds are not in order"));
+            }
+            previous = Some(feed);
+            let value = u64::from_le_bytes(entry[32..].try_into().expect("entry holds a value"));
+            values.insert(feed, value);
+        }
+        Ok(Self { values })
+    }
+}
+
+/// Median of the values reported for a feed at one tick
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FeedValue {
+    pub feed: FeedId,
+    pub value: u64,
+    /// Number of witnesses that reported the feed
+    pub reports: u32,
+}
+
+impl FeedValue {
+    /// Leaf committing to the value in the tick's oracle root
+    pub fn leaf(&self) -> Hash {
+        CryptoUtils::hash_multiple(&[
+            b"kala-oracle",
+            &self.feed,
+            &self.value.to_le_bytes(),
+            &self.reports.to_le_bytes(),
+        ])
+    }
+}
+
+/// Medianized feed values of a finalized tick
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct OracleTick {
+    pub tick_number: BlockHeight,
+    /// Values in feed order
+    pub values: Vec<FeedValue>,
+}
+
+impl OracleTick {
+    /// Medianize the reports in a finality record
+    ///
+    /// With an even number of reports, the lower of the two middle values
+    /// is taken, so the result is always a value some witness reported.
+    pub fn from_finality(finality: &TickFinality) -> Self {
+        let mut reported: BTreeMap<FeedId, Vec<u64>> = BTreeMap::new();
+        for entry in &finality.metadata {
+            let Ok(report) = OracleReport::decode(&entry.metadata.data) else {
+                continue;
+            };
+            for (feed, value) in report.values {
+                reported.entry(feed).or_default().push(value);
+            }
+        }
+
+        let values = reported
+            .into_iter()
+            .map(|(feed, mut values)| {
+                values.sort_unstable();
+                FeedValue {
+                    feed,
+                    value: values[(values.len() - 1) / 2],
+                    reports: values.len() as u32,
+                }
+            })
+            .collect();
+        Self {
+            tick_number: finality.tick_number,
+            values,
+        }
+    }
+
+    /// Merkle root over the values, in feed order
+    pub fn root(&self) -> Hash {
+        self.tree().root()
+    }
+
+    pub fn value(&self, feed: &FeedId) -> Option<&FeedValue> {
+        self.values
+            .binary_search_by(|value| value.feed.cmp(feed))
+            .ok()
+            .map(|index| &self.values[index])
+    }
+
+    fn tree(&self) -> MerkleTree {
+        MerkleTree::new(self.values.iter().map(FeedValue::leaf).collect())
+    }
+}
+
+/// A feed value and the Merkle path committing it to a tick's oracle root,
+/// with the finality record the root is medianized from
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OracleProof {
+    pub tick_number: BlockHeight,
+    pub value: FeedValue,
+    /// Position of the value among the tick's values
+    pub index: u64,
+    pub siblings: Vec<Hash>,
+    pub root: Hash,
+    /// Witness signatures finalizing the tick, carrying the signed reports
+    pub finality: TickFinality,
+}
+
+impl OracleProof {
+    /// Value of `feed` medianized from `finality`, if it was reported
+    pub fn from_finality(finality: &TickFinality, feed: &FeedId) -> Option<Self> {
+        let oracle = OracleTick::from_finality(finality);
+        let index = oracle.values.binary_search_by(|value| value.feed.cmp(feed)).ok()?;
+        Some(Self {
+            tick_number: oracle.tick_number,
+            value: oracle.values[index].clone(),
+            index: index as u64,
+            siblings: oracle.tree().proof(index)?,
+            root: oracle.root(),
+            finality: finality.clone(),
+        })
+    }
+
+    /// Verify the value against `witnesses`
+    ///
+    /// The finality record must be signed by a quorum of them, each report
+    /// in it by its witness, and the root must be the one medianized from
+    /// those reports.
+    pub fn verify(&self, witnesses: &WitnessSet) -> KalaResult<()> {
+        self.finality.verify(witnesses)?;
+        if self.finality.tick_number != self.tick_number {
+            return Err(KalaError::validation(format!(
+                "Oracle proof for tick {} carries finality of tick {}",
+                self.tick_number, self.finality.tick_number
+            )));
+        }
+        if OracleTick::from_finality(&self.finality).root() != self.root {
+            return Err(KalaError::validation(format!(
+                "Oracle root of tick {} is not medianized from its finality",
+                self.tick_number
+            )));
+        }
+        let index = self.index as usize;
+        if !MerkleTree::verify_proof(&self.value.leaf(), &self.siblings, &self.root, index) {
+            return Err(KalaError::crypto(format!(
+                "Oracle value of tick {} is not committed to its root",
+                self.tick_number
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::{TickVote, Witness};
+    use kala_common::crypto::signer::{InMemorySigner, SignatureScheme, Signer};
+
+    #[test]
+    fn test_report_encoding() {
+        let report = OracleReport {
+            values: BTreeMap::from([([1u8; 32], 7), ([2u8; 32], u64::MAX)]),
+        };
+        let data = report.encode();
+        assert_eq!(data.len(), 2 * ORACLE_ENTRY_SIZE);
+        assert_eq!(OracleReport::decode(&data).unwrap(), report);
+
+        let mut swapped = data[ORACLE_ENTRY_SIZE..].to_vec();
+        swapped.extend_from_slice(&data[..ORACLE_ENTRY_SIZE]);
+        assert!(OracleReport::decode(&swapped).is_err());
+        assert!(OracleReport::decode(&data[..39]).is_err());
+        assert!(OracleReport::decode(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_feeds_medianized_with_proofs() {
+        let (btc, eth) = ([1u8; 32], [2u8; 32]);
+        let reports = [
+            vec![(btc, 100), (eth, 10)],
+            vec![(btc, 102)],
+            vec![(btc, 5_000), (eth, 12)],
+            vec![(btc, 101), (eth, 11)],
+        ];
+
+        let mut witnesses = Vec::new();
+        let mut votes = Vec::new();
+        for (seed, values) in (1u8..).zip(reports) {
+            let signer = InMemorySigner::from_seed(&[seed; 32]).unwrap();
+            witnesses.push(Witness {
+                key: signer.public_key(SignatureScheme::Ed25519).try_into().unwrap(),
+                bls_key: signer.public_key(SignatureScheme::Bls12381),
+                weight: 1,
+            });
+            let report = OracleReport {
+                values: values.into_iter().collect(),
+            };
+            let mut vote = TickVote::sign_with(9, [7u8; 32], SignatureScheme::Bls12381, &signer)
+                .await
+                .unwrap();
+            vote.attach_metadata(report.encode(), &signer).await.unwrap();
+            votes.push(vote);
+        }
+        let witnesses = WitnessSet::new(witnesses).unwrap();
+        let finality = TickFinality::aggregate(&witnesses, &votes).unwrap();
+
+        // The outlier doesn't move the median
+        let oracle = OracleTick::from_finality(&finality);
+        assert_eq!(oracle.tick_number, 9);
+        assert_eq!(oracle.value(&btc).map(|v| (v.value, v.reports)), Some((101, 4)));
+        assert_eq!(oracle.value(&eth).map(|v| (v.value, v.reports)), Some((11, 3)));
+        assert!(oracle.value(&[3u8; 32]).is_none());
+
+        let proof = OracleProof::from_finality(&finality, &eth).unwrap();
+        proof.verify(&witnesses).unwrap();
+        assert_eq!(proof.root, oracle.root());
+        assert!(OracleProof::from_finality(&finality, &[3u8; 32]).is_none());
+
+        let mut forged = proof.clone();
+        forged.value.value = 5_000;
+        assert!(forged.verify(&witnesses).is_err());
+
+        // A root not medianized from the signed reports is rejected
+        let mut rerooted = proof.clone();
+        let tree = MerkleTree::new(vec![forged.value.leaf(), [0u8; 32]]);
+        rerooted.value = forged.value.clone();
+        rerooted.index = 0;
+        rerooted.siblings = tree.proof(0).unwrap();
+        rerooted.root = tree.root();
+        assert!(rerooted.verify(&witnesses).is_err());
+
+        // Dropping reports from the record changes the root
+        let mut stripped = proof.clone();
+        stripped.finality.metadata.truncate(1);
+        assert!(stripped.verify(&witnesses).is_err());
+        let mut unsigned = proof.clone();
+        unsigned.finality.signers = vec![0u8; unsigned.finality.signers.len()];
+        assert!(unsigned.verify(&witnesses).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Packets between Kala timelines (experimental, behind the `ibc` feature)
+//!
+//! A prototype of IBC-style transfers on top of [`ForeignClients`]. Sending
+//! a [`Packet`] on chain A escrows the amount and commits the packet into
+//! A's state root. Once an epoch summary carrying that root is submitted to
+//! chain B's light client of A, anyone can relay the packet with a
+//! [`PacketProof`] of its commitment. B checks the proof against the root,
+//! records a receipt so the packet can't be relayed twice, and credits the
+//! receiver with a voucher for A's native token.
+//!
+//! There are no acknowledgements or timeouts yet: escrowed funds stay
+//! escrowed, and vouchers can't be sent back.
+//!
+//! [`ForeignClients`]: crate::ForeignClients
+
+use crate::foreign::ForeignClient;
+use bincode::{Decode, Encode};
+use kala_common::crypto::{CryptoUtils, MerkleTree};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::types::Hash;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A transfer from this timeline to another
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    /// Position among the packets sent by the source chain
+    pub sequence: u64,
+    pub sender: Hash,
+    pub receiver: Hash,
+    pub amount: u64,
+    /// Chain ID of the timeline the packet is for
+    pub destination: Hash,
+}
+
+impl Packet {
+    /// Leaf committing to the packet in the source chain's state root
+    pub fn commitment(&self) -> Hash {
+        CryptoUtils::hash_multiple(&[
+            b"kala-packet",
+            &self.sequence.to_le_bytes(),
+            &self.sender,
+            &self.receiver,
+            &self.amount.to_le_bytes(),
+            &self.destination,
+        ])
+    }
+}
+
+/// Merkle path committing a packet to the source chain's state root at
+/// the end of an epoch
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PacketProof {
+    /// Epoch of the source chain whose state root commits the packet
+    pub epoch_number: u64,
+    /// Position of the commitment among the state root's leaves
+    pub index: u64,
+    pub siblings: Vec<Hash>,
+}
+
+impl PacketProof {
+    /// Whether `packet` is committed in epoch `epoch_number` of `client`
+    pub fn verify(&self, client: &ForeignClient, packet: &Packet) -> bool {
+        let index = self.index as usize;
+        client.epochs.get(&self.epoch_number).is_some_and(|epoch| {
+            MerkleTree::verify_proof(&packet.commitment(), &self.siblings, &epoch.state_root, index)
+        })
+    }
+}
+
+/// Packets sent from and received by this timeline
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PacketStore {
+    next_sequence: u64,
+    /// Commitments of sent packets, by sequence
+    commitments: BTreeMap<u64, Hash>,
+    /// Received packets, by the light client they were proven against
+    receipts: BTreeSet<(Hash, u64)>,
+    /// Voucher balances by light client and account
+    vouchers: BTreeMap<(Hash, Hash), u64>,
+}
+
+impl PacketStore {
+    /// Commit a packet from `sender`, assigning it the next sequence
+    pub fn send(
+        &mut self,
+        sender: &Hash,
+        receiver: &Hash,
+        amount: u64,
+        destination: &Hash,
+    ) -> Packet {
+        let packet = Packet {
+            sequence: self.next_sequence,
+            sender: *sender,
+            receiver: *receiver,
+            amount,
+            destination: *destination,
+        };
+        self.next_sequence += 1;
+        self.commitments.insert(packet.sequence, packet.commitment());
+        packet
+    }
+
+    /// Commitments of sent packets, in sequence order
+    pub fn commitments(&self) -> impl Iterator<Item = (u64, &Hash)> {
+        self.commitments.iter().map(|(sequence, commitment)| (*sequence, commitment))
+    }
+
+    /// Verify `packet` was sent to `chain_id` by the timeline client
+    /// `client_id` follows, and record its receipt
+    pub fn receive(
+        &mut self,
+        client_id: &Hash,
+        client: &ForeignClient,
+        packet: &Packet,
+        proof: &PacketProof,
+        chain_id: &Hash,
+    ) -> KalaResult<()> {
+        if packet.destination != *chain_id {
+            return Err(KalaError::validation("Packet is for another timeline"));
+        }
+        if self.receipts.contains(&(*client_id, packet.sequence)) {
+            return Err(KalaError::validation(format!(
+                "Packet {} was already received",
+                packet.sequence
+            )));
+        }
+        if !proof.verify(client, packet) {
+            return Err(KalaError::validation("Packet commitment proof doesn't verify"));
+        }
+
+        self.receipts.insert((*client_id, packet.sequence));
+        let balance = self.vouchers.entry((*client_id, packet.receiver)).or_default();
+        *balance = balance.saturating_add(packet.amount);
+        Ok(())
+    }
+
+    pub fn has_receipt(&self, client_id: &Hash, sequence: u64) -> bool {
+        self.receipts.contains(&(*client_id, sequence))
+    }
+
+    /// Vouchers `account` holds for the native token of the timeline
+    /// `client_id` follows
+    pub fn voucher_balance(&self, client_id: &Hash, account: &Hash) -> u64 {
+        self.vouchers.get(&(*client_id, *account)).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epoch::{EpochStats, EpochSummary, SignedEpochSummary};
+    use crate::ChainState;
+
+    #[test]
+    fn test_packet_relayed_between_timelines() {
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let (chain_a, chain_b) = ([0xa; 32], [0xb; 32]);
+
+        let mut source = ChainState::new();
+        source.mint(&alice, 100).unwrap();
+        assert!(source.send_packet(&alice, &bob, 150, &chain_b).is_err());
+        let root_before = source.state_root();
+        let packet = source.send_packet(&alice, &bob, 60, &chain_b).unwrap();
+        let stray = source.send_packet(&alice, &bob, 10, &chain_a).unwrap();
+        assert_eq!(source.get_balance(&alice), 30);
+        assert_ne!(source.state_root(), root_before);
+        let proof = source.packet_proof(packet.sequence, 0).unwrap();
+        let stray_proof = source.packet_proof(stray.sequence, 0).unwrap();
+        assert!(source.packet_proof(9, 0).is_none());
+
+        // The destination follows the source from an epoch committing the packet
+        let mut destination = ChainState::new();
+        let summary = EpochSummary {
+            epoch_number: 0,
+            start_tick: 0,
+            end_tick: 3,
+            end_tick_hash: [3u8; 32],
+            mmr_root: [0u8; 32],
+            state_root: source.state_root(),
+            witnesses: Vec::new(),
+            stats: EpochStats::default(),
+            previous_epoch_hash: [0u8; 32],
+        };
+        let client_id = destination
+            .submit_foreign_epoch(&[0u8; 32], &SignedEpochSummary::new(summary))
+            .unwrap();
+
+        assert!(destination.receive_packet(&[9u8; 32], &packet, &proof, &chain_b).is_err());
+        assert!(destination.receive_packet(&client_id, &stray, &stray_proof, &chain_b).is_err());
+        let mut forged = packet.clone();
+        forged.amount = 600;
+        assert!(destination.receive_packet(&client_id, &forged, &proof, &chain_b).is_err());
+
+        destination.receive_packet(&client_id, &packet, &proof, &chain_b).unwrap();
+        assert_eq!(destination.packets().voucher_balance(&client_id, &bob), 60);
+        assert!(destination.packets().has_receipt(&client_id, packet.sequence));
+        assert!(destination.receive_packet(&client_id, &packet, &proof, &chain_b).is_err());
+        assert_eq!(destination.get_balance(&bob), 0);
+    }
+}
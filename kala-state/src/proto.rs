@@ -0,0 +1,469 @@
+//! Protobuf wire format of gossiped and synced records
+//!
+//! See `kala-common/schema/network.proto`. The chain state has no schema of
+//! its own yet and travels bincode-encoded.
+
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::proto::{self, fixed, fixed_list, required, ProtoCodec};
+use kala_vdf::form_bytes;
+
+use crate::epoch::{EpochSignature, EpochStats, EpochSummary, SignedEpochSummary, WitnessSignature};
+use crate::observation::WitnessObservation;
+use crate::reveal::ShareReveal;
+use crate::tick::{TickCertificate, TickHeader, TickType};
+use crate::usage::ModuleUsage;
+use crate::witness::{TickVote, VoteMetadata, VoteTime};
+use crate::ChainState;
+
+impl From<&TickType> for proto::TickType {
+    fn from(tick_type: &TickType) -> Self {
+        match tick_type {
+            TickType::Full => proto::TickType::Full,
+            TickType::Empty => proto::TickType::Empty,
+            TickType::Checkpoint => proto::TickType::Checkpoint,
+        }
+    }
+}
+
+impl From<proto::TickType> for TickType {
+    fn from(tick_type: proto::TickType) -> Self {
+        match tick_type {
+            proto::TickType::Full => TickType::Full,
+            proto::TickType::Empty => TickType::Empty,
+            proto::TickType::Checkpoint => TickType::Checkpoint,
+        }
+    }
+}
+
+impl ProtoCodec for TickCertificate {
+    type Proto = proto::TickCertificate;
+
+    fn to_proto(&self) -> proto::TickCertificate {
+        let (a, b, c) = &self.vdf_form;
+        proto::TickCertificate {
+            tick_number: self.tick_number,
+            tick_type: proto::TickType::from(&self.tick_type).into(),
+            vdf_iteration: self.vdf_iteration,
+            vdf_form: Some(proto::VdfForm {
+                encoded: form_bytes::encode_form(a, b, c),
+                ..Default::default()
+            }),
+            hash_chain_value: self.hash_chain_value.to_vec(),
+            tick_hash: self.tick_hash.to_vec(),
+            transaction_count: self.transaction_count,
+            transaction_merkle_root: self.transaction_merkle_root.to_vec(),
+            timestamp: self.timestamp,
+            previous_tick_hash: self.previous_tick_hash.to_vec(),
+            usage: (!self.usage.is_empty()).then(|| self.usage.to_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::TickCertificate) -> KalaResult<Self> {
+        let tick_type = proto::TickType::try_from(proto.tick_type)
+            .map_err(|_| KalaError::serialization(format!("Unknown tick type {}", proto.tick_type)))?;
+        let form = required(proto.vdf_form, "VDF form")?;
+        // Peers that predate the byte encoding send the strings
+        let vdf_form = if form.encoded.is_empty() {
+            (form.a.into(), form.b.into(), form.c.into())
+        } else {
+            form_bytes::decode_form(&form.encoded)?
+        };
+        Ok(Self {
+            tick_number: proto.tick_number,
+            tick_type: tick_type.into(),
+            vdf_iteration: proto.vdf_iteration,
+            vdf_form,
+            hash_chain_value: fixed(proto.hash_chain_value, "hash chain value")?,
+            tick_hash: fixed(proto.tick_hash, "tick hash")?,
+            transaction_count: proto.transaction_count,
+            transaction_merkle_root: fixed(proto.transaction_merkle_root, "transaction merkle root")?,
+            timestamp: proto.timestamp,
+            previous_tick_hash: fixed(proto.previous_tick_hash, "previous tick hash")?,
+            usage: proto.usage.map(ModuleUsage::from_proto).transpose()?.unwrap_or_default(),
+        })
+    }
+}
+
+impl ProtoCodec for ModuleUsage {
+    type Proto = proto::ModuleUsage;
+
+    fn to_proto(&self) -> proto::ModuleUsage {
+        proto::ModuleUsage {
+            native_transactions: self
+                .native_transactions
+                .iter()
+                .map(|(module, count)| (module.clone(), *count))
+                .collect(),
+            contract_gas: self.contract_gas,
+        }
+    }
+
+    fn from_proto(proto: proto::ModuleUsage) -> KalaResult<Self> {
+        Ok(Self {
+            native_transactions: proto.native_transactions.into_iter().collect(),
+            contract_gas: proto.contract_gas,
+        })
+    }
+}
+
+impl ProtoCodec for WitnessObservation {
+    type Proto = proto::WitnessObservation;
+
+    fn to_proto(&self) -> proto::WitnessObservation {
+        proto::WitnessObservation {
+            witness: self.witness.to_vec(),
+            tick_number: self.tick_number,
+            envelope_hash: self.envelope_hash.to_vec(),
+            observed_iteration: self.observed_iteration,
+            vdf_proof: self.vdf_proof.to_vec(),
+            signature: self.signature.clone(),
+        }
+    }
+
+    fn from_proto(proto: proto::WitnessObservation) -> KalaResult<Self> {
+        Ok(Self {
+            witness: fixed(proto.witness, "witness")?,
+            tick_number: proto.tick_number,
+            envelope_hash: fixed(proto.envelope_hash, "envelope hash")?,
+            observed_iteration: proto.observed_iteration,
+            vdf_proof: fixed(proto.vdf_proof, "VDF proof")?,
+            signature: proto.signature,
+        })
+    }
+}
+
+impl ProtoCodec for TickHeader {
+    type Proto = proto::TickHeader;
+
+    fn to_proto(&self) -> proto::TickHeader {
+        proto::TickHeader {
+            tick_number: self.tick_number,
+            vdf_iteration: self.vdf_iteration,
+            tick_hash: self.tick_hash.to_vec(),
+            previous_tick_hash: self.previous_tick_hash.to_vec(),
+            transaction_count: self.transaction_count,
+        }
+    }
+
+    fn from_proto(proto: proto::TickHeader) -> KalaResult<Self> {
+        Ok(Self {
+            tick_number: proto.tick_number,
+            vdf_iteration: proto.vdf_iteration,
+            tick_hash: fixed(proto.tick_hash, "tick hash")?,
+            previous_tick_hash: fixed(proto.previous_tick_hash, "previous tick hash")?,
+            transaction_count: proto.transaction_count,
+        })
+    }
+}
+
+impl ProtoCodec for TickVote {
+    type Proto = proto::TickVote;
+
+    fn to_proto(&self) -> proto::TickVote {
+        proto::TickVote {
+            tick_number: self.tick_number,
+            tick_hash: self.tick_hash.to_vec(),
+            witness: self.witness.to_vec(),
+            signature: self.signature.clone(),
+            metadata: self.metadata.as_ref().map(|metadata| proto::VoteMetadata {
+                data: metadata.data.clone(),
+                signature: metadata.signature.clone(),
+            }),
+            time: self.time.as_ref().map(|time| proto::VoteTime {
+                time_ms: time.time_ms,
+                signature: time.signature.clone(),
+            }),
+        }
+    }
+
+    fn from_proto(proto: proto::TickVote) -> KalaResult<Self> {
+        Ok(Self {
+            tick_number: proto.tick_number,
+            tick_hash: fixed(proto.tick_hash, "tick hash")?,
+            witness: fixed(proto.witness, "witness")?,
+            signature: proto.signature,
+            metadata: proto.metadata.map(|metadata| VoteMetadata {
+                data: metadata.data,
+                signature: metadata.signature,
+            }),
+            time: proto.time.map(|time| VoteTime {
+                time_ms: time.time_ms,
+                signature: time.signature,
+            }),
+        })
+    }
+}
+
+impl ProtoCodec for WitnessSignature {
+    type Proto = proto::WitnessSignature;
+
+    fn to_proto(&self) -> proto::WitnessSignature {
+        proto::WitnessSignature {
+            witness: self.witness.to_vec(),
+            signature: self.signature.clone(),
+        }
+    }
+
+    fn from_proto(proto: proto::WitnessSignature) -> KalaResult<Self> {
+        Ok(Self {
+            witness: fixed(proto.witness, "witness")?,
+            signature: proto.signature,
+        })
+    }
+}
+
+impl ProtoCodec for EpochSignature {
+    type Proto = proto::EpochSignature;
+
+    fn to_proto(&self) -> proto::EpochSignature {
+        proto::EpochSignature {
+            epoch_number: self.epoch_number,
+            summary_hash: self.summary_hash.to_vec(),
+            signature: Some(self.signature.to_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::EpochSignature) -> KalaResult<Self> {
+        Ok(Self {
+            epoch_number: proto.epoch_number,
+            summary_hash: fixed(proto.summary_hash, "summary hash")?,
+            signature: WitnessSignature::from_proto(required(proto.signature, "signature")?)?,
+        })
+    }
+}
+
+impl ProtoCodec for EpochStats {
+    type Proto = proto::EpochStats;
+
+    fn to_proto(&self) -> proto::EpochStats {
+        proto::EpochStats {
+            tick_count: self.tick_count,
+            full_ticks: self.full_ticks,
+            empty_ticks: self.empty_ticks,
+            checkpoint_ticks: self.checkpoint_ticks,
+            transaction_count: self.transaction_count,
+            start_iteration: self.start_iteration,
+            end_iteration: self.end_iteration,
+            start_timestamp: self.start_timestamp,
+            end_timestamp: self.end_timestamp,
+            usage: (!self.usage.is_empty()).then(|| self.usage.to_proto()),
+            oracle_updates: self.oracle_updates,
+        }
+    }
+
+    fn from_proto(proto: proto::EpochStats) -> KalaResult<Self> {
+        Ok(Self {
+            tick_count: proto.tick_count,
+            full_ticks: proto.full_ticks,
+            empty_ticks: proto.empty_ticks,
+            checkpoint_ticks: proto.checkpoint_ticks,
+            transaction_count: proto.transaction_count,
+            start_iteration: proto.start_iteration,
+            end_iteration: proto.end_iteration,
+            start_timestamp: proto.start_timestamp,
+            end_timestamp: proto.end_timestamp,
+            usage: proto.usage.map(ModuleUsage::from_proto).transpose()?.unwrap_or_default(),
+            oracle_updates: proto.oracle_updates,
+        })
+    }
+}
+
+impl ProtoCodec for EpochSummary {
+    type Proto = proto::EpochSummary;
+
+    fn to_proto(&self) -> proto::EpochSummary {
+        proto::EpochSummary {
+            epoch_number: self.epoch_number,
+            start_tick: self.start_tick,
+            end_tick: self.end_tick,
+            end_tick_hash: self.end_tick_hash.to_vec(),
+            mmr_root: self.mmr_root.to_vec(),
+            state_root: self.state_root.to_vec(),
+            witnesses: self.witnesses.iter().map(|witness| witness.to_vec()).collect(),
+            stats: Some(self.stats.to_proto()),
+            previous_epoch_hash: self.previous_epoch_hash.to_vec(),
+        }
+    }
+
+    fn from_proto(proto: proto::EpochSummary) -> KalaResult<Self> {
+        Ok(Self {
+            epoch_number: proto.epoch_number,
+            start_tick: proto.start_tick,
+            end_tick: proto.end_tick,
+            end_tick_hash: fixed(proto.end_tick_hash, "end tick hash")?,
+            mmr_root: fixed(proto.mmr_root, "MMR root")?,
+            state_root: fixed(proto.state_root, "state root")?,
+            witnesses: fixed_list(proto.witnesses, "witness")?,
+            stats: EpochStats::from_proto(required(proto.stats, "epoch stats")?)?,
+            previous_epoch_hash: fixed(proto.previous_epoch_hash, "previous epoch hash")?,
+        })
+    }
+}
+
+impl ProtoCodec for SignedEpochSummary {
+    type Proto = proto::SignedEpochSummary;
+
+    fn to_proto(&self) -> proto::SignedEpochSummary {
+        proto::SignedEpochSummary {
+            summary: Some(self.summary.to_proto()),
+            signatures: self.signatures.iter().map(ProtoCodec::to_proto).collect(),
+        }
+    }
+
+    fn from_proto(proto: proto::SignedEpochSummary) -> KalaResult<Self> {
+        Ok(Self {
+            summary: EpochSummary::from_proto(required(proto.summary, "epoch summary")?)?,
+            signatures: proto
+                .signatures
+                .into_iter()
+                .map(WitnessSignature::from_proto)
+                .collect::<KalaResult<_>>()?,
+        })
+    }
+}
+
+impl ProtoCodec for ShareReveal {
+    type Proto = proto::ShareReveal;
+
+    fn to_proto(&self) -> proto::ShareReveal {
+        proto::ShareReveal {
+            witness: self.witness.to_vec(),
+            tick_number: self.tick_number,
+            envelope_hash: self.envelope_hash.to_vec(),
+            share: Some(self.share.to_proto()),
+            signature: self.signature.clone(),
+        }
+    }
+
+    fn from_proto(proto: proto::ShareReveal) -> KalaResult<Self> {
+        Ok(Self {
+            witness: fixed(proto.witness, "witness")?,
+            tick_number: proto.tick_number,
+            envelope_hash: fixed(proto.envelope_hash, "envelope hash")?,
+            share: ProtoCodec::from_proto(required(proto.share, "key share")?)?,
+            signature: proto.signature,
+        })
+    }
+}
+
+impl ProtoCodec for ChainState {
+    type Proto = proto::ChainState;
+
+    fn to_proto(&self) -> proto::ChainState {
+        proto::ChainState {
+            // Encoding into memory can't fail
+            encoded: bincode::encode_to_vec(self, bincode::config::standard()).unwrap_or_default(),
+        }
+    }
+
+    fn from_proto(proto: proto::ChainState) -> KalaResult<Self> {
+        bincode::decode_from_slice(&proto.encoded, bincode::config::standard())
+            .map(|(state, _)| state)
+            .map_err(|e| KalaError::serialization(format!("Invalid chain state: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_common::crypto::CryptoUtils;
+
+    fn tick() -> TickCertificate {
+        TickCertificate {
+            tick_number: 7,
+            tick_type: TickType::Checkpoint,
+            vdf_iteration: 7 * 65536,
+            vdf_form: ("2".into(), "1".into(), "3".into()),
+            hash_chain_value: [1u8; 32],
+            tick_hash: [2u8; 32],
+            transaction_count: 0,
+            transaction_merkle_root: [3u8; 32],
+            timestamp: 1_700_000_000,
+            previous_tick_hash: [4u8; 32],
+            usage: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_tick_certificate_round_trip() {
+        let tick = tick();
+        let decoded = TickCertificate::decode_proto(&tick.encode_proto()).unwrap();
+        assert_eq!(
+            CryptoUtils::hash_to_hex(&decoded.compute_hash()),
+            CryptoUtils::hash_to_hex(&tick.compute_hash())
+        );
+        assert_eq!(decoded.timestamp, tick.timestamp);
+        assert_eq!(decoded.tick_hash, tick.tick_hash);
+
+        // Forms come packed into bytes, or as strings from older peers
+        let mut hex = tick.clone();
+        hex.vdf_form = ("0x2".into(), "-0x1".into(), "0x3f1".into());
+        let packed = hex.to_proto().vdf_form.unwrap();
+        assert!(packed.a.is_empty());
+        assert_eq!(TickCertificate::from_proto(hex.to_proto()).unwrap().vdf_form, hex.vdf_form);
+        let mut legacy = hex.to_proto();
+        legacy.vdf_form = Some(proto::VdfForm {
+            a: hex.vdf_form.0.to_hex(),
+            b: hex.vdf_form.1.to_hex(),
+            c: hex.vdf_form.2.to_hex(),
+            encoded: Vec::new(),
+        });
+        assert_eq!(TickCertificate::from_proto(legacy).unwrap().vdf_form, hex.vdf_form);
+
+        let mut unknown = tick.to_proto();
+        unknown.tick_type = 9;
+        assert!(TickCertificate::from_proto(unknown).is_err());
+        let mut truncated = tick.to_proto();
+        truncated.tick_hash.pop();
+        assert!(TickCertificate::from_proto(truncated).is_err());
+
+        let mut used = tick.clone();
+        used.usage.record_native("bank");
+        let decoded = TickCertificate::decode_proto(&used.encode_proto()).unwrap();
+        assert_eq!(decoded.usage, used.usage);
+        assert_eq!(decoded.compute_hash(), used.compute_hash());
+
+        // Compact certificates arrive with c empty
+        let mut compact = hex.clone();
+        compact.vdf_form.2 = Default::default();
+        assert!(TickCertificate::decode_proto(&compact.encode_proto()).unwrap().is_compact());
+
+        let header = TickHeader::decode_proto(&tick.header().encode_proto()).unwrap();
+        assert_eq!(header, tick.header());
+    }
+
+    #[test]
+    fn test_epoch_summary_round_trip() {
+        let mut signed = SignedEpochSummary::new(EpochSummary {
+            epoch_number: 1,
+            start_tick: 10,
+            end_tick: 19,
+            end_tick_hash: [5u8; 32],
+            mmr_root: [6u8; 32],
+            state_root: [7u8; 32],
+            witnesses: vec![[8u8; 32], [9u8; 32]],
+            stats: EpochStats {
+                tick_count: 10,
+                usage: ModuleUsage {
+                    native_transactions: [("bank".to_string(), 3)].into(),
+                    contract_gas: 0,
+                },
+                oracle_updates: 4,
+                ..EpochStats::default()
+            },
+            previous_epoch_hash: [0u8; 32],
+        });
+        signed.signatures.push(WitnessSignature {
+            witness: [8u8; 32],
+            signature: vec![1u8; 64],
+        });
+
+        let decoded = SignedEpochSummary::decode_proto(&signed.encode_proto()).unwrap();
+        assert_eq!(decoded, signed);
+
+        let mut state = ChainState::new();
+        state.mint(&[1u8; 32], 500).unwrap();
+        let decoded = ChainState::decode_proto(&state.encode_proto()).unwrap();
+        assert_eq!(decoded.state_root(), state.state_root());
+    }
+}
@@ -0,0 +1,292 @@
+//! Compact proofs of a range of ticks for light clients
+//!
+//! Checking every tick of a long range means downloading every certificate
+//! and recomputing every VDF segment. A [`TickRangeProof`] instead carries
+//! the range's first and last certificates and a logarithmic number of
+//! sampled segments, each a certificate with its predecessor. Every
+//! certificate comes with an MMR inclusion proof against the tick
+//! accumulator root after the range's last tick, which a light client takes
+//! from a signed epoch summary ending there.
+//!
+//! The sampled ticks are derived from that root, so they're fixed once the
+//! ticks are committed and a prover can't choose to show only its honest
+//! segments. If a fraction `f` of the segments in the range were forged,
+//! each sample misses all of them with probability `1 - f`, and the proof
+//! passes with probability `(1 - f)^s` for `s` samples.
+
+use crate::tick::{verify_tick_certificate, TickCertificate};
+use kala_common::crypto::CryptoUtils;
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::mmr::MmrProof;
+use kala_common::types::Hash;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Segments sampled per doubling of the range's length
+pub const SAMPLES_PER_LEVEL: u64 = 8;
+
+/// A certificate and its inclusion in the tick accumulator
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProvenTick {
+    pub certificate: TickCertificate,
+    pub proof: MmrProof,
+}
+
+impl ProvenTick {
+    fn verify(&self, mmr_root: &Hash, leaf_count: u64) -> KalaResult<()> {
+        let tick = self.certificate.tick_number;
+        if self.proof.leaf_index != tick
+            || self.proof.leaf_count != leaf_count
+            || !self.proof.verify(&self.certificate.tick_hash, mmr_root)
+        {
+            return Err(KalaError::validation(format!(
+                "Tick {} is not committed to the accumulator",
+                tick
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A sampled VDF segment: a tick and the one before it
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SampledSegment {
+    pub previous: ProvenTick,
+    pub tick: ProvenTick,
+}
+
+/// Ticks `start..=end`, proven by their endpoints and sampled segments
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TickRangeProof {
+    pub first: ProvenTick,
+    pub last: ProvenTick,
+    /// Segments at [`sample_ticks`], in tick order
+    pub segments: Vec<SampledSegment>,
+}
+
+/// Ticks whose segments a proof of `start..=end` samples
+///
+/// Draws `SAMPLES_PER_LEVEL * log2(n)` ticks, for `n` ticks after `start`,
+/// from a hash of the accumulator root; repeats are drawn once. Every tick
+/// is taken once the range is too short to sample.
+pub fn sample_ticks(mmr_root: &Hash, start: u64, end: u64) -> Vec<u64> {
+    let span = end.saturating_sub(start);
+    let draws = SAMPLES_PER_LEVEL * u64::from(u64::BITS - span.leading_zeros());
+    if draws >= span {
+        return (start + 1..=end).collect();
+    }
+
+    let mut ticks = BTreeSet::new();
+    for draw in 0..draws {
+        let seed = CryptoUtils::hash_multiple(&[
+            b"kala-tick-sample",
+            mmr_root,
+            &start.to_le_bytes(),
+            &end.to_le_bytes(),
+            &draw.to_le_bytes(),
+        ]);
+        let value = u64::from_le_bytes(seed[..8].try_into().expect("hash holds 8 bytes"));
+        ticks.insert(start + 1 + value % span);
+    }
+    ticks.into_iter().collect()
+}
+
+impl TickRangeProof {
+    /// Prove the range ending at the last tick committed under `mmr_root`
+    ///
+    /// `load` returns a stored certificate with its inclusion proof against
+    /// that root.
+    pub fn build(
+        mmr_root: &Hash,
+        start: u64,
+        end: u64,
+        mut load: impl FnMut(u64) -> KalaResult<ProvenTick>,
+    ) -> KalaResult<Self> {
+        if end < start {
+            return Err(KalaError::validation(format!(
+                "Range ends at tick {} before it starts at {}",
+                end, start
+            )));
+        }
+        let segments = sample_ticks(mmr_root, start, end)
+            .into_iter()
+            .map(|tick| {
+                Ok(SampledSegment {
+                    previous: load(tick - 1)?,
+                    tick: load(tick)?,
+                })
+            })
+            .collect::<KalaResult<_>>()?;
+        Ok(Self {
+            first: load(start)?,
+            last: load(end)?,
+            segments,
+        })
+    }
+
+    /// First tick of the range
+    pub fn start(&self) -> u64 {
+        self.first.certificate.tick_number
+    }
+
+    /// Last tick of the range, the last one committed under the root
+    pub fn end(&self) -> u64 {
+        self.last.certificate.tick_number
+    }
+
+    /// Check the range against `mmr_root`, the tick accumulator root after
+    /// its last tick
+    ///
+    /// Every certificate must be committed under the root, the sampled
+    /// ticks must be those the root selects, and each sampled segment must
+    /// verify as in [`verify_tick_certificate`]. A range starting at
+    /// genesis also has its first tick checked from the generator.
+    pub fn verify(
+        &self,
+        mmr_root: &Hash,
+        iterations_per_tick: u64,
+        discriminant: &str,
+    ) -> KalaResult<()> {
+        let (start, end) = (self.start(), self.end());
+        let leaf_count = end
+            .checked_add(1)
+            .ok_or_else(|| KalaError::validation("Range ends past the last tick"))?;
+        self.first.verify(mmr_root, leaf_count)?;
+        self.last.verify(mmr_root, leaf_count)?;
+
+        let expected = sample_ticks(mmr_root, start, end);
+        if expected.len() != self.segments.len()
+            || expected
+                .iter()
+                .zip(&self.segments)
+                .any(|(tick, segment)| segment.tick.certificate.tick_number != *tick)
+        {
+            return Err(KalaError::validation("Proof doesn't carry the sampled ticks"));
+        }
+
+        if start == 0 {
+            let first = &self.first.certificate;
+            verify_tick_certificate(first, None, iterations_per_tick, discriminant)?;
+        }
+        for segment in &self.segments {
+            segment.previous.verify(mmr_root, leaf_count)?;
+            segment.tick.verify(mmr_root, leaf_count)?;
+            verify_tick_certificate(
+                &segment.tick.certificate,
+                Some(&segment.previous.certificate),
+                iterations_per_tick,
+                discriminant,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tick::TickType;
+    use kala_common::mmr::MerkleMountainRange;
+    use kala_common::types::consensus::DEFAULT_DISCRIMINANT as DISCRIMINANT;
+    use kala_vdf::EternalVDF;
+
+    const K: u64 = 4;
+
+    fn certificates(count: u64) -> Vec<TickCertificate> {
+        let mut vdf = EternalVDF::with_tick_size(DISCRIMINANT, K);
+        let mut previous_tick_hash = [0u8; 32];
+        (0..count)
+            .map(|tick_number| {
+                vdf.advance(K);
+                let mut certificate = TickCertificate {
+                    tick_number,
+                    tick_type: TickType::Empty,
+                    vdf_iteration: vdf.get_iteration(),
+                    vdf_form: vdf.get_form_values(),
+                    hash_chain_value: vdf.get_hash_chain(),
+                    tick_hash: [0u8; 32],
+                    transaction_count: 0,
+                    transaction_merkle_root: [0u8; 32],
+                    timestamp: tick_number,
+                    previous_tick_hash,
+                    usage: Default::default(),
+                };
+                certificate.tick_hash = certificate.compute_hash();
+                previous_tick_hash = certificate.tick_hash;
+                certificate
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tick_range_proof() {
+        let ticks = certificates(100);
+        let mut mmr = MerkleMountainRange::new();
+        for certificate in &ticks {
+            mmr.append(certificate.tick_hash).unwrap();
+        }
+        let root = mmr.root().unwrap();
+        let load = |tick: u64| {
+            Ok(ProvenTick {
+                certificate: ticks[tick as usize].clone(),
+                proof: mmr.proof(tick)?,
+            })
+        };
+
+        // Short ranges take every segment
+        assert_eq!(sample_ticks(&root, 90, 99), (91..=99).collect::<Vec<_>>());
+        let sampled = sample_ticks(&root, 0, 99);
+        assert!(sampled.len() < 99);
+        assert!(sampled.iter().all(|tick| (1..=99).contains(tick)));
+
+        let proof = TickRangeProof::build(&root, 0, 99, load).unwrap();
+        assert_eq!((proof.start(), proof.end()), (0, 99));
+        assert!(proof.verify(&root, K, DISCRIMINANT).is_ok());
+        assert!(proof.verify(&[7u8; 32], K, DISCRIMINANT).is_err());
+        assert!(proof.verify(&root, K + 1, DISCRIMINANT).is_err());
+        assert!(TickRangeProof::build(&root, 5, 4, load).is_err());
+
+        // Dropping a sampled segment, or swapping in another, is caught
+        let mut missing = proof.clone();
+        missing.segments.pop();
+        assert!(missing.verify(&root, K, DISCRIMINANT).is_err());
+        let mut swapped = proof.clone();
+        let unsampled = (1..=99).find(|tick| !sampled.contains(tick)).unwrap();
+        swapped.segments[0] = SampledSegment {
+            previous: load(unsampled - 1).unwrap(),
+            tick: load(unsampled).unwrap(),
+        };
+        assert!(swapped.verify(&root, K, DISCRIMINANT).is_err());
+    }
+
+    #[test]
+    fn test_portable_verifier_agrees() {
+        // kala-verify squares forms without GMP; it must accept exactly the
+        // proofs nodes build, read back from their JSON
+        let ticks = certificates(40);
+        let mut mmr = MerkleMountainRange::new();
+        for certificate in &ticks {
+            mmr.append(certificate.tick_hash).unwrap();
+        }
+        let root = mmr.root().unwrap();
+        let load = |tick: u64| {
+            Ok(ProvenTick {
+                certificate: ticks[tick as usize].clone(),
+                proof: mmr.proof(tick)?,
+            })
+        };
+        let proof = TickRangeProof::build(&root, 0, 39, load).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+
+        let portable: kala_verify::TickRangeProof = serde_json::from_str(&json).unwrap();
+        assert!(portable.verify(&root, K, DISCRIMINANT).is_ok());
+        assert!(portable.verify(&root, K + 1, DISCRIMINANT).is_err());
+        for certificate in &portable.segments {
+            let tick = &certificate.tick.certificate;
+            assert_eq!(tick.compute_hash(), tick.tick_hash);
+        }
+    }
+}
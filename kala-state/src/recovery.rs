@@ -0,0 +1,294 @@
+//! Guardian-based account recovery
+//!
+//! An account can name guardians and a threshold of them. If its key is
+//! lost, the guardians each request that the account move to a new owner;
+//! once `threshold` of them agree on the same owner, the recovery is
+//! scheduled a delay of ticks ahead. The chain's ticks are its clock, so the
+//! delay can't be cut short by anyone: the original key has until the
+//! activation tick to veto, which cancels the recovery and clears every
+//! approval. Guardians who collude therefore can't take an account whose
+//! owner is still watching.
+//!
+//! When the activation tick finalizes, the account's balance and stake move
+//! to the new owner as balance, along with transfers still vesting to it.
+//! The recovered account keeps its nonce and loses its guardians.
+
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::types::{BlockHeight, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Shortest delay an account may give its guardians, so the owner always
+/// has time to veto
+pub const MIN_RECOVERY_DELAY_TICKS: u64 = 1024;
+
+/// Guardians able to recover an account
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct GuardianSet {
+    pub guardians: Vec<Hash>,
+    /// Guardians that must agree on a new owner
+    pub threshold: u32,
+    /// Ticks from the threshold being met to the recovery taking effect
+    pub delay_ticks: u64,
+}
+
+/// Recovery agreed on by the guardians, waiting out its delay
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduledRecovery {
+    pub new_owner: Hash,
+    pub activation_tick: BlockHeight,
+}
+
+/// Guardian requests to recover an account
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PendingRecovery {
+    /// New owner each guardian asked for
+    pub approvals: BTreeMap<Hash, Hash>,
+    pub scheduled: Option<ScheduledRecovery>,
+}
+
+/// A recovery that took effect
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletedRecovery {
+    pub account: Hash,
+    pub new_owner: Hash,
+    /// Balance and stake moved to the new owner
+    pub amount: u64,
+}
+
+/// Guardians and pending recoveries by account
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recoveries {
+    guardians: BTreeMap<Hash, GuardianSet>,
+    pending: BTreeMap<Hash, PendingRecovery>,
+}
+
+impl Recoveries {
+    /// Replace the guardians of `account`, removing them if `guardians` is
+    /// empty
+    ///
+    /// Any pending recovery of the account is cancelled, its owner evidently
+    /// still holding the key.
+    pub fn set_guardians(
+        &mut self,
+        account: &Hash,
+        guardians: Vec<Hash>,
+        threshold: u32,
+        delay_ticks: u64,
+    ) -> KalaResult<()> {
+        Self::check_guardians(account, &guardians, threshold, delay_ticks)?;
+        self.pending.remove(account);
+        if guardians.is_empty() {
+            self.guardians.remove(account);
+        } else {
+            self.guardians.insert(
+                *account,
+                GuardianSet {
+                    guardians,
+                    threshold,
+                    delay_ticks,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Check `account` may name `guardians`
+    pub fn check_guardians(
+        account: &Hash,
+        guardians: &[Hash],
+        threshold: u32,
+        delay_ticks: u64,
+    ) -> KalaResult<()> {
+        if guardians.is_empty() {
+            return Ok(());
+        }
+        if threshold == 0 || threshold as usize > guardians.len() {
+            return Err(KalaError::validation(format!(
+                "Guardian threshold {} out of range for {} guardians",
+                threshold,
+                guardians.len()
+            )));
+        }
+        if delay_ticks < MIN_RECOVERY_DELAY_TICKS {
+            return Err(KalaError::validation(format!(
+                "Recovery delay of {} ticks is below {}",
+                delay_ticks, MIN_RECOVERY_DELAY_TICKS
+            )));
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardian == account {
+                return Err(KalaError::validation("Account cannot guard itself"));
+            }
+            if guardians[..i].contains(guardian) {
+                return Err(KalaError::validation("Duplicate guardian"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn guardians_of(&self, account: &Hash) -> Option<&GuardianSet> {
+        self.guardians.get(account)
+    }
+
+    pub fn pending(&self, account: &Hash) -> Option<&PendingRecovery> {
+        self.pending.get(account)
+    }
+
+    /// Check `guardian` may request that `account` move to `new_owner`
+    pub fn check_request(
+        &self,
+        guardian: &Hash,
+        account: &Hash,
+        new_owner: &Hash,
+    ) -> KalaResult<()> {
+        let set = self
+            .guardians
+            .get(account)
+            .ok_or_else(|| KalaError::state("Account has no guardians"))?;
+        if !set.guardians.contains(guardian) {
+            return Err(KalaError::validation("Sender is not a guardian of the account"));
+        }
+        if new_owner == account {
+            return Err(KalaError::validation("Account cannot be recovered to itself"));
+        }
+        if let Some(scheduled) = self.pending.get(account).and_then(|pending| pending.scheduled) {
+            return Err(KalaError::state(format!(
+                "Recovery already scheduled for tick {}",
+                scheduled.activation_tick
+            )));
+        }
+        Ok(())
+    }
+
+    /// Record `guardian`'s request, replacing any earlier one of theirs
+    ///
+    /// Returns the activation tick once the threshold agrees on
+    /// `new_owner`, counting from `current_tick`.
+    pub fn request(
+        &mut self,
+        guardian: &Hash,
+        account: &Hash,
+        new_owner: &Hash,
+        current_tick: BlockHeight,
+    ) -> KalaResult<Option<BlockHeight>> {
+        self.check_request(guardian, account, new_owner)?;
+        let Some(set) = self.guardians.get(account) else {
+            return Ok(None);
+        };
+        let pending = self.pending.entry(*account).or_default();
+        pending.approvals.insert(*guardian, *new_owner);
+
+        let agreeing = pending
+            .approvals
+            .values()
+            .filter(|owner| *owner == new_owner)
+            .count();
+        if agreeing < set.threshold as usize {
+            return Ok(None);
+        }
+        let activation_tick = current_tick.saturating_add(set.delay_ticks);
+        pending.scheduled = Some(ScheduledRecovery {
+            new_owner: *new_owner,
+            activation_tick,
+        });
+        Ok(Some(activation_tick))
+    }
+
+    /// Cancel the pending recovery of `account`
+    pub fn veto(&mut self, account: &Hash) -> KalaResult<()> {
+        self.pending
+            .remove(account)
+            .map(|_| ())
+            .ok_or_else(|| KalaError::state("No recovery pending"))
+    }
+
+    /// Remove the recoveries activating at or before `tick`, with the
+    /// recovered accounts' guardians
+    ///
+    /// Returns each recovered account with its new owner.
+    pub fn take_due(&mut self, tick: BlockHeight) -> Vec<(Hash, Hash)> {
+        let due: Vec<(Hash, Hash)> = self
+            .pending
+            .iter()
+            .filter_map(|(account, pending)| {
+                pending
+                    .scheduled
+                    .filter(|scheduled| scheduled.activation_tick <= tick)
+                    .map(|scheduled| (*account, scheduled.new_owner))
+            })
+            .collect();
+        for (account, _) in &due {
+            self.pending.remove(account);
+            self.guardians.remove(account);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: Hash = [1; 32];
+    const NEW_OWNER: Hash = [9; 32];
+
+    fn guarded() -> Recoveries {
+        let mut recoveries = Recoveries::default();
+        recoveries
+            .set_guardians(&OWNER, vec![[2; 32], [3; 32], [4; 32]], 2, MIN_RECOVERY_DELAY_TICKS)
+            .unwrap();
+        recoveries
+    }
+
+    #[test]
+    fn test_threshold_schedules_recovery() {
+        let mut recoveries = guarded();
+        assert!(recoveries.request(&[5; 32], &OWNER, &NEW_OWNER, 10).is_err());
+        assert_eq!(recoveries.request(&[2; 32], &OWNER, &NEW_OWNER, 10).unwrap(), None);
+        // A guardian asking for another owner doesn't count towards this one
+        assert_eq!(recoveries.request(&[3; 32], &OWNER, &[8; 32], 11).unwrap(), None);
+        let activation = recoveries.request(&[3; 32], &OWNER, &NEW_OWNER, 12).unwrap();
+        assert_eq!(activation, Some(12 + MIN_RECOVERY_DELAY_TICKS));
+        assert!(recoveries.request(&[4; 32], &OWNER, &[8; 32], 13).is_err());
+
+        assert!(recoveries.take_due(12 + MIN_RECOVERY_DELAY_TICKS - 1).is_empty());
+        let due = recoveries.take_due(12 + MIN_RECOVERY_DELAY_TICKS);
+        assert_eq!(due, vec![(OWNER, NEW_OWNER)]);
+        assert!(recoveries.guardians_of(&OWNER).is_none());
+        assert!(recoveries.pending(&OWNER).is_none());
+    }
+
+    #[test]
+    fn test_veto_and_reset() {
+        let mut recoveries = guarded();
+        assert!(recoveries.veto(&OWNER).is_err());
+        recoveries.request(&[2; 32], &OWNER, &NEW_OWNER, 10).unwrap();
+        recoveries.request(&[3; 32], &OWNER, &NEW_OWNER, 10).unwrap();
+        recoveries.veto(&OWNER).unwrap();
+        assert!(recoveries.take_due(u64::MAX).is_empty());
+
+        // Approvals start over after a veto, and changing guardians cancels
+        assert_eq!(recoveries.request(&[4; 32], &OWNER, &NEW_OWNER, 20).unwrap(), None);
+        recoveries
+            .set_guardians(&OWNER, vec![[4; 32]], 1, MIN_RECOVERY_DELAY_TICKS)
+            .unwrap();
+        assert!(recoveries.pending(&OWNER).is_none());
+        recoveries.set_guardians(&OWNER, Vec::new(), 0, 0).unwrap();
+        assert!(recoveries.guardians_of(&OWNER).is_none());
+    }
+
+    #[test]
+    fn test_check_guardians() {
+        let check = |guardians: &[Hash], threshold, delay| {
+            Recoveries::check_guardians(&OWNER, guardians, threshold, delay).is_ok()
+        };
+        assert!(check(&[[2; 32]], 1, MIN_RECOVERY_DELAY_TICKS));
+        assert!(!check(&[[2; 32]], 2, MIN_RECOVERY_DELAY_TICKS));
+        assert!(!check(&[[2; 32]], 0, MIN_RECOVERY_DELAY_TICKS));
+        assert!(!check(&[[2; 32]], 1, MIN_RECOVERY_DELAY_TICKS - 1));
+        assert!(!check(&[OWNER], 1, MIN_RECOVERY_DELAY_TICKS));
+        assert!(!check(&[[2; 32], [2; 32]], 1, MIN_RECOVERY_DELAY_TICKS));
+    }
+}
@@ -0,0 +1,202 @@
+//! Revealed key shares of threshold-locked envelopes
+//!
+//! Envelopes may lock their AES key by sharing it among the witnesses
+//! instead of behind an RSW puzzle. Once a tick's ordering is committed,
+//! each witness decrypts its share of every threshold-locked envelope in
+//! the tick and publishes it as a signed [`ShareReveal`]. Nodes collect the
+//! reveals in a [`ShareRevealPool`] and decrypt an envelope as soon as they
+//! hold enough shares.
+
+use bincode::{Decode, Encode};
+use kala_common::crypto::shamir::KeyShare;
+use kala_common::crypto::signer::{SignatureScheme, Signer};
+use kala_common::crypto::CryptoUtils;
+use kala_common::error::{KalaError, KalaResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::witness::WitnessSet;
+
+/// A witness's key share of an envelope, published after the ordering commitment
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct ShareReveal {
+    pub witness: [u8; 32],
+    pub tick_number: u64,
+    pub envelope_hash: [u8; 32],
+    pub share: KeyShare,
+    /// Ed25519 signature over [`ShareReveal::signing_message`]
+    pub signature: Vec<u8>,
+}
+
+impl ShareReveal {
+    /// Reveal `share` with the witness key behind `signer`
+    pub async fn sign(
+        tick_number: u64,
+        envelope_hash: [u8; 32],
+        share: KeyShare,
+        signer: &dyn Signer,
+    ) -> KalaResult<Self> {
+        let witness = signer
+            .public_key(SignatureScheme::Ed25519)
+            .try_into()
+            .map_err(|_| KalaError::crypto("Witness key must be 32 bytes"))?;
+        let mut reveal = Self {
+            witness,
+            tick_number,
+            envelope_hash,
+            share,
+            signature: Vec::new(),
+        };
+        reveal.signature = signer
+            .sign(SignatureScheme::Ed25519, &reveal.signing_message())
+            .await?;
+        Ok(reveal)
+    }
+
+    pub fn signing_message(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-share-reveal");
+        hasher.update(self.witness);
+        hasher.update(self.tick_number.to_le_bytes());
+        hasher.update(self.envelope_hash);
+        hasher.update([self.share.index]);
+        hasher.update(self.share.value);
+        hasher.finalize().into()
+    }
+
+    /// Check the reveal is from a member of `witnesses` and its signature is valid
+    ///
+    /// Whether the share belongs to the envelope is checked against the
+    /// envelope's commitments when it's opened.
+    pub fn verify(&self, witnesses: &WitnessSet) -> KalaResult<()> {
+        if witnesses.get(&self.witness).is_none() {
+            return Err(KalaError::validation(format!(
+                "{} is not a witness",
+                CryptoUtils::hash_to_hex(&self.witness)
+            )));
+        }
+        if !SignatureScheme::Ed25519.verify(&self.witness, &self.signing_message(), &self.signature) {
+            return Err(KalaError::crypto(format!(
+                "Share reveal from {} does not verify",
+                CryptoUtils::hash_to_hex(&self.witness)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Reveals collected per envelope until their tick is processed
+#[derive(Default, Debug)]
+pub struct ShareRevealPool {
+    envelopes: BTreeMap<(u64, [u8; 32]), Vec<ShareReveal>>,
+}
+
+impl ShareRevealPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a verified reveal from a member of `witnesses`
+    ///
+    /// Returns `Ok(false)` if the witness already revealed a share of the
+    /// envelope.
+    pub fn insert(&mut self, reveal: ShareReveal, witnesses: &WitnessSet) -> KalaResult<bool> {
+        reveal.verify(witnesses)?;
+        Ok(self.insert_verified(reveal))
+    }
+
+    /// Record a reveal already checked with [`ShareReveal::verify`], such as
+    /// this node's own
+    pub fn insert_verified(&mut self, reveal: ShareReveal) -> bool {
+        let reveals = self
+            .envelopes
+            .entry((reveal.tick_number, reveal.envelope_hash))
+            .or_default();
+        if reveals.iter().any(|seen| seen.witness == reveal.witness) {
+            return false;
+        }
+        reveals.push(reveal);
+        true
+    }
+
+    /// Shares of an envelope revealed so far
+    pub fn shares(&self, tick_number: u64, envelope_hash: &[u8; 32]) -> Vec<KeyShare> {
+        self.envelopes
+            .get(&(tick_number, *envelope_hash))
+            .map(|reveals| reveals.iter().map(|reveal| reveal.share.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop reveals for ticks before `tick_number`
+    pub fn prune(&mut self, tick_number: u64) {
+        self.envelopes = self.envelopes.split_off(&(tick_number, [0u8; 32]));
+    }
+
+    /// Number of envelopes with reveals
+    pub fn len(&self) -> usize {
+        self.envelopes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.envelopes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::{bls_public_key, derive_bls_key, Witness};
+    use kala_common::crypto::signer::InMemorySigner;
+
+    const ENVELOPE: [u8; 32] = [7u8; 32];
+
+    fn share(index: u8) -> KeyShare {
+        KeyShare {
+            index,
+            value: [index; 32],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reveal_pool() {
+        let signers: Vec<InMemorySigner> = (1..=2u8)
+            .map(|seed| InMemorySigner::from_seed(&[seed; 32]).unwrap())
+            .collect();
+        let witnesses = WitnessSet::new(
+            signers
+                .iter()
+                .map(|signer| Witness {
+                    key: signer.public_key(SignatureScheme::Ed25519).try_into().unwrap(),
+                    bls_key: bls_public_key(&derive_bls_key(&[1u8; 32]).unwrap()),
+                    weight: 1,
+                })
+                .collect(),
+        )
+        .unwrap();
+        let mut pool = ShareRevealPool::new();
+
+        let first = ShareReveal::sign(3, ENVELOPE, share(1), &signers[0]).await.unwrap();
+        assert!(pool.insert(first.clone(), &witnesses).unwrap());
+        assert!(!pool.insert(first.clone(), &witnesses).unwrap());
+
+        // Tampered shares no longer match the signature
+        let mut tampered = ShareReveal::sign(3, ENVELOPE, share(2), &signers[1]).await.unwrap();
+        tampered.share.value = [0u8; 32];
+        assert!(pool.insert(tampered, &witnesses).is_err());
+
+        let outsider = InMemorySigner::from_seed(&[9u8; 32]).unwrap();
+        let reveal = ShareReveal::sign(3, ENVELOPE, share(2), &outsider).await.unwrap();
+        assert!(pool.insert(reveal, &witnesses).is_err());
+
+        let second = ShareReveal::sign(3, ENVELOPE, share(2), &signers[1]).await.unwrap();
+        pool.insert(second, &witnesses).unwrap();
+        assert_eq!(pool.shares(3, &ENVELOPE), vec![share(1), share(2)]);
+        assert!(pool.shares(4, &ENVELOPE).is_empty());
+
+        pool.prune(3);
+        assert_eq!(pool.len(), 1);
+        pool.prune(4);
+        assert!(pool.is_empty());
+    }
+}
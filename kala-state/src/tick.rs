@@ -1,37 +1,583 @@
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
 use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{
+    decode_versioned, encode_versioned, CanonicalEncode, CanonicalEncoder, EncodingType,
+    KalaSerialize, SerializationError,
+};
+use kala_vdf::{derive_form_c, form_bytes, verify_form_transition, PackedForm, VDFCheckpoint};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
-#[derive(Serialize, Deserialize, Encode, Decode, Clone)]
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::usage::ModuleUsage;
+use crate::witness::{TickFinality, WitnessSet};
+
+/// Certificate of a finalized tick
+///
+/// The form is held packed, see [`form_bytes`], and served as hex strings.
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TickCertificate {
     pub tick_number: u64,
     pub tick_type: TickType,
     pub vdf_iteration: u64,
-    pub vdf_form: (String, String, String), // (a, b, c)
+    #[cfg_attr(feature = "schema", schemars(with = "(String, String, String)"))]
+    pub vdf_form: PackedForm, // (a, b, c)
     pub hash_chain_value: [u8; 32],
     pub tick_hash: [u8; 32],
     pub transaction_count: u32,
     pub transaction_merkle_root: [u8; 32],
     pub timestamp: u64,
     pub previous_tick_hash: [u8; 32],
+    /// Resources the tick's transactions used, by module
+    #[serde(default, skip_serializing_if = "ModuleUsage::is_empty")]
+    pub usage: ModuleUsage,
+}
+
+/// The fields of a [`TickCertificate`] that identify it and link it to
+/// the tick before it
+///
+/// Gossiped to peers too short of bandwidth for every certificate, which
+/// fetch the certificate itself only when they need to check it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TickHeader {
+    pub tick_number: u64,
+    pub vdf_iteration: u64,
+    pub tick_hash: [u8; 32],
+    pub previous_tick_hash: [u8; 32],
+    pub transaction_count: u32,
 }
 
 #[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TickType {
     Full,       // Contains validated transactions with consensus
-    Empty,      // Consensus achieved but no transactions included
-    Checkpoint, // No consensus - only VDF proof preserved
+    Empty,      // Witness-only: consensus achieved but no transactions included
+    Checkpoint, // Partial: no envelope has a quorum-agreed arrival,
+                // none are included - only VDF proof preserved
+}
+
+impl TickType {
+    /// Discriminant in the canonical encoding
+    pub fn canonical_id(&self) -> u8 {
+        match self {
+            TickType::Full => 0,
+            TickType::Empty => 1,
+            TickType::Checkpoint => 2,
+        }
+    }
 }
 
 impl TickCertificate {
+    /// Hash of the canonical encoding, which witnesses vote on
     pub fn compute_hash(&self) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(&self.tick_number.to_le_bytes());
-        hasher.update(&self.vdf_iteration.to_le_bytes());
-        hasher.update(self.vdf_form.0.as_bytes());
-        hasher.update(self.vdf_form.1.as_bytes());
-        hasher.update(self.vdf_form.2.as_bytes());
-        hasher.update(&self.hash_chain_value);
-        hasher.update(&self.transaction_merkle_root);
-        hasher.finalize().into()
+        self.canonical_hash()
+    }
+
+    pub fn header(&self) -> TickHeader {
+        TickHeader {
+            tick_number: self.tick_number,
+            vdf_iteration: self.vdf_iteration,
+            tick_hash: self.tick_hash,
+            previous_tick_hash: self.previous_tick_hash,
+            transaction_count: self.transaction_count,
+        }
+    }
+
+    /// The certificate with the output form's `c` left out
+    ///
+    /// `c` follows from the discriminant, `a` and `b`, see
+    /// [`derive_form_c`]. It is only left out if deriving it gives back the
+    /// same coefficient, so [`expand`](Self::expand) restores the certificate
+    /// exactly and its hash still verifies.
+    pub fn compact(&self, discriminant: &str) -> TickCertificate {
+        let mut compact = self.clone();
+        let (a, b, c) = &self.vdf_form;
+        if derive_form_c(discriminant, a, b).is_ok_and(|derived| derived == *c) {
+            compact.vdf_form.2 = Default::default();
+        }
+        compact
+    }
+
+    /// Whether the output form's `c` was left out by [`compact`](Self::compact)
+    pub fn is_compact(&self) -> bool {
+        self.vdf_form.2.is_empty()
+    }
+
+    /// Restore the output form's `c` if it was left out
+    pub fn expand(&mut self, discriminant: &str) -> KalaResult<()> {
+        if self.is_compact() {
+            let (a, b, _) = &self.vdf_form;
+            self.vdf_form.2 = derive_form_c(discriminant, a, b).map_err(|e| {
+                KalaError::validation(format!("Tick {}: {}", self.tick_number, e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Verify that `certificate` extends `previous`, the certificate of the
+/// tick before it
+///
+/// Without a previous certificate, `certificate` must be the first tick,
+/// starting from the generator of `discriminant`. Checks the hash, the
+/// linkage, and the iteration count, then recomputes the VDF segment, which
+/// takes as many squarings as producing the tick did. Needs nothing but the
+/// certificates and the chain parameters, so it runs without a node.
+pub fn verify_tick_certificate(
+    certificate: &TickCertificate,
+    previous: Option<&TickCertificate>,
+    iterations_per_tick: u64,
+    discriminant: &str,
+) -> KalaResult<()> {
+    let tick = certificate.tick_number;
+    if certificate.compute_hash() != certificate.tick_hash {
+        return Err(KalaError::validation(format!("Tick {} has an invalid hash", tick)));
+    }
+
+    let (previous_hash, start_iteration, start_form) = match previous {
+        Some(previous) => {
+            if previous.tick_number.checked_add(1) != Some(tick) {
+                return Err(KalaError::validation(format!(
+                    "Tick {} does not follow tick {}",
+                    tick, previous.tick_number
+                )));
+            }
+            (previous.tick_hash, previous.vdf_iteration, previous.vdf_form.clone())
+        }
+        None if tick == 0 => {
+            let genesis = VDFCheckpoint::genesis(discriminant, iterations_per_tick);
+            ([0u8; 32], 0, (genesis.form_a, genesis.form_b, genesis.form_c))
+        }
+        None => {
+            return Err(KalaError::validation(format!(
+                "Tick {} needs the certificate of the tick before it",
+                tick
+            )));
+        }
+    };
+    if certificate.previous_tick_hash != previous_hash {
+        return Err(KalaError::validation(format!(
+            "Tick {} does not link to the previous tick",
+            tick
+        )));
+    }
+    if start_iteration.checked_add(iterations_per_tick) != Some(certificate.vdf_iteration) {
+        return Err(KalaError::validation(format!(
+            "Tick {} ends at iteration {}, expected {}",
+            tick,
+            certificate.vdf_iteration,
+            start_iteration.saturating_add(iterations_per_tick)
+        )));
+    }
+    if !verify_form_transition(
+        discriminant,
+        &start_form,
+        iterations_per_tick,
+        &certificate.vdf_form,
+    ) {
+        return Err(KalaError::validation(format!("VDF output of tick {} does not verify", tick)));
+    }
+    Ok(())
+}
+
+/// What a verified run of certificates amounts to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ChainSummary {
+    pub first_tick: u64,
+    pub last_tick: u64,
+    /// Hash of the last certificate
+    pub head_hash: [u8; 32],
+    /// VDF iteration the last tick ends at
+    pub vdf_iteration: u64,
+    pub transaction_count: u64,
+    /// Least witness weight that signed any one tick
+    pub min_signed_weight: u64,
+}
+
+/// The first certificate at which a chain fails to verify
+#[derive(Debug)]
+pub struct ChainDivergence {
+    /// Position of the certificate in the chain
+    pub index: usize,
+    pub tick_number: u64,
+    pub error: KalaError,
+}
+
+impl fmt::Display for ChainDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Chain diverges at tick {} (certificate {}): {}",
+            self.tick_number, self.index, self.error
+        )
+    }
+}
+
+impl std::error::Error for ChainDivergence {}
+
+impl From<ChainDivergence> for KalaError {
+    fn from(divergence: ChainDivergence) -> Self {
+        KalaError::validation(divergence.to_string())
+    }
+}
+
+/// Verify a run of consecutive certificates and the witness signatures
+/// finalizing each
+///
+/// Each certificate is checked against the one before it with
+/// [`verify_tick_certificate`], so the hashes link, the tick numbers and
+/// VDF iterations are contiguous and every VDF segment is recomputed. Each
+/// must also have a [`TickFinality`] in `finality` signing its hash with a
+/// quorum of `witnesses`. `previous` is the trusted certificate before the
+/// first one, and must be given unless the chain starts at tick 0.
+/// Compact certificates must be expanded first.
+///
+/// Returns the first certificate that fails, checking them in order.
+pub fn verify_certificate_chain(
+    certificates: &[TickCertificate],
+    previous: Option<&TickCertificate>,
+    finality: &[TickFinality],
+    witnesses: &WitnessSet,
+    iterations_per_tick: u64,
+    discriminant: &str,
+) -> Result<ChainSummary, ChainDivergence> {
+    let first = certificates.first().ok_or_else(|| ChainDivergence {
+        index: 0,
+        tick_number: previous.map_or(0, |previous| previous.tick_number.saturating_add(1)),
+        error: KalaError::validation("No certificates to verify"),
+    })?;
+    let finality: HashMap<u64, &TickFinality> =
+        finality.iter().map(|finality| (finality.tick_number, finality)).collect();
+
+    let mut summary = ChainSummary {
+        first_tick: first.tick_number,
+        last_tick: first.tick_number,
+        head_hash: first.tick_hash,
+        vdf_iteration: first.vdf_iteration,
+        transaction_count: 0,
+        min_signed_weight: u64::MAX,
+    };
+    let mut previous = previous;
+    for (index, certificate) in certificates.iter().enumerate() {
+        let tick = certificate.tick_number;
+        let signed_weight = verify_tick_certificate(
+            certificate,
+            previous,
+            iterations_per_tick,
+            discriminant,
+        )
+        .and_then(|()| {
+            let finality = finality.get(&tick).ok_or_else(|| {
+                KalaError::validation(format!("Tick {} is not finalized", tick))
+            })?;
+            if finality.tick_hash != certificate.tick_hash {
+                return Err(KalaError::validation(format!(
+                    "Witnesses finalized another hash at tick {}",
+                    tick
+                )));
+            }
+            finality.verify(witnesses)
+        })
+        .map_err(|error| ChainDivergence {
+            index,
+            tick_number: tick,
+            error,
+        })?;
+
+        summary.last_tick = tick;
+        summary.head_hash = certificate.tick_hash;
+        summary.vdf_iteration = certificate.vdf_iteration;
+        summary.transaction_count += certificate.transaction_count as u64;
+        summary.min_signed_weight = summary.min_signed_weight.min(signed_weight);
+        previous = Some(certificate);
+    }
+    Ok(summary)
+}
+
+/// Fields in declaration order, leaving out `tick_hash`, which is the hash
+/// of this encoding, and `timestamp`, which is each node's own clock
+///
+/// The usage is covered by its canonical hash, appended only when the tick
+/// used something, so ticks recorded before usage was tracked hash as
+/// before.
+impl CanonicalEncode for TickCertificate {
+    const DOMAIN: &'static str = "kala-tick-v1";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder
+            .u64(self.tick_number)
+            .u8(self.tick_type.canonical_id())
+            .u64(self.vdf_iteration)
+            .str(&self.vdf_form.0.to_hex())
+            .str(&self.vdf_form.1.to_hex())
+            .str(&self.vdf_form.2.to_hex())
+            .fixed(&self.hash_chain_value)
+            .u32(self.transaction_count)
+            .fixed(&self.transaction_merkle_root)
+            .fixed(&self.previous_tick_hash);
+        if !self.usage.is_empty() {
+            encoder.fixed(&self.usage.canonical_hash());
+        }
+    }
+}
+
+/// Stored versioned, with the form packed. Certificates stored as JSON by
+/// older nodes still load.
+impl KalaSerialize for TickCertificate {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Stored for every tick
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, SerializationError> {
+        encode_versioned(self)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, SerializationError> {
+        decode_versioned(bytes)
+    }
+}
+
+impl Encode for TickCertificate {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        Encode::encode(&self.tick_number, encoder)?;
+        Encode::encode(&self.tick_type, encoder)?;
+        Encode::encode(&self.vdf_iteration, encoder)?;
+        let (a, b, c) = &self.vdf_form;
+        form_bytes::encode_bincode(a, b, c, encoder)?;
+        Encode::encode(&self.hash_chain_value, encoder)?;
+        Encode::encode(&self.tick_hash, encoder)?;
+        Encode::encode(&self.transaction_count, encoder)?;
+        Encode::encode(&self.transaction_merkle_root, encoder)?;
+        Encode::encode(&self.timestamp, encoder)?;
+        Encode::encode(&self.previous_tick_hash, encoder)?;
+        Encode::encode(&self.usage, encoder)
+    }
+}
+
+impl<Context> Decode<Context> for TickCertificate {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let tick_number: u64 = Decode::decode(decoder)?;
+        let tick_type: TickType = Decode::decode(decoder)?;
+        let vdf_iteration: u64 = Decode::decode(decoder)?;
+        let vdf_form = form_bytes::decode_bincode(decoder)?;
+        Ok(Self {
+            tick_number,
+            tick_type,
+            vdf_iteration,
+            vdf_form,
+            hash_chain_value: Decode::decode(decoder)?,
+            tick_hash: Decode::decode(decoder)?,
+            transaction_count: Decode::decode(decoder)?,
+            transaction_merkle_root: Decode::decode(decoder)?,
+            timestamp: Decode::decode(decoder)?,
+            previous_tick_hash: Decode::decode(decoder)?,
+            usage: Decode::decode(decoder)?,
+        })
+    }
+}
+
+bincode::impl_borrow_decode!(TickCertificate);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kala_common::crypto::CryptoUtils;
+    use kala_common::types::consensus::DEFAULT_DISCRIMINANT;
+    use kala_vdf::EternalVDF;
+    use crate::witness::{bls_public_key, derive_bls_key, TickVote, Witness};
+
+    fn certificate() -> TickCertificate {
+        TickCertificate {
+            tick_number: 42,
+            tick_type: TickType::Full,
+            vdf_iteration: 65536,
+            vdf_form: ("2".into(), "1".into(), "-3".into()),
+            hash_chain_value: [1u8; 32],
+            tick_hash: [0u8; 32],
+            transaction_count: 3,
+            transaction_merkle_root: [2u8; 32],
+            timestamp: 1_700_000_000,
+            previous_tick_hash: [3u8; 32],
+            usage: ModuleUsage::default(),
+        }
+    }
+
+    #[test]
+    fn test_certificate_hash_golden_vector() {
+        let certificate = certificate();
+        assert_eq!(
+            CryptoUtils::hash_to_hex(&certificate.compute_hash()),
+            "1fb518ec0603b84d48e97dc5bd8887737ebd3126d774a72150c4a990761c7eb9"
+        );
+
+        // Neither the hash itself nor the local timestamp are covered
+        let mut restamped = certificate.clone();
+        restamped.timestamp += 60;
+        restamped.tick_hash = certificate.compute_hash();
+        assert_eq!(restamped.compute_hash(), certificate.compute_hash());
+
+        let mut checkpoint = certificate.clone();
+        checkpoint.tick_type = TickType::Checkpoint;
+        assert_ne!(checkpoint.compute_hash(), certificate.compute_hash());
+
+        // Form components can't be shifted into each other
+        let mut shifted = certificate.clone();
+        shifted.vdf_form = ("21".into(), Default::default(), "-3".into());
+        assert_ne!(shifted.compute_hash(), certificate.compute_hash());
+
+        // Usage is covered once the tick used something
+        let mut used = certificate.clone();
+        used.usage.record_native("bank");
+        assert_ne!(used.compute_hash(), certificate.compute_hash());
+    }
+
+    #[test]
+    fn test_verify_tick_certificate() {
+        let k = 4;
+        let mut vdf = EternalVDF::with_tick_size(DEFAULT_DISCRIMINANT, k);
+        let mut certificates: Vec<TickCertificate> = Vec::new();
+        for tick_number in 0..2 {
+            vdf.advance(k);
+            let mut certificate = certificate();
+            certificate.tick_number = tick_number;
+            certificate.vdf_iteration = vdf.get_iteration();
+            certificate.vdf_form = vdf.get_form_values();
+            certificate.previous_tick_hash = certificates.last().map_or([0u8; 32], |c| c.tick_hash);
+            certificate.tick_hash = certificate.compute_hash();
+            certificates.push(certificate);
+        }
+        let (first, second) = (&certificates[0], &certificates[1]);
+
+        assert!(verify_tick_certificate(first, None, k, DEFAULT_DISCRIMINANT).is_ok());
+        assert!(verify_tick_certificate(second, Some(first), k, DEFAULT_DISCRIMINANT).is_ok());
+        assert!(verify_tick_certificate(second, None, k, DEFAULT_DISCRIMINANT).is_err());
+        assert!(verify_tick_certificate(second, Some(second), k, DEFAULT_DISCRIMINANT).is_err());
+        assert!(verify_tick_certificate(second, Some(first), k + 1, DEFAULT_DISCRIMINANT).is_err());
+
+        // A forged output fails even with a consistent hash
+        let mut forged = second.clone();
+        forged.vdf_form = first.vdf_form.clone();
+        forged.tick_hash = forged.compute_hash();
+        assert!(verify_tick_certificate(&forged, Some(first), k, DEFAULT_DISCRIMINANT).is_err());
+
+        // Bincode packs the form without changing what it decodes to
+        let config = bincode::config::standard();
+        let bytes = bincode::encode_to_vec(second, config).unwrap();
+        let (decoded, _): (TickCertificate, _) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded.vdf_form, second.vdf_form);
+        assert!(verify_tick_certificate(&decoded, Some(first), k, DEFAULT_DISCRIMINANT).is_ok());
+    }
+
+    #[test]
+    fn test_verify_certificate_chain() {
+        let k = 4;
+        let keys: Vec<_> = (1..=3u8).map(|i| derive_bls_key(&[i; 32]).unwrap()).collect();
+        let witnesses = WitnessSet::new(
+            keys.iter()
+                .enumerate()
+                .map(|(i, key)| Witness {
+                    key: [i as u8 + 1; 32],
+                    bls_key: bls_public_key(key),
+                    weight: 1,
+                })
+                .collect(),
+        )
+        .unwrap();
+        let finalize = |certificate: &TickCertificate, signers: usize| {
+            let votes: Vec<TickVote> = keys[..signers]
+                .iter()
+                .enumerate()
+                .map(|(i, key)| {
+                    let witness = [i as u8 + 1; 32];
+                    TickVote::sign(certificate.tick_number, certificate.tick_hash, witness, key)
+                })
+                .collect();
+            TickFinality::aggregate(&witnesses, &votes).unwrap()
+        };
+
+        let mut vdf = EternalVDF::with_tick_size(DEFAULT_DISCRIMINANT, k);
+        let mut certificates: Vec<TickCertificate> = Vec::new();
+        for tick_number in 0..3 {
+            vdf.advance(k);
+            let mut certificate = certificate();
+            certificate.tick_number = tick_number;
+            certificate.vdf_iteration = vdf.get_iteration();
+            certificate.vdf_form = vdf.get_form_values();
+            certificate.previous_tick_hash = certificates.last().map_or([0u8; 32], |c| c.tick_hash);
+            certificate.tick_hash = certificate.compute_hash();
+            certificates.push(certificate);
+        }
+        let mut finality: Vec<TickFinality> =
+            certificates.iter().map(|certificate| finalize(certificate, 3)).collect();
+        let verify = |certificates: &[TickCertificate],
+                      previous: Option<&TickCertificate>,
+                      finality: &[TickFinality]| {
+            verify_certificate_chain(
+                certificates,
+                previous,
+                finality,
+                &witnesses,
+                k,
+                DEFAULT_DISCRIMINANT,
+            )
+        };
+
+        let summary = verify(&certificates, None, &finality).unwrap();
+        assert_eq!((summary.first_tick, summary.last_tick), (0, 2));
+        assert_eq!(summary.head_hash, certificates[2].tick_hash);
+        assert_eq!(summary.vdf_iteration, 3 * k);
+        assert_eq!(summary.transaction_count, 9);
+        assert_eq!(summary.min_signed_weight, 3);
+
+        // A suffix verifies from its trusted predecessor
+        let summary = verify(&certificates[1..], Some(&certificates[0]), &finality).unwrap();
+        assert_eq!(summary.first_tick, 1);
+        assert_eq!(verify(&certificates[1..], None, &finality).unwrap_err().index, 0);
+        assert!(verify(&[], None, &finality).is_err());
+
+        // Too few signers at tick 1
+        finality[1] = finalize(&certificates[1], 2);
+        let divergence = verify(&certificates, None, &finality).unwrap_err();
+        assert_eq!((divergence.index, divergence.tick_number), (1, 1));
+        finality[1] = finalize(&certificates[1], 3);
+
+        // A gap, and a finality for another hash
+        let gapped = [certificates[0].clone(), certificates[2].clone()];
+        assert_eq!(verify(&gapped, None, &finality).unwrap_err().tick_number, 2);
+        let mut forked = certificates.clone();
+        forked[2].transaction_count = 0;
+        forked[2].tick_hash = forked[2].compute_hash();
+        let divergence = verify(&forked, None, &finality).unwrap_err();
+        assert_eq!(divergence.index, 2);
+        assert!(KalaError::from(divergence).to_string().contains("tick 2"));
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        let mut vdf = EternalVDF::with_tick_size(DEFAULT_DISCRIMINANT, 4);
+        vdf.advance(4);
+        let mut full = certificate();
+        full.vdf_form = vdf.get_form_values();
+        full.tick_hash = full.compute_hash();
+
+        let mut compact = full.compact(DEFAULT_DISCRIMINANT);
+        assert!(compact.is_compact());
+        assert_eq!(compact.header(), full.header());
+        compact.expand(DEFAULT_DISCRIMINANT).unwrap();
+        assert_eq!(compact.vdf_form, full.vdf_form);
+        assert_eq!(compact.compute_hash(), full.tick_hash);
+
+        // A form whose c can't be derived as written is sent whole
+        let verbatim = certificate().compact(DEFAULT_DISCRIMINANT);
+        assert_eq!(verbatim.vdf_form, certificate().vdf_form);
+
+        let mut forged = full.compact(DEFAULT_DISCRIMINANT);
+        forged.vdf_form.1 = "0x2".into();
+        assert!(forged.expand(DEFAULT_DISCRIMINANT).is_err());
     }
 }
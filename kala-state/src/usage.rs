@@ -0,0 +1,102 @@
+//! Resource usage by module
+//!
+//! Every tick certificate records how much of each module its transactions
+//! used, and epoch summaries add up the usage of their ticks. Governance
+//! sizes per-module limits from these figures: a module whose usage keeps
+//! approaching its limit needs more room, one far below it can give some up.
+//!
+//! Usage only covers what a tick's transactions determine, so every node
+//! records the same figures and certificates hash alike everywhere. Oracle
+//! updates depend on the votes in each node's own finality records, so they
+//! are tallied per epoch outside the summary hash instead, see
+//! [`EpochStats::oracle_updates`](crate::EpochStats::oracle_updates).
+
+use bincode::{Decode, Encode};
+use kala_common::serialization::{CanonicalEncode, CanonicalEncoder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Resources used, by module
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ModuleUsage {
+    /// Native transactions applied, by module name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub native_transactions: BTreeMap<String, u64>,
+    /// Gas metered by contract calls, zero while no module runs contracts
+    #[serde(default)]
+    pub contract_gas: u64,
+}
+
+impl ModuleUsage {
+    /// Whether nothing was used
+    pub fn is_empty(&self) -> bool {
+        self.native_transactions.is_empty() && self.contract_gas == 0
+    }
+
+    /// Count a native transaction of `module`
+    pub fn record_native(&mut self, module: &str) {
+        let count = self.native_transactions.entry(module.to_string()).or_default();
+        *count = count.saturating_add(1);
+    }
+
+    /// Add `other`'s usage to this
+    pub fn add(&mut self, other: &ModuleUsage) {
+        for (module, count) in &other.native_transactions {
+            let total = self.native_transactions.entry(module.clone()).or_default();
+            *total = total.saturating_add(*count);
+        }
+        self.contract_gas = self.contract_gas.saturating_add(other.contract_gas);
+    }
+
+    /// Native transactions across all modules
+    pub fn native_total(&self) -> u64 {
+        self.native_transactions
+            .values()
+            .fold(0u64, |total, count| total.saturating_add(*count))
+    }
+}
+
+/// The number of modules, each module's name and count in name order, then
+/// the contract gas
+impl CanonicalEncode for ModuleUsage {
+    const DOMAIN: &'static str = "kala-module-usage-v1";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder.u32(self.native_transactions.len() as u32);
+        for (module, count) in &self.native_transactions {
+            encoder.str(module).u64(*count);
+        }
+        encoder.u64(self.contract_gas);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_add() {
+        let mut tick = ModuleUsage::default();
+        assert!(tick.is_empty());
+        tick.record_native("bank");
+        tick.record_native("bank");
+        tick.record_native("staking");
+        assert_eq!(tick.native_total(), 3);
+
+        let mut epoch = ModuleUsage::default();
+        epoch.add(&tick);
+        epoch.add(&ModuleUsage {
+            native_transactions: BTreeMap::from([("auction".to_string(), 4)]),
+            contract_gas: 7,
+        });
+        assert_eq!(epoch.native_transactions["bank"], 2);
+        assert_eq!(epoch.native_transactions["auction"], 4);
+        assert_eq!((epoch.native_total(), epoch.contract_gas), (7, 7));
+
+        // Module names can't run into their counts
+        let mut renamed = tick.clone();
+        renamed.native_transactions = BTreeMap::from([("bankstaking".to_string(), 3)]);
+        assert_ne!(renamed.canonical_hash(), tick.canonical_hash());
+    }
+}
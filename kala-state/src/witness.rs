@@ -0,0 +1,948 @@
+//! Witness set and aggregated tick signatures
+//!
+//! Each witness signs every tick hash and gossips the resulting
+//! [`TickVote`]. Once the signers hold more than 2/3 of the witness weight,
+//! their votes are folded into a [`TickFinality`]: a bitmap of signers and
+//! their [`WitnessSignatures`]. The witness set decides the scheme. With
+//! BLS12-381, the default, the signatures aggregate into one and the
+//! finality record stays the same size however many witnesses there are;
+//! small sets may vote with Ed25519 instead, which is cheaper to verify.
+//!
+//! A witness that signs two hashes for the same tick is caught by the pair
+//! of votes itself: [`DoubleSignEvidence`] verifies without trusting
+//! whoever reports it.
+//!
+//! Witnesses may attach a small [`VoteMetadata`] blob to their votes, such
+//! as a price feed reading or the head of another chain. The blob is signed
+//! separately with the witness's Ed25519 key, so votes on one hash still
+//! aggregate, and it travels into the [`TickFinality`] alongside the
+//! signatures. The timeline then doubles as a transport for oracle data,
+//! attributed to the witness that reported it.
+//!
+//! At ticks chosen for time anchors, witnesses also sign their clock
+//! reading as a [`VoteTime`]. It is kept apart from the metadata blob, so
+//! anchors don't compete with oracle reports for it, and combined into a
+//! [`TimeAnchor`](crate::anchor::TimeAnchor) once the tick is finalized.
+//!
+//! BLS signatures use the message-augmentation scheme of
+//! [`kala_common::crypto::bls`], which makes aggregates safe against
+//! rogue-key attacks without proofs of possession.
+
+use bincode::{Decode, Encode};
+use kala_common::crypto::bls::{self, SecretKey};
+use kala_common::crypto::signer::{SignatureScheme, Signer};
+use kala_common::crypto::CryptoUtils;
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::quorum::QuorumCalculator;
+use kala_common::serialization::{EncodingType, KalaSerialize};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub use kala_common::crypto::bls::{BLS_PUBLIC_KEY_SIZE, BLS_SIGNATURE_SIZE};
+
+/// BLS secret key type used by witnesses
+pub use kala_common::crypto::bls::SecretKey as BlsSecretKey;
+
+
+/// Derive a witness's BLS secret key from its 32-byte witness seed
+pub fn derive_bls_key(seed: &[u8; 32]) -> KalaResult<SecretKey> {
+    bls::keygen(seed)
+}
+
+/// Compressed public key for a BLS secret key
+pub fn bls_public_key(key: &SecretKey) -> Vec<u8> {
+    bls::public_key(key)
+}
+
+/// A member of the witness set
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct Witness {
+    /// Ed25519 public key identifying the witness
+    pub key: [u8; 32],
+    /// Compressed BLS12-381 public key used for tick votes
+    pub bls_key: Vec<u8>,
+    pub weight: u64,
+}
+
+impl Witness {
+    /// Public key the witness votes with under `scheme`
+    pub fn public_key(&self, scheme: SignatureScheme) -> &[u8] {
+        match scheme {
+            SignatureScheme::Ed25519 => &self.key,
+            SignatureScheme::Bls12381 => &self.bls_key,
+        }
+    }
+}
+
+/// The witnesses whose votes finalize ticks, ordered by key
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct WitnessSet {
+    witnesses: Vec<Witness>,
+    /// Scheme the witnesses sign tick votes with
+    #[serde(default)]
+    scheme: SignatureScheme,
+}
+
+impl WitnessSet {
+    pub fn new(mut witnesses: Vec<Witness>) -> KalaResult<Self> {
+        if witnesses.is_empty() {
+            return Err(KalaError::validation("Witness set cannot be empty"));
+        }
+
+        witnesses.sort_by_key(|witness| witness.key);
+        for pair in witnesses.windows(2) {
+            if pair[0].key == pair[1].key {
+                return Err(KalaError::validation(format!(
+                    "Duplicate witness {}",
+                    CryptoUtils::hash_to_hex(&pair[0].key)
+                )));
+            }
+        }
+        for witness in &mut witnesses {
+            if witness.weight == 0 {
+                return Err(KalaError::validation(format!(
+                    "Witness {} has zero weight",
+                    CryptoUtils::hash_to_hex(&witness.key)
+                )));
+            }
+            // Votes are signed over the compressed key, so store it in that form
+            witness.bls_key = bls::validate_public_key(&witness.bls_key).map_err(|e| {
+                KalaError::crypto(format!(
+                    "Invalid BLS key for witness {}: {}",
+                    CryptoUtils::hash_to_hex(&witness.key),
+                    e
+                ))
+            })?;
+        }
+        witnesses
+            .iter()
+            .try_fold(0u64, |total, witness| total.checked_add(witness.weight))
+            .ok_or_else(|| KalaError::validation("Total witness weight overflows"))?;
+
+        Ok(Self {
+            witnesses,
+            scheme: SignatureScheme::default(),
+        })
+    }
+
+    /// Vote with `scheme` instead of BLS12-381
+    pub fn with_scheme(mut self, scheme: SignatureScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    pub fn witnesses(&self) -> &[Witness] {
+        &self.witnesses
+    }
+
+    /// Scheme the witnesses sign tick votes with
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    /// Ed25519 keys of all witnesses, sorted
+    pub fn keys(&self) -> Vec<[u8; 32]> {
+        self.witnesses.iter().map(|witness| witness.key).collect()
+    }
+
+    pub fn get(&self, key: &[u8; 32]) -> Option<&Witness> {
+        self.index_of(key).map(|index| &self.witnesses[index])
+    }
+
+    pub fn index_of(&self, key: &[u8; 32]) -> Option<usize> {
+        self.witnesses
+            .binary_search_by_key(key, |witness| witness.key)
+            .ok()
+    }
+
+    pub fn total_weight(&self) -> u64 {
+        self.witnesses.iter().map(|witness| witness.weight).sum()
+    }
+
+    /// Fault threshold and quorum size of the set's weight
+    pub fn quorum(&self) -> QuorumCalculator {
+        QuorumCalculator::new(self.total_weight())
+    }
+
+    /// Whether `weight` is more than 2/3 of the total
+    pub fn is_quorum(&self, weight: u64) -> bool {
+        self.quorum().is_quorum(weight)
+    }
+
+    /// Largest faulty weight the set tolerates while keeping a quorum (< 1/3)
+    pub fn byzantine_threshold(&self) -> u64 {
+        self.quorum().max_faults()
+    }
+
+    /// Weigh `candidates` by the stake delegated to their keys
+    ///
+    /// Candidates with less than `min_stake` delegated are left out.
+    /// Returns `None` if no candidate qualifies.
+    pub fn from_stake(
+        candidates: &[Witness],
+        stake: &BTreeMap<[u8; 32], u64>,
+        min_stake: u64,
+    ) -> KalaResult<Option<Self>> {
+        let witnesses: Vec<Witness> = candidates
+            .iter()
+            .filter_map(|candidate| {
+                let weight = stake.get(&candidate.key).copied().unwrap_or(0);
+                (weight >= min_stake.max(1)).then(|| Witness {
+                    weight,
+                    ..candidate.clone()
+                })
+            })
+            .collect();
+
+        if witnesses.is_empty() {
+            return Ok(None);
+        }
+        Self::new(witnesses).map(Some)
+    }
+}
+
+/// Message a witness signs to vote for `tick_hash` at `tick_number`
+pub fn tick_message(tick_number: u64, tick_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"kala-tick-vote");
+    hasher.update(tick_number.to_le_bytes());
+    hasher.update(tick_hash);
+    hasher.finalize().into()
+}
+
+/// Largest metadata blob a witness may attach to a tick vote
+pub const MAX_VOTE_METADATA_SIZE: usize = 1024;
+
+/// Message a witness signs to attach `data` to its vote for `tick_hash`
+pub fn metadata_message(tick_number: u64, tick_hash: &[u8; 32], data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"kala-vote-metadata");
+    hasher.update(tick_number.to_le_bytes());
+    hasher.update(tick_hash);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Message a witness signs to report its clock read `time_ms` when it
+/// voted for `tick_hash`
+pub fn time_message(tick_number: u64, tick_hash: &[u8; 32], time_ms: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"kala-vote-time");
+    hasher.update(tick_number.to_le_bytes());
+    hasher.update(tick_hash);
+    hasher.update(time_ms.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Blob a witness attaches to its tick vote
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VoteMetadata {
+    pub data: Vec<u8>,
+    /// Ed25519 signature of the witness over [`metadata_message`]
+    pub signature: Vec<u8>,
+}
+
+impl VoteMetadata {
+    /// Check the size and the signature of `witness` over the blob
+    pub fn verify(
+        &self,
+        tick_number: u64,
+        tick_hash: &[u8; 32],
+        witness: &Witness,
+    ) -> KalaResult<()> {
+        if self.data.len() > MAX_VOTE_METADATA_SIZE {
+            return Err(KalaError::validation(format!(
+                "Vote metadata of {} bytes exceeds {}",
+                self.data.len(),
+                MAX_VOTE_METADATA_SIZE
+            )));
+        }
+        let message = metadata_message(tick_number, tick_hash, &self.data);
+        if !SignatureScheme::Ed25519.verify(&witness.key, &message, &self.signature) {
+            return Err(KalaError::crypto(format!(
+                "Vote metadata from {} on tick {} does not verify",
+                CryptoUtils::hash_to_hex(&witness.key),
+                tick_number
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Clock reading a witness attaches to its tick vote
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VoteTime {
+    /// Unix time in milliseconds
+    pub time_ms: u64,
+    /// Ed25519 signature of the witness over [`time_message`]
+    pub signature: Vec<u8>,
+}
+
+impl VoteTime {
+    /// Check the signature of `witness` over the reading
+    pub fn verify(
+        &self,
+        tick_number: u64,
+        tick_hash: &[u8; 32],
+        witness: &Witness,
+    ) -> KalaResult<()> {
+        let message = time_message(tick_number, tick_hash, self.time_ms);
+        if !SignatureScheme::Ed25519.verify(&witness.key, &message, &self.signature) {
+            return Err(KalaError::crypto(format!(
+                "Clock reading from {} on tick {} does not verify",
+                CryptoUtils::hash_to_hex(&witness.key),
+                tick_number
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One witness's signature on a tick hash, in its witness set's scheme
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct TickVote {
+    pub tick_number: u64,
+    pub tick_hash: [u8; 32],
+    pub witness: [u8; 32],
+    pub signature: Vec<u8>,
+    /// Signed blob the witness attached, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<VoteMetadata>,
+    /// Signed clock reading, at ticks chosen for time anchors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<VoteTime>,
+}
+
+impl TickVote {
+    /// Vote with a BLS key
+    pub fn sign(tick_number: u64, tick_hash: [u8; 32], witness: [u8; 32], key: &SecretKey) -> Self {
+        let message = tick_message(tick_number, &tick_hash);
+        Self {
+            tick_number,
+            tick_hash,
+            witness,
+            signature: bls::sign(key, &message),
+            metadata: None,
+            time: None,
+        }
+    }
+
+    /// Vote with the witness keys behind `signer`, signing with `scheme`
+    pub async fn sign_with(
+        tick_number: u64,
+        tick_hash: [u8; 32],
+        scheme: SignatureScheme,
+        signer: &dyn Signer,
+    ) -> KalaResult<Self> {
+        let witness = signer
+            .public_key(SignatureScheme::Ed25519)
+            .try_into()
+            .map_err(|_| KalaError::crypto("Witness key must be 32 bytes"))?;
+        let message = tick_message(tick_number, &tick_hash);
+        let signature = signer.sign(scheme, &message).await?;
+        Ok(Self {
+            tick_number,
+            tick_hash,
+            witness,
+            signature,
+            metadata: None,
+            time: None,
+        })
+    }
+
+    /// Attach `data` to the vote, signed with the Ed25519 key behind `signer`
+    pub async fn attach_metadata(&mut self, data: Vec<u8>, signer: &dyn Signer) -> KalaResult<()> {
+        if data.len() > MAX_VOTE_METADATA_SIZE {
+            return Err(KalaError::validation(format!(
+                "Vote metadata of {} bytes exceeds {}",
+                data.len(),
+                MAX_VOTE_METADATA_SIZE
+            )));
+        }
+        let message = metadata_message(self.tick_number, &self.tick_hash, &data);
+        let signature = signer.sign(SignatureScheme::Ed25519, &message).await?;
+        self.metadata = Some(VoteMetadata { data, signature });
+        Ok(())
+    }
+
+    /// Attach the clock reading `time_ms`, signed with the Ed25519 key
+    /// behind `signer`
+    pub async fn attach_time(&mut self, time_ms: u64, signer: &dyn Signer) -> KalaResult<()> {
+        let message = time_message(self.tick_number, &self.tick_hash, time_ms);
+        let signature = signer.sign(SignatureScheme::Ed25519, &message).await?;
+        self.time = Some(VoteTime { time_ms, signature });
+        Ok(())
+    }
+
+    /// Check the vote is from a member of `witnesses` and its signature is valid
+    pub fn verify(&self, witnesses: &WitnessSet) -> KalaResult<()> {
+        let witness = witnesses.get(&self.witness).ok_or_else(|| {
+            KalaError::validation(format!(
+                "{} is not a witness",
+                CryptoUtils::hash_to_hex(&self.witness)
+            ))
+        })?;
+        let scheme = witnesses.scheme();
+        let message = tick_message(self.tick_number, &self.tick_hash);
+        if !scheme.verify(witness.public_key(scheme), &message, &self.signature) {
+            return Err(KalaError::crypto(format!(
+                "Vote from {} on tick {} does not verify under {:?}",
+                CryptoUtils::hash_to_hex(&self.witness),
+                self.tick_number,
+                scheme
+            )));
+        }
+        if let Some(metadata) = &self.metadata {
+            metadata.verify(self.tick_number, &self.tick_hash, witness)?;
+        }
+        if let Some(time) = &self.time {
+            time.verify(self.tick_number, &self.tick_hash, witness)?;
+        }
+        Ok(())
+    }
+}
+
+/// Two valid votes by one witness for different hashes at the same tick
+///
+/// The votes are kept in hash order, so every node that sees the same
+/// conflict builds the same evidence.
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct DoubleSignEvidence {
+    pub first: TickVote,
+    pub second: TickVote,
+}
+
+impl DoubleSignEvidence {
+    /// Pair two votes, or `None` if they don't conflict
+    pub fn new(a: TickVote, b: TickVote) -> Option<Self> {
+        if a.witness != b.witness || a.tick_number != b.tick_number || a.tick_hash == b.tick_hash {
+            return None;
+        }
+        let (first, second) = if a.tick_hash < b.tick_hash { (a, b) } else { (b, a) };
+        Some(Self { first, second })
+    }
+
+    pub fn witness(&self) -> [u8; 32] {
+        self.first.witness
+    }
+
+    pub fn tick_number(&self) -> u64 {
+        self.first.tick_number
+    }
+
+    /// Check the votes conflict and both are signed by a member of `witnesses`
+    pub fn verify(&self, witnesses: &WitnessSet) -> KalaResult<()> {
+        if self.first.witness != self.second.witness
+            || self.first.tick_number != self.second.tick_number
+            || self.first.tick_hash >= self.second.tick_hash
+        {
+            return Err(KalaError::validation(
+                "Evidence votes must be for different hashes at one tick, in hash order",
+            ));
+        }
+        self.first.verify(witnesses)?;
+        self.second.verify(witnesses)
+    }
+}
+
+/// Signatures of the witnesses in a [`TickFinality`] bitmap
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum WitnessSignatures {
+    /// One signature per signer, in witness order
+    Ed25519(Vec<Vec<u8>>),
+    /// Aggregate of every signer's signature, one signature in size
+    Bls12381(Vec<u8>),
+}
+
+impl WitnessSignatures {
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            WitnessSignatures::Ed25519(_) => SignatureScheme::Ed25519,
+            WitnessSignatures::Bls12381(_) => SignatureScheme::Bls12381,
+        }
+    }
+
+    /// Total size of the signatures in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            WitnessSignatures::Ed25519(signatures) => signatures.iter().map(Vec::len).sum(),
+            WitnessSignatures::Bls12381(signature) => signature.len(),
+        }
+    }
+}
+
+/// Metadata a signer attached to its vote, carried in a [`TickFinality`]
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WitnessMetadata {
+    pub witness: [u8; 32],
+    #[serde(flatten)]
+    pub metadata: VoteMetadata,
+}
+
+/// Clock reading a signer attached to its vote, carried in a [`TickFinality`]
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WitnessTime {
+    pub witness: [u8; 32],
+    #[serde(flatten)]
+    pub time: VoteTime,
+}
+
+/// Witness signatures finalizing a tick
+#[derive(Serialize, Deserialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TickFinality {
+    pub tick_number: u64,
+    pub tick_hash: [u8; 32],
+    /// Bitmap of signers by index in the witness set
+    pub signers: Vec<u8>,
+    /// Signatures of all signers, in the witness set's scheme
+    pub signatures: WitnessSignatures,
+    /// Metadata attached by signers, in witness order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<WitnessMetadata>,
+    /// Clock readings attached by signers, in witness order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub times: Vec<WitnessTime>,
+}
+
+impl TickFinality {
+    /// Combine votes for one tick hash; votes are assumed to be verified
+    pub fn aggregate(witnesses: &WitnessSet, votes: &[TickVote]) -> KalaResult<Self> {
+        let first = votes
+            .first()
+            .ok_or_else(|| KalaError::validation("No votes to aggregate"))?;
+
+        let mut signers = vec![0u8; witnesses.witnesses().len().div_ceil(8)];
+        let mut signed = BTreeMap::new();
+        let mut metadata = BTreeMap::new();
+        let mut times = BTreeMap::new();
+        for vote in votes {
+            if (vote.tick_number, vote.tick_hash) != (first.tick_number, first.tick_hash) {
+                return Err(KalaError::validation("Votes are for different ticks"));
+            }
+            let index = witnesses.index_of(&vote.witness).ok_or_else(|| {
+                KalaError::validation(format!(
+                    "{} is not a witness",
+                    CryptoUtils::hash_to_hex(&vote.witness)
+                ))
+            })?;
+            if signers[index / 8] & (1 << (index % 8)) != 0 {
+                return Err(KalaError::validation("Duplicate vote in aggregate"));
+            }
+            signers[index / 8] |= 1 << (index % 8);
+            signed.insert(index, vote.signature.clone());
+            if let Some(attached) = &vote.metadata {
+                metadata.insert(
+                    index,
+                    WitnessMetadata {
+                        witness: vote.witness,
+                        metadata: attached.clone(),
+                    },
+                );
+            }
+            if let Some(time) = &vote.time {
+                times.insert(
+                    index,
+                    WitnessTime {
+                        witness: vote.witness,
+                        time: time.clone(),
+                    },
+                );
+            }
+        }
+
+        let signatures: Vec<Vec<u8>> = signed.into_values().collect();
+        let signatures = match witnesses.scheme() {
+            SignatureScheme::Ed25519 => WitnessSignatures::Ed25519(signatures),
+            SignatureScheme::Bls12381 => WitnessSignatures::Bls12381(bls::aggregate(&signatures)?),
+        };
+
+        Ok(Self {
+            tick_number: first.tick_number,
+            tick_hash: first.tick_hash,
+            signers,
+            signatures,
+            metadata: metadata.into_values().collect(),
+            times: times.into_values().collect(),
+        })
+    }
+
+    /// Witnesses marked in the signer bitmap
+    pub fn signers<'a>(&self, witnesses: &'a WitnessSet) -> Vec<&'a Witness> {
+        witnesses
+            .witnesses()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                self.signers
+                    .get(index / 8)
+                    .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+            })
+            .map(|(_, witness)| witness)
+            .collect()
+    }
+
+    /// Verify the signatures and quorum, returning the signed weight
+    pub fn verify(&self, witnesses: &WitnessSet) -> KalaResult<u64> {
+        if self.signers.len() != witnesses.witnesses().len().div_ceil(8) {
+            return Err(KalaError::validation(
+                "Signer bitmap does not match the witness set",
+            ));
+        }
+        let scheme = witnesses.scheme();
+        if self.signatures.scheme() != scheme {
+            return Err(KalaError::validation(format!(
+                "Finality for tick {} is signed with {:?}, witnesses vote with {:?}",
+                self.tick_number,
+                self.signatures.scheme(),
+                scheme
+            )));
+        }
+        let signers = self.signers(witnesses);
+        let weight: u64 = signers.iter().map(|witness| witness.weight).sum();
+        if !witnesses.is_quorum(weight) {
+            return Err(KalaError::validation(format!(
+                "Tick {} signed by {} of {} witness weight",
+                self.tick_number,
+                weight,
+                witnesses.total_weight()
+            )));
+        }
+
+        let message = tick_message(self.tick_number, &self.tick_hash);
+        let valid = match &self.signatures {
+            WitnessSignatures::Ed25519(signatures) => {
+                signatures.len() == signers.len()
+                    && signers.iter().zip(signatures).all(|(witness, signature)| {
+                        scheme.verify(witness.public_key(scheme), &message, signature)
+                    })
+            }
+            WitnessSignatures::Bls12381(signature) => {
+                let public_keys: Vec<&[u8]> =
+                    signers.iter().map(|witness| witness.bls_key.as_slice()).collect();
+                bls::aggregate_verify(&public_keys, &message, signature)
+            }
+        };
+        if !valid {
+            return Err(KalaError::crypto(format!(
+                "Finality signatures for tick {} do not verify",
+                self.tick_number
+            )));
+        }
+
+        // Metadata and clock readings must come from signers, at most once
+        // each and in witness order
+        let signer_index = |key: &[u8; 32], previous: &mut Option<usize>| {
+            let index = witnesses
+                .index_of(key)
+                .filter(|index| self.signers[index / 8] & (1 << (index % 8)) != 0)
+                .ok_or_else(|| {
+                    KalaError::validation(format!(
+                        "Attachment from {}, which did not sign tick {}",
+                        CryptoUtils::hash_to_hex(key),
+                        self.tick_number
+                    ))
+                })?;
+            if *previous >= Some(index) {
+                return Err(KalaError::validation("Finality attachments out of witness order"));
+            }
+            *previous = Some(index);
+            Ok(&witnesses.witnesses()[index])
+        };
+        let mut previous = None;
+        for entry in &self.metadata {
+            let witness = signer_index(&entry.witness, &mut previous)?;
+            entry.metadata.verify(self.tick_number, &self.tick_hash, witness)?;
+        }
+        let mut previous = None;
+        for entry in &self.times {
+            let witness = signer_index(&entry.witness, &mut previous)?;
+            entry.time.verify(self.tick_number, &self.tick_hash, witness)?;
+        }
+        Ok(weight)
+    }
+}
+
+impl KalaSerialize for TickFinality {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Json // Served alongside tick certificates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn witness(seed: u8, weight: u64) -> (Witness, SecretKey) {
+        let key = derive_bls_key(&[seed; 32]).unwrap();
+        let witness = Witness {
+            key: [seed; 32],
+            bls_key: bls_public_key(&key),
+            weight,
+        };
+        (witness, key)
+    }
+
+    fn witness_set(weights: &[u64]) -> (WitnessSet, Vec<SecretKey>) {
+        let (witnesses, keys): (Vec<_>, Vec<_>) = weights
+            .iter()
+            .enumerate()
+            .map(|(i, weight)| witness(i as u8 + 1, *weight))
+            .unzip();
+        (WitnessSet::new(witnesses).unwrap(), keys)
+    }
+
+    fn vote(index: usize, keys: &[SecretKey], tick_hash: [u8; 32]) -> TickVote {
+        TickVote::sign(7, tick_hash, [index as u8 + 1; 32], &keys[index])
+    }
+
+    #[test]
+    fn test_witness_set_validation() {
+        let (a, _) = witness(1, 1);
+        assert!(WitnessSet::new(Vec::new()).is_err());
+        assert!(WitnessSet::new(vec![a.clone(), a.clone()]).is_err());
+        assert!(WitnessSet::new(vec![Witness {
+            weight: 0,
+            ..a.clone()
+        }])
+        .is_err());
+        assert!(WitnessSet::new(vec![Witness {
+            bls_key: vec![1; 48],
+            ..a
+        }])
+        .is_err());
+
+        let (set, _) = witness_set(&[1, 1, 1]);
+        assert!(!set.is_quorum(2));
+        assert!(set.is_quorum(3));
+        assert_eq!(set.byzantine_threshold(), 0);
+        let (set, _) = witness_set(&[3, 2, 2]);
+        assert_eq!(set.byzantine_threshold(), 2);
+        assert!(set.is_quorum(set.total_weight() - set.byzantine_threshold()));
+        assert_eq!(set.quorum().quorum(), 5);
+    }
+
+    #[test]
+    fn test_witness_set_from_stake() {
+        let candidates: Vec<_> = (1..=3).map(|seed| witness(seed, 1).0).collect();
+        let mut stake = BTreeMap::new();
+        assert!(WitnessSet::from_stake(&candidates, &stake, 10).unwrap().is_none());
+
+        stake.insert([1; 32], 50);
+        stake.insert([2; 32], 5);
+        stake.insert([3; 32], 10);
+        // Stake delegated to a key without a candidate entry is ignored
+        stake.insert([4; 32], 100);
+
+        let set = WitnessSet::from_stake(&candidates, &stake, 10).unwrap().unwrap();
+        assert_eq!(set.keys(), vec![[1; 32], [3; 32]]);
+        assert_eq!(set.get(&[1; 32]).unwrap().weight, 50);
+        assert_eq!(set.total_weight(), 60);
+        assert_eq!(set.byzantine_threshold(), 19);
+    }
+
+    #[test]
+    fn test_votes_verify_against_witness_set() {
+        let (set, keys) = witness_set(&[1, 1]);
+        let good = vote(0, &keys, [9; 32]);
+        assert!(good.verify(&set).is_ok());
+
+        // Signed by the wrong key or for a different hash
+        let forged = TickVote {
+            witness: [2; 32],
+            ..good.clone()
+        };
+        assert!(forged.verify(&set).is_err());
+        let moved = TickVote {
+            tick_hash: [8; 32],
+            ..good
+        };
+        assert!(moved.verify(&set).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_with_matches_derived_keys() {
+        use kala_common::crypto::signer::InMemorySigner;
+
+        let seed = [4u8; 32];
+        let signer = InMemorySigner::from_seed(&seed).unwrap();
+        let witness = signer.public_key(SignatureScheme::Ed25519).try_into().unwrap();
+        let vote = TickVote::sign_with(3, [9; 32], SignatureScheme::Bls12381, &signer)
+            .await
+            .unwrap();
+        assert_eq!(vote, TickVote::sign(3, [9; 32], witness, &derive_bls_key(&seed).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_vote_metadata_carried_into_finality() {
+        use kala_common::crypto::signer::InMemorySigner;
+
+        let signers: Vec<_> = (1..=4u8)
+            .map(|seed| InMemorySigner::from_seed(&[seed; 32]).unwrap())
+            .collect();
+        let set = WitnessSet::new(
+            signers
+                .iter()
+                .map(|signer| Witness {
+                    key: signer.public_key(SignatureScheme::Ed25519).try_into().unwrap(),
+                    bls_key: signer.public_key(SignatureScheme::Bls12381),
+                    weight: 1,
+                })
+                .collect(),
+        )
+        .unwrap();
+
+        let mut votes = Vec::new();
+        for (i, signer) in signers.iter().enumerate() {
+            let mut vote = TickVote::sign_with(3, [9; 32], SignatureScheme::Bls12381, signer)
+                .await
+                .unwrap();
+            if i > 0 {
+                vote.attach_metadata(format!("price:{}", i).into_bytes(), signer).await.unwrap();
+            }
+            assert!(vote.verify(&set).is_ok());
+            votes.push(vote);
+        }
+        assert!(votes[0]
+            .clone()
+            .attach_metadata(vec![0; MAX_VOTE_METADATA_SIZE + 1], &signers[0])
+            .await
+            .is_err());
+
+        // Tampered metadata fails the vote
+        let mut tampered = votes[1].clone();
+        tampered.metadata.as_mut().unwrap().data = b"price:0".to_vec();
+        assert!(tampered.verify(&set).is_err());
+
+        // Votes still aggregate, and the metadata travels in witness order
+        votes.reverse();
+        let finality = TickFinality::aggregate(&set, &votes).unwrap();
+        assert_eq!(finality.verify(&set).unwrap(), 4);
+        let index = |entry: &WitnessMetadata| set.index_of(&entry.witness).unwrap();
+        assert!(finality.metadata.windows(2).all(|pair| index(&pair[0]) < index(&pair[1])));
+        assert_eq!(finality.metadata.len(), 3);
+
+        // Metadata from a witness outside the signers is rejected
+        let partial = TickFinality::aggregate(&set, &votes[1..]).unwrap();
+        assert_eq!(partial.verify(&set).unwrap(), 3);
+        let mut forged = partial.clone();
+        let outsider = votes[0].metadata.clone().unwrap();
+        forged.metadata.push(WitnessMetadata {
+            witness: votes[0].witness,
+            metadata: outsider,
+        });
+        assert!(forged.verify(&set).is_err());
+    }
+
+    #[test]
+    fn test_double_sign_evidence() {
+        let (set, keys) = witness_set(&[1, 1]);
+        let a = vote(0, &keys, [9; 32]);
+        let b = vote(0, &keys, [3; 32]);
+
+        assert!(DoubleSignEvidence::new(a.clone(), a.clone()).is_none());
+        assert!(DoubleSignEvidence::new(a.clone(), vote(1, &keys, [3; 32])).is_none());
+
+        // Either order gives the same evidence
+        let evidence = DoubleSignEvidence::new(a.clone(), b.clone()).unwrap();
+        assert_eq!(evidence, DoubleSignEvidence::new(b.clone(), a.clone()).unwrap());
+        assert_eq!(evidence.first.tick_hash, [3; 32]);
+        assert_eq!(evidence.witness(), [1; 32]);
+        assert!(evidence.verify(&set).is_ok());
+
+        // A forged vote can't frame an honest witness
+        let framed = DoubleSignEvidence {
+            second: TickVote {
+                tick_hash: [10; 32],
+                ..a
+            },
+            ..evidence
+        };
+        assert!(framed.verify(&set).is_err());
+    }
+
+    #[test]
+    fn test_finality_requires_weighted_quorum() {
+        // One heavy witness outweighs the other two combined
+        let (set, keys) = witness_set(&[5, 1, 1]);
+        let hash = [4; 32];
+
+        let light =
+            TickFinality::aggregate(&set, &[vote(1, &keys, hash), vote(2, &keys, hash)]).unwrap();
+        assert!(light.verify(&set).is_err());
+
+        let finality =
+            TickFinality::aggregate(&set, &[vote(0, &keys, hash), vote(2, &keys, hash)]).unwrap();
+        assert_eq!(finality.signatures.size(), BLS_SIGNATURE_SIZE);
+        assert_eq!(finality.verify(&set).unwrap(), 6);
+
+        // Claiming an extra signer breaks the aggregate
+        let mut inflated = finality.clone();
+        inflated.signers[0] |= 0b010;
+        assert!(inflated.verify(&set).is_err());
+
+        assert!(
+            TickFinality::aggregate(&set, &[vote(0, &keys, hash), vote(0, &keys, hash)]).is_err()
+        );
+        assert!(
+            TickFinality::aggregate(&set, &[vote(0, &keys, hash), vote(1, &keys, [5; 32])])
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ed25519_finality() {
+        use kala_common::crypto::signer::InMemorySigner;
+
+        let signers: Vec<_> = (1..=3)
+            .map(|seed| InMemorySigner::from_seed(&[seed; 32]).unwrap())
+            .collect();
+        let witnesses: Vec<_> = signers
+            .iter()
+            .map(|signer| Witness {
+                key: signer.public_key(SignatureScheme::Ed25519).try_into().unwrap(),
+                bls_key: signer.public_key(SignatureScheme::Bls12381),
+                weight: 1,
+            })
+            .collect();
+        let set = WitnessSet::new(witnesses).unwrap().with_scheme(SignatureScheme::Ed25519);
+
+        let mut votes = Vec::new();
+        for signer in &signers {
+            let vote = TickVote::sign_with(7, [4; 32], SignatureScheme::Ed25519, signer)
+                .await
+                .unwrap();
+            assert!(vote.verify(&set).is_ok());
+            votes.push(vote);
+        }
+
+        // BLS votes don't verify against an Ed25519 set
+        let bls_vote = TickVote::sign_with(7, [4; 32], SignatureScheme::Bls12381, &signers[0])
+            .await
+            .unwrap();
+        assert!(bls_vote.verify(&set).is_err());
+
+        let finality = TickFinality::aggregate(&set, &votes).unwrap();
+        assert_eq!(finality.signatures.scheme(), SignatureScheme::Ed25519);
+        assert_eq!(finality.signatures.size(), 3 * 64);
+        assert_eq!(finality.verify(&set).unwrap(), 3);
+
+        // A swapped signature fails even with the right signers
+        let mut swapped = finality.clone();
+        if let WitnessSignatures::Ed25519(signatures) = &mut swapped.signatures {
+            signatures.swap(0, 1);
+        }
+        assert!(swapped.verify(&set).is_err());
+
+        // The record must match the set's scheme
+        let bls_set = set.clone().with_scheme(SignatureScheme::Bls12381);
+        assert!(finality.verify(&bls_set).is_err());
+    }
+}
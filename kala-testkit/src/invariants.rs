@@ -0,0 +1,218 @@
+//! Ordering-before-decryption invariants of a tick
+//!
+//! Timelock envelopes protect against MEV only if the tick's order is
+//! fixed before anyone can read them. [`TestNode::check_pipeline`]
+//! follows a [`SubmittedBatch`] through its target tick using only what
+//! the node serves over RPC, the transaction traces and the tick's
+//! timestamped data, and fails on the first violation.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use kala_rpc::{TimestampProof, TimestampVerification, TraceStage, TransactionTrace};
+use kala_transaction::TimelockTransaction;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::{SubmittedBatch, TestNode};
+
+/// What a batch's target tick did with it
+#[derive(Debug, Clone)]
+pub struct PipelineReport {
+    /// Indices into the batch, in the order the tick committed to
+    pub order: Vec<usize>,
+    /// Iteration each envelope was ordered by, by batch index
+    pub ordering_iterations: Vec<u64>,
+    /// Iteration the ordering commitment was timestamped at
+    pub commitment_iteration: u64,
+    /// Whether each envelope was decrypted, by batch index
+    pub decrypted: Vec<bool>,
+    /// Whether each transaction was applied, by batch index
+    pub applied: Vec<bool>,
+}
+
+/// The ordering commitment a tick timestamps for `ordered` envelopes
+///
+/// Written independently of the consensus code it checks: the SHA-256
+/// hash of `"ordering"` followed, for each envelope in order, by its
+/// submission iteration and target tick (8 bytes little-endian each), its
+/// AES-GCM nonce and tag, and the SHA-256 hash of its ciphertext.
+pub fn ordering_commitment<'a>(
+    ordered: impl IntoIterator<Item = &'a TimelockTransaction>,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"ordering");
+    for envelope in ordered {
+        hasher.update(envelope.submission_iteration.to_le_bytes());
+        hasher.update(envelope.target_tick.to_le_bytes());
+        hasher.update(envelope.encrypted_data.nonce);
+        hasher.update(envelope.encrypted_data.tag);
+        hasher.update(Sha256::digest(&envelope.encrypted_data.ciphertext));
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Position in the committed order, from an `Ordered` event's detail
+fn ordered_position(detail: Option<&str>) -> Option<usize> {
+    detail?.strip_prefix("position ")?.split(' ').next()?.parse().ok()
+}
+
+impl TestNode {
+    /// Check that `batch` was ordered and committed before decryption
+    ///
+    /// Waits for the batch's target tick to be finalized, then checks that:
+    ///
+    /// - each envelope is `Ordered` in the target tick, and before that
+    ///   tick decrypts or applies it;
+    /// - the batch makes up the whole tick, at consecutive positions;
+    /// - the positions follow the iterations the envelopes were ordered
+    ///   by, ties broken by envelope hash, and an envelope ordered by its
+    ///   local arrival is ordered by the iteration it was submitted at;
+    /// - the tick timestamped the ordering commitment of the envelopes in
+    ///   that order, with a proof the node verifies, after the collection
+    ///   phase and before the earliest iteration any puzzle can be solved.
+    ///
+    /// # Parameters
+    /// * `batch` - Envelopes submitted with [`TestNode::submit_batch`]
+    ///
+    /// # Returns
+    /// What the tick did with the batch, for further checks
+    pub async fn check_pipeline(&self, batch: &SubmittedBatch) -> Result<PipelineReport> {
+        let tick = batch.target_tick;
+        let k = self.params().iterations_per_tick;
+        self.wait_for_tick(tick).await?;
+
+        let mut positions = Vec::with_capacity(batch.envelopes.len());
+        let mut ordering_iterations = Vec::with_capacity(batch.envelopes.len());
+        let mut decrypted = Vec::with_capacity(batch.envelopes.len());
+        let mut applied = Vec::with_capacity(batch.envelopes.len());
+        for (envelope, response) in batch.envelopes.iter().zip(&batch.responses) {
+            ensure!(
+                hex::encode(envelope.envelope_hash()) == response.tx_hash,
+                "Node hashed envelope {} differently",
+                response.tx_hash
+            );
+            let trace: TransactionTrace = self
+                .call::<Option<_>>("kala_traceTransaction", json!([response.tx_hash]))
+                .await?
+                .ok_or_else(|| anyhow!("No trace of {}", response.tx_hash))?;
+
+            let ordered_at = trace
+                .events
+                .iter()
+                .position(|event| event.stage == TraceStage::Ordered)
+                .ok_or_else(|| anyhow!("{} was never ordered", response.tx_hash))?;
+            let ordered = &trace.events[ordered_at];
+            ensure!(
+                ordered.tick_number == tick,
+                "{} was ordered in tick {} instead of {}",
+                response.tx_hash,
+                ordered.tick_number,
+                tick
+            );
+            for (index, event) in trace.events.iter().enumerate() {
+                let opened = matches!(
+                    event.stage,
+                    TraceStage::Decrypted | TraceStage::DecryptionFailed | TraceStage::Applied
+                );
+                ensure!(
+                    !opened || index > ordered_at,
+                    "{} reached {:?} before it was ordered",
+                    response.tx_hash,
+                    event.stage
+                );
+            }
+
+            let detail = ordered.detail.as_deref();
+            let position = ordered_position(detail)
+                .with_context(|| format!("Unexpected Ordered detail {:?}", detail))?;
+            let iteration = ordered
+                .iteration
+                .ok_or_else(|| anyhow!("{} was ordered without an iteration", response.tx_hash))?;
+            if detail.is_some_and(|detail| detail.ends_with("local arrival")) {
+                ensure!(
+                    iteration == response.submission_iteration,
+                    "{} was ordered by iteration {} but arrived at {}",
+                    response.tx_hash,
+                    iteration,
+                    response.submission_iteration
+                );
+            }
+            positions.push(position);
+            ordering_iterations.push(iteration);
+            let reached = |stage| trace.events.iter().any(|event| event.stage == stage);
+            decrypted.push(reached(TraceStage::Decrypted));
+            applied.push(reached(TraceStage::Applied));
+        }
+
+        // Positions are a permutation of the batch, in arrival order
+        let mut order: Vec<usize> = (0..batch.envelopes.len()).collect();
+        order.sort_by_key(|&index| positions[index]);
+        for (expected, &index) in order.iter().enumerate() {
+            ensure!(
+                positions[index] == expected,
+                "Tick {} holds envelopes outside the batch, or repeats a position",
+                tick
+            );
+        }
+        for pair in order.windows(2) {
+            let key = |index: usize| {
+                (ordering_iterations[index], batch.envelopes[index].envelope_hash())
+            };
+            ensure!(
+                key(pair[0]) < key(pair[1]),
+                "{} is ordered before {}, which arrived first",
+                batch.responses[pair[0]].tx_hash,
+                batch.responses[pair[1]].tx_hash
+            );
+        }
+
+        // The commitment to that order is in the tick's timestamped data
+        let commitment = ordering_commitment(order.iter().map(|&index| &batch.envelopes[index]));
+        let data_hash = hex::encode(Sha256::digest(&commitment));
+        let proof: TimestampProof = self
+            .call::<Option<_>>(
+                "kala_proveTickData",
+                json!([{ "tick_number": tick, "data_hash": data_hash }]),
+            )
+            .await?
+            .ok_or_else(|| anyhow!("Tick {} never timestamped the ordering commitment", tick))?;
+        ensure!(
+            proof.data == hex::encode(&commitment),
+            "Tick {} served different commitment data",
+            tick
+        );
+        let verification: TimestampVerification =
+            self.call("kala_verifyTimestamp", json!([proof])).await?;
+        ensure!(
+            verification.valid,
+            "Proof of the ordering commitment doesn't verify: {:?}",
+            verification.error
+        );
+
+        let collection_end = tick * k + self.params().phases.collection_end;
+        ensure!(
+            proof.iteration > collection_end,
+            "Order committed at iteration {}, before collection ended at {}",
+            proof.iteration,
+            collection_end
+        );
+        for index in 0..batch.envelopes.len() {
+            if let Some(decrypt_at) = batch.decrypt_at(index) {
+                ensure!(
+                    proof.iteration < decrypt_at,
+                    "Order committed at iteration {}, after {} could be decrypted at {}",
+                    proof.iteration,
+                    batch.responses[index].tx_hash,
+                    decrypt_at
+                );
+            }
+        }
+
+        Ok(PipelineReport {
+            order,
+            ordering_iterations,
+            commitment_iteration: proof.iteration,
+            decrypted,
+            applied,
+        })
+    }
+}
@@ -0,0 +1,521 @@
+//! # Kala Testkit
+//!
+//! End-to-end tests of the consensus path against a real node.
+//!
+//! [`TestNode`] runs a [`KalaNode`] in-process on a temporary database,
+//! with a tick small enough to finish in a couple of seconds and the real
+//! VDF, and talks to it only over JSON-RPC. Tests seal transactions from
+//! [`TestAccount`]s in RSW timelock envelopes, submit them together for an
+//! upcoming tick with [`TestNode::submit_batch`], and then check with
+//! [`TestNode::check_pipeline`] that the node fixed and committed their
+//! order before any of them could be decrypted:
+//!
+//! - every envelope is ordered in its target tick before it is decrypted
+//!   or applied;
+//! - the order follows when the envelopes arrived, never what they contain;
+//! - the ordering commitment timestamped in the tick's data matches the
+//!   envelopes in that order, and was hashed into the VDF before the
+//!   earliest iteration any of their puzzles could be solved at.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use kala_testkit::{TestAccount, TestNode};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let node = TestNode::start().await?;
+//! let batch = node
+//!     .submit_batch(|| async {
+//!         let account = TestAccount::new(node.next_seed())?;
+//!         let nonce = node.next_nonce(&account).await?;
+//!         Ok(vec![account.mint(node.chain_id(), 1_000, 0, nonce).await?])
+//!     })
+//!     .await?;
+//! let report = node.check_pipeline(&batch).await?;
+//! assert_eq!(report.order.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod invariants;
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use kala_common::crypto::signer::{InMemorySigner, SignatureScheme, Signer};
+use kala_common::error::KalaResult;
+use kala_core::{KalaNode, NodeConfig};
+use kala_rpc::{
+    AccountInfo, ChainInfo, ChainParams, SubmitTransactionRequest, SubmitTransactionResponse,
+};
+use kala_transaction::{seal_transaction, KeyLock, Mint, TimelockTransaction, Transaction};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use tokio::task::JoinHandle;
+
+pub use invariants::{ordering_commitment, PipelineReport};
+
+/// Iterations per tick of a [`TestNode`], about two seconds of real VDF
+pub const TEST_ITERATIONS_PER_TICK: u64 = 16_384;
+
+/// Attempts [`TestNode::submit_batch`] makes before giving up
+const SUBMIT_ATTEMPTS: usize = 5;
+
+/// Time between polls of the node's progress
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// Longest wait for the node to reach a point on its timeline
+const WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A node running in-process, driven over JSON-RPC
+///
+/// The node's task is aborted and its database deleted when this is
+/// dropped.
+pub struct TestNode {
+    client: reqwest::Client,
+    url: String,
+    params: ChainParams,
+    next_id: AtomicU64,
+    next_seed: AtomicU64,
+    task: JoinHandle<()>,
+    /// Why the node stopped, once it has
+    stopped: Arc<Mutex<Option<String>>>,
+    _db: TempDir,
+}
+
+/// Envelopes accepted together for the same tick
+pub struct SubmittedBatch {
+    /// Tick every envelope targets
+    pub target_tick: u64,
+    /// The envelopes, in the order they were submitted, with the
+    /// submission iteration the node assigned them
+    pub envelopes: Vec<TimelockTransaction>,
+    /// The node's response to each submission
+    pub responses: Vec<SubmitTransactionResponse>,
+    /// The transactions sealed in the envelopes
+    pub transactions: Vec<Transaction>,
+}
+
+impl SubmittedBatch {
+    /// Earliest iteration the puzzle of the `index`th envelope can be solved at
+    pub fn decrypt_at(&self, index: usize) -> Option<u64> {
+        let envelope = &self.envelopes[index];
+        match &envelope.lock {
+            KeyLock::Puzzle(puzzle) => {
+                Some(envelope.submission_iteration + puzzle.hardness as u64)
+            }
+            KeyLock::Threshold(_) => None,
+        }
+    }
+}
+
+impl TestNode {
+    /// Start a node with the test defaults
+    ///
+    /// # Returns
+    /// The node, once its RPC server answers
+    pub async fn start() -> Result<Self> {
+        Self::start_with(|_| {}).await
+    }
+
+    /// Start a node with the test defaults adjusted by `configure`
+    ///
+    /// The defaults are [`TEST_ITERATIONS_PER_TICK`], a small timelock
+    /// hardness factor, no GPU, no peers, no metrics, no VDF calibration
+    /// runs competing with the timeline for the CPU, and minting open to
+    /// every account so [`TestAccount::mint`] can fund it.
+    ///
+    /// # Parameters
+    /// * `configure` - Changes to make to the configuration; the database
+    ///   path and RPC port are overwritten afterwards
+    ///
+    /// # Returns
+    /// The node, once its RPC server answers
+    pub async fn start_with(configure: impl FnOnce(&mut NodeConfig)) -> Result<Self> {
+        let db = tempfile::tempdir()?;
+        let mut config = NodeConfig {
+            iterations_per_tick: TEST_ITERATIONS_PER_TICK,
+            timelock_hardness_factor: 0.05,
+            enable_gpu: false,
+            vdf_calibration_interval_secs: 0,
+            dev_open_mint: true,
+            log_level: "warn".to_string(),
+            ..Default::default()
+        };
+        configure(&mut config);
+        config.db_path = db.path().join("db").to_string_lossy().into_owned();
+        config.rpc_bind_addr = "127.0.0.1".to_string();
+        config.rpc_port = free_port()?;
+        config
+            .validate()
+            .map_err(|e| anyhow!("Test node config is invalid: {}", e))?;
+
+        let url = format!("http://127.0.0.1:{}", config.rpc_port);
+        let node = Arc::new(KalaNode::new(config).await?);
+        let stopped = Arc::new(Mutex::new(None));
+        let task = tokio::spawn({
+            let stopped = stopped.clone();
+            async move {
+                let reason = match node.run().await {
+                    Ok(()) => "Node stopped".to_string(),
+                    Err(e) => format!("Node failed: {:#}", e),
+                };
+                *stopped.lock().unwrap() = Some(reason);
+            }
+        });
+
+        let client = reqwest::Client::new();
+        let started = Instant::now();
+        let params = loop {
+            let request = json!({
+                "jsonrpc": "2.0",
+                "method": "kala_getChainParams",
+                "params": [],
+                "id": 0,
+            });
+            if let Ok(response) = client.post(&url).json(&request).send().await {
+                let mut response: Value = response.json().await?;
+                break serde_json::from_value(response["result"].take())?;
+            }
+            if let Some(reason) = stopped.lock().unwrap().take() {
+                bail!(reason);
+            }
+            ensure!(started.elapsed() < WAIT_TIMEOUT, "RPC server never came up");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+
+        Ok(Self {
+            client,
+            url,
+            params,
+            next_id: AtomicU64::new(1),
+            next_seed: AtomicU64::new(1),
+            task,
+            stopped,
+            _db: db,
+        })
+    }
+
+    /// Protocol parameters the node reported at startup
+    pub fn params(&self) -> &ChainParams {
+        &self.params
+    }
+
+    /// Chain ID transactions must be signed for
+    pub fn chain_id(&self) -> &str {
+        &self.params.chain_id
+    }
+
+    /// A seed no other [`TestAccount`] of this node was made from
+    pub fn next_seed(&self) -> u64 {
+        self.next_seed.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Call `method` on the node's JSON-RPC server
+    ///
+    /// # Parameters
+    /// * `method` - Method name, e.g. `kala_chainInfo`
+    /// * `params` - Positional parameters, as a JSON array
+    ///
+    /// # Returns
+    /// The result, or an error carrying the JSON-RPC error object
+    pub async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+        });
+        let mut response: Value = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            bail!("{} failed: {}", method, error);
+        }
+        serde_json::from_value(response["result"].take())
+            .with_context(|| format!("Unexpected result from {}", method))
+    }
+
+    /// State of the account at `address`, if it has any
+    pub async fn account(&self, address: &[u8; 32]) -> Result<Option<AccountInfo>> {
+        self.call("kala_getAccount", json!([{ "address": hex::encode(address) }]))
+            .await
+    }
+
+    /// Nonce the next transaction of `account` must carry
+    pub async fn next_nonce(&self, account: &TestAccount) -> Result<u64> {
+        let info = self.account(&account.address()).await?;
+        Ok(info.map_or(0, |info| info.nonce) + 1)
+    }
+
+    /// Current tick and VDF iteration of the node
+    pub async fn chain_info(&self) -> Result<ChainInfo> {
+        self.call("kala_chainInfo", json!([])).await
+    }
+
+    /// Wait until `tick` is finalized and its certificate is served
+    pub async fn wait_for_tick(&self, tick: u64) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            let certificate: Option<Value> = self
+                .call("kala_getTick", json!([{ "tick_number": tick }]))
+                .await?;
+            if certificate.is_some() {
+                return Ok(());
+            }
+            self.ensure_running()?;
+            ensure!(started.elapsed() < WAIT_TIMEOUT, "Tick {} was never finalized", tick);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// The first tick whose acceptance window opens `lead` or more iterations from now
+    ///
+    /// # Parameters
+    /// * `lead` - Iterations needed before submitting, e.g. to seal envelopes
+    ///
+    /// # Returns
+    /// The tick to target
+    pub async fn upcoming_tick(&self, lead: u64) -> Result<u64> {
+        let info = self.chain_info().await?;
+        let k = self.params.iterations_per_tick;
+        let earliest = info.current_iteration + lead;
+        let window_start = self.params.hardness.acceptance_window_start;
+        Ok(earliest.saturating_sub(window_start).div_ceil(k) + 1)
+    }
+
+    /// First iteration the node accepts envelopes for `target_tick` at
+    pub fn acceptance_start(&self, target_tick: u64) -> u64 {
+        let k = self.params.iterations_per_tick;
+        (target_tick.max(1) - 1) * k + self.params.hardness.acceptance_window_start
+    }
+
+    /// Wait until the node accepts envelopes for `target_tick`
+    ///
+    /// # Returns
+    /// The iteration the node had reached
+    pub async fn wait_for_acceptance(&self, target_tick: u64) -> Result<u64> {
+        let window_start = self.acceptance_start(target_tick);
+        let started = Instant::now();
+        loop {
+            let iteration = self.chain_info().await?.current_iteration;
+            if iteration >= window_start {
+                return Ok(iteration);
+            }
+            self.ensure_running()?;
+            ensure!(
+                started.elapsed() < WAIT_TIMEOUT,
+                "Acceptance window of tick {} never opened",
+                target_tick
+            );
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Seal envelopes for an upcoming tick, ready to submit before it starts
+    ///
+    /// Sealing generates an RSA modulus per puzzle, which can take longer
+    /// than a test tick, so a tick that started while `seal` ran is given
+    /// up and the envelopes are sealed again for one further away.
+    ///
+    /// # Parameters
+    /// * `seal` - Seals the envelopes for a target tick, given it and the
+    ///   iteration its acceptance window opens at
+    ///
+    /// # Returns
+    /// The target tick and the envelopes, once its acceptance window is
+    /// open and before it starts
+    pub async fn seal_for_upcoming_tick<F>(
+        &self,
+        mut seal: F,
+    ) -> Result<(u64, Vec<TimelockTransaction>)>
+    where
+        F: FnMut(u64, u64) -> KalaResult<Vec<TimelockTransaction>> + Send + 'static,
+    {
+        let k = self.params.iterations_per_tick;
+        let mut lead = 0;
+        for _ in 0..SUBMIT_ATTEMPTS {
+            let target_tick = self.upcoming_tick(lead).await?;
+            let window_start = self.acceptance_start(target_tick);
+            let sealing_from = self.chain_info().await?.current_iteration;
+            let (returned, envelopes) = tokio::task::spawn_blocking(move || {
+                let envelopes = seal(target_tick, window_start);
+                (seal, envelopes)
+            })
+            .await?;
+            seal = returned;
+            let envelopes = envelopes?;
+
+            let iteration = self.wait_for_acceptance(target_tick).await?;
+            if iteration < target_tick * k {
+                return Ok((target_tick, envelopes));
+            }
+            lead = 2 * (iteration - sealing_from);
+        }
+        bail!("Sealing never finished before the target tick in {} attempts", SUBMIT_ATTEMPTS)
+    }
+
+    /// Submit a sealed envelope through `kala_submitTransaction`
+    pub async fn submit(
+        &self,
+        envelope: &TimelockTransaction,
+    ) -> Result<SubmitTransactionResponse> {
+        let request = json!([SubmitTransactionRequest::new(envelope)]);
+        self.call("kala_submitTransaction", request).await
+    }
+
+    /// Seal transactions for one upcoming tick and submit them together
+    ///
+    /// Each puzzle is sized to be solved halfway through the target tick,
+    /// well after the ordering commitment, and the envelopes are submitted
+    /// in order as soon as the tick's acceptance window opens, so they all
+    /// reach the pool before the tick starts. If any of them misses that,
+    /// the harness waits for the missed tick to be final, as the envelopes
+    /// that made it may still apply, and builds the batch again.
+    ///
+    /// # Parameters
+    /// * `build` - Makes the transactions for an attempt, taking nonces
+    ///   from [`TestNode::next_nonce`]
+    ///
+    /// # Returns
+    /// The accepted batch
+    pub async fn submit_batch<F, Fut>(&self, mut build: F) -> Result<SubmittedBatch>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Vec<Transaction>>>,
+    {
+        let k = self.params.iterations_per_tick;
+        for attempt in 1..=SUBMIT_ATTEMPTS {
+            let transactions = build().await?;
+            let txs = transactions.clone();
+            let (target_tick, mut envelopes) = self
+                .seal_for_upcoming_tick(move |target_tick, window_start| {
+                    let hardness = (target_tick * k + k / 2 - window_start) as u32;
+                    txs.iter()
+                        .map(|tx| seal_transaction(tx, target_tick, window_start, hardness))
+                        .collect()
+                })
+                .await?;
+
+            let mut responses = Vec::with_capacity(envelopes.len());
+            for envelope in &envelopes {
+                match self.submit(envelope).await {
+                    Ok(response) => responses.push(response),
+                    Err(e) => {
+                        eprintln!("Batch for tick {} rejected: {:#}", target_tick, e);
+                        break;
+                    }
+                }
+            }
+            let all_in_time = responses.len() == envelopes.len()
+                && responses
+                    .iter()
+                    .all(|response| response.submission_iteration < target_tick * k);
+            if all_in_time {
+                for (envelope, response) in envelopes.iter_mut().zip(&responses) {
+                    envelope.submission_iteration = response.submission_iteration;
+                }
+                return Ok(SubmittedBatch {
+                    target_tick,
+                    envelopes,
+                    responses,
+                    transactions,
+                });
+            }
+            eprintln!(
+                "Attempt {} missed the start of tick {}, retrying",
+                attempt, target_tick
+            );
+            self.wait_for_tick(target_tick).await?;
+        }
+        bail!("No batch reached the pool in time in {} attempts", SUBMIT_ATTEMPTS)
+    }
+
+    /// Fail if the node has stopped running
+    fn ensure_running(&self) -> Result<()> {
+        match &*self.stopped.lock().unwrap() {
+            Some(reason) => bail!("{}", reason),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for TestNode {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A port nothing is listening on
+fn free_port() -> Result<u16> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// An account signing transactions with an Ed25519 key
+pub struct TestAccount {
+    signer: InMemorySigner,
+    address: [u8; 32],
+}
+
+impl TestAccount {
+    /// Derive the account's key from `seed`
+    pub fn new(seed: u64) -> Result<Self> {
+        let mut key_seed = [0u8; 32];
+        key_seed[..8].copy_from_slice(&seed.to_le_bytes());
+        key_seed[8..15].copy_from_slice(b"testkit");
+        let signer = InMemorySigner::from_seed(&key_seed)?;
+        let address = signer
+            .public_key(SignatureScheme::Ed25519)
+            .try_into()
+            .map_err(|_| anyhow!("Account key must be 32 bytes"))?;
+        Ok(Self { signer, address })
+    }
+
+    /// The account's address, its Ed25519 public key
+    pub fn address(&self) -> [u8; 32] {
+        self.address
+    }
+
+    /// A signed mint of `amount` to the account, paying `tip`
+    ///
+    /// # Parameters
+    /// * `chain_id` - Chain the transaction is signed for
+    /// * `amount` - Amount of the native denomination to mint
+    /// * `tip` - Tip offered to the tick's producer
+    /// * `nonce` - The account's next nonce
+    ///
+    /// # Returns
+    /// The signed transaction
+    pub async fn mint(
+        &self,
+        chain_id: &str,
+        amount: u64,
+        tip: u64,
+        nonce: u64,
+    ) -> Result<Transaction> {
+        let mut mint = Mint {
+            sender: self.address,
+            amount,
+            denom: [0; 32],
+            nonce,
+            signature: Vec::new(),
+            gas_sponsorer: self.address,
+            sponsor_signature: Vec::new(),
+            tip,
+        };
+        let message = Transaction::Mint(mint.clone()).signing_message(chain_id)?;
+        mint.signature = self.signer.sign(SignatureScheme::Ed25519, &message).await?;
+        Ok(Transaction::Mint(mint))
+    }
+}
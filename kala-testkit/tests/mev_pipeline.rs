@@ -0,0 +1,101 @@
+//! End-to-end tests of the MEV protection of a tick
+//!
+//! Each test runs its own node with the real VDF, so the suite takes a
+//! few ticks of about two seconds each.
+
+use anyhow::Result;
+use kala_testkit::{TestAccount, TestNode};
+use kala_transaction::seal_transaction;
+
+/// Amount each test mint creates
+const AMOUNT: u64 = 1_000;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_order_committed_before_decryption() -> Result<()> {
+    let node = TestNode::start().await?;
+    let accounts = (0..4)
+        .map(|_| TestAccount::new(node.next_seed()))
+        .collect::<Result<Vec<_>>>()?;
+    let mint_all = |tips: [u64; 4]| {
+        let (node, accounts) = (&node, &accounts);
+        move || async move {
+            let mut txs = Vec::new();
+            for (account, tip) in accounts.iter().zip(tips) {
+                let nonce = node.next_nonce(account).await?;
+                txs.push(account.mint(node.chain_id(), AMOUNT, tip, nonce).await?);
+            }
+            Ok(txs)
+        }
+    };
+
+    // Fund the accounts so they can pay tips
+    let funding = node.submit_batch(mint_all([0; 4])).await?;
+    assert!(node.check_pipeline(&funding).await?.applied.iter().all(|&applied| applied));
+
+    // Later arrivals bid ever higher tips, which must not move them ahead
+    let tips = [0, 100, 200, 300];
+    let batch = node.submit_batch(mint_all(tips)).await?;
+    let report = node.check_pipeline(&batch).await?;
+    assert!(report.decrypted.iter().all(|&decrypted| decrypted));
+    assert!(report.applied.iter().all(|&applied| applied));
+    let mut by_arrival: Vec<usize> = (0..batch.envelopes.len()).collect();
+    by_arrival.sort_by_key(|&index| {
+        let envelope = &batch.envelopes[index];
+        (envelope.submission_iteration, envelope.envelope_hash())
+    });
+    assert_eq!(report.order, by_arrival);
+
+    for (account, tip) in accounts.iter().zip(tips) {
+        let info = node.account(&account.address()).await?.unwrap();
+        assert_eq!(info.balance, 2 * AMOUNT - tip);
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_commitment_precedes_every_puzzle() -> Result<()> {
+    let node = TestNode::start().await?;
+
+    let batch = node
+        .submit_batch(|| async {
+            let account = TestAccount::new(node.next_seed())?;
+            let nonce = node.next_nonce(&account).await?;
+            Ok(vec![account.mint(node.chain_id(), AMOUNT, 0, nonce).await?])
+        })
+        .await?;
+    let report = node.check_pipeline(&batch).await?;
+
+    // Committed right as collection ends, before the puzzle is halfway solvable
+    let k = node.params().iterations_per_tick;
+    let collection_end = batch.target_tick * k + node.params().phases.collection_end;
+    assert_eq!(report.commitment_iteration, collection_end + 1);
+    assert!(report.commitment_iteration < batch.decrypt_at(0).unwrap());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_puzzles_outside_decryption_window_rejected() -> Result<()> {
+    let node = TestNode::start().await?;
+    let k = node.params().iterations_per_tick;
+    let account = TestAccount::new(node.next_seed())?;
+    let tx = account.mint(node.chain_id(), AMOUNT, 0, 1).await?;
+
+    // A puzzle solvable before the order is committed would let it be read first
+    let (_, envelopes) = node
+        .seal_for_upcoming_tick(move |target_tick, window_start| {
+            let too_easy = seal_transaction(&tx, target_tick, window_start, 1)?;
+            let too_hard = seal_transaction(&tx, target_tick, window_start, 2 * k as u32)?;
+            Ok(vec![too_easy, too_hard])
+        })
+        .await?;
+    let [too_easy, too_hard] = &envelopes[..] else {
+        unreachable!()
+    };
+
+    let error = node.submit(too_easy).await.err().expect("Puzzle too easy");
+    assert!(error.to_string().contains("decrypt too early"), "{}", error);
+    // No puzzle may take longer than a tick, which rules it out before timing
+    let error = node.submit(too_hard).await.err().expect("Puzzle too hard");
+    assert!(error.to_string().contains("outside 1 to the tick size"), "{}", error);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+//! Decode arbitrary bytes as a flatbuffer transaction
+//!
+//! Transactions arrive from peers inside timelock envelopes, so decoding
+//! must reject malformed bytes with an error rather than panic. Whatever
+//! decodes must encode back to a transaction that decodes the same.
+
+#![no_main]
+
+use kala_transaction::{flatbuffer_to_transaction, transaction_to_flatbuffer};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(tx) = flatbuffer_to_transaction(data) else {
+        return;
+    };
+    if let Ok(bytes) = transaction_to_flatbuffer(&tx) {
+        let decoded = flatbuffer_to_transaction(&bytes).expect("re-encoded transaction decodes");
+        assert_eq!(decoded.signing_message("").unwrap(), tx.signing_message("").unwrap());
+        assert_eq!(decoded.signature(), tx.signature());
+    }
+});
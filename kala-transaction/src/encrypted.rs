@@ -1,7 +1,8 @@
 // encrypted.rs - Encryption module for kala-transaction
 
+use crate::puzzle_pool::{wipe_integer, PuzzlePool};
 use crate::types::{
-    Nonce96Array, RSWPuzzle, SealedTransaction, Tag128Array, TimelockTransaction,
+    KeyLock, Nonce96Array, RSWPuzzle, SealedTransaction, Tag128Array, TimelockTransaction,
     Transaction, AES_KEY_SIZE, TAG_SIZE,
 };
 use kala_common::prelude::{KalaResult, KalaError};
@@ -10,9 +11,10 @@ use aes_gcm::{
     Aes256Gcm, Key, Nonce,
 };
 use rand::Rng;
-use rug::{rand::RandState, Integer};
+use rug::Integer;
 use std::sync::Arc;
 use timelocks::Solver;
+use zeroize::Zeroizing;
 
 /// Thread-safe encryption context
 #[derive(Clone)]
@@ -47,7 +49,7 @@ pub fn encrypt_transaction(
     key: &[u8; AES_KEY_SIZE],
 ) -> KalaResult<SealedTransaction> {
     // Convert to FlatBuffer for canonical serialization
-    let plaintext = crate::decrypted::transaction_to_flatbuffer(tx)?;
+    let plaintext = Zeroizing::new(crate::decrypted::transaction_to_flatbuffer(tx)?);
 
     // Create cipher
     let key = Key::<Aes256Gcm>::from_slice(key);
@@ -96,9 +98,10 @@ pub fn decrypt_transaction(
     // Create nonce
     let nonce = Nonce::from_slice(&sealed.nonce);
 
-    // Decrypt
+    // Decrypt, wiping the plaintext once it is deserialized
     let plaintext = cipher
         .decrypt(nonce, full_ciphertext.as_ref())
+        .map(Zeroizing::new)
         .map_err(|e| {
             KalaError::crypto(format!("AES-GCM decryption failed: {e}"))
         })?;
@@ -131,7 +134,7 @@ impl RSWTimelock {
         use rug::integer::Order;
 
         // Generate safe RSA modulus n = p*q
-        let mut rand_state = RandState::new();
+        let mut rand_state = crate::puzzle_pool::seeded_rand_state();
         let bits = self.modulus_bits / 2;
 
         // Generate two random primes
@@ -147,28 +150,35 @@ impl RSWTimelock {
         let a = Integer::from(2);
 
         // Convert key to Integer (little-endian)
-        let key_int = Integer::from_digits(key, Order::Lsf);
+        let mut key_int = Integer::from_digits(key, Order::Lsf);
 
         // For fast puzzle creation, use Euler's theorem
         // λ(n) = lcm(p-1, q-1)
-        let p_minus_1 = Integer::from(&p - 1);
-        let q_minus_1 = Integer::from(&q - 1);
-        let lambda = p_minus_1.lcm(&q_minus_1);
+        let mut p_minus_1 = Integer::from(&p - 1);
+        let mut q_minus_1 = Integer::from(&q - 1);
+        let mut lambda = p_minus_1.clone().lcm(&q_minus_1);
 
         // Reduce exponent: 2^hardness mod λ(n)
         let two = Integer::from(2);
-        let reduced_exp = two
-            .pow_mod(&Integer::from(hardness), &lambda)
-            .map_err(|e| KalaError::crypto(format!("pow_mod failed: {e}")))?;
+        let reduced_exp = two.pow_mod(&Integer::from(hardness), &lambda);
+
+        // The factorization opens every puzzle on n, so none of it outlives this call
+        for secret in [&mut p, &mut q, &mut p_minus_1, &mut q_minus_1, &mut lambda] {
+            wipe_integer(secret);
+        }
+        let mut reduced_exp =
+            reduced_exp.map_err(|e| KalaError::crypto(format!("pow_mod failed: {e}")))?;
 
         // Compute a^(2^hardness mod λ(n)) mod n (fast!)
-        let a_power = a
-            .clone()
-            .pow_mod(&reduced_exp, &n)
-            .map_err(|e| KalaError::crypto(format!("pow_mod failed: {e}")))?;
+        let a_power = a.clone().pow_mod(&reduced_exp, &n);
+        wipe_integer(&mut reduced_exp);
+        let mut a_power =
+            a_power.map_err(|e| KalaError::crypto(format!("pow_mod failed: {e}")))?;
 
         // C = (key + a^(2^hardness)) mod n
-        let puzzle_value = (key_int + a_power) % &n;
+        let puzzle_value = Integer::from(&key_int + &a_power) % &n;
+        wipe_integer(&mut key_int);
+        wipe_integer(&mut a_power);
 
         // Convert to bytes (big-endian for compatibility)
         let n_bytes = n.to_digits::<u8>(Order::Msf);
@@ -184,7 +194,9 @@ impl RSWTimelock {
     }
 
     /// Solve RSW puzzle to recover key using GPU acceleration
-    pub fn solve_puzzle(&self, puzzle: &RSWPuzzle) -> KalaResult<[u8; AES_KEY_SIZE]> {
+    ///
+    /// The key is wiped from memory when the returned value is dropped.
+    pub fn solve_puzzle(&self, puzzle: &RSWPuzzle) -> KalaResult<Zeroizing<[u8; AES_KEY_SIZE]>> {
         // Convert to hex strings for the GPU solver
         let n_hex = hex::encode(&puzzle.n);
         let a_hex = hex::encode(&puzzle.a);
@@ -196,11 +208,16 @@ impl RSWTimelock {
             .solve(&n_hex, &a_hex, &c_hex, puzzle.hardness)
             .map_err(|e| KalaError::crypto(format!("RSW solve failed: {e}")))?;
 
-        Ok(result.key)
+        Ok(Zeroizing::new(result.key))
     }
 
     /// Batch solve multiple puzzles in parallel on GPU
-    pub fn solve_batch(&self, puzzles: &[RSWPuzzle]) -> KalaResult<Vec<[u8; AES_KEY_SIZE]>> {
+    ///
+    /// Like [`RSWTimelock::solve_puzzle`], each key is wiped when dropped.
+    pub fn solve_batch(
+        &self,
+        puzzles: &[RSWPuzzle],
+    ) -> KalaResult<Vec<Zeroizing<[u8; AES_KEY_SIZE]>>> {
         let puzzle_inputs: Vec<(String, String, String, u32)> = puzzles
             .iter()
             .map(|p| {
@@ -217,7 +234,7 @@ impl RSWTimelock {
             KalaError::crypto(format!("Batch RSW solve failed: {e}"))
         })?;
 
-        Ok(results.into_iter().map(|r| r.key).collect())
+        Ok(results.iter().map(|r| Zeroizing::new(r.key)).collect())
     }
 
     /// Get optimal batch size for GPU
@@ -251,9 +268,22 @@ pub fn create_timelock_transaction(
     let safe_hardness = (remaining / 2) as u32;
     let hardness = max_hardness.min(safe_hardness).max(1);
 
-    // Generate encryption key
-    let mut key = [0u8; AES_KEY_SIZE];
-    rand::thread_rng().fill(&mut key);
+    seal_transaction(tx, current_tick, current_iteration, hardness)
+}
+
+/// Seal a transaction for `target_tick` behind a puzzle of the given hardness
+///
+/// Unlike [`create_timelock_transaction`], the caller picks the hardness,
+/// e.g. to submit for a tick other than the one in progress.
+pub fn seal_transaction(
+    tx: &Transaction,
+    target_tick: u64,
+    submission_iteration: u64,
+    hardness: u32,
+) -> KalaResult<TimelockTransaction> {
+    // Generate encryption key, wiped once it is locked in the puzzle
+    let mut key = Zeroizing::new([0u8; AES_KEY_SIZE]);
+    rand::rng().fill(&mut key[..]);
 
     // Encrypt transaction
     let encrypted_data = encrypt_transaction(tx, &key)?;
@@ -270,25 +300,60 @@ pub fn create_timelock_transaction(
 
     Ok(TimelockTransaction {
         encrypted_data,
-        puzzle,
-        submission_iteration: current_iteration,
-        target_tick: current_tick,
+        lock: KeyLock::Puzzle(puzzle),
+        submission_iteration,
+        target_tick,
+    })
+}
+
+/// Seal a transaction like [`seal_transaction`], on a modulus from `pool`
+///
+/// Skips generating a modulus, which is most of the cost of sealing.
+pub fn seal_transaction_fast(
+    tx: &Transaction,
+    pool: &PuzzlePool,
+    target_tick: u64,
+    submission_iteration: u64,
+    hardness: u32,
+) -> KalaResult<TimelockTransaction> {
+    let mut key = Zeroizing::new([0u8; AES_KEY_SIZE]);
+    rand::rng().fill(&mut key[..]);
+
+    let encrypted_data = encrypt_transaction(tx, &key)?;
+    let puzzle = pool.generate_puzzle_fast(&key, hardness)?;
+
+    Ok(TimelockTransaction {
+        encrypted_data,
+        lock: KeyLock::Puzzle(puzzle),
+        submission_iteration,
+        target_tick,
     })
 }
 
 /// Decrypt a timelock transaction (requires solving the puzzle)
 pub fn decrypt_timelock_transaction(timelock_tx: &TimelockTransaction) -> KalaResult<Transaction> {
+    let puzzle = puzzle_of(timelock_tx)?;
+
     // Create solver
     let timelock = RSWTimelock::new(2048)?;
 
     // Solve the RSW puzzle to recover the key
-    let key = timelock.solve_puzzle(&timelock_tx.puzzle)?;
+    let key = timelock.solve_puzzle(puzzle)?;
 
     // Decrypt the transaction
     decrypt_transaction(&timelock_tx.encrypted_data, &key)
 }
 
+/// The puzzle of an envelope, which threshold-locked envelopes lack
+fn puzzle_of(timelock_tx: &TimelockTransaction) -> KalaResult<&RSWPuzzle> {
+    timelock_tx.puzzle().ok_or_else(|| {
+        KalaError::validation("Threshold-locked envelopes are opened with key shares".to_string())
+    })
+}
+
 /// Batch decrypt multiple timelock transactions using GPU acceleration
+///
+/// Every envelope must be puzzle-locked.
 pub fn decrypt_timelock_batch(timelock_txs: &[TimelockTransaction]) -> KalaResult<Vec<Transaction>> {
     if timelock_txs.is_empty() {
         return Ok(vec![]);
@@ -303,7 +368,10 @@ pub fn decrypt_timelock_batch(timelock_txs: &[TimelockTransaction]) -> KalaResul
 
     for chunk in timelock_txs.chunks(batch_size) {
         // Extract puzzles
-        let puzzles: Vec<RSWPuzzle> = chunk.iter().map(|tx| tx.puzzle.clone()).collect();
+        let puzzles: Vec<RSWPuzzle> = chunk
+            .iter()
+            .map(|tx| puzzle_of(tx).cloned())
+            .collect::<KalaResult<_>>()?;
 
         // Solve batch on GPU
         let keys = timelock.solve_batch(&puzzles)?;
@@ -339,6 +407,9 @@ mod tests {
             nonce: 1,
             signature: [0u8; 64].to_vec(),
             gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: 0,
         });
 
         let key = [42u8; AES_KEY_SIZE];
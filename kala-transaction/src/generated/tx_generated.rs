@@ -26,18 +26,31 @@ pub mod tx {
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
-    pub const ENUM_MAX_TX_BODY: u8 = 4;
+    pub const ENUM_MAX_TX_BODY: u8 = 17;
     #[deprecated(
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
     #[allow(non_camel_case_types)]
-    pub const ENUM_VALUES_TX_BODY: [TxBody; 5] = [
+    pub const ENUM_VALUES_TX_BODY: [TxBody; 18] = [
         TxBody::NONE,
         TxBody::SendTx,
         TxBody::MintTx,
         TxBody::StakeTx,
         TxBody::SolveTx,
+        TxBody::UnstakeTx,
+        TxBody::EvidenceTx,
+        TxBody::MultiSendTx,
+        TxBody::VestingTransferTx,
+        TxBody::ProposeTx,
+        TxBody::VoteTx,
+        TxBody::SubmitForeignEpochTx,
+        TxBody::CreateAuctionTx,
+        TxBody::BidTx,
+        TxBody::SetGuardiansTx,
+        TxBody::RequestRecoveryTx,
+        TxBody::VetoRecoveryTx,
+        TxBody::TransferMintAuthorityTx,
     ];
 
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -50,15 +63,41 @@ pub mod tx {
         pub const MintTx: Self = Self(2);
         pub const StakeTx: Self = Self(3);
         pub const SolveTx: Self = Self(4);
+        pub const UnstakeTx: Self = Self(5);
+        pub const EvidenceTx: Self = Self(6);
+        pub const MultiSendTx: Self = Self(7);
+        pub const VestingTransferTx: Self = Self(8);
+        pub const ProposeTx: Self = Self(9);
+        pub const VoteTx: Self = Self(10);
+        pub const SubmitForeignEpochTx: Self = Self(11);
+        pub const CreateAuctionTx: Self = Self(12);
+        pub const BidTx: Self = Self(13);
+        pub const SetGuardiansTx: Self = Self(14);
+        pub const RequestRecoveryTx: Self = Self(15);
+        pub const VetoRecoveryTx: Self = Self(16);
+        pub const TransferMintAuthorityTx: Self = Self(17);
 
         pub const ENUM_MIN: u8 = 0;
-        pub const ENUM_MAX: u8 = 4;
+        pub const ENUM_MAX: u8 = 17;
         pub const ENUM_VALUES: &'static [Self] = &[
             Self::NONE,
             Self::SendTx,
             Self::MintTx,
             Self::StakeTx,
             Self::SolveTx,
+            Self::UnstakeTx,
+            Self::EvidenceTx,
+            Self::MultiSendTx,
+            Self::VestingTransferTx,
+            Self::ProposeTx,
+            Self::VoteTx,
+            Self::SubmitForeignEpochTx,
+            Self::CreateAuctionTx,
+            Self::BidTx,
+            Self::SetGuardiansTx,
+            Self::RequestRecoveryTx,
+            Self::VetoRecoveryTx,
+            Self::TransferMintAuthorityTx,
         ];
         /// Returns the variant's name or "" if unknown.
         pub fn variant_name(self) -> Option<&'static str> {
@@ -68,6 +107,19 @@ pub mod tx {
                 Self::MintTx => Some("MintTx"),
                 Self::StakeTx => Some("StakeTx"),
                 Self::SolveTx => Some("SolveTx"),
+                Self::UnstakeTx => Some("UnstakeTx"),
+                Self::EvidenceTx => Some("EvidenceTx"),
+                Self::MultiSendTx => Some("MultiSendTx"),
+                Self::VestingTransferTx => Some("VestingTransferTx"),
+                Self::ProposeTx => Some("ProposeTx"),
+                Self::VoteTx => Some("VoteTx"),
+                Self::SubmitForeignEpochTx => Some("SubmitForeignEpochTx"),
+                Self::CreateAuctionTx => Some("CreateAuctionTx"),
+                Self::BidTx => Some("BidTx"),
+                Self::SetGuardiansTx => Some("SetGuardiansTx"),
+                Self::RequestRecoveryTx => Some("RequestRecoveryTx"),
+                Self::VetoRecoveryTx => Some("VetoRecoveryTx"),
+                Self::TransferMintAuthorityTx => Some("TransferMintAuthorityTx"),
                 _ => None,
             }
         }
@@ -641,6 +693,9 @@ pub mod tx {
         pub const VT_NONCE: flatbuffers::VOffsetT = 12;
         pub const VT_SIGNATURE: flatbuffers::VOffsetT = 14;
         pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 16;
+        pub const VT_MEMO: flatbuffers::VOffsetT = 18;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 20;
+        pub const VT_TIP: flatbuffers::VOffsetT = 22;
 
         #[inline]
         pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -657,8 +712,15 @@ pub mod tx {
             args: &'args SendTxArgs<'args>,
         ) -> flatbuffers::WIPOffset<SendTx<'bldr>> {
             let mut builder = SendTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
             builder.add_nonce(args.nonce);
             builder.add_amount(args.amount);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.memo {
+                builder.add_memo(x);
+            }
             if let Some(x) = args.gas_sponsorer {
                 builder.add_gas_sponsorer(x);
             }
@@ -756,6 +818,39 @@ pub mod tx {
                     )
             }
         }
+        #[inline]
+        pub fn memo(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SendTx::VT_MEMO,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SendTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SendTx::VT_TIP, Some(0)).unwrap() }
+        }
     }
 
     impl flatbuffers::Verifiable for SendTx<'_> {
@@ -793,6 +888,17 @@ pub mod tx {
                     Self::VT_GAS_SPONSORER,
                     false,
                 )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "memo",
+                    Self::VT_MEMO,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
                 .finish();
             Ok(())
         }
@@ -805,6 +911,9 @@ pub mod tx {
         pub nonce: u64,
         pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
         pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub memo: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
     }
     impl<'a> Default for SendTxArgs<'a> {
         #[inline]
@@ -817,6 +926,9 @@ pub mod tx {
                 nonce: 0,
                 signature: None,
                 gas_sponsorer: None,
+                memo: None,
+                sponsor_signature: None,
+                tip: 0,
             }
         }
     }
@@ -871,6 +983,25 @@ pub mod tx {
             );
         }
         #[inline]
+        pub fn add_memo(&mut self, memo: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(SendTx::VT_MEMO, memo);
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SendTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(SendTx::VT_TIP, tip, 0);
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> SendTxBuilder<'a, 'b, A> {
@@ -897,6 +1028,9 @@ pub mod tx {
             ds.field("nonce", &self.nonce());
             ds.field("signature", &self.signature());
             ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("memo", &self.memo());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
             ds.finish()
         }
     }
@@ -924,6 +1058,8 @@ pub mod tx {
         pub const VT_NONCE: flatbuffers::VOffsetT = 10;
         pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
         pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
 
         #[inline]
         pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -940,8 +1076,12 @@ pub mod tx {
             args: &'args MintTxArgs<'args>,
         ) -> flatbuffers::WIPOffset<MintTx<'bldr>> {
             let mut builder = MintTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
             builder.add_nonce(args.nonce);
             builder.add_amount(args.amount);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
             if let Some(x) = args.gas_sponsorer {
                 builder.add_gas_sponsorer(x);
             }
@@ -1023,6 +1163,26 @@ pub mod tx {
                     )
             }
         }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MintTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(MintTx::VT_TIP, Some(0)).unwrap() }
+        }
     }
 
     impl flatbuffers::Verifiable for MintTx<'_> {
@@ -1055,6 +1215,12 @@ pub mod tx {
                     Self::VT_GAS_SPONSORER,
                     false,
                 )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
                 .finish();
             Ok(())
         }
@@ -1066,6 +1232,8 @@ pub mod tx {
         pub nonce: u64,
         pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
         pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
     }
     impl<'a> Default for MintTxArgs<'a> {
         #[inline]
@@ -1077,6 +1245,8 @@ pub mod tx {
                 nonce: 0,
                 signature: None,
                 gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
             }
         }
     }
@@ -1123,6 +1293,20 @@ pub mod tx {
             );
         }
         #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                MintTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(MintTx::VT_TIP, tip, 0);
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> MintTxBuilder<'a, 'b, A> {
@@ -1148,6 +1332,8 @@ pub mod tx {
             ds.field("nonce", &self.nonce());
             ds.field("signature", &self.signature());
             ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
             ds.finish()
         }
     }
@@ -1175,6 +1361,8 @@ pub mod tx {
         pub const VT_NONCE: flatbuffers::VOffsetT = 10;
         pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
         pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
 
         #[inline]
         pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1191,8 +1379,12 @@ pub mod tx {
             args: &'args StakeTxArgs<'args>,
         ) -> flatbuffers::WIPOffset<StakeTx<'bldr>> {
             let mut builder = StakeTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
             builder.add_nonce(args.nonce);
             builder.add_amount(args.amount);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
             if let Some(x) = args.gas_sponsorer {
                 builder.add_gas_sponsorer(x);
             }
@@ -1274,6 +1466,26 @@ pub mod tx {
                     )
             }
         }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        StakeTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(StakeTx::VT_TIP, Some(0)).unwrap() }
+        }
     }
 
     impl flatbuffers::Verifiable for StakeTx<'_> {
@@ -1306,6 +1518,12 @@ pub mod tx {
                     Self::VT_GAS_SPONSORER,
                     false,
                 )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
                 .finish();
             Ok(())
         }
@@ -1317,6 +1535,8 @@ pub mod tx {
         pub nonce: u64,
         pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
         pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
     }
     impl<'a> Default for StakeTxArgs<'a> {
         #[inline]
@@ -1328,6 +1548,8 @@ pub mod tx {
                 nonce: 0,
                 signature: None,
                 gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
             }
         }
     }
@@ -1379,6 +1601,20 @@ pub mod tx {
             );
         }
         #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                StakeTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(StakeTx::VT_TIP, tip, 0);
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> StakeTxBuilder<'a, 'b, A> {
@@ -1404,6 +1640,8 @@ pub mod tx {
             ds.field("nonce", &self.nonce());
             ds.field("signature", &self.signature());
             ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
             ds.finish()
         }
     }
@@ -1431,6 +1669,8 @@ pub mod tx {
         pub const VT_NONCE: flatbuffers::VOffsetT = 10;
         pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
         pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
 
         #[inline]
         pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1447,7 +1687,11 @@ pub mod tx {
             args: &'args SolveTxArgs<'args>,
         ) -> flatbuffers::WIPOffset<SolveTx<'bldr>> {
             let mut builder = SolveTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
             builder.add_nonce(args.nonce);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
             if let Some(x) = args.gas_sponsorer {
                 builder.add_gas_sponsorer(x);
             }
@@ -1538,6 +1782,26 @@ pub mod tx {
                     )
             }
         }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SolveTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SolveTx::VT_TIP, Some(0)).unwrap() }
+        }
     }
 
     impl flatbuffers::Verifiable for SolveTx<'_> {
@@ -1574,6 +1838,12 @@ pub mod tx {
                     Self::VT_GAS_SPONSORER,
                     false,
                 )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
                 .finish();
             Ok(())
         }
@@ -1585,6 +1855,8 @@ pub mod tx {
         pub nonce: u64,
         pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
         pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
     }
     impl<'a> Default for SolveTxArgs<'a> {
         #[inline]
@@ -1596,6 +1868,8 @@ pub mod tx {
                 nonce: 0,
                 signature: None,
                 gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
             }
         }
     }
@@ -1646,6 +1920,20 @@ pub mod tx {
             );
         }
         #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SolveTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(SolveTx::VT_TIP, tip, 0);
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> SolveTxBuilder<'a, 'b, A> {
@@ -1671,18 +1959,20 @@ pub mod tx {
             ds.field("nonce", &self.nonce());
             ds.field("signature", &self.signature());
             ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
             ds.finish()
         }
     }
-    pub enum TransactionOffset {}
+    pub enum UnstakeTxOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct Transaction<'a> {
+    pub struct UnstakeTx<'a> {
         pub _tab: flatbuffers::Table<'a>,
     }
 
-    impl<'a> flatbuffers::Follow<'a> for Transaction<'a> {
-        type Inner = Transaction<'a>;
+    impl<'a> flatbuffers::Follow<'a> for UnstakeTx<'a> {
+        type Inner = UnstakeTx<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -1691,13 +1981,18 @@ pub mod tx {
         }
     }
 
-    impl<'a> Transaction<'a> {
-        pub const VT_BODY_TYPE: flatbuffers::VOffsetT = 4;
-        pub const VT_BODY: flatbuffers::VOffsetT = 6;
+    impl<'a> UnstakeTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_AMOUNT: flatbuffers::VOffsetT = 6;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 8;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 10;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 12;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 14;
+        pub const VT_TIP: flatbuffers::VOffsetT = 16;
 
         #[inline]
         pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-            Transaction { _tab: table }
+            UnstakeTx { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -1707,102 +2002,103 @@ pub mod tx {
             A: flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args TransactionArgs,
-        ) -> flatbuffers::WIPOffset<Transaction<'bldr>> {
-            let mut builder = TransactionBuilder::new(_fbb);
-            if let Some(x) = args.body {
-                builder.add_body(x);
+            args: &'args UnstakeTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<UnstakeTx<'bldr>> {
+            let mut builder = UnstakeTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_amount(args.amount);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
             }
-            builder.add_body_type(args.body_type);
             builder.finish()
         }
 
         #[inline]
-        pub fn body_type(&self) -> TxBody {
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<TxBody>(Transaction::VT_BODY_TYPE, Some(TxBody::NONE))
-                    .unwrap()
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        UnstakeTx::VT_SENDER,
+                        None,
+                    )
             }
         }
         #[inline]
-        pub fn body(&self) -> Option<flatbuffers::Table<'a>> {
+        pub fn amount(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(UnstakeTx::VT_AMOUNT, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(UnstakeTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(
-                        Transaction::VT_BODY,
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        UnstakeTx::VT_SIGNATURE,
                         None,
                     )
             }
         }
         #[inline]
-        #[allow(non_snake_case)]
-        pub fn body_as_send_tx(&self) -> Option<SendTx<'a>> {
-            if self.body_type() == TxBody::SendTx {
-                self.body().map(|t| {
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    unsafe { SendTx::init_from_table(t) }
-                })
-            } else {
-                None
-            }
-        }
-
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn body_as_mint_tx(&self) -> Option<MintTx<'a>> {
-            if self.body_type() == TxBody::MintTx {
-                self.body().map(|t| {
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    unsafe { MintTx::init_from_table(t) }
-                })
-            } else {
-                None
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        UnstakeTx::VT_GAS_SPONSORER,
+                        None,
+                    )
             }
         }
-
         #[inline]
-        #[allow(non_snake_case)]
-        pub fn body_as_stake_tx(&self) -> Option<StakeTx<'a>> {
-            if self.body_type() == TxBody::StakeTx {
-                self.body().map(|t| {
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    unsafe { StakeTx::init_from_table(t) }
-                })
-            } else {
-                None
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        UnstakeTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
             }
         }
-
         #[inline]
-        #[allow(non_snake_case)]
-        pub fn body_as_solve_tx(&self) -> Option<SolveTx<'a>> {
-            if self.body_type() == TxBody::SolveTx {
-                self.body().map(|t| {
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    unsafe { SolveTx::init_from_table(t) }
-                })
-            } else {
-                None
-            }
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(UnstakeTx::VT_TIP, Some(0)).unwrap() }
         }
     }
 
-    impl flatbuffers::Verifiable for Transaction<'_> {
+    impl flatbuffers::Verifiable for UnstakeTx<'_> {
         #[inline]
         fn run_verifier(
             v: &mut flatbuffers::Verifier,
@@ -1810,9 +2106,4602 @@ pub mod tx {
         ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
             use self::flatbuffers::Verifiable;
             v.visit_table(pos)?
-                .visit_union::<TxBody, _>(
-                    "body_type",
-                    Self::VT_BODY_TYPE,
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<u64>("amount", Self::VT_AMOUNT, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct UnstakeTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub amount: u64,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for UnstakeTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            UnstakeTxArgs {
+                sender: None,
+                amount: 0,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct UnstakeTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> UnstakeTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(UnstakeTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_amount(&mut self, amount: u64) {
+            self.fbb_.push_slot::<u64>(UnstakeTx::VT_AMOUNT, amount, 0);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(UnstakeTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(UnstakeTx::VT_SIGNATURE, signature);
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                UnstakeTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                UnstakeTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(UnstakeTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> UnstakeTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            UnstakeTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<UnstakeTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for UnstakeTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("UnstakeTx");
+            ds.field("sender", &self.sender());
+            ds.field("amount", &self.amount());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum EvidenceTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct EvidenceTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for EvidenceTx<'a> {
+        type Inner = EvidenceTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> EvidenceTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_WITNESS: flatbuffers::VOffsetT = 6;
+        pub const VT_TICK_NUMBER: flatbuffers::VOffsetT = 8;
+        pub const VT_FIRST_HASH: flatbuffers::VOffsetT = 10;
+        pub const VT_FIRST_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_SECOND_HASH: flatbuffers::VOffsetT = 14;
+        pub const VT_SECOND_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 18;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 20;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 22;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 24;
+        pub const VT_TIP: flatbuffers::VOffsetT = 26;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            EvidenceTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args EvidenceTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<EvidenceTx<'bldr>> {
+            let mut builder = EvidenceTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_tick_number(args.tick_number);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.second_signature {
+                builder.add_second_signature(x);
+            }
+            if let Some(x) = args.second_hash {
+                builder.add_second_hash(x);
+            }
+            if let Some(x) = args.first_signature {
+                builder.add_first_signature(x);
+            }
+            if let Some(x) = args.first_hash {
+                builder.add_first_hash(x);
+            }
+            if let Some(x) = args.witness {
+                builder.add_witness(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn witness(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_WITNESS,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tick_number(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(EvidenceTx::VT_TICK_NUMBER, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn first_hash(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_FIRST_HASH,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn first_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_FIRST_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn second_hash(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_SECOND_HASH,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn second_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_SECOND_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(EvidenceTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        EvidenceTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(EvidenceTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for EvidenceTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "witness",
+                    Self::VT_WITNESS,
+                    false,
+                )?
+                .visit_field::<u64>("tick_number", Self::VT_TICK_NUMBER, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "first_hash",
+                    Self::VT_FIRST_HASH,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "first_signature",
+                    Self::VT_FIRST_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "second_hash",
+                    Self::VT_SECOND_HASH,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "second_signature",
+                    Self::VT_SECOND_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct EvidenceTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub witness: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tick_number: u64,
+        pub first_hash: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub first_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub second_hash: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub second_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for EvidenceTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            EvidenceTxArgs {
+                sender: None,
+                witness: None,
+                tick_number: 0,
+                first_hash: None,
+                first_signature: None,
+                second_hash: None,
+                second_signature: None,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct EvidenceTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> EvidenceTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(
+            &mut self,
+            sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_SENDER,
+                sender,
+            );
+        }
+        #[inline]
+        pub fn add_witness(
+            &mut self,
+            witness: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_WITNESS,
+                witness,
+            );
+        }
+        #[inline]
+        pub fn add_tick_number(&mut self, tick_number: u64) {
+            self.fbb_.push_slot::<u64>(EvidenceTx::VT_TICK_NUMBER, tick_number, 0);
+        }
+        #[inline]
+        pub fn add_first_hash(
+            &mut self,
+            first_hash: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_FIRST_HASH,
+                first_hash,
+            );
+        }
+        #[inline]
+        pub fn add_first_signature(
+            &mut self,
+            first_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_FIRST_SIGNATURE,
+                first_signature,
+            );
+        }
+        #[inline]
+        pub fn add_second_hash(
+            &mut self,
+            second_hash: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_SECOND_HASH,
+                second_hash,
+            );
+        }
+        #[inline]
+        pub fn add_second_signature(
+            &mut self,
+            second_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_SECOND_SIGNATURE,
+                second_signature,
+            );
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(EvidenceTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                EvidenceTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(EvidenceTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> EvidenceTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            EvidenceTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<EvidenceTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for EvidenceTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("EvidenceTx");
+            ds.field("sender", &self.sender());
+            ds.field("witness", &self.witness());
+            ds.field("tick_number", &self.tick_number());
+            ds.field("first_hash", &self.first_hash());
+            ds.field("first_signature", &self.first_signature());
+            ds.field("second_hash", &self.second_hash());
+            ds.field("second_signature", &self.second_signature());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum MultiSendOutputOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct MultiSendOutput<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for MultiSendOutput<'a> {
+        type Inner = MultiSendOutput<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> MultiSendOutput<'a> {
+        pub const VT_RECEIVER: flatbuffers::VOffsetT = 4;
+        pub const VT_AMOUNT: flatbuffers::VOffsetT = 6;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            MultiSendOutput { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args MultiSendOutputArgs<'args>,
+        ) -> flatbuffers::WIPOffset<MultiSendOutput<'bldr>> {
+            let mut builder = MultiSendOutputBuilder::new(_fbb);
+            builder.add_amount(args.amount);
+            if let Some(x) = args.receiver {
+                builder.add_receiver(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn receiver(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MultiSendOutput::VT_RECEIVER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn amount(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(MultiSendOutput::VT_AMOUNT, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for MultiSendOutput<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "receiver",
+                    Self::VT_RECEIVER,
+                    false,
+                )?
+                .visit_field::<u64>("amount", Self::VT_AMOUNT, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct MultiSendOutputArgs<'a> {
+        pub receiver: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub amount: u64,
+    }
+    impl<'a> Default for MultiSendOutputArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            MultiSendOutputArgs {
+                receiver: None,
+                amount: 0,
+            }
+        }
+    }
+
+    pub struct MultiSendOutputBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> MultiSendOutputBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_receiver(
+            &mut self,
+            receiver: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                MultiSendOutput::VT_RECEIVER,
+                receiver,
+            );
+        }
+        #[inline]
+        pub fn add_amount(&mut self, amount: u64) {
+            self.fbb_.push_slot::<u64>(MultiSendOutput::VT_AMOUNT, amount, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> MultiSendOutputBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            MultiSendOutputBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<MultiSendOutput<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for MultiSendOutput<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("MultiSendOutput");
+            ds.field("receiver", &self.receiver());
+            ds.field("amount", &self.amount());
+            ds.finish()
+        }
+    }
+    pub enum MultiSendTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct MultiSendTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for MultiSendTx<'a> {
+        type Inner = MultiSendTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> MultiSendTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_DENOM: flatbuffers::VOffsetT = 6;
+        pub const VT_OUTPUTS: flatbuffers::VOffsetT = 8;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 10;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            MultiSendTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args MultiSendTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<MultiSendTx<'bldr>> {
+            let mut builder = MultiSendTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.outputs {
+                builder.add_outputs(x);
+            }
+            if let Some(x) = args.denom {
+                builder.add_denom(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MultiSendTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn denom(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MultiSendTx::VT_DENOM,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn outputs(
+            &self,
+        ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<MultiSendOutput<'a>>>>
+        {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab.get::<flatbuffers::ForwardsUOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<MultiSendOutput>>,
+                >>(MultiSendTx::VT_OUTPUTS, None)
+            }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(MultiSendTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MultiSendTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MultiSendTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        MultiSendTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(MultiSendTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for MultiSendTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "denom",
+                    Self::VT_DENOM,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<
+                    flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<MultiSendOutput>>,
+                >>("outputs", Self::VT_OUTPUTS, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct MultiSendTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub denom: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub outputs: Option<
+            flatbuffers::WIPOffset<
+                flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<MultiSendOutput<'a>>>,
+            >,
+        >,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for MultiSendTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            MultiSendTxArgs {
+                sender: None,
+                denom: None,
+                outputs: None,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct MultiSendTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> MultiSendTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(MultiSendTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_denom(&mut self, denom: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(MultiSendTx::VT_DENOM, denom);
+        }
+        #[inline]
+        pub fn add_outputs(
+            &mut self,
+            outputs: flatbuffers::WIPOffset<
+                flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<MultiSendOutput<'b>>>,
+            >,
+        ) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(MultiSendTx::VT_OUTPUTS, outputs);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(MultiSendTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                MultiSendTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                MultiSendTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                MultiSendTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(MultiSendTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> MultiSendTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            MultiSendTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<MultiSendTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for MultiSendTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("MultiSendTx");
+            ds.field("sender", &self.sender());
+            ds.field("denom", &self.denom());
+            ds.field("outputs", &self.outputs());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum VestingTransferTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct VestingTransferTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for VestingTransferTx<'a> {
+        type Inner = VestingTransferTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> VestingTransferTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_RECEIVER: flatbuffers::VOffsetT = 6;
+        pub const VT_DENOM: flatbuffers::VOffsetT = 8;
+        pub const VT_AMOUNT: flatbuffers::VOffsetT = 10;
+        pub const VT_UNLOCK_TICK: flatbuffers::VOffsetT = 12;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 14;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 18;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 20;
+        pub const VT_TIP: flatbuffers::VOffsetT = 22;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            VestingTransferTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args VestingTransferTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<VestingTransferTx<'bldr>> {
+            let mut builder = VestingTransferTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_unlock_tick(args.unlock_tick);
+            builder.add_amount(args.amount);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.denom {
+                builder.add_denom(x);
+            }
+            if let Some(x) = args.receiver {
+                builder.add_receiver(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VestingTransferTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn receiver(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VestingTransferTx::VT_RECEIVER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn denom(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VestingTransferTx::VT_DENOM,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn amount(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VestingTransferTx::VT_AMOUNT, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn unlock_tick(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VestingTransferTx::VT_UNLOCK_TICK, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VestingTransferTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VestingTransferTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VestingTransferTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VestingTransferTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VestingTransferTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for VestingTransferTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "receiver",
+                    Self::VT_RECEIVER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "denom",
+                    Self::VT_DENOM,
+                    false,
+                )?
+                .visit_field::<u64>("amount", Self::VT_AMOUNT, false)?
+                .visit_field::<u64>("unlock_tick", Self::VT_UNLOCK_TICK, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct VestingTransferTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub receiver: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub denom: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub amount: u64,
+        pub unlock_tick: u64,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for VestingTransferTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            VestingTransferTxArgs {
+                sender: None,
+                receiver: None,
+                denom: None,
+                amount: 0,
+                unlock_tick: 0,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct VestingTransferTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> VestingTransferTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VestingTransferTx::VT_SENDER,
+                sender,
+            );
+        }
+        #[inline]
+        pub fn add_receiver(
+            &mut self,
+            receiver: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VestingTransferTx::VT_RECEIVER,
+                receiver,
+            );
+        }
+        #[inline]
+        pub fn add_denom(&mut self, denom: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(VestingTransferTx::VT_DENOM, denom);
+        }
+        #[inline]
+        pub fn add_amount(&mut self, amount: u64) {
+            self.fbb_.push_slot::<u64>(VestingTransferTx::VT_AMOUNT, amount, 0);
+        }
+        #[inline]
+        pub fn add_unlock_tick(&mut self, unlock_tick: u64) {
+            self.fbb_.push_slot::<u64>(VestingTransferTx::VT_UNLOCK_TICK, unlock_tick, 0);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(VestingTransferTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VestingTransferTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VestingTransferTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VestingTransferTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(VestingTransferTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> VestingTransferTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            VestingTransferTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<VestingTransferTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for VestingTransferTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("VestingTransferTx");
+            ds.field("sender", &self.sender());
+            ds.field("receiver", &self.receiver());
+            ds.field("denom", &self.denom());
+            ds.field("amount", &self.amount());
+            ds.field("unlock_tick", &self.unlock_tick());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum ProposeTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct ProposeTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for ProposeTx<'a> {
+        type Inner = ProposeTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> ProposeTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_PARAMETER: flatbuffers::VOffsetT = 6;
+        pub const VT_VALUE: flatbuffers::VOffsetT = 8;
+        pub const VT_ACTIVATION_TICK: flatbuffers::VOffsetT = 10;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 12;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 14;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 16;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 18;
+        pub const VT_TIP: flatbuffers::VOffsetT = 20;
+        pub const VT_DENOM: flatbuffers::VOffsetT = 22;
+        pub const VT_MINTER: flatbuffers::VOffsetT = 24;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            ProposeTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args ProposeTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<ProposeTx<'bldr>> {
+            let mut builder = ProposeTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_activation_tick(args.activation_tick);
+            builder.add_value(args.value);
+            if let Some(x) = args.minter {
+                builder.add_minter(x);
+            }
+            if let Some(x) = args.denom {
+                builder.add_denom(x);
+            }
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            builder.add_parameter(args.parameter);
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        ProposeTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn parameter(&self) -> u32 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u32>(ProposeTx::VT_PARAMETER, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn value(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(ProposeTx::VT_VALUE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn activation_tick(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(ProposeTx::VT_ACTIVATION_TICK, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(ProposeTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        ProposeTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        ProposeTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        ProposeTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(ProposeTx::VT_TIP, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn denom(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        ProposeTx::VT_DENOM,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn minter(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        ProposeTx::VT_MINTER,
+                        None,
+                    )
+            }
+        }
+    }
+
+    impl flatbuffers::Verifiable for ProposeTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<u32>("parameter", Self::VT_PARAMETER, false)?
+                .visit_field::<u64>("value", Self::VT_VALUE, false)?
+                .visit_field::<u64>("activation_tick", Self::VT_ACTIVATION_TICK, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "denom",
+                    Self::VT_DENOM,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "minter",
+                    Self::VT_MINTER,
+                    false,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct ProposeTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub parameter: u32,
+        pub value: u64,
+        pub activation_tick: u64,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+        pub denom: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub minter: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for ProposeTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            ProposeTxArgs {
+                sender: None,
+                parameter: 0,
+                value: 0,
+                activation_tick: 0,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+                denom: None,
+                minter: None,
+            }
+        }
+    }
+
+    pub struct ProposeTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ProposeTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(ProposeTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_parameter(&mut self, parameter: u32) {
+            self.fbb_.push_slot::<u32>(ProposeTx::VT_PARAMETER, parameter, 0);
+        }
+        #[inline]
+        pub fn add_value(&mut self, value: u64) {
+            self.fbb_.push_slot::<u64>(ProposeTx::VT_VALUE, value, 0);
+        }
+        #[inline]
+        pub fn add_activation_tick(&mut self, activation_tick: u64) {
+            self.fbb_.push_slot::<u64>(ProposeTx::VT_ACTIVATION_TICK, activation_tick, 0);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(ProposeTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(ProposeTx::VT_SIGNATURE, signature);
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                ProposeTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                ProposeTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(ProposeTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn add_denom(&mut self, denom: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(ProposeTx::VT_DENOM, denom);
+        }
+        #[inline]
+        pub fn add_minter(&mut self, minter: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(ProposeTx::VT_MINTER, minter);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> ProposeTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            ProposeTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<ProposeTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for ProposeTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("ProposeTx");
+            ds.field("sender", &self.sender());
+            ds.field("parameter", &self.parameter());
+            ds.field("value", &self.value());
+            ds.field("activation_tick", &self.activation_tick());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.field("denom", &self.denom());
+            ds.field("minter", &self.minter());
+            ds.finish()
+        }
+    }
+    pub enum VoteTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct VoteTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for VoteTx<'a> {
+        type Inner = VoteTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> VoteTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_PROPOSAL_ID: flatbuffers::VOffsetT = 6;
+        pub const VT_APPROVE: flatbuffers::VOffsetT = 8;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 10;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            VoteTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args VoteTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<VoteTx<'bldr>> {
+            let mut builder = VoteTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_proposal_id(args.proposal_id);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.add_approve(args.approve);
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VoteTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn proposal_id(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VoteTx::VT_PROPOSAL_ID, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn approve(&self) -> bool {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<bool>(VoteTx::VT_APPROVE, Some(false)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VoteTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VoteTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VoteTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VoteTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VoteTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for VoteTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<u64>("proposal_id", Self::VT_PROPOSAL_ID, false)?
+                .visit_field::<bool>("approve", Self::VT_APPROVE, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct VoteTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub proposal_id: u64,
+        pub approve: bool,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for VoteTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            VoteTxArgs {
+                sender: None,
+                proposal_id: 0,
+                approve: false,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct VoteTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> VoteTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(VoteTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_proposal_id(&mut self, proposal_id: u64) {
+            self.fbb_.push_slot::<u64>(VoteTx::VT_PROPOSAL_ID, proposal_id, 0);
+        }
+        #[inline]
+        pub fn add_approve(&mut self, approve: bool) {
+            self.fbb_.push_slot::<bool>(VoteTx::VT_APPROVE, approve, false);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(VoteTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(VoteTx::VT_SIGNATURE, signature);
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VoteTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VoteTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(VoteTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> VoteTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            VoteTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<VoteTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for VoteTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("VoteTx");
+            ds.field("sender", &self.sender());
+            ds.field("proposal_id", &self.proposal_id());
+            ds.field("approve", &self.approve());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum SubmitForeignEpochTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct SubmitForeignEpochTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for SubmitForeignEpochTx<'a> {
+        type Inner = SubmitForeignEpochTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> SubmitForeignEpochTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_CLIENT_ID: flatbuffers::VOffsetT = 6;
+        pub const VT_EPOCH: flatbuffers::VOffsetT = 8;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 10;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            SubmitForeignEpochTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args SubmitForeignEpochTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<SubmitForeignEpochTx<'bldr>> {
+            let mut builder = SubmitForeignEpochTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.epoch {
+                builder.add_epoch(x);
+            }
+            if let Some(x) = args.client_id {
+                builder.add_client_id(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SubmitForeignEpochTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn client_id(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SubmitForeignEpochTx::VT_CLIENT_ID,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn epoch(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SubmitForeignEpochTx::VT_EPOCH,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SubmitForeignEpochTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SubmitForeignEpochTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SubmitForeignEpochTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SubmitForeignEpochTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SubmitForeignEpochTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for SubmitForeignEpochTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "client_id",
+                    Self::VT_CLIENT_ID,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "epoch",
+                    Self::VT_EPOCH,
+                    false,
+                )?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct SubmitForeignEpochTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub client_id: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub epoch: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for SubmitForeignEpochTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            SubmitForeignEpochTxArgs {
+                sender: None,
+                client_id: None,
+                epoch: None,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct SubmitForeignEpochTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SubmitForeignEpochTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SubmitForeignEpochTx::VT_SENDER,
+                sender,
+            );
+        }
+        #[inline]
+        pub fn add_client_id(
+            &mut self,
+            client_id: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SubmitForeignEpochTx::VT_CLIENT_ID,
+                client_id,
+            );
+        }
+        #[inline]
+        pub fn add_epoch(&mut self, epoch: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SubmitForeignEpochTx::VT_EPOCH,
+                epoch,
+            );
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(SubmitForeignEpochTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SubmitForeignEpochTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SubmitForeignEpochTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SubmitForeignEpochTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(SubmitForeignEpochTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> SubmitForeignEpochTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            SubmitForeignEpochTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<SubmitForeignEpochTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for SubmitForeignEpochTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("SubmitForeignEpochTx");
+            ds.field("sender", &self.sender());
+            ds.field("client_id", &self.client_id());
+            ds.field("epoch", &self.epoch());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum CreateAuctionTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct CreateAuctionTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for CreateAuctionTx<'a> {
+        type Inner = CreateAuctionTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> CreateAuctionTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_ITEM: flatbuffers::VOffsetT = 6;
+        pub const VT_RESERVE_PRICE: flatbuffers::VOffsetT = 8;
+        pub const VT_CLOSE_TICK: flatbuffers::VOffsetT = 10;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 12;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 14;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 16;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 18;
+        pub const VT_TIP: flatbuffers::VOffsetT = 20;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            CreateAuctionTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args CreateAuctionTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<CreateAuctionTx<'bldr>> {
+            let mut builder = CreateAuctionTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_close_tick(args.close_tick);
+            builder.add_reserve_price(args.reserve_price);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.item {
+                builder.add_item(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        CreateAuctionTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn item(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        CreateAuctionTx::VT_ITEM,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn reserve_price(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(CreateAuctionTx::VT_RESERVE_PRICE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn close_tick(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(CreateAuctionTx::VT_CLOSE_TICK, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(CreateAuctionTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        CreateAuctionTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        CreateAuctionTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        CreateAuctionTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(CreateAuctionTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for CreateAuctionTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "item",
+                    Self::VT_ITEM,
+                    false,
+                )?
+                .visit_field::<u64>("reserve_price", Self::VT_RESERVE_PRICE, false)?
+                .visit_field::<u64>("close_tick", Self::VT_CLOSE_TICK, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct CreateAuctionTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub item: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub reserve_price: u64,
+        pub close_tick: u64,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for CreateAuctionTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            CreateAuctionTxArgs {
+                sender: None,
+                item: None,
+                reserve_price: 0,
+                close_tick: 0,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct CreateAuctionTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CreateAuctionTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(CreateAuctionTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_item(&mut self, item: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(CreateAuctionTx::VT_ITEM, item);
+        }
+        #[inline]
+        pub fn add_reserve_price(&mut self, reserve_price: u64) {
+            self.fbb_.push_slot::<u64>(CreateAuctionTx::VT_RESERVE_PRICE, reserve_price, 0);
+        }
+        #[inline]
+        pub fn add_close_tick(&mut self, close_tick: u64) {
+            self.fbb_.push_slot::<u64>(CreateAuctionTx::VT_CLOSE_TICK, close_tick, 0);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(CreateAuctionTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                CreateAuctionTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                CreateAuctionTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                CreateAuctionTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(CreateAuctionTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> CreateAuctionTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            CreateAuctionTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<CreateAuctionTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for CreateAuctionTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("CreateAuctionTx");
+            ds.field("sender", &self.sender());
+            ds.field("item", &self.item());
+            ds.field("reserve_price", &self.reserve_price());
+            ds.field("close_tick", &self.close_tick());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum BidTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct BidTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for BidTx<'a> {
+        type Inner = BidTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> BidTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_AUCTION_ID: flatbuffers::VOffsetT = 6;
+        pub const VT_AMOUNT: flatbuffers::VOffsetT = 8;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 10;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            BidTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args BidTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<BidTx<'bldr>> {
+            let mut builder = BidTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_amount(args.amount);
+            builder.add_auction_id(args.auction_id);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        BidTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn auction_id(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(BidTx::VT_AUCTION_ID, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn amount(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(BidTx::VT_AMOUNT, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(BidTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        BidTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        BidTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        BidTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(BidTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for BidTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<u64>("auction_id", Self::VT_AUCTION_ID, false)?
+                .visit_field::<u64>("amount", Self::VT_AMOUNT, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct BidTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub auction_id: u64,
+        pub amount: u64,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for BidTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            BidTxArgs {
+                sender: None,
+                auction_id: 0,
+                amount: 0,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct BidTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> BidTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(BidTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_auction_id(&mut self, auction_id: u64) {
+            self.fbb_.push_slot::<u64>(BidTx::VT_AUCTION_ID, auction_id, 0);
+        }
+        #[inline]
+        pub fn add_amount(&mut self, amount: u64) {
+            self.fbb_.push_slot::<u64>(BidTx::VT_AMOUNT, amount, 0);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(BidTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(BidTx::VT_SIGNATURE, signature);
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                BidTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                BidTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(BidTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> BidTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            BidTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<BidTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for BidTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("BidTx");
+            ds.field("sender", &self.sender());
+            ds.field("auction_id", &self.auction_id());
+            ds.field("amount", &self.amount());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum SetGuardiansTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct SetGuardiansTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for SetGuardiansTx<'a> {
+        type Inner = SetGuardiansTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> SetGuardiansTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_GUARDIANS: flatbuffers::VOffsetT = 6;
+        pub const VT_THRESHOLD: flatbuffers::VOffsetT = 8;
+        pub const VT_DELAY_TICKS: flatbuffers::VOffsetT = 10;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 12;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 14;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 16;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 18;
+        pub const VT_TIP: flatbuffers::VOffsetT = 20;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            SetGuardiansTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args SetGuardiansTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<SetGuardiansTx<'bldr>> {
+            let mut builder = SetGuardiansTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            builder.add_delay_ticks(args.delay_ticks);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            builder.add_threshold(args.threshold);
+            if let Some(x) = args.guardians {
+                builder.add_guardians(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SetGuardiansTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn guardians(&self) -> Option<flatbuffers::Vector<'a, Bytes32>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, Bytes32>>>(
+                        SetGuardiansTx::VT_GUARDIANS,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn threshold(&self) -> u32 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u32>(SetGuardiansTx::VT_THRESHOLD, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn delay_ticks(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SetGuardiansTx::VT_DELAY_TICKS, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SetGuardiansTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SetGuardiansTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SetGuardiansTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        SetGuardiansTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(SetGuardiansTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for SetGuardiansTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, Bytes32>>>(
+                    "guardians",
+                    Self::VT_GUARDIANS,
+                    false,
+                )?
+                .visit_field::<u32>("threshold", Self::VT_THRESHOLD, false)?
+                .visit_field::<u64>("delay_ticks", Self::VT_DELAY_TICKS, false)?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct SetGuardiansTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub guardians: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, Bytes32>>>,
+        pub threshold: u32,
+        pub delay_ticks: u64,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for SetGuardiansTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            SetGuardiansTxArgs {
+                sender: None,
+                guardians: None,
+                threshold: 0,
+                delay_ticks: 0,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct SetGuardiansTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SetGuardiansTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(SetGuardiansTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_guardians(
+            &mut self,
+            guardians: flatbuffers::WIPOffset<flatbuffers::Vector<'b, Bytes32>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SetGuardiansTx::VT_GUARDIANS,
+                guardians,
+            );
+        }
+        #[inline]
+        pub fn add_threshold(&mut self, threshold: u32) {
+            self.fbb_.push_slot::<u32>(SetGuardiansTx::VT_THRESHOLD, threshold, 0);
+        }
+        #[inline]
+        pub fn add_delay_ticks(&mut self, delay_ticks: u64) {
+            self.fbb_.push_slot::<u64>(SetGuardiansTx::VT_DELAY_TICKS, delay_ticks, 0);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(SetGuardiansTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SetGuardiansTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SetGuardiansTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                SetGuardiansTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(SetGuardiansTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> SetGuardiansTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            SetGuardiansTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<SetGuardiansTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for SetGuardiansTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("SetGuardiansTx");
+            ds.field("sender", &self.sender());
+            ds.field("guardians", &self.guardians());
+            ds.field("threshold", &self.threshold());
+            ds.field("delay_ticks", &self.delay_ticks());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum RequestRecoveryTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct RequestRecoveryTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for RequestRecoveryTx<'a> {
+        type Inner = RequestRecoveryTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> RequestRecoveryTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_ACCOUNT: flatbuffers::VOffsetT = 6;
+        pub const VT_NEW_OWNER: flatbuffers::VOffsetT = 8;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 10;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            RequestRecoveryTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args RequestRecoveryTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<RequestRecoveryTx<'bldr>> {
+            let mut builder = RequestRecoveryTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.new_owner {
+                builder.add_new_owner(x);
+            }
+            if let Some(x) = args.account {
+                builder.add_account(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        RequestRecoveryTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn account(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        RequestRecoveryTx::VT_ACCOUNT,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn new_owner(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        RequestRecoveryTx::VT_NEW_OWNER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(RequestRecoveryTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        RequestRecoveryTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        RequestRecoveryTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        RequestRecoveryTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(RequestRecoveryTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for RequestRecoveryTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "account",
+                    Self::VT_ACCOUNT,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "new_owner",
+                    Self::VT_NEW_OWNER,
+                    false,
+                )?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct RequestRecoveryTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub account: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub new_owner: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for RequestRecoveryTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            RequestRecoveryTxArgs {
+                sender: None,
+                account: None,
+                new_owner: None,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct RequestRecoveryTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RequestRecoveryTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                RequestRecoveryTx::VT_SENDER,
+                sender,
+            );
+        }
+        #[inline]
+        pub fn add_account(
+            &mut self,
+            account: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                RequestRecoveryTx::VT_ACCOUNT,
+                account,
+            );
+        }
+        #[inline]
+        pub fn add_new_owner(
+            &mut self,
+            new_owner: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                RequestRecoveryTx::VT_NEW_OWNER,
+                new_owner,
+            );
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(RequestRecoveryTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                RequestRecoveryTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                RequestRecoveryTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                RequestRecoveryTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(RequestRecoveryTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> RequestRecoveryTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            RequestRecoveryTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<RequestRecoveryTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for RequestRecoveryTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("RequestRecoveryTx");
+            ds.field("sender", &self.sender());
+            ds.field("account", &self.account());
+            ds.field("new_owner", &self.new_owner());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum VetoRecoveryTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct VetoRecoveryTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for VetoRecoveryTx<'a> {
+        type Inner = VetoRecoveryTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> VetoRecoveryTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 6;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 8;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 10;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_TIP: flatbuffers::VOffsetT = 14;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            VetoRecoveryTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args VetoRecoveryTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<VetoRecoveryTx<'bldr>> {
+            let mut builder = VetoRecoveryTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VetoRecoveryTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VetoRecoveryTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VetoRecoveryTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VetoRecoveryTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        VetoRecoveryTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(VetoRecoveryTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for VetoRecoveryTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct VetoRecoveryTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for VetoRecoveryTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            VetoRecoveryTxArgs {
+                sender: None,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct VetoRecoveryTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> VetoRecoveryTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<flatbuffers::WIPOffset<_>>(VetoRecoveryTx::VT_SENDER, sender);
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(VetoRecoveryTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VetoRecoveryTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VetoRecoveryTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                VetoRecoveryTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(VetoRecoveryTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> VetoRecoveryTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            VetoRecoveryTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<VetoRecoveryTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for VetoRecoveryTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("VetoRecoveryTx");
+            ds.field("sender", &self.sender());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum TransferMintAuthorityTxOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct TransferMintAuthorityTx<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for TransferMintAuthorityTx<'a> {
+        type Inner = TransferMintAuthorityTx<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> TransferMintAuthorityTx<'a> {
+        pub const VT_SENDER: flatbuffers::VOffsetT = 4;
+        pub const VT_DENOM: flatbuffers::VOffsetT = 6;
+        pub const VT_NEW_AUTHORITY: flatbuffers::VOffsetT = 8;
+        pub const VT_NONCE: flatbuffers::VOffsetT = 10;
+        pub const VT_SIGNATURE: flatbuffers::VOffsetT = 12;
+        pub const VT_GAS_SPONSORER: flatbuffers::VOffsetT = 14;
+        pub const VT_SPONSOR_SIGNATURE: flatbuffers::VOffsetT = 16;
+        pub const VT_TIP: flatbuffers::VOffsetT = 18;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            TransferMintAuthorityTx { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args TransferMintAuthorityTxArgs<'args>,
+        ) -> flatbuffers::WIPOffset<TransferMintAuthorityTx<'bldr>> {
+            let mut builder = TransferMintAuthorityTxBuilder::new(_fbb);
+            builder.add_tip(args.tip);
+            builder.add_nonce(args.nonce);
+            if let Some(x) = args.sponsor_signature {
+                builder.add_sponsor_signature(x);
+            }
+            if let Some(x) = args.gas_sponsorer {
+                builder.add_gas_sponsorer(x);
+            }
+            if let Some(x) = args.signature {
+                builder.add_signature(x);
+            }
+            if let Some(x) = args.new_authority {
+                builder.add_new_owner(x);
+            }
+            if let Some(x) = args.denom {
+                builder.add_account(x);
+            }
+            if let Some(x) = args.sender {
+                builder.add_sender(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn sender(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        TransferMintAuthorityTx::VT_SENDER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn denom(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        TransferMintAuthorityTx::VT_DENOM,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn new_authority(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        TransferMintAuthorityTx::VT_NEW_AUTHORITY,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn nonce(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(TransferMintAuthorityTx::VT_NONCE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        TransferMintAuthorityTx::VT_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn gas_sponsorer(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        TransferMintAuthorityTx::VT_GAS_SPONSORER,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn sponsor_signature(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                        TransferMintAuthorityTx::VT_SPONSOR_SIGNATURE,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn tip(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(TransferMintAuthorityTx::VT_TIP, Some(0)).unwrap() }
+        }
+    }
+
+    impl flatbuffers::Verifiable for TransferMintAuthorityTx<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sender",
+                    Self::VT_SENDER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "denom",
+                    Self::VT_DENOM,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "new_authority",
+                    Self::VT_NEW_AUTHORITY,
+                    false,
+                )?
+                .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "signature",
+                    Self::VT_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "gas_sponsorer",
+                    Self::VT_GAS_SPONSORER,
+                    false,
+                )?
+                .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                    "sponsor_signature",
+                    Self::VT_SPONSOR_SIGNATURE,
+                    false,
+                )?
+                .visit_field::<u64>("tip", Self::VT_TIP, false)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct TransferMintAuthorityTxArgs<'a> {
+        pub sender: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub denom: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub new_authority: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub nonce: u64,
+        pub signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub gas_sponsorer: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub sponsor_signature: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+        pub tip: u64,
+    }
+    impl<'a> Default for TransferMintAuthorityTxArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            TransferMintAuthorityTxArgs {
+                sender: None,
+                denom: None,
+                new_authority: None,
+                nonce: 0,
+                signature: None,
+                gas_sponsorer: None,
+                sponsor_signature: None,
+                tip: 0,
+            }
+        }
+    }
+
+    pub struct TransferMintAuthorityTxBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TransferMintAuthorityTxBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                TransferMintAuthorityTx::VT_SENDER,
+                sender,
+            );
+        }
+        #[inline]
+        pub fn add_account(
+            &mut self,
+            denom: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                TransferMintAuthorityTx::VT_DENOM,
+                denom,
+            );
+        }
+        #[inline]
+        pub fn add_new_owner(
+            &mut self,
+            new_authority: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                TransferMintAuthorityTx::VT_NEW_AUTHORITY,
+                new_authority,
+            );
+        }
+        #[inline]
+        pub fn add_nonce(&mut self, nonce: u64) {
+            self.fbb_.push_slot::<u64>(TransferMintAuthorityTx::VT_NONCE, nonce, 0);
+        }
+        #[inline]
+        pub fn add_signature(
+            &mut self,
+            signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                TransferMintAuthorityTx::VT_SIGNATURE,
+                signature,
+            );
+        }
+        #[inline]
+        pub fn add_gas_sponsorer(
+            &mut self,
+            gas_sponsorer: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                TransferMintAuthorityTx::VT_GAS_SPONSORER,
+                gas_sponsorer,
+            );
+        }
+        #[inline]
+        pub fn add_sponsor_signature(
+            &mut self,
+            sponsor_signature: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                TransferMintAuthorityTx::VT_SPONSOR_SIGNATURE,
+                sponsor_signature,
+            );
+        }
+        #[inline]
+        pub fn add_tip(&mut self, tip: u64) {
+            self.fbb_.push_slot::<u64>(TransferMintAuthorityTx::VT_TIP, tip, 0);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> TransferMintAuthorityTxBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            TransferMintAuthorityTxBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> flatbuffers::WIPOffset<TransferMintAuthorityTx<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl core::fmt::Debug for TransferMintAuthorityTx<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut ds = f.debug_struct("TransferMintAuthorityTx");
+            ds.field("sender", &self.sender());
+            ds.field("denom", &self.denom());
+            ds.field("new_authority", &self.new_authority());
+            ds.field("nonce", &self.nonce());
+            ds.field("signature", &self.signature());
+            ds.field("gas_sponsorer", &self.gas_sponsorer());
+            ds.field("sponsor_signature", &self.sponsor_signature());
+            ds.field("tip", &self.tip());
+            ds.finish()
+        }
+    }
+    pub enum TransactionOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct Transaction<'a> {
+        pub _tab: flatbuffers::Table<'a>,
+    }
+
+    impl<'a> flatbuffers::Follow<'a> for Transaction<'a> {
+        type Inner = Transaction<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: flatbuffers::Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl<'a> Transaction<'a> {
+        pub const VT_BODY_TYPE: flatbuffers::VOffsetT = 4;
+        pub const VT_BODY: flatbuffers::VOffsetT = 6;
+        pub const VT_VERSION: flatbuffers::VOffsetT = 8;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+            Transaction { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args TransactionArgs,
+        ) -> flatbuffers::WIPOffset<Transaction<'bldr>> {
+            let mut builder = TransactionBuilder::new(_fbb);
+            builder.add_version(args.version);
+            if let Some(x) = args.body {
+                builder.add_body(x);
+            }
+            builder.add_body_type(args.body_type);
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn body_type(&self) -> TxBody {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<TxBody>(Transaction::VT_BODY_TYPE, Some(TxBody::NONE))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn body(&self) -> Option<flatbuffers::Table<'a>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(
+                        Transaction::VT_BODY,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn version(&self) -> u32 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u32>(Transaction::VT_VERSION, Some(0)).unwrap() }
+        }
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_send_tx(&self) -> Option<SendTx<'a>> {
+            if self.body_type() == TxBody::SendTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { SendTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_mint_tx(&self) -> Option<MintTx<'a>> {
+            if self.body_type() == TxBody::MintTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { MintTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_stake_tx(&self) -> Option<StakeTx<'a>> {
+            if self.body_type() == TxBody::StakeTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { StakeTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_solve_tx(&self) -> Option<SolveTx<'a>> {
+            if self.body_type() == TxBody::SolveTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { SolveTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_unstake_tx(&self) -> Option<UnstakeTx<'a>> {
+            if self.body_type() == TxBody::UnstakeTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { UnstakeTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_evidence_tx(&self) -> Option<EvidenceTx<'a>> {
+            if self.body_type() == TxBody::EvidenceTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { EvidenceTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_multi_send_tx(&self) -> Option<MultiSendTx<'a>> {
+            if self.body_type() == TxBody::MultiSendTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { MultiSendTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_vesting_transfer_tx(&self) -> Option<VestingTransferTx<'a>> {
+            if self.body_type() == TxBody::VestingTransferTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { VestingTransferTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_propose_tx(&self) -> Option<ProposeTx<'a>> {
+            if self.body_type() == TxBody::ProposeTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { ProposeTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_vote_tx(&self) -> Option<VoteTx<'a>> {
+            if self.body_type() == TxBody::VoteTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { VoteTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_submit_foreign_epoch_tx(&self) -> Option<SubmitForeignEpochTx<'a>> {
+            if self.body_type() == TxBody::SubmitForeignEpochTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { SubmitForeignEpochTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_create_auction_tx(&self) -> Option<CreateAuctionTx<'a>> {
+            if self.body_type() == TxBody::CreateAuctionTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { CreateAuctionTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_bid_tx(&self) -> Option<BidTx<'a>> {
+            if self.body_type() == TxBody::BidTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { BidTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_set_guardians_tx(&self) -> Option<SetGuardiansTx<'a>> {
+            if self.body_type() == TxBody::SetGuardiansTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { SetGuardiansTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_request_recovery_tx(&self) -> Option<RequestRecoveryTx<'a>> {
+            if self.body_type() == TxBody::RequestRecoveryTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { RequestRecoveryTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_veto_recovery_tx(&self) -> Option<VetoRecoveryTx<'a>> {
+            if self.body_type() == TxBody::VetoRecoveryTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { VetoRecoveryTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn body_as_transfer_mint_authority_tx(&self) -> Option<TransferMintAuthorityTx<'a>> {
+            if self.body_type() == TxBody::TransferMintAuthorityTx {
+                self.body().map(|t| {
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    unsafe { TransferMintAuthorityTx::init_from_table(t) }
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    impl flatbuffers::Verifiable for Transaction<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+            use self::flatbuffers::Verifiable;
+            v.visit_table(pos)?
+                .visit_union::<TxBody, _>(
+                    "body_type",
+                    Self::VT_BODY_TYPE,
                     "body",
                     Self::VT_BODY,
                     false,
@@ -1837,9 +6726,67 @@ pub mod tx {
                                 "TxBody::SolveTx",
                                 pos,
                             ),
+                        TxBody::UnstakeTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<UnstakeTx>>(
+                                "TxBody::UnstakeTx",
+                                pos,
+                            ),
+                        TxBody::EvidenceTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<EvidenceTx>>(
+                                "TxBody::EvidenceTx",
+                                pos,
+                            ),
+                        TxBody::MultiSendTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<MultiSendTx>>(
+                                "TxBody::MultiSendTx",
+                                pos,
+                            ),
+                        TxBody::VestingTransferTx => v.verify_union_variant::<
+                            flatbuffers::ForwardsUOffset<VestingTransferTx>,
+                        >("TxBody::VestingTransferTx", pos),
+                        TxBody::ProposeTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<ProposeTx>>(
+                                "TxBody::ProposeTx",
+                                pos,
+                            ),
+                        TxBody::VoteTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<VoteTx>>(
+                                "TxBody::VoteTx",
+                                pos,
+                            ),
+                        TxBody::SubmitForeignEpochTx => v.verify_union_variant::<
+                            flatbuffers::ForwardsUOffset<SubmitForeignEpochTx>,
+                        >("TxBody::SubmitForeignEpochTx", pos),
+                        TxBody::CreateAuctionTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<CreateAuctionTx>>(
+                                "TxBody::CreateAuctionTx",
+                                pos,
+                            ),
+                        TxBody::BidTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<BidTx>>(
+                                "TxBody::BidTx",
+                                pos,
+                            ),
+                        TxBody::SetGuardiansTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<SetGuardiansTx>>(
+                                "TxBody::SetGuardiansTx",
+                                pos,
+                            ),
+                        TxBody::RequestRecoveryTx => v.verify_union_variant::<
+                            flatbuffers::ForwardsUOffset<RequestRecoveryTx>,
+                        >("TxBody::RequestRecoveryTx", pos),
+                        TxBody::VetoRecoveryTx => v
+                            .verify_union_variant::<flatbuffers::ForwardsUOffset<VetoRecoveryTx>>(
+                                "TxBody::VetoRecoveryTx",
+                                pos,
+                            ),
+                        TxBody::TransferMintAuthorityTx => v.verify_union_variant::<
+                            flatbuffers::ForwardsUOffset<TransferMintAuthorityTx>,
+                        >("TxBody::TransferMintAuthorityTx", pos),
                         _ => Ok(()),
                     },
                 )?
+                .visit_field::<u32>("version", Self::VT_VERSION, false)?
                 .finish();
             Ok(())
         }
@@ -1847,6 +6794,7 @@ pub mod tx {
     pub struct TransactionArgs {
         pub body_type: TxBody,
         pub body: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+        pub version: u32,
     }
     impl<'a> Default for TransactionArgs {
         #[inline]
@@ -1854,6 +6802,7 @@ pub mod tx {
             TransactionArgs {
                 body_type: TxBody::NONE,
                 body: None,
+                version: 0,
             }
         }
     }
@@ -1874,6 +6823,10 @@ pub mod tx {
                 .push_slot_always::<flatbuffers::WIPOffset<_>>(Transaction::VT_BODY, body);
         }
         #[inline]
+        pub fn add_version(&mut self, version: u32) {
+            self.fbb_.push_slot::<u32>(Transaction::VT_VERSION, version, 0);
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> TransactionBuilder<'a, 'b, A> {
@@ -1935,11 +6888,142 @@ pub mod tx {
                         )
                     }
                 }
+                TxBody::UnstakeTx => {
+                    if let Some(x) = self.body_as_unstake_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::EvidenceTx => {
+                    if let Some(x) = self.body_as_evidence_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::MultiSendTx => {
+                    if let Some(x) = self.body_as_multi_send_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::VestingTransferTx => {
+                    if let Some(x) = self.body_as_vesting_transfer_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::ProposeTx => {
+                    if let Some(x) = self.body_as_propose_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::VoteTx => {
+                    if let Some(x) = self.body_as_vote_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::SubmitForeignEpochTx => {
+                    if let Some(x) = self.body_as_submit_foreign_epoch_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::CreateAuctionTx => {
+                    if let Some(x) = self.body_as_create_auction_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::BidTx => {
+                    if let Some(x) = self.body_as_bid_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::SetGuardiansTx => {
+                    if let Some(x) = self.body_as_set_guardians_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::RequestRecoveryTx => {
+                    if let Some(x) = self.body_as_request_recovery_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::VetoRecoveryTx => {
+                    if let Some(x) = self.body_as_veto_recovery_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                TxBody::TransferMintAuthorityTx => {
+                    if let Some(x) = self.body_as_transfer_mint_authority_tx() {
+                        ds.field("body", &x)
+                    } else {
+                        ds.field(
+                            "body",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
                 _ => {
                     let x: Option<()> = None;
                     ds.field("body", &x)
                 }
             };
+            ds.field("version", &self.version());
             ds.finish()
         }
     }
@@ -2,6 +2,11 @@
 
 pub mod decrypted;
 pub mod encrypted;
+pub mod proto;
+pub mod puzzle_pool;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategy;
+pub mod threshold;
 pub mod types;
 
 // Re-export the generated module
@@ -11,8 +16,21 @@ pub mod generated;
 
 pub use decrypted::*;
 pub use encrypted::*;
+pub use proto::{decode_envelope, encode_envelope};
+pub use puzzle_pool::PuzzlePool;
+pub use threshold::*;
 pub use types::*;
 
+/// Version of the timelock puzzle solver library
+pub const SOLVER_VERSION: &str = timelocks::VERSION;
+
+/// Allocations across the timelock solver library's boundary, by kind of object
+///
+/// See [`timelocks::memory`].
+pub fn solver_allocations() -> Vec<(&'static str, timelocks::memory::AllocationStats)> {
+    timelocks::memory::usage()
+}
+
 use sha2::{Digest, Sha256};
 
 /// Compute transaction hash
@@ -23,5 +41,6 @@ pub fn hash_transaction(data: &[u8]) -> [u8; 32] {
 pub mod prelude {
     pub use crate::decrypted::{flatbuffer_to_transaction, transaction_to_flatbuffer};
     pub use crate::encrypted::{decrypt_transaction, encrypt_transaction};
+    pub use crate::threshold::{open_threshold_transaction, seal_threshold_transaction};
     pub use crate::types::*;
 }
@@ -0,0 +1,204 @@
+// proto.rs - Protobuf wire format of envelopes for kala-transaction
+//
+// Envelopes are gossiped as `kala.network.Envelope`, see
+// kala-common/schema/network.proto. The sealed payload stays opaque; only
+// the AES-GCM framing and the key lock are spelled out. The same bytes are
+// what clients submit over RPC, see `encode_envelope`.
+
+use crate::threshold::{EncryptedShare, ThresholdLock};
+use crate::types::{KeyLock, RSWPuzzle, SealedTransaction, TimelockTransaction};
+use kala_common::prelude::{KalaError, KalaResult};
+use kala_common::proto::{self, envelope, fixed, narrow, required, ProtoCodec};
+
+impl ProtoCodec for SealedTransaction {
+    type Proto = proto::SealedTransaction;
+
+    fn to_proto(&self) -> proto::SealedTransaction {
+        proto::SealedTransaction {
+            nonce: self.nonce.to_vec(),
+            tag: self.tag.to_vec(),
+            ciphertext: self.ciphertext.clone(),
+        }
+    }
+
+    fn from_proto(proto: proto::SealedTransaction) -> KalaResult<Self> {
+        Ok(Self {
+            nonce: fixed(proto.nonce, "nonce")?,
+            tag: fixed(proto.tag, "tag")?,
+            ciphertext: proto.ciphertext,
+        })
+    }
+}
+
+impl ProtoCodec for RSWPuzzle {
+    type Proto = proto::PuzzleLock;
+
+    fn to_proto(&self) -> proto::PuzzleLock {
+        proto::PuzzleLock {
+            puzzle_value: self.puzzle_value.clone(),
+            a: self.a.clone(),
+            n: self.n.clone(),
+            hardness: self.hardness,
+        }
+    }
+
+    fn from_proto(proto: proto::PuzzleLock) -> KalaResult<Self> {
+        Ok(Self {
+            puzzle_value: proto.puzzle_value,
+            a: proto.a,
+            n: proto.n,
+            hardness: proto.hardness,
+        })
+    }
+}
+
+impl ProtoCodec for EncryptedShare {
+    type Proto = proto::EncryptedShare;
+
+    fn to_proto(&self) -> proto::EncryptedShare {
+        proto::EncryptedShare {
+            witness: self.witness.to_vec(),
+            index: self.index.into(),
+            commitment: self.commitment.to_vec(),
+            ciphertext: self.ciphertext.clone(),
+        }
+    }
+
+    fn from_proto(proto: proto::EncryptedShare) -> KalaResult<Self> {
+        Ok(Self {
+            witness: fixed(proto.witness, "share witness")?,
+            index: narrow(proto.index, "share index")?,
+            commitment: fixed(proto.commitment, "share commitment")?,
+            ciphertext: proto.ciphertext,
+        })
+    }
+}
+
+impl ProtoCodec for ThresholdLock {
+    type Proto = proto::ThresholdLock;
+
+    fn to_proto(&self) -> proto::ThresholdLock {
+        proto::ThresholdLock {
+            threshold: self.threshold.into(),
+            ephemeral_key: self.ephemeral_key.to_vec(),
+            shares: self.shares.iter().map(ProtoCodec::to_proto).collect(),
+        }
+    }
+
+    fn from_proto(proto: proto::ThresholdLock) -> KalaResult<Self> {
+        Ok(Self {
+            threshold: narrow(proto.threshold, "threshold")?,
+            ephemeral_key: fixed(proto.ephemeral_key, "ephemeral key")?,
+            shares: proto
+                .shares
+                .into_iter()
+                .map(EncryptedShare::from_proto)
+                .collect::<KalaResult<_>>()?,
+        })
+    }
+}
+
+impl ProtoCodec for TimelockTransaction {
+    type Proto = proto::Envelope;
+
+    fn to_proto(&self) -> proto::Envelope {
+        proto::Envelope {
+            sealed: Some(self.encrypted_data.to_proto()),
+            submission_iteration: self.submission_iteration,
+            target_tick: self.target_tick,
+            lock: Some(match &self.lock {
+                KeyLock::Puzzle(puzzle) => envelope::Lock::Puzzle(puzzle.to_proto()),
+                KeyLock::Threshold(lock) => envelope::Lock::Threshold(lock.to_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: proto::Envelope) -> KalaResult<Self> {
+        Ok(Self {
+            encrypted_data: SealedTransaction::from_proto(required(proto.sealed, "sealed transaction")?)?,
+            lock: match required(proto.lock, "key lock")? {
+                envelope::Lock::Puzzle(puzzle) => KeyLock::Puzzle(RSWPuzzle::from_proto(puzzle)?),
+                envelope::Lock::Threshold(lock) => KeyLock::Threshold(ThresholdLock::from_proto(lock)?),
+            },
+            submission_iteration: proto.submission_iteration,
+            target_tick: proto.target_tick,
+        })
+    }
+}
+
+/// Encode an envelope in its canonical wire format
+///
+/// The protobuf encoding of `kala.network.Envelope`, as gossiped between
+/// peers, so clients in any language can build it from the schema.
+pub fn encode_envelope(envelope: &TimelockTransaction) -> Vec<u8> {
+    envelope.encode_proto()
+}
+
+/// Decode an envelope from [`encode_envelope`] bytes
+///
+/// Also accepts the serde JSON serialization clients sent before the wire
+/// format was fixed. It is told apart by its opening brace, which no
+/// envelope encoding starts with.
+pub fn decode_envelope(bytes: &[u8]) -> KalaResult<TimelockTransaction> {
+    if bytes.first() == Some(&b'{') {
+        return serde_json::from_slice(bytes)
+            .map_err(|e| KalaError::serialization(format!("Invalid JSON envelope: {}", e)));
+    }
+    TimelockTransaction::decode_proto(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold::seal_threshold_transaction;
+    use crate::types::{Send, Transaction};
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let tx = Transaction::Send(Send {
+            sender: [1u8; 32],
+            receiver: [2u8; 32],
+            denom: [3u8; 32],
+            amount: 1000,
+            nonce: 1,
+            signature: [0u8; 64].to_vec(),
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: 0,
+        });
+        let witnesses: Vec<[u8; 32]> = (1..=3u8)
+            .map(|seed| SigningKey::from_bytes(&[seed; 32]).verifying_key().to_bytes())
+            .collect();
+        let envelope = seal_threshold_transaction(&tx, 5, 100, &witnesses, 2).unwrap();
+
+        let decoded = TimelockTransaction::decode_proto(&envelope.encode_proto()).unwrap();
+        assert_eq!(decoded.envelope_hash(), envelope.envelope_hash());
+        assert_eq!(decoded.submission_iteration, 100);
+
+        let puzzle = TimelockTransaction {
+            lock: KeyLock::Puzzle(RSWPuzzle {
+                puzzle_value: vec![1, 2],
+                a: vec![3],
+                n: vec![4, 5, 6],
+                hardness: 20,
+            }),
+            ..envelope
+        };
+        let decoded = TimelockTransaction::decode_proto(&puzzle.encode_proto()).unwrap();
+        assert_eq!(decoded.envelope_hash(), puzzle.envelope_hash());
+
+        // Envelopes without a key lock are rejected
+        let mut unlocked = puzzle.to_proto();
+        unlocked.lock = None;
+        assert!(TimelockTransaction::from_proto(unlocked).is_err());
+
+        // The wire format, and the JSON clients used to send
+        let decoded = decode_envelope(&encode_envelope(&puzzle)).unwrap();
+        assert_eq!(decoded.envelope_hash(), puzzle.envelope_hash());
+        let decoded = decode_envelope(&serde_json::to_vec(&puzzle).unwrap()).unwrap();
+        assert_eq!(decoded.envelope_hash(), puzzle.envelope_hash());
+        assert!(decode_envelope(b"{}").is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! Pregenerated RSW moduli for sealing envelopes quickly
+//!
+//! Creating a puzzle takes a 2048-bit modulus n = pq, and finding its two
+//! 1024-bit primes dwarfs the rest of sealing a transaction. A
+//! [`PuzzlePool`] finds them on a background thread ahead of time, and
+//! keeps each modulus with its trapdoor λ(n) = lcm(p-1, q-1), which is
+//! all puzzle creation needs of the factorization.
+//!
+//! A modulus is reused for a bounded number of puzzles, each with a fresh
+//! random base a. The squarings that solve one puzzle become public once
+//! it is decrypted, but they are of a different base, so they don't
+//! shorten any other puzzle on the same modulus. Once a modulus's uses
+//! are spent, or the pool is dropped, the pool's copy of its trapdoor is
+//! zeroized.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+use kala_common::prelude::{KalaError, KalaResult};
+use rand::Rng;
+use rug::integer::Order;
+use rug::rand::RandState;
+use rug::Integer;
+use zeroize::Zeroizing;
+
+use crate::types::{RSWPuzzle, AES_KEY_SIZE};
+
+/// Moduli a pool keeps ready by default
+pub const DEFAULT_POOL_SIZE: usize = 4;
+
+/// Puzzles a modulus is used for by default
+pub const DEFAULT_USES_PER_MODULUS: u32 = 16;
+
+/// A GMP random state seeded from the operating system
+///
+/// GMP's default seed is fixed, so an unseeded state would give every
+/// process the same primes.
+pub(crate) fn seeded_rand_state() -> RandState<'static> {
+    let seed = Zeroizing::new(rand::rng().random::<[u8; 32]>());
+    let mut rand_state = RandState::new();
+    rand_state.seed(&Integer::from_digits(&seed[..], Order::Lsf));
+    rand_state
+}
+
+/// Overwrite a secret integer's limbs with zeros
+///
+/// GMP frees limbs without clearing them. Clearing bits from the lowest
+/// up zeroes each limb in place before the integer shrinks past it.
+pub(crate) fn wipe_integer(x: &mut Integer) {
+    for bit in 0..x.significant_bits() {
+        x.set_bit(bit, false);
+    }
+}
+
+/// A modulus and what creating puzzles on it takes
+struct Trapdoor {
+    n: Integer,
+    /// λ(n), big-endian, wiped when the trapdoor is dropped
+    lambda: Zeroizing<Vec<u8>>,
+    uses_left: u32,
+}
+
+impl Trapdoor {
+    fn generate(modulus_bits: usize, uses: u32) -> Self {
+        let mut rand_state = seeded_rand_state();
+        let bits = (modulus_bits / 2) as u32;
+
+        let mut p = Integer::from(Integer::random_bits(bits, &mut rand_state));
+        p.next_prime_mut();
+        let mut q = Integer::from(Integer::random_bits(bits, &mut rand_state));
+        q.next_prime_mut();
+
+        let n = Integer::from(&p * &q);
+        let mut q_minus_1 = Integer::from(&q - 1);
+        let mut lambda = Integer::from(&p - 1).lcm(&q_minus_1);
+        let trapdoor = Self {
+            n,
+            lambda: Zeroizing::new(lambda.to_digits::<u8>(Order::Msf)),
+            uses_left: uses,
+        };
+        for secret in [&mut p, &mut q, &mut q_minus_1, &mut lambda] {
+            wipe_integer(secret);
+        }
+        trapdoor
+    }
+
+    /// Lock `key` behind `hardness` squarings of a fresh random base
+    fn create_puzzle(&self, key: &[u8; AES_KEY_SIZE], hardness: u32) -> KalaResult<RSWPuzzle> {
+        let mut rand_state = seeded_rand_state();
+
+        // a in [2, n-2], coprime to n
+        let a = loop {
+            let a = Integer::from(&self.n - 3u32).random_below(&mut rand_state) + 2u32;
+            if Integer::from(a.gcd_ref(&self.n)) == 1u32 {
+                break a;
+            }
+        };
+
+        // a^(2^hardness) mod n, with the exponent reduced mod λ(n)
+        let mut lambda = Integer::from_digits(&self.lambda[..], Order::Msf);
+        let reduced_exp = Integer::from(2).pow_mod(&Integer::from(hardness), &lambda);
+        wipe_integer(&mut lambda);
+        let mut reduced_exp =
+            reduced_exp.map_err(|e| KalaError::crypto(format!("pow_mod failed: {e}")))?;
+        let a_power = a.clone().pow_mod(&reduced_exp, &self.n);
+        wipe_integer(&mut reduced_exp);
+        let mut a_power =
+            a_power.map_err(|e| KalaError::crypto(format!("pow_mod failed: {e}")))?;
+
+        let mut key_int = Integer::from_digits(key, Order::Lsf);
+        let puzzle_value = Integer::from(&key_int + &a_power) % &self.n;
+        wipe_integer(&mut key_int);
+        wipe_integer(&mut a_power);
+        Ok(RSWPuzzle {
+            puzzle_value: puzzle_value.to_digits::<u8>(Order::Msf),
+            a: a.to_digits::<u8>(Order::Msf),
+            n: self.n.to_digits::<u8>(Order::Msf),
+            hardness,
+        })
+    }
+}
+
+struct Shared {
+    ready: Mutex<VecDeque<Trapdoor>>,
+    /// Signalled when a trapdoor is taken, or the pool is dropped
+    taken: Condvar,
+    stopped: AtomicBool,
+}
+
+/// Moduli generated in the background, for creating puzzles without waiting on primes
+///
+/// # Example
+///
+/// ```no_run
+/// use kala_transaction::PuzzlePool;
+///
+/// let pool = PuzzlePool::new(2048, 4, 16);
+/// let puzzle = pool.generate_puzzle_fast(&[7u8; 32], 10_000)?;
+/// # Ok::<(), kala_common::prelude::KalaError>(())
+/// ```
+pub struct PuzzlePool {
+    shared: Arc<Shared>,
+    modulus_bits: usize,
+    uses_per_modulus: u32,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl PuzzlePool {
+    /// Start a pool and its generating thread
+    ///
+    /// # Parameters
+    /// * `modulus_bits` - Size of the moduli, 2048 for the GPU solver
+    /// * `size` - Moduli to keep ready
+    /// * `uses_per_modulus` - Puzzles to create on each modulus
+    pub fn new(modulus_bits: usize, size: usize, uses_per_modulus: u32) -> Self {
+        let shared = Arc::new(Shared {
+            ready: Mutex::new(VecDeque::with_capacity(size)),
+            taken: Condvar::new(),
+            stopped: AtomicBool::new(false),
+        });
+        let uses_per_modulus = uses_per_modulus.max(1);
+        let worker = std::thread::Builder::new()
+            .name("puzzle-pool".to_string())
+            .spawn({
+                let shared = shared.clone();
+                move || Self::fill(&shared, modulus_bits, size, uses_per_modulus)
+            })
+            .expect("Failed to spawn puzzle pool thread");
+        Self {
+            shared,
+            modulus_bits,
+            uses_per_modulus,
+            worker: Some(worker),
+        }
+    }
+
+    /// Keep `size` moduli ready until the pool is dropped
+    fn fill(shared: &Shared, modulus_bits: usize, size: usize, uses: u32) {
+        while !shared.stopped.load(Ordering::Acquire) {
+            let mut ready = shared.ready.lock().unwrap();
+            while ready.len() >= size && !shared.stopped.load(Ordering::Acquire) {
+                ready = shared.taken.wait(ready).unwrap();
+            }
+            drop(ready);
+            if shared.stopped.load(Ordering::Acquire) {
+                break;
+            }
+            let trapdoor = Trapdoor::generate(modulus_bits, uses);
+            shared.ready.lock().unwrap().push_back(trapdoor);
+        }
+    }
+
+    /// Moduli ready to create puzzles on
+    pub fn ready(&self) -> usize {
+        self.shared.ready.lock().unwrap().len()
+    }
+
+    /// Create a puzzle locking `key` on a pregenerated modulus
+    ///
+    /// Falls back to generating a modulus on the calling thread if none
+    /// is ready, so it is never slower than [`RSWTimelock::generate_puzzle`].
+    ///
+    /// [`RSWTimelock::generate_puzzle`]: crate::RSWTimelock::generate_puzzle
+    ///
+    /// # Parameters
+    /// * `key` - AES key the puzzle locks
+    /// * `hardness` - Squarings needed to solve it
+    ///
+    /// # Returns
+    /// The puzzle, with a base no other puzzle uses
+    pub fn generate_puzzle_fast(
+        &self,
+        key: &[u8; AES_KEY_SIZE],
+        hardness: u32,
+    ) -> KalaResult<RSWPuzzle> {
+        let taken = self.shared.ready.lock().unwrap().pop_front();
+        let mut trapdoor = match taken {
+            Some(trapdoor) => trapdoor,
+            None => {
+                tracing::debug!("Puzzle pool empty, generating a modulus inline");
+                Trapdoor::generate(self.modulus_bits, self.uses_per_modulus)
+            }
+        };
+        let puzzle = trapdoor.create_puzzle(key, hardness);
+
+        trapdoor.uses_left -= 1;
+        if trapdoor.uses_left > 0 {
+            self.shared.ready.lock().unwrap().push_front(trapdoor);
+        } else {
+            self.shared.taken.notify_one();
+        }
+        puzzle
+    }
+}
+
+impl Default for PuzzlePool {
+    /// A pool of 2048-bit moduli with the default size and reuse
+    fn default() -> Self {
+        Self::new(2048, DEFAULT_POOL_SIZE, DEFAULT_USES_PER_MODULUS)
+    }
+}
+
+impl Drop for PuzzlePool {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::Release);
+        self.shared.taken.notify_all();
+        // A modulus in progress is finished and wiped with the others
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        self.shared.ready.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recover the key by squaring, the way the solver does
+    fn solve(puzzle: &RSWPuzzle) -> [u8; AES_KEY_SIZE] {
+        let n = Integer::from_digits(&puzzle.n, Order::Msf);
+        let mut x = Integer::from_digits(&puzzle.a, Order::Msf);
+        for _ in 0..puzzle.hardness {
+            x = x.square() % &n;
+        }
+        let c = Integer::from_digits(&puzzle.puzzle_value, Order::Msf);
+        let key = (c - x).modulo(&n);
+        let mut bytes = [0u8; AES_KEY_SIZE];
+        key.write_digits(&mut bytes, Order::Lsf);
+        bytes
+    }
+
+    #[test]
+    fn test_wipe_integer() {
+        let mut x = Integer::from_digits(&[0xa5u8; 256], Order::Msf);
+        wipe_integer(&mut x);
+        assert_eq!(x, 0u32);
+    }
+
+    #[test]
+    fn test_pool_puzzles_solve() {
+        let pool = PuzzlePool::new(512, 1, 3);
+        let keys: Vec<[u8; AES_KEY_SIZE]> = (1..=4u8).map(|i| [i; AES_KEY_SIZE]).collect();
+        let puzzles: Vec<RSWPuzzle> = keys
+            .iter()
+            .map(|key| pool.generate_puzzle_fast(key, 100).unwrap())
+            .collect();
+
+        for (puzzle, key) in puzzles.iter().zip(&keys) {
+            assert_eq!(&solve(puzzle), key);
+        }
+        // A modulus serves three puzzles, each with its own base
+        assert_eq!(puzzles[0].n, puzzles[2].n);
+        assert_ne!(puzzles[0].n, puzzles[3].n);
+        assert_ne!(puzzles[0].a, puzzles[1].a);
+    }
+}
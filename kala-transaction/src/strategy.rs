@@ -0,0 +1,210 @@
+//! Proptest strategies for sequences of signed transactions
+//!
+//! Transactions move funds between a handful of [`account`]s, so a
+//! generated sequence keeps touching the same balances. Bodies are drawn
+//! unsigned, then [`sequence`] numbers and signs them: a sender's nonces
+//! mostly follow on from its previous transaction in the sequence, with
+//! the odd replay or gap, so sequences exercise rejected and held
+//! transactions as well as applied ones.
+//!
+//! Built for the crate's own tests, and for other crates with the
+//! `proptest` feature.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use ed25519_dalek::SigningKey;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::types::{Mint, MultiSend, MultiSendOutput, Send, Stake, Transaction, Unstake};
+
+/// Number of accounts transactions are drawn over
+pub const ACCOUNTS: u8 = 4;
+
+/// Keys and addresses of the accounts, by index
+fn accounts() -> &'static [(SigningKey, [u8; 32])] {
+    static ACCOUNT_KEYS: OnceLock<Vec<(SigningKey, [u8; 32])>> = OnceLock::new();
+    ACCOUNT_KEYS.get_or_init(|| {
+        (0..ACCOUNTS)
+            .map(|index| {
+                let key = SigningKey::from_bytes(&[index + 1; 32]);
+                let address = key.verifying_key().to_bytes();
+                (key, address)
+            })
+            .collect()
+    })
+}
+
+/// Signing key of account `index`
+pub fn account_key(index: u8) -> SigningKey {
+    accounts()[index as usize].0.clone()
+}
+
+/// Address of account `index`
+pub fn account(index: u8) -> [u8; 32] {
+    accounts()[index as usize].1
+}
+
+fn address() -> impl Strategy<Value = [u8; 32]> {
+    (0..ACCOUNTS).prop_map(account)
+}
+
+fn amount() -> impl Strategy<Value = u64> {
+    0..1_000u64
+}
+
+fn tip() -> impl Strategy<Value = u64> {
+    prop_oneof![3 => Just(0), 1 => 1..50u64]
+}
+
+/// A transfer between two accounts
+pub fn send() -> impl Strategy<Value = Transaction> {
+    (address(), address(), amount(), tip()).prop_map(|(sender, receiver, amount, tip)| {
+        Transaction::Send(Send {
+            sender,
+            receiver,
+            denom: [0u8; 32],
+            amount,
+            nonce: 0,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip,
+        })
+    })
+}
+
+/// A transfer to up to three accounts
+pub fn multi_send() -> impl Strategy<Value = Transaction> {
+    let outputs = vec(
+        (address(), amount()).prop_map(|(receiver, amount)| MultiSendOutput { receiver, amount }),
+        1..4,
+    );
+    (address(), outputs, tip()).prop_map(|(sender, outputs, tip)| {
+        Transaction::MultiSend(MultiSend {
+            sender,
+            denom: [0u8; 32],
+            outputs,
+            nonce: 0,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            sponsor_signature: Vec::new(),
+            tip,
+        })
+    })
+}
+
+/// Newly created funds credited to the sender
+pub fn mint() -> impl Strategy<Value = Transaction> {
+    (address(), amount(), tip()).prop_map(|(sender, amount, tip)| {
+        Transaction::Mint(Mint {
+            sender,
+            amount,
+            denom: [0u8; 32],
+            nonce: 0,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            sponsor_signature: Vec::new(),
+            tip,
+        })
+    })
+}
+
+/// Stake delegated to one of the accounts
+pub fn stake() -> impl Strategy<Value = Transaction> {
+    (address(), address(), amount(), tip()).prop_map(|(sender, validator, amount, tip)| {
+        Transaction::Stake(Stake {
+            sender,
+            delegation_receiver: validator,
+            amount,
+            nonce: 0,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            sponsor_signature: Vec::new(),
+            tip,
+        })
+    })
+}
+
+/// Stake withdrawn from the sender's delegation
+pub fn unstake() -> impl Strategy<Value = Transaction> {
+    (address(), amount(), tip()).prop_map(|(sender, amount, tip)| {
+        Transaction::Unstake(Unstake {
+            sender,
+            amount,
+            nonce: 0,
+            signature: vec![0u8; 64],
+            gas_sponsorer: [0u8; 32],
+            sponsor_signature: Vec::new(),
+            tip,
+        })
+    })
+}
+
+/// Any of the bodies above, mostly transfers
+pub fn transaction() -> impl Strategy<Value = Transaction> {
+    prop_oneof![
+        4 => send(),
+        1 => multi_send(),
+        2 => mint(),
+        1 => stake(),
+        1 => unstake(),
+    ]
+}
+
+/// Nonce of a transaction relative to the one following its sender's last
+fn nonce_skew() -> impl Strategy<Value = i64> {
+    prop_oneof![8 => Just(0), 1 => Just(-1), 1 => Just(1)]
+}
+
+fn set_nonce(tx: &mut Transaction, nonce: u64) {
+    match tx {
+        Transaction::Send(t) => t.nonce = nonce,
+        Transaction::MultiSend(t) => t.nonce = nonce,
+        Transaction::Mint(t) => t.nonce = nonce,
+        Transaction::Stake(t) => t.nonce = nonce,
+        Transaction::Unstake(t) => t.nonce = nonce,
+        other => unreachable!("No strategy draws {} transactions", other.module()),
+    }
+}
+
+/// Sequences of `len` transactions drawn from `body`, signed for `chain_id`
+///
+/// # Parameters
+/// * `body` - Strategy of unsigned bodies sent by the [`account`]s
+/// * `len` - Range of sequence lengths
+/// * `chain_id` - Chain the transactions are signed for
+///
+/// # Returns
+/// Transactions in the order they were drawn, which nonces count from 1
+/// in per sender
+pub fn sequence(
+    body: impl Strategy<Value = Transaction>,
+    len: Range<usize>,
+    chain_id: &str,
+) -> impl Strategy<Value = Vec<Transaction>> {
+    let chain_id = chain_id.to_string();
+    vec((body, nonce_skew()), len).prop_map(move |drawn| {
+        let mut nonces: HashMap<[u8; 32], u64> = HashMap::new();
+        drawn
+            .into_iter()
+            .map(|(mut tx, skew)| {
+                let sender = *tx.sender();
+                let next = nonces.get(&sender).copied().unwrap_or(0) + 1;
+                if skew == 0 {
+                    nonces.insert(sender, next);
+                }
+                set_nonce(&mut tx, next.saturating_add_signed(skew).max(1));
+                let (key, _) = accounts()
+                    .iter()
+                    .find(|(_, address)| *address == sender)
+                    .expect("bodies are sent by the accounts");
+                tx.sign(key, &chain_id).expect("transaction signs");
+                tx
+            })
+            .collect()
+    })
+}
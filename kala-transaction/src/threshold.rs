@@ -0,0 +1,274 @@
+// threshold.rs - Threshold key locks for kala-transaction
+//
+// An alternative to RSW puzzles: the AES key of an envelope is split with
+// Shamir secret sharing and each witness's share is encrypted to its
+// Ed25519 key. After the tick's ordering commitment, witnesses reveal
+// their shares and any node holding `threshold` of them decrypts the
+// envelope. No sequential work is needed, but a coalition of `threshold`
+// witnesses could decrypt early, so the threshold should exceed the weight
+// faulty witnesses may hold.
+
+use crate::encrypted::{decrypt_transaction, encrypt_transaction};
+use crate::types::{KeyLock, TimelockTransaction, Transaction, AES_KEY_SIZE};
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
+use ed25519_dalek::SigningKey;
+use kala_common::crypto::shamir::{self, KeyShare};
+use kala_common::crypto::signer::key_agreement;
+use kala_common::prelude::{KalaError, KalaResult};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// A witness's share of an envelope key, encrypted to the witness
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedShare {
+    /// Ed25519 key of the witness the share is for
+    pub witness: [u8; 32],
+    pub index: u8,
+    /// [`KeyShare::commitment`] of the share, to check it once revealed
+    pub commitment: [u8; 32],
+    /// Share value sealed with AES-256-GCM under the agreed key
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedShare {
+    /// Decrypt the share with the witness's agreed secret
+    ///
+    /// `shared_secret` is the witness's key agreement with the lock's
+    /// ephemeral key, see [`Signer::key_agreement`](kala_common::crypto::signer::Signer::key_agreement).
+    pub fn open(&self, ephemeral_key: &[u8; 32], shared_secret: &[u8; 32]) -> KalaResult<KeyShare> {
+        let cipher = share_cipher(shared_secret, ephemeral_key, &self.witness);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&[0u8; 12]), self.ciphertext.as_ref())
+            .map(Zeroizing::new)
+            .map_err(|e| KalaError::crypto(format!("Share decryption failed: {e}")))?;
+        let value = plaintext[..]
+            .try_into()
+            .map_err(|_| KalaError::crypto("Invalid share size".to_string()))?;
+
+        let share = KeyShare {
+            index: self.index,
+            value,
+        };
+        if share.commitment() != self.commitment {
+            return Err(KalaError::crypto("Share does not match its commitment".to_string()));
+        }
+        Ok(share)
+    }
+}
+
+/// Key of an envelope, Shamir-shared among witnesses
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThresholdLock {
+    /// Shares needed to recover the key
+    pub threshold: u8,
+    /// Public half of the sender's one-off Ed25519 key, for key agreement
+    pub ephemeral_key: [u8; 32],
+    pub shares: Vec<EncryptedShare>,
+}
+
+impl ThresholdLock {
+    /// Share `key` among `witnesses`, any `threshold` of which recover it
+    pub fn seal(key: &[u8; AES_KEY_SIZE], witnesses: &[[u8; 32]], threshold: u8) -> KalaResult<Self> {
+        let count = u8::try_from(witnesses.len())
+            .map_err(|_| KalaError::validation(format!("Too many witnesses: {}", witnesses.len())))?;
+        let shares = shamir::split(key, threshold, count)?;
+
+        // Each share is encrypted under its own agreed key, so a zero nonce is safe
+        let mut seed = Zeroizing::new([0u8; 32]);
+        rand::rng().fill(&mut seed[..]);
+        let ephemeral = SigningKey::from_bytes(&seed);
+        let ephemeral_key = ephemeral.verifying_key().to_bytes();
+
+        let shares = witnesses
+            .iter()
+            .zip(shares)
+            .map(|(witness, share)| {
+                let shared_secret = key_agreement(&ephemeral, witness)?;
+                let ciphertext = share_cipher(&shared_secret, &ephemeral_key, witness)
+                    .encrypt(Nonce::from_slice(&[0u8; 12]), share.value.as_ref())
+                    .map_err(|e| KalaError::crypto(format!("Share encryption failed: {e}")))?;
+                Ok(EncryptedShare {
+                    witness: *witness,
+                    index: share.index,
+                    commitment: share.commitment(),
+                    ciphertext,
+                })
+            })
+            .collect::<KalaResult<Vec<_>>>()?;
+
+        Ok(Self {
+            threshold,
+            ephemeral_key,
+            shares,
+        })
+    }
+
+    /// Check the lock is well formed
+    ///
+    /// The threshold must be reachable, and every share must go to a
+    /// distinct witness at a distinct, non-zero index.
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.threshold == 0 || self.threshold as usize > self.shares.len() {
+            return Err(KalaError::validation(format!(
+                "Threshold {} must be between 1 and the share count {}",
+                self.threshold,
+                self.shares.len()
+            )));
+        }
+        for (i, share) in self.shares.iter().enumerate() {
+            let duplicate = self.shares[..i]
+                .iter()
+                .any(|other| other.witness == share.witness || other.index == share.index);
+            if share.index == 0 || duplicate {
+                return Err(KalaError::validation(format!(
+                    "Duplicate or invalid share {} for witness {}",
+                    share.index,
+                    hex::encode(share.witness)
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The share encrypted to `witness`, if it holds one
+    pub fn share_of(&self, witness: &[u8; 32]) -> Option<&EncryptedShare> {
+        self.shares.iter().find(|share| &share.witness == witness)
+    }
+
+    /// Whether a revealed share is one the lock committed to
+    pub fn is_valid_share(&self, share: &KeyShare) -> bool {
+        self.shares
+            .iter()
+            .any(|committed| committed.index == share.index && committed.commitment == share.commitment())
+    }
+
+    /// Recover the key from revealed shares
+    ///
+    /// Shares the lock didn't commit to are ignored, and the lowest
+    /// indices are used, so every node holding the same shares recovers
+    /// the same key. It is wiped from memory when dropped.
+    pub fn recover_key(&self, shares: &[KeyShare]) -> KalaResult<Zeroizing<[u8; AES_KEY_SIZE]>> {
+        let mut valid: Vec<KeyShare> = shares
+            .iter()
+            .filter(|share| self.is_valid_share(share))
+            .cloned()
+            .collect();
+        valid.sort_by_key(|share| share.index);
+        valid.dedup_by_key(|share| share.index);
+        shamir::combine(&valid, self.threshold).map(Zeroizing::new)
+    }
+}
+
+/// AES-256-GCM cipher for one witness's share
+fn share_cipher(shared_secret: &[u8; 32], ephemeral_key: &[u8; 32], witness: &[u8; 32]) -> Aes256Gcm {
+    let mut hasher = Sha256::new();
+    hasher.update(b"kala-share-key");
+    hasher.update(shared_secret);
+    hasher.update(ephemeral_key);
+    hasher.update(witness);
+    let key = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key[..]))
+}
+
+/// Seal a transaction for `target_tick` with its key shared among `witnesses`
+///
+/// Any `threshold` of the witnesses can decrypt it once they reveal their
+/// shares after the tick's ordering commitment.
+pub fn seal_threshold_transaction(
+    tx: &Transaction,
+    target_tick: u64,
+    submission_iteration: u64,
+    witnesses: &[[u8; 32]],
+    threshold: u8,
+) -> KalaResult<TimelockTransaction> {
+    // Generate encryption key, wiped once it is shared out
+    let mut key = Zeroizing::new([0u8; AES_KEY_SIZE]);
+    rand::rng().fill(&mut key[..]);
+
+    let encrypted_data = encrypt_transaction(tx, &key)?;
+    let lock = ThresholdLock::seal(&key, witnesses, threshold)?;
+
+    Ok(TimelockTransaction {
+        encrypted_data,
+        lock: KeyLock::Threshold(lock),
+        submission_iteration,
+        target_tick,
+    })
+}
+
+/// Decrypt a threshold-locked transaction from its revealed key shares
+pub fn open_threshold_transaction(timelock_tx: &TimelockTransaction, shares: &[KeyShare]) -> KalaResult<Transaction> {
+    let KeyLock::Threshold(lock) = &timelock_tx.lock else {
+        return Err(KalaError::validation("Envelope is not threshold-locked".to_string()));
+    };
+    let key = lock.recover_key(shares)?;
+    decrypt_transaction(&timelock_tx.encrypted_data, &key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Send;
+
+    #[test]
+    fn test_threshold_roundtrip() {
+        let witness_keys: Vec<SigningKey> = (1..=3u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let witnesses: Vec<[u8; 32]> = witness_keys.iter().map(|key| key.verifying_key().to_bytes()).collect();
+        let tx = Transaction::Send(Send {
+            sender: [1u8; 32],
+            receiver: [2u8; 32],
+            denom: [3u8; 32],
+            amount: 1000,
+            nonce: 1,
+            signature: [0u8; 64].to_vec(),
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: 0,
+        });
+
+        let envelope = seal_threshold_transaction(&tx, 5, 100, &witnesses, 2).unwrap();
+        let KeyLock::Threshold(lock) = &envelope.lock else {
+            panic!("Expected a threshold lock");
+        };
+        lock.validate().unwrap();
+        assert!(envelope.puzzle().is_none());
+
+        // Each witness opens only its own share
+        let shares: Vec<KeyShare> = witness_keys
+            .iter()
+            .zip(&witnesses)
+            .map(|(key, witness)| {
+                let secret = key_agreement(key, &lock.ephemeral_key).unwrap();
+                lock.share_of(witness).unwrap().open(&lock.ephemeral_key, &secret).unwrap()
+            })
+            .collect();
+        let secret = key_agreement(&witness_keys[0], &lock.ephemeral_key).unwrap();
+        assert!(lock.shares[1].open(&lock.ephemeral_key, &secret).is_err());
+
+        // Two revealed shares decrypt, one doesn't
+        assert!(open_threshold_transaction(&envelope, &shares[..1]).is_err());
+        match open_threshold_transaction(&envelope, &shares[1..]).unwrap() {
+            Transaction::Send(send) => assert_eq!(send.amount, 1000),
+            _ => panic!("Transaction type mismatch"),
+        }
+
+        // Forged shares are ignored
+        let forged = KeyShare {
+            index: 1,
+            value: [0u8; 32],
+        };
+        assert!(!lock.is_valid_share(&forged));
+        assert!(open_threshold_transaction(&envelope, &[forged.clone(), shares[2].clone()]).is_err());
+        assert!(open_threshold_transaction(&envelope, &[forged, shares[2].clone(), shares[0].clone()]).is_ok());
+
+        let mut duplicate = lock.clone();
+        duplicate.shares[1].witness = witnesses[0];
+        assert!(duplicate.validate().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
 use kala_common::prelude::*;
 use kala_common::types::{Hash, PublicKey, Signature};
 
@@ -35,6 +36,22 @@ pub struct Send {
     pub nonce: u64,
     pub signature: Bytes64, // Now a Vec<u8>
     pub gas_sponsorer: Bytes32Array,
+    /// Payment reference or note, at most [`MAX_MEMO_SIZE`] bytes
+    ///
+    /// Committed to the tick along with the rest of the transaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memo: Vec<u8>,
+    /// Gas sponsorer's signature over [`Transaction::sponsor_signing_message`]
+    ///
+    /// Empty unless the fees are paid by an account other than the sender.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    /// Charged to the fee payer on top of the transaction fee
+    ///
+    /// A transaction replaces one with the same sender and nonce by
+    /// offering a higher tip.
+    #[serde(default)]
+    pub tip: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +62,10 @@ pub struct Mint {
     pub nonce: u64,
     pub signature: Bytes64, // As Vec<u8>
     pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +76,261 @@ pub struct Stake {
     pub nonce: u64,
     pub signature: Bytes64,
     pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Withdraw stake from the sender's current delegation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unstake {
+    pub sender: Bytes32Array,
+    pub amount: u64,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Two conflicting tick votes signed by one witness
+///
+/// Anyone can submit the evidence. Applying it slashes the stake delegated
+/// to the witness.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evidence {
+    pub sender: Bytes32Array,
+    pub witness: Bytes32Array,
+    pub tick_number: u64,
+    pub first_hash: Bytes32Array,
+    pub first_signature: Vec<u8>, // BLS signature
+    pub second_hash: Bytes32Array,
+    pub second_signature: Vec<u8>,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Transfer of one denom from a single sender to many receivers
+///
+/// Covered by one signature and nonce, so an airdrop or exchange payout
+/// costs a single transaction. The outputs apply together or not at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiSend {
+    pub sender: Bytes32Array,
+    pub denom: Bytes32Array,
+    pub outputs: Vec<MultiSendOutput>,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// One receiver of a [`MultiSend`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSendOutput {
+    pub receiver: Bytes32Array,
+    pub amount: u64,
+}
+
+/// Transfer held back until the VDF clock reaches `unlock_tick`
+///
+/// The amount leaves the sender when the transaction applies and is
+/// credited to the receiver when tick `unlock_tick` is finalized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VestingTransfer {
+    pub sender: Bytes32Array,
+    pub receiver: Bytes32Array,
+    pub denom: Bytes32Array,
+    pub amount: u64,
+    pub unlock_tick: u64,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Proposal to change a governed chain parameter
+///
+/// `parameter` identifies the parameter; the state's governance module
+/// decides which values it accepts. If the vote passes, `value` takes
+/// effect at `activation_tick`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Propose {
+    pub sender: Bytes32Array,
+    pub parameter: u32,
+    pub value: u64,
+    pub activation_tick: u64,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+    /// Account whose minting a minter approval proposal allows or revokes
+    ///
+    /// Set only by those proposals, which `value` approves with 1 and
+    /// revokes with 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minter: Option<MinterGrant>,
+}
+
+/// Denomination and account of a minter approval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinterGrant {
+    pub denom: Bytes32Array,
+    pub minter: Bytes32Array,
+}
+
+/// Stake-weighted vote on an open proposal
+///
+/// A later vote from the same sender replaces the earlier one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vote {
+    pub sender: Bytes32Array,
+    pub proposal_id: u64,
+    pub approve: bool,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Epoch summary of another Kala timeline, for this chain's light client of it
+///
+/// `epoch` is an encoded signed epoch summary. With a zero `client_id` it
+/// anchors a new client, identified by the summary's hash; otherwise it
+/// must follow the client's latest epoch, signed by that epoch's witnesses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitForeignEpoch {
+    pub sender: Bytes32Array,
+    pub client_id: Bytes32Array,
+    pub epoch: Vec<u8>,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Open a sealed-bid auction of `item`, closing at `close_tick`
+///
+/// `item` describes what is sold, opaque to the chain. Bids must be sealed
+/// for the closing tick, so none is revealed before the others.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAuction {
+    pub sender: Bytes32Array,
+    pub item: Vec<u8>,
+    pub reserve_price: u64,
+    pub close_tick: u64,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Bid of `amount` on an auction, escrowed until it settles
+///
+/// Only applies in the auction's closing tick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bid {
+    pub sender: Bytes32Array,
+    pub auction_id: u64,
+    pub amount: u64,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Designate guardians able to recover the sender's account
+///
+/// Once `threshold` of the `guardians` request the same new owner, the
+/// account moves to it after `delay_ticks`, unless the sender vetoes it
+/// first. An empty list removes the guardians.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetGuardians {
+    pub sender: Bytes32Array,
+    pub guardians: Vec<Bytes32Array>,
+    pub threshold: u32,
+    pub delay_ticks: u64,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// A guardian's request to move `account` to `new_owner`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestRecovery {
+    pub sender: Bytes32Array,
+    pub account: Bytes32Array,
+    pub new_owner: Bytes32Array,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Cancel the pending recovery of the sender's account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VetoRecovery {
+    pub sender: Bytes32Array,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
+}
+
+/// Hand the issuance of `denom` from the sender to `new_authority`
+///
+/// Only the denomination's current issuer may send it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferMintAuthority {
+    pub sender: Bytes32Array,
+    pub denom: Bytes32Array,
+    pub new_authority: Bytes32Array,
+    pub nonce: u64,
+    pub signature: Bytes64,
+    pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,8 +341,15 @@ pub struct Solve {
     pub nonce: u64,
     pub signature: Bytes64,
     pub gas_sponsorer: Bytes32Array,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sponsor_signature: Bytes64,
+    #[serde(default)]
+    pub tip: u64,
 }
 
+/// Largest memo a [`Send`] may carry
+pub const MAX_MEMO_SIZE: usize = 256;
+
 // Add validation methods using kala-common
 impl Send {
     pub fn validate(&self) -> KalaResult<()> {
@@ -76,6 +359,13 @@ impl Send {
                 self.signature.len()
             )));
         }
+        if self.memo.len() > MAX_MEMO_SIZE {
+            return Err(KalaError::validation(format!(
+                "Memo too large: {} bytes exceeds {}",
+                self.memo.len(),
+                MAX_MEMO_SIZE
+            )));
+        }
         Ok(())
     }
 }
@@ -98,6 +388,169 @@ impl Solve {
     }
 }
 
+/// Most outputs a single [`MultiSend`] may carry
+pub const MAX_MULTISEND_OUTPUTS: usize = 1024;
+
+impl MultiSend {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.outputs.is_empty() {
+            return Err(KalaError::validation("MultiSend has no outputs"));
+        }
+        if self.outputs.len() > MAX_MULTISEND_OUTPUTS {
+            return Err(KalaError::validation(format!(
+                "Too many outputs: {} exceeds {}",
+                self.outputs.len(),
+                MAX_MULTISEND_OUTPUTS
+            )));
+        }
+        self.total_amount()?;
+        Ok(())
+    }
+
+    /// Sum of the output amounts, debited from the sender
+    pub fn total_amount(&self) -> KalaResult<u64> {
+        self.outputs
+            .iter()
+            .try_fold(0u64, |total, output| total.checked_add(output.amount))
+            .ok_or_else(|| KalaError::validation("MultiSend total overflows"))
+    }
+}
+
+impl VestingTransfer {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.amount == 0 {
+            return Err(KalaError::validation("VestingTransfer amount is zero"));
+        }
+        Ok(())
+    }
+}
+
+/// Largest encoded epoch summary a [`SubmitForeignEpoch`] may carry
+pub const MAX_FOREIGN_EPOCH_SIZE: usize = 64 * 1024;
+
+impl SubmitForeignEpoch {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.epoch.is_empty() || self.epoch.len() > MAX_FOREIGN_EPOCH_SIZE {
+            return Err(KalaError::validation(format!(
+                "Foreign epoch of {} bytes, expected 1 to {}",
+                self.epoch.len(),
+                MAX_FOREIGN_EPOCH_SIZE
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Largest item description a [`CreateAuction`] may carry
+pub const MAX_AUCTION_ITEM_SIZE: usize = 256;
+
+impl CreateAuction {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.item.len() > MAX_AUCTION_ITEM_SIZE {
+            return Err(KalaError::validation(format!(
+                "Auction item of {} bytes exceeds {}",
+                self.item.len(),
+                MAX_AUCTION_ITEM_SIZE
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Most guardians a [`SetGuardians`] may designate
+pub const MAX_GUARDIANS: usize = 16;
+
+impl SetGuardians {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.guardians.len() > MAX_GUARDIANS {
+            return Err(KalaError::validation(format!(
+                "{} guardians exceeds {}",
+                self.guardians.len(),
+                MAX_GUARDIANS
+            )));
+        }
+        if self.guardians.is_empty() {
+            return Ok(());
+        }
+        if self.threshold == 0 || self.threshold as usize > self.guardians.len() {
+            return Err(KalaError::validation(format!(
+                "Guardian threshold {} out of range for {} guardians",
+                self.threshold,
+                self.guardians.len()
+            )));
+        }
+        for (i, guardian) in self.guardians.iter().enumerate() {
+            if *guardian == self.sender {
+                return Err(KalaError::validation("Account cannot guard itself"));
+            }
+            if self.guardians[..i].contains(guardian) {
+                return Err(KalaError::validation("Duplicate guardian"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RequestRecovery {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.new_owner == self.account {
+            return Err(KalaError::validation("Account cannot be recovered to itself"));
+        }
+        Ok(())
+    }
+}
+
+impl TransferMintAuthority {
+    pub fn validate(&self) -> KalaResult<()> {
+        if self.signature.len() != 64 {
+            return Err(KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature.len()
+            )));
+        }
+        if self.new_authority == self.sender {
+            return Err(KalaError::validation("Mint authority already held by the sender"));
+        }
+        Ok(())
+    }
+}
+
 // Transaction enum
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Transaction {
@@ -105,6 +558,368 @@ pub enum Transaction {
     Mint(Mint),
     Stake(Stake),
     Solve(Solve),
+    Unstake(Unstake),
+    Evidence(Evidence),
+    MultiSend(MultiSend),
+    VestingTransfer(VestingTransfer),
+    Propose(Propose),
+    Vote(Vote),
+    SubmitForeignEpoch(SubmitForeignEpoch),
+    CreateAuction(CreateAuction),
+    Bid(Bid),
+    SetGuardians(SetGuardians),
+    RequestRecovery(RequestRecovery),
+    VetoRecovery(VetoRecovery),
+    TransferMintAuthority(TransferMintAuthority),
+}
+
+/// Prefix of every transaction signing message
+pub const SIGNING_DOMAIN: &[u8] = b"kala-transaction-v1";
+
+/// Prefix of the message a gas sponsorer signs
+pub const SPONSOR_SIGNING_DOMAIN: &[u8] = b"kala-sponsor-v1";
+
+impl Transaction {
+    /// Account that signs the transaction and whose nonce it uses
+    pub fn sender(&self) -> &Bytes32Array {
+        match self {
+            Transaction::Send(t) => &t.sender,
+            Transaction::Mint(t) => &t.sender,
+            Transaction::Stake(t) => &t.sender,
+            Transaction::Solve(t) => &t.sender,
+            Transaction::Unstake(t) => &t.sender,
+            Transaction::Evidence(t) => &t.sender,
+            Transaction::MultiSend(t) => &t.sender,
+            Transaction::VestingTransfer(t) => &t.sender,
+            Transaction::Propose(t) => &t.sender,
+            Transaction::Vote(t) => &t.sender,
+            Transaction::SubmitForeignEpoch(t) => &t.sender,
+            Transaction::CreateAuction(t) => &t.sender,
+            Transaction::Bid(t) => &t.sender,
+            Transaction::SetGuardians(t) => &t.sender,
+            Transaction::RequestRecovery(t) => &t.sender,
+            Transaction::VetoRecovery(t) => &t.sender,
+            Transaction::TransferMintAuthority(t) => &t.sender,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        match self {
+            Transaction::Send(t) => t.nonce,
+            Transaction::Mint(t) => t.nonce,
+            Transaction::Stake(t) => t.nonce,
+            Transaction::Solve(t) => t.nonce,
+            Transaction::Unstake(t) => t.nonce,
+            Transaction::Evidence(t) => t.nonce,
+            Transaction::MultiSend(t) => t.nonce,
+            Transaction::VestingTransfer(t) => t.nonce,
+            Transaction::Propose(t) => t.nonce,
+            Transaction::Vote(t) => t.nonce,
+            Transaction::SubmitForeignEpoch(t) => t.nonce,
+            Transaction::CreateAuction(t) => t.nonce,
+            Transaction::Bid(t) => t.nonce,
+            Transaction::SetGuardians(t) => t.nonce,
+            Transaction::RequestRecovery(t) => t.nonce,
+            Transaction::VetoRecovery(t) => t.nonce,
+            Transaction::TransferMintAuthority(t) => t.nonce,
+        }
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        match self {
+            Transaction::Send(t) => &t.signature,
+            Transaction::Mint(t) => &t.signature,
+            Transaction::Stake(t) => &t.signature,
+            Transaction::Solve(t) => &t.signature,
+            Transaction::Unstake(t) => &t.signature,
+            Transaction::Evidence(t) => &t.signature,
+            Transaction::MultiSend(t) => &t.signature,
+            Transaction::VestingTransfer(t) => &t.signature,
+            Transaction::Propose(t) => &t.signature,
+            Transaction::Vote(t) => &t.signature,
+            Transaction::SubmitForeignEpoch(t) => &t.signature,
+            Transaction::CreateAuction(t) => &t.signature,
+            Transaction::Bid(t) => &t.signature,
+            Transaction::SetGuardians(t) => &t.signature,
+            Transaction::RequestRecovery(t) => &t.signature,
+            Transaction::VetoRecovery(t) => &t.signature,
+            Transaction::TransferMintAuthority(t) => &t.signature,
+        }
+    }
+
+    fn signature_mut(&mut self) -> &mut Bytes64 {
+        match self {
+            Transaction::Send(t) => &mut t.signature,
+            Transaction::Mint(t) => &mut t.signature,
+            Transaction::Stake(t) => &mut t.signature,
+            Transaction::Solve(t) => &mut t.signature,
+            Transaction::Unstake(t) => &mut t.signature,
+            Transaction::Evidence(t) => &mut t.signature,
+            Transaction::MultiSend(t) => &mut t.signature,
+            Transaction::VestingTransfer(t) => &mut t.signature,
+            Transaction::Propose(t) => &mut t.signature,
+            Transaction::Vote(t) => &mut t.signature,
+            Transaction::SubmitForeignEpoch(t) => &mut t.signature,
+            Transaction::CreateAuction(t) => &mut t.signature,
+            Transaction::Bid(t) => &mut t.signature,
+            Transaction::SetGuardians(t) => &mut t.signature,
+            Transaction::RequestRecovery(t) => &mut t.signature,
+            Transaction::VetoRecovery(t) => &mut t.signature,
+            Transaction::TransferMintAuthority(t) => &mut t.signature,
+        }
+    }
+
+    /// Account named to pay the transaction's fees, zero if none
+    pub fn gas_sponsorer(&self) -> &Bytes32Array {
+        match self {
+            Transaction::Send(t) => &t.gas_sponsorer,
+            Transaction::Mint(t) => &t.gas_sponsorer,
+            Transaction::Stake(t) => &t.gas_sponsorer,
+            Transaction::Solve(t) => &t.gas_sponsorer,
+            Transaction::Unstake(t) => &t.gas_sponsorer,
+            Transaction::Evidence(t) => &t.gas_sponsorer,
+            Transaction::MultiSend(t) => &t.gas_sponsorer,
+            Transaction::VestingTransfer(t) => &t.gas_sponsorer,
+            Transaction::Propose(t) => &t.gas_sponsorer,
+            Transaction::Vote(t) => &t.gas_sponsorer,
+            Transaction::SubmitForeignEpoch(t) => &t.gas_sponsorer,
+            Transaction::CreateAuction(t) => &t.gas_sponsorer,
+            Transaction::Bid(t) => &t.gas_sponsorer,
+            Transaction::SetGuardians(t) => &t.gas_sponsorer,
+            Transaction::RequestRecovery(t) => &t.gas_sponsorer,
+            Transaction::VetoRecovery(t) => &t.gas_sponsorer,
+            Transaction::TransferMintAuthority(t) => &t.gas_sponsorer,
+        }
+    }
+
+    pub fn sponsor_signature(&self) -> &[u8] {
+        match self {
+            Transaction::Send(t) => &t.sponsor_signature,
+            Transaction::Mint(t) => &t.sponsor_signature,
+            Transaction::Stake(t) => &t.sponsor_signature,
+            Transaction::Solve(t) => &t.sponsor_signature,
+            Transaction::Unstake(t) => &t.sponsor_signature,
+            Transaction::Evidence(t) => &t.sponsor_signature,
+            Transaction::MultiSend(t) => &t.sponsor_signature,
+            Transaction::VestingTransfer(t) => &t.sponsor_signature,
+            Transaction::Propose(t) => &t.sponsor_signature,
+            Transaction::Vote(t) => &t.sponsor_signature,
+            Transaction::SubmitForeignEpoch(t) => &t.sponsor_signature,
+            Transaction::CreateAuction(t) => &t.sponsor_signature,
+            Transaction::Bid(t) => &t.sponsor_signature,
+            Transaction::SetGuardians(t) => &t.sponsor_signature,
+            Transaction::RequestRecovery(t) => &t.sponsor_signature,
+            Transaction::VetoRecovery(t) => &t.sponsor_signature,
+            Transaction::TransferMintAuthority(t) => &t.sponsor_signature,
+        }
+    }
+
+    fn sponsor_signature_mut(&mut self) -> &mut Bytes64 {
+        match self {
+            Transaction::Send(t) => &mut t.sponsor_signature,
+            Transaction::Mint(t) => &mut t.sponsor_signature,
+            Transaction::Stake(t) => &mut t.sponsor_signature,
+            Transaction::Solve(t) => &mut t.sponsor_signature,
+            Transaction::Unstake(t) => &mut t.sponsor_signature,
+            Transaction::Evidence(t) => &mut t.sponsor_signature,
+            Transaction::MultiSend(t) => &mut t.sponsor_signature,
+            Transaction::VestingTransfer(t) => &mut t.sponsor_signature,
+            Transaction::Propose(t) => &mut t.sponsor_signature,
+            Transaction::Vote(t) => &mut t.sponsor_signature,
+            Transaction::SubmitForeignEpoch(t) => &mut t.sponsor_signature,
+            Transaction::CreateAuction(t) => &mut t.sponsor_signature,
+            Transaction::Bid(t) => &mut t.sponsor_signature,
+            Transaction::SetGuardians(t) => &mut t.sponsor_signature,
+            Transaction::RequestRecovery(t) => &mut t.sponsor_signature,
+            Transaction::VetoRecovery(t) => &mut t.sponsor_signature,
+            Transaction::TransferMintAuthority(t) => &mut t.sponsor_signature,
+        }
+    }
+
+    /// Whether an account other than the sender pays the fees
+    ///
+    /// A zero gas sponsorer, or the sender itself, means the sender pays.
+    pub fn is_sponsored(&self) -> bool {
+        let sponsorer = self.gas_sponsorer();
+        *sponsorer != [0u8; 32] && sponsorer != self.sender()
+    }
+
+    /// Account the fees are charged to
+    pub fn fee_payer(&self) -> &Bytes32Array {
+        if self.is_sponsored() {
+            self.gas_sponsorer()
+        } else {
+            self.sender()
+        }
+    }
+
+    /// Charged to the fee payer on top of the transaction fee
+    pub fn tip(&self) -> u64 {
+        match self {
+            Transaction::Send(t) => t.tip,
+            Transaction::Mint(t) => t.tip,
+            Transaction::Stake(t) => t.tip,
+            Transaction::Solve(t) => t.tip,
+            Transaction::Unstake(t) => t.tip,
+            Transaction::Evidence(t) => t.tip,
+            Transaction::MultiSend(t) => t.tip,
+            Transaction::VestingTransfer(t) => t.tip,
+            Transaction::Propose(t) => t.tip,
+            Transaction::Vote(t) => t.tip,
+            Transaction::SubmitForeignEpoch(t) => t.tip,
+            Transaction::CreateAuction(t) => t.tip,
+            Transaction::Bid(t) => t.tip,
+            Transaction::SetGuardians(t) => t.tip,
+            Transaction::RequestRecovery(t) => t.tip,
+            Transaction::VetoRecovery(t) => t.tip,
+            Transaction::TransferMintAuthority(t) => t.tip,
+        }
+    }
+
+    /// Module whose state the transaction changes, which its usage is
+    /// counted against
+    pub fn module(&self) -> &'static str {
+        match self {
+            Transaction::Send(_)
+            | Transaction::Mint(_)
+            | Transaction::MultiSend(_)
+            | Transaction::VestingTransfer(_)
+            | Transaction::TransferMintAuthority(_) => "bank",
+            Transaction::Stake(_) | Transaction::Unstake(_) | Transaction::Evidence(_) => "staking",
+            Transaction::Solve(_) => "timelock",
+            Transaction::Propose(_) | Transaction::Vote(_) => "governance",
+            Transaction::SubmitForeignEpoch(_) => "interchain",
+            Transaction::CreateAuction(_) | Transaction::Bid(_) => "auction",
+            Transaction::SetGuardians(_)
+            | Transaction::RequestRecovery(_)
+            | Transaction::VetoRecovery(_) => "recovery",
+        }
+    }
+
+    /// Bytes the sender signs
+    ///
+    /// [`SIGNING_DOMAIN`], the length-prefixed chain ID, then the canonical
+    /// flatbuffer encoding of the transaction with both signatures zeroed,
+    /// in schema version 0.
+    pub fn signing_message(&self, chain_id: &str) -> KalaResult<Vec<u8>> {
+        self.domain_message(SIGNING_DOMAIN, chain_id)
+    }
+
+    /// Bytes the gas sponsorer signs to agree to pay the fees
+    ///
+    /// Like [`Transaction::signing_message`] under [`SPONSOR_SIGNING_DOMAIN`],
+    /// so the sender's signature can't stand in for the sponsor's. Both
+    /// parties sign the same fields, the sponsor named by the sender included,
+    /// and may sign in either order.
+    pub fn sponsor_signing_message(&self, chain_id: &str) -> KalaResult<Vec<u8>> {
+        self.domain_message(SPONSOR_SIGNING_DOMAIN, chain_id)
+    }
+
+    fn domain_message(&self, domain: &[u8], chain_id: &str) -> KalaResult<Vec<u8>> {
+        let mut unsigned = self.clone();
+        *unsigned.signature_mut() = EMPTY64BYTES.to_vec();
+        // Left empty so unsponsored transactions encode as they always have
+        unsigned.sponsor_signature_mut().clear();
+        // Pinned to version 0, so signatures survive schema version bumps
+        let body = crate::decrypted::encode_transaction(&unsigned, 0)?;
+
+        let mut message = Vec::with_capacity(domain.len() + 4 + chain_id.len() + body.len());
+        message.extend_from_slice(domain);
+        message.extend_from_slice(&(chain_id.len() as u32).to_le_bytes());
+        message.extend_from_slice(chain_id.as_bytes());
+        message.extend_from_slice(&body);
+        Ok(message)
+    }
+
+    /// Sign the transaction for `chain_id`
+    ///
+    /// `key` must be the sender's key.
+    pub fn sign(&mut self, key: &SigningKey, chain_id: &str) -> KalaResult<()> {
+        if key.verifying_key().to_bytes() != *self.sender() {
+            return Err(KalaError::crypto("Signing key does not match the sender"));
+        }
+        let message = self.signing_message(chain_id)?;
+        *self.signature_mut() = key.sign(&message).to_bytes().to_vec();
+        Ok(())
+    }
+
+    /// Check the sender's Ed25519 signature over the transaction for `chain_id`
+    ///
+    /// Uses the strict check, rejecting small-order keys and `R` values, as
+    /// [`verify_signatures_batch`] does for a tick's transactions.
+    ///
+    /// [`verify_signatures_batch`]: kala_common::crypto::verify_signatures_batch
+    pub fn verify_signature(&self, chain_id: &str) -> KalaResult<()> {
+        let signature: [u8; 64] = self.signature().try_into().map_err(|_| {
+            KalaError::validation(format!(
+                "Invalid signature size: expected 64, got {}",
+                self.signature().len()
+            ))
+        })?;
+        let key = VerifyingKey::from_bytes(self.sender())
+            .map_err(|_| KalaError::crypto("Sender is not an Ed25519 public key"))?;
+        let message = self.signing_message(chain_id)?;
+        key.verify_strict(&message, &ed25519_dalek::Signature::from_bytes(&signature))
+            .map_err(|_| KalaError::crypto("Invalid transaction signature"))
+    }
+
+    /// Co-sign the transaction for `chain_id` as its gas sponsorer
+    ///
+    /// `key` must be the gas sponsorer's key, and the sponsorer an account
+    /// other than the sender.
+    pub fn sign_as_sponsor(&mut self, key: &SigningKey, chain_id: &str) -> KalaResult<()> {
+        if !self.is_sponsored() {
+            return Err(KalaError::validation("Transaction has no gas sponsorer"));
+        }
+        if key.verifying_key().to_bytes() != *self.gas_sponsorer() {
+            return Err(KalaError::crypto("Signing key does not match the gas sponsorer"));
+        }
+        let message = self.sponsor_signing_message(chain_id)?;
+        *self.sponsor_signature_mut() = key.sign(&message).to_bytes().to_vec();
+        Ok(())
+    }
+
+    /// Check the gas sponsorer agreed to pay the fees
+    ///
+    /// A sponsored transaction needs the sponsorer's Ed25519 signature over
+    /// the transaction for `chain_id`. One the sender pays for must not
+    /// carry a sponsor signature.
+    pub fn verify_sponsor_signature(&self, chain_id: &str) -> KalaResult<()> {
+        if !self.is_sponsored() {
+            if !self.sponsor_signature().is_empty() {
+                return Err(KalaError::validation(
+                    "Sponsor signature on a transaction the sender pays for",
+                ));
+            }
+            return Ok(());
+        }
+
+        let signature: [u8; 64] = self.sponsor_signature().try_into().map_err(|_| {
+            KalaError::validation(format!(
+                "Invalid sponsor signature size: expected 64, got {}",
+                self.sponsor_signature().len()
+            ))
+        })?;
+        let key = VerifyingKey::from_bytes(self.gas_sponsorer())
+            .map_err(|_| KalaError::crypto("Gas sponsorer is not an Ed25519 public key"))?;
+        let message = self.sponsor_signing_message(chain_id)?;
+        key.verify_strict(&message, &ed25519_dalek::Signature::from_bytes(&signature))
+            .map_err(|_| KalaError::crypto("Invalid sponsor signature"))
+    }
+}
+
+impl SignedPayload for Transaction {
+    fn signer(&self) -> &PublicKey {
+        self.sender()
+    }
+
+    fn signature_bytes(&self) -> &[u8] {
+        self.signature()
+    }
+
+    fn signing_message(&self, chain_id: &str) -> KalaResult<Vec<u8>> {
+        Transaction::signing_message(self, chain_id)
+    }
 }
 
 // Metadata for timestamping using kala-common types
@@ -127,11 +942,69 @@ pub struct SealedTransaction {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimelockTransaction {
     pub encrypted_data: SealedTransaction,
-    pub puzzle: RSWPuzzle,
+    pub lock: KeyLock,
     pub submission_iteration: IterationNumber,
     pub target_tick: BlockHeight,
 }
 
+/// How the AES key of an envelope is withheld until its order is committed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyLock {
+    /// Behind an RSW puzzle anyone can solve with enough sequential work
+    Puzzle(RSWPuzzle),
+    /// Shamir-shared among the witnesses, who reveal their shares after
+    /// the ordering commitment
+    Threshold(ThresholdLock),
+}
+
+impl TimelockTransaction {
+    /// Hash identifying the envelope across nodes
+    ///
+    /// Covers the sealed payload, key lock, and target tick but not the
+    /// submission iteration, which each node assigns on arrival.
+    pub fn envelope_hash(&self) -> Hash {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-envelope");
+        hasher.update(self.target_tick.to_le_bytes());
+        hasher.update(self.encrypted_data.nonce);
+        hasher.update(self.encrypted_data.tag);
+        hasher.update(Sha256::digest(&self.encrypted_data.ciphertext));
+        match &self.lock {
+            KeyLock::Puzzle(puzzle) => {
+                for part in [&puzzle.puzzle_value, &puzzle.a, &puzzle.n] {
+                    hasher.update((part.len() as u64).to_le_bytes());
+                    hasher.update(part);
+                }
+                hasher.update(puzzle.hardness.to_le_bytes());
+            }
+            KeyLock::Threshold(lock) => {
+                hasher.update(b"threshold");
+                hasher.update([lock.threshold]);
+                hasher.update(lock.ephemeral_key);
+                for share in &lock.shares {
+                    hasher.update(share.witness);
+                    hasher.update([share.index]);
+                    hasher.update(share.commitment);
+                    hasher.update((share.ciphertext.len() as u64).to_le_bytes());
+                    hasher.update(&share.ciphertext);
+                }
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    /// The RSW puzzle locking the key, if the envelope uses one
+    pub fn puzzle(&self) -> Option<&RSWPuzzle> {
+        match &self.lock {
+            KeyLock::Puzzle(puzzle) => Some(puzzle),
+            KeyLock::Threshold(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RSWPuzzle {
     pub puzzle_value: Vec<u8>,
@@ -140,6 +1013,79 @@ pub struct RSWPuzzle {
     pub hardness: u32,
 }
 
+/// Widest modulus the GPU solver handles, in bits
+pub const MAX_PUZZLE_MODULUS_BITS: usize = 2048;
+
+/// Narrowest modulus accepted, in bits; smaller ones can be factored
+/// and the puzzle solved without the squarings
+pub const MIN_PUZZLE_MODULUS_BITS: usize = 1536;
+
+/// Largest encoded puzzle: its value, base and modulus, each at most as
+/// wide as the widest modulus
+pub const MAX_PUZZLE_SIZE: usize = 3 * MAX_PUZZLE_MODULUS_BITS / 8;
+
+impl RSWPuzzle {
+    /// Check the puzzle is one the solver can be trusted to take on
+    ///
+    /// The modulus must be an odd number of [`MIN_PUZZLE_MODULUS_BITS`] to
+    /// [`MAX_PUZZLE_MODULUS_BITS`] bits, the base must not square to
+    /// itself or 1 (0, 1 or n-1), the value must be reduced mod n, the
+    /// whole puzzle must fit in [`MAX_PUZZLE_SIZE`] bytes, and it must take
+    /// between 1 and `tick_size` squarings.
+    pub fn validate(&self, tick_size: u64) -> KalaResult<()> {
+        let size = self.puzzle_value.len() + self.a.len() + self.n.len();
+        if size > MAX_PUZZLE_SIZE {
+            return Err(KalaError::validation(format!(
+                "Puzzle too large: {} bytes exceeds {}",
+                size, MAX_PUZZLE_SIZE
+            )));
+        }
+
+        let n = trim_leading_zeros(&self.n);
+        let bits = n.first().map_or(0, |top| 8 * n.len() - top.leading_zeros() as usize);
+        if !(MIN_PUZZLE_MODULUS_BITS..=MAX_PUZZLE_MODULUS_BITS).contains(&bits) {
+            return Err(KalaError::validation(format!(
+                "Puzzle modulus of {} bits, expected {} to {}",
+                bits, MIN_PUZZLE_MODULUS_BITS, MAX_PUZZLE_MODULUS_BITS
+            )));
+        }
+        if n[n.len() - 1].is_multiple_of(2) {
+            return Err(KalaError::validation("Puzzle modulus is even"));
+        }
+
+        // n is odd, so n-1 only differs in its last byte
+        let a = trim_leading_zeros(&self.a);
+        let mut n_minus_one = n.to_vec();
+        n_minus_one[n.len() - 1] -= 1;
+        if matches!(a, [] | [1]) || a == n_minus_one {
+            return Err(KalaError::validation("Puzzle base is trivial"));
+        }
+        if !less_than(a, n) || !less_than(trim_leading_zeros(&self.puzzle_value), n) {
+            return Err(KalaError::validation("Puzzle base and value must be below the modulus"));
+        }
+
+        if self.hardness == 0 || self.hardness as u64 > tick_size {
+            return Err(KalaError::validation(format!(
+                "Puzzle hardness {} outside 1 to the tick size {}",
+                self.hardness, tick_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|&byte| byte != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// `a < b` for big-endian numbers without leading zeros
+fn less_than(a: &[u8], b: &[u8]) -> bool {
+    (a.len(), a) < (b.len(), b)
+}
+
+use crate::threshold::ThresholdLock;
+
 // Use constants from kala-common instead of duplicating
 pub use kala_common::types::sizes::{AES_KEY_SIZE, TAG_SIZE, NONCE_SIZE};
 pub const EMPTY64BYTES: [u8; 64] = [0u8; 64];
@@ -163,6 +1109,84 @@ impl KalaSerialize for Stake {
     }
 }
 
+impl KalaSerialize for Unstake {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for Evidence {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for MultiSend {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for VestingTransfer {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for Propose {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for Vote {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for SubmitForeignEpoch {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for CreateAuction {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for Bid {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for SetGuardians {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for RequestRecovery {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for VetoRecovery {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
+impl KalaSerialize for TransferMintAuthority {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::FlatBuffers
+    }
+}
+
 impl KalaSerialize for Solve {
     fn preferred_encoding() -> EncodingType {
         EncodingType::FlatBuffers
@@ -198,3 +1222,144 @@ impl KalaSerialize for RSWPuzzle {
         EncodingType::Bincode // Compact for puzzle data
     }
 }
+
+impl KalaSerialize for ThresholdLock {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Compact for encrypted shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(key: &SigningKey) -> Transaction {
+        Transaction::Send(Send {
+            sender: key.verifying_key().to_bytes(),
+            receiver: [2u8; 32],
+            denom: [0u8; 32],
+            amount: 100,
+            nonce: 1,
+            signature: bytes64(EMPTY64BYTES),
+            gas_sponsorer: [0u8; 32],
+            memo: Vec::new(),
+            sponsor_signature: Vec::new(),
+            tip: 0,
+        })
+    }
+
+    #[test]
+    fn test_signature_verification() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut tx = send(&key);
+        assert!(tx.verify_signature("kala-devnet").is_err());
+
+        tx.sign(&key, "kala-devnet").unwrap();
+        assert!(tx.verify_signature("kala-devnet").is_ok());
+        // Signatures don't carry over to another chain
+        assert!(tx.verify_signature("kala-testnet").is_err());
+
+        // The signature covers every field
+        let mut tampered = tx.clone();
+        if let Transaction::Send(send) = &mut tampered {
+            send.amount = 101;
+        }
+        assert!(tampered.verify_signature("kala-devnet").is_err());
+
+        // Only the sender's key can sign
+        let mut forged = send(&key);
+        assert!(forged
+            .sign(&SigningKey::from_bytes(&[8u8; 32]), "kala-devnet")
+            .is_err());
+    }
+
+    #[test]
+    fn test_sponsor_signature() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let sponsor = SigningKey::from_bytes(&[9u8; 32]);
+        let mut tx = send(&key);
+        assert!(!tx.is_sponsored());
+        assert_eq!(tx.fee_payer(), tx.sender());
+        assert!(tx.verify_sponsor_signature("kala-devnet").is_ok());
+        assert!(tx.sign_as_sponsor(&sponsor, "kala-devnet").is_err());
+
+        if let Transaction::Send(send) = &mut tx {
+            send.gas_sponsorer = sponsor.verifying_key().to_bytes();
+        }
+        assert!(tx.is_sponsored());
+        assert_eq!(*tx.fee_payer(), sponsor.verifying_key().to_bytes());
+        tx.sign(&key, "kala-devnet").unwrap();
+        assert!(tx.verify_sponsor_signature("kala-devnet").is_err());
+
+        // Only the named sponsor can co-sign, and its signature leaves the sender's intact
+        assert!(tx.sign_as_sponsor(&key, "kala-devnet").is_err());
+        tx.sign_as_sponsor(&sponsor, "kala-devnet").unwrap();
+        assert!(tx.verify_signature("kala-devnet").is_ok());
+        assert!(tx.verify_sponsor_signature("kala-devnet").is_ok());
+        assert!(tx.verify_sponsor_signature("kala-testnet").is_err());
+
+        // The sender's signature can't stand in for the sponsor's
+        let mut replayed = tx.clone();
+        let signature = replayed.signature().to_vec();
+        *replayed.sponsor_signature_mut() = signature;
+        assert!(replayed.verify_sponsor_signature("kala-devnet").is_err());
+
+        // Survives the wire encoding
+        let decoded = crate::decrypted::flatbuffer_to_transaction(
+            &crate::decrypted::transaction_to_flatbuffer(&tx).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(decoded.sponsor_signature(), tx.sponsor_signature());
+        assert!(decoded.verify_sponsor_signature("kala-devnet").is_ok());
+
+        // A sender paying its own fees carries no sponsor signature
+        if let Transaction::Send(send) = &mut tx {
+            send.gas_sponsorer = send.sender;
+        }
+        assert!(tx.verify_sponsor_signature("kala-devnet").is_err());
+    }
+
+    #[test]
+    fn test_batch_signature_verification() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut txs: Vec<Transaction> = (0..4).map(|_| send(&key)).collect();
+        for tx in &mut txs[..3] {
+            tx.sign(&key, "kala-devnet").unwrap();
+        }
+
+        assert_eq!(
+            verify_signatures_batch(&txs, "kala-devnet"),
+            vec![true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_puzzle_validation() {
+        let puzzle = RSWPuzzle {
+            puzzle_value: vec![0x12; 256],
+            a: vec![2],
+            n: vec![0xff; 256],
+            hardness: 1_000,
+        };
+        assert!(puzzle.validate(1_000).is_ok());
+        assert!(puzzle.validate(999).is_err());
+
+        let invalid = [
+            RSWPuzzle { hardness: 0, ..puzzle.clone() },
+            // Moduli too narrow to resist factoring, too wide for the solver, or even
+            RSWPuzzle { n: vec![0xff; 128], puzzle_value: vec![1], ..puzzle.clone() },
+            RSWPuzzle { n: [vec![1], vec![0xff; 256]].concat(), ..puzzle.clone() },
+            RSWPuzzle { n: [vec![0xff; 255], vec![0xfe]].concat(), ..puzzle.clone() },
+            // Bases whose powers don't depend on the hardness
+            RSWPuzzle { a: vec![], ..puzzle.clone() },
+            RSWPuzzle { a: vec![0, 1], ..puzzle.clone() },
+            RSWPuzzle { a: [vec![0xff; 255], vec![0xfe]].concat(), ..puzzle.clone() },
+            // Unreduced values, and padding past the size cap
+            RSWPuzzle { puzzle_value: vec![0xff; 256], ..puzzle.clone() },
+            RSWPuzzle { puzzle_value: vec![0; 1024], ..puzzle.clone() },
+        ];
+        for puzzle in invalid {
+            assert!(puzzle.validate(1_000).is_err(), "{:?}", puzzle);
+        }
+    }
+}
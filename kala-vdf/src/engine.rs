@@ -0,0 +1,220 @@
+//! [`VdfEngine`] adapters for the VDF implementations
+//!
+//! [`EternalVDF`] is the engine consensus runs: it hashes timestamped data
+//! into its chain and certifies every tick. [`FormChain`] adapts the tick
+//! library's bare squaring, which tracks forms only, for work that needs no
+//! hash chain such as recomputing segments. Both square the same way, so
+//! they reach the same forms. [`MockVdf`](crate::MockVdf) skips the
+//! squaring altogether, for simulations.
+
+use std::sync::Mutex;
+
+use kala_common::vdf::VdfEngine;
+use serde::{Deserialize, Serialize};
+use tick::{nudupl_form_inplace, Reducer, VdfForm};
+
+use crate::{
+    initialize_vdf, packed_values, unpacked_form, verify_form_transition, EternalVDF,
+    PackedForm, TickCertificate, VDFCheckpoint, VdfInternals,
+};
+
+impl VdfEngine for EternalVDF {
+    type Checkpoint = VDFCheckpoint;
+    type Proof = TickCertificate;
+
+    fn step(&mut self, data: Option<Vec<u8>>) {
+        EternalVDF::step(self, data)
+    }
+
+    fn iteration(&self) -> u64 {
+        self.get_iteration()
+    }
+
+    fn prove(&self, tick_number: u64) -> Option<TickCertificate> {
+        self.get_tick_certificate(tick_number)
+    }
+
+    /// Recompute the tick's squarings from the end of the tick before
+    ///
+    /// The forms don't depend on the timestamped data, so the hash chain
+    /// is not checked. Fails for a tick whose predecessor isn't known.
+    fn verify(&self, proof: &TickCertificate) -> bool {
+        let start = match proof.tick_number {
+            0 => packed_values(&VdfForm::generator(&self.discriminant)),
+            tick_number => match self.get_tick_certificate(tick_number - 1) {
+                Some(previous) => (previous.form_a, previous.form_b, previous.form_c),
+                None => return false,
+            },
+        };
+        let end = (proof.form_a.clone(), proof.form_b.clone(), proof.form_c.clone());
+        proof.start_iteration == proof.tick_number * self.tick_size
+            && proof.end_iteration == proof.start_iteration + self.tick_size
+            && verify_form_transition(&self.discriminant, &start, self.tick_size, &end)
+    }
+
+    fn checkpoint(&self) -> VDFCheckpoint {
+        EternalVDF::checkpoint(self)
+    }
+}
+
+/// Squarings from one form to another
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormTransition {
+    pub start_iteration: u64,
+    pub end_iteration: u64,
+    pub start: PackedForm,
+    pub end: PackedForm,
+}
+
+/// The tick library's squaring chain, without hashing or certificates
+///
+/// Data passed to [`VdfEngine::step`] is ignored. Only the latest
+/// completed tick can be proven.
+pub struct FormChain {
+    internals: Mutex<VdfInternals>,
+    discriminant: String,
+    tick_size: u64,
+    iteration: u64,
+    /// Form at the start of the tick in progress
+    tick_start: PackedForm,
+    /// The latest completed tick
+    last_tick: Option<FormTransition>,
+}
+
+impl FormChain {
+    /// Start from the generator of `discriminant`
+    pub fn new(discriminant: &str, tick_size: u64) -> Self {
+        initialize_vdf();
+        let form = VdfForm::generator(discriminant);
+        Self::with_form(discriminant, tick_size, 0, form)
+    }
+
+    /// Resume at `iteration` from `form`, which must be reduced
+    pub fn resume(
+        discriminant: &str,
+        tick_size: u64,
+        iteration: u64,
+        form: &PackedForm,
+    ) -> Self {
+        initialize_vdf();
+        Self::with_form(discriminant, tick_size, iteration, unpacked_form(form))
+    }
+
+    fn with_form(discriminant: &str, tick_size: u64, iteration: u64, form: VdfForm) -> Self {
+        let tick_start = packed_values(&form);
+        Self {
+            internals: Mutex::new(VdfInternals {
+                current_form: form,
+                reducer: Reducer::new(),
+            }),
+            discriminant: discriminant.to_string(),
+            tick_size,
+            iteration,
+            tick_start,
+            last_tick: None,
+        }
+    }
+
+    /// The current form
+    pub fn form(&self) -> PackedForm {
+        packed_values(&self.internals.lock().unwrap().current_form)
+    }
+}
+
+impl VdfEngine for FormChain {
+    type Checkpoint = (u64, PackedForm);
+    type Proof = FormTransition;
+
+    fn step(&mut self, _data: Option<Vec<u8>>) {
+        let mut internals = self.internals.lock().unwrap();
+        let VdfInternals {
+            current_form,
+            reducer,
+        } = &mut *internals;
+        nudupl_form_inplace(current_form, &self.discriminant);
+        reducer.reduce(current_form);
+        let form = packed_values(current_form);
+        drop(internals);
+
+        self.iteration += 1;
+        if self.iteration.is_multiple_of(self.tick_size) {
+            let start = std::mem::replace(&mut self.tick_start, form.clone());
+            self.last_tick = Some(FormTransition {
+                start_iteration: self.iteration - self.tick_size,
+                end_iteration: self.iteration,
+                start,
+                end: form,
+            });
+        }
+    }
+
+    fn iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    fn prove(&self, tick_number: u64) -> Option<FormTransition> {
+        self.last_tick
+            .clone()
+            .filter(|transition| transition.end_iteration == (tick_number + 1) * self.tick_size)
+    }
+
+    fn verify(&self, proof: &FormTransition) -> bool {
+        match proof.end_iteration.checked_sub(proof.start_iteration) {
+            Some(iterations) => {
+                verify_form_transition(&self.discriminant, &proof.start, iterations, &proof.end)
+            }
+            None => false,
+        }
+    }
+
+    fn checkpoint(&self) -> (u64, PackedForm) {
+        (self.iteration, self.form())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+
+    /// Step an engine through two ticks, timestamping data along the way
+    fn run<E: VdfEngine>(engine: &mut E) {
+        for iteration in 0..20u64 {
+            let data = (iteration % 3 == 0).then(|| iteration.to_le_bytes().to_vec());
+            engine.step(data);
+        }
+    }
+
+    #[test]
+    fn test_engines_agree() {
+        let mut eternal = EternalVDF::with_tick_size(DISCRIMINANT, 10);
+        let mut chain = FormChain::new(DISCRIMINANT, 10);
+        run(&mut eternal);
+        run(&mut chain);
+        assert_eq!(VdfEngine::iteration(&eternal), 20);
+        assert_eq!(chain.iteration(), 20);
+        assert_eq!(eternal.get_form_values(), chain.form());
+
+        // Each engine verifies its own proofs
+        let certificate = VdfEngine::prove(&eternal, 1).unwrap();
+        assert!(VdfEngine::verify(&eternal, &certificate));
+        assert!(VdfEngine::verify(&eternal, &VdfEngine::prove(&eternal, 0).unwrap()));
+        let mut forged = certificate.clone();
+        forged.form_b = VdfEngine::prove(&eternal, 0).unwrap().form_b;
+        assert!(!VdfEngine::verify(&eternal, &forged));
+
+        let transition = chain.prove(1).unwrap();
+        assert_eq!((transition.start_iteration, transition.end_iteration), (10, 20));
+        assert_eq!(transition.end, (certificate.form_a, certificate.form_b, certificate.form_c));
+        assert!(chain.verify(&transition));
+        assert!(chain.prove(0).is_none());
+
+        // A resumed chain continues where the checkpoint left off
+        let (iteration, form) = chain.checkpoint();
+        let mut resumed = FormChain::resume(DISCRIMINANT, 10, iteration, &form);
+        resumed.step(None);
+        chain.step(None);
+        assert_eq!(resumed.checkpoint(), chain.checkpoint());
+    }
+}
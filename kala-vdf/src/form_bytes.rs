@@ -0,0 +1,320 @@
+//! Compact byte encoding of form coefficients
+//!
+//! The tick library reads and writes form coefficients as hex strings,
+//! `0x1f…` or `-0x1f…`, which take two bytes per byte of the integer.
+//! Forms are instead held as [`PackedInteger`]s, in memory and wherever
+//! they are encoded as bytes, in bincode and protobuf, and only written as
+//! hex at the tick library's boundary and in JSON. Each coefficient is:
+//!
+//! - a tag byte: [`NON_NEGATIVE`], [`NEGATIVE`], or [`VERBATIM`]
+//! - the payload length as a big-endian `u32`
+//! - the payload: the magnitude in big-endian bytes without leading zeros,
+//!   or for [`VERBATIM`] the string's UTF-8 bytes
+//!
+//! Only hex in the form the tick library writes it is packed, so unpacking
+//! always gives back the original string and hashes over the strings stay
+//! the same. Anything else, such as a decimal test value or a left out
+//! coefficient, is kept verbatim.
+
+use std::fmt;
+
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use rug::integer::Order;
+use rug::Integer;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Tag of a non-negative coefficient
+pub const NON_NEGATIVE: u8 = 0;
+
+/// Tag of a negative coefficient
+pub const NEGATIVE: u8 = 1;
+
+/// Tag of a string kept as it is
+pub const VERBATIM: u8 = 2;
+
+/// A form's coefficients `(a, b, c)`
+pub type PackedForm = (PackedInteger, PackedInteger, PackedInteger);
+
+/// A form coefficient, held packed
+///
+/// Serializes as the hex string the tick library writes when the format
+/// is human readable, and as its packed encoding otherwise.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct PackedInteger {
+    tag: u8,
+    payload: Vec<u8>,
+}
+
+impl PackedInteger {
+    /// Pack a coefficient written by the tick library
+    pub fn new(value: &str) -> Self {
+        match packed_magnitude(value) {
+            Some((negative, payload)) => Self {
+                tag: if negative { NEGATIVE } else { NON_NEGATIVE },
+                payload,
+            },
+            None => Self {
+                tag: VERBATIM,
+                payload: value.as_bytes().to_vec(),
+            },
+        }
+    }
+
+    /// The coefficient as the tick library writes it
+    pub fn to_hex(&self) -> String {
+        match self.tag {
+            VERBATIM => String::from_utf8_lossy(&self.payload).into_owned(),
+            tag => {
+                let magnitude = Integer::from_digits(&self.payload, Order::Msf);
+                let value = if tag == NEGATIVE { -magnitude } else { magnitude };
+                format!("{:#x}", value)
+            }
+        }
+    }
+
+    /// Whether the coefficient was left out, as compact certificates
+    /// leave out `c`
+    pub fn is_empty(&self) -> bool {
+        self.tag == VERBATIM && self.payload.is_empty()
+    }
+
+    /// Length of the packed encoding
+    pub fn encoded_len(&self) -> usize {
+        5 + self.payload.len()
+    }
+
+    /// Append the packed encoding to `out`
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.tag);
+        out.extend_from_slice(&(self.payload.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.payload);
+    }
+
+    /// Decode the coefficient at the start of `bytes`
+    ///
+    /// # Returns
+    ///
+    /// The coefficient and the bytes after it
+    pub fn decode(bytes: &[u8]) -> KalaResult<(Self, &[u8])> {
+        let truncated = || KalaError::serialization("Truncated form coefficient");
+        let (&tag, rest) = bytes.split_first().ok_or_else(truncated)?;
+        let (length, rest) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+        let length = u32::from_be_bytes(*length) as usize;
+        if rest.len() < length {
+            return Err(truncated());
+        }
+        let (payload, rest) = rest.split_at(length);
+
+        match tag {
+            NON_NEGATIVE | NEGATIVE => {
+                if payload.first() == Some(&0) || (tag == NEGATIVE && payload.is_empty()) {
+                    return Err(KalaError::serialization(
+                        "Form coefficient is not minimally encoded",
+                    ));
+                }
+            }
+            VERBATIM => {
+                std::str::from_utf8(payload)
+                    .map_err(|_| KalaError::serialization("Form coefficient is not UTF-8"))?;
+            }
+            tag => {
+                return Err(KalaError::serialization(format!(
+                    "Unknown form coefficient tag {}",
+                    tag
+                )))
+            }
+        }
+        let value = Self {
+            tag,
+            payload: payload.to_vec(),
+        };
+        Ok((value, rest))
+    }
+}
+
+impl From<&str> for PackedInteger {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for PackedInteger {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl fmt::Display for PackedInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl fmt::Debug for PackedInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.to_hex())
+    }
+}
+
+impl Serialize for PackedInteger {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_hex())
+        } else {
+            let mut bytes = Vec::with_capacity(self.encoded_len());
+            self.encode(&mut bytes);
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PackedInteger {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer).map(Self::from)
+        } else {
+            let bytes = Vec::<u8>::deserialize(deserializer)?;
+            match Self::decode(&bytes).map_err(serde::de::Error::custom)? {
+                (value, []) => Ok(value),
+                _ => Err(serde::de::Error::custom("Trailing bytes after form coefficient")),
+            }
+        }
+    }
+}
+
+/// Pack a form written by the tick library
+pub fn pack_form(a: &str, b: &str, c: &str) -> PackedForm {
+    (a.into(), b.into(), c.into())
+}
+
+/// A form's coefficients as the tick library writes them
+pub fn unpack_form(form: &PackedForm) -> (String, String, String) {
+    (form.0.to_hex(), form.1.to_hex(), form.2.to_hex())
+}
+
+/// Encode a form's coefficients one after another
+pub fn encode_form(a: &PackedInteger, b: &PackedInteger, c: &PackedInteger) -> Vec<u8> {
+    let mut out = Vec::with_capacity(a.encoded_len() + b.encoded_len() + c.encoded_len());
+    for coefficient in [a, b, c] {
+        coefficient.encode(&mut out);
+    }
+    out
+}
+
+/// Decode a form encoded by [`encode_form`], which must fill `bytes`
+pub fn decode_form(bytes: &[u8]) -> KalaResult<PackedForm> {
+    let (a, rest) = PackedInteger::decode(bytes)?;
+    let (b, rest) = PackedInteger::decode(rest)?;
+    let (c, rest) = PackedInteger::decode(rest)?;
+    if !rest.is_empty() {
+        return Err(KalaError::serialization("Trailing bytes after form"));
+    }
+    Ok((a, b, c))
+}
+
+/// Write a form into a bincode encoding
+pub fn encode_bincode<E: Encoder>(
+    a: &PackedInteger,
+    b: &PackedInteger,
+    c: &PackedInteger,
+    encoder: &mut E,
+) -> Result<(), EncodeError> {
+    Encode::encode(&encode_form(a, b, c), encoder)
+}
+
+/// Read a form written by [`encode_bincode`]
+pub fn decode_bincode<Context, D: Decoder<Context = Context>>(
+    decoder: &mut D,
+) -> Result<PackedForm, DecodeError> {
+    let bytes: Vec<u8> = Decode::decode(decoder)?;
+    decode_form(&bytes).map_err(|e| DecodeError::OtherString(e.to_string()))
+}
+
+/// Sign and big-endian magnitude of a coefficient written by the tick
+/// library: lowercase hex after `0x`, no leading zeros, and no `-0x0`
+fn packed_magnitude(value: &str) -> Option<(bool, Vec<u8>)> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let hex = digits.strip_prefix("0x")?;
+    if hex.is_empty() || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    let magnitude = Integer::from_str_radix(hex, 16).ok()?;
+    let parsed = if negative { -magnitude.clone() } else { magnitude.clone() };
+    // Written back, anything else would not give the original string
+    if format!("{:#x}", parsed) != value {
+        return None;
+    }
+    Some((negative, magnitude.to_digits::<u8>(Order::Msf)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let values = [
+            "0x0", "0x1", "0xabc", "-0x1f", "0x100", "1", "-3", "", "0x0a", "-0x0", "0xAB",
+        ];
+        for value in values {
+            let packed = PackedInteger::new(value);
+            assert_eq!(packed.to_hex(), value);
+            let mut encoded = Vec::new();
+            packed.encode(&mut encoded);
+            assert_eq!(encoded.len(), packed.encoded_len());
+            let (decoded, rest) = PackedInteger::decode(&encoded).unwrap();
+            assert_eq!(decoded, packed);
+            assert!(rest.is_empty());
+        }
+
+        // Hex is packed, anything else kept verbatim
+        let mut packed = Vec::new();
+        PackedInteger::new("-0xabc").encode(&mut packed);
+        assert_eq!(packed, [NEGATIVE, 0, 0, 0, 2, 0x0a, 0xbc]);
+        let mut verbatim = Vec::new();
+        PackedInteger::new("0x0a").encode(&mut verbatim);
+        assert_eq!(verbatim[0], VERBATIM);
+        assert!(PackedInteger::new("").is_empty());
+        assert!(!PackedInteger::new("0x0").is_empty());
+    }
+
+    #[test]
+    fn test_serde() {
+        let packed = PackedInteger::new("-0x1f");
+        assert_eq!(serde_json::to_string(&packed).unwrap(), "\"-0x1f\"");
+        assert_eq!(serde_json::from_str::<PackedInteger>("\"-0x1f\"").unwrap(), packed);
+    }
+
+    #[test]
+    fn test_form_encoding() {
+        let vdf = crate::EternalVDF::with_tick_size(
+            kala_common::types::consensus::DEFAULT_DISCRIMINANT,
+            10,
+        );
+        let form = vdf.get_form_values();
+        let hex = unpack_form(&form);
+        let encoded = encode_form(&form.0, &form.1, &form.2);
+        assert!(encoded.len() < (hex.0.len() + hex.1.len() + hex.2.len()) * 3 / 5);
+        assert_eq!(decode_form(&encoded).unwrap(), form);
+        assert_eq!(pack_form(&hex.0, &hex.1, &hex.2), form);
+
+        let config = bincode::config::standard();
+        let checkpoint = vdf.checkpoint();
+        let bytes = bincode::encode_to_vec(&checkpoint, config).unwrap();
+        let (decoded, _): (crate::VDFCheckpoint, _) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!((decoded.form_a, decoded.form_b, decoded.form_c), form);
+
+        assert!(decode_form(&encoded[..encoded.len() - 1]).is_err());
+        assert!(decode_form(&[encoded.as_slice(), &[0]].concat()).is_err());
+        assert!(PackedInteger::decode(&[NON_NEGATIVE, 0, 0, 0, 2, 0, 1]).is_err());
+        assert!(PackedInteger::decode(&[3, 0, 0, 0, 0]).is_err());
+    }
+}
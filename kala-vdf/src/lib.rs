@@ -1,12 +1,47 @@
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
 use bincode::{Decode, Encode};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{
+    decode_versioned, encode_versioned, EncodingType, KalaSerialize, SerializationError,
+};
+use num_bigint::BigInt;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex, Once};
-use tick::{init, nudupl_form_inplace, Reducer, VdfForm};
+use tick::{init, nudupl_form_inplace, CudaSquarer, Reducer, VdfForm};
+use tracing::warn;
+
+pub mod engine;
+pub mod form_bytes;
+pub mod mock;
+pub mod persistence;
+pub mod tuning;
+
+pub use engine::{FormChain, FormTransition};
+pub use form_bytes::{pack_form, unpack_form, PackedForm, PackedInteger};
+pub use mock::MockVdf;
+pub use persistence::{DatabasePersistence, VdfPersistence};
+pub use tick::memory::AllocationStats;
+pub use tuning::{Capabilities, VdfConfig};
 
 static INIT: Once = Once::new();
 
+/// Versions of the VDF library and the GMP it links, e.g. `tick 0.1.0 (GMP 6.3.0)`
+pub fn library_version() -> String {
+    format!("tick {} (GMP {})", tick::VERSION, tick::gmp_version())
+}
+
+/// Allocations across the tick library's boundary, by kind of object
+///
+/// See [`tick::memory`].
+pub fn ffi_allocations() -> Vec<(&'static str, AllocationStats)> {
+    tick::memory::usage()
+}
+
 /// Initialize VDF library
 pub fn initialize_vdf() {
     INIT.call_once(|| {
@@ -22,12 +57,81 @@ struct VdfInternals {
 unsafe impl Send for VdfInternals {}
 unsafe impl Sync for VdfInternals {}
 
+/// Where the VDF's squarings run
+///
+/// `Gpu` dispatches them to the CUDA library when one loads and sees a
+/// device, like the RSW solver; otherwise, or if a squaring fails on the
+/// device, the VDF downgrades to `Cpu`. Both produce the same forms.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VdfBackend {
+    /// The tick library's CPU squaring
+    #[default]
+    Cpu,
+    /// A CUDA device, when available
+    Gpu,
+}
+
 /// Represents data timestamped at a specific iteration
 #[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
 pub struct TimestampedData {
     pub iteration: u64,
     pub data: Vec<u8>,
-    pub data_hash: [u8; 32], // H(data) for efficiency
+    pub data_hash: [u8; 32],         // H(data) for efficiency
+    pub hash_at_timestamp: [u8; 32], // h_i, the hash chain after the data
+    #[serde(default)]
+    pub location: PayloadLocation, // Where `data` is kept once stored
+}
+
+/// Where a stored tick's timestamped payload is kept
+///
+/// Merkle leaves commit to the payload's hash, so inclusion proofs hold
+/// whether or not the payload itself is kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadLocation {
+    /// In the entry's `data`
+    #[default]
+    Inline,
+    /// Nowhere, only its hash is kept
+    Dropped,
+    /// At a blob store URI, such as `s3://bucket/key`
+    External(String),
+}
+
+/// Data timestamped during a tick, in the order of its Merkle leaves
+///
+/// Handed to [`VdfPersistence::tick_data`] as the tick finalizes, so
+/// the data and its inclusion proofs can be served later.
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+pub struct TickData {
+    pub tick_number: u64,
+    pub entries: Vec<TimestampedData>,
+}
+
+impl TickData {
+    /// Leaf index of the first entry with `data_hash`
+    pub fn position(&self, data_hash: &[u8; 32]) -> Option<u64> {
+        self.entries
+            .iter()
+            .position(|entry| &entry.data_hash == data_hash)
+            .map(|index| index as u64)
+    }
+
+    /// Proof that the entry at `leaf_index` is committed to the tick's
+    /// data root
+    pub fn prove(&self, leaf_index: u64) -> Option<TimestampProof> {
+        let entry = self.entries.get(usize::try_from(leaf_index).ok()?)?;
+        Some(TimestampProof {
+            iteration: entry.iteration,
+            tick_number: self.tick_number,
+            data: entry.data.clone(),
+            data_hash: entry.data_hash,
+            hash_at_timestamp: entry.hash_at_timestamp,
+            leaf_index,
+            merkle_path: merkle_path(&data_leaves(&self.entries), leaf_index as usize),
+        })
+    }
 }
 
 /// Merkle tree node for efficient proofs
@@ -39,19 +143,48 @@ pub struct MerkleNode {
 }
 
 /// Tick certificate - stored every k iterations
-#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
+///
+/// The form is held packed, see [`form_bytes`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TickCertificate {
     pub tick_number: u64,     // i = iteration / k
     pub start_iteration: u64, // i * k
     pub end_iteration: u64,   // (i + 1) * k
-    pub form_a: String,       // VDF state at end
-    pub form_b: String,
-    pub form_c: String,
+    pub form_a: PackedInteger, // VDF state at end
+    pub form_b: PackedInteger,
+    pub form_c: PackedInteger,
     pub hash_chain: [u8; 32],              // h_(i+1)k
     pub data_merkle_root: [u8; 32],        // Root of all data in this tick
     pub wesolowski_proof: Option<Vec<u8>>, // π for efficient verification ommited for brevity for now need to efficiently generate wesolowski's
 }
 
+/// Memory an [`EternalVDF`] holds for proofs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VdfMemoryUsage {
+    /// Tick certificates kept, all of which a full checkpoint copies
+    pub certificates: u64,
+    /// Bytes of the certificates' forms, hashes and proofs
+    pub certificate_bytes: u64,
+    /// Important timestamps kept with their proofs
+    pub timestamp_proofs: u64,
+    /// Bytes of data timestamped in the current tick
+    pub pending_data_bytes: u64,
+}
+
+/// Important data waiting to be timestamped, and where to send its proof
+type ScheduledData = (Vec<u8>, Sender<TimestampProof>);
+
+/// Iteration of important data, and where to send its completed proof
+type PendingProof = (u64, Option<Sender<TimestampProof>>);
+
+/// Data an iteration timestamps
+enum StepData {
+    /// Kept only until its tick's Merkle root is computed
+    Plain(Vec<u8>),
+    /// Kept with a proof, sent once its tick finalizes if requested
+    Important(Vec<u8>, Option<Sender<TimestampProof>>),
+}
+
 /// The eternal VDF computation state
 #[derive(Clone)]
 pub struct EternalVDF {
@@ -71,8 +204,20 @@ pub struct EternalVDF {
     tick_certificates: Arc<Mutex<HashMap<u64, TickCertificate>>>,
     // Current tick's data (cleared after tick completes)
     current_tick_data: Arc<Mutex<Vec<TimestampedData>>>,
-    // For important data, store with Merkle proofs
-    important_timestamps: Arc<Mutex<HashMap<u64, (TimestampedData, Vec<[u8; 32]>)>>>,
+    // For important data, store with Merkle proofs, by iteration
+    important_timestamps: Arc<Mutex<HashMap<u64, TimestampProof>>>,
+    // Important data of the current tick, whose proofs lack their Merkle path
+    pending_proofs: Arc<Mutex<Vec<PendingProof>>>,
+    // Important data waiting for an iteration that timestamps nothing else
+    scheduled_data: Arc<Mutex<VecDeque<ScheduledData>>>,
+    // Handed finalized certificates and periodic checkpoints
+    persistence: Option<Arc<dyn VdfPersistence>>,
+    // Ticks between checkpoints handed to persistence, 0 for none
+    checkpoint_interval: u64,
+    // Advance iterations without squaring, for development only
+    simulated: bool,
+    // CUDA device the squarings run on, if the GPU backend is in use
+    gpu: Option<&'static CudaSquarer>,
 }
 
 impl EternalVDF {
@@ -106,17 +251,28 @@ impl EternalVDF {
             tick_certificates: Arc::new(Mutex::new(HashMap::new())),
             current_tick_data: Arc::new(Mutex::new(Vec::new())),
             important_timestamps: Arc::new(Mutex::new(HashMap::new())),
+            pending_proofs: Arc::new(Mutex::new(Vec::new())),
+            scheduled_data: Arc::new(Mutex::new(VecDeque::new())),
+            persistence: None,
+            checkpoint_interval: 0,
+            simulated: false,
+            gpu: None,
         }
     }
 
     /// Load from a checkpoint (tick boundary)
-    pub fn from_checkpoint(checkpoint: &VDFCheckpoint) -> Result<Self, String> {
+    ///
+    /// Fails if the checkpoint's discriminant or forms are malformed, as
+    /// they may be when read back from a corrupt database.
+    pub fn from_checkpoint(checkpoint: &VDFCheckpoint) -> KalaResult<Self> {
+        checkpoint.validate()?;
         initialize_vdf();
 
-        let mut form = VdfForm::new();
-        form.set_a(&checkpoint.form_a);
-        form.set_b(&checkpoint.form_b);
-        form.set_c(&checkpoint.form_c);
+        let form = unpacked_form(&(
+            checkpoint.form_a.clone(),
+            checkpoint.form_b.clone(),
+            checkpoint.form_c.clone(),
+        ));
 
         let internals = VdfInternals {
             current_form: form,
@@ -137,18 +293,108 @@ impl EternalVDF {
             tick_certificates: Arc::new(Mutex::new(tick_certs)),
             current_tick_data: Arc::new(Mutex::new(Vec::new())),
             important_timestamps: Arc::new(Mutex::new(HashMap::new())),
+            pending_proofs: Arc::new(Mutex::new(Vec::new())),
+            scheduled_data: Arc::new(Mutex::new(VecDeque::new())),
+            persistence: None,
+            checkpoint_interval: 0,
+            simulated: false,
+            gpu: None,
         })
     }
 
+    /// Hand finalized tick certificates to `persistence`, and a checkpoint
+    /// every `checkpoint_interval` ticks
+    ///
+    /// A `checkpoint_interval` of 0 hands over certificates only.
+    pub fn set_persistence(
+        &mut self,
+        persistence: Arc<dyn VdfPersistence>,
+        checkpoint_interval: u64,
+    ) {
+        self.persistence = Some(persistence);
+        self.checkpoint_interval = checkpoint_interval;
+    }
+
+    /// Replace the squarings with a simulated clock, for development only
+    ///
+    /// NOT FOR PRODUCTION. Iterations, the hash chain, and timestamped data
+    /// advance as usual, but the form is never squared, so no time passes
+    /// between iterations and the certificates produced don't verify.
+    pub fn set_simulated(&mut self, simulated: bool) {
+        self.simulated = simulated;
+    }
+
+    /// Whether iterations advance without squaring
+    pub fn is_simulated(&self) -> bool {
+        self.simulated
+    }
+
+    /// Run the squarings on `backend`
+    ///
+    /// Returns the backend actually in use, `Cpu` if no GPU is available.
+    pub fn set_backend(&mut self, backend: VdfBackend) -> VdfBackend {
+        self.gpu = match backend {
+            VdfBackend::Cpu => None,
+            VdfBackend::Gpu => tick::cuda(),
+        };
+        self.backend()
+    }
+
+    /// Backend the squarings run on
+    pub fn backend(&self) -> VdfBackend {
+        match self.gpu {
+            Some(_) => VdfBackend::Gpu,
+            None => VdfBackend::Cpu,
+        }
+    }
+
+    /// Square `form` on the GPU backend if it is in use
+    ///
+    /// Returns whether it was squared. A failed squaring leaves `form` as
+    /// it was and downgrades the VDF to the CPU for good.
+    fn square_on_gpu(
+        gpu: &mut Option<&'static CudaSquarer>,
+        form: &mut VdfForm,
+        discriminant: &str,
+    ) -> bool {
+        let Some(squarer) = gpu else {
+            return false;
+        };
+        match squarer.repeated_square(form, discriminant, 1) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("{}, downgrading the VDF to the CPU", e);
+                *gpu = None;
+                false
+            }
+        }
+    }
+
     /// Core computation step following Algorithm 1
+    ///
+    /// An iteration given no data timestamps the oldest data scheduled with
+    /// [`schedule_important_data`](Self::schedule_important_data), if any.
     pub fn step(&mut self, data_to_timestamp: Option<Vec<u8>>) {
+        let data = match data_to_timestamp {
+            Some(data) => Some(StepData::Plain(data)),
+            None => {
+                let scheduled = self.scheduled_data.lock().unwrap().pop_front();
+                scheduled.map(|(data, proof_tx)| StepData::Important(data, Some(proof_tx)))
+            }
+        };
+        self.compute_step(data);
+    }
+
+    fn compute_step(&mut self, data_to_timestamp: Option<StepData>) {
         let mut internals = self.internals.lock().unwrap();
 
         // fi ← fi-1^2 (mod D) - VDF step
-        nudupl_form_inplace(&mut internals.current_form, &self.discriminant);
-
-        // Reduce the form
+        if !self.simulated
+            && !Self::square_on_gpu(&mut self.gpu, &mut internals.current_form, &self.discriminant)
         {
+            nudupl_form_inplace(&mut internals.current_form, &self.discriminant);
+
+            // Reduce the form
             let VdfInternals {
                 current_form,
                 reducer,
@@ -174,23 +420,46 @@ impl EternalVDF {
         hasher.update(&self.hash_chain);
 
         // If we have data to timestamp
-        if let Some(data) = data_to_timestamp {
-            hasher.update(&data);
+        if let Some(StepData::Plain(data) | StepData::Important(data, _)) = &data_to_timestamp {
+            hasher.update(data);
+        }
+
+        self.hash_chain = hasher.finalize().into();
 
-            // Store in current tick's data
+        // Store in current tick's data, in timestamp order
+        if let Some(step_data) = data_to_timestamp {
+            let (data, important) = match step_data {
+                StepData::Plain(data) => (data, None),
+                StepData::Important(data, proof_tx) => (data, Some(proof_tx)),
+            };
             let data_hash = Sha256::digest(&data).into();
-            let ts_data = TimestampedData {
+            let mut tick_data = self.current_tick_data.lock().unwrap();
+
+            if let Some(proof_tx) = important {
+                // The Merkle path is filled in when the tick finalizes
+                let proof = TimestampProof {
+                    iteration: self.iteration,
+                    // Iteration (i + 1)k still belongs to tick i
+                    tick_number: (self.iteration - 1) / self.tick_size,
+                    data: data.clone(),
+                    data_hash,
+                    hash_at_timestamp: self.hash_chain,
+                    leaf_index: tick_data.len() as u64,
+                    merkle_path: vec![],
+                };
+                self.important_timestamps.lock().unwrap().insert(self.iteration, proof);
+                self.pending_proofs.lock().unwrap().push((self.iteration, proof_tx));
+            }
+
+            tick_data.push(TimestampedData {
                 iteration: self.iteration,
                 data,
                 data_hash,
-            };
-
-            let mut tick_data = self.current_tick_data.lock().unwrap();
-            tick_data.push(ts_data);
+                hash_at_timestamp: self.hash_chain,
+                location: PayloadLocation::Inline,
+            });
         }
 
-        self.hash_chain = hasher.finalize().into();
-
         // Check if we completed a tick (finalize after k iterations, not at boundary)
         if self.iteration > 0 && self.iteration % self.tick_size == 0 {
             self.finalize_tick();
@@ -205,7 +474,7 @@ impl EternalVDF {
 
         // Get current VDF state
         let internals = self.internals.lock().unwrap();
-        let (form_a, form_b, form_c) = internals.current_form.get_values();
+        let (form_a, form_b, form_c) = packed_values(&internals.current_form);
         drop(internals);
 
         // Calculate Merkle root of tick's data
@@ -216,6 +485,20 @@ impl EternalVDF {
             Self::compute_merkle_root(&tick_data)
         };
 
+        // Complete the proofs of the tick's important data
+        let pending = std::mem::take(&mut *self.pending_proofs.lock().unwrap());
+        let mut completed = Vec::new();
+        if !pending.is_empty() {
+            let leaves = data_leaves(&tick_data);
+            let mut important = self.important_timestamps.lock().unwrap();
+            for (iteration, proof_tx) in pending {
+                if let Some(proof) = important.get_mut(&iteration) {
+                    proof.merkle_path = merkle_path(&leaves, proof.leaf_index as usize);
+                    completed.extend(proof_tx.map(|proof_tx| (proof.clone(), proof_tx)));
+                }
+            }
+        }
+
         // Create tick certificate
         let certificate = TickCertificate {
             tick_number,
@@ -229,12 +512,38 @@ impl EternalVDF {
             wesolowski_proof: None, // Would compute In multinode setup
         };
 
+        // Clear current tick data (already in Merkle tree)
+        let entries = std::mem::take(&mut *tick_data);
+        drop(tick_data);
+
+        if let Some(persistence) = &self.persistence {
+            // Data first, so it is stored by the time its certificate is
+            if !entries.is_empty() {
+                persistence.tick_data(&TickData {
+                    tick_number,
+                    entries,
+                });
+            }
+            persistence.tick_finalized(&certificate);
+        }
+
         // Store certificate
         let mut certs = self.tick_certificates.lock().unwrap();
         certs.insert(tick_number, certificate);
+        drop(certs);
 
-        // Clear current tick data (already in Merkle tree)
-        tick_data.clear();
+        // Proofs verify now that the certificate is stored
+        for (proof, proof_tx) in completed {
+            // The requester may have given up waiting
+            let _ = proof_tx.send(proof);
+        }
+
+        if let Some(persistence) = &self.persistence {
+            let interval = self.checkpoint_interval;
+            if interval > 0 && (tick_number + 1).is_multiple_of(interval) {
+                persistence.checkpoint(&self.checkpoint_with(Vec::new()));
+            }
+        }
     }
 
     /// Compute Merkle root of timestamped data
@@ -243,33 +552,11 @@ impl EternalVDF {
             return [0u8; 32];
         }
 
-        // Leaf nodes are H(iteration || data_hash)
-        let mut hashes: Vec<[u8; 32]> = data
-            .iter()
-            .map(|ts| {
-                let mut hasher = Sha256::new();
-                hasher.update(&ts.iteration.to_le_bytes());
-                hasher.update(&ts.data_hash);
-                hasher.finalize().into()
-            })
-            .collect();
+        let mut hashes = data_leaves(data);
 
         // Build tree bottom-up
         while hashes.len() > 1 {
-            let mut next_level = Vec::new();
-
-            for chunk in hashes.chunks(2) {
-                let mut hasher = Sha256::new();
-                hasher.update(&chunk[0]);
-                if chunk.len() > 1 {
-                    hasher.update(&chunk[1]);
-                } else {
-                    hasher.update(&chunk[0]); // Duplicate for odd number
-                }
-                next_level.push(hasher.finalize().into());
-            }
-
-            hashes = next_level;
+            hashes = merkle_level(&hashes);
         }
 
         hashes[0]
@@ -293,8 +580,8 @@ impl EternalVDF {
     }
 
     /// Get current form values
-    pub fn get_form_values(&self) -> (String, String, String) {
-        let checkpoint = self.checkpoint();
+    pub fn get_form_values(&self) -> PackedForm {
+        let checkpoint = self.checkpoint_with(Vec::new());
         (checkpoint.form_a, checkpoint.form_b, checkpoint.form_c)
     }
 
@@ -322,38 +609,75 @@ impl EternalVDF {
         all_certs
     }
 
+    /// Memory held for proofs, which grows with every tick
+    pub fn memory_usage(&self) -> VdfMemoryUsage {
+        let certs = self.tick_certificates.lock().unwrap();
+        let certificate_bytes = certs
+            .values()
+            .map(|cert| {
+                let forms = cert.form_a.encoded_len()
+                    + cert.form_b.encoded_len()
+                    + cert.form_c.encoded_len();
+                let proof = cert.wesolowski_proof.as_ref().map_or(0, Vec::len);
+                (3 * 8 + 2 * 32 + forms + proof) as u64
+            })
+            .sum();
+        let certificates = certs.len() as u64;
+        drop(certs);
+
+        let pending_data_bytes = self
+            .current_tick_data
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|data| data.data.len() as u64)
+            .sum();
+        VdfMemoryUsage {
+            certificates,
+            certificate_bytes,
+            timestamp_proofs: self.important_timestamps.lock().unwrap().len() as u64,
+            pending_data_bytes,
+        }
+    }
+
     /// Store important data with Merkle proof for long-term verification
+    ///
+    /// The proof returned lacks its Merkle path until the tick finalizes,
+    /// after which [`timestamp_proof`](Self::timestamp_proof) returns it
+    /// complete.
     pub fn timestamp_important_data(&mut self, data: Vec<u8>) -> TimestampProof {
-        // First timestamp it normally
-        self.step(Some(data.clone()));
-
-        // Create proof that can be verified later
-        let data_hash = Sha256::digest(&data).into();
-        let ts_data = TimestampedData {
-            iteration: self.iteration,
-            data: data.clone(),
-            data_hash,
-        };
+        self.compute_step(Some(StepData::Important(data, None)));
+        self.timestamp_proof(self.iteration)
+            .expect("important data is recorded when timestamped")
+    }
 
-        // In multinode setup, we'd compute the Merkle path
-        // For now, just store it
-        let mut important = self.important_timestamps.lock().unwrap();
-        important.insert(self.iteration, (ts_data.clone(), vec![]));
+    /// Timestamp important data at the next iteration given no other data
+    ///
+    /// Unlike [`timestamp_important_data`](Self::timestamp_important_data),
+    /// this takes no iteration of its own, so it can't shift the iterations
+    /// consensus timestamps at. The proof is sent, with its Merkle path,
+    /// once the tick holding the data finalizes.
+    pub fn schedule_important_data(&self, data: Vec<u8>) -> Receiver<TimestampProof> {
+        let (proof_tx, proof_rx) = channel();
+        self.scheduled_data.lock().unwrap().push_back((data, proof_tx));
+        proof_rx
+    }
 
-        TimestampProof {
-            iteration: self.iteration,
-            tick_number: self.iteration / self.tick_size,
-            data,
-            data_hash,
-            hash_at_timestamp: self.hash_chain,
-            merkle_path: vec![], // Would include actual path
-        }
+    /// Proof of the important data timestamped at `iteration`
+    ///
+    /// The Merkle path is empty until the data's tick finalizes.
+    pub fn timestamp_proof(&self, iteration: u64) -> Option<TimestampProof> {
+        self.important_timestamps.lock().unwrap().get(&iteration).cloned()
     }
 
     /// Create a checkpoint for persistence
     pub fn checkpoint(&self) -> VDFCheckpoint {
+        self.checkpoint_with(self.get_all_certificates())
+    }
+
+    fn checkpoint_with(&self, tick_certificates: Vec<TickCertificate>) -> VDFCheckpoint {
         let internals = self.internals.lock().unwrap();
-        let (a, b, c) = internals.current_form.get_values();
+        let (a, b, c) = packed_values(&internals.current_form);
 
         VDFCheckpoint {
             iteration: self.iteration,
@@ -363,42 +687,205 @@ impl EternalVDF {
             hash_chain: self.hash_chain,
             discriminant: self.discriminant.clone(),
             tick_size: self.tick_size,
-            tick_certificates: self.get_all_certificates(),
+            tick_certificates,
         }
     }
 
     /// Verify a timestamp proof using tick certificates
+    ///
+    /// See [`TimestampProof::verify`]. Fails for a tick this VDF hasn't
+    /// finalized.
     pub fn verify_timestamp_proof(&self, proof: &TimestampProof) -> bool {
         // Get the tick certificate for this proof
-        let tick_num = proof.tick_number;
         let certs = self.tick_certificates.lock().unwrap();
+        certs
+            .get(&proof.tick_number)
+            .is_some_and(|cert| proof.verify(cert))
+    }
+}
 
-        if let Some(cert) = certs.get(&tick_num) {
-            // In multinode setup, verify:
-            // 1. The Merkle path from data to cert.data_merkle_root
-            // 2. The VDF proof if needed
-            // 3. The iteration is within the tick range
+/// Check that squaring `start` `iterations` times yields `end`
+///
+/// Tick certificates do not carry a succinct proof yet, so a VDF segment is
+/// verified by recomputing it. The form sequence does not depend on the
+/// timestamped data, and each segment only needs its start form, so many
+/// ticks can be verified in parallel.
+pub fn verify_form_transition(
+    discriminant: &str,
+    start: &PackedForm,
+    iterations: u64,
+    end: &PackedForm,
+) -> bool {
+    if validate_form(discriminant, start).is_err() {
+        return false;
+    }
+    initialize_vdf();
 
-            proof.iteration >= cert.start_iteration && proof.iteration < cert.end_iteration
-        } else {
-            false
+    let mut form = unpacked_form(start);
+    let reducer = Reducer::new();
+
+    for _ in 0..iterations {
+        nudupl_form_inplace(&mut form, discriminant);
+        reducer.reduce(&mut form);
+    }
+
+    packed_values(&form) == *end
+}
+
+/// The tick library's form, packed
+pub(crate) fn packed_values(form: &VdfForm) -> PackedForm {
+    let (a, b, c) = form.get_values();
+    pack_form(&a, &b, &c)
+}
+
+/// A tick library form holding `packed`
+pub(crate) fn unpacked_form(packed: &PackedForm) -> VdfForm {
+    let (a, b, c) = unpack_form(packed);
+    let mut form = VdfForm::new();
+    form.set_a(&a);
+    form.set_b(&b);
+    form.set_c(&c);
+    form
+}
+
+/// VDF iterations per second this machine sustains, measured over `iterations`
+///
+/// Steps a scratch VDF over `discriminant` from its generator, hashing each
+/// iteration as the eternal VDF does, so the rate predicts tick durations.
+pub fn benchmark(discriminant: &str, iterations: u64) -> f64 {
+    let mut vdf = EternalVDF::with_tick_size(discriminant, u64::MAX);
+    let started = std::time::Instant::now();
+    vdf.advance(iterations);
+    iterations as f64 / started.elapsed().as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+/// Parse an integer the way the tick library writes it
+///
+/// Forms are written as hex with a `0x` prefix, discriminants in decimal.
+/// Both may be negative.
+fn parse_integer(name: &str, value: &str) -> KalaResult<BigInt> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let parsed = match digits.strip_prefix("0x") {
+        Some(hex) if !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            BigInt::parse_bytes(hex.as_bytes(), 16)
+        }
+        // The tick library would read a leading zero as octal
+        Some(_) => None,
+        None if digits == "0" => Some(BigInt::from(0)),
+        None if digits.starts_with('0') => None,
+        None if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+            BigInt::parse_bytes(digits.as_bytes(), 10)
         }
+        None => None,
+    };
+    let parsed = parsed.ok_or_else(|| KalaError::validation(format!("{} is not an integer", name)))?;
+    Ok(if negative { -parsed } else { parsed })
+}
+
+/// Check `discriminant` can define the class group the VDF runs in
+///
+/// It must be negative and congruent to 1 mod 4.
+pub fn validate_discriminant(discriminant: &str) -> KalaResult<()> {
+    let d = parse_integer("Discriminant", discriminant)?;
+    if d >= BigInt::from(0) {
+        return Err(KalaError::validation("Discriminant must be negative"));
+    }
+    if (&d % 4 + 4) % 4 != BigInt::from(1) {
+        return Err(KalaError::validation("Discriminant must be 1 mod 4"));
+    }
+    Ok(())
+}
+
+/// Check `form` is a positive definite form of `discriminant`
+///
+/// That is, a > 0 and b² - 4ac = D.
+pub fn validate_form(discriminant: &str, form: &PackedForm) -> KalaResult<()> {
+    let d = parse_integer("Discriminant", discriminant)?;
+    let a = parse_integer("Form a", &form.0.to_hex())?;
+    let b = parse_integer("Form b", &form.1.to_hex())?;
+    let c = parse_integer("Form c", &form.2.to_hex())?;
+    if a <= BigInt::from(0) {
+        return Err(KalaError::validation("Form a must be positive"));
+    }
+    if &b * &b - 4 * &a * &c != d {
+        return Err(KalaError::validation("Form does not match the discriminant"));
     }
+    Ok(())
+}
+
+/// Recover the `c` coefficient of a form of `discriminant` from `a` and `b`
+///
+/// A form is fixed by its discriminant and two coefficients, so `c` needn't
+/// be sent: it is `(b² - D) / 4a`, written as the tick library writes it.
+pub fn derive_form_c(
+    discriminant: &str,
+    a: &PackedInteger,
+    b: &PackedInteger,
+) -> KalaResult<PackedInteger> {
+    let d = parse_integer("Discriminant", discriminant)?;
+    let a = parse_integer("Form a", &a.to_hex())?;
+    let b = parse_integer("Form b", &b.to_hex())?;
+    if a <= BigInt::from(0) {
+        return Err(KalaError::validation("Form a must be positive"));
+    }
+    let numerator = &b * &b - d;
+    let denominator = 4 * a;
+    if &numerator % &denominator != BigInt::from(0) {
+        return Err(KalaError::validation("Form does not match the discriminant"));
+    }
+    let c: BigInt = numerator / denominator;
+    let sign = if c < BigInt::from(0) { "-" } else { "" };
+    Ok(format!("{}0x{}", sign, c.magnitude().to_str_radix(16)).into())
 }
 
 /// Checkpoint structure for persistence
-#[derive(Serialize, Deserialize, Clone, Encode, Decode)]
+///
+/// The forms are held packed, see [`form_bytes`].
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VDFCheckpoint {
     pub iteration: u64,
-    pub form_a: String,
-    pub form_b: String,
-    pub form_c: String,
+    pub form_a: PackedInteger,
+    pub form_b: PackedInteger,
+    pub form_c: PackedInteger,
     pub hash_chain: [u8; 32],
     pub discriminant: String,
     pub tick_size: u64,
     pub tick_certificates: Vec<TickCertificate>,
 }
 
+impl VDFCheckpoint {
+    /// Start of a timeline: the generator of `discriminant`, h0 ← H("genesis")
+    pub fn genesis(discriminant: &str, tick_size: u64) -> Self {
+        EternalVDF::with_tick_size(discriminant, tick_size).checkpoint()
+    }
+
+    /// Check the discriminant, the current form, and the form of every
+    /// tick certificate
+    pub fn validate(&self) -> KalaResult<()> {
+        validate_discriminant(&self.discriminant)?;
+        if self.tick_size == 0 {
+            return Err(KalaError::validation("Tick size must be positive"));
+        }
+        let form = (self.form_a.clone(), self.form_b.clone(), self.form_c.clone());
+        validate_form(&self.discriminant, &form)
+            .map_err(|e| KalaError::validation(format!("Checkpoint at iteration {}: {}", self.iteration, e)))?;
+        for certificate in &self.tick_certificates {
+            let form = (
+                certificate.form_a.clone(),
+                certificate.form_b.clone(),
+                certificate.form_c.clone(),
+            );
+            validate_form(&self.discriminant, &form).map_err(|e| {
+                KalaError::validation(format!("Tick {} certificate: {}", certificate.tick_number, e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
 /// Timestamp proof that can be verified efficiently
 #[derive(Serialize, Deserialize, Clone, Debug, Encode, Decode)]
 pub struct TimestampProof {
@@ -407,9 +894,164 @@ pub struct TimestampProof {
     pub data: Vec<u8>,
     pub data_hash: [u8; 32],
     pub hash_at_timestamp: [u8; 32],
+    pub leaf_index: u64,            // Position among the tick's timestamped data
     pub merkle_path: Vec<[u8; 32]>, // Path to tick's Merkle root
 }
 
+impl TimestampProof {
+    /// Check the proof against the certificate of its tick
+    ///
+    /// The data must match its hash, fall in the tick, and be committed to
+    /// the certificate's `data_merkle_root` by the Merkle path, recomputed
+    /// from the leaf `H(iteration || data_hash)` at `leaf_index`.
+    pub fn verify(&self, certificate: &TickCertificate) -> bool {
+        let data_hash: [u8; 32] = Sha256::digest(&self.data).into();
+        if data_hash != self.data_hash
+            || certificate.tick_number != self.tick_number
+            || self.iteration <= certificate.start_iteration
+            || self.iteration > certificate.end_iteration
+        {
+            return false;
+        }
+
+        let mut node = data_leaf(self.iteration, &data_hash);
+        let mut index = self.leaf_index;
+        for sibling in &self.merkle_path {
+            node = if index.is_multiple_of(2) {
+                hash_pair(&node, sibling)
+            } else {
+                hash_pair(sibling, &node)
+            };
+            index /= 2;
+        }
+        index == 0 && node == certificate.data_merkle_root
+    }
+}
+
+/// Leaf of a tick's data Merkle tree, `H(iteration || data_hash)`
+fn data_leaf(iteration: u64, data_hash: &[u8; 32]) -> [u8; 32] {
+    hash_pair(&iteration.to_le_bytes(), data_hash)
+}
+
+/// Leaves of a tick's data Merkle tree, in timestamp order
+fn data_leaves(data: &[TimestampedData]) -> Vec<[u8; 32]> {
+    data.iter().map(|ts| data_leaf(ts.iteration, &ts.data_hash)).collect()
+}
+
+fn hash_pair(left: &[u8], right: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// The Merkle tree level above `hashes`, duplicating an odd last node
+fn merkle_level(hashes: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    hashes
+        .chunks(2)
+        .map(|chunk| hash_pair(&chunk[0], chunk.get(1).unwrap_or(&chunk[0])))
+        .collect()
+}
+
+/// Siblings from the leaf at `index` up to the root of `leaves`
+fn merkle_path(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let sibling = (index ^ 1).min(level.len() - 1);
+        path.push(level[sibling]);
+        level = merkle_level(&level);
+        index /= 2;
+    }
+    path
+}
+
+impl KalaSerialize for TickData {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Written for every tick with data
+    }
+}
+
+/// Stored versioned, with the form packed. Certificates stored as JSON by
+/// older nodes still load.
+impl KalaSerialize for TickCertificate {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Stored alongside tick certificates
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, SerializationError> {
+        encode_versioned(self)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, SerializationError> {
+        decode_versioned(bytes)
+    }
+}
+
+impl Encode for TickCertificate {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        Encode::encode(&self.tick_number, encoder)?;
+        Encode::encode(&self.start_iteration, encoder)?;
+        Encode::encode(&self.end_iteration, encoder)?;
+        form_bytes::encode_bincode(&self.form_a, &self.form_b, &self.form_c, encoder)?;
+        Encode::encode(&self.hash_chain, encoder)?;
+        Encode::encode(&self.data_merkle_root, encoder)?;
+        Encode::encode(&self.wesolowski_proof, encoder)
+    }
+}
+
+impl<Context> Decode<Context> for TickCertificate {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let tick_number: u64 = Decode::decode(decoder)?;
+        let start_iteration: u64 = Decode::decode(decoder)?;
+        let end_iteration: u64 = Decode::decode(decoder)?;
+        let (form_a, form_b, form_c) = form_bytes::decode_bincode(decoder)?;
+        Ok(Self {
+            tick_number,
+            start_iteration,
+            end_iteration,
+            form_a,
+            form_b,
+            form_c,
+            hash_chain: Decode::decode(decoder)?,
+            data_merkle_root: Decode::decode(decoder)?,
+            wesolowski_proof: Decode::decode(decoder)?,
+        })
+    }
+}
+
+bincode::impl_borrow_decode!(TickCertificate);
+
+impl Encode for VDFCheckpoint {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        Encode::encode(&self.iteration, encoder)?;
+        form_bytes::encode_bincode(&self.form_a, &self.form_b, &self.form_c, encoder)?;
+        Encode::encode(&self.hash_chain, encoder)?;
+        Encode::encode(&self.discriminant, encoder)?;
+        Encode::encode(&self.tick_size, encoder)?;
+        Encode::encode(&self.tick_certificates, encoder)
+    }
+}
+
+impl<Context> Decode<Context> for VDFCheckpoint {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let iteration: u64 = Decode::decode(decoder)?;
+        let (form_a, form_b, form_c) = form_bytes::decode_bincode(decoder)?;
+        Ok(Self {
+            iteration,
+            form_a,
+            form_b,
+            form_c,
+            hash_chain: Decode::decode(decoder)?,
+            discriminant: Decode::decode(decoder)?,
+            tick_size: Decode::decode(decoder)?,
+            tick_certificates: Decode::decode(decoder)?,
+        })
+    }
+}
+
+bincode::impl_borrow_decode!(VDFCheckpoint);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -471,6 +1113,35 @@ mod tests {
         assert!(vdf2.get_tick_certificate(1).is_some());
     }
 
+    #[test]
+    fn test_memory_usage() {
+        let mut vdf = EternalVDF::with_tick_size(
+            kala_common::types::consensus::DEFAULT_DISCRIMINANT,
+            5,
+        );
+        vdf.timestamp_data(b"pending".to_vec());
+        let usage = vdf.memory_usage();
+        assert_eq!((usage.certificates, usage.pending_data_bytes), (0, 7));
+
+        vdf.advance(9);
+        let usage = vdf.memory_usage();
+        assert_eq!((usage.certificates, usage.pending_data_bytes), (2, 0));
+        assert!(usage.certificate_bytes > 2 * 88);
+        assert!(ffi_allocations().iter().any(|(name, stats)| *name == "forms" && stats.live() > 0));
+    }
+
+    #[test]
+    fn test_derive_form_c() {
+        let discriminant = kala_common::types::consensus::DEFAULT_DISCRIMINANT;
+        let mut vdf = EternalVDF::with_tick_size(discriminant, 4);
+        vdf.advance(5);
+        let (a, b, c) = vdf.get_form_values();
+        assert_eq!(derive_form_c(discriminant, &a, &b).unwrap(), c);
+        // An even b never fits a discriminant that is 1 mod 4
+        assert!(derive_form_c(discriminant, &a, &"0x2".into()).is_err());
+        assert!(derive_form_c(discriminant, &"0x0".into(), &b).is_err());
+    }
+
     #[test]
     fn test_important_timestamp_proof() {
         let mut vdf = EternalVDF::with_tick_size(
@@ -478,20 +1149,111 @@ mod tests {
             20
         );
 
-        // Advance to middle of tick
-        vdf.advance(15);
+        // Advance to middle of tick, timestamping other data on the way
+        for i in 0..15u8 {
+            match i % 4 {
+                0 => vdf.timestamp_data(vec![i]),
+                _ => vdf.step(None),
+            }
+        }
 
         // Timestamp important data
         let proof = vdf.timestamp_important_data(b"Critical document".to_vec());
         assert_eq!(proof.iteration, 16);
         assert_eq!(proof.tick_number, 0);
         assert_eq!(proof.data, b"Critical document");
+        assert_eq!(proof.leaf_index, 4);
+        assert!(proof.merkle_path.is_empty());
 
         // Complete the tick
-        vdf.advance(4);
+        vdf.timestamp_data(b"later".to_vec());
+        vdf.advance(3);
+        assert!(!vdf.verify_timestamp_proof(&proof));
 
-        // Verify the proof
+        // Verify the proof, completed with its path to the six leaves' root
+        let proof = vdf.timestamp_proof(16).unwrap();
+        assert_eq!(proof.merkle_path.len(), 3);
         assert!(vdf.verify_timestamp_proof(&proof));
+        let certificate = vdf.get_tick_certificate(0).unwrap();
+        assert!(proof.verify(&certificate));
+
+        let mut forged = proof.clone();
+        forged.data = b"Forged document".to_vec();
+        assert!(!vdf.verify_timestamp_proof(&forged));
+        forged.data_hash = Sha256::digest(&forged.data).into();
+        assert!(!vdf.verify_timestamp_proof(&forged));
+
+        // The path only leads to the root from the proven leaf
+        let mut moved = proof.clone();
+        moved.leaf_index = 5;
+        assert!(!vdf.verify_timestamp_proof(&moved));
+        moved.leaf_index = 4 + 8;
+        assert!(!vdf.verify_timestamp_proof(&moved));
+        let mut shifted = proof.clone();
+        shifted.iteration = 15;
+        assert!(!vdf.verify_timestamp_proof(&shifted));
+        let mut short = proof;
+        short.merkle_path.pop();
+        assert!(!vdf.verify_timestamp_proof(&short));
+    }
+
+    #[test]
+    fn test_merkle_paths_lead_to_root() {
+        for count in 1..=9u64 {
+            let data: Vec<TimestampedData> = (0..count)
+                .map(|iteration| TimestampedData {
+                    iteration,
+                    data: vec![],
+                    data_hash: [iteration as u8; 32],
+                    hash_at_timestamp: [0; 32],
+                    location: PayloadLocation::Inline,
+                })
+                .collect();
+            let root = EternalVDF::compute_merkle_root(&data);
+            let leaves: Vec<[u8; 32]> =
+                data.iter().map(|ts| data_leaf(ts.iteration, &ts.data_hash)).collect();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let mut node = *leaf;
+                for (level, sibling) in merkle_path(&leaves, index).iter().enumerate() {
+                    node = match (index >> level) % 2 {
+                        0 => hash_pair(&node, sibling),
+                        _ => hash_pair(sibling, &node),
+                    };
+                }
+                assert_eq!(node, root, "leaf {} of {}", index, count);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scheduled_timestamp_takes_no_iteration() {
+        let discriminant = kala_common::types::consensus::DEFAULT_DISCRIMINANT;
+        let mut vdf = EternalVDF::with_tick_size(discriminant, 4);
+        vdf.advance(4);
+        vdf.advance(1);
+
+        let proof_rx = vdf.schedule_important_data(b"document".to_vec());
+        let last_rx = vdf.schedule_important_data(b"last".to_vec());
+        assert!(proof_rx.try_recv().is_err());
+
+        // Iterations given data keep it; the next free one takes the schedule
+        vdf.timestamp_data(b"transaction".to_vec());
+        vdf.step(None);
+        assert_eq!(vdf.timestamp_proof(7).unwrap().hash_at_timestamp, vdf.get_hash_chain());
+
+        // Proofs are sent complete once the tick finalizes
+        assert!(proof_rx.try_recv().is_err());
+        vdf.step(None);
+        let proof = proof_rx.try_recv().unwrap();
+        assert_eq!((vdf.get_iteration(), proof.iteration, proof.tick_number), (8, 7, 1));
+        assert_eq!(proof.leaf_index, 1);
+        assert!(vdf.verify_timestamp_proof(&proof));
+
+        // The last iteration of a tick is proven against that tick
+        let last = last_rx.try_recv().unwrap();
+        assert_eq!((last.iteration, last.tick_number), (8, 1));
+        assert!(vdf.verify_timestamp_proof(&last));
     }
 
     #[test]
@@ -501,11 +1263,15 @@ mod tests {
                 iteration: 1,
                 data: b"first".to_vec(),
                 data_hash: Sha256::digest(b"first").into(),
+                hash_at_timestamp: [0; 32],
+                location: PayloadLocation::Inline,
             },
             TimestampedData {
                 iteration: 2,
                 data: b"second".to_vec(),
                 data_hash: Sha256::digest(b"second").into(),
+                hash_at_timestamp: [0; 32],
+                location: PayloadLocation::Inline,
             },
         ];
 
@@ -518,11 +1284,101 @@ mod tests {
             iteration: 1,
             data: b"different".to_vec(),
             data_hash: Sha256::digest(b"different").into(),
+            hash_at_timestamp: [0; 32],
+            location: PayloadLocation::Inline,
         }];
         let root3 = EternalVDF::compute_merkle_root(&data2);
         assert_ne!(root1, root3);
     }
 
+    #[test]
+    fn test_verify_form_transition() {
+        let discriminant = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+        let mut vdf = EternalVDF::with_tick_size(discriminant, 10);
+        vdf.advance(10);
+        let start = vdf.get_form_values();
+
+        // Timestamped data changes the hash chain but not the forms
+        vdf.timestamp_data(b"some data".to_vec());
+        vdf.advance(9);
+        let end = vdf.get_form_values();
+
+        assert!(verify_form_transition(discriminant, &start, 10, &end));
+        assert!(!verify_form_transition(discriminant, &start, 9, &end));
+        assert!(!verify_form_transition(discriminant, &end, 10, &start));
+    }
+
+    #[test]
+    fn test_gpu_backend_matches_cpu() {
+        let discriminant = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+        let mut cpu = EternalVDF::with_tick_size(discriminant, 10);
+        assert_eq!(cpu.backend(), VdfBackend::Cpu);
+
+        // Without a device the VDF stays on the CPU
+        let mut gpu = EternalVDF::with_tick_size(discriminant, 10);
+        let expected = match tick::cuda() {
+            Some(_) => VdfBackend::Gpu,
+            None => VdfBackend::Cpu,
+        };
+        assert_eq!(gpu.set_backend(VdfBackend::Gpu), expected);
+
+        cpu.advance(15);
+        gpu.advance(15);
+        assert_eq!(gpu.get_form_values(), cpu.get_form_values());
+        assert_eq!(gpu.get_hash_chain(), cpu.get_hash_chain());
+        assert_eq!(gpu.set_backend(VdfBackend::Cpu), VdfBackend::Cpu);
+    }
+
+    #[test]
+    fn test_simulated_clock() {
+        let discriminant = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+        let mut vdf = EternalVDF::with_tick_size(discriminant, 10);
+        vdf.set_simulated(true);
+        assert!(vdf.is_simulated());
+        let start = vdf.get_form_values();
+        let genesis_hash = vdf.get_hash_chain();
+
+        // Ticks complete and data is timestamped, but the form never moves
+        vdf.timestamp_data(b"some data".to_vec());
+        vdf.advance(19);
+        assert_eq!(vdf.get_iteration(), 20);
+        assert_ne!(vdf.get_hash_chain(), genesis_hash);
+        assert_eq!(vdf.get_form_values(), start);
+        let certificate = vdf.get_tick_certificate(1).unwrap();
+        assert_eq!(certificate.end_iteration, 20);
+        assert!(!verify_form_transition(discriminant, &start, 20, &vdf.get_form_values()));
+    }
+
+    #[test]
+    fn test_checkpoint_validation() {
+        let discriminant = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+        let mut vdf = EternalVDF::with_tick_size(discriminant, 5);
+        vdf.advance(5);
+        let checkpoint = vdf.checkpoint();
+        assert!(checkpoint.validate().is_ok());
+        assert!(VDFCheckpoint::genesis(discriminant, 5).validate().is_ok());
+
+        // Corrupt forms fail to load instead of panicking
+        let mut corrupt = checkpoint.clone();
+        corrupt.form_b = "0x1\0".into();
+        assert!(EternalVDF::from_checkpoint(&corrupt).is_err());
+        let mut corrupt = checkpoint.clone();
+        corrupt.form_a = "1".into();
+        corrupt.form_b = "0".into();
+        corrupt.form_c = "1".into();
+        assert!(EternalVDF::from_checkpoint(&corrupt).is_err());
+        let mut corrupt = checkpoint.clone();
+        corrupt.tick_certificates[0].form_c = "0x2".into();
+        assert!(corrupt.validate().is_err());
+
+        let mut corrupt = checkpoint;
+        corrupt.discriminant = discriminant.trim_start_matches('-').to_string();
+        assert!(corrupt.validate().is_err());
+        assert!(validate_discriminant("-7").is_ok());
+        assert!(validate_discriminant("-5").is_err());
+        assert!(validate_discriminant("-07").is_err());
+    }
+
     #[test]
     fn test_vdf_thread_safety() {
         let vdf = EternalVDF::new("-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679");
@@ -0,0 +1,202 @@
+//! A [`VdfEngine`] that skips the squaring, for deterministic simulation
+//!
+//! [`MockVdf`] keeps the eternal VDF's hash chain, timestamped data and
+//! tick certificates, but its forms are hashes of a seed and the
+//! iteration instead of squarings. An iteration costs a hash, so the
+//! consensus, state and RPC layers can be driven through many ticks in
+//! milliseconds, and two engines given the same seed and data reach the
+//! same chain. Its proofs only show that an engine with the same seed
+//! made them, so it must never stand in for the VDF outside of tests.
+
+use std::collections::BTreeMap;
+
+use kala_common::vdf::VdfEngine;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    pack_form, unpack_form, EternalVDF, PackedForm, PayloadLocation, TickCertificate,
+    TimestampedData, VDFCheckpoint,
+};
+
+/// A seeded stand-in for [`EternalVDF`]
+///
+/// Every tick is certified like the eternal VDF's, with the hash chain
+/// h_i ← H(i ∥ f_i ∥ h_i-1 ∥ d_i) and a Merkle root of its timestamped
+/// data, so anything driving it sees the same shape of output.
+#[derive(Clone, Debug)]
+pub struct MockVdf {
+    seed: u64,
+    tick_size: u64,
+    iteration: u64,
+    hash_chain: [u8; 32],
+    /// Data timestamped in the tick in progress
+    current_tick_data: Vec<TimestampedData>,
+    tick_certificates: BTreeMap<u64, TickCertificate>,
+}
+
+impl MockVdf {
+    /// Start at iteration 0 with h0 ← H("genesis"), like [`EternalVDF`]
+    pub fn new(seed: u64, tick_size: u64) -> Self {
+        Self {
+            seed,
+            tick_size,
+            iteration: 0,
+            hash_chain: Sha256::digest(b"genesis").into(),
+            current_tick_data: Vec::new(),
+            tick_certificates: BTreeMap::new(),
+        }
+    }
+
+    /// Form at `iteration` of the engines seeded with `seed`
+    pub fn form_at(seed: u64, iteration: u64) -> PackedForm {
+        let mut hasher = Sha256::new();
+        hasher.update(b"mock form");
+        hasher.update(seed.to_le_bytes());
+        hasher.update(iteration.to_le_bytes());
+        let digest = hasher.finalize();
+        pack_form(&hex::encode(&digest[..16]), &hex::encode(&digest[16..]), "1")
+    }
+
+    /// Seed the forms are derived from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Current hash chain value
+    pub fn hash_chain(&self) -> [u8; 32] {
+        self.hash_chain
+    }
+
+    /// Certificate of tick `tick_number`, if it has been finalized
+    pub fn tick_certificate(&self, tick_number: u64) -> Option<&TickCertificate> {
+        self.tick_certificates.get(&tick_number)
+    }
+
+    fn finalize_tick(&mut self) {
+        let tick_number = (self.iteration - 1) / self.tick_size;
+        let (form_a, form_b, form_c) = Self::form_at(self.seed, self.iteration);
+        let data = std::mem::take(&mut self.current_tick_data);
+        let certificate = TickCertificate {
+            tick_number,
+            start_iteration: tick_number * self.tick_size,
+            end_iteration: self.iteration,
+            form_a,
+            form_b,
+            form_c,
+            hash_chain: self.hash_chain,
+            data_merkle_root: EternalVDF::compute_merkle_root(&data),
+            wesolowski_proof: None,
+        };
+        self.tick_certificates.insert(tick_number, certificate);
+    }
+}
+
+impl VdfEngine for MockVdf {
+    type Checkpoint = VDFCheckpoint;
+    type Proof = TickCertificate;
+
+    fn step(&mut self, data: Option<Vec<u8>>) {
+        self.iteration += 1;
+        let (form_a, form_b, form_c) = unpack_form(&Self::form_at(self.seed, self.iteration));
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.iteration.to_le_bytes());
+        hasher.update(form_a.as_bytes());
+        hasher.update(form_b.as_bytes());
+        hasher.update(form_c.as_bytes());
+        hasher.update(self.hash_chain);
+        if let Some(data) = &data {
+            hasher.update(data);
+        }
+        self.hash_chain = hasher.finalize().into();
+
+        if let Some(data) = data {
+            self.current_tick_data.push(TimestampedData {
+                iteration: self.iteration,
+                data_hash: Sha256::digest(&data).into(),
+                data,
+                hash_at_timestamp: self.hash_chain,
+                location: PayloadLocation::Inline,
+            });
+        }
+
+        if self.iteration.is_multiple_of(self.tick_size) {
+            self.finalize_tick();
+        }
+    }
+
+    fn iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    fn prove(&self, tick_number: u64) -> Option<TickCertificate> {
+        self.tick_certificates.get(&tick_number).cloned()
+    }
+
+    /// Rederive the tick's final form from the seed
+    ///
+    /// Like the eternal VDF's, the hash chain is not checked.
+    fn verify(&self, proof: &TickCertificate) -> bool {
+        let end = (proof.form_a.clone(), proof.form_b.clone(), proof.form_c.clone());
+        proof.start_iteration == proof.tick_number * self.tick_size
+            && proof.end_iteration == proof.start_iteration + self.tick_size
+            && end == Self::form_at(self.seed, proof.end_iteration)
+    }
+
+    /// Checkpoint whose discriminant names the seed, as `mock:<seed>`
+    fn checkpoint(&self) -> VDFCheckpoint {
+        let (form_a, form_b, form_c) = Self::form_at(self.seed, self.iteration);
+        VDFCheckpoint {
+            iteration: self.iteration,
+            form_a,
+            form_b,
+            form_c,
+            hash_chain: self.hash_chain,
+            discriminant: format!("mock:{}", self.seed),
+            tick_size: self.tick_size,
+            tick_certificates: self.tick_certificates.values().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Step through `ticks` ticks of 10 iterations, timestamping now and then
+    fn run(seed: u64, ticks: u64) -> MockVdf {
+        let mut vdf = MockVdf::new(seed, 10);
+        for iteration in 0..ticks * 10 {
+            let data = (iteration % 3 == 0).then(|| iteration.to_le_bytes().to_vec());
+            vdf.step(data);
+        }
+        vdf
+    }
+
+    #[test]
+    fn test_mock_is_deterministic() {
+        let vdf = run(7, 1_000);
+        assert_eq!(vdf.iteration(), 10_000);
+        assert_eq!(vdf.hash_chain(), run(7, 1_000).hash_chain());
+        assert_ne!(vdf.hash_chain(), run(8, 1_000).hash_chain());
+
+        // Certificates verify against the same seed only
+        let certificate = vdf.prove(999).unwrap();
+        assert_eq!((certificate.start_iteration, certificate.end_iteration), (9_990, 10_000));
+        assert_eq!(certificate.hash_chain, vdf.hash_chain());
+        assert_ne!(certificate.data_merkle_root, [0u8; 32]);
+        assert!(vdf.verify(&certificate));
+        assert!(!run(8, 1).verify(&certificate));
+        let mut forged = certificate.clone();
+        forged.form_b = vdf.prove(998).unwrap().form_b;
+        assert!(!vdf.verify(&forged));
+
+        let checkpoint = vdf.checkpoint();
+        assert_eq!(checkpoint.discriminant, "mock:7");
+        assert_eq!(checkpoint.tick_certificates.len(), 1_000);
+        assert_eq!(
+            (checkpoint.form_a, checkpoint.form_b, checkpoint.form_c),
+            MockVdf::form_at(7, 10_000)
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Streaming persistence of the eternal VDF
+//!
+//! An [`EternalVDF`] keeps its tick certificates in memory. A
+//! [`VdfPersistence`] hook set with [`EternalVDF::set_persistence`] is
+//! handed each certificate and the data timestamped in its tick as the tick
+//! finalizes, and a checkpoint every `checkpoint_interval` ticks.
+//! [`DatabasePersistence`] writes them to a [`KalaDatabase`] on a
+//! background thread, so the VDF never waits on disk.
+//! After a crash, [`DatabasePersistence::resume`] picks up where it stopped.
+//!
+//! [`EternalVDF`]: crate::EternalVDF
+//! [`EternalVDF::set_persistence`]: crate::EternalVDF::set_persistence
+
+use crate::{TickCertificate, TickData, VDFCheckpoint};
+use kala_common::database::{KalaDatabase, TypedColumn};
+use kala_common::error::{KalaError, KalaResult};
+use kala_common::serialization::{
+    decode_versioned, encode_versioned, EncodingType, KalaSerialize, SerializationError,
+};
+use kala_common::types::database::{VDF_TICK_DATA_PREFIX, VDF_TICK_PREFIX};
+use std::sync::mpsc::{self, Sender};
+use tracing::warn;
+
+/// Prefix checkpoints are stored under
+const CHECKPOINT_PREFIX: &str = "vdf_checkpoint";
+
+/// Key of the latest checkpoint
+pub const LATEST_CHECKPOINT: &str = "latest";
+
+/// Receives the VDF's output as it is computed
+///
+/// Called on the thread stepping the VDF, so implementations should hand
+/// the work off instead of blocking.
+pub trait VdfPersistence: Send + Sync {
+    /// A tick's certificate, once its last iteration is computed
+    fn tick_finalized(&self, certificate: &TickCertificate);
+
+    /// The data timestamped in a tick, before the tick's certificate
+    ///
+    /// Not called for ticks without data.
+    fn tick_data(&self, _data: &TickData) {}
+
+    /// The VDF's state at a tick boundary
+    ///
+    /// Carries no tick certificates, which were handed over one by one.
+    fn checkpoint(&self, checkpoint: &VDFCheckpoint);
+}
+
+enum Write {
+    Tick(TickCertificate),
+    Data(TickData),
+    Checkpoint(VDFCheckpoint),
+    Flush(Sender<()>),
+}
+
+/// Writes tick certificates and checkpoints to a database in the background
+///
+/// Certificates are stored by tick number under `vdf_tick`, where
+/// `StateDB::get_vdf_tick_certificate` reads them, and the ticks' data
+/// under `vdf_data`, read by `StateDB::get_vdf_tick_data`. Only the latest
+/// checkpoint is kept. Failed writes are logged and skipped.
+pub struct DatabasePersistence {
+    writes: Sender<Write>,
+}
+
+impl DatabasePersistence {
+    /// Start the writer thread for `db`
+    pub fn new(db: &KalaDatabase) -> KalaResult<Self> {
+        let ticks: TypedColumn<u64, TickCertificate> = TypedColumn::new(db, VDF_TICK_PREFIX);
+        let data: TypedColumn<u64, TickData> = TypedColumn::new(db, VDF_TICK_DATA_PREFIX);
+        let checkpoints = Self::checkpoints(db);
+        let (writes, queue) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("vdf-persistence".to_string())
+            .spawn(move || {
+                // Ends once the last sender is dropped
+                for write in queue {
+                    let result = match write {
+                        Write::Tick(certificate) => {
+                            ticks.put(&certificate.tick_number, &certificate)
+                        }
+                        Write::Data(tick_data) => data.put(&tick_data.tick_number, &tick_data),
+                        Write::Checkpoint(checkpoint) => {
+                            checkpoints.put(&LATEST_CHECKPOINT, &checkpoint)
+                        }
+                        Write::Flush(done) => {
+                            let _ = done.send(());
+                            continue;
+                        }
+                    };
+                    if let Err(e) = result {
+                        warn!("Failed to persist VDF output: {}", e);
+                    }
+                }
+            })
+            .map_err(|e| KalaError::internal(format!("Failed to start VDF persistence: {}", e)))?;
+        Ok(Self { writes })
+    }
+
+    /// Latest checkpoint stored in `db`
+    pub fn load_checkpoint(db: &KalaDatabase) -> KalaResult<Option<VDFCheckpoint>> {
+        Self::checkpoints(db).get(&LATEST_CHECKPOINT)
+    }
+
+    /// Newest VDF state in `db` at or before tick `until_tick`
+    ///
+    /// Starts from the latest checkpoint and moves through the certificates
+    /// stored after it, each carrying the state at its tick's end. The
+    /// certificate of the checkpoint's last tick must carry its hash chain,
+    /// and each later one must start where the one before it ended. The
+    /// returned checkpoint holds those certificates. `None` if no checkpoint
+    /// is stored, or the latest one is past `until_tick`.
+    pub fn resume(db: &KalaDatabase, until_tick: u64) -> KalaResult<Option<VDFCheckpoint>> {
+        let mut checkpoint = match Self::load_checkpoint(db)? {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(None),
+        };
+        let tick_size = checkpoint.tick_size;
+        if tick_size == 0 || checkpoint.iteration % tick_size != 0 {
+            return Err(KalaError::validation(format!(
+                "Checkpoint at iteration {} is not at a tick boundary",
+                checkpoint.iteration
+            )));
+        }
+        let mut tick = checkpoint.iteration / tick_size;
+        if tick > until_tick {
+            return Ok(None);
+        }
+
+        let ticks: TypedColumn<u64, TickCertificate> = TypedColumn::new(db, VDF_TICK_PREFIX);
+        let mut recent = Vec::new();
+        let last = match tick {
+            0 => None,
+            _ => ticks.get(&(tick - 1))?,
+        };
+        if let Some(last) = last {
+            if last.end_iteration != checkpoint.iteration
+                || last.hash_chain != checkpoint.hash_chain
+            {
+                return Err(KalaError::validation(format!(
+                    "Checkpoint at iteration {} doesn't continue tick {}",
+                    checkpoint.iteration, last.tick_number
+                )));
+            }
+            recent.push(last);
+        }
+        while tick < until_tick {
+            let certificate = match ticks.get(&tick)? {
+                Some(certificate) => certificate,
+                None => break,
+            };
+            if certificate.start_iteration != checkpoint.iteration
+                || certificate.end_iteration != checkpoint.iteration + tick_size
+            {
+                return Err(KalaError::validation(format!(
+                    "Tick {} doesn't continue from iteration {}",
+                    tick, checkpoint.iteration
+                )));
+            }
+            checkpoint.iteration = certificate.end_iteration;
+            checkpoint.form_a = certificate.form_a.clone();
+            checkpoint.form_b = certificate.form_b.clone();
+            checkpoint.form_c = certificate.form_c.clone();
+            checkpoint.hash_chain = certificate.hash_chain;
+            recent.push(certificate);
+            tick += 1;
+        }
+        checkpoint.tick_certificates = recent;
+        Ok(Some(checkpoint))
+    }
+
+    /// Block until every write queued so far has landed
+    pub fn flush(&self) {
+        let (done, flushed) = mpsc::channel();
+        if self.writes.send(Write::Flush(done)).is_ok() {
+            let _ = flushed.recv();
+        }
+    }
+
+    /// The checkpoints stored in `db`, the latest under [`LATEST_CHECKPOINT`]
+    pub fn checkpoints(db: &KalaDatabase) -> TypedColumn<&'static str, VDFCheckpoint> {
+        TypedColumn::new(db, CHECKPOINT_PREFIX)
+    }
+
+    fn queue(&self, write: Write) {
+        if self.writes.send(write).is_err() {
+            warn!("VDF persistence thread has stopped");
+        }
+    }
+}
+
+impl VdfPersistence for DatabasePersistence {
+    fn tick_finalized(&self, certificate: &TickCertificate) {
+        self.queue(Write::Tick(certificate.clone()));
+    }
+
+    fn tick_data(&self, data: &TickData) {
+        self.queue(Write::Data(data.clone()));
+    }
+
+    fn checkpoint(&self, checkpoint: &VDFCheckpoint) {
+        self.queue(Write::Checkpoint(checkpoint.clone()));
+    }
+}
+
+/// Stored versioned, with the forms packed. Checkpoints stored as JSON by
+/// older nodes still load.
+impl KalaSerialize for VDFCheckpoint {
+    fn preferred_encoding() -> EncodingType {
+        EncodingType::Bincode // Compact for frequent checkpoints
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, SerializationError> {
+        encode_versioned(self)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, SerializationError> {
+        decode_versioned(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EternalVDF;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    const DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+
+    #[test]
+    fn test_ticks_and_checkpoints_streamed_to_database() {
+        let dir = tempdir().unwrap();
+        let db = KalaDatabase::new(dir.path().to_str().unwrap()).unwrap();
+        let persistence = Arc::new(DatabasePersistence::new(&db).unwrap());
+        let ticks: TypedColumn<u64, TickCertificate> = TypedColumn::new(&db, VDF_TICK_PREFIX);
+
+        let mut vdf = EternalVDF::with_tick_size(DISCRIMINANT, 5);
+        vdf.set_persistence(persistence.clone(), 2);
+        vdf.advance(15);
+        persistence.flush();
+
+        for tick in 0..3 {
+            let stored = ticks.get(&tick).unwrap().unwrap();
+            assert_eq!(stored.hash_chain, vdf.get_tick_certificate(tick).unwrap().hash_chain);
+        }
+        assert!(ticks.get(&3).unwrap().is_none());
+
+        // Checkpoints land every second tick, without certificates
+        let checkpoint = DatabasePersistence::load_checkpoint(&db).unwrap().unwrap();
+        assert_eq!(checkpoint.iteration, 10);
+        assert!(checkpoint.tick_certificates.is_empty());
+        let mut resumed = EternalVDF::from_checkpoint(&checkpoint).unwrap();
+        resumed.advance(5);
+        assert_eq!(resumed.get_hash_chain(), vdf.get_hash_chain());
+    }
+
+    #[test]
+    fn test_tick_data_streamed_to_database() {
+        let dir = tempdir().unwrap();
+        let db = KalaDatabase::new(dir.path().to_str().unwrap()).unwrap();
+        let persistence = Arc::new(DatabasePersistence::new(&db).unwrap());
+        let data: TypedColumn<u64, TickData> = TypedColumn::new(&db, VDF_TICK_DATA_PREFIX);
+
+        let mut vdf = EternalVDF::with_tick_size(DISCRIMINANT, 5);
+        vdf.set_persistence(persistence.clone(), 0);
+        vdf.advance(6);
+        vdf.timestamp_data(b"first".to_vec());
+        vdf.timestamp_data(b"second".to_vec());
+        vdf.advance(2);
+        persistence.flush();
+
+        // Ticks without data store nothing
+        assert!(data.get(&0).unwrap().is_none());
+        let stored = data.get(&1).unwrap().unwrap();
+        assert_eq!(stored.entries.len(), 2);
+        assert_eq!(stored.entries[1].data, b"second".to_vec());
+
+        let certificate = vdf.get_tick_certificate(1).unwrap();
+        let proof = stored.prove(stored.position(&stored.entries[1].data_hash).unwrap()).unwrap();
+        assert_eq!((proof.iteration, proof.leaf_index), (8, 1));
+        assert!(proof.verify(&certificate));
+        assert!(stored.prove(2).is_none());
+    }
+
+    #[test]
+    fn test_resume_past_latest_checkpoint() {
+        let dir = tempdir().unwrap();
+        let db = KalaDatabase::new(dir.path().to_str().unwrap()).unwrap();
+        let persistence = Arc::new(DatabasePersistence::new(&db).unwrap());
+        assert!(DatabasePersistence::resume(&db, 10).unwrap().is_none());
+
+        let mut vdf = EternalVDF::with_tick_size(DISCRIMINANT, 5);
+        vdf.set_persistence(persistence.clone(), 2);
+        vdf.advance(12);
+        vdf.timestamp_data(b"data".to_vec());
+        vdf.advance(2);
+        persistence.flush();
+
+        // The certificate after the checkpoint carries the newest state
+        let checkpoint = DatabasePersistence::resume(&db, 10).unwrap().unwrap();
+        assert_eq!(checkpoint.iteration, 15);
+        assert_eq!(checkpoint.hash_chain, vdf.get_hash_chain());
+        let ticks: Vec<_> = checkpoint.tick_certificates.iter().map(|c| c.tick_number).collect();
+        assert_eq!(ticks, vec![1, 2]);
+        let resumed = EternalVDF::from_checkpoint(&checkpoint).unwrap();
+        assert_eq!(resumed.get_form_values(), vdf.get_form_values());
+        assert!(resumed.get_tick_certificate(2).is_some());
+
+        assert_eq!(DatabasePersistence::resume(&db, 2).unwrap().unwrap().iteration, 10);
+        assert!(DatabasePersistence::resume(&db, 1).unwrap().is_none());
+
+        // A certificate that skips iterations breaks the chain
+        let column: TypedColumn<u64, TickCertificate> = TypedColumn::new(&db, VDF_TICK_PREFIX);
+        let mut gap = column.get(&2).unwrap().unwrap();
+        gap.start_iteration += 1;
+        column.put(&2, &gap).unwrap();
+        assert!(DatabasePersistence::resume(&db, 10).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Tuning the VDF library to the machine
+//!
+//! The tick library's fast squaring path can use hand-written assembly and
+//! AVX-512 IFMA, and segment verification parallelises across cores.
+//! [`VdfConfig::auto_tune`] picks these from the detected
+//! [`Capabilities`], keeping the instruction switches only if a benchmark
+//! finds them no slower.
+
+use serde::{Deserialize, Serialize};
+pub use tick::{get_capabilities, Capabilities};
+
+use crate::benchmark;
+
+/// Iterations each tuning benchmark runs
+const TUNING_ITERATIONS: u64 = 2048;
+
+/// How the VDF library uses the machine
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VdfConfig {
+    /// Threads recomputing VDF segments during verification
+    pub num_threads: usize,
+    /// Let the fast squaring path use AVX-512 IFMA
+    pub avx512: bool,
+    /// Let the fast squaring path use its assembly
+    pub fast_mode: bool,
+}
+
+impl VdfConfig {
+    /// Pick the configuration for this machine and apply it
+    ///
+    /// One core is left to the eternal VDF and the rest verify. The
+    /// instructions the CPU supports are enabled, then benchmarked over
+    /// `discriminant` against the portable path and dropped if slower.
+    pub fn auto_tune(discriminant: &str) -> Self {
+        let capabilities = get_capabilities();
+        let portable = Self {
+            num_threads: capabilities.cores.saturating_sub(1).max(1),
+            avx512: false,
+            fast_mode: false,
+        };
+        let accelerated = Self {
+            avx512: capabilities.avx512_ifma,
+            fast_mode: capabilities.avx2,
+            ..portable
+        };
+        if accelerated == portable {
+            portable.apply();
+            return portable;
+        }
+
+        accelerated.apply();
+        let accelerated_rate = benchmark(discriminant, TUNING_ITERATIONS);
+        portable.apply();
+        let portable_rate = benchmark(discriminant, TUNING_ITERATIONS);
+        let chosen = if accelerated_rate >= portable_rate {
+            accelerated
+        } else {
+            portable
+        };
+        chosen.apply();
+        chosen
+    }
+
+    /// Set the library's instruction switches to this configuration
+    ///
+    /// Applies to the whole process. `num_threads` is for the caller's
+    /// verification pool.
+    pub fn apply(&self) {
+        tick::set_instructions(self.avx512, self.fast_mode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+
+    #[test]
+    fn test_auto_tune_within_capabilities() {
+        let capabilities = get_capabilities();
+        let config = VdfConfig::auto_tune(DISCRIMINANT);
+        assert!(config.num_threads >= 1);
+        assert!(config.num_threads <= capabilities.cores);
+        assert!(!config.avx512 || capabilities.avx512_ifma);
+        assert!(!config.fast_mode || capabilities.avx2);
+    }
+}
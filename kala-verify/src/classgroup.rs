@@ -0,0 +1,296 @@
+//! Class group arithmetic in pure Rust
+//!
+//! The VDF repeatedly squares a binary quadratic form `(a, b, c)` of a
+//! negative discriminant `D = b² - 4ac`. Every class of forms holds exactly
+//! one reduced form, with `|b| <= a <= c` and `b >= 0` when `|b| = a` or
+//! `a = c`, so squaring and reducing here yields the very coefficients the
+//! tick library computes with GMP.
+//!
+//! Coefficients are read and written as the tick library writes them: hex
+//! with a `0x` prefix for forms, possibly negative, and decimal for
+//! discriminants.
+
+use crate::{VerifyError, VerifyResult};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// Positive definite binary quadratic form `ax² + bxy + cy²`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Form {
+    /// Coefficient of `x²`, always positive
+    pub a: BigInt,
+    /// Coefficient of `xy`
+    pub b: BigInt,
+    /// Coefficient of `y²`
+    pub c: BigInt,
+}
+
+impl Form {
+    /// The reduced form of `discriminant` with coefficients `a` and `b`
+    ///
+    /// # Errors
+    ///
+    /// [`VerifyError::Malformed`] if `a` isn't positive or no integer `c`
+    /// gives the discriminant
+    pub fn from_ab(discriminant: &BigInt, a: BigInt, b: BigInt) -> VerifyResult<Self> {
+        if !a.is_positive() {
+            return Err(VerifyError::malformed("Form a must be positive"));
+        }
+        let numerator = &b * &b - discriminant;
+        let denominator: BigInt = &a << 2;
+        if !numerator.is_multiple_of(&denominator) {
+            return Err(VerifyError::malformed("Form does not match the discriminant"));
+        }
+        let mut form = Self {
+            c: numerator / denominator,
+            a,
+            b,
+        };
+        form.reduce();
+        Ok(form)
+    }
+
+    /// Generator the VDF starts from, `(2, 1, c)` reduced
+    pub fn generator(discriminant: &BigInt) -> VerifyResult<Self> {
+        Self::from_ab(discriminant, BigInt::from(2), BigInt::one())
+    }
+
+    /// Read a form as the tick library writes it
+    ///
+    /// # Errors
+    ///
+    /// [`VerifyError::Malformed`] if a coefficient isn't an integer, `a`
+    /// isn't positive, or the form isn't of `discriminant`
+    pub fn parse(discriminant: &BigInt, form: &(String, String, String)) -> VerifyResult<Self> {
+        let form = Self {
+            a: parse_integer("Form a", &form.0)?,
+            b: parse_integer("Form b", &form.1)?,
+            c: parse_integer("Form c", &form.2)?,
+        };
+        if !form.a.is_positive() {
+            return Err(VerifyError::malformed("Form a must be positive"));
+        }
+        if form.discriminant() != *discriminant {
+            return Err(VerifyError::malformed("Form does not match the discriminant"));
+        }
+        Ok(form)
+    }
+
+    /// The coefficients as the tick library writes them
+    pub fn to_strings(&self) -> (String, String, String) {
+        (format_integer(&self.a), format_integer(&self.b), format_integer(&self.c))
+    }
+
+    /// `b² - 4ac`
+    pub fn discriminant(&self) -> BigInt {
+        &self.b * &self.b - ((&self.a * &self.c) << 2)
+    }
+
+    /// Whether this is the reduced form of its class
+    pub fn is_reduced(&self) -> bool {
+        let b_abs = self.b.abs();
+        b_abs <= self.a
+            && self.a <= self.c
+            && !(self.b.is_negative() && (b_abs == self.a || self.a == self.c))
+    }
+
+    /// Move `b` into `(-a, a]` without changing the class
+    fn normalize(&mut self) {
+        let two_a: BigInt = &self.a << 1;
+        if -&self.a < self.b && self.b <= self.a {
+            return;
+        }
+        let r = (&self.a - &self.b).div_floor(&two_a);
+        // (a, b + 2ra, ar² + br + c)
+        self.c = &self.a * &r * &r + &self.b * &r + &self.c;
+        self.b += &two_a * &r;
+    }
+
+    /// Replace the form with the reduced form of its class
+    pub fn reduce(&mut self) {
+        self.normalize();
+        while self.a > self.c || (self.a == self.c && self.b.is_negative()) {
+            std::mem::swap(&mut self.a, &mut self.c);
+            self.b = -&self.b;
+            self.normalize();
+        }
+    }
+
+    /// Reduced composition of two forms of the same discriminant
+    ///
+    /// Cohen, *A Course in Computational Algebraic Number Theory*,
+    /// algorithm 5.4.7.
+    pub fn compose(&self, other: &Form) -> Form {
+        let (f1, f2) = if self.a > other.a { (other, self) } else { (self, other) };
+        let s: BigInt = (&f1.b + &f2.b) >> 1;
+        let n = &f2.b - &s;
+
+        let (y1, d) = if f2.a.is_multiple_of(&f1.a) {
+            (BigInt::zero(), f1.a.clone())
+        } else {
+            let gcd = f2.a.extended_gcd(&f1.a);
+            (gcd.x, gcd.gcd)
+        };
+        let (x2, y2, d1) = if s.is_multiple_of(&d) {
+            (BigInt::zero(), -BigInt::one(), d)
+        } else {
+            let gcd = s.extended_gcd(&d);
+            (gcd.x, -gcd.y, gcd.gcd)
+        };
+
+        let v1 = &f1.a / &d1;
+        let v2 = &f2.a / &d1;
+        let r = (&y1 * &y2 * &n - &x2 * &f2.c).mod_floor(&v1);
+        let b3 = &f2.b + ((&v2 * &r) << 1);
+        let c3 = (&f2.c * &d1 + &r * (&f2.b + &v2 * &r)) / &v1;
+        let mut form = Form {
+            a: v1 * v2,
+            b: b3,
+            c: c3,
+        };
+        form.reduce();
+        form
+    }
+
+    /// Reduced square of the form, one VDF iteration
+    pub fn square(&self) -> Form {
+        self.compose(self)
+    }
+}
+
+/// Read an integer the way the tick library writes it
+///
+/// Forms are written as hex with a `0x` prefix, discriminants in decimal.
+/// Both may be negative.
+///
+/// # Errors
+///
+/// [`VerifyError::Malformed`] naming `name` if `value` isn't such an
+/// integer
+pub fn parse_integer(name: &str, value: &str) -> VerifyResult<BigInt> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let parsed = match digits.strip_prefix("0x") {
+        Some(hex) if !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            BigInt::parse_bytes(hex.as_bytes(), 16)
+        }
+        // The tick library would read a leading zero as octal
+        Some(_) => None,
+        None if digits == "0" => Some(BigInt::zero()),
+        None if digits.starts_with('0') => None,
+        None if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+            BigInt::parse_bytes(digits.as_bytes(), 10)
+        }
+        None => None,
+    };
+    let parsed =
+        parsed.ok_or_else(|| VerifyError::malformed(format!("{} is not an integer", name)))?;
+    Ok(if negative { -parsed } else { parsed })
+}
+
+/// Write a form coefficient as the tick library does
+pub fn format_integer(value: &BigInt) -> String {
+    let sign = if value.is_negative() { "-" } else { "" };
+    format!("{}0x{}", sign, value.magnitude().to_str_radix(16))
+}
+
+/// Read a discriminant the VDF can run over
+///
+/// # Errors
+///
+/// [`VerifyError::Malformed`] unless it's a negative integer congruent to
+/// 1 mod 4
+pub fn parse_discriminant(discriminant: &str) -> VerifyResult<BigInt> {
+    let d = parse_integer("Discriminant", discriminant)?;
+    if !d.is_negative() {
+        return Err(VerifyError::malformed("Discriminant must be negative"));
+    }
+    if d.mod_floor(&BigInt::from(4)) != BigInt::one() {
+        return Err(VerifyError::malformed("Discriminant must be 1 mod 4"));
+    }
+    Ok(d)
+}
+
+/// Check that squaring `start` `iterations` times yields `end`
+///
+/// Recomputes the segment like `kala_vdf::verify_form_transition`, and,
+/// like it, compares the result with `end` as written, so only the
+/// coefficients the tick library would write verify.
+pub fn verify_form_transition(
+    discriminant: &str,
+    start: &(String, String, String),
+    iterations: u64,
+    end: &(String, String, String),
+) -> bool {
+    let Ok(d) = parse_discriminant(discriminant) else {
+        return false;
+    };
+    let Ok(mut form) = Form::parse(&d, start) else {
+        return false;
+    };
+    for _ in 0..iterations {
+        form = form.square();
+    }
+    form.to_strings() == *end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+
+    #[test]
+    fn test_square_keeps_forms_reduced() {
+        let d = parse_discriminant(DISCRIMINANT).unwrap();
+        let mut form = Form::generator(&d).unwrap();
+        assert_eq!(form.to_strings().0, "0x2");
+        for _ in 0..64 {
+            form = form.square();
+            assert!(form.is_reduced());
+            assert_eq!(form.discriminant(), d);
+        }
+
+        // Squaring twice is composing the square with itself
+        let square = form.square();
+        assert_eq!(square.square(), square.compose(&square));
+        assert_eq!(form.compose(&square), square.compose(&form));
+    }
+
+    #[test]
+    fn test_small_class_group() {
+        // The class group of -23 has order 3
+        let d = BigInt::from(-23);
+        let generator = Form::generator(&d).unwrap();
+        let identity = Form::from_ab(&d, BigInt::one(), BigInt::one()).unwrap();
+        let cube = generator.square().compose(&generator);
+        assert_eq!(cube, identity);
+        assert_eq!(identity.to_strings(), ("0x1".into(), "0x1".into(), "0x6".into()));
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_integer("x", "-0x1f").unwrap(), BigInt::from(-31));
+        assert_eq!(parse_integer("x", "23").unwrap(), BigInt::from(23));
+        assert!(parse_integer("x", "017").is_err());
+        assert!(parse_integer("x", "0x").is_err());
+        assert!(parse_discriminant("23").is_err());
+        assert!(parse_discriminant("-21").is_err());
+        assert_eq!(format_integer(&BigInt::from(-31)), "-0x1f");
+    }
+
+    #[test]
+    fn test_verify_form_transition() {
+        let d = parse_discriminant(DISCRIMINANT).unwrap();
+        let start = Form::generator(&d).unwrap();
+        let end = start.square().square().square();
+        let (start, end) = (start.to_strings(), end.to_strings());
+
+        assert!(verify_form_transition(DISCRIMINANT, &start, 3, &end));
+        assert!(!verify_form_transition(DISCRIMINANT, &start, 2, &end));
+        assert!(!verify_form_transition("-23", &start, 3, &end));
+    }
+}
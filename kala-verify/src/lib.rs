@@ -0,0 +1,73 @@
+//! # Kala Verify
+//!
+//! Verification of the Kala timeline that runs anywhere Rust compiles,
+//! including wasm32 in browsers. A node verifies ticks with the tick
+//! library, which squares class group forms with GMP through C++; this
+//! crate does the same squarings with `num-bigint`, so a web page can check
+//! what a node serves without trusting it.
+//!
+//! ## What It Verifies
+//!
+//! - **Tick certificates** ([`tick`]): the hash, the link to the previous
+//!   tick, the iteration count, and the VDF segment, as
+//!   `kala_state::verify_tick_certificate` does
+//! - **Tick range proofs** ([`range`]): the accumulator inclusion of each
+//!   certificate, the sampled ticks, and each sampled segment, as
+//!   `kala_state::TickRangeProof::verify` does
+//!
+//! Certificates don't carry a succinct (Pietrzak or Wesolowski) proof of
+//! their segment yet, so a segment is verified by recomputing it, which
+//! takes as many squarings as producing the tick did. Range proofs keep the
+//! number of recomputed segments logarithmic in the range's length.
+//!
+//! The types deserialize from the JSON the RPC API serves, such as the
+//! results of `kala_getTick` and `kala_getTickRangeProof`.
+//!
+//! ## JavaScript
+//!
+//! With the `wasm` feature, [`wasm`] exports the verifiers through
+//! `wasm-bindgen`, taking certificates and proofs as JSON strings.
+
+#![warn(missing_docs)]
+
+pub mod classgroup;
+pub mod mmr;
+pub mod range;
+pub mod tick;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use classgroup::{verify_form_transition, Form};
+pub use mmr::MmrProof;
+pub use range::{sample_ticks, ProvenTick, SampledSegment, TickRangeProof};
+pub use tick::{verify_tick_certificate, ModuleUsage, TickCertificate, TickType};
+
+/// Result of a verification
+pub type VerifyResult<T> = Result<T, VerifyError>;
+
+/// Why verification failed
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// Input that can't be read, such as a malformed form coefficient
+    #[error("Malformed input: {0}")]
+    Malformed(String),
+
+    /// Well-formed input that doesn't verify
+    #[error("Verification failed: {0}")]
+    Invalid(String),
+}
+
+impl VerifyError {
+    /// Create a new malformed input error
+    pub fn malformed(msg: impl Into<String>) -> Self {
+        Self::Malformed(msg.into())
+    }
+
+    /// Create a new failed verification error
+    pub fn invalid(msg: impl Into<String>) -> Self {
+        Self::Invalid(msg.into())
+    }
+}
+
+/// 32-byte SHA-256 hash
+pub type Hash = [u8; 32];
@@ -0,0 +1,160 @@
+//! Inclusion proofs of the tick accumulator
+//!
+//! Nodes commit every tick hash to a Merkle Mountain Range, and signed epoch
+//! summaries carry its root. [`MmrProof`] checks a leaf against that root
+//! exactly as `kala_common::mmr::MmrProof` does; nodes are addressed by
+//! their 0-based position in post-order.
+
+use crate::Hash;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Inclusion proof for a single leaf of a Merkle Mountain Range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MmrProof {
+    /// Index of the proven leaf
+    pub leaf_index: u64,
+    /// Leaf count of the accumulator the proof was generated against
+    pub leaf_count: u64,
+    /// Sibling hashes from the leaf up to its mountain peak
+    pub siblings: Vec<Hash>,
+    /// Hashes of the other peaks, left to right
+    pub peaks: Vec<Hash>,
+}
+
+impl MmrProof {
+    /// Verify that `leaf` is included under `root`
+    pub fn verify(&self, leaf: &Hash, root: &Hash) -> bool {
+        if self.leaf_index >= self.leaf_count {
+            return false;
+        }
+
+        let peak_positions = peak_positions(self.leaf_count);
+        if self.peaks.len() + 1 != peak_positions.len() {
+            return false;
+        }
+
+        let mut pos = leaf_position(self.leaf_index);
+        let mut height = 0;
+        let mut current = *leaf;
+        let mut siblings = self.siblings.iter();
+
+        while !peak_positions.contains(&pos) {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            let (sibling_pos, parent) = sibling_and_parent(pos, height);
+            current = if sibling_pos < pos {
+                hash_pair(sibling, &current)
+            } else {
+                hash_pair(&current, sibling)
+            };
+            pos = parent;
+            height += 1;
+        }
+
+        if siblings.next().is_some() {
+            return false;
+        }
+
+        let Some(peak_index) = peak_positions.iter().position(|&peak| peak == pos) else {
+            return false;
+        };
+        let mut peaks = self.peaks.clone();
+        peaks.insert(peak_index, current);
+
+        bag_peaks(self.leaf_count, &peaks) == *root
+    }
+}
+
+fn hash_pair(left: &[u8], right: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Node position of the leaf at `leaf_index`
+fn leaf_position(leaf_index: u64) -> u64 {
+    2 * leaf_index - leaf_index.count_ones() as u64
+}
+
+/// Positions of the mountain peaks, left to right
+fn peak_positions(leaf_count: u64) -> Vec<u64> {
+    let mut peaks = Vec::new();
+    let mut offset = 0;
+
+    for height in (0..64).rev() {
+        if leaf_count & (1 << height) != 0 {
+            let mountain_size = (2u64 << height) - 1;
+            peaks.push(offset + mountain_size - 1);
+            offset += mountain_size;
+        }
+    }
+
+    peaks
+}
+
+/// Combine peaks right to left, binding the leaf count into the root
+fn bag_peaks(leaf_count: u64, peaks: &[Hash]) -> Hash {
+    let Some((last, rest)) = peaks.split_last() else {
+        return [0u8; 32];
+    };
+
+    let bagged = rest.iter().rev().fold(*last, |acc, peak| hash_pair(peak, &acc));
+
+    hash_pair(&leaf_count.to_le_bytes(), &bagged)
+}
+
+/// Height of the node at `pos` (leaves have height 0)
+fn pos_height(pos: u64) -> u32 {
+    let mut pos = pos + 1;
+    // Jump left across whole mountains until pos is a run of 1 bits
+    while pos.count_ones() != 64 - pos.leading_zeros() {
+        let msb = 1u64 << (63 - pos.leading_zeros());
+        pos -= msb - 1;
+    }
+    63 - pos.leading_zeros()
+}
+
+/// Sibling and parent positions of a non-peak node at `pos`
+fn sibling_and_parent(pos: u64, height: u32) -> (u64, u64) {
+    let offset = (2u64 << height) - 1;
+    if pos_height(pos + 1) > height {
+        // Right child: the parent follows immediately
+        (pos - offset, pos + 1)
+    } else {
+        (pos + offset, pos + offset + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_leaf_proof() {
+        // Leaves 0 and 1 form a mountain, leaf 2 stands alone
+        let leaves: Vec<Hash> = (0u8..3).map(|i| [i; 32]).collect();
+        let mountain = hash_pair(&leaves[0], &leaves[1]);
+        let root = bag_peaks(3, &[mountain, leaves[2]]);
+
+        let proof = MmrProof {
+            leaf_index: 1,
+            leaf_count: 3,
+            siblings: vec![leaves[0]],
+            peaks: vec![leaves[2]],
+        };
+        assert!(proof.verify(&leaves[1], &root));
+        assert!(!proof.verify(&leaves[0], &root));
+        assert!(!MmrProof { leaf_count: 4, ..proof.clone() }.verify(&leaves[1], &root));
+
+        let single = MmrProof {
+            leaf_index: 2,
+            leaf_count: 3,
+            siblings: vec![],
+            peaks: vec![mountain],
+        };
+        assert!(single.verify(&leaves[2], &root));
+    }
+}
@@ -0,0 +1,174 @@
+//! Compact proofs of a range of ticks
+//!
+//! A [`TickRangeProof`] carries a range's first and last certificates and a
+//! logarithmic number of sampled segments, each a certificate with its
+//! predecessor, every certificate with an inclusion proof against the tick
+//! accumulator root after the range's last tick. The sampled ticks are
+//! derived from that root, so a prover can't choose to show only its honest
+//! segments. See `kala_state::range_proof` for the construction.
+
+use crate::mmr::MmrProof;
+use crate::tick::{verify_tick_certificate, TickCertificate};
+use crate::{Hash, VerifyError, VerifyResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+
+/// Segments sampled per doubling of the range's length
+pub const SAMPLES_PER_LEVEL: u64 = 8;
+
+/// A certificate and its inclusion in the tick accumulator
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProvenTick {
+    /// Certificate of the tick
+    pub certificate: TickCertificate,
+    /// Inclusion of the certificate's hash under the accumulator root
+    pub proof: MmrProof,
+}
+
+impl ProvenTick {
+    fn verify(&self, mmr_root: &Hash, leaf_count: u64) -> VerifyResult<()> {
+        let tick = self.certificate.tick_number;
+        if self.proof.leaf_index != tick
+            || self.proof.leaf_count != leaf_count
+            || !self.proof.verify(&self.certificate.tick_hash, mmr_root)
+        {
+            return Err(VerifyError::invalid(format!(
+                "Tick {} is not committed to the accumulator",
+                tick
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A sampled VDF segment: a tick and the one before it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SampledSegment {
+    /// The tick before the sampled one, where the segment starts
+    pub previous: ProvenTick,
+    /// The sampled tick, where the segment ends
+    pub tick: ProvenTick,
+}
+
+/// Ticks `start..=end`, proven by their endpoints and sampled segments
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TickRangeProof {
+    /// First tick of the range
+    pub first: ProvenTick,
+    /// Last tick of the range
+    pub last: ProvenTick,
+    /// Segments at [`sample_ticks`], in tick order
+    pub segments: Vec<SampledSegment>,
+}
+
+/// Ticks whose segments a proof of `start..=end` samples
+///
+/// Draws `SAMPLES_PER_LEVEL * log2(n)` ticks, for `n` ticks after `start`,
+/// from a hash of the accumulator root; repeats are drawn once. Every tick
+/// is taken once the range is too short to sample.
+pub fn sample_ticks(mmr_root: &Hash, start: u64, end: u64) -> Vec<u64> {
+    let span = end.saturating_sub(start);
+    let draws = SAMPLES_PER_LEVEL * u64::from(u64::BITS - span.leading_zeros());
+    if draws >= span {
+        return (start + 1..=end).collect();
+    }
+
+    let mut ticks = BTreeSet::new();
+    for draw in 0..draws {
+        let mut hasher = Sha256::new();
+        hasher.update(b"kala-tick-sample");
+        hasher.update(mmr_root);
+        hasher.update(start.to_le_bytes());
+        hasher.update(end.to_le_bytes());
+        hasher.update(draw.to_le_bytes());
+        let seed: Hash = hasher.finalize().into();
+        let value = u64::from_le_bytes(seed[..8].try_into().expect("hash holds 8 bytes"));
+        ticks.insert(start + 1 + value % span);
+    }
+    ticks.into_iter().collect()
+}
+
+impl TickRangeProof {
+    /// First tick of the range
+    pub fn start(&self) -> u64 {
+        self.first.certificate.tick_number
+    }
+
+    /// Last tick of the range, the last one committed under the root
+    pub fn end(&self) -> u64 {
+        self.last.certificate.tick_number
+    }
+
+    /// Check the range against `mmr_root`, the tick accumulator root after
+    /// its last tick
+    ///
+    /// Every certificate must be committed under the root, the sampled
+    /// ticks must be those the root selects, and each sampled segment must
+    /// verify as in [`verify_tick_certificate`]. A range starting at
+    /// genesis also has its first tick checked from the generator.
+    ///
+    /// # Errors
+    ///
+    /// [`VerifyError::Invalid`] naming the first check that fails, or
+    /// [`VerifyError::Malformed`] for a discriminant that can't define the
+    /// class group
+    pub fn verify(
+        &self,
+        mmr_root: &Hash,
+        iterations_per_tick: u64,
+        discriminant: &str,
+    ) -> VerifyResult<()> {
+        let (start, end) = (self.start(), self.end());
+        let leaf_count = end
+            .checked_add(1)
+            .ok_or_else(|| VerifyError::invalid("Range ends past the last tick"))?;
+        self.first.verify(mmr_root, leaf_count)?;
+        self.last.verify(mmr_root, leaf_count)?;
+
+        let expected = sample_ticks(mmr_root, start, end);
+        if expected.len() != self.segments.len()
+            || expected
+                .iter()
+                .zip(&self.segments)
+                .any(|(tick, segment)| segment.tick.certificate.tick_number != *tick)
+        {
+            return Err(VerifyError::invalid("Proof doesn't carry the sampled ticks"));
+        }
+
+        if start == 0 {
+            let first = &self.first.certificate;
+            verify_tick_certificate(first, None, iterations_per_tick, discriminant)?;
+        }
+        for segment in &self.segments {
+            segment.previous.verify(mmr_root, leaf_count)?;
+            segment.tick.verify(mmr_root, leaf_count)?;
+            verify_tick_certificate(
+                &segment.tick.certificate,
+                Some(&segment.previous.certificate),
+                iterations_per_tick,
+                discriminant,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_ticks() {
+        let root = [7u8; 32];
+        // Short ranges take every segment
+        assert_eq!(sample_ticks(&root, 90, 99), (91..=99).collect::<Vec<_>>());
+        assert!(sample_ticks(&root, 5, 5).is_empty());
+
+        let sampled = sample_ticks(&root, 0, 9_999);
+        assert!(sampled.len() <= (SAMPLES_PER_LEVEL * 14) as usize);
+        assert!(sampled.iter().all(|tick| (1..=9_999).contains(tick)));
+        assert_eq!(sampled, sample_ticks(&root, 0, 9_999));
+        assert_ne!(sampled, sample_ticks(&[8u8; 32], 0, 9_999));
+    }
+}
@@ -0,0 +1,67 @@
+//! JavaScript bindings of the verifiers
+//!
+//! Certificates and proofs are passed as the JSON strings the RPC API
+//! returns, so a page can hand a response's `result` to the verifier
+//! unchanged. A verifier returns nothing when the input verifies and throws
+//! an `Error` naming the failed check otherwise.
+//!
+//! ```js
+//! import init, { verifyTickCertificate } from "./pkg/kala_verify.js";
+//!
+//! await init();
+//! verifyTickCertificate(JSON.stringify(tick), JSON.stringify(previous),
+//!     BigInt(iterationsPerTick), discriminant);
+//! ```
+
+use crate::{Hash, TickCertificate, TickRangeProof, VerifyError};
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+
+/// Verify that a tick certificate extends the certificate before it
+///
+/// `previous` may be omitted only for the first tick. See
+/// [`crate::verify_tick_certificate`].
+#[wasm_bindgen(js_name = verifyTickCertificate)]
+pub fn verify_tick_certificate(
+    certificate: &str,
+    previous: Option<String>,
+    iterations_per_tick: u64,
+    discriminant: &str,
+) -> Result<(), JsError> {
+    let certificate: TickCertificate = parse_json("certificate", certificate)?;
+    let previous = previous
+        .map(|previous| parse_json::<TickCertificate>("previous certificate", &previous))
+        .transpose()?;
+    crate::verify_tick_certificate(
+        &certificate,
+        previous.as_ref(),
+        iterations_per_tick,
+        discriminant,
+    )?;
+    Ok(())
+}
+
+/// Verify a proof of a range of ticks against a hex accumulator root
+///
+/// `mmrRoot` is the `mmr_root` of the signed epoch summary ending at the
+/// range's last tick. See [`TickRangeProof::verify`].
+#[wasm_bindgen(js_name = verifyTickRangeProof)]
+pub fn verify_tick_range_proof(
+    proof: &str,
+    mmr_root: &str,
+    iterations_per_tick: u64,
+    discriminant: &str,
+) -> Result<(), JsError> {
+    let proof: TickRangeProof = parse_json("range proof", proof)?;
+    let mmr_root: Hash = hex::decode(mmr_root.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| VerifyError::malformed("Accumulator root must be 32 hex bytes"))?;
+    proof.verify(&mmr_root, iterations_per_tick, discriminant)?;
+    Ok(())
+}
+
+fn parse_json<T: DeserializeOwned>(name: &str, json: &str) -> Result<T, VerifyError> {
+    serde_json::from_str(json)
+        .map_err(|e| VerifyError::malformed(format!("Invalid {} JSON: {}", name, e)))
+}
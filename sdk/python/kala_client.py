@@ -0,0 +1,1269 @@
+# Kala JSON-RPC client, generated from the OpenRPC document of kala-rpc 0.0.2.
+# Do not edit: run `cargo run -p kala-rpc-codegen` to regenerate.
+"""Typed client of the Kala JSON-RPC API"""
+
+from __future__ import annotations
+
+import json
+import urllib.error
+import urllib.request
+from typing import (
+    Any,
+    Dict,
+    List,
+    Literal,
+    NotRequired,
+    Optional,
+    Tuple,
+    TypedDict,
+    Union,
+)
+
+
+class DenomMetadata(TypedDict):
+    """Display metadata of a denomination from the node's registry
+
+    Amounts on chain are integers of base units; `decimals` says how many
+    of their digits are fractional in the display unit named by `symbol`.
+    """
+
+    # Number of decimal places in the display unit
+    decimals: int
+
+    # Denomination ID (hex-encoded)
+    denom: str
+
+    # Human-readable name
+    name: str
+
+    # Ticker symbol of the display unit, e.g. "KALA"
+    symbol: str
+
+
+class AccountInfo(TypedDict):
+    """Account state information
+
+    Contains the complete state of an account including balances,
+    transaction history, and staking status. Amounts are given in base
+    units and, for display, formatted with the denomination's decimals.
+    """
+
+    # Account balance in base units
+    balance: int
+
+    # Balance in display units, e.g. "12.5"
+    balance_display: str
+
+    # Optional delegation target (hex-encoded address)
+    delegation: NotRequired[Optional[str]]
+
+    # Denomination the balances are held in
+    denom: DenomMetadata
+
+    # Transaction nonce (prevents replay attacks)
+    nonce: int
+
+    # Nonces of decrypted transactions held until the nonces before them
+    # are applied
+    pending_nonces: List[int]
+
+    # Amount currently staked by this account
+    staked_amount: int
+
+    # Staked amount in display units
+    staked_display: str
+
+
+class AccountUpdate(TypedDict):
+    """Account state pushed to `kala_watchAccount` subscribers
+
+    Sent after every tick that changed the account, with the hashes of the
+    transactions that changed it.
+    """
+
+    # Account address (hex-encoded)
+    address: str
+
+    # Account balance in base units after the tick
+    balance: int
+
+    # Optional delegation target (hex-encoded address)
+    delegation: NotRequired[Optional[str]]
+
+    # Transaction nonce after the tick
+    nonce: int
+
+    # Amount staked after the tick
+    staked_amount: int
+
+    # Tick whose transactions changed the account
+    tick_number: int
+
+    # Hex-encoded hashes of the transactions that changed the account
+    tx_hashes: List[str]
+
+
+class VdfCalibration(TypedDict):
+    """Measured VDF speed of a node against the tick duration target"""
+
+    # VDF iterations per second measured by the benchmark
+    iterations_per_second: float
+
+    # Whether the node computes ticks at least as fast as the target
+    keeps_pace: bool
+
+    # Unix time of the measurement, in seconds
+    measured_at: int
+
+    # Tick duration the network targets, in milliseconds
+    target_tick_duration_ms: int
+
+    # Tick duration expected at the measured rate, in milliseconds
+    tick_duration_ms: int
+
+
+class VdfTuning(TypedDict):
+    """Configuration a node's VDF library runs with"""
+
+    # Whether the fast squaring path may use AVX-512 IFMA
+    avx512: bool
+
+    # Whether the fast squaring path may use its assembly
+    fast_mode: bool
+
+    # Threads recomputing VDF segments during verification
+    num_threads: int
+
+
+class ChainInfo(TypedDict):
+    """Current blockchain and VDF state information
+
+    This structure contains a comprehensive snapshot of the current
+    blockchain state, including VDF progress, transaction statistics,
+    and network health indicators.
+    """
+
+    # Number of accounts with non-zero state
+    accounts: int
+
+    # Current VDF iteration number within the eternal computation
+    current_iteration: int
+
+    # Current tick number (block height equivalent)
+    current_tick: int
+
+    # Current VDF hash chain value (hex-encoded)
+    hash_chain: str
+
+    # Total number of transactions processed across all ticks
+    total_transactions: int
+
+    # Latest measurement of the node's VDF speed, if calibration is enabled
+    vdf_calibration: NotRequired[Optional[VdfCalibration]]
+
+    # Current VDF output as a formatted string (a, b, c form values)
+    vdf_output: str
+
+    # How the node's VDF library uses its machine
+    vdf_tuning: NotRequired[Optional[VdfTuning]]
+
+
+class FeatureStatus(TypedDict):
+    """Activation status of a protocol feature"""
+
+    # Tick the feature activates at
+    activation_tick: int
+
+    # Whether the feature is active at the head of the chain
+    active: bool
+
+    # Feature name, e.g. "fork_choice"
+    name: str
+
+
+class HardnessBounds(TypedDict):
+    """Timelock puzzle limits for transactions targeting a tick
+
+    A puzzle must decrypt after the target tick's collection phase and
+    before the tick ends.
+    """
+
+    # Offset into the target tick until which transactions are accepted
+    acceptance_window_end: int
+
+    # Offset into the tick before the target from which transactions are accepted
+    acceptance_window_start: int
+
+    # Largest fraction of a tick a puzzle may take to solve
+    hardness_factor: float
+
+    # Largest recommended hardness in iterations
+    max_hardness: int
+
+    # Smallest accepted hardness in iterations
+    min_hardness: int
+
+
+class TickPhases(TypedDict):
+    """Iteration offsets, from the start of a tick, at which its phases end"""
+
+    # End of collection; the transaction order is fixed here (k/3)
+    collection_end: int
+
+    # End of puzzle decryption (2k/3)
+    decryption_end: int
+
+    # End of validation and of the tick (k)
+    validation_end: int
+
+
+class ChainParams(TypedDict):
+    """Protocol parameters of the chain a node runs
+
+    SDKs configure themselves from these values instead of compile-time
+    constants, so they keep working across parameter changes and upgrades.
+    """
+
+    # Chain ID transactions are signed for
+    chain_id: str
+
+    # Class group discriminant of the VDF (decimal)
+    discriminant: str
+
+    # Ticks per epoch
+    epoch_length: int
+
+    # Protocol features and whether they are active at the head
+    features: List[FeatureStatus]
+
+    # Limits on timelock puzzles of submitted transactions
+    hardness: HardnessBounds
+
+    # VDF iterations per tick (k)
+    iterations_per_tick: int
+
+    # Iteration offsets of the phases within a tick
+    phases: TickPhases
+
+    # Network protocol version
+    protocol_version: int
+
+
+class ModuleUsage(TypedDict):
+    """Resources used, by module"""
+
+    # Gas metered by contract calls, zero while no module runs contracts
+    contract_gas: NotRequired[int]
+
+    # Native transactions applied, by module name
+    native_transactions: NotRequired[Dict[str, int]]
+
+
+class EpochStats(TypedDict):
+    """Aggregate statistics over the ticks of an epoch"""
+
+    checkpoint_ticks: int
+
+    empty_ticks: int
+
+    end_iteration: int
+
+    end_timestamp: int
+
+    full_ticks: int
+
+    # Oracle feed values finalized over the epoch
+    #
+    # Not covered by the summary hash: each node medianizes its own
+    # finality records, which may hold different votes, so nodes can
+    # count differently.
+    oracle_updates: NotRequired[int]
+
+    start_iteration: int
+
+    start_timestamp: int
+
+    tick_count: int
+
+    transaction_count: int
+
+    # Usage of the epoch's ticks, added up
+    usage: NotRequired[ModuleUsage]
+
+
+class EpochSummary(TypedDict):
+    """Commitment to a run of `epoch_length` ticks"""
+
+    # Last tick of the epoch, inclusive
+    end_tick: int
+
+    end_tick_hash: List[int]
+
+    epoch_number: int
+
+    # Root of the tick MMR after `end_tick` was appended
+    mmr_root: List[int]
+
+    # Hash of the previous epoch's summary, zero for epoch 0
+    previous_epoch_hash: List[int]
+
+    start_tick: int
+
+    state_root: List[int]
+
+    stats: EpochStats
+
+    # Ed25519 public keys of the witnesses, sorted
+    witnesses: List[List[int]]
+
+
+class FeedValue(TypedDict):
+    """Median of the values reported for a feed at one tick"""
+
+    feed: List[int]
+
+    # Number of witnesses that reported the feed
+    reports: int
+
+    value: int
+
+
+class FfiAllocations(TypedDict):
+    """Allocations of one kind of object crossing into a native library"""
+
+    allocations: int
+
+    frees: int
+
+    # Library the objects cross into, `tick` or `timelocks`
+    library: str
+
+    # Objects allocated and not yet freed
+    live: int
+
+    # Bytes allocated and not yet freed, where the size is known
+    live_bytes: int
+
+    # Kind of object, e.g. `forms` or `c_strings`
+    resource: str
+
+
+class FfiMemoryReport(TypedDict):
+    """Memory a node holds across its native library boundaries
+
+    Allocation counts are kept since the node started. A live count that
+    keeps growing while the node runs points at a leak.
+    """
+
+    allocations: List[FfiAllocations]
+
+    # Kinds of object freed more often than allocated, `library/resource`
+    over_freed: List[str]
+
+    # Bytes of the certificates' forms, hashes and proofs
+    vdf_certificate_bytes: int
+
+    # Tick certificates the VDF keeps, all of which a full checkpoint copies
+    vdf_certificates: int
+
+
+class GetAccountRequest(TypedDict):
+    """Request to retrieve account information
+
+    Queries the current state of a specific account, including
+    balance, nonce, and staking information.
+    """
+
+    # Account address as a hex-encoded public key (64 characters)
+    address: str
+
+
+class GetEpochRequest(TypedDict):
+    """Request to retrieve an epoch summary
+
+    Epoch summaries commit to every tick in the epoch and are signed by
+    the witnesses, letting light clients follow the timeline
+    epoch-to-epoch instead of tick-by-tick.
+    """
+
+    # The epoch number to retrieve, or `None` for the latest sealed epoch
+    epoch_number: NotRequired[Optional[int]]
+
+
+# Request to retrieve persisted metrics samples
+#
+# Both bounds are Unix timestamps in seconds and inclusive. The node
+# keeps roughly a day of samples.
+GetMetricsHistoryRequest = TypedDict(
+    "GetMetricsHistoryRequest",
+    {
+        # Earliest sample time, or `None` for the oldest sample kept
+        "from": NotRequired[Optional[int]],
+        # Latest sample time, or `None` for the newest sample
+        "to": NotRequired[Optional[int]],
+    },
+)
+
+
+class GetOracleValueRequest(TypedDict):
+    """Request to retrieve an oracle feed's value at a tick"""
+
+    # Feed ID as a hex-encoded 32-byte hash (64 characters)
+    feed: str
+
+    # The finalized tick to read the value at
+    tick_number: int
+
+
+class GetTickDataRequest(TypedDict):
+    """Request to retrieve data timestamped in a tick"""
+
+    # Position of the data among the data timestamped in the tick
+    leaf_index: int
+
+    # The finalized tick the data was timestamped in
+    tick_number: int
+
+
+class GetTickRangeProofRequest(TypedDict):
+    """Request to prove a range of ticks
+
+    Proofs are against the tick accumulator root after `end_tick`, so
+    light clients usually end the range at an epoch summary's `end_tick`.
+    """
+
+    # Last tick of the range, inclusive
+    end_tick: int
+
+    # First tick of the range
+    start_tick: int
+
+
+class GetTickRequest(TypedDict):
+    """Request to retrieve a specific tick certificate
+
+    Used to query historical tick information including transaction
+    processing results and VDF state at tick completion.
+    """
+
+    # The tick number to retrieve
+    tick_number: int
+
+
+class MempoolEntry(TypedDict):
+    """A transaction waiting in the node's mempool"""
+
+    # Size of the sealed payload, in bytes
+    ciphertext_bytes: int
+
+    # How the key is locked, `puzzle` or `threshold`
+    lock: str
+
+    # VDF iteration the node timestamped the transaction at
+    submission_iteration: int
+
+    # Tick the transaction is ordered into
+    target_tick: int
+
+    # Hex-encoded envelope hash
+    tx_hash: str
+
+
+class MetricsSample(TypedDict):
+    """Node performance over the ticks since the previous sample"""
+
+    # VDF iterations per second over those ticks
+    iterations_per_second: float
+
+    # Longest tick duration in milliseconds
+    max_tick_ms: int
+
+    # Mean tick duration in milliseconds
+    mean_tick_ms: int
+
+    # Transactions waiting in the pool
+    mempool_depth: int
+
+    # Latest tick produced when the sample was taken
+    tick_number: int
+
+    # Number of ticks the sample covers
+    ticks: int
+
+    # Unix time the sample was taken, in seconds
+    timestamp: int
+
+
+class MmrProof(TypedDict):
+    """Inclusion proof for a single leaf of a Merkle Mountain Range"""
+
+    # Leaf count of the accumulator the proof was generated against
+    leaf_count: int
+
+    # Index of the proven leaf
+    leaf_index: int
+
+    # Hashes of the other peaks, left to right
+    peaks: List[List[int]]
+
+    # Sibling hashes from the leaf up to its mountain peak
+    siblings: List[List[int]]
+
+
+class NodeVersion(TypedDict):
+    """The build of a node's software
+
+    Builds are reproducible: the same commit, profile, target, flags, and
+    features give the same binary, so operators and auditors can confirm
+    exactly what code produced a tick.
+    """
+
+    # Cargo profile, e.g. `release`
+    build_profile: str
+
+    # Cargo features compiled in
+    features: List[str]
+
+    # Git commit the node was built from, `unknown` outside a checkout
+    git_commit: str
+
+    # Optimization level
+    opt_level: str
+
+    # Network protocol version
+    protocol_version: int
+
+    # Extra flags passed to rustc, space-separated
+    rustflags: str
+
+    # Timelock puzzle solver library
+    solver_library: str
+
+    # Target triple the binary was built for
+    target: str
+
+    # VDF library and the GMP version it links
+    vdf_library: str
+
+    # Version of the node crate
+    version: str
+
+
+class NonceReservation(TypedDict):
+    """Nonces reserved for an account
+
+    No other reservation will hand out nonces in `first_nonce..=last_nonce`.
+    Reserved nonces may be used in any order within a tick's ordering and
+    unused ones are simply skipped, since a transaction only needs a nonce
+    above the account's last applied one.
+    """
+
+    # Nonce of the account's last applied transaction
+    account_nonce: int
+
+    # Account address (hex-encoded)
+    address: str
+
+    # First reserved nonce
+    first_nonce: int
+
+    # Last reserved nonce, inclusive
+    last_nonce: int
+
+
+class OracleProof(TypedDict):
+    """A feed value and the Merkle path committing it to a tick's oracle root, with the finality record the root is medianized from"""
+
+    # Witness signatures finalizing the tick, carrying the signed reports
+    finality: TickFinality
+
+    # Position of the value among the tick's values
+    index: int
+
+    root: List[int]
+
+    siblings: List[List[int]]
+
+    tick_number: int
+
+    value: FeedValue
+
+
+class PeerInfo(TypedDict):
+    """A peer known to the node"""
+
+    # Multiaddrs the peer was reached on or advertised
+    addresses: List[str]
+
+    # Whether the peer is refused and never redialed
+    banned: bool
+
+    # Whether the peer is connected now
+    connected: bool
+
+    # Unix timestamp of the last connection to the peer
+    last_seen: int
+
+    # Base58 libp2p peer ID
+    peer_id: str
+
+    # Accumulated reputation score
+    reputation: int
+
+
+class ProveTickDataRequest(TypedDict):
+    """Request to prove data was timestamped in a tick"""
+
+    # SHA-256 hash of the data as a hex-encoded 32-byte hash (64 characters)
+    data_hash: str
+
+    # The finalized tick the data was timestamped in
+    tick_number: int
+
+
+TickType = Literal["Full", "Empty", "Checkpoint"]
+
+
+class TickCertificate(TypedDict):
+    """Certificate of a finalized tick
+
+    The form is held packed, see [`form_bytes`], and served as hex strings.
+    """
+
+    hash_chain_value: List[int]
+
+    previous_tick_hash: List[int]
+
+    tick_hash: List[int]
+
+    tick_number: int
+
+    tick_type: TickType
+
+    timestamp: int
+
+    transaction_count: int
+
+    transaction_merkle_root: List[int]
+
+    # Resources the tick's transactions used, by module
+    usage: NotRequired[ModuleUsage]
+
+    vdf_form: Tuple[str, str, str]
+
+    vdf_iteration: int
+
+
+class ProvenTick(TypedDict):
+    """A certificate and its inclusion in the tick accumulator"""
+
+    certificate: TickCertificate
+
+    proof: MmrProof
+
+
+class ReserveNoncesRequest(TypedDict):
+    """Request to reserve a contiguous range of nonces for an account
+
+    Must be signed with the account's Ed25519 key over
+    [`ReserveNoncesRequest::signing_message`], so only the key holder can
+    reserve the account's nonces. The signature binds the chain and a
+    request identifier the node accepts once, so a captured request can't
+    be replayed, here or on another network.
+    """
+
+    # Account address as a hex-encoded public key (64 characters)
+    address: str
+
+    # Number of nonces to reserve
+    count: int
+
+    # Client-chosen identifier, accepted once per account while the
+    # request is unexpired
+    request_id: int
+
+    # Unix time in seconds after which the request is rejected
+    expires_at: int
+
+    # Hex-encoded Ed25519 signature by the account key
+    signature: str
+
+
+class SampledSegment(TypedDict):
+    """A sampled VDF segment: a tick and the one before it"""
+
+    previous: ProvenTick
+
+    tick: ProvenTick
+
+
+class WitnessSignature(TypedDict):
+    """A witness's Ed25519 signature over an epoch summary hash"""
+
+    signature: List[int]
+
+    witness: List[int]
+
+
+class SignedEpochSummary(TypedDict):
+    """An epoch summary together with the witness signatures collected for it"""
+
+    signatures: List[WitnessSignature]
+
+    summary: EpochSummary
+
+
+class SnapshotExport(TypedDict):
+    """Snapshot written by `admin_exportSnapshot`"""
+
+    # Directory the snapshot was written to, on the node's machine
+    path: str
+
+    # Hex-encoded state root of the exported state
+    state_root: str
+
+
+class SubmitTransactionRequest(TypedDict):
+    """Request to submit a timelock-encrypted transaction
+
+    Contains the complete timelock transaction data in hex-encoded format.
+    The transaction must be properly encrypted, with its key locked behind
+    an RSW timelock puzzle or shared among the witnesses, and targeted for a
+    future tick.
+    """
+
+    # Hex-encoded timelock-encrypted transaction data
+    #
+    # The [`TimelockTransaction`] in its canonical wire format: the
+    # protobuf encoding of `kala.network.Envelope` from
+    # kala-common/schema/network.proto, as made by
+    # [`SubmitTransactionRequest::new`]. Hex of the envelope's JSON
+    # serialization is still accepted from older clients.
+    encrypted_tx: str
+
+
+class SubmitTransactionResponse(TypedDict):
+    """Response from submitting a timelock transaction
+
+    Contains confirmation details and timing information for the
+    submitted transaction, allowing clients to track its progress
+    through the MEV-resistant processing pipeline.
+    """
+
+    # VDF iteration number when the transaction was timestamped
+    submission_iteration: int
+
+    # Target tick number when the transaction will be processed
+    target_tick: int
+
+    # Identifier tagging the transaction in this node's logs and trace
+    trace_id: str
+
+    # Unique transaction hash for tracking and identification
+    tx_hash: str
+
+
+class SupplyInfo(TypedDict):
+    """Token supply at the head of the chain
+
+    Amounts are given in base units and, for display, formatted with the
+    denomination's decimals.
+    """
+
+    # Denomination the supply is counted in
+    denom: DenomMetadata
+
+    # Base units currently staked
+    staked_supply: int
+
+    # Staked supply in display units
+    staked_supply_display: str
+
+    # Next tick to be produced; the supply includes all ticks before it
+    tick_number: int
+
+    # Base units held by all accounts, staked amounts included
+    total_supply: int
+
+    # Total supply in display units
+    total_supply_display: str
+
+
+# Whether a node is catching up with the network or at its head
+SyncMode = Literal["syncing", "following"]
+
+
+class SyncStatus(TypedDict):
+    """Progress of the tick sync protocol
+
+    A node joining an existing network starts in [`SyncMode::Syncing`],
+    downloads tick certificates and a state snapshot from its peers, and
+    switches to [`SyncMode::Following`] once it has reached their head.
+    """
+
+    # Next tick the node will process or verify
+    current_tick: int
+
+    # Highest next tick reported by any peer
+    highest_tick: int
+
+    # Current sync mode
+    mode: SyncMode
+
+    # Number of connected peers
+    peer_count: int
+
+    # Local tick when syncing started
+    starting_tick: int
+
+
+class TickDataEntry(TypedDict):
+    """Data timestamped in a tick, with its place in the tick's data tree
+
+    Byte fields are hex-encoded.
+    """
+
+    # The timestamped data, or `None` if the node doesn't keep it
+    data: NotRequired[Optional[str]]
+
+    # SHA-256 hash of the data
+    data_hash: str
+
+    # Blob store URI the data was moved to, such as `s3://bucket/key`
+    data_uri: NotRequired[Optional[str]]
+
+    # Hash chain value after the iteration
+    hash_at_timestamp: str
+
+    # VDF iteration the data was hashed into
+    iteration: int
+
+    # Number of data timestamped in the tick
+    leaf_count: int
+
+    # Position of the data among the data timestamped in the tick
+    leaf_index: int
+
+    # Tick the data was timestamped in
+    tick_number: int
+
+
+class WitnessMetadata(TypedDict):
+    """Metadata a signer attached to its vote, carried in a [`TickFinality`]"""
+
+    data: List[int]
+
+    # Ed25519 signature of the witness over [`metadata_message`]
+    signature: List[int]
+
+    witness: List[int]
+
+
+class WitnessSignaturesEd25519(TypedDict):
+    """One signature per signer, in witness order"""
+
+    ed25519: List[List[int]]
+
+
+class WitnessSignaturesBls12381(TypedDict):
+    """Aggregate of every signer's signature, one signature in size"""
+
+    bls12381: List[int]
+
+
+# Signatures of the witnesses in a [`TickFinality`] bitmap
+WitnessSignatures = Union[WitnessSignaturesEd25519, WitnessSignaturesBls12381]
+
+
+class WitnessTime(TypedDict):
+    """Clock reading a signer attached to its vote, carried in a [`TickFinality`]"""
+
+    # Ed25519 signature of the witness over [`time_message`]
+    signature: List[int]
+
+    # Unix time in milliseconds
+    time_ms: int
+
+    witness: List[int]
+
+
+class TickFinality(TypedDict):
+    """Witness signatures finalizing a tick"""
+
+    # Metadata attached by signers, in witness order
+    metadata: NotRequired[List[WitnessMetadata]]
+
+    # Signatures of all signers, in the witness set's scheme
+    signatures: WitnessSignatures
+
+    # Bitmap of signers by index in the witness set
+    signers: List[int]
+
+    tick_hash: List[int]
+
+    tick_number: int
+
+    # Clock readings attached by signers, in witness order
+    times: NotRequired[List[WitnessTime]]
+
+
+class TickProofVerification(TypedDict):
+    """Outcome of verifying a tick certificate"""
+
+    # Why the certificate failed, if it did
+    error: NotRequired[Optional[str]]
+
+    # Tick the certificate is for
+    tick_number: int
+
+    # Whether the hash, linkage, and VDF output all check out
+    valid: bool
+
+
+class TickRangeProof(TypedDict):
+    """Ticks `start..=end`, proven by their endpoints and sampled segments"""
+
+    first: ProvenTick
+
+    last: ProvenTick
+
+    # Segments at [`sample_ticks`], in tick order
+    segments: List[SampledSegment]
+
+
+class TimeAnchor(TypedDict):
+    """Time the witnesses agree an iteration ran at"""
+
+    # Iteration the tick ended at
+    iteration: int
+
+    # Witness weight behind the readings
+    reported_weight: int
+
+    reports: int
+
+    # Tick whose finality record carries the readings
+    tick_number: int
+
+    # Weighted median of the readings, Unix time in milliseconds
+    time_ms: int
+
+
+class TimeBounds(TypedDict):
+    """Range of UTC times an iteration ran within, from the anchors around it"""
+
+    # Latest anchor at or before the iteration
+    earliest: NotRequired[Optional[TimeAnchor]]
+
+    # Finality record carrying the readings of `earliest`
+    earliest_finality: NotRequired[Optional[TickFinality]]
+
+    iteration: int
+
+    # Earliest anchor at or after the iteration
+    latest: NotRequired[Optional[TimeAnchor]]
+
+    # Finality record carrying the readings of `latest`
+    latest_finality: NotRequired[Optional[TickFinality]]
+
+
+class TimelinePoint(TypedDict):
+    """A point on the timeline, estimated by the node's timeline clock"""
+
+    # VDF iteration
+    iteration: int
+
+    # Iterations per second the estimate assumes
+    iterations_per_second: float
+
+    # Unix time in milliseconds at which the iteration runs
+    time_ms: int
+
+
+class TimestampDataRequest(TypedDict):
+    """Request to timestamp data in the VDF"""
+
+    # Hex-encoded data, at most [`MAX_TIMESTAMP_DATA_BYTES`] bytes
+    data: str
+
+
+class TimestampProof(TypedDict):
+    """Proof that data was hashed into the VDF at an iteration
+
+    The data can't have existed later than the iteration, since every
+    later hash chain value depends on it. The Merkle path commits it to the
+    data root of the tick's VDF certificate. Byte fields are hex-encoded.
+    """
+
+    # The timestamped data
+    data: str
+
+    # SHA-256 hash of the data
+    data_hash: str
+
+    # Hash chain value after the iteration
+    hash_at_timestamp: str
+
+    # VDF iteration the data was hashed into
+    iteration: int
+
+    # Position of the data among the data timestamped in the tick
+    leaf_index: int
+
+    # Sibling hashes from the leaf `H(iteration || data_hash)` up to the
+    # tick's data root, where `iteration` is 8 bytes little-endian
+    merkle_path: List[str]
+
+    # Tick the iteration belongs to
+    tick_number: int
+
+
+class TimestampVerification(TypedDict):
+    """Outcome of verifying a timestamp proof"""
+
+    # Why the proof failed, if it did
+    error: NotRequired[Optional[str]]
+
+    # Whether the data was timestamped at the proof's iteration
+    valid: bool
+
+
+# Stage of a transaction's lifecycle on a node
+TraceStage = Literal["submitted", "received", "observed", "ordered", "decrypted", "decryption_failed", "held", "applied", "rejected", "included", "dropped"]
+
+
+class TraceEvent(TypedDict):
+    """One step of a transaction's lifecycle"""
+
+    # Stage-specific details, such as an error or the certificate hash
+    detail: NotRequired[Optional[str]]
+
+    # VDF iteration the event refers to, if any
+    iteration: NotRequired[Optional[int]]
+
+    # What happened
+    stage: TraceStage
+
+    # Tick the event happened in
+    tick_number: int
+
+    # Unix time of the event, in milliseconds
+    timestamp_ms: int
+
+
+class TransactionTrace(TypedDict):
+    """Lifecycle of a transaction as seen by one node
+
+    Traces are kept in memory for the most recent transactions only and do
+    not survive a restart.
+    """
+
+    # Events in the order they happened
+    events: List[TraceEvent]
+
+    # Tick the transaction targets
+    target_tick: int
+
+    # Identifier the node tagged the transaction's log lines with
+    trace_id: str
+
+    # Hex-encoded envelope hash, as returned on submission
+    tx_hash: str
+
+
+class VerifyTickProofRequest(TypedDict):
+    """Request to verify a tick certificate
+
+    The certificate is checked against the one of the tick before it, taken
+    from the request if given and otherwise from the node's database. The
+    first tick needs no predecessor.
+    """
+
+    # Certificate to verify
+    certificate: TickCertificate
+
+    # Certificate of the tick before it, if the node shouldn't look it up
+    previous: NotRequired[Optional[TickCertificate]]
+
+
+class KalaRpcError(Exception):
+    """Error a node answered a JSON-RPC call with"""
+
+    def __init__(self, code: int, message: str, data: Any = None) -> None:
+        super().__init__(message)
+        self.code = code
+        self.message = message
+        self.data = data
+
+
+class KalaClient:
+    """Client of a Kala node's JSON-RPC API over HTTP
+
+    ``headers`` are sent with every call, such as an ``Authorization``
+    bearer token for the ``admin_*`` methods.
+    """
+
+    def __init__(
+        self,
+        url: str,
+        headers: Optional[Dict[str, str]] = None,
+        timeout: float = 30.0,
+    ) -> None:
+        self.url = url
+        self.headers = dict(headers or {})
+        self.timeout = timeout
+        self._next_id = 1
+
+    def _call(self, method: str, params: List[Any]) -> Any:
+        body = {"jsonrpc": "2.0", "id": self._next_id, "method": method, "params": params}
+        self._next_id += 1
+        request = urllib.request.Request(
+            self.url,
+            data=json.dumps(body).encode(),
+            headers={"Content-Type": "application/json", **self.headers},
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=self.timeout) as response:
+                reply = json.load(response)
+        except urllib.error.HTTPError as error:
+            # Rate limits and oversized requests are still answered in JSON-RPC
+            try:
+                reply = json.load(error)
+            except ValueError:
+                raise error from None
+        if "error" in reply:
+            error = reply["error"]
+            raise KalaRpcError(error["code"], error["message"], error.get("data"))
+        return reply.get("result")
+
+    def chain_info(self) -> ChainInfo:
+        """Get current blockchain state and VDF progress"""
+        return self._call("kala_chainInfo", [])
+
+    def get_chain_params(self) -> ChainParams:
+        """Get the protocol parameters SDKs configure from"""
+        return self._call("kala_getChainParams", [])
+
+    def submit_transaction(self, req: SubmitTransactionRequest) -> SubmitTransactionResponse:
+        """Submit a timelock-encrypted transaction"""
+        return self._call("kala_submitTransaction", [req])
+
+    def reserve_nonces(self, req: ReserveNoncesRequest) -> NonceReservation:
+        """Reserve a nonce range for parallel submission"""
+        return self._call("kala_reserveNonces", [req])
+
+    def get_tick(self, req: GetTickRequest) -> Optional[TickCertificate]:
+        """Retrieve a tick certificate"""
+        return self._call("kala_getTick", [req])
+
+    def verify_tick_proof(self, req: VerifyTickProofRequest) -> TickProofVerification:
+        """Verify a tick certificate's hash, linkage, and VDF output"""
+        return self._call("kala_verifyTickProof", [req])
+
+    def get_tick_range_proof(self, req: GetTickRangeProofRequest) -> TickRangeProof:
+        """Prove a range of ticks with sampled VDF segments"""
+        return self._call("kala_getTickRangeProof", [req])
+
+    def timestamp_data(self, req: TimestampDataRequest) -> TimestampProof:
+        """Timestamp arbitrary data in the VDF hash chain"""
+        return self._call("kala_timestampData", [req])
+
+    def verify_timestamp(self, proof: TimestampProof) -> TimestampVerification:
+        """Verify a timestamp proof issued by the node"""
+        return self._call("kala_verifyTimestamp", [proof])
+
+    def get_tick_data(self, req: GetTickDataRequest) -> Optional[TickDataEntry]:
+        """Retrieve data timestamped in a tick"""
+        return self._call("kala_getTickData", [req])
+
+    def prove_tick_data(self, req: ProveTickDataRequest) -> Optional[TimestampProof]:
+        """Prove data is committed to its tick's data root"""
+        return self._call("kala_proveTickData", [req])
+
+    def get_recent_ticks(self, count: int) -> List[TickCertificate]:
+        """Get recent tick history"""
+        return self._call("kala_getRecentTicks", [count])
+
+    def get_account(self, req: GetAccountRequest) -> Optional[AccountInfo]:
+        """Query account balances and state"""
+        return self._call("kala_getAccount", [req])
+
+    def get_supply(self) -> SupplyInfo:
+        """Get the token supply"""
+        return self._call("kala_getSupply", [])
+
+    def unwatch_account(self, subscription: int) -> bool:
+        """Cancel an account subscription"""
+        return self._call("kala_unwatchAccount", [subscription])
+
+    def sync_status(self) -> SyncStatus:
+        """Check whether the node is catching up with its peers"""
+        return self._call("kala_syncStatus", [])
+
+    def get_epoch_summary(self, req: GetEpochRequest) -> Optional[SignedEpochSummary]:
+        """Get a signed epoch summary for light clients"""
+        return self._call("kala_getEpochSummary", [req])
+
+    def get_tick_finality(self, req: GetTickRequest) -> Optional[TickFinality]:
+        """Get the witness signatures and metadata finalizing a tick"""
+        return self._call("kala_getTickFinality", [req])
+
+    def get_oracle_value(self, req: GetOracleValueRequest) -> Optional[OracleProof]:
+        """Get a feed's medianized value at a tick, with its proof"""
+        return self._call("kala_getOracleValue", [req])
+
+    def get_metrics_history(self, req: GetMetricsHistoryRequest) -> List[MetricsSample]:
+        """Get persisted performance samples"""
+        return self._call("kala_getMetricsHistory", [req])
+
+    def trace_transaction(self, tx_hash: str) -> Optional[TransactionTrace]:
+        """Get the lifecycle timeline of a transaction"""
+        return self._call("kala_traceTransaction", [tx_hash])
+
+    def get_version(self) -> NodeVersion:
+        """Identify the exact build of the node software"""
+        return self._call("kala_getVersion", [])
+
+    def iteration_to_time(self, iteration: int) -> TimelinePoint:
+        """Estimate the UTC time an iteration runs at"""
+        return self._call("kala_iterationToTime", [iteration])
+
+    def time_to_iteration(self, time_ms: int) -> TimelinePoint:
+        """Estimate the iteration running at a UTC time"""
+        return self._call("kala_timeToIteration", [time_ms])
+
+    def get_time_bounds(self, req: GetTickRequest) -> TimeBounds:
+        """Bound a tick's UTC time by witness-signed anchors"""
+        return self._call("kala_getTimeBounds", [req])
+
+    def debug_ffi_memory(self) -> FfiMemoryReport:
+        """Report memory held across native libraries, if enabled"""
+        return self._call("kala_debugFfiMemory", [])
+
+    def admin_peers(self) -> List[PeerInfo]:
+        """List the peers the node knows"""
+        return self._call("admin_peers", [])
+
+    def admin_ban_peer(self, peer_id: str) -> PeerInfo:
+        """Ban a peer, disconnecting it and never redialing it"""
+        return self._call("admin_banPeer", [peer_id])
+
+    def admin_mempool(self) -> List[MempoolEntry]:
+        """List the transactions waiting in the mempool"""
+        return self._call("admin_mempool", [])
+
+    def admin_flush_mempool(self) -> int:
+        """Drop every transaction waiting in the mempool"""
+        return self._call("admin_flushMempool", [])
+
+    def admin_export_snapshot(self, path: str) -> SnapshotExport:
+        """Write a verified snapshot of the state database"""
+        return self._call("admin_exportSnapshot", [path])
+
+    def admin_prune_now(self) -> int:
+        """Move settled ticks past the retention window to the archive now"""
+        return self._call("admin_pruneNow", [])
+
+    def admin_set_log_level(self, filter: str) -> None:
+        """Change which log lines the node writes"""
+        return self._call("admin_setLogLevel", [filter])
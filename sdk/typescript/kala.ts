@@ -0,0 +1,1056 @@
+// Kala JSON-RPC client, generated from the OpenRPC document of kala-rpc 0.0.2.
+// Do not edit: run `cargo run -p kala-rpc-codegen` to regenerate.
+//
+// Integers are plain numbers, exact up to 2^53.
+
+/**
+ * Display metadata of a denomination from the node's registry
+ *
+ * Amounts on chain are integers of base units; `decimals` says how many
+ * of their digits are fractional in the display unit named by `symbol`.
+ */
+export interface DenomMetadata {
+  /** Number of decimal places in the display unit */
+  decimals: number;
+  /** Denomination ID (hex-encoded) */
+  denom: string;
+  /** Human-readable name */
+  name: string;
+  /** Ticker symbol of the display unit, e.g. "KALA" */
+  symbol: string;
+}
+
+/**
+ * Account state information
+ *
+ * Contains the complete state of an account including balances,
+ * transaction history, and staking status. Amounts are given in base
+ * units and, for display, formatted with the denomination's decimals.
+ */
+export interface AccountInfo {
+  /** Account balance in base units */
+  balance: number;
+  /** Balance in display units, e.g. "12.5" */
+  balance_display: string;
+  /** Optional delegation target (hex-encoded address) */
+  delegation?: string | null;
+  /** Denomination the balances are held in */
+  denom: DenomMetadata;
+  /** Transaction nonce (prevents replay attacks) */
+  nonce: number;
+  /**
+   * Nonces of decrypted transactions held until the nonces before them
+   * are applied
+   */
+  pending_nonces: number[];
+  /** Amount currently staked by this account */
+  staked_amount: number;
+  /** Staked amount in display units */
+  staked_display: string;
+}
+
+/**
+ * Account state pushed to `kala_watchAccount` subscribers
+ *
+ * Sent after every tick that changed the account, with the hashes of the
+ * transactions that changed it.
+ */
+export interface AccountUpdate {
+  /** Account address (hex-encoded) */
+  address: string;
+  /** Account balance in base units after the tick */
+  balance: number;
+  /** Optional delegation target (hex-encoded address) */
+  delegation?: string | null;
+  /** Transaction nonce after the tick */
+  nonce: number;
+  /** Amount staked after the tick */
+  staked_amount: number;
+  /** Tick whose transactions changed the account */
+  tick_number: number;
+  /** Hex-encoded hashes of the transactions that changed the account */
+  tx_hashes: string[];
+}
+
+/** Measured VDF speed of a node against the tick duration target */
+export interface VdfCalibration {
+  /** VDF iterations per second measured by the benchmark */
+  iterations_per_second: number;
+  /** Whether the node computes ticks at least as fast as the target */
+  keeps_pace: boolean;
+  /** Unix time of the measurement, in seconds */
+  measured_at: number;
+  /** Tick duration the network targets, in milliseconds */
+  target_tick_duration_ms: number;
+  /** Tick duration expected at the measured rate, in milliseconds */
+  tick_duration_ms: number;
+}
+
+/** Configuration a node's VDF library runs with */
+export interface VdfTuning {
+  /** Whether the fast squaring path may use AVX-512 IFMA */
+  avx512: boolean;
+  /** Whether the fast squaring path may use its assembly */
+  fast_mode: boolean;
+  /** Threads recomputing VDF segments during verification */
+  num_threads: number;
+}
+
+/**
+ * Current blockchain and VDF state information
+ *
+ * This structure contains a comprehensive snapshot of the current
+ * blockchain state, including VDF progress, transaction statistics,
+ * and network health indicators.
+ */
+export interface ChainInfo {
+  /** Number of accounts with non-zero state */
+  accounts: number;
+  /** Current VDF iteration number within the eternal computation */
+  current_iteration: number;
+  /** Current tick number (block height equivalent) */
+  current_tick: number;
+  /** Current VDF hash chain value (hex-encoded) */
+  hash_chain: string;
+  /** Total number of transactions processed across all ticks */
+  total_transactions: number;
+  /** Latest measurement of the node's VDF speed, if calibration is enabled */
+  vdf_calibration?: VdfCalibration | null;
+  /** Current VDF output as a formatted string (a, b, c form values) */
+  vdf_output: string;
+  /** How the node's VDF library uses its machine */
+  vdf_tuning?: VdfTuning | null;
+}
+
+/** Activation status of a protocol feature */
+export interface FeatureStatus {
+  /** Tick the feature activates at */
+  activation_tick: number;
+  /** Whether the feature is active at the head of the chain */
+  active: boolean;
+  /** Feature name, e.g. "fork_choice" */
+  name: string;
+}
+
+/**
+ * Timelock puzzle limits for transactions targeting a tick
+ *
+ * A puzzle must decrypt after the target tick's collection phase and
+ * before the tick ends.
+ */
+export interface HardnessBounds {
+  /** Offset into the target tick until which transactions are accepted */
+  acceptance_window_end: number;
+  /** Offset into the tick before the target from which transactions are accepted */
+  acceptance_window_start: number;
+  /** Largest fraction of a tick a puzzle may take to solve */
+  hardness_factor: number;
+  /** Largest recommended hardness in iterations */
+  max_hardness: number;
+  /** Smallest accepted hardness in iterations */
+  min_hardness: number;
+}
+
+/** Iteration offsets, from the start of a tick, at which its phases end */
+export interface TickPhases {
+  /** End of collection; the transaction order is fixed here (k/3) */
+  collection_end: number;
+  /** End of puzzle decryption (2k/3) */
+  decryption_end: number;
+  /** End of validation and of the tick (k) */
+  validation_end: number;
+}
+
+/**
+ * Protocol parameters of the chain a node runs
+ *
+ * SDKs configure themselves from these values instead of compile-time
+ * constants, so they keep working across parameter changes and upgrades.
+ */
+export interface ChainParams {
+  /** Chain ID transactions are signed for */
+  chain_id: string;
+  /** Class group discriminant of the VDF (decimal) */
+  discriminant: string;
+  /** Ticks per epoch */
+  epoch_length: number;
+  /** Protocol features and whether they are active at the head */
+  features: FeatureStatus[];
+  /** Limits on timelock puzzles of submitted transactions */
+  hardness: HardnessBounds;
+  /** VDF iterations per tick (k) */
+  iterations_per_tick: number;
+  /** Iteration offsets of the phases within a tick */
+  phases: TickPhases;
+  /** Network protocol version */
+  protocol_version: number;
+}
+
+/** Resources used, by module */
+export interface ModuleUsage {
+  /** Gas metered by contract calls, zero while no module runs contracts */
+  contract_gas?: number;
+  /** Native transactions applied, by module name */
+  native_transactions?: Record<string, number>;
+}
+
+/** Aggregate statistics over the ticks of an epoch */
+export interface EpochStats {
+  checkpoint_ticks: number;
+  empty_ticks: number;
+  end_iteration: number;
+  end_timestamp: number;
+  full_ticks: number;
+  /**
+   * Oracle feed values finalized over the epoch
+   *
+   * Not covered by the summary hash: each node medianizes its own
+   * finality records, which may hold different votes, so nodes can
+   * count differently.
+   */
+  oracle_updates?: number;
+  start_iteration: number;
+  start_timestamp: number;
+  tick_count: number;
+  transaction_count: number;
+  /** Usage of the epoch's ticks, added up */
+  usage?: ModuleUsage;
+}
+
+/** Commitment to a run of `epoch_length` ticks */
+export interface EpochSummary {
+  /** Last tick of the epoch, inclusive */
+  end_tick: number;
+  end_tick_hash: number[];
+  epoch_number: number;
+  /** Root of the tick MMR after `end_tick` was appended */
+  mmr_root: number[];
+  /** Hash of the previous epoch's summary, zero for epoch 0 */
+  previous_epoch_hash: number[];
+  start_tick: number;
+  state_root: number[];
+  stats: EpochStats;
+  /** Ed25519 public keys of the witnesses, sorted */
+  witnesses: number[][];
+}
+
+/** Median of the values reported for a feed at one tick */
+export interface FeedValue {
+  feed: number[];
+  /** Number of witnesses that reported the feed */
+  reports: number;
+  value: number;
+}
+
+/** Allocations of one kind of object crossing into a native library */
+export interface FfiAllocations {
+  allocations: number;
+  frees: number;
+  /** Library the objects cross into, `tick` or `timelocks` */
+  library: string;
+  /** Objects allocated and not yet freed */
+  live: number;
+  /** Bytes allocated and not yet freed, where the size is known */
+  live_bytes: number;
+  /** Kind of object, e.g. `forms` or `c_strings` */
+  resource: string;
+}
+
+/**
+ * Memory a node holds across its native library boundaries
+ *
+ * Allocation counts are kept since the node started. A live count that
+ * keeps growing while the node runs points at a leak.
+ */
+export interface FfiMemoryReport {
+  allocations: FfiAllocations[];
+  /** Kinds of object freed more often than allocated, `library/resource` */
+  over_freed: string[];
+  /** Bytes of the certificates' forms, hashes and proofs */
+  vdf_certificate_bytes: number;
+  /** Tick certificates the VDF keeps, all of which a full checkpoint copies */
+  vdf_certificates: number;
+}
+
+/**
+ * Request to retrieve account information
+ *
+ * Queries the current state of a specific account, including
+ * balance, nonce, and staking information.
+ */
+export interface GetAccountRequest {
+  /** Account address as a hex-encoded public key (64 characters) */
+  address: string;
+}
+
+/**
+ * Request to retrieve an epoch summary
+ *
+ * Epoch summaries commit to every tick in the epoch and are signed by
+ * the witnesses, letting light clients follow the timeline
+ * epoch-to-epoch instead of tick-by-tick.
+ */
+export interface GetEpochRequest {
+  /** The epoch number to retrieve, or `None` for the latest sealed epoch */
+  epoch_number?: number | null;
+}
+
+/**
+ * Request to retrieve persisted metrics samples
+ *
+ * Both bounds are Unix timestamps in seconds and inclusive. The node
+ * keeps roughly a day of samples.
+ */
+export interface GetMetricsHistoryRequest {
+  /** Earliest sample time, or `None` for the oldest sample kept */
+  from?: number | null;
+  /** Latest sample time, or `None` for the newest sample */
+  to?: number | null;
+}
+
+/** Request to retrieve an oracle feed's value at a tick */
+export interface GetOracleValueRequest {
+  /** Feed ID as a hex-encoded 32-byte hash (64 characters) */
+  feed: string;
+  /** The finalized tick to read the value at */
+  tick_number: number;
+}
+
+/** Request to retrieve data timestamped in a tick */
+export interface GetTickDataRequest {
+  /** Position of the data among the data timestamped in the tick */
+  leaf_index: number;
+  /** The finalized tick the data was timestamped in */
+  tick_number: number;
+}
+
+/**
+ * Request to prove a range of ticks
+ *
+ * Proofs are against the tick accumulator root after `end_tick`, so
+ * light clients usually end the range at an epoch summary's `end_tick`.
+ */
+export interface GetTickRangeProofRequest {
+  /** Last tick of the range, inclusive */
+  end_tick: number;
+  /** First tick of the range */
+  start_tick: number;
+}
+
+/**
+ * Request to retrieve a specific tick certificate
+ *
+ * Used to query historical tick information including transaction
+ * processing results and VDF state at tick completion.
+ */
+export interface GetTickRequest {
+  /** The tick number to retrieve */
+  tick_number: number;
+}
+
+/** A transaction waiting in the node's mempool */
+export interface MempoolEntry {
+  /** Size of the sealed payload, in bytes */
+  ciphertext_bytes: number;
+  /** How the key is locked, `puzzle` or `threshold` */
+  lock: string;
+  /** VDF iteration the node timestamped the transaction at */
+  submission_iteration: number;
+  /** Tick the transaction is ordered into */
+  target_tick: number;
+  /** Hex-encoded envelope hash */
+  tx_hash: string;
+}
+
+/** Node performance over the ticks since the previous sample */
+export interface MetricsSample {
+  /** VDF iterations per second over those ticks */
+  iterations_per_second: number;
+  /** Longest tick duration in milliseconds */
+  max_tick_ms: number;
+  /** Mean tick duration in milliseconds */
+  mean_tick_ms: number;
+  /** Transactions waiting in the pool */
+  mempool_depth: number;
+  /** Latest tick produced when the sample was taken */
+  tick_number: number;
+  /** Number of ticks the sample covers */
+  ticks: number;
+  /** Unix time the sample was taken, in seconds */
+  timestamp: number;
+}
+
+/** Inclusion proof for a single leaf of a Merkle Mountain Range */
+export interface MmrProof {
+  /** Leaf count of the accumulator the proof was generated against */
+  leaf_count: number;
+  /** Index of the proven leaf */
+  leaf_index: number;
+  /** Hashes of the other peaks, left to right */
+  peaks: number[][];
+  /** Sibling hashes from the leaf up to its mountain peak */
+  siblings: number[][];
+}
+
+/**
+ * The build of a node's software
+ *
+ * Builds are reproducible: the same commit, profile, target, flags, and
+ * features give the same binary, so operators and auditors can confirm
+ * exactly what code produced a tick.
+ */
+export interface NodeVersion {
+  /** Cargo profile, e.g. `release` */
+  build_profile: string;
+  /** Cargo features compiled in */
+  features: string[];
+  /** Git commit the node was built from, `unknown` outside a checkout */
+  git_commit: string;
+  /** Optimization level */
+  opt_level: string;
+  /** Network protocol version */
+  protocol_version: number;
+  /** Extra flags passed to rustc, space-separated */
+  rustflags: string;
+  /** Timelock puzzle solver library */
+  solver_library: string;
+  /** Target triple the binary was built for */
+  target: string;
+  /** VDF library and the GMP version it links */
+  vdf_library: string;
+  /** Version of the node crate */
+  version: string;
+}
+
+/**
+ * Nonces reserved for an account
+ *
+ * No other reservation will hand out nonces in `first_nonce..=last_nonce`.
+ * Reserved nonces may be used in any order within a tick's ordering and
+ * unused ones are simply skipped, since a transaction only needs a nonce
+ * above the account's last applied one.
+ */
+export interface NonceReservation {
+  /** Nonce of the account's last applied transaction */
+  account_nonce: number;
+  /** Account address (hex-encoded) */
+  address: string;
+  /** First reserved nonce */
+  first_nonce: number;
+  /** Last reserved nonce, inclusive */
+  last_nonce: number;
+}
+
+/** A feed value and the Merkle path committing it to a tick's oracle root, with the finality record the root is medianized from */
+export interface OracleProof {
+  /** Witness signatures finalizing the tick, carrying the signed reports */
+  finality: TickFinality;
+  /** Position of the value among the tick's values */
+  index: number;
+  root: number[];
+  siblings: number[][];
+  tick_number: number;
+  value: FeedValue;
+}
+
+/** A peer known to the node */
+export interface PeerInfo {
+  /** Multiaddrs the peer was reached on or advertised */
+  addresses: string[];
+  /** Whether the peer is refused and never redialed */
+  banned: boolean;
+  /** Whether the peer is connected now */
+  connected: boolean;
+  /** Unix timestamp of the last connection to the peer */
+  last_seen: number;
+  /** Base58 libp2p peer ID */
+  peer_id: string;
+  /** Accumulated reputation score */
+  reputation: number;
+}
+
+/** Request to prove data was timestamped in a tick */
+export interface ProveTickDataRequest {
+  /** SHA-256 hash of the data as a hex-encoded 32-byte hash (64 characters) */
+  data_hash: string;
+  /** The finalized tick the data was timestamped in */
+  tick_number: number;
+}
+
+export type TickType = "Full" | "Empty" | "Checkpoint";
+
+/**
+ * Certificate of a finalized tick
+ *
+ * The form is held packed, see [`form_bytes`], and served as hex strings.
+ */
+export interface TickCertificate {
+  hash_chain_value: number[];
+  previous_tick_hash: number[];
+  tick_hash: number[];
+  tick_number: number;
+  tick_type: TickType;
+  timestamp: number;
+  transaction_count: number;
+  transaction_merkle_root: number[];
+  /** Resources the tick's transactions used, by module */
+  usage?: ModuleUsage;
+  vdf_form: [string, string, string];
+  vdf_iteration: number;
+}
+
+/** A certificate and its inclusion in the tick accumulator */
+export interface ProvenTick {
+  certificate: TickCertificate;
+  proof: MmrProof;
+}
+
+/**
+ * Request to reserve a contiguous range of nonces for an account
+ *
+ * Must be signed with the account's Ed25519 key over
+ * [`ReserveNoncesRequest::signing_message`], so only the key holder can
+ * reserve the account's nonces. The signature binds the chain and a
+ * request identifier the node accepts once, so a captured request can't
+ * be replayed, here or on another network.
+ */
+export interface ReserveNoncesRequest {
+  /** Account address as a hex-encoded public key (64 characters) */
+  address: string;
+  /** Number of nonces to reserve */
+  count: number;
+  /**
+   * Client-chosen identifier, accepted once per account while the
+   * request is unexpired
+   */
+  request_id: number;
+  /** Unix time in seconds after which the request is rejected */
+  expires_at: number;
+  /** Hex-encoded Ed25519 signature by the account key */
+  signature: string;
+}
+
+/** A sampled VDF segment: a tick and the one before it */
+export interface SampledSegment {
+  previous: ProvenTick;
+  tick: ProvenTick;
+}
+
+/** A witness's Ed25519 signature over an epoch summary hash */
+export interface WitnessSignature {
+  signature: number[];
+  witness: number[];
+}
+
+/** An epoch summary together with the witness signatures collected for it */
+export interface SignedEpochSummary {
+  signatures: WitnessSignature[];
+  summary: EpochSummary;
+}
+
+/** Snapshot written by `admin_exportSnapshot` */
+export interface SnapshotExport {
+  /** Directory the snapshot was written to, on the node's machine */
+  path: string;
+  /** Hex-encoded state root of the exported state */
+  state_root: string;
+}
+
+/**
+ * Request to submit a timelock-encrypted transaction
+ *
+ * Contains the complete timelock transaction data in hex-encoded format.
+ * The transaction must be properly encrypted, with its key locked behind
+ * an RSW timelock puzzle or shared among the witnesses, and targeted for a
+ * future tick.
+ */
+export interface SubmitTransactionRequest {
+  /**
+   * Hex-encoded timelock-encrypted transaction data
+   *
+   * The [`TimelockTransaction`] in its canonical wire format: the
+   * protobuf encoding of `kala.network.Envelope` from
+   * kala-common/schema/network.proto, as made by
+   * [`SubmitTransactionRequest::new`]. Hex of the envelope's JSON
+   * serialization is still accepted from older clients.
+   */
+  encrypted_tx: string;
+}
+
+/**
+ * Response from submitting a timelock transaction
+ *
+ * Contains confirmation details and timing information for the
+ * submitted transaction, allowing clients to track its progress
+ * through the MEV-resistant processing pipeline.
+ */
+export interface SubmitTransactionResponse {
+  /** VDF iteration number when the transaction was timestamped */
+  submission_iteration: number;
+  /** Target tick number when the transaction will be processed */
+  target_tick: number;
+  /** Identifier tagging the transaction in this node's logs and trace */
+  trace_id: string;
+  /** Unique transaction hash for tracking and identification */
+  tx_hash: string;
+}
+
+/**
+ * Token supply at the head of the chain
+ *
+ * Amounts are given in base units and, for display, formatted with the
+ * denomination's decimals.
+ */
+export interface SupplyInfo {
+  /** Denomination the supply is counted in */
+  denom: DenomMetadata;
+  /** Base units currently staked */
+  staked_supply: number;
+  /** Staked supply in display units */
+  staked_supply_display: string;
+  /** Next tick to be produced; the supply includes all ticks before it */
+  tick_number: number;
+  /** Base units held by all accounts, staked amounts included */
+  total_supply: number;
+  /** Total supply in display units */
+  total_supply_display: string;
+}
+
+/** Whether a node is catching up with the network or at its head */
+export type SyncMode = "syncing" | "following";
+
+/**
+ * Progress of the tick sync protocol
+ *
+ * A node joining an existing network starts in [`SyncMode::Syncing`],
+ * downloads tick certificates and a state snapshot from its peers, and
+ * switches to [`SyncMode::Following`] once it has reached their head.
+ */
+export interface SyncStatus {
+  /** Next tick the node will process or verify */
+  current_tick: number;
+  /** Highest next tick reported by any peer */
+  highest_tick: number;
+  /** Current sync mode */
+  mode: SyncMode;
+  /** Number of connected peers */
+  peer_count: number;
+  /** Local tick when syncing started */
+  starting_tick: number;
+}
+
+/**
+ * Data timestamped in a tick, with its place in the tick's data tree
+ *
+ * Byte fields are hex-encoded.
+ */
+export interface TickDataEntry {
+  /** The timestamped data, or `None` if the node doesn't keep it */
+  data?: string | null;
+  /** SHA-256 hash of the data */
+  data_hash: string;
+  /** Blob store URI the data was moved to, such as `s3://bucket/key` */
+  data_uri?: string | null;
+  /** Hash chain value after the iteration */
+  hash_at_timestamp: string;
+  /** VDF iteration the data was hashed into */
+  iteration: number;
+  /** Number of data timestamped in the tick */
+  leaf_count: number;
+  /** Position of the data among the data timestamped in the tick */
+  leaf_index: number;
+  /** Tick the data was timestamped in */
+  tick_number: number;
+}
+
+/** Metadata a signer attached to its vote, carried in a [`TickFinality`] */
+export interface WitnessMetadata {
+  data: number[];
+  /** Ed25519 signature of the witness over [`metadata_message`] */
+  signature: number[];
+  witness: number[];
+}
+
+/** One signature per signer, in witness order */
+export interface WitnessSignaturesEd25519 {
+  ed25519: number[][];
+}
+
+/** Aggregate of every signer's signature, one signature in size */
+export interface WitnessSignaturesBls12381 {
+  bls12381: number[];
+}
+
+/** Signatures of the witnesses in a [`TickFinality`] bitmap */
+export type WitnessSignatures = WitnessSignaturesEd25519 | WitnessSignaturesBls12381;
+
+/** Clock reading a signer attached to its vote, carried in a [`TickFinality`] */
+export interface WitnessTime {
+  /** Ed25519 signature of the witness over [`time_message`] */
+  signature: number[];
+  /** Unix time in milliseconds */
+  time_ms: number;
+  witness: number[];
+}
+
+/** Witness signatures finalizing a tick */
+export interface TickFinality {
+  /** Metadata attached by signers, in witness order */
+  metadata?: WitnessMetadata[];
+  /** Signatures of all signers, in the witness set's scheme */
+  signatures: WitnessSignatures;
+  /** Bitmap of signers by index in the witness set */
+  signers: number[];
+  tick_hash: number[];
+  tick_number: number;
+  /** Clock readings attached by signers, in witness order */
+  times?: WitnessTime[];
+}
+
+/** Outcome of verifying a tick certificate */
+export interface TickProofVerification {
+  /** Why the certificate failed, if it did */
+  error?: string | null;
+  /** Tick the certificate is for */
+  tick_number: number;
+  /** Whether the hash, linkage, and VDF output all check out */
+  valid: boolean;
+}
+
+/** Ticks `start..=end`, proven by their endpoints and sampled segments */
+export interface TickRangeProof {
+  first: ProvenTick;
+  last: ProvenTick;
+  /** Segments at [`sample_ticks`], in tick order */
+  segments: SampledSegment[];
+}
+
+/** Time the witnesses agree an iteration ran at */
+export interface TimeAnchor {
+  /** Iteration the tick ended at */
+  iteration: number;
+  /** Witness weight behind the readings */
+  reported_weight: number;
+  reports: number;
+  /** Tick whose finality record carries the readings */
+  tick_number: number;
+  /** Weighted median of the readings, Unix time in milliseconds */
+  time_ms: number;
+}
+
+/** Range of UTC times an iteration ran within, from the anchors around it */
+export interface TimeBounds {
+  /** Latest anchor at or before the iteration */
+  earliest?: TimeAnchor | null;
+  /** Finality record carrying the readings of `earliest` */
+  earliest_finality?: TickFinality | null;
+  iteration: number;
+  /** Earliest anchor at or after the iteration */
+  latest?: TimeAnchor | null;
+  /** Finality record carrying the readings of `latest` */
+  latest_finality?: TickFinality | null;
+}
+
+/** A point on the timeline, estimated by the node's timeline clock */
+export interface TimelinePoint {
+  /** VDF iteration */
+  iteration: number;
+  /** Iterations per second the estimate assumes */
+  iterations_per_second: number;
+  /** Unix time in milliseconds at which the iteration runs */
+  time_ms: number;
+}
+
+/** Request to timestamp data in the VDF */
+export interface TimestampDataRequest {
+  /** Hex-encoded data, at most [`MAX_TIMESTAMP_DATA_BYTES`] bytes */
+  data: string;
+}
+
+/**
+ * Proof that data was hashed into the VDF at an iteration
+ *
+ * The data can't have existed later than the iteration, since every
+ * later hash chain value depends on it. The Merkle path commits it to the
+ * data root of the tick's VDF certificate. Byte fields are hex-encoded.
+ */
+export interface TimestampProof {
+  /** The timestamped data */
+  data: string;
+  /** SHA-256 hash of the data */
+  data_hash: string;
+  /** Hash chain value after the iteration */
+  hash_at_timestamp: string;
+  /** VDF iteration the data was hashed into */
+  iteration: number;
+  /** Position of the data among the data timestamped in the tick */
+  leaf_index: number;
+  /**
+   * Sibling hashes from the leaf `H(iteration || data_hash)` up to the
+   * tick's data root, where `iteration` is 8 bytes little-endian
+   */
+  merkle_path: string[];
+  /** Tick the iteration belongs to */
+  tick_number: number;
+}
+
+/** Outcome of verifying a timestamp proof */
+export interface TimestampVerification {
+  /** Why the proof failed, if it did */
+  error?: string | null;
+  /** Whether the data was timestamped at the proof's iteration */
+  valid: boolean;
+}
+
+/** Stage of a transaction's lifecycle on a node */
+export type TraceStage = "submitted" | "received" | "observed" | "ordered" | "decrypted" | "decryption_failed" | "held" | "applied" | "rejected" | "included" | "dropped";
+
+/** One step of a transaction's lifecycle */
+export interface TraceEvent {
+  /** Stage-specific details, such as an error or the certificate hash */
+  detail?: string | null;
+  /** VDF iteration the event refers to, if any */
+  iteration?: number | null;
+  /** What happened */
+  stage: TraceStage;
+  /** Tick the event happened in */
+  tick_number: number;
+  /** Unix time of the event, in milliseconds */
+  timestamp_ms: number;
+}
+
+/**
+ * Lifecycle of a transaction as seen by one node
+ *
+ * Traces are kept in memory for the most recent transactions only and do
+ * not survive a restart.
+ */
+export interface TransactionTrace {
+  /** Events in the order they happened */
+  events: TraceEvent[];
+  /** Tick the transaction targets */
+  target_tick: number;
+  /** Identifier the node tagged the transaction's log lines with */
+  trace_id: string;
+  /** Hex-encoded envelope hash, as returned on submission */
+  tx_hash: string;
+}
+
+/**
+ * Request to verify a tick certificate
+ *
+ * The certificate is checked against the one of the tick before it, taken
+ * from the request if given and otherwise from the node's database. The
+ * first tick needs no predecessor.
+ */
+export interface VerifyTickProofRequest {
+  /** Certificate to verify */
+  certificate: TickCertificate;
+  /** Certificate of the tick before it, if the node shouldn't look it up */
+  previous?: TickCertificate | null;
+}
+
+/** Error a node answered a JSON-RPC call with */
+export class KalaRpcError extends Error {
+  constructor(
+    readonly code: number,
+    message: string,
+    readonly data?: unknown,
+  ) {
+    super(message);
+    this.name = "KalaRpcError";
+  }
+}
+
+/**
+ * Client of a Kala node's JSON-RPC API over HTTP
+ *
+ * `headers` are sent with every call, such as an `Authorization` bearer
+ * token for the `admin_*` methods.
+ */
+export class KalaClient {
+  private nextId = 1;
+
+  constructor(
+    readonly url: string,
+    readonly headers: Record<string, string> = {},
+  ) {}
+
+  private async call<T>(method: string, params: unknown[]): Promise<T> {
+    const response = await fetch(this.url, {
+      method: "POST",
+      headers: { "Content-Type": "application/json", ...this.headers },
+      body: JSON.stringify({ jsonrpc: "2.0", id: this.nextId++, method, params }),
+    });
+    const reply = await response.json();
+    if (reply.error) {
+      throw new KalaRpcError(reply.error.code, reply.error.message, reply.error.data);
+    }
+    return reply.result as T;
+  }
+
+  /** Get current blockchain state and VDF progress */
+  chainInfo(): Promise<ChainInfo> {
+    return this.call("kala_chainInfo", []);
+  }
+
+  /** Get the protocol parameters SDKs configure from */
+  getChainParams(): Promise<ChainParams> {
+    return this.call("kala_getChainParams", []);
+  }
+
+  /** Submit a timelock-encrypted transaction */
+  submitTransaction(req: SubmitTransactionRequest): Promise<SubmitTransactionResponse> {
+    return this.call("kala_submitTransaction", [req]);
+  }
+
+  /** Reserve a nonce range for parallel submission */
+  reserveNonces(req: ReserveNoncesRequest): Promise<NonceReservation> {
+    return this.call("kala_reserveNonces", [req]);
+  }
+
+  /** Retrieve a tick certificate */
+  getTick(req: GetTickRequest): Promise<TickCertificate | null> {
+    return this.call("kala_getTick", [req]);
+  }
+
+  /** Verify a tick certificate's hash, linkage, and VDF output */
+  verifyTickProof(req: VerifyTickProofRequest): Promise<TickProofVerification> {
+    return this.call("kala_verifyTickProof", [req]);
+  }
+
+  /** Prove a range of ticks with sampled VDF segments */
+  getTickRangeProof(req: GetTickRangeProofRequest): Promise<TickRangeProof> {
+    return this.call("kala_getTickRangeProof", [req]);
+  }
+
+  /** Timestamp arbitrary data in the VDF hash chain */
+  timestampData(req: TimestampDataRequest): Promise<TimestampProof> {
+    return this.call("kala_timestampData", [req]);
+  }
+
+  /** Verify a timestamp proof issued by the node */
+  verifyTimestamp(proof: TimestampProof): Promise<TimestampVerification> {
+    return this.call("kala_verifyTimestamp", [proof]);
+  }
+
+  /** Retrieve data timestamped in a tick */
+  getTickData(req: GetTickDataRequest): Promise<TickDataEntry | null> {
+    return this.call("kala_getTickData", [req]);
+  }
+
+  /** Prove data is committed to its tick's data root */
+  proveTickData(req: ProveTickDataRequest): Promise<TimestampProof | null> {
+    return this.call("kala_proveTickData", [req]);
+  }
+
+  /** Get recent tick history */
+  getRecentTicks(count: number): Promise<TickCertificate[]> {
+    return this.call("kala_getRecentTicks", [count]);
+  }
+
+  /** Query account balances and state */
+  getAccount(req: GetAccountRequest): Promise<AccountInfo | null> {
+    return this.call("kala_getAccount", [req]);
+  }
+
+  /** Get the token supply */
+  getSupply(): Promise<SupplyInfo> {
+    return this.call("kala_getSupply", []);
+  }
+
+  /** Cancel an account subscription */
+  unwatchAccount(subscription: number): Promise<boolean> {
+    return this.call("kala_unwatchAccount", [subscription]);
+  }
+
+  /** Check whether the node is catching up with its peers */
+  syncStatus(): Promise<SyncStatus> {
+    return this.call("kala_syncStatus", []);
+  }
+
+  /** Get a signed epoch summary for light clients */
+  getEpochSummary(req: GetEpochRequest): Promise<SignedEpochSummary | null> {
+    return this.call("kala_getEpochSummary", [req]);
+  }
+
+  /** Get the witness signatures and metadata finalizing a tick */
+  getTickFinality(req: GetTickRequest): Promise<TickFinality | null> {
+    return this.call("kala_getTickFinality", [req]);
+  }
+
+  /** Get a feed's medianized value at a tick, with its proof */
+  getOracleValue(req: GetOracleValueRequest): Promise<OracleProof | null> {
+    return this.call("kala_getOracleValue", [req]);
+  }
+
+  /** Get persisted performance samples */
+  getMetricsHistory(req: GetMetricsHistoryRequest): Promise<MetricsSample[]> {
+    return this.call("kala_getMetricsHistory", [req]);
+  }
+
+  /** Get the lifecycle timeline of a transaction */
+  traceTransaction(txHash: string): Promise<TransactionTrace | null> {
+    return this.call("kala_traceTransaction", [txHash]);
+  }
+
+  /** Identify the exact build of the node software */
+  getVersion(): Promise<NodeVersion> {
+    return this.call("kala_getVersion", []);
+  }
+
+  /** Estimate the UTC time an iteration runs at */
+  iterationToTime(iteration: number): Promise<TimelinePoint> {
+    return this.call("kala_iterationToTime", [iteration]);
+  }
+
+  /** Estimate the iteration running at a UTC time */
+  timeToIteration(timeMs: number): Promise<TimelinePoint> {
+    return this.call("kala_timeToIteration", [timeMs]);
+  }
+
+  /** Bound a tick's UTC time by witness-signed anchors */
+  getTimeBounds(req: GetTickRequest): Promise<TimeBounds> {
+    return this.call("kala_getTimeBounds", [req]);
+  }
+
+  /** Report memory held across native libraries, if enabled */
+  debugFfiMemory(): Promise<FfiMemoryReport> {
+    return this.call("kala_debugFfiMemory", []);
+  }
+
+  /** List the peers the node knows */
+  adminPeers(): Promise<PeerInfo[]> {
+    return this.call("admin_peers", []);
+  }
+
+  /** Ban a peer, disconnecting it and never redialing it */
+  adminBanPeer(peerId: string): Promise<PeerInfo> {
+    return this.call("admin_banPeer", [peerId]);
+  }
+
+  /** List the transactions waiting in the mempool */
+  adminMempool(): Promise<MempoolEntry[]> {
+    return this.call("admin_mempool", []);
+  }
+
+  /** Drop every transaction waiting in the mempool */
+  adminFlushMempool(): Promise<number> {
+    return this.call("admin_flushMempool", []);
+  }
+
+  /** Write a verified snapshot of the state database */
+  adminExportSnapshot(path: string): Promise<SnapshotExport> {
+    return this.call("admin_exportSnapshot", [path]);
+  }
+
+  /** Move settled ticks past the retention window to the archive now */
+  adminPruneNow(): Promise<number> {
+    return this.call("admin_pruneNow", []);
+  }
+
+  /** Change which log lines the node writes */
+  adminSetLogLevel(filter: string): Promise<null> {
+    return this.call("admin_setLogLevel", [filter]);
+  }
+}
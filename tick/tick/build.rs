@@ -40,6 +40,9 @@ fn main() {
     println!("cargo:rustc-link-lib=boost_system");
     println!("cargo:rustc-link-lib=stdc++");
 
+    // The optional CUDA backend is opened at runtime
+    println!("cargo:rustc-link-lib=dl");
+
     // Tell cargo to invalidate the built crate whenever files change
     println!(
         "cargo:rerun-if-changed={}",
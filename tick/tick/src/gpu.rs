@@ -0,0 +1,211 @@
+//! Optional CUDA squaring backend
+//!
+//! The CUDA implementation ships as a separate shared library, so the VDF
+//! builds and runs on machines without the CUDA toolkit. [`cuda`] opens it
+//! at runtime; when it is missing or finds no device, callers stay on the
+//! CPU. The library is `libtick_cuda.so`, or the path in `TICK_CUDA_LIBRARY`,
+//! and exports:
+//!
+//! ```c
+//! int tick_cuda_device_count(void);
+//! int tick_cuda_repeated_square(const char* discriminant_hex,
+//!                               const char* a, const char* b, const char* c,
+//!                               uint64_t iterations,
+//!                               char* out_a, char* out_b, char* out_c,
+//!                               size_t out_capacity);
+//! ```
+//!
+//! Forms are passed as the hex strings [`VdfForm::get_values`] returns, and
+//! come back reduced. `tick_cuda_repeated_square` returns 0 on success.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::sync::OnceLock;
+
+use crate::memory::CUDA_BUFFERS;
+use crate::VdfForm;
+
+/// Library opened when `TICK_CUDA_LIBRARY` is not set
+pub const DEFAULT_CUDA_LIBRARY: &str = "libtick_cuda.so";
+
+/// Capacity of each output buffer, ample for 2048-bit discriminants
+const FORM_CAPACITY: usize = 2048;
+
+type DeviceCountFn = unsafe extern "C" fn() -> c_int;
+type RepeatedSquareFn = unsafe extern "C" fn(
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    u64,
+    *mut c_char,
+    *mut c_char,
+    *mut c_char,
+    usize,
+) -> c_int;
+
+static CUDA: OnceLock<Option<CudaSquarer>> = OnceLock::new();
+
+/// The process-wide CUDA backend, loaded on first use
+///
+/// `None` if the library can't be loaded or no device is present.
+pub fn cuda() -> Option<&'static CudaSquarer> {
+    CUDA.get_or_init(|| {
+        let path = std::env::var("TICK_CUDA_LIBRARY")
+            .unwrap_or_else(|_| DEFAULT_CUDA_LIBRARY.to_string());
+        CudaSquarer::load_from(&path).ok()
+    })
+    .as_ref()
+}
+
+/// Squares forms on a CUDA device through the shared library
+pub struct CudaSquarer {
+    handle: *mut c_void,
+    repeated_square: RepeatedSquareFn,
+    device_count: i32,
+}
+
+// The library serialises access to its devices
+unsafe impl Send for CudaSquarer {}
+unsafe impl Sync for CudaSquarer {}
+
+impl CudaSquarer {
+    /// Open the library at `path`
+    ///
+    /// Fails if it can't be opened, lacks a symbol, or sees no device.
+    pub fn load_from(path: &str) -> Result<Self, String> {
+        let c_path = CString::new(path).map_err(|e| e.to_string())?;
+        let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        if handle.is_null() {
+            return Err(format!("Failed to open {}: {}", path, last_dl_error()));
+        }
+
+        let symbols = unsafe {
+            (
+                libc::dlsym(handle, c"tick_cuda_device_count".as_ptr()),
+                libc::dlsym(handle, c"tick_cuda_repeated_square".as_ptr()),
+            )
+        };
+        let (device_count, repeated_square) = match symbols {
+            (count, square) if !count.is_null() && !square.is_null() => unsafe {
+                (
+                    std::mem::transmute::<*mut c_void, DeviceCountFn>(count),
+                    std::mem::transmute::<*mut c_void, RepeatedSquareFn>(square),
+                )
+            },
+            _ => {
+                unsafe { libc::dlclose(handle) };
+                return Err(format!("{} is not a tick CUDA library", path));
+            }
+        };
+
+        let squarer = Self {
+            handle,
+            repeated_square,
+            device_count: unsafe { device_count() },
+        };
+        if squarer.device_count <= 0 {
+            return Err("No CUDA device available".to_string());
+        }
+        Ok(squarer)
+    }
+
+    /// Number of CUDA devices the library found
+    pub fn device_count(&self) -> i32 {
+        self.device_count
+    }
+
+    /// Square `form` `iterations` times on the device, reducing as it goes
+    ///
+    /// `form` is left unchanged on failure.
+    pub fn repeated_square(
+        &self,
+        form: &mut VdfForm,
+        discriminant_hex: &str,
+        iterations: u64,
+    ) -> Result<(), String> {
+        let (a, b, c) = form.get_values();
+        let to_c = |value: &str| CString::new(value).map_err(|e| e.to_string());
+        let (discriminant, a, b, c) = (to_c(discriminant_hex)?, to_c(&a)?, to_c(&b)?, to_c(&c)?);
+        let mut out = [(); 3].map(|_| vec![0u8; FORM_CAPACITY]);
+        let _lent = LentBuffers::new();
+
+        let [out_a, out_b, out_c] = &mut out;
+        let status = unsafe {
+            (self.repeated_square)(
+                discriminant.as_ptr(),
+                a.as_ptr(),
+                b.as_ptr(),
+                c.as_ptr(),
+                iterations,
+                out_a.as_mut_ptr() as *mut c_char,
+                out_b.as_mut_ptr() as *mut c_char,
+                out_c.as_mut_ptr() as *mut c_char,
+                FORM_CAPACITY,
+            )
+        };
+        if status != 0 {
+            return Err(format!("CUDA squaring failed with status {}", status));
+        }
+
+        let mut values = Vec::with_capacity(3);
+        for buffer in &out {
+            let value = CStr::from_bytes_until_nul(buffer)
+                .map_err(|_| "CUDA squaring returned an unterminated form".to_string())?;
+            values.push(value.to_string_lossy().into_owned());
+        }
+        form.set_a(&values[0]);
+        form.set_b(&values[1]);
+        form.set_c(&values[2]);
+        Ok(())
+    }
+}
+
+impl Drop for CudaSquarer {
+    fn drop(&mut self) {
+        unsafe {
+            libc::dlclose(self.handle);
+        }
+    }
+}
+
+/// Counts the output buffers as lent to the library until dropped
+struct LentBuffers;
+
+impl LentBuffers {
+    fn new() -> Self {
+        for _ in 0..3 {
+            CUDA_BUFFERS.allocated(FORM_CAPACITY);
+        }
+        Self
+    }
+}
+
+impl Drop for LentBuffers {
+    fn drop(&mut self) {
+        for _ in 0..3 {
+            CUDA_BUFFERS.freed(FORM_CAPACITY);
+        }
+    }
+}
+
+/// The message of the last failed `dlopen` or `dlsym`
+fn last_dl_error() -> String {
+    let error = unsafe { libc::dlerror() };
+    if error.is_null() {
+        "unknown error".to_string()
+    } else {
+        unsafe { CStr::from_ptr(error).to_string_lossy().into_owned() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_library() {
+        let error = CudaSquarer::load_from("/nonexistent/libtick_cuda.so").err().unwrap();
+        assert!(error.contains("/nonexistent/libtick_cuda.so"));
+    }
+}
@@ -6,10 +6,72 @@
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
 use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
 use std::sync::Once;
 
+pub mod gpu;
+pub mod memory;
+
+pub use gpu::{cuda, CudaSquarer};
+
 static INIT: Once = Once::new();
 
+/// Version of these bindings
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+extern "C" {
+    // Defined by every libgmp
+    #[link_name = "__gmp_version"]
+    static GMP_VERSION: *const c_char;
+
+    // Instruction switches of the fast squaring path, in parameters.h
+    static mut enable_avx512_ifma: bool;
+    static mut enable_all_instructions: bool;
+}
+
+/// Version of the GMP library the VDF is linked against
+pub fn gmp_version() -> String {
+    unsafe { CStr::from_ptr(GMP_VERSION).to_string_lossy().into_owned() }
+}
+
+/// What the machine offers the VDF
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Logical CPU cores
+    pub cores: usize,
+    /// AVX2 and ADX, which the assembly squaring path needs
+    pub avx2: bool,
+    /// AVX-512 IFMA
+    pub avx512_ifma: bool,
+}
+
+/// Detect what the machine offers the VDF
+pub fn get_capabilities() -> Capabilities {
+    let cores = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+    #[cfg(target_arch = "x86_64")]
+    let (avx2, avx512_ifma) = (
+        is_x86_feature_detected!("avx2") && is_x86_feature_detected!("adx"),
+        is_x86_feature_detected!("avx512ifma"),
+    );
+    #[cfg(not(target_arch = "x86_64"))]
+    let (avx2, avx512_ifma) = (false, false);
+    Capabilities {
+        cores,
+        avx2,
+        avx512_ifma,
+    }
+}
+
+/// Select the instructions the fast squaring path may use
+///
+/// Applies to the whole process, so set it before squaring starts.
+pub fn set_instructions(avx512_ifma: bool, all_instructions: bool) {
+    unsafe {
+        enable_avx512_ifma = avx512_ifma;
+        enable_all_instructions = all_instructions;
+    }
+}
+
 /// Initialize the VDF library (call once at program start)
 pub fn init() {
     INIT.call_once(|| unsafe {
@@ -18,7 +80,7 @@ pub fn init() {
 }
 
 /// A VDF form (a, b, c) representing a binary quadratic form
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct VdfForm {
     handle: tick_form_t,
 }
@@ -26,6 +88,7 @@ pub struct VdfForm {
 impl VdfForm {
     /// Create a new empty form
     pub fn new() -> Self {
+        memory::FORMS.allocated(0);
         unsafe {
             VdfForm {
                 handle: tick_form_create(),
@@ -36,6 +99,7 @@ impl VdfForm {
     /// Create a generator form for the given discriminant
     pub fn generator(discriminant_hex: &str) -> Self {
         let c_str = CString::new(discriminant_hex).unwrap();
+        memory::FORMS.allocated(0);
         unsafe {
             VdfForm {
                 handle: tick_form_generator(c_str.as_ptr()),
@@ -79,17 +143,33 @@ impl VdfForm {
             let b_ptr = tick_form_get_b(self.handle);
             let c_ptr = tick_form_get_c(self.handle);
 
-            let a = CStr::from_ptr(a_ptr).to_string_lossy().into_owned();
-            let b = CStr::from_ptr(b_ptr).to_string_lossy().into_owned();
-            let c = CStr::from_ptr(c_ptr).to_string_lossy().into_owned();
+            (take_c_string(a_ptr), take_c_string(b_ptr), take_c_string(c_ptr))
+        }
+    }
+}
 
-            // Free the C strings
-            libc::free(a_ptr as *mut libc::c_void);
-            libc::free(b_ptr as *mut libc::c_void);
-            libc::free(c_ptr as *mut libc::c_void);
+/// Copy a C string the library allocated, then free it
+///
+/// # Safety
+///
+/// `ptr` must be a NUL-terminated string from `malloc`, not used afterwards.
+unsafe fn take_c_string(ptr: *mut c_char) -> String {
+    let bytes = CStr::from_ptr(ptr).to_bytes_with_nul().len();
+    memory::C_STRINGS.allocated(bytes);
+    let value = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    libc::free(ptr as *mut libc::c_void);
+    memory::C_STRINGS.freed(bytes);
+    value
+}
 
-            (a, b, c)
-        }
+impl Clone for VdfForm {
+    /// A new form with the same values
+    ///
+    /// Copying the handle instead would destroy the library's form twice.
+    fn clone(&self) -> Self {
+        let mut form = VdfForm::new();
+        form.copy_from(self);
+        form
     }
 }
 
@@ -98,6 +178,7 @@ impl Drop for VdfForm {
         unsafe {
             tick_form_destroy(self.handle);
         }
+        memory::FORMS.freed(0);
     }
 }
 
@@ -105,13 +186,14 @@ impl Drop for VdfForm {
 // (The lack of Send/Sync implementations prevents sharing across threads)
 
 /// A reducer for normalizing forms
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct Reducer {
     handle: tick_reducer_t,
 }
 
 impl Reducer {
     pub fn new() -> Self {
+        memory::REDUCERS.allocated(0);
         unsafe {
             Reducer {
                 handle: tick_reducer_create(),
@@ -126,11 +208,19 @@ impl Reducer {
     }
 }
 
+impl Clone for Reducer {
+    /// A reducer of its own, as reducers keep only scratch space
+    fn clone(&self) -> Self {
+        Reducer::new()
+    }
+}
+
 impl Drop for Reducer {
     fn drop(&mut self) {
         unsafe {
             tick_reducer_destroy(self.handle);
         }
+        memory::REDUCERS.freed(0);
     }
 }
 
@@ -141,6 +231,7 @@ pub struct SquareState {
 
 impl SquareState {
     pub fn new(pairindex: i32) -> Self {
+        memory::SQUARE_STATES.allocated(0);
         unsafe {
             SquareState {
                 handle: tick_square_state_create(pairindex),
@@ -154,6 +245,7 @@ impl Drop for SquareState {
         unsafe {
             tick_square_state_destroy(self.handle);
         }
+        memory::SQUARE_STATES.freed(0);
     }
 }
 
@@ -0,0 +1,143 @@
+//! Accounting of memory crossing the FFI boundary
+//!
+//! Every object the tick library allocates for Rust, and every buffer
+//! handed across in either direction, is counted as it is allocated and
+//! freed. The counters are process-wide and never reset: a count of live
+//! objects that keeps growing on a node that runs for days points at a
+//! leak, and frees outnumbering allocations at a double free.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Allocations and frees of one kind of object
+pub struct AllocationCounter {
+    allocations: AtomicU64,
+    frees: AtomicU64,
+    bytes_allocated: AtomicU64,
+    bytes_freed: AtomicU64,
+}
+
+/// Counts read from an [`AllocationCounter`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocations: u64,
+    pub frees: u64,
+    /// Bytes allocated, where the size is known on the Rust side
+    pub bytes_allocated: u64,
+    pub bytes_freed: u64,
+}
+
+impl AllocationCounter {
+    pub const fn new() -> Self {
+        Self {
+            allocations: AtomicU64::new(0),
+            frees: AtomicU64::new(0),
+            bytes_allocated: AtomicU64::new(0),
+            bytes_freed: AtomicU64::new(0),
+        }
+    }
+
+    /// Count an allocation of `bytes`, 0 if the size isn't known
+    pub fn allocated(&self, bytes: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes_allocated.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Count a free of an allocation of `bytes`
+    pub fn freed(&self, bytes: usize) {
+        self.frees.fetch_add(1, Ordering::Relaxed);
+        self.bytes_freed.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> AllocationStats {
+        AllocationStats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            frees: self.frees.load(Ordering::Relaxed),
+            bytes_allocated: self.bytes_allocated.load(Ordering::Relaxed),
+            bytes_freed: self.bytes_freed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for AllocationCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AllocationStats {
+    /// Objects allocated and not yet freed
+    pub fn live(&self) -> u64 {
+        self.allocations.saturating_sub(self.frees)
+    }
+
+    pub fn live_bytes(&self) -> u64 {
+        self.bytes_allocated.saturating_sub(self.bytes_freed)
+    }
+
+    /// Whether more was freed than allocated
+    pub fn over_freed(&self) -> bool {
+        self.frees > self.allocations || self.bytes_freed > self.bytes_allocated
+    }
+}
+
+/// Forms created by the library
+pub static FORMS: AllocationCounter = AllocationCounter::new();
+
+/// Reducers created by the library
+pub static REDUCERS: AllocationCounter = AllocationCounter::new();
+
+/// Fast squaring states created by the library
+pub static SQUARE_STATES: AllocationCounter = AllocationCounter::new();
+
+/// Form coefficients the library returns as `malloc`ed C strings
+pub static C_STRINGS: AllocationCounter = AllocationCounter::new();
+
+/// Output buffers lent to the CUDA library for squared forms
+pub static CUDA_BUFFERS: AllocationCounter = AllocationCounter::new();
+
+/// Every counter of the tick bindings, by name
+pub fn usage() -> Vec<(&'static str, AllocationStats)> {
+    vec![
+        ("forms", FORMS.stats()),
+        ("reducers", REDUCERS.stats()),
+        ("square_states", SQUARE_STATES.stats()),
+        ("c_strings", C_STRINGS.stats()),
+        ("cuda_buffers", CUDA_BUFFERS.stats()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISCRIMINANT: &str = "-141140317794792668862943332656856519378482291428727287413318722089216448567155737094768903643716404517549715385664163360316296284155310058980984373770517398492951860161717960368874227473669336541818575166839209228684755811071416376384551902149780184532086881683576071479646499601330824259260645952517205526679";
+
+    #[test]
+    fn test_counter() {
+        let counter = AllocationCounter::new();
+        counter.allocated(10);
+        counter.allocated(0);
+        counter.freed(10);
+        let stats = counter.stats();
+        assert_eq!((stats.live(), stats.live_bytes()), (1, 0));
+        assert!(!stats.over_freed());
+
+        counter.freed(0);
+        counter.freed(0);
+        assert!(counter.stats().over_freed());
+    }
+
+    #[test]
+    fn test_clone_owns_its_form() {
+        crate::init();
+        let (forms, strings) = (FORMS.stats(), C_STRINGS.stats());
+        let form = crate::VdfForm::generator(DISCRIMINANT);
+        let values = form.get_values();
+        let clone = form.clone();
+        drop(form);
+        assert_eq!(clone.get_values(), values);
+        drop(clone);
+        assert!(FORMS.stats().frees >= forms.frees + 2);
+        assert!(C_STRINGS.stats().frees >= strings.frees + 9);
+    }
+}
@@ -5,6 +5,13 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
+use zeroize::Zeroize;
+
+pub mod memory;
+
+/// Version of the solver bindings
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[repr(C)]
 struct RSWSolver {
     _private: [u8; 0],
@@ -54,12 +61,20 @@ pub struct Solver {
 }
 
 /// Result of solving an RSW puzzle
+///
+/// The key is wiped from memory when the result is dropped.
 #[derive(Debug, Clone)]
 pub struct SolveResult {
     /// The 256-bit key derived from the puzzle
     pub key: [u8; 32],
 }
 
+impl Drop for SolveResult {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
 /// Error type for RSW operations
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -81,6 +96,7 @@ impl Solver {
             if solver.is_null() {
                 Err(Error::CreationFailed)
             } else {
+                memory::SOLVERS.allocated(0);
                 Ok(Solver { inner: solver })
             }
         }
@@ -120,9 +136,10 @@ impl Solver {
             CString::new(a).map_err(|_| Error::InvalidHex("a contains null".to_string()))?;
         let c_cstr =
             CString::new(c).map_err(|_| Error::InvalidHex("c contains null".to_string()))?;
+        let _lent = LentStrings::new([&n_cstr, &a_cstr, &c_cstr]);
 
         unsafe {
-            let result = rsw_solver_solve(
+            let mut result = rsw_solver_solve(
                 self.inner,
                 n_cstr.as_ptr(),
                 a_cstr.as_ptr(),
@@ -131,15 +148,19 @@ impl Solver {
             );
 
             if result.success {
-                Ok(SolveResult { key: result.key })
+                let solved = SolveResult { key: result.key };
+                result.key.zeroize();
+                Ok(solved)
             } else {
                 let error_msg = if result.error_msg.is_null() {
                     "Unknown error".to_string()
                 } else {
-                    let msg = CStr::from_ptr(result.error_msg)
-                        .to_string_lossy()
-                        .to_string();
+                    let msg = CStr::from_ptr(result.error_msg);
+                    let bytes = msg.to_bytes_with_nul().len();
+                    memory::ERROR_STRINGS.allocated(bytes);
+                    let msg = msg.to_string_lossy().to_string();
                     rsw_result_free_error(result.error_msg);
+                    memory::ERROR_STRINGS.freed(bytes);
                     msg
                 };
                 Err(Error::SolverError(error_msg))
@@ -199,6 +220,7 @@ impl Solver {
         let n_ptrs: Vec<*const c_char> = n_cstrings.iter().map(|s| s.as_ptr()).collect();
         let a_ptrs: Vec<*const c_char> = a_cstrings.iter().map(|s| s.as_ptr()).collect();
         let c_ptrs: Vec<*const c_char> = c_cstrings.iter().map(|s| s.as_ptr()).collect();
+        let _lent = LentStrings::new(n_cstrings.iter().chain(&a_cstrings).chain(&c_cstrings));
 
         unsafe {
             let batch_result = rsw_solver_solve_batch(
@@ -209,20 +231,28 @@ impl Solver {
                 t_values.as_ptr(),
                 puzzles.len(),
             );
+            if !batch_result.results.is_null() {
+                memory::BATCH_RESULTS.allocated(0);
+            }
+            let free_batch = || {
+                rsw_batch_result_free(&batch_result as *const _ as *mut _);
+                memory::BATCH_RESULTS.freed(0);
+            };
 
             // Convert results
             let mut results = Vec::with_capacity(puzzles.len());
 
             if !batch_result.results.is_null() && batch_result.count == puzzles.len() {
                 let result_slice =
-                    std::slice::from_raw_parts(batch_result.results, batch_result.count);
+                    std::slice::from_raw_parts_mut(batch_result.results, batch_result.count);
 
-                for (i, rsw_result) in result_slice.iter().enumerate() {
+                let mut failure = None;
+                for (i, rsw_result) in result_slice.iter_mut().enumerate() {
                     if rsw_result.success {
                         results.push(SolveResult {
                             key: rsw_result.key,
                         });
-                    } else {
+                    } else if failure.is_none() {
                         let error_msg = if rsw_result.error_msg.is_null() {
                             "Unknown error".to_string()
                         } else {
@@ -230,16 +260,22 @@ impl Solver {
                                 .to_string_lossy()
                                 .to_string()
                         };
-
-                        // Free the batch result before returning error
-                        rsw_batch_result_free(&batch_result as *const _ as *mut _);
-                        return Err(Error::SolverError(format!("Puzzle {i}: {error_msg}")));
+                        failure = Some(format!("Puzzle {i}: {error_msg}"));
                     }
+                    // The library frees results without clearing them
+                    rsw_result.key.zeroize();
                 }
 
                 // Free the batch result
-                rsw_batch_result_free(&batch_result as *const _ as *mut _);
+                free_batch();
+                if let Some(error_msg) = failure {
+                    return Err(Error::SolverError(error_msg));
+                }
             } else {
+                // A short batch is still allocated
+                if !batch_result.results.is_null() {
+                    free_batch();
+                }
                 return Err(Error::SolverError("Batch solve failed".to_string()));
             }
 
@@ -270,6 +306,31 @@ impl Drop for Solver {
         unsafe {
             rsw_solver_free(self.inner);
         }
+        memory::SOLVERS.freed(0);
+    }
+}
+
+/// Counts C strings as lent to the library until dropped
+struct LentStrings(Vec<usize>);
+
+impl LentStrings {
+    fn new<'a>(strings: impl IntoIterator<Item = &'a CString>) -> Self {
+        let sizes: Vec<usize> = strings
+            .into_iter()
+            .map(|string| string.as_bytes_with_nul().len())
+            .collect();
+        for bytes in &sizes {
+            memory::C_STRINGS.allocated(*bytes);
+        }
+        Self(sizes)
+    }
+}
+
+impl Drop for LentStrings {
+    fn drop(&mut self) {
+        for bytes in &self.0 {
+            memory::C_STRINGS.freed(*bytes);
+        }
     }
 }
 
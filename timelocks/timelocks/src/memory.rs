@@ -0,0 +1,103 @@
+//! Accounting of memory crossing the FFI boundary
+//!
+//! Every solver and result the solver library allocates for Rust, and
+//! every puzzle string handed to it, is counted as it is allocated and
+//! freed. The counters are process-wide and never reset: a count of live
+//! objects that keeps growing on a node that runs for days points at a
+//! leak, and frees outnumbering allocations at a double free.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Allocations and frees of one kind of object
+pub struct AllocationCounter {
+    allocations: AtomicU64,
+    frees: AtomicU64,
+    bytes_allocated: AtomicU64,
+    bytes_freed: AtomicU64,
+}
+
+/// Counts read from an [`AllocationCounter`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocations: u64,
+    pub frees: u64,
+    /// Bytes allocated, where the size is known on the Rust side
+    pub bytes_allocated: u64,
+    pub bytes_freed: u64,
+}
+
+impl AllocationCounter {
+    pub const fn new() -> Self {
+        Self {
+            allocations: AtomicU64::new(0),
+            frees: AtomicU64::new(0),
+            bytes_allocated: AtomicU64::new(0),
+            bytes_freed: AtomicU64::new(0),
+        }
+    }
+
+    /// Count an allocation of `bytes`, 0 if the size isn't known
+    pub fn allocated(&self, bytes: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes_allocated.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Count a free of an allocation of `bytes`
+    pub fn freed(&self, bytes: usize) {
+        self.frees.fetch_add(1, Ordering::Relaxed);
+        self.bytes_freed.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> AllocationStats {
+        AllocationStats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            frees: self.frees.load(Ordering::Relaxed),
+            bytes_allocated: self.bytes_allocated.load(Ordering::Relaxed),
+            bytes_freed: self.bytes_freed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for AllocationCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AllocationStats {
+    /// Objects allocated and not yet freed
+    pub fn live(&self) -> u64 {
+        self.allocations.saturating_sub(self.frees)
+    }
+
+    pub fn live_bytes(&self) -> u64 {
+        self.bytes_allocated.saturating_sub(self.bytes_freed)
+    }
+
+    /// Whether more was freed than allocated
+    pub fn over_freed(&self) -> bool {
+        self.frees > self.allocations || self.bytes_freed > self.bytes_allocated
+    }
+}
+
+/// Solvers created by the library
+pub static SOLVERS: AllocationCounter = AllocationCounter::new();
+
+/// Batch results the library allocates, freed with `rsw_batch_result_free`
+pub static BATCH_RESULTS: AllocationCounter = AllocationCounter::new();
+
+/// Error messages the library allocates, freed with `rsw_result_free_error`
+pub static ERROR_STRINGS: AllocationCounter = AllocationCounter::new();
+
+/// Puzzle values lent to the library as C strings
+pub static C_STRINGS: AllocationCounter = AllocationCounter::new();
+
+/// Every counter of the solver bindings, by name
+pub fn usage() -> Vec<(&'static str, AllocationStats)> {
+    vec![
+        ("solvers", SOLVERS.stats()),
+        ("batch_results", BATCH_RESULTS.stats()),
+        ("error_strings", ERROR_STRINGS.stats()),
+        ("c_strings", C_STRINGS.stats()),
+    ]
+}
@@ -0,0 +1,138 @@
+// xtask/src/main.rs - Kala developer tasks
+//
+// Runs the tests of the crates wrapping native libraries under memory
+// checkers. The VDF and timelock libraries hand forms, reducers, solvers and
+// C strings across the FFI boundary, and a handle freed twice or never
+// freed there goes unnoticed by the Rust compiler. The allocation counters
+// in the bindings catch imbalances; these runs catch the invalid accesses
+// and leaks behind them.
+//
+// Usage:
+//   cargo xtask asan       Tests under AddressSanitizer (nightly toolchain)
+//   cargo xtask valgrind   Tests under Valgrind's memcheck
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+use std::env;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Crates whose tests exercise the FFI bindings
+const FFI_CRATES: &[&str] = &["tick", "timelocks", "kala-vdf", "kala-transaction"];
+
+fn main() -> Result<()> {
+    let task = env::args().nth(1);
+    match task.as_deref() {
+        Some("asan") => asan(),
+        Some("valgrind") => valgrind(),
+        _ => {
+            eprintln!("Usage: cargo xtask <asan|valgrind>");
+            eprintln!();
+            eprintln!("  asan       Run the FFI tests under AddressSanitizer (nightly)");
+            eprintln!("  valgrind   Run the FFI tests under Valgrind memcheck");
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Run the FFI tests built with AddressSanitizer
+///
+/// Instrumenting build scripts and proc macros breaks them, so the tests are
+/// built for an explicit target, which keeps the sanitizer flags off host
+/// artifacts. The native libraries aren't rebuilt: ASan's allocator still
+/// intercepts their `malloc` and `free`, catching double frees and leaks of
+/// anything crossing the boundary.
+fn asan() -> Result<()> {
+    let target = host_target()?;
+    // Through the rustup proxy, the cargo running xtask can't switch toolchains
+    let mut command = Command::new("cargo");
+    command
+        .args(["+nightly", "test", "--profile", "asan", "--target", &target, "--lib"])
+        .args(package_args())
+        .args(["--", "--test-threads=1"])
+        .env("RUSTFLAGS", "-Zsanitizer=address")
+        .env("RUSTDOCFLAGS", "-Zsanitizer=address")
+        .env("ASAN_OPTIONS", "detect_leaks=1:detect_odr_violation=0");
+    run(&mut command)
+}
+
+/// Run each FFI test binary under Valgrind's memcheck
+///
+/// Valgrind wraps the test executables rather than cargo, so they are built
+/// first and their paths read from cargo's JSON messages. Only definite
+/// leaks fail the run: the libraries keep some global state alive for the
+/// life of the process.
+fn valgrind() -> Result<()> {
+    let mut build = cargo();
+    build
+        .args(["test", "--profile", "valgrind", "--lib", "--no-run", "--message-format=json"])
+        .args(package_args())
+        .stdout(Stdio::piped());
+    let output = build.output().context("Failed to run cargo")?;
+    if !output.status.success() {
+        bail!("Building the FFI tests failed");
+    }
+
+    let executables = test_executables(&String::from_utf8_lossy(&output.stdout));
+    if executables.is_empty() {
+        bail!("Cargo reported no test executables");
+    }
+    for executable in executables {
+        println!("valgrind {}", executable.display());
+        let mut command = Command::new("valgrind");
+        command
+            .args([
+                "--leak-check=full",
+                "--errors-for-leak-kinds=definite",
+                "--error-exitcode=1",
+            ])
+            .arg(&executable)
+            .arg("--test-threads=1");
+        run(&mut command)?;
+    }
+    Ok(())
+}
+
+/// Paths of the test executables in cargo's `--message-format=json` output
+fn test_executables(messages: &str) -> Vec<PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter(|message| message["profile"]["test"] == true)
+        .filter_map(|message| message["executable"].as_str().map(PathBuf::from))
+        .collect()
+}
+
+fn package_args() -> Vec<String> {
+    FFI_CRATES
+        .iter()
+        .flat_map(|name| ["-p".to_string(), name.to_string()])
+        .collect()
+}
+
+/// The target triple of the host, from `rustc -vV`
+fn host_target() -> Result<String> {
+    let output = Command::new("rustc")
+        .arg("-vV")
+        .output()
+        .context("Failed to run rustc")?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("rustc did not report a host target"))
+}
+
+fn cargo() -> Command {
+    Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !status.success() {
+        bail!("{:?} failed with {}", command.get_program(), status);
+    }
+    Ok(())
+}